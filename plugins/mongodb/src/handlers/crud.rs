@@ -0,0 +1,83 @@
+//! Row-level CRUD, addressed by `_id` (or whichever column the grid uses
+//! as the primary key).
+
+use mongodb::bson::oid::ObjectId;
+use mongodb::bson::{doc, Bson, Document};
+use serde_json::Value;
+
+use crate::client::Client;
+use crate::error::PluginError;
+use crate::models::{inner_params, str_arg, ConnectionParams};
+use crate::rpc::respond;
+use crate::utils::convert::{coerce_like, json_to_bson, pk_filter};
+
+fn collection(params: &Value) -> Result<mongodb::sync::Collection<Document>, PluginError> {
+    let client = Client::connect(&ConnectionParams::from_value(inner_params(params)))?;
+    Ok(client
+        .database()?
+        .collection::<Document>(str_arg(params, "table")?))
+}
+
+pub fn insert_record(id: Value, params: &Value) -> Value {
+    respond(
+        id,
+        collection(params).and_then(|coll| {
+            let data = params
+                .get("data")
+                .and_then(Value::as_object)
+                .ok_or_else(|| PluginError::invalid_params("missing 'data'"))?;
+            let mut document = Document::new();
+            for (key, value) in data {
+                // Fields left blank in the new-row form are simply absent;
+                // a blank `_id` is generated by the server.
+                if value.is_null() || (key == "_id" && value.as_str() == Some("")) {
+                    continue;
+                }
+                let bson = match (key.as_str(), value) {
+                    ("_id", Value::String(s)) => ObjectId::parse_str(s)
+                        .map(Bson::ObjectId)
+                        .unwrap_or_else(|_| Bson::String(s.clone())),
+                    _ => json_to_bson(value.clone())?,
+                };
+                document.insert(key.clone(), bson);
+            }
+            coll.insert_one(document).run()?;
+            Ok(Value::from(1))
+        }),
+    )
+}
+
+pub fn update_record(id: Value, params: &Value) -> Value {
+    respond(
+        id,
+        collection(params).and_then(|coll| {
+            let pk_col = str_arg(params, "pk_col")?;
+            let col_name = str_arg(params, "col_name")?;
+            let pk_val = params.get("pk_val").cloned().unwrap_or(Value::Null);
+            let new_val = params.get("new_val").cloned().unwrap_or(Value::Null);
+
+            let filter = pk_filter(pk_col, pk_val)?;
+            let existing = coll
+                .find_one(filter.clone())
+                .projection(doc! { col_name: 1 })
+                .run()?;
+            let value = coerce_like(existing.as_ref().and_then(|d| d.get(col_name)), new_val)?;
+            let result = coll
+                .update_one(filter, doc! { "$set": { col_name: value } })
+                .run()?;
+            Ok(Value::from(result.matched_count))
+        }),
+    )
+}
+
+pub fn delete_record(id: Value, params: &Value) -> Value {
+    respond(
+        id,
+        collection(params).and_then(|coll| {
+            let pk_col = str_arg(params, "pk_col")?;
+            let pk_val = params.get("pk_val").cloned().unwrap_or(Value::Null);
+            let result = coll.delete_one(pk_filter(pk_col, pk_val)?).run()?;
+            Ok(Value::from(result.deleted_count))
+        }),
+    )
+}
@@ -0,0 +1,142 @@
+//! Schema metadata. Collections are reported as tables; their columns are
+//! inferred from a sample of documents. MongoDB has no foreign keys, so
+//! those lists are always empty.
+
+use mongodb::bson::{doc, Document};
+use mongodb::sync::Database;
+use serde_json::{json, Map, Value};
+
+use crate::client::Client;
+use crate::error::PluginError;
+use crate::models::{inner_params, str_arg, ConnectionParams};
+use crate::rpc::respond;
+use crate::utils::schema::{infer_columns, SAMPLE_SIZE};
+
+fn connect(params: &Value) -> Result<Client, PluginError> {
+    Client::connect(&ConnectionParams::from_value(inner_params(params)))
+}
+
+pub fn get_databases(id: Value, params: &Value) -> Value {
+    respond(
+        id,
+        connect(params)
+            .and_then(|client| client.database_names())
+            .map(|names| json!(names)),
+    )
+}
+
+pub fn get_tables(id: Value, params: &Value) -> Value {
+    respond(
+        id,
+        connect(params).and_then(|client| {
+            let names = collection_names(&client.database()?)?;
+            Ok(names
+                .into_iter()
+                .map(|name| json!({ "name": name }))
+                .collect())
+        }),
+    )
+}
+
+pub fn get_columns(id: Value, params: &Value) -> Value {
+    respond(
+        id,
+        connect(params).and_then(|client| {
+            let table = str_arg(params, "table")?;
+            Ok(Value::Array(sample_columns(&client.database()?, table)?))
+        }),
+    )
+}
+
+pub fn get_indexes(id: Value, params: &Value) -> Value {
+    respond(
+        id,
+        connect(params).and_then(|client| {
+            let table = str_arg(params, "table")?;
+            let indexes = client
+                .database()?
+                .collection::<Document>(table)
+                .list_indexes()
+                .run()?;
+            let mut rows = Vec::new();
+            for index in indexes {
+                let index = index?;
+                let options = index.options.unwrap_or_default();
+                let name = options.name.unwrap_or_default();
+                for (seq, column) in index.keys.keys().enumerate() {
+                    rows.push(json!({
+                        "name": name,
+                        "column_name": column,
+                        "is_unique": options.unique.unwrap_or(false) || name == "_id_",
+                        "is_primary": name == "_id_",
+                        "seq_in_index": seq + 1,
+                    }));
+                }
+            }
+            Ok(Value::Array(rows))
+        }),
+    )
+}
+
+pub fn get_schema_snapshot(id: Value, params: &Value) -> Value {
+    respond(
+        id,
+        connect(params).and_then(|client| {
+            let db = client.database()?;
+            let mut tables = Vec::new();
+            for name in collection_names(&db)? {
+                let columns = sample_columns(&db, &name)?;
+                tables.push(json!({ "name": name, "columns": columns, "foreign_keys": [] }));
+            }
+            Ok(Value::Array(tables))
+        }),
+    )
+}
+
+pub fn get_all_columns_batch(id: Value, params: &Value) -> Value {
+    respond(
+        id,
+        connect(params).and_then(|client| {
+            let db = client.database()?;
+            let mut columns = Map::new();
+            for name in collection_names(&db)? {
+                let sampled = sample_columns(&db, &name)?;
+                columns.insert(name, Value::Array(sampled));
+            }
+            Ok(Value::Object(columns))
+        }),
+    )
+}
+
+pub fn get_all_foreign_keys_batch(id: Value, params: &Value) -> Value {
+    respond(
+        id,
+        connect(params).and_then(|client| {
+            let names = collection_names(&client.database()?)?;
+            Ok(Value::Object(
+                names.into_iter().map(|name| (name, json!([]))).collect(),
+            ))
+        }),
+    )
+}
+
+/// User collections and views, sorted. `system.*` collections are internal.
+fn collection_names(db: &Database) -> Result<Vec<String>, PluginError> {
+    let mut names: Vec<String> = db
+        .list_collection_names()
+        .run()?
+        .into_iter()
+        .filter(|name| !name.starts_with("system."))
+        .collect();
+    names.sort();
+    Ok(names)
+}
+
+fn sample_columns(db: &Database, collection: &str) -> Result<Vec<Value>, PluginError> {
+    let docs = db
+        .collection::<Document>(collection)
+        .aggregate([doc! { "$sample": { "size": SAMPLE_SIZE } }])
+        .run()?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(infer_columns(&docs))
+}
@@ -0,0 +1,3 @@
+pub mod crud;
+pub mod metadata;
+pub mod query;
@@ -0,0 +1,167 @@
+//! Connection checks and query execution. See `utils::shell` for the query
+//! syntax.
+
+use mongodb::bson::{doc, Document};
+use serde_json::{json, Value};
+
+use crate::client::Client;
+use crate::error::PluginError;
+use crate::models::{inner_params, str_arg, ConnectionParams};
+use crate::rpc::respond;
+use crate::utils::convert::tabulate;
+use crate::utils::shell::{parse_query, Query};
+
+pub fn test_connection(id: Value, params: &Value) -> Value {
+    respond(
+        id,
+        Client::connect(&ConnectionParams::from_value(inner_params(params)))
+            .and_then(|client| client.ping())
+            .map(|_| json!({ "success": true })),
+    )
+}
+
+pub fn ping(id: Value, params: &Value) -> Value {
+    respond(
+        id,
+        Client::connect(&ConnectionParams::from_value(inner_params(params)))
+            .and_then(|client| client.ping())
+            .map(|_| Value::Null),
+    )
+}
+
+pub fn execute_query(id: Value, params: &Value) -> Value {
+    respond(id, run_query(params))
+}
+
+fn run_query(params: &Value) -> Result<Value, PluginError> {
+    let client = Client::connect(&ConnectionParams::from_value(inner_params(params)))?;
+    let query = parse_query(str_arg(params, "query")?)?;
+    let page = params
+        .get("page")
+        .and_then(Value::as_u64)
+        .unwrap_or(1)
+        .max(1);
+    let page_size = params.get("limit").and_then(Value::as_u64);
+    let db = client.database()?;
+
+    let docs: Vec<Document> = match query {
+        Query::Find(find) => {
+            let (skip, limit) = page_window(find.skip, find.limit, page, page_size);
+            let collection = db.collection::<Document>(&find.collection);
+            let mut action = collection.find(find.filter);
+            if let Some(projection) = find.projection {
+                action = action.projection(projection);
+            }
+            if let Some(sort) = find.sort {
+                action = action.sort(sort);
+            }
+            if skip > 0 {
+                action = action.skip(skip);
+            }
+            if let Some(limit) = limit {
+                // A zero limit means "no limit" to the server.
+                if limit == 0 {
+                    return Ok(result(Vec::new(), page, page_size));
+                }
+                action = action.limit(limit as i64);
+            }
+            action.run()?.collect::<Result<_, _>>()?
+        }
+        Query::Aggregate {
+            collection,
+            mut pipeline,
+        } => {
+            // Nothing may follow a stage that writes its output.
+            let writes = pipeline
+                .last()
+                .is_some_and(|stage| stage.contains_key("$out") || stage.contains_key("$merge"));
+            if !writes {
+                let (skip, limit) = page_window(None, None, page, page_size);
+                if skip > 0 {
+                    pipeline.push(doc! { "$skip": skip as i64 });
+                }
+                if let Some(limit) = limit {
+                    pipeline.push(doc! { "$limit": limit as i64 });
+                }
+            }
+            db.collection::<Document>(&collection)
+                .aggregate(pipeline)
+                .run()?
+                .collect::<Result<_, _>>()?
+        }
+    };
+
+    Ok(result(docs, page, page_size))
+}
+
+/// Skip and limit for one page of a result set that the query itself may
+/// already window. One row past the page is fetched to tell whether
+/// another page follows.
+fn page_window(
+    skip: Option<u64>,
+    limit: Option<u64>,
+    page: u64,
+    page_size: Option<u64>,
+) -> (u64, Option<u64>) {
+    let skip = skip.unwrap_or(0);
+    let Some(page_size) = page_size else {
+        return (skip, limit);
+    };
+    let offset = (page - 1).saturating_mul(page_size);
+    let fetch = page_size + 1;
+    let fetch = match limit {
+        Some(limit) => fetch.min(limit.saturating_sub(offset)),
+        None => fetch,
+    };
+    (skip + offset, Some(fetch))
+}
+
+fn result(mut docs: Vec<Document>, page: u64, page_size: Option<u64>) -> Value {
+    let pagination = page_size.map(|size| {
+        let has_more = docs.len() as u64 > size;
+        docs.truncate(size as usize);
+        json!({
+            "page": page,
+            "page_size": size,
+            "total_rows": null,
+            "has_more": has_more,
+        })
+    });
+    let (columns, rows) = tabulate(docs);
+    json!({
+        "columns": columns,
+        "rows": rows,
+        "affected_rows": 0,
+        "truncated": false,
+        "pagination": pagination,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::page_window;
+
+    #[test]
+    fn pages_through_an_unbounded_query() {
+        assert_eq!(page_window(None, None, 1, Some(50)), (0, Some(51)));
+        assert_eq!(page_window(None, None, 3, Some(50)), (100, Some(51)));
+        assert_eq!(page_window(None, None, 2, None), (0, None));
+    }
+
+    #[test]
+    fn pages_stay_inside_the_query_window() {
+        // .skip(10).limit(120), pages of 50.
+        assert_eq!(
+            page_window(Some(10), Some(120), 1, Some(50)),
+            (10, Some(51))
+        );
+        assert_eq!(
+            page_window(Some(10), Some(120), 3, Some(50)),
+            (110, Some(20))
+        );
+        assert_eq!(
+            page_window(Some(10), Some(120), 4, Some(50)),
+            (160, Some(0))
+        );
+    }
+}
@@ -0,0 +1,68 @@
+//! Shared request/response shapes.
+//!
+//! These mirror the `ConnectionParams` struct the host sends. Keep fields
+//! optional — different database types leave different fields blank.
+
+use serde_json::Value;
+
+use crate::error::PluginError;
+
+#[derive(Debug, Clone)]
+pub struct ConnectionParams {
+    pub host: Option<String>,
+    pub port: Option<u16>,
+    pub database: Option<String>,
+    pub username: Option<String>,
+    pub password: Option<String>,
+    pub ssl_mode: Option<String>,
+    pub ssl_ca: Option<String>,
+}
+
+impl ConnectionParams {
+    pub fn from_value(value: &Value) -> Self {
+        let obj = value.as_object();
+        let get_str = |k: &str| {
+            obj.and_then(|o| o.get(k))
+                .and_then(Value::as_str)
+                .filter(|s| !s.is_empty())
+                .map(str::to_string)
+        };
+        let port = obj
+            .and_then(|o| o.get("port"))
+            .and_then(Value::as_u64)
+            .and_then(|p| u16::try_from(p).ok());
+        // Multi-database connections send a list; the first entry is the
+        // one queries run against.
+        let database = match obj.and_then(|o| o.get("database")) {
+            Some(Value::Array(list)) => list.first().and_then(Value::as_str),
+            Some(other) => other.as_str(),
+            None => None,
+        }
+        .filter(|s| !s.is_empty())
+        .map(str::to_string);
+
+        Self {
+            host: get_str("host"),
+            port,
+            database,
+            username: get_str("username"),
+            password: get_str("password"),
+            ssl_mode: get_str("ssl_mode"),
+            ssl_ca: get_str("ssl_ca"),
+        }
+    }
+}
+
+/// Extract the nested `params` object every RPC method receives.
+/// Tabularis wraps connection params in `params.params`.
+pub fn inner_params(value: &Value) -> &Value {
+    value.get("params").unwrap_or(&Value::Null)
+}
+
+/// A required string argument of the request.
+pub fn str_arg<'a>(value: &'a Value, key: &str) -> Result<&'a str, PluginError> {
+    value
+        .get(key)
+        .and_then(Value::as_str)
+        .ok_or_else(|| PluginError::invalid_params(format!("missing '{key}'")))
+}
@@ -0,0 +1,93 @@
+//! JSON-RPC dispatch and response helpers.
+
+use serde_json::{json, Value};
+
+use crate::error::PluginError;
+use crate::handlers;
+
+/// Parse one JSON-RPC line and return the response value (serialised
+/// downstream by `main.rs`), or `None` for a notification. Never panics —
+/// parse errors and method failures are surfaced as JSON-RPC error
+/// responses.
+pub fn handle_line(line: &str) -> Option<Value> {
+    let request: Value = match serde_json::from_str(line) {
+        Ok(v) => v,
+        Err(err) => {
+            return Some(error_response(
+                Value::Null,
+                -32700,
+                &format!("parse error: {err}"),
+            ))
+        }
+    };
+
+    // Queries run on this thread, so `cancel_query` can never arrive while
+    // one is in flight; there is nothing to interrupt.
+    let id = request.get("id").cloned()?;
+    let method = request
+        .get("method")
+        .and_then(Value::as_str)
+        .unwrap_or("")
+        .to_string();
+    let params = request.get("params").cloned().unwrap_or(Value::Null);
+
+    let response = match method.as_str() {
+        "initialize" => ok_response(id, Value::Null),
+        "test_connection" => handlers::query::test_connection(id, &params),
+        "ping" => handlers::query::ping(id, &params),
+
+        "get_databases" => handlers::metadata::get_databases(id, &params),
+        "get_schemas" => ok_response(id, json!([])),
+        "get_tables" => handlers::metadata::get_tables(id, &params),
+        "get_columns" => handlers::metadata::get_columns(id, &params),
+        "get_foreign_keys" => ok_response(id, json!([])),
+        "get_indexes" => handlers::metadata::get_indexes(id, &params),
+        "get_views" | "get_routines" => ok_response(id, json!([])),
+        "get_schema_snapshot" => handlers::metadata::get_schema_snapshot(id, &params),
+        "get_all_columns_batch" => handlers::metadata::get_all_columns_batch(id, &params),
+        "get_all_foreign_keys_batch" => handlers::metadata::get_all_foreign_keys_batch(id, &params),
+
+        "execute_query" => handlers::query::execute_query(id, &params),
+
+        "insert_record" => handlers::crud::insert_record(id, &params),
+        "update_record" => handlers::crud::update_record(id, &params),
+        "delete_record" => handlers::crud::delete_record(id, &params),
+
+        // Views, routines, EXPLAIN and DDL have no MongoDB equivalent the
+        // UI could drive.
+        other => not_implemented(id, other),
+    };
+    Some(response)
+}
+
+pub fn ok_response(id: Value, result: Value) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "result": result,
+        "id": id,
+    })
+}
+
+pub fn error_response(id: Value, code: i64, message: &str) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "error": { "code": code, "message": message },
+        "id": id,
+    })
+}
+
+pub fn not_implemented(id: Value, method: &str) -> Value {
+    error_response(
+        id,
+        -32601,
+        &format!("method '{method}' is not implemented by this plugin"),
+    )
+}
+
+/// Turn a handler outcome into a response.
+pub fn respond(id: Value, result: Result<Value, PluginError>) -> Value {
+    match result {
+        Ok(value) => ok_response(id, value),
+        Err(err) => error_response(id, err.code, &err.message),
+    }
+}
@@ -0,0 +1,209 @@
+//! Conversions between BSON and the JSON values Tabularis shows in the grid.
+//!
+//! Top-level scalars are flattened so the grid can display and edit them
+//! (an ObjectId becomes its hex string, a date its RFC 3339 form). Nested
+//! documents and arrays stay in relaxed Extended JSON, which round-trips:
+//! `{"$oid": ...}` inside an edited JSON cell is written back as an
+//! ObjectId.
+
+use mongodb::bson::oid::ObjectId;
+use mongodb::bson::{doc, Bson, DateTime, Decimal128, Document};
+use serde_json::Value;
+
+use crate::error::PluginError;
+
+/// Grid cell for a field value.
+pub fn bson_to_json(value: Bson) -> Value {
+    match value {
+        Bson::ObjectId(oid) => Value::String(oid.to_hex()),
+        Bson::DateTime(dt) => match dt.try_to_rfc3339_string() {
+            Ok(s) => Value::String(s),
+            Err(_) => Bson::DateTime(dt).into_relaxed_extjson(),
+        },
+        Bson::Decimal128(d) => Value::String(d.to_string()),
+        Bson::Int64(n) => Value::from(n),
+        other => other.into_relaxed_extjson(),
+    }
+}
+
+/// Parse (Extended) JSON into BSON.
+pub fn json_to_bson(value: Value) -> Result<Bson, PluginError> {
+    Bson::try_from(value).map_err(|e| PluginError::invalid_params(e.to_string()))
+}
+
+/// Parse (Extended) JSON that must be a document.
+pub fn json_to_document(value: Value) -> Result<Document, PluginError> {
+    match json_to_bson(value)? {
+        Bson::Document(doc) => Ok(doc),
+        other => Err(PluginError::invalid_params(format!(
+            "expected a document, got {other}"
+        ))),
+    }
+}
+
+/// Column type shown for a field value; `None` for null.
+pub fn type_name(value: &Bson) -> Option<&'static str> {
+    Some(match value {
+        Bson::Null | Bson::Undefined => return None,
+        Bson::ObjectId(_) => "OBJECTID",
+        Bson::String(_) => "STRING",
+        Bson::Int32(_) => "INT32",
+        Bson::Int64(_) => "INT64",
+        Bson::Double(_) => "DOUBLE",
+        Bson::Decimal128(_) => "DECIMAL128",
+        Bson::Boolean(_) => "BOOL",
+        Bson::DateTime(_) => "DATE",
+        Bson::Binary(_) => "BINARY",
+        // Everything else is displayed as Extended JSON.
+        _ => "JSON",
+    })
+}
+
+/// Convert an edited grid value back to BSON, keeping the type of the
+/// value it replaces where the edit allows it. Without this, editing a
+/// date or an ObjectId would store a plain string.
+pub fn coerce_like(existing: Option<&Bson>, value: Value) -> Result<Bson, PluginError> {
+    let coerced = match (existing, &value) {
+        (_, Value::Null) => Some(Bson::Null),
+        (Some(Bson::ObjectId(_)), Value::String(s)) => {
+            ObjectId::parse_str(s).ok().map(Bson::ObjectId)
+        }
+        (Some(Bson::DateTime(_)), Value::String(s)) => {
+            DateTime::parse_rfc3339_str(s).ok().map(Bson::DateTime)
+        }
+        (Some(Bson::Decimal128(_)), Value::String(s)) => {
+            s.parse::<Decimal128>().ok().map(Bson::Decimal128)
+        }
+        (Some(Bson::Decimal128(_)), Value::Number(n)) => n
+            .to_string()
+            .parse::<Decimal128>()
+            .ok()
+            .map(Bson::Decimal128),
+        (Some(Bson::Int64(_)), Value::Number(n)) => n.as_i64().map(Bson::Int64),
+        (Some(Bson::Double(_)), Value::Number(n)) => n.as_f64().map(Bson::Double),
+        (Some(Bson::Document(_) | Bson::Array(_)), Value::String(s)) => {
+            match serde_json::from_str::<Value>(s) {
+                Ok(parsed @ (Value::Object(_) | Value::Array(_))) => Some(json_to_bson(parsed)?),
+                _ => None,
+            }
+        }
+        _ => None,
+    };
+    match coerced {
+        Some(bson) => Ok(bson),
+        None => json_to_bson(value),
+    }
+}
+
+/// Filter matching a row by primary key. The grid sends `_id` ObjectIds as
+/// hex strings, and a string `_id` may look the same, so both are matched.
+pub fn pk_filter(pk_col: &str, pk_val: Value) -> Result<Document, PluginError> {
+    if let Value::String(s) = &pk_val {
+        if let Ok(oid) = ObjectId::parse_str(s) {
+            return Ok(doc! { pk_col: { "$in": [oid, s.as_str()] } });
+        }
+    }
+    Ok(doc! { pk_col: json_to_bson(pk_val)? })
+}
+
+/// Grid columns and rows for a result set: the union of the documents'
+/// top-level fields, `_id` first and the rest in first-seen order. A field
+/// a document lacks is `null` in its row.
+pub fn tabulate(docs: Vec<Document>) -> (Vec<String>, Vec<Vec<Value>>) {
+    let mut columns: Vec<String> = Vec::new();
+    for doc in &docs {
+        for key in doc.keys() {
+            if !columns.contains(key) {
+                columns.push(key.clone());
+            }
+        }
+    }
+    if let Some(idx) = columns.iter().position(|c| c == "_id") {
+        let id = columns.remove(idx);
+        columns.insert(0, id);
+    }
+
+    let rows = docs
+        .into_iter()
+        .map(|mut doc| {
+            columns
+                .iter()
+                .map(|c| doc.remove(c).map_or(Value::Null, bson_to_json))
+                .collect()
+        })
+        .collect();
+    (columns, rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn flattens_top_level_scalars() {
+        let oid = ObjectId::parse_str("65a1f0c2e4b0a1b2c3d4e5f6").unwrap();
+        assert_eq!(
+            bson_to_json(Bson::ObjectId(oid)),
+            json!("65a1f0c2e4b0a1b2c3d4e5f6")
+        );
+        assert_eq!(
+            bson_to_json(Bson::DateTime(DateTime::from_millis(0))),
+            json!("1970-01-01T00:00:00Z")
+        );
+        assert_eq!(
+            bson_to_json(Bson::Document(doc! { "ref": oid })),
+            json!({ "ref": { "$oid": "65a1f0c2e4b0a1b2c3d4e5f6" } })
+        );
+    }
+
+    #[test]
+    fn coerces_edits_to_the_existing_type() {
+        let date = Bson::DateTime(DateTime::from_millis(0));
+        assert_eq!(
+            coerce_like(Some(&date), json!("2024-05-01T10:00:00Z")).unwrap(),
+            Bson::DateTime(DateTime::parse_rfc3339_str("2024-05-01T10:00:00Z").unwrap())
+        );
+        assert_eq!(
+            coerce_like(Some(&Bson::Int64(1)), json!(5)).unwrap(),
+            Bson::Int64(5)
+        );
+        assert_eq!(
+            coerce_like(Some(&Bson::Document(doc! {})), json!("{\"a\": 1}")).unwrap(),
+            Bson::Document(doc! { "a": 1 })
+        );
+        // Text that no longer fits the old type is stored as typed.
+        assert_eq!(
+            coerce_like(Some(&date), json!("soon")).unwrap(),
+            Bson::String("soon".into())
+        );
+    }
+
+    #[test]
+    fn tabulates_heterogeneous_documents() {
+        let docs = vec![
+            doc! { "name": "Ada", "_id": 1 },
+            doc! { "_id": 2, "email": "l@example.com" },
+        ];
+        let (columns, rows) = tabulate(docs);
+        assert_eq!(columns, ["_id", "name", "email"]);
+        assert_eq!(
+            rows,
+            vec![
+                vec![json!(1), json!("Ada"), Value::Null],
+                vec![json!(2), Value::Null, json!("l@example.com")],
+            ]
+        );
+    }
+
+    #[test]
+    fn pk_filter_matches_object_id_and_string() {
+        let filter = pk_filter("_id", json!("65a1f0c2e4b0a1b2c3d4e5f6")).unwrap();
+        let oid = ObjectId::parse_str("65a1f0c2e4b0a1b2c3d4e5f6").unwrap();
+        assert_eq!(
+            filter,
+            doc! { "_id": { "$in": [oid, "65a1f0c2e4b0a1b2c3d4e5f6"] } }
+        );
+        assert_eq!(pk_filter("sku", json!(42)).unwrap(), doc! { "sku": 42 });
+    }
+}
@@ -0,0 +1,3 @@
+pub mod convert;
+pub mod schema;
+pub mod shell;
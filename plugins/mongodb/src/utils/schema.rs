@@ -0,0 +1,135 @@
+//! Column inference. Collections have no schema, so the columns shown for
+//! one are the union of the fields found in a sample of its documents.
+
+use mongodb::bson::Document;
+use serde_json::{json, Value};
+
+use crate::utils::convert::type_name;
+
+/// How many documents `get_columns` looks at.
+pub const SAMPLE_SIZE: i64 = 100;
+
+struct Field {
+    name: String,
+    /// Occurrences of each type, in first-seen order.
+    types: Vec<(&'static str, usize)>,
+    present: usize,
+    has_null: bool,
+}
+
+/// Columns in the shape of the host's `TableColumn`. `_id` comes first,
+/// the other fields in the order they were first seen; each column takes
+/// its most common type. A field missing from some documents is nullable.
+pub fn infer_columns(docs: &[Document]) -> Vec<Value> {
+    let mut fields: Vec<Field> = vec![Field {
+        name: "_id".to_string(),
+        types: Vec::new(),
+        present: 0,
+        has_null: false,
+    }];
+    for doc in docs {
+        for (key, value) in doc {
+            let idx = match fields.iter().position(|f| &f.name == key) {
+                Some(idx) => idx,
+                None => {
+                    fields.push(Field {
+                        name: key.clone(),
+                        types: Vec::new(),
+                        present: 0,
+                        has_null: false,
+                    });
+                    fields.len() - 1
+                }
+            };
+            let field = &mut fields[idx];
+            field.present += 1;
+            match type_name(value) {
+                None => field.has_null = true,
+                Some(ty) => match field.types.iter_mut().find(|(t, _)| *t == ty) {
+                    Some((_, count)) => *count += 1,
+                    None => field.types.push((ty, 1)),
+                },
+            }
+        }
+    }
+
+    fields
+        .into_iter()
+        .map(|field| {
+            // `max_by_key` keeps the last maximum; reverse to prefer the
+            // type seen first on ties.
+            let data_type = field
+                .types
+                .iter()
+                .rev()
+                .max_by_key(|(_, count)| *count)
+                .map_or(
+                    if field.name == "_id" {
+                        "OBJECTID"
+                    } else {
+                        "STRING"
+                    },
+                    |(t, _)| *t,
+                );
+            let is_pk = field.name == "_id";
+            json!({
+                "name": field.name,
+                "data_type": data_type,
+                "is_pk": is_pk,
+                "is_nullable": !is_pk && (field.present < docs.len() || field.has_null),
+                // The server fills in a missing ObjectId `_id` on insert.
+                "is_auto_increment": is_pk && data_type == "OBJECTID",
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::infer_columns;
+    use mongodb::bson::doc;
+    use mongodb::bson::oid::ObjectId;
+    use serde_json::json;
+
+    #[test]
+    fn unions_fields_across_documents() {
+        let docs = vec![
+            doc! { "_id": ObjectId::new(), "name": "Ada", "age": 36 },
+            doc! { "_id": ObjectId::new(), "name": "Linus", "email": "l@example.com" },
+            doc! { "_id": ObjectId::new(), "name": "Grace", "age": 85 },
+        ];
+        let columns = infer_columns(&docs);
+        let names: Vec<&str> = columns
+            .iter()
+            .map(|c| c["name"].as_str().unwrap())
+            .collect();
+        assert_eq!(names, ["_id", "name", "age", "email"]);
+
+        assert_eq!(columns[0]["data_type"], json!("OBJECTID"));
+        assert_eq!(columns[0]["is_pk"], json!(true));
+        assert_eq!(columns[0]["is_auto_increment"], json!(true));
+        assert_eq!(columns[1]["is_nullable"], json!(false));
+        assert_eq!(columns[2]["data_type"], json!("INT32"));
+        assert_eq!(columns[2]["is_nullable"], json!(true));
+    }
+
+    #[test]
+    fn picks_the_most_common_type() {
+        let docs = vec![
+            doc! { "_id": 1, "price": 10 },
+            doc! { "_id": 2, "price": 10.5 },
+            doc! { "_id": 3, "price": 11.5 },
+        ];
+        let columns = infer_columns(&docs);
+        assert_eq!(columns[0]["data_type"], json!("INT32"));
+        assert_eq!(columns[0]["is_auto_increment"], json!(false));
+        assert_eq!(columns[1]["data_type"], json!("DOUBLE"));
+    }
+
+    #[test]
+    fn empty_collection_has_only_id() {
+        let columns = infer_columns(&[]);
+        assert_eq!(columns.len(), 1);
+        assert_eq!(columns[0]["name"], json!("_id"));
+    }
+}
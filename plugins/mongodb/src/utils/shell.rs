@@ -0,0 +1,564 @@
+//! Query text accepted by `execute_query`.
+//!
+//! Two forms are understood:
+//! - mongo shell calls: `db.orders.find({status: "pending"}).sort({ordered_at: -1})`,
+//!   `db.orders.aggregate([{$group: {_id: "$status", n: {$sum: 1}}}])`, and
+//!   `db.getCollection("order items").find()` for names that are not
+//!   identifiers;
+//! - the `SELECT * FROM "orders"` the grid issues when a collection is
+//!   opened, optionally with `ORDER BY` and `LIMIT` / `OFFSET`.
+//!
+//! Shell arguments are relaxed JSON: keys may be unquoted, strings may use
+//! single quotes and trailing commas are allowed. `ObjectId(..)`,
+//! `ISODate(..)` / `new Date(..)`, `NumberInt(..)`, `NumberLong(..)` and
+//! `NumberDecimal(..)` are turned into their Extended JSON forms.
+
+use mongodb::bson::oid::ObjectId;
+use mongodb::bson::{DateTime, Document};
+use serde_json::{json, Map, Value};
+
+use crate::error::PluginError;
+use crate::utils::convert::json_to_document;
+
+#[derive(Debug, PartialEq)]
+pub enum Query {
+    Find(Box<Find>),
+    Aggregate {
+        collection: String,
+        pipeline: Vec<Document>,
+    },
+}
+
+#[derive(Debug, Default, PartialEq)]
+pub struct Find {
+    pub collection: String,
+    pub filter: Document,
+    pub projection: Option<Document>,
+    pub sort: Option<Document>,
+    pub skip: Option<u64>,
+    pub limit: Option<u64>,
+}
+
+const USAGE: &str =
+    "expected db.<collection>.find(...), db.<collection>.aggregate([...]) or SELECT * FROM <collection>";
+
+pub fn parse_query(text: &str) -> Result<Query, PluginError> {
+    let text = text.trim().trim_end_matches(';').trim_end();
+    let mut parser = Parser::new(text);
+    let query = if parser.keyword("select") {
+        parser.select()?
+    } else {
+        parser.shell()?
+    };
+    if !parser.at_end() {
+        return Err(parser.error("unexpected input"));
+    }
+    Ok(query)
+}
+
+struct Parser {
+    chars: Vec<char>,
+    pos: usize,
+}
+
+impl Parser {
+    fn new(text: &str) -> Self {
+        Self {
+            chars: text.chars().collect(),
+            pos: 0,
+        }
+    }
+
+    fn error(&self, msg: &str) -> PluginError {
+        PluginError::invalid_params(format!("{msg} at offset {}", self.pos))
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn skip_ws(&mut self) {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.pos += 1;
+        }
+    }
+
+    fn at_end(&mut self) -> bool {
+        self.skip_ws();
+        self.pos >= self.chars.len()
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        self.skip_ws();
+        if self.peek() == Some(c) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, c: char) -> Result<(), PluginError> {
+        if self.eat(c) {
+            Ok(())
+        } else {
+            Err(self.error(&format!("expected '{c}'")))
+        }
+    }
+
+    /// A run of characters accepted by `allowed`, which must not start with
+    /// a digit.
+    fn word(&mut self, allowed: fn(char) -> bool) -> Option<String> {
+        self.skip_ws();
+        let start = self.pos;
+        if self.peek().is_some_and(|c| c.is_ascii_digit()) {
+            return None;
+        }
+        while self.peek().is_some_and(allowed) {
+            self.pos += 1;
+        }
+        (self.pos > start).then(|| self.chars[start..self.pos].iter().collect())
+    }
+
+    fn ident(&mut self) -> Option<String> {
+        self.word(|c| c.is_alphanumeric() || c == '_' || c == '$')
+    }
+
+    /// Consume `kw` (case-insensitively) if it is the next word.
+    fn keyword(&mut self, kw: &str) -> bool {
+        let start = self.pos;
+        match self.ident() {
+            Some(word) if word.eq_ignore_ascii_case(kw) => true,
+            _ => {
+                self.pos = start;
+                false
+            }
+        }
+    }
+
+    fn unsigned(&mut self) -> Result<u64, PluginError> {
+        self.skip_ws();
+        let start = self.pos;
+        while self.peek().is_some_and(|c| c.is_ascii_digit()) {
+            self.pos += 1;
+        }
+        self.chars[start..self.pos]
+            .iter()
+            .collect::<String>()
+            .parse()
+            .map_err(|_| self.error("expected a number"))
+    }
+
+    // --- SELECT ---------------------------------------------------------
+
+    fn select(&mut self) -> Result<Query, PluginError> {
+        if !(self.eat('*') && self.keyword("from")) {
+            return Err(self.error(
+                "only SELECT * FROM <collection> is supported; use db.<collection>.find(...) for anything else",
+            ));
+        }
+        let mut find = Find {
+            collection: self.name()?,
+            ..Find::default()
+        };
+        if self.keyword("where") {
+            return Err(self.error(&format!(
+                "WHERE is not supported; filter with db.{}.find({{...}}) instead",
+                find.collection
+            )));
+        }
+        if self.keyword("order") {
+            if !self.keyword("by") {
+                return Err(self.error("expected BY"));
+            }
+            let mut sort = Document::new();
+            loop {
+                let field = self.name()?;
+                let direction = if self.keyword("desc") {
+                    -1
+                } else {
+                    self.keyword("asc");
+                    1
+                };
+                sort.insert(field, direction);
+                if !self.eat(',') {
+                    break;
+                }
+            }
+            find.sort = Some(sort);
+        }
+        if self.keyword("limit") {
+            find.limit = Some(self.unsigned()?);
+        }
+        if self.keyword("offset") {
+            find.skip = Some(self.unsigned()?);
+        }
+        Ok(Query::Find(Box::new(find)))
+    }
+
+    /// A bare or quoted (`"name"`, `` `name` ``) identifier.
+    fn name(&mut self) -> Result<String, PluginError> {
+        self.skip_ws();
+        match self.peek() {
+            Some(quote @ ('"' | '`')) => {
+                self.pos += 1;
+                let mut name = String::new();
+                loop {
+                    match self.peek() {
+                        None => return Err(self.error("unterminated identifier")),
+                        Some(c) if c == quote => {
+                            self.pos += 1;
+                            if self.peek() == Some(quote) {
+                                name.push(quote);
+                                self.pos += 1;
+                            } else {
+                                return Ok(name);
+                            }
+                        }
+                        Some(c) => {
+                            name.push(c);
+                            self.pos += 1;
+                        }
+                    }
+                }
+            }
+            _ => self
+                .word(|c| c.is_alphanumeric() || c == '_' || c == '$' || c == '.')
+                .ok_or_else(|| self.error("expected a name")),
+        }
+    }
+
+    // --- Shell ----------------------------------------------------------
+
+    fn shell(&mut self) -> Result<Query, PluginError> {
+        if self.ident().as_deref() != Some("db") || !self.eat('.') {
+            return Err(PluginError::invalid_params(USAGE));
+        }
+        let mut collection = self
+            .ident()
+            .ok_or_else(|| self.error("expected a collection"))?;
+        if collection == "getCollection" {
+            collection = match self.call_args()?.as_slice() {
+                [Value::String(name)] => name.clone(),
+                _ => return Err(self.error("getCollection expects a name")),
+            };
+        }
+        self.expect('.')?;
+
+        let method = self
+            .ident()
+            .ok_or_else(|| self.error("expected a method"))?;
+        let mut args = self.call_args()?.into_iter();
+        let mut find = match method.as_str() {
+            "find" | "findOne" => Find {
+                filter: optional_document(args.next(), "filter")?.unwrap_or_default(),
+                projection: optional_document(args.next(), "projection")?,
+                limit: (method == "findOne").then_some(1),
+                collection,
+                ..Find::default()
+            },
+            "aggregate" => {
+                let pipeline = match args.next() {
+                    Some(Value::Array(stages)) => stages
+                        .into_iter()
+                        .map(json_to_document)
+                        .collect::<Result<_, _>>()?,
+                    None => Vec::new(),
+                    Some(_) => return Err(self.error("aggregate expects an array of stages")),
+                };
+                if self.eat('.') {
+                    return Err(self.error("aggregate takes no chained methods"));
+                }
+                return Ok(Query::Aggregate {
+                    collection,
+                    pipeline,
+                });
+            }
+            other => {
+                return Err(PluginError::invalid_params(format!(
+                    "unsupported method '{other}'; {USAGE}"
+                )))
+            }
+        };
+
+        while self.eat('.') {
+            let modifier = self
+                .ident()
+                .ok_or_else(|| self.error("expected a method"))?;
+            let arg = self.call_args()?.into_iter().next();
+            match modifier.as_str() {
+                "sort" => find.sort = optional_document(arg, "sort")?,
+                "projection" => find.projection = optional_document(arg, "projection")?,
+                "skip" => find.skip = Some(count(arg, "skip")?),
+                // As in the shell, `limit(0)` means no limit.
+                "limit" => find.limit = Some(count(arg, "limit")?).filter(|n| *n > 0),
+                other => {
+                    return Err(PluginError::invalid_params(format!(
+                        "unsupported cursor method '{other}'"
+                    )))
+                }
+            }
+        }
+        Ok(Query::Find(Box::new(find)))
+    }
+
+    /// `( value, ... )`
+    fn call_args(&mut self) -> Result<Vec<Value>, PluginError> {
+        self.expect('(')?;
+        let mut args = Vec::new();
+        while !self.eat(')') {
+            args.push(self.value()?);
+            if !self.eat(',') {
+                self.expect(')')?;
+                break;
+            }
+        }
+        Ok(args)
+    }
+
+    fn value(&mut self) -> Result<Value, PluginError> {
+        self.skip_ws();
+        match self.peek() {
+            Some('{') => self.object(),
+            Some('[') => {
+                self.pos += 1;
+                let mut items = Vec::new();
+                while !self.eat(']') {
+                    items.push(self.value()?);
+                    if !self.eat(',') {
+                        self.expect(']')?;
+                        break;
+                    }
+                }
+                Ok(Value::Array(items))
+            }
+            Some(quote @ ('"' | '\'')) => self.string(quote).map(Value::String),
+            Some(c) if c == '-' || c.is_ascii_digit() => self.number(),
+            Some(_) => {
+                let word = self.ident().ok_or_else(|| self.error("expected a value"))?;
+                match word.as_str() {
+                    "true" => Ok(Value::Bool(true)),
+                    "false" => Ok(Value::Bool(false)),
+                    "null" => Ok(Value::Null),
+                    "new" => {
+                        let ctor = self.ident().ok_or_else(|| self.error("expected a type"))?;
+                        self.constructor(&ctor)
+                    }
+                    ctor => self.constructor(ctor),
+                }
+            }
+            None => Err(self.error("unexpected end of query")),
+        }
+    }
+
+    fn object(&mut self) -> Result<Value, PluginError> {
+        self.expect('{')?;
+        let mut map = Map::new();
+        while !self.eat('}') {
+            self.skip_ws();
+            let key = match self.peek() {
+                Some(quote @ ('"' | '\'')) => self.string(quote)?,
+                _ => self
+                    .word(|c| c.is_alphanumeric() || c == '_' || c == '$' || c == '.')
+                    .ok_or_else(|| self.error("expected a key"))?,
+            };
+            self.expect(':')?;
+            map.insert(key, self.value()?);
+            if !self.eat(',') {
+                self.expect('}')?;
+                break;
+            }
+        }
+        Ok(Value::Object(map))
+    }
+
+    fn string(&mut self, quote: char) -> Result<String, PluginError> {
+        self.pos += 1;
+        let mut out = String::new();
+        loop {
+            let c = self
+                .peek()
+                .ok_or_else(|| self.error("unterminated string"))?;
+            self.pos += 1;
+            match c {
+                c if c == quote => return Ok(out),
+                '\\' => {
+                    let escaped = self
+                        .peek()
+                        .ok_or_else(|| self.error("unterminated string"))?;
+                    self.pos += 1;
+                    match escaped {
+                        'n' => out.push('\n'),
+                        't' => out.push('\t'),
+                        'r' => out.push('\r'),
+                        'b' => out.push('\u{8}'),
+                        'f' => out.push('\u{c}'),
+                        'u' => {
+                            let hex: String = self.chars.iter().skip(self.pos).take(4).collect();
+                            let ch = u32::from_str_radix(&hex, 16)
+                                .ok()
+                                .and_then(char::from_u32)
+                                .ok_or_else(|| self.error("invalid \\u escape"))?;
+                            out.push(ch);
+                            self.pos += 4;
+                        }
+                        other => out.push(other),
+                    }
+                }
+                c => out.push(c),
+            }
+        }
+    }
+
+    fn number(&mut self) -> Result<Value, PluginError> {
+        let start = self.pos;
+        while self
+            .peek()
+            .is_some_and(|c| c.is_ascii_digit() || matches!(c, '-' | '+' | '.' | 'e' | 'E'))
+        {
+            self.pos += 1;
+        }
+        let text: String = self.chars[start..self.pos].iter().collect();
+        serde_json::from_str::<Value>(&text)
+            .ok()
+            .filter(Value::is_number)
+            .ok_or_else(|| self.error(&format!("invalid number '{text}'")))
+    }
+
+    fn constructor(&mut self, name: &str) -> Result<Value, PluginError> {
+        let args = self.call_args()?;
+        Ok(match (name, args.first()) {
+            ("ObjectId", None) => json!({ "$oid": ObjectId::new().to_hex() }),
+            ("ObjectId", Some(Value::String(hex))) => json!({ "$oid": hex }),
+            ("ISODate" | "Date", None) => {
+                json!({ "$date": { "$numberLong": DateTime::now().timestamp_millis().to_string() } })
+            }
+            ("ISODate" | "Date", Some(Value::Number(millis))) => {
+                json!({ "$date": { "$numberLong": millis.to_string() } })
+            }
+            // A bare date means midnight UTC, as in the shell.
+            ("ISODate" | "Date", Some(Value::String(s))) if s.len() == 10 => {
+                json!({ "$date": format!("{s}T00:00:00Z") })
+            }
+            ("ISODate" | "Date", Some(Value::String(s))) => json!({ "$date": s }),
+            ("NumberInt", Some(n)) => json!({ "$numberInt": scalar_text(n) }),
+            ("NumberLong", Some(n)) => json!({ "$numberLong": scalar_text(n) }),
+            ("NumberDecimal", Some(n)) => json!({ "$numberDecimal": scalar_text(n) }),
+            _ => return Err(self.error(&format!("invalid {name}(...)"))),
+        })
+    }
+}
+
+/// `NumberLong(5)` and `NumberLong("5")` mean the same.
+fn scalar_text(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+fn optional_document(value: Option<Value>, what: &str) -> Result<Option<Document>, PluginError> {
+    match value {
+        None => Ok(None),
+        Some(v @ Value::Object(_)) => json_to_document(v).map(Some),
+        Some(_) => Err(PluginError::invalid_params(format!(
+            "{what} must be a document"
+        ))),
+    }
+}
+
+fn count(value: Option<Value>, what: &str) -> Result<u64, PluginError> {
+    value
+        .as_ref()
+        .and_then(Value::as_u64)
+        .ok_or_else(|| PluginError::invalid_params(format!("{what} expects a non-negative number")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_query, Find, Query};
+    use mongodb::bson::oid::ObjectId;
+    use mongodb::bson::{doc, DateTime};
+
+    fn find(query: &str) -> Find {
+        match parse_query(query).expect("query should parse") {
+            Query::Find(find) => *find,
+            other => panic!("expected find, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn parses_browse_select() {
+        assert_eq!(
+            find(
+                r#"SELECT * FROM "order ""items""" ORDER BY "qty" DESC, sku LIMIT 50 OFFSET 100;"#
+            ),
+            Find {
+                collection: "order \"items\"".into(),
+                sort: Some(doc! { "qty": -1, "sku": 1 }),
+                limit: Some(50),
+                skip: Some(100),
+                ..Find::default()
+            }
+        );
+        assert!(parse_query("SELECT * FROM orders WHERE id = 1").is_err());
+        assert!(parse_query("SELECT name FROM orders").is_err());
+    }
+
+    #[test]
+    fn parses_find_with_relaxed_json_and_cursor_methods() {
+        let parsed = find(
+            "db.orders.find({status: 'pending', 'total': {$gte: 10.5}, tags: ['a', \"b\",],}, {_id: 0})\n  .sort({ordered_at: -1}).skip(5).limit(0)",
+        );
+        assert_eq!(
+            parsed,
+            Find {
+                collection: "orders".into(),
+                filter: doc! { "status": "pending", "total": { "$gte": 10.5 }, "tags": ["a", "b"] },
+                projection: Some(doc! { "_id": 0 }),
+                sort: Some(doc! { "ordered_at": -1 }),
+                skip: Some(5),
+                limit: None,
+            }
+        );
+    }
+
+    #[test]
+    fn parses_shell_constructors() {
+        let parsed = find(
+            r#"db.getCollection("audit.log").findOne({_id: ObjectId("65a1f0c2e4b0a1b2c3d4e5f6"), at: {$lt: ISODate("2024-05-01")}, n: NumberLong("7")})"#,
+        );
+        assert_eq!(parsed.collection, "audit.log");
+        assert_eq!(parsed.limit, Some(1));
+        assert_eq!(
+            parsed.filter,
+            doc! {
+                "_id": ObjectId::parse_str("65a1f0c2e4b0a1b2c3d4e5f6").unwrap(),
+                "at": { "$lt": DateTime::parse_rfc3339_str("2024-05-01T00:00:00Z").unwrap() },
+                "n": 7_i64,
+            }
+        );
+    }
+
+    #[test]
+    fn parses_aggregate() {
+        assert_eq!(
+            parse_query(r#"db.orders.aggregate([{$match: {status: "shipped"}}, {$group: {_id: "$customer_id", n: {$sum: 1}}}])"#).unwrap(),
+            Query::Aggregate {
+                collection: "orders".into(),
+                pipeline: vec![
+                    doc! { "$match": { "status": "shipped" } },
+                    doc! { "$group": { "_id": "$customer_id", "n": { "$sum": 1 } } },
+                ],
+            }
+        );
+    }
+
+    #[test]
+    fn rejects_unsupported_input() {
+        assert!(parse_query("db.orders.drop()").is_err());
+        assert!(parse_query("db.orders.find({a: 1}) extra").is_err());
+        assert!(parse_query("show collections").is_err());
+        assert!(parse_query("db.orders.find({a: })").is_err());
+    }
+}
@@ -5,7 +5,7 @@ use serde_json::json;
 use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::time::{SystemTime, UNIX_EPOCH};
-use tauri::{AppHandle, Manager};
+use tauri::AppHandle;
 
 // --- Data Structures ---
 
@@ -15,6 +15,10 @@ pub struct AiGenerateRequest {
     pub model: String,
     pub prompt: String,
     pub schema: String,
+    /// When the connection has a schema embeddings index, the tables
+    /// relevant to the prompt replace `schema`.
+    #[serde(default)]
+    pub connection_id: Option<String>,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -77,6 +81,20 @@ struct OpenRouterModel {
     id: String,
 }
 
+#[derive(Deserialize, Debug)]
+struct GeminiModelList {
+    #[serde(default)]
+    models: Vec<GeminiModel>,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct GeminiModel {
+    name: String,
+    #[serde(default)]
+    supported_generation_methods: Vec<String>,
+}
+
 #[derive(Serialize, Deserialize, Debug)]
 struct AiModelsCache {
     last_updated: u64,
@@ -94,8 +112,7 @@ fn load_default_models() -> HashMap<String, Vec<String>> {
 }
 
 fn get_cache_path(app: &AppHandle) -> Option<std::path::PathBuf> {
-    app.path()
-        .app_config_dir()
+    crate::paths::app_config_dir(app)
         .ok()
         .map(|p| p.join("ai_models_cache.json"))
 }
@@ -193,6 +210,46 @@ async fn fetch_openrouter_models() -> Vec<String> {
     }
 }
 
+pub(crate) const GEMINI_API_BASE: &str = "https://generativelanguage.googleapis.com/v1beta";
+
+/// Gemini chat model ids from a `models.list` response, without the
+/// `models/` prefix. Embedding and other non-chat models are skipped.
+fn gemini_model_ids(list: GeminiModelList) -> Vec<String> {
+    list.models
+        .into_iter()
+        .filter(|m| {
+            m.supported_generation_methods
+                .iter()
+                .any(|method| method == "generateContent")
+        })
+        .map(|m| m.name.trim_start_matches("models/").to_string())
+        .filter(|id| id.starts_with("gemini"))
+        .collect()
+}
+
+async fn fetch_gemini_models(api_key: &str) -> Vec<String> {
+    if api_key.is_empty() {
+        return Vec::new();
+    }
+    let client = Client::new();
+    match client
+        .get(format!("{}/models?pageSize=1000", GEMINI_API_BASE))
+        .header("x-goog-api-key", api_key)
+        .send()
+        .await
+    {
+        Ok(res) => {
+            if res.status().is_success() {
+                if let Ok(json) = res.json::<GeminiModelList>().await {
+                    return gemini_model_ids(json);
+                }
+            }
+            Vec::new()
+        }
+        Err(_) => Vec::new(),
+    }
+}
+
 /// Build an API endpoint URL from a user-provided base_url.
 ///
 /// Handles various formats the user might input:
@@ -203,7 +260,7 @@ async fn fetch_openrouter_models() -> Vec<String> {
 ///
 /// This avoids hardcoding `/v1` so that providers using different
 /// version paths (e.g., Zhipu GLM's `/v4`) work correctly.
-fn build_api_url(base_url: &str, endpoint: &str) -> String {
+pub(crate) fn build_api_url(base_url: &str, endpoint: &str) -> String {
     let trimmed = base_url.trim_end_matches('/');
 
     // User already provided the full path
@@ -323,7 +380,20 @@ pub async fn get_ai_models(
         }
     }
 
-    // 3. OpenRouter (Dynamic public)
+    // 3. Gemini (Dynamic if key exists)
+    if let Ok(key) = config::get_ai_api_key("gemini") {
+        let remote_models = fetch_gemini_models(&key).await;
+        if !remote_models.is_empty() {
+            if let Some(static_list) = models.get_mut("gemini") {
+                let mut set: HashSet<String> = static_list.iter().cloned().collect();
+                set.extend(remote_models);
+                *static_list = set.into_iter().collect();
+                static_list.sort();
+            }
+        }
+    }
+
+    // 4. OpenRouter (Dynamic public)
     let openrouter_models = fetch_openrouter_models().await;
     if !openrouter_models.is_empty() {
         if let Some(static_list) = models.get_mut("openrouter") {
@@ -339,7 +409,7 @@ pub async fn get_ai_models(
         }
     }
 
-    // 4. Custom OpenAI (Dynamic if configured)
+    // 5. Custom OpenAI (Dynamic if configured)
     if let (Some(base_url), Ok(api_key)) = (
         app_config.ai_custom_openai_url,
         config::get_ai_api_key("custom-openai"),
@@ -389,7 +459,7 @@ pub async fn generate_cell_name(app: AppHandle, req: AiCellNameRequest) -> Resul
 
 // --- Shared helpers ---
 
-async fn resolve_model(
+pub(crate) async fn resolve_model(
     provider: &str,
     model: &str,
     app_config: &config::AppConfig,
@@ -450,6 +520,7 @@ async fn dispatch_provider(
             generate_custom_openai(&client, &api_key, gen_req, system_prompt, base_url).await
         }
         "minimax" => generate_minimax(&client, &api_key, gen_req, system_prompt).await,
+        "gemini" => generate_gemini(&client, &api_key, gen_req, system_prompt).await,
         _ => Err(format!("Unsupported provider: {}", gen_req.provider)),
     }
 }
@@ -463,6 +534,14 @@ pub async fn generate_query(app: AppHandle, mut req: AiGenerateRequest) -> Resul
     let ollama_port = app_config.ai_ollama_port.unwrap_or(11434);
     req.model = resolve_model(&req.provider, &req.model, &app_config, ollama_port).await?;
 
+    if let Some(connection_id) = req.connection_id.as_deref() {
+        match crate::embeddings::relevant_schema_context(&app, connection_id, &req.prompt).await {
+            Ok(Some(context)) => req.schema = context,
+            Ok(None) => {}
+            Err(e) => log::warn!("Schema embeddings lookup failed, using full schema: {}", e),
+        }
+    }
+
     let raw_prompt = config::get_system_prompt(app);
     let system_prompt = raw_prompt.replace("{{SCHEMA}}", &req.schema);
 
@@ -491,6 +570,7 @@ pub async fn explain_query(app: AppHandle, mut req: AiExplainRequest) -> Result<
         model: req.model.clone(),
         prompt: format!("Query:\n{}\n", req.query),
         schema: String::new(),
+        connection_id: None,
     };
 
     let result = dispatch_provider(&app_config, &gen_req, &system_prompt, ollama_port).await;
@@ -524,6 +604,7 @@ pub async fn analyze_explain_plan(
         model: req.model.clone(),
         prompt: req.query.clone(),
         schema: String::new(),
+        connection_id: None,
     };
 
     let result = dispatch_provider(&app_config, &gen_req, &system_prompt, ollama_port).await;
@@ -560,6 +641,7 @@ async fn generate_with_simple_prompt(
         model: resolved_model.clone(),
         prompt: query,
         schema: String::new(),
+        connection_id: None,
     };
 
     let result = dispatch_provider(&app_config, &gen_req, &system_prompt, ollama_port).await;
@@ -614,6 +696,7 @@ pub async fn suggest_table_name(
         model: resolved_model,
         prompt,
         schema: String::new(),
+        connection_id: None,
     };
 
     let system_prompt = "You are a database naming expert. Reply with only a snake_case table name, no explanation.";
@@ -887,7 +970,55 @@ async fn generate_minimax(
     Ok(clean_response(content))
 }
 
-fn clean_response(text: &str) -> String {
+/// Request body for Gemini's `generateContent` and `streamGenerateContent`.
+pub(crate) fn gemini_body(system_prompt: &str, prompt: &str) -> serde_json::Value {
+    json!({
+        "systemInstruction": {
+            "parts": [{"text": system_prompt}]
+        },
+        "contents": [
+            {"role": "user", "parts": [{"text": prompt}]}
+        ],
+        "generationConfig": {
+            "temperature": 0.0
+        }
+    })
+}
+
+/// The text of a Gemini response (or streamed chunk): the parts of its
+/// first candidate, joined.
+pub(crate) fn gemini_text(json: &serde_json::Value) -> Option<String> {
+    let parts = json["candidates"][0]["content"]["parts"].as_array()?;
+    Some(parts.iter().filter_map(|p| p["text"].as_str()).collect())
+}
+
+async fn generate_gemini(
+    client: &Client,
+    api_key: &str,
+    req: &AiGenerateRequest,
+    system_prompt: &str,
+) -> Result<String, String> {
+    let url = format!("{}/models/{}:generateContent", GEMINI_API_BASE, req.model);
+    let res = client
+        .post(&url)
+        .header("x-goog-api-key", api_key)
+        .json(&gemini_body(system_prompt, &req.prompt))
+        .send()
+        .await
+        .map_err(|e| e.to_string())?;
+
+    if !res.status().is_success() {
+        let error_text = res.text().await.unwrap_or_default();
+        return Err(format!("Gemini Error: {}", error_text));
+    }
+
+    let json: serde_json::Value = res.json().await.map_err(|e| e.to_string())?;
+    let content = gemini_text(&json).ok_or("Invalid response format from Gemini")?;
+
+    Ok(clean_response(&content))
+}
+
+pub(crate) fn clean_response(text: &str) -> String {
     let text = text.trim();
     if text.starts_with("```") {
         let mut lines = text.lines();
@@ -915,6 +1046,7 @@ mod tests {
         assert!(models.contains_key("anthropic"));
         assert!(models.contains_key("openrouter"));
         assert!(models.contains_key("minimax"));
+        assert!(models.contains_key("gemini"));
 
         // Check for new futuristic models from yaml
         let openai = models.get("openai").unwrap();
@@ -929,6 +1061,39 @@ mod tests {
         assert!(!models.contains_key("ollama"));
     }
 
+    #[test]
+    fn test_gemini_model_ids() {
+        let list: GeminiModelList = serde_json::from_value(json!({
+            "models": [
+                {
+                    "name": "models/gemini-2.5-pro",
+                    "supportedGenerationMethods": ["generateContent", "countTokens"]
+                },
+                {
+                    "name": "models/text-embedding-004",
+                    "supportedGenerationMethods": ["embedContent"]
+                },
+                {
+                    "name": "models/gemma-3-27b-it",
+                    "supportedGenerationMethods": ["generateContent"]
+                }
+            ]
+        }))
+        .unwrap();
+        assert_eq!(gemini_model_ids(list), vec!["gemini-2.5-pro".to_string()]);
+    }
+
+    #[test]
+    fn test_gemini_text() {
+        let response = json!({
+            "candidates": [{
+                "content": {"role": "model", "parts": [{"text": "SELECT "}, {"text": "1;"}]}
+            }]
+        });
+        assert_eq!(gemini_text(&response).as_deref(), Some("SELECT 1;"));
+        assert_eq!(gemini_text(&json!({"candidates": []})), None);
+    }
+
     #[test]
     fn test_clean_response() {
         let input = "```sql\nSELECT * FROM users;\n```";
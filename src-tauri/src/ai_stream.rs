@@ -0,0 +1,431 @@
+//! Streaming AI completions.
+//!
+//! `generate_ai_query` and `explain_ai_query` resolve only once the model
+//! has written its whole answer. The streaming variants ask the provider for
+//! a streamed completion (server-sent events for OpenAI-compatible APIs and
+//! Anthropic and Gemini, NDJSON for Ollama) and emit each piece of text as an
+//! `ai_stream_chunk` event tagged with the caller's `stream_id`, so the UI
+//! can render tokens as they arrive. The command still resolves with the
+//! full, cleaned answer; `cancel_ai_stream` stops it early.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use reqwest::{Client, RequestBuilder};
+use serde::Serialize;
+use serde_json::{json, Value};
+use tauri::{AppHandle, Emitter, State};
+use tokio::task::AbortHandle;
+
+use crate::ai::{
+    build_api_url, clean_response, gemini_body, gemini_text, resolve_model, AiExplainRequest,
+    AiGenerateRequest, GEMINI_API_BASE,
+};
+use crate::config;
+
+pub const AI_STREAM_CHUNK_EVENT: &str = "ai_stream_chunk";
+
+/// One piece of a streamed answer. The last event of a stream has `done`
+/// set and an empty `delta`.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AiStreamChunk {
+    pub stream_id: String,
+    pub delta: String,
+    pub done: bool,
+}
+
+/// Running streams by `stream_id`, for `cancel_ai_stream`.
+#[derive(Default)]
+pub struct AiStreamState {
+    handles: Mutex<HashMap<String, AbortHandle>>,
+}
+
+/// How a provider frames its streamed response.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StreamFormat {
+    /// Server-sent events carrying chat completion chunks, ended by
+    /// `data: [DONE]`.
+    OpenAi,
+    /// Server-sent events from the Messages API.
+    Anthropic,
+    /// One JSON object per line.
+    Ollama,
+    /// Server-sent events, each a whole `generateContent` response.
+    Gemini,
+}
+
+impl StreamFormat {
+    pub fn for_provider(provider: &str) -> Option<Self> {
+        match provider {
+            "openai" | "openrouter" | "custom-openai" | "minimax" => Some(Self::OpenAi),
+            "anthropic" => Some(Self::Anthropic),
+            "ollama" => Some(Self::Ollama),
+            "gemini" => Some(Self::Gemini),
+            _ => None,
+        }
+    }
+
+    /// The text one stream event adds to the answer, if any. Error events
+    /// sent mid-stream fail the whole stream.
+    pub fn delta(self, event: &Value) -> Result<Option<String>, String> {
+        let error = match self {
+            Self::Anthropic if event["type"] == "error" => Some(&event["error"]["message"]),
+            _ => event.get("error").map(|e| e.get("message").unwrap_or(e)),
+        };
+        if let Some(error) = error {
+            let message = error
+                .as_str()
+                .map(str::to_string)
+                .unwrap_or_else(|| error.to_string());
+            return Err(message);
+        }
+        let text = match self {
+            Self::OpenAi => event["choices"][0]["delta"]["content"].as_str(),
+            Self::Anthropic if event["type"] == "content_block_delta" => {
+                event["delta"]["text"].as_str()
+            }
+            Self::Anthropic => None,
+            Self::Ollama => event["message"]["content"].as_str(),
+            Self::Gemini => return Ok(gemini_text(event).filter(|t| !t.is_empty())),
+        };
+        Ok(text.filter(|t| !t.is_empty()).map(str::to_string))
+    }
+}
+
+/// Splits a response body, received in arbitrary chunks, into the JSON
+/// payloads of its events: the `data:` lines of server-sent events, or the
+/// lines of NDJSON.
+pub struct EventSplitter {
+    format: StreamFormat,
+    buffer: Vec<u8>,
+}
+
+impl EventSplitter {
+    pub fn new(format: StreamFormat) -> Self {
+        Self {
+            format,
+            buffer: Vec::new(),
+        }
+    }
+
+    /// Payloads completed by `bytes`. A line cut by the chunk boundary is
+    /// kept until the rest arrives.
+    pub fn push(&mut self, bytes: &[u8]) -> Vec<String> {
+        self.buffer.extend_from_slice(bytes);
+        let mut payloads = Vec::new();
+        while let Some(end) = self.buffer.iter().position(|b| *b == b'\n') {
+            let line: Vec<u8> = self.buffer.drain(..=end).collect();
+            if let Some(payload) = self.payload(&line) {
+                payloads.push(payload);
+            }
+        }
+        payloads
+    }
+
+    /// The payload of a last line without a trailing newline.
+    pub fn finish(&mut self) -> Vec<String> {
+        let line = std::mem::take(&mut self.buffer);
+        self.payload(&line).into_iter().collect()
+    }
+
+    fn payload(&self, line: &[u8]) -> Option<String> {
+        let line = String::from_utf8_lossy(line);
+        let line = line.trim();
+        let payload = match self.format {
+            StreamFormat::Ollama => line,
+            // `event:`, `id:` and comment lines carry nothing we need: the
+            // event type is repeated inside the JSON.
+            _ => line.strip_prefix("data:")?.trim_start(),
+        };
+        if payload.is_empty() || payload == "[DONE]" {
+            return None;
+        }
+        Some(payload.to_string())
+    }
+}
+
+/// The streaming chat request for `provider`, mirroring the non-streaming
+/// requests in `crate::ai`.
+fn stream_request(
+    client: &Client,
+    app_config: &config::AppConfig,
+    provider: &str,
+    model: &str,
+    system_prompt: &str,
+    prompt: &str,
+    ollama_port: u16,
+) -> Result<RequestBuilder, String> {
+    let api_key = if provider != "ollama" {
+        config::get_ai_api_key(provider)?
+    } else {
+        String::new()
+    };
+    let chat_body = |temperature: f64| {
+        json!({
+            "model": model,
+            "messages": [
+                {"role": "system", "content": system_prompt},
+                {"role": "user", "content": prompt}
+            ],
+            "temperature": temperature,
+            "stream": true
+        })
+    };
+
+    let request = match provider {
+        "openai" => client
+            .post("https://api.openai.com/v1/chat/completions")
+            .header("Authorization", format!("Bearer {}", api_key))
+            .json(&chat_body(0.0)),
+        "openrouter" => client
+            .post("https://openrouter.ai/api/v1/chat/completions")
+            .header("Authorization", format!("Bearer {}", api_key))
+            .header("HTTP-Referer", "https://github.com/TabularisDB/tabularis")
+            .header("X-Title", "Tabularis")
+            .json(&chat_body(0.0)),
+        "custom-openai" => {
+            let base_url = app_config
+                .ai_custom_openai_url
+                .as_ref()
+                .filter(|u| !u.is_empty())
+                .ok_or("Custom OpenAI URL not configured.")?;
+            client
+                .post(build_api_url(base_url, "/chat/completions"))
+                .header("Authorization", format!("Bearer {}", api_key))
+                .json(&chat_body(0.0))
+        }
+        "minimax" => client
+            .post("https://api.minimax.io/v1/chat/completions")
+            .header("Authorization", format!("Bearer {}", api_key))
+            .json(&chat_body(0.1)),
+        "anthropic" => client
+            .post("https://api.anthropic.com/v1/messages")
+            .header("x-api-key", api_key)
+            .header("anthropic-version", "2023-06-01")
+            .json(&json!({
+                "model": model,
+                "system": system_prompt,
+                "messages": [
+                    {"role": "user", "content": prompt}
+                ],
+                "max_tokens": 1024,
+                "temperature": 0.0,
+                "stream": true
+            })),
+        "ollama" => client
+            .post(format!("http://localhost:{}/api/chat", ollama_port))
+            .json(&json!({
+                "model": model,
+                "messages": [
+                    {"role": "system", "content": system_prompt},
+                    {"role": "user", "content": prompt}
+                ],
+                "stream": true,
+                "options": {
+                    "temperature": 0.0
+                }
+            })),
+        "gemini" => client
+            .post(format!(
+                "{}/models/{}:streamGenerateContent?alt=sse",
+                GEMINI_API_BASE, model
+            ))
+            .header("x-goog-api-key", api_key)
+            .json(&gemini_body(system_prompt, prompt)),
+        _ => return Err(format!("Unsupported provider: {}", provider)),
+    };
+    Ok(request)
+}
+
+pub(crate) fn provider_label(provider: &str) -> &str {
+    match provider {
+        "openai" => "OpenAI",
+        "anthropic" => "Anthropic",
+        "openrouter" => "OpenRouter",
+        "ollama" => "Ollama",
+        "custom-openai" => "Custom OpenAI",
+        "minimax" => "MiniMax",
+        "gemini" => "Gemini",
+        other => other,
+    }
+}
+
+fn emit_chunk(app: &AppHandle, stream_id: &str, delta: String, done: bool) {
+    let chunk = AiStreamChunk {
+        stream_id: stream_id.to_string(),
+        delta,
+        done,
+    };
+    if let Err(e) = app.emit(AI_STREAM_CHUNK_EVENT, chunk) {
+        log::warn!("Failed to emit AI stream chunk: {}", e);
+    }
+}
+
+/// Send `request` and emit the answer as it streams in. Returns the whole
+/// answer.
+async fn read_stream(
+    app: &AppHandle,
+    stream_id: &str,
+    provider: &str,
+    format: StreamFormat,
+    request: RequestBuilder,
+) -> Result<String, String> {
+    let mut res = request.send().await.map_err(|e| e.to_string())?;
+    if !res.status().is_success() {
+        let error_text = res.text().await.unwrap_or_default();
+        return Err(format!(
+            "{} Error: {}",
+            provider_label(provider),
+            error_text
+        ));
+    }
+
+    let mut splitter = EventSplitter::new(format);
+    let mut answer = String::new();
+    let handle = |payload: String, answer: &mut String| -> Result<(), String> {
+        // Keep-alive and other non-JSON lines are skipped.
+        let Ok(event) = serde_json::from_str::<Value>(&payload) else {
+            return Ok(());
+        };
+        if let Some(delta) = format.delta(&event)? {
+            answer.push_str(&delta);
+            emit_chunk(app, stream_id, delta, false);
+        }
+        Ok(())
+    };
+    while let Some(bytes) = res.chunk().await.map_err(|e| e.to_string())? {
+        for payload in splitter.push(&bytes) {
+            handle(payload, &mut answer)?;
+        }
+    }
+    for payload in splitter.finish() {
+        handle(payload, &mut answer)?;
+    }
+    Ok(answer)
+}
+
+/// Stream one completion under `stream_id`, cancellable until it ends.
+async fn run_stream(
+    app: AppHandle,
+    state: State<'_, AiStreamState>,
+    stream_id: String,
+    provider: String,
+    model: String,
+    system_prompt: String,
+    prompt: String,
+) -> Result<String, String> {
+    let format = StreamFormat::for_provider(&provider)
+        .ok_or_else(|| format!("Unsupported provider: {}", provider))?;
+    let app_config = config::load_config_internal(&app);
+    let ollama_port = app_config.ai_ollama_port.unwrap_or(11434);
+    let request = stream_request(
+        &Client::new(),
+        &app_config,
+        &provider,
+        &model,
+        &system_prompt,
+        &prompt,
+        ollama_port,
+    )?;
+
+    let task_app = app.clone();
+    let task_stream_id = stream_id.clone();
+    let task = tokio::spawn(async move {
+        read_stream(&task_app, &task_stream_id, &provider, format, request).await
+    });
+    state
+        .handles
+        .lock()
+        .unwrap()
+        .insert(stream_id.clone(), task.abort_handle());
+
+    let result = task.await;
+    state.handles.lock().unwrap().remove(&stream_id);
+    emit_chunk(&app, &stream_id, String::new(), true);
+
+    match result {
+        Ok(Ok(answer)) => {
+            log::info!("AI stream {} finished using {}", stream_id, model);
+            Ok(clean_response(&answer))
+        }
+        Ok(Err(e)) => {
+            log::error!("AI stream {} failed: {}", stream_id, e);
+            Err(e)
+        }
+        Err(_) => {
+            log::warn!("AI stream {} was cancelled", stream_id);
+            Err("AI request cancelled".to_string())
+        }
+    }
+}
+
+/// `generate_ai_query`, streamed.
+#[tauri::command]
+pub async fn stream_ai_query(
+    app: AppHandle,
+    state: State<'_, AiStreamState>,
+    stream_id: String,
+    req: AiGenerateRequest,
+) -> Result<String, String> {
+    log::info!("Streaming AI query using provider: {}", req.provider);
+
+    let app_config = config::load_config_internal(&app);
+    let ollama_port = app_config.ai_ollama_port.unwrap_or(11434);
+    let model = resolve_model(&req.provider, &req.model, &app_config, ollama_port).await?;
+    let system_prompt = config::get_system_prompt(app.clone()).replace("{{SCHEMA}}", &req.schema);
+
+    run_stream(
+        app,
+        state,
+        stream_id,
+        req.provider,
+        model,
+        system_prompt,
+        req.prompt,
+    )
+    .await
+}
+
+/// `explain_ai_query`, streamed.
+#[tauri::command]
+pub async fn stream_explain_ai_query(
+    app: AppHandle,
+    state: State<'_, AiStreamState>,
+    stream_id: String,
+    req: AiExplainRequest,
+) -> Result<String, String> {
+    log::info!(
+        "Streaming query explanation using AI provider: {}",
+        req.provider
+    );
+
+    let app_config = config::load_config_internal(&app);
+    let ollama_port = app_config.ai_ollama_port.unwrap_or(11434);
+    let model = resolve_model(&req.provider, &req.model, &app_config, ollama_port).await?;
+    let system_prompt =
+        config::get_explain_prompt(app.clone()).replace("{{LANGUAGE}}", &req.language);
+
+    run_stream(
+        app,
+        state,
+        stream_id,
+        req.provider,
+        model,
+        system_prompt,
+        format!("Query:\n{}\n", req.query),
+    )
+    .await
+}
+
+/// Stop the stream `stream_id`. Returns whether it was still running.
+#[tauri::command]
+pub fn cancel_ai_stream(state: State<'_, AiStreamState>, stream_id: String) -> bool {
+    match state.handles.lock().unwrap().remove(&stream_id) {
+        Some(handle) => {
+            handle.abort();
+            true
+        }
+        None => false,
+    }
+}
@@ -0,0 +1,138 @@
+#[cfg(test)]
+mod tests {
+    use crate::ai_stream::{EventSplitter, StreamFormat};
+    use serde_json::json;
+
+    #[test]
+    fn maps_providers_to_stream_formats() {
+        assert_eq!(
+            StreamFormat::for_provider("openai"),
+            Some(StreamFormat::OpenAi)
+        );
+        assert_eq!(
+            StreamFormat::for_provider("openrouter"),
+            Some(StreamFormat::OpenAi)
+        );
+        assert_eq!(
+            StreamFormat::for_provider("custom-openai"),
+            Some(StreamFormat::OpenAi)
+        );
+        assert_eq!(
+            StreamFormat::for_provider("minimax"),
+            Some(StreamFormat::OpenAi)
+        );
+        assert_eq!(
+            StreamFormat::for_provider("anthropic"),
+            Some(StreamFormat::Anthropic)
+        );
+        assert_eq!(
+            StreamFormat::for_provider("ollama"),
+            Some(StreamFormat::Ollama)
+        );
+        assert_eq!(
+            StreamFormat::for_provider("gemini"),
+            Some(StreamFormat::Gemini)
+        );
+        assert_eq!(StreamFormat::for_provider("unknown"), None);
+    }
+
+    #[test]
+    fn splits_sse_across_chunk_boundaries() {
+        let mut splitter = EventSplitter::new(StreamFormat::OpenAi);
+        assert!(splitter.push(b"data: {\"a\":").is_empty());
+        let payloads = splitter.push(b"1}\n\ndata: {\"b\":2}\n\ndata: [DONE]\n\n");
+        assert_eq!(payloads, vec![r#"{"a":1}"#, r#"{"b":2}"#]);
+        assert!(splitter.finish().is_empty());
+    }
+
+    #[test]
+    fn skips_sse_event_and_comment_lines() {
+        let mut splitter = EventSplitter::new(StreamFormat::Anthropic);
+        let payloads = splitter
+            .push(b"event: content_block_delta\r\ndata: {\"type\":\"ping\"}\r\n\r\n: keep-alive\n");
+        assert_eq!(payloads, vec![r#"{"type":"ping"}"#]);
+    }
+
+    #[test]
+    fn splits_ndjson_and_flushes_last_line() {
+        let mut splitter = EventSplitter::new(StreamFormat::Ollama);
+        let payloads = splitter.push(b"{\"a\":1}\n\n{\"b\"");
+        assert_eq!(payloads, vec![r#"{"a":1}"#]);
+        assert!(splitter.push(b":2}").is_empty());
+        assert_eq!(splitter.finish(), vec![r#"{"b":2}"#]);
+    }
+
+    #[test]
+    fn extracts_openai_deltas() {
+        let event = json!({"choices": [{"delta": {"content": "SELECT"}}]});
+        assert_eq!(
+            StreamFormat::OpenAi.delta(&event).unwrap().as_deref(),
+            Some("SELECT")
+        );
+        let role_only = json!({"choices": [{"delta": {"role": "assistant"}}]});
+        assert_eq!(StreamFormat::OpenAi.delta(&role_only).unwrap(), None);
+        let empty = json!({"choices": [{"delta": {"content": ""}, "finish_reason": "stop"}]});
+        assert_eq!(StreamFormat::OpenAi.delta(&empty).unwrap(), None);
+    }
+
+    #[test]
+    fn extracts_anthropic_text_deltas_only() {
+        let delta = json!({
+            "type": "content_block_delta",
+            "index": 0,
+            "delta": {"type": "text_delta", "text": " FROM"}
+        });
+        assert_eq!(
+            StreamFormat::Anthropic.delta(&delta).unwrap().as_deref(),
+            Some(" FROM")
+        );
+        let start = json!({"type": "message_start", "message": {"content": []}});
+        assert_eq!(StreamFormat::Anthropic.delta(&start).unwrap(), None);
+    }
+
+    #[test]
+    fn extracts_ollama_deltas() {
+        let event = json!({"message": {"role": "assistant", "content": "users"}, "done": false});
+        assert_eq!(
+            StreamFormat::Ollama.delta(&event).unwrap().as_deref(),
+            Some("users")
+        );
+        let last = json!({"message": {"role": "assistant", "content": ""}, "done": true});
+        assert_eq!(StreamFormat::Ollama.delta(&last).unwrap(), None);
+    }
+
+    #[test]
+    fn extracts_gemini_deltas() {
+        let event = json!({
+            "candidates": [{"content": {"role": "model", "parts": [{"text": "WHERE"}]}}]
+        });
+        assert_eq!(
+            StreamFormat::Gemini.delta(&event).unwrap().as_deref(),
+            Some("WHERE")
+        );
+        let usage_only = json!({"usageMetadata": {"totalTokenCount": 12}});
+        assert_eq!(StreamFormat::Gemini.delta(&usage_only).unwrap(), None);
+    }
+
+    #[test]
+    fn fails_on_error_events() {
+        let anthropic = json!({
+            "type": "error",
+            "error": {"type": "overloaded_error", "message": "Overloaded"}
+        });
+        assert_eq!(
+            StreamFormat::Anthropic.delta(&anthropic).unwrap_err(),
+            "Overloaded"
+        );
+        let openai = json!({"error": {"message": "Rate limit reached"}});
+        assert_eq!(
+            StreamFormat::OpenAi.delta(&openai).unwrap_err(),
+            "Rate limit reached"
+        );
+        let ollama = json!({"error": "model not found"});
+        assert_eq!(
+            StreamFormat::Ollama.delta(&ollama).unwrap_err(),
+            "model not found"
+        );
+    }
+}
@@ -0,0 +1,478 @@
+//! Tool calling for AI query generation.
+//!
+//! `generate_ai_query` pastes a schema string into the prompt. The tool
+//! variant instead lets the model inspect the connection itself: it is
+//! offered three read-only tools (`list_tables`, `get_columns`,
+//! `run_select_limited`), and each call it makes is run here and sent back
+//! until the model answers with its query. Only OpenAI-compatible providers
+//! and Anthropic support tool calls.
+
+use std::sync::Arc;
+
+use reqwest::{Client, RequestBuilder};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use tauri::AppHandle;
+
+use crate::ai::{build_api_url, clean_response, resolve_model};
+use crate::ai_stream::provider_label;
+use crate::commands::{
+    driver_for, expand_ssh_connection_params, find_connection_by_id,
+    resolve_connection_params_with_id,
+};
+use crate::config;
+use crate::drivers::driver_trait::DatabaseDriver;
+use crate::models::ConnectionParams;
+
+/// Model round trips before the assistant gives up.
+pub const MAX_TOOL_TURNS: usize = 8;
+
+/// Rows `run_select_limited` returns to the model.
+pub const SAMPLE_ROW_LIMIT: u32 = 20;
+
+/// Stands in for `{{SCHEMA}}` when the caller sends no schema.
+const SCHEMA_VIA_TOOLS: &str = "Not provided. Use the list_tables and get_columns tools to \
+inspect the database, and run_select_limited to look at sample rows. Call a tool only when you \
+need it.";
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct AiToolQueryRequest {
+    pub provider: String,
+    pub model: String,
+    pub prompt: String,
+    pub connection_id: String,
+    /// Schema to open the conversation with; the tools fill in the rest.
+    #[serde(default)]
+    pub schema: Option<String>,
+}
+
+/// A tool call the model made, as sent by the provider.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ToolCallRequest {
+    pub id: String,
+    pub name: String,
+    pub arguments: Value,
+}
+
+/// What the model did with its turn.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Turn {
+    Answer(String),
+    Calls(Vec<ToolCallRequest>),
+}
+
+/// The tools the model may call.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SchemaTool {
+    ListTables {
+        schema: Option<String>,
+    },
+    GetColumns {
+        table: String,
+        schema: Option<String>,
+    },
+    RunSelectLimited {
+        query: String,
+    },
+}
+
+impl SchemaTool {
+    pub fn parse(name: &str, arguments: &Value) -> Result<Self, String> {
+        let text = |key: &str| {
+            arguments[key]
+                .as_str()
+                .map(str::trim)
+                .filter(|s| !s.is_empty())
+                .map(str::to_string)
+        };
+        let required =
+            |key: &str| text(key).ok_or_else(|| format!("{} requires \"{}\"", name, key));
+        match name {
+            "list_tables" => Ok(Self::ListTables {
+                schema: text("schema"),
+            }),
+            "get_columns" => Ok(Self::GetColumns {
+                table: required("table")?,
+                schema: text("schema"),
+            }),
+            "run_select_limited" => Ok(Self::RunSelectLimited {
+                query: required("query")?,
+            }),
+            _ => Err(format!("Unknown tool: {}", name)),
+        }
+    }
+}
+
+/// Name, description and JSON schema of the parameters of each tool.
+fn tool_specs() -> Vec<(&'static str, &'static str, Value)> {
+    let schema_param = json!({
+        "type": "string",
+        "description": "Schema or database to look in. Defaults to the connection's current one."
+    });
+    vec![
+        (
+            "list_tables",
+            "List the tables of the database.",
+            json!({
+                "type": "object",
+                "properties": {"schema": schema_param}
+            }),
+        ),
+        (
+            "get_columns",
+            "List the columns of a table with their types, nullability and primary key flags.",
+            json!({
+                "type": "object",
+                "properties": {
+                    "table": {"type": "string", "description": "Table name."},
+                    "schema": schema_param
+                },
+                "required": ["table"]
+            }),
+        ),
+        (
+            "run_select_limited",
+            "Run a read-only SELECT and return at most 20 rows, to check values or formats.",
+            json!({
+                "type": "object",
+                "properties": {
+                    "query": {
+                        "type": "string",
+                        "description": "A single read-only SELECT statement."
+                    }
+                },
+                "required": ["query"]
+            }),
+        ),
+    ]
+}
+
+/// How a provider declares tools and frames tool calls.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ToolDialect {
+    /// Chat completions `tools` / `tool_calls`.
+    OpenAi,
+    /// Messages API `tool_use` / `tool_result` blocks.
+    Anthropic,
+}
+
+impl ToolDialect {
+    pub fn for_provider(provider: &str) -> Option<Self> {
+        match provider {
+            "openai" | "openrouter" | "custom-openai" | "minimax" | "ollama" => Some(Self::OpenAi),
+            "anthropic" => Some(Self::Anthropic),
+            _ => None,
+        }
+    }
+
+    pub fn tools(self) -> Value {
+        let tools = tool_specs()
+            .into_iter()
+            .map(|(name, description, parameters)| match self {
+                Self::OpenAi => json!({
+                    "type": "function",
+                    "function": {
+                        "name": name,
+                        "description": description,
+                        "parameters": parameters
+                    }
+                }),
+                Self::Anthropic => json!({
+                    "name": name,
+                    "description": description,
+                    "input_schema": parameters
+                }),
+            });
+        Value::Array(tools.collect())
+    }
+
+    /// The request body for the next turn of `messages`.
+    pub fn request_body(self, model: &str, system_prompt: &str, messages: &[Value]) -> Value {
+        match self {
+            Self::OpenAi => {
+                let mut all = vec![json!({"role": "system", "content": system_prompt})];
+                all.extend_from_slice(messages);
+                json!({
+                    "model": model,
+                    "messages": all,
+                    "tools": self.tools(),
+                    "temperature": 0.0
+                })
+            }
+            Self::Anthropic => json!({
+                "model": model,
+                "system": system_prompt,
+                "messages": messages,
+                "tools": self.tools(),
+                "max_tokens": 1024,
+                "temperature": 0.0
+            }),
+        }
+    }
+
+    /// Read one model response: the turn it took and the assistant message
+    /// to keep in the conversation.
+    pub fn parse_response(self, response: &Value) -> Result<(Turn, Value), String> {
+        match self {
+            Self::OpenAi => {
+                let message = &response["choices"][0]["message"];
+                if !message.is_object() {
+                    return Err("Invalid response format: missing message".to_string());
+                }
+                let calls: Vec<ToolCallRequest> = message["tool_calls"]
+                    .as_array()
+                    .map(|calls| {
+                        calls
+                            .iter()
+                            .map(|call| ToolCallRequest {
+                                id: call["id"].as_str().unwrap_or_default().to_string(),
+                                name: call["function"]["name"]
+                                    .as_str()
+                                    .unwrap_or_default()
+                                    .to_string(),
+                                // Arguments arrive as a JSON-encoded string.
+                                arguments: call["function"]["arguments"]
+                                    .as_str()
+                                    .and_then(|a| serde_json::from_str(a).ok())
+                                    .unwrap_or_else(|| json!({})),
+                            })
+                            .collect()
+                    })
+                    .unwrap_or_default();
+                let turn = if calls.is_empty() {
+                    let text = message["content"]
+                        .as_str()
+                        .ok_or("Invalid response format: missing content")?;
+                    Turn::Answer(text.to_string())
+                } else {
+                    Turn::Calls(calls)
+                };
+                Ok((turn, message.clone()))
+            }
+            Self::Anthropic => {
+                let content = response["content"]
+                    .as_array()
+                    .ok_or("Invalid response format: missing content")?;
+                let calls: Vec<ToolCallRequest> = content
+                    .iter()
+                    .filter(|block| block["type"] == "tool_use")
+                    .map(|block| ToolCallRequest {
+                        id: block["id"].as_str().unwrap_or_default().to_string(),
+                        name: block["name"].as_str().unwrap_or_default().to_string(),
+                        arguments: block["input"].clone(),
+                    })
+                    .collect();
+                let turn = if calls.is_empty() {
+                    let text: String = content
+                        .iter()
+                        .filter(|block| block["type"] == "text")
+                        .filter_map(|block| block["text"].as_str())
+                        .collect();
+                    Turn::Answer(text)
+                } else {
+                    Turn::Calls(calls)
+                };
+                Ok((turn, json!({"role": "assistant", "content": content})))
+            }
+        }
+    }
+
+    /// The messages that hand tool outputs back to the model.
+    pub fn tool_result_messages(self, results: &[(ToolCallRequest, String)]) -> Vec<Value> {
+        match self {
+            Self::OpenAi => results
+                .iter()
+                .map(|(call, output)| {
+                    json!({"role": "tool", "tool_call_id": call.id, "content": output})
+                })
+                .collect(),
+            Self::Anthropic => {
+                let blocks: Vec<Value> = results
+                    .iter()
+                    .map(|(call, output)| {
+                        json!({"type": "tool_result", "tool_use_id": call.id, "content": output})
+                    })
+                    .collect();
+                vec![json!({"role": "user", "content": blocks})]
+            }
+        }
+    }
+}
+
+/// The connection the tools run against.
+struct ToolContext {
+    drv: Arc<dyn DatabaseDriver>,
+    params: ConnectionParams,
+}
+
+impl ToolContext {
+    async fn run(&self, tool: SchemaTool) -> Result<Value, String> {
+        match tool {
+            SchemaTool::ListTables { schema } => {
+                let tables = self.drv.get_tables(&self.params, schema.as_deref()).await?;
+                Ok(json!(tables.iter().map(|t| &t.name).collect::<Vec<_>>()))
+            }
+            SchemaTool::GetColumns { table, schema } => {
+                let columns = self
+                    .drv
+                    .get_columns(&self.params, &table, schema.as_deref())
+                    .await?;
+                serde_json::to_value(columns).map_err(|e| e.to_string())
+            }
+            SchemaTool::RunSelectLimited { query } => {
+                if !crate::read_only::is_read_only_query(&self.params.driver, &query) {
+                    return Err("Only read-only SELECT statements can be run".to_string());
+                }
+                let mut result = self
+                    .drv
+                    .execute_query(&self.params, &query, Some(SAMPLE_ROW_LIMIT), 1, None)
+                    .await?;
+                result.rows.truncate(SAMPLE_ROW_LIMIT as usize);
+                Ok(json!({"columns": result.columns, "rows": result.rows}))
+            }
+        }
+    }
+
+    /// Run `call` and render its outcome for the model. Failures are
+    /// reported to the model, which can correct itself, rather than ending
+    /// the conversation.
+    async fn output(&self, call: &ToolCallRequest) -> String {
+        log::info!("AI tool call: {} {}", call.name, call.arguments);
+        let outcome = match SchemaTool::parse(&call.name, &call.arguments) {
+            Ok(tool) => self.run(tool).await,
+            Err(e) => Err(e),
+        };
+        match outcome {
+            Ok(value) => value.to_string(),
+            Err(e) => json!({ "error": e }).to_string(),
+        }
+    }
+}
+
+/// The chat endpoint of `provider`, with its authentication headers.
+fn tool_request(
+    client: &Client,
+    app_config: &config::AppConfig,
+    provider: &str,
+    ollama_port: u16,
+) -> Result<RequestBuilder, String> {
+    let api_key = if provider != "ollama" {
+        config::get_ai_api_key(provider)?
+    } else {
+        String::new()
+    };
+    let request = match provider {
+        "openai" => client
+            .post("https://api.openai.com/v1/chat/completions")
+            .header("Authorization", format!("Bearer {}", api_key)),
+        "openrouter" => client
+            .post("https://openrouter.ai/api/v1/chat/completions")
+            .header("Authorization", format!("Bearer {}", api_key))
+            .header("HTTP-Referer", "https://github.com/TabularisDB/tabularis")
+            .header("X-Title", "Tabularis"),
+        "custom-openai" => {
+            let base_url = app_config
+                .ai_custom_openai_url
+                .as_ref()
+                .filter(|u| !u.is_empty())
+                .ok_or("Custom OpenAI URL not configured.")?;
+            client
+                .post(build_api_url(base_url, "/chat/completions"))
+                .header("Authorization", format!("Bearer {}", api_key))
+        }
+        "minimax" => client
+            .post("https://api.minimax.io/v1/chat/completions")
+            .header("Authorization", format!("Bearer {}", api_key)),
+        // Ollama's OpenAI-compatible endpoint accepts `tools`.
+        "ollama" => client.post(format!(
+            "http://localhost:{}/v1/chat/completions",
+            ollama_port
+        )),
+        "anthropic" => client
+            .post("https://api.anthropic.com/v1/messages")
+            .header("x-api-key", api_key)
+            .header("anthropic-version", "2023-06-01"),
+        _ => return Err(format!("Unsupported provider: {}", provider)),
+    };
+    Ok(request)
+}
+
+/// Generate a query for `req.prompt`, letting the model inspect
+/// `req.connection_id` through the schema tools.
+#[tauri::command]
+pub async fn generate_ai_query_with_tools(
+    app: AppHandle,
+    mut req: AiToolQueryRequest,
+) -> Result<String, String> {
+    log::info!(
+        "Generating AI query with tools using provider: {}",
+        req.provider
+    );
+    let dialect = ToolDialect::for_provider(&req.provider).ok_or_else(|| {
+        format!(
+            "Tool calling is not supported for provider {}",
+            req.provider
+        )
+    })?;
+
+    let saved_conn = find_connection_by_id(&app, &req.connection_id)?;
+    let expanded_params = expand_ssh_connection_params(&app, &saved_conn.params).await?;
+    let params = resolve_connection_params_with_id(&expanded_params, &req.connection_id)?;
+    let context = ToolContext {
+        drv: driver_for(&saved_conn.params.driver).await?,
+        params,
+    };
+
+    let app_config = config::load_config_internal(&app);
+    let ollama_port = app_config.ai_ollama_port.unwrap_or(11434);
+    req.model = resolve_model(&req.provider, &req.model, &app_config, ollama_port).await?;
+
+    let schema = req
+        .schema
+        .as_deref()
+        .filter(|s| !s.trim().is_empty())
+        .unwrap_or(SCHEMA_VIA_TOOLS);
+    let system_prompt = config::get_system_prompt(app).replace("{{SCHEMA}}", schema);
+
+    let client = Client::new();
+    let mut messages = vec![json!({"role": "user", "content": req.prompt})];
+    for _ in 0..MAX_TOOL_TURNS {
+        let body = dialect.request_body(&req.model, &system_prompt, &messages);
+        let res = tool_request(&client, &app_config, &req.provider, ollama_port)?
+            .json(&body)
+            .send()
+            .await
+            .map_err(|e| e.to_string())?;
+        if !res.status().is_success() {
+            let error_text = res.text().await.unwrap_or_default();
+            return Err(format!(
+                "{} Error: {}",
+                provider_label(&req.provider),
+                error_text
+            ));
+        }
+        let response: Value = res.json().await.map_err(|e| e.to_string())?;
+
+        let (turn, assistant_message) = dialect.parse_response(&response)?;
+        messages.push(assistant_message);
+        match turn {
+            Turn::Answer(text) => {
+                log::info!("AI query generated with tools using {}", req.model);
+                return Ok(clean_response(&text));
+            }
+            Turn::Calls(calls) => {
+                let mut results = Vec::with_capacity(calls.len());
+                for call in calls {
+                    let output = context.output(&call).await;
+                    results.push((call, output));
+                }
+                messages.extend(dialect.tool_result_messages(&results));
+            }
+        }
+    }
+    Err(format!(
+        "The model did not produce a query within {} turns",
+        MAX_TOOL_TURNS
+    ))
+}
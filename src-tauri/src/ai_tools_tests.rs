@@ -0,0 +1,154 @@
+#[cfg(test)]
+mod tests {
+    use crate::ai_tools::{SchemaTool, ToolCallRequest, ToolDialect, Turn};
+    use serde_json::json;
+
+    fn call(id: &str, name: &str) -> ToolCallRequest {
+        ToolCallRequest {
+            id: id.to_string(),
+            name: name.to_string(),
+            arguments: json!({}),
+        }
+    }
+
+    #[test]
+    fn maps_providers_to_dialects() {
+        assert_eq!(
+            ToolDialect::for_provider("openrouter"),
+            Some(ToolDialect::OpenAi)
+        );
+        assert_eq!(
+            ToolDialect::for_provider("ollama"),
+            Some(ToolDialect::OpenAi)
+        );
+        assert_eq!(
+            ToolDialect::for_provider("anthropic"),
+            Some(ToolDialect::Anthropic)
+        );
+        assert_eq!(ToolDialect::for_provider("gemini"), None);
+    }
+
+    #[test]
+    fn parses_tool_arguments() {
+        assert_eq!(
+            SchemaTool::parse("get_columns", &json!({"table": " users ", "schema": ""})).unwrap(),
+            SchemaTool::GetColumns {
+                table: "users".to_string(),
+                schema: None
+            }
+        );
+        assert_eq!(
+            SchemaTool::parse("list_tables", &json!({})).unwrap(),
+            SchemaTool::ListTables { schema: None }
+        );
+        let err = SchemaTool::parse("run_select_limited", &json!({})).unwrap_err();
+        assert!(err.contains("\"query\""));
+        assert!(SchemaTool::parse("drop_table", &json!({})).is_err());
+    }
+
+    #[test]
+    fn declares_every_tool_in_both_dialects() {
+        let openai = ToolDialect::OpenAi.tools();
+        let names: Vec<&str> = openai
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|t| t["function"]["name"].as_str().unwrap())
+            .collect();
+        assert_eq!(names, ["list_tables", "get_columns", "run_select_limited"]);
+
+        let anthropic = ToolDialect::Anthropic.tools();
+        assert_eq!(anthropic[1]["name"], "get_columns");
+        assert_eq!(anthropic[1]["input_schema"]["required"], json!(["table"]));
+    }
+
+    #[test]
+    fn openai_body_leads_with_the_system_prompt() {
+        let body = ToolDialect::OpenAi.request_body(
+            "gpt-4o",
+            "Be brief.",
+            &[json!({"role": "user", "content": "count users"})],
+        );
+        assert_eq!(body["messages"][0]["role"], "system");
+        assert_eq!(body["messages"][1]["content"], "count users");
+        assert_eq!(body["tools"].as_array().unwrap().len(), 3);
+
+        let body = ToolDialect::Anthropic.request_body("claude", "Be brief.", &[]);
+        assert_eq!(body["system"], "Be brief.");
+        assert!(body["messages"].as_array().unwrap().is_empty());
+    }
+
+    #[test]
+    fn parses_openai_tool_calls_and_answers() {
+        let response = json!({"choices": [{"message": {
+            "role": "assistant",
+            "content": null,
+            "tool_calls": [{
+                "id": "call_1",
+                "type": "function",
+                "function": {"name": "get_columns", "arguments": "{\"table\":\"orders\"}"}
+            }]
+        }}]});
+        let (turn, assistant) = ToolDialect::OpenAi.parse_response(&response).unwrap();
+        assert_eq!(
+            turn,
+            Turn::Calls(vec![ToolCallRequest {
+                id: "call_1".to_string(),
+                name: "get_columns".to_string(),
+                arguments: json!({"table": "orders"}),
+            }])
+        );
+        assert_eq!(assistant["tool_calls"][0]["id"], "call_1");
+
+        let answer =
+            json!({"choices": [{"message": {"role": "assistant", "content": "SELECT 1"}}]});
+        let (turn, _) = ToolDialect::OpenAi.parse_response(&answer).unwrap();
+        assert_eq!(turn, Turn::Answer("SELECT 1".to_string()));
+
+        assert!(ToolDialect::OpenAi
+            .parse_response(&json!({"choices": []}))
+            .is_err());
+    }
+
+    #[test]
+    fn parses_anthropic_tool_use_and_answers() {
+        let response = json!({
+            "stop_reason": "tool_use",
+            "content": [
+                {"type": "text", "text": "Let me look."},
+                {"type": "tool_use", "id": "toolu_1", "name": "list_tables", "input": {}}
+            ]
+        });
+        let (turn, assistant) = ToolDialect::Anthropic.parse_response(&response).unwrap();
+        assert_eq!(turn, Turn::Calls(vec![call("toolu_1", "list_tables")]));
+        assert_eq!(assistant["role"], "assistant");
+        assert_eq!(assistant["content"][1]["id"], "toolu_1");
+
+        let answer = json!({"content": [{"type": "text", "text": "SELECT * FROM users"}]});
+        let (turn, _) = ToolDialect::Anthropic.parse_response(&answer).unwrap();
+        assert_eq!(turn, Turn::Answer("SELECT * FROM users".to_string()));
+    }
+
+    #[test]
+    fn builds_tool_result_messages() {
+        let results = vec![
+            (call("a", "list_tables"), "[\"users\"]".to_string()),
+            (
+                call("b", "get_columns"),
+                "{\"error\":\"no table\"}".to_string(),
+            ),
+        ];
+
+        let openai = ToolDialect::OpenAi.tool_result_messages(&results);
+        assert_eq!(openai.len(), 2);
+        assert_eq!(openai[0]["role"], "tool");
+        assert_eq!(openai[1]["tool_call_id"], "b");
+
+        // Anthropic wants every result of a turn in one user message.
+        let anthropic = ToolDialect::Anthropic.tool_result_messages(&results);
+        assert_eq!(anthropic.len(), 1);
+        assert_eq!(anthropic[0]["role"], "user");
+        assert_eq!(anthropic[0]["content"][0]["type"], "tool_result");
+        assert_eq!(anthropic[0]["content"][1]["tool_use_id"], "b");
+    }
+}
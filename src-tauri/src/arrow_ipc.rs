@@ -0,0 +1,144 @@
+//! Arrow encoding of query results.
+//!
+//! Rows come out of the drivers as JSON values; [`infer_type`] and
+//! [`column_array`] turn them into typed Arrow columns (also used by the
+//! Parquet export). [`result_to_ipc`] writes a whole `QueryResult` as an
+//! Arrow IPC stream: one record batch, with `truncated` and `pagination`
+//! kept in the schema metadata so the frontend can page through it like a
+//! JSON result.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use arrow::array::{ArrayRef, BooleanArray, Float64Array, Int64Array, StringArray};
+use arrow::datatypes::{DataType, Field, Schema};
+use arrow::ipc::writer::StreamWriter;
+use arrow::record_batch::{RecordBatch, RecordBatchOptions};
+use serde_json::Value;
+
+use crate::models::QueryResult;
+
+pub const METADATA_TRUNCATED: &str = "tabularis.truncated";
+pub const METADATA_PAGINATION: &str = "tabularis.pagination";
+
+/// Column type for the values of one column: booleans, integers and floats
+/// keep their type (integers mixed with floats widen to float); anything
+/// else, including objects and arrays, is written as text. A column that is
+/// entirely NULL is text as well.
+pub fn infer_type<'a>(values: impl IntoIterator<Item = &'a Value>) -> DataType {
+    let mut inferred: Option<DataType> = None;
+    for value in values {
+        let ty = match value {
+            Value::Null => continue,
+            Value::Bool(_) => DataType::Boolean,
+            Value::Number(n) if n.is_i64() => DataType::Int64,
+            Value::Number(_) => DataType::Float64,
+            _ => return DataType::Utf8,
+        };
+        inferred = match (inferred, ty) {
+            (None, ty) => Some(ty),
+            (Some(a), b) if a == b => Some(a),
+            (Some(DataType::Int64), DataType::Float64)
+            | (Some(DataType::Float64), DataType::Int64) => Some(DataType::Float64),
+            _ => return DataType::Utf8,
+        };
+    }
+    inferred.unwrap_or(DataType::Utf8)
+}
+
+/// The `index`-th value of every row as an array of `field`'s type. Fails
+/// when a value does not fit the type, e.g. text in an integer column.
+pub fn column_array(field: &Field, rows: &[Vec<Value>], index: usize) -> Result<ArrayRef, String> {
+    let values = rows
+        .iter()
+        .map(|row| row.get(index).unwrap_or(&Value::Null));
+    let mismatch = |value: &Value| {
+        format!(
+            "Column \"{}\" holds {} after values of type {}; Arrow columns cannot change type",
+            field.name(),
+            value,
+            field.data_type()
+        )
+    };
+    let array: ArrayRef = match field.data_type() {
+        DataType::Boolean => Arc::new(
+            values
+                .map(|v| match v {
+                    Value::Null => Ok(None),
+                    Value::Bool(b) => Ok(Some(*b)),
+                    other => Err(mismatch(other)),
+                })
+                .collect::<Result<BooleanArray, _>>()?,
+        ),
+        DataType::Int64 => Arc::new(
+            values
+                .map(|v| match v {
+                    Value::Null => Ok(None),
+                    other => other.as_i64().map(Some).ok_or_else(|| mismatch(other)),
+                })
+                .collect::<Result<Int64Array, _>>()?,
+        ),
+        DataType::Float64 => Arc::new(
+            values
+                .map(|v| match v {
+                    Value::Null => Ok(None),
+                    other => other.as_f64().map(Some).ok_or_else(|| mismatch(other)),
+                })
+                .collect::<Result<Float64Array, _>>()?,
+        ),
+        _ => Arc::new(
+            values
+                .map(|v| match v {
+                    Value::Null => None,
+                    Value::String(s) => Some(s.clone()),
+                    other => Some(other.to_string()),
+                })
+                .collect::<StringArray>(),
+        ),
+    };
+    Ok(array)
+}
+
+/// One record batch holding `rows`, with each column's type inferred from
+/// all of its values.
+pub fn record_batch(
+    columns: &[String],
+    rows: &[Vec<Value>],
+    metadata: HashMap<String, String>,
+) -> Result<RecordBatch, String> {
+    let fields: Vec<Field> = columns
+        .iter()
+        .enumerate()
+        .map(|(i, name)| {
+            let ty = infer_type(rows.iter().filter_map(|row| row.get(i)));
+            Field::new(name, ty, true)
+        })
+        .collect();
+    let schema = Arc::new(Schema::new_with_metadata(fields, metadata));
+    let arrays = schema
+        .fields()
+        .iter()
+        .enumerate()
+        .map(|(i, field)| column_array(field, rows, i))
+        .collect::<Result<Vec<_>, _>>()?;
+    let options = RecordBatchOptions::new().with_row_count(Some(rows.len()));
+    RecordBatch::try_new_with_options(schema, arrays, &options).map_err(|e| e.to_string())
+}
+
+/// `result` as an Arrow IPC stream.
+pub fn result_to_ipc(result: &QueryResult) -> Result<Vec<u8>, String> {
+    let mut metadata = HashMap::new();
+    metadata.insert(METADATA_TRUNCATED.to_string(), result.truncated.to_string());
+    if let Some(pagination) = &result.pagination {
+        metadata.insert(
+            METADATA_PAGINATION.to_string(),
+            serde_json::to_string(pagination).map_err(|e| e.to_string())?,
+        );
+    }
+    let batch = record_batch(&result.columns, &result.rows, metadata)?;
+
+    let mut writer =
+        StreamWriter::try_new(Vec::new(), &batch.schema()).map_err(|e| e.to_string())?;
+    writer.write(&batch).map_err(|e| e.to_string())?;
+    writer.into_inner().map_err(|e| e.to_string())
+}
@@ -0,0 +1,128 @@
+#[cfg(test)]
+mod tests {
+    use crate::arrow_ipc::{record_batch, result_to_ipc, METADATA_PAGINATION, METADATA_TRUNCATED};
+    use crate::models::{Pagination, QueryResult};
+    use arrow::array::{Array, BooleanArray, Float64Array, Int64Array, StringArray};
+    use arrow::datatypes::DataType;
+    use arrow::ipc::reader::StreamReader;
+    use serde_json::{json, Value};
+    use std::collections::HashMap;
+
+    fn result(rows: Vec<Vec<Value>>) -> QueryResult {
+        QueryResult {
+            columns: vec![
+                "id".to_string(),
+                "price".to_string(),
+                "active".to_string(),
+                "name".to_string(),
+            ],
+            rows,
+            affected_rows: 0,
+            truncated: false,
+            truncation: None,
+            pagination: None,
+            style_hints: None,
+            provenance: None,
+        }
+    }
+
+    #[test]
+    fn infers_column_types() {
+        let batch = record_batch(
+            &result(vec![]).columns,
+            &[
+                vec![json!(1), json!(2), json!(true), json!("a")],
+                vec![json!(2), json!(2.5), Value::Null, json!({"k": 1})],
+            ],
+            HashMap::new(),
+        )
+        .unwrap();
+        let types: Vec<DataType> = batch
+            .schema()
+            .fields()
+            .iter()
+            .map(|f| f.data_type().clone())
+            .collect();
+        assert_eq!(
+            types,
+            vec![
+                DataType::Int64,
+                DataType::Float64,
+                DataType::Boolean,
+                DataType::Utf8
+            ]
+        );
+        let active = batch
+            .column(2)
+            .as_any()
+            .downcast_ref::<BooleanArray>()
+            .unwrap();
+        assert!(active.is_null(1));
+        let name = batch
+            .column(3)
+            .as_any()
+            .downcast_ref::<StringArray>()
+            .unwrap();
+        assert_eq!(name.value(1), r#"{"k":1}"#);
+    }
+
+    #[test]
+    fn rejects_value_that_does_not_fit_column() {
+        let rows = vec![vec![json!(true)], vec![json!(1)]];
+        let field = arrow::datatypes::Field::new("flag", DataType::Boolean, true);
+        let err = crate::arrow_ipc::column_array(&field, &rows, 0).unwrap_err();
+        assert!(err.contains("\"flag\""));
+    }
+
+    #[test]
+    fn round_trips_result_through_ipc_stream() {
+        let mut result = result(vec![
+            vec![json!(1), json!(9.5), json!(true), json!("one")],
+            vec![json!(2), Value::Null, json!(false), Value::Null],
+        ]);
+        result.truncated = true;
+        result.pagination = Some(Pagination {
+            page: 2,
+            page_size: 100,
+            total_rows: Some(250),
+            has_more: true,
+        });
+
+        let bytes = result_to_ipc(&result).unwrap();
+        let reader = StreamReader::try_new(bytes.as_slice(), None).unwrap();
+        let schema = reader.schema();
+        assert_eq!(schema.metadata()[METADATA_TRUNCATED], "true");
+        let pagination: Value =
+            serde_json::from_str(&schema.metadata()[METADATA_PAGINATION]).unwrap();
+        assert_eq!(pagination["page"], 2);
+        assert_eq!(pagination["total_rows"], 250);
+
+        let batches: Vec<_> = reader.map(|b| b.unwrap()).collect();
+        assert_eq!(batches.len(), 1);
+        let batch = &batches[0];
+        assert_eq!(batch.num_rows(), 2);
+        let ids = batch
+            .column(0)
+            .as_any()
+            .downcast_ref::<Int64Array>()
+            .unwrap();
+        assert_eq!(ids.values(), &[1, 2]);
+        let prices = batch
+            .column(1)
+            .as_any()
+            .downcast_ref::<Float64Array>()
+            .unwrap();
+        assert_eq!(prices.value(0), 9.5);
+        assert!(prices.is_null(1));
+    }
+
+    #[test]
+    fn encodes_empty_result() {
+        let bytes = result_to_ipc(&result(vec![])).unwrap();
+        let reader = StreamReader::try_new(bytes.as_slice(), None).unwrap();
+        assert_eq!(reader.schema().fields().len(), 4);
+        assert!(!reader.schema().metadata().contains_key(METADATA_PAGINATION));
+        let rows: usize = reader.map(|b| b.unwrap().num_rows()).sum();
+        assert_eq!(rows, 0);
+    }
+}
@@ -0,0 +1,427 @@
+//! Local automation API.
+//!
+//! The running app listens on a per-profile local socket (a Unix domain
+//! socket in a private directory under the config directory, a named pipe
+//! on Windows). Invoking the binary with a subcommand (`tabularis
+//! run-saved-query "Daily sales" --export csv`) sends the request there
+//! instead of opening a window, so shell scripts can drive the app the user
+//! already has open.
+//!
+//! The wire format is one JSON object per line in each direction: an
+//! [`AutomationRequest`] from the client, an [`AutomationResponse`] back.
+
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tauri::{AppHandle, Manager, Runtime};
+use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncWrite, AsyncWriteExt, BufReader};
+
+use crate::commands::{find_connection_by_id, get_config_path, QueryCancellationState};
+use crate::dump_commands::{DumpCancellationState, DumpOptions};
+use crate::export::ExportCancellationState;
+
+#[derive(clap::Subcommand, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "command", rename_all = "snake_case")]
+pub enum AutomationRequest {
+    /// Check that Tabularis is running.
+    Ping,
+    /// Run a saved query and print its rows as JSON, or export them.
+    RunSavedQuery {
+        /// Name (or id) of the saved query.
+        name: String,
+        /// Connection the saved query belongs to, by name or id, when
+        /// several connections have a query with that name.
+        #[arg(long)]
+        connection: Option<String>,
+        /// Export format (`csv` or `json`) instead of printing the result.
+        #[arg(long, value_name = "FORMAT")]
+        export: Option<String>,
+        /// File to export to; the export is printed when omitted.
+        #[arg(long, short, value_name = "FILE")]
+        output: Option<String>,
+    },
+    /// Export the result of a query.
+    Export {
+        /// Connection to query, by name or id.
+        #[arg(long)]
+        connection: String,
+        #[arg(long)]
+        query: String,
+        /// `csv` or `json`.
+        #[arg(long, default_value = "csv")]
+        format: String,
+        /// File to export to; the export is printed when omitted.
+        #[arg(long, short, value_name = "FILE")]
+        output: Option<String>,
+    },
+    /// Write a SQL dump (structure and data) of a connection.
+    Backup {
+        /// Connection to back up, by name or id.
+        #[arg(long)]
+        connection: String,
+        #[arg(long, short, value_name = "FILE")]
+        output: String,
+        /// PostgreSQL schema to dump (defaults to `public`).
+        #[arg(long)]
+        schema: Option<String>,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AutomationResponse {
+    pub ok: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub result: Option<Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl From<Result<Value, String>> for AutomationResponse {
+    fn from(result: Result<Value, String>) -> Self {
+        match result {
+            Ok(value) => Self {
+                ok: true,
+                result: Some(value),
+                error: None,
+            },
+            Err(e) => Self {
+                ok: false,
+                result: None,
+                error: Some(e),
+            },
+        }
+    }
+}
+
+impl AutomationRequest {
+    /// Resolve relative output paths against `cwd`: the running app has
+    /// its own working directory, not the caller's.
+    pub fn with_absolute_paths(mut self, cwd: &Path) -> Self {
+        let output = match &mut self {
+            AutomationRequest::RunSavedQuery { output, .. }
+            | AutomationRequest::Export { output, .. } => output.as_mut(),
+            AutomationRequest::Backup { output, .. } => Some(output),
+            AutomationRequest::Ping => None,
+        };
+        if let Some(output) = output {
+            *output = cwd.join(&*output).to_string_lossy().to_string();
+        }
+        self
+    }
+}
+
+/// Exit code when no running instance answers.
+pub const EXIT_NOT_RUNNING: i32 = 2;
+
+/// Where the running app listens for the active profile. The directory is
+/// created with mode 0700 before the socket is bound.
+#[cfg(unix)]
+pub fn socket_path() -> PathBuf {
+    crate::paths::get_app_config_dir()
+        .join("automation")
+        .join("automation.sock")
+}
+
+#[cfg(windows)]
+pub fn socket_path() -> PathBuf {
+    PathBuf::from(format!(
+        r"\\.\pipe\tabularis-{}-automation",
+        crate::paths::get_active_profile()
+    ))
+}
+
+/// Run one request against the running app.
+pub async fn dispatch_command<R: Runtime>(
+    app: &AppHandle<R>,
+    request: AutomationRequest,
+) -> Result<Value, String> {
+    log::info!("Automation request: {:?}", request);
+    match request {
+        AutomationRequest::Ping => Ok(Value::String("pong".into())),
+        AutomationRequest::RunSavedQuery {
+            name,
+            connection,
+            export,
+            output,
+        } => {
+            let connection_id = match connection.as_deref() {
+                Some(connection) => Some(resolve_connection(app, connection)?),
+                None => None,
+            };
+            let saved =
+                crate::saved_queries::find_saved_query(app, &name, connection_id.as_deref())?;
+            match export {
+                Some(format) => {
+                    export_query(app, saved.connection_id, saved.sql, format, output).await
+                }
+                None => {
+                    let result = crate::commands::execute_query_in_scope(
+                        app.clone(),
+                        crate::window_state::BACKGROUND_TASKS,
+                        app.state::<QueryCancellationState>(),
+                        saved.connection_id,
+                        saved.sql,
+                        None,
+                        None,
+                        None,
+                        None,
+                        None,
+                        None,
+                        Some(saved.id),
+                    )
+                    .await?;
+                    serde_json::to_value(result).map_err(|e| e.to_string())
+                }
+            }
+        }
+        AutomationRequest::Export {
+            connection,
+            query,
+            format,
+            output,
+        } => {
+            let connection_id = resolve_connection(app, &connection)?;
+            export_query(app, connection_id, query, format, output).await
+        }
+        AutomationRequest::Backup {
+            connection,
+            output,
+            schema,
+        } => {
+            let connection_id = resolve_connection(app, &connection)?;
+            crate::dump_commands::dump_database_in_scope(
+                app.clone(),
+                crate::window_state::BACKGROUND_TASKS,
+                app.state::<DumpCancellationState>(),
+                connection_id,
+                output.clone(),
+                DumpOptions {
+                    structure: true,
+                    data: true,
+                    tables: None,
+                },
+                schema,
+            )
+            .await?;
+            Ok(Value::String(output))
+        }
+    }
+}
+
+/// Export through the regular export path. Without `output` the file is
+/// written to a temporary location and its content returned.
+async fn export_query<R: Runtime>(
+    app: &AppHandle<R>,
+    connection_id: String,
+    query: String,
+    format: String,
+    output: Option<String>,
+) -> Result<Value, String> {
+    let (path, temporary) = match output {
+        Some(path) => (PathBuf::from(path), false),
+        None => (
+            std::env::temp_dir().join(format!("tabularis-automation-{}", uuid::Uuid::new_v4())),
+            true,
+        ),
+    };
+    let result = crate::export::export_query_to_file_in_scope(
+        app.clone(),
+        crate::window_state::BACKGROUND_TASKS,
+        app.state::<ExportCancellationState>(),
+        connection_id,
+        query,
+        path.to_string_lossy().to_string(),
+        format,
+        None,
+        None,
+        None,
+        None,
+        None,
+    )
+    .await;
+
+    if !temporary {
+        return result.map(|_| Value::String(path.to_string_lossy().to_string()));
+    }
+    let content = result.and_then(|_| std::fs::read_to_string(&path).map_err(|e| e.to_string()));
+    let _ = std::fs::remove_file(&path);
+    content.map(Value::String)
+}
+
+/// Saved connection id for a connection given by id or by name.
+fn resolve_connection<R: Runtime>(app: &AppHandle<R>, connection: &str) -> Result<String, String> {
+    if find_connection_by_id(app, connection).is_ok() {
+        return Ok(connection.to_string());
+    }
+    let path = get_config_path(app)?;
+    let connections = crate::persistence::load_connections_file(&path)?.connections;
+    let ids: Vec<&str> = connections
+        .iter()
+        .filter(|c| c.name == connection)
+        .map(|c| c.id.as_str())
+        .collect();
+    match ids.as_slice() {
+        [id] => Ok(id.to_string()),
+        [] => Err(format!("Connection not found: {}", connection)),
+        _ => Err(format!(
+            "Several connections are named '{}'; use its id instead",
+            connection
+        )),
+    }
+}
+
+/// Answer requests on one client connection until it closes.
+async fn serve_client<R, S>(app: AppHandle<R>, stream: S)
+where
+    R: Runtime,
+    S: AsyncRead + AsyncWrite + Unpin + Send + 'static,
+{
+    let (reader, mut writer) = tokio::io::split(stream);
+    let mut lines = BufReader::new(reader).lines();
+    while let Ok(Some(line)) = lines.next_line().await {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let response: AutomationResponse = match serde_json::from_str(&line) {
+            Ok(request) => dispatch_command(&app, request).await.into(),
+            Err(e) => Err(format!("Invalid automation request: {}", e)).into(),
+        };
+        let mut payload = serde_json::to_string(&response).unwrap_or_default();
+        payload.push('\n');
+        if writer.write_all(payload.as_bytes()).await.is_err() {
+            break;
+        }
+    }
+}
+
+/// Start listening for automation requests. Does nothing when another
+/// instance of the same profile already listens.
+pub fn start_server<R: Runtime>(app: AppHandle<R>) {
+    tauri::async_runtime::spawn(async move {
+        if let Err(e) = listen(app).await {
+            log::warn!("Automation socket unavailable: {}", e);
+        }
+    });
+}
+
+#[cfg(unix)]
+async fn listen<R: Runtime>(app: AppHandle<R>) -> Result<(), String> {
+    use std::os::unix::fs::{DirBuilderExt, PermissionsExt};
+    use tokio::net::{UnixListener, UnixStream};
+
+    let path = socket_path();
+    // Only the current user may enter the socket's directory, so nobody can
+    // connect in the moment between `bind` and narrowing the socket's own
+    // permissions.
+    if let Some(dir) = path.parent() {
+        std::fs::DirBuilder::new()
+            .recursive(true)
+            .mode(0o700)
+            .create(dir)
+            .map_err(|e| e.to_string())?;
+        std::fs::set_permissions(dir, std::fs::Permissions::from_mode(0o700))
+            .map_err(|e| e.to_string())?;
+    }
+    if UnixStream::connect(&path).await.is_ok() {
+        return Err(format!("{} is in use by another instance", path.display()));
+    }
+    // A socket file left behind by a crashed instance blocks `bind`.
+    let _ = std::fs::remove_file(&path);
+    let listener = UnixListener::bind(&path).map_err(|e| e.to_string())?;
+    // Only the current user may drive the app.
+    std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o600))
+        .map_err(|e| e.to_string())?;
+    log::info!("Automation socket listening at {}", path.display());
+
+    loop {
+        let (stream, _) = listener.accept().await.map_err(|e| e.to_string())?;
+        tauri::async_runtime::spawn(serve_client(app.clone(), stream));
+    }
+}
+
+#[cfg(windows)]
+async fn listen<R: Runtime>(app: AppHandle<R>) -> Result<(), String> {
+    use tokio::net::windows::named_pipe::ServerOptions;
+
+    let name = socket_path();
+    let mut server = ServerOptions::new()
+        .first_pipe_instance(true)
+        .reject_remote_clients(true)
+        .create(&name)
+        .map_err(|e| e.to_string())?;
+    log::info!("Automation pipe listening at {}", name.display());
+
+    loop {
+        server.connect().await.map_err(|e| e.to_string())?;
+        let client = server;
+        server = ServerOptions::new()
+            .reject_remote_clients(true)
+            .create(&name)
+            .map_err(|e| e.to_string())?;
+        tauri::async_runtime::spawn(serve_client(app.clone(), client));
+    }
+}
+
+/// Send `request` to the running app, print the result and return the
+/// process exit code.
+pub fn run_client(request: AutomationRequest) -> i32 {
+    let request = match std::env::current_dir() {
+        Ok(cwd) => request.with_absolute_paths(&cwd),
+        Err(_) => request,
+    };
+    let rt = tokio::runtime::Runtime::new().expect("Failed to create Tokio runtime");
+    match rt.block_on(send_request(&request)) {
+        Ok(response) if response.ok => {
+            match response.result {
+                Some(Value::String(text)) => println!("{}", text.trim_end_matches('\n')),
+                Some(value) => println!(
+                    "{}",
+                    serde_json::to_string_pretty(&value).unwrap_or_default()
+                ),
+                None => {}
+            }
+            0
+        }
+        Ok(response) => {
+            eprintln!("{}", response.error.unwrap_or_default());
+            1
+        }
+        Err(e) => {
+            eprintln!("Tabularis is not running ({}). Start the app first.", e);
+            EXIT_NOT_RUNNING
+        }
+    }
+}
+
+async fn send_request(request: &AutomationRequest) -> Result<AutomationResponse, String> {
+    let stream = connect().await?;
+    let (reader, mut writer) = tokio::io::split(stream);
+    let mut payload = serde_json::to_string(request).map_err(|e| e.to_string())?;
+    payload.push('\n');
+    writer
+        .write_all(payload.as_bytes())
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let mut line = String::new();
+    BufReader::new(reader)
+        .read_line(&mut line)
+        .await
+        .map_err(|e| e.to_string())?;
+    serde_json::from_str(&line).map_err(|e| format!("Invalid automation response: {}", e))
+}
+
+#[cfg(unix)]
+async fn connect() -> Result<tokio::net::UnixStream, String> {
+    tokio::net::UnixStream::connect(socket_path())
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[cfg(windows)]
+async fn connect() -> Result<tokio::net::windows::named_pipe::NamedPipeClient, String> {
+    tokio::net::windows::named_pipe::ClientOptions::new()
+        .open(socket_path())
+        .map_err(|e| e.to_string())
+}
@@ -0,0 +1,99 @@
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use clap::Parser;
+    use serde_json::json;
+
+    use crate::automation::{AutomationRequest, AutomationResponse};
+    use crate::cli::Args;
+
+    #[test]
+    fn parses_run_saved_query_subcommand() {
+        let args = Args::try_parse_from([
+            "tabularis",
+            "run-saved-query",
+            "Daily sales",
+            "--export",
+            "csv",
+        ])
+        .unwrap();
+        assert_eq!(
+            args.command,
+            Some(AutomationRequest::RunSavedQuery {
+                name: "Daily sales".into(),
+                connection: None,
+                export: Some("csv".into()),
+                output: None,
+            })
+        );
+    }
+
+    #[test]
+    fn launching_without_subcommand_starts_the_app() {
+        let args = Args::try_parse_from(["tabularis", "--debug"]).unwrap();
+        assert!(args.command.is_none());
+    }
+
+    #[test]
+    fn requests_are_tagged_by_command() {
+        let request = AutomationRequest::Backup {
+            connection: "prod".into(),
+            output: "/tmp/prod.sql".into(),
+            schema: None,
+        };
+        assert_eq!(
+            serde_json::to_value(&request).unwrap(),
+            json!({
+                "command": "backup",
+                "connection": "prod",
+                "output": "/tmp/prod.sql",
+                "schema": null
+            })
+        );
+        let parsed: AutomationRequest = serde_json::from_str(r#"{"command":"ping"}"#).unwrap();
+        assert_eq!(parsed, AutomationRequest::Ping);
+    }
+
+    #[test]
+    fn relative_outputs_resolve_against_the_caller() {
+        let cwd = Path::new("/home/me/reports");
+        let request = AutomationRequest::Export {
+            connection: "prod".into(),
+            query: "SELECT 1".into(),
+            format: "csv".into(),
+            output: Some("out.csv".into()),
+        }
+        .with_absolute_paths(cwd);
+        match request {
+            AutomationRequest::Export { output, .. } => {
+                assert_eq!(
+                    output.as_deref().map(Path::new),
+                    Some(cwd.join("out.csv").as_path())
+                )
+            }
+            other => panic!("unexpected request {:?}", other),
+        }
+
+        let absolute = AutomationRequest::Backup {
+            connection: "prod".into(),
+            output: "/var/backups/prod.sql".into(),
+            schema: None,
+        };
+        assert_eq!(absolute.clone().with_absolute_paths(cwd), absolute);
+    }
+
+    #[test]
+    fn response_carries_result_or_error() {
+        let ok: AutomationResponse = Ok(json!("pong")).into();
+        assert_eq!(
+            serde_json::to_value(&ok).unwrap(),
+            json!({ "ok": true, "result": "pong" })
+        );
+        let err: AutomationResponse = Err("Connection not found: prod".to_string()).into();
+        assert_eq!(
+            serde_json::to_value(&err).unwrap(),
+            json!({ "ok": false, "error": "Connection not found: prod" })
+        );
+    }
+}
@@ -0,0 +1,305 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use tauri::{AppHandle, Runtime};
+use uuid::Uuid;
+
+use crate::bulk_edit::{table_ref, BoundStatement};
+use crate::drivers::common::quote_identifier;
+use crate::models::QueryResult;
+
+/// A bookmarked row, identified by its table and primary key values.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RowBookmark {
+    pub id: String,
+    pub table: String,
+    #[serde(default)]
+    pub schema: Option<String>,
+    #[serde(default)]
+    pub database: Option<String>,
+    /// Primary key column → value. Composite keys carry one entry per column.
+    pub pk: serde_json::Map<String, serde_json::Value>,
+    #[serde(default)]
+    pub note: Option<String>,
+    /// CSS hex color used to highlight the row (e.g. `"#f97316"`).
+    #[serde(default)]
+    pub color: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+fn get_bookmarks_dir<R: Runtime>(app: &AppHandle<R>) -> Result<PathBuf, String> {
+    let config_dir = crate::paths::app_config_dir(app)?;
+    let bookmarks_dir = config_dir.join("bookmarks");
+    if !bookmarks_dir.exists() {
+        fs::create_dir_all(&bookmarks_dir).map_err(|e| e.to_string())?;
+    }
+    Ok(bookmarks_dir)
+}
+
+fn get_bookmarks_path<R: Runtime>(
+    app: &AppHandle<R>,
+    connection_id: &str,
+) -> Result<PathBuf, String> {
+    let dir = get_bookmarks_dir(app)?;
+    Ok(dir.join(format!("{}.json", connection_id)))
+}
+
+fn read_bookmarks<R: Runtime>(
+    app: &AppHandle<R>,
+    connection_id: &str,
+) -> Result<Vec<RowBookmark>, String> {
+    let path = get_bookmarks_path(app, connection_id)?;
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let content = fs::read_to_string(&path).map_err(|e| e.to_string())?;
+    serde_json::from_str(&content).map_err(|e| e.to_string())
+}
+
+fn write_bookmarks<R: Runtime>(
+    app: &AppHandle<R>,
+    connection_id: &str,
+    bookmarks: &[RowBookmark],
+) -> Result<(), String> {
+    let path = get_bookmarks_path(app, connection_id)?;
+    let content = serde_json::to_string_pretty(bookmarks).map_err(|e| e.to_string())?;
+    fs::write(path, content).map_err(|e| e.to_string())
+}
+
+/// Returns `true` when `bookmark` points at the same row as the given location.
+pub fn is_same_row(
+    bookmark: &RowBookmark,
+    table: &str,
+    schema: Option<&str>,
+    database: Option<&str>,
+    pk: &serde_json::Map<String, serde_json::Value>,
+) -> bool {
+    bookmark.table == table
+        && bookmark.schema.as_deref() == schema
+        && bookmark.database.as_deref() == database
+        && &bookmark.pk == pk
+}
+
+/// Keeps only bookmarks matching the optional table/schema/database filters.
+pub fn filter_bookmarks(
+    bookmarks: Vec<RowBookmark>,
+    table: Option<&str>,
+    schema: Option<&str>,
+    database: Option<&str>,
+) -> Vec<RowBookmark> {
+    bookmarks
+        .into_iter()
+        .filter(|b| table.map_or(true, |t| b.table == t))
+        .filter(|b| schema.map_or(true, |s| b.schema.as_deref() == Some(s)))
+        .filter(|b| database.map_or(true, |d| b.database.as_deref() == Some(d)))
+        .collect()
+}
+
+/// Builds the WHERE clause (without the keyword) that selects the bookmarked row.
+///
+/// `quote` is the driver's identifier quote character (see
+/// `DriverCapabilities::identifier_quote`). Key values are bound as `:pN`
+/// placeholders added to `params`; NULL key values compare with `IS NULL`.
+pub fn build_bookmark_filter(
+    quote: &str,
+    pk: &serde_json::Map<String, serde_json::Value>,
+    params: &mut HashMap<String, serde_json::Value>,
+) -> Result<String, String> {
+    if pk.is_empty() {
+        return Err("Bookmark has no primary key values".to_string());
+    }
+    let clauses: Vec<String> = pk
+        .iter()
+        .map(|(col, val)| {
+            let col = quote_identifier(quote, col);
+            if val.is_null() {
+                format!("{} IS NULL", col)
+            } else {
+                let param = format!("p{}", params.len());
+                params.insert(param.clone(), val.clone());
+                format!("{} = :{}", col, param)
+            }
+        })
+        .collect();
+    Ok(clauses.join(" AND "))
+}
+
+/// Builds the SELECT statement used to jump to a bookmarked row.
+pub fn build_bookmark_query(quote: &str, bookmark: &RowBookmark) -> Result<BoundStatement, String> {
+    let mut params = HashMap::new();
+    let filter = build_bookmark_filter(quote, &bookmark.pk, &mut params)?;
+    let table_ref = table_ref(quote, bookmark.schema.as_deref(), &bookmark.table);
+    Ok(BoundStatement {
+        sql: format!("SELECT * FROM {} WHERE {}", table_ref, filter),
+        params,
+    })
+}
+
+#[tauri::command]
+pub async fn get_row_bookmarks<R: Runtime>(
+    app: AppHandle<R>,
+    connection_id: String,
+    table: Option<String>,
+    schema: Option<String>,
+    database: Option<String>,
+) -> Result<Vec<RowBookmark>, String> {
+    let bookmarks = read_bookmarks(&app, &connection_id)?;
+    Ok(filter_bookmarks(
+        bookmarks,
+        table.as_deref(),
+        schema.as_deref(),
+        database.as_deref(),
+    ))
+}
+
+#[tauri::command]
+pub async fn add_row_bookmark<R: Runtime>(
+    app: AppHandle<R>,
+    connection_id: String,
+    table: String,
+    schema: Option<String>,
+    database: Option<String>,
+    pk: serde_json::Map<String, serde_json::Value>,
+    note: Option<String>,
+    color: Option<String>,
+) -> Result<RowBookmark, String> {
+    if pk.is_empty() {
+        return Err("Cannot bookmark a row without primary key values".to_string());
+    }
+
+    let mut bookmarks = read_bookmarks(&app, &connection_id)?;
+    let now = chrono::Utc::now().to_rfc3339();
+
+    // Bookmarking the same row twice updates the existing entry
+    if let Some(existing) = bookmarks
+        .iter_mut()
+        .find(|b| is_same_row(b, &table, schema.as_deref(), database.as_deref(), &pk))
+    {
+        existing.note = note;
+        existing.color = color;
+        existing.updated_at = now;
+        let updated = existing.clone();
+        write_bookmarks(&app, &connection_id, &bookmarks)?;
+        return Ok(updated);
+    }
+
+    let bookmark = RowBookmark {
+        id: Uuid::new_v4().to_string(),
+        table,
+        schema,
+        database,
+        pk,
+        note,
+        color,
+        created_at: now.clone(),
+        updated_at: now,
+    };
+
+    bookmarks.insert(0, bookmark.clone());
+    write_bookmarks(&app, &connection_id, &bookmarks)?;
+    Ok(bookmark)
+}
+
+#[tauri::command]
+pub async fn update_row_bookmark<R: Runtime>(
+    app: AppHandle<R>,
+    connection_id: String,
+    id: String,
+    note: Option<String>,
+    color: Option<String>,
+) -> Result<RowBookmark, String> {
+    let mut bookmarks = read_bookmarks(&app, &connection_id)?;
+    let bookmark = bookmarks
+        .iter_mut()
+        .find(|b| b.id == id)
+        .ok_or_else(|| crate::i18n::t("errors.bookmarkNotFound"))?;
+
+    bookmark.note = note;
+    bookmark.color = color;
+    bookmark.updated_at = chrono::Utc::now().to_rfc3339();
+    let updated = bookmark.clone();
+
+    write_bookmarks(&app, &connection_id, &bookmarks)?;
+    Ok(updated)
+}
+
+#[tauri::command]
+pub async fn delete_row_bookmark<R: Runtime>(
+    app: AppHandle<R>,
+    connection_id: String,
+    id: String,
+) -> Result<(), String> {
+    let mut bookmarks = read_bookmarks(&app, &connection_id)?;
+    let original_len = bookmarks.len();
+    bookmarks.retain(|b| b.id != id);
+
+    if bookmarks.len() == original_len {
+        return Err(crate::i18n::t("errors.bookmarkNotFound"));
+    }
+
+    write_bookmarks(&app, &connection_id, &bookmarks)
+}
+
+/// Fetches the current contents of a bookmarked row so the UI can jump to it.
+#[tauri::command]
+pub async fn open_row_bookmark<R: Runtime>(
+    app: AppHandle<R>,
+    connection_id: String,
+    id: String,
+) -> Result<QueryResult, String> {
+    let bookmark = read_bookmarks(&app, &connection_id)?
+        .into_iter()
+        .find(|b| b.id == id)
+        .ok_or_else(|| crate::i18n::t("errors.bookmarkNotFound"))?;
+
+    let saved_conn = crate::commands::find_connection_by_id(&app, &connection_id)?;
+    let expanded_params =
+        crate::commands::expand_ssh_connection_params(&app, &saved_conn.params).await?;
+    let mut params =
+        crate::commands::resolve_connection_params_with_id(&expanded_params, &connection_id)?;
+    if let Some(db) = &bookmark.database {
+        params.database = crate::models::DatabaseSelection::Single(db.clone());
+    }
+
+    let drv = crate::drivers::registry::get_driver(&saved_conn.params.driver)
+        .await
+        .ok_or_else(|| {
+            crate::i18n::t_with(
+                "errors.unsupportedDriver",
+                &[("driver", &saved_conn.params.driver)],
+            )
+        })?;
+    let query = build_bookmark_query(&drv.manifest().capabilities.identifier_quote, &bookmark)?;
+
+    log::info!(
+        "Opening bookmark {} on connection: {} | Query: {}",
+        id,
+        connection_id,
+        query.sql
+    );
+
+    drv.execute_query_with_params(
+        &params,
+        &query.sql,
+        &query.params,
+        Some(1),
+        1,
+        bookmark.schema.as_deref(),
+    )
+    .await
+}
+
+/// Remove bookmarks file for a connection (called during connection deletion).
+pub fn remove_bookmarks_for_connection<R: Runtime>(
+    app: &AppHandle<R>,
+    connection_id: &str,
+) -> Result<(), String> {
+    let path = get_bookmarks_path(app, connection_id)?;
+    if path.exists() {
+        fs::remove_file(path).map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
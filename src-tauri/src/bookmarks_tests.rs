@@ -0,0 +1,139 @@
+#[cfg(test)]
+mod tests {
+    use crate::bookmarks::{
+        build_bookmark_filter, build_bookmark_query, filter_bookmarks, is_same_row, RowBookmark,
+    };
+    use serde_json::{json, Map, Value};
+    use std::collections::HashMap;
+
+    fn pk(pairs: &[(&str, Value)]) -> Map<String, Value> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.clone()))
+            .collect()
+    }
+
+    fn make_bookmark(id: &str, table: &str, schema: Option<&str>) -> RowBookmark {
+        RowBookmark {
+            id: id.into(),
+            table: table.into(),
+            schema: schema.map(|s| s.into()),
+            database: None,
+            pk: pk(&[("id", json!(1))]),
+            note: None,
+            color: None,
+            created_at: "2024-01-01T00:00:00Z".into(),
+            updated_at: "2024-01-01T00:00:00Z".into(),
+        }
+    }
+
+    fn filter(quote: &str, key: &Map<String, Value>) -> (String, HashMap<String, Value>) {
+        let mut params = HashMap::new();
+        let sql = build_bookmark_filter(quote, key, &mut params).unwrap();
+        (sql, params)
+    }
+
+    #[test]
+    fn filter_binds_single_key() {
+        let (sql, params) = filter("\"", &pk(&[("id", json!(42))]));
+        assert_eq!(sql, r#""id" = :p0"#);
+        assert_eq!(params["p0"], json!(42));
+    }
+
+    #[test]
+    fn filter_composite_key_uses_and() {
+        let (sql, params) = filter("`", &pk(&[("order_id", json!(7)), ("sku", json!("AB-1"))]));
+        assert_eq!(sql, "`order_id` = :p0 AND `sku` = :p1");
+        assert_eq!(params["p1"], json!("AB-1"));
+    }
+
+    #[test]
+    fn filter_keeps_values_out_of_the_sql() {
+        // A MySQL string literal would treat the backslash as an escape.
+        let (sql, params) = filter("\"", &pk(&[("we\"ird", json!("O'Brien\\"))]));
+        assert_eq!(sql, r#""we""ird" = :p0"#);
+        assert_eq!(params["p0"], json!("O'Brien\\"));
+
+        let (_, params) = filter("`", &pk(&[("active", json!(true))]));
+        assert_eq!(params["p0"], json!(true));
+    }
+
+    #[test]
+    fn filter_null_key_uses_is_null() {
+        let (sql, params) = filter("\"", &pk(&[("id", Value::Null)]));
+        assert_eq!(sql, r#""id" IS NULL"#);
+        assert!(params.is_empty());
+    }
+
+    #[test]
+    fn filter_rejects_empty_key() {
+        assert!(build_bookmark_filter("\"", &Map::new(), &mut HashMap::new()).is_err());
+    }
+
+    #[test]
+    fn query_is_schema_qualified_when_schema_present() {
+        let bookmark = make_bookmark("1", "users", Some("public"));
+        let query = build_bookmark_query("\"", &bookmark).unwrap();
+        assert_eq!(
+            query.sql,
+            r#"SELECT * FROM "public"."users" WHERE "id" = :p0"#
+        );
+        assert_eq!(query.params["p0"], json!(1));
+    }
+
+    #[test]
+    fn query_without_schema() {
+        let bookmark = make_bookmark("1", "users", None);
+        assert_eq!(
+            build_bookmark_query("`", &bookmark).unwrap().sql,
+            "SELECT * FROM `users` WHERE `id` = :p0"
+        );
+    }
+
+    #[test]
+    fn same_row_compares_location_and_key() {
+        let bookmark = make_bookmark("1", "users", Some("public"));
+        assert!(is_same_row(
+            &bookmark,
+            "users",
+            Some("public"),
+            None,
+            &pk(&[("id", json!(1))])
+        ));
+        assert!(!is_same_row(
+            &bookmark,
+            "users",
+            Some("public"),
+            None,
+            &pk(&[("id", json!(2))])
+        ));
+        assert!(!is_same_row(
+            &bookmark,
+            "users",
+            Some("audit"),
+            None,
+            &pk(&[("id", json!(1))])
+        ));
+    }
+
+    #[test]
+    fn filter_bookmarks_by_table() {
+        let bookmarks = vec![
+            make_bookmark("1", "users", None),
+            make_bookmark("2", "orders", None),
+            make_bookmark("3", "users", None),
+        ];
+        let filtered = filter_bookmarks(bookmarks, Some("users"), None, None);
+        let ids: Vec<&str> = filtered.iter().map(|b| b.id.as_str()).collect();
+        assert_eq!(ids, vec!["1", "3"]);
+    }
+
+    #[test]
+    fn filter_bookmarks_without_filters_returns_all() {
+        let bookmarks = vec![
+            make_bookmark("1", "users", None),
+            make_bookmark("2", "orders", Some("sales")),
+        ];
+        assert_eq!(filter_bookmarks(bookmarks, None, None, None).len(), 2);
+    }
+}
@@ -0,0 +1,201 @@
+//! Run one query against many connections.
+//!
+//! `broadcast_query` executes the same SQL on every selected connection
+//! concurrently (at most `DEFAULT_CONCURRENCY` at a time unless the caller
+//! asks otherwise) and returns one entry per connection, in the order given.
+//! A failing connection never aborts the others: its error is reported in
+//! its own entry. Each run is registered under its connection id for the
+//! calling window, so `cancel_query` from that window stops a single target.
+
+use futures::StreamExt;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use std::time::Instant;
+use tauri::{AppHandle, Runtime, State, WebviewWindow};
+
+use crate::commands::{
+    driver_for, expand_ssh_connection_params, find_connection_by_id, register_abort_handle,
+    resolve_connection_params_with_id, sanitize_user_query, unregister_abort_handle,
+    QueryCancellationState,
+};
+use crate::models::QueryResult;
+
+/// Connections queried at the same time when the caller does not say.
+pub const DEFAULT_CONCURRENCY: usize = 8;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BroadcastResult {
+    pub connection_id: String,
+    /// Display name of the connection; empty when it could not be found.
+    pub connection_name: String,
+    pub result: Option<QueryResult>,
+    pub error: Option<String>,
+    pub execution_time_ms: Option<f64>,
+}
+
+// ---------------------------------------------------------------------------
+// Targets (testable; pure)
+// ---------------------------------------------------------------------------
+
+/// Drop blank and repeated ids, keeping the first occurrence of each.
+pub fn normalize_targets(connection_ids: &[String]) -> Result<Vec<String>, String> {
+    let mut targets: Vec<String> = Vec::new();
+    for id in connection_ids {
+        let id = id.trim();
+        if !id.is_empty() && !targets.iter().any(|t| t == id) {
+            targets.push(id.to_string());
+        }
+    }
+    if targets.is_empty() {
+        return Err("Select at least one connection to broadcast to".into());
+    }
+    Ok(targets)
+}
+
+/// Concurrency to use, clamped to `1..=targets`.
+pub fn effective_concurrency(requested: Option<usize>, targets: usize) -> usize {
+    requested
+        .unwrap_or(DEFAULT_CONCURRENCY)
+        .clamp(1, targets.max(1))
+}
+
+// ---------------------------------------------------------------------------
+// Execution
+// ---------------------------------------------------------------------------
+
+/// Run `query` on one connection, reporting failures in the entry. Also
+/// used by `diff_query_results` for each side of a diff. The run can be
+/// cancelled from the window labelled `window_label`.
+pub(crate) async fn run_on_connection<R: Runtime>(
+    app: &AppHandle<R>,
+    window_label: &str,
+    state: &QueryCancellationState,
+    connection_id: &str,
+    query: &str,
+    limit: Option<u32>,
+    schema: Option<&str>,
+    allow_costly: bool,
+) -> BroadcastResult {
+    let mut entry = BroadcastResult {
+        connection_id: connection_id.to_string(),
+        connection_name: String::new(),
+        result: None,
+        error: None,
+        execution_time_ms: None,
+    };
+
+    let saved_conn = match find_connection_by_id(app, connection_id) {
+        Ok(conn) => conn,
+        Err(e) => {
+            entry.error = Some(e);
+            return entry;
+        }
+    };
+    entry.connection_name = saved_conn.name.clone();
+    if let Err(e) = crate::read_only::ensure_query_allowed(&saved_conn.params, query) {
+        entry.error = Some(e);
+        return entry;
+    }
+
+    let prepared = async {
+        let expanded = expand_ssh_connection_params(app, &saved_conn.params).await?;
+        let params = resolve_connection_params_with_id(&expanded, connection_id)?;
+        let drv = driver_for(&saved_conn.params.driver).await?;
+        if !allow_costly {
+            crate::cost_guard::enforce(app, connection_id, query, schema).await?;
+        }
+        Ok::<_, String>((params, drv))
+    }
+    .await;
+    let (params, drv) = match prepared {
+        Ok(prepared) => prepared,
+        Err(e) => {
+            entry.error = Some(e);
+            return entry;
+        }
+    };
+
+    let start = Instant::now();
+    let task = {
+        let query = query.to_string();
+        let schema = schema.map(str::to_string);
+        tokio::spawn(async move {
+            drv.execute_query(&params, &query, limit, 1, schema.as_deref())
+                .await
+        })
+    };
+    let abort_handle = Arc::new(task.abort_handle());
+    let cancel_key = crate::window_state::cancellation_key(window_label, connection_id);
+    register_abort_handle(&state.handles, cancel_key.clone(), abort_handle.clone());
+    let outcome = task.await;
+    unregister_abort_handle(&state.handles, &cancel_key, &abort_handle);
+    entry.execution_time_ms = Some(start.elapsed().as_secs_f64() * 1000.0);
+
+    match outcome {
+        Ok(Ok(result)) => {
+            crate::session_objects::track(app, connection_id, query);
+            crate::schema_watch::note_local_ddl(connection_id, query);
+            entry.result = Some(result);
+        }
+        Ok(Err(e)) => entry.error = Some(e),
+        Err(_) => entry.error = Some("Query cancelled".into()),
+    }
+    entry
+}
+
+// ---------------------------------------------------------------------------
+// Tauri commands
+// ---------------------------------------------------------------------------
+
+/// Execute `query` on every connection in `connection_ids` and collect the
+/// per-connection results and errors.
+#[tauri::command]
+pub async fn broadcast_query<R: Runtime>(
+    app: AppHandle<R>,
+    window: WebviewWindow<R>,
+    state: State<'_, QueryCancellationState>,
+    connection_ids: Vec<String>,
+    query: String,
+    limit: Option<u32>,
+    schema: Option<String>,
+    concurrency: Option<usize>,
+    allow_costly: Option<bool>,
+) -> Result<Vec<BroadcastResult>, String> {
+    let targets = normalize_targets(&connection_ids)?;
+    let query = sanitize_user_query(&query);
+    let concurrency = effective_concurrency(concurrency, targets.len());
+    let allow_costly = allow_costly.unwrap_or(false);
+    log::info!(
+        "Broadcasting query to {} connections (concurrency {}) | Query: {}",
+        targets.len(),
+        concurrency,
+        query
+    );
+
+    let state = state.inner();
+    let results: Vec<BroadcastResult> = futures::stream::iter(targets.iter())
+        .map(|connection_id| {
+            run_on_connection(
+                &app,
+                window.label(),
+                state,
+                connection_id,
+                &query,
+                limit,
+                schema.as_deref(),
+                allow_costly,
+            )
+        })
+        .buffered(concurrency)
+        .collect()
+        .await;
+
+    let failed = results.iter().filter(|r| r.error.is_some()).count();
+    log::info!(
+        "Broadcast finished: {} succeeded, {} failed",
+        results.len() - failed,
+        failed
+    );
+    Ok(results)
+}
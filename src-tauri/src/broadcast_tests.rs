@@ -0,0 +1,32 @@
+#[cfg(test)]
+mod tests {
+    use crate::broadcast::{effective_concurrency, normalize_targets, DEFAULT_CONCURRENCY};
+
+    fn ids(values: &[&str]) -> Vec<String> {
+        values.iter().map(|v| v.to_string()).collect()
+    }
+
+    // --- normalize_targets ---
+
+    #[test]
+    fn keeps_first_occurrence_and_order() {
+        let targets = normalize_targets(&ids(&["shard-2", "shard-1", " shard-2 ", ""])).unwrap();
+        assert_eq!(targets, ids(&["shard-2", "shard-1"]));
+    }
+
+    #[test]
+    fn rejects_empty_selection() {
+        assert!(normalize_targets(&[]).is_err());
+        assert!(normalize_targets(&ids(&["  "])).is_err());
+    }
+
+    // --- effective_concurrency ---
+
+    #[test]
+    fn concurrency_defaults_and_clamps_to_targets() {
+        assert_eq!(effective_concurrency(None, 100), DEFAULT_CONCURRENCY);
+        assert_eq!(effective_concurrency(None, 3), 3);
+        assert_eq!(effective_concurrency(Some(0), 5), 1);
+        assert_eq!(effective_concurrency(Some(2), 5), 2);
+    }
+}
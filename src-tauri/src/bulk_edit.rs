@@ -0,0 +1,260 @@
+//! Multi-row edits: update or delete many rows of a table at once.
+//!
+//! Rows are identified by their primary key values, one map per row, so
+//! composite keys work the same as single-column ones. Every value is bound
+//! as a query parameter (see `DatabaseDriver::execute_query_with_params`).
+//! Large selections are split into statements of at most
+//! `MAX_STATEMENT_PARAMS` parameters, run in one transaction.
+
+use std::collections::HashMap;
+
+use serde_json::{Map, Value};
+use tauri::{AppHandle, Runtime};
+
+use crate::drivers::common::quote_identifier;
+
+/// Most parameters bound to one statement: under SQL Server's limit of 2100
+/// and the 999 of older SQLite builds.
+pub const MAX_STATEMENT_PARAMS: usize = 999;
+
+/// A statement with `:name` placeholders and the values to bind to them.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BoundStatement {
+    pub sql: String,
+    pub params: HashMap<String, Value>,
+}
+
+pub(crate) fn table_ref(quote: &str, schema: Option<&str>, table: &str) -> String {
+    match schema {
+        Some(schema) if !schema.is_empty() => format!(
+            "{}.{}",
+            quote_identifier(quote, schema),
+            quote_identifier(quote, table)
+        ),
+        _ => quote_identifier(quote, table),
+    }
+}
+
+/// `(a = :pk_0_0 AND b = :pk_0_1) OR (...)`, one group per row. A NULL key
+/// value compares with `IS NULL`.
+fn keys_filter(
+    quote: &str,
+    keys: &[Map<String, Value>],
+    params: &mut HashMap<String, Value>,
+) -> Result<String, String> {
+    if keys.is_empty() {
+        return Err("No rows selected".to_string());
+    }
+    let mut groups = Vec::with_capacity(keys.len());
+    for (row, key) in keys.iter().enumerate() {
+        if key.is_empty() {
+            return Err(format!("Row {} has no primary key values", row + 1));
+        }
+        let clauses: Vec<String> = key
+            .iter()
+            .enumerate()
+            .map(|(col, (name, value))| {
+                let column = quote_identifier(quote, name);
+                if value.is_null() {
+                    format!("{} IS NULL", column)
+                } else {
+                    let param = format!("pk_{}_{}", row, col);
+                    params.insert(param.clone(), value.clone());
+                    format!("{} = :{}", column, param)
+                }
+            })
+            .collect();
+        groups.push(format!("({})", clauses.join(" AND ")));
+    }
+    Ok(groups.join(" OR "))
+}
+
+/// Split `keys` into runs of rows whose key values, plus `reserved` other
+/// parameters, fit in one statement. Always returns at least one run, so
+/// an empty selection still reaches the statement builder and its error.
+pub fn chunk_keys(keys: &[Map<String, Value>], reserved: usize) -> Vec<&[Map<String, Value>]> {
+    let mut chunks = Vec::new();
+    let mut start = 0;
+    let mut used = reserved;
+    for (row, key) in keys.iter().enumerate() {
+        // NULL key values compare with `IS NULL` and bind nothing.
+        let needed = key.values().filter(|v| !v.is_null()).count();
+        if row > start && used + needed > MAX_STATEMENT_PARAMS {
+            chunks.push(&keys[start..row]);
+            start = row;
+            used = reserved;
+        }
+        used += needed;
+    }
+    chunks.push(&keys[start..]);
+    chunks
+}
+
+/// `DELETE FROM table WHERE <keys>`.
+pub fn build_bulk_delete(
+    quote: &str,
+    schema: Option<&str>,
+    table: &str,
+    keys: &[Map<String, Value>],
+) -> Result<BoundStatement, String> {
+    let mut params = HashMap::new();
+    let filter = keys_filter(quote, keys, &mut params)?;
+    Ok(BoundStatement {
+        sql: format!(
+            "DELETE FROM {} WHERE {}",
+            table_ref(quote, schema, table),
+            filter
+        ),
+        params,
+    })
+}
+
+/// `UPDATE table SET <changes> WHERE <keys>`: the same new values on every
+/// selected row.
+pub fn build_bulk_update(
+    quote: &str,
+    schema: Option<&str>,
+    table: &str,
+    keys: &[Map<String, Value>],
+    changes: &Map<String, Value>,
+) -> Result<BoundStatement, String> {
+    if changes.is_empty() {
+        return Err("No columns to update".to_string());
+    }
+    let mut params = HashMap::new();
+    let assignments: Vec<String> = changes
+        .iter()
+        .enumerate()
+        .map(|(i, (column, value))| {
+            let param = format!("set_{}", i);
+            params.insert(param.clone(), value.clone());
+            format!("{} = :{}", quote_identifier(quote, column), param)
+        })
+        .collect();
+    let filter = keys_filter(quote, keys, &mut params)?;
+    Ok(BoundStatement {
+        sql: format!(
+            "UPDATE {} SET {} WHERE {}",
+            table_ref(quote, schema, table),
+            assignments.join(", "),
+            filter
+        ),
+        params,
+    })
+}
+
+/// Run the statements `build` makes for each chunk of `keys` (see
+/// `chunk_keys`) and return the total number of affected rows. Several
+/// statements run in one transaction, so a failing one undoes the others;
+/// inside a transaction the user opened they run in that one instead.
+async fn execute_bound<R: Runtime>(
+    app: &AppHandle<R>,
+    connection_id: &str,
+    schema: Option<&str>,
+    database: Option<String>,
+    keys: &[Map<String, Value>],
+    reserved_params: usize,
+    build: impl Fn(&str, &[Map<String, Value>]) -> Result<BoundStatement, String>,
+) -> Result<u64, String> {
+    let saved_conn = crate::commands::find_connection_by_id(app, connection_id)?;
+    crate::read_only::ensure_writable(&saved_conn.params)?;
+    let expanded_params =
+        crate::commands::expand_ssh_connection_params(app, &saved_conn.params).await?;
+    let mut params =
+        crate::commands::resolve_connection_params_with_id(&expanded_params, connection_id)?;
+    if let Some(db) = database {
+        params.database = crate::models::DatabaseSelection::Single(db);
+    }
+    let drv = crate::commands::driver_for(&saved_conn.params.driver).await?;
+    let quote = drv.manifest().capabilities.identifier_quote.clone();
+    // Build every statement first, so bad input fails before anything runs.
+    let statements = chunk_keys(keys, reserved_params)
+        .into_iter()
+        .map(|chunk| build(&quote, chunk))
+        .collect::<Result<Vec<_>, String>>()?;
+
+    let own_transaction =
+        statements.len() > 1 && !crate::pool_manager::has_transaction(connection_id).await;
+    if own_transaction {
+        crate::pool_manager::begin_transaction(&params).await?;
+    }
+
+    let mut affected = 0;
+    let mut outcome = Ok(());
+    for statement in &statements {
+        log::info!(
+            "Executing query on connection: {} | {} parameter(s) | Query: {}",
+            connection_id,
+            statement.params.len(),
+            statement.sql
+        );
+        match drv
+            .execute_query_with_params(&params, &statement.sql, &statement.params, None, 1, schema)
+            .await
+        {
+            Ok(result) => affected += result.affected_rows,
+            Err(e) => {
+                outcome = Err(e);
+                break;
+            }
+        }
+    }
+
+    if own_transaction {
+        let finished =
+            crate::pool_manager::finish_transaction(connection_id, outcome.is_ok()).await;
+        outcome = outcome.and(finished);
+    }
+    outcome.map(|_| affected)
+}
+
+/// Delete every row whose primary key is in `keys`. Returns the total number
+/// of deleted rows.
+#[tauri::command]
+pub async fn bulk_delete_records<R: Runtime>(
+    app: AppHandle<R>,
+    connection_id: String,
+    table: String,
+    keys: Vec<Map<String, Value>>,
+    schema: Option<String>,
+    database: Option<String>,
+) -> Result<u64, String> {
+    let deleted = execute_bound(
+        &app,
+        &connection_id,
+        schema.as_deref(),
+        database,
+        &keys,
+        0,
+        |quote, chunk| build_bulk_delete(quote, schema.as_deref(), &table, chunk),
+    )
+    .await?;
+    crate::table_activity::track_edit(&app, &connection_id, schema.as_deref(), &table);
+    Ok(deleted)
+}
+
+/// Set `changes` (column → new value) on every row whose primary key is in
+/// `keys`. Returns the total number of updated rows.
+#[tauri::command]
+pub async fn bulk_update_records<R: Runtime>(
+    app: AppHandle<R>,
+    connection_id: String,
+    table: String,
+    keys: Vec<Map<String, Value>>,
+    changes: Map<String, Value>,
+    schema: Option<String>,
+    database: Option<String>,
+) -> Result<u64, String> {
+    let updated = execute_bound(
+        &app,
+        &connection_id,
+        schema.as_deref(),
+        database,
+        &keys,
+        changes.len(),
+        |quote, chunk| build_bulk_update(quote, schema.as_deref(), &table, chunk, &changes),
+    )
+    .await?;
+    crate::table_activity::track_edit(&app, &connection_id, schema.as_deref(), &table);
+    Ok(updated)
+}
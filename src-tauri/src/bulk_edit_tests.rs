@@ -0,0 +1,108 @@
+#[cfg(test)]
+mod tests {
+    use crate::bulk_edit::{
+        build_bulk_delete, build_bulk_update, chunk_keys, MAX_STATEMENT_PARAMS,
+    };
+    use serde_json::{json, Map, Value};
+
+    fn key(pairs: &[(&str, Value)]) -> Map<String, Value> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.clone()))
+            .collect()
+    }
+
+    #[test]
+    fn delete_matches_each_row_by_key() {
+        let stmt = build_bulk_delete(
+            "\"",
+            Some("public"),
+            "users",
+            &[key(&[("id", json!(1))]), key(&[("id", json!(2))])],
+        )
+        .unwrap();
+        assert_eq!(
+            stmt.sql,
+            "DELETE FROM \"public\".\"users\" WHERE (\"id\" = :pk_0_0) OR (\"id\" = :pk_1_0)"
+        );
+        assert_eq!(stmt.params["pk_0_0"], json!(1));
+        assert_eq!(stmt.params["pk_1_0"], json!(2));
+    }
+
+    #[test]
+    fn composite_and_null_keys() {
+        let stmt = build_bulk_delete(
+            "`",
+            None,
+            "order_lines",
+            &[
+                key(&[("order_id", json!(7)), ("line", json!(1))]),
+                key(&[("order_id", json!(7)), ("line", Value::Null)]),
+            ],
+        )
+        .unwrap();
+        assert_eq!(
+            stmt.sql,
+            "DELETE FROM `order_lines` WHERE (`line` = :pk_0_0 AND `order_id` = :pk_0_1) \
+             OR (`line` IS NULL AND `order_id` = :pk_1_1)"
+        );
+        assert_eq!(stmt.params.len(), 3);
+    }
+
+    #[test]
+    fn update_binds_new_values() {
+        let changes = key(&[("status", json!("archived")), ("note", json!("it's done"))]);
+        let stmt =
+            build_bulk_update("\"", None, "tasks", &[key(&[("id", json!(3))])], &changes).unwrap();
+        assert_eq!(
+            stmt.sql,
+            "UPDATE \"tasks\" SET \"note\" = :set_0, \"status\" = :set_1 WHERE (\"id\" = :pk_0_0)"
+        );
+        assert_eq!(stmt.params["set_0"], json!("it's done"));
+        assert_eq!(stmt.params["set_1"], json!("archived"));
+    }
+
+    #[test]
+    fn quotes_hostile_identifiers() {
+        let stmt = build_bulk_delete("\"", None, "a\"b", &[key(&[("c\"d", json!(1))])]).unwrap();
+        assert_eq!(
+            stmt.sql,
+            "DELETE FROM \"a\"\"b\" WHERE (\"c\"\"d\" = :pk_0_0)"
+        );
+    }
+
+    #[test]
+    fn chunks_keep_each_statement_under_the_parameter_limit() {
+        let keys: Vec<Map<String, Value>> = (0..1000)
+            .map(|i| key(&[("id", json!(i)), ("tenant", json!(1))]))
+            .collect();
+        let chunks = chunk_keys(&keys, 3);
+        let sizes: Vec<usize> = chunks.iter().map(|c| c.len()).collect();
+        let per_chunk = (MAX_STATEMENT_PARAMS - 3) / 2;
+        assert_eq!(sizes, vec![per_chunk, per_chunk, 1000 - 2 * per_chunk]);
+
+        for chunk in chunks {
+            let changes = key(&[("a", json!(1)), ("b", json!(2)), ("c", json!(3))]);
+            let stmt = build_bulk_update("\"", None, "t", chunk, &changes).unwrap();
+            assert!(stmt.params.len() <= MAX_STATEMENT_PARAMS);
+        }
+    }
+
+    #[test]
+    fn null_keys_take_no_parameters_and_empty_input_stays_one_chunk() {
+        let keys: Vec<Map<String, Value>> = (0..MAX_STATEMENT_PARAMS + 10)
+            .map(|_| key(&[("id", Value::Null)]))
+            .collect();
+        assert_eq!(chunk_keys(&keys, 0).len(), 1);
+        assert_eq!(chunk_keys(&[], 0), vec![&[] as &[Map<String, Value>]]);
+    }
+
+    #[test]
+    fn rejects_empty_input() {
+        assert!(build_bulk_delete("\"", None, "t", &[]).is_err());
+        assert!(build_bulk_delete("\"", None, "t", &[Map::new()]).is_err());
+        assert!(
+            build_bulk_update("\"", None, "t", &[key(&[("id", json!(1))])], &Map::new()).is_err()
+        );
+    }
+}
@@ -1,8 +1,9 @@
 //! Command-line argument parsing for the Tabularis binary.
 //!
 //! Keeping this in its own module means `lib.rs` does not have to know about
-//! clap, and the flag surface (`--mcp`, `--debug`, `--explain`, `--help`,
-//! `--version`) lives in one place.
+//! clap, and the flag surface (`--mcp`, `--debug`, `--explain`, `--portable`,
+//! `--profile`, `--help`, `--version`) lives in one place. Subcommands are
+//! automation requests for an already-running app (see `automation`).
 
 use clap::Parser;
 
@@ -21,6 +22,19 @@ pub struct Args {
     /// (Postgres `EXPLAIN (FORMAT JSON)` output).
     #[arg(long, value_name = "FILE")]
     pub explain: Option<String>,
+
+    /// Store config, plugins and caches in a `tabularis-data` folder next to
+    /// the executable (same as placing a `.portable` file there).
+    #[arg(long)]
+    pub portable: bool,
+
+    /// Use a separate profile (config, connections, saved queries) by name.
+    #[arg(long, value_name = "NAME")]
+    pub profile: Option<String>,
+
+    /// Send a request to the running app instead of starting one.
+    #[command(subcommand)]
+    pub command: Option<crate::automation::AutomationRequest>,
 }
 
 impl Args {
@@ -29,6 +43,9 @@ impl Args {
             mcp: false,
             debug: false,
             explain: None,
+            portable: false,
+            profile: None,
+            command: None,
         }
     }
 }
@@ -50,3 +67,9 @@ pub fn parse() -> Args {
         Args::defaults()
     })
 }
+
+/// Parse the arguments a second launch forwarded to the running instance.
+/// Returns `None` when they do not parse (e.g. a bare deep-link URL).
+pub fn parse_from(argv: &[String]) -> Option<Args> {
+    Args::try_parse_from(argv).ok()
+}
@@ -2,7 +2,7 @@ use std::sync::Arc;
 use crate::{
     commands::{expand_ssh_connection_params, find_connection_by_id, resolve_connection_params_with_id},
     drivers::{driver_trait::DatabaseDriver, registry::get_driver},
-    models::ColumnDefinition,
+    models::{ColumnDefinition, ConnectionParams},
 };
 use serde::{Deserialize, Serialize};
 use tauri::{AppHandle, Runtime};
@@ -76,11 +76,16 @@ pub async fn execute_clipboard_import<R: Runtime>(
     );
 
     let saved_conn = find_connection_by_id(&app, &req.connection_id)?;
+    crate::read_only::ensure_writable(&saved_conn.params)?;
     let expanded = expand_ssh_connection_params(&app, &saved_conn.params).await?;
     let params = resolve_connection_params_with_id(&expanded, &req.connection_id)?;
-    let drv: Arc<dyn DatabaseDriver> = get_driver(&saved_conn.params.driver)
-        .await
-        .ok_or_else(|| format!("Unsupported driver: {}", saved_conn.params.driver))?;
+    let drv: Arc<dyn DatabaseDriver> =
+        get_driver(&saved_conn.params.driver).await.ok_or_else(|| {
+            crate::i18n::t_with(
+                "errors.unsupportedDriver",
+                &[("driver", &saved_conn.params.driver)],
+            )
+        })?;
 
     let schema_ref = req.schema.as_deref();
     let tbl_ref = table_ref(&req.table_name, schema_ref);
@@ -92,7 +97,7 @@ pub async fn execute_clipboard_import<R: Runtime>(
                 let drop_sql = format!("DROP TABLE IF EXISTS {}", tbl_ref);
                 drv.execute_query(&params, &drop_sql, None, 1, schema_ref)
                     .await
-                    .map_err(|e| format!("Failed to drop existing table: {e}"))?;
+                    .map_err(|e| crate::i18n::t_with("errors.dropTableFailed", &[("error", &e)]))?;
             }
             IfExistsStrategy::Append => {
                 add_new_columns(drv.as_ref(), &params, &req, schema_ref, &tbl_ref).await?;
@@ -104,12 +109,12 @@ pub async fn execute_clipboard_import<R: Runtime>(
         let stmts = drv
             .get_create_table_sql(&req.table_name, req.columns.clone(), schema_ref)
             .await
-            .map_err(|e| format!("Failed to generate CREATE TABLE SQL: {e}"))?;
+            .map_err(|e| crate::i18n::t_with("errors.createTableSqlFailed", &[("error", &e)]))?;
 
         for stmt in &stmts {
             drv.execute_query(&params, stmt, None, 1, schema_ref)
                 .await
-                .map_err(|e| format!("Failed to create table: {e}"))?;
+                .map_err(|e| crate::i18n::t_with("errors.createTableFailed", &[("error", &e)]))?;
         }
         table_created = true;
     } else {
@@ -137,7 +142,12 @@ async fn add_new_columns(
         );
         drv.execute_query(params, &sql, None, 1, schema_ref)
             .await
-            .map_err(|e| format!("Failed to add column '{}': {e}", col.name))?;
+            .map_err(|e| {
+                crate::i18n::t_with(
+                    "errors.addColumnFailed",
+                    &[("column", &col.name), ("error", &e)],
+                )
+            })?;
     }
     Ok(())
 }
@@ -175,7 +185,12 @@ async fn insert_rows(
 
         drv.execute_query(params, &insert_sql, None, 1, schema_ref)
             .await
-            .map_err(|e| format!("Failed to insert rows (batch starting at {}): {e}", rows_inserted))?;
+            .map_err(|e| {
+                crate::i18n::t_with(
+                    "errors.insertRowsFailed",
+                    &[("row", &rows_inserted.to_string()), ("error", &e)],
+                )
+            })?;
 
         rows_inserted += chunk.len();
     }
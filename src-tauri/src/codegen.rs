@@ -0,0 +1,679 @@
+//! Code generation from a schema through user-editable templates.
+//!
+//! A template is a pair of Handlebars templates: one for the file name and
+//! one for its content. A `schema` template renders once with every table;
+//! a `table` template renders once per table, e.g. one model file each.
+//! Columns come with their type already spelled in TypeScript, Rust, Python
+//! and SQLAlchemy, and the `pascal_case`, `camel_case` and `snake_case`
+//! helpers turn names into identifiers. Output is not HTML-escaped.
+//!
+//! The built-in templates (TypeScript interfaces, Rust serde structs,
+//! SQLAlchemy models) cannot be changed; saving one with a new id makes an
+//! editable copy. User templates live in `code_templates.json`.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+use handlebars::{handlebars_helper, Handlebars};
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Runtime};
+
+use crate::commands::{
+    driver_for, expand_ssh_connection_params, find_connection_by_id,
+    resolve_connection_params_with_id,
+};
+use crate::models::{ForeignKey, TableSchema};
+use crate::sql_schema::{is_array_type, type_kind, TypeKind};
+
+const TEMPLATES_FILE: &str = "code_templates.json";
+const BUILTIN_ID_PREFIX: &str = "builtin-";
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum TemplateScope {
+    /// One file for all tables.
+    #[default]
+    Schema,
+    /// One file per table.
+    Table,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct CodeTemplate {
+    #[serde(default)]
+    pub id: String,
+    pub name: String,
+    /// Language of the output, for highlighting (`typescript`, `rust`, ...).
+    #[serde(default)]
+    pub language: String,
+    #[serde(default)]
+    pub scope: TemplateScope,
+    /// Handlebars template of the file name.
+    pub file_name: String,
+    /// Handlebars template of the file content.
+    pub body: String,
+    #[serde(default)]
+    pub builtin: bool,
+    #[serde(default)]
+    pub created_at: Option<String>,
+    #[serde(default)]
+    pub updated_at: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct GeneratedFile {
+    pub name: String,
+    pub content: String,
+}
+
+/// A column as templates see it.
+#[derive(Debug, Clone, Serialize)]
+pub struct CodegenColumn {
+    pub name: String,
+    pub data_type: String,
+    pub nullable: bool,
+    pub primary_key: bool,
+    pub auto_increment: bool,
+    pub default: Option<String>,
+    pub max_length: Option<u64>,
+    /// `table.column` of a single-column foreign key.
+    pub references: Option<String>,
+    pub ts_type: String,
+    pub rust_type: String,
+    pub python_type: String,
+    pub sqlalchemy_type: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CodegenTable {
+    pub name: String,
+    pub columns: Vec<CodegenColumn>,
+    /// Primary key columns, in column order.
+    pub primary_key: Vec<String>,
+    pub foreign_keys: Vec<ForeignKey>,
+}
+
+/// What a `schema` template renders with.
+#[derive(Debug, Clone, Serialize)]
+pub struct CodegenContext {
+    /// Name of the saved connection.
+    pub connection: String,
+    pub driver: String,
+    pub schema: Option<String>,
+    pub tables: Vec<CodegenTable>,
+}
+
+/// What a `table` template renders with: the table's fields, plus those of
+/// the connection.
+#[derive(Serialize)]
+struct TableContext<'a> {
+    #[serde(flatten)]
+    table: &'a CodegenTable,
+    connection: &'a str,
+    driver: &'a str,
+    schema: Option<&'a str>,
+}
+
+// ---------------------------------------------------------------------------
+// Names (testable; pure)
+// ---------------------------------------------------------------------------
+
+/// The words of an identifier: `userEmail`, `user_email` and `UserEmail`
+/// are all `user`, `email`. A run of capitals is one word (`HTTPServer`).
+fn words(name: &str) -> Vec<String> {
+    let chars: Vec<char> = name.chars().collect();
+    let mut words = Vec::new();
+    let mut current = String::new();
+    for (i, &c) in chars.iter().enumerate() {
+        if !c.is_alphanumeric() {
+            if !current.is_empty() {
+                words.push(std::mem::take(&mut current));
+            }
+            continue;
+        }
+        let boundary = !current.is_empty()
+            && c.is_uppercase()
+            && (!chars[i - 1].is_uppercase()
+                || chars.get(i + 1).is_some_and(|next| next.is_lowercase()));
+        if boundary {
+            words.push(std::mem::take(&mut current));
+        }
+        current.push(c);
+    }
+    if !current.is_empty() {
+        words.push(current);
+    }
+    words
+}
+
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first
+            .to_uppercase()
+            .chain(chars.flat_map(char::to_lowercase))
+            .collect(),
+        None => String::new(),
+    }
+}
+
+/// `user_email` → `UserEmail`.
+pub fn pascal_case(name: &str) -> String {
+    words(name).iter().map(|w| capitalize(w)).collect()
+}
+
+/// `user_email` → `userEmail`.
+pub fn camel_case(name: &str) -> String {
+    words(name)
+        .iter()
+        .enumerate()
+        .map(|(i, w)| {
+            if i == 0 {
+                w.to_lowercase()
+            } else {
+                capitalize(w)
+            }
+        })
+        .collect()
+}
+
+/// `UserEmail` → `user_email`.
+pub fn snake_case(name: &str) -> String {
+    words(name)
+        .iter()
+        .map(|w| w.to_lowercase())
+        .collect::<Vec<_>>()
+        .join("_")
+}
+
+// ---------------------------------------------------------------------------
+// Types (testable; pure)
+// ---------------------------------------------------------------------------
+
+/// The TypeScript type of a column's values as the app reads them.
+pub fn ts_type(data_type: &str, nullable: bool) -> String {
+    let base = match type_kind(data_type) {
+        Some(TypeKind::Bool) => "boolean",
+        Some(
+            TypeKind::TinyInt
+            | TypeKind::SmallInt
+            | TypeKind::Int
+            | TypeKind::BigInt
+            | TypeKind::Double
+            | TypeKind::Real,
+        ) => "number",
+        Some(
+            TypeKind::Decimal
+            | TypeKind::Char
+            | TypeKind::VarChar
+            | TypeKind::Text
+            | TypeKind::Date
+            | TypeKind::Time
+            | TypeKind::DateTime
+            | TypeKind::TimestampTz
+            | TypeKind::Uuid,
+        ) => "string",
+        Some(TypeKind::Binary | TypeKind::Blob) => "Uint8Array",
+        Some(TypeKind::Json) | None => "unknown",
+    };
+    let base = if is_array_type(data_type) {
+        format!("{}[]", base)
+    } else {
+        base.to_string()
+    };
+    if nullable {
+        format!("{} | null", base)
+    } else {
+        base
+    }
+}
+
+pub fn rust_type(data_type: &str, nullable: bool) -> String {
+    let base = match type_kind(data_type) {
+        Some(TypeKind::Bool) => "bool",
+        Some(TypeKind::TinyInt) => "i8",
+        Some(TypeKind::SmallInt) => "i16",
+        Some(TypeKind::Int) => "i32",
+        Some(TypeKind::BigInt) => "i64",
+        Some(TypeKind::Decimal) => "rust_decimal::Decimal",
+        Some(TypeKind::Double) => "f64",
+        Some(TypeKind::Real) => "f32",
+        Some(TypeKind::Char | TypeKind::VarChar | TypeKind::Text) | None => "String",
+        Some(TypeKind::Binary | TypeKind::Blob) => "Vec<u8>",
+        Some(TypeKind::Date) => "chrono::NaiveDate",
+        Some(TypeKind::Time) => "chrono::NaiveTime",
+        Some(TypeKind::DateTime) => "chrono::NaiveDateTime",
+        Some(TypeKind::TimestampTz) => "chrono::DateTime<chrono::Utc>",
+        Some(TypeKind::Json) => "serde_json::Value",
+        Some(TypeKind::Uuid) => "uuid::Uuid",
+    };
+    let base = if is_array_type(data_type) {
+        format!("Vec<{}>", base)
+    } else {
+        base.to_string()
+    };
+    if nullable {
+        format!("Option<{}>", base)
+    } else {
+        base
+    }
+}
+
+pub fn python_type(data_type: &str, nullable: bool) -> String {
+    let base = match type_kind(data_type) {
+        Some(TypeKind::Bool) => "bool",
+        Some(TypeKind::TinyInt | TypeKind::SmallInt | TypeKind::Int | TypeKind::BigInt) => "int",
+        Some(TypeKind::Decimal) => "Decimal",
+        Some(TypeKind::Double | TypeKind::Real) => "float",
+        Some(TypeKind::Char | TypeKind::VarChar | TypeKind::Text) => "str",
+        Some(TypeKind::Binary | TypeKind::Blob) => "bytes",
+        Some(TypeKind::Date) => "date",
+        Some(TypeKind::Time) => "time",
+        Some(TypeKind::DateTime | TypeKind::TimestampTz) => "datetime",
+        Some(TypeKind::Uuid) => "uuid.UUID",
+        Some(TypeKind::Json) | None => "Any",
+    };
+    let base = if is_array_type(data_type) {
+        format!("list[{}]", base)
+    } else {
+        base.to_string()
+    };
+    if nullable {
+        format!("Optional[{}]", base)
+    } else {
+        base
+    }
+}
+
+/// The SQLAlchemy column type, e.g. `String(255)`.
+pub fn sqlalchemy_type(data_type: &str, max_length: Option<u64>) -> String {
+    let base = match type_kind(data_type) {
+        Some(TypeKind::Bool) => "Boolean".to_string(),
+        Some(TypeKind::TinyInt | TypeKind::SmallInt) => "SmallInteger".to_string(),
+        Some(TypeKind::Int) => "Integer".to_string(),
+        Some(TypeKind::BigInt) => "BigInteger".to_string(),
+        Some(TypeKind::Decimal) => "Numeric".to_string(),
+        Some(TypeKind::Double | TypeKind::Real) => "Float".to_string(),
+        Some(TypeKind::Char | TypeKind::VarChar) => match max_length {
+            Some(length) => format!("String({})", length),
+            None => "String".to_string(),
+        },
+        Some(TypeKind::Text) => "Text".to_string(),
+        Some(TypeKind::Binary | TypeKind::Blob) => "LargeBinary".to_string(),
+        Some(TypeKind::Date) => "Date".to_string(),
+        Some(TypeKind::Time) => "Time".to_string(),
+        Some(TypeKind::DateTime) => "DateTime".to_string(),
+        Some(TypeKind::TimestampTz) => "DateTime(timezone=True)".to_string(),
+        Some(TypeKind::Json) => "JSON".to_string(),
+        Some(TypeKind::Uuid) => "Uuid".to_string(),
+        None => "String".to_string(),
+    };
+    if is_array_type(data_type) {
+        format!("ARRAY({})", base)
+    } else {
+        base
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Rendering (testable; pure)
+// ---------------------------------------------------------------------------
+
+/// The render context of `tables`.
+pub fn build_context(
+    connection: &str,
+    driver: &str,
+    schema: Option<&str>,
+    tables: Vec<TableSchema>,
+) -> CodegenContext {
+    let tables = tables
+        .into_iter()
+        .map(|table| {
+            // Composite keys cannot be written as one column's reference.
+            let mut key_columns: HashMap<&str, usize> = HashMap::new();
+            for fk in &table.foreign_keys {
+                *key_columns.entry(fk.name.as_str()).or_default() += 1;
+            }
+            let references: HashMap<&str, String> = table
+                .foreign_keys
+                .iter()
+                .filter(|fk| key_columns[fk.name.as_str()] == 1)
+                .map(|fk| {
+                    (
+                        fk.column_name.as_str(),
+                        format!("{}.{}", fk.ref_table, fk.ref_column),
+                    )
+                })
+                .collect();
+
+            let columns = table
+                .columns
+                .iter()
+                .map(|c| CodegenColumn {
+                    name: c.name.clone(),
+                    data_type: c.data_type.clone(),
+                    nullable: c.is_nullable,
+                    primary_key: c.is_pk,
+                    auto_increment: c.is_auto_increment,
+                    default: c.default_value.clone(),
+                    max_length: c.character_maximum_length,
+                    references: references.get(c.name.as_str()).cloned(),
+                    ts_type: ts_type(&c.data_type, c.is_nullable),
+                    rust_type: rust_type(&c.data_type, c.is_nullable),
+                    python_type: python_type(&c.data_type, c.is_nullable),
+                    sqlalchemy_type: sqlalchemy_type(&c.data_type, c.character_maximum_length),
+                })
+                .collect();
+            CodegenTable {
+                primary_key: table
+                    .columns
+                    .iter()
+                    .filter(|c| c.is_pk)
+                    .map(|c| c.name.clone())
+                    .collect(),
+                name: table.name,
+                columns,
+                foreign_keys: table.foreign_keys,
+            }
+        })
+        .collect();
+    CodegenContext {
+        connection: connection.to_string(),
+        driver: driver.to_string(),
+        schema: schema.map(str::to_string),
+        tables,
+    }
+}
+
+handlebars_helper!(pascal_case_helper: |name: str| pascal_case(name));
+handlebars_helper!(camel_case_helper: |name: str| camel_case(name));
+handlebars_helper!(snake_case_helper: |name: str| snake_case(name));
+
+/// A registry holding the file name and body templates of `template`.
+fn compile(template: &CodeTemplate) -> Result<Handlebars<'static>, String> {
+    let mut hb = Handlebars::new();
+    hb.register_escape_fn(handlebars::no_escape);
+    hb.register_helper("pascal_case", Box::new(pascal_case_helper));
+    hb.register_helper("camel_case", Box::new(camel_case_helper));
+    hb.register_helper("snake_case", Box::new(snake_case_helper));
+    hb.register_template_string("file_name", &template.file_name)
+        .map_err(|e| format!("Invalid file name template: {}", e))?;
+    hb.register_template_string("body", &template.body)
+        .map_err(|e| format!("Invalid template: {}", e))?;
+    Ok(hb)
+}
+
+fn render_file<T: Serialize>(hb: &Handlebars, data: &T) -> Result<GeneratedFile, String> {
+    let name = hb
+        .render("file_name", data)
+        .map_err(|e| format!("Failed to render file name: {}", e))?;
+    let content = hb
+        .render("body", data)
+        .map_err(|e| format!("Failed to render template: {}", e))?;
+    Ok(GeneratedFile {
+        name: name.trim().to_string(),
+        content,
+    })
+}
+
+/// Render `template` with `context`: one file, or one per table.
+pub fn render(
+    template: &CodeTemplate,
+    context: &CodegenContext,
+) -> Result<Vec<GeneratedFile>, String> {
+    let hb = compile(template)?;
+    match template.scope {
+        TemplateScope::Schema => Ok(vec![render_file(&hb, context)?]),
+        TemplateScope::Table => context
+            .tables
+            .iter()
+            .map(|table| {
+                render_file(
+                    &hb,
+                    &TableContext {
+                        table,
+                        connection: &context.connection,
+                        driver: &context.driver,
+                        schema: context.schema.as_deref(),
+                    },
+                )
+            })
+            .collect(),
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Built-in templates
+// ---------------------------------------------------------------------------
+
+const TYPESCRIPT_BODY: &str = r#"// Generated from {{connection}}{{#if schema}} ({{schema}}){{/if}}.
+{{#each tables}}
+
+export interface {{pascal_case name}} {
+{{#each columns}}
+  {{name}}: {{ts_type}};
+{{/each}}
+}
+{{/each}}
+"#;
+
+const RUST_BODY: &str = r#"// Generated from {{connection}}{{#if schema}} ({{schema}}){{/if}}.
+use serde::{Deserialize, Serialize};
+{{#each tables}}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct {{pascal_case name}} {
+{{#each columns}}
+{{#if (ne (snake_case name) name)}}
+    #[serde(rename = "{{name}}")]
+{{/if}}
+    pub {{snake_case name}}: {{rust_type}},
+{{/each}}
+}
+{{/each}}
+"#;
+
+const SQLALCHEMY_BODY: &str = r#"# Generated from {{connection}}{{#if schema}} ({{schema}}){{/if}}.
+import uuid
+from datetime import date, datetime, time
+from decimal import Decimal
+from typing import Any, Optional
+
+from sqlalchemy import (
+    ARRAY, JSON, BigInteger, Boolean, Date, DateTime, Float, ForeignKey, Integer,
+    LargeBinary, Numeric, SmallInteger, String, Text, Time, Uuid,
+)
+from sqlalchemy.orm import DeclarativeBase, Mapped, mapped_column
+
+
+class Base(DeclarativeBase):
+    pass
+{{#each tables}}
+
+
+class {{pascal_case name}}(Base):
+    __tablename__ = "{{name}}"
+
+{{#each columns}}
+    {{snake_case name}}: Mapped[{{python_type}}] = mapped_column({{#if (ne (snake_case name) name)}}"{{name}}", {{/if}}{{sqlalchemy_type}}{{#if references}}, ForeignKey("{{references}}"){{/if}}{{#if primary_key}}, primary_key=True{{/if}})
+{{/each}}
+{{/each}}
+"#;
+
+fn builtin(id: &str, name: &str, language: &str, file_name: &str, body: &str) -> CodeTemplate {
+    CodeTemplate {
+        id: format!("{}{}", BUILTIN_ID_PREFIX, id),
+        name: name.to_string(),
+        language: language.to_string(),
+        scope: TemplateScope::Schema,
+        file_name: file_name.to_string(),
+        body: body.to_string(),
+        builtin: true,
+        created_at: None,
+        updated_at: None,
+    }
+}
+
+pub fn builtin_templates() -> Vec<CodeTemplate> {
+    vec![
+        builtin(
+            "typescript",
+            "TypeScript interfaces",
+            "typescript",
+            "types.ts",
+            TYPESCRIPT_BODY,
+        ),
+        builtin(
+            "rust",
+            "Rust structs (serde)",
+            "rust",
+            "models.rs",
+            RUST_BODY,
+        ),
+        builtin(
+            "sqlalchemy",
+            "SQLAlchemy models",
+            "python",
+            "models.py",
+            SQLALCHEMY_BODY,
+        ),
+    ]
+}
+
+// ---------------------------------------------------------------------------
+// Storage (testable; take a directory)
+// ---------------------------------------------------------------------------
+
+pub fn read_templates_in(dir: &Path) -> Vec<CodeTemplate> {
+    fs::read_to_string(dir.join(TEMPLATES_FILE))
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn write_templates_in(dir: &Path, templates: &[CodeTemplate]) -> Result<(), String> {
+    fs::create_dir_all(dir).map_err(|e| format!("Failed to create config dir: {}", e))?;
+    let json = serde_json::to_string_pretty(templates)
+        .map_err(|e| format!("Failed to serialize code templates: {}", e))?;
+    fs::write(dir.join(TEMPLATES_FILE), json)
+        .map_err(|e| format!("Failed to save code templates: {}", e))
+}
+
+/// Insert or replace `template`, matched by id. The template must compile;
+/// one without an id gets a fresh one.
+pub fn upsert_template_in(dir: &Path, mut template: CodeTemplate) -> Result<CodeTemplate, String> {
+    if template.id.starts_with(BUILTIN_ID_PREFIX) {
+        return Err("Built-in code templates cannot be changed".to_string());
+    }
+    template.name = template.name.trim().to_string();
+    if template.name.is_empty() {
+        return Err("Code template name cannot be empty".to_string());
+    }
+    template.file_name = template.file_name.trim().to_string();
+    if template.file_name.is_empty() {
+        return Err("Code template file name cannot be empty".to_string());
+    }
+    compile(&template)?;
+    template.builtin = false;
+
+    let mut templates = read_templates_in(dir);
+    let now = chrono::Utc::now().to_rfc3339();
+    if template.id.is_empty() {
+        template.id = uuid::Uuid::new_v4().to_string();
+    }
+    template.updated_at = Some(now.clone());
+
+    match templates.iter_mut().find(|t| t.id == template.id) {
+        Some(existing) => {
+            template.created_at = existing.created_at.clone();
+            *existing = template.clone();
+        }
+        None => {
+            template.created_at = Some(now);
+            templates.push(template.clone());
+        }
+    }
+    write_templates_in(dir, &templates)?;
+    Ok(template)
+}
+
+pub fn delete_template_in(dir: &Path, id: &str) -> Result<(), String> {
+    if id.starts_with(BUILTIN_ID_PREFIX) {
+        return Err("Built-in code templates cannot be deleted".to_string());
+    }
+    let mut templates = read_templates_in(dir);
+    let before = templates.len();
+    templates.retain(|t| t.id != id);
+    if templates.len() == before {
+        return Err(format!("Code template not found: {}", id));
+    }
+    write_templates_in(dir, &templates)
+}
+
+// ---------------------------------------------------------------------------
+// Tauri commands
+// ---------------------------------------------------------------------------
+
+/// The built-in templates, then the saved ones.
+#[tauri::command]
+pub fn list_code_templates<R: Runtime>(app: AppHandle<R>) -> Result<Vec<CodeTemplate>, String> {
+    let mut templates = builtin_templates();
+    templates.extend(read_templates_in(&crate::paths::app_config_dir(&app)?));
+    Ok(templates)
+}
+
+#[tauri::command]
+pub fn save_code_template<R: Runtime>(
+    app: AppHandle<R>,
+    template: CodeTemplate,
+) -> Result<CodeTemplate, String> {
+    upsert_template_in(&crate::paths::app_config_dir(&app)?, template)
+}
+
+#[tauri::command]
+pub fn delete_code_template<R: Runtime>(app: AppHandle<R>, id: String) -> Result<(), String> {
+    delete_template_in(&crate::paths::app_config_dir(&app)?, &id)
+}
+
+/// Render `template` for the tables of `schema` (all of them, or those
+/// named in `tables`). The template need not be saved, so the editor can
+/// preview changes.
+#[tauri::command]
+pub async fn generate_code<R: Runtime>(
+    app: AppHandle<R>,
+    connection_id: String,
+    template: CodeTemplate,
+    schema: Option<String>,
+    tables: Option<Vec<String>>,
+) -> Result<Vec<GeneratedFile>, String> {
+    let saved_conn = find_connection_by_id(&app, &connection_id)?;
+    let expanded_params = expand_ssh_connection_params(&app, &saved_conn.params).await?;
+    let params = resolve_connection_params_with_id(&expanded_params, &connection_id)?;
+    let drv = driver_for(&saved_conn.params.driver).await?;
+
+    let snapshot = drv.get_schema_snapshot(&params, schema.as_deref()).await?;
+    let names = crate::er_snapshot::select_tables(
+        snapshot.iter().map(|table| table.name.clone()).collect(),
+        tables.as_deref(),
+    );
+    let selected: Vec<TableSchema> = snapshot
+        .into_iter()
+        .filter(|table| names.contains(&table.name))
+        .collect();
+    log::info!(
+        "Generating code from {} table(s) on connection {} with template {}",
+        selected.len(),
+        connection_id,
+        template.name
+    );
+
+    let context = build_context(
+        &saved_conn.name,
+        &saved_conn.params.driver,
+        schema.as_deref(),
+        selected,
+    );
+    render(&template, &context)
+}
@@ -0,0 +1,187 @@
+#[cfg(test)]
+mod tests {
+    use crate::codegen::{
+        build_context, builtin_templates, camel_case, delete_template_in, pascal_case, python_type,
+        read_templates_in, render, rust_type, snake_case, sqlalchemy_type, ts_type,
+        upsert_template_in, CodeTemplate, CodegenContext, TemplateScope,
+    };
+    use crate::models::{ForeignKey, TableColumn, TableSchema};
+    use tempfile::TempDir;
+
+    fn column(name: &str, data_type: &str, is_pk: bool, is_nullable: bool) -> TableColumn {
+        TableColumn {
+            name: name.to_string(),
+            data_type: data_type.to_string(),
+            is_pk,
+            is_nullable,
+            is_auto_increment: is_pk,
+            default_value: None,
+            character_maximum_length: None,
+        }
+    }
+
+    fn context() -> CodegenContext {
+        let users = TableSchema {
+            name: "user_accounts".to_string(),
+            columns: vec![
+                column("id", "INT", true, false),
+                column("emailAddress", "VARCHAR", false, false),
+                column("created_at", "TIMESTAMP", false, true),
+            ],
+            foreign_keys: Vec::new(),
+        };
+        let orders = TableSchema {
+            name: "orders".to_string(),
+            columns: vec![
+                column("id", "BIGINT", true, false),
+                column("user_id", "INT", false, false),
+            ],
+            foreign_keys: vec![ForeignKey {
+                name: "fk_orders_user".to_string(),
+                column_name: "user_id".to_string(),
+                ref_table: "user_accounts".to_string(),
+                ref_column: "id".to_string(),
+                on_delete: None,
+                on_update: None,
+            }],
+        };
+        build_context("Local", "postgres", Some("public"), vec![users, orders])
+    }
+
+    fn template(scope: TemplateScope, file_name: &str, body: &str) -> CodeTemplate {
+        CodeTemplate {
+            id: String::new(),
+            name: "Custom".to_string(),
+            language: String::new(),
+            scope,
+            file_name: file_name.to_string(),
+            body: body.to_string(),
+            builtin: false,
+            created_at: None,
+            updated_at: None,
+        }
+    }
+
+    #[test]
+    fn names_change_case() {
+        assert_eq!(pascal_case("user_accounts"), "UserAccounts");
+        assert_eq!(pascal_case("HTTPServer"), "HttpServer");
+        assert_eq!(camel_case("created-at"), "createdAt");
+        assert_eq!(camel_case("UserID"), "userId");
+        assert_eq!(snake_case("emailAddress"), "email_address");
+        assert_eq!(snake_case("Order Lines2"), "order_lines2");
+    }
+
+    #[test]
+    fn column_types_per_language() {
+        assert_eq!(ts_type("INTEGER", false), "number");
+        assert_eq!(ts_type("TEXT[]", true), "string[] | null");
+        assert_eq!(ts_type("jsonb", false), "unknown");
+        assert_eq!(rust_type("BIGINT", true), "Option<i64>");
+        assert_eq!(rust_type("uuid", false), "uuid::Uuid");
+        assert_eq!(rust_type("GEOMETRY", false), "String");
+        assert_eq!(python_type("NUMERIC(10,2)", true), "Optional[Decimal]");
+        assert_eq!(python_type("BOOLEAN", false), "bool");
+        assert_eq!(sqlalchemy_type("VARCHAR", Some(80)), "String(80)");
+        assert_eq!(
+            sqlalchemy_type("TIMESTAMP WITH TIME ZONE", None),
+            "DateTime(timezone=True)"
+        );
+    }
+
+    #[test]
+    fn builtin_typescript_renders_interfaces() {
+        let typescript = builtin_templates()
+            .into_iter()
+            .find(|t| t.id == "builtin-typescript")
+            .unwrap();
+        let files = render(&typescript, &context()).unwrap();
+        assert_eq!(files.len(), 1);
+        assert_eq!(files[0].name, "types.ts");
+        assert_eq!(
+            files[0].content,
+            "// Generated from Local (public).
+
+export interface UserAccounts {
+  id: number;
+  emailAddress: string;
+  created_at: string | null;
+}
+
+export interface Orders {
+  id: number;
+  user_id: number;
+}
+"
+        );
+    }
+
+    #[test]
+    fn builtins_render_renames_and_references() {
+        let context = context();
+        let by_id = |id: &str| {
+            let template = builtin_templates()
+                .into_iter()
+                .find(|t| t.id == id)
+                .unwrap();
+            render(&template, &context).unwrap().remove(0).content
+        };
+
+        let rust = by_id("builtin-rust");
+        assert!(rust
+            .contains("    #[serde(rename = \"emailAddress\")]\n    pub email_address: String,\n"));
+        assert!(rust.contains("    pub created_at: Option<chrono::NaiveDateTime>,\n"));
+
+        let python = by_id("builtin-sqlalchemy");
+        assert!(python.contains(
+            "    user_id: Mapped[int] = mapped_column(Integer, ForeignKey(\"user_accounts.id\"))\n"
+        ));
+        assert!(
+            python.contains("    id: Mapped[int] = mapped_column(BigInteger, primary_key=True)\n")
+        );
+    }
+
+    #[test]
+    fn table_templates_render_one_file_per_table() {
+        let template = template(
+            TemplateScope::Table,
+            "{{snake_case name}}.md",
+            "# {{name}} <{{driver}}>\n{{#each primary_key}}{{this}}{{/each}}\n",
+        );
+        let files = render(&template, &context()).unwrap();
+        let names: Vec<&str> = files.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(names, ["user_accounts.md", "orders.md"]);
+        // Not HTML-escaped.
+        assert_eq!(files[1].content, "# orders <postgres>\nid\n");
+    }
+
+    #[test]
+    fn broken_templates_are_reported() {
+        let broken = template(TemplateScope::Schema, "out.txt", "{{#each tables}}");
+        let error = render(&broken, &context()).unwrap_err();
+        assert!(error.starts_with("Invalid template"), "{}", error);
+    }
+
+    #[test]
+    fn templates_are_stored_and_builtins_protected() {
+        let dir = TempDir::new().unwrap();
+        let saved = upsert_template_in(
+            dir.path(),
+            template(TemplateScope::Schema, " out.txt ", "{{connection}}"),
+        )
+        .unwrap();
+        assert!(!saved.id.is_empty());
+        assert_eq!(saved.file_name, "out.txt");
+        assert_eq!(read_templates_in(dir.path()), vec![saved.clone()]);
+
+        let invalid = template(TemplateScope::Schema, "out.txt", "{{#if}}");
+        assert!(upsert_template_in(dir.path(), invalid).is_err());
+        let mut builtin = builtin_templates().remove(0);
+        builtin.name = "Changed".to_string();
+        assert!(upsert_template_in(dir.path(), builtin).is_err());
+        assert!(delete_template_in(dir.path(), "builtin-rust").is_err());
+
+        delete_template_in(dir.path(), &saved.id).unwrap();
+        assert!(read_templates_in(dir.path()).is_empty());
+    }
+}
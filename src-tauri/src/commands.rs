@@ -2,7 +2,7 @@ use std::collections::HashMap;
 use std::fs;
 use std::path::PathBuf;
 use std::sync::{Arc, Mutex};
-use tauri::{AppHandle, Manager, Runtime, State};
+use tauri::{AppHandle, Manager, Runtime, State, WebviewWindow};
 use tokio::task::AbortHandle;
 use urlencoding::encode;
 use uuid::Uuid;
@@ -11,21 +11,21 @@ use crate::credential_cache;
 use crate::keychain_utils;
 use crate::models::{
     BatchStatementResult, ColumnDefinition, ConnectionGroup, ConnectionParams, ConnectionsFile,
-    ExplainPlan, ExportPayload, ForeignKey, Index, QueryResult, RoutineInfo, RoutineParameter,
-    SavedConnection, SshConnection, SshConnectionInput, SshTestParams, TableColumn, TableInfo,
-    TestConnectionRequest, TriggerInfo,
+    ExplainPlan, ExportPayload, ForeignKey, Index, QueryResult, ReplicaEndpoint, RoutineInfo,
+    RoutineParameter, SavedConnection, SshConnection, SshConnectionInput, SshTestParams,
+    TableColumn, TableInfo, TestConnectionRequest, TriggerInfo,
 };
 use crate::persistence;
 use crate::ssh_tunnel::{get_tunnels, SshTunnel};
 
 // Constants
 /// Resolve the driver from the registry or return a descriptive error.
-async fn driver_for(
+pub(crate) async fn driver_for(
     id: &str,
 ) -> Result<std::sync::Arc<dyn crate::drivers::driver_trait::DatabaseDriver>, String> {
     crate::drivers::registry::get_driver(id)
         .await
-        .ok_or_else(|| format!("Unsupported driver: {}", id))
+        .ok_or_else(|| crate::i18n::t_with("errors.unsupportedDriver", &[("driver", id)]))
 }
 
 const DEFAULT_MYSQL_PORT: u16 = 3306;
@@ -83,10 +83,25 @@ pub(crate) fn unregister_abort_handle(
     }
 }
 
+/// Abort every registered task across all slots and clear the map. Used
+/// when the app shuts down. Returns how many handles were aborted.
+pub(crate) fn abort_all_handles(handles: &Mutex<AbortHandleMap>) -> usize {
+    let drained: Vec<Arc<AbortHandle>> = handles
+        .lock()
+        .unwrap()
+        .drain()
+        .flat_map(|(_, entry)| entry)
+        .collect();
+    for handle in &drained {
+        handle.abort();
+    }
+    drained.len()
+}
+
 /// Trims trailing semicolons and normalises Unicode smart quotes that some
 /// editors insert when the user pastes a query. Called on every query the
 /// UI hands off to a driver.
-fn sanitize_user_query(query: &str) -> String {
+pub(crate) fn sanitize_user_query(query: &str) -> String {
     query
         .trim()
         .trim_end_matches(';')
@@ -108,7 +123,10 @@ async fn get_ssh_connection_by_id<R: Runtime>(
 ) -> Result<SshConnection, String> {
     let path = get_ssh_config_path(app)?;
     if !path.exists() {
-        return Err(format!("SSH connection with ID {} not found", ssh_id));
+        return Err(crate::i18n::t_with(
+            "errors.sshConnectionWithIdNotFound",
+            &[("id", ssh_id)],
+        ));
     }
 
     // File I/O off the Tokio executor thread
@@ -123,7 +141,9 @@ async fn get_ssh_connection_by_id<R: Runtime>(
         .unwrap_or_default()
         .into_iter()
         .find(|s| s.id == ssh_id)
-        .ok_or_else(|| format!("SSH connection with ID {} not found", ssh_id))?;
+        .ok_or_else(|| {
+            crate::i18n::t_with("errors.sshConnectionWithIdNotFound", &[("id", ssh_id)])
+        })?;
 
     // Backward compat: determine auth_type if absent (mirrors get_ssh_connections logic)
     if ssh.auth_type.is_none() {
@@ -224,9 +244,15 @@ pub fn resolve_connection_params(params: &ConnectionParams) -> Result<Connection
         return Ok(params.clone());
     }
 
-    let ssh_host = params.ssh_host.as_deref().ok_or("Missing SSH Host")?;
+    let ssh_host = params
+        .ssh_host
+        .as_deref()
+        .ok_or_else(|| crate::i18n::t("errors.missingSshHost"))?;
     let ssh_port = params.ssh_port.unwrap_or(22);
-    let ssh_user = params.ssh_user.as_deref().ok_or("Missing SSH User")?;
+    let ssh_user = params
+        .ssh_user
+        .as_deref()
+        .ok_or_else(|| crate::i18n::t("errors.missingSshUser"))?;
     let remote_host = params.host.as_deref().unwrap_or("localhost");
     let remote_port = params.port.unwrap_or(DEFAULT_MYSQL_PORT);
 
@@ -280,6 +306,40 @@ pub fn resolve_connection_params(params: &ConnectionParams) -> Result<Connection
     Ok(new_params)
 }
 
+/// Key of the cached SSH tunnel `params` would reuse; `None` without SSH.
+/// Connections to the same database through the same SSH host share it.
+pub fn ssh_tunnel_key(params: &ConnectionParams) -> Option<String> {
+    if !params.ssh_enabled.unwrap_or(false) {
+        return None;
+    }
+    let (Some(ssh_host), Some(ssh_user)) = (params.ssh_host.as_deref(), params.ssh_user.as_deref())
+    else {
+        return None;
+    };
+    Some(build_tunnel_map_key(
+        ssh_user,
+        ssh_host,
+        params.ssh_port.unwrap_or(22),
+        params.host.as_deref().unwrap_or("localhost"),
+        params.port.unwrap_or(DEFAULT_MYSQL_PORT),
+    ))
+}
+
+/// Stop and forget the SSH tunnel `params` would reuse, so the next
+/// `resolve_connection_params` opens a new one. For tunnels whose SSH
+/// session dropped while the tunnel stayed cached. The tunnel may be shared
+/// (see [`ssh_tunnel_key`]): callers check that no other open connection
+/// uses it.
+pub fn close_ssh_tunnel(params: &ConnectionParams) {
+    let Some(map_key) = ssh_tunnel_key(params) else {
+        return;
+    };
+    if let Some(tunnel) = get_tunnels().lock().unwrap().remove(&map_key) {
+        log::info!("Closing SSH tunnel on port {}", tunnel.local_port);
+        tunnel.stop();
+    }
+}
+
 /// Resolve connection params and set connection_id for stable pooling
 pub fn resolve_connection_params_with_id(
     params: &ConnectionParams,
@@ -291,7 +351,7 @@ pub fn resolve_connection_params_with_id(
 }
 
 pub fn get_config_path<R: Runtime>(app: &AppHandle<R>) -> Result<PathBuf, String> {
-    let config_dir = app.path().app_config_dir().map_err(|e| e.to_string())?;
+    let config_dir = crate::paths::app_config_dir(app)?;
     if !config_dir.exists() {
         fs::create_dir_all(&config_dir).map_err(|e| e.to_string())?;
     }
@@ -299,7 +359,7 @@ pub fn get_config_path<R: Runtime>(app: &AppHandle<R>) -> Result<PathBuf, String
 }
 
 pub fn get_ssh_config_path<R: Runtime>(app: &AppHandle<R>) -> Result<PathBuf, String> {
-    let config_dir = app.path().app_config_dir().map_err(|e| e.to_string())?;
+    let config_dir = crate::paths::app_config_dir(app)?;
     if !config_dir.exists() {
         fs::create_dir_all(&config_dir).map_err(|e| e.to_string())?;
     }
@@ -310,9 +370,14 @@ pub fn find_connection_by_id<R: Runtime>(
     app: &AppHandle<R>,
     id: &str,
 ) -> Result<SavedConnection, String> {
+    // Members of connection templates are not stored in connections.json.
+    if crate::connection_templates::is_member_id(id) {
+        return crate::connection_templates::resolve_member(app, id);
+    }
+
     let path = get_config_path(app)?;
     if !path.exists() {
-        return Err("Connection not found".into());
+        return Err(crate::i18n::t("errors.connectionNotFound"));
     }
     // Use persistence module to properly load connections (handles both old and new formats)
     let conn_file = persistence::load_connections_file(&path)?;
@@ -320,7 +385,7 @@ pub fn find_connection_by_id<R: Runtime>(
         .connections
         .into_iter()
         .find(|c| c.id == id)
-        .ok_or_else(|| "Connection not found".to_string())?;
+        .ok_or_else(|| crate::i18n::t("errors.connectionNotFound"))?;
 
     // Load passwords from keychain if needed, via the in-memory cache.
     // On a warm cache hit this is a HashMap lookup (nanoseconds); on a cold miss
@@ -370,6 +435,10 @@ pub async fn get_schemas<R: Runtime>(
 ) -> Result<Vec<String>, String> {
     log::info!("Fetching schemas for connection: {}", connection_id);
 
+    if let Some(schemas) = crate::prewarm::take_schemas(&connection_id) {
+        return Ok(schemas);
+    }
+
     let saved_conn = find_connection_by_id(&app, &connection_id)?;
     let expanded_params = expand_ssh_connection_params(&app, &saved_conn.params).await?;
     let params = resolve_connection_params_with_id(&expanded_params, &connection_id)?;
@@ -458,17 +527,26 @@ pub async fn get_routine_definition<R: Runtime>(
         .await
 }
 
+/// Every table of `schema` with its columns and foreign keys. `tables`
+/// limits the snapshot to those tables plus, with `depth`, the tables up to
+/// that many foreign keys away from them.
 #[tauri::command]
 pub async fn get_schema_snapshot<R: Runtime>(
     app: AppHandle<R>,
     connection_id: String,
     schema: Option<String>,
+    tables: Option<Vec<String>>,
+    depth: Option<u32>,
 ) -> Result<Vec<crate::models::TableSchema>, String> {
     let saved_conn = find_connection_by_id(&app, &connection_id)?;
     let expanded_params = expand_ssh_connection_params(&app, &saved_conn.params).await?;
     let params = resolve_connection_params_with_id(&expanded_params, &connection_id)?;
     let drv = driver_for(&saved_conn.params.driver).await?;
-    drv.get_schema_snapshot(&params, schema.as_deref()).await
+    let snapshot = drv.get_schema_snapshot(&params, schema.as_deref()).await?;
+    Ok(match tables {
+        Some(tables) => crate::er_snapshot::filter_snapshot(snapshot, &tables, depth.unwrap_or(0)),
+        None => snapshot,
+    })
 }
 
 #[tauri::command]
@@ -477,10 +555,15 @@ pub async fn save_connection<R: Runtime>(
     name: String,
     params: ConnectionParams,
     detect_json_in_text_columns: Option<bool>,
+    protected: Option<bool>,
+    read_replicas: Option<Vec<ReplicaEndpoint>>,
+    connect_on_startup: Option<bool>,
 ) -> Result<SavedConnection, String> {
     log::info!("Saving new connection: {}", name);
+    crate::pool_manager::ensure_auth_method_supported(&params)?;
 
     let path = get_config_path(&app)?;
+    let _lock = crate::config_store::lock(&path)?;
     let mut conn_file = persistence::load_connections_file(&path).unwrap_or_default();
 
     let id = Uuid::new_v4().to_string();
@@ -517,9 +600,12 @@ pub async fn save_connection<R: Runtime>(
         group_id: None,
         sort_order: None,
         detect_json_in_text_columns,
+        protected,
+        read_replicas,
+        connect_on_startup,
     };
     conn_file.connections.push(new_conn.clone());
-    persistence::save_connections_file(&path, &conn_file)?;
+    persistence::write_connections_file(&path, &conn_file)?;
 
     log::info!("Connection saved successfully: {} (ID: {})", name, id);
 
@@ -537,11 +623,13 @@ pub async fn delete_connection<R: Runtime>(app: AppHandle<R>, id: String) -> Res
         return Ok(());
     }
 
+    let lock = crate::config_store::lock(&path)?;
     let mut conn_file = persistence::load_connections_file(&path)?;
 
     let initial_count = conn_file.connections.len();
     conn_file.connections.retain(|c| c.id != id);
     let deleted = conn_file.connections.len() < initial_count;
+    crate::prewarm::forget(&id);
 
     // Attempt to remove passwords from keychain (ignore if not found)
     keychain_utils::delete_db_password(&id).ok();
@@ -551,13 +639,33 @@ pub async fn delete_connection<R: Runtime>(app: AppHandle<R>, id: String) -> Res
     let cache = app.state::<std::sync::Arc<crate::credential_cache::CredentialCache>>();
     credential_cache::invalidate_all_for_connection(&cache, &id);
 
-    persistence::save_connections_file(&path, &conn_file)?;
+    persistence::write_connections_file(&path, &conn_file)?;
+    drop(lock);
 
     // Clean up query history for this connection
     if let Err(e) = crate::query_history::remove_history_for_connection(&app, &id) {
         log::warn!("Failed to remove query history for connection {}: {}", id, e);
     }
 
+    // Clean up captured query plans for this connection
+    if let Err(e) = crate::plan_history::remove_plan_history_for_connection(&app, &id) {
+        log::warn!("Failed to remove plan history for connection {}: {}", id, e);
+    }
+
+    // Clean up table activity for this connection
+    if let Err(e) = crate::table_activity::remove_table_activity_for_connection(&app, &id) {
+        log::warn!(
+            "Failed to remove table activity for connection {}: {}",
+            id,
+            e
+        );
+    }
+
+    // Clean up row bookmarks for this connection
+    if let Err(e) = crate::bookmarks::remove_bookmarks_for_connection(&app, &id) {
+        log::warn!("Failed to remove bookmarks for connection {}: {}", id, e);
+    }
+
     if deleted {
         log::info!("Connection deleted successfully: {}", id);
     } else {
@@ -574,15 +682,20 @@ pub async fn update_connection<R: Runtime>(
     name: String,
     params: ConnectionParams,
     detect_json_in_text_columns: Option<bool>,
+    protected: Option<bool>,
+    read_replicas: Option<Vec<ReplicaEndpoint>>,
+    connect_on_startup: Option<bool>,
 ) -> Result<SavedConnection, String> {
+    crate::pool_manager::ensure_auth_method_supported(&params)?;
     let path = get_config_path(&app)?;
+    let lock = crate::config_store::lock(&path)?;
     let mut conn_file = persistence::load_connections_file(&path)?;
 
     let conn_idx = conn_file
         .connections
         .iter()
         .position(|c| c.id == id)
-        .ok_or("Connection not found")?;
+        .ok_or_else(|| crate::i18n::t("errors.connectionNotFound"))?;
 
     let mut params_to_save = params.clone();
 
@@ -623,6 +736,7 @@ pub async fn update_connection<R: Runtime>(
     let original_group_id = conn_file.connections[conn_idx].group_id.clone();
     let original_sort_order = conn_file.connections[conn_idx].sort_order;
     let original_db_selection = conn_file.connections[conn_idx].params.database.clone();
+    let original_params = conn_file.connections[conn_idx].params.clone();
 
     let updated = SavedConnection {
         id: id.clone(),
@@ -631,11 +745,22 @@ pub async fn update_connection<R: Runtime>(
         group_id: original_group_id,
         sort_order: original_sort_order,
         detect_json_in_text_columns,
+        protected,
+        read_replicas,
+        connect_on_startup,
     };
 
     conn_file.connections[conn_idx] = updated.clone();
+    crate::prewarm::forget(&id);
+
+    persistence::write_connections_file(&path, &conn_file)?;
+    drop(lock);
 
-    persistence::save_connections_file(&path, &conn_file)?;
+    // Read-only is set on the session when a pooled connection opens, so an
+    // open pool must be replaced when the flag changes.
+    if original_params.read_only != params.read_only {
+        crate::pool_manager::close_pool_with_id(&original_params, Some(&id)).await;
+    }
 
     // On single→multi transition, associate existing favorites/history (with no
     // database set) to the original single database name.
@@ -678,13 +803,14 @@ pub async fn duplicate_connection<R: Runtime>(
     id: String,
 ) -> Result<SavedConnection, String> {
     let path = get_config_path(&app)?;
+    let _lock = crate::config_store::lock(&path)?;
     let mut conn_file = persistence::load_connections_file(&path)?;
 
     let original_idx = conn_file
         .connections
         .iter()
         .position(|c| c.id == id)
-        .ok_or("Connection not found")?;
+        .ok_or_else(|| crate::i18n::t("errors.connectionNotFound"))?;
     let mut original = conn_file.connections[original_idx].clone();
 
     let cache = app.state::<std::sync::Arc<crate::credential_cache::CredentialCache>>();
@@ -747,11 +873,14 @@ pub async fn duplicate_connection<R: Runtime>(
         group_id: original.group_id.clone(), // Copy to same group as original
         sort_order: None,                    // Will be placed at end of group
         detect_json_in_text_columns: original.detect_json_in_text_columns,
+        protected: original.protected,
+        read_replicas: original.read_replicas.clone(),
+        connect_on_startup: original.connect_on_startup,
     };
 
     conn_file.connections.push(new_conn.clone());
 
-    persistence::save_connections_file(&path, &conn_file)?;
+    persistence::write_connections_file(&path, &conn_file)?;
 
     let mut returned_conn = new_conn;
     // Return with passwords for frontend consistency
@@ -788,6 +917,7 @@ async fn migrate_ssh_connections<R: Runtime>(app: &AppHandle<R>) -> Result<(), S
     }
 
     // Load connections using persistence (handles both old and new formats)
+    let _conn_lock = crate::config_store::lock(&conn_path)?;
     let mut conn_file = persistence::load_connections_file(&conn_path)?;
     let connections = &conn_file.connections;
 
@@ -803,12 +933,8 @@ async fn migrate_ssh_connections<R: Runtime>(app: &AppHandle<R>) -> Result<(), S
     println!("[Migration] Starting SSH connections migration...");
 
     let ssh_path = get_ssh_config_path(app)?;
-    let mut ssh_connections: Vec<SshConnection> = if ssh_path.exists() {
-        let ssh_content = fs::read_to_string(&ssh_path).map_err(|e| e.to_string())?;
-        serde_json::from_str(&ssh_content).unwrap_or_default()
-    } else {
-        Vec::new()
-    };
+    let _ssh_lock = crate::config_store::lock(&ssh_path)?;
+    let mut ssh_connections = persistence::load_ssh_connections_file(&ssh_path)?;
 
     let mut migrated_connections = Vec::new();
     let mut ssh_connection_map: HashMap<String, String> = HashMap::new(); // (ssh_key -> ssh_id)
@@ -890,12 +1016,11 @@ async fn migrate_ssh_connections<R: Runtime>(app: &AppHandle<R>) -> Result<(), S
     }
 
     // Save migrated SSH connections
-    let ssh_json = serde_json::to_string_pretty(&ssh_connections).map_err(|e| e.to_string())?;
-    fs::write(ssh_path, ssh_json).map_err(|e| e.to_string())?;
+    persistence::write_ssh_connections_file(&ssh_path, &ssh_connections)?;
 
     // Save migrated connections using new format (preserving groups)
     conn_file.connections = migrated_connections;
-    persistence::save_connections_file(&conn_path, &conn_file)?;
+    persistence::write_connections_file(&conn_path, &conn_file)?;
 
     println!(
         "[Migration] Successfully migrated {} SSH connections",
@@ -995,12 +1120,8 @@ pub async fn save_ssh_connection<R: Runtime>(
     ssh: SshConnectionInput,
 ) -> Result<SshConnection, String> {
     let path = get_ssh_config_path(&app)?;
-    let mut ssh_connections: Vec<SshConnection> = if path.exists() {
-        let content = fs::read_to_string(&path).map_err(|e| e.to_string())?;
-        serde_json::from_str(&content).unwrap_or_default()
-    } else {
-        Vec::new()
-    };
+    let _lock = crate::config_store::lock(&path)?;
+    let mut ssh_connections = persistence::load_ssh_connections_file(&path)?;
 
     let id = Uuid::new_v4().to_string();
     let ssh_to_save = SshConnection {
@@ -1037,8 +1158,7 @@ pub async fn save_ssh_connection<R: Runtime>(
     };
 
     ssh_connections.push(ssh_to_save.clone());
-    let json = serde_json::to_string_pretty(&ssh_connections).map_err(|e| e.to_string())?;
-    fs::write(path, json).map_err(|e| e.to_string())?;
+    persistence::write_ssh_connections_file(&path, &ssh_connections)?;
 
     let mut returned_ssh = ssh_to_save;
     returned_ssh.password = ssh.password;
@@ -1054,14 +1174,13 @@ pub async fn update_ssh_connection<R: Runtime>(
     ssh: SshConnectionInput,
 ) -> Result<SshConnection, String> {
     let path = get_ssh_config_path(&app)?;
-    let content = fs::read_to_string(&path).map_err(|e| e.to_string())?;
-    let mut ssh_connections: Vec<SshConnection> =
-        serde_json::from_str(&content).unwrap_or_default();
+    let _lock = crate::config_store::lock(&path)?;
+    let mut ssh_connections = persistence::load_ssh_connections_file(&path)?;
 
     let ssh_idx = ssh_connections
         .iter()
         .position(|s| s.id == id)
-        .ok_or("SSH connection not found")?;
+        .ok_or_else(|| crate::i18n::t("errors.sshConnectionNotFound"))?;
 
     let cache = app.state::<std::sync::Arc<crate::credential_cache::CredentialCache>>();
     if ssh.save_in_keychain.unwrap_or(false) {
@@ -1105,8 +1224,7 @@ pub async fn update_ssh_connection<R: Runtime>(
 
     ssh_connections[ssh_idx] = ssh_to_save.clone();
 
-    let json = serde_json::to_string_pretty(&ssh_connections).map_err(|e| e.to_string())?;
-    fs::write(path, json).map_err(|e| e.to_string())?;
+    persistence::write_ssh_connections_file(&path, &ssh_connections)?;
 
     let mut returned_ssh = ssh_to_save;
     returned_ssh.password = ssh.password;
@@ -1124,9 +1242,8 @@ pub async fn delete_ssh_connection<R: Runtime>(
         return Ok(());
     }
 
-    let content = fs::read_to_string(&path).map_err(|e| e.to_string())?;
-    let mut ssh_connections: Vec<SshConnection> =
-        serde_json::from_str(&content).unwrap_or_default();
+    let _lock = crate::config_store::lock(&path)?;
+    let mut ssh_connections = persistence::load_ssh_connections_file(&path)?;
 
     ssh_connections.retain(|s| s.id != id);
 
@@ -1137,8 +1254,7 @@ pub async fn delete_ssh_connection<R: Runtime>(
     credential_cache::invalidate_ssh_password(&cache, &id);
     credential_cache::invalidate_ssh_key_passphrase(&cache, &id);
 
-    let json = serde_json::to_string_pretty(&ssh_connections).map_err(|e| e.to_string())?;
-    fs::write(path, json).map_err(|e| e.to_string())?;
+    persistence::write_ssh_connections_file(&path, &ssh_connections)?;
     Ok(())
 }
 
@@ -1208,6 +1324,7 @@ pub async fn test_connection<R: Runtime>(
         "Testing connection to database: {}",
         request.params.database
     );
+    crate::pool_manager::ensure_auth_method_supported(&request.params)?;
 
     let mut expanded_params = expand_ssh_connection_params(&app, &request.params).await?;
 
@@ -1252,7 +1369,7 @@ pub async fn test_connection<R: Runtime>(
         "Connection test successful for database: {}",
         request.params.database
     );
-    Ok("Connection successful!".to_string())
+    Ok(crate::i18n::t("messages.connectionSuccessful"))
 }
 
 #[cfg(test)]
@@ -1281,6 +1398,9 @@ mod tests {
             ssh_key_file: None,
             ssh_key_passphrase: None,
             save_in_keychain: None,
+            read_only: false,
+            auth_method: None,
+            pool: None,
             connection_id: None,
         }
     }
@@ -1297,6 +1417,9 @@ mod tests {
             group_id: None,
             sort_order: None,
             detect_json_in_text_columns: None,
+            protected: None,
+            read_replicas: None,
+            connect_on_startup: None,
         }
     }
 
@@ -1366,6 +1489,9 @@ mod tests {
                 ssh_key_file: None,
                 ssh_key_passphrase: None,
                 save_in_keychain: None,
+                read_only: false,
+                auth_method: None,
+                pool: None,
                 connection_id: None,
             }
         }
@@ -1647,6 +1773,9 @@ mod tests {
                 ssh_key_file: Some("/home/user/.ssh/id_rsa".to_string()),
                 ssh_key_passphrase: None,
                 save_in_keychain: None,
+                read_only: false,
+                auth_method: None,
+                pool: None,
                 connection_id: None,
             }
         }
@@ -1735,7 +1864,7 @@ mod tests {
 
     mod cancellation_state {
         use super::super::{
-            cancel_query_impl, register_abort_handle, unregister_abort_handle,
+            abort_all_handles, cancel_query_impl, register_abort_handle, unregister_abort_handle,
             QueryCancellationState,
         };
         use std::sync::Arc;
@@ -1916,6 +2045,30 @@ mod tests {
             assert!(explain_task.await.unwrap_err().is_cancelled());
             assert!(state.handles.lock().unwrap().get("conn-1").is_none());
         }
+
+        #[tokio::test]
+        async fn abort_all_handles_empties_every_slot() {
+            let state = QueryCancellationState::default();
+            let task_a = spawn_sleeper().await;
+            let task_b = spawn_sleeper().await;
+            register_abort_handle(
+                &state.handles,
+                "conn-1".into(),
+                Arc::new(task_a.abort_handle()),
+            );
+            register_abort_handle(
+                &state.handles,
+                "conn-2".into(),
+                Arc::new(task_b.abort_handle()),
+            );
+
+            assert_eq!(abort_all_handles(&state.handles), 2);
+
+            assert!(task_a.await.unwrap_err().is_cancelled());
+            assert!(task_b.await.unwrap_err().is_cancelled());
+            assert!(state.handles.lock().unwrap().is_empty());
+            assert_eq!(abort_all_handles(&state.handles), 0);
+        }
     }
 }
 
@@ -1963,6 +2116,10 @@ pub async fn get_tables<R: Runtime>(
 ) -> Result<Vec<TableInfo>, String> {
     log::info!("Fetching tables for connection: {}", connection_id);
 
+    if let Some(tables) = crate::prewarm::take_tables(&connection_id, schema.as_deref()) {
+        return Ok(tables);
+    }
+
     let saved_conn = find_connection_by_id(&app, &connection_id)?;
     let expanded_params = expand_ssh_connection_params(&app, &saved_conn.params).await?;
     let params = resolve_connection_params_with_id(&expanded_params, &connection_id)?;
@@ -2047,14 +2204,18 @@ pub async fn delete_record<R: Runtime>(
         pk_val
     );
     let saved_conn = find_connection_by_id(&app, &connection_id)?;
+    crate::read_only::ensure_writable(&saved_conn.params)?;
     let expanded_params = expand_ssh_connection_params(&app, &saved_conn.params).await?;
     let mut params = resolve_connection_params_with_id(&expanded_params, &connection_id)?;
     if let Some(db) = database {
         params.database = crate::models::DatabaseSelection::Single(db);
     }
     let drv = driver_for(&saved_conn.params.driver).await?;
-    drv.delete_record(&params, &table, &pk_col, pk_val, schema.as_deref())
-        .await
+    let deleted = drv
+        .delete_record(&params, &table, &pk_col, pk_val, schema.as_deref())
+        .await?;
+    crate::table_activity::track_edit(&app, &connection_id, schema.as_deref(), &table);
+    Ok(deleted)
 }
 
 #[tauri::command]
@@ -2079,6 +2240,7 @@ pub async fn update_record<R: Runtime>(
         pk_val
     );
     let saved_conn = find_connection_by_id(&app, &connection_id)?;
+    crate::read_only::ensure_writable(&saved_conn.params)?;
     let expanded_params = expand_ssh_connection_params(&app, &saved_conn.params).await?;
     let mut params = resolve_connection_params_with_id(&expanded_params, &connection_id)?;
     if let Some(db) = database {
@@ -2086,17 +2248,20 @@ pub async fn update_record<R: Runtime>(
     }
     let max_blob_size = crate::config::get_max_blob_size(&app);
     let drv = driver_for(&saved_conn.params.driver).await?;
-    drv.update_record(
-        &params,
-        &table,
-        &pk_col,
-        pk_val,
-        &col_name,
-        new_val,
-        schema.as_deref(),
-        max_blob_size,
-    )
-    .await
+    let updated = drv
+        .update_record(
+            &params,
+            &table,
+            &pk_col,
+            pk_val,
+            &col_name,
+            new_val,
+            schema.as_deref(),
+            max_blob_size,
+        )
+        .await?;
+    crate::table_activity::track_edit(&app, &connection_id, schema.as_deref(), &table);
+    Ok(updated)
 }
 
 #[tauri::command]
@@ -2325,7 +2490,7 @@ pub async fn insert_record<R: Runtime>(
     data: std::collections::HashMap<String, serde_json::Value>,
     schema: Option<String>,
     database: Option<String>,
-) -> Result<u64, String> {
+) -> Result<crate::models::InsertedRecord, String> {
     let columns: Vec<&str> = data.keys().map(|k| k.as_str()).collect();
     log::info!(
         "Executing query on connection: {} | Query: INSERT INTO {} ({}) VALUES (...)",
@@ -2334,6 +2499,7 @@ pub async fn insert_record<R: Runtime>(
         columns.join(", ")
     );
     let saved_conn = find_connection_by_id(&app, &connection_id)?;
+    crate::read_only::ensure_writable(&saved_conn.params)?;
     let expanded_params = expand_ssh_connection_params(&app, &saved_conn.params).await?;
     let mut params = resolve_connection_params_with_id(&expanded_params, &connection_id)?;
     if let Some(db) = database {
@@ -2341,8 +2507,45 @@ pub async fn insert_record<R: Runtime>(
     }
     let max_blob_size = crate::config::get_max_blob_size(&app);
     let drv = driver_for(&saved_conn.params.driver).await?;
-    drv.insert_record(&params, &table, data, schema.as_deref(), max_blob_size)
-        .await
+    let inserted = drv
+        .insert_record_returning(&params, &table, data, schema.as_deref(), max_blob_size)
+        .await?;
+    crate::table_activity::track_edit(&app, &connection_id, schema.as_deref(), &table);
+    Ok(inserted)
+}
+
+/// Insert many rows in as few statements as the driver allows (multi-row
+/// paste in the data grid). Rows that fail are reported by index in
+/// `errors`; the others are still inserted.
+#[tauri::command]
+pub async fn insert_records_batch<R: Runtime>(
+    app: AppHandle<R>,
+    connection_id: String,
+    table: String,
+    rows: Vec<std::collections::HashMap<String, serde_json::Value>>,
+    schema: Option<String>,
+    database: Option<String>,
+) -> Result<crate::models::BatchInsertResult, String> {
+    log::info!(
+        "Executing query on connection: {} | Query: INSERT INTO {} ... ({} rows)",
+        connection_id,
+        table,
+        rows.len()
+    );
+    let saved_conn = find_connection_by_id(&app, &connection_id)?;
+    crate::read_only::ensure_writable(&saved_conn.params)?;
+    let expanded_params = expand_ssh_connection_params(&app, &saved_conn.params).await?;
+    let mut params = resolve_connection_params_with_id(&expanded_params, &connection_id)?;
+    if let Some(db) = database {
+        params.database = crate::models::DatabaseSelection::Single(db);
+    }
+    let max_blob_size = crate::config::get_max_blob_size(&app);
+    let drv = driver_for(&saved_conn.params.driver).await?;
+    let result = drv
+        .insert_records_batch(&params, &table, rows, schema.as_deref(), max_blob_size)
+        .await?;
+    crate::table_activity::track_edit(&app, &connection_id, schema.as_deref(), &table);
+    Ok(result)
 }
 
 pub(crate) fn cancel_query_impl(
@@ -2354,7 +2557,7 @@ pub(crate) fn cancel_query_impl(
         handles.remove(connection_id).unwrap_or_default()
     };
     if entries.is_empty() {
-        return Err("No running query found".into());
+        return Err(crate::i18n::t("errors.noRunningQuery"));
     }
     for handle in entries {
         handle.abort();
@@ -2362,23 +2565,88 @@ pub(crate) fn cancel_query_impl(
     Ok(())
 }
 
+/// Cancel the calling window's queries on `connection_id`.
 #[tauri::command]
-pub async fn cancel_query(
+pub async fn cancel_query<R: Runtime>(
+    window: WebviewWindow<R>,
     state: State<'_, QueryCancellationState>,
     connection_id: String,
 ) -> Result<(), String> {
-    cancel_query_impl(&state, &connection_id)
+    let cancel_key = crate::window_state::cancellation_key(window.label(), &connection_id);
+    cancel_query_impl(&state, &cancel_key)
+}
+
+/// Start a transaction on a dedicated connection. Until it is committed or
+/// rolled back, queries and record edits on `connection_id` run on that
+/// connection instead of the pool.
+#[tauri::command]
+pub async fn begin_transaction<R: Runtime>(
+    app: AppHandle<R>,
+    connection_id: String,
+) -> Result<(), String> {
+    let saved_conn = find_connection_by_id(&app, &connection_id)?;
+    let expanded_params = expand_ssh_connection_params(&app, &saved_conn.params).await?;
+    let params = resolve_connection_params_with_id(&expanded_params, &connection_id)?;
+    crate::pool_manager::begin_transaction(&params).await
+}
+
+#[tauri::command]
+pub async fn commit_transaction(connection_id: String) -> Result<(), String> {
+    crate::pool_manager::finish_transaction(&connection_id, true).await
+}
+
+#[tauri::command]
+pub async fn rollback_transaction(connection_id: String) -> Result<(), String> {
+    crate::pool_manager::finish_transaction(&connection_id, false).await
 }
 
 #[tauri::command]
 pub async fn execute_query<R: Runtime>(
     app: AppHandle<R>,
+    window: WebviewWindow<R>,
+    state: State<'_, QueryCancellationState>,
+    connection_id: String,
+    query: String,
+    limit: Option<u32>,
+    page: Option<u32>,
+    schema: Option<String>,
+    table: Option<String>,
+    allow_costly: Option<bool>,
+    use_primary: Option<bool>,
+    saved_query_id: Option<String>,
+) -> Result<QueryResult, String> {
+    execute_query_in_scope(
+        app,
+        window.label(),
+        state,
+        connection_id,
+        query,
+        limit,
+        page,
+        schema,
+        table,
+        allow_costly,
+        use_primary,
+        saved_query_id,
+    )
+    .await
+}
+
+/// [`execute_query`], cancellable under `cancel_scope` (see
+/// [`crate::window_state::cancellation_key`]).
+pub(crate) async fn execute_query_in_scope<R: Runtime>(
+    app: AppHandle<R>,
+    cancel_scope: &str,
     state: State<'_, QueryCancellationState>,
     connection_id: String,
     query: String,
     limit: Option<u32>,
     page: Option<u32>,
     schema: Option<String>,
+    table: Option<String>,
+    allow_costly: Option<bool>,
+    use_primary: Option<bool>,
+    saved_query_id: Option<String>,
 ) -> Result<QueryResult, String> {
     log::info!(
         "Executing query on connection: {} | Query: {}",
@@ -2389,43 +2657,235 @@ pub async fn execute_query<R: Runtime>(
     let sanitized_query = sanitize_user_query(&query);
 
     let saved_conn = find_connection_by_id(&app, &connection_id)?;
-    let expanded_params = expand_ssh_connection_params(&app, &saved_conn.params).await?;
-    let params = resolve_connection_params_with_id(&expanded_params, &connection_id)?;
+    crate::read_only::ensure_query_allowed(&saved_conn.params, &sanitized_query)?;
+    // Reads may go to a replica. Temporary objects and open transactions
+    // only exist on the writer, so a session holding either keeps reading
+    // from it.
+    let use_primary = use_primary.unwrap_or(false)
+        || crate::session_objects::has_objects(&connection_id)
+        || crate::pool_manager::has_transaction(&connection_id).await;
+    let (routed_params, pool_id) = crate::pool_manager::route_query(
+        &saved_conn,
+        &connection_id,
+        &sanitized_query,
+        use_primary,
+    );
+    let expanded_params = expand_ssh_connection_params(&app, &routed_params).await?;
+    let params = resolve_connection_params_with_id(&expanded_params, &pool_id)?;
 
     let drv = driver_for(&saved_conn.params.driver).await?;
-    let task = tokio::spawn(async move {
-        drv.execute_query(
-            &params,
+
+    // When browsing a table, append the user's computed columns to the select list
+    let sanitized_query = match table.as_deref() {
+        Some(table) => {
+            let computed = crate::preferences::load_computed_columns(
+                &connection_id,
+                schema.as_deref(),
+                table,
+            )?;
+            crate::preferences::apply_computed_columns(
+                &drv.manifest().capabilities.identifier_quote,
+                &sanitized_query,
+                &computed,
+            )
+        }
+        None => sanitized_query,
+    };
+
+    let data_rules = match table.as_deref() {
+        Some(table) => {
+            crate::data_rules::load_data_rules(&connection_id, schema.as_deref(), table)?
+        }
+        None => Vec::new(),
+    };
+
+    // User scripts reshape ad-hoc and saved query results, not table data
+    // the grid may edit.
+    let result_scripts = match table {
+        Some(_) => Vec::new(),
+        None => crate::result_scripts::load_result_scripts(&connection_id)?,
+    };
+
+    // Ad-hoc queries on protected connections are estimated first.
+    if table.is_none() && !allow_costly.unwrap_or(false) {
+        crate::cost_guard::enforce(&app, &connection_id, &sanitized_query, schema.as_deref())
+            .await?;
+    }
+
+    // Ad-hoc queries (not table browsing) feed the plan history.
+    let tracked_query = table
+        .is_none()
+        .then(|| (sanitized_query.clone(), schema.clone()));
+    let executed = (sanitized_query.clone(), schema.clone());
+    let started_at = chrono::Utc::now();
+    let started = std::time::Instant::now();
+
+    // The first page is sized from the row width measured on the query's
+    // previous run; the statement itself runs once.
+    let page_limits = crate::page_sizing::PageSizeLimits::from_config();
+    let limit = match (limit, page_limits.as_ref()) {
+        (Some(requested), Some(limits)) if page.unwrap_or(1) == 1 => {
+            Some(crate::page_sizing::first_page_size(
+                requested,
+                crate::page_sizing::remembered_row_bytes(&connection_id, &sanitized_query),
+                limits,
+            ))
+        }
+        _ => limit,
+    };
+    let width_connection_id = connection_id.clone();
+
+    let job = crate::query_jobs::start(&connection_id, &saved_conn.name, &sanitized_query);
+    let task = tokio::spawn(job.job().scope(async move {
+        let mut result = drv
+            .execute_query(
+                &params,
+                &sanitized_query,
+                limit,
+                page.unwrap_or(1),
+                schema.as_deref(),
+            )
+            .await?;
+        if let Some(avg) = page_limits
+            .as_ref()
+            .and_then(|limits| crate::page_sizing::adapt_first_page(&mut result, limits))
+        {
+            crate::page_sizing::remember_row_bytes(&width_connection_id, &sanitized_query, avg);
+        }
+        let scripts =
+            crate::result_scripts::scripts_for(&result_scripts, saved_query_id.as_deref());
+        crate::result_scripts::apply_result_scripts(&mut result, &scripts)?;
+        crate::data_rules::apply_data_rules(&mut result, &data_rules);
+        Ok::<_, String>(result)
+    }));
+    job.job().set_abort_handle(task.abort_handle());
+
+    let abort_handle = Arc::new(task.abort_handle());
+    let cancel_key = crate::window_state::cancellation_key(cancel_scope, &connection_id);
+    register_abort_handle(&state.handles, cancel_key.clone(), abort_handle.clone());
+
+    let result = task.await;
+
+    unregister_abort_handle(&state.handles, &cancel_key, &abort_handle);
+
+    match result {
+        Ok(Ok(mut query_result)) => {
+            log::info!(
+                "Query executed successfully, returned {} rows",
+                query_result.rows.len()
+            );
+            let (executed_query, executed_schema) = executed;
+            query_result.provenance = Some(crate::provenance::describe(
+                &saved_conn,
+                executed_schema.as_deref(),
+                &executed_query,
+                started_at,
+                started.elapsed(),
+                crate::schema_watch::known_checksum(&connection_id),
+            ));
+            crate::table_activity::track_query(
+                &app,
+                &connection_id,
+                &saved_conn.params.driver,
+                &executed_query,
+                executed_schema.as_deref(),
+            );
+            if let Some((query, schema)) = tracked_query {
+                crate::session_objects::track(&app, &connection_id, &query);
+                crate::schema_watch::note_local_ddl(&connection_id, &query);
+                crate::plan_history::track_run(app.clone(), connection_id, query, schema);
+            }
+            Ok(query_result)
+        }
+        Ok(Err(e)) => {
+            log::error!("Query execution failed: {}", e);
+            Err(e)
+        }
+        Err(_) => {
+            log::warn!("Query was cancelled");
+            Err(crate::i18n::t("errors.queryCancelled"))
+        }
+    }
+}
+
+/// Runs an editor query whose values are passed separately as bind
+/// parameters (`:name`, `$1` or `?`) rather than interpolated into the SQL.
+/// Each driver rewrites the placeholders to its native syntax.
+#[tauri::command]
+pub async fn execute_query_with_params<R: Runtime>(
+    app: AppHandle<R>,
+    window: WebviewWindow<R>,
+    state: State<'_, QueryCancellationState>,
+    connection_id: String,
+    query: String,
+    params: HashMap<String, serde_json::Value>,
+    limit: Option<u32>,
+    page: Option<u32>,
+    schema: Option<String>,
+    use_primary: Option<bool>,
+) -> Result<QueryResult, String> {
+    log::info!(
+        "Executing parameterized query on connection: {} | {} parameter(s) | Query: {}",
+        connection_id,
+        params.len(),
+        query
+    );
+
+    let sanitized_query = sanitize_user_query(&query);
+
+    let saved_conn = find_connection_by_id(&app, &connection_id)?;
+    crate::read_only::ensure_query_allowed(&saved_conn.params, &sanitized_query)?;
+    let use_primary = use_primary.unwrap_or(false)
+        || crate::session_objects::has_objects(&connection_id)
+        || crate::pool_manager::has_transaction(&connection_id).await;
+    let (routed_params, pool_id) = crate::pool_manager::route_query(
+        &saved_conn,
+        &connection_id,
+        &sanitized_query,
+        use_primary,
+    );
+    let expanded_params = expand_ssh_connection_params(&app, &routed_params).await?;
+    let conn_params = resolve_connection_params_with_id(&expanded_params, &pool_id)?;
+
+    let drv = driver_for(&saved_conn.params.driver).await?;
+
+    let job = crate::query_jobs::start(&connection_id, &saved_conn.name, &sanitized_query);
+    let task = tokio::spawn(job.job().scope(async move {
+        drv.execute_query_with_params(
+            &conn_params,
             &sanitized_query,
+            &params,
             limit,
             page.unwrap_or(1),
             schema.as_deref(),
         )
         .await
-    });
+    }));
+    job.job().set_abort_handle(task.abort_handle());
 
     let abort_handle = Arc::new(task.abort_handle());
-    register_abort_handle(&state.handles, connection_id.clone(), abort_handle.clone());
+    let cancel_key = crate::window_state::cancellation_key(window.label(), &connection_id);
+    register_abort_handle(&state.handles, cancel_key.clone(), abort_handle.clone());
 
     let result = task.await;
 
-    unregister_abort_handle(&state.handles, &connection_id, &abort_handle);
+    unregister_abort_handle(&state.handles, &cancel_key, &abort_handle);
 
     match result {
         Ok(Ok(query_result)) => {
             log::info!(
-                "Query executed successfully, returned {} rows",
+                "Parameterized query executed successfully, returned {} rows",
                 query_result.rows.len()
             );
             Ok(query_result)
         }
         Ok(Err(e)) => {
-            log::error!("Query execution failed: {}", e);
+            log::error!("Parameterized query execution failed: {}", e);
             Err(e)
         }
         Err(_) => {
             log::warn!("Query was cancelled");
-            Err("Query cancelled".into())
+            Err(crate::i18n::t("errors.queryCancelled"))
         }
     }
 }
@@ -2441,6 +2901,7 @@ pub async fn execute_query<R: Runtime>(
 #[tauri::command]
 pub async fn execute_query_batch<R: Runtime>(
     app: AppHandle<R>,
+    window: WebviewWindow<R>,
     state: State<'_, QueryCancellationState>,
     connection_id: String,
     queries: Vec<String>,
@@ -2457,11 +2918,17 @@ pub async fn execute_query_batch<R: Runtime>(
     let sanitized_queries: Vec<String> = queries.iter().map(|q| sanitize_user_query(q)).collect();
 
     let saved_conn = find_connection_by_id(&app, &connection_id)?;
+    for query in &sanitized_queries {
+        crate::read_only::ensure_query_allowed(&saved_conn.params, query)?;
+    }
     let expanded_params = expand_ssh_connection_params(&app, &saved_conn.params).await?;
     let params = resolve_connection_params_with_id(&expanded_params, &connection_id)?;
 
     let drv = driver_for(&saved_conn.params.driver).await?;
-    let task = tokio::spawn(async move {
+    let statements = sanitized_queries.clone();
+    let tracked_schema = schema.clone();
+    let job = crate::query_jobs::start(&connection_id, &saved_conn.name, &statements.join(";\n"));
+    let task = tokio::spawn(job.job().scope(async move {
         drv.execute_batch(
             &params,
             &sanitized_queries,
@@ -2470,14 +2937,16 @@ pub async fn execute_query_batch<R: Runtime>(
             schema.as_deref(),
         )
         .await
-    });
+    }));
+    job.job().set_abort_handle(task.abort_handle());
 
     let abort_handle = Arc::new(task.abort_handle());
-    register_abort_handle(&state.handles, connection_id.clone(), abort_handle.clone());
+    let cancel_key = crate::window_state::cancellation_key(window.label(), &connection_id);
+    register_abort_handle(&state.handles, cancel_key.clone(), abort_handle.clone());
 
     let result = task.await;
 
-    unregister_abort_handle(&state.handles, &connection_id, &abort_handle);
+    unregister_abort_handle(&state.handles, &cancel_key, &abort_handle);
 
     match result {
         Ok(Ok(batch_results)) => {
@@ -2488,6 +2957,19 @@ pub async fn execute_query_batch<R: Runtime>(
                 batch_results.len() - success_count,
                 batch_results.len()
             );
+            for (statement, outcome) in statements.iter().zip(&batch_results) {
+                if outcome.result.is_some() {
+                    crate::session_objects::track(&app, &connection_id, statement);
+                    crate::schema_watch::note_local_ddl(&connection_id, statement);
+                    crate::table_activity::track_query(
+                        &app,
+                        &connection_id,
+                        &saved_conn.params.driver,
+                        statement,
+                        tracked_schema.as_deref(),
+                    );
+                }
+            }
             Ok(batch_results)
         }
         Ok(Err(e)) => {
@@ -2496,7 +2978,7 @@ pub async fn execute_query_batch<R: Runtime>(
         }
         Err(_) => {
             log::warn!("Batch was cancelled");
-            Err("Query cancelled".into())
+            Err(crate::i18n::t("errors.queryCancelled"))
         }
     }
 }
@@ -2506,6 +2988,7 @@ pub async fn execute_query_batch<R: Runtime>(
 #[tauri::command]
 pub async fn explain_query_plan<R: Runtime>(
     app: AppHandle<R>,
+    window: WebviewWindow<R>,
     state: State<'_, QueryCancellationState>,
     connection_id: String,
     query: String,
@@ -2529,6 +3012,10 @@ pub async fn explain_query_plan<R: Runtime>(
     }
 
     let saved_conn = find_connection_by_id(&app, &connection_id)?;
+    // ANALYZE runs the statement.
+    if analyze {
+        crate::read_only::ensure_query_allowed(&saved_conn.params, &sanitized_query)?;
+    }
     let expanded_params = expand_ssh_connection_params(&app, &saved_conn.params).await?;
     let params = resolve_connection_params_with_id(&expanded_params, &connection_id)?;
 
@@ -2539,15 +3026,17 @@ pub async fn explain_query_plan<R: Runtime>(
     });
 
     let abort_handle = Arc::new(task.abort_handle());
-    register_abort_handle(&state.handles, connection_id.clone(), abort_handle.clone());
+    let cancel_key = crate::window_state::cancellation_key(window.label(), &connection_id);
+    register_abort_handle(&state.handles, cancel_key.clone(), abort_handle.clone());
 
     let result = task.await;
 
-    unregister_abort_handle(&state.handles, &connection_id, &abort_handle);
+    unregister_abort_handle(&state.handles, &cancel_key, &abort_handle);
 
     match result {
         Ok(Ok(plan)) => {
             log::info!("Explain query completed successfully");
+            crate::plan_history::capture(&app, &connection_id, &plan);
             Ok(plan)
         }
         Ok(Err(e)) => {
@@ -2556,7 +3045,7 @@ pub async fn explain_query_plan<R: Runtime>(
         }
         Err(_) => {
             log::warn!("Explain query was cancelled");
-            Err("Explain query cancelled".into())
+            Err(crate::i18n::t("errors.explainCancelled"))
         }
     }
 }
@@ -2607,12 +3096,7 @@ pub async fn count_query<R: Runtime>(
 ///
 /// This workaround should be removed once the upstream issue is resolved.
 #[tauri::command]
-pub async fn set_window_title(app: AppHandle, title: String) -> Result<(), String> {
-    // Get the main window
-    let window = app
-        .get_webview_window("main")
-        .ok_or("Failed to get main window")?;
-
+pub async fn set_window_title(window: WebviewWindow, title: String) -> Result<(), String> {
     // Set title using standard Tauri API (works on all platforms)
     window
         .set_title(&title)
@@ -2654,6 +3138,8 @@ pub async fn open_er_diagram_window(
     database_name: String,
     focus_table: Option<String>,
     schema: Option<String>,
+    tables: Option<Vec<String>>,
+    depth: Option<u32>,
 ) -> Result<(), String> {
     use tauri::{WebviewUrl, WebviewWindowBuilder};
     use urlencoding::encode;
@@ -2681,6 +3167,14 @@ pub async fn open_er_diagram_window(
         url.push_str(&format!("&schema={}", encode(s)));
     }
 
+    // Open on a subset of tables instead of the whole schema.
+    for table in tables.iter().flatten() {
+        url.push_str(&format!("&table={}", encode(table)));
+    }
+    if let Some(depth) = depth {
+        url.push_str(&format!("&depth={}", depth));
+    }
+
     let _webview = WebviewWindowBuilder::new(&app, "er-diagram", WebviewUrl::App(url.into()))
         .title(&title)
         .inner_size(1200.0, 800.0)
@@ -2718,7 +3212,10 @@ pub async fn build_connection_url(params: &ConnectionParams) -> Result<String, S
             params.port.unwrap_or(DEFAULT_MYSQL_PORT),
             params.database
         )),
-        _ => Err("Unsupported driver".into()),
+        other => Err(crate::i18n::t_with(
+            "errors.unsupportedDriver",
+            &[("driver", other)],
+        )),
     }
 }
 
@@ -2879,6 +3376,7 @@ pub async fn create_view<R: Runtime>(
     );
 
     let saved_conn = find_connection_by_id(&app, &connection_id)?;
+    crate::read_only::ensure_writable(&saved_conn.params)?;
     let expanded_params = expand_ssh_connection_params(&app, &saved_conn.params).await?;
     let params = resolve_connection_params_with_id(&expanded_params, &connection_id)?;
 
@@ -2888,7 +3386,10 @@ pub async fn create_view<R: Runtime>(
         .await;
 
     match &result {
-        Ok(_) => log::info!("Successfully created view: {}", view_name),
+        Ok(_) => {
+            log::info!("Successfully created view: {}", view_name);
+            crate::schema_watch::forget(&connection_id);
+        }
         Err(e) => log::error!("Failed to create view {}: {}", view_name, e),
     }
 
@@ -2910,6 +3411,7 @@ pub async fn alter_view<R: Runtime>(
     );
 
     let saved_conn = find_connection_by_id(&app, &connection_id)?;
+    crate::read_only::ensure_writable(&saved_conn.params)?;
     let expanded_params = expand_ssh_connection_params(&app, &saved_conn.params).await?;
     let params = resolve_connection_params_with_id(&expanded_params, &connection_id)?;
 
@@ -2919,7 +3421,10 @@ pub async fn alter_view<R: Runtime>(
         .await;
 
     match &result {
-        Ok(_) => log::info!("Successfully altered view: {}", view_name),
+        Ok(_) => {
+            log::info!("Successfully altered view: {}", view_name);
+            crate::schema_watch::forget(&connection_id);
+        }
         Err(e) => log::error!("Failed to alter view {}: {}", view_name, e),
     }
 
@@ -2940,6 +3445,7 @@ pub async fn drop_view<R: Runtime>(
     );
 
     let saved_conn = find_connection_by_id(&app, &connection_id)?;
+    crate::read_only::ensure_writable(&saved_conn.params)?;
     let expanded_params = expand_ssh_connection_params(&app, &saved_conn.params).await?;
     let params = resolve_connection_params_with_id(&expanded_params, &connection_id)?;
 
@@ -2947,13 +3453,81 @@ pub async fn drop_view<R: Runtime>(
     let result = drv.drop_view(&params, &view_name, schema.as_deref()).await;
 
     match &result {
-        Ok(_) => log::info!("Successfully dropped view: {}", view_name),
+        Ok(_) => {
+            log::info!("Successfully dropped view: {}", view_name);
+            crate::schema_watch::forget(&connection_id);
+        }
         Err(e) => log::error!("Failed to drop view {}: {}", view_name, e),
     }
 
     result
 }
 
+/// Identifier length limit of a dialect, in bytes.
+fn max_identifier_len(driver: &str) -> Option<usize> {
+    match driver {
+        "postgres" => Some(63),
+        "mysql" => Some(64),
+        "mssql" => Some(128),
+        _ => None,
+    }
+}
+
+/// Persist the rows of a query as a new table (`CREATE TABLE ... AS`).
+#[tauri::command]
+pub async fn create_table_from_query<R: Runtime>(
+    app: AppHandle<R>,
+    connection_id: String,
+    table_name: String,
+    query: String,
+    schema: Option<String>,
+) -> Result<(), String> {
+    log::info!(
+        "Creating table {} from query on connection: {}",
+        table_name,
+        connection_id
+    );
+
+    let saved_conn = find_connection_by_id(&app, &connection_id)?;
+    crate::read_only::ensure_writable(&saved_conn.params)?;
+    let max_len = max_identifier_len(&saved_conn.params.driver);
+    crate::drivers::common::validate_object_name("table", &table_name, max_len)?;
+    if let Some(schema) = schema.as_deref() {
+        crate::drivers::common::validate_object_name("schema", schema, max_len)?;
+    }
+
+    let sanitized_query = sanitize_user_query(&query);
+    if !crate::drivers::common::is_materializable_query(&sanitized_query) {
+        return Err(crate::i18n::t("errors.notMaterializable"));
+    }
+
+    let expanded_params = expand_ssh_connection_params(&app, &saved_conn.params).await?;
+    let params = resolve_connection_params_with_id(&expanded_params, &connection_id)?;
+    let drv = driver_for(&saved_conn.params.driver).await?;
+
+    let existing = drv.get_tables(&params, schema.as_deref()).await?;
+    if existing.iter().any(|t| t.name == table_name) {
+        return Err(crate::i18n::t_with(
+            "errors.tableAlreadyExists",
+            &[("table", &table_name)],
+        ));
+    }
+
+    let result = drv
+        .create_table_from_query(&params, &table_name, &sanitized_query, schema.as_deref())
+        .await;
+
+    match &result {
+        Ok(_) => {
+            log::info!("Successfully created table: {}", table_name);
+            crate::schema_watch::forget(&connection_id);
+        }
+        Err(e) => log::error!("Failed to create table {}: {}", table_name, e),
+    }
+
+    result
+}
+
 #[tauri::command]
 pub async fn get_view_columns<R: Runtime>(
     app: AppHandle<R>,
@@ -3040,6 +3614,7 @@ pub async fn create_trigger<R: Runtime>(
     log::info!("Creating trigger on connection: {}", connection_id);
 
     let saved_conn = find_connection_by_id(&app, &connection_id)?;
+    crate::read_only::ensure_writable(&saved_conn.params)?;
     let expanded_params = expand_ssh_connection_params(&app, &saved_conn.params).await?;
     let params = resolve_connection_params_with_id(&expanded_params, &connection_id)?;
 
@@ -3071,6 +3646,7 @@ pub async fn drop_trigger<R: Runtime>(
     );
 
     let saved_conn = find_connection_by_id(&app, &connection_id)?;
+    crate::read_only::ensure_writable(&saved_conn.params)?;
     let expanded_params = expand_ssh_connection_params(&app, &saved_conn.params).await?;
     let params = resolve_connection_params_with_id(&expanded_params, &connection_id)?;
 
@@ -3087,29 +3663,68 @@ pub async fn drop_trigger<R: Runtime>(
     result
 }
 
-/// Register a connection as active for health-check pinging.
+/// Register a connection as active for health-check pinging, and as open in
+/// the calling window. The server's flavor and version are detected in the
+/// background.
 #[tauri::command]
-pub async fn register_active_connection(connection_id: String) {
+pub async fn register_active_connection<R: Runtime>(
+    app: AppHandle<R>,
+    window: WebviewWindow<R>,
+    registry: State<'_, crate::window_state::WindowRegistry>,
+    connection_id: String,
+) -> Result<(), String> {
+    registry.open_connection(window.label(), &connection_id);
+    if crate::server_info::cached(&connection_id).is_none() {
+        let id = connection_id.clone();
+        tauri::async_runtime::spawn(async move {
+            if let Err(e) = crate::server_info::detect_server(&app, &id).await {
+                log::debug!("Could not detect the server of connection {}: {}", id, e);
+            }
+        });
+    }
     crate::health_check::register_connection(connection_id).await;
+    Ok(())
 }
 
-/// Disconnect from a database connection by closing its connection pool
+/// Disconnect the calling window from a connection. The connection pool is
+/// closed once no other window has the connection open.
 #[tauri::command]
 pub async fn disconnect_connection<R: Runtime>(
     app: AppHandle<R>,
+    window: WebviewWindow<R>,
+    registry: State<'_, crate::window_state::WindowRegistry>,
     connection_id: String,
+) -> Result<(), String> {
+    if !registry.release_connection(window.label(), &connection_id) {
+        log::info!(
+            "Connection {} is still open in another window, keeping its pool",
+            connection_id
+        );
+        return Ok(());
+    }
+    close_connection(&app, &connection_id).await
+}
+
+/// Disconnect from a database connection by closing its connection pool
+pub(crate) async fn close_connection<R: Runtime>(
+    app: &AppHandle<R>,
+    connection_id: &str,
 ) -> Result<(), String> {
     log::info!("Disconnecting from connection: {}", connection_id);
 
     // Unregister from health check before closing the pool.
-    crate::health_check::unregister_connection(&connection_id).await;
+    crate::health_check::unregister_connection(connection_id).await;
 
-    let saved_conn = find_connection_by_id(&app, &connection_id)?;
-    let expanded_params = expand_ssh_connection_params(&app, &saved_conn.params).await?;
-    let params = resolve_connection_params_with_id(&expanded_params, &connection_id)?;
+    let saved_conn = find_connection_by_id(app, connection_id)?;
+    let expanded_params = expand_ssh_connection_params(app, &saved_conn.params).await?;
+    let params = resolve_connection_params_with_id(&expanded_params, connection_id)?;
+
+    // Drop the session's temporary objects while the pool is still open.
+    crate::session_objects::cleanup(app, connection_id, &saved_conn.params.driver, &params).await;
 
     // Close the connection pool
-    crate::pool_manager::close_pool_with_id(&params, Some(&connection_id)).await;
+    crate::pool_manager::close_pool_with_id(&params, Some(connection_id)).await;
+    crate::server_info::forget(connection_id);
 
     log::info!(
         "Successfully disconnected from connection: {}",
@@ -3118,6 +3733,17 @@ pub async fn disconnect_connection<R: Runtime>(
     Ok(())
 }
 
+/// Connection counts of the open pools of a connection, one entry per
+/// database and read replica in use. Empty when the connection is closed.
+#[tauri::command]
+pub async fn get_pool_stats<R: Runtime>(
+    app: AppHandle<R>,
+    connection_id: String,
+) -> Result<Vec<crate::pool_manager::PoolStats>, String> {
+    let saved_conn = find_connection_by_id(&app, &connection_id)?;
+    Ok(crate::pool_manager::pool_stats(&saved_conn.params.driver, &connection_id).await)
+}
+
 // --- Type Registry ---
 
 #[tauri::command]
@@ -3153,8 +3779,11 @@ pub async fn get_create_table_sql<R: Runtime>(
 ) -> Result<Vec<String>, String> {
     let saved_conn = find_connection_by_id(&app, &connection_id)?;
     let drv = driver_for(&saved_conn.params.driver).await?;
-    drv.get_create_table_sql(&table_name, columns, schema.as_deref())
-        .await
+    crate::server_info::scope(
+        crate::server_info::cached(&connection_id),
+        drv.get_create_table_sql(&table_name, columns, schema.as_deref()),
+    )
+    .await
 }
 
 #[tauri::command]
@@ -3167,8 +3796,11 @@ pub async fn get_add_column_sql<R: Runtime>(
 ) -> Result<Vec<String>, String> {
     let saved_conn = find_connection_by_id(&app, &connection_id)?;
     let drv = driver_for(&saved_conn.params.driver).await?;
-    drv.get_add_column_sql(&table, column, schema.as_deref())
-        .await
+    crate::server_info::scope(
+        crate::server_info::cached(&connection_id),
+        drv.get_add_column_sql(&table, column, schema.as_deref()),
+    )
+    .await
 }
 
 #[tauri::command]
@@ -3182,8 +3814,11 @@ pub async fn get_alter_column_sql<R: Runtime>(
 ) -> Result<Vec<String>, String> {
     let saved_conn = find_connection_by_id(&app, &connection_id)?;
     let drv = driver_for(&saved_conn.params.driver).await?;
-    drv.get_alter_column_sql(&table, old_column, new_column, schema.as_deref())
-        .await
+    crate::server_info::scope(
+        crate::server_info::cached(&connection_id),
+        drv.get_alter_column_sql(&table, old_column, new_column, schema.as_deref()),
+    )
+    .await
 }
 
 #[tauri::command]
@@ -3217,15 +3852,18 @@ pub async fn get_create_foreign_key_sql<R: Runtime>(
 ) -> Result<Vec<String>, String> {
     let saved_conn = find_connection_by_id(&app, &connection_id)?;
     let drv = driver_for(&saved_conn.params.driver).await?;
-    drv.get_create_foreign_key_sql(
-        &table,
-        &fk_name,
-        &column,
-        &ref_table,
-        &ref_column,
-        on_delete.as_deref(),
-        on_update.as_deref(),
-        schema.as_deref(),
+    crate::server_info::scope(
+        crate::server_info::cached(&connection_id),
+        drv.get_create_foreign_key_sql(
+            &table,
+            &fk_name,
+            &column,
+            &ref_table,
+            &ref_column,
+            on_delete.as_deref(),
+            on_update.as_deref(),
+            schema.as_deref(),
+        ),
     )
     .await
 }
@@ -3239,6 +3877,7 @@ pub async fn drop_index_action<R: Runtime>(
     schema: Option<String>,
 ) -> Result<(), String> {
     let saved_conn = find_connection_by_id(&app, &connection_id)?;
+    crate::read_only::ensure_writable(&saved_conn.params)?;
     let expanded_params = expand_ssh_connection_params(&app, &saved_conn.params).await?;
     let params = resolve_connection_params_with_id(&expanded_params, &connection_id)?;
     let drv = driver_for(&saved_conn.params.driver).await?;
@@ -3255,6 +3894,7 @@ pub async fn drop_foreign_key_action<R: Runtime>(
     schema: Option<String>,
 ) -> Result<(), String> {
     let saved_conn = find_connection_by_id(&app, &connection_id)?;
+    crate::read_only::ensure_writable(&saved_conn.params)?;
     let expanded_params = expand_ssh_connection_params(&app, &saved_conn.params).await?;
     let params = resolve_connection_params_with_id(&expanded_params, &connection_id)?;
     let drv = driver_for(&saved_conn.params.driver).await?;
@@ -3269,7 +3909,7 @@ pub async fn get_registered_drivers() -> Vec<crate::drivers::driver_trait::Plugi
 
 #[tauri::command]
 pub async fn get_keybindings<R: Runtime>(app: AppHandle<R>) -> Result<serde_json::Value, String> {
-    let config_dir = app.path().app_config_dir().map_err(|e| e.to_string())?;
+    let config_dir = crate::paths::app_config_dir(&app)?;
     let path = config_dir.join("keybindings.json");
     if !path.exists() {
         return Ok(serde_json::Value::Object(serde_json::Map::new()));
@@ -3283,7 +3923,7 @@ pub async fn save_keybindings<R: Runtime>(
     app: AppHandle<R>,
     keybindings: serde_json::Value,
 ) -> Result<(), String> {
-    let config_dir = app.path().app_config_dir().map_err(|e| e.to_string())?;
+    let config_dir = crate::paths::app_config_dir(&app)?;
     fs::create_dir_all(&config_dir).map_err(|e| e.to_string())?;
     let path = config_dir.join("keybindings.json");
     let content = serde_json::to_string_pretty(&keybindings).map_err(|e| e.to_string())?;
@@ -3326,6 +3966,7 @@ pub async fn create_connection_group<R: Runtime>(
     name: String,
 ) -> Result<ConnectionGroup, String> {
     let path = get_config_path(&app)?;
+    let _lock = crate::config_store::lock(&path)?;
     let mut file = persistence::load_connections_file(&path).unwrap_or_default();
 
     // Calculate next sort_order
@@ -3339,7 +3980,7 @@ pub async fn create_connection_group<R: Runtime>(
     };
 
     file.groups.push(group.clone());
-    persistence::save_connections_file(&path, &file)?;
+    persistence::write_connections_file(&path, &file)?;
 
     Ok(group)
 }
@@ -3353,13 +3994,14 @@ pub async fn update_connection_group<R: Runtime>(
     sort_order: Option<i32>,
 ) -> Result<ConnectionGroup, String> {
     let path = get_config_path(&app)?;
+    let _lock = crate::config_store::lock(&path)?;
     let mut file = persistence::load_connections_file(&path)?;
 
     let group = file
         .groups
         .iter_mut()
         .find(|g| g.id == id)
-        .ok_or_else(|| format!("Group with ID {} not found", id))?;
+        .ok_or_else(|| crate::i18n::t_with("errors.groupNotFound", &[("id", &id)]))?;
 
     if let Some(n) = name {
         group.name = n;
@@ -3372,7 +4014,7 @@ pub async fn update_connection_group<R: Runtime>(
     }
 
     let updated = group.clone();
-    persistence::save_connections_file(&path, &file)?;
+    persistence::write_connections_file(&path, &file)?;
 
     Ok(updated)
 }
@@ -3383,6 +4025,7 @@ pub async fn delete_connection_group<R: Runtime>(
     id: String,
 ) -> Result<(), String> {
     let path = get_config_path(&app)?;
+    let _lock = crate::config_store::lock(&path)?;
     let mut file = persistence::load_connections_file(&path)?;
 
     // Remove connections from the group (set group_id to None)
@@ -3394,7 +4037,7 @@ pub async fn delete_connection_group<R: Runtime>(
 
     // Remove the group
     file.groups.retain(|g| g.id != id);
-    persistence::save_connections_file(&path, &file)?;
+    persistence::write_connections_file(&path, &file)?;
 
     Ok(())
 }
@@ -3407,13 +4050,16 @@ pub async fn move_connection_to_group<R: Runtime>(
     sort_order: Option<i32>,
 ) -> Result<SavedConnection, String> {
     let path = get_config_path(&app)?;
+    let _lock = crate::config_store::lock(&path)?;
     let mut file = persistence::load_connections_file(&path)?;
 
     let conn = file
         .connections
         .iter_mut()
         .find(|c| c.id == connection_id)
-        .ok_or_else(|| format!("Connection with ID {} not found", connection_id))?;
+        .ok_or_else(|| {
+            crate::i18n::t_with("errors.connectionWithIdNotFound", &[("id", &connection_id)])
+        })?;
 
     conn.group_id = group_id;
     if let Some(order) = sort_order {
@@ -3421,7 +4067,7 @@ pub async fn move_connection_to_group<R: Runtime>(
     }
 
     let updated = conn.clone();
-    persistence::save_connections_file(&path, &file)?;
+    persistence::write_connections_file(&path, &file)?;
 
     Ok(updated)
 }
@@ -3432,6 +4078,7 @@ pub async fn reorder_groups<R: Runtime>(
     group_orders: Vec<(String, i32)>,
 ) -> Result<(), String> {
     let path = get_config_path(&app)?;
+    let _lock = crate::config_store::lock(&path)?;
     let mut file = persistence::load_connections_file(&path)?;
 
     for (group_id, order) in group_orders {
@@ -3440,7 +4087,7 @@ pub async fn reorder_groups<R: Runtime>(
         }
     }
 
-    persistence::save_connections_file(&path, &file)?;
+    persistence::write_connections_file(&path, &file)?;
     Ok(())
 }
 
@@ -3450,6 +4097,7 @@ pub async fn reorder_connections_in_group<R: Runtime>(
     connection_orders: Vec<(String, i32)>,
 ) -> Result<(), String> {
     let path = get_config_path(&app)?;
+    let _lock = crate::config_store::lock(&path)?;
     let mut file = persistence::load_connections_file(&path)?;
 
     for (conn_id, order) in connection_orders {
@@ -3458,7 +4106,7 @@ pub async fn reorder_connections_in_group<R: Runtime>(
         }
     }
 
-    persistence::save_connections_file(&path, &file)?;
+    persistence::write_connections_file(&path, &file)?;
     Ok(())
 }
 
@@ -3542,11 +4190,24 @@ pub async fn export_connections_payload<R: Runtime>(
         }
     }
 
+    // Include named filter presets for every exported connection
+    let mut named_filters = std::collections::HashMap::new();
+    for conn in &conn_file.connections {
+        match crate::preferences::load_named_filters(&conn.id) {
+            Ok(filters) if !filters.is_empty() => {
+                named_filters.insert(conn.id.clone(), filters);
+            }
+            Ok(_) => {}
+            Err(e) => log::warn!("Failed to export named filters for {}: {}", conn.id, e),
+        }
+    }
+
     Ok(ExportPayload {
         version: 1,
         groups: conn_file.groups,
         connections: conn_file.connections,
         ssh_connections,
+        named_filters,
     })
 }
 
@@ -3558,13 +4219,10 @@ pub async fn import_connections_payload<R: Runtime>(
     let conn_path = get_config_path(&app)?;
     let ssh_path = get_ssh_config_path(&app)?;
 
+    let _conn_lock = crate::config_store::lock(&conn_path)?;
+    let _ssh_lock = crate::config_store::lock(&ssh_path)?;
     let mut current_file = persistence::load_connections_file(&conn_path).unwrap_or_default();
-    let mut current_ssh = if ssh_path.exists() {
-        let content = fs::read_to_string(&ssh_path).map_err(|e| e.to_string())?;
-        serde_json::from_str::<Vec<SshConnection>>(&content).unwrap_or_default()
-    } else {
-        Vec::new()
-    };
+    let mut current_ssh = persistence::load_ssh_connections_file(&ssh_path)?;
 
     let cache = app
         .state::<std::sync::Arc<crate::credential_cache::CredentialCache>>()
@@ -3580,6 +4238,15 @@ pub async fn import_connections_payload<R: Runtime>(
         }
     }
 
+    // Named filter keys become preferences directories: check them before
+    // anything is written.
+    let imported_ids: Vec<String> = payload.connections.iter().map(|c| c.id.clone()).collect();
+    for connection_id in payload.named_filters.keys() {
+        if imported_ids.contains(connection_id) {
+            crate::preferences::validate_connection_id(connection_id)?;
+        }
+    }
+
     // Merge connections and handle passwords
     for mut new_conn in payload.connections {
         // Handle passwords in keychain
@@ -3643,9 +4310,29 @@ pub async fn import_connections_payload<R: Runtime>(
     }
 
     // Save files
-    persistence::save_connections_file(&conn_path, &current_file)?;
-    let ssh_json = serde_json::to_string_pretty(&current_ssh).map_err(|e| e.to_string())?;
-    fs::write(ssh_path, ssh_json).map_err(|e| e.to_string())?;
+    persistence::write_connections_file(&conn_path, &current_file)?;
+    persistence::write_ssh_connections_file(&ssh_path, &current_ssh)?;
+
+    // Merge named filter presets, replacing presets with the same id. Only
+    // presets of connections in this payload are taken.
+    for (connection_id, filters) in payload.named_filters {
+        if !imported_ids.contains(&connection_id) {
+            log::warn!(
+                "Ignoring named filters of connection {} missing from the import",
+                connection_id
+            );
+            continue;
+        }
+        let mut current = crate::preferences::load_named_filters(&connection_id)?;
+        for filter in filters {
+            if let Some(existing) = current.iter_mut().find(|f| f.id == filter.id) {
+                *existing = filter;
+            } else {
+                current.push(filter);
+            }
+        }
+        crate::preferences::save_named_filters(&connection_id, &current)?;
+    }
 
     Ok(())
 }
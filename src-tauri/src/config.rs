@@ -1,10 +1,10 @@
+use crate::config_store::{self, Migration};
 use crate::keychain_utils;
 use once_cell::sync::Lazy;
 use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::PathBuf;
 use tauri::AppHandle;
-use tauri::Manager;
 use std::sync::RwLock;
 
 use std::collections::HashMap;
@@ -35,6 +35,9 @@ pub struct AppConfig {
     pub check_for_updates: Option<bool>,
     pub auto_check_updates_on_startup: Option<bool>,
     pub last_dismissed_version: Option<String>,
+    /// Release channel followed by the updater: `"stable"` | `"beta"`.
+    /// Default: `"stable"`.
+    pub update_channel: Option<String>,
     pub er_diagram_default_layout: Option<String>,
     pub schema_preferences: Option<HashMap<String, String>>,
     pub selected_schemas: Option<HashMap<String, Vec<String>>>,
@@ -58,6 +61,14 @@ pub struct AppConfig {
     pub editor_accept_suggestion_on_enter: Option<bool>,
     /// Connection health check interval in seconds. 0 = disabled. Default: 30.
     pub ping_interval: Option<u32>,
+    /// Schema change detection interval in seconds. 0 = disabled. Default: 300.
+    pub schema_watch_interval: Option<u32>,
+    /// Interval in seconds between keep-alive pings of idle pooled
+    /// connections. 0 = disabled. Default: 60.
+    pub pool_keepalive_interval: Option<u32>,
+    /// Pool settings of connections that leave them unset. Default: the
+    /// driver's (10 connections, 5 for SQLite).
+    pub pool_defaults: Option<crate::models::PoolSettings>,
     /// Maximum number of query history entries per connection. Default: 500.
     pub query_history_max_entries: Option<u32>,
     /// Whether to show the welcome screen on startup. Default: true (first launch).
@@ -89,18 +100,101 @@ pub struct AppConfig {
     pub mcp_approval_timeout_seconds: Option<u32>,
     /// Run a pre-flight EXPLAIN before opening the approval modal. Default: true.
     pub mcp_preflight_explain: Option<bool>,
+
+    // ----- Telemetry -----
+    /// Queue anonymous usage/error events for reporting. Default: false.
+    pub telemetry_enabled: Option<bool>,
+    /// URL the queued events are posted to. Default: none (nothing is sent).
+    pub telemetry_endpoint: Option<String>,
+
+    // ----- Crash reports -----
+    /// URL reviewed crash reports are posted to. Default: none (nothing is sent).
+    pub crash_report_endpoint: Option<String>,
+
+    // ----- Plan history -----
+    /// Keep EXPLAIN plans of frequently run queries and alert on plan
+    /// regressions. Default: true.
+    pub plan_history_enabled: Option<bool>,
+
+    // ----- Table activity -----
+    /// Count the tables each query reads and writes for the table activity
+    /// report. Default: true.
+    pub table_activity_enabled: Option<bool>,
+
+    // ----- Cost guard -----
+    /// Refuse queries on protected connections whose EXPLAIN estimates more
+    /// rows than this. 0 = no row limit. Default: 100000.
+    pub cost_guard_max_rows: Option<u64>,
+    /// Refuse queries on protected connections whose planner cost exceeds
+    /// this. Default: none (no cost limit).
+    pub cost_guard_max_cost: Option<f64>,
+
+    // ----- Result guardrails -----
+    /// Stop collecting a query result after this many rows. 0 = no limit.
+    /// Default: 500000.
+    pub result_max_rows: Option<u64>,
+    /// Stop collecting a query result once its estimated in-memory size
+    /// exceeds this many bytes. 0 = no limit. Default: 512MB.
+    pub result_max_bytes: Option<u64>,
+
+    // ----- Secret storage -----
+    // Read from the default profile's config only: all profiles share one
+    // secret store.
+    /// Keep every secret in the encrypted file store, never in the system
+    /// keychain; for headless servers. Default: false.
+    pub secrets_file_only: Option<bool>,
+    /// Key file of the encrypted file store. Default: `secrets.key` in the
+    /// config directory.
+    pub secrets_key_file: Option<String>,
+    /// Command printing the key of the encrypted file store in base64, e.g.
+    /// a KMS or vault CLI call. Wins over `secretsKeyFile`. Default: none.
+    pub secrets_key_command: Option<String>,
+
+    // ----- Adaptive page size -----
+    /// Fit the page size of paginated results to their row width.
+    /// Default: true.
+    pub adaptive_page_size: Option<bool>,
+    /// Fewest rows an adapted page holds. Default: 50.
+    pub result_page_size_min: Option<u32>,
+    /// Most rows an adapted page holds. Default: 5000.
+    pub result_page_size_max: Option<u32>,
+    /// Serialized size an adapted page aims for, in bytes. Default: 1MB.
+    pub result_page_target_bytes: Option<u64>,
 }
 
 static CONFIG_CACHE: Lazy<RwLock<AppConfig>> = Lazy::new(|| RwLock::new(AppConfig::default()));
 
+/// Migrations of `config.json`, oldest first; see `config_store`.
+pub const CONFIG_MIGRATIONS: &[Migration] = &[drop_hidden_schemas];
+
+/// v0 → v1: `hiddenSchemas` was replaced by `selectedSchemas`.
+fn drop_hidden_schemas(value: &mut serde_json::Value) {
+    if let Some(map) = value.as_object_mut() {
+        map.remove("hiddenSchemas");
+    }
+}
+
+/// `config.json` at `path`, migrated. `None` when it is missing or cannot
+/// be read, even from its backup.
+pub fn read_config_file(path: &std::path::Path) -> Option<AppConfig> {
+    match config_store::load_json(path, CONFIG_MIGRATIONS) {
+        Ok(value) => serde_json::from_value(value?).ok(),
+        Err(e) => {
+            log::warn!("{}", e);
+            None
+        }
+    }
+}
+
 pub fn get_config_dir<R: tauri::Runtime>(app: &AppHandle<R>) -> Option<PathBuf> {
-    app.path().app_config_dir().ok()
+    crate::paths::app_config_dir(app).ok()
 }
 
 fn cache_config(config: &AppConfig) {
     if let Ok(mut cached) = CONFIG_CACHE.write() {
         *cached = config.clone();
     }
+    crate::i18n::set_language(config.language.as_deref());
 }
 
 pub fn get_cached_config() -> AppConfig {
@@ -126,13 +220,7 @@ pub const DEFAULT_MCP_PREFLIGHT_EXPLAIN: bool = true;
 /// unreadable.
 pub fn load_config_from_disk() -> AppConfig {
     let path = crate::paths::get_app_config_dir().join("config.json");
-    if !path.exists() {
-        return AppConfig::default();
-    }
-    fs::read_to_string(&path)
-        .ok()
-        .and_then(|s| serde_json::from_str::<AppConfig>(&s).ok())
-        .unwrap_or_default()
+    read_config_file(&path).unwrap_or_default()
 }
 
 /// True when `connection_id` should be treated as read-only by MCP, taking
@@ -158,14 +246,9 @@ pub fn is_connection_readonly(config: &AppConfig, connection_id: &str) -> bool {
 // Internal load
 pub fn load_config_internal<R: tauri::Runtime>(app: &AppHandle<R>) -> AppConfig {
     if let Some(config_dir) = get_config_dir(app) {
-        let config_path = config_dir.join("config.json");
-        if config_path.exists() {
-            if let Ok(content) = fs::read_to_string(config_path) {
-                if let Ok(config) = serde_json::from_str(&content) {
-                    cache_config(&config);
-                    return config;
-                }
-            }
+        if let Some(config) = read_config_file(&config_dir.join("config.json")) {
+            cache_config(&config);
+            return config;
         }
     }
     let default_config = AppConfig::default();
@@ -185,6 +268,7 @@ pub fn save_config(app: AppHandle, config: AppConfig) -> Result<(), String> {
             fs::create_dir_all(&config_dir).map_err(|e| e.to_string())?;
         }
         let config_path = config_dir.join("config.json");
+        let _lock = config_store::lock(&config_path)?;
 
         // Load existing config and merge with new values
         let mut existing_config = load_config_internal(&app);
@@ -235,6 +319,9 @@ pub fn save_config(app: AppHandle, config: AppConfig) -> Result<(), String> {
         if config.last_dismissed_version.is_some() {
             existing_config.last_dismissed_version = config.last_dismissed_version;
         }
+        if config.update_channel.is_some() {
+            existing_config.update_channel = config.update_channel;
+        }
         if config.er_diagram_default_layout.is_some() {
             existing_config.er_diagram_default_layout = config.er_diagram_default_layout;
         }
@@ -298,6 +385,36 @@ pub fn save_config(app: AppHandle, config: AppConfig) -> Result<(), String> {
                 ));
             }
         }
+        if config.schema_watch_interval.is_some() {
+            let old_interval = existing_config.schema_watch_interval;
+            existing_config.schema_watch_interval = config.schema_watch_interval;
+            // Restart the schema watch loop if the interval changed.
+            if existing_config.schema_watch_interval != old_interval {
+                let interval = existing_config
+                    .schema_watch_interval
+                    .unwrap_or(crate::schema_watch::DEFAULT_SCHEMA_WATCH_INTERVAL);
+                tauri::async_runtime::spawn(crate::schema_watch::restart_schema_watch_loop(
+                    app.clone(),
+                    interval as u64,
+                ));
+            }
+        }
+        if config.pool_keepalive_interval.is_some() {
+            let old_interval = existing_config.pool_keepalive_interval;
+            existing_config.pool_keepalive_interval = config.pool_keepalive_interval;
+            // Restart the keep-alive loop if the interval changed.
+            if existing_config.pool_keepalive_interval != old_interval {
+                let interval = existing_config
+                    .pool_keepalive_interval
+                    .unwrap_or(crate::pool_manager::DEFAULT_KEEPALIVE_INTERVAL);
+                tauri::async_runtime::spawn(crate::pool_manager::restart_keepalive_loop(
+                    interval as u64,
+                ));
+            }
+        }
+        if config.pool_defaults.is_some() {
+            existing_config.pool_defaults = config.pool_defaults;
+        }
         if config.query_history_max_entries.is_some() {
             existing_config.query_history_max_entries = config.query_history_max_entries;
         }
@@ -328,10 +445,65 @@ pub fn save_config(app: AppHandle, config: AppConfig) -> Result<(), String> {
         if config.mcp_preflight_explain.is_some() {
             existing_config.mcp_preflight_explain = config.mcp_preflight_explain;
         }
+        if config.telemetry_enabled.is_some() {
+            existing_config.telemetry_enabled = config.telemetry_enabled;
+        }
+        if config.telemetry_endpoint.is_some() {
+            existing_config.telemetry_endpoint = config.telemetry_endpoint;
+        }
+        if config.crash_report_endpoint.is_some() {
+            existing_config.crash_report_endpoint = config.crash_report_endpoint;
+        }
+        if config.plan_history_enabled.is_some() {
+            existing_config.plan_history_enabled = config.plan_history_enabled;
+        }
+        if config.table_activity_enabled.is_some() {
+            existing_config.table_activity_enabled = config.table_activity_enabled;
+        }
+        if config.cost_guard_max_rows.is_some() {
+            existing_config.cost_guard_max_rows = config.cost_guard_max_rows;
+        }
+        if config.cost_guard_max_cost.is_some() {
+            existing_config.cost_guard_max_cost = config.cost_guard_max_cost;
+        }
+        if config.result_max_rows.is_some() {
+            existing_config.result_max_rows = config.result_max_rows;
+        }
+        if config.result_max_bytes.is_some() {
+            existing_config.result_max_bytes = config.result_max_bytes;
+        }
+        if config.secrets_file_only.is_some() {
+            existing_config.secrets_file_only = config.secrets_file_only;
+        }
+        if config.secrets_key_file.is_some() {
+            existing_config.secrets_key_file = config.secrets_key_file;
+        }
+        if config.secrets_key_command.is_some() {
+            existing_config.secrets_key_command = config.secrets_key_command;
+        }
+        if config.adaptive_page_size.is_some() {
+            existing_config.adaptive_page_size = config.adaptive_page_size;
+        }
+        if config.result_page_size_min.is_some() {
+            existing_config.result_page_size_min = config.result_page_size_min;
+        }
+        if config.result_page_size_max.is_some() {
+            existing_config.result_page_size_max = config.result_page_size_max;
+        }
+        if config.result_page_target_bytes.is_some() {
+            existing_config.result_page_target_bytes = config.result_page_target_bytes;
+        }
+
+        // Opting out also discards whatever was queued while opted in.
+        if existing_config.telemetry_enabled != Some(true) {
+            if let Err(e) = crate::telemetry::purge() {
+                log::warn!("Failed to purge telemetry queue: {}", e);
+            }
+        }
 
-        let content = serde_json::to_string_pretty(&existing_config).map_err(|e| e.to_string())?;
-        fs::write(config_path, content).map_err(|e| e.to_string())?;
+        config_store::write_json(&config_path, &existing_config, CONFIG_MIGRATIONS)?;
         cache_config(&existing_config);
+        crate::secrets::invalidate_file_store();
         Ok(())
     } else {
         Err("Could not resolve config directory".to_string())
@@ -357,11 +529,11 @@ pub fn set_schema_preference(
             fs::create_dir_all(&config_dir).map_err(|e| e.to_string())?;
         }
         let config_path = config_dir.join("config.json");
+        let _lock = config_store::lock(&config_path)?;
         let mut config = load_config_internal(&app);
         let prefs = config.schema_preferences.get_or_insert_with(HashMap::new);
         prefs.insert(connection_id, schema);
-        let content = serde_json::to_string_pretty(&config).map_err(|e| e.to_string())?;
-        fs::write(config_path, content).map_err(|e| e.to_string())?;
+        config_store::write_json(&config_path, &config, CONFIG_MIGRATIONS)?;
         Ok(())
     } else {
         Err("Could not resolve config directory".to_string())
@@ -388,6 +560,7 @@ pub fn set_selected_schemas(
             fs::create_dir_all(&config_dir).map_err(|e| e.to_string())?;
         }
         let config_path = config_dir.join("config.json");
+        let _lock = config_store::lock(&config_path)?;
         let mut config = load_config_internal(&app);
         let map = config.selected_schemas.get_or_insert_with(HashMap::new);
         if schemas.is_empty() {
@@ -395,8 +568,7 @@ pub fn set_selected_schemas(
         } else {
             map.insert(connection_id, schemas);
         }
-        let content = serde_json::to_string_pretty(&config).map_err(|e| e.to_string())?;
-        fs::write(config_path, content).map_err(|e| e.to_string())?;
+        config_store::write_json(&config_path, &config, CONFIG_MIGRATIONS)?;
         Ok(())
     } else {
         Err("Could not resolve config directory".to_string())
@@ -436,6 +608,7 @@ pub fn get_ai_api_key(provider: &str) -> Result<String, String> {
         "openrouter" => "OPENROUTER_API_KEY",
         "custom-openai" => "CUSTOM_OPENAI_API_KEY",
         "minimax" => "MINIMAX_API_KEY",
+        "gemini" => "GEMINI_API_KEY",
         _ => "",
     };
 
@@ -471,6 +644,7 @@ pub fn get_ai_api_key_status(provider: &str) -> AiKeyStatus {
         "openrouter" => "OPENROUTER_API_KEY",
         "custom-openai" => "CUSTOM_OPENAI_API_KEY",
         "minimax" => "MINIMAX_API_KEY",
+        "gemini" => "GEMINI_API_KEY",
         _ => "",
     };
 
@@ -537,7 +711,7 @@ fn save_prompt(app: &AppHandle, filename: &str, prompt: &str) -> Result<(), Stri
     if !config_dir.exists() {
         fs::create_dir_all(&config_dir).map_err(|e| e.to_string())?;
     }
-    fs::write(config_dir.join(filename), prompt).map_err(|e| e.to_string())
+    config_store::write_atomic(&config_dir.join(filename), prompt)
 }
 
 fn reset_prompt(app: &AppHandle, filename: &str, default: &str) -> Result<String, String> {
@@ -643,10 +817,10 @@ pub fn save_config_json(app: AppHandle, json: String) -> Result<(), String> {
             fs::create_dir_all(&config_dir).map_err(|e| e.to_string())?;
         }
         let config_path = config_dir.join("config.json");
-        // Re-serialize with pretty-printing for consistency
         let value: serde_json::Value = serde_json::from_str(&json).map_err(|e| e.to_string())?;
-        let pretty = serde_json::to_string_pretty(&value).map_err(|e| e.to_string())?;
-        fs::write(config_path, pretty).map_err(|e| e.to_string())?;
+        let value = config_store::migrate(value, CONFIG_MIGRATIONS);
+        config_store::save_json(&config_path, &value, CONFIG_MIGRATIONS)?;
+        crate::secrets::invalidate_file_store();
         Ok(())
     } else {
         Err("Could not resolve config directory".to_string())
@@ -0,0 +1,251 @@
+//! Crash- and concurrency-safe storage for `config.json` and
+//! `connections.json`.
+//!
+//! - Writes go to a temporary file in the same directory, are synced, then
+//!   renamed over the target, so a crash leaves the old file or the new
+//!   one, never half of it.
+//! - A `<file>.lock` file serializes writers across windows and processes
+//!   (the MCP subprocess reads the same files). A lock left behind by a
+//!   crashed writer is taken over once it is stale.
+//! - Before a file is replaced, its content is kept as `<file>.bak` when it
+//!   still parses. A file that no longer parses is moved aside as
+//!   `<file>.corrupt` and the backup restored in its place.
+//! - Files carry a `version`. Reading runs the migrations from the file's
+//!   version on; writing stamps the current one, which is the number of
+//!   migrations.
+
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant, SystemTime};
+
+use serde::Serialize;
+use serde_json::Value;
+
+const VERSION_KEY: &str = "version";
+/// How long a writer waits for another one before giving up.
+const LOCK_TIMEOUT: Duration = Duration::from_secs(5);
+const LOCK_RETRY: Duration = Duration::from_millis(20);
+/// A lock older than this was left behind by a writer that died.
+const STALE_LOCK: Duration = Duration::from_secs(30);
+
+/// Brings a file from one version to the next.
+pub type Migration = fn(&mut Value);
+
+/// `config.json.lock` for `config.json`.
+fn sibling(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(".");
+    name.push(suffix);
+    path.with_file_name(name)
+}
+
+pub fn backup_path(path: &Path) -> PathBuf {
+    sibling(path, "bak")
+}
+
+pub fn corrupt_path(path: &Path) -> PathBuf {
+    sibling(path, "corrupt")
+}
+
+// ---------------------------------------------------------------------------
+// Locking
+// ---------------------------------------------------------------------------
+
+/// Held while a file is read, changed and written back. Not reentrant:
+/// code holding it writes with [`write_json`], not [`save_json`].
+pub struct FileLock {
+    path: PathBuf,
+}
+
+impl Drop for FileLock {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.path);
+    }
+}
+
+fn is_stale(lock_path: &Path) -> bool {
+    fs::metadata(lock_path)
+        .and_then(|m| m.modified())
+        .ok()
+        .and_then(|modified| SystemTime::now().duration_since(modified).ok())
+        .is_some_and(|age| age > STALE_LOCK)
+}
+
+/// Take the write lock of `path`, waiting for another writer to finish.
+pub fn lock(path: &Path) -> Result<FileLock, String> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("Failed to create config dir: {}", e))?;
+    }
+    let lock_path = sibling(path, "lock");
+    let deadline = Instant::now() + LOCK_TIMEOUT;
+    loop {
+        match OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&lock_path)
+        {
+            Ok(mut file) => {
+                let _ = write!(file, "{}", std::process::id());
+                return Ok(FileLock { path: lock_path });
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => {
+                if is_stale(&lock_path) {
+                    log::warn!("Removing stale lock {}", lock_path.display());
+                    let _ = fs::remove_file(&lock_path);
+                    continue;
+                }
+                if Instant::now() >= deadline {
+                    return Err(format!(
+                        "{} is being written by another window or process",
+                        path.display()
+                    ));
+                }
+                std::thread::sleep(LOCK_RETRY);
+            }
+            Err(e) => return Err(format!("Failed to lock {}: {}", path.display(), e)),
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Writing
+// ---------------------------------------------------------------------------
+
+static TEMP_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+/// Replace `path` with `contents` through a synced temporary file and a
+/// rename.
+pub fn write_atomic(path: &Path, contents: &str) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("Failed to create config dir: {}", e))?;
+    }
+    let temp = sibling(
+        path,
+        &format!(
+            "{}-{}.tmp",
+            std::process::id(),
+            TEMP_COUNTER.fetch_add(1, Ordering::Relaxed)
+        ),
+    );
+    let written = (|| {
+        let mut file = fs::File::create(&temp)?;
+        file.write_all(contents.as_bytes())?;
+        file.sync_all()?;
+        fs::rename(&temp, path)
+    })();
+    written.map_err(|e| {
+        let _ = fs::remove_file(&temp);
+        format!("Failed to write {}: {}", path.display(), e)
+    })
+}
+
+/// `value` as pretty JSON stamped with `version`.
+pub fn to_versioned_json<T: Serialize>(value: &T, version: usize) -> Result<String, String> {
+    let mut json = serde_json::to_value(value).map_err(|e| e.to_string())?;
+    if let Value::Object(map) = &mut json {
+        map.insert(VERSION_KEY.to_string(), Value::from(version));
+    }
+    serde_json::to_string_pretty(&json).map_err(|e| e.to_string())
+}
+
+/// Write `value` to `path`, keeping the current content as the backup when
+/// it parses. The caller holds the lock of `path`.
+pub fn write_json<T: Serialize>(
+    path: &Path,
+    value: &T,
+    migrations: &[Migration],
+) -> Result<(), String> {
+    let contents = to_versioned_json(value, migrations.len())?;
+    let current_parses = fs::read_to_string(path)
+        .ok()
+        .is_some_and(|current| serde_json::from_str::<Value>(&current).is_ok());
+    if current_parses {
+        if let Err(e) = fs::copy(path, backup_path(path)) {
+            log::warn!("Failed to back up {}: {}", path.display(), e);
+        }
+    }
+    write_atomic(path, &contents)
+}
+
+/// [`write_json`] under the lock of `path`.
+pub fn save_json<T: Serialize>(
+    path: &Path,
+    value: &T,
+    migrations: &[Migration],
+) -> Result<(), String> {
+    let _lock = lock(path)?;
+    write_json(path, value, migrations)
+}
+
+// ---------------------------------------------------------------------------
+// Reading
+// ---------------------------------------------------------------------------
+
+/// The version of a file's content; files from before versioning are 0.
+pub fn version_of(value: &Value) -> usize {
+    value
+        .get(VERSION_KEY)
+        .and_then(Value::as_u64)
+        .map_or(0, |v| v as usize)
+}
+
+/// Run the migrations `value` has not been through yet.
+pub fn migrate(mut value: Value, migrations: &[Migration]) -> Value {
+    let version = version_of(&value);
+    if version > migrations.len() {
+        log::warn!(
+            "Config file version {} is newer than this build ({}); reading it as is",
+            version,
+            migrations.len()
+        );
+        return value;
+    }
+    for migration in &migrations[version..] {
+        migration(&mut value);
+    }
+    value
+}
+
+fn parse(path: &Path) -> Result<Option<Value>, String> {
+    match fs::read_to_string(path) {
+        Ok(content) => serde_json::from_str(&content)
+            .map(Some)
+            .map_err(|e| format!("{} is corrupt: {}", path.display(), e)),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(format!("Failed to read {}: {}", path.display(), e)),
+    }
+}
+
+/// Put the backup back in place of a corrupt `path`, keeping a copy of the
+/// corrupt file next to it.
+///
+/// Does not take the lock: readers such as `save_config` already hold it
+/// when they load. The corrupt file is copied, not moved, and the backup
+/// goes in with an atomic rename, so `path` never goes missing.
+fn restore_backup(path: &Path, backup: &Value) -> Result<(), String> {
+    fs::copy(path, corrupt_path(path))
+        .map_err(|e| format!("Failed to keep corrupt {} aside: {}", path.display(), e))?;
+    let contents = serde_json::to_string_pretty(backup).map_err(|e| e.to_string())?;
+    write_atomic(path, &contents)
+}
+
+/// The migrated content of `path`; `None` when there is no such file. When
+/// the file does not parse, the backup is restored and read instead.
+pub fn load_json(path: &Path, migrations: &[Migration]) -> Result<Option<Value>, String> {
+    let value = match parse(path) {
+        Ok(value) => value,
+        Err(error) => {
+            let Ok(Some(backup)) = parse(&backup_path(path)) else {
+                return Err(error);
+            };
+            log::warn!("{}; restoring the last good copy", error);
+            if let Err(e) = restore_backup(path, &backup) {
+                log::warn!("Failed to restore {}: {}", path.display(), e);
+            }
+            Some(backup)
+        }
+    };
+    Ok(value.map(|value| migrate(value, migrations)))
+}
@@ -0,0 +1,158 @@
+#[cfg(test)]
+mod tests {
+    use crate::config_store::{
+        backup_path, corrupt_path, load_json, lock, migrate, save_json, version_of, Migration,
+    };
+    use serde_json::{json, Value};
+    use std::fs;
+    use std::time::{Duration, Instant};
+
+    fn add_flag(value: &mut Value) {
+        value["flag"] = json!(true);
+    }
+
+    const MIGRATIONS: &[Migration] = &[add_flag];
+
+    #[test]
+    fn writes_are_versioned_and_keep_a_backup() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.json");
+
+        save_json(&path, &json!({ "theme": "dark" }), MIGRATIONS).unwrap();
+        let written: Value = serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(written, json!({ "theme": "dark", "version": 1 }));
+        assert!(!backup_path(&path).exists());
+
+        save_json(&path, &json!({ "theme": "light" }), MIGRATIONS).unwrap();
+        let backup: Value =
+            serde_json::from_str(&fs::read_to_string(backup_path(&path)).unwrap()).unwrap();
+        assert_eq!(backup["theme"], "dark");
+        // No temporary or lock files are left behind.
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 2);
+    }
+
+    #[test]
+    fn migrations_run_from_the_file_version() {
+        assert_eq!(version_of(&json!({})), 0);
+        assert_eq!(migrate(json!({}), MIGRATIONS), json!({ "flag": true }));
+        assert_eq!(
+            migrate(json!({ "version": 1 }), MIGRATIONS),
+            json!({ "version": 1 })
+        );
+        // A file from a newer build is read untouched.
+        assert_eq!(
+            migrate(json!({ "version": 5 }), MIGRATIONS),
+            json!({ "version": 5 })
+        );
+    }
+
+    #[test]
+    fn corrupt_files_are_restored_from_the_backup() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.json");
+        save_json(&path, &json!({ "theme": "dark" }), MIGRATIONS).unwrap();
+        save_json(&path, &json!({ "theme": "light" }), MIGRATIONS).unwrap();
+        fs::write(&path, "{\"theme\": \"li").unwrap();
+
+        let loaded = load_json(&path, MIGRATIONS).unwrap().unwrap();
+        assert_eq!(loaded["theme"], "dark");
+        assert_eq!(
+            fs::read_to_string(corrupt_path(&path)).unwrap(),
+            "{\"theme\": \"li"
+        );
+        let restored: Value = serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(restored["theme"], "dark");
+
+        // Without a backup the error is reported.
+        let other = dir.path().join("other.json");
+        fs::write(&other, "not json").unwrap();
+        assert!(load_json(&other, MIGRATIONS).is_err());
+        assert_eq!(
+            load_json(&dir.path().join("missing.json"), MIGRATIONS),
+            Ok(None)
+        );
+    }
+
+    #[test]
+    fn a_writer_holding_the_lock_can_load_a_corrupt_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.json");
+        save_json(&path, &json!({ "theme": "dark" }), MIGRATIONS).unwrap();
+        save_json(&path, &json!({ "theme": "light" }), MIGRATIONS).unwrap();
+        fs::write(&path, "{").unwrap();
+
+        let _held = lock(&path).unwrap();
+        let started = Instant::now();
+        let loaded = load_json(&path, MIGRATIONS).unwrap().unwrap();
+        assert_eq!(loaded["theme"], "dark");
+        assert!(started.elapsed() < Duration::from_secs(1));
+        assert_eq!(fs::read_to_string(corrupt_path(&path)).unwrap(), "{");
+    }
+
+    #[test]
+    fn a_second_writer_waits_for_the_lock() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.json");
+        let held = lock(&path).unwrap();
+
+        let waiter_path = path.clone();
+        let waiter = std::thread::spawn(move || {
+            let started = Instant::now();
+            save_json(&waiter_path, &json!({ "n": 2 }), MIGRATIONS).unwrap();
+            started.elapsed()
+        });
+        std::thread::sleep(Duration::from_millis(100));
+        assert!(!path.exists());
+        drop(held);
+
+        assert!(waiter.join().unwrap() >= Duration::from_millis(100));
+        assert_eq!(load_json(&path, MIGRATIONS).unwrap().unwrap()["n"], 2);
+    }
+
+    #[test]
+    fn legacy_connection_lists_are_wrapped_in_groups() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("connections.json");
+        fs::write(&path, "[]").unwrap();
+        let file = crate::persistence::load_connections_file(&path).unwrap();
+        assert!(file.groups.is_empty() && file.connections.is_empty());
+
+        let config = dir.path().join("config.json");
+        fs::write(&config, r#"{ "hiddenSchemas": {}, "theme": "dark" }"#).unwrap();
+        let loaded = load_json(&config, crate::config::CONFIG_MIGRATIONS)
+            .unwrap()
+            .unwrap();
+        assert_eq!(loaded, json!({ "theme": "dark" }));
+    }
+
+    #[test]
+    fn ssh_connections_are_written_atomically_with_a_backup() {
+        use crate::models::SshConnection;
+        use crate::persistence::{load_ssh_connections_file, write_ssh_connections_file};
+
+        let ssh = |id: &str| SshConnection {
+            id: id.into(),
+            name: format!("deploy@{}", id),
+            host: id.into(),
+            port: 22,
+            user: "deploy".into(),
+            auth_type: Some("password".into()),
+            password: None,
+            key_file: None,
+            key_passphrase: None,
+            save_in_keychain: Some(true),
+        };
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("ssh_connections.json");
+        assert!(load_ssh_connections_file(&path).unwrap().is_empty());
+
+        write_ssh_connections_file(&path, &[ssh("a")]).unwrap();
+        write_ssh_connections_file(&path, &[ssh("a"), ssh("b")]).unwrap();
+        assert_eq!(load_ssh_connections_file(&path).unwrap().len(), 2);
+
+        fs::write(&path, "[{").unwrap();
+        let restored = load_ssh_connections_file(&path).unwrap();
+        assert_eq!(restored.len(), 1);
+        assert_eq!(restored[0].id, "a");
+    }
+}
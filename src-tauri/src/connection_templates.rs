@@ -0,0 +1,440 @@
+//! Connection templates for shard and tenant fleets.
+//!
+//! A template borrows driver, credentials, SSH and SSL settings from a saved
+//! "base" connection and expands to one connection per host × database. Hosts
+//! and databases are patterns: `{01..12}` ranges and `{eu,us}` lists expand
+//! locally, while `*` / `?` globs (e.g. `tenant_*`) are matched against the
+//! databases the server lists.
+//!
+//! Members are never written to `connections.json`. Their ids
+//! (`template-<template id>-<hex key>`) resolve through
+//! `find_connection_by_id`, so every command that takes a connection id,
+//! `broadcast_query` included, works on them. Members are listed in the
+//! template's sidebar group.
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+use tauri::{AppHandle, Runtime};
+
+use crate::models::{ConnectionGroup, DatabaseSelection, SavedConnection};
+
+const TEMPLATES_FILE: &str = "connection_templates.json";
+const MEMBER_ID_PREFIX: &str = "template-";
+
+/// Upper bound on members per template, to catch runaway ranges.
+pub const MAX_MEMBERS: usize = 1000;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ConnectionTemplate {
+    #[serde(default)]
+    pub id: String,
+    pub name: String,
+    /// Saved connection providing everything except host and database.
+    pub base_connection_id: String,
+    /// Host patterns. Empty = the base connection's host.
+    #[serde(default)]
+    pub hosts: Vec<String>,
+    /// Database patterns. Empty = the base connection's database.
+    #[serde(default)]
+    pub databases: Vec<String>,
+    /// Sidebar group of the members; created from the name when missing.
+    #[serde(default)]
+    pub group_id: Option<String>,
+    #[serde(default)]
+    pub created_at: Option<String>,
+    #[serde(default)]
+    pub updated_at: Option<String>,
+}
+
+// ---------------------------------------------------------------------------
+// Patterns (testable; pure)
+// ---------------------------------------------------------------------------
+
+/// Expand `{a,b}` lists and `{1..10}` / `{01..10}` ranges. Several groups
+/// expand to their cartesian product, in order.
+pub fn expand_pattern(pattern: &str) -> Result<Vec<String>, String> {
+    let Some(open) = pattern.find('{') else {
+        return Ok(vec![pattern.to_string()]);
+    };
+    let close = pattern[open..]
+        .find('}')
+        .map(|i| open + i)
+        .ok_or_else(|| format!("Unclosed '{{' in pattern {}", pattern))?;
+    let (prefix, body, rest) = (
+        &pattern[..open],
+        &pattern[open + 1..close],
+        &pattern[close + 1..],
+    );
+
+    let choices: Vec<String> = match body.split_once("..") {
+        Some((start, end)) => {
+            let parse = |s: &str| {
+                s.parse::<u64>()
+                    .map_err(|_| format!("Invalid range {{{}}} in pattern {}", body, pattern))
+            };
+            let (from, to) = (parse(start)?, parse(end)?);
+            if from > to {
+                return Err(format!("Empty range {{{}}} in pattern {}", body, pattern));
+            }
+            if to - from >= MAX_MEMBERS as u64 {
+                return Err(format!("Range {{{}}} is too large", body));
+            }
+            let width = if start.len() > 1 && start.starts_with('0') {
+                start.len()
+            } else {
+                0
+            };
+            (from..=to)
+                .map(|n| format!("{:0width$}", n, width = width))
+                .collect()
+        }
+        None => body.split(',').map(|s| s.trim().to_string()).collect(),
+    };
+
+    let tails = expand_pattern(rest)?;
+    let mut out = Vec::with_capacity(choices.len() * tails.len());
+    for choice in &choices {
+        for tail in &tails {
+            out.push(format!("{}{}{}", prefix, choice, tail));
+            if out.len() > MAX_MEMBERS {
+                return Err(format!("Pattern {} expands to too many names", pattern));
+            }
+        }
+    }
+    Ok(out)
+}
+
+pub fn is_glob(pattern: &str) -> bool {
+    pattern.contains('*') || pattern.contains('?')
+}
+
+/// `*` matches any run of characters, `?` exactly one.
+pub fn glob_match(pattern: &str, name: &str) -> bool {
+    fn matches(p: &[char], n: &[char]) -> bool {
+        match p.split_first() {
+            None => n.is_empty(),
+            Some(('*', rest)) => (0..=n.len()).any(|i| matches(rest, &n[i..])),
+            Some(('?', rest)) => !n.is_empty() && matches(rest, &n[1..]),
+            Some((c, rest)) => n.first() == Some(c) && matches(rest, &n[1..]),
+        }
+    }
+    let p: Vec<char> = pattern.chars().collect();
+    let n: Vec<char> = name.chars().collect();
+    matches(&p, &n)
+}
+
+/// Expand every pattern, dropping duplicates. Globs are left out.
+pub fn expand_all(patterns: &[String]) -> Result<Vec<String>, String> {
+    let mut out: Vec<String> = Vec::new();
+    for pattern in patterns.iter().map(|p| p.trim()).filter(|p| !p.is_empty()) {
+        if is_glob(pattern) {
+            continue;
+        }
+        for name in expand_pattern(pattern)? {
+            if !out.contains(&name) {
+                out.push(name);
+            }
+        }
+    }
+    Ok(out)
+}
+
+/// Whether `database` is one of the template's databases.
+pub fn database_matches(template: &ConnectionTemplate, database: &str) -> Result<bool, String> {
+    if template.databases.iter().all(|p| p.trim().is_empty()) {
+        return Ok(database.is_empty());
+    }
+    Ok(template
+        .databases
+        .iter()
+        .any(|p| is_glob(p.trim()) && glob_match(p.trim(), database))
+        || expand_all(&template.databases)?
+            .iter()
+            .any(|d| d == database))
+}
+
+// ---------------------------------------------------------------------------
+// Members (testable; pure)
+// ---------------------------------------------------------------------------
+
+/// Id of the member for `host` and `database` (empty = the base value). Ids
+/// are used in file names, so host and database are hex-encoded.
+pub fn member_id(template_id: &str, host: &str, database: &str) -> String {
+    let key: String = format!("{}\u{1f}{}", host, database)
+        .bytes()
+        .map(|b| format!("{:02x}", b))
+        .collect();
+    format!("{}{}-{}", MEMBER_ID_PREFIX, template_id, key)
+}
+
+/// Split a member id into (template id, host, database).
+pub fn parse_member_id(id: &str) -> Option<(String, String, String)> {
+    let (template_id, key) = id.strip_prefix(MEMBER_ID_PREFIX)?.rsplit_once('-')?;
+    if key.len() % 2 != 0 || !key.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return None;
+    }
+    let bytes = (0..key.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&key[i..i + 2], 16).ok())
+        .collect::<Option<Vec<u8>>>()?;
+    let decoded = String::from_utf8(bytes).ok()?;
+    let (host, database) = decoded.split_once('\u{1f}')?;
+    Some((
+        template_id.to_string(),
+        host.to_string(),
+        database.to_string(),
+    ))
+}
+
+pub fn is_member_id(id: &str) -> bool {
+    id.starts_with(MEMBER_ID_PREFIX)
+}
+
+/// The concrete connection for one host/database of `template`.
+pub fn build_member(
+    template: &ConnectionTemplate,
+    base: &SavedConnection,
+    host: &str,
+    database: &str,
+) -> SavedConnection {
+    let mut member = base.clone();
+    member.id = member_id(&template.id, host, database);
+    member.name = match (host.is_empty(), database.is_empty()) {
+        (true, true) => template.name.clone(),
+        (false, true) => format!("{} / {}", template.name, host),
+        (true, false) => format!("{} / {}", template.name, database),
+        (false, false) => format!("{} / {} / {}", template.name, host, database),
+    };
+    member.group_id = template.group_id.clone();
+    member.sort_order = None;
+    // A template can expand to hundreds of members; none is warmed at launch.
+    member.connect_on_startup = None;
+    if !host.is_empty() {
+        // The base connection's replicas belong to its own host.
+        member.params.host = Some(host.to_string());
+        member.read_replicas = None;
+    }
+    if !database.is_empty() {
+        member.params.database = DatabaseSelection::Single(database.to_string());
+    }
+    member
+}
+
+// ---------------------------------------------------------------------------
+// Storage (testable; take a directory)
+// ---------------------------------------------------------------------------
+
+pub fn read_templates_in(dir: &Path) -> Vec<ConnectionTemplate> {
+    fs::read_to_string(dir.join(TEMPLATES_FILE))
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn write_templates_in(dir: &Path, templates: &[ConnectionTemplate]) -> Result<(), String> {
+    fs::create_dir_all(dir).map_err(|e| format!("Failed to create config dir: {}", e))?;
+    let json = serde_json::to_string_pretty(templates)
+        .map_err(|e| format!("Failed to serialize connection templates: {}", e))?;
+    fs::write(dir.join(TEMPLATES_FILE), json)
+        .map_err(|e| format!("Failed to save connection templates: {}", e))
+}
+
+pub fn find_template_in(dir: &Path, id: &str) -> Result<ConnectionTemplate, String> {
+    read_templates_in(dir)
+        .into_iter()
+        .find(|t| t.id == id)
+        .ok_or_else(|| format!("Connection template not found: {}", id))
+}
+
+/// Insert or replace `template`, matched by id. Patterns are validated and
+/// a template without an id gets a fresh one.
+pub fn upsert_template_in(
+    dir: &Path,
+    mut template: ConnectionTemplate,
+) -> Result<ConnectionTemplate, String> {
+    template.name = template.name.trim().to_string();
+    if template.name.is_empty() {
+        return Err("Connection template name cannot be empty".to_string());
+    }
+    if template.base_connection_id.is_empty() || is_member_id(&template.base_connection_id) {
+        return Err("Connection template needs a saved base connection".to_string());
+    }
+    if template.hosts.iter().any(|h| is_glob(h)) {
+        return Err("Host patterns cannot use '*' or '?'".to_string());
+    }
+    let hosts = expand_all(&template.hosts)?;
+    let databases = expand_all(&template.databases)?;
+    if hosts.len().max(1) * databases.len().max(1) > MAX_MEMBERS {
+        return Err(format!(
+            "Connection template expands to more than {} connections",
+            MAX_MEMBERS
+        ));
+    }
+
+    let mut templates = read_templates_in(dir);
+    let now = chrono::Utc::now().to_rfc3339();
+    if template.id.is_empty() {
+        template.id = uuid::Uuid::new_v4().to_string();
+    }
+    template.updated_at = Some(now.clone());
+
+    match templates.iter_mut().find(|t| t.id == template.id) {
+        Some(existing) => {
+            template.created_at = existing.created_at.clone();
+            *existing = template.clone();
+        }
+        None => {
+            template.created_at = Some(now);
+            templates.push(template.clone());
+        }
+    }
+    write_templates_in(dir, &templates)?;
+    Ok(template)
+}
+
+pub fn delete_template_in(dir: &Path, id: &str) -> Result<(), String> {
+    let mut templates = read_templates_in(dir);
+    let before = templates.len();
+    templates.retain(|t| t.id != id);
+    if templates.len() == before {
+        return Err(format!("Connection template not found: {}", id));
+    }
+    write_templates_in(dir, &templates)
+}
+
+// ---------------------------------------------------------------------------
+// Resolution
+// ---------------------------------------------------------------------------
+
+/// Resolve a member id to its connection, credentials included. Used by
+/// `find_connection_by_id`.
+pub fn resolve_member<R: Runtime>(app: &AppHandle<R>, id: &str) -> Result<SavedConnection, String> {
+    let not_found = || crate::i18n::t("errors.connectionNotFound");
+    let (template_id, host, database) = parse_member_id(id).ok_or_else(not_found)?;
+    let dir = crate::paths::app_config_dir(app)?;
+    let template = find_template_in(&dir, &template_id).map_err(|_| not_found())?;
+
+    let hosts = expand_all(&template.hosts)?;
+    let host_ok = if hosts.is_empty() {
+        host.is_empty()
+    } else {
+        hosts.contains(&host)
+    };
+    if !host_ok || !database_matches(&template, &database)? {
+        return Err(not_found());
+    }
+
+    let base = crate::commands::find_connection_by_id(app, &template.base_connection_id)?;
+    Ok(build_member(&template, &base, &host, &database))
+}
+
+/// Every member of `template`. Glob databases are listed on each host.
+/// Passwords are not included, like `get_connections`.
+async fn expand_members<R: Runtime>(
+    app: &AppHandle<R>,
+    template: &ConnectionTemplate,
+) -> Result<Vec<SavedConnection>, String> {
+    let base = crate::commands::find_connection_by_id(app, &template.base_connection_id)?;
+    let hosts = match expand_all(&template.hosts)? {
+        hosts if hosts.is_empty() => vec![String::new()],
+        hosts => hosts,
+    };
+    let static_databases = expand_all(&template.databases)?;
+    let globs: Vec<&str> = template
+        .databases
+        .iter()
+        .map(|p| p.trim())
+        .filter(|p| is_glob(p))
+        .collect();
+
+    let mut members = Vec::new();
+    for host in &hosts {
+        let mut databases = static_databases.clone();
+        if !globs.is_empty() {
+            let probe = build_member(template, &base, host, "");
+            let expanded =
+                crate::commands::expand_ssh_connection_params(app, &probe.params).await?;
+            let params = crate::commands::resolve_connection_params_with_id(&expanded, &probe.id)?;
+            let drv = crate::commands::driver_for(&probe.params.driver).await?;
+            for name in drv.get_databases(&params).await? {
+                if globs.iter().any(|g| glob_match(g, &name)) && !databases.contains(&name) {
+                    databases.push(name);
+                }
+            }
+        }
+        if databases.is_empty() && template.databases.iter().all(|p| p.trim().is_empty()) {
+            databases.push(String::new());
+        }
+        for database in &databases {
+            let mut member = build_member(template, &base, host, database);
+            member.params.password = None;
+            member.params.ssh_password = None;
+            member.params.ssh_key_passphrase = None;
+            members.push(member);
+            if members.len() > MAX_MEMBERS {
+                return Err(format!(
+                    "Connection template expands to more than {} connections",
+                    MAX_MEMBERS
+                ));
+            }
+        }
+    }
+    Ok(members)
+}
+
+// ---------------------------------------------------------------------------
+// Tauri commands
+// ---------------------------------------------------------------------------
+
+#[tauri::command]
+pub fn list_connection_templates<R: Runtime>(
+    app: AppHandle<R>,
+) -> Result<Vec<ConnectionTemplate>, String> {
+    Ok(read_templates_in(&crate::paths::app_config_dir(&app)?))
+}
+
+/// Save a template. A template without a group gets a new sidebar group
+/// named after it.
+#[tauri::command]
+pub fn save_connection_template<R: Runtime>(
+    app: AppHandle<R>,
+    mut template: ConnectionTemplate,
+) -> Result<ConnectionTemplate, String> {
+    crate::commands::find_connection_by_id(&app, &template.base_connection_id)?;
+
+    if template.group_id.is_none() {
+        let path = crate::commands::get_config_path(&app)?;
+        let _lock = crate::config_store::lock(&path)?;
+        let mut file = crate::persistence::load_connections_file(&path).unwrap_or_default();
+        let max_order = file.groups.iter().map(|g| g.sort_order).max().unwrap_or(-1);
+        let group = ConnectionGroup {
+            id: uuid::Uuid::new_v4().to_string(),
+            name: template.name.trim().to_string(),
+            collapsed: false,
+            sort_order: max_order + 1,
+        };
+        template.group_id = Some(group.id.clone());
+        file.groups.push(group);
+        crate::persistence::write_connections_file(&path, &file)?;
+    }
+
+    upsert_template_in(&crate::paths::app_config_dir(&app)?, template)
+}
+
+#[tauri::command]
+pub fn delete_connection_template<R: Runtime>(app: AppHandle<R>, id: String) -> Result<(), String> {
+    delete_template_in(&crate::paths::app_config_dir(&app)?, &id)
+}
+
+/// The concrete connections of a template, for the sidebar or as
+/// `broadcast_query` targets.
+#[tauri::command]
+pub async fn expand_connection_template<R: Runtime>(
+    app: AppHandle<R>,
+    id: String,
+) -> Result<Vec<SavedConnection>, String> {
+    let template = find_template_in(&crate::paths::app_config_dir(&app)?, &id)?;
+    expand_members(&app, &template).await
+}
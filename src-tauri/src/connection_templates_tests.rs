@@ -0,0 +1,190 @@
+#[cfg(test)]
+mod tests {
+    use crate::connection_templates::{
+        build_member, database_matches, delete_template_in, expand_all, expand_pattern, glob_match,
+        member_id, parse_member_id, read_templates_in, upsert_template_in, ConnectionTemplate,
+    };
+    use crate::models::{ConnectionParams, DatabaseSelection, ReplicaEndpoint, SavedConnection};
+    use tempfile::TempDir;
+
+    fn template(databases: &[&str]) -> ConnectionTemplate {
+        ConnectionTemplate {
+            id: "tpl1".to_string(),
+            name: "Tenants".to_string(),
+            base_connection_id: "base".to_string(),
+            hosts: Vec::new(),
+            databases: databases.iter().map(|d| d.to_string()).collect(),
+            group_id: Some("group1".to_string()),
+            created_at: None,
+            updated_at: None,
+        }
+    }
+
+    fn base() -> SavedConnection {
+        SavedConnection {
+            id: "base".to_string(),
+            name: "Primary".to_string(),
+            params: ConnectionParams {
+                driver: "postgres".to_string(),
+                host: Some("db.internal".to_string()),
+                port: Some(5432),
+                username: Some("app".to_string()),
+                password: Some("secret".to_string()),
+                database: DatabaseSelection::Single("postgres".to_string()),
+                ssl_mode: None,
+                ssl_ca: None,
+                ssl_cert: None,
+                ssl_key: None,
+                ssh_enabled: Some(false),
+                ssh_connection_id: None,
+                ssh_host: None,
+                ssh_port: None,
+                ssh_user: None,
+                ssh_password: None,
+                ssh_key_file: None,
+                ssh_key_passphrase: None,
+                save_in_keychain: Some(true),
+                read_only: false,
+                auth_method: None,
+                pool: None,
+                connection_id: None,
+            },
+            group_id: None,
+            sort_order: Some(3),
+            detect_json_in_text_columns: None,
+            protected: Some(true),
+            read_replicas: Some(vec![ReplicaEndpoint {
+                host: "replica.internal".to_string(),
+                port: None,
+            }]),
+            connect_on_startup: Some(true),
+        }
+    }
+
+    // --- expand_pattern ---
+
+    #[test]
+    fn expands_ranges_lists_and_products() {
+        assert_eq!(
+            expand_pattern("tenant_{1..3}").unwrap(),
+            vec!["tenant_1", "tenant_2", "tenant_3"]
+        );
+        assert_eq!(
+            expand_pattern("shard{08..10}").unwrap(),
+            vec!["shard08", "shard09", "shard10"]
+        );
+        assert_eq!(
+            expand_pattern("{eu,us}-db{1..2}").unwrap(),
+            vec!["eu-db1", "eu-db2", "us-db1", "us-db2"]
+        );
+        assert_eq!(expand_pattern("plain").unwrap(), vec!["plain"]);
+    }
+
+    #[test]
+    fn rejects_bad_patterns() {
+        assert!(expand_pattern("tenant_{1..").is_err());
+        assert!(expand_pattern("tenant_{5..1}").is_err());
+        assert!(expand_pattern("tenant_{a..z}").is_err());
+        assert!(expand_pattern("tenant_{1..5000}").is_err());
+    }
+
+    #[test]
+    fn expand_all_skips_globs_and_duplicates() {
+        let patterns = vec![
+            "a{1..2}".to_string(),
+            "a1".to_string(),
+            "tenant_*".to_string(),
+        ];
+        assert_eq!(expand_all(&patterns).unwrap(), vec!["a1", "a2"]);
+    }
+
+    // --- globs ---
+
+    #[test]
+    fn glob_matches_star_and_question_mark() {
+        assert!(glob_match("tenant_*", "tenant_acme"));
+        assert!(glob_match("tenant_*", "tenant_"));
+        assert!(glob_match("db?", "db1"));
+        assert!(!glob_match("db?", "db10"));
+        assert!(!glob_match("tenant_*", "billing"));
+    }
+
+    #[test]
+    fn database_matches_static_and_glob_patterns() {
+        let t = template(&["tenant_*", "billing_{1..2}"]);
+        assert!(database_matches(&t, "tenant_acme").unwrap());
+        assert!(database_matches(&t, "billing_2").unwrap());
+        assert!(!database_matches(&t, "billing_3").unwrap());
+        assert!(database_matches(&template(&[]), "").unwrap());
+    }
+
+    // --- members ---
+
+    #[test]
+    fn member_ids_round_trip_and_are_file_safe() {
+        let id = member_id("tpl1", "10.0.0.1", "tenant/a:b");
+        assert!(id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-'));
+        assert_eq!(
+            parse_member_id(&id),
+            Some((
+                "tpl1".to_string(),
+                "10.0.0.1".to_string(),
+                "tenant/a:b".to_string()
+            ))
+        );
+        assert_eq!(parse_member_id("template-tpl1-zz"), None);
+        assert_eq!(parse_member_id("conn1"), None);
+    }
+
+    #[test]
+    fn build_member_overrides_host_and_database() {
+        let member = build_member(&template(&["tenant_*"]), &base(), "", "tenant_acme");
+        assert_eq!(member.id, member_id("tpl1", "", "tenant_acme"));
+        assert_eq!(member.name, "Tenants / tenant_acme");
+        assert_eq!(member.group_id.as_deref(), Some("group1"));
+        assert_eq!(member.sort_order, None);
+        assert_eq!(member.params.host.as_deref(), Some("db.internal"));
+        assert_eq!(member.params.database.primary(), "tenant_acme");
+        assert_eq!(member.params.password.as_deref(), Some("secret"));
+        assert_eq!(member.protected, Some(true));
+        assert!(member.read_replicas.is_some());
+        assert_eq!(member.connect_on_startup, None);
+
+        let on_other_host = build_member(&template(&[]), &base(), "shard2.internal", "");
+        assert_eq!(on_other_host.read_replicas, None);
+    }
+
+    // --- storage ---
+
+    #[test]
+    fn upsert_assigns_id_and_keeps_created_at() {
+        let dir = TempDir::new().unwrap();
+        let mut t = template(&["tenant_*"]);
+        t.id = String::new();
+        let saved = upsert_template_in(dir.path(), t).unwrap();
+        assert!(!saved.id.is_empty());
+        assert!(saved.created_at.is_some());
+
+        let mut renamed = saved.clone();
+        renamed.name = "Customers".to_string();
+        let updated = upsert_template_in(dir.path(), renamed).unwrap();
+        assert_eq!(updated.created_at, saved.created_at);
+        assert_eq!(read_templates_in(dir.path()).len(), 1);
+
+        delete_template_in(dir.path(), &saved.id).unwrap();
+        assert!(read_templates_in(dir.path()).is_empty());
+        assert!(delete_template_in(dir.path(), &saved.id).is_err());
+    }
+
+    #[test]
+    fn upsert_rejects_glob_hosts_and_missing_base() {
+        let dir = TempDir::new().unwrap();
+        let mut t = template(&[]);
+        t.hosts = vec!["db-*".to_string()];
+        assert!(upsert_template_in(dir.path(), t).is_err());
+
+        let mut t = template(&[]);
+        t.base_connection_id = String::new();
+        assert!(upsert_template_in(dir.path(), t).is_err());
+    }
+}
@@ -0,0 +1,208 @@
+//! Query cost guard for protected connections.
+//!
+//! Before an ad-hoc query runs on a connection flagged as `protected`, the
+//! guard runs a plain EXPLAIN (never ANALYZE) and compares the planner's
+//! estimates with the configured limits. Queries over a limit are refused
+//! until the user confirms and re-runs them with an explicit override.
+//!
+//! The guard fails open: statements EXPLAIN cannot handle (DDL, driver
+//! without EXPLAIN support, permission errors) run as usual.
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Runtime};
+
+use crate::commands::{
+    driver_for, expand_ssh_connection_params, find_connection_by_id,
+    resolve_connection_params_with_id,
+};
+use crate::models::{ExplainNode, ExplainPlan};
+use crate::plan_history::{classify_access, AccessKind};
+
+pub const DEFAULT_MAX_ROWS: u64 = 100_000;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CostLimits {
+    pub max_rows: Option<u64>,
+    pub max_cost: Option<f64>,
+}
+
+impl CostLimits {
+    pub fn from_config() -> Self {
+        let config = crate::config::get_cached_config();
+        Self {
+            max_rows: Some(config.cost_guard_max_rows.unwrap_or(DEFAULT_MAX_ROWS))
+                .filter(|rows| *rows > 0),
+            max_cost: config.cost_guard_max_cost.filter(|cost| *cost > 0.0),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct CostEstimate {
+    /// Largest row estimate of any plan node, i.e. the rows the query touches.
+    pub estimated_rows: Option<f64>,
+    /// Planner cost of the whole query (PostgreSQL/MySQL units).
+    pub total_cost: Option<f64>,
+    /// Relations read by a full table scan.
+    pub full_scans: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct CostCheck {
+    pub protected: bool,
+    pub estimate: Option<CostEstimate>,
+    /// Limits the estimate exceeds; empty when the query may run.
+    pub exceeded: Vec<String>,
+    /// Why no estimate is available (e.g. EXPLAIN failed).
+    pub error: Option<String>,
+}
+
+// ---------------------------------------------------------------------------
+// Estimates (testable; pure)
+// ---------------------------------------------------------------------------
+
+/// Statements the guard estimates. Anything else (DDL, SET, ...) is let through.
+pub fn is_guarded_statement(query: &str) -> bool {
+    let stripped = crate::drivers::common::strip_leading_sql_comments(query);
+    let keyword = stripped
+        .trim_start_matches(|c: char| c == '(' || c.is_whitespace())
+        .split(|c: char| c.is_whitespace() || c == '(')
+        .next()
+        .unwrap_or_default()
+        .to_uppercase();
+    matches!(
+        keyword.as_str(),
+        "SELECT" | "WITH" | "UPDATE" | "DELETE" | "INSERT" | "REPLACE"
+    )
+}
+
+pub fn estimate(plan: &ExplainPlan) -> CostEstimate {
+    fn walk(node: &ExplainNode, out: &mut CostEstimate) {
+        if let Some(rows) = node.plan_rows {
+            out.estimated_rows = Some(out.estimated_rows.map_or(rows, |r| r.max(rows)));
+        }
+        if classify_access(node) == AccessKind::FullScan {
+            if let Some(relation) = &node.relation {
+                if !out.full_scans.contains(relation) {
+                    out.full_scans.push(relation.clone());
+                }
+            }
+        }
+        for child in &node.children {
+            walk(child, out);
+        }
+    }
+
+    let mut out = CostEstimate {
+        estimated_rows: None,
+        total_cost: plan.root.total_cost,
+        full_scans: Vec::new(),
+    };
+    walk(&plan.root, &mut out);
+    out
+}
+
+/// Human-readable list of the limits `estimate` exceeds.
+pub fn exceeded_limits(estimate: &CostEstimate, limits: &CostLimits) -> Vec<String> {
+    let mut exceeded = Vec::new();
+    if let (Some(rows), Some(max)) = (estimate.estimated_rows, limits.max_rows) {
+        if rows > max as f64 {
+            let scans = if estimate.full_scans.is_empty() {
+                String::new()
+            } else {
+                format!(" (full scan of {})", estimate.full_scans.join(", "))
+            };
+            exceeded.push(format!(
+                "~{:.0} rows estimated, limit {}{}",
+                rows, max, scans
+            ));
+        }
+    }
+    if let (Some(cost), Some(max)) = (estimate.total_cost, limits.max_cost) {
+        if cost > max {
+            exceeded.push(format!("cost {:.0} estimated, limit {:.0}", cost, max));
+        }
+    }
+    exceeded
+}
+
+// ---------------------------------------------------------------------------
+// Checks
+// ---------------------------------------------------------------------------
+
+/// Estimate `query` on `connection_id`. Connections that are not protected,
+/// and statements the guard does not cover, come back without an estimate.
+pub async fn check_query<R: Runtime>(
+    app: &AppHandle<R>,
+    connection_id: &str,
+    query: &str,
+    schema: Option<&str>,
+) -> Result<CostCheck, String> {
+    let saved_conn = find_connection_by_id(app, connection_id)?;
+    let protected = saved_conn.protected.unwrap_or(false);
+    let mut check = CostCheck {
+        protected,
+        estimate: None,
+        exceeded: Vec::new(),
+        error: None,
+    };
+    if !protected || !is_guarded_statement(query) {
+        return Ok(check);
+    }
+
+    let expanded = expand_ssh_connection_params(app, &saved_conn.params).await?;
+    let params = resolve_connection_params_with_id(&expanded, connection_id)?;
+    let drv = driver_for(&saved_conn.params.driver).await?;
+    match drv.explain_query(&params, query, false, schema).await {
+        Ok(plan) => {
+            let estimate = estimate(&plan);
+            check.exceeded = exceeded_limits(&estimate, &CostLimits::from_config());
+            check.estimate = Some(estimate);
+        }
+        Err(e) => {
+            log::debug!("Cost guard could not estimate query: {}", e);
+            check.error = Some(e);
+        }
+    }
+    Ok(check)
+}
+
+/// Refuse `query` when it exceeds the limits of a protected connection.
+pub async fn enforce<R: Runtime>(
+    app: &AppHandle<R>,
+    connection_id: &str,
+    query: &str,
+    schema: Option<&str>,
+) -> Result<(), String> {
+    let check = check_query(app, connection_id, query, schema).await?;
+    if check.exceeded.is_empty() {
+        return Ok(());
+    }
+    log::warn!(
+        "Cost guard blocked query on protected connection {}: {}",
+        connection_id,
+        check.exceeded.join("; ")
+    );
+    Err(crate::i18n::t_with(
+        "errors.costGuardExceeded",
+        &[("reasons", &check.exceeded.join("; "))],
+    ))
+}
+
+// ---------------------------------------------------------------------------
+// Tauri commands
+// ---------------------------------------------------------------------------
+
+/// Estimate a query before running it, e.g. to show why the guard refused it.
+#[tauri::command]
+pub async fn estimate_query_cost<R: Runtime>(
+    app: AppHandle<R>,
+    connection_id: String,
+    query: String,
+    schema: Option<String>,
+) -> Result<CostCheck, String> {
+    let query = crate::commands::sanitize_user_query(&query);
+    check_query(&app, &connection_id, &query, schema.as_deref()).await
+}
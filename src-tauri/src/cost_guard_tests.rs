@@ -0,0 +1,132 @@
+#[cfg(test)]
+mod tests {
+    use crate::cost_guard::{estimate, exceeded_limits, is_guarded_statement, CostLimits};
+    use crate::models::{ExplainNode, ExplainPlan};
+    use std::collections::HashMap;
+
+    fn node(
+        node_type: &str,
+        relation: Option<&str>,
+        rows: Option<f64>,
+        children: Vec<ExplainNode>,
+    ) -> ExplainNode {
+        ExplainNode {
+            id: node_type.to_string(),
+            node_type: node_type.to_string(),
+            relation: relation.map(str::to_string),
+            startup_cost: None,
+            total_cost: None,
+            plan_rows: rows,
+            actual_rows: None,
+            actual_time_ms: None,
+            actual_loops: None,
+            buffers_hit: None,
+            buffers_read: None,
+            filter: None,
+            index_condition: None,
+            join_type: None,
+            hash_condition: None,
+            extra: HashMap::new(),
+            children,
+        }
+    }
+
+    fn plan(root: ExplainNode, cost: Option<f64>) -> ExplainPlan {
+        let mut root = root;
+        root.total_cost = cost;
+        ExplainPlan {
+            root,
+            planning_time_ms: None,
+            execution_time_ms: None,
+            original_query: "SELECT 1".to_string(),
+            driver: "postgres".to_string(),
+            has_analyze_data: false,
+            raw_output: None,
+        }
+    }
+
+    fn limits(max_rows: Option<u64>, max_cost: Option<f64>) -> CostLimits {
+        CostLimits { max_rows, max_cost }
+    }
+
+    // --- is_guarded_statement ---
+
+    #[test]
+    fn guards_reads_and_writes() {
+        assert!(is_guarded_statement("SELECT * FROM users"));
+        assert!(is_guarded_statement(
+            "  with x as (select 1) select * from x"
+        ));
+        assert!(is_guarded_statement("-- cleanup\nDELETE FROM logs"));
+        assert!(is_guarded_statement("update users set a = 1"));
+        assert!(is_guarded_statement("(SELECT 1) UNION (SELECT 2)"));
+    }
+
+    #[test]
+    fn lets_ddl_and_session_statements_through() {
+        assert!(!is_guarded_statement("CREATE INDEX idx ON users (email)"));
+        assert!(!is_guarded_statement("SET search_path = app"));
+        assert!(!is_guarded_statement(""));
+    }
+
+    // --- estimate ---
+
+    #[test]
+    fn estimate_takes_largest_row_count_and_root_cost() {
+        let p = plan(
+            node(
+                "Hash Join",
+                None,
+                Some(10.0),
+                vec![
+                    node("Seq Scan", Some("events"), Some(2_000_000.0), vec![]),
+                    node("Index Scan", Some("users"), Some(1.0), vec![]),
+                ],
+            ),
+            Some(45_000.0),
+        );
+        let e = estimate(&p);
+        assert_eq!(e.estimated_rows, Some(2_000_000.0));
+        assert_eq!(e.total_cost, Some(45_000.0));
+        assert_eq!(e.full_scans, vec!["events".to_string()]);
+    }
+
+    #[test]
+    fn estimate_without_row_counts_is_empty() {
+        let e = estimate(&plan(node("SCAN", Some("t"), None, vec![]), None));
+        assert_eq!(e.estimated_rows, None);
+        assert_eq!(e.total_cost, None);
+    }
+
+    // --- exceeded_limits ---
+
+    #[test]
+    fn reports_row_limit_with_full_scans() {
+        let p = plan(
+            node("Seq Scan", Some("events"), Some(500_000.0), vec![]),
+            Some(9_000.0),
+        );
+        let exceeded = exceeded_limits(&estimate(&p), &limits(Some(100_000), None));
+        assert_eq!(exceeded.len(), 1);
+        assert!(exceeded[0].contains("500000"));
+        assert!(exceeded[0].contains("full scan of events"));
+    }
+
+    #[test]
+    fn reports_cost_limit() {
+        let p = plan(node("Index Scan", Some("t"), Some(10.0), vec![]), Some(1e6));
+        let exceeded = exceeded_limits(&estimate(&p), &limits(Some(100_000), Some(50_000.0)));
+        assert_eq!(exceeded.len(), 1);
+        assert!(exceeded[0].starts_with("cost 1000000"));
+    }
+
+    #[test]
+    fn nothing_exceeded_within_limits_or_without_limits() {
+        let p = plan(
+            node("Seq Scan", Some("t"), Some(5_000.0), vec![]),
+            Some(100.0),
+        );
+        assert!(exceeded_limits(&estimate(&p), &limits(Some(100_000), Some(1_000.0))).is_empty());
+        assert!(exceeded_limits(&estimate(&p), &limits(None, None)).is_empty());
+    }
+}
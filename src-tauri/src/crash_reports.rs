@@ -0,0 +1,232 @@
+//! Crash reports for Rust panics.
+//!
+//! A panic hook writes a redacted report (panic message, location, backtrace,
+//! OS and app version) to disk before the process aborts. Only panics that
+//! end the process are recorded: every panic in release builds, which abort
+//! on panic, and main-thread panics otherwise. A panic the runtime recovers
+//! from, such as one inside a spawned tokio task of a debug build, is not a
+//! crash. Nothing leaves the machine on its own: the UI lists pending reports
+//! on the next launch, the user reviews the exact content, and only then
+//! calls `send_crash_report`.
+//!
+//! Native crashes (e.g. a dyld failure on macOS) never reach the hook and are
+//! left to the OS crash reporter. No minidumps are written for them: that
+//! takes an out-of-process crash handler, and a minidump holds raw process
+//! memory (row data, passwords) that the user cannot review or redact before
+//! sending, which the reporting flow above relies on.
+//!
+//! Storage layout under the application config directory:
+//!   - `crash_reports/<id>.json` — one report per crash
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use tauri::AppHandle;
+
+use crate::paths::get_app_config_dir;
+
+const CRASH_REPORTS_DIR: &str = "crash_reports";
+/// Older reports are deleted once more than this many are on disk.
+pub const MAX_CRASH_REPORTS: usize = 20;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct CrashReport {
+    pub id: String,
+    pub timestamp: String,
+    pub app_version: String,
+    pub os: String,
+    pub os_version: Option<String>,
+    pub arch: String,
+    pub thread: Option<String>,
+    pub message: String,
+    /// `file:line:column` of the panic.
+    pub location: Option<String>,
+    pub backtrace: String,
+    /// Set once the report was delivered by `send_crash_report`.
+    #[serde(default)]
+    pub sent: bool,
+}
+
+// ---------------------------------------------------------------------------
+// Report building
+// ---------------------------------------------------------------------------
+
+/// Build a redacted report. The message goes through the telemetry scrubber
+/// (it may quote row data or connection details); the backtrace only has the
+/// user's home directory replaced, so symbol names and source paths survive.
+pub fn build_report(
+    message: &str,
+    location: Option<String>,
+    thread: Option<String>,
+    backtrace: &str,
+    home_dir: Option<&str>,
+) -> CrashReport {
+    let backtrace = match home_dir.filter(|h| h.len() > 1) {
+        Some(home) => backtrace.replace(home, "~"),
+        None => backtrace.to_string(),
+    };
+
+    CrashReport {
+        id: uuid::Uuid::new_v4().to_string(),
+        timestamp: chrono::Utc::now().to_rfc3339(),
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+        os: std::env::consts::OS.to_string(),
+        os_version: sysinfo::System::long_os_version(),
+        arch: std::env::consts::ARCH.to_string(),
+        thread,
+        message: crate::telemetry::scrub_text(message, home_dir),
+        location,
+        backtrace,
+        sent: false,
+    }
+}
+
+/// Whether a panic on `thread` ends the process: always when panics abort,
+/// otherwise only on the main thread. Panics on other threads unwind that
+/// thread alone, and tokio turns a task's panic into a `JoinError`.
+pub fn is_fatal_panic(thread: Option<&str>, panics_abort: bool) -> bool {
+    panics_abort || thread == Some("main")
+}
+
+/// Install a panic hook that saves a crash report for panics that end the
+/// process, then runs the previous hook.
+pub fn install_panic_hook() {
+    let previous = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        let thread = std::thread::current().name().map(str::to_string);
+        if !is_fatal_panic(thread.as_deref(), cfg!(panic = "abort")) {
+            previous(info);
+            return;
+        }
+
+        let payload = info.payload();
+        let message = payload
+            .downcast_ref::<&str>()
+            .map(|s| s.to_string())
+            .or_else(|| payload.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "Box<dyn Any>".to_string());
+        let location = info
+            .location()
+            .map(|l| format!("{}:{}:{}", l.file(), l.line(), l.column()));
+        let backtrace = std::backtrace::Backtrace::force_capture().to_string();
+        let home = directories::BaseDirs::new().map(|d| d.home_dir().to_string_lossy().to_string());
+
+        let report = build_report(&message, location, thread, &backtrace, home.as_deref());
+        if let Err(e) = save_report_in(&reports_dir(), &report, MAX_CRASH_REPORTS) {
+            eprintln!("Failed to save crash report: {}", e);
+        }
+
+        previous(info);
+    }));
+}
+
+// ---------------------------------------------------------------------------
+// Storage (testable; take a directory)
+// ---------------------------------------------------------------------------
+
+fn reports_dir() -> PathBuf {
+    get_app_config_dir().join(CRASH_REPORTS_DIR)
+}
+
+fn report_path_in(dir: &Path, id: &str) -> Result<PathBuf, String> {
+    // Ids are UUIDs; refuse anything that could escape the directory.
+    if id.is_empty() || !id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-') {
+        return Err(format!("Invalid crash report id: {}", id));
+    }
+    Ok(dir.join(format!("{}.json", id)))
+}
+
+pub fn list_reports_in(dir: &Path) -> Result<Vec<CrashReport>, String> {
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut reports: Vec<CrashReport> = fs::read_dir(dir)
+        .map_err(|e| format!("Failed to read crash reports: {}", e))?
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.path().extension().map_or(false, |ext| ext == "json"))
+        .filter_map(|entry| {
+            let content = fs::read_to_string(entry.path()).ok()?;
+            serde_json::from_str(&content).ok()
+        })
+        .collect();
+    reports.sort_by(|a, b| b.timestamp.cmp(&a.timestamp));
+    Ok(reports)
+}
+
+pub fn save_report_in(dir: &Path, report: &CrashReport, max_reports: usize) -> Result<(), String> {
+    fs::create_dir_all(dir).map_err(|e| format!("Failed to create crash reports dir: {}", e))?;
+    let json = serde_json::to_string_pretty(report)
+        .map_err(|e| format!("Failed to serialize crash report: {}", e))?;
+    fs::write(report_path_in(dir, &report.id)?, json)
+        .map_err(|e| format!("Failed to write crash report: {}", e))?;
+
+    for old in list_reports_in(dir)?.into_iter().skip(max_reports) {
+        delete_report_in(dir, &old.id)?;
+    }
+    Ok(())
+}
+
+pub fn get_report_in(dir: &Path, id: &str) -> Result<CrashReport, String> {
+    let path = report_path_in(dir, id)?;
+    let content =
+        fs::read_to_string(&path).map_err(|_| format!("Crash report not found: {}", id))?;
+    serde_json::from_str(&content).map_err(|e| format!("Failed to parse crash report: {}", e))
+}
+
+pub fn delete_report_in(dir: &Path, id: &str) -> Result<(), String> {
+    let path = report_path_in(dir, id)?;
+    if path.exists() {
+        fs::remove_file(&path).map_err(|e| format!("Failed to delete crash report: {}", e))?;
+    }
+    Ok(())
+}
+
+// ---------------------------------------------------------------------------
+// Tauri commands
+// ---------------------------------------------------------------------------
+
+/// All saved reports, newest first.
+#[tauri::command]
+pub fn list_crash_reports() -> Result<Vec<CrashReport>, String> {
+    list_reports_in(&reports_dir())
+}
+
+#[tauri::command]
+pub fn get_crash_report(id: String) -> Result<CrashReport, String> {
+    get_report_in(&reports_dir(), &id)
+}
+
+#[tauri::command]
+pub fn delete_crash_report(id: String) -> Result<(), String> {
+    delete_report_in(&reports_dir(), &id)
+}
+
+/// Send a report the user has reviewed to `crashReportEndpoint`, then mark it
+/// as sent. The report is posted exactly as stored on disk.
+#[tauri::command]
+pub async fn send_crash_report(app: AppHandle, id: String) -> Result<(), String> {
+    let endpoint = crate::config::load_config_internal(&app)
+        .crash_report_endpoint
+        .filter(|e| !e.trim().is_empty())
+        .ok_or_else(|| "No crash report endpoint configured".to_string())?;
+
+    let dir = reports_dir();
+    let mut report = get_report_in(&dir, &id)?;
+
+    let res = reqwest::Client::new()
+        .post(&endpoint)
+        .header("User-Agent", "Tabularis")
+        .json(&report)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to send crash report: {}", e))?;
+
+    if !res.status().is_success() {
+        return Err(format!("Crash report endpoint error: {}", res.status()));
+    }
+
+    report.sent = true;
+    save_report_in(&dir, &report, MAX_CRASH_REPORTS)
+}
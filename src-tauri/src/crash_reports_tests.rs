@@ -0,0 +1,95 @@
+#[cfg(test)]
+mod tests {
+    use crate::crash_reports::{
+        build_report, delete_report_in, get_report_in, is_fatal_panic, list_reports_in,
+        save_report_in, CrashReport,
+    };
+    use tempfile::TempDir;
+
+    fn report_at(timestamp: &str) -> CrashReport {
+        let mut report = build_report("boom", None, None, "", None);
+        report.timestamp = timestamp.to_string();
+        report
+    }
+
+    #[test]
+    fn build_report_redacts_message_and_home_dir() {
+        let report = build_report(
+            "failed to open '/home/alice/secret.db' for bob@corp.com",
+            Some("src/commands.rs:10:5".to_string()),
+            Some("main".to_string()),
+            "0: tabularis::run\n   at /home/alice/src/tabularis/src/lib.rs:42",
+            Some("/home/alice"),
+        );
+
+        assert_eq!(report.message, "failed to open '?' for <email>");
+        assert!(report
+            .backtrace
+            .contains("at ~/src/tabularis/src/lib.rs:42"));
+        assert!(!report.backtrace.contains("alice"));
+        assert_eq!(report.location.as_deref(), Some("src/commands.rs:10:5"));
+        assert_eq!(report.app_version, env!("CARGO_PKG_VERSION"));
+        assert!(!report.sent);
+    }
+
+    #[test]
+    fn only_panics_that_end_the_process_are_fatal() {
+        assert!(is_fatal_panic(Some("main"), false));
+        assert!(!is_fatal_panic(Some("tokio-runtime-worker"), false));
+        assert!(!is_fatal_panic(None, false));
+        assert!(is_fatal_panic(Some("tokio-runtime-worker"), true));
+    }
+
+    #[test]
+    fn save_and_get_round_trip() {
+        let tmp = TempDir::new().unwrap();
+        let report = report_at("2026-01-01T00:00:00Z");
+
+        save_report_in(tmp.path(), &report, 10).unwrap();
+
+        assert_eq!(get_report_in(tmp.path(), &report.id).unwrap(), report);
+    }
+
+    #[test]
+    fn list_is_newest_first_and_pruned() {
+        let tmp = TempDir::new().unwrap();
+        for ts in [
+            "2026-01-01T00:00:00Z",
+            "2026-01-03T00:00:00Z",
+            "2026-01-02T00:00:00Z",
+        ] {
+            save_report_in(tmp.path(), &report_at(ts), 2).unwrap();
+        }
+
+        let reports = list_reports_in(tmp.path()).unwrap();
+        let stamps: Vec<&str> = reports.iter().map(|r| r.timestamp.as_str()).collect();
+        assert_eq!(stamps, vec!["2026-01-03T00:00:00Z", "2026-01-02T00:00:00Z"]);
+    }
+
+    #[test]
+    fn delete_removes_report() {
+        let tmp = TempDir::new().unwrap();
+        let report = report_at("2026-01-01T00:00:00Z");
+        save_report_in(tmp.path(), &report, 10).unwrap();
+
+        delete_report_in(tmp.path(), &report.id).unwrap();
+
+        assert!(list_reports_in(tmp.path()).unwrap().is_empty());
+        assert!(get_report_in(tmp.path(), &report.id).is_err());
+    }
+
+    #[test]
+    fn rejects_ids_escaping_the_directory() {
+        let tmp = TempDir::new().unwrap();
+        assert!(get_report_in(tmp.path(), "../config").is_err());
+        assert!(delete_report_in(tmp.path(), "a/b").is_err());
+    }
+
+    #[test]
+    fn list_missing_dir_is_empty() {
+        let tmp = TempDir::new().unwrap();
+        assert!(list_reports_in(&tmp.path().join("missing"))
+            .unwrap()
+            .is_empty());
+    }
+}
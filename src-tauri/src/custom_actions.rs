@@ -0,0 +1,181 @@
+//! User-defined context-menu actions.
+//!
+//! An action is a parametrized query bound to a table, and optionally to one
+//! of its columns, e.g. "Show orders for this customer" on `customers.id`:
+//!
+//! ```sql
+//! SELECT * FROM orders WHERE customer_id = :value
+//! ```
+//!
+//! `:value` is the clicked cell; `:<column>` is any other cell of the same
+//! row. Values are always bound as query parameters, never spliced into the
+//! SQL.
+
+use std::collections::HashMap;
+use std::fs;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tauri::{AppHandle, Runtime, State, WebviewWindow};
+
+use crate::commands::QueryCancellationState;
+use crate::models::QueryResult;
+use crate::preferences::connection_preferences_file;
+
+const CUSTOM_ACTIONS_FILE: &str = "custom_actions.json";
+
+/// Name of the parameter holding the clicked cell.
+pub const VALUE_PARAM: &str = "value";
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct CustomAction {
+    pub id: String,
+    /// Label shown in the context menu.
+    pub name: String,
+    pub table: String,
+    #[serde(default)]
+    pub schema: Option<String>,
+    /// Offer the action only on this column's cells instead of every cell
+    /// of the table.
+    #[serde(default)]
+    pub column: Option<String>,
+    pub sql: String,
+    /// Default: true
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+/// Load the actions of a connection, in menu order.
+pub fn load_custom_actions(connection_id: &str) -> Result<Vec<CustomAction>, String> {
+    let path = connection_preferences_file(connection_id, CUSTOM_ACTIONS_FILE)?;
+
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let content = fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read custom actions file: {}", e))?;
+
+    serde_json::from_str(&content)
+        .map_err(|e| format!("Failed to parse custom actions file: {}", e))
+}
+
+/// The enabled actions to offer on a cell of `table` (and `column`, when a
+/// cell rather than a row was clicked).
+pub fn actions_for<'a>(
+    actions: &'a [CustomAction],
+    schema: Option<&str>,
+    table: &str,
+    column: Option<&str>,
+) -> Vec<&'a CustomAction> {
+    actions
+        .iter()
+        .filter(|a| a.enabled && a.table == table)
+        .filter(|a| match (a.schema.as_deref(), schema) {
+            (Some(expected), Some(actual)) => expected == actual,
+            _ => true,
+        })
+        .filter(|a| match a.column.as_deref() {
+            None => true,
+            Some(expected) => column == Some(expected),
+        })
+        .collect()
+}
+
+/// Query parameters for a run: every cell of `row` by column name, plus the
+/// clicked cell as `value`.
+pub fn action_params(value: Value, row: Option<HashMap<String, Value>>) -> HashMap<String, Value> {
+    let mut params = row.unwrap_or_default();
+    params.insert(VALUE_PARAM.to_string(), value);
+    params
+}
+
+fn validate_action(action: &CustomAction) -> Result<(), String> {
+    if action.name.trim().is_empty() {
+        return Err(format!("Custom action {} has no name", action.id));
+    }
+    if action.table.trim().is_empty() {
+        return Err(format!("Custom action '{}' has no table", action.name));
+    }
+    if action.sql.trim().is_empty() {
+        return Err(format!("Custom action '{}' has no SQL", action.name));
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn get_custom_actions(
+    connection_id: String,
+    table: Option<String>,
+    schema: Option<String>,
+    column: Option<String>,
+) -> Result<Vec<CustomAction>, String> {
+    let actions = load_custom_actions(&connection_id)?;
+    Ok(match table {
+        Some(table) => actions_for(&actions, schema.as_deref(), &table, column.as_deref())
+            .into_iter()
+            .cloned()
+            .collect(),
+        None => actions,
+    })
+}
+
+/// Replace the actions of a connection.
+#[tauri::command]
+pub async fn save_custom_actions(
+    connection_id: String,
+    actions: Vec<CustomAction>,
+) -> Result<(), String> {
+    for action in &actions {
+        validate_action(action)?;
+    }
+
+    let path = connection_preferences_file(&connection_id, CUSTOM_ACTIONS_FILE)?;
+    let json = serde_json::to_string_pretty(&actions)
+        .map_err(|e| format!("Failed to serialize custom actions: {}", e))?;
+
+    fs::write(&path, json).map_err(|e| format!("Failed to write custom actions file: {}", e))
+}
+
+/// Run an action with the clicked cell (`value`) and, optionally, the rest
+/// of its row. Goes through `execute_query_with_params`, so read-only
+/// connections and cancellation behave as for any editor query.
+#[tauri::command]
+pub async fn run_custom_action<R: Runtime>(
+    app: AppHandle<R>,
+    window: WebviewWindow<R>,
+    state: State<'_, QueryCancellationState>,
+    connection_id: String,
+    action_id: String,
+    value: Value,
+    row: Option<HashMap<String, Value>>,
+    limit: Option<u32>,
+    page: Option<u32>,
+) -> Result<QueryResult, String> {
+    let action = load_custom_actions(&connection_id)?
+        .into_iter()
+        .find(|a| a.id == action_id)
+        .ok_or_else(|| format!("Custom action {} not found", action_id))?;
+    if !action.enabled {
+        return Err(format!("Custom action '{}' is disabled", action.name));
+    }
+
+    crate::commands::execute_query_with_params(
+        app,
+        window,
+        state,
+        connection_id,
+        action.sql,
+        action_params(value, row),
+        limit,
+        page,
+        action.schema,
+        None,
+    )
+    .await
+}
@@ -0,0 +1,98 @@
+#[cfg(test)]
+mod tests {
+    use crate::custom_actions::{action_params, actions_for, CustomAction, VALUE_PARAM};
+    use serde_json::json;
+    use std::collections::HashMap;
+
+    fn action(id: &str, table: &str, schema: Option<&str>, column: Option<&str>) -> CustomAction {
+        CustomAction {
+            id: id.into(),
+            name: format!("Action {}", id),
+            table: table.into(),
+            schema: schema.map(|s| s.into()),
+            column: column.map(|c| c.into()),
+            sql: "SELECT * FROM orders WHERE customer_id = :value".into(),
+            enabled: true,
+        }
+    }
+
+    fn ids(actions: Vec<&CustomAction>) -> Vec<&str> {
+        actions.iter().map(|a| a.id.as_str()).collect()
+    }
+
+    #[test]
+    fn column_actions_only_match_their_column() {
+        let actions = vec![
+            action("row", "customers", None, None),
+            action("id", "customers", None, Some("id")),
+        ];
+        assert_eq!(
+            ids(actions_for(&actions, None, "customers", Some("id"))),
+            vec!["row", "id"]
+        );
+        assert_eq!(
+            ids(actions_for(&actions, None, "customers", Some("name"))),
+            vec!["row"]
+        );
+        assert_eq!(
+            ids(actions_for(&actions, None, "customers", None)),
+            vec!["row"]
+        );
+    }
+
+    #[test]
+    fn filters_by_table_and_schema() {
+        let actions = vec![
+            action("public", "customers", Some("public"), None),
+            action("sales", "customers", Some("sales"), None),
+            action("any", "customers", None, None),
+            action("other", "orders", None, None),
+        ];
+        assert_eq!(
+            ids(actions_for(&actions, Some("public"), "customers", None)),
+            vec!["public", "any"]
+        );
+        // Without a schema (MySQL, SQLite) the action's schema is not checked.
+        assert_eq!(
+            ids(actions_for(&actions, None, "customers", None)),
+            vec!["public", "sales", "any"]
+        );
+    }
+
+    #[test]
+    fn skips_disabled_actions() {
+        let mut disabled = action("off", "customers", None, None);
+        disabled.enabled = false;
+        let actions = vec![disabled, action("on", "customers", None, None)];
+        assert_eq!(
+            ids(actions_for(&actions, None, "customers", None)),
+            vec!["on"]
+        );
+    }
+
+    #[test]
+    fn params_include_row_and_clicked_value() {
+        let row = HashMap::from([
+            ("id".to_string(), json!(7)),
+            ("value".to_string(), json!("column named value")),
+        ]);
+        let params = action_params(json!(42), Some(row));
+        assert_eq!(params.get("id"), Some(&json!(7)));
+        // The clicked cell wins over a column that happens to be named `value`.
+        assert_eq!(params.get(VALUE_PARAM), Some(&json!(42)));
+        assert_eq!(action_params(json!(null), None).len(), 1);
+    }
+
+    #[test]
+    fn enabled_defaults_to_true() {
+        let parsed: CustomAction = serde_json::from_value(json!({
+            "id": "a1",
+            "name": "Orders",
+            "table": "customers",
+            "sql": "SELECT 1"
+        }))
+        .unwrap();
+        assert!(parsed.enabled);
+        assert_eq!(parsed.column, None);
+    }
+}
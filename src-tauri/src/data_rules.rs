@@ -0,0 +1,188 @@
+mod expr;
+
+#[cfg(test)]
+mod tests;
+
+pub use expr::{like_matches, parse as parse_rule_expression, Expr};
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+
+use crate::models::{QueryResult, StyleHint};
+use crate::preferences::{column_layout_key, connection_preferences_file};
+
+const DATA_RULES_FILE: &str = "data_rules.json";
+
+/// A conditional formatting rule: when `expression` matches a row, the style
+/// is attached to the row (or to the `column` cell only, when set).
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct DataRule {
+    pub id: String,
+    #[serde(default)]
+    pub name: Option<String>,
+    /// Boolean expression, e.g. `amount > 1000 AND status = 'late'`.
+    pub expression: String,
+    /// Style only this column instead of the whole row.
+    #[serde(default)]
+    pub column: Option<String>,
+    #[serde(default)]
+    pub color: Option<String>,
+    #[serde(default)]
+    pub background: Option<String>,
+    #[serde(default)]
+    pub badge: Option<String>,
+    /// Default: true
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+fn load_all_rules(connection_id: &str) -> Result<HashMap<String, Vec<DataRule>>, String> {
+    let path = connection_preferences_file(connection_id, DATA_RULES_FILE)?;
+
+    if !path.exists() {
+        return Ok(HashMap::new());
+    }
+
+    let content =
+        fs::read_to_string(&path).map_err(|e| format!("Failed to read data rules file: {}", e))?;
+
+    serde_json::from_str(&content).map_err(|e| format!("Failed to parse data rules file: {}", e))
+}
+
+fn save_all_rules(
+    connection_id: &str,
+    rules: &HashMap<String, Vec<DataRule>>,
+) -> Result<(), String> {
+    let path = connection_preferences_file(connection_id, DATA_RULES_FILE)?;
+    let json = serde_json::to_string_pretty(rules)
+        .map_err(|e| format!("Failed to serialize data rules: {}", e))?;
+
+    fs::write(&path, json).map_err(|e| format!("Failed to write data rules file: {}", e))
+}
+
+/// Load the rules defined for a single table
+pub fn load_data_rules(
+    connection_id: &str,
+    schema: Option<&str>,
+    table: &str,
+) -> Result<Vec<DataRule>, String> {
+    let mut all = load_all_rules(connection_id)?;
+    Ok(all
+        .remove(&column_layout_key(schema, table))
+        .unwrap_or_default())
+}
+
+/// The enabled rules of a table with their parsed expressions, ready to be
+/// evaluated against any number of rows. Rules whose expression does not
+/// parse are skipped.
+#[derive(Debug, Clone)]
+pub struct CompiledDataRules {
+    rules: Vec<(DataRule, Expr)>,
+}
+
+impl CompiledDataRules {
+    pub fn new(rules: &[DataRule]) -> Self {
+        let rules = rules
+            .iter()
+            .filter(|r| r.enabled)
+            .filter_map(|r| match parse_rule_expression(&r.expression) {
+                Ok(expr) => Some((r.clone(), expr)),
+                Err(e) => {
+                    log::warn!("Skipping data rule {}: {}", r.id, e);
+                    None
+                }
+            })
+            .collect();
+        Self { rules }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// Style hints of `rows`, numbered from `first_row`: a streamed batch
+    /// passes the number of rows emitted before it.
+    pub fn style_hints(
+        &self,
+        columns: &[String],
+        rows: &[Vec<serde_json::Value>],
+        first_row: usize,
+    ) -> Vec<StyleHint> {
+        let mut hints = Vec::new();
+        for (idx, row) in rows.iter().enumerate() {
+            for (rule, expr) in &self.rules {
+                if expr.eval(columns, row) {
+                    hints.push(StyleHint {
+                        row: first_row + idx,
+                        column: rule.column.clone(),
+                        rule_id: rule.id.clone(),
+                        color: rule.color.clone(),
+                        background: rule.background.clone(),
+                        badge: rule.badge.clone(),
+                    });
+                }
+            }
+        }
+        hints
+    }
+}
+
+/// Evaluate `rules` against every row of `result` and attach the resulting
+/// style hints. Rules are applied in order; rules whose expression does not
+/// parse are skipped.
+pub fn apply_data_rules(result: &mut QueryResult, rules: &[DataRule]) {
+    let compiled = CompiledDataRules::new(rules);
+    if compiled.is_empty() {
+        return;
+    }
+
+    let hints = compiled.style_hints(&result.columns, &result.rows, 0);
+    if !hints.is_empty() {
+        result.style_hints = Some(hints);
+    }
+}
+
+#[tauri::command]
+pub async fn get_data_rules(
+    connection_id: String,
+    table: String,
+    schema: Option<String>,
+) -> Result<Vec<DataRule>, String> {
+    load_data_rules(&connection_id, schema.as_deref(), &table)
+}
+
+/// Replace the rules of a table. Every expression must parse.
+#[tauri::command]
+pub async fn save_data_rules(
+    connection_id: String,
+    table: String,
+    schema: Option<String>,
+    rules: Vec<DataRule>,
+) -> Result<(), String> {
+    for rule in &rules {
+        parse_rule_expression(&rule.expression)
+            .map_err(|e| format!("Invalid expression in rule {}: {}", rule.id, e))?;
+    }
+
+    let mut all = load_all_rules(&connection_id)?;
+    let key = column_layout_key(schema.as_deref(), &table);
+    if rules.is_empty() {
+        all.remove(&key);
+    } else {
+        all.insert(key, rules);
+    }
+
+    save_all_rules(&connection_id, &all)
+}
+
+/// Check a rule expression without saving it, so the editor can show errors inline.
+#[tauri::command]
+pub async fn validate_data_rule_expression(expression: String) -> Result<(), String> {
+    parse_rule_expression(&expression).map(|_| ())
+}
@@ -0,0 +1,421 @@
+//! Tiny boolean expression language used by conditional formatting rules.
+//!
+//! Supported syntax (keywords are case-insensitive):
+//!
+//! ```text
+//! amount > 1000 AND (status = 'late' OR status = 'lost')
+//! NOT deleted_at IS NULL
+//! email LIKE '%@example.com'
+//! "Order Total" >= -5.5
+//! ```
+//!
+//! The left-hand side of every comparison is a column name (bare, or quoted
+//! with `"`, `` ` `` or `[]`), the right-hand side a literal: string, number,
+//! `TRUE`, `FALSE` or `NULL`.
+
+use serde_json::Value;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompareOp {
+    Eq,
+    NotEq,
+    Lt,
+    LtEq,
+    Gt,
+    GtEq,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Literal {
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(String),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expr {
+    And(Box<Expr>, Box<Expr>),
+    Or(Box<Expr>, Box<Expr>),
+    Not(Box<Expr>),
+    Compare {
+        column: String,
+        op: CompareOp,
+        value: Literal,
+    },
+    IsNull {
+        column: String,
+        negated: bool,
+    },
+    Like {
+        column: String,
+        pattern: String,
+        negated: bool,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Ident(String),
+    Str(String),
+    Number(f64),
+    Op(CompareOp),
+    Minus,
+    LParen,
+    RParen,
+}
+
+fn tokenize(input: &str) -> Result<Vec<Token>, String> {
+    let chars: Vec<char> = input.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        match c {
+            c if c.is_whitespace() => i += 1,
+            '(' => {
+                tokens.push(Token::LParen);
+                i += 1;
+            }
+            ')' => {
+                tokens.push(Token::RParen);
+                i += 1;
+            }
+            '-' => {
+                tokens.push(Token::Minus);
+                i += 1;
+            }
+            '=' => {
+                // Accept both `=` and `==`
+                i += if chars.get(i + 1) == Some(&'=') { 2 } else { 1 };
+                tokens.push(Token::Op(CompareOp::Eq));
+            }
+            '!' if chars.get(i + 1) == Some(&'=') => {
+                tokens.push(Token::Op(CompareOp::NotEq));
+                i += 2;
+            }
+            '<' => match chars.get(i + 1) {
+                Some('=') => {
+                    tokens.push(Token::Op(CompareOp::LtEq));
+                    i += 2;
+                }
+                Some('>') => {
+                    tokens.push(Token::Op(CompareOp::NotEq));
+                    i += 2;
+                }
+                _ => {
+                    tokens.push(Token::Op(CompareOp::Lt));
+                    i += 1;
+                }
+            },
+            '>' => {
+                if chars.get(i + 1) == Some(&'=') {
+                    tokens.push(Token::Op(CompareOp::GtEq));
+                    i += 2;
+                } else {
+                    tokens.push(Token::Op(CompareOp::Gt));
+                    i += 1;
+                }
+            }
+            '\'' => {
+                let mut s = String::new();
+                i += 1;
+                loop {
+                    match chars.get(i) {
+                        Some('\'') if chars.get(i + 1) == Some(&'\'') => {
+                            s.push('\'');
+                            i += 2;
+                        }
+                        Some('\'') => {
+                            i += 1;
+                            break;
+                        }
+                        Some(ch) => {
+                            s.push(*ch);
+                            i += 1;
+                        }
+                        None => return Err("Unterminated string literal".to_string()),
+                    }
+                }
+                tokens.push(Token::Str(s));
+            }
+            '"' | '`' | '[' => {
+                let close = if c == '[' { ']' } else { c };
+                let start = i + 1;
+                let end = chars[start..]
+                    .iter()
+                    .position(|&ch| ch == close)
+                    .map(|p| start + p)
+                    .ok_or_else(|| "Unterminated quoted identifier".to_string())?;
+                tokens.push(Token::Ident(chars[start..end].iter().collect()));
+                i = end + 1;
+            }
+            c if c.is_ascii_digit() || c == '.' => {
+                let start = i;
+                while i < chars.len() && (chars[i].is_ascii_digit() || chars[i] == '.') {
+                    i += 1;
+                }
+                let text: String = chars[start..i].iter().collect();
+                let n = text
+                    .parse::<f64>()
+                    .map_err(|_| format!("Invalid number: {}", text))?;
+                tokens.push(Token::Number(n));
+            }
+            c if c.is_alphabetic() || c == '_' => {
+                let start = i;
+                while i < chars.len()
+                    && (chars[i].is_alphanumeric() || chars[i] == '_' || chars[i] == '.')
+                {
+                    i += 1;
+                }
+                tokens.push(Token::Ident(chars[start..i].iter().collect()));
+            }
+            other => return Err(format!("Unexpected character '{}'", other)),
+        }
+    }
+
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn advance(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn peek_keyword(&self, keyword: &str) -> bool {
+        matches!(self.peek(), Some(Token::Ident(s)) if s.eq_ignore_ascii_case(keyword))
+    }
+
+    fn eat_keyword(&mut self, keyword: &str) -> bool {
+        if self.peek_keyword(keyword) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn parse_or(&mut self) -> Result<Expr, String> {
+        let mut left = self.parse_and()?;
+        while self.eat_keyword("OR") {
+            let right = self.parse_and()?;
+            left = Expr::Or(Box::new(left), Box::new(right));
+        }
+        Ok(left)
+    }
+
+    fn parse_and(&mut self) -> Result<Expr, String> {
+        let mut left = self.parse_unary()?;
+        while self.eat_keyword("AND") {
+            let right = self.parse_unary()?;
+            left = Expr::And(Box::new(left), Box::new(right));
+        }
+        Ok(left)
+    }
+
+    fn parse_unary(&mut self) -> Result<Expr, String> {
+        if self.eat_keyword("NOT") {
+            return Ok(Expr::Not(Box::new(self.parse_unary()?)));
+        }
+        self.parse_primary()
+    }
+
+    fn parse_primary(&mut self) -> Result<Expr, String> {
+        match self.advance() {
+            Some(Token::LParen) => {
+                let expr = self.parse_or()?;
+                match self.advance() {
+                    Some(Token::RParen) => Ok(expr),
+                    _ => Err("Expected ')'".to_string()),
+                }
+            }
+            Some(Token::Ident(column)) => self.parse_condition(column),
+            Some(other) => Err(format!("Expected a column name, found {:?}", other)),
+            None => Err("Unexpected end of expression".to_string()),
+        }
+    }
+
+    fn parse_condition(&mut self, column: String) -> Result<Expr, String> {
+        if self.eat_keyword("IS") {
+            let negated = self.eat_keyword("NOT");
+            if !self.eat_keyword("NULL") {
+                return Err("Expected NULL after IS".to_string());
+            }
+            return Ok(Expr::IsNull { column, negated });
+        }
+
+        let negated = self.eat_keyword("NOT");
+        if self.eat_keyword("LIKE") {
+            return match self.advance() {
+                Some(Token::Str(pattern)) => Ok(Expr::Like {
+                    column,
+                    pattern,
+                    negated,
+                }),
+                _ => Err("Expected a string pattern after LIKE".to_string()),
+            };
+        }
+        if negated {
+            return Err("Expected LIKE after NOT".to_string());
+        }
+
+        let op = match self.advance() {
+            Some(Token::Op(op)) => op,
+            _ => return Err(format!("Expected a comparison after '{}'", column)),
+        };
+        let value = self.parse_literal()?;
+        Ok(Expr::Compare { column, op, value })
+    }
+
+    fn parse_literal(&mut self) -> Result<Literal, String> {
+        match self.advance() {
+            Some(Token::Str(s)) => Ok(Literal::String(s)),
+            Some(Token::Number(n)) => Ok(Literal::Number(n)),
+            Some(Token::Minus) => match self.advance() {
+                Some(Token::Number(n)) => Ok(Literal::Number(-n)),
+                _ => Err("Expected a number after '-'".to_string()),
+            },
+            Some(Token::Ident(s)) if s.eq_ignore_ascii_case("NULL") => Ok(Literal::Null),
+            Some(Token::Ident(s)) if s.eq_ignore_ascii_case("TRUE") => Ok(Literal::Bool(true)),
+            Some(Token::Ident(s)) if s.eq_ignore_ascii_case("FALSE") => Ok(Literal::Bool(false)),
+            Some(other) => Err(format!("Expected a literal value, found {:?}", other)),
+            None => Err("Unexpected end of expression".to_string()),
+        }
+    }
+}
+
+/// Parse a rule expression.
+pub fn parse(input: &str) -> Result<Expr, String> {
+    let tokens = tokenize(input)?;
+    if tokens.is_empty() {
+        return Err("Expression is empty".to_string());
+    }
+    let mut parser = Parser { tokens, pos: 0 };
+    let expr = parser.parse_or()?;
+    if let Some(token) = parser.peek() {
+        return Err(format!("Unexpected token {:?}", token));
+    }
+    Ok(expr)
+}
+
+fn value_as_f64(value: &Value) -> Option<f64> {
+    match value {
+        Value::Number(n) => n.as_f64(),
+        Value::String(s) => s.trim().parse().ok(),
+        Value::Bool(b) => Some(if *b { 1.0 } else { 0.0 }),
+        _ => None,
+    }
+}
+
+fn value_as_string(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+fn compare(value: &Value, op: CompareOp, literal: &Literal) -> bool {
+    use std::cmp::Ordering;
+
+    let ordering = match literal {
+        // SQL semantics: comparing with NULL is never true
+        Literal::Null => return false,
+        _ if value.is_null() => return false,
+        Literal::Number(n) => match value_as_f64(value) {
+            Some(v) => v.partial_cmp(n),
+            None => return false,
+        },
+        Literal::Bool(b) => match value {
+            Value::Bool(v) => Some(v.cmp(b)),
+            other => value_as_f64(other).and_then(|v| v.partial_cmp(&(*b as u8 as f64))),
+        },
+        Literal::String(s) => Some(value_as_string(value).as_str().cmp(s.as_str())),
+    };
+
+    match ordering {
+        Some(ord) => match op {
+            CompareOp::Eq => ord == Ordering::Equal,
+            CompareOp::NotEq => ord != Ordering::Equal,
+            CompareOp::Lt => ord == Ordering::Less,
+            CompareOp::LtEq => ord != Ordering::Greater,
+            CompareOp::Gt => ord == Ordering::Greater,
+            CompareOp::GtEq => ord != Ordering::Less,
+        },
+        None => false,
+    }
+}
+
+/// Case-insensitive SQL `LIKE` matching with `%` and `_` wildcards.
+pub fn like_matches(text: &str, pattern: &str) -> bool {
+    let text: Vec<char> = text.to_lowercase().chars().collect();
+    let pattern: Vec<char> = pattern.to_lowercase().chars().collect();
+
+    // Iterative wildcard matching with backtracking on the last `%`
+    let (mut t, mut p) = (0, 0);
+    let mut star: Option<(usize, usize)> = None;
+    while t < text.len() {
+        if p < pattern.len() && (pattern[p] == '_' || pattern[p] == text[t]) {
+            t += 1;
+            p += 1;
+        } else if p < pattern.len() && pattern[p] == '%' {
+            star = Some((p, t));
+            p += 1;
+        } else if let Some((sp, st)) = star {
+            p = sp + 1;
+            t = st + 1;
+            star = Some((sp, st + 1));
+        } else {
+            return false;
+        }
+    }
+    pattern[p..].iter().all(|&c| c == '%')
+}
+
+static NULL: Value = Value::Null;
+
+fn lookup<'a>(columns: &[String], row: &'a [Value], name: &str) -> &'a Value {
+    columns
+        .iter()
+        .position(|c| c == name)
+        .or_else(|| columns.iter().position(|c| c.eq_ignore_ascii_case(name)))
+        .and_then(|idx| row.get(idx))
+        .unwrap_or(&NULL)
+}
+
+impl Expr {
+    /// Evaluate the expression against one row. Unknown columns behave like NULL.
+    pub fn eval(&self, columns: &[String], row: &[Value]) -> bool {
+        match self {
+            Expr::And(a, b) => a.eval(columns, row) && b.eval(columns, row),
+            Expr::Or(a, b) => a.eval(columns, row) || b.eval(columns, row),
+            Expr::Not(inner) => !inner.eval(columns, row),
+            Expr::Compare { column, op, value } => {
+                compare(lookup(columns, row, column), *op, value)
+            }
+            Expr::IsNull { column, negated } => lookup(columns, row, column).is_null() != *negated,
+            Expr::Like {
+                column,
+                pattern,
+                negated,
+            } => {
+                let value = lookup(columns, row, column);
+                if value.is_null() {
+                    return false;
+                }
+                like_matches(&value_as_string(value), pattern) != *negated
+            }
+        }
+    }
+}
@@ -0,0 +1,190 @@
+use super::{apply_data_rules, like_matches, parse_rule_expression, DataRule};
+use crate::models::QueryResult;
+use serde_json::{json, Value};
+
+fn cols(names: &[&str]) -> Vec<String> {
+    names.iter().map(|s| s.to_string()).collect()
+}
+
+fn eval(expression: &str, columns: &[&str], row: Vec<Value>) -> bool {
+    parse_rule_expression(expression)
+        .unwrap()
+        .eval(&cols(columns), &row)
+}
+
+// ---------------------------------------------------------------------------
+// Expression parsing
+// ---------------------------------------------------------------------------
+
+#[test]
+fn parse_rejects_empty_expression() {
+    assert!(parse_rule_expression("   ").is_err());
+}
+
+#[test]
+fn parse_rejects_dangling_operator() {
+    assert!(parse_rule_expression("amount >").is_err());
+    assert!(parse_rule_expression("amount > 1 AND").is_err());
+}
+
+#[test]
+fn parse_rejects_unbalanced_parentheses() {
+    assert!(parse_rule_expression("(amount > 1").is_err());
+    assert!(parse_rule_expression("amount > 1)").is_err());
+}
+
+#[test]
+fn parse_rejects_unterminated_string() {
+    assert!(parse_rule_expression("status = 'late").is_err());
+}
+
+// ---------------------------------------------------------------------------
+// Expression evaluation
+// ---------------------------------------------------------------------------
+
+#[test]
+fn numeric_comparisons() {
+    assert!(eval("amount > 1000", &["amount"], vec![json!(1500)]));
+    assert!(!eval("amount > 1000", &["amount"], vec![json!(1000)]));
+    assert!(eval("amount >= 1000", &["amount"], vec![json!(1000)]));
+    assert!(eval("amount <> 5", &["amount"], vec![json!(4)]));
+    assert!(eval("amount != 5", &["amount"], vec![json!(4)]));
+    assert!(eval("delta < -2.5", &["delta"], vec![json!(-3)]));
+}
+
+#[test]
+fn numeric_comparison_accepts_decimal_strings() {
+    assert!(eval("price <= 9.99", &["price"], vec![json!("9.99")]));
+}
+
+#[test]
+fn string_equality_and_escaped_quotes() {
+    assert!(eval("name = 'O''Brien'", &["name"], vec![json!("O'Brien")]));
+    assert!(!eval("name = 'obrien'", &["name"], vec![json!("O'Brien")]));
+}
+
+#[test]
+fn null_handling_follows_sql_semantics() {
+    assert!(eval(
+        "deleted_at IS NULL",
+        &["deleted_at"],
+        vec![Value::Null]
+    ));
+    assert!(eval("x IS NOT NULL", &["x"], vec![json!(1)]));
+    assert!(!eval("x = 1", &["x"], vec![Value::Null]));
+    assert!(!eval("x != 1", &["x"], vec![Value::Null]));
+    assert!(!eval("x = NULL", &["x"], vec![Value::Null]));
+}
+
+#[test]
+fn unknown_columns_behave_like_null() {
+    assert!(eval("missing IS NULL", &["a"], vec![json!(1)]));
+}
+
+#[test]
+fn boolean_logic_and_precedence() {
+    let columns = &["amount", "status"];
+    let expr = "amount > 100 AND status = 'late' OR status = 'lost'";
+    assert!(eval(expr, columns, vec![json!(10), json!("lost")]));
+    assert!(eval(expr, columns, vec![json!(200), json!("late")]));
+    assert!(!eval(expr, columns, vec![json!(10), json!("late")]));
+
+    let grouped = "amount > 100 AND (status = 'late' OR status = 'lost')";
+    assert!(!eval(grouped, columns, vec![json!(10), json!("lost")]));
+    assert!(eval(
+        "NOT status = 'ok'",
+        columns,
+        vec![json!(1), json!("late")]
+    ));
+}
+
+#[test]
+fn boolean_literals() {
+    assert!(eval("active = TRUE", &["active"], vec![json!(true)]));
+    assert!(eval("active = true", &["active"], vec![json!(1)]));
+    assert!(eval("active = FALSE", &["active"], vec![json!(0)]));
+}
+
+#[test]
+fn quoted_identifiers() {
+    assert!(eval(
+        "\"Order Total\" > 5",
+        &["Order Total"],
+        vec![json!(6)]
+    ));
+    assert!(eval("`Order Total` > 5", &["Order Total"], vec![json!(6)]));
+    assert!(eval("[Order Total] > 5", &["Order Total"], vec![json!(6)]));
+}
+
+#[test]
+fn like_patterns() {
+    assert!(like_matches("alice@example.com", "%@example.com"));
+    assert!(like_matches("ABC", "a_c"));
+    assert!(like_matches("abc", "%"));
+    assert!(!like_matches("abc", "a_"));
+    assert!(eval(
+        "email NOT LIKE '%@test%'",
+        &["email"],
+        vec![json!("a@b.com")]
+    ));
+    assert!(!eval("email LIKE '%x%'", &["email"], vec![Value::Null]));
+}
+
+// ---------------------------------------------------------------------------
+// apply_data_rules
+// ---------------------------------------------------------------------------
+
+fn rule(id: &str, expression: &str) -> DataRule {
+    DataRule {
+        id: id.to_string(),
+        name: None,
+        expression: expression.to_string(),
+        column: None,
+        color: Some("#ef4444".to_string()),
+        background: None,
+        badge: None,
+        enabled: true,
+    }
+}
+
+fn result(rows: Vec<Vec<Value>>) -> QueryResult {
+    QueryResult {
+        columns: cols(&["id", "amount"]),
+        rows,
+        affected_rows: 0,
+        truncated: false,
+        truncation: None,
+        pagination: None,
+        style_hints: None,
+        provenance: None,
+    }
+}
+
+#[test]
+fn apply_attaches_hints_for_matching_rows() {
+    let mut res = result(vec![vec![json!(1), json!(50)], vec![json!(2), json!(5000)]]);
+    let mut cell_rule = rule("big", "amount > 1000");
+    cell_rule.column = Some("amount".to_string());
+    cell_rule.badge = Some("BIG".to_string());
+
+    apply_data_rules(&mut res, &[cell_rule, rule("first", "id = 1")]);
+
+    let hints = res.style_hints.unwrap();
+    assert_eq!(hints.len(), 2);
+    assert_eq!(hints[0].row, 0);
+    assert_eq!(hints[0].rule_id, "first");
+    assert_eq!(hints[1].row, 1);
+    assert_eq!(hints[1].column.as_deref(), Some("amount"));
+    assert_eq!(hints[1].badge.as_deref(), Some("BIG"));
+}
+
+#[test]
+fn apply_skips_disabled_and_invalid_rules() {
+    let mut res = result(vec![vec![json!(1), json!(50)]]);
+    let mut disabled = rule("off", "id = 1");
+    disabled.enabled = false;
+
+    apply_data_rules(&mut res, &[disabled, rule("broken", "id = ")]);
+
+    assert!(res.style_hints.is_none());
+}
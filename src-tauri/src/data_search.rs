@@ -0,0 +1,319 @@
+//! "Search everywhere": find a string in the data of a database.
+//!
+//! `search_data` looks for the text, ignoring case, in every text column of
+//! the selected tables (all of them by default), reading at most
+//! `limit_per_table` matching rows from each. Tables are searched a few at
+//! a time; as each finishes, a `data-search://result` event carrying its
+//! matches goes to the calling window, told apart by `search_id`. The
+//! command resolves with a summary once every table has been searched, or
+//! with an error when `cancel_data_search` stopped it.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use futures::StreamExt;
+use serde::Serialize;
+use serde_json::Value;
+use tauri::{AppHandle, Emitter, Runtime, State, WebviewWindow};
+
+use crate::commands::{
+    driver_for, expand_ssh_connection_params, find_connection_by_id, register_abort_handle,
+    resolve_connection_params_with_id, unregister_abort_handle, AbortHandleMap,
+};
+use crate::drivers::driver_trait::DatabaseDriver;
+use crate::filter_builder::{compile_filter, BuiltFilter, Combinator, FilterNode, FilterOperator};
+use crate::models::{ConnectionParams, TableColumn};
+
+const DATA_SEARCH_RESULT_EVENT: &str = "data-search://result";
+
+const DEFAULT_LIMIT_PER_TABLE: u32 = 50;
+const LIMIT_PER_TABLE_CAP: u32 = 1_000;
+/// Tables searched at the same time, so a large database does not take
+/// every connection of the pool.
+const DEFAULT_CONCURRENCY: usize = 4;
+const CONCURRENCY_CAP: usize = 16;
+
+/// Abort handles of running searches, keyed by `search_id`.
+pub struct DataSearchCancellationState {
+    pub handles: Arc<Mutex<AbortHandleMap>>,
+}
+
+impl Default for DataSearchCancellationState {
+    fn default() -> Self {
+        Self {
+            handles: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+}
+
+/// The matches of one table, sent as soon as the table has been searched.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct TableMatches {
+    pub search_id: String,
+    pub table: String,
+    /// The text columns the search looked in.
+    pub searched_columns: Vec<String>,
+    pub columns: Vec<String>,
+    pub rows: Vec<Vec<Value>>,
+    /// More rows match than `limit_per_table`.
+    pub truncated: bool,
+    /// Why the table could not be searched; the search goes on without it.
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, PartialEq)]
+pub struct DataSearchSummary {
+    pub tables_searched: usize,
+    pub tables_with_matches: usize,
+    pub rows_found: usize,
+    pub tables_failed: usize,
+}
+
+impl DataSearchSummary {
+    fn add(&mut self, matches: &TableMatches) {
+        self.tables_searched += 1;
+        if matches.error.is_some() {
+            self.tables_failed += 1;
+        } else if !matches.rows.is_empty() {
+            self.tables_with_matches += 1;
+            self.rows_found += matches.rows.len();
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Queries (testable; pure)
+// ---------------------------------------------------------------------------
+
+/// Whether a column of `data_type` holds text that LIKE can search.
+/// Binary, numeric, date and JSON columns are left out: comparing them as
+/// text either fails (PostgreSQL) or matches their formatting.
+pub fn is_text_type(data_type: &str) -> bool {
+    let ty = data_type.to_ascii_lowercase();
+    ty.contains("char")
+        || ty.contains("text")
+        || ty.contains("clob")
+        || ty.contains("string")
+        || ty == "enum"
+        || ty.starts_with("enum(")
+}
+
+pub fn text_columns(columns: &[TableColumn]) -> Vec<String> {
+    columns
+        .iter()
+        .filter(|column| is_text_type(&column.data_type))
+        .map(|column| column.name.clone())
+        .collect()
+}
+
+/// `SELECT *` of the rows of `table` where any of `columns` contains
+/// `term`, ignoring case. `None` when the table has no text column.
+pub fn build_search_query(
+    driver: &str,
+    quote: &str,
+    schema: Option<&str>,
+    table: &str,
+    columns: &[String],
+    term: &str,
+) -> Result<Option<BuiltFilter>, String> {
+    if columns.is_empty() {
+        return Ok(None);
+    }
+    let filter = compile_filter(
+        driver,
+        quote,
+        &FilterNode::Group {
+            combinator: Combinator::Or,
+            negate: false,
+            children: columns
+                .iter()
+                .map(|column| FilterNode::Condition {
+                    column: column.clone(),
+                    operator: FilterOperator::Contains,
+                    value: Some(Value::String(term.to_string())),
+                    values: Vec::new(),
+                    case_insensitive: true,
+                })
+                .collect(),
+        },
+    )?;
+    Ok(Some(BuiltFilter {
+        sql: format!(
+            "SELECT * FROM {} WHERE {}",
+            crate::bulk_edit::table_ref(quote, schema, table),
+            filter.sql
+        ),
+        params: filter.params,
+    }))
+}
+
+// ---------------------------------------------------------------------------
+// Search
+// ---------------------------------------------------------------------------
+
+/// What every table of one search is searched with.
+struct Search<'a> {
+    drv: &'a dyn DatabaseDriver,
+    driver: &'a str,
+    params: &'a ConnectionParams,
+    quote: &'a str,
+    schema: Option<&'a str>,
+    term: &'a str,
+    limit: u32,
+}
+
+impl Search<'_> {
+    /// Search the `searched` columns of `table`. Errors are reported in the
+    /// result rather than returned, so one failing table does not end the
+    /// search.
+    async fn table(&self, search_id: &str, table: String, searched: Vec<String>) -> TableMatches {
+        let mut matches = TableMatches {
+            search_id: search_id.to_string(),
+            table,
+            searched_columns: searched,
+            columns: Vec::new(),
+            rows: Vec::new(),
+            truncated: false,
+            error: None,
+        };
+        let query = build_search_query(
+            self.driver,
+            self.quote,
+            self.schema,
+            &matches.table,
+            &matches.searched_columns,
+            self.term,
+        );
+        let query = match query {
+            Ok(Some(query)) => query,
+            Ok(None) => return matches,
+            Err(e) => {
+                matches.error = Some(e);
+                return matches;
+            }
+        };
+        // One row more than the limit tells whether there are more.
+        let limit = self.limit as usize;
+        match self
+            .drv
+            .execute_query_with_params(
+                self.params,
+                &query.sql,
+                &query.params,
+                Some(self.limit + 1),
+                1,
+                self.schema,
+            )
+            .await
+        {
+            Ok(result) => {
+                matches.truncated = result.rows.len() > limit;
+                matches.columns = result.columns;
+                matches.rows = result.rows;
+                matches.rows.truncate(limit);
+            }
+            Err(e) => matches.error = Some(e),
+        }
+        matches
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tauri commands
+// ---------------------------------------------------------------------------
+
+#[tauri::command]
+pub async fn search_data<R: Runtime>(
+    app: AppHandle<R>,
+    window: WebviewWindow<R>,
+    state: State<'_, DataSearchCancellationState>,
+    connection_id: String,
+    search_id: String,
+    term: String,
+    schema: Option<String>,
+    tables: Option<Vec<String>>,
+    limit_per_table: Option<u32>,
+    concurrency: Option<usize>,
+) -> Result<DataSearchSummary, String> {
+    let term = term.trim().to_string();
+    if term.is_empty() {
+        return Err("Nothing to search for".into());
+    }
+    let saved_conn = find_connection_by_id(&app, &connection_id)?;
+    let expanded_params = expand_ssh_connection_params(&app, &saved_conn.params).await?;
+    let params = resolve_connection_params_with_id(&expanded_params, &connection_id)?;
+    let driver = saved_conn.params.driver.clone();
+    let drv = driver_for(&driver).await?;
+    let quote = drv.manifest().capabilities.identifier_quote.clone();
+    let limit = limit_per_table
+        .unwrap_or(DEFAULT_LIMIT_PER_TABLE)
+        .clamp(1, LIMIT_PER_TABLE_CAP);
+    let concurrency = concurrency
+        .unwrap_or(DEFAULT_CONCURRENCY)
+        .clamp(1, CONCURRENCY_CAP);
+
+    let all = drv
+        .get_tables(&params, schema.as_deref())
+        .await?
+        .into_iter()
+        .map(|table| table.name)
+        .collect();
+    let names = crate::er_snapshot::select_tables(all, tables.as_deref());
+    let mut columns = drv
+        .get_all_columns_batch(&params, schema.as_deref())
+        .await?;
+    log::info!(
+        "Searching the data of {} table(s) on connection {}",
+        names.len(),
+        connection_id
+    );
+
+    let task_search_id = search_id.clone();
+    let task = tokio::spawn(async move {
+        let search = Search {
+            drv: drv.as_ref(),
+            driver: &driver,
+            params: &params,
+            quote: &quote,
+            schema: schema.as_deref(),
+            term: &term,
+            limit,
+        };
+        let jobs = names.into_iter().map(|table| {
+            let searched = text_columns(&columns.remove(&table).unwrap_or_default());
+            search.table(&task_search_id, table, searched)
+        });
+
+        let mut summary = DataSearchSummary::default();
+        let mut results = futures::stream::iter(jobs).buffer_unordered(concurrency);
+        while let Some(matches) = results.next().await {
+            summary.add(&matches);
+            if let Err(e) = app.emit_to(window.label(), DATA_SEARCH_RESULT_EVENT, &matches) {
+                log::warn!("Failed to emit data search result: {}", e);
+            }
+        }
+        summary
+    });
+
+    let abort_handle = Arc::new(task.abort_handle());
+    register_abort_handle(&state.handles, search_id.clone(), abort_handle.clone());
+    let result = task.await;
+    unregister_abort_handle(&state.handles, &search_id, &abort_handle);
+
+    result.map_err(|_| "Search cancelled".to_string())
+}
+
+#[tauri::command]
+pub async fn cancel_data_search(
+    state: State<'_, DataSearchCancellationState>,
+    search_id: String,
+) -> Result<(), String> {
+    let entries = {
+        let mut handles = state.handles.lock().unwrap();
+        handles.remove(&search_id).unwrap_or_default()
+    };
+    for handle in entries {
+        handle.abort();
+    }
+    Ok(())
+}
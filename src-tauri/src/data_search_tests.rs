@@ -0,0 +1,101 @@
+#[cfg(test)]
+mod tests {
+    use serde_json::Value;
+
+    use crate::data_search::{build_search_query, is_text_type, text_columns};
+    use crate::models::TableColumn;
+
+    fn column(name: &str, data_type: &str) -> TableColumn {
+        TableColumn {
+            name: name.to_string(),
+            data_type: data_type.to_string(),
+            is_pk: false,
+            is_nullable: true,
+            is_auto_increment: false,
+            default_value: None,
+            character_maximum_length: None,
+        }
+    }
+
+    #[test]
+    fn only_text_types_are_searched() {
+        for ty in [
+            "varchar",
+            "character varying",
+            "TEXT",
+            "mediumtext",
+            "nvarchar(50)",
+            "enum",
+            "enum('a','b')",
+        ] {
+            assert!(is_text_type(ty), "{} should be searched", ty);
+        }
+        for ty in [
+            "int",
+            "bytea",
+            "varbinary",
+            "json",
+            "jsonb",
+            "timestamp",
+            "uuid",
+        ] {
+            assert!(!is_text_type(ty), "{} should not be searched", ty);
+        }
+    }
+
+    #[test]
+    fn text_columns_keep_the_table_order() {
+        let columns = vec![
+            column("id", "integer"),
+            column("name", "varchar"),
+            column("payload", "blob"),
+            column("notes", "text"),
+        ];
+        assert_eq!(text_columns(&columns), ["name", "notes"]);
+    }
+
+    #[test]
+    fn a_table_without_text_columns_is_not_queried() {
+        let query = build_search_query("mysql", "`", None, "numbers", &[], "x").unwrap();
+        assert!(query.is_none());
+    }
+
+    #[test]
+    fn every_text_column_is_matched_ignoring_case() {
+        let query = build_search_query(
+            "mysql",
+            "`",
+            None,
+            "users",
+            &["name".to_string(), "email".to_string()],
+            "Ann",
+        )
+        .unwrap()
+        .unwrap();
+        assert_eq!(
+            query.sql,
+            "SELECT * FROM `users` WHERE (LOWER(`name`) LIKE LOWER(:p0) ESCAPE '!' \
+             OR LOWER(`email`) LIKE LOWER(:p1) ESCAPE '!')"
+        );
+        assert_eq!(query.params["p0"], Value::String("%Ann%".into()));
+    }
+
+    #[test]
+    fn postgres_uses_ilike_and_the_schema() {
+        let query = build_search_query(
+            "postgres",
+            "\"",
+            Some("crm"),
+            "users",
+            &["name".to_string()],
+            "50%",
+        )
+        .unwrap()
+        .unwrap();
+        assert_eq!(
+            query.sql,
+            "SELECT * FROM \"crm\".\"users\" WHERE (\"name\" ILIKE :p0 ESCAPE '!')"
+        );
+        assert_eq!(query.params["p0"], Value::String("%50!%%".into()));
+    }
+}
@@ -0,0 +1,67 @@
+//! Size overview of a database.
+//!
+//! Drivers with the `table_stats` capability report each table's row
+//! estimate, data and index size from their catalog; the overview adds them
+//! up and lists the tables largest first for the size-overview panel.
+
+use serde::Serialize;
+use tauri::{AppHandle, Runtime};
+
+use crate::commands::{
+    driver_for, expand_ssh_connection_params, find_connection_by_id,
+    resolve_connection_params_with_id,
+};
+use crate::models::TableStats;
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct DatabaseOverview {
+    /// Largest first, by data plus index size.
+    pub tables: Vec<TableStats>,
+    /// Sums over the tables whose figure is known.
+    pub total_rows: i64,
+    pub total_data_bytes: i64,
+    pub total_index_bytes: i64,
+}
+
+// ---------------------------------------------------------------------------
+// Totals (testable; pure)
+// ---------------------------------------------------------------------------
+
+fn total_bytes(table: &TableStats) -> i64 {
+    table.data_bytes.unwrap_or(0) + table.index_bytes.unwrap_or(0)
+}
+
+pub fn overview(mut tables: Vec<TableStats>) -> DatabaseOverview {
+    tables.sort_by(|a, b| {
+        total_bytes(b)
+            .cmp(&total_bytes(a))
+            .then_with(|| a.name.cmp(&b.name))
+    });
+    let sum = |figure: fn(&TableStats) -> Option<i64>| tables.iter().filter_map(figure).sum();
+    DatabaseOverview {
+        total_rows: sum(|t| t.row_estimate),
+        total_data_bytes: sum(|t| t.data_bytes),
+        total_index_bytes: sum(|t| t.index_bytes),
+        tables,
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tauri commands
+// ---------------------------------------------------------------------------
+
+#[tauri::command]
+pub async fn get_database_overview<R: Runtime>(
+    app: AppHandle<R>,
+    connection_id: String,
+    schema: Option<String>,
+) -> Result<DatabaseOverview, String> {
+    let saved_conn = find_connection_by_id(&app, &connection_id)?;
+    let expanded_params = expand_ssh_connection_params(&app, &saved_conn.params).await?;
+    let params = resolve_connection_params_with_id(&expanded_params, &connection_id)?;
+    let tables = driver_for(&saved_conn.params.driver)
+        .await?
+        .get_table_stats(&params, schema.as_deref())
+        .await?;
+    Ok(overview(tables))
+}
@@ -0,0 +1,47 @@
+#[cfg(test)]
+mod tests {
+    use crate::database_overview::overview;
+    use crate::models::TableStats;
+
+    fn table(name: &str, rows: Option<i64>, data: Option<i64>, index: Option<i64>) -> TableStats {
+        TableStats {
+            name: name.to_string(),
+            row_estimate: rows,
+            data_bytes: data,
+            index_bytes: index,
+            last_analyzed: None,
+        }
+    }
+
+    #[test]
+    fn tables_are_listed_largest_first() {
+        let result = overview(vec![
+            table("small", Some(10), Some(100), Some(0)),
+            table("large", Some(5), Some(500), Some(500)),
+            table("medium", Some(1000), Some(300), Some(200)),
+        ]);
+        let names: Vec<&str> = result.tables.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(names, ["large", "medium", "small"]);
+    }
+
+    #[test]
+    fn ties_are_broken_by_name() {
+        let result = overview(vec![
+            table("b", None, Some(10), None),
+            table("a", None, Some(10), None),
+        ]);
+        assert_eq!(result.tables[0].name, "a");
+    }
+
+    #[test]
+    fn totals_skip_unknown_figures() {
+        let result = overview(vec![
+            table("a", Some(10), Some(100), Some(20)),
+            table("b", None, None, None),
+            table("c", Some(5), Some(50), None),
+        ]);
+        assert_eq!(result.total_rows, 15);
+        assert_eq!(result.total_data_bytes, 150);
+        assert_eq!(result.total_index_bytes, 20);
+    }
+}
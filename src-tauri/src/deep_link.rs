@@ -0,0 +1,176 @@
+//! `tabularis://` links and single-instance forwarding.
+//!
+//! Only one instance of the default profile runs at a time. A second launch
+//! hands its arguments to the running instance, which focuses its main
+//! window and opens any `tabularis://` link it was given. Other profiles
+//! are not single-instance: the single-instance lock is keyed on the bundle
+//! identifier, which all profiles share, so it cannot tell them apart. Links
+//! opened by the OS start the app without `--profile` and so always reach
+//! the default profile.
+//!
+//! Supported links:
+//!
+//! - `tabularis://connect?id=<connection id>` opens a saved connection;
+//! - `tabularis://query?id=<saved query id or name>[&connection=<id>]`
+//!   opens a saved query in a new editor tab, without running it.
+//!
+//! Links are resolved here and queued. The main window is told through
+//! [`DEEP_LINK_EVENT`] and drains the queue with `take_pending_deep_links`.
+//! A link that starts the app is queued before the window has loaded, so
+//! the window also drains the queue once when it mounts.
+
+use std::sync::Mutex;
+
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Runtime, Url};
+
+pub const SCHEME: &str = "tabularis";
+
+/// Emitted to the main window when links were queued.
+pub const DEEP_LINK_EVENT: &str = "deep-link";
+
+/// A parsed `tabularis://` link.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DeepLink {
+    Connect {
+        connection_id: String,
+    },
+    OpenQuery {
+        query: String,
+        connection_id: Option<String>,
+    },
+}
+
+/// What the main window should do for a link.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "action", rename_all = "camelCase")]
+pub enum DeepLinkAction {
+    #[serde(rename_all = "camelCase")]
+    Connect { connection_id: String },
+    #[serde(rename_all = "camelCase")]
+    OpenQuery {
+        connection_id: String,
+        name: String,
+        sql: String,
+        database: Option<String>,
+    },
+}
+
+static PENDING: Lazy<Mutex<Vec<DeepLinkAction>>> = Lazy::new(|| Mutex::new(Vec::new()));
+
+// ---------------------------------------------------------------------------
+// Parsing (testable; pure)
+// ---------------------------------------------------------------------------
+
+fn query_param(url: &Url, name: &str) -> Option<String> {
+    url.query_pairs()
+        .find(|(key, _)| key == name)
+        .map(|(_, value)| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+}
+
+/// Parse a `tabularis://` link.
+pub fn parse(url: &Url) -> Result<DeepLink, String> {
+    if url.scheme() != SCHEME {
+        return Err(format!("Not a {}:// link: {}", SCHEME, url));
+    }
+    let id = || query_param(url, "id").ok_or_else(|| format!("Link is missing an id: {}", url));
+    match url.host_str().unwrap_or_default() {
+        "connect" => Ok(DeepLink::Connect {
+            connection_id: id()?,
+        }),
+        "query" => Ok(DeepLink::OpenQuery {
+            query: id()?,
+            connection_id: query_param(url, "connection"),
+        }),
+        other => Err(format!("Unknown {}:// action '{}'", SCHEME, other)),
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Forwarding
+// ---------------------------------------------------------------------------
+
+fn resolve<R: Runtime>(app: &AppHandle<R>, link: DeepLink) -> Result<DeepLinkAction, String> {
+    match link {
+        DeepLink::Connect { connection_id } => {
+            crate::commands::find_connection_by_id(app, &connection_id)?;
+            Ok(DeepLinkAction::Connect { connection_id })
+        }
+        DeepLink::OpenQuery {
+            query,
+            connection_id,
+        } => {
+            let saved =
+                crate::saved_queries::find_saved_query(app, &query, connection_id.as_deref())?;
+            Ok(DeepLinkAction::OpenQuery {
+                connection_id: saved.connection_id,
+                name: saved.name,
+                sql: saved.sql,
+                database: saved.database,
+            })
+        }
+    }
+}
+
+/// Bring the main window to the front.
+pub fn focus_main_window<R: Runtime>(app: &AppHandle<R>) {
+    let Some(window) = crate::window_state::primary_main_window(app) else {
+        return;
+    };
+    let _ = window.unminimize();
+    let _ = window.show();
+    if let Err(e) = window.set_focus() {
+        log::warn!("Failed to focus main window: {}", e);
+    }
+}
+
+/// Queue the actions of `urls` and tell the main window about them.
+pub fn open_urls<R: Runtime>(app: &AppHandle<R>, urls: Vec<Url>) {
+    let mut queued = 0;
+    for url in urls {
+        match parse(&url).and_then(|link| resolve(app, link)) {
+            Ok(action) => {
+                log::info!("Opening deep link {}", url);
+                PENDING.lock().unwrap().push(action);
+                queued += 1;
+            }
+            Err(e) => log::warn!("Ignoring deep link {}: {}", url, e),
+        }
+    }
+    if queued == 0 {
+        return;
+    }
+    let Some(window) = crate::window_state::primary_main_window(app) else {
+        return;
+    };
+    focus_main_window(app);
+    if let Err(e) = app.emit_to(window.label(), DEEP_LINK_EVENT, ()) {
+        log::warn!("Failed to notify main window of deep link: {}", e);
+    }
+}
+
+/// Handle a second launch: its deep links arrive through the deep-link
+/// plugin, so only the window and `--explain` are handled here.
+pub fn on_second_instance<R: Runtime>(app: &AppHandle<R>, argv: Vec<String>) {
+    log::info!("Second launch forwarded to the running instance");
+    let explain = crate::cli::parse_from(&argv).and_then(|args| args.explain);
+    if let Some(path) = explain {
+        if let Err(e) = crate::explain_import::spawn_visual_explain_window(app, Some(path)) {
+            log::error!("Failed to open Visual Explain window: {e}");
+        }
+        return;
+    }
+    focus_main_window(app);
+}
+
+// ---------------------------------------------------------------------------
+// Tauri commands
+// ---------------------------------------------------------------------------
+
+/// Hand the queued link actions to the main window, oldest first.
+#[tauri::command]
+pub fn take_pending_deep_links() -> Vec<DeepLinkAction> {
+    std::mem::take(&mut *PENDING.lock().unwrap())
+}
@@ -0,0 +1,65 @@
+#[cfg(test)]
+mod tests {
+    use crate::deep_link::{parse, DeepLink, DeepLinkAction};
+    use tauri::Url;
+
+    fn link(url: &str) -> Result<DeepLink, String> {
+        parse(&Url::parse(url).unwrap())
+    }
+
+    #[test]
+    fn parses_connect_links() {
+        assert_eq!(
+            link("tabularis://connect?id=6f1c").unwrap(),
+            DeepLink::Connect {
+                connection_id: "6f1c".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn parses_query_links_with_encoded_names() {
+        assert_eq!(
+            link("tabularis://query?id=Daily%20revenue&connection=prod").unwrap(),
+            DeepLink::OpenQuery {
+                query: "Daily revenue".to_string(),
+                connection_id: Some("prod".to_string()),
+            }
+        );
+        assert_eq!(
+            link("tabularis://query?id=q-1&connection=").unwrap(),
+            DeepLink::OpenQuery {
+                query: "q-1".to_string(),
+                connection_id: None,
+            }
+        );
+    }
+
+    #[test]
+    fn rejects_malformed_links() {
+        assert!(link("tabularis://connect").is_err());
+        assert!(link("tabularis://connect?id=%20").is_err());
+        assert!(link("tabularis://drop?id=x").is_err());
+        assert!(link("https://connect?id=x").is_err());
+    }
+
+    #[test]
+    fn actions_serialize_for_the_frontend() {
+        let action = DeepLinkAction::OpenQuery {
+            connection_id: "prod".to_string(),
+            name: "Daily revenue".to_string(),
+            sql: "SELECT 1".to_string(),
+            database: None,
+        };
+        assert_eq!(
+            serde_json::to_value(&action).unwrap(),
+            serde_json::json!({
+                "action": "openQuery",
+                "connectionId": "prod",
+                "name": "Daily revenue",
+                "sql": "SELECT 1",
+                "database": null
+            })
+        );
+    }
+}
@@ -0,0 +1,363 @@
+//! Environment checks behind the "health" panel.
+//!
+//! Each check reports a finding with a status and, when something is off, a
+//! hint the user can act on. Checks never fail the command: an error while
+//! checking is itself a finding.
+
+use std::ffi::OsStr;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use serde::Serialize;
+use tauri::{AppHandle, Runtime};
+
+use crate::keychain_utils::SERVICE_NAME;
+use crate::plugins::manager::ConfigManifest;
+
+const OLLAMA_TIMEOUT: Duration = Duration::from_secs(2);
+
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum FindingStatus {
+    Ok,
+    Warning,
+    Error,
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct DiagnosticFinding {
+    /// Stable id of the check, e.g. `ollama` or `plugin:duckdb`.
+    pub id: String,
+    pub title: String,
+    pub status: FindingStatus,
+    pub message: String,
+    /// What to do about it, when the status is not `ok`.
+    pub fix: Option<String>,
+}
+
+impl DiagnosticFinding {
+    fn new(
+        id: impl Into<String>,
+        title: impl Into<String>,
+        status: FindingStatus,
+        message: impl Into<String>,
+    ) -> Self {
+        Self {
+            id: id.into(),
+            title: title.into(),
+            status,
+            message: message.into(),
+            fix: None,
+        }
+    }
+
+    fn with_fix(mut self, fix: impl Into<String>) -> Self {
+        self.fix = Some(fix.into());
+        self
+    }
+}
+
+#[tauri::command]
+pub async fn diagnose_environment<R: Runtime>(
+    app: AppHandle<R>,
+) -> Result<Vec<DiagnosticFinding>, String> {
+    let ollama_port = crate::config::load_config_internal(&app)
+        .ai_ollama_port
+        .unwrap_or(11434);
+
+    let mut findings = vec![check_cloudflared(), check_ollama(ollama_port).await];
+    findings.extend(check_plugins());
+    findings.push(
+        tokio::task::spawn_blocking(check_keychain)
+            .await
+            .map_err(|e| e.to_string())?,
+    );
+    Ok(findings)
+}
+
+fn check_cloudflared() -> DiagnosticFinding {
+    let title = "Cloudflare Tunnel client";
+    match std::env::var_os("PATH").and_then(|path| find_in_path("cloudflared", &path)) {
+        Some(path) => DiagnosticFinding::new(
+            "cloudflared",
+            title,
+            FindingStatus::Ok,
+            format!("cloudflared found at {}", path.display()),
+        ),
+        None => DiagnosticFinding::new(
+            "cloudflared",
+            title,
+            FindingStatus::Warning,
+            "cloudflared is not on PATH; connections cannot be reached through a Cloudflare tunnel",
+        )
+        .with_fix("Install cloudflared from https://developers.cloudflare.com/cloudflare-one/connections/connect-networks/downloads/ and restart tabularis"),
+    }
+}
+
+async fn check_ollama(port: u16) -> DiagnosticFinding {
+    let title = "Ollama";
+    let url = format!("http://localhost:{}/api/tags", port);
+    let client = match reqwest::Client::builder().timeout(OLLAMA_TIMEOUT).build() {
+        Ok(client) => client,
+        Err(e) => {
+            return DiagnosticFinding::new("ollama", title, FindingStatus::Error, e.to_string())
+        }
+    };
+    match client.get(&url).send().await {
+        Ok(res) if res.status().is_success() => DiagnosticFinding::new(
+            "ollama",
+            title,
+            FindingStatus::Ok,
+            format!("Ollama is running on port {}", port),
+        ),
+        Ok(res) => DiagnosticFinding::new(
+            "ollama",
+            title,
+            FindingStatus::Warning,
+            format!(
+                "Port {} answered with HTTP {}, which is not Ollama",
+                port,
+                res.status()
+            ),
+        )
+        .with_fix("Check the Ollama port in Settings → AI"),
+        Err(_) => DiagnosticFinding::new(
+            "ollama",
+            title,
+            FindingStatus::Warning,
+            format!(
+                "Ollama is not reachable on port {}; local AI models are unavailable",
+                port
+            ),
+        )
+        .with_fix(
+            "Start Ollama with `ollama serve`, or set the port it listens on in Settings → AI",
+        ),
+    }
+}
+
+/// One finding per installed plugin that ships an executable.
+fn check_plugins() -> Vec<DiagnosticFinding> {
+    let Some(plugins_dir) = crate::paths::get_app_data_dir().map(|d| d.join("plugins")) else {
+        return Vec::new();
+    };
+    let Ok(entries) = fs::read_dir(&plugins_dir) else {
+        return Vec::new();
+    };
+    let mut dirs: Vec<PathBuf> = entries
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| p.is_dir())
+        .collect();
+    dirs.sort();
+    dirs.iter().filter_map(|dir| check_plugin(dir)).collect()
+}
+
+fn check_plugin(dir: &Path) -> Option<DiagnosticFinding> {
+    let dir_name = dir.file_name()?.to_string_lossy().to_string();
+    let id = format!("plugin:{}", dir_name);
+    let manifest: ConfigManifest = match fs::read_to_string(dir.join("manifest.json"))
+        .map_err(|e| e.to_string())
+        .and_then(|s| serde_json::from_str(&s).map_err(|e| e.to_string()))
+    {
+        Ok(manifest) => manifest,
+        Err(e) => {
+            return Some(
+                DiagnosticFinding::new(
+                    id,
+                    format!("Plugin {}", dir_name),
+                    FindingStatus::Error,
+                    format!("Unreadable manifest.json: {}", e),
+                )
+                .with_fix("Reinstall the plugin from Settings → Plugins"),
+            )
+        }
+    };
+    let title = format!("Plugin {}", manifest.name);
+    // UI-only plugins have nothing to run.
+    let executable = manifest.executable?;
+
+    let mut path = dir.join(&executable);
+    if !path.exists() && cfg!(windows) {
+        path = dir.join(format!("{}.exe", executable));
+    }
+    if !path.exists() {
+        return Some(
+            DiagnosticFinding::new(
+                id,
+                title,
+                FindingStatus::Error,
+                format!("Executable {} is missing", executable),
+            )
+            .with_fix("Reinstall the plugin from Settings → Plugins"),
+        );
+    }
+
+    // Script plugins run through an interpreter, which must be on PATH.
+    if let Some(interpreter) = manifest.interpreter.or_else(|| {
+        (path.extension() == Some(OsStr::new("py")))
+            .then(|| if cfg!(windows) { "python" } else { "python3" }.to_string())
+    }) {
+        let found = Path::new(&interpreter).is_file()
+            || std::env::var_os("PATH")
+                .and_then(|p| find_in_path(&interpreter, &p))
+                .is_some();
+        return Some(if found {
+            DiagnosticFinding::new(
+                id,
+                title,
+                FindingStatus::Ok,
+                format!("Runs with {}", interpreter),
+            )
+        } else {
+            DiagnosticFinding::new(
+                id,
+                title,
+                FindingStatus::Error,
+                format!("Interpreter {} was not found", interpreter),
+            )
+            .with_fix(format!(
+                "Install {} or set the plugin's interpreter in Settings → Plugins",
+                interpreter
+            ))
+        });
+    }
+
+    if !is_executable(&path) {
+        return Some(
+            DiagnosticFinding::new(
+                id,
+                title,
+                FindingStatus::Error,
+                format!("{} is not executable", path.display()),
+            )
+            .with_fix(format!("Run `chmod +x \"{}\"`", path.display())),
+        );
+    }
+
+    let missing = missing_shared_libraries(&path);
+    if !missing.is_empty() {
+        return Some(
+            DiagnosticFinding::new(
+                id,
+                title,
+                FindingStatus::Error,
+                format!("Missing shared libraries: {}", missing.join(", ")),
+            )
+            .with_fix(library_fix(&missing)),
+        );
+    }
+
+    Some(DiagnosticFinding::new(
+        id,
+        title,
+        FindingStatus::Ok,
+        format!("{} is ready", executable),
+    ))
+}
+
+fn check_keychain() -> DiagnosticFinding {
+    let title = "System keychain";
+    let probe = keyring::Entry::new(SERVICE_NAME, "diagnostics:probe")
+        .and_then(|entry| entry.get_password());
+    match probe {
+        Ok(_) | Err(keyring::Error::NoEntry) => {
+            DiagnosticFinding::new("keychain", title, FindingStatus::Ok, "The system keychain is available")
+        }
+        Err(e) => DiagnosticFinding::new(
+            "keychain",
+            title,
+            FindingStatus::Warning,
+            format!(
+                "The system keychain is unavailable ({}); passwords are stored in the encrypted file {} instead",
+                e,
+                crate::secrets::SECRETS_FILE
+            ),
+        )
+        .with_fix(if cfg!(target_os = "linux") {
+            "Install and unlock a Secret Service provider such as GNOME Keyring or KWallet"
+        } else {
+            "Unlock the system keychain"
+        }),
+    }
+}
+
+/// First `name` (or `name.exe` on Windows) found in the `PATH`-style list.
+pub fn find_in_path(name: &str, path_var: &OsStr) -> Option<PathBuf> {
+    std::env::split_paths(path_var).find_map(|dir| {
+        let candidate = dir.join(name);
+        if candidate.is_file() {
+            return Some(candidate);
+        }
+        if cfg!(windows) {
+            let exe = dir.join(format!("{}.exe", name));
+            if exe.is_file() {
+                return Some(exe);
+            }
+        }
+        None
+    })
+}
+
+#[cfg(unix)]
+fn is_executable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    fs::metadata(path)
+        .map(|m| m.permissions().mode() & 0o111 != 0)
+        .unwrap_or(false)
+}
+
+#[cfg(not(unix))]
+fn is_executable(_path: &Path) -> bool {
+    true
+}
+
+/// Shared libraries the dynamic linker cannot resolve for `path`. Only
+/// Linux reports them (through `ldd`); elsewhere the list is empty.
+fn missing_shared_libraries(path: &Path) -> Vec<String> {
+    if !cfg!(target_os = "linux") {
+        return Vec::new();
+    }
+    match std::process::Command::new("ldd").arg(path).output() {
+        Ok(output) => parse_ldd_missing(&String::from_utf8_lossy(&output.stdout)),
+        Err(_) => Vec::new(),
+    }
+}
+
+/// Library names from the `libfoo.so.1 => not found` lines of `ldd` output.
+pub fn parse_ldd_missing(output: &str) -> Vec<String> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let (lib, target) = line.trim().split_once("=>")?;
+            (target.trim() == "not found").then(|| lib.trim().to_string())
+        })
+        .collect()
+}
+
+/// Install hint for missing libraries; OpenSSL is by far the most common.
+pub fn library_fix(missing: &[String]) -> String {
+    if missing
+        .iter()
+        .any(|lib| lib.starts_with("libssl") || lib.starts_with("libcrypto"))
+    {
+        let version = missing
+            .iter()
+            .find_map(|lib| {
+                lib.strip_prefix("libssl.so.")
+                    .or_else(|| lib.strip_prefix("libcrypto.so."))
+            })
+            .unwrap_or("3");
+        format!(
+            "Install OpenSSL {} (e.g. `libssl{}` on Debian/Ubuntu, `openssl-libs` on Fedora), or ask the plugin author for a build linked against the OpenSSL your system ships",
+            version,
+            version
+        )
+    } else {
+        format!(
+            "Install the packages providing {} with your system package manager",
+            missing.join(", ")
+        )
+    }
+}
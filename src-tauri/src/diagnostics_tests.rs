@@ -0,0 +1,52 @@
+#[cfg(test)]
+mod tests {
+    use crate::diagnostics::{find_in_path, library_fix, parse_ldd_missing};
+
+    #[test]
+    fn parse_ldd_missing_collects_unresolved_libraries() {
+        let output = "\tlinux-vdso.so.1 (0x00007ffd6a5f2000)\n\
+                      \tlibssl.so.1.1 => not found\n\
+                      \tlibcrypto.so.1.1 => not found\n\
+                      \tlibc.so.6 => /lib/x86_64-linux-gnu/libc.so.6 (0x00007f1c2a000000)\n\
+                      \t/lib64/ld-linux-x86-64.so.2 (0x00007f1c2a400000)\n";
+
+        assert_eq!(
+            parse_ldd_missing(output),
+            vec!["libssl.so.1.1".to_string(), "libcrypto.so.1.1".to_string()]
+        );
+    }
+
+    #[test]
+    fn parse_ldd_missing_is_empty_when_all_resolve() {
+        let output = "\tlibc.so.6 => /lib/x86_64-linux-gnu/libc.so.6 (0x00007f1c2a000000)\n";
+        assert!(parse_ldd_missing(output).is_empty());
+    }
+
+    #[test]
+    fn library_fix_names_the_openssl_version() {
+        let fix = library_fix(&["libssl.so.1.1".to_string()]);
+        assert!(fix.contains("OpenSSL 1.1"));
+        assert!(fix.contains("libssl1.1"));
+    }
+
+    #[test]
+    fn library_fix_lists_other_libraries() {
+        let fix = library_fix(&["libpq.so.5".to_string()]);
+        assert!(fix.contains("libpq.so.5"));
+        assert!(!fix.contains("OpenSSL"));
+    }
+
+    #[test]
+    fn find_in_path_searches_each_directory() {
+        let empty = tempfile::tempdir().unwrap();
+        let bin = tempfile::tempdir().unwrap();
+        std::fs::write(bin.path().join("cloudflared"), b"").unwrap();
+        let path_var = std::env::join_paths([empty.path(), bin.path()]).unwrap();
+
+        assert_eq!(
+            find_in_path("cloudflared", &path_var),
+            Some(bin.path().join("cloudflared"))
+        );
+        assert_eq!(find_in_path("ollama", &path_var), None);
+    }
+}
@@ -1,14 +1,28 @@
+mod batch;
 mod blob;
+mod convert;
+mod guard;
+mod params;
 mod query;
+mod stream;
 
 #[cfg(test)]
 mod tests;
 
+pub use batch::{plan_insert_chunks, InsertBatchLimits, InsertChunk};
 pub use blob::{
     decode_blob_wire_format, encode_blob, encode_blob_full, resolve_blob_file_ref,
     DEFAULT_MAX_BLOB_SIZE, MAX_BLOB_PREVIEW_SIZE,
 };
+pub use convert::{convert_rows, RowCollector, RowConverter, CONVERT_BATCH_ROWS};
+pub use guard::{
+    estimate_value_size, ResultGuard, ResultLimits, DEFAULT_MAX_RESULT_BYTES,
+    DEFAULT_MAX_RESULT_ROWS,
+};
+pub use params::{bind_query_params, BoundQuery, PlaceholderStyle};
 pub use query::{
-    build_paginated_query, calculate_offset, extract_user_limit, is_explainable_query,
-    is_select_query, returns_result_set, strip_leading_sql_comments, strip_limit_offset,
+    append_select_columns, build_paginated_query, calculate_offset, extract_user_limit,
+    is_explainable_query, is_materializable_query, is_select_query, quote_identifier,
+    returns_result_set, strip_leading_sql_comments, strip_limit_offset, validate_object_name,
 };
+pub use stream::{RowBatchCallback, RowBatcher};
@@ -0,0 +1,78 @@
+use std::collections::HashMap;
+use std::ops::Range;
+
+use serde_json::Value;
+
+/// Bounds for one multi-row `INSERT ... VALUES (...), (...)` statement.
+/// Each driver picks values below its server's hard limits (parameter
+/// count, packet size, rows per `VALUES` list).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InsertBatchLimits {
+    /// Rows per statement.
+    pub max_rows: usize,
+    /// Bound parameters per statement (one per cell).
+    pub max_params: usize,
+    /// Approximate payload per statement, in bytes.
+    pub max_bytes: usize,
+}
+
+/// Consecutive rows of a batch that share one column list and fit in a
+/// single statement. `rows` indexes into the submitted batch.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InsertChunk {
+    pub columns: Vec<String>,
+    pub rows: Range<usize>,
+}
+
+/// Split `rows` into chunks for multi-row inserts. A chunk ends when the
+/// next row has a different set of columns or would push the chunk past
+/// any of `limits`; a row that exceeds the limits on its own still gets a
+/// chunk of its own. Columns are sorted so the generated SQL is stable.
+pub fn plan_insert_chunks(
+    rows: &[HashMap<String, Value>],
+    limits: InsertBatchLimits,
+) -> Vec<InsertChunk> {
+    let mut chunks: Vec<InsertChunk> = Vec::new();
+    let mut chunk_bytes = 0usize;
+
+    for (idx, row) in rows.iter().enumerate() {
+        let mut columns: Vec<String> = row.keys().cloned().collect();
+        columns.sort();
+        let size = row_size(row);
+
+        if let Some(last) = chunks.last_mut() {
+            let count = last.rows.len() + 1;
+            let fits = last.columns == columns
+                && count <= limits.max_rows
+                && count * columns.len() <= limits.max_params
+                && chunk_bytes + size <= limits.max_bytes;
+            if fits {
+                last.rows.end = idx + 1;
+                chunk_bytes += size;
+                continue;
+            }
+        }
+
+        chunks.push(InsertChunk {
+            columns,
+            rows: idx..idx + 1,
+        });
+        chunk_bytes = size;
+    }
+
+    chunks
+}
+
+/// Rough wire size of a row: strings count their length, other values
+/// the length of their JSON spelling.
+fn row_size(row: &HashMap<String, Value>) -> usize {
+    row.iter()
+        .map(|(col, val)| {
+            col.len()
+                + match val {
+                    Value::String(s) => s.len(),
+                    other => other.to_string().len(),
+                }
+        })
+        .sum()
+}
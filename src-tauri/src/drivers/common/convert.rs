@@ -0,0 +1,97 @@
+use serde_json::Value;
+
+use super::guard::{ResultGuard, ResultLimits};
+use crate::models::ResultTruncation;
+
+/// Raw rows converted to JSON per blocking task.
+pub const CONVERT_BATCH_ROWS: usize = 1000;
+
+/// Batches smaller than this are converted in place: handing them to the
+/// blocking pool costs more than the conversion itself.
+const INLINE_CONVERT_ROWS: usize = 64;
+
+/// Turns one driver row into its JSON values.
+pub type RowConverter<R> = fn(&R) -> Vec<Value>;
+
+/// Convert `rows` with `convert`, on the blocking thread pool unless the
+/// batch is small.
+pub async fn convert_rows<R: Send + 'static>(
+    rows: Vec<R>,
+    convert: RowConverter<R>,
+) -> Result<Vec<Vec<Value>>, String> {
+    if rows.len() < INLINE_CONVERT_ROWS {
+        return Ok(rows.iter().map(convert).collect());
+    }
+    tokio::task::spawn_blocking(move || rows.iter().map(convert).collect())
+        .await
+        .map_err(|e| format!("Row conversion failed: {}", e))
+}
+
+/// Collects a driver's raw rows into a JSON result. Rows are queued as they
+/// arrive and converted `CONVERT_BATCH_ROWS` at a time on the blocking pool,
+/// so decoding a big result never holds an async worker for long: the fetch
+/// loop yields to the runtime while each batch converts. Converted rows are
+/// admitted through a [`ResultGuard`].
+pub struct RowCollector<R> {
+    convert: RowConverter<R>,
+    pending: Vec<R>,
+    rows: Vec<Vec<Value>>,
+    guard: ResultGuard,
+    truncation: Option<ResultTruncation>,
+}
+
+impl<R: Send + 'static> RowCollector<R> {
+    pub fn new(limits: ResultLimits, convert: RowConverter<R>) -> Self {
+        Self {
+            convert,
+            pending: Vec::new(),
+            rows: Vec::new(),
+            guard: ResultGuard::new(limits),
+            truncation: None,
+        }
+    }
+
+    /// Rows collected or still waiting for conversion.
+    pub fn len(&self) -> usize {
+        self.rows.len() + self.pending.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Queue `row`. Returns `false` once a result limit has cut the result
+    /// short; the driver then stops fetching.
+    pub async fn push(&mut self, row: R) -> Result<bool, String> {
+        if self.truncation.is_some() {
+            return Ok(false);
+        }
+        self.pending.push(row);
+        if self.pending.len() >= CONVERT_BATCH_ROWS {
+            self.flush().await?;
+        }
+        Ok(self.truncation.is_none())
+    }
+
+    async fn flush(&mut self) -> Result<(), String> {
+        let pending = std::mem::take(&mut self.pending);
+        for json_row in convert_rows(pending, self.convert).await? {
+            if let Some(t) = self.guard.admit(&json_row) {
+                self.truncation = Some(t);
+                break;
+            }
+            self.rows.push(json_row);
+        }
+        crate::query_jobs::record_progress(self.guard.rows(), self.guard.bytes());
+        Ok(())
+    }
+
+    /// Convert the queued rows. Returns the JSON rows and, when a limit cut
+    /// the result short, why.
+    pub async fn finish(mut self) -> Result<(Vec<Vec<Value>>, Option<ResultTruncation>), String> {
+        if self.truncation.is_none() {
+            self.flush().await?;
+        }
+        Ok((self.rows, self.truncation))
+    }
+}
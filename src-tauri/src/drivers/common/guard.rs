@@ -0,0 +1,123 @@
+use serde_json::Value;
+
+use crate::models::{ResultTruncation, TruncationReason};
+
+/// Rows collected into one result before fetching stops. Can be overridden
+/// via config.json with "resultMaxRows" (0 = no limit).
+pub const DEFAULT_MAX_RESULT_ROWS: u64 = 500_000;
+
+/// Estimated in-memory size of one result before fetching stops: 512MB.
+/// Can be overridden via config.json with "resultMaxBytes" (0 = no limit).
+pub const DEFAULT_MAX_RESULT_BYTES: u64 = 512 * 1024 * 1024;
+
+/// Upper bounds on a collected result set. `None` disables a bound.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ResultLimits {
+    pub max_rows: Option<u64>,
+    pub max_bytes: Option<u64>,
+}
+
+impl ResultLimits {
+    pub fn new(max_rows: u64, max_bytes: u64) -> Self {
+        Self {
+            max_rows: (max_rows > 0).then_some(max_rows),
+            max_bytes: (max_bytes > 0).then_some(max_bytes),
+        }
+    }
+
+    /// The limits from the cached app config, or the defaults.
+    pub fn from_config() -> Self {
+        let config = crate::config::get_cached_config();
+        Self::new(
+            config.result_max_rows.unwrap_or(DEFAULT_MAX_RESULT_ROWS),
+            config.result_max_bytes.unwrap_or(DEFAULT_MAX_RESULT_BYTES),
+        )
+    }
+}
+
+/// Rough heap footprint of a row value: the `Value` itself plus the text,
+/// elements and keys it owns.
+pub fn estimate_value_size(value: &Value) -> u64 {
+    let own = std::mem::size_of::<Value>() as u64;
+    match value {
+        Value::String(s) => own + s.len() as u64,
+        Value::Array(items) => own + items.iter().map(estimate_value_size).sum::<u64>(),
+        Value::Object(map) => {
+            own + map
+                .iter()
+                .map(|(k, v)| k.len() as u64 + estimate_value_size(v))
+                .sum::<u64>()
+        }
+        _ => own,
+    }
+}
+
+/// Tracks the rows a driver collects against [`ResultLimits`].
+pub struct ResultGuard {
+    limits: ResultLimits,
+    rows: u64,
+    bytes: u64,
+}
+
+impl ResultGuard {
+    pub fn new(limits: ResultLimits) -> Self {
+        Self {
+            limits,
+            rows: 0,
+            bytes: 0,
+        }
+    }
+
+    /// Count `row` in. Returns why the result must stop when keeping it
+    /// would exceed a limit; the driver then drops the row and stops
+    /// fetching.
+    pub fn admit(&mut self, row: &[Value]) -> Option<ResultTruncation> {
+        if let Some(max_rows) = self.limits.max_rows {
+            if self.rows >= max_rows {
+                return Some(self.truncation(TruncationReason::MaxRows, max_rows));
+            }
+        }
+        let size = row.iter().map(estimate_value_size).sum::<u64>();
+        if let Some(max_bytes) = self.limits.max_bytes {
+            if self.bytes + size > max_bytes {
+                return Some(self.truncation(TruncationReason::MaxBytes, max_bytes));
+            }
+        }
+        self.rows += 1;
+        self.bytes += size;
+        None
+    }
+
+    /// Rows admitted so far.
+    pub fn rows(&self) -> u64 {
+        self.rows
+    }
+
+    /// Estimated size of the rows admitted so far.
+    pub fn bytes(&self) -> u64 {
+        self.bytes
+    }
+
+    fn truncation(&self, reason: TruncationReason, limit: u64) -> ResultTruncation {
+        let rows = self.rows.to_string();
+        let message = match reason {
+            TruncationReason::MaxRows => crate::i18n::t_with(
+                "results.rowLimitReached",
+                &[("rows", &rows), ("limit", &limit.to_string())],
+            ),
+            TruncationReason::MaxBytes => crate::i18n::t_with(
+                "results.sizeLimitReached",
+                &[
+                    ("rows", &rows),
+                    ("limit", &(limit / (1024 * 1024)).to_string()),
+                ],
+            ),
+        };
+        ResultTruncation {
+            reason,
+            limit,
+            rows: self.rows,
+            message,
+        }
+    }
+}
@@ -0,0 +1,189 @@
+use std::collections::HashMap;
+
+use serde_json::Value;
+
+/// How a driver spells bind parameters on the wire.
+pub enum PlaceholderStyle<'a> {
+    /// `?`, bound once per occurrence in order (MySQL, SQLite).
+    Positional,
+    /// Numbered parameters rendered by the closure from their 1-based
+    /// index (`$1`, `@P1`). A name used twice is bound once.
+    Numbered(&'a dyn Fn(usize) -> String),
+}
+
+/// A query rewritten to the driver's native placeholders, with the values
+/// to bind in order.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BoundQuery {
+    pub sql: String,
+    pub values: Vec<Value>,
+}
+
+/// Rewrite the placeholders of an editor query for a driver and collect
+/// the values to bind.
+///
+/// Three spellings are recognised, and may be mixed:
+/// - `:name`, looked up as `name`;
+/// - `$1`, looked up as `1`;
+/// - `?`, the n-th one looked up as `n`.
+///
+/// String literals, quoted identifiers, comments, PostgreSQL `::` casts and
+/// dollar-quoted bodies are left alone, as are `?|` / `?&` (PostgreSQL JSON
+/// operators). A placeholder without a value is an error; unused values are
+/// ignored.
+pub fn bind_query_params(
+    query: &str,
+    params: &HashMap<String, Value>,
+    style: PlaceholderStyle<'_>,
+) -> Result<BoundQuery, String> {
+    let chars: Vec<char> = query.chars().collect();
+    let mut sql = String::with_capacity(query.len());
+    let mut values: Vec<Value> = Vec::new();
+    let mut numbered: HashMap<String, usize> = HashMap::new();
+    let mut positional = 0usize;
+    let mut i = 0;
+
+    let mut emit = |key: String, label: String, sql: &mut String| -> Result<(), String> {
+        let value = params
+            .get(&key)
+            .ok_or_else(|| format!("Missing value for query parameter {}", label))?;
+        match &style {
+            PlaceholderStyle::Positional => {
+                values.push(value.clone());
+                sql.push('?');
+            }
+            PlaceholderStyle::Numbered(render) => {
+                let idx = match numbered.get(&key) {
+                    Some(idx) => *idx,
+                    None => {
+                        values.push(value.clone());
+                        numbered.insert(key, values.len());
+                        values.len()
+                    }
+                };
+                sql.push_str(&render(idx));
+            }
+        }
+        Ok(())
+    };
+
+    while i < chars.len() {
+        let c = chars[i];
+        let next = chars.get(i + 1).copied();
+        match c {
+            '\'' | '"' | '`' => {
+                let end = skip_quoted(&chars, i, c);
+                sql.extend(&chars[i..end]);
+                i = end;
+            }
+            '-' if next == Some('-') => {
+                let end = chars[i..]
+                    .iter()
+                    .position(|&ch| ch == '\n')
+                    .map_or(chars.len(), |p| i + p);
+                sql.extend(&chars[i..end]);
+                i = end;
+            }
+            '/' if next == Some('*') => {
+                let end = (i + 2..chars.len().saturating_sub(1))
+                    .find(|&j| chars[j] == '*' && chars[j + 1] == '/')
+                    .map_or(chars.len(), |j| j + 2);
+                sql.extend(&chars[i..end]);
+                i = end;
+            }
+            ':' if next == Some(':') => {
+                sql.push_str("::");
+                i += 2;
+            }
+            ':' if next.is_some_and(is_ident_start) && !prev_is_ident(&chars, i) => {
+                let end = ident_end(&chars, i + 1);
+                let name: String = chars[i + 1..end].iter().collect();
+                emit(name.clone(), format!(":{}", name), &mut sql)?;
+                i = end;
+            }
+            '$' if next.is_some_and(|ch| ch.is_ascii_digit()) && !prev_is_ident(&chars, i) => {
+                let end = (i + 1..chars.len())
+                    .find(|&j| !chars[j].is_ascii_digit())
+                    .unwrap_or(chars.len());
+                let number: String = chars[i + 1..end].iter().collect();
+                emit(number.clone(), format!("${}", number), &mut sql)?;
+                i = end;
+            }
+            '$' if !prev_is_ident(&chars, i) => match dollar_quote_end(&chars, i) {
+                Some(end) => {
+                    sql.extend(&chars[i..end]);
+                    i = end;
+                }
+                None => {
+                    sql.push('$');
+                    i += 1;
+                }
+            },
+            '?' if matches!(next, Some('|' | '&')) => {
+                sql.extend(&chars[i..i + 2]);
+                i += 2;
+            }
+            '?' => {
+                positional += 1;
+                emit(positional.to_string(), format!("?{}", positional), &mut sql)?;
+                i += 1;
+            }
+            _ => {
+                sql.push(c);
+                i += 1;
+            }
+        }
+    }
+
+    Ok(BoundQuery { sql, values })
+}
+
+fn is_ident_start(c: char) -> bool {
+    c.is_alphabetic() || c == '_'
+}
+
+fn prev_is_ident(chars: &[char], i: usize) -> bool {
+    i > 0 && (chars[i - 1].is_alphanumeric() || chars[i - 1] == '_')
+}
+
+fn ident_end(chars: &[char], start: usize) -> usize {
+    (start..chars.len())
+        .find(|&j| !(chars[j].is_alphanumeric() || chars[j] == '_'))
+        .unwrap_or(chars.len())
+}
+
+/// Index just past the literal or identifier opened by `quote` at `start`.
+/// A doubled quote is an escaped one.
+fn skip_quoted(chars: &[char], start: usize, quote: char) -> usize {
+    let mut j = start + 1;
+    while j < chars.len() {
+        if chars[j] == quote {
+            if chars.get(j + 1) == Some(&quote) {
+                j += 2;
+                continue;
+            }
+            return j + 1;
+        }
+        j += 1;
+    }
+    chars.len()
+}
+
+/// For a PostgreSQL dollar quote (`$$` or `$tag$`) opening at `start`,
+/// the index just past its closing delimiter.
+fn dollar_quote_end(chars: &[char], start: usize) -> Option<usize> {
+    let tag_end = (start + 1..chars.len()).find(|&j| chars[j] == '$')?;
+    if !chars[start + 1..tag_end]
+        .iter()
+        .all(|&c| c.is_alphanumeric() || c == '_')
+    {
+        return None;
+    }
+    let tag = &chars[start..=tag_end];
+    let body = tag_end + 1;
+    // An unterminated quote runs to the end of the query.
+    let end = (body..=chars.len().saturating_sub(tag.len()))
+        .find(|&j| chars[j..j + tag.len()] == *tag)
+        .map_or(chars.len(), |j| j + tag.len());
+    Some(end)
+}
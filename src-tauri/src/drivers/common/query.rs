@@ -58,6 +58,54 @@ pub fn returns_result_set(query: &str) -> bool {
     )
 }
 
+/// Whether `query` can be the body of `CREATE TABLE ... AS`: a plain
+/// row-producing query (SELECT, WITH, VALUES or TABLE).
+pub fn is_materializable_query(query: &str) -> bool {
+    let head = strip_leading_sql_comments(query)
+        .trim_start_matches('(')
+        .split(|c: char| !c.is_ascii_alphanumeric() && c != '_')
+        .next()
+        .unwrap_or("")
+        .to_uppercase();
+    matches!(head.as_str(), "SELECT" | "WITH" | "VALUES" | "TABLE")
+}
+
+/// Quote `name` with the dialect's identifier quote (see
+/// `DriverCapabilities::identifier_quote`), doubling any quote inside it.
+/// `[` quotes as `[name]`.
+pub fn quote_identifier(quote: &str, name: &str) -> String {
+    match quote {
+        "[" => format!("[{}]", name.replace(']', "]]")),
+        q => format!("{q}{}{q}", name.replace(q, &format!("{q}{q}"))),
+    }
+}
+
+/// Validate a user-supplied name for a new table or schema. `max_len` is the
+/// dialect's identifier limit in bytes (e.g. 63 for PostgreSQL).
+pub fn validate_object_name(kind: &str, name: &str, max_len: Option<usize>) -> Result<(), String> {
+    if name.trim().is_empty() {
+        return Err(format!("The {} name cannot be empty", kind));
+    }
+    if name.trim() != name {
+        return Err(format!(
+            "The {} name cannot start or end with whitespace",
+            kind
+        ));
+    }
+    if name.chars().any(char::is_control) {
+        return Err(format!("The {} name contains invalid characters", kind));
+    }
+    if let Some(max) = max_len.filter(|max| name.len() > *max) {
+        return Err(format!(
+            "The {} name is too long ({} bytes, max {})",
+            kind,
+            name.len(),
+            max
+        ));
+    }
+    Ok(())
+}
+
 /// Check if a query type supports EXPLAIN.
 ///
 /// MySQL/MariaDB support EXPLAIN for DML statements only:
@@ -75,6 +123,49 @@ pub fn is_explainable_query(query: &str) -> bool {
         || upper.starts_with("TABLE")
 }
 
+/// Append extra items to the select list of a plain `SELECT * FROM ...` query,
+/// producing `SELECT *, <extra...> FROM ...`.
+///
+/// Returns `None` when `extra` is empty or the query does not start with
+/// `SELECT *` followed by `FROM` (leading comments are ignored), so callers
+/// can fall back to the original query.
+pub fn append_select_columns(query: &str, extra: &[String]) -> Option<String> {
+    if extra.is_empty() {
+        return None;
+    }
+
+    let body = strip_leading_sql_comments(query);
+    if !body
+        .get(..6)
+        .map_or(false, |kw| kw.eq_ignore_ascii_case("SELECT"))
+    {
+        return None;
+    }
+
+    let after_select = &body[6..];
+    let list = after_select.trim_start();
+    if list.len() == after_select.len() {
+        return None;
+    }
+
+    let after_star = list.strip_prefix('*')?;
+    if !after_star
+        .trim_start()
+        .get(..4)
+        .map_or(false, |kw| kw.eq_ignore_ascii_case("FROM"))
+    {
+        return None;
+    }
+
+    let star_end = query.len() - after_star.len();
+    Some(format!(
+        "{}, {}{}",
+        &query[..star_end],
+        extra.join(", "),
+        after_star
+    ))
+}
+
 /// Calculate offset for pagination
 pub fn calculate_offset(page: u32, page_size: u32) -> u32 {
     (page - 1) * page_size
@@ -0,0 +1,56 @@
+use serde_json::Value;
+
+/// Receives one batch of a streamed result: the column names and up to
+/// `batch_size` rows.
+pub type RowBatchCallback<'a> =
+    dyn FnMut(&[String], Vec<Vec<Value>>) -> Result<(), String> + Send + 'a;
+
+/// Collects rows from a driver's row stream and hands them to a
+/// [`RowBatchCallback`] `batch_size` at a time, so only one batch is held
+/// in memory.
+pub struct RowBatcher<'a, 'b> {
+    batch_size: usize,
+    columns: Vec<String>,
+    rows: Vec<Vec<Value>>,
+    total: u64,
+    on_batch: &'a mut RowBatchCallback<'b>,
+}
+
+impl<'a, 'b> RowBatcher<'a, 'b> {
+    pub fn new(batch_size: usize, on_batch: &'a mut RowBatchCallback<'b>) -> Self {
+        let batch_size = batch_size.max(1);
+        Self {
+            batch_size,
+            columns: Vec::new(),
+            rows: Vec::with_capacity(batch_size),
+            total: 0,
+            on_batch,
+        }
+    }
+
+    pub fn push(&mut self, columns: &[String], values: Vec<Value>) -> Result<(), String> {
+        if self.columns.is_empty() {
+            self.columns = columns.to_vec();
+        }
+        self.rows.push(values);
+        self.total += 1;
+        if self.rows.len() >= self.batch_size {
+            self.flush()?;
+        }
+        Ok(())
+    }
+
+    /// Send the last, partial batch and return the number of rows streamed.
+    pub fn finish(mut self) -> Result<u64, String> {
+        self.flush()?;
+        Ok(self.total)
+    }
+
+    fn flush(&mut self) -> Result<(), String> {
+        if self.rows.is_empty() {
+            return Ok(());
+        }
+        let rows = std::mem::replace(&mut self.rows, Vec::with_capacity(self.batch_size));
+        (self.on_batch)(&self.columns, rows)
+    }
+}
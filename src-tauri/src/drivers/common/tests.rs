@@ -1,8 +1,14 @@
 use super::{
-    build_paginated_query, decode_blob_wire_format, encode_blob, encode_blob_full,
-    is_explainable_query, is_select_query, strip_leading_sql_comments, strip_limit_offset,
+    append_select_columns, bind_query_params, build_paginated_query, decode_blob_wire_format,
+    encode_blob, encode_blob_full, estimate_value_size, is_explainable_query,
+    is_materializable_query, is_select_query, plan_insert_chunks, quote_identifier,
+    strip_leading_sql_comments, strip_limit_offset, validate_object_name, InsertBatchLimits,
+    PlaceholderStyle, ResultGuard, ResultLimits, RowBatcher, RowCollector, CONVERT_BATCH_ROWS,
     DEFAULT_MAX_BLOB_SIZE, MAX_BLOB_PREVIEW_SIZE,
 };
+use crate::models::TruncationReason;
+use serde_json::{json, Value};
+use std::collections::HashMap;
 
 #[test]
 fn test_decode_blob_wire_format_valid() {
@@ -355,3 +361,326 @@ fn test_encode_blob_full_roundtrip_large() {
         .expect("should decode 50KB wire format");
     assert_eq!(decoded, data);
 }
+
+#[test]
+fn test_append_select_columns_to_select_star() {
+    let extra = vec!["(price * qty) AS \"total\"".to_string()];
+    assert_eq!(
+        append_select_columns("SELECT * FROM orders WHERE id > 1", &extra).unwrap(),
+        "SELECT *, (price * qty) AS \"total\" FROM orders WHERE id > 1"
+    );
+}
+
+#[test]
+fn test_append_select_columns_is_case_insensitive_and_skips_comments() {
+    let extra = vec!["1 AS one".to_string(), "2 AS two".to_string()];
+    assert_eq!(
+        append_select_columns("-- browse\nselect *\nfrom t", &extra).unwrap(),
+        "-- browse\nselect *, 1 AS one, 2 AS two\nfrom t"
+    );
+}
+
+#[test]
+fn test_append_select_columns_ignores_other_queries() {
+    let extra = vec!["1 AS one".to_string()];
+    assert!(append_select_columns("SELECT id FROM t", &extra).is_none());
+    assert!(append_select_columns("SELECT *, x FROM t", &extra).is_none());
+    assert!(append_select_columns("UPDATE t SET a = 1", &extra).is_none());
+    assert!(append_select_columns("SELECT * FROM t", &[]).is_none());
+}
+
+#[test]
+fn test_is_materializable_query() {
+    assert!(is_materializable_query("SELECT * FROM users"));
+    assert!(is_materializable_query(
+        "-- report\nwith t as (select 1) select * from t"
+    ));
+    assert!(is_materializable_query("VALUES (1, 'a'), (2, 'b')"));
+    assert!(is_materializable_query("(SELECT 1) UNION (SELECT 2)"));
+    assert!(!is_materializable_query("DELETE FROM users"));
+    assert!(!is_materializable_query("SHOW TABLES"));
+    assert!(!is_materializable_query(""));
+}
+
+#[test]
+fn test_validate_object_name() {
+    assert!(validate_object_name("table", "monthly_sales", Some(63)).is_ok());
+    assert!(validate_object_name("table", "Ventes 2024", None).is_ok());
+    assert!(validate_object_name("table", "", None).is_err());
+    assert!(validate_object_name("table", " padded", None).is_err());
+    assert!(validate_object_name("table", "bad\nname", None).is_err());
+
+    let long = "x".repeat(64);
+    let err = validate_object_name("schema", &long, Some(63)).unwrap_err();
+    assert!(err.contains("schema"));
+    assert!(validate_object_name("table", &long, Some(64)).is_ok());
+}
+
+fn insert_row(cells: &[(&str, serde_json::Value)]) -> HashMap<String, serde_json::Value> {
+    cells
+        .iter()
+        .map(|(k, v)| (k.to_string(), v.clone()))
+        .collect()
+}
+
+const WIDE_LIMITS: InsertBatchLimits = InsertBatchLimits {
+    max_rows: 1000,
+    max_params: 65_535,
+    max_bytes: 1024 * 1024,
+};
+
+#[test]
+fn test_plan_insert_chunks_groups_matching_columns() {
+    let rows = vec![
+        insert_row(&[("name", json!("a")), ("id", json!(1))]),
+        insert_row(&[("id", json!(2)), ("name", json!("b"))]),
+        insert_row(&[("id", json!(3))]),
+        insert_row(&[("id", json!(4)), ("name", json!("d"))]),
+    ];
+    let chunks = plan_insert_chunks(&rows, WIDE_LIMITS);
+    assert_eq!(chunks.len(), 3);
+    assert_eq!(chunks[0].columns, vec!["id", "name"]);
+    assert_eq!(chunks[0].rows, 0..2);
+    assert_eq!(chunks[1].columns, vec!["id"]);
+    assert_eq!(chunks[1].rows, 2..3);
+    assert_eq!(chunks[2].rows, 3..4);
+}
+
+#[test]
+fn test_plan_insert_chunks_respects_limits() {
+    let rows: Vec<_> = (0..10)
+        .map(|i| insert_row(&[("a", json!(i)), ("b", json!("x"))]))
+        .collect();
+
+    let by_rows = InsertBatchLimits {
+        max_rows: 4,
+        ..WIDE_LIMITS
+    };
+    let ranges: Vec<_> = plan_insert_chunks(&rows, by_rows)
+        .into_iter()
+        .map(|c| c.rows)
+        .collect();
+    assert_eq!(ranges, vec![0..4, 4..8, 8..10]);
+
+    let by_params = InsertBatchLimits {
+        max_params: 6,
+        ..WIDE_LIMITS
+    };
+    assert_eq!(plan_insert_chunks(&rows, by_params).len(), 4);
+
+    let by_bytes = InsertBatchLimits {
+        max_bytes: 1,
+        ..WIDE_LIMITS
+    };
+    assert_eq!(plan_insert_chunks(&rows, by_bytes).len(), 10);
+}
+
+#[test]
+fn test_plan_insert_chunks_empty_batch() {
+    assert!(plan_insert_chunks(&[], WIDE_LIMITS).is_empty());
+}
+
+#[test]
+fn test_row_batcher_flushes_full_and_partial_batches() {
+    let columns = vec!["id".to_string()];
+    let mut batches: Vec<(Vec<String>, usize)> = Vec::new();
+    let mut on_batch = |cols: &[String], rows: Vec<Vec<serde_json::Value>>| -> Result<(), String> {
+        batches.push((cols.to_vec(), rows.len()));
+        Ok(())
+    };
+
+    let mut batcher = RowBatcher::new(2, &mut on_batch);
+    for i in 0..5 {
+        batcher.push(&columns, vec![json!(i)]).unwrap();
+    }
+    assert_eq!(batcher.finish().unwrap(), 5);
+
+    let sizes: Vec<usize> = batches.iter().map(|(_, n)| *n).collect();
+    assert_eq!(sizes, vec![2, 2, 1]);
+    assert!(batches.iter().all(|(cols, _)| cols == &columns));
+}
+
+#[test]
+fn test_row_batcher_stops_on_callback_error() {
+    let mut on_batch =
+        |_: &[String], _: Vec<Vec<serde_json::Value>>| Err("receiver gone".to_string());
+    let mut batcher = RowBatcher::new(1, &mut on_batch);
+    let err = batcher
+        .push(&["a".to_string()], vec![json!(1)])
+        .unwrap_err();
+    assert_eq!(err, "receiver gone");
+}
+
+#[test]
+fn test_row_batcher_empty_stream() {
+    let mut calls = 0;
+    let mut on_batch = |_: &[String], _: Vec<Vec<serde_json::Value>>| -> Result<(), String> {
+        calls += 1;
+        Ok(())
+    };
+    let batcher = RowBatcher::new(10, &mut on_batch);
+    assert_eq!(batcher.finish().unwrap(), 0);
+    assert_eq!(calls, 0);
+}
+
+fn query_params(pairs: &[(&str, serde_json::Value)]) -> HashMap<String, serde_json::Value> {
+    pairs
+        .iter()
+        .map(|(k, v)| (k.to_string(), v.clone()))
+        .collect()
+}
+
+#[test]
+fn test_bind_query_params_positional_repeats_named_values() {
+    let params = query_params(&[("id", json!(7)), ("1", json!("x"))]);
+    let bound = bind_query_params(
+        "SELECT * FROM t WHERE a = :id OR b = :id OR c = ?",
+        &params,
+        PlaceholderStyle::Positional,
+    )
+    .unwrap();
+    assert_eq!(bound.sql, "SELECT * FROM t WHERE a = ? OR b = ? OR c = ?");
+    assert_eq!(bound.values, vec![json!(7), json!(7), json!("x")]);
+}
+
+#[test]
+fn test_bind_query_params_numbered_binds_each_name_once() {
+    let render = |i: usize| format!("@P{}", i);
+    let params = query_params(&[("id", json!(7)), ("2", json!("x"))]);
+    let bound = bind_query_params(
+        "SELECT * FROM t WHERE a = :id AND b = $2 AND c = :id",
+        &params,
+        PlaceholderStyle::Numbered(&render),
+    )
+    .unwrap();
+    assert_eq!(
+        bound.sql,
+        "SELECT * FROM t WHERE a = @P1 AND b = @P2 AND c = @P1"
+    );
+    assert_eq!(bound.values, vec![json!(7), json!("x")]);
+}
+
+#[test]
+fn test_bind_query_params_skips_literals_comments_and_casts() {
+    let params = query_params(&[("id", json!(1))]);
+    let query = "SELECT ':nope', \"?\", $$ :body $$, x::text, data ?| array['a'] -- :c ?\n\
+                 FROM t /* $1 */ WHERE id = :id";
+    let bound = bind_query_params(query, &params, PlaceholderStyle::Positional).unwrap();
+    assert!(bound.sql.ends_with("WHERE id = ?"));
+    assert!(bound.sql.contains("':nope'"));
+    assert!(bound.sql.contains("x::text"));
+    assert_eq!(bound.values, vec![json!(1)]);
+}
+
+#[test]
+fn test_bind_query_params_missing_value_is_an_error() {
+    let err = bind_query_params(
+        "SELECT * FROM t WHERE id = :id",
+        &HashMap::new(),
+        PlaceholderStyle::Positional,
+    )
+    .unwrap_err();
+    assert!(err.contains(":id"));
+}
+
+#[test]
+fn test_result_guard_stops_at_row_limit() {
+    let mut guard = ResultGuard::new(ResultLimits::new(2, 0));
+    assert!(guard.admit(&[json!(1)]).is_none());
+    assert!(guard.admit(&[json!(2)]).is_none());
+    let truncation = guard
+        .admit(&[json!(3)])
+        .expect("third row exceeds the limit");
+    assert_eq!(truncation.reason, TruncationReason::MaxRows);
+    assert_eq!(truncation.limit, 2);
+    assert_eq!(truncation.rows, 2);
+    assert!(!truncation.message.is_empty());
+}
+
+#[test]
+fn test_result_guard_stops_before_exceeding_byte_limit() {
+    let row = [json!("x".repeat(100))];
+    let row_size = estimate_value_size(&row[0]);
+    let mut guard = ResultGuard::new(ResultLimits::new(0, row_size * 2 + 1));
+    assert!(guard.admit(&row).is_none());
+    assert!(guard.admit(&row).is_none());
+    let truncation = guard.admit(&row).expect("third row exceeds the size");
+    assert_eq!(truncation.reason, TruncationReason::MaxBytes);
+    assert_eq!(truncation.rows, 2);
+}
+
+#[test]
+fn test_result_limits_zero_disables_a_limit() {
+    let limits = ResultLimits::new(0, 0);
+    assert_eq!(limits.max_rows, None);
+    assert_eq!(limits.max_bytes, None);
+    let mut guard = ResultGuard::new(limits);
+    for i in 0..1000 {
+        assert!(guard.admit(&[json!(i)]).is_none());
+    }
+}
+
+#[test]
+fn test_estimate_value_size_counts_owned_data() {
+    let scalar = estimate_value_size(&json!(1));
+    assert_eq!(estimate_value_size(&json!(null)), scalar);
+    assert_eq!(estimate_value_size(&json!("abcd")), scalar + 4);
+    assert_eq!(estimate_value_size(&json!(["ab", 1])), 3 * scalar + 2);
+    assert_eq!(estimate_value_size(&json!({"k": "vv"})), 2 * scalar + 3);
+}
+
+fn int_row(n: &i64) -> Vec<Value> {
+    vec![json!(n)]
+}
+
+#[tokio::test]
+async fn test_row_collector_converts_across_batches() {
+    let mut collector = RowCollector::new(ResultLimits::new(0, 0), int_row);
+    let total = CONVERT_BATCH_ROWS as i64 * 2 + 5;
+    for n in 0..total {
+        assert!(collector.push(n).await.unwrap());
+    }
+    assert_eq!(collector.len(), total as usize);
+    let (rows, truncation) = collector.finish().await.unwrap();
+    assert!(truncation.is_none());
+    assert_eq!(rows.len(), total as usize);
+    assert_eq!(rows[0], vec![json!(0)]);
+    assert_eq!(rows[rows.len() - 1], vec![json!(total - 1)]);
+}
+
+#[tokio::test]
+async fn test_row_collector_applies_result_limits() {
+    let mut collector = RowCollector::new(ResultLimits::new(2, 0), int_row);
+    for n in 0..3 {
+        collector.push(n).await.unwrap();
+    }
+    let (rows, truncation) = collector.finish().await.unwrap();
+    assert_eq!(rows, vec![vec![json!(0)], vec![json!(1)]]);
+    let truncation = truncation.expect("third row exceeds the limit");
+    assert_eq!(truncation.reason, TruncationReason::MaxRows);
+}
+
+#[tokio::test]
+async fn test_row_collector_stops_once_truncated() {
+    let limit = CONVERT_BATCH_ROWS as i64;
+    let mut collector = RowCollector::new(ResultLimits::new((limit - 1) as u64, 0), int_row);
+    let mut accepted = 0;
+    for n in 0..limit * 2 {
+        if !collector.push(n).await.unwrap() {
+            break;
+        }
+        accepted += 1;
+    }
+    // The limit is only checked when a full batch is converted.
+    assert_eq!(accepted, limit - 1);
+    let (rows, truncation) = collector.finish().await.unwrap();
+    assert_eq!(rows.len(), limit as usize - 1);
+    assert!(truncation.is_some());
+}
+
+#[test]
+fn test_quote_identifier_doubles_the_quote() {
+    assert_eq!(quote_identifier("\"", "a\"b"), "\"a\"\"b\"");
+    assert_eq!(quote_identifier("`", "a`b"), "`a``b`");
+    assert_eq!(quote_identifier("[", "a]b"), "[a]]b]");
+}
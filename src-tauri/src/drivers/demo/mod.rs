@@ -0,0 +1,746 @@
+//! Built-in demo driver.
+//!
+//! Serves a small shop database (customers, products, orders) held in
+//! memory, so the UI can be developed, tested and demoed without a real
+//! server, and screenshots always show the same data. Every connection gets
+//! its own copy, seeded from `seed.sql` the first time it is used and kept
+//! until the app exits. Under the hood the data lives in an in-memory SQLite
+//! database and every call goes through the SQLite driver, so SQL typed in
+//! the editor behaves like it would against a real file.
+
+#[cfg(test)]
+mod tests;
+
+use std::collections::HashMap;
+use std::str::FromStr;
+
+use async_trait::async_trait;
+use serde_json::Value;
+use sqlx::sqlite::{SqliteConnectOptions, SqlitePoolOptions};
+
+use crate::drivers::common::{InsertBatchLimits, RowBatchCallback};
+use crate::drivers::driver_trait::{DatabaseDriver, DriverCapabilities, PluginManifest};
+use crate::drivers::sqlite::SqliteDriver;
+use crate::models::{
+    BatchStatementResult, ColumnDefinition, ColumnSignature, ConnectionParams, DataTypeInfo,
+    DatabaseSelection, ExplainPlan, ForeignKey, Index, InsertedRecord, QueryResult, RoutineInfo,
+    RoutineParameter, TableColumn, TableInfo, TableSchema, TableStats, TriggerInfo, ViewInfo,
+};
+
+/// Schema and rows loaded into each new demo connection.
+const SEED_SQL: &str = include_str!("seed.sql");
+
+/// Database name shown for demo connections; also part of the pool key.
+pub const DEMO_DATABASE: &str = ":memory:";
+
+/// Points `params` at the in-memory SQLite database of this connection,
+/// creating and seeding it on first use.
+pub async fn dataset_params(params: &ConnectionParams) -> Result<ConnectionParams, String> {
+    let mut demo = params.clone();
+    demo.driver = "sqlite".to_string();
+    demo.database = DatabaseSelection::Single(DEMO_DATABASE.to_string());
+
+    if !crate::pool_manager::has_pool(&demo, demo.connection_id.as_deref()).await {
+        let pool = create_dataset().await?;
+        crate::pool_manager::register_sqlite_pool(&demo, pool).await;
+    }
+    Ok(demo)
+}
+
+async fn create_dataset() -> Result<sqlx::SqlitePool, String> {
+    // Each parse of `sqlite::memory:` names a fresh database. A single
+    // connection that never expires keeps it alive for the whole session.
+    let options = SqliteConnectOptions::from_str("sqlite::memory:")
+        .map_err(|e| e.to_string())?
+        .foreign_keys(true);
+    let pool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .idle_timeout(None)
+        .max_lifetime(None)
+        .connect_with(options)
+        .await
+        .map_err(|e| format!("Failed to open demo database: {}", e))?;
+    sqlx::raw_sql(SEED_SQL)
+        .execute(&pool)
+        .await
+        .map_err(|e| format!("Failed to seed demo database: {}", e))?;
+    log::info!("Demo dataset created");
+    Ok(pool)
+}
+
+pub struct DemoDriver {
+    manifest: PluginManifest,
+    inner: SqliteDriver,
+}
+
+impl DemoDriver {
+    pub fn new() -> Self {
+        Self {
+            manifest: PluginManifest {
+                id: "demo".to_string(),
+                name: "Demo".to_string(),
+                version: "1.0.0".to_string(),
+                description: "In-memory sample shop database, no server required".to_string(),
+                default_port: None,
+                capabilities: DriverCapabilities {
+                    schemas: false,
+                    views: true,
+                    routines: false,
+                    file_based: false,
+                    folder_based: false,
+                    connection_string: false,
+                    connection_string_example: String::new(),
+                    identifier_quote: "\"".into(),
+                    alter_primary_key: true,
+                    auto_increment_keyword: "AUTOINCREMENT".into(),
+                    serial_type: String::new(),
+                    inline_pk: true,
+                    alter_column: false,
+                    create_foreign_keys: false,
+                    no_connection_required: true,
+                    manage_tables: true,
+                    readonly: false,
+                    arrow: false,
+                    triggers: true,
+                    privileges: false,
+                    server_status: false,
+                    table_stats: true,
+                },
+                is_builtin: true,
+                default_username: String::new(),
+                color: "#a855f7".to_string(),
+                icon: "database".to_string(),
+                settings: vec![],
+                ui_extensions: None,
+            },
+            inner: SqliteDriver::new(),
+        }
+    }
+}
+
+#[async_trait]
+impl DatabaseDriver for DemoDriver {
+    fn manifest(&self) -> &PluginManifest {
+        &self.manifest
+    }
+
+    fn get_data_types(&self) -> Vec<DataTypeInfo> {
+        self.inner.get_data_types()
+    }
+
+    fn map_inferred_type(&self, kind: &str) -> String {
+        self.inner.map_inferred_type(kind)
+    }
+
+    fn build_connection_url(&self, _params: &ConnectionParams) -> Result<String, String> {
+        Ok("sqlite::memory:".to_string())
+    }
+
+    async fn ping(&self, params: &ConnectionParams) -> Result<(), String> {
+        self.inner.ping(&dataset_params(params).await?).await
+    }
+
+    async fn test_connection(&self, params: &ConnectionParams) -> Result<(), String> {
+        dataset_params(params).await?;
+        Ok(())
+    }
+
+    async fn get_databases(&self, _params: &ConnectionParams) -> Result<Vec<String>, String> {
+        Ok(vec![])
+    }
+
+    async fn get_schemas(&self, _params: &ConnectionParams) -> Result<Vec<String>, String> {
+        Ok(vec![])
+    }
+
+    async fn get_tables(
+        &self,
+        params: &ConnectionParams,
+        schema: Option<&str>,
+    ) -> Result<Vec<TableInfo>, String> {
+        self.inner
+            .get_tables(&dataset_params(params).await?, schema)
+            .await
+    }
+
+    async fn get_columns(
+        &self,
+        params: &ConnectionParams,
+        table: &str,
+        schema: Option<&str>,
+    ) -> Result<Vec<TableColumn>, String> {
+        self.inner
+            .get_columns(&dataset_params(params).await?, table, schema)
+            .await
+    }
+
+    async fn get_foreign_keys(
+        &self,
+        params: &ConnectionParams,
+        table: &str,
+        schema: Option<&str>,
+    ) -> Result<Vec<ForeignKey>, String> {
+        self.inner
+            .get_foreign_keys(&dataset_params(params).await?, table, schema)
+            .await
+    }
+
+    async fn get_indexes(
+        &self,
+        params: &ConnectionParams,
+        table: &str,
+        schema: Option<&str>,
+    ) -> Result<Vec<Index>, String> {
+        self.inner
+            .get_indexes(&dataset_params(params).await?, table, schema)
+            .await
+    }
+
+    async fn get_views(
+        &self,
+        params: &ConnectionParams,
+        schema: Option<&str>,
+    ) -> Result<Vec<ViewInfo>, String> {
+        self.inner
+            .get_views(&dataset_params(params).await?, schema)
+            .await
+    }
+
+    async fn get_view_definition(
+        &self,
+        params: &ConnectionParams,
+        view_name: &str,
+        schema: Option<&str>,
+    ) -> Result<String, String> {
+        self.inner
+            .get_view_definition(&dataset_params(params).await?, view_name, schema)
+            .await
+    }
+
+    async fn get_view_columns(
+        &self,
+        params: &ConnectionParams,
+        view_name: &str,
+        schema: Option<&str>,
+    ) -> Result<Vec<TableColumn>, String> {
+        self.inner
+            .get_view_columns(&dataset_params(params).await?, view_name, schema)
+            .await
+    }
+
+    async fn create_view(
+        &self,
+        params: &ConnectionParams,
+        view_name: &str,
+        definition: &str,
+        schema: Option<&str>,
+    ) -> Result<(), String> {
+        self.inner
+            .create_view(
+                &dataset_params(params).await?,
+                view_name,
+                definition,
+                schema,
+            )
+            .await
+    }
+
+    async fn alter_view(
+        &self,
+        params: &ConnectionParams,
+        view_name: &str,
+        definition: &str,
+        schema: Option<&str>,
+    ) -> Result<(), String> {
+        self.inner
+            .alter_view(
+                &dataset_params(params).await?,
+                view_name,
+                definition,
+                schema,
+            )
+            .await
+    }
+
+    async fn drop_view(
+        &self,
+        params: &ConnectionParams,
+        view_name: &str,
+        schema: Option<&str>,
+    ) -> Result<(), String> {
+        self.inner
+            .drop_view(&dataset_params(params).await?, view_name, schema)
+            .await
+    }
+
+    async fn create_table_from_query(
+        &self,
+        params: &ConnectionParams,
+        table_name: &str,
+        query: &str,
+        schema: Option<&str>,
+    ) -> Result<(), String> {
+        self.inner
+            .create_table_from_query(&dataset_params(params).await?, table_name, query, schema)
+            .await
+    }
+
+    async fn get_routines(
+        &self,
+        _params: &ConnectionParams,
+        _schema: Option<&str>,
+    ) -> Result<Vec<RoutineInfo>, String> {
+        Ok(vec![])
+    }
+
+    async fn get_routine_parameters(
+        &self,
+        _params: &ConnectionParams,
+        _routine_name: &str,
+        _schema: Option<&str>,
+    ) -> Result<Vec<RoutineParameter>, String> {
+        Ok(vec![])
+    }
+
+    async fn get_routine_definition(
+        &self,
+        _params: &ConnectionParams,
+        _routine_name: &str,
+        _routine_type: &str,
+        _schema: Option<&str>,
+    ) -> Result<String, String> {
+        Err("The demo database has no routines".into())
+    }
+
+    async fn get_triggers(
+        &self,
+        params: &ConnectionParams,
+        schema: Option<&str>,
+    ) -> Result<Vec<TriggerInfo>, String> {
+        self.inner
+            .get_triggers(&dataset_params(params).await?, schema)
+            .await
+    }
+
+    async fn get_trigger_definition(
+        &self,
+        params: &ConnectionParams,
+        trigger_name: &str,
+        table_name: &str,
+        schema: Option<&str>,
+    ) -> Result<String, String> {
+        self.inner
+            .get_trigger_definition(
+                &dataset_params(params).await?,
+                trigger_name,
+                table_name,
+                schema,
+            )
+            .await
+    }
+
+    async fn create_trigger(
+        &self,
+        params: &ConnectionParams,
+        trigger_sql: &str,
+        schema: Option<&str>,
+    ) -> Result<(), String> {
+        self.inner
+            .create_trigger(&dataset_params(params).await?, trigger_sql, schema)
+            .await
+    }
+
+    async fn drop_trigger(
+        &self,
+        params: &ConnectionParams,
+        trigger_name: &str,
+        table_name: &str,
+        schema: Option<&str>,
+    ) -> Result<(), String> {
+        self.inner
+            .drop_trigger(
+                &dataset_params(params).await?,
+                trigger_name,
+                table_name,
+                schema,
+            )
+            .await
+    }
+
+    async fn execute_query(
+        &self,
+        params: &ConnectionParams,
+        query: &str,
+        limit: Option<u32>,
+        page: u32,
+        schema: Option<&str>,
+    ) -> Result<QueryResult, String> {
+        self.inner
+            .execute_query(&dataset_params(params).await?, query, limit, page, schema)
+            .await
+    }
+
+    async fn execute_batch(
+        &self,
+        params: &ConnectionParams,
+        queries: &[String],
+        limit: Option<u32>,
+        page: u32,
+        schema: Option<&str>,
+    ) -> Result<Vec<BatchStatementResult>, String> {
+        self.inner
+            .execute_batch(&dataset_params(params).await?, queries, limit, page, schema)
+            .await
+    }
+
+    async fn execute_query_with_params(
+        &self,
+        params: &ConnectionParams,
+        query: &str,
+        values: &HashMap<String, Value>,
+        limit: Option<u32>,
+        page: u32,
+        schema: Option<&str>,
+    ) -> Result<QueryResult, String> {
+        self.inner
+            .execute_query_with_params(
+                &dataset_params(params).await?,
+                query,
+                values,
+                limit,
+                page,
+                schema,
+            )
+            .await
+    }
+
+    async fn execute_query_stream(
+        &self,
+        params: &ConnectionParams,
+        query: &str,
+        batch_size: usize,
+        schema: Option<&str>,
+        on_batch: &mut RowBatchCallback<'_>,
+    ) -> Result<u64, String> {
+        self.inner
+            .execute_query_stream(
+                &dataset_params(params).await?,
+                query,
+                batch_size,
+                schema,
+                on_batch,
+            )
+            .await
+    }
+
+    async fn get_table_stats(
+        &self,
+        params: &ConnectionParams,
+        schema: Option<&str>,
+    ) -> Result<Vec<TableStats>, String> {
+        self.inner
+            .get_table_stats(&dataset_params(params).await?, schema)
+            .await
+    }
+
+    async fn explain_query(
+        &self,
+        params: &ConnectionParams,
+        query: &str,
+        analyze: bool,
+        schema: Option<&str>,
+    ) -> Result<ExplainPlan, String> {
+        self.inner
+            .explain_query(&dataset_params(params).await?, query, analyze, schema)
+            .await
+    }
+
+    async fn insert_record(
+        &self,
+        params: &ConnectionParams,
+        table: &str,
+        data: HashMap<String, Value>,
+        schema: Option<&str>,
+        max_blob_size: u64,
+    ) -> Result<u64, String> {
+        self.inner
+            .insert_record(
+                &dataset_params(params).await?,
+                table,
+                data,
+                schema,
+                max_blob_size,
+            )
+            .await
+    }
+
+    async fn insert_record_returning(
+        &self,
+        params: &ConnectionParams,
+        table: &str,
+        data: HashMap<String, Value>,
+        schema: Option<&str>,
+        max_blob_size: u64,
+    ) -> Result<InsertedRecord, String> {
+        self.inner
+            .insert_record_returning(
+                &dataset_params(params).await?,
+                table,
+                data,
+                schema,
+                max_blob_size,
+            )
+            .await
+    }
+
+    fn insert_batch_limits(&self) -> Option<InsertBatchLimits> {
+        self.inner.insert_batch_limits()
+    }
+
+    async fn insert_rows(
+        &self,
+        params: &ConnectionParams,
+        table: &str,
+        columns: &[String],
+        rows: Vec<HashMap<String, Value>>,
+        schema: Option<&str>,
+        max_blob_size: u64,
+    ) -> Result<u64, String> {
+        self.inner
+            .insert_rows(
+                &dataset_params(params).await?,
+                table,
+                columns,
+                rows,
+                schema,
+                max_blob_size,
+            )
+            .await
+    }
+
+    async fn update_record(
+        &self,
+        params: &ConnectionParams,
+        table: &str,
+        pk_col: &str,
+        pk_val: Value,
+        col_name: &str,
+        new_val: Value,
+        schema: Option<&str>,
+        max_blob_size: u64,
+    ) -> Result<u64, String> {
+        self.inner
+            .update_record(
+                &dataset_params(params).await?,
+                table,
+                pk_col,
+                pk_val,
+                col_name,
+                new_val,
+                schema,
+                max_blob_size,
+            )
+            .await
+    }
+
+    async fn delete_record(
+        &self,
+        params: &ConnectionParams,
+        table: &str,
+        pk_col: &str,
+        pk_val: Value,
+        schema: Option<&str>,
+    ) -> Result<u64, String> {
+        self.inner
+            .delete_record(
+                &dataset_params(params).await?,
+                table,
+                pk_col,
+                pk_val,
+                schema,
+            )
+            .await
+    }
+
+    async fn save_blob_to_file(
+        &self,
+        params: &ConnectionParams,
+        table: &str,
+        col_name: &str,
+        pk_col: &str,
+        pk_val: Value,
+        schema: Option<&str>,
+        file_path: &str,
+    ) -> Result<(), String> {
+        self.inner
+            .save_blob_to_file(
+                &dataset_params(params).await?,
+                table,
+                col_name,
+                pk_col,
+                pk_val,
+                schema,
+                file_path,
+            )
+            .await
+    }
+
+    async fn fetch_blob_as_data_url(
+        &self,
+        params: &ConnectionParams,
+        table: &str,
+        col_name: &str,
+        pk_col: &str,
+        pk_val: Value,
+        schema: Option<&str>,
+    ) -> Result<String, String> {
+        self.inner
+            .fetch_blob_as_data_url(
+                &dataset_params(params).await?,
+                table,
+                col_name,
+                pk_col,
+                pk_val,
+                schema,
+            )
+            .await
+    }
+
+    async fn get_table_ddl(
+        &self,
+        params: &ConnectionParams,
+        table: &str,
+        schema: Option<&str>,
+    ) -> Result<String, String> {
+        self.inner
+            .get_table_ddl(&dataset_params(params).await?, table, schema)
+            .await
+    }
+
+    async fn get_create_table_sql(
+        &self,
+        table_name: &str,
+        columns: Vec<ColumnDefinition>,
+        schema: Option<&str>,
+    ) -> Result<Vec<String>, String> {
+        self.inner
+            .get_create_table_sql(table_name, columns, schema)
+            .await
+    }
+
+    async fn get_add_column_sql(
+        &self,
+        table: &str,
+        column: ColumnDefinition,
+        schema: Option<&str>,
+    ) -> Result<Vec<String>, String> {
+        self.inner.get_add_column_sql(table, column, schema).await
+    }
+
+    async fn get_alter_column_sql(
+        &self,
+        table: &str,
+        old_column: ColumnDefinition,
+        new_column: ColumnDefinition,
+        schema: Option<&str>,
+    ) -> Result<Vec<String>, String> {
+        self.inner
+            .get_alter_column_sql(table, old_column, new_column, schema)
+            .await
+    }
+
+    async fn get_create_index_sql(
+        &self,
+        table: &str,
+        index_name: &str,
+        columns: Vec<String>,
+        is_unique: bool,
+        schema: Option<&str>,
+    ) -> Result<Vec<String>, String> {
+        self.inner
+            .get_create_index_sql(table, index_name, columns, is_unique, schema)
+            .await
+    }
+
+    async fn get_create_foreign_key_sql(
+        &self,
+        table: &str,
+        fk_name: &str,
+        column: &str,
+        ref_table: &str,
+        ref_column: &str,
+        on_delete: Option<&str>,
+        on_update: Option<&str>,
+        schema: Option<&str>,
+    ) -> Result<Vec<String>, String> {
+        self.inner
+            .get_create_foreign_key_sql(
+                table, fk_name, column, ref_table, ref_column, on_delete, on_update, schema,
+            )
+            .await
+    }
+
+    async fn drop_index(
+        &self,
+        params: &ConnectionParams,
+        table: &str,
+        index_name: &str,
+        schema: Option<&str>,
+    ) -> Result<(), String> {
+        self.inner
+            .drop_index(&dataset_params(params).await?, table, index_name, schema)
+            .await
+    }
+
+    async fn drop_foreign_key(
+        &self,
+        params: &ConnectionParams,
+        table: &str,
+        fk_name: &str,
+        schema: Option<&str>,
+    ) -> Result<(), String> {
+        self.inner
+            .drop_foreign_key(&dataset_params(params).await?, table, fk_name, schema)
+            .await
+    }
+
+    async fn get_all_columns_batch(
+        &self,
+        params: &ConnectionParams,
+        schema: Option<&str>,
+    ) -> Result<HashMap<String, Vec<TableColumn>>, String> {
+        self.inner
+            .get_all_columns_batch(&dataset_params(params).await?, schema)
+            .await
+    }
+
+    async fn get_all_foreign_keys_batch(
+        &self,
+        params: &ConnectionParams,
+        schema: Option<&str>,
+    ) -> Result<HashMap<String, Vec<ForeignKey>>, String> {
+        self.inner
+            .get_all_foreign_keys_batch(&dataset_params(params).await?, schema)
+            .await
+    }
+
+    async fn get_schema_signature(
+        &self,
+        params: &ConnectionParams,
+        schema: Option<&str>,
+    ) -> Result<Vec<ColumnSignature>, String> {
+        self.inner
+            .get_schema_signature(&dataset_params(params).await?, schema)
+            .await
+    }
+
+    async fn get_schema_snapshot(
+        &self,
+        params: &ConnectionParams,
+        schema: Option<&str>,
+    ) -> Result<Vec<TableSchema>, String> {
+        self.inner
+            .get_schema_snapshot(&dataset_params(params).await?, schema)
+            .await
+    }
+}
@@ -0,0 +1,103 @@
+-- Demo dataset loaded into every new demo connection.
+
+CREATE TABLE customers (
+  id INTEGER PRIMARY KEY AUTOINCREMENT,
+  name TEXT NOT NULL,
+  email TEXT NOT NULL UNIQUE,
+  country TEXT,
+  created_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE TABLE products (
+  id INTEGER PRIMARY KEY AUTOINCREMENT,
+  name TEXT NOT NULL,
+  category TEXT NOT NULL,
+  price REAL NOT NULL,
+  stock INTEGER NOT NULL DEFAULT 0
+);
+
+CREATE TABLE orders (
+  id INTEGER PRIMARY KEY AUTOINCREMENT,
+  customer_id INTEGER NOT NULL REFERENCES customers(id) ON DELETE CASCADE,
+  status TEXT NOT NULL DEFAULT 'pending',
+  ordered_at TEXT NOT NULL
+);
+
+CREATE TABLE order_items (
+  order_id INTEGER NOT NULL REFERENCES orders(id) ON DELETE CASCADE,
+  product_id INTEGER NOT NULL REFERENCES products(id),
+  quantity INTEGER NOT NULL,
+  unit_price REAL NOT NULL,
+  PRIMARY KEY (order_id, product_id)
+);
+
+CREATE INDEX idx_orders_customer ON orders (customer_id);
+CREATE INDEX idx_order_items_product ON order_items (product_id);
+
+CREATE VIEW order_totals AS
+SELECT o.id AS order_id,
+       c.name AS customer,
+       o.status,
+       o.ordered_at,
+       SUM(i.quantity * i.unit_price) AS total
+FROM orders o
+JOIN customers c ON c.id = o.customer_id
+JOIN order_items i ON i.order_id = o.id
+GROUP BY o.id;
+
+CREATE TRIGGER order_items_reserve_stock
+AFTER INSERT ON order_items
+BEGIN
+  UPDATE products SET stock = stock - NEW.quantity WHERE id = NEW.product_id;
+END;
+
+INSERT INTO customers (name, email, country, created_at) VALUES
+  ('Alice Martin', 'alice@example.com', 'France', '2024-01-08 09:12:00'),
+  ('Bruno Rossi', 'bruno@example.com', 'Italy', '2024-01-15 14:30:00'),
+  ('Chen Wei', 'chen@example.com', 'China', '2024-02-02 08:45:00'),
+  ('Dana Kowalski', 'dana@example.com', 'Poland', '2024-02-20 17:05:00'),
+  ('Elif Yilmaz', 'elif@example.com', 'Turkey', '2024-03-11 11:20:00'),
+  ('Felix Wagner', 'felix@example.com', 'Germany', '2024-03-28 16:40:00'),
+  ('Grace Okafor', 'grace@example.com', 'Nigeria', '2024-04-09 10:00:00'),
+  ('Hiro Tanaka', 'hiro@example.com', 'Japan', '2024-04-22 07:55:00');
+
+-- Stock is set before the order items so the trigger leaves it positive.
+INSERT INTO products (name, category, price, stock) VALUES
+  ('Mechanical Keyboard', 'Peripherals', 89.90, 60),
+  ('Wireless Mouse', 'Peripherals', 29.50, 120),
+  ('27" Monitor', 'Displays', 249.00, 35),
+  ('USB-C Hub', 'Accessories', 39.99, 80),
+  ('Laptop Stand', 'Accessories', 45.00, 50),
+  ('Noise-Cancelling Headphones', 'Audio', 199.00, 40),
+  ('Webcam 1080p', 'Peripherals', 59.00, 70),
+  ('Desk Lamp', 'Office', 34.90, 90);
+
+INSERT INTO orders (customer_id, status, ordered_at) VALUES
+  (1, 'delivered', '2024-05-02 10:15:00'),
+  (2, 'delivered', '2024-05-06 18:22:00'),
+  (3, 'shipped', '2024-05-12 09:01:00'),
+  (1, 'shipped', '2024-05-19 13:47:00'),
+  (4, 'pending', '2024-05-23 20:10:00'),
+  (5, 'cancelled', '2024-05-25 08:30:00'),
+  (6, 'delivered', '2024-06-01 15:55:00'),
+  (7, 'pending', '2024-06-04 11:11:00'),
+  (8, 'shipped', '2024-06-07 19:40:00'),
+  (3, 'pending', '2024-06-10 07:25:00');
+
+INSERT INTO order_items (order_id, product_id, quantity, unit_price) VALUES
+  (1, 1, 1, 89.90),
+  (1, 2, 1, 29.50),
+  (2, 3, 2, 249.00),
+  (3, 6, 1, 199.00),
+  (3, 4, 2, 39.99),
+  (4, 5, 1, 45.00),
+  (5, 7, 1, 59.00),
+  (5, 8, 2, 34.90),
+  (6, 2, 3, 29.50),
+  (7, 1, 2, 89.90),
+  (7, 3, 1, 249.00),
+  (8, 6, 1, 199.00),
+  (9, 4, 1, 39.99),
+  (9, 5, 2, 45.00),
+  (10, 8, 1, 34.90),
+  (10, 2, 2, 29.50);
@@ -0,0 +1,67 @@
+use std::collections::HashMap;
+
+use serde_json::json;
+
+use super::DemoDriver;
+use crate::drivers::driver_trait::DatabaseDriver;
+use crate::models::ConnectionParams;
+
+fn demo_params(connection_id: &str) -> ConnectionParams {
+    ConnectionParams {
+        driver: "demo".to_string(),
+        connection_id: Some(connection_id.to_string()),
+        ..Default::default()
+    }
+}
+
+async fn count_customers(driver: &DemoDriver, params: &ConnectionParams) -> serde_json::Value {
+    let result = driver
+        .execute_query(params, "SELECT COUNT(*) FROM customers", None, 1, None)
+        .await
+        .expect("count customers");
+    result.rows[0][0].clone()
+}
+
+#[tokio::test]
+async fn test_seeds_sample_tables() {
+    let driver = DemoDriver::new();
+    let params = demo_params("demo-test-seed");
+
+    let tables: Vec<String> = driver
+        .get_tables(&params, None)
+        .await
+        .expect("list tables")
+        .into_iter()
+        .map(|t| t.name)
+        .collect();
+    for expected in ["customers", "products", "orders", "order_items"] {
+        assert!(tables.contains(&expected.to_string()), "missing {expected}");
+    }
+
+    let fks = driver
+        .get_foreign_keys(&params, "orders", None)
+        .await
+        .expect("foreign keys");
+    assert!(fks.iter().any(|fk| fk.ref_table == "customers"));
+
+    assert_eq!(count_customers(&driver, &params).await, json!(8));
+}
+
+#[tokio::test]
+async fn test_each_connection_gets_its_own_dataset() {
+    let driver = DemoDriver::new();
+    let first = demo_params("demo-test-isolation-a");
+    let second = demo_params("demo-test-isolation-b");
+
+    let mut data = HashMap::new();
+    data.insert("name".to_string(), json!("Zoe Demo"));
+    data.insert("email".to_string(), json!("zoe@example.com"));
+    let inserted = driver
+        .insert_record_returning(&first, "customers", data, None, 1024)
+        .await
+        .expect("insert customer");
+    assert_eq!(inserted.affected_rows, 1);
+
+    assert_eq!(count_customers(&driver, &first).await, json!(9));
+    assert_eq!(count_customers(&driver, &second).await, json!(8));
+}
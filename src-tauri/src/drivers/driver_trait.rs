@@ -6,10 +6,12 @@ use sqlx::any::AnyConnectOptions;
 use sqlx::{AnyConnection, Connection};
 use std::str::FromStr;
 
+use crate::drivers::common::{plan_insert_chunks, InsertBatchLimits, RowBatchCallback, RowBatcher};
 use crate::models::{
-    BatchStatementResult, ColumnDefinition, ConnectionParams, DataTypeInfo, ExplainPlan,
-    ForeignKey, Index, QueryResult, RoutineInfo, RoutineParameter, TableColumn, TableInfo,
-    TableSchema, TriggerInfo, ViewInfo,
+    BatchInsertResult, BatchStatementResult, ColumnDefinition, ColumnSignature, ConnectionParams,
+    DataTypeInfo, DbUser, ExplainPlan, ForeignKey, Index, InsertedRecord, LockWait,
+    PrivilegeChange, QueryResult, RoutineInfo, RoutineParameter, RowInsertError, ServerValue,
+    TableColumn, TableInfo, TableSchema, TableStats, TriggerInfo, UserGrant, ViewInfo,
 };
 
 /// Capabilities advertised by a driver.
@@ -72,12 +74,26 @@ pub struct DriverCapabilities {
     /// Supports listing and managing database triggers.
     #[serde(default)]
     pub triggers: bool,
+    /// Supports listing users and granting or revoking their privileges.
+    #[serde(default)]
+    pub privileges: bool,
+    /// Reports server status counters and configuration variables.
+    #[serde(default)]
+    pub server_status: bool,
+    /// Reports row estimates and sizes of tables.
+    #[serde(default)]
+    pub table_stats: bool,
     /// When `true`, the driver is read-only: all data modification operations
     /// (INSERT, UPDATE, DELETE) are disabled in the UI.
     /// Table/column management is also hidden regardless of `manage_tables`.
     /// Defaults to `false`.
     #[serde(default)]
     pub readonly: bool,
+    /// Whether the driver can answer `execute_query_arrow` with an Arrow IPC
+    /// stream of its own (e.g. DuckDB). When `false`, results are converted
+    /// from JSON rows on the host. Defaults to `false`.
+    #[serde(default)]
+    pub arrow: bool,
 }
 
 fn default_double_quote() -> String {
@@ -290,6 +306,20 @@ pub trait DatabaseDriver: Send + Sync {
         schema: Option<&str>,
     ) -> Result<(), String>;
 
+    // --- Materialization ----------------------------------------------------
+
+    /// Persists the rows of `query` as a new table (`CREATE TABLE ... AS`).
+    /// Callers validate `table_name` and check `query` is row-producing.
+    async fn create_table_from_query(
+        &self,
+        _params: &ConnectionParams,
+        _table_name: &str,
+        _query: &str,
+        _schema: Option<&str>,
+    ) -> Result<(), String> {
+        Err("Creating tables from queries is not supported by this driver".into())
+    }
+
     // --- Routines -----------------------------------------------------------
 
     async fn get_routines(
@@ -356,6 +386,66 @@ pub trait DatabaseDriver: Send + Sync {
         Ok(results)
     }
 
+    /// Runs `query` with `values` bound to its placeholders (`:name`, `$1`
+    /// or `?`, see [`crate::drivers::common::bind_query_params`]) instead of
+    /// spliced into the SQL text. Built-in drivers rewrite the placeholders
+    /// to their native syntax and bind on the wire; the default reports
+    /// that the driver cannot bind parameters.
+    async fn execute_query_with_params(
+        &self,
+        _params: &ConnectionParams,
+        _query: &str,
+        _values: &HashMap<String, serde_json::Value>,
+        _limit: Option<u32>,
+        _page: u32,
+        _schema: Option<&str>,
+    ) -> Result<QueryResult, String> {
+        Err("Query parameters are not supported by this driver".into())
+    }
+
+    /// Runs `query` and hands its rows to `on_batch` in batches of up to
+    /// `batch_size` rows instead of collecting them into one `QueryResult`,
+    /// so a multi-million-row SELECT runs in bounded memory. Returns the
+    /// total number of rows streamed.
+    ///
+    /// Built-in drivers read rows from the server as they arrive. The
+    /// default implementation runs `execute_query` without a limit and
+    /// splits the buffered result, so plugin drivers keep working but
+    /// without the memory benefit.
+    async fn execute_query_stream(
+        &self,
+        params: &ConnectionParams,
+        query: &str,
+        batch_size: usize,
+        schema: Option<&str>,
+        on_batch: &mut RowBatchCallback<'_>,
+    ) -> Result<u64, String> {
+        let result = self.execute_query(params, query, None, 1, schema).await?;
+        let mut batcher = RowBatcher::new(batch_size, on_batch);
+        for row in result.rows {
+            batcher.push(&result.columns, row)?;
+        }
+        batcher.finish()
+    }
+
+    /// Runs `query` like `execute_query` but returns the result as an Arrow
+    /// IPC stream (see [`crate::arrow_ipc`]), which the frontend reads
+    /// column by column without parsing JSON rows. The default converts the
+    /// `QueryResult`; drivers that produce Arrow natively override it.
+    async fn execute_query_arrow(
+        &self,
+        params: &ConnectionParams,
+        query: &str,
+        limit: Option<u32>,
+        page: u32,
+        schema: Option<&str>,
+    ) -> Result<Vec<u8>, String> {
+        let result = self
+            .execute_query(params, query, limit, page, schema)
+            .await?;
+        crate::arrow_ipc::result_to_ipc(&result)
+    }
+
     /// Runs EXPLAIN (or EXPLAIN ANALYZE) on the given query and returns a
     /// parsed execution plan tree. Drivers that do not support EXPLAIN can
     /// rely on the default implementation which returns an error.
@@ -380,6 +470,123 @@ pub trait DatabaseDriver: Send + Sync {
         max_blob_size: u64,
     ) -> Result<u64, String>;
 
+    /// Insert one row and read it back as the server stored it, including
+    /// generated keys and defaults. The default implementation only
+    /// reports the affected row count.
+    async fn insert_record_returning(
+        &self,
+        params: &ConnectionParams,
+        table: &str,
+        data: HashMap<String, serde_json::Value>,
+        schema: Option<&str>,
+        max_blob_size: u64,
+    ) -> Result<InsertedRecord, String> {
+        let affected_rows = self
+            .insert_record(params, table, data, schema, max_blob_size)
+            .await?;
+        Ok(InsertedRecord {
+            affected_rows,
+            row: None,
+        })
+    }
+
+    /// Bounds for one multi-row INSERT, or `None` when the driver cannot
+    /// insert several rows in one statement. Drivers returning `Some` must
+    /// also override `insert_rows`.
+    fn insert_batch_limits(&self) -> Option<InsertBatchLimits> {
+        None
+    }
+
+    /// Insert `rows` with one multi-row `INSERT ... VALUES` statement.
+    /// Every row has exactly `columns` (non-empty). The statement either
+    /// inserts every row or none of them.
+    async fn insert_rows(
+        &self,
+        _params: &ConnectionParams,
+        _table: &str,
+        _columns: &[String],
+        _rows: Vec<HashMap<String, serde_json::Value>>,
+        _schema: Option<&str>,
+        _max_blob_size: u64,
+    ) -> Result<u64, String> {
+        Err("Multi-row insert not supported by this driver".into())
+    }
+
+    /// Insert many rows at once (e.g. a multi-row paste in the data grid).
+    ///
+    /// The default implementation splits the batch with
+    /// `plan_insert_chunks` and runs one `insert_rows` statement per chunk.
+    /// When a chunk fails, its rows are retried one by one through
+    /// `insert_record` so only the offending rows are reported. Drivers
+    /// without `insert_batch_limits` insert every row individually.
+    ///
+    /// The outer `Result` is reserved for batch-level failures; per-row
+    /// failures are collected in `BatchInsertResult::errors`.
+    async fn insert_records_batch(
+        &self,
+        params: &ConnectionParams,
+        table: &str,
+        rows: Vec<HashMap<String, serde_json::Value>>,
+        schema: Option<&str>,
+        max_blob_size: u64,
+    ) -> Result<BatchInsertResult, String> {
+        let mut result = BatchInsertResult::default();
+        let Some(limits) = self.insert_batch_limits() else {
+            return Ok(insert_rows_individually(
+                self,
+                params,
+                table,
+                &rows,
+                0,
+                schema,
+                max_blob_size,
+            )
+            .await);
+        };
+
+        for chunk in plan_insert_chunks(&rows, limits) {
+            let chunk_rows = &rows[chunk.rows.clone()];
+            if !chunk.columns.is_empty() && chunk_rows.len() > 1 {
+                let outcome = self
+                    .insert_rows(
+                        params,
+                        table,
+                        &chunk.columns,
+                        chunk_rows.to_vec(),
+                        schema,
+                        max_blob_size,
+                    )
+                    .await;
+                match outcome {
+                    Ok(inserted) => {
+                        result.inserted += inserted;
+                        continue;
+                    }
+                    Err(e) => log::debug!(
+                        "Multi-row insert into {} failed, retrying rows {}..{} one by one: {}",
+                        table,
+                        chunk.rows.start,
+                        chunk.rows.end,
+                        e
+                    ),
+                }
+            }
+            result.extend(
+                insert_rows_individually(
+                    self,
+                    params,
+                    table,
+                    chunk_rows,
+                    chunk.rows.start,
+                    schema,
+                    max_blob_size,
+                )
+                .await,
+            );
+        }
+        Ok(result)
+    }
+
     async fn update_record(
         &self,
         params: &ConnectionParams,
@@ -439,6 +646,33 @@ pub trait DatabaseDriver: Send + Sync {
         Err("DDL generation not supported".into())
     }
 
+    /// `CREATE TABLE` statement(s) for an existing table, as written to SQL
+    /// dumps. The default implementation rebuilds them from `get_columns`
+    /// through `get_create_table_sql`; drivers that can ask the server for
+    /// the real DDL override it.
+    async fn get_table_ddl(
+        &self,
+        params: &ConnectionParams,
+        table: &str,
+        schema: Option<&str>,
+    ) -> Result<String, String> {
+        let columns = self
+            .get_columns(params, table, schema)
+            .await?
+            .into_iter()
+            .map(|c| ColumnDefinition {
+                name: c.name,
+                data_type: c.data_type,
+                is_nullable: c.is_nullable,
+                is_pk: c.is_pk,
+                is_auto_increment: c.is_auto_increment,
+                default_value: c.default_value,
+            })
+            .collect();
+        let statements = self.get_create_table_sql(table, columns, schema).await?;
+        Ok(format!("{};", statements.join(";\n")))
+    }
+
     async fn get_add_column_sql(
         &self,
         _table: &str,
@@ -542,6 +776,121 @@ pub trait DatabaseDriver: Send + Sync {
         Err("Triggers not supported by this driver".into())
     }
 
+    // --- Server -------------------------------------------------------------
+
+    /// The version string the server reports about itself (`SELECT
+    /// VERSION()` and the like), parsed by `crate::server_info::detect`.
+    async fn get_server_version(&self, _params: &ConnectionParams) -> Result<String, String> {
+        Err("Server version not available for this driver".into())
+    }
+
+    /// Installed extensions that change what the catalog looks like (e.g.
+    /// TimescaleDB). Most drivers have none.
+    async fn get_server_extensions(
+        &self,
+        _params: &ConnectionParams,
+    ) -> Result<Vec<String>, String> {
+        Ok(Vec::new())
+    }
+
+    /// Global status counters (connections, queries, cache hits, ...).
+    async fn get_server_status(
+        &self,
+        _params: &ConnectionParams,
+    ) -> Result<Vec<ServerValue>, String> {
+        Err("Server status not supported by this driver".into())
+    }
+
+    /// Configuration variables and their current values.
+    async fn get_server_variables(
+        &self,
+        _params: &ConnectionParams,
+    ) -> Result<Vec<ServerValue>, String> {
+        Err("Server status not supported by this driver".into())
+    }
+
+    // --- Table statistics ---------------------------------------------------
+
+    /// Row estimate, data and index size and last ANALYZE of every table in
+    /// `schema` (the connection's default scope when `None`).
+    async fn get_table_stats(
+        &self,
+        _params: &ConnectionParams,
+        _schema: Option<&str>,
+    ) -> Result<Vec<TableStats>, String> {
+        Err("Table statistics not supported by this driver".into())
+    }
+
+    // --- Sessions -----------------------------------------------------------
+
+    /// Current lock waits: one entry per (blocked, blocking) session pair.
+    async fn get_lock_waits(&self, _params: &ConnectionParams) -> Result<Vec<LockWait>, String> {
+        Err("Lock inspection not supported by this driver".into())
+    }
+
+    /// Terminate the server session `pid` (e.g. a blocking session).
+    async fn kill_session(&self, _params: &ConnectionParams, _pid: i64) -> Result<(), String> {
+        Err("Killing sessions not supported by this driver".into())
+    }
+
+    // --- Users and privileges -----------------------------------------------
+
+    async fn get_users(&self, _params: &ConnectionParams) -> Result<Vec<DbUser>, String> {
+        Err("User management not supported by this driver".into())
+    }
+
+    /// Privileges granted directly to `user` (`host` is the MySQL account
+    /// host).
+    async fn get_user_grants(
+        &self,
+        _params: &ConnectionParams,
+        _user: &str,
+        _host: Option<&str>,
+    ) -> Result<Vec<UserGrant>, String> {
+        Err("User management not supported by this driver".into())
+    }
+
+    async fn grant_privilege(
+        &self,
+        _params: &ConnectionParams,
+        _change: &PrivilegeChange,
+    ) -> Result<(), String> {
+        Err("User management not supported by this driver".into())
+    }
+
+    async fn revoke_privilege(
+        &self,
+        _params: &ConnectionParams,
+        _change: &PrivilegeChange,
+    ) -> Result<(), String> {
+        Err("User management not supported by this driver".into())
+    }
+
+    // --- Schema signature ---------------------------------------------------
+
+    /// Every (table, column, type) of the schema, for change detection.
+    /// `schema: None` covers the connection's default scope. The default
+    /// builds it from `get_schema_snapshot`; drivers override it with a
+    /// single catalog query.
+    async fn get_schema_signature(
+        &self,
+        params: &ConnectionParams,
+        schema: Option<&str>,
+    ) -> Result<Vec<ColumnSignature>, String> {
+        let tables = self.get_schema_snapshot(params, schema).await?;
+        Ok(tables
+            .into_iter()
+            .flat_map(|t| {
+                let table = t.name;
+                t.columns.into_iter().map(move |c| ColumnSignature {
+                    table: table.clone(),
+                    column: c.name,
+                    data_type: c.data_type,
+                })
+            })
+            .collect())
+    }
+
     // --- ER diagram (batch) -------------------------------------------------
 
     async fn get_schema_snapshot(
@@ -561,4 +910,48 @@ pub trait DatabaseDriver: Send + Sync {
         params: &ConnectionParams,
         schema: Option<&str>,
     ) -> Result<HashMap<String, Vec<ForeignKey>>, String>;
+
+    /// Indexes of every table, keyed by table name. The default asks
+    /// `get_indexes` table by table; drivers that can read them all in one
+    /// query override it.
+    async fn get_all_indexes_batch(
+        &self,
+        params: &ConnectionParams,
+        schema: Option<&str>,
+    ) -> Result<HashMap<String, Vec<Index>>, String> {
+        let mut result = HashMap::new();
+        for table in self.get_tables(params, schema).await? {
+            let indexes = self.get_indexes(params, &table.name, schema).await?;
+            result.insert(table.name, indexes);
+        }
+        Ok(result)
+    }
+}
+
+/// Insert `rows` with one `insert_record` call each. Failed rows are
+/// reported at `first_row` + their position in `rows`, i.e. their index
+/// in the whole batch.
+pub async fn insert_rows_individually<D: DatabaseDriver + ?Sized>(
+    driver: &D,
+    params: &ConnectionParams,
+    table: &str,
+    rows: &[HashMap<String, serde_json::Value>],
+    first_row: usize,
+    schema: Option<&str>,
+    max_blob_size: u64,
+) -> BatchInsertResult {
+    let mut result = BatchInsertResult::default();
+    for (offset, row) in rows.iter().enumerate() {
+        match driver
+            .insert_record(params, table, row.clone(), schema, max_blob_size)
+            .await
+        {
+            Ok(inserted) => result.inserted += inserted,
+            Err(error) => result.errors.push(RowInsertError {
+                row: first_row + offset,
+                error,
+            }),
+        }
+    }
+    result
 }
@@ -0,0 +1,161 @@
+use crate::models::ConnectionParams;
+use crate::pool_manager::{
+    active_transaction, format_error_chain, get_mssql_pool, MssqlClient, MssqlManager, MssqlPool,
+    TransactionConnection,
+};
+use deadpool::managed::Object;
+use tiberius::{Query, Row, ToSql};
+
+use super::helpers::MssqlValue;
+
+pub(super) type MssqlObject = Object<MssqlManager>;
+
+/// Server errors carry the message, number and line separately; keep all
+/// three so the user can find the failing statement in a batch.
+pub(super) fn format_mssql_error(e: &tiberius::error::Error) -> String {
+    match e {
+        tiberius::error::Error::Server(token) => format!(
+            "{} (error {}, state {}, line {})",
+            token.message(),
+            token.code(),
+            token.state(),
+            token.line()
+        ),
+        other => other.to_string(),
+    }
+}
+
+#[inline(always)]
+pub(super) async fn get_client(pool: &MssqlPool) -> Result<MssqlObject, String> {
+    pool.get().await.map_err(|e| format_error_chain(&e))
+}
+
+#[inline]
+pub(super) async fn query_all(
+    pool: &MssqlPool,
+    sql: &str,
+    params: &[&dyn ToSql],
+) -> Result<Vec<Row>, String> {
+    let mut client = get_client(pool).await?;
+    client
+        .query(sql, params)
+        .await
+        .map_err(|e| format_mssql_error(&e))?
+        .into_first_result()
+        .await
+        .map_err(|e| format_mssql_error(&e))
+}
+
+#[inline]
+pub(super) async fn query_opt(
+    pool: &MssqlPool,
+    sql: &str,
+    params: &[&dyn ToSql],
+) -> Result<Option<Row>, String> {
+    Ok(query_all(pool, sql, params).await?.into_iter().next())
+}
+
+#[inline]
+pub(super) async fn execute(pool: &MssqlPool, sql: &str) -> Result<u64, String> {
+    let mut client = get_client(pool).await?;
+    client
+        .execute(sql, &[])
+        .await
+        .map(|r| r.total())
+        .map_err(|e| format_mssql_error(&e))
+}
+
+/// Run `sql` with `values` bound to `@P1..@Pn` in order, inside the
+/// transaction open on this connection when there is one.
+pub(super) async fn execute_bound(
+    conn_params: &ConnectionParams,
+    sql: String,
+    values: Vec<MssqlValue>,
+) -> Result<u64, String> {
+    if let Some(shared) = active_transaction(conn_params).await {
+        if let Some(TransactionConnection::Mssql(client)) = shared.lock().await.as_mut() {
+            return run_bound(client, sql, values).await;
+        }
+    }
+
+    let pool = get_mssql_pool(conn_params).await?;
+    let mut client = get_client(&pool).await?;
+    run_bound(&mut client, sql, values).await
+}
+
+/// [`execute_bound`] for a statement that returns rows, such as an
+/// `INSERT ... OUTPUT`. Only the first result set is read.
+pub(super) async fn query_bound(
+    conn_params: &ConnectionParams,
+    sql: String,
+    values: Vec<MssqlValue>,
+) -> Result<Vec<Row>, String> {
+    if let Some(shared) = active_transaction(conn_params).await {
+        if let Some(TransactionConnection::Mssql(client)) = shared.lock().await.as_mut() {
+            return query_rows_bound(client, sql, values).await;
+        }
+    }
+
+    let pool = get_mssql_pool(conn_params).await?;
+    let mut client = get_client(&pool).await?;
+    query_rows_bound(&mut client, sql, values).await
+}
+
+fn bound_query(sql: String, values: Vec<MssqlValue>) -> Query<'static> {
+    let mut query = Query::new(sql);
+    for value in values {
+        match value {
+            MssqlValue::Bool(v) => query.bind(v),
+            MssqlValue::Int(v) => query.bind(v),
+            MssqlValue::Float(v) => query.bind(v),
+            MssqlValue::Text(v) => query.bind(v),
+            MssqlValue::Bytes(v) => query.bind(v),
+        }
+    }
+    query
+}
+
+async fn run_bound(
+    client: &mut MssqlClient,
+    sql: String,
+    values: Vec<MssqlValue>,
+) -> Result<u64, String> {
+    bound_query(sql, values)
+        .execute(client)
+        .await
+        .map(|r| r.total())
+        .map_err(|e| format_mssql_error(&e))
+}
+
+async fn query_rows_bound(
+    client: &mut MssqlClient,
+    sql: String,
+    values: Vec<MssqlValue>,
+) -> Result<Vec<Row>, String> {
+    bound_query(sql, values)
+        .query(client)
+        .await
+        .map_err(|e| format_mssql_error(&e))?
+        .into_first_result()
+        .await
+        .map_err(|e| format_mssql_error(&e))
+}
+
+pub(super) fn row_str(row: &Row, col: &str) -> String {
+    row_str_opt(row, col).unwrap_or_default()
+}
+
+pub(super) fn row_str_opt(row: &Row, col: &str) -> Option<String> {
+    row.try_get::<&str, _>(col)
+        .ok()
+        .flatten()
+        .map(str::to_string)
+}
+
+pub(super) fn row_bool(row: &Row, col: &str) -> bool {
+    row.try_get::<bool, _>(col).ok().flatten().unwrap_or(false)
+}
+
+pub(super) fn row_i32(row: &Row, col: &str) -> i32 {
+    row.try_get::<i32, _>(col).ok().flatten().unwrap_or(0)
+}
@@ -0,0 +1,41 @@
+use futures::stream::TryStreamExt;
+use serde_json::Value;
+
+use crate::models::ConnectionParams;
+use crate::pool_manager::get_mssql_pool;
+
+use super::client::{format_mssql_error, get_client};
+use super::extract::extract_value;
+
+/// Streams the rows produced by `query` against a SQL Server connection. See
+/// the MySQL counterpart for the contract of `on_row`.
+pub async fn stream_query<F>(
+    params: &ConnectionParams,
+    query: &str,
+    mut on_row: F,
+) -> Result<(), String>
+where
+    F: FnMut(&[String], &[Value]) -> Result<(), String> + Send,
+{
+    let pool = get_mssql_pool(params).await?;
+    let mut client = get_client(&pool).await?;
+
+    let mut stream = client
+        .simple_query(query)
+        .await
+        .map_err(|e| format_mssql_error(&e))?;
+    let headers: Vec<String> = stream
+        .columns()
+        .await
+        .map_err(|e| format_mssql_error(&e))?
+        .map(|cols| cols.iter().map(|c| c.name().to_string()).collect())
+        .unwrap_or_default();
+
+    let mut rows = stream.into_row_stream();
+    while let Some(row) = rows.try_next().await.map_err(|e| format_mssql_error(&e))? {
+        let values: Vec<Value> = row.into_iter().map(|cell| extract_value(&cell)).collect();
+        on_row(&headers, &values)?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,53 @@
+use serde_json::Value as JsonValue;
+use tiberius::{ColumnData, FromSql};
+
+fn number<T: Into<serde_json::Number>>(v: Option<T>) -> JsonValue {
+    v.map_or(JsonValue::Null, |v| JsonValue::Number(v.into()))
+}
+
+fn float(v: Option<f64>) -> JsonValue {
+    v.and_then(serde_json::Number::from_f64)
+        .map_or(JsonValue::Null, JsonValue::Number)
+}
+
+/// Decode a temporal cell as `T`; `None` when the cell has another type.
+fn temporal<'a, T: FromSql<'a> + ToString>(data: &'a ColumnData<'static>) -> Option<JsonValue> {
+    T::from_sql(data)
+        .ok()
+        .map(|v| v.map_or(JsonValue::Null, |v| JsonValue::String(v.to_string())))
+}
+
+/// Convert one SQL Server cell to JSON. Decimals and GUIDs become strings
+/// (no precision loss, same spelling as SSMS); binary data uses the shared
+/// blob wire format.
+pub(super) fn extract_value(data: &ColumnData<'static>) -> JsonValue {
+    match data {
+        ColumnData::U8(v) => number(*v),
+        ColumnData::I16(v) => number(*v),
+        ColumnData::I32(v) => number(*v),
+        ColumnData::I64(v) => number(*v),
+        ColumnData::F32(v) => float(v.map(f64::from)),
+        ColumnData::F64(v) => float(*v),
+        ColumnData::Bit(v) => v.map_or(JsonValue::Null, JsonValue::Bool),
+        ColumnData::String(v) => v
+            .as_ref()
+            .map_or(JsonValue::Null, |s| JsonValue::String(s.to_string())),
+        ColumnData::Guid(v) => v.map_or(JsonValue::Null, |g| {
+            JsonValue::String(g.to_string().to_uppercase())
+        }),
+        ColumnData::Numeric(v) => v
+            .as_ref()
+            .map_or(JsonValue::Null, |n| JsonValue::String(n.to_string())),
+        ColumnData::Binary(v) => v.as_ref().map_or(JsonValue::Null, |bytes| {
+            JsonValue::String(crate::drivers::common::encode_blob(bytes))
+        }),
+        ColumnData::Xml(v) => v
+            .as_ref()
+            .map_or(JsonValue::Null, |xml| JsonValue::String(xml.to_string())),
+        other => temporal::<chrono::NaiveDateTime>(other)
+            .or_else(|| temporal::<chrono::NaiveDate>(other))
+            .or_else(|| temporal::<chrono::NaiveTime>(other))
+            .or_else(|| temporal::<chrono::DateTime<chrono::FixedOffset>>(other))
+            .unwrap_or(JsonValue::Null),
+    }
+}
@@ -0,0 +1,199 @@
+/// Quote an identifier with brackets, doubling any closing bracket.
+pub(super) fn quote_ident(name: &str) -> String {
+    format!("[{}]", name.replace(']', "]]"))
+}
+
+/// `[schema].[name]`
+pub(super) fn qualified(schema: &str, name: &str) -> String {
+    format!("{}.{}", quote_ident(schema), quote_ident(name))
+}
+
+/// Unicode string literal: `N'...'`.
+pub(super) fn quote_literal(value: &str) -> String {
+    format!("N'{}'", value.replace('\'', "''"))
+}
+
+/// SQL Server stores column defaults wrapped in parentheses, e.g. `((0))`
+/// or `(getdate())`. Strip the wrapping pairs so the value reads like the
+/// other drivers' defaults.
+pub(super) fn strip_default_parens(default: &str) -> String {
+    let mut s = default.trim();
+    while s.starts_with('(') && s.ends_with(')') && closes_at_end(s) {
+        s = s[1..s.len() - 1].trim();
+    }
+    s.to_string()
+}
+
+/// Whether the `(` at the start of `s` is closed by the `)` at its end.
+fn closes_at_end(s: &str) -> bool {
+    let mut depth = 0usize;
+    let mut in_string = false;
+    for (i, c) in s.char_indices() {
+        match c {
+            '\'' => in_string = !in_string,
+            '(' if !in_string => depth += 1,
+            ')' if !in_string => {
+                depth = depth.saturating_sub(1);
+                if depth == 0 {
+                    return i == s.len() - 1;
+                }
+            }
+            _ => {}
+        }
+    }
+    false
+}
+
+/// `sys.foreign_keys` reports actions as `NO_ACTION`, `SET_NULL`, ...;
+/// the other drivers use the SQL spelling (`NO ACTION`, `SET NULL`).
+pub(super) fn referential_action(desc: &str) -> String {
+    desc.replace('_', " ")
+}
+
+/// Like `common::returns_result_set`, plus `EXEC`: stored procedures are
+/// how most SQL Server code returns rows.
+pub(super) fn returns_result_set(query: &str) -> bool {
+    if crate::drivers::common::returns_result_set(query) {
+        return true;
+    }
+    let head = crate::drivers::common::strip_leading_sql_comments(query)
+        .split(|c: char| !c.is_ascii_alphanumeric() && c != '_')
+        .next()
+        .unwrap_or("")
+        .to_uppercase();
+    matches!(head.as_str(), "EXEC" | "EXECUTE")
+}
+
+/// Page a SELECT with `OFFSET ... FETCH`, fetching one extra row to detect
+/// whether more pages follow. `OFFSET` needs an `ORDER BY`, so queries
+/// without one get `ORDER BY (SELECT NULL)`. Returns `None` for queries
+/// that already limit themselves with `TOP`, `OFFSET` or `FETCH`, which
+/// cannot take a second page clause; those are capped while reading.
+pub(super) fn build_paginated_query(query: &str, page_size: u32, page: u32) -> Option<String> {
+    let base = query.trim().trim_end_matches(';').trim_end();
+    let upper = base.to_uppercase();
+    let words: Vec<&str> = upper
+        .split(|c: char| !c.is_ascii_alphanumeric() && c != '_')
+        .filter(|w| !w.is_empty())
+        .collect();
+    if words
+        .iter()
+        .any(|w| matches!(*w, "TOP" | "OFFSET" | "FETCH"))
+    {
+        return None;
+    }
+
+    let offset = crate::drivers::common::calculate_offset(page, page_size);
+    let order_by = if has_top_level_order_by(&upper) {
+        ""
+    } else {
+        " ORDER BY (SELECT NULL)"
+    };
+    Some(format!(
+        "{}{} OFFSET {} ROWS FETCH NEXT {} ROWS ONLY",
+        base,
+        order_by,
+        offset,
+        page_size + 1
+    ))
+}
+
+/// Whether the last `ORDER BY` in `upper` sits outside any parentheses.
+fn has_top_level_order_by(upper: &str) -> bool {
+    let Some(pos) = upper.rfind("ORDER BY") else {
+        return false;
+    };
+    let mut depth = 0i32;
+    for c in upper[pos..].chars() {
+        match c {
+            '(' => depth += 1,
+            ')' => {
+                depth -= 1;
+                if depth < 0 {
+                    return false;
+                }
+            }
+            _ => {}
+        }
+    }
+    true
+}
+
+/// A value bound to an `@Pn` parameter.
+#[derive(Debug, Clone, PartialEq)]
+pub(super) enum MssqlValue {
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    Text(String),
+    Bytes(Vec<u8>),
+}
+
+/// The SQL to put in place of a value, and the parameter it needs (none
+/// for `NULL` and `DEFAULT`, which are written inline).
+#[derive(Debug, PartialEq)]
+pub(super) struct BoundValue {
+    pub sql: String,
+    pub value: Option<MssqlValue>,
+}
+
+/// Bind a grid value as parameter `@P{placeholder_idx}`. Blob wire-format
+/// strings are decoded to bytes; `__USE_DEFAULT__` becomes `DEFAULT` when
+/// `allow_default` is set. `NULL` is written inline so it converts to any
+/// column type, including `varbinary`.
+pub(super) fn bind_json_value(
+    value: serde_json::Value,
+    placeholder_idx: usize,
+    max_blob_size: u64,
+    allow_default: bool,
+) -> BoundValue {
+    let placeholder = format!("@P{}", placeholder_idx);
+    let value = match value {
+        serde_json::Value::Null => {
+            return BoundValue {
+                sql: "NULL".to_string(),
+                value: None,
+            }
+        }
+        serde_json::Value::Bool(b) => MssqlValue::Bool(b),
+        serde_json::Value::Number(n) => match n.as_i64() {
+            Some(i) => MssqlValue::Int(i),
+            None => match n.as_f64() {
+                Some(f) if n.is_f64() => MssqlValue::Float(f),
+                _ => MssqlValue::Text(n.to_string()),
+            },
+        },
+        serde_json::Value::String(s) => {
+            if allow_default && s == "__USE_DEFAULT__" {
+                return BoundValue {
+                    sql: "DEFAULT".to_string(),
+                    value: None,
+                };
+            }
+            match crate::drivers::common::decode_blob_wire_format(&s, max_blob_size) {
+                Some(bytes) => MssqlValue::Bytes(bytes),
+                None => MssqlValue::Text(s),
+            }
+        }
+        other => MssqlValue::Text(other.to_string()),
+    };
+    BoundValue {
+        sql: placeholder,
+        value: Some(value),
+    }
+}
+
+/// Column type for an `IDENTITY` column: SQL Server only allows integer
+/// and exact numeric types, so anything else falls back to `INT`.
+pub(super) fn identity_type(data_type: &str) -> String {
+    let upper = data_type.trim().to_uppercase();
+    let base = upper.split('(').next().unwrap_or("").trim();
+    if matches!(
+        base,
+        "TINYINT" | "SMALLINT" | "INT" | "BIGINT" | "DECIMAL" | "NUMERIC"
+    ) {
+        data_type.to_string()
+    } else {
+        "INT".to_string()
+    }
+}
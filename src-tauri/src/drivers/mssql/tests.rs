@@ -0,0 +1,182 @@
+use super::helpers::{
+    bind_json_value, build_paginated_query, identity_type, quote_ident, quote_literal,
+    referential_action, returns_result_set, strip_default_parens, BoundValue, MssqlValue,
+};
+use serde_json::json;
+
+mod quoting_tests {
+    use super::*;
+
+    #[test]
+    fn brackets_identifiers() {
+        assert_eq!(quote_ident("users"), "[users]");
+        assert_eq!(quote_ident("odd]name"), "[odd]]name]");
+    }
+
+    #[test]
+    fn unicode_literals() {
+        assert_eq!(quote_literal("O'Brien"), "N'O''Brien'");
+    }
+}
+
+mod default_value_tests {
+    use super::*;
+
+    #[test]
+    fn strips_wrapping_parens() {
+        assert_eq!(strip_default_parens("((0))"), "0");
+        assert_eq!(strip_default_parens("(getdate())"), "getdate()");
+        assert_eq!(strip_default_parens("('a(b')"), "'a(b'");
+    }
+
+    #[test]
+    fn keeps_parens_that_do_not_wrap() {
+        assert_eq!(strip_default_parens("(1)+(2)"), "(1)+(2)");
+        assert_eq!(strip_default_parens("newid()"), "newid()");
+    }
+
+    #[test]
+    fn normalizes_referential_actions() {
+        assert_eq!(referential_action("NO_ACTION"), "NO ACTION");
+        assert_eq!(referential_action("CASCADE"), "CASCADE");
+    }
+}
+
+mod pagination_tests {
+    use super::*;
+
+    #[test]
+    fn adds_order_by_when_missing() {
+        assert_eq!(
+            build_paginated_query("SELECT * FROM users;", 50, 2).unwrap(),
+            "SELECT * FROM users ORDER BY (SELECT NULL) OFFSET 50 ROWS FETCH NEXT 51 ROWS ONLY"
+        );
+    }
+
+    #[test]
+    fn keeps_top_level_order_by() {
+        assert_eq!(
+            build_paginated_query("SELECT * FROM users ORDER BY id", 10, 1).unwrap(),
+            "SELECT * FROM users ORDER BY id OFFSET 0 ROWS FETCH NEXT 11 ROWS ONLY"
+        );
+    }
+
+    #[test]
+    fn ignores_order_by_inside_subquery() {
+        let q = build_paginated_query(
+            "SELECT * FROM (SELECT ROW_NUMBER() OVER (ORDER BY id) AS n FROM t) AS x",
+            10,
+            1,
+        )
+        .unwrap();
+        assert!(q.ends_with("AS x ORDER BY (SELECT NULL) OFFSET 0 ROWS FETCH NEXT 11 ROWS ONLY"));
+    }
+
+    #[test]
+    fn skips_queries_that_limit_themselves() {
+        assert_eq!(
+            build_paginated_query("SELECT TOP 5 * FROM users", 10, 1),
+            None
+        );
+        assert_eq!(
+            build_paginated_query(
+                "SELECT * FROM users ORDER BY id OFFSET 5 ROWS FETCH NEXT 5 ROWS ONLY",
+                10,
+                1
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn exec_returns_rows() {
+        assert!(returns_result_set("EXEC dbo.report @year = 2024"));
+        assert!(returns_result_set("-- run\nEXECUTE sp_who"));
+        assert!(returns_result_set("SELECT 1"));
+        assert!(!returns_result_set("UPDATE t SET a = 1"));
+    }
+}
+
+mod binding_tests {
+    use super::*;
+
+    #[test]
+    fn binds_scalars_as_parameters() {
+        assert_eq!(
+            bind_json_value(json!(42), 3, 0, false),
+            BoundValue {
+                sql: "@P3".to_string(),
+                value: Some(MssqlValue::Int(42)),
+            }
+        );
+        assert_eq!(
+            bind_json_value(json!(1.5), 1, 0, false).value,
+            Some(MssqlValue::Float(1.5))
+        );
+        assert_eq!(
+            bind_json_value(json!(true), 1, 0, false).value,
+            Some(MssqlValue::Bool(true))
+        );
+        assert_eq!(
+            bind_json_value(json!("abc"), 1, 0, false).value,
+            Some(MssqlValue::Text("abc".to_string()))
+        );
+        assert_eq!(
+            bind_json_value(json!({"a": 1}), 1, 0, false).value,
+            Some(MssqlValue::Text("{\"a\":1}".to_string()))
+        );
+    }
+
+    #[test]
+    fn writes_null_and_default_inline() {
+        let null = bind_json_value(json!(null), 1, 0, false);
+        assert_eq!(null.sql, "NULL");
+        assert_eq!(null.value, None);
+
+        let default = bind_json_value(json!("__USE_DEFAULT__"), 1, 0, true);
+        assert_eq!(default.sql, "DEFAULT");
+        assert_eq!(default.value, None);
+
+        let literal = bind_json_value(json!("__USE_DEFAULT__"), 1, 0, false);
+        assert_eq!(
+            literal.value,
+            Some(MssqlValue::Text("__USE_DEFAULT__".to_string()))
+        );
+    }
+
+    #[test]
+    fn decodes_blob_wire_format() {
+        let bound = bind_json_value(
+            json!("BLOB:3:application/octet-stream:AQID"),
+            1,
+            1024,
+            false,
+        );
+        assert_eq!(bound.value, Some(MssqlValue::Bytes(vec![1, 2, 3])));
+    }
+
+    #[test]
+    fn insert_puts_the_output_clause_before_values() {
+        let row = std::collections::HashMap::from([("name".to_string(), json!("a"))]);
+        let (sql, values) = super::super::build_insert(
+            "users",
+            &["name".to_string()],
+            vec![row],
+            "dbo",
+            0,
+            " OUTPUT INSERTED.*",
+        );
+        assert_eq!(
+            sql,
+            "INSERT INTO [dbo].[users] ([name]) OUTPUT INSERTED.* VALUES (@P1)"
+        );
+        assert_eq!(values, vec![MssqlValue::Text("a".to_string())]);
+    }
+
+    #[test]
+    fn identity_falls_back_to_int() {
+        assert_eq!(identity_type("BIGINT"), "BIGINT");
+        assert_eq!(identity_type("decimal(10,0)"), "decimal(10,0)");
+        assert_eq!(identity_type("NVARCHAR(50)"), "INT");
+    }
+}
@@ -0,0 +1,327 @@
+use crate::models::DataTypeInfo;
+
+/// Returns the list of data types supported by SQL Server.
+pub fn get_data_types() -> Vec<DataTypeInfo> {
+    vec![
+        // Numeric types
+        DataTypeInfo {
+            name: "TINYINT".to_string(),
+            category: "numeric".to_string(),
+            requires_length: false,
+            requires_precision: false,
+            default_length: None,
+            supports_auto_increment: true,
+            requires_extension: None,
+        },
+        DataTypeInfo {
+            name: "SMALLINT".to_string(),
+            category: "numeric".to_string(),
+            requires_length: false,
+            requires_precision: false,
+            default_length: None,
+            supports_auto_increment: true,
+            requires_extension: None,
+        },
+        DataTypeInfo {
+            name: "INT".to_string(),
+            category: "numeric".to_string(),
+            requires_length: false,
+            requires_precision: false,
+            default_length: None,
+            supports_auto_increment: true,
+            requires_extension: None,
+        },
+        DataTypeInfo {
+            name: "BIGINT".to_string(),
+            category: "numeric".to_string(),
+            requires_length: false,
+            requires_precision: false,
+            default_length: None,
+            supports_auto_increment: true,
+            requires_extension: None,
+        },
+        DataTypeInfo {
+            name: "DECIMAL".to_string(),
+            category: "numeric".to_string(),
+            requires_length: false,
+            requires_precision: true,
+            default_length: Some("18,2".to_string()),
+            supports_auto_increment: false,
+            requires_extension: None,
+        },
+        DataTypeInfo {
+            name: "NUMERIC".to_string(),
+            category: "numeric".to_string(),
+            requires_length: false,
+            requires_precision: true,
+            default_length: Some("18,2".to_string()),
+            supports_auto_increment: false,
+            requires_extension: None,
+        },
+        DataTypeInfo {
+            name: "REAL".to_string(),
+            category: "numeric".to_string(),
+            requires_length: false,
+            requires_precision: false,
+            default_length: None,
+            supports_auto_increment: false,
+            requires_extension: None,
+        },
+        DataTypeInfo {
+            name: "FLOAT".to_string(),
+            category: "numeric".to_string(),
+            requires_length: false,
+            requires_precision: false,
+            default_length: None,
+            supports_auto_increment: false,
+            requires_extension: None,
+        },
+        DataTypeInfo {
+            name: "SMALLMONEY".to_string(),
+            category: "numeric".to_string(),
+            requires_length: false,
+            requires_precision: false,
+            default_length: None,
+            supports_auto_increment: false,
+            requires_extension: None,
+        },
+        DataTypeInfo {
+            name: "MONEY".to_string(),
+            category: "numeric".to_string(),
+            requires_length: false,
+            requires_precision: false,
+            default_length: None,
+            supports_auto_increment: false,
+            requires_extension: None,
+        },
+        // String types
+        DataTypeInfo {
+            name: "CHAR".to_string(),
+            category: "string".to_string(),
+            requires_length: true,
+            requires_precision: false,
+            default_length: Some("10".to_string()),
+            supports_auto_increment: false,
+            requires_extension: None,
+        },
+        DataTypeInfo {
+            name: "VARCHAR".to_string(),
+            category: "string".to_string(),
+            requires_length: true,
+            requires_precision: false,
+            default_length: Some("255".to_string()),
+            supports_auto_increment: false,
+            requires_extension: None,
+        },
+        DataTypeInfo {
+            name: "NCHAR".to_string(),
+            category: "string".to_string(),
+            requires_length: true,
+            requires_precision: false,
+            default_length: Some("10".to_string()),
+            supports_auto_increment: false,
+            requires_extension: None,
+        },
+        DataTypeInfo {
+            name: "NVARCHAR".to_string(),
+            category: "string".to_string(),
+            requires_length: true,
+            requires_precision: false,
+            default_length: Some("255".to_string()),
+            supports_auto_increment: false,
+            requires_extension: None,
+        },
+        DataTypeInfo {
+            name: "VARCHAR(MAX)".to_string(),
+            category: "string".to_string(),
+            requires_length: false,
+            requires_precision: false,
+            default_length: None,
+            supports_auto_increment: false,
+            requires_extension: None,
+        },
+        DataTypeInfo {
+            name: "NVARCHAR(MAX)".to_string(),
+            category: "string".to_string(),
+            requires_length: false,
+            requires_precision: false,
+            default_length: None,
+            supports_auto_increment: false,
+            requires_extension: None,
+        },
+        DataTypeInfo {
+            name: "TEXT".to_string(),
+            category: "string".to_string(),
+            requires_length: false,
+            requires_precision: false,
+            default_length: None,
+            supports_auto_increment: false,
+            requires_extension: None,
+        },
+        DataTypeInfo {
+            name: "NTEXT".to_string(),
+            category: "string".to_string(),
+            requires_length: false,
+            requires_precision: false,
+            default_length: None,
+            supports_auto_increment: false,
+            requires_extension: None,
+        },
+        // Date and time types
+        DataTypeInfo {
+            name: "DATE".to_string(),
+            category: "date".to_string(),
+            requires_length: false,
+            requires_precision: false,
+            default_length: None,
+            supports_auto_increment: false,
+            requires_extension: None,
+        },
+        DataTypeInfo {
+            name: "TIME".to_string(),
+            category: "date".to_string(),
+            requires_length: false,
+            requires_precision: false,
+            default_length: None,
+            supports_auto_increment: false,
+            requires_extension: None,
+        },
+        DataTypeInfo {
+            name: "DATETIME".to_string(),
+            category: "date".to_string(),
+            requires_length: false,
+            requires_precision: false,
+            default_length: None,
+            supports_auto_increment: false,
+            requires_extension: None,
+        },
+        DataTypeInfo {
+            name: "DATETIME2".to_string(),
+            category: "date".to_string(),
+            requires_length: false,
+            requires_precision: false,
+            default_length: None,
+            supports_auto_increment: false,
+            requires_extension: None,
+        },
+        DataTypeInfo {
+            name: "SMALLDATETIME".to_string(),
+            category: "date".to_string(),
+            requires_length: false,
+            requires_precision: false,
+            default_length: None,
+            supports_auto_increment: false,
+            requires_extension: None,
+        },
+        DataTypeInfo {
+            name: "DATETIMEOFFSET".to_string(),
+            category: "date".to_string(),
+            requires_length: false,
+            requires_precision: false,
+            default_length: None,
+            supports_auto_increment: false,
+            requires_extension: None,
+        },
+        // Binary types
+        DataTypeInfo {
+            name: "BINARY".to_string(),
+            category: "binary".to_string(),
+            requires_length: true,
+            requires_precision: false,
+            default_length: Some("16".to_string()),
+            supports_auto_increment: false,
+            requires_extension: None,
+        },
+        DataTypeInfo {
+            name: "VARBINARY".to_string(),
+            category: "binary".to_string(),
+            requires_length: true,
+            requires_precision: false,
+            default_length: Some("255".to_string()),
+            supports_auto_increment: false,
+            requires_extension: None,
+        },
+        DataTypeInfo {
+            name: "VARBINARY(MAX)".to_string(),
+            category: "binary".to_string(),
+            requires_length: false,
+            requires_precision: false,
+            default_length: None,
+            supports_auto_increment: false,
+            requires_extension: None,
+        },
+        DataTypeInfo {
+            name: "IMAGE".to_string(),
+            category: "binary".to_string(),
+            requires_length: false,
+            requires_precision: false,
+            default_length: None,
+            supports_auto_increment: false,
+            requires_extension: None,
+        },
+        // Other types
+        DataTypeInfo {
+            name: "BIT".to_string(),
+            category: "boolean".to_string(),
+            requires_length: false,
+            requires_precision: false,
+            default_length: None,
+            supports_auto_increment: false,
+            requires_extension: None,
+        },
+        DataTypeInfo {
+            name: "UNIQUEIDENTIFIER".to_string(),
+            category: "identifier".to_string(),
+            requires_length: false,
+            requires_precision: false,
+            default_length: None,
+            supports_auto_increment: false,
+            requires_extension: None,
+        },
+        DataTypeInfo {
+            name: "XML".to_string(),
+            category: "xml".to_string(),
+            requires_length: false,
+            requires_precision: false,
+            default_length: None,
+            supports_auto_increment: false,
+            requires_extension: None,
+        },
+        DataTypeInfo {
+            name: "SQL_VARIANT".to_string(),
+            category: "other".to_string(),
+            requires_length: false,
+            requires_precision: false,
+            default_length: None,
+            supports_auto_increment: false,
+            requires_extension: None,
+        },
+        DataTypeInfo {
+            name: "HIERARCHYID".to_string(),
+            category: "other".to_string(),
+            requires_length: false,
+            requires_precision: false,
+            default_length: None,
+            supports_auto_increment: false,
+            requires_extension: None,
+        },
+        DataTypeInfo {
+            name: "GEOGRAPHY".to_string(),
+            category: "spatial".to_string(),
+            requires_length: false,
+            requires_precision: false,
+            default_length: None,
+            supports_auto_increment: false,
+            requires_extension: None,
+        },
+        DataTypeInfo {
+            name: "GEOMETRY".to_string(),
+            category: "spatial".to_string(),
+            requires_length: false,
+            requires_precision: false,
+            default_length: None,
+            supports_auto_increment: false,
+            requires_extension: None,
+        },
+    ]
+}
@@ -0,0 +1,86 @@
+//! Compatibility mode for Vitess and PlanetScale behind the MySQL driver.
+//!
+//! vtgate answers to the MySQL protocol but only emulates part of
+//! `information_schema`, manages no users, exposes no `performance_schema`
+//! and has no foreign keys on sharded keyspaces. Schema browsing falls back
+//! to empty results where a catalog query fails on Vitess, and features it
+//! cannot offer fail with a clear message instead of the server's error.
+
+use super::helpers::mysql_row_str;
+use crate::models::{ConnectionParams, ServerValue};
+use crate::server_info::{ServerFlavor, ServerInfo};
+use sqlx::MySqlPool;
+
+/// vtgate session variables shown next to the MySQL ones; the `boost`
+/// ones only exist on PlanetScale.
+const VITESS_VARIABLES: &[&str] = &[
+    "workload",
+    "ddl_strategy",
+    "transaction_mode",
+    "planner_version",
+    "read_after_write_consistency",
+    "enable_system_settings",
+    "boost_cached_queries",
+];
+
+pub(super) fn is_vitess_server(info: Option<&ServerInfo>) -> bool {
+    info.is_some_and(|info| info.flavor == ServerFlavor::Vitess)
+}
+
+/// Whether the server behind `params` was detected as Vitess.
+pub(super) fn is_vitess(params: &ConnectionParams) -> bool {
+    is_vitess_server(crate::server_info::for_params(params).as_ref())
+}
+
+/// On Vitess, a failed catalog query of `what` yields nothing rather than
+/// an error, so the rest of the schema can still be browsed.
+pub(super) fn fallback<T: Default>(
+    vitess: bool,
+    what: &str,
+    result: Result<T, String>,
+) -> Result<T, String> {
+    match result {
+        Err(e) if vitess => {
+            log::debug!("MySQL: {} unavailable on Vitess ({}), skipping", what, e);
+            Ok(T::default())
+        }
+        result => result,
+    }
+}
+
+pub(super) fn or_empty<T: Default>(
+    params: &ConnectionParams,
+    what: &str,
+    result: Result<T, String>,
+) -> Result<T, String> {
+    fallback(result.is_err() && is_vitess(params), what, result)
+}
+
+/// Fail with a clear message for what Vitess cannot offer, rather than with
+/// the error of the query that would have been run.
+pub(super) fn ensure_supported(params: &ConnectionParams, feature: &str) -> Result<(), String> {
+    if is_vitess(params) {
+        return Err(format!("{} is not available on Vitess", feature));
+    }
+    Ok(())
+}
+
+/// The [`VITESS_VARIABLES`] this vtgate knows, each read on its own since
+/// an unknown one fails the whole `SELECT`.
+pub(super) async fn vitess_variables(pool: &MySqlPool) -> Vec<ServerValue> {
+    let mut values = Vec::new();
+    for name in VITESS_VARIABLES {
+        match sqlx::query(&format!("SELECT @@{}", name))
+            .fetch_one(pool)
+            .await
+        {
+            Ok(row) => {
+                let mut value = ServerValue::new(*name, mysql_row_str(&row, 0));
+                value.category = Some("vitess".to_string());
+                values.push(value);
+            }
+            Err(e) => log::debug!("MySQL: @@{} unavailable ({})", name, e),
+        }
+    }
+    values
+}
@@ -1,9 +1,10 @@
 use super::helpers::{mysql_row_str, mysql_row_str_opt};
 use crate::models::{ConnectionParams, ExplainNode, ExplainPlan};
 use crate::pool_manager::get_mysql_pool;
+use crate::server_info::{ServerFlavor, ServerInfo};
 use sqlx::{Column, Row};
 
-/// Server capabilities detected via `SELECT VERSION()`.
+/// EXPLAIN variants the server supports.
 struct MysqlCapabilities {
     /// EXPLAIN FORMAT=JSON (MySQL 5.6+ / MariaDB 10.1+)
     supports_json_format: bool,
@@ -13,31 +14,18 @@ struct MysqlCapabilities {
     supports_analyze_format: bool,
 }
 
-fn parse_mysql_version(version_str: &str) -> MysqlCapabilities {
-    let is_mariadb = version_str.to_lowercase().contains("mariadb");
-
-    // Extract "5.5.24" from "5.5.24-55-log" or "10.5.22-MariaDB"
-    let version_part = version_str.split('-').next().unwrap_or("");
-    let parts: Vec<u32> = version_part
-        .split('.')
-        .filter_map(|s| s.parse().ok())
-        .collect();
-    let ver = (
-        parts.first().copied().unwrap_or(0),
-        parts.get(1).copied().unwrap_or(0),
-        parts.get(2).copied().unwrap_or(0),
-    );
-
-    if is_mariadb {
+fn explain_capabilities(info: &ServerInfo) -> MysqlCapabilities {
+    let v = info.version;
+    if info.flavor == ServerFlavor::Mariadb {
         MysqlCapabilities {
-            supports_json_format: ver >= (10, 1, 0),
+            supports_json_format: v.at_least(10, 1, 0),
             supports_explain_analyze: false,
-            supports_analyze_format: ver >= (10, 1, 0),
+            supports_analyze_format: v.at_least(10, 1, 0),
         }
     } else {
         MysqlCapabilities {
-            supports_json_format: ver >= (5, 6, 0),
-            supports_explain_analyze: ver >= (8, 0, 18),
+            supports_json_format: v.at_least(5, 6, 0),
+            supports_explain_analyze: v.at_least(8, 0, 18),
             supports_analyze_format: false,
         }
     }
@@ -61,17 +49,22 @@ pub async fn explain_query(
         get_mysql_pool(params).await?
     };
 
-    // Detect server version to skip unsupported EXPLAIN variants
-    let caps = {
-        let mut vc = pool.acquire().await.map_err(|e| e.to_string())?;
-        let ver_row = sqlx::query("SELECT VERSION()")
-            .fetch_one(&mut *vc)
-            .await
-            .ok();
-        let ver_str: String = ver_row.and_then(|r| r.try_get(0).ok()).unwrap_or_default();
-        log::debug!("MySQL/MariaDB version: {}", ver_str);
-        parse_mysql_version(&ver_str)
+    // Skip EXPLAIN variants the server does not have. The version is read
+    // here only when it was not detected when the connection opened.
+    let info = match crate::server_info::for_params(params) {
+        Some(info) => info,
+        None => {
+            let mut vc = pool.acquire().await.map_err(|e| e.to_string())?;
+            let ver_row = sqlx::query("SELECT VERSION()")
+                .fetch_one(&mut *vc)
+                .await
+                .ok();
+            let ver_str: String = ver_row.and_then(|r| r.try_get(0).ok()).unwrap_or_default();
+            crate::server_info::detect("mysql", &ver_str)
+        }
     };
+    log::debug!("MySQL/MariaDB version: {}", info.version_string);
+    let caps = explain_capabilities(&info);
 
     // EXPLAIN ANALYZE — MySQL 8.0.18+ text tree with estimated + actual data
     if analyze && caps.supports_explain_analyze {
@@ -9,6 +9,13 @@ mod tests;
 
 use sqlx::Row;
 
+/// All values of `row`, in column order.
+pub fn row_to_json(row: &sqlx::mysql::MySqlRow) -> Vec<serde_json::Value> {
+    (0..row.len())
+        .map(|i| extract_value(row, i, None))
+        .collect()
+}
+
 pub fn extract_value(
     row: &sqlx::mysql::MySqlRow,
     index: usize,
@@ -0,0 +1,95 @@
+use super::compat;
+use super::helpers::mysql_row_str_opt;
+use crate::models::{ConnectionParams, LockWait};
+use crate::pool_manager::get_mysql_pool;
+use sqlx::Row;
+
+/// MySQL 8.0+: lock waits from `performance_schema.data_lock_waits`.
+const DATA_LOCK_WAITS_QUERY: &str = r#"
+    SELECT
+        CAST(rt.PROCESSLIST_ID AS SIGNED) AS blocked_pid,
+        rt.PROCESSLIST_USER AS blocked_user,
+        rt.PROCESSLIST_INFO AS blocked_query,
+        CAST(rt.PROCESSLIST_TIME AS DOUBLE) AS wait_seconds,
+        CAST(bt.PROCESSLIST_ID AS SIGNED) AS blocking_pid,
+        bt.PROCESSLIST_USER AS blocking_user,
+        bt.PROCESSLIST_INFO AS blocking_query,
+        bt.PROCESSLIST_COMMAND AS blocking_state,
+        rl.LOCK_MODE AS lock_type,
+        CONCAT_WS('.', rl.OBJECT_SCHEMA, rl.OBJECT_NAME) AS relation
+    FROM performance_schema.data_lock_waits w
+    JOIN performance_schema.data_locks rl
+        ON rl.ENGINE_LOCK_ID = w.REQUESTING_ENGINE_LOCK_ID
+    JOIN performance_schema.threads rt ON rt.THREAD_ID = w.REQUESTING_THREAD_ID
+    JOIN performance_schema.threads bt ON bt.THREAD_ID = w.BLOCKING_THREAD_ID
+    ORDER BY blocking_pid, wait_seconds DESC
+"#;
+
+/// MySQL 5.7 / MariaDB: lock waits from the InnoDB information_schema tables.
+const INNODB_LOCK_WAITS_QUERY: &str = r#"
+    SELECT
+        CAST(r.trx_mysql_thread_id AS SIGNED) AS blocked_pid,
+        rp.USER AS blocked_user,
+        r.trx_query AS blocked_query,
+        CAST(TIMESTAMPDIFF(SECOND, r.trx_wait_started, NOW()) AS DOUBLE) AS wait_seconds,
+        CAST(b.trx_mysql_thread_id AS SIGNED) AS blocking_pid,
+        bp.USER AS blocking_user,
+        b.trx_query AS blocking_query,
+        bp.COMMAND AS blocking_state,
+        l.lock_mode AS lock_type,
+        l.lock_table AS relation
+    FROM information_schema.innodb_lock_waits w
+    JOIN information_schema.innodb_trx r ON r.trx_id = w.requesting_trx_id
+    JOIN information_schema.innodb_trx b ON b.trx_id = w.blocking_trx_id
+    LEFT JOIN information_schema.innodb_locks l ON l.lock_id = w.requested_lock_id
+    LEFT JOIN information_schema.processlist rp ON rp.ID = r.trx_mysql_thread_id
+    LEFT JOIN information_schema.processlist bp ON bp.ID = b.trx_mysql_thread_id
+    ORDER BY blocking_pid, wait_seconds DESC
+"#;
+
+fn to_lock_wait(row: &sqlx::mysql::MySqlRow) -> LockWait {
+    LockWait {
+        blocked_pid: row.try_get::<i64, _>(0).unwrap_or_default(),
+        blocked_user: mysql_row_str_opt(row, 1),
+        blocked_query: mysql_row_str_opt(row, 2),
+        wait_seconds: row.try_get::<Option<f64>, _>(3).unwrap_or_default(),
+        blocking_pid: row.try_get::<i64, _>(4).unwrap_or_default(),
+        blocking_user: mysql_row_str_opt(row, 5),
+        blocking_query: mysql_row_str_opt(row, 6),
+        blocking_state: mysql_row_str_opt(row, 7),
+        lock_type: mysql_row_str_opt(row, 8),
+        relation: mysql_row_str_opt(row, 9),
+    }
+}
+
+pub async fn get_lock_waits(params: &ConnectionParams) -> Result<Vec<LockWait>, String> {
+    compat::ensure_supported(params, "Lock inspection")?;
+    let pool = get_mysql_pool(params).await?;
+    let rows = match sqlx::query(DATA_LOCK_WAITS_QUERY).fetch_all(&pool).await {
+        Ok(rows) => rows,
+        Err(e) => {
+            log::debug!(
+                "MySQL: data_lock_waits unavailable ({}), using information_schema",
+                e
+            );
+            sqlx::query(INNODB_LOCK_WAITS_QUERY)
+                .fetch_all(&pool)
+                .await
+                .map_err(|e| format!("Failed to read lock waits: {}", e))?
+        }
+    };
+    Ok(rows.iter().map(to_lock_wait).collect())
+}
+
+pub async fn kill_session(params: &ConnectionParams, pid: i64) -> Result<(), String> {
+    if pid <= 0 {
+        return Err(format!("Invalid thread id: {}", pid));
+    }
+    let pool = get_mysql_pool(params).await?;
+    // KILL cannot be prepared; `pid` is an integer, so formatting is safe.
+    sqlx::raw_sql(&format!("KILL {}", pid))
+        .execute(&pool)
+        .await
+        .map_err(|e| format!("Failed to kill session {}: {}", pid, e))?;
+    Ok(())
+}
@@ -2,17 +2,23 @@ pub mod export;
 pub mod extract;
 pub mod types;
 
+mod compat;
 mod explain;
 mod helpers;
+mod locks;
+mod privileges;
+mod signature;
+mod status;
+mod table_stats;
 
 #[cfg(test)]
 mod tests;
 
 use crate::models::{
-    ConnectionParams, ForeignKey, Index, Pagination, QueryResult, RoutineInfo, RoutineParameter,
-    TableColumn, TableInfo, TriggerInfo, ViewInfo,
+    ConnectionParams, ForeignKey, Index, InsertedRecord, Pagination, QueryResult, RoutineInfo,
+    RoutineParameter, TableColumn, TableInfo, TriggerInfo, ViewInfo,
 };
-use crate::pool_manager::get_mysql_pool;
+use crate::pool_manager::{get_mysql_pool, TransactionConnection};
 pub use explain::explain_query;
 use extract::extract_value;
 use helpers::{
@@ -33,6 +39,15 @@ pub async fn get_databases(params: &ConnectionParams) -> Result<Vec<String>, Str
     Ok(rows.iter().map(|r| mysql_row_str(r, 0)).collect())
 }
 
+pub async fn get_server_version(params: &ConnectionParams) -> Result<String, String> {
+    let pool = get_mysql_pool(params).await?;
+    let row = sqlx::query("SELECT VERSION()")
+        .fetch_one(&pool)
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(mysql_row_str(&row, 0))
+}
+
 pub async fn get_tables(
     params: &ConnectionParams,
     schema: Option<&str>,
@@ -145,7 +160,8 @@ pub async fn get_foreign_keys(
         .bind(table_name)
         .fetch_all(&pool)
         .await
-        .map_err(|e| e.to_string())?;
+        .map_err(|e| e.to_string());
+    let rows = compat::or_empty(params, "foreign keys", rows)?;
 
     Ok(rows
         .iter()
@@ -255,7 +271,8 @@ pub async fn get_all_foreign_keys_batch(
         .bind(db_name)
         .fetch_all(&pool)
         .await
-        .map_err(|e| e.to_string())?;
+        .map_err(|e| e.to_string());
+    let rows = compat::or_empty(params, "foreign keys", rows)?;
 
     let mut result: HashMap<String, Vec<ForeignKey>> = HashMap::new();
 
@@ -277,6 +294,55 @@ pub async fn get_all_foreign_keys_batch(
     Ok(result)
 }
 
+// Batch function: Get all indexes for all tables in one query
+pub async fn get_all_indexes_batch(
+    params: &ConnectionParams,
+    schema: Option<&str>,
+) -> Result<std::collections::HashMap<String, Vec<Index>>, String> {
+    use std::collections::HashMap;
+    let db_name = schema.unwrap_or_else(|| params.database.primary());
+    let pool = get_mysql_pool(params).await?;
+
+    let query = r#"
+        SELECT
+            TABLE_NAME,
+            INDEX_NAME,
+            COLUMN_NAME,
+            NON_UNIQUE,
+            SEQ_IN_INDEX
+        FROM information_schema.STATISTICS
+        WHERE TABLE_SCHEMA = ?
+        ORDER BY TABLE_NAME, INDEX_NAME, SEQ_IN_INDEX
+    "#;
+
+    let rows = sqlx::query(query)
+        .bind(db_name)
+        .fetch_all(&pool)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let mut result: HashMap<String, Vec<Index>> = HashMap::new();
+
+    for row in &rows {
+        let index_name = mysql_row_str(row, 1);
+        let non_unique: i64 = row.try_get(3).unwrap_or(1);
+        let index = Index {
+            name: index_name.clone(),
+            column_name: mysql_row_str(row, 2),
+            is_unique: non_unique == 0,
+            is_primary: index_name == "PRIMARY",
+            seq_in_index: row.try_get::<i64, _>(4).unwrap_or(0) as i32,
+        };
+
+        result
+            .entry(mysql_row_str(row, 0))
+            .or_insert_with(Vec::new)
+            .push(index);
+    }
+
+    Ok(result)
+}
+
 pub async fn get_indexes(
     params: &ConnectionParams,
     table_name: &str,
@@ -399,25 +465,23 @@ pub async fn delete_record(
     pk_col: &str,
     pk_val: serde_json::Value,
 ) -> Result<u64, String> {
-    let pool = get_mysql_pool(params).await?;
+    let sql = format!("DELETE FROM `{}` WHERE `{}` = ?", table, pk_col);
 
-    let query = format!("DELETE FROM `{}` WHERE `{}` = ?", table, pk_col);
-
-    let result = match pk_val {
+    let query = match pk_val {
         serde_json::Value::Number(n) => {
             if n.is_i64() {
-                sqlx::query(&query).bind(n.as_i64()).execute(&pool).await
+                sqlx::query(&sql).bind(n.as_i64())
             } else if n.is_f64() {
-                sqlx::query(&query).bind(n.as_f64()).execute(&pool).await
+                sqlx::query(&sql).bind(n.as_f64())
             } else {
-                sqlx::query(&query).bind(n.to_string()).execute(&pool).await
+                sqlx::query(&sql).bind(n.to_string())
             }
         }
-        serde_json::Value::String(s) => sqlx::query(&query).bind(s).execute(&pool).await,
+        serde_json::Value::String(s) => sqlx::query(&sql).bind(s),
         _ => return Err("Unsupported PK type".into()),
     };
 
-    result.map(|r| r.rows_affected()).map_err(|e| e.to_string())
+    execute_write(params, query).await
 }
 
 pub async fn update_record(
@@ -429,8 +493,6 @@ pub async fn update_record(
     new_val: serde_json::Value,
     max_blob_size: u64,
 ) -> Result<u64, String> {
-    let pool = get_mysql_pool(params).await?;
-
     let mut qb = sqlx::QueryBuilder::new(format!("UPDATE `{}` SET `{}` = ", table, col_name));
 
     match new_val {
@@ -494,9 +556,7 @@ pub async fn update_record(
         _ => return Err("Unsupported PK type".into()),
     }
 
-    let query = qb.build();
-    let result = query.execute(&pool).await.map_err(|e| e.to_string())?;
-    Ok(result.rows_affected())
+    execute_write(params, qb.build()).await
 }
 
 pub async fn insert_record(
@@ -505,77 +565,242 @@ pub async fn insert_record(
     data: std::collections::HashMap<String, serde_json::Value>,
     max_blob_size: u64,
 ) -> Result<u64, String> {
-    let pool = get_mysql_pool(params).await?;
+    // Allow empty inserts for auto-generated values (e.g., auto-increment PKs)
+    if data.is_empty() {
+        let query = format!("INSERT INTO `{}` () VALUES ()", table);
+        return execute_write(params, sqlx::query(&query)).await;
+    }
 
-    let mut cols = Vec::new();
-    let mut vals = Vec::new();
+    let columns: Vec<String> = data.keys().cloned().collect();
+    insert_rows(params, table, &columns, vec![data], max_blob_size).await
+}
 
-    for (k, v) in data {
-        cols.push(format!("`{}`", k));
-        vals.push(v);
-    }
+/// Insert one row and read it back. MySQL has no `RETURNING`, so the row
+/// is refetched by `LAST_INSERT_ID()` when the table has an auto-increment
+/// column, or else by the primary-key values that were inserted.
+pub async fn insert_record_returning(
+    params: &ConnectionParams,
+    table: &str,
+    data: std::collections::HashMap<String, serde_json::Value>,
+    max_blob_size: u64,
+) -> Result<InsertedRecord, String> {
+    let table_columns = match get_columns(params, table, None).await {
+        Ok(cols) => cols,
+        Err(err) => {
+            log::debug!(
+                "Could not load MySQL column metadata for {}: {}",
+                table,
+                err
+            );
+            Vec::new()
+        }
+    };
+    let auto_increment = table_columns.iter().find(|c| c.is_auto_increment);
+    let pk_values: Vec<(String, serde_json::Value)> = table_columns
+        .iter()
+        .filter(|c| c.is_pk)
+        .filter_map(|c| data.get(&c.name).map(|v| (c.name.clone(), v.clone())))
+        .collect();
+    let pk_count = table_columns.iter().filter(|c| c.is_pk).count();
 
-    // Allow empty inserts for auto-generated values (e.g., auto-increment PKs)
-    let mut qb = if cols.is_empty() {
-        sqlx::QueryBuilder::new(format!("INSERT INTO `{}` () VALUES ()", table))
+    let result = if data.is_empty() {
+        let query = format!("INSERT INTO `{}` () VALUES ()", table);
+        run_write(params, sqlx::query(&query)).await?
     } else {
-        let mut qb = sqlx::QueryBuilder::new(format!(
-            "INSERT INTO `{}` ({}) VALUES (",
-            table,
-            cols.join(", ")
-        ));
+        let columns: Vec<String> = data.keys().cloned().collect();
+        let mut qb = build_insert(table, &columns, vec![data], max_blob_size)?;
+        run_write(params, qb.build()).await?
+    };
 
-        let mut separated = qb.separated(", ");
-        for val in vals {
-            match val {
-                serde_json::Value::Number(n) => {
-                    if n.is_i64() {
-                        separated.push_bind(n.as_i64());
-                    } else {
-                        separated.push_bind(n.as_f64());
-                    }
-                }
-                serde_json::Value::String(s) => {
-                    if let Some(bytes) =
-                        crate::drivers::common::decode_blob_wire_format(&s, max_blob_size)
-                    {
-                        // Blob wire format: decode to raw bytes so the DB stores binary data,
-                        // not the internal wire format string.
-                        separated.push_bind(bytes);
-                    } else if is_raw_sql_function(&s) {
-                        // If it's a raw SQL function (e.g., ST_GeomFromText('POINT(1 2)', 4326))
-                        // insert it directly without parameter binding
-                        separated.push_unseparated(&s);
-                    } else if is_wkt_geometry(&s) {
-                        // If it's WKT geometry format, wrap with ST_GeomFromText
-                        separated.push_unseparated("ST_GeomFromText(");
-                        separated.push_bind_unseparated(s);
-                        separated.push_unseparated(")");
-                    } else {
-                        separated.push_bind(s);
-                    }
-                }
-                serde_json::Value::Bool(b) => {
-                    separated.push_bind(b);
-                }
-                serde_json::Value::Null => {
-                    separated.push("NULL");
-                }
-                serde_json::Value::Object(_) | serde_json::Value::Array(_) => {
-                    let json_str = serde_json::to_string(&val).map_err(|e| e.to_string())?;
-                    separated.push_unseparated("CAST(");
-                    separated.push_bind_unseparated(json_str);
-                    separated.push_unseparated(" AS JSON)");
-                }
+    let key = match auto_increment {
+        Some(col) if result.last_insert_id() > 0 => {
+            vec![(col.name.clone(), serde_json::json!(result.last_insert_id()))]
+        }
+        _ if pk_count > 0 && pk_values.len() == pk_count => pk_values,
+        _ => Vec::new(),
+    };
+    let row = if key.is_empty() {
+        None
+    } else {
+        match fetch_inserted_row(params, table, key, max_blob_size).await {
+            Ok(row) => row,
+            Err(err) => {
+                log::warn!(
+                    "Could not read back the row inserted into {}: {}",
+                    table,
+                    err
+                );
+                None
             }
         }
-        separated.push_unseparated(")");
-        qb
     };
 
-    let query = qb.build();
-    let result = query.execute(&pool).await.map_err(|e| e.to_string())?;
-    Ok(result.rows_affected())
+    Ok(InsertedRecord {
+        affected_rows: result.rows_affected(),
+        row,
+    })
+}
+
+async fn fetch_inserted_row(
+    params: &ConnectionParams,
+    table: &str,
+    key: Vec<(String, serde_json::Value)>,
+    max_blob_size: u64,
+) -> Result<Option<std::collections::HashMap<String, serde_json::Value>>, String> {
+    let mut qb = sqlx::QueryBuilder::new(format!("SELECT * FROM `{}` WHERE ", table));
+    for (idx, (col, val)) in key.into_iter().enumerate() {
+        if idx > 0 {
+            qb.push(" AND ");
+        }
+        qb.push(format!("`{}` = ", col));
+        push_insert_value(&mut qb, val, max_blob_size)?;
+    }
+    qb.push(" LIMIT 1");
+
+    let row = fetch_write(params, qb.build()).await?;
+    Ok(row.map(|row| {
+        row.columns()
+            .iter()
+            .enumerate()
+            .map(|(i, col)| (col.name().to_string(), extract_value(&row, i, None)))
+            .collect()
+    }))
+}
+
+/// Insert `rows` with a single multi-row `INSERT ... VALUES (...), (...)`.
+/// Cells missing from a row are written as `NULL`.
+pub async fn insert_rows(
+    params: &ConnectionParams,
+    table: &str,
+    columns: &[String],
+    rows: Vec<std::collections::HashMap<String, serde_json::Value>>,
+    max_blob_size: u64,
+) -> Result<u64, String> {
+    let mut qb = build_insert(table, columns, rows, max_blob_size)?;
+    execute_write(params, qb.build()).await
+}
+
+fn build_insert(
+    table: &str,
+    columns: &[String],
+    rows: Vec<std::collections::HashMap<String, serde_json::Value>>,
+    max_blob_size: u64,
+) -> Result<sqlx::QueryBuilder<'static, sqlx::MySql>, String> {
+    let cols: Vec<String> = columns.iter().map(|c| format!("`{}`", c)).collect();
+    let mut qb = sqlx::QueryBuilder::new(format!(
+        "INSERT INTO `{}` ({}) VALUES ",
+        table,
+        cols.join(", ")
+    ));
+
+    for (row_idx, mut row) in rows.into_iter().enumerate() {
+        if row_idx > 0 {
+            qb.push(", ");
+        }
+        qb.push("(");
+        for (col_idx, col) in columns.iter().enumerate() {
+            if col_idx > 0 {
+                qb.push(", ");
+            }
+            let val = row.remove(col).unwrap_or(serde_json::Value::Null);
+            push_insert_value(&mut qb, val, max_blob_size)?;
+        }
+        qb.push(")");
+    }
+
+    Ok(qb)
+}
+
+/// Run a data-modifying statement inside the transaction open on this
+/// connection, or on the pool when there is none.
+async fn execute_write(
+    params: &ConnectionParams,
+    query: sqlx::query::Query<'_, sqlx::MySql, sqlx::mysql::MySqlArguments>,
+) -> Result<u64, String> {
+    run_write(params, query).await.map(|r| r.rows_affected())
+}
+
+async fn run_write(
+    params: &ConnectionParams,
+    query: sqlx::query::Query<'_, sqlx::MySql, sqlx::mysql::MySqlArguments>,
+) -> Result<sqlx::mysql::MySqlQueryResult, String> {
+    if let Some(shared) = crate::pool_manager::active_transaction(params).await {
+        if let Some(TransactionConnection::MySql(tx)) = shared.lock().await.as_mut() {
+            return query.execute(&mut **tx).await.map_err(|e| e.to_string());
+        }
+    }
+
+    let pool = get_mysql_pool(params).await?;
+    query.execute(&pool).await.map_err(|e| e.to_string())
+}
+
+/// Like `execute_write`, for reads that must see the open transaction's
+/// uncommitted rows.
+async fn fetch_write(
+    params: &ConnectionParams,
+    query: sqlx::query::Query<'_, sqlx::MySql, sqlx::mysql::MySqlArguments>,
+) -> Result<Option<sqlx::mysql::MySqlRow>, String> {
+    if let Some(shared) = crate::pool_manager::active_transaction(params).await {
+        if let Some(TransactionConnection::MySql(tx)) = shared.lock().await.as_mut() {
+            return query
+                .fetch_optional(&mut **tx)
+                .await
+                .map_err(|e| e.to_string());
+        }
+    }
+
+    let pool = get_mysql_pool(params).await?;
+    query.fetch_optional(&pool).await.map_err(|e| e.to_string())
+}
+
+/// Append one INSERT value to `qb`: bound as a parameter, or written
+/// inline for `NULL` and raw SQL functions.
+fn push_insert_value(
+    qb: &mut sqlx::QueryBuilder<'_, sqlx::MySql>,
+    val: serde_json::Value,
+    max_blob_size: u64,
+) -> Result<(), String> {
+    match val {
+        serde_json::Value::Number(n) => {
+            if n.is_i64() {
+                qb.push_bind(n.as_i64());
+            } else {
+                qb.push_bind(n.as_f64());
+            }
+        }
+        serde_json::Value::String(s) => {
+            if let Some(bytes) = crate::drivers::common::decode_blob_wire_format(&s, max_blob_size)
+            {
+                // Blob wire format: decode to raw bytes so the DB stores binary data,
+                // not the internal wire format string.
+                qb.push_bind(bytes);
+            } else if is_raw_sql_function(&s) {
+                // If it's a raw SQL function (e.g., ST_GeomFromText('POINT(1 2)', 4326))
+                // insert it directly without parameter binding
+                qb.push(s);
+            } else if is_wkt_geometry(&s) {
+                // If it's WKT geometry format, wrap with ST_GeomFromText
+                qb.push("ST_GeomFromText(");
+                qb.push_bind(s);
+                qb.push(")");
+            } else {
+                qb.push_bind(s);
+            }
+        }
+        serde_json::Value::Bool(b) => {
+            qb.push_bind(b);
+        }
+        serde_json::Value::Null => {
+            qb.push("NULL");
+        }
+        serde_json::Value::Object(_) | serde_json::Value::Array(_) => {
+            let json_str = serde_json::to_string(&val).map_err(|e| e.to_string())?;
+            qb.push("CAST(");
+            qb.push_bind(json_str);
+            qb.push(" AS JSON)");
+        }
+    }
+    Ok(())
 }
 
 pub async fn get_table_ddl(params: &ConnectionParams, table_name: &str) -> Result<String, String> {
@@ -603,7 +828,8 @@ pub async fn get_views(
         .bind(db_name)
         .fetch_all(&pool)
         .await
-        .map_err(|e| e.to_string())?;
+        .map_err(|e| e.to_string());
+    let rows = compat::or_empty(params, "views", rows)?;
     let views: Vec<ViewInfo> = rows
         .iter()
         .map(|r| ViewInfo {
@@ -672,6 +898,31 @@ pub async fn drop_view(params: &ConnectionParams, view_name: &str) -> Result<(),
     Ok(())
 }
 
+/// MySQL schemas are databases: with `schema` the table is created there,
+/// otherwise in the connection's current database.
+pub async fn create_table_from_query(
+    params: &ConnectionParams,
+    table_name: &str,
+    query: &str,
+    schema: Option<&str>,
+) -> Result<(), String> {
+    let pool = get_mysql_pool(params).await?;
+    let target = match schema {
+        Some(schema) => format!(
+            "`{}`.`{}`",
+            escape_identifier(schema),
+            escape_identifier(table_name)
+        ),
+        None => format!("`{}`", escape_identifier(table_name)),
+    };
+    let statement = format!("CREATE TABLE {} AS {}", target, query);
+    sqlx::query(&statement)
+        .execute(&pool)
+        .await
+        .map_err(|e| format!("Failed to create table: {}", e))?;
+    Ok(())
+}
+
 pub async fn get_view_columns(
     params: &ConnectionParams,
     view_name: &str,
@@ -747,7 +998,8 @@ pub async fn get_routines(
         .bind(db_name)
         .fetch_all(&pool)
         .await
-        .map_err(|e| e.to_string())?;
+        .map_err(|e| e.to_string());
+    let rows = compat::or_empty(params, "routines", rows)?;
 
     Ok(rows
         .iter()
@@ -889,6 +1141,7 @@ fn is_text_protocol_stmt(query: &str) -> bool {
 async fn exec_on_mysql_conn(
     conn: &mut sqlx::MySqlConnection,
     query: &str,
+    binds: &[serde_json::Value],
     limit: Option<u32>,
     page: u32,
 ) -> Result<QueryResult, String> {
@@ -906,7 +1159,10 @@ async fn exec_on_mysql_conn(
             rows: vec![],
             affected_rows: exec_result.rows_affected(),
             truncated: false,
+            truncation: None,
             pagination: None,
+            style_hints: None,
+            provenance: None,
         });
     }
 
@@ -915,7 +1171,7 @@ async fn exec_on_mysql_conn(
     if !crate::drivers::common::returns_result_set(query) {
         use sqlx::Executor;
         let exec_result = conn
-            .execute(sqlx::query(query))
+            .execute(bind_values(sqlx::query(query), binds))
             .await
             .map_err(|e| e.to_string())?;
         return Ok(QueryResult {
@@ -923,7 +1179,10 @@ async fn exec_on_mysql_conn(
             rows: vec![],
             affected_rows: exec_result.rows_affected(),
             truncated: false,
+            truncation: None,
             pagination: None,
+            style_hints: None,
+            provenance: None,
         });
     }
 
@@ -951,12 +1210,15 @@ async fn exec_on_mysql_conn(
     }
 
     let mut columns: Vec<String> = Vec::new();
-    let mut json_rows = Vec::new();
+    let mut collector = crate::drivers::common::RowCollector::new(
+        crate::drivers::common::ResultLimits::from_config(),
+        extract::row_to_json,
+    );
 
     // Scope the stream so `conn` borrow is released before returning
     {
         use futures::stream::StreamExt;
-        let mut rows_stream = sqlx::query(&final_query).fetch(&mut *conn);
+        let mut rows_stream = bind_values(sqlx::query(&final_query), binds).fetch(&mut *conn);
 
         while let Some(result) = rows_stream.next().await {
             match result {
@@ -968,24 +1230,22 @@ async fn exec_on_mysql_conn(
 
                     // Check limit (only if manual_limit is set)
                     if let Some(l) = manual_limit {
-                        if json_rows.len() >= l as usize {
+                        if collector.len() >= l as usize {
                             truncated = true;
                             break;
                         }
                     }
 
-                    // Map row using type extraction function
-                    let mut json_row = Vec::new();
-                    for (i, _) in row.columns().iter().enumerate() {
-                        let val = extract_value(&row, i, None);
-                        json_row.push(val);
+                    // Converted to JSON in batches off the async executor
+                    if !collector.push(row).await? {
+                        break;
                     }
-                    json_rows.push(json_row);
                 }
                 Err(e) => return Err(e.to_string()),
             }
         }
     } // rows_stream dropped here — conn borrow released
+    let (mut json_rows, truncation) = collector.finish().await?;
 
     // Apply LIMIT +1 result: if we got page_size+1 rows, has_more=true
     if let Some(ref mut p) = pagination {
@@ -1001,11 +1261,35 @@ async fn exec_on_mysql_conn(
         columns,
         rows: json_rows,
         affected_rows: 0,
-        truncated,
+        truncated: truncated || truncation.is_some(),
+        truncation,
         pagination,
+        style_hints: None,
+        provenance: None,
     })
 }
 
+/// Binds editor query parameters in order. Objects and arrays are bound
+/// as their JSON text.
+fn bind_values<'q>(
+    mut query: sqlx::query::Query<'q, sqlx::MySql, sqlx::mysql::MySqlArguments>,
+    values: &[serde_json::Value],
+) -> sqlx::query::Query<'q, sqlx::MySql, sqlx::mysql::MySqlArguments> {
+    for value in values {
+        query = match value {
+            serde_json::Value::Null => query.bind(None::<String>),
+            serde_json::Value::Bool(b) => query.bind(*b),
+            serde_json::Value::Number(n) => match n.as_i64() {
+                Some(i) => query.bind(i),
+                None => query.bind(n.as_f64()),
+            },
+            serde_json::Value::String(s) => query.bind(s.clone()),
+            other => query.bind(other.to_string()),
+        };
+    }
+    query
+}
+
 pub async fn execute_query(
     params: &ConnectionParams,
     query: &str,
@@ -1013,8 +1297,45 @@ pub async fn execute_query(
     page: u32,
     schema: Option<&str>,
 ) -> Result<QueryResult, String> {
+    execute_bound_query(params, query, &[], limit, page, schema).await
+}
+
+/// `execute_query` with `:name` / `$1` / `?` placeholders bound from
+/// `values`.
+pub async fn execute_query_with_params(
+    params: &ConnectionParams,
+    query: &str,
+    values: &std::collections::HashMap<String, serde_json::Value>,
+    limit: Option<u32>,
+    page: u32,
+    schema: Option<&str>,
+) -> Result<QueryResult, String> {
+    let bound = crate::drivers::common::bind_query_params(
+        query,
+        values,
+        crate::drivers::common::PlaceholderStyle::Positional,
+    )?;
+    execute_bound_query(params, &bound.sql, &bound.values, limit, page, schema).await
+}
+
+async fn execute_bound_query(
+    params: &ConnectionParams,
+    query: &str,
+    binds: &[serde_json::Value],
+    limit: Option<u32>,
+    page: u32,
+    schema: Option<&str>,
+) -> Result<QueryResult, String> {
+    // An open transaction owns its connection (and its default database),
+    // so the schema override does not apply to it.
+    if let Some(shared) = crate::pool_manager::active_transaction(params).await {
+        if let Some(TransactionConnection::MySql(tx)) = shared.lock().await.as_mut() {
+            return exec_on_mysql_conn(&mut **tx, query, binds, limit, page).await;
+        }
+    }
+
     let mut conn = acquire_mysql_conn(params, schema).await?;
-    exec_on_mysql_conn(&mut *conn, query, limit, page).await
+    exec_on_mysql_conn(&mut *conn, query, binds, limit, page).await
 }
 
 /// Runs a sequence of statements on a single pooled connection so that
@@ -1033,16 +1354,31 @@ pub async fn execute_batch(
     page: u32,
     schema: Option<&str>,
 ) -> Result<Vec<crate::models::BatchStatementResult>, String> {
+    if let Some(shared) = crate::pool_manager::active_transaction(params).await {
+        if let Some(TransactionConnection::MySql(tx)) = shared.lock().await.as_mut() {
+            return Ok(run_batch_on_mysql_conn(&mut **tx, queries, limit, page).await);
+        }
+    }
+
     let mut conn = acquire_mysql_conn(params, schema).await?;
+    Ok(run_batch_on_mysql_conn(&mut *conn, queries, limit, page).await)
+}
+
+async fn run_batch_on_mysql_conn(
+    conn: &mut sqlx::MySqlConnection,
+    queries: &[String],
+    limit: Option<u32>,
+    page: u32,
+) -> Vec<crate::models::BatchStatementResult> {
     let mut results = Vec::with_capacity(queries.len());
     for q in queries {
         let start = std::time::Instant::now();
-        let outcome = exec_on_mysql_conn(&mut *conn, q, limit, page).await;
+        let outcome = exec_on_mysql_conn(conn, q, &[], limit, page).await;
         results.push(crate::models::BatchStatementResult::from_outcome(
             start, outcome,
         ));
     }
-    Ok(results)
+    results
 }
 
 pub async fn get_triggers(
@@ -1062,7 +1398,8 @@ pub async fn get_triggers(
         .bind(db_name)
         .fetch_all(&pool)
         .await
-        .map_err(|e| e.to_string())?;
+        .map_err(|e| e.to_string());
+    let rows = compat::or_empty(params, "triggers", rows)?;
     let triggers: Vec<TriggerInfo> = rows
         .iter()
         .map(|r| TriggerInfo {
@@ -1151,6 +1488,9 @@ const DEFAULT_MYSQL_MAX_ALLOWED_PACKET: u64 = 1_073_741_824;
 const DEFAULT_MYSQL_SOCKET_TIMEOUT_MS: u64 = 600_000;
 const DEFAULT_MYSQL_CONNECT_TIMEOUT_MS: u64 = 60_000;
 const DEFAULT_MYSQL_TIMEZONE: &str = "SYSTEM";
+/// Batch inserts stay under 4 MiB, the server's `max_allowed_packet`
+/// default before MySQL 8.0, even when the connector allows more.
+const MYSQL_INSERT_BATCH_MAX_BYTES: u64 = 4 * 1024 * 1024;
 
 fn mysql_setting_value(key: &str) -> Option<serde_json::Value> {
     crate::config::get_cached_config()
@@ -1208,7 +1548,11 @@ impl MysqlDriver {
                     no_connection_required: false,
                     manage_tables: true,
                     readonly: false,
+                    arrow: false,
                     triggers: true,
+                    privileges: true,
+                    server_status: true,
+                    table_stats: true,
                 },
                 is_builtin: true,
                 default_username: "root".to_string(),
@@ -1433,6 +1777,16 @@ impl DatabaseDriver for MysqlDriver {
         drop_view(params, view_name).await
     }
 
+    async fn create_table_from_query(
+        &self,
+        params: &crate::models::ConnectionParams,
+        table_name: &str,
+        query: &str,
+        schema: Option<&str>,
+    ) -> Result<(), String> {
+        create_table_from_query(params, table_name, query, schema).await
+    }
+
     async fn get_routines(
         &self,
         params: &crate::models::ConnectionParams,
@@ -1497,6 +1851,90 @@ impl DatabaseDriver for MysqlDriver {
         drop_trigger(params, trigger_name, schema).await
     }
 
+    async fn get_server_version(
+        &self,
+        params: &crate::models::ConnectionParams,
+    ) -> Result<String, String> {
+        get_server_version(params).await
+    }
+
+    async fn get_server_status(
+        &self,
+        params: &crate::models::ConnectionParams,
+    ) -> Result<Vec<crate::models::ServerValue>, String> {
+        status::get_server_status(params).await
+    }
+
+    async fn get_server_variables(
+        &self,
+        params: &crate::models::ConnectionParams,
+    ) -> Result<Vec<crate::models::ServerValue>, String> {
+        status::get_server_variables(params).await
+    }
+
+    async fn get_table_stats(
+        &self,
+        params: &crate::models::ConnectionParams,
+        schema: Option<&str>,
+    ) -> Result<Vec<crate::models::TableStats>, String> {
+        table_stats::get_table_stats(params, schema).await
+    }
+
+    async fn get_lock_waits(
+        &self,
+        params: &crate::models::ConnectionParams,
+    ) -> Result<Vec<crate::models::LockWait>, String> {
+        locks::get_lock_waits(params).await
+    }
+
+    async fn kill_session(
+        &self,
+        params: &crate::models::ConnectionParams,
+        pid: i64,
+    ) -> Result<(), String> {
+        locks::kill_session(params, pid).await
+    }
+
+    async fn get_users(
+        &self,
+        params: &crate::models::ConnectionParams,
+    ) -> Result<Vec<crate::models::DbUser>, String> {
+        privileges::get_users(params).await
+    }
+
+    async fn get_user_grants(
+        &self,
+        params: &crate::models::ConnectionParams,
+        user: &str,
+        host: Option<&str>,
+    ) -> Result<Vec<crate::models::UserGrant>, String> {
+        privileges::get_user_grants(params, user, host).await
+    }
+
+    async fn grant_privilege(
+        &self,
+        params: &crate::models::ConnectionParams,
+        change: &crate::models::PrivilegeChange,
+    ) -> Result<(), String> {
+        privileges::grant_privilege(params, change).await
+    }
+
+    async fn revoke_privilege(
+        &self,
+        params: &crate::models::ConnectionParams,
+        change: &crate::models::PrivilegeChange,
+    ) -> Result<(), String> {
+        privileges::revoke_privilege(params, change).await
+    }
+
+    async fn get_schema_signature(
+        &self,
+        params: &crate::models::ConnectionParams,
+        schema: Option<&str>,
+    ) -> Result<Vec<crate::models::ColumnSignature>, String> {
+        signature::get_schema_signature(params, schema).await
+    }
+
     async fn execute_query(
         &self,
         params: &crate::models::ConnectionParams,
@@ -1519,6 +1957,34 @@ impl DatabaseDriver for MysqlDriver {
         execute_batch(params, queries, limit, page, schema).await
     }
 
+    async fn execute_query_with_params(
+        &self,
+        params: &crate::models::ConnectionParams,
+        query: &str,
+        values: &HashMap<String, serde_json::Value>,
+        limit: Option<u32>,
+        page: u32,
+        schema: Option<&str>,
+    ) -> Result<crate::models::QueryResult, String> {
+        execute_query_with_params(params, query, values, limit, page, schema).await
+    }
+
+    async fn execute_query_stream(
+        &self,
+        params: &crate::models::ConnectionParams,
+        query: &str,
+        batch_size: usize,
+        _schema: Option<&str>,
+        on_batch: &mut crate::drivers::common::RowBatchCallback<'_>,
+    ) -> Result<u64, String> {
+        let mut batcher = crate::drivers::common::RowBatcher::new(batch_size, on_batch);
+        export::stream_query(params, query, |headers, values| {
+            batcher.push(headers, values.to_vec())
+        })
+        .await?;
+        batcher.finish()
+    }
+
     async fn explain_query(
         &self,
         params: &crate::models::ConnectionParams,
@@ -1540,6 +2006,40 @@ impl DatabaseDriver for MysqlDriver {
         insert_record(params, table, data, max_blob_size).await
     }
 
+    async fn insert_record_returning(
+        &self,
+        params: &crate::models::ConnectionParams,
+        table: &str,
+        data: std::collections::HashMap<String, serde_json::Value>,
+        _schema: Option<&str>,
+        max_blob_size: u64,
+    ) -> Result<InsertedRecord, String> {
+        insert_record_returning(params, table, data, max_blob_size).await
+    }
+
+    fn insert_batch_limits(&self) -> Option<crate::drivers::common::InsertBatchLimits> {
+        let max_packet =
+            mysql_numeric_setting("maxAllowedPacket", DEFAULT_MYSQL_MAX_ALLOWED_PACKET);
+        Some(crate::drivers::common::InsertBatchLimits {
+            max_rows: 1000,
+            // Placeholder count is a u16 in the binary protocol.
+            max_params: 65_535,
+            max_bytes: max_packet.min(MYSQL_INSERT_BATCH_MAX_BYTES) as usize,
+        })
+    }
+
+    async fn insert_rows(
+        &self,
+        params: &crate::models::ConnectionParams,
+        table: &str,
+        columns: &[String],
+        rows: Vec<std::collections::HashMap<String, serde_json::Value>>,
+        _schema: Option<&str>,
+        max_blob_size: u64,
+    ) -> Result<u64, String> {
+        insert_rows(params, table, columns, rows, max_blob_size).await
+    }
+
     async fn update_record(
         &self,
         params: &crate::models::ConnectionParams,
@@ -1599,6 +2099,15 @@ impl DatabaseDriver for MysqlDriver {
         fetch_blob_column_as_data_url(params, table, col_name, pk_col, pk_val).await
     }
 
+    async fn get_table_ddl(
+        &self,
+        params: &crate::models::ConnectionParams,
+        table: &str,
+        _schema: Option<&str>,
+    ) -> Result<String, String> {
+        get_table_ddl(params, table).await
+    }
+
     async fn get_create_table_sql(
         &self,
         table_name: &str,
@@ -1733,6 +2242,14 @@ impl DatabaseDriver for MysqlDriver {
         on_update: Option<&str>,
         _schema: Option<&str>,
     ) -> Result<Vec<String>, String> {
+        if let Some(info) = crate::server_info::current() {
+            if !info.features.foreign_keys {
+                return Err(format!(
+                    "Foreign keys are not available on {}",
+                    info.flavor.name()
+                ));
+            }
+        }
         let mut sql = format!(
             "ALTER TABLE `{}` ADD CONSTRAINT `{}` FOREIGN KEY (`{}`) REFERENCES `{}` (`{}`)",
             escape_identifier(table),
@@ -1798,6 +2315,14 @@ impl DatabaseDriver for MysqlDriver {
         get_all_foreign_keys_batch(params, schema).await
     }
 
+    async fn get_all_indexes_batch(
+        &self,
+        params: &crate::models::ConnectionParams,
+        schema: Option<&str>,
+    ) -> Result<HashMap<String, Vec<crate::models::Index>>, String> {
+        get_all_indexes_batch(params, schema).await
+    }
+
     async fn get_schema_snapshot(
         &self,
         params: &crate::models::ConnectionParams,
@@ -0,0 +1,187 @@
+use super::compat;
+use super::helpers::{escape_identifier, mysql_row_str, mysql_row_str_opt};
+use crate::models::{ConnectionParams, DbUser, PrivilegeChange, PrivilegeScope, UserGrant};
+use crate::pool_manager::get_mysql_pool;
+use crate::privileges::normalize_privilege;
+use sqlx::Row;
+
+/// MySQL 5.7+ / MariaDB 10.4+: accounts with their lock state.
+const USERS_QUERY: &str = r#"
+    SELECT User, Host,
+        CAST(account_locked = 'N' AS SIGNED) AS can_login,
+        CAST(Super_priv = 'Y' AS SIGNED) AS superuser
+    FROM mysql.user
+    ORDER BY User, Host
+"#;
+
+/// Older servers have no `account_locked` column.
+const LEGACY_USERS_QUERY: &str = r#"
+    SELECT User, Host, 1 AS can_login, CAST(Super_priv = 'Y' AS SIGNED) AS superuser
+    FROM mysql.user
+    ORDER BY User, Host
+"#;
+
+/// Global, database and table privileges of one grantee (`'user'@'host'`).
+/// `USAGE` only means "may connect" and is left out.
+const GRANTS_QUERY: &str = r#"
+    SELECT 'global', NULL, NULL, PRIVILEGE_TYPE, IS_GRANTABLE
+    FROM information_schema.USER_PRIVILEGES
+    WHERE GRANTEE = ? AND PRIVILEGE_TYPE <> 'USAGE'
+    UNION ALL
+    SELECT 'database', TABLE_SCHEMA, NULL, PRIVILEGE_TYPE, IS_GRANTABLE
+    FROM information_schema.SCHEMA_PRIVILEGES
+    WHERE GRANTEE = ?
+    UNION ALL
+    SELECT 'table', TABLE_SCHEMA, TABLE_NAME, PRIVILEGE_TYPE, IS_GRANTABLE
+    FROM information_schema.TABLE_PRIVILEGES
+    WHERE GRANTEE = ?
+"#;
+
+/// `'user'@'host'` as a string literal pair for GRANT and REVOKE.
+pub(super) fn account_literal(user: &str, host: Option<&str>) -> String {
+    let quote = |s: &str| format!("'{}'", s.replace('\\', "\\\\").replace('\'', "''"));
+    format!("{}@{}", quote(user), quote(host.unwrap_or("%")))
+}
+
+fn grant_target(grant: &UserGrant) -> Result<String, String> {
+    let database = || {
+        grant
+            .database
+            .as_deref()
+            .ok_or_else(|| "A database is required for this privilege".to_string())
+    };
+    match grant.scope {
+        PrivilegeScope::Global => Ok("*.*".to_string()),
+        PrivilegeScope::Database => Ok(format!("`{}`.*", escape_identifier(database()?))),
+        PrivilegeScope::Table => {
+            let table = grant
+                .table
+                .as_deref()
+                .ok_or_else(|| "A table is required for a table privilege".to_string())?;
+            Ok(format!(
+                "`{}`.`{}`",
+                escape_identifier(database()?),
+                escape_identifier(table)
+            ))
+        }
+        PrivilegeScope::Schema => {
+            Err("MySQL has no schema privileges; use the database scope".to_string())
+        }
+    }
+}
+
+pub(super) fn grant_sql(change: &PrivilegeChange) -> Result<String, String> {
+    let mut sql = format!(
+        "GRANT {} ON {} TO {}",
+        normalize_privilege(&change.grant.privilege)?,
+        grant_target(&change.grant)?,
+        account_literal(&change.user, change.host.as_deref())
+    );
+    if change.grant.grantable {
+        sql.push_str(" WITH GRANT OPTION");
+    }
+    Ok(sql)
+}
+
+/// REVOKE leaves the grant option in place unless it is named too.
+pub(super) fn revoke_sql(change: &PrivilegeChange) -> Result<String, String> {
+    let mut privilege = normalize_privilege(&change.grant.privilege)?;
+    if change.grant.grantable {
+        privilege.push_str(", GRANT OPTION");
+    }
+    Ok(format!(
+        "REVOKE {} ON {} FROM {}",
+        privilege,
+        grant_target(&change.grant)?,
+        account_literal(&change.user, change.host.as_deref())
+    ))
+}
+
+fn to_user(row: &sqlx::mysql::MySqlRow) -> DbUser {
+    DbUser {
+        name: mysql_row_str(row, 0),
+        host: mysql_row_str_opt(row, 1),
+        can_login: row.try_get::<i64, _>(2).unwrap_or(1) != 0,
+        superuser: row.try_get::<i64, _>(3).unwrap_or_default() != 0,
+    }
+}
+
+pub async fn get_users(params: &ConnectionParams) -> Result<Vec<DbUser>, String> {
+    compat::ensure_supported(params, "User management")?;
+    let pool = get_mysql_pool(params).await?;
+    let rows = match sqlx::query(USERS_QUERY).fetch_all(&pool).await {
+        Ok(rows) => rows,
+        Err(e) => {
+            log::debug!(
+                "MySQL: account_locked unavailable ({}), listing without it",
+                e
+            );
+            sqlx::query(LEGACY_USERS_QUERY)
+                .fetch_all(&pool)
+                .await
+                .map_err(|e| format!("Failed to list users: {}", e))?
+        }
+    };
+    Ok(rows.iter().map(to_user).collect())
+}
+
+pub async fn get_user_grants(
+    params: &ConnectionParams,
+    user: &str,
+    host: Option<&str>,
+) -> Result<Vec<UserGrant>, String> {
+    compat::ensure_supported(params, "User management")?;
+    let pool = get_mysql_pool(params).await?;
+    // information_schema spells grantees without escaping.
+    let grantee = format!("'{}'@'{}'", user, host.unwrap_or("%"));
+    let rows = sqlx::query(GRANTS_QUERY)
+        .bind(&grantee)
+        .bind(&grantee)
+        .bind(&grantee)
+        .fetch_all(&pool)
+        .await
+        .map_err(|e| format!("Failed to read grants: {}", e))?;
+    Ok(rows
+        .iter()
+        .map(|r| UserGrant {
+            scope: match mysql_row_str(r, 0).as_str() {
+                "global" => PrivilegeScope::Global,
+                "database" => PrivilegeScope::Database,
+                _ => PrivilegeScope::Table,
+            },
+            database: mysql_row_str_opt(r, 1),
+            schema: None,
+            table: mysql_row_str_opt(r, 2),
+            privilege: mysql_row_str(r, 3),
+            grantable: mysql_row_str(r, 4) == "YES",
+        })
+        .collect())
+}
+
+pub async fn grant_privilege(
+    params: &ConnectionParams,
+    change: &PrivilegeChange,
+) -> Result<(), String> {
+    compat::ensure_supported(params, "User management")?;
+    let sql = grant_sql(change)?;
+    let pool = get_mysql_pool(params).await?;
+    sqlx::raw_sql(&sql)
+        .execute(&pool)
+        .await
+        .map_err(|e| format!("Failed to grant privilege: {}", e))?;
+    Ok(())
+}
+
+pub async fn revoke_privilege(
+    params: &ConnectionParams,
+    change: &PrivilegeChange,
+) -> Result<(), String> {
+    compat::ensure_supported(params, "User management")?;
+    let sql = revoke_sql(change)?;
+    let pool = get_mysql_pool(params).await?;
+    sqlx::raw_sql(&sql)
+        .execute(&pool)
+        .await
+        .map_err(|e| format!("Failed to revoke privilege: {}", e))?;
+    Ok(())
+}
@@ -0,0 +1,32 @@
+use super::helpers::mysql_row_str;
+use crate::models::{ColumnSignature, ConnectionParams};
+use crate::pool_manager::get_mysql_pool;
+
+/// Columns of `schema`, or of the connection's primary database.
+pub async fn get_schema_signature(
+    params: &ConnectionParams,
+    schema: Option<&str>,
+) -> Result<Vec<ColumnSignature>, String> {
+    let db_name = schema.unwrap_or_else(|| params.database.primary());
+    let pool = get_mysql_pool(params).await?;
+    let query = r#"
+        SELECT table_name, column_name, column_type
+        FROM information_schema.columns
+        WHERE table_schema = ?
+        ORDER BY table_name, column_name
+    "#;
+    let rows = sqlx::query(query)
+        .bind(db_name)
+        .fetch_all(&pool)
+        .await
+        .map_err(|e| format!("Failed to read schema signature: {}", e))?;
+
+    Ok(rows
+        .iter()
+        .map(|r| ColumnSignature {
+            table: mysql_row_str(r, 0),
+            column: mysql_row_str(r, 1),
+            data_type: mysql_row_str(r, 2),
+        })
+        .collect())
+}
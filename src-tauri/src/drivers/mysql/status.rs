@@ -0,0 +1,30 @@
+use super::compat;
+use super::helpers::mysql_row_str;
+use crate::models::{ConnectionParams, ServerValue};
+use crate::pool_manager::get_mysql_pool;
+
+/// Both statements answer with `Variable_name`, `Value` rows.
+async fn show(params: &ConnectionParams, statement: &str) -> Result<Vec<ServerValue>, String> {
+    let pool = get_mysql_pool(params).await?;
+    let rows = sqlx::query(statement)
+        .fetch_all(&pool)
+        .await
+        .map_err(|e| format!("{} failed: {}", statement, e))?;
+    Ok(rows
+        .iter()
+        .map(|r| ServerValue::new(mysql_row_str(r, 0), mysql_row_str(r, 1)))
+        .collect())
+}
+
+pub async fn get_server_status(params: &ConnectionParams) -> Result<Vec<ServerValue>, String> {
+    show(params, "SHOW GLOBAL STATUS").await
+}
+
+pub async fn get_server_variables(params: &ConnectionParams) -> Result<Vec<ServerValue>, String> {
+    let mut variables = show(params, "SHOW GLOBAL VARIABLES").await?;
+    if compat::is_vitess(params) {
+        let pool = get_mysql_pool(params).await?;
+        variables.extend(compat::vitess_variables(&pool).await);
+    }
+    Ok(variables)
+}
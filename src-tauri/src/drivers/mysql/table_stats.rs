@@ -0,0 +1,67 @@
+use super::helpers::{mysql_row_str, mysql_row_str_opt};
+use crate::models::{ConnectionParams, TableStats};
+use crate::pool_manager::get_mysql_pool;
+use sqlx::Row;
+
+/// Sizes from `information_schema.TABLES`, with the time InnoDB last
+/// gathered statistics.
+const TABLE_STATS_QUERY: &str = r#"
+    SELECT t.TABLE_NAME,
+        CAST(t.TABLE_ROWS AS SIGNED),
+        CAST(t.DATA_LENGTH AS SIGNED),
+        CAST(t.INDEX_LENGTH AS SIGNED),
+        CAST(s.last_update AS CHAR)
+    FROM information_schema.TABLES t
+    LEFT JOIN mysql.innodb_table_stats s
+        ON s.database_name = t.TABLE_SCHEMA AND s.table_name = t.TABLE_NAME
+    WHERE t.TABLE_SCHEMA = ? AND t.TABLE_TYPE = 'BASE TABLE'
+    ORDER BY t.TABLE_NAME
+"#;
+
+/// Without access to the `mysql` schema the statistics time is unknown.
+const LEGACY_TABLE_STATS_QUERY: &str = r#"
+    SELECT TABLE_NAME,
+        CAST(TABLE_ROWS AS SIGNED),
+        CAST(DATA_LENGTH AS SIGNED),
+        CAST(INDEX_LENGTH AS SIGNED),
+        NULL
+    FROM information_schema.TABLES
+    WHERE TABLE_SCHEMA = ? AND TABLE_TYPE = 'BASE TABLE'
+    ORDER BY TABLE_NAME
+"#;
+
+pub async fn get_table_stats(
+    params: &ConnectionParams,
+    schema: Option<&str>,
+) -> Result<Vec<TableStats>, String> {
+    let db_name = schema.unwrap_or_else(|| params.database.primary());
+    let pool = get_mysql_pool(params).await?;
+    let rows = match sqlx::query(TABLE_STATS_QUERY)
+        .bind(db_name)
+        .fetch_all(&pool)
+        .await
+    {
+        Ok(rows) => rows,
+        Err(e) => {
+            log::debug!(
+                "MySQL: innodb_table_stats unavailable ({}), reading sizes only",
+                e
+            );
+            sqlx::query(LEGACY_TABLE_STATS_QUERY)
+                .bind(db_name)
+                .fetch_all(&pool)
+                .await
+                .map_err(|e| format!("Failed to read table statistics: {}", e))?
+        }
+    };
+    Ok(rows
+        .iter()
+        .map(|r| TableStats {
+            name: mysql_row_str(r, 0),
+            row_estimate: r.try_get::<Option<i64>, _>(1).unwrap_or_default(),
+            data_bytes: r.try_get::<Option<i64>, _>(2).unwrap_or_default(),
+            index_bytes: r.try_get::<Option<i64>, _>(3).unwrap_or_default(),
+            last_analyzed: mysql_row_str_opt(r, 4),
+        })
+        .collect())
+}
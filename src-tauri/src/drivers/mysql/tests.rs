@@ -1,5 +1,8 @@
+use super::compat::{fallback, is_vitess_server};
 use super::explain::parse_mysql_query_block;
-use crate::models::ExplainNode;
+use super::privileges::{grant_sql, revoke_sql};
+use crate::models::{ExplainNode, PrivilegeChange, PrivilegeScope, UserGrant};
+use crate::server_info::detect;
 
 /// Helper: parse a MariaDB ANALYZE FORMAT=JSON string and return the root node.
 fn parse_json(json: &str) -> ExplainNode {
@@ -516,3 +519,90 @@ fn test_filesort_with_direct_table() {
     assert_eq!(table.relation.as_deref(), Some("t"));
     assert!((table.actual_rows.unwrap() - 100.0).abs() < 0.1);
 }
+
+// -- GRANT / REVOKE statements ---------------------------------------------
+
+fn change(scope: PrivilegeScope, database: Option<&str>, table: Option<&str>) -> PrivilegeChange {
+    PrivilegeChange {
+        user: "app".to_string(),
+        host: None,
+        grant: UserGrant {
+            privilege: "select".to_string(),
+            scope,
+            database: database.map(str::to_string),
+            schema: None,
+            table: table.map(str::to_string),
+            grantable: false,
+        },
+    }
+}
+
+#[test]
+fn test_grant_sql_per_scope() {
+    assert_eq!(
+        grant_sql(&change(PrivilegeScope::Global, None, None)).unwrap(),
+        "GRANT SELECT ON *.* TO 'app'@'%'"
+    );
+    assert_eq!(
+        grant_sql(&change(PrivilegeScope::Database, Some("shop"), None)).unwrap(),
+        "GRANT SELECT ON `shop`.* TO 'app'@'%'"
+    );
+
+    let mut on_table = change(PrivilegeScope::Table, Some("shop"), Some("or`ders"));
+    on_table.host = Some("10.0.%".to_string());
+    on_table.grant.grantable = true;
+    assert_eq!(
+        grant_sql(&on_table).unwrap(),
+        "GRANT SELECT ON `shop`.`or``ders` TO 'app'@'10.0.%' WITH GRANT OPTION"
+    );
+}
+
+#[test]
+fn test_revoke_sql_names_the_grant_option() {
+    let mut grant = change(PrivilegeScope::Database, Some("shop"), None);
+    assert_eq!(
+        revoke_sql(&grant).unwrap(),
+        "REVOKE SELECT ON `shop`.* FROM 'app'@'%'"
+    );
+    grant.grant.grantable = true;
+    assert_eq!(
+        revoke_sql(&grant).unwrap(),
+        "REVOKE SELECT, GRANT OPTION ON `shop`.* FROM 'app'@'%'"
+    );
+}
+
+#[test]
+fn test_grant_sql_rejects_bad_input() {
+    let mut grant = change(PrivilegeScope::Global, None, None);
+    grant.grant.privilege = "SELECT ON *.* TO 'x'@'%'; --".to_string();
+    assert!(grant_sql(&grant).is_err());
+    assert!(grant_sql(&change(PrivilegeScope::Schema, Some("shop"), None)).is_err());
+    assert!(grant_sql(&change(PrivilegeScope::Table, Some("shop"), None)).is_err());
+
+    let mut quoted = change(PrivilegeScope::Global, None, None);
+    quoted.user = "o'brien".to_string();
+    assert_eq!(
+        grant_sql(&quoted).unwrap(),
+        "GRANT SELECT ON *.* TO 'o''brien'@'%'"
+    );
+}
+
+// -- Vitess compatibility --------------------------------------------------
+
+#[test]
+fn test_vitess_is_recognised_from_its_version() {
+    assert!(is_vitess_server(Some(&detect("mysql", "8.0.30-Vitess"))));
+    assert!(!is_vitess_server(Some(&detect("mysql", "8.0.36"))));
+    assert!(!is_vitess_server(None));
+}
+
+#[test]
+fn test_vitess_catalog_errors_fall_back_to_empty() {
+    let failed: Result<Vec<String>, String> = Err("unknown table REFERENTIAL_CONSTRAINTS".into());
+    assert_eq!(fallback(true, "foreign keys", failed.clone()), Ok(vec![]));
+    assert!(fallback(false, "foreign keys", failed).is_err());
+    assert_eq!(
+        fallback(true, "foreign keys", Ok(vec!["fk".to_string()])),
+        Ok(vec!["fk".to_string()])
+    );
+}
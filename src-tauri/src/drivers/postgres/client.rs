@@ -1,6 +1,9 @@
 use deadpool_postgres::{Object as PgObject, Pool as PgPool};
 use tokio_postgres::Row as PgRow;
 
+use crate::models::ConnectionParams;
+use crate::pool_manager::{active_transaction, get_postgres_pool, TransactionConnection};
+
 pub(super) fn format_pg_error(e: &tokio_postgres::Error) -> String {
     if let Some(db) = e.as_db_error() {
         let brief = format!("{}: {}", db.severity(), db.message());
@@ -65,3 +68,43 @@ pub(super) async fn execute(
         .await
         .map_err(|e| format_pg_error(&e))
 }
+
+/// Like `execute`, but runs inside the transaction open on this connection
+/// when there is one.
+pub(super) async fn execute_write(
+    conn_params: &ConnectionParams,
+    sql: &str,
+    params: &[&(dyn tokio_postgres::types::ToSql + Sync)],
+) -> Result<u64, String> {
+    if let Some(shared) = active_transaction(conn_params).await {
+        if let Some(TransactionConnection::Postgres(client)) = shared.lock().await.as_ref() {
+            return client
+                .execute(sql, params)
+                .await
+                .map_err(|e| format_pg_error(&e));
+        }
+    }
+
+    let pool = get_postgres_pool(conn_params).await?;
+    execute(&pool, sql, params).await
+}
+
+/// Like `query_all`, but for statements that write (`INSERT ... RETURNING`):
+/// runs inside the transaction open on this connection when there is one.
+pub(super) async fn query_write(
+    conn_params: &ConnectionParams,
+    sql: &str,
+    params: &[&(dyn tokio_postgres::types::ToSql + Sync)],
+) -> Result<Vec<PgRow>, String> {
+    if let Some(shared) = active_transaction(conn_params).await {
+        if let Some(TransactionConnection::Postgres(client)) = shared.lock().await.as_ref() {
+            return client
+                .query(sql, params)
+                .await
+                .map_err(|e| format_pg_error(&e));
+        }
+    }
+
+    let pool = get_postgres_pool(conn_params).await?;
+    query_all(&pool, sql, params).await
+}
@@ -0,0 +1,99 @@
+//! Compatibility profiles for servers the PostgreSQL driver talks to whose
+//! catalogs differ from PostgreSQL's.
+//!
+//! CockroachDB answers to the same protocol but lacks several catalog
+//! functions and has its own `SHOW` statements; TimescaleDB adds schemas of
+//! its own and hypertables whose DDL is more than `CREATE TABLE`. The
+//! profile is picked from the server detected when the connection opened.
+
+use super::helpers::escape_identifier;
+use crate::models::ConnectionParams;
+use crate::server_info::{ServerFlavor, ServerInfo};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum Profile {
+    Postgres,
+    Cockroach,
+    Timescale,
+}
+
+/// Schemas CockroachDB lists in `information_schema.schemata` for its own
+/// virtual tables.
+const COCKROACH_SCHEMAS: &[&str] = &["crdb_internal", "pg_extension"];
+
+/// Schemas TimescaleDB keeps its catalog, jobs and chunks in.
+const TIMESCALE_SCHEMAS: &[&str] = &[
+    "_timescaledb_cache",
+    "_timescaledb_catalog",
+    "_timescaledb_config",
+    "_timescaledb_functions",
+    "_timescaledb_internal",
+    "timescaledb_experimental",
+    "timescaledb_information",
+];
+
+pub(super) fn profile_of(info: Option<&ServerInfo>) -> Profile {
+    match info {
+        Some(info) if info.flavor == ServerFlavor::Cockroachdb => Profile::Cockroach,
+        Some(info) if info.has_extension("timescaledb") => Profile::Timescale,
+        _ => Profile::Postgres,
+    }
+}
+
+/// The profile of the server behind `params`; plain PostgreSQL until the
+/// server has been detected.
+pub(super) fn profile(params: &ConnectionParams) -> Profile {
+    profile_of(crate::server_info::for_params(params).as_ref())
+}
+
+/// Leave out the schemas the server keeps for itself.
+pub(super) fn hide_internal_schemas(profile: Profile, schemas: Vec<String>) -> Vec<String> {
+    let internal = match profile {
+        Profile::Postgres => return schemas,
+        Profile::Cockroach => COCKROACH_SCHEMAS,
+        Profile::Timescale => TIMESCALE_SCHEMAS,
+    };
+    schemas
+        .into_iter()
+        .filter(|schema| !internal.contains(&schema.as_str()))
+        .collect()
+}
+
+/// Fail with a clear message for what CockroachDB's catalog cannot answer,
+/// rather than with the error of the query that would have been run.
+pub(super) fn ensure_supported(profile: Profile, feature: &str) -> Result<(), String> {
+    if profile == Profile::Cockroach {
+        return Err(format!("{} is not available on CockroachDB", feature));
+    }
+    Ok(())
+}
+
+/// `create_hypertable` call that turns the table back into a hypertable
+/// partitioned on `time_column`, with the chunk interval when it is a time
+/// interval (integer dimensions have none).
+pub(super) fn create_hypertable_sql(
+    schema: &str,
+    table: &str,
+    time_column: &str,
+    chunk_interval: Option<&str>,
+) -> String {
+    let literal = |s: &str| s.replace('\'', "''");
+    let relation = format!(
+        "\"{}\".\"{}\"",
+        escape_identifier(schema),
+        escape_identifier(table)
+    );
+    let mut sql = format!(
+        "SELECT create_hypertable('{}', '{}'",
+        literal(&relation),
+        literal(time_column)
+    );
+    if let Some(interval) = chunk_interval {
+        sql.push_str(&format!(
+            ", chunk_time_interval => INTERVAL '{}'",
+            literal(interval)
+        ));
+    }
+    sql.push_str(");");
+    sql
+}
@@ -1,4 +1,5 @@
 use super::client::{format_pg_error, query_all};
+use super::compat;
 use super::helpers::escape_identifier;
 use crate::explain_import::parse_postgres_json;
 use crate::models::{ConnectionParams, ExplainPlan};
@@ -10,6 +11,7 @@ pub async fn explain_query(
     analyze: bool,
     schema: Option<&str>,
 ) -> Result<ExplainPlan, String> {
+    compat::ensure_supported(compat::profile(params), "EXPLAIN (FORMAT JSON)")?;
     let pool = get_postgres_pool(params).await?;
 
     if let Some(s) = schema {
@@ -11,7 +11,7 @@ use super::extract::extract_value;
 pub async fn stream_query<F>(
     params: &ConnectionParams,
     query: &str,
-    mut on_row: F,
+    on_row: F,
 ) -> Result<(), String>
 where
     F: FnMut(&[String], &[Value]) -> Result<(), String> + Send,
@@ -22,6 +22,19 @@ where
         .await
         .map_err(|e| format!("failed to get postgres client: {:?}", e))?;
 
+    stream_query_on_client(&client, query, on_row).await
+}
+
+/// Like [`stream_query`], on an already-acquired client (e.g. one whose
+/// `search_path` has been set).
+pub async fn stream_query_on_client<F>(
+    client: &tokio_postgres::Client,
+    query: &str,
+    mut on_row: F,
+) -> Result<(), String>
+where
+    F: FnMut(&[String], &[Value]) -> Result<(), String> + Send,
+{
     let bind_params: Vec<i32> = vec![];
     let mut rows = std::pin::pin!(client
         .query_raw(query, &bind_params)
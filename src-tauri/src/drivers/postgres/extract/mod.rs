@@ -13,6 +13,13 @@ use tokio_postgres::{
     Row,
 };
 
+/// All values of `row`, in column order.
+pub fn row_to_json(row: &Row) -> Vec<JsonValue> {
+    (0..row.len())
+        .map(|i| extract_value(row, i, None))
+        .collect()
+}
+
 pub fn extract_value(row: &Row, index: usize, _known_type: Option<&str>) -> JsonValue {
     match row.try_get::<_, Extractor>(index) {
         Ok(extractor) => extractor.value,
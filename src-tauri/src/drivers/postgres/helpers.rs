@@ -38,6 +38,24 @@ pub(super) fn is_implicit_cast_compatible(old_type: &str, new_type: &str) -> boo
     false
 }
 
+/// Column type for an auto-increment column declared as `data_type`:
+/// an identity column where the server has them, a serial type otherwise.
+pub(super) fn auto_increment_type(data_type: &str, identity: bool) -> String {
+    let upper = data_type.to_uppercase();
+    let (integer, serial) = if upper.contains("BIGINT") || upper.contains("BIGSERIAL") {
+        ("BIGINT", "BIGSERIAL")
+    } else if upper.contains("SMALLINT") || upper.contains("SMALLSERIAL") {
+        ("SMALLINT", "SMALLSERIAL")
+    } else {
+        ("INTEGER", "SERIAL")
+    };
+    if identity {
+        format!("{} GENERATED BY DEFAULT AS IDENTITY", integer)
+    } else {
+        serial.to_string()
+    }
+}
+
 // Helper function to escape double quotes in identifiers for PostgreSQL
 pub(super) fn escape_identifier(name: &str) -> String {
     name.replace('"', "\"\"")
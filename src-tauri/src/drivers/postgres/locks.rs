@@ -0,0 +1,65 @@
+use super::client::{format_pg_error, query_all, query_one};
+use super::compat;
+use crate::models::{ConnectionParams, LockWait};
+use crate::pool_manager::get_postgres_pool;
+
+/// Lock waits in the current database. `pg_blocking_pids` gives the blocker
+/// of each waiting backend; the ungranted `pg_locks` row gives what it waits on.
+pub async fn get_lock_waits(params: &ConnectionParams) -> Result<Vec<LockWait>, String> {
+    compat::ensure_supported(compat::profile(params), "Lock inspection")?;
+    let pool = get_postgres_pool(params).await?;
+    let query = r#"
+        SELECT
+            blocked.pid::int8 AS blocked_pid,
+            blocked.usename::text AS blocked_user,
+            blocked.query AS blocked_query,
+            EXTRACT(EPOCH FROM (now() - blocked.query_start))::float8 AS wait_seconds,
+            blocking.pid::int8 AS blocking_pid,
+            blocking.usename::text AS blocking_user,
+            blocking.query AS blocking_query,
+            blocking.state AS blocking_state,
+            waiting.locktype AS lock_type,
+            waiting.relation::regclass::text AS relation
+        FROM pg_stat_activity blocked
+        CROSS JOIN LATERAL unnest(pg_blocking_pids(blocked.pid)) AS b(pid)
+        JOIN pg_stat_activity blocking ON blocking.pid = b.pid
+        LEFT JOIN LATERAL (
+            SELECT l.locktype, l.relation
+            FROM pg_locks l
+            WHERE l.pid = blocked.pid AND NOT l.granted
+            LIMIT 1
+        ) waiting ON true
+        WHERE blocked.datname = current_database()
+        ORDER BY blocking.pid, wait_seconds DESC NULLS LAST
+    "#;
+    let rows = query_all(&pool, query, &[]).await?;
+
+    rows.iter()
+        .map(|r| {
+            Ok(LockWait {
+                blocked_pid: r.try_get("blocked_pid").map_err(|e| format_pg_error(&e))?,
+                blocked_user: r.try_get("blocked_user").unwrap_or_default(),
+                blocked_query: r.try_get("blocked_query").unwrap_or_default(),
+                wait_seconds: r.try_get("wait_seconds").unwrap_or_default(),
+                blocking_pid: r.try_get("blocking_pid").map_err(|e| format_pg_error(&e))?,
+                blocking_user: r.try_get("blocking_user").unwrap_or_default(),
+                blocking_query: r.try_get("blocking_query").unwrap_or_default(),
+                blocking_state: r.try_get("blocking_state").unwrap_or_default(),
+                lock_type: r.try_get("lock_type").unwrap_or_default(),
+                relation: r.try_get("relation").unwrap_or_default(),
+            })
+        })
+        .collect()
+}
+
+pub async fn kill_session(params: &ConnectionParams, pid: i64) -> Result<(), String> {
+    let pid = i32::try_from(pid).map_err(|_| format!("Invalid backend pid: {}", pid))?;
+    let pool = get_postgres_pool(params).await?;
+    let row = query_one(&pool, "SELECT pg_terminate_backend($1)", &[&pid]).await?;
+    let terminated: bool = row.try_get(0).map_err(|e| format_pg_error(&e))?;
+    if terminated {
+        Ok(())
+    } else {
+        Err(format!("Session {} not found", pid))
+    }
+}
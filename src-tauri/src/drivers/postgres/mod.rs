@@ -5,22 +5,32 @@ pub mod extract;
 
 mod binding;
 mod client;
+mod compat;
 mod explain;
 mod helpers;
+mod locks;
+mod privileges;
+mod signature;
+mod status;
+mod table_stats;
 
 #[cfg(test)]
 mod tests;
 
 use crate::models::{
-    ConnectionParams, ForeignKey, Index, Pagination, QueryResult, RoutineInfo, RoutineParameter,
-    TableColumn, TableInfo, TriggerInfo, ViewInfo,
+    ConnectionParams, ForeignKey, Index, InsertedRecord, Pagination, QueryResult, RoutineInfo,
+    RoutineParameter, TableColumn, TableInfo, TriggerInfo, ViewInfo,
 };
-use crate::pool_manager::get_postgres_pool;
+use crate::pool_manager::{get_postgres_pool, TransactionConnection};
 use binding::{PgValueOptions, bind_pg_value, build_pk_predicate};
-use client::{execute, format_pg_error, get_client, query_all, query_one};
+use client::{
+    execute, execute_write, format_pg_error, get_client, query_all, query_one, query_write,
+};
 pub use explain::explain_query;
 use extract::extract_value;
-use helpers::{escape_identifier, extract_base_type, is_implicit_cast_compatible};
+use helpers::{
+    auto_increment_type, escape_identifier, extract_base_type, is_implicit_cast_compatible,
+};
 use tokio_postgres::types::ToSql;
 
 pub async fn get_schemas(params: &ConnectionParams) -> Result<Vec<String>, String> {
@@ -36,10 +46,14 @@ ORDER BY schema_name",
     )
     .await?;
 
-    Ok(rows
+    let schemas = rows
         .iter()
         .map(|r| r.try_get("schema_name").unwrap_or_default())
-        .collect())
+        .collect();
+    Ok(compat::hide_internal_schemas(
+        compat::profile(params),
+        schemas,
+    ))
 }
 
 pub async fn get_databases(params: &ConnectionParams) -> Result<Vec<String>, String> {
@@ -56,6 +70,32 @@ pub async fn get_databases(params: &ConnectionParams) -> Result<Vec<String>, Str
         .collect())
 }
 
+pub async fn get_server_version(params: &ConnectionParams) -> Result<String, String> {
+    let pool = get_postgres_pool(params).await?;
+    let row = query_one(&pool, "SELECT version()", &[]).await?;
+    row.try_get(0).map_err(|e| format_pg_error(&e))
+}
+
+pub async fn get_server_extensions(params: &ConnectionParams) -> Result<Vec<String>, String> {
+    let pool = get_postgres_pool(params).await?;
+    let rows = query_all(
+        &pool,
+        "SELECT extname::text FROM pg_extension ORDER BY extname",
+        &[],
+    )
+    .await?;
+    Ok(rows
+        .iter()
+        .map(|r| r.try_get(0).unwrap_or_default())
+        .collect())
+}
+
+/// Whether the server the DDL is generated for has identity columns.
+/// Unknown servers get serial types, which every version accepts.
+fn identity_columns() -> bool {
+    crate::server_info::current().is_some_and(|info| info.features.identity_columns)
+}
+
 pub async fn get_tables(params: &ConnectionParams, schema: &str) -> Result<Vec<TableInfo>, String> {
     log::debug!(
         "PostgreSQL: Fetching tables for database: {} schema: {}",
@@ -331,6 +371,59 @@ pub async fn get_all_foreign_keys_batch(
     Ok(result)
 }
 
+// Batch function: Get all indexes for all tables in one query
+pub async fn get_all_indexes_batch(
+    params: &ConnectionParams,
+    schema: &str,
+) -> Result<std::collections::HashMap<String, Vec<Index>>, String> {
+    use std::collections::HashMap;
+    let pool = get_postgres_pool(params).await?;
+
+    let query = r#"
+        SELECT
+            t.relname as table_name,
+            i.relname as index_name,
+            a.attname as column_name,
+            ix.indisunique as is_unique,
+            ix.indisprimary as is_primary,
+            array_position(ix.indkey, a.attnum) as seq_in_index
+        FROM
+            pg_class t
+            JOIN pg_namespace n ON t.relnamespace = n.oid
+            JOIN pg_index ix ON t.oid = ix.indrelid
+            JOIN pg_class i ON i.oid = ix.indexrelid
+            JOIN pg_attribute a ON a.attrelid = t.oid AND a.attnum = ANY(ix.indkey)
+        WHERE
+            t.relkind = 'r'
+            AND n.nspname = $1
+        ORDER BY
+            t.relname,
+            i.relname,
+            seq_in_index
+    "#;
+
+    let rows = query_all(&pool, &query, &[&schema]).await?;
+
+    let mut result: HashMap<String, Vec<Index>> = HashMap::new();
+
+    for r in &rows {
+        let index = Index {
+            name: r.try_get("index_name").unwrap_or_default(),
+            column_name: r.try_get("column_name").unwrap_or_default(),
+            is_unique: r.try_get("is_unique").unwrap_or(false),
+            is_primary: r.try_get("is_primary").unwrap_or(false),
+            seq_in_index: r.try_get("seq_in_index").unwrap_or(0),
+        };
+        let table_name: String = r.try_get("table_name").unwrap_or_default();
+        result
+            .entry(table_name)
+            .or_insert_with(Vec::new)
+            .push(index);
+    }
+
+    Ok(result)
+}
+
 pub async fn get_indexes(
     params: &ConnectionParams,
     table_name: &str,
@@ -509,8 +602,6 @@ pub async fn delete_record(
     pk_val: serde_json::Value,
     schema: &str,
 ) -> Result<u64, String> {
-    let pool = get_postgres_pool(params).await?;
-
     let (predicate, param) = build_pk_predicate(pk_col, pk_val, 1)?;
     let query = format!(
         "DELETE FROM \"{}\".\"{}\" WHERE {}",
@@ -519,7 +610,7 @@ pub async fn delete_record(
         predicate,
     );
 
-    execute(&pool, &query, &[param.as_ref() as &(dyn ToSql + Sync)]).await
+    execute_write(params, &query, &[param.as_ref() as &(dyn ToSql + Sync)]).await
 }
 
 pub async fn update_record(
@@ -556,11 +647,11 @@ pub async fn update_record(
         escape_identifier(col_name)
     );
 
-    let mut params: Vec<Box<dyn tokio_postgres::types::ToSql + Send + Sync>> = Vec::new();
+    let mut bind_params: Vec<Box<dyn tokio_postgres::types::ToSql + Send + Sync>> = Vec::new();
 
     let bound = bind_pg_value(
         new_val,
-        params.len() + 1,
+        bind_params.len() + 1,
         PgValueOptions {
             column_type: column_data_type.as_deref(),
             max_blob_size,
@@ -569,32 +660,34 @@ pub async fn update_record(
     )?;
     query.push_str(&bound.sql);
     if let Some(param) = bound.param {
-        params.push(param);
+        bind_params.push(param);
     }
 
-    let (predicate, pk_param) = build_pk_predicate(pk_col, pk_val, params.len() + 1)?;
+    let (predicate, pk_param) = build_pk_predicate(pk_col, pk_val, bind_params.len() + 1)?;
     query.push_str(" WHERE ");
     query.push_str(&predicate);
-    params.push(pk_param);
+    bind_params.push(pk_param);
 
-    let params: Vec<&(dyn ToSql + Sync)> = params
+    let bind_params: Vec<&(dyn ToSql + Sync)> = bind_params
         .iter()
         .map(|b| b.as_ref() as &(dyn ToSql + Sync))
         .collect();
 
-    execute(&pool, &query, &params).await.map_err(|err| {
-        update_record_error_context(
-            err,
-            schema,
-            table,
-            pk_col,
-            &pk_val_for_context,
-            col_name,
-            &new_val_for_context,
-            column_data_type.as_deref(),
-            &query,
-        )
-    })
+    execute_write(params, &query, &bind_params)
+        .await
+        .map_err(|err| {
+            update_record_error_context(
+                err,
+                schema,
+                table,
+                pk_col,
+                &pk_val_for_context,
+                col_name,
+                &new_val_for_context,
+                column_data_type.as_deref(),
+                &query,
+            )
+        })
 }
 
 pub async fn insert_record(
@@ -604,20 +697,10 @@ pub async fn insert_record(
     schema: &str,
     max_blob_size: u64,
 ) -> Result<u64, String> {
-    let pool = get_postgres_pool(params).await?;
-
-    // Preserve original column ordering for stable SQL (collect from HashMap once)
-    let mut entries: Vec<(String, serde_json::Value)> = data.into_iter().collect();
-
-    let mut cols = Vec::with_capacity(entries.len());
-    for (name, _) in &entries {
-        cols.push(format!("\"{}\"", name));
-    }
-
     // Allow empty inserts for auto-generated values (e.g., auto-increment PKs)
-    if cols.is_empty() {
-        return execute(
-            &pool,
+    if data.is_empty() {
+        return execute_write(
+            params,
             &format!(
                 "INSERT INTO \"{}\".\"{}\" DEFAULT VALUES",
                 escape_identifier(schema),
@@ -628,6 +711,80 @@ pub async fn insert_record(
         .await;
     };
 
+    let columns: Vec<String> = data.keys().cloned().collect();
+    insert_rows(params, table, &columns, vec![data], schema, max_blob_size).await
+}
+
+/// Insert one row with `RETURNING *` so generated keys and defaults come
+/// back with it.
+pub async fn insert_record_returning(
+    params: &ConnectionParams,
+    table: &str,
+    data: std::collections::HashMap<String, serde_json::Value>,
+    schema: &str,
+    max_blob_size: u64,
+) -> Result<InsertedRecord, String> {
+    let (query, bind_params) = if data.is_empty() {
+        let query = format!(
+            "INSERT INTO \"{}\".\"{}\" DEFAULT VALUES",
+            escape_identifier(schema),
+            escape_identifier(table)
+        );
+        (query, Vec::new())
+    } else {
+        let columns: Vec<String> = data.keys().cloned().collect();
+        build_insert(params, table, &columns, vec![data], schema, max_blob_size).await?
+    };
+    let query = format!("{} RETURNING *", query);
+
+    let bind_params: Vec<&(dyn ToSql + Sync)> = bind_params
+        .iter()
+        .map(|b| b.as_ref() as &(dyn ToSql + Sync))
+        .collect();
+
+    let rows = query_write(params, &query, &bind_params).await?;
+    Ok(InsertedRecord {
+        affected_rows: rows.len() as u64,
+        row: rows.first().map(|row| {
+            row.columns()
+                .iter()
+                .enumerate()
+                .map(|(i, col)| (col.name().to_string(), extract_value(row, i, None)))
+                .collect()
+        }),
+    })
+}
+
+/// Insert `rows` with a single multi-row `INSERT ... VALUES (...), (...)`.
+/// Cells missing from a row are written as `NULL`.
+pub async fn insert_rows(
+    params: &ConnectionParams,
+    table: &str,
+    columns: &[String],
+    rows: Vec<std::collections::HashMap<String, serde_json::Value>>,
+    schema: &str,
+    max_blob_size: u64,
+) -> Result<u64, String> {
+    let (query, bind_params) =
+        build_insert(params, table, columns, rows, schema, max_blob_size).await?;
+
+    let bind_params: Vec<&(dyn ToSql + Sync)> = bind_params
+        .iter()
+        .map(|b| b.as_ref() as &(dyn ToSql + Sync))
+        .collect();
+
+    execute_write(params, &query, &bind_params).await
+}
+
+/// SQL and bind values of a multi-row `INSERT` of `rows`.
+async fn build_insert(
+    params: &ConnectionParams,
+    table: &str,
+    columns: &[String],
+    rows: Vec<std::collections::HashMap<String, serde_json::Value>>,
+    schema: &str,
+    max_blob_size: u64,
+) -> Result<(String, Vec<Box<dyn ToSql + Sync + Send>>), String> {
     // Fetch column types so json/jsonb columns get JSON-aware binding.
     let col_types: std::collections::HashMap<String, String> =
         match get_columns(params, table, schema).await {
@@ -646,40 +803,45 @@ pub async fn insert_record(
             }
         };
 
-    let mut params: Vec<Box<dyn ToSql + Sync + Send>> = Vec::with_capacity(entries.len());
-    let mut vals_set: Vec<String> = Vec::with_capacity(entries.len());
-
-    for (col_name, val) in entries.drain(..) {
-        let column_type = col_types.get(&col_name).map(|s| s.as_str());
-        let bound = bind_pg_value(
-            val,
-            params.len() + 1,
-            PgValueOptions {
-                column_type,
-                max_blob_size,
-                allow_default: false,
-            },
-        )?;
-        vals_set.push(bound.sql);
-        if let Some(param) = bound.param {
-            params.push(param);
+    let cols: Vec<String> = columns
+        .iter()
+        .map(|c| format!("\"{}\"", escape_identifier(c)))
+        .collect();
+    let mut bind_params: Vec<Box<dyn ToSql + Sync + Send>> =
+        Vec::with_capacity(rows.len() * columns.len());
+    let mut values_rows: Vec<String> = Vec::with_capacity(rows.len());
+
+    for mut row in rows {
+        let mut vals_set: Vec<String> = Vec::with_capacity(columns.len());
+        for col_name in columns {
+            let val = row.remove(col_name).unwrap_or(serde_json::Value::Null);
+            let column_type = col_types.get(col_name).map(|s| s.as_str());
+            let bound = bind_pg_value(
+                val,
+                bind_params.len() + 1,
+                PgValueOptions {
+                    column_type,
+                    max_blob_size,
+                    allow_default: false,
+                },
+            )?;
+            vals_set.push(bound.sql);
+            if let Some(param) = bound.param {
+                bind_params.push(param);
+            }
         }
+        values_rows.push(format!("({})", vals_set.join(", ")));
     }
 
     let query = format!(
-        "INSERT INTO \"{}\".\"{}\" ({}) VALUES ({})",
+        "INSERT INTO \"{}\".\"{}\" ({}) VALUES {}",
         escape_identifier(schema),
         escape_identifier(table),
         cols.join(", "),
-        vals_set.join(", ")
+        values_rows.join(", ")
     );
 
-    let params: Vec<&(dyn ToSql + Sync)> = params
-        .iter()
-        .map(|b| b.as_ref() as &(dyn ToSql + Sync))
-        .collect();
-
-    execute(&pool, &query, &params).await
+    Ok((query, bind_params))
 }
 
 pub async fn get_table_ddl(
@@ -687,6 +849,10 @@ pub async fn get_table_ddl(
     table_name: &str,
     schema: &str,
 ) -> Result<String, String> {
+    let profile = compat::profile(params);
+    if profile == compat::Profile::Cockroach {
+        return show_create_table(params, table_name, schema).await;
+    }
     let cols = get_columns(params, table_name, schema).await?;
     if cols.is_empty() {
         return Err(format!("Table {} not found or empty", table_name));
@@ -712,12 +878,62 @@ pub async fn get_table_ddl(
         defs.push(format!("PRIMARY KEY ({})", pks.join(", ")));
     }
 
-    Ok(format!(
+    let mut ddl = format!(
         "CREATE TABLE \"{}\".\"{}\" (\n  {}\n);",
         escape_identifier(schema),
         escape_identifier(table_name),
         defs.join(",\n  ")
-    ))
+    );
+    if profile == compat::Profile::Timescale {
+        if let Some(hypertable) = hypertable_ddl(params, table_name, schema).await? {
+            ddl.push_str("\n\n");
+            ddl.push_str(&hypertable);
+        }
+    }
+    Ok(ddl)
+}
+
+/// CockroachDB prints a table's full DDL itself.
+async fn show_create_table(
+    params: &ConnectionParams,
+    table_name: &str,
+    schema: &str,
+) -> Result<String, String> {
+    let pool = get_postgres_pool(params).await?;
+    let sql = format!(
+        "SHOW CREATE TABLE \"{}\".\"{}\"",
+        escape_identifier(schema),
+        escape_identifier(table_name)
+    );
+    let row = query_one(&pool, &sql, &[]).await?;
+    let ddl: String = row
+        .try_get("create_statement")
+        .map_err(|e| format_pg_error(&e))?;
+    Ok(format!("{};", ddl))
+}
+
+/// The `create_hypertable` call for a TimescaleDB hypertable, or `None` for
+/// an ordinary table.
+async fn hypertable_ddl(
+    params: &ConnectionParams,
+    table_name: &str,
+    schema: &str,
+) -> Result<Option<String>, String> {
+    let pool = get_postgres_pool(params).await?;
+    let rows = query_all(
+        &pool,
+        "SELECT column_name::text, time_interval::text \
+FROM timescaledb_information.dimensions \
+WHERE hypertable_schema = $1 AND hypertable_name = $2 \
+ORDER BY dimension_number LIMIT 1",
+        &[&schema, &table_name],
+    )
+    .await?;
+    Ok(rows.first().map(|r| {
+        let time_column: String = r.try_get(0).unwrap_or_default();
+        let interval: Option<String> = r.try_get(1).unwrap_or_default();
+        compat::create_hypertable_sql(schema, table_name, &time_column, interval.as_deref())
+    }))
 }
 
 /// Acquires a single PostgreSQL client from the pool and applies the
@@ -748,6 +964,7 @@ async fn acquire_pg_client(
 async fn exec_on_pg_client(
     client: &tokio_postgres::Client,
     query: &str,
+    binds: &[&(dyn ToSql + Sync)],
     limit: Option<u32>,
     page: u32,
 ) -> Result<QueryResult, String> {
@@ -756,7 +973,7 @@ async fn exec_on_pg_client(
     // The fetch path below is reserved for SELECT-like statements.
     if !crate::drivers::common::returns_result_set(query) {
         let affected = client
-            .execute(query, &[])
+            .execute(query, binds)
             .await
             .map_err(|e| format_pg_error(&e))?;
         return Ok(QueryResult {
@@ -764,7 +981,10 @@ async fn exec_on_pg_client(
             rows: vec![],
             affected_rows: affected,
             truncated: false,
+            truncation: None,
             pagination: None,
+            style_hints: None,
+            provenance: None,
         });
     }
 
@@ -781,16 +1001,18 @@ async fn exec_on_pg_client(
         (query.to_string(), None)
     };
 
-    let pg_params: Vec<i32> = vec![];
     let mut rows_stream = std::pin::pin!(
         client
-            .query_raw(&final_query, &pg_params)
+            .query_raw(&final_query, binds.iter().copied())
             .await
             .map_err(|e| format_pg_error(&e))?
     );
 
     let mut columns: Vec<String> = Vec::new();
-    let mut json_rows = Vec::new();
+    let mut collector = crate::drivers::common::RowCollector::new(
+        crate::drivers::common::ResultLimits::from_config(),
+        extract::row_to_json,
+    );
 
     use futures::stream::StreamExt;
 
@@ -802,22 +1024,21 @@ async fn exec_on_pg_client(
                 }
 
                 if let Some(l) = manual_limit {
-                    if json_rows.len() >= l as usize {
+                    if collector.len() >= l as usize {
                         truncated = true;
                         break;
                     }
                 }
 
-                let mut json_row = Vec::new();
-                for (i, _) in row.columns().iter().enumerate() {
-                    let val = extract_value(&row, i, None);
-                    json_row.push(val);
+                // Converted to JSON in batches off the async executor
+                if !collector.push(row).await? {
+                    break;
                 }
-                json_rows.push(json_row);
             }
             Err(e) => return Err(format_pg_error(&e)),
         }
     }
+    let (mut json_rows, truncation) = collector.finish().await?;
 
     let pagination = if let Some((page_size, p)) = pagination_meta {
         let has_more = json_rows.len() > page_size as usize;
@@ -839,8 +1060,11 @@ async fn exec_on_pg_client(
         columns,
         rows: json_rows,
         affected_rows: 0,
-        truncated,
+        truncated: truncated || truncation.is_some(),
+        truncation,
         pagination,
+        style_hints: None,
+        provenance: None,
     })
 }
 
@@ -851,8 +1075,95 @@ pub async fn execute_query(
     page: u32,
     schema: Option<&str>,
 ) -> Result<QueryResult, String> {
+    if let Some(shared) = crate::pool_manager::active_transaction(params).await {
+        if let Some(TransactionConnection::Postgres(client)) = shared.lock().await.as_ref() {
+            set_local_search_path(client, schema).await?;
+            return exec_on_pg_client(client, query, &[], limit, page).await;
+        }
+    }
+
+    let client = acquire_pg_client(params, schema).await?;
+    exec_on_pg_client(&client, query, &[], limit, page).await
+}
+
+/// `execute_query` with `:name` / `$1` / `?` placeholders bound from
+/// `values`. The statement is prepared once to learn the type PostgreSQL
+/// infers for each parameter; every value is then sent as text and cast to
+/// that type, so `'2024-01-01'` binds to a date and `42` to a numeric
+/// column without an encoder per type.
+pub async fn execute_query_with_params(
+    params: &ConnectionParams,
+    query: &str,
+    values: &std::collections::HashMap<String, serde_json::Value>,
+    limit: Option<u32>,
+    page: u32,
+    schema: Option<&str>,
+) -> Result<QueryResult, String> {
+    let numbered = |i: usize| format!("${}", i);
+    let probe = crate::drivers::common::bind_query_params(
+        query,
+        values,
+        crate::drivers::common::PlaceholderStyle::Numbered(&numbered),
+    )?;
+    let texts: Vec<Option<String>> = probe.values.iter().map(value_as_text).collect();
+    let binds: Vec<&(dyn ToSql + Sync)> = texts.iter().map(|t| t as &(dyn ToSql + Sync)).collect();
+
+    if let Some(shared) = crate::pool_manager::active_transaction(params).await {
+        if let Some(TransactionConnection::Postgres(client)) = shared.lock().await.as_ref() {
+            set_local_search_path(client, schema).await?;
+            let sql = cast_placeholders(client, query, values, &probe.sql).await?;
+            return exec_on_pg_client(client, &sql, &binds, limit, page).await;
+        }
+    }
+
     let client = acquire_pg_client(params, schema).await?;
-    exec_on_pg_client(&client, query, limit, page).await
+    let sql = cast_placeholders(&client, query, values, &probe.sql).await?;
+    exec_on_pg_client(&client, &sql, &binds, limit, page).await
+}
+
+/// Renders `query` with each placeholder as `CAST($n::text AS <type>)`,
+/// using the parameter types PostgreSQL reports for `probe_sql`.
+async fn cast_placeholders(
+    client: &tokio_postgres::Client,
+    query: &str,
+    values: &std::collections::HashMap<String, serde_json::Value>,
+    probe_sql: &str,
+) -> Result<String, String> {
+    let statement = client
+        .prepare(probe_sql)
+        .await
+        .map_err(|e| format_pg_error(&e))?;
+    let types: Vec<String> = statement
+        .params()
+        .iter()
+        .map(|t| {
+            format!(
+                "\"{}\".\"{}\"",
+                escape_identifier(t.schema()),
+                escape_identifier(t.name())
+            )
+        })
+        .collect();
+    let render = |i: usize| match types.get(i - 1) {
+        Some(ty) => format!("CAST(${}::text AS {})", i, ty),
+        None => format!("${}::text", i),
+    };
+    let bound = crate::drivers::common::bind_query_params(
+        query,
+        values,
+        crate::drivers::common::PlaceholderStyle::Numbered(&render),
+    )?;
+    Ok(bound.sql)
+}
+
+/// Text form of a bound value; PostgreSQL parses it into the parameter's
+/// type. Objects and arrays are sent as JSON.
+fn value_as_text(value: &serde_json::Value) -> Option<String> {
+    match value {
+        serde_json::Value::Null => None,
+        serde_json::Value::String(s) => Some(s.clone()),
+        other => Some(other.to_string()),
+    }
 }
 
 /// Runs a sequence of statements on a single pooled client so
@@ -868,16 +1179,48 @@ pub async fn execute_batch(
     page: u32,
     schema: Option<&str>,
 ) -> Result<Vec<crate::models::BatchStatementResult>, String> {
+    if let Some(shared) = crate::pool_manager::active_transaction(params).await {
+        if let Some(TransactionConnection::Postgres(client)) = shared.lock().await.as_ref() {
+            set_local_search_path(client, schema).await?;
+            return Ok(run_batch_on_pg_client(client, queries, limit, page).await);
+        }
+    }
+
     let client = acquire_pg_client(params, schema).await?;
+    Ok(run_batch_on_pg_client(&client, queries, limit, page).await)
+}
+
+async fn run_batch_on_pg_client(
+    client: &tokio_postgres::Client,
+    queries: &[String],
+    limit: Option<u32>,
+    page: u32,
+) -> Vec<crate::models::BatchStatementResult> {
     let mut results = Vec::with_capacity(queries.len());
     for q in queries {
         let start = std::time::Instant::now();
-        let outcome = exec_on_pg_client(&client, q, limit, page).await;
+        let outcome = exec_on_pg_client(client, q, &[], limit, page).await;
         results.push(crate::models::BatchStatementResult::from_outcome(
             start, outcome,
         ));
     }
-    Ok(results)
+    results
+}
+
+/// Inside an open transaction the schema override uses `SET LOCAL`, so it
+/// ends with the transaction instead of sticking to the pooled connection.
+async fn set_local_search_path(
+    client: &tokio_postgres::Client,
+    schema: Option<&str>,
+) -> Result<(), String> {
+    if let Some(s) = schema {
+        let search_path = format!("SET LOCAL search_path TO \"{}\"", escape_identifier(s));
+        client
+            .execute(&search_path, &[])
+            .await
+            .map_err(|e| format_pg_error(&e))?;
+    }
+    Ok(())
 }
 
 pub async fn get_views(params: &ConnectionParams, schema: &str) -> Result<Vec<ViewInfo>, String> {
@@ -984,6 +1327,29 @@ pub async fn alter_view(
     Ok(())
 }
 
+pub async fn create_table_from_query(
+    params: &ConnectionParams,
+    table_name: &str,
+    query: &str,
+    schema: &str,
+) -> Result<(), String> {
+    let pool = get_postgres_pool(params).await?;
+    let statement = format!(
+        "CREATE TABLE \"{}\".\"{}\" AS {}",
+        escape_identifier(schema),
+        escape_identifier(table_name),
+        query
+    );
+
+    let client = pool.get().await.map_err(|e| e.to_string())?;
+    client
+        .execute(&statement, &[])
+        .await
+        .map_err(|e| format!("Failed to create table: {}", e))?;
+
+    Ok(())
+}
+
 pub async fn drop_view(
     params: &ConnectionParams,
     view_name: &str,
@@ -1329,7 +1695,11 @@ impl PostgresDriver {
                     no_connection_required: false,
                     manage_tables: true,
                     readonly: false,
+                    arrow: false,
                     triggers: true,
+                    privileges: true,
+                    server_status: true,
+                    table_stats: true,
                 },
                 is_builtin: true,
                 default_username: "postgres".to_string(),
@@ -1504,6 +1874,16 @@ impl DatabaseDriver for PostgresDriver {
         drop_view(params, view_name, self.resolve_schema(schema)).await
     }
 
+    async fn create_table_from_query(
+        &self,
+        params: &crate::models::ConnectionParams,
+        table_name: &str,
+        query: &str,
+        schema: Option<&str>,
+    ) -> Result<(), String> {
+        create_table_from_query(params, table_name, query, self.resolve_schema(schema)).await
+    }
+
     async fn get_routines(
         &self,
         params: &crate::models::ConnectionParams,
@@ -1574,6 +1954,97 @@ impl DatabaseDriver for PostgresDriver {
         drop_trigger(params, trigger_name, table_name, self.resolve_schema(schema)).await
     }
 
+    async fn get_server_version(
+        &self,
+        params: &crate::models::ConnectionParams,
+    ) -> Result<String, String> {
+        get_server_version(params).await
+    }
+
+    async fn get_server_extensions(
+        &self,
+        params: &crate::models::ConnectionParams,
+    ) -> Result<Vec<String>, String> {
+        get_server_extensions(params).await
+    }
+
+    async fn get_server_status(
+        &self,
+        params: &crate::models::ConnectionParams,
+    ) -> Result<Vec<crate::models::ServerValue>, String> {
+        status::get_server_status(params).await
+    }
+
+    async fn get_server_variables(
+        &self,
+        params: &crate::models::ConnectionParams,
+    ) -> Result<Vec<crate::models::ServerValue>, String> {
+        status::get_server_variables(params).await
+    }
+
+    async fn get_table_stats(
+        &self,
+        params: &crate::models::ConnectionParams,
+        schema: Option<&str>,
+    ) -> Result<Vec<crate::models::TableStats>, String> {
+        table_stats::get_table_stats(params, self.resolve_schema(schema)).await
+    }
+
+    async fn get_lock_waits(
+        &self,
+        params: &crate::models::ConnectionParams,
+    ) -> Result<Vec<crate::models::LockWait>, String> {
+        locks::get_lock_waits(params).await
+    }
+
+    async fn kill_session(
+        &self,
+        params: &crate::models::ConnectionParams,
+        pid: i64,
+    ) -> Result<(), String> {
+        locks::kill_session(params, pid).await
+    }
+
+    async fn get_users(
+        &self,
+        params: &crate::models::ConnectionParams,
+    ) -> Result<Vec<crate::models::DbUser>, String> {
+        privileges::get_users(params).await
+    }
+
+    async fn get_user_grants(
+        &self,
+        params: &crate::models::ConnectionParams,
+        user: &str,
+        _host: Option<&str>,
+    ) -> Result<Vec<crate::models::UserGrant>, String> {
+        privileges::get_user_grants(params, user).await
+    }
+
+    async fn grant_privilege(
+        &self,
+        params: &crate::models::ConnectionParams,
+        change: &crate::models::PrivilegeChange,
+    ) -> Result<(), String> {
+        privileges::grant_privilege(params, change).await
+    }
+
+    async fn revoke_privilege(
+        &self,
+        params: &crate::models::ConnectionParams,
+        change: &crate::models::PrivilegeChange,
+    ) -> Result<(), String> {
+        privileges::revoke_privilege(params, change).await
+    }
+
+    async fn get_schema_signature(
+        &self,
+        params: &crate::models::ConnectionParams,
+        schema: Option<&str>,
+    ) -> Result<Vec<crate::models::ColumnSignature>, String> {
+        signature::get_schema_signature(params, schema).await
+    }
+
     async fn execute_query(
         &self,
         params: &crate::models::ConnectionParams,
@@ -1596,6 +2067,35 @@ impl DatabaseDriver for PostgresDriver {
         execute_batch(params, queries, limit, page, schema).await
     }
 
+    async fn execute_query_with_params(
+        &self,
+        params: &crate::models::ConnectionParams,
+        query: &str,
+        values: &HashMap<String, serde_json::Value>,
+        limit: Option<u32>,
+        page: u32,
+        schema: Option<&str>,
+    ) -> Result<crate::models::QueryResult, String> {
+        execute_query_with_params(params, query, values, limit, page, schema).await
+    }
+
+    async fn execute_query_stream(
+        &self,
+        params: &crate::models::ConnectionParams,
+        query: &str,
+        batch_size: usize,
+        schema: Option<&str>,
+        on_batch: &mut crate::drivers::common::RowBatchCallback<'_>,
+    ) -> Result<u64, String> {
+        let client = acquire_pg_client(params, schema).await?;
+        let mut batcher = crate::drivers::common::RowBatcher::new(batch_size, on_batch);
+        export::stream_query_on_client(&client, query, |headers, values| {
+            batcher.push(headers, values.to_vec())
+        })
+        .await?;
+        batcher.finish()
+    }
+
     async fn explain_query(
         &self,
         params: &crate::models::ConnectionParams,
@@ -1624,6 +2124,53 @@ impl DatabaseDriver for PostgresDriver {
         .await
     }
 
+    async fn insert_record_returning(
+        &self,
+        params: &crate::models::ConnectionParams,
+        table: &str,
+        data: std::collections::HashMap<String, serde_json::Value>,
+        schema: Option<&str>,
+        max_blob_size: u64,
+    ) -> Result<InsertedRecord, String> {
+        insert_record_returning(
+            params,
+            table,
+            data,
+            self.resolve_schema(schema),
+            max_blob_size,
+        )
+        .await
+    }
+
+    fn insert_batch_limits(&self) -> Option<crate::drivers::common::InsertBatchLimits> {
+        Some(crate::drivers::common::InsertBatchLimits {
+            max_rows: 1000,
+            // Bind parameter count is a u16 in the extended query protocol.
+            max_params: 65_535,
+            max_bytes: 16 * 1024 * 1024,
+        })
+    }
+
+    async fn insert_rows(
+        &self,
+        params: &crate::models::ConnectionParams,
+        table: &str,
+        columns: &[String],
+        rows: Vec<std::collections::HashMap<String, serde_json::Value>>,
+        schema: Option<&str>,
+        max_blob_size: u64,
+    ) -> Result<u64, String> {
+        insert_rows(
+            params,
+            table,
+            columns,
+            rows,
+            self.resolve_schema(schema),
+            max_blob_size,
+        )
+        .await
+    }
+
     async fn update_record(
         &self,
         params: &crate::models::ConnectionParams,
@@ -1701,6 +2248,15 @@ impl DatabaseDriver for PostgresDriver {
         .await
     }
 
+    async fn get_table_ddl(
+        &self,
+        params: &crate::models::ConnectionParams,
+        table: &str,
+        schema: Option<&str>,
+    ) -> Result<String, String> {
+        get_table_ddl(params, table, self.resolve_schema(schema)).await
+    }
+
     async fn get_create_table_sql(
         &self,
         table_name: &str,
@@ -1708,18 +2264,12 @@ impl DatabaseDriver for PostgresDriver {
         schema: Option<&str>,
     ) -> Result<Vec<String>, String> {
         let pg_schema = self.resolve_schema(schema);
+        let identity = identity_columns();
         let mut col_defs = Vec::new();
         let mut pk_cols = Vec::new();
         for col in &columns {
             let type_str = if col.is_auto_increment {
-                let upper = col.data_type.to_uppercase();
-                if upper.contains("BIGINT") || upper.contains("BIGSERIAL") {
-                    "BIGSERIAL".to_string()
-                } else if upper.contains("SMALLINT") || upper.contains("SMALLSERIAL") {
-                    "SMALLSERIAL".to_string()
-                } else {
-                    "SERIAL".to_string()
-                }
+                auto_increment_type(&col.data_type, identity)
             } else {
                 col.data_type.clone()
             };
@@ -1761,14 +2311,7 @@ impl DatabaseDriver for PostgresDriver {
             table.replace('"', "\"\"")
         );
         let type_str = if column.is_auto_increment {
-            let upper = column.data_type.to_uppercase();
-            if upper.contains("BIGINT") || upper.contains("BIGSERIAL") {
-                "BIGSERIAL".to_string()
-            } else if upper.contains("SMALLINT") || upper.contains("SMALLSERIAL") {
-                "SMALLSERIAL".to_string()
-            } else {
-                "SERIAL".to_string()
-            }
+            auto_increment_type(&column.data_type, identity_columns())
         } else {
             column.data_type.clone()
         };
@@ -1976,6 +2519,14 @@ impl DatabaseDriver for PostgresDriver {
         get_all_foreign_keys_batch(params, self.resolve_schema(schema)).await
     }
 
+    async fn get_all_indexes_batch(
+        &self,
+        params: &crate::models::ConnectionParams,
+        schema: Option<&str>,
+    ) -> Result<HashMap<String, Vec<crate::models::Index>>, String> {
+        get_all_indexes_batch(params, self.resolve_schema(schema)).await
+    }
+
     async fn get_schema_snapshot(
         &self,
         params: &crate::models::ConnectionParams,
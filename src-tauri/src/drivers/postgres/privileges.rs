@@ -0,0 +1,162 @@
+use super::client::{execute, format_pg_error, query_all};
+use super::helpers::escape_identifier;
+use crate::models::{ConnectionParams, DbUser, PrivilegeChange, PrivilegeScope, UserGrant};
+use crate::pool_manager::get_postgres_pool;
+use crate::privileges::normalize_privilege;
+
+/// Database, schema and relation privileges granted directly to one role,
+/// read from the ACLs so that grants made by other roles show up too.
+const GRANTS_QUERY: &str = r#"
+    SELECT 'database' AS scope, d.datname::text AS database, NULL::text AS schema,
+        NULL::text AS tbl, a.privilege_type, a.is_grantable
+    FROM pg_database d
+    CROSS JOIN LATERAL aclexplode(d.datacl) a
+    JOIN pg_roles r ON r.oid = a.grantee
+    WHERE r.rolname::text = $1
+    UNION ALL
+    SELECT 'schema', current_database()::text, n.nspname::text, NULL,
+        a.privilege_type, a.is_grantable
+    FROM pg_namespace n
+    CROSS JOIN LATERAL aclexplode(n.nspacl) a
+    JOIN pg_roles r ON r.oid = a.grantee
+    WHERE r.rolname::text = $1
+    UNION ALL
+    SELECT 'table', current_database()::text, n.nspname::text, c.relname::text,
+        a.privilege_type, a.is_grantable
+    FROM pg_class c
+    JOIN pg_namespace n ON n.oid = c.relnamespace
+    CROSS JOIN LATERAL aclexplode(c.relacl) a
+    JOIN pg_roles r ON r.oid = a.grantee
+    WHERE r.rolname::text = $1 AND c.relkind IN ('r', 'v', 'm', 'p', 'f')
+    ORDER BY 1, 2, 3, 4, 5
+"#;
+
+fn quote(name: &str) -> String {
+    format!("\"{}\"", escape_identifier(name))
+}
+
+fn grant_target(grant: &UserGrant) -> Result<String, String> {
+    let required = |value: &Option<String>, what: &str| {
+        value
+            .as_deref()
+            .map(quote)
+            .ok_or_else(|| format!("A {} is required for this privilege", what))
+    };
+    match grant.scope {
+        PrivilegeScope::Database => Ok(format!(
+            "DATABASE {}",
+            required(&grant.database, "database")?
+        )),
+        PrivilegeScope::Schema => Ok(format!("SCHEMA {}", required(&grant.schema, "schema")?)),
+        PrivilegeScope::Table => {
+            let table = required(&grant.table, "table")?;
+            Ok(match &grant.schema {
+                Some(schema) => format!("TABLE {}.{}", quote(schema), table),
+                None => format!("TABLE {}", table),
+            })
+        }
+        PrivilegeScope::Global => {
+            Err("PostgreSQL has no global privileges; use role attributes instead".to_string())
+        }
+    }
+}
+
+pub(super) fn grant_sql(change: &PrivilegeChange) -> Result<String, String> {
+    let mut sql = format!(
+        "GRANT {} ON {} TO {}",
+        normalize_privilege(&change.grant.privilege)?,
+        grant_target(&change.grant)?,
+        quote(&change.user)
+    );
+    if change.grant.grantable {
+        sql.push_str(" WITH GRANT OPTION");
+    }
+    Ok(sql)
+}
+
+/// Revoking a privilege also revokes its grant option.
+pub(super) fn revoke_sql(change: &PrivilegeChange) -> Result<String, String> {
+    Ok(format!(
+        "REVOKE {} ON {} FROM {}",
+        normalize_privilege(&change.grant.privilege)?,
+        grant_target(&change.grant)?,
+        quote(&change.user)
+    ))
+}
+
+/// Roles other than the built-in `pg_*` ones.
+pub async fn get_users(params: &ConnectionParams) -> Result<Vec<DbUser>, String> {
+    let pool = get_postgres_pool(params).await?;
+    let query = r#"
+        SELECT rolname::text AS name, rolcanlogin, rolsuper
+        FROM pg_roles
+        WHERE rolname !~ '^pg_'
+        ORDER BY rolname
+    "#;
+    let rows = query_all(&pool, query, &[]).await?;
+    rows.iter()
+        .map(|r| {
+            Ok(DbUser {
+                name: r.try_get("name").map_err(|e| format_pg_error(&e))?,
+                host: None,
+                can_login: r.try_get("rolcanlogin").unwrap_or_default(),
+                superuser: r.try_get("rolsuper").unwrap_or_default(),
+            })
+        })
+        .collect()
+}
+
+pub async fn get_user_grants(
+    params: &ConnectionParams,
+    user: &str,
+) -> Result<Vec<UserGrant>, String> {
+    if let Some(info) = crate::server_info::for_params(params) {
+        if !info.features.catalog_acls {
+            return Err(format!(
+                "Listing grants is not supported on {}",
+                info.flavor.name()
+            ));
+        }
+    }
+    let pool = get_postgres_pool(params).await?;
+    let rows = query_all(&pool, GRANTS_QUERY, &[&user]).await?;
+    rows.iter()
+        .map(|r| {
+            let scope: String = r.try_get("scope").map_err(|e| format_pg_error(&e))?;
+            Ok(UserGrant {
+                privilege: r
+                    .try_get("privilege_type")
+                    .map_err(|e| format_pg_error(&e))?,
+                scope: match scope.as_str() {
+                    "database" => PrivilegeScope::Database,
+                    "schema" => PrivilegeScope::Schema,
+                    _ => PrivilegeScope::Table,
+                },
+                database: r.try_get("database").unwrap_or_default(),
+                schema: r.try_get("schema").unwrap_or_default(),
+                table: r.try_get("tbl").unwrap_or_default(),
+                grantable: r.try_get("is_grantable").unwrap_or_default(),
+            })
+        })
+        .collect()
+}
+
+pub async fn grant_privilege(
+    params: &ConnectionParams,
+    change: &PrivilegeChange,
+) -> Result<(), String> {
+    let sql = grant_sql(change)?;
+    let pool = get_postgres_pool(params).await?;
+    execute(&pool, &sql, &[]).await?;
+    Ok(())
+}
+
+pub async fn revoke_privilege(
+    params: &ConnectionParams,
+    change: &PrivilegeChange,
+) -> Result<(), String> {
+    let sql = revoke_sql(change)?;
+    let pool = get_postgres_pool(params).await?;
+    execute(&pool, &sql, &[]).await?;
+    Ok(())
+}
@@ -0,0 +1,32 @@
+use super::client::query_all;
+use crate::models::{ColumnSignature, ConnectionParams};
+use crate::pool_manager::get_postgres_pool;
+
+/// Columns of every user schema (or only `schema`), tables as `schema.table`.
+pub async fn get_schema_signature(
+    params: &ConnectionParams,
+    schema: Option<&str>,
+) -> Result<Vec<ColumnSignature>, String> {
+    let pool = get_postgres_pool(params).await?;
+    let query = r#"
+        SELECT
+            (table_schema || '.' || table_name)::text AS table_name,
+            column_name::text AS column_name,
+            data_type::text AS data_type
+        FROM information_schema.columns
+        WHERE ($1::text IS NULL AND table_schema NOT IN ('pg_catalog', 'information_schema')
+                AND table_schema NOT LIKE 'pg\_%')
+            OR table_schema = $1::text
+        ORDER BY 1, 2
+    "#;
+    let rows = query_all(&pool, query, &[&schema]).await?;
+
+    Ok(rows
+        .iter()
+        .map(|r| ColumnSignature {
+            table: r.try_get("table_name").unwrap_or_default(),
+            column: r.try_get("column_name").unwrap_or_default(),
+            data_type: r.try_get("data_type").unwrap_or_default(),
+        })
+        .collect())
+}
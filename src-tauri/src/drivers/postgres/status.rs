@@ -0,0 +1,92 @@
+use super::client::{format_pg_error, query_all, query_one};
+use super::compat::{self, Profile};
+use crate::models::{ConnectionParams, ServerValue};
+use crate::pool_manager::get_postgres_pool;
+use deadpool_postgres::Pool as PgPool;
+
+/// The current database's row of `pg_stat_database` as JSON, so that the
+/// columns added by newer versions come along without naming them.
+const DATABASE_STATS_QUERY: &str = r#"
+    SELECT row_to_json(s)::text
+    FROM pg_stat_database s
+    WHERE datname = current_database()
+"#;
+
+/// CockroachDB keeps no `pg_stat_database` statistics; its node metrics
+/// take their place.
+const COCKROACH_METRICS_QUERY: &str = r#"
+    SELECT name, value::text AS value
+    FROM crdb_internal.node_metrics
+    ORDER BY name
+"#;
+
+const SETTINGS_QUERY: &str = r#"
+    SELECT name, setting, unit, category, short_desc
+    FROM pg_settings
+    ORDER BY name
+"#;
+
+/// One value per column of a `pg_stat_database` row; columns that are NULL
+/// (e.g. `stats_reset` before the first reset) are left out.
+pub(super) fn database_stats(row_json: &str) -> Result<Vec<ServerValue>, String> {
+    let row: serde_json::Map<String, serde_json::Value> = serde_json::from_str(row_json)
+        .map_err(|e| format!("Unexpected pg_stat_database row: {}", e))?;
+    Ok(row
+        .into_iter()
+        .filter_map(|(name, value)| {
+            let value = match value {
+                serde_json::Value::Null => return None,
+                serde_json::Value::String(s) => s,
+                other => other.to_string(),
+            };
+            let mut stat = ServerValue::new(name, value);
+            stat.category = Some("pg_stat_database".to_string());
+            Some(stat)
+        })
+        .collect())
+}
+
+/// Rows of a query whose first two columns are a name and a text value.
+async fn name_values(pool: &PgPool, sql: &str) -> Result<Vec<ServerValue>, String> {
+    let rows = query_all(pool, sql, &[]).await?;
+    rows.iter()
+        .map(|r| {
+            Ok(ServerValue::new(
+                r.try_get::<_, String>(0).map_err(|e| format_pg_error(&e))?,
+                r.try_get::<_, Option<String>>(1)
+                    .unwrap_or_default()
+                    .unwrap_or_default(),
+            ))
+        })
+        .collect()
+}
+
+pub async fn get_server_status(params: &ConnectionParams) -> Result<Vec<ServerValue>, String> {
+    let pool = get_postgres_pool(params).await?;
+    if compat::profile(params) == Profile::Cockroach {
+        return name_values(&pool, COCKROACH_METRICS_QUERY).await;
+    }
+    let row = query_one(&pool, DATABASE_STATS_QUERY, &[]).await?;
+    let row_json: String = row.try_get(0).map_err(|e| format_pg_error(&e))?;
+    database_stats(&row_json)
+}
+
+pub async fn get_server_variables(params: &ConnectionParams) -> Result<Vec<ServerValue>, String> {
+    let pool = get_postgres_pool(params).await?;
+    if compat::profile(params) == Profile::Cockroach {
+        // Session variables; cluster settings need the admin role.
+        return name_values(&pool, "SHOW ALL").await;
+    }
+    let rows = query_all(&pool, SETTINGS_QUERY, &[]).await?;
+    rows.iter()
+        .map(|r| {
+            Ok(ServerValue {
+                name: r.try_get("name").map_err(|e| format_pg_error(&e))?,
+                value: r.try_get("setting").unwrap_or_default(),
+                unit: r.try_get("unit").unwrap_or_default(),
+                category: r.try_get("category").unwrap_or_default(),
+                description: r.try_get("short_desc").unwrap_or_default(),
+            })
+        })
+        .collect()
+}
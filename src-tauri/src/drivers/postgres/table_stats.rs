@@ -0,0 +1,39 @@
+use super::client::{format_pg_error, query_all};
+use super::compat;
+use crate::models::{ConnectionParams, TableStats};
+use crate::pool_manager::get_postgres_pool;
+
+/// Tables, partitioned tables and materialized views of one schema.
+/// `reltuples` is -1 (PostgreSQL 14+) for a table never analyzed.
+const TABLE_STATS_QUERY: &str = r#"
+    SELECT c.relname::text AS name,
+        CASE WHEN c.reltuples < 0 THEN NULL ELSE c.reltuples::int8 END AS row_estimate,
+        pg_table_size(c.oid)::int8 AS data_bytes,
+        pg_indexes_size(c.oid)::int8 AS index_bytes,
+        GREATEST(s.last_analyze, s.last_autoanalyze)::text AS last_analyzed
+    FROM pg_class c
+    JOIN pg_namespace n ON n.oid = c.relnamespace
+    LEFT JOIN pg_stat_user_tables s ON s.relid = c.oid
+    WHERE n.nspname = $1 AND c.relkind IN ('r', 'p', 'm')
+    ORDER BY c.relname
+"#;
+
+pub async fn get_table_stats(
+    params: &ConnectionParams,
+    schema: &str,
+) -> Result<Vec<TableStats>, String> {
+    compat::ensure_supported(compat::profile(params), "Table statistics")?;
+    let pool = get_postgres_pool(params).await?;
+    let rows = query_all(&pool, TABLE_STATS_QUERY, &[&schema]).await?;
+    rows.iter()
+        .map(|r| {
+            Ok(TableStats {
+                name: r.try_get("name").map_err(|e| format_pg_error(&e))?,
+                row_estimate: r.try_get("row_estimate").unwrap_or_default(),
+                data_bytes: r.try_get("data_bytes").unwrap_or_default(),
+                index_bytes: r.try_get("index_bytes").unwrap_or_default(),
+                last_analyzed: r.try_get("last_analyzed").unwrap_or_default(),
+            })
+        })
+        .collect()
+}
@@ -2,7 +2,12 @@ use super::binding::{
     PgValueOptions, bind_pg_boolean_string, bind_pg_number, bind_pg_numeric_string, bind_pg_value,
     build_pk_predicate,
 };
-use super::helpers::{extract_base_type, is_implicit_cast_compatible};
+use super::compat::{
+    create_hypertable_sql, ensure_supported, hide_internal_schemas, profile_of, Profile,
+};
+use super::helpers::{auto_increment_type, extract_base_type, is_implicit_cast_compatible};
+use super::privileges::{grant_sql, revoke_sql};
+use super::status::database_stats;
 
 mod extract_base_type_tests {
     use super::*;
@@ -162,6 +167,33 @@ mod is_implicit_cast_compatible_tests {
     }
 }
 
+mod auto_increment_type_tests {
+    use super::*;
+
+    #[test]
+    fn serial_types_without_identity_columns() {
+        assert_eq!(auto_increment_type("INTEGER", false), "SERIAL");
+        assert_eq!(auto_increment_type("bigint", false), "BIGSERIAL");
+        assert_eq!(auto_increment_type("SMALLSERIAL", false), "SMALLSERIAL");
+    }
+
+    #[test]
+    fn identity_columns_keep_the_integer_width() {
+        assert_eq!(
+            auto_increment_type("INTEGER", true),
+            "INTEGER GENERATED BY DEFAULT AS IDENTITY"
+        );
+        assert_eq!(
+            auto_increment_type("BIGSERIAL", true),
+            "BIGINT GENERATED BY DEFAULT AS IDENTITY"
+        );
+        assert_eq!(
+            auto_increment_type("smallint", true),
+            "SMALLINT GENERATED BY DEFAULT AS IDENTITY"
+        );
+    }
+}
+
 mod pg_number_binding_tests {
     use super::*;
 
@@ -536,3 +568,150 @@ mod build_pk_predicate_tests {
         assert!(build_pk_predicate("id", serde_json::json!(true), 1).is_err());
     }
 }
+
+mod privilege_sql_tests {
+    use super::*;
+    use crate::models::{PrivilegeChange, PrivilegeScope, UserGrant};
+
+    fn change(scope: PrivilegeScope, schema: Option<&str>, table: Option<&str>) -> PrivilegeChange {
+        PrivilegeChange {
+            user: "report\"er".to_string(),
+            host: None,
+            grant: UserGrant {
+                privilege: "usage".to_string(),
+                scope,
+                database: Some("shop".to_string()),
+                schema: schema.map(str::to_string),
+                table: table.map(str::to_string),
+                grantable: false,
+            },
+        }
+    }
+
+    #[test]
+    fn grant_quotes_objects_and_role() {
+        assert_eq!(
+            grant_sql(&change(PrivilegeScope::Schema, Some("sales"), None)).unwrap(),
+            r#"GRANT USAGE ON SCHEMA "sales" TO "report""er""#
+        );
+        assert_eq!(
+            grant_sql(&change(PrivilegeScope::Database, None, None)).unwrap(),
+            r#"GRANT USAGE ON DATABASE "shop" TO "report""er""#
+        );
+    }
+
+    #[test]
+    fn table_grant_with_grant_option() {
+        let mut grant = change(PrivilegeScope::Table, Some("sales"), Some("orders"));
+        grant.grant.privilege = "all   privileges".to_string();
+        grant.grant.grantable = true;
+        assert_eq!(
+            grant_sql(&grant).unwrap(),
+            r#"GRANT ALL PRIVILEGES ON TABLE "sales"."orders" TO "report""er" WITH GRANT OPTION"#
+        );
+        assert_eq!(
+            revoke_sql(&grant).unwrap(),
+            r#"REVOKE ALL PRIVILEGES ON TABLE "sales"."orders" FROM "report""er""#
+        );
+    }
+
+    #[test]
+    fn global_scope_is_rejected() {
+        assert!(grant_sql(&change(PrivilegeScope::Global, None, None)).is_err());
+        assert!(grant_sql(&change(PrivilegeScope::Schema, None, None)).is_err());
+    }
+}
+
+mod database_stats_tests {
+    use super::*;
+
+    #[test]
+    fn every_column_becomes_a_value() {
+        let stats = database_stats(
+            r#"{"datid":16384,"datname":"shop","numbackends":3,"blks_hit":1200,"stats_reset":null}"#,
+        )
+        .unwrap();
+        let pairs: Vec<(&str, &str)> = stats
+            .iter()
+            .map(|s| (s.name.as_str(), s.value.as_str()))
+            .collect();
+        assert_eq!(
+            pairs,
+            [
+                ("blks_hit", "1200"),
+                ("datid", "16384"),
+                ("datname", "shop"),
+                ("numbackends", "3"),
+            ]
+        );
+        assert!(stats
+            .iter()
+            .all(|s| s.category.as_deref() == Some("pg_stat_database")));
+    }
+
+    #[test]
+    fn malformed_rows_are_rejected() {
+        assert!(database_stats("not json").is_err());
+    }
+}
+
+mod compat_tests {
+    use super::*;
+    use crate::server_info::detect;
+
+    fn schemas(names: &[&str]) -> Vec<String> {
+        names.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn profile_follows_the_detected_server() {
+        assert_eq!(profile_of(None), Profile::Postgres);
+
+        let postgres = detect("postgres", "PostgreSQL 16.2");
+        assert_eq!(profile_of(Some(&postgres)), Profile::Postgres);
+
+        let cockroach = detect("postgres", "CockroachDB CCL v23.1.11");
+        assert_eq!(profile_of(Some(&cockroach)), Profile::Cockroach);
+
+        let mut timescale = detect("postgres", "PostgreSQL 15.4");
+        timescale.extensions = vec!["plpgsql".into(), "timescaledb".into()];
+        assert_eq!(profile_of(Some(&timescale)), Profile::Timescale);
+    }
+
+    #[test]
+    fn internal_schemas_are_hidden_per_profile() {
+        let all = schemas(&["_timescaledb_internal", "crdb_internal", "public"]);
+        assert_eq!(hide_internal_schemas(Profile::Postgres, all.clone()), all);
+        assert_eq!(
+            hide_internal_schemas(Profile::Timescale, all.clone()),
+            schemas(&["crdb_internal", "public"])
+        );
+        assert_eq!(
+            hide_internal_schemas(Profile::Cockroach, all),
+            schemas(&["_timescaledb_internal", "public"])
+        );
+    }
+
+    #[test]
+    fn cockroach_rejects_unsupported_features_by_name() {
+        assert!(ensure_supported(Profile::Postgres, "Lock inspection").is_ok());
+        assert!(ensure_supported(Profile::Timescale, "Lock inspection").is_ok());
+        assert_eq!(
+            ensure_supported(Profile::Cockroach, "Lock inspection").unwrap_err(),
+            "Lock inspection is not available on CockroachDB"
+        );
+    }
+
+    #[test]
+    fn hypertable_call_quotes_the_relation() {
+        assert_eq!(
+            create_hypertable_sql("public", "conditions", "time", Some("7 days")),
+            "SELECT create_hypertable('\"public\".\"conditions\"', 'time', \
+chunk_time_interval => INTERVAL '7 days');"
+        );
+        assert_eq!(
+            create_hypertable_sql("o'neil", "events", "ts", None),
+            "SELECT create_hypertable('\"o''neil\".\"events\"', 'ts');"
+        );
+    }
+}
@@ -47,6 +47,13 @@ pub async fn unregister_driver(id: &str) -> bool {
     }
 }
 
+/// Shut down the background process of every registered driver, all at
+/// once. Called on application exit; the drivers stay registered.
+pub async fn shutdown_all() {
+    let drivers: Vec<Arc<dyn DatabaseDriver>> = REGISTRY.read().await.values().cloned().collect();
+    futures::future::join_all(drivers.iter().map(|d| d.shutdown())).await;
+}
+
 /// Register the manifest of a UI-only plugin (no driver process).
 pub async fn register_manifest(manifest: PluginManifest) {
     let id = manifest.id.clone();
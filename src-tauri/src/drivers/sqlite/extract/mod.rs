@@ -6,6 +6,13 @@ mod tests;
 
 use sqlx::Row;
 
+/// All values of `row`, in column order.
+pub fn row_to_json(row: &sqlx::sqlite::SqliteRow) -> Vec<serde_json::Value> {
+    (0..row.len())
+        .map(|i| extract_value(row, i, None))
+        .collect()
+}
+
 /// Extract value from SQLite row
 pub fn extract_value(
     row: &sqlx::sqlite::SqliteRow,
@@ -3,15 +3,16 @@ pub mod extract;
 pub mod types;
 
 mod explain;
+mod table_stats;
 
 #[cfg(test)]
 mod tests;
 
 use crate::models::{
-    ConnectionParams, ForeignKey, Index, Pagination, QueryResult, RoutineInfo, RoutineParameter,
-    TableColumn, TableInfo, TriggerInfo, ViewInfo,
+    ConnectionParams, ForeignKey, Index, InsertedRecord, Pagination, QueryResult, RoutineInfo,
+    RoutineParameter, TableColumn, TableInfo, TriggerInfo, ViewInfo,
 };
-use crate::pool_manager::get_sqlite_pool;
+use crate::pool_manager::{get_sqlite_pool, TransactionConnection};
 use extract::extract_value;
 use sqlx::{Column, Row};
 
@@ -31,6 +32,15 @@ pub async fn get_databases(_params: &ConnectionParams) -> Result<Vec<String>, St
     Ok(vec![])
 }
 
+pub async fn get_server_version(params: &ConnectionParams) -> Result<String, String> {
+    let pool = get_sqlite_pool(params).await?;
+    let row = sqlx::query("SELECT sqlite_version()")
+        .fetch_one(&pool)
+        .await
+        .map_err(|e| e.to_string())?;
+    row.try_get(0).map_err(|e| e.to_string())
+}
+
 pub async fn get_tables(params: &ConnectionParams) -> Result<Vec<TableInfo>, String> {
     log::debug!("SQLite: Fetching tables for database: {}", params.database);
     let pool = get_sqlite_pool(params).await?;
@@ -335,23 +345,21 @@ pub async fn delete_record(
     pk_col: &str,
     pk_val: serde_json::Value,
 ) -> Result<u64, String> {
-    let pool = get_sqlite_pool(params).await?;
-
-    let query = format!("DELETE FROM \"{}\" WHERE \"{}\" = ?", table, pk_col);
+    let sql = format!("DELETE FROM \"{}\" WHERE \"{}\" = ?", table, pk_col);
 
-    let result = match pk_val {
+    let query = match pk_val {
         serde_json::Value::Number(n) => {
             if n.is_i64() {
-                sqlx::query(&query).bind(n.as_i64()).execute(&pool).await
+                sqlx::query(&sql).bind(n.as_i64())
             } else {
-                sqlx::query(&query).bind(n.as_f64()).execute(&pool).await
+                sqlx::query(&sql).bind(n.as_f64())
             }
         }
-        serde_json::Value::String(s) => sqlx::query(&query).bind(s).execute(&pool).await,
+        serde_json::Value::String(s) => sqlx::query(&sql).bind(s),
         _ => return Err("Unsupported PK type".into()),
     };
 
-    result.map(|r| r.rows_affected()).map_err(|e| e.to_string())
+    execute_write(params, query).await
 }
 
 pub async fn update_record(
@@ -363,8 +371,6 @@ pub async fn update_record(
     new_val: serde_json::Value,
     max_blob_size: u64,
 ) -> Result<u64, String> {
-    let pool = get_sqlite_pool(params).await?;
-
     let mut qb = sqlx::QueryBuilder::new(format!("UPDATE \"{}\" SET \"{}\" = ", table, col_name));
 
     match new_val {
@@ -414,9 +420,7 @@ pub async fn update_record(
         _ => return Err("Unsupported PK type".into()),
     }
 
-    let query = qb.build();
-    let result = query.execute(&pool).await.map_err(|e| e.to_string())?;
-    Ok(result.rows_affected())
+    execute_write(params, qb.build()).await
 }
 
 pub async fn insert_record(
@@ -425,34 +429,86 @@ pub async fn insert_record(
     data: std::collections::HashMap<String, serde_json::Value>,
     max_blob_size: u64,
 ) -> Result<u64, String> {
-    let pool = get_sqlite_pool(params).await?;
-
-    let mut cols = Vec::new();
-    let mut vals = Vec::new();
-
-    for (k, v) in data {
-        cols.push(format!("\"{}\"", k));
-        vals.push(v);
+    // Allow empty inserts for auto-generated values (e.g., auto-increment PKs)
+    if data.is_empty() {
+        let query = format!("INSERT INTO \"{}\" DEFAULT VALUES", table);
+        return execute_write(params, sqlx::query(&query)).await;
     }
 
-    // Allow empty inserts for auto-generated values (e.g., auto-increment PKs)
-    let mut qb = if cols.is_empty() {
+    let columns: Vec<String> = data.keys().cloned().collect();
+    insert_rows(params, table, &columns, vec![data], max_blob_size).await
+}
+
+/// Insert one row with `RETURNING *` (SQLite 3.35+) so generated keys and
+/// defaults come back with it.
+pub async fn insert_record_returning(
+    params: &ConnectionParams,
+    table: &str,
+    data: std::collections::HashMap<String, serde_json::Value>,
+    max_blob_size: u64,
+) -> Result<InsertedRecord, String> {
+    let mut qb = if data.is_empty() {
         sqlx::QueryBuilder::new(format!("INSERT INTO \"{}\" DEFAULT VALUES", table))
     } else {
-        let mut qb = sqlx::QueryBuilder::new(format!(
-            "INSERT INTO \"{}\" ({}) VALUES (",
-            table,
-            cols.join(", ")
-        ));
+        let columns: Vec<String> = data.keys().cloned().collect();
+        build_insert(table, &columns, vec![data], max_blob_size)?
+    };
+    qb.push(" RETURNING *");
+
+    let rows = fetch_write(params, qb.build()).await?;
+    Ok(InsertedRecord {
+        affected_rows: rows.len() as u64,
+        row: rows.first().map(|row| {
+            row.columns()
+                .iter()
+                .enumerate()
+                .map(|(i, col)| (col.name().to_string(), extract_value(row, i, None)))
+                .collect()
+        }),
+    })
+}
+
+/// Insert `rows` with a single multi-row `INSERT ... VALUES (...), (...)`.
+/// Cells missing from a row are written as `NULL`.
+pub async fn insert_rows(
+    params: &ConnectionParams,
+    table: &str,
+    columns: &[String],
+    rows: Vec<std::collections::HashMap<String, serde_json::Value>>,
+    max_blob_size: u64,
+) -> Result<u64, String> {
+    let mut qb = build_insert(table, columns, rows, max_blob_size)?;
+    execute_write(params, qb.build()).await
+}
 
-        let mut separated = qb.separated(", ");
-        for val in vals {
-            match val {
+fn build_insert(
+    table: &str,
+    columns: &[String],
+    rows: Vec<std::collections::HashMap<String, serde_json::Value>>,
+    max_blob_size: u64,
+) -> Result<sqlx::QueryBuilder<'static, sqlx::Sqlite>, String> {
+    let cols: Vec<String> = columns.iter().map(|c| format!("\"{}\"", c)).collect();
+    let mut qb = sqlx::QueryBuilder::new(format!(
+        "INSERT INTO \"{}\" ({}) VALUES ",
+        table,
+        cols.join(", ")
+    ));
+
+    for (row_idx, mut row) in rows.into_iter().enumerate() {
+        if row_idx > 0 {
+            qb.push(", ");
+        }
+        qb.push("(");
+        for (col_idx, col) in columns.iter().enumerate() {
+            if col_idx > 0 {
+                qb.push(", ");
+            }
+            match row.remove(col).unwrap_or(serde_json::Value::Null) {
                 serde_json::Value::Number(n) => {
                     if n.is_i64() {
-                        separated.push_bind(n.as_i64());
+                        qb.push_bind(n.as_i64());
                     } else {
-                        separated.push_bind(n.as_f64());
+                        qb.push_bind(n.as_f64());
                     }
                 }
                 serde_json::Value::String(s) => {
@@ -460,29 +516,59 @@ pub async fn insert_record(
                         crate::drivers::common::decode_blob_wire_format(&s, max_blob_size)
                     {
                         // Blob wire format: decode to raw bytes so the DB stores binary data.
-                        separated.push_bind(bytes);
+                        qb.push_bind(bytes);
                     } else {
-                        separated.push_bind(s);
+                        qb.push_bind(s);
                     }
                 }
                 serde_json::Value::Bool(b) => {
-                    separated.push_bind(b);
+                    qb.push_bind(b);
                 }
                 serde_json::Value::Null => {
-                    separated.push("NULL");
+                    qb.push("NULL");
                 }
                 _ => return Err("Unsupported value type".into()),
             }
         }
-        separated.push_unseparated(")");
-        qb
-    };
+        qb.push(")");
+    }
+
+    Ok(qb)
+}
 
-    let query = qb.build();
+/// Run a data-modifying statement inside the transaction open on this
+/// connection, or on the pool when there is none.
+async fn execute_write(
+    params: &ConnectionParams,
+    query: sqlx::query::Query<'_, sqlx::Sqlite, sqlx::sqlite::SqliteArguments<'_>>,
+) -> Result<u64, String> {
+    if let Some(shared) = crate::pool_manager::active_transaction(params).await {
+        if let Some(TransactionConnection::Sqlite(tx)) = shared.lock().await.as_mut() {
+            let result = query.execute(&mut **tx).await.map_err(|e| e.to_string())?;
+            return Ok(result.rows_affected());
+        }
+    }
+
+    let pool = get_sqlite_pool(params).await?;
     let result = query.execute(&pool).await.map_err(|e| e.to_string())?;
     Ok(result.rows_affected())
 }
 
+/// Like `execute_write`, for statements that return rows (`RETURNING`).
+async fn fetch_write(
+    params: &ConnectionParams,
+    query: sqlx::query::Query<'_, sqlx::Sqlite, sqlx::sqlite::SqliteArguments<'_>>,
+) -> Result<Vec<sqlx::sqlite::SqliteRow>, String> {
+    if let Some(shared) = crate::pool_manager::active_transaction(params).await {
+        if let Some(TransactionConnection::Sqlite(tx)) = shared.lock().await.as_mut() {
+            return query.fetch_all(&mut **tx).await.map_err(|e| e.to_string());
+        }
+    }
+
+    let pool = get_sqlite_pool(params).await?;
+    query.fetch_all(&pool).await.map_err(|e| e.to_string())
+}
+
 pub async fn get_table_ddl(params: &ConnectionParams, table_name: &str) -> Result<String, String> {
     let pool = get_sqlite_pool(params).await?;
     let query = "SELECT sql FROM sqlite_master WHERE type='table' AND name = ?";
@@ -501,6 +587,7 @@ pub async fn get_table_ddl(params: &ConnectionParams, table_name: &str) -> Resul
 async fn exec_on_sqlite_conn(
     conn: &mut sqlx::SqliteConnection,
     query: &str,
+    binds: &[serde_json::Value],
     limit: Option<u32>,
     page: u32,
 ) -> Result<QueryResult, String> {
@@ -509,7 +596,7 @@ async fn exec_on_sqlite_conn(
     if !crate::drivers::common::returns_result_set(query) {
         use sqlx::Executor;
         let exec_result = conn
-            .execute(sqlx::query(query))
+            .execute(bind_values(sqlx::query(query), binds))
             .await
             .map_err(|e| e.to_string())?;
         return Ok(QueryResult {
@@ -517,7 +604,10 @@ async fn exec_on_sqlite_conn(
             rows: vec![],
             affected_rows: exec_result.rows_affected(),
             truncated: false,
+            truncation: None,
             pagination: None,
+            style_hints: None,
+            provenance: None,
         });
     }
 
@@ -544,10 +634,13 @@ async fn exec_on_sqlite_conn(
     }
 
     // Streaming
-    let mut rows_stream = sqlx::query(&final_query).fetch(&mut *conn);
+    let mut rows_stream = bind_values(sqlx::query(&final_query), binds).fetch(&mut *conn);
 
     let mut columns: Vec<String> = Vec::new();
-    let mut json_rows = Vec::new();
+    let mut collector = crate::drivers::common::RowCollector::new(
+        crate::drivers::common::ResultLimits::from_config(),
+        extract::row_to_json,
+    );
     let mut truncated = false;
 
     use futures::stream::StreamExt;
@@ -560,22 +653,21 @@ async fn exec_on_sqlite_conn(
                 }
 
                 if let Some(l) = manual_limit {
-                    if json_rows.len() >= l as usize {
+                    if collector.len() >= l as usize {
                         truncated = true;
                         break;
                     }
                 }
 
-                let mut json_row = Vec::new();
-                for (i, _) in row.columns().iter().enumerate() {
-                    let val = extract_value(&row, i, None);
-                    json_row.push(val);
+                // Converted to JSON in batches off the async executor
+                if !collector.push(row).await? {
+                    break;
                 }
-                json_rows.push(json_row);
             }
             Err(e) => return Err(e.to_string()),
         }
     }
+    let (mut json_rows, truncation) = collector.finish().await?;
 
     // Apply LIMIT +1 result: if we got page_size+1 rows, has_more=true
     if let Some(ref mut p) = pagination {
@@ -591,20 +683,77 @@ async fn exec_on_sqlite_conn(
         columns,
         rows: json_rows,
         affected_rows: 0,
-        truncated,
+        truncated: truncated || truncation.is_some(),
+        truncation,
         pagination,
+        style_hints: None,
+        provenance: None,
     })
 }
 
+/// Binds editor query parameters in order. Objects and arrays are bound
+/// as their JSON text.
+fn bind_values<'q>(
+    mut query: sqlx::query::Query<'q, sqlx::Sqlite, sqlx::sqlite::SqliteArguments<'q>>,
+    values: &[serde_json::Value],
+) -> sqlx::query::Query<'q, sqlx::Sqlite, sqlx::sqlite::SqliteArguments<'q>> {
+    for value in values {
+        query = match value {
+            serde_json::Value::Null => query.bind(None::<String>),
+            serde_json::Value::Bool(b) => query.bind(*b),
+            serde_json::Value::Number(n) => match n.as_i64() {
+                Some(i) => query.bind(i),
+                None => query.bind(n.as_f64()),
+            },
+            serde_json::Value::String(s) => query.bind(s.clone()),
+            other => query.bind(other.to_string()),
+        };
+    }
+    query
+}
+
 pub async fn execute_query(
     params: &ConnectionParams,
     query: &str,
     limit: Option<u32>,
     page: u32,
 ) -> Result<QueryResult, String> {
+    execute_bound_query(params, query, &[], limit, page).await
+}
+
+/// `execute_query` with `:name` / `$1` / `?` placeholders bound from
+/// `values`.
+pub async fn execute_query_with_params(
+    params: &ConnectionParams,
+    query: &str,
+    values: &std::collections::HashMap<String, serde_json::Value>,
+    limit: Option<u32>,
+    page: u32,
+) -> Result<QueryResult, String> {
+    let bound = crate::drivers::common::bind_query_params(
+        query,
+        values,
+        crate::drivers::common::PlaceholderStyle::Positional,
+    )?;
+    execute_bound_query(params, &bound.sql, &bound.values, limit, page).await
+}
+
+async fn execute_bound_query(
+    params: &ConnectionParams,
+    query: &str,
+    binds: &[serde_json::Value],
+    limit: Option<u32>,
+    page: u32,
+) -> Result<QueryResult, String> {
+    if let Some(shared) = crate::pool_manager::active_transaction(params).await {
+        if let Some(TransactionConnection::Sqlite(tx)) = shared.lock().await.as_mut() {
+            return exec_on_sqlite_conn(&mut **tx, query, binds, limit, page).await;
+        }
+    }
+
     let pool = get_sqlite_pool(params).await?;
     let mut conn = pool.acquire().await.map_err(|e| e.to_string())?;
-    exec_on_sqlite_conn(&mut *conn, query, limit, page).await
+    exec_on_sqlite_conn(&mut *conn, query, binds, limit, page).await
 }
 
 /// Runs a sequence of statements on a single pooled connection so
@@ -617,17 +766,32 @@ pub async fn execute_batch(
     limit: Option<u32>,
     page: u32,
 ) -> Result<Vec<crate::models::BatchStatementResult>, String> {
+    if let Some(shared) = crate::pool_manager::active_transaction(params).await {
+        if let Some(TransactionConnection::Sqlite(tx)) = shared.lock().await.as_mut() {
+            return Ok(run_batch_on_sqlite_conn(&mut **tx, queries, limit, page).await);
+        }
+    }
+
     let pool = get_sqlite_pool(params).await?;
     let mut conn = pool.acquire().await.map_err(|e| e.to_string())?;
+    Ok(run_batch_on_sqlite_conn(&mut *conn, queries, limit, page).await)
+}
+
+async fn run_batch_on_sqlite_conn(
+    conn: &mut sqlx::SqliteConnection,
+    queries: &[String],
+    limit: Option<u32>,
+    page: u32,
+) -> Vec<crate::models::BatchStatementResult> {
     let mut results = Vec::with_capacity(queries.len());
     for q in queries {
         let start = std::time::Instant::now();
-        let outcome = exec_on_sqlite_conn(&mut *conn, q, limit, page).await;
+        let outcome = exec_on_sqlite_conn(conn, q, &[], limit, page).await;
         results.push(crate::models::BatchStatementResult::from_outcome(
             start, outcome,
         ));
     }
-    Ok(results)
+    results
 }
 
 pub async fn get_views(params: &ConnectionParams) -> Result<Vec<ViewInfo>, String> {
@@ -713,6 +877,46 @@ pub async fn drop_view(params: &ConnectionParams, view_name: &str) -> Result<(),
     Ok(())
 }
 
+pub async fn create_table_from_query(
+    params: &ConnectionParams,
+    table_name: &str,
+    query: &str,
+) -> Result<(), String> {
+    let pool = get_sqlite_pool(params).await?;
+    let escaped_name = escape_identifier(table_name);
+    let statement = format!("CREATE TABLE \"{}\" AS {}", escaped_name, query);
+    sqlx::query(&statement)
+        .execute(&pool)
+        .await
+        .map_err(|e| format!("Failed to create table: {}", e))?;
+    Ok(())
+}
+
+/// Columns of every table and view, read with one `pragma_table_info` join.
+pub async fn get_schema_signature(
+    params: &ConnectionParams,
+) -> Result<Vec<crate::models::ColumnSignature>, String> {
+    let pool = get_sqlite_pool(params).await?;
+    let rows = sqlx::query(
+        "SELECT m.name, p.name, p.type FROM sqlite_master m \
+         JOIN pragma_table_info(m.name) p \
+         WHERE m.type IN ('table', 'view') AND m.name NOT LIKE 'sqlite_%' \
+         ORDER BY m.name, p.name",
+    )
+    .fetch_all(&pool)
+    .await
+    .map_err(|e| format!("Failed to read schema signature: {}", e))?;
+
+    Ok(rows
+        .iter()
+        .map(|r| crate::models::ColumnSignature {
+            table: r.try_get(0).unwrap_or_default(),
+            column: r.try_get(1).unwrap_or_default(),
+            data_type: r.try_get(2).unwrap_or_default(),
+        })
+        .collect())
+}
+
 pub async fn get_view_columns(
     params: &ConnectionParams,
     view_name: &str,
@@ -879,7 +1083,11 @@ impl SqliteDriver {
                     no_connection_required: false,
                     manage_tables: true,
                     readonly: false,
+                    arrow: false,
                     triggers: true,
+                    privileges: false,
+                    server_status: false,
+                    table_stats: true,
                 },
                 is_builtin: true,
                 default_username: String::new(),
@@ -1049,6 +1257,16 @@ impl DatabaseDriver for SqliteDriver {
         drop_view(params, view_name).await
     }
 
+    async fn create_table_from_query(
+        &self,
+        params: &crate::models::ConnectionParams,
+        table_name: &str,
+        query: &str,
+        _schema: Option<&str>,
+    ) -> Result<(), String> {
+        create_table_from_query(params, table_name, query).await
+    }
+
     async fn get_routines(
         &self,
         params: &crate::models::ConnectionParams,
@@ -1135,6 +1353,49 @@ impl DatabaseDriver for SqliteDriver {
         execute_batch(params, queries, limit, page).await
     }
 
+    async fn execute_query_with_params(
+        &self,
+        params: &crate::models::ConnectionParams,
+        query: &str,
+        values: &HashMap<String, serde_json::Value>,
+        limit: Option<u32>,
+        page: u32,
+        _schema: Option<&str>,
+    ) -> Result<crate::models::QueryResult, String> {
+        execute_query_with_params(params, query, values, limit, page).await
+    }
+
+    async fn execute_query_stream(
+        &self,
+        params: &crate::models::ConnectionParams,
+        query: &str,
+        batch_size: usize,
+        _schema: Option<&str>,
+        on_batch: &mut crate::drivers::common::RowBatchCallback<'_>,
+    ) -> Result<u64, String> {
+        let mut batcher = crate::drivers::common::RowBatcher::new(batch_size, on_batch);
+        export::stream_query(params, query, |headers, values| {
+            batcher.push(headers, values.to_vec())
+        })
+        .await?;
+        batcher.finish()
+    }
+
+    async fn get_server_version(
+        &self,
+        params: &crate::models::ConnectionParams,
+    ) -> Result<String, String> {
+        get_server_version(params).await
+    }
+
+    async fn get_table_stats(
+        &self,
+        params: &crate::models::ConnectionParams,
+        _schema: Option<&str>,
+    ) -> Result<Vec<crate::models::TableStats>, String> {
+        table_stats::get_table_stats(params).await
+    }
+
     async fn explain_query(
         &self,
         params: &crate::models::ConnectionParams,
@@ -1156,6 +1417,38 @@ impl DatabaseDriver for SqliteDriver {
         insert_record(params, table, data, max_blob_size).await
     }
 
+    async fn insert_record_returning(
+        &self,
+        params: &crate::models::ConnectionParams,
+        table: &str,
+        data: std::collections::HashMap<String, serde_json::Value>,
+        _schema: Option<&str>,
+        max_blob_size: u64,
+    ) -> Result<InsertedRecord, String> {
+        insert_record_returning(params, table, data, max_blob_size).await
+    }
+
+    fn insert_batch_limits(&self) -> Option<crate::drivers::common::InsertBatchLimits> {
+        Some(crate::drivers::common::InsertBatchLimits {
+            max_rows: 1000,
+            // SQLITE_MAX_VARIABLE_NUMBER since SQLite 3.32.
+            max_params: 32_766,
+            max_bytes: 16 * 1024 * 1024,
+        })
+    }
+
+    async fn insert_rows(
+        &self,
+        params: &crate::models::ConnectionParams,
+        table: &str,
+        columns: &[String],
+        rows: Vec<std::collections::HashMap<String, serde_json::Value>>,
+        _schema: Option<&str>,
+        max_blob_size: u64,
+    ) -> Result<u64, String> {
+        insert_rows(params, table, columns, rows, max_blob_size).await
+    }
+
     async fn update_record(
         &self,
         params: &crate::models::ConnectionParams,
@@ -1215,6 +1508,15 @@ impl DatabaseDriver for SqliteDriver {
         fetch_blob_column_as_data_url(params, table, col_name, pk_col, pk_val).await
     }
 
+    async fn get_table_ddl(
+        &self,
+        params: &crate::models::ConnectionParams,
+        table: &str,
+        _schema: Option<&str>,
+    ) -> Result<String, String> {
+        get_table_ddl(params, table).await
+    }
+
     async fn get_create_table_sql(
         &self,
         table_name: &str,
@@ -1282,6 +1584,14 @@ impl DatabaseDriver for SqliteDriver {
         _schema: Option<&str>,
     ) -> Result<Vec<String>, String> {
         if old_column.name != new_column.name {
+            if let Some(info) = crate::server_info::current() {
+                if !info.features.rename_column {
+                    return Err(format!(
+                        "Renaming columns needs SQLite 3.25 or later (this is {})",
+                        info.version
+                    ));
+                }
+            }
             return Ok(vec![format!(
                 "ALTER TABLE \"{}\" RENAME COLUMN \"{}\" TO \"{}\"",
                 table.replace('"', "\"\""),
@@ -1370,6 +1680,14 @@ impl DatabaseDriver for SqliteDriver {
         get_all_foreign_keys_batch(params, &names).await
     }
 
+    async fn get_schema_signature(
+        &self,
+        params: &crate::models::ConnectionParams,
+        _schema: Option<&str>,
+    ) -> Result<Vec<crate::models::ColumnSignature>, String> {
+        get_schema_signature(params).await
+    }
+
     async fn get_schema_snapshot(
         &self,
         params: &crate::models::ConnectionParams,
@@ -0,0 +1,86 @@
+use crate::models::{ConnectionParams, TableStats};
+use crate::pool_manager::get_sqlite_pool;
+use sqlx::Row;
+use std::collections::HashMap;
+
+/// Bytes per table and index name, from the `dbstat` virtual table.
+const DBSTAT_QUERY: &str = r#"
+    SELECT m.tbl_name AS tbl, m.type = 'index' AS is_index, SUM(d.pgsize) AS bytes
+    FROM dbstat d
+    JOIN sqlite_master m ON m.name = d.name
+    GROUP BY m.tbl_name, is_index
+"#;
+
+/// Rows counted by ANALYZE: the first number of a `sqlite_stat1` `stat`.
+pub(super) fn stat1_rows(stat: &str) -> Option<i64> {
+    stat.split_whitespace().next()?.parse().ok()
+}
+
+/// Row estimates need a prior ANALYZE and sizes need SQLite built with
+/// `dbstat`; whatever is missing is left `None`. SQLite keeps no record of
+/// when ANALYZE ran.
+pub async fn get_table_stats(params: &ConnectionParams) -> Result<Vec<TableStats>, String> {
+    let pool = get_sqlite_pool(params).await?;
+    let names: Vec<String> = sqlx::query(
+        "SELECT name FROM sqlite_master WHERE type='table' AND name NOT LIKE 'sqlite_%' ORDER BY name",
+    )
+    .fetch_all(&pool)
+    .await
+    .map_err(|e| e.to_string())?
+    .iter()
+    .map(|r| r.try_get("name").unwrap_or_default())
+    .collect();
+
+    let mut row_estimates: HashMap<String, i64> = HashMap::new();
+    match sqlx::query("SELECT tbl, stat FROM sqlite_stat1")
+        .fetch_all(&pool)
+        .await
+    {
+        Ok(rows) => {
+            for r in &rows {
+                let table: String = r.try_get("tbl").unwrap_or_default();
+                let stat: String = r.try_get("stat").unwrap_or_default();
+                if let Some(count) = stat1_rows(&stat) {
+                    let estimate = row_estimates.entry(table).or_insert(count);
+                    *estimate = (*estimate).max(count);
+                }
+            }
+        }
+        Err(e) => log::debug!("SQLite: no sqlite_stat1 ({}), rows unknown", e),
+    }
+
+    let mut sizes: HashMap<String, (i64, i64)> = HashMap::new();
+    let has_sizes = match sqlx::query(DBSTAT_QUERY).fetch_all(&pool).await {
+        Ok(rows) => {
+            for r in &rows {
+                let table: String = r.try_get("tbl").unwrap_or_default();
+                let bytes: i64 = r.try_get("bytes").unwrap_or_default();
+                let size = sizes.entry(table).or_default();
+                if r.try_get::<bool, _>("is_index").unwrap_or_default() {
+                    size.1 += bytes;
+                } else {
+                    size.0 += bytes;
+                }
+            }
+            true
+        }
+        Err(e) => {
+            log::debug!("SQLite: dbstat unavailable ({}), sizes unknown", e);
+            false
+        }
+    };
+
+    Ok(names
+        .into_iter()
+        .map(|name| {
+            let (data, index) = sizes.get(&name).copied().unwrap_or_default();
+            TableStats {
+                row_estimate: row_estimates.get(&name).copied(),
+                data_bytes: has_sizes.then_some(data),
+                index_bytes: has_sizes.then_some(index),
+                last_analyzed: None,
+                name,
+            }
+        })
+        .collect())
+}
@@ -32,6 +32,9 @@ async fn setup_test_db() -> (ConnectionParams, NamedTempFile) {
         ssh_key_file: None,
         ssh_key_passphrase: None,
         save_in_keychain: None,
+        read_only: false,
+        auth_method: None,
+        pool: None,
         connection_id: None,
     };
 
@@ -167,3 +170,155 @@ async fn test_view_lifecycle() {
     // Cleanup: Close the pool created by the functions (via pool_manager)
     crate::pool_manager::close_pool(&params).await;
 }
+
+#[tokio::test]
+async fn test_insert_records_batch_reports_failed_rows() {
+    use crate::drivers::driver_trait::DatabaseDriver;
+    use serde_json::json;
+
+    let (params, _file) = setup_test_db().await;
+    let row = |id: i64, name: &str| {
+        std::collections::HashMap::from([
+            ("id".to_string(), json!(id)),
+            ("name".to_string(), json!(name)),
+        ])
+    };
+    // id 1 already exists (Alice), so the second row fails.
+    let rows = vec![row(10, "Carol"), row(1, "Dup"), row(11, "Dave")];
+
+    let result = super::SqliteDriver::new()
+        .insert_records_batch(&params, "users", rows, None, 1024)
+        .await
+        .expect("batch should run");
+    assert_eq!(result.inserted, 2);
+    assert_eq!(result.errors.len(), 1);
+    assert_eq!(result.errors[0].row, 1);
+
+    let pool = crate::pool_manager::get_sqlite_pool(&params)
+        .await
+        .expect("pool");
+    let (count,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM users")
+        .fetch_one(&pool)
+        .await
+        .expect("count");
+    assert_eq!(count, 4);
+
+    crate::pool_manager::close_pool(&params).await;
+}
+
+#[tokio::test]
+async fn test_rollback_transaction_discards_edits() {
+    use serde_json::json;
+
+    let (mut params, file) = setup_test_db().await;
+    params.connection_id = Some(format!("tx-{}", file.path().display()));
+    let connection_id = params.connection_id.clone().unwrap();
+
+    crate::pool_manager::begin_transaction(&params)
+        .await
+        .expect("begin");
+    assert!(crate::pool_manager::begin_transaction(&params)
+        .await
+        .is_err());
+
+    let deleted = super::delete_record(&params, "users", "id", json!(1))
+        .await
+        .expect("delete");
+    assert_eq!(deleted, 1);
+    let inside = super::execute_query(&params, "SELECT COUNT(*) FROM users", None, 1)
+        .await
+        .expect("count inside transaction");
+    assert_eq!(inside.rows[0][0], json!(1));
+
+    crate::pool_manager::finish_transaction(&connection_id, false)
+        .await
+        .expect("rollback");
+    assert!(!crate::pool_manager::has_transaction(&connection_id).await);
+
+    let after = super::execute_query(&params, "SELECT COUNT(*) FROM users", None, 1)
+        .await
+        .expect("count after rollback");
+    assert_eq!(after.rows[0][0], json!(2));
+
+    crate::pool_manager::close_pool(&params).await;
+}
+
+#[tokio::test]
+async fn test_insert_record_returning_reads_back_generated_id() {
+    use serde_json::json;
+
+    let (params, _file) = setup_test_db().await;
+    let data = std::collections::HashMap::from([("name".to_string(), json!("Carol"))]);
+
+    let inserted = super::insert_record_returning(&params, "users", data, 1024)
+        .await
+        .expect("insert should succeed");
+    assert_eq!(inserted.affected_rows, 1);
+    let row = inserted.row.expect("row should be returned");
+    assert_eq!(row["id"], json!(3));
+    assert_eq!(row["name"], json!("Carol"));
+
+    crate::pool_manager::close_pool(&params).await;
+}
+
+#[tokio::test]
+async fn test_execute_query_with_params_binds_named_values() {
+    use serde_json::json;
+
+    let (params, _file) = setup_test_db().await;
+    let values = std::collections::HashMap::from([("name".to_string(), json!("Bob"))]);
+
+    let result = super::execute_query_with_params(
+        &params,
+        "SELECT id FROM users WHERE name = :name",
+        &values,
+        None,
+        1,
+    )
+    .await
+    .expect("query should succeed");
+    assert_eq!(result.rows, vec![vec![json!(2)]]);
+
+    let missing = super::execute_query_with_params(
+        &params,
+        "SELECT id FROM users WHERE id = :id",
+        &values,
+        None,
+        1,
+    )
+    .await;
+    assert!(missing.is_err());
+
+    crate::pool_manager::close_pool(&params).await;
+}
+
+#[test]
+fn test_stat1_rows_reads_the_leading_count() {
+    use super::table_stats::stat1_rows;
+
+    assert_eq!(stat1_rows("1200 3 1"), Some(1200));
+    assert_eq!(stat1_rows("42"), Some(42));
+    assert_eq!(stat1_rows(""), None);
+}
+
+#[tokio::test]
+async fn test_get_table_stats_reports_analyzed_rows() {
+    let (params, _file) = setup_test_db().await;
+    let pool = crate::pool_manager::get_sqlite_pool(&params)
+        .await
+        .expect("pool should open");
+    sqlx::query("ANALYZE")
+        .execute(&pool)
+        .await
+        .expect("ANALYZE should succeed");
+
+    let stats = super::table_stats::get_table_stats(&params)
+        .await
+        .expect("stats should be read");
+    assert_eq!(stats.len(), 1);
+    assert_eq!(stats[0].name, "users");
+    assert_eq!(stats[0].row_estimate, Some(2));
+    assert!(stats[0].last_analyzed.is_none());
+
+    crate::pool_manager::close_pool(&params).await;
+}
@@ -3,7 +3,10 @@ use crate::commands::{
     resolve_connection_params_with_id, unregister_abort_handle, AbortHandleMap,
 };
 use crate::drivers::{mysql, postgres, sqlite};
-use crate::dump_utils::{drop_table_if_exists, format_table_ref, insert_into_statement};
+use crate::dump_utils::{
+    deferred_constraints, dependency_order, drop_table_if_exists, format_table_ref,
+    insert_into_statement,
+};
 use crate::models::ConnectionParams;
 use crate::pool_manager::{get_mysql_pool, get_postgres_pool, get_sqlite_pool};
 use futures::TryStreamExt;
@@ -12,7 +15,7 @@ use sqlx::Row;
 use std::fs::File;
 use std::io::{BufRead, BufReader, BufWriter, Read, Write};
 use std::sync::{Arc, Mutex};
-use tauri::{AppHandle, Emitter, Runtime, State};
+use tauri::{AppHandle, Emitter, Runtime, State, WebviewWindow};
 use zip::ZipArchive;
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -30,21 +33,24 @@ pub struct DumpCancellationState {
 /// Slot key for the cancellation registry. Imports share the dump state
 /// but need a distinct slot so `cancel_dump` and `cancel_import` don't
 /// alias each other.
-fn import_slot_key(connection_id: &str) -> String {
+pub(crate) fn import_slot_key(connection_id: &str) -> String {
     format!("{}_import", connection_id)
 }
 
+/// Cancel the calling window's dump of `connection_id`.
 #[tauri::command]
-pub async fn cancel_dump(
+pub async fn cancel_dump<R: Runtime>(
+    window: WebviewWindow<R>,
     state: State<'_, DumpCancellationState>,
     connection_id: String,
 ) -> Result<(), String> {
+    let cancel_key = crate::window_state::cancellation_key(window.label(), &connection_id);
     let entries = {
         let mut handles = state.handles.lock().unwrap();
-        handles.remove(&connection_id).unwrap_or_default()
+        handles.remove(&cancel_key).unwrap_or_default()
     };
     if entries.is_empty() {
-        return Err("No active dump process found".into());
+        return Err(crate::i18n::t("errors.noActiveDump"));
     }
     for handle in entries {
         handle.abort();
@@ -55,6 +61,30 @@ pub async fn cancel_dump(
 #[tauri::command]
 pub async fn dump_database<R: Runtime>(
     app: AppHandle<R>,
+    window: WebviewWindow<R>,
+    state: State<'_, DumpCancellationState>,
+    connection_id: String,
+    file_path: String,
+    options: DumpOptions,
+    schema: Option<String>,
+) -> Result<(), String> {
+    dump_database_in_scope(
+        app,
+        window.label(),
+        state,
+        connection_id,
+        file_path,
+        options,
+        schema,
+    )
+    .await
+}
+
+/// [`dump_database`], cancellable under `cancel_scope` (see
+/// [`crate::window_state::cancellation_key`]).
+pub(crate) async fn dump_database_in_scope<R: Runtime>(
+    app: AppHandle<R>,
+    cancel_scope: &str,
     state: State<'_, DumpCancellationState>,
     connection_id: String,
     file_path: String,
@@ -83,7 +113,12 @@ pub async fn dump_database<R: Runtime>(
             "mysql" => mysql::get_tables(&params, None).await?,
             "postgres" => postgres::get_tables(&params, &schema).await?,
             "sqlite" => sqlite::get_tables(&params).await?,
-            _ => return Err("Unsupported driver".into()),
+            other => {
+                return Err(crate::i18n::t_with(
+                    "errors.unsupportedDriver",
+                    &[("driver", other)],
+                ))
+            }
         };
 
         let tables_to_process: Vec<String> = if let Some(selection) = &options.tables {
@@ -92,6 +127,31 @@ pub async fn dump_database<R: Runtime>(
             all_tables.into_iter().map(|t| t.name).collect()
         };
 
+        // Dump parents before children so the INSERTs restore without
+        // tripping foreign keys.
+        let foreign_keys = match driver.as_str() {
+            "mysql" => mysql::get_all_foreign_keys_batch(&params, None).await?,
+            "postgres" => postgres::get_all_foreign_keys_batch(&params, &schema).await?,
+            "sqlite" => sqlite::get_all_foreign_keys_batch(&params, &tables_to_process).await?,
+            other => {
+                return Err(crate::i18n::t_with(
+                    "errors.unsupportedDriver",
+                    &[("driver", other)],
+                ))
+            }
+        };
+        let (tables_to_process, has_cycles) = dependency_order(&tables_to_process, &foreign_keys);
+
+        // Tables that reference each other in a cycle have no safe order.
+        let deferred = if has_cycles && options.data {
+            deferred_constraints(&driver)
+        } else {
+            None
+        };
+        if let Some((suspend, _)) = deferred {
+            writeln!(writer, "{}\n", suspend).map_err(|e| e.to_string())?;
+        }
+
         for table in tables_to_process {
             if options.structure {
                 writeln!(
@@ -107,7 +167,12 @@ pub async fn dump_database<R: Runtime>(
                     "mysql" => mysql::get_table_ddl(&params, &table).await?,
                     "postgres" => postgres::get_table_ddl(&params, &table, &schema).await?,
                     "sqlite" => sqlite::get_table_ddl(&params, &table).await?,
-                    _ => return Err("Unsupported driver".into()),
+                    other => {
+                        return Err(crate::i18n::t_with(
+                            "errors.unsupportedDriver",
+                            &[("driver", other)],
+                        ))
+                    }
                 };
 
                 writeln!(writer, "{}\n", ddl).map_err(|e| e.to_string())?;
@@ -125,20 +190,25 @@ pub async fn dump_database<R: Runtime>(
             }
         }
 
+        if let Some((_, restore)) = deferred {
+            writeln!(writer, "{}", restore).map_err(|e| e.to_string())?;
+        }
+
         writer.flush().map_err(|e| e.to_string())?;
         Ok::<(), String>(())
     });
 
     let abort_handle = Arc::new(task.abort_handle());
-    register_abort_handle(&state.handles, connection_id.clone(), abort_handle.clone());
+    let cancel_key = crate::window_state::cancellation_key(cancel_scope, &connection_id);
+    register_abort_handle(&state.handles, cancel_key.clone(), abort_handle.clone());
 
     let result = task.await;
 
-    unregister_abort_handle(&state.handles, &connection_id, &abort_handle);
+    unregister_abort_handle(&state.handles, &cancel_key, &abort_handle);
 
     match result {
         Ok(res) => res,
-        Err(_) => Err("Dump cancelled".into()),
+        Err(_) => Err(crate::i18n::t("errors.dumpCancelled")),
     }
 }
 
@@ -269,7 +339,12 @@ async fn export_table_data(
                 .map_err(|e| e.to_string())?;
             }
         }
-        _ => return Err("Unsupported driver".into()),
+        other => {
+            return Err(crate::i18n::t_with(
+                "errors.unsupportedDriver",
+                &[("driver", other)],
+            ))
+        }
     }
 
     Ok(())
@@ -297,6 +372,96 @@ pub struct ImportProgress {
     pub total_statements: usize,
     pub percentage: f32,
     pub current_operation: String,
+    /// Table the last executed statement wrote to, when it names one.
+    pub current_table: Option<String>,
+}
+
+/// Why a restore stopped. Emitted as `import_failed` before the import
+/// command returns its error, so the UI can point at the offending line.
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct ImportFailure {
+    /// 1-based number of the failing statement.
+    pub statement_number: usize,
+    /// 1-based line of the dump file where the failing statement starts.
+    pub line: usize,
+    pub table: Option<String>,
+    pub error: String,
+    /// The failing statement, cut to `FAILURE_STATEMENT_PREVIEW` characters.
+    pub statement: String,
+}
+
+const FAILURE_STATEMENT_PREVIEW: usize = 300;
+
+impl ImportFailure {
+    pub(crate) fn new(
+        statement_number: usize,
+        line: usize,
+        statement: &str,
+        error: String,
+    ) -> Self {
+        let mut preview: String = statement.chars().take(FAILURE_STATEMENT_PREVIEW).collect();
+        if preview.len() < statement.len() {
+            preview.push_str("...");
+        }
+        Self {
+            statement_number,
+            line,
+            table: statement_table(statement),
+            error,
+            statement: preview,
+        }
+    }
+
+    pub(crate) fn summary(&self) -> String {
+        format!(
+            "Error at statement {} (line {}): {}\nQuery: {}",
+            self.statement_number, self.line, self.error, self.statement
+        )
+    }
+}
+
+/// Table a dump statement writes to, for progress reporting: the target of
+/// `INSERT INTO`, `CREATE TABLE`, `DROP TABLE`, `ALTER TABLE`, `COPY` or
+/// `TRUNCATE`, without quotes or schema.
+pub(crate) fn statement_table(stmt: &str) -> Option<String> {
+    const MODIFIERS: &[&str] = &[
+        "INTO",
+        "IF",
+        "NOT",
+        "EXISTS",
+        "IGNORE",
+        "ONLY",
+        "TEMPORARY",
+        "TEMP",
+        "UNLOGGED",
+    ];
+    let mut words = stmt.split_whitespace();
+    let verb = words.next()?.to_ascii_uppercase();
+    let needs_table_keyword = match verb.as_str() {
+        "INSERT" | "REPLACE" | "COPY" | "TRUNCATE" => false,
+        "CREATE" | "DROP" | "ALTER" => true,
+        _ => return None,
+    };
+
+    let mut saw_table_keyword = false;
+    let name = loop {
+        let word = words.next()?;
+        match word.to_ascii_uppercase().as_str() {
+            "TABLE" => saw_table_keyword = true,
+            w if MODIFIERS.contains(&w) => {}
+            _ => break word,
+        }
+    };
+    if needs_table_keyword && !saw_table_keyword {
+        return None;
+    }
+
+    let name = name.split('(').next()?.trim_end_matches(';');
+    let name = name
+        .rsplit('.')
+        .next()?
+        .trim_matches(|c| matches!(c, '"' | '`' | '[' | ']'));
+    (!name.is_empty()).then(|| name.to_string())
 }
 
 // Stream-based statement parser that yields statements as they are read
@@ -304,6 +469,11 @@ struct SqlStatementStream<R: BufRead> {
     reader: R,
     current_statement: String,
     line_buffer: String,
+    /// Lines and bytes consumed so far, for error locations and progress.
+    lines_read: usize,
+    bytes_read: u64,
+    /// Line where the statement being read starts.
+    statement_line: usize,
 }
 
 impl<R: BufRead> SqlStatementStream<R> {
@@ -312,6 +482,23 @@ impl<R: BufRead> SqlStatementStream<R> {
             reader,
             current_statement: String::new(),
             line_buffer: String::new(),
+            lines_read: 0,
+            bytes_read: 0,
+            statement_line: 0,
+        }
+    }
+
+    /// 1-based line where the statement last returned by `next_statement`
+    /// starts.
+    fn statement_line(&self) -> usize {
+        self.statement_line
+    }
+
+    /// Share of the input read so far, when its size is known.
+    fn percentage(&self, total_bytes: Option<u64>) -> f32 {
+        match total_bytes {
+            Some(total) if total > 0 => (self.bytes_read as f32 / total as f32 * 100.0).min(100.0),
+            _ => 0.0,
         }
     }
 
@@ -333,6 +520,8 @@ impl<R: BufRead> SqlStatementStream<R> {
                     return Ok(Some(stmt));
                 }
             }
+            self.lines_read += 1;
+            self.bytes_read += bytes_read as u64;
 
             let trimmed = self.line_buffer.trim();
 
@@ -341,6 +530,9 @@ impl<R: BufRead> SqlStatementStream<R> {
                 continue;
             }
 
+            if self.current_statement.is_empty() {
+                self.statement_line = self.lines_read;
+            }
             self.current_statement.push_str(&self.line_buffer);
 
             // Check if statement is complete
@@ -355,37 +547,62 @@ impl<R: BufRead> SqlStatementStream<R> {
     }
 }
 
+/// Split a SQL script into statements using the same rules as
+/// `import_database`: `--` comment lines are dropped and a statement ends at a
+/// line ending with `;`.
+pub(crate) fn split_sql_statements(sql: &str) -> Result<Vec<String>, String> {
+    let mut stream = SqlStatementStream::new(std::io::Cursor::new(sql.as_bytes()));
+    let mut statements = Vec::new();
+    while let Some(stmt) = stream.next_statement()? {
+        statements.push(stmt);
+    }
+    Ok(statements)
+}
+
 // Helper macro for streaming execution with progress
 macro_rules! execute_statements_streaming {
-    ($executor_macro:ident, $stream:expr, $app:expr) => {{
+    ($executor_macro:ident, $stream:expr, $app:expr, $total_bytes:expr) => {{
         // Larger batch for better performance - execute and emit progress every 500 statements
         const PROGRESS_EMIT_INTERVAL: usize = 500;
         let mut executed = 0;
         let mut since_last_progress = 0;
+        let mut current_table: Option<String> = None;
 
         while let Some(stmt) = $stream.next_statement()? {
             // Execute statement immediately without batching in memory
-            $executor_macro!(&stmt).await.map_err(|e| {
-                format!(
-                    "Error at statement {}: {}\nQuery: {}",
+            if let Err(e) = $executor_macro!(&stmt).await {
+                let failure = ImportFailure::new(
                     executed + 1,
-                    e,
-                    stmt
-                )
-            })?;
+                    $stream.statement_line(),
+                    &stmt,
+                    e.to_string(),
+                );
+                let _ = $app.emit("import_failed", failure.clone());
+                return Err(failure.summary());
+            }
 
             executed += 1;
             since_last_progress += 1;
 
-            // Emit progress only every PROGRESS_EMIT_INTERVAL statements to reduce overhead
-            if since_last_progress >= PROGRESS_EMIT_INTERVAL {
+            // Emit progress every PROGRESS_EMIT_INTERVAL statements to reduce
+            // overhead, and whenever the dump moves on to another table
+            let table = statement_table(&stmt);
+            let table_changed = table.is_some() && table != current_table;
+            if table_changed {
+                current_table = table;
+            }
+            if since_last_progress >= PROGRESS_EMIT_INTERVAL || table_changed {
                 let _ = $app.emit(
                     "import_progress",
                     ImportProgress {
                         statements_executed: executed,
                         total_statements: 0, // 0 indicates unknown total
-                        percentage: 0.0,
-                        current_operation: format!("Imported {} statements", executed),
+                        percentage: $stream.percentage($total_bytes),
+                        current_operation: match &current_table {
+                            Some(table) => format!("Importing {} ({} statements)", table, executed),
+                            None => format!("Imported {} statements", executed),
+                        },
+                        current_table: current_table.clone(),
                     },
                 );
                 since_last_progress = 0;
@@ -400,6 +617,7 @@ macro_rules! execute_statements_streaming {
                 total_statements: executed,
                 percentage: 100.0,
                 current_operation: "Import completed".to_string(),
+                current_table: None,
             },
         );
 
@@ -407,18 +625,23 @@ macro_rules! execute_statements_streaming {
     }};
 }
 
+/// Cancel the calling window's import into `connection_id`.
 #[tauri::command]
-pub async fn cancel_import(
+pub async fn cancel_import<R: Runtime>(
+    window: WebviewWindow<R>,
     state: State<'_, DumpCancellationState>,
     connection_id: String,
 ) -> Result<(), String> {
-    let key = import_slot_key(&connection_id);
+    let key = import_slot_key(&crate::window_state::cancellation_key(
+        window.label(),
+        &connection_id,
+    ));
     let entries = {
         let mut handles = state.handles.lock().unwrap();
         handles.remove(&key).unwrap_or_default()
     };
     if entries.is_empty() {
-        return Err("No active import process found".into());
+        return Err(crate::i18n::t("errors.noActiveImport"));
     }
     for handle in entries {
         handle.abort();
@@ -429,12 +652,14 @@ pub async fn cancel_import(
 #[tauri::command]
 pub async fn import_database<R: Runtime>(
     app: AppHandle<R>,
+    window: WebviewWindow<R>,
     state: State<'_, DumpCancellationState>,
     connection_id: String,
     file_path: String,
     schema: Option<String>,
 ) -> Result<(), String> {
     let saved_conn = find_connection_by_id(&app, &connection_id)?;
+    crate::read_only::ensure_writable(&saved_conn.params)?;
     let expanded_params = expand_ssh_connection_params(&app, &saved_conn.params).await?;
     let params = resolve_connection_params_with_id(&expanded_params, &connection_id)?;
     let driver = saved_conn.params.driver.clone();
@@ -446,160 +671,197 @@ pub async fn import_database<R: Runtime>(
     let task = tokio::spawn(async move {
         // Open file and create streaming reader
         let file = File::open(&file_path).map_err(|e| e.to_string())?;
-        let reader = create_sql_reader(file, &file_path)?;
-        let mut stream = SqlStatementStream::new(reader);
+        let (reader, total_bytes) = create_sql_reader(file, &file_path)?;
+        run_sql_import(
+            &app_handle,
+            &driver,
+            &params,
+            &pg_schema,
+            reader,
+            Some(total_bytes),
+        )
+        .await
+    });
 
-        // Emit initial progress
-        let _ = app_handle.emit(
-            "import_progress",
-            ImportProgress {
-                statements_executed: 0,
-                total_statements: 0,
-                percentage: 0.0,
-                current_operation: "Starting import...".to_string(),
-            },
-        );
+    let abort_handle = Arc::new(task.abort_handle());
+    let import_key = import_slot_key(&crate::window_state::cancellation_key(
+        window.label(),
+        &conn_id,
+    ));
+    register_abort_handle(&state.handles, import_key.clone(), abort_handle.clone());
 
-        // Execute with transaction and optimizations for speed
-        match driver.as_str() {
-            "mysql" => {
-                let pool = get_mysql_pool(&params).await?;
-                let mut tx = pool.begin().await.map_err(|e| e.to_string())?;
+    let result = task.await;
 
-                // Performance optimizations for MySQL
-                sqlx::query("SET FOREIGN_KEY_CHECKS=0")
-                    .execute(&mut *tx)
-                    .await
-                    .map_err(|e| e.to_string())?;
-                sqlx::query("SET UNIQUE_CHECKS=0")
-                    .execute(&mut *tx)
-                    .await
-                    .map_err(|e| e.to_string())?;
-                sqlx::query("SET AUTOCOMMIT=0")
-                    .execute(&mut *tx)
-                    .await
-                    .map_err(|e| e.to_string())?;
+    unregister_abort_handle(&state.handles, &import_key, &abort_handle);
 
-                macro_rules! execute_statement {
-                    ($stmt:expr) => {
-                        sqlx::query($stmt).execute(&mut *tx)
-                    };
-                }
+    match result {
+        Ok(res) => res,
+        Err(_) => Err(crate::i18n::t("errors.importCancelled")),
+    }
+}
 
-                execute_statements_streaming!(execute_statement, stream, app_handle)?;
+/// Execute a SQL script against a built-in driver in one transaction,
+/// emitting `import_progress` as it goes and `import_failed` with the
+/// offending line if a statement fails. `total_bytes` is the script size,
+/// when known, for the progress percentage. Shared by `import_database`
+/// and the sample database installer.
+pub(crate) async fn run_sql_import<R: Runtime>(
+    app_handle: &AppHandle<R>,
+    driver: &str,
+    params: &ConnectionParams,
+    pg_schema: &str,
+    reader: Box<dyn BufRead + Send>,
+    total_bytes: Option<u64>,
+) -> Result<(), String> {
+    let mut stream = SqlStatementStream::new(reader);
+
+    // Emit initial progress
+    let _ = app_handle.emit(
+        "import_progress",
+        ImportProgress {
+            statements_executed: 0,
+            total_statements: 0,
+            percentage: 0.0,
+            current_operation: "Starting import...".to_string(),
+            current_table: None,
+        },
+    );
+
+    // Execute with transaction and optimizations for speed
+    match driver {
+        "mysql" => {
+            let pool = get_mysql_pool(params).await?;
+            let mut tx = pool.begin().await.map_err(|e| e.to_string())?;
 
-                // Restore settings
-                sqlx::query("SET FOREIGN_KEY_CHECKS=1")
-                    .execute(&mut *tx)
-                    .await
-                    .map_err(|e| e.to_string())?;
-                sqlx::query("SET UNIQUE_CHECKS=1")
-                    .execute(&mut *tx)
-                    .await
-                    .map_err(|e| e.to_string())?;
-                sqlx::query("SET AUTOCOMMIT=1")
-                    .execute(&mut *tx)
-                    .await
-                    .map_err(|e| e.to_string())?;
+            // Performance optimizations for MySQL
+            sqlx::query("SET FOREIGN_KEY_CHECKS=0")
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| e.to_string())?;
+            sqlx::query("SET UNIQUE_CHECKS=0")
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| e.to_string())?;
+            sqlx::query("SET AUTOCOMMIT=0")
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| e.to_string())?;
 
-                tx.commit().await.map_err(|e| e.to_string())?;
+            macro_rules! execute_statement {
+                ($stmt:expr) => {
+                    sqlx::query($stmt).execute(&mut *tx)
+                };
             }
-            "postgres" => {
-                let pool = get_postgres_pool(&params).await?;
-                let mut client = pool.get().await.map_err(|e| e.to_string())?;
-                let tx = client.transaction().await.map_err(|e| e.to_string())?;
-
-                // Set schema search path so unqualified table names resolve correctly
-                tx.execute(&format!("SET search_path TO \"{}\"", pg_schema), &[])
-                    .await
-                    .map_err(|e| e.to_string())?;
 
-                // Performance optimizations for PostgreSQL
-                tx.execute("SET CONSTRAINTS ALL DEFERRED", &[])
-                    .await
-                    .map_err(|e| e.to_string())?;
-                // Temporarily disable synchronous commit for speed (data at risk until commit)
-                tx.execute("SET LOCAL synchronous_commit=OFF", &[])
-                    .await
-                    .map_err(|e| e.to_string())?;
+            execute_statements_streaming!(execute_statement, stream, app_handle, total_bytes)?;
 
-                macro_rules! execute_statement {
-                    ($stmt:expr) => {
-                        tx.execute($stmt, &[])
-                    };
-                }
+            // Restore settings
+            sqlx::query("SET FOREIGN_KEY_CHECKS=1")
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| e.to_string())?;
+            sqlx::query("SET UNIQUE_CHECKS=1")
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| e.to_string())?;
+            sqlx::query("SET AUTOCOMMIT=1")
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| e.to_string())?;
 
-                execute_statements_streaming!(execute_statement, stream, app_handle)?;
+            tx.commit().await.map_err(|e| e.to_string())?;
+        }
+        "postgres" => {
+            let pool = get_postgres_pool(params).await?;
+            let mut client = pool.get().await.map_err(|e| e.to_string())?;
+            let tx = client.transaction().await.map_err(|e| e.to_string())?;
 
-                tx.commit().await.map_err(|e| e.to_string())?;
-            }
-            "sqlite" => {
-                let pool = get_sqlite_pool(&params).await?;
-                let mut tx = pool.begin().await.map_err(|e| e.to_string())?;
-
-                // Performance optimizations for SQLite
-                sqlx::query("PRAGMA foreign_keys=OFF")
-                    .execute(&mut *tx)
-                    .await
-                    .map_err(|e| e.to_string())?;
-                sqlx::query("PRAGMA synchronous=OFF")
-                    .execute(&mut *tx)
-                    .await
-                    .map_err(|e| e.to_string())?;
-                sqlx::query("PRAGMA journal_mode=MEMORY")
-                    .execute(&mut *tx)
-                    .await
-                    .map_err(|e| e.to_string())?;
+            // Set schema search path so unqualified table names resolve correctly
+            tx.execute(&format!("SET search_path TO \"{}\"", pg_schema), &[])
+                .await
+                .map_err(|e| e.to_string())?;
 
-                macro_rules! execute_statement {
-                    ($stmt:expr) => {
-                        sqlx::query($stmt).execute(&mut *tx)
-                    };
-                }
+            // Performance optimizations for PostgreSQL
+            tx.execute("SET CONSTRAINTS ALL DEFERRED", &[])
+                .await
+                .map_err(|e| e.to_string())?;
+            // Temporarily disable synchronous commit for speed (data at risk until commit)
+            tx.execute("SET LOCAL synchronous_commit=OFF", &[])
+                .await
+                .map_err(|e| e.to_string())?;
 
-                execute_statements_streaming!(execute_statement, stream, app_handle)?;
+            macro_rules! execute_statement {
+                ($stmt:expr) => {
+                    tx.execute($stmt, &[])
+                };
+            }
 
-                // Restore settings
-                sqlx::query("PRAGMA foreign_keys=ON")
-                    .execute(&mut *tx)
-                    .await
-                    .map_err(|e| e.to_string())?;
-                sqlx::query("PRAGMA synchronous=FULL")
-                    .execute(&mut *tx)
-                    .await
-                    .map_err(|e| e.to_string())?;
+            execute_statements_streaming!(execute_statement, stream, app_handle, total_bytes)?;
 
-                tx.commit().await.map_err(|e| e.to_string())?;
-            }
-            _ => return Err("Unsupported driver".into()),
+            tx.commit().await.map_err(|e| e.to_string())?;
         }
+        "sqlite" => {
+            let pool = get_sqlite_pool(params).await?;
+            let mut tx = pool.begin().await.map_err(|e| e.to_string())?;
 
-        Ok::<(), String>(())
-    });
+            // Performance optimizations for SQLite
+            sqlx::query("PRAGMA foreign_keys=OFF")
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| e.to_string())?;
+            sqlx::query("PRAGMA synchronous=OFF")
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| e.to_string())?;
+            sqlx::query("PRAGMA journal_mode=MEMORY")
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| e.to_string())?;
 
-    let abort_handle = Arc::new(task.abort_handle());
-    let import_key = import_slot_key(&conn_id);
-    register_abort_handle(&state.handles, import_key.clone(), abort_handle.clone());
+            macro_rules! execute_statement {
+                ($stmt:expr) => {
+                    sqlx::query($stmt).execute(&mut *tx)
+                };
+            }
 
-    let result = task.await;
+            execute_statements_streaming!(execute_statement, stream, app_handle, total_bytes)?;
 
-    unregister_abort_handle(&state.handles, &import_key, &abort_handle);
+            // Restore settings
+            sqlx::query("PRAGMA foreign_keys=ON")
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| e.to_string())?;
+            sqlx::query("PRAGMA synchronous=FULL")
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| e.to_string())?;
 
-    match result {
-        Ok(res) => res,
-        Err(_) => Err("Import cancelled".into()),
+            tx.commit().await.map_err(|e| e.to_string())?;
+        }
+        other => {
+            return Err(crate::i18n::t_with(
+                "errors.unsupportedDriver",
+                &[("driver", other)],
+            ))
+        }
     }
+    Ok(())
 }
 
 // Creates a BufReader from the file without loading entire content into memory
 // For ZIP files, extracts to a string in memory (limitation of zip crate)
 // For regular SQL files, uses streaming with a large buffer
-fn create_sql_reader(file: File, file_path: &str) -> Result<Box<dyn BufRead + Send>, String> {
+// Also returns the size of the SQL content, for progress reporting
+fn create_sql_reader(
+    file: File,
+    file_path: &str,
+) -> Result<(Box<dyn BufRead + Send>, u64), String> {
     if file_path.ends_with(".zip") {
         // For ZIP files, we need to extract the SQL content to memory
         // The zip crate doesn't support true streaming because by_index requires ownership
-        let mut archive =
-            ZipArchive::new(file).map_err(|e| format!("Failed to open zip: {}", e))?;
+        let mut archive = ZipArchive::new(file).map_err(|e| {
+            crate::i18n::t_with("errors.openZipFailed", &[("error", &e.to_string())])
+        })?;
 
         // Find first .sql file and extract content
         for i in 0..archive.len() {
@@ -611,15 +873,17 @@ fn create_sql_reader(file: File, file_path: &str) -> Result<Box<dyn BufRead + Se
                     .map_err(|e| e.to_string())?;
 
                 // Create a BufReader from the extracted string
+                let total_bytes = content.len() as u64;
                 let cursor = std::io::Cursor::new(content.into_bytes());
-                return Ok(Box::new(BufReader::new(cursor)));
+                return Ok((Box::new(BufReader::new(cursor)), total_bytes));
             }
         }
-        Err("No .sql file found in zip archive".into())
+        Err(crate::i18n::t("errors.noSqlFileInZip"))
     } else {
         // For regular files, use a buffered reader with larger buffer for efficient streaming
+        let total_bytes = file.metadata().map(|m| m.len()).unwrap_or(0);
         let reader = BufReader::with_capacity(8192 * 16, file); // 128KB buffer
-        Ok(Box::new(reader))
+        Ok((Box::new(reader), total_bytes))
     }
 }
 
@@ -640,4 +904,65 @@ mod tests {
         assert_eq!(escape_sql_value(json!("Back\\slash")), "'Back\\\\slash'");
         assert_eq!(escape_sql_value(json!("Multi\nLine")), "'Multi\nLine'");
     }
+
+    #[test]
+    fn test_split_sql_statements() {
+        let sql =
+            "-- create\nCREATE TABLE t (\n  id INT\n);\n\nINSERT INTO t VALUES (1);\nSELECT 1";
+        assert_eq!(
+            split_sql_statements(sql).unwrap(),
+            vec![
+                "CREATE TABLE t (\n  id INT\n);",
+                "INSERT INTO t VALUES (1);",
+                "SELECT 1",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_statement_stream_tracks_lines_and_bytes() {
+        let sql = "-- header\n\nCREATE TABLE t (\n  id INT\n);\nINSERT INTO t VALUES (1);\n";
+        let mut stream = SqlStatementStream::new(std::io::Cursor::new(sql.as_bytes()));
+
+        stream.next_statement().unwrap().unwrap();
+        assert_eq!(stream.statement_line(), 3);
+        stream.next_statement().unwrap().unwrap();
+        assert_eq!(stream.statement_line(), 6);
+        assert_eq!(stream.percentage(Some(sql.len() as u64)), 100.0);
+        assert_eq!(stream.percentage(None), 0.0);
+        assert!(stream.next_statement().unwrap().is_none());
+    }
+
+    #[test]
+    fn test_statement_table() {
+        assert_eq!(
+            statement_table("INSERT INTO `users` VALUES (1);").as_deref(),
+            Some("users")
+        );
+        assert_eq!(
+            statement_table(r#"insert into "public"."orders"(id) values (1);"#).as_deref(),
+            Some("orders")
+        );
+        assert_eq!(
+            statement_table("DROP TABLE IF EXISTS \"items\";").as_deref(),
+            Some("items")
+        );
+        assert_eq!(
+            statement_table("CREATE TABLE IF NOT EXISTS logs (id INT);").as_deref(),
+            Some("logs")
+        );
+        assert_eq!(statement_table("CREATE INDEX idx ON logs (id);"), None);
+        assert_eq!(statement_table("SET FOREIGN_KEY_CHECKS=0;"), None);
+    }
+
+    #[test]
+    fn test_import_failure_summary() {
+        let stmt = format!("INSERT INTO t VALUES ('{}');", "x".repeat(400));
+        let failure = ImportFailure::new(7, 42, &stmt, "duplicate key".to_string());
+        assert_eq!(failure.table.as_deref(), Some("t"));
+        assert!(failure.statement.ends_with("..."));
+        assert!(failure
+            .summary()
+            .starts_with("Error at statement 7 (line 42): duplicate key\nQuery: INSERT INTO t"));
+    }
 }
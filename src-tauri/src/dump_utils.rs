@@ -1,3 +1,7 @@
+use std::collections::{BTreeSet, HashMap};
+
+use crate::models::ForeignKey;
+
 /// Returns a properly quoted, schema-qualified table identifier for SQL output.
 ///
 /// - MySQL: `table` (backtick-quoted, no schema prefix)
@@ -28,6 +32,75 @@ pub fn insert_into_statement(driver: &str, schema: &str, table: &str, values: &s
     )
 }
 
+/// Orders `tables` so that each table comes after the tables its foreign
+/// keys reference, keeping the input order where there is a choice. This
+/// lets a dump's INSERTs load parents before children.
+///
+/// References to tables outside `tables` and self-references are ignored.
+/// Tables in a reference cycle, or depending on one, cannot be ordered:
+/// they follow the others in input order and the second value is `true`.
+pub fn dependency_order(
+    tables: &[String],
+    foreign_keys: &HashMap<String, Vec<ForeignKey>>,
+) -> (Vec<String>, bool) {
+    let mut index: HashMap<&str, usize> = HashMap::new();
+    for (i, table) in tables.iter().enumerate() {
+        index.entry(table.as_str()).or_insert(i);
+    }
+
+    let mut pending = vec![0usize; tables.len()];
+    let mut children: Vec<Vec<usize>> = vec![Vec::new(); tables.len()];
+    for (i, table) in tables.iter().enumerate() {
+        let parents: BTreeSet<usize> = foreign_keys
+            .get(table)
+            .into_iter()
+            .flatten()
+            .filter_map(|fk| index.get(fk.ref_table.as_str()).copied())
+            .filter(|&parent| parent != i)
+            .collect();
+        pending[i] = parents.len();
+        for parent in parents {
+            children[parent].push(i);
+        }
+    }
+
+    let mut ready: BTreeSet<usize> = (0..tables.len()).filter(|&i| pending[i] == 0).collect();
+    let mut ordered = Vec::with_capacity(tables.len());
+    while let Some(i) = ready.pop_first() {
+        ordered.push(i);
+        for &child in &children[i] {
+            pending[child] -= 1;
+            if pending[child] == 0 {
+                ready.insert(child);
+            }
+        }
+    }
+
+    let has_cycles = ordered.len() < tables.len();
+    ordered.extend((0..tables.len()).filter(|&i| pending[i] > 0));
+    (
+        ordered.into_iter().map(|i| tables[i].clone()).collect(),
+        has_cycles,
+    )
+}
+
+/// Statements that suspend and restore foreign key checks around a dump
+/// whose tables reference each other in a cycle. PostgreSQL cannot switch
+/// checks off without superuser rights, so its constraints are deferred to
+/// the end of the restoring transaction instead; this covers constraints
+/// declared `DEFERRABLE`. Returns `None` for drivers without an equivalent.
+pub fn deferred_constraints(driver: &str) -> Option<(&'static str, &'static str)> {
+    match driver {
+        "mysql" => Some(("SET FOREIGN_KEY_CHECKS=0;", "SET FOREIGN_KEY_CHECKS=1;")),
+        "postgres" => Some((
+            "SET CONSTRAINTS ALL DEFERRED;",
+            "SET CONSTRAINTS ALL IMMEDIATE;",
+        )),
+        "sqlite" => Some(("PRAGMA foreign_keys=OFF;", "PRAGMA foreign_keys=ON;")),
+        _ => None,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -130,4 +203,77 @@ mod tests {
             );
         }
     }
+
+    mod dependency_order_tests {
+        use super::*;
+
+        fn fk(ref_table: &str) -> ForeignKey {
+            ForeignKey {
+                name: format!("fk_{}", ref_table),
+                column_name: format!("{}_id", ref_table),
+                ref_table: ref_table.to_string(),
+                ref_column: "id".to_string(),
+                on_delete: None,
+                on_update: None,
+            }
+        }
+
+        fn names(tables: &[&str]) -> Vec<String> {
+            tables.iter().map(|t| t.to_string()).collect()
+        }
+
+        #[test]
+        fn parents_come_before_children() {
+            let fks = HashMap::from([
+                (
+                    "order_items".to_string(),
+                    vec![fk("orders"), fk("products")],
+                ),
+                ("orders".to_string(), vec![fk("customers")]),
+            ]);
+            let (ordered, has_cycles) = dependency_order(
+                &names(&["order_items", "orders", "products", "customers"]),
+                &fks,
+            );
+            assert_eq!(
+                ordered,
+                names(&["products", "customers", "orders", "order_items"])
+            );
+            assert!(!has_cycles);
+        }
+
+        #[test]
+        fn self_and_outside_references_are_ignored() {
+            let fks = HashMap::from([(
+                "employees".to_string(),
+                vec![fk("employees"), fk("departments")],
+            )]);
+            let (ordered, has_cycles) = dependency_order(&names(&["employees", "audit"]), &fks);
+            assert_eq!(ordered, names(&["employees", "audit"]));
+            assert!(!has_cycles);
+        }
+
+        #[test]
+        fn cycles_follow_the_rest_in_input_order() {
+            let fks = HashMap::from([
+                ("a".to_string(), vec![fk("b")]),
+                ("b".to_string(), vec![fk("a")]),
+                ("c".to_string(), vec![fk("a")]),
+            ]);
+            let (ordered, has_cycles) = dependency_order(&names(&["c", "b", "a", "d"]), &fks);
+            assert_eq!(ordered, names(&["d", "c", "b", "a"]));
+            assert!(has_cycles);
+        }
+    }
+
+    #[test]
+    fn deferred_constraints_per_driver() {
+        assert_eq!(
+            deferred_constraints("mysql"),
+            Some(("SET FOREIGN_KEY_CHECKS=0;", "SET FOREIGN_KEY_CHECKS=1;"))
+        );
+        assert!(deferred_constraints("postgres").is_some());
+        assert!(deferred_constraints("sqlite").is_some());
+        assert!(deferred_constraints("mssql").is_none());
+    }
 }
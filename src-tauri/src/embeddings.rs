@@ -0,0 +1,430 @@
+//! Embeddings index of a connection's schema, for AI query generation.
+//!
+//! Pasting every table into the prompt stops working once a database has
+//! thousands of them. Instead, each table is rendered as a short document
+//! (its name and columns), embedded once through Ollama or OpenAI, and the
+//! vectors are stored on disk. `generate_ai_query` then embeds the user's
+//! prompt and sends only the closest tables as the schema context.
+//!
+//! Storage layout under the application config directory:
+//!   - `embeddings/<connection_id>.json` — the index of a connection
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use tauri::{AppHandle, Runtime};
+
+use crate::commands::{
+    driver_for, expand_ssh_connection_params, find_connection_by_id,
+    resolve_connection_params_with_id,
+};
+use crate::config;
+use crate::models::TableColumn;
+
+const EMBEDDINGS_DIR: &str = "embeddings";
+
+/// Tables sent to the model as schema context.
+pub const RELEVANT_TABLES: usize = 15;
+
+/// Documents embedded per request.
+const EMBED_BATCH: usize = 128;
+
+pub const DEFAULT_OPENAI_EMBEDDING_MODEL: &str = "text-embedding-3-small";
+pub const DEFAULT_OLLAMA_EMBEDDING_MODEL: &str = "nomic-embed-text";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IndexedTable {
+    pub name: String,
+    /// The text that was embedded, reused as the table's schema context.
+    pub document: String,
+    pub vector: Vec<f32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EmbeddingIndex {
+    pub provider: String,
+    pub model: String,
+    pub schema: Option<String>,
+    pub built_at: String,
+    pub tables: Vec<IndexedTable>,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct EmbeddingIndexInfo {
+    pub provider: String,
+    pub model: String,
+    pub schema: Option<String>,
+    pub built_at: String,
+    pub table_count: usize,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct RelevantTable {
+    pub name: String,
+    pub document: String,
+    pub score: f32,
+}
+
+impl EmbeddingIndex {
+    pub fn info(&self) -> EmbeddingIndexInfo {
+        EmbeddingIndexInfo {
+            provider: self.provider.clone(),
+            model: self.model.clone(),
+            schema: self.schema.clone(),
+            built_at: self.built_at.clone(),
+            table_count: self.tables.len(),
+        }
+    }
+
+    /// The `limit` tables closest to `query`, best first.
+    pub fn rank(&self, query: &[f32], limit: usize) -> Vec<RelevantTable> {
+        let mut scored: Vec<RelevantTable> = self
+            .tables
+            .iter()
+            .map(|t| RelevantTable {
+                name: t.name.clone(),
+                document: t.document.clone(),
+                score: cosine_similarity(query, &t.vector),
+            })
+            .collect();
+        scored.sort_by(|a, b| b.score.total_cmp(&a.score));
+        scored.truncate(limit);
+        scored
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Pure helpers
+// ---------------------------------------------------------------------------
+
+/// The text embedded for a table, in the format the AI prompt uses for
+/// schema context: `Table: orders (id integer PK, total numeric)`.
+pub fn table_document(table: &str, columns: &[TableColumn]) -> String {
+    let columns: Vec<String> = columns
+        .iter()
+        .map(|c| {
+            let pk = if c.is_pk { " PK" } else { "" };
+            format!("{} {}{}", c.name, c.data_type, pk)
+        })
+        .collect();
+    format!("Table: {} ({})", table, columns.join(", "))
+}
+
+/// Cosine similarity of two vectors; 0 when either is empty, zero, or the
+/// lengths differ.
+pub fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
+    if a.len() != b.len() || a.is_empty() {
+        return 0.0;
+    }
+    let (mut dot, mut norm_a, mut norm_b) = (0.0f32, 0.0f32, 0.0f32);
+    for (x, y) in a.iter().zip(b) {
+        dot += x * y;
+        norm_a += x * x;
+        norm_b += y * y;
+    }
+    if norm_a == 0.0 || norm_b == 0.0 {
+        return 0.0;
+    }
+    dot / (norm_a.sqrt() * norm_b.sqrt())
+}
+
+/// Schema context for the prompt: one document per line.
+pub fn schema_context(tables: &[RelevantTable]) -> String {
+    tables
+        .iter()
+        .map(|t| t.document.as_str())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+pub fn default_model(provider: &str) -> Result<&'static str, String> {
+    match provider {
+        "openai" => Ok(DEFAULT_OPENAI_EMBEDDING_MODEL),
+        "ollama" => Ok(DEFAULT_OLLAMA_EMBEDDING_MODEL),
+        _ => Err(format!(
+            "Embeddings are not supported for provider {}",
+            provider
+        )),
+    }
+}
+
+fn vector(value: &Value) -> Option<Vec<f32>> {
+    value
+        .as_array()?
+        .iter()
+        .map(|x| x.as_f64().map(|x| x as f32))
+        .collect()
+}
+
+/// Vectors of an OpenAI `/v1/embeddings` response, in input order.
+pub fn parse_openai_embeddings(response: &Value) -> Result<Vec<Vec<f32>>, String> {
+    let data = response["data"]
+        .as_array()
+        .ok_or("Invalid embeddings response from OpenAI")?;
+    let mut indexed = data
+        .iter()
+        .map(|item| {
+            let index = item["index"].as_u64().unwrap_or(0);
+            vector(&item["embedding"])
+                .map(|v| (index, v))
+                .ok_or("Invalid embedding in OpenAI response")
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    indexed.sort_by_key(|(index, _)| *index);
+    Ok(indexed.into_iter().map(|(_, v)| v).collect())
+}
+
+/// Vectors of an Ollama `/api/embed` response.
+pub fn parse_ollama_embeddings(response: &Value) -> Result<Vec<Vec<f32>>, String> {
+    response["embeddings"]
+        .as_array()
+        .ok_or("Invalid embeddings response from Ollama")?
+        .iter()
+        .map(|v| vector(v).ok_or_else(|| "Invalid embedding in Ollama response".to_string()))
+        .collect()
+}
+
+// ---------------------------------------------------------------------------
+// Storage (testable; take a directory)
+// ---------------------------------------------------------------------------
+
+fn index_path_in(dir: &Path, connection_id: &str) -> PathBuf {
+    dir.join(format!("{}.json", connection_id))
+}
+
+pub fn read_index_in(dir: &Path, connection_id: &str) -> Option<EmbeddingIndex> {
+    fs::read_to_string(index_path_in(dir, connection_id))
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+}
+
+pub fn write_index_in(
+    dir: &Path,
+    connection_id: &str,
+    index: &EmbeddingIndex,
+) -> Result<(), String> {
+    fs::create_dir_all(dir).map_err(|e| format!("Failed to create embeddings dir: {}", e))?;
+    let json = serde_json::to_string(index)
+        .map_err(|e| format!("Failed to serialize embeddings index: {}", e))?;
+    fs::write(index_path_in(dir, connection_id), json)
+        .map_err(|e| format!("Failed to write embeddings index: {}", e))
+}
+
+pub fn delete_index_in(dir: &Path, connection_id: &str) -> Result<(), String> {
+    let path = index_path_in(dir, connection_id);
+    if path.exists() {
+        fs::remove_file(path).map_err(|e| format!("Failed to delete embeddings index: {}", e))?;
+    }
+    Ok(())
+}
+
+// ---------------------------------------------------------------------------
+// Providers
+// ---------------------------------------------------------------------------
+
+async fn embed_batch(
+    client: &Client,
+    app_config: &config::AppConfig,
+    provider: &str,
+    model: &str,
+    inputs: &[String],
+) -> Result<Vec<Vec<f32>>, String> {
+    let body = json!({ "model": model, "input": inputs });
+    let request = match provider {
+        "openai" => {
+            let api_key = config::get_ai_api_key(provider)?;
+            client
+                .post("https://api.openai.com/v1/embeddings")
+                .header("Authorization", format!("Bearer {}", api_key))
+        }
+        "ollama" => {
+            let port = app_config.ai_ollama_port.unwrap_or(11434);
+            client.post(format!("http://localhost:{}/api/embed", port))
+        }
+        _ => {
+            return Err(format!(
+                "Embeddings are not supported for provider {}",
+                provider
+            ))
+        }
+    };
+
+    let res = request
+        .json(&body)
+        .send()
+        .await
+        .map_err(|e| e.to_string())?;
+    if !res.status().is_success() {
+        let error_text = res.text().await.unwrap_or_default();
+        return Err(format!("Embeddings Error: {}", error_text));
+    }
+    let response: Value = res.json().await.map_err(|e| e.to_string())?;
+    let vectors = match provider {
+        "openai" => parse_openai_embeddings(&response)?,
+        _ => parse_ollama_embeddings(&response)?,
+    };
+    if vectors.len() != inputs.len() {
+        return Err(format!(
+            "Expected {} embeddings, got {}",
+            inputs.len(),
+            vectors.len()
+        ));
+    }
+    Ok(vectors)
+}
+
+async fn embed(
+    app_config: &config::AppConfig,
+    provider: &str,
+    model: &str,
+    inputs: &[String],
+) -> Result<Vec<Vec<f32>>, String> {
+    let client = Client::new();
+    let mut vectors = Vec::with_capacity(inputs.len());
+    for batch in inputs.chunks(EMBED_BATCH) {
+        vectors.extend(embed_batch(&client, app_config, provider, model, batch).await?);
+    }
+    Ok(vectors)
+}
+
+// ---------------------------------------------------------------------------
+// App integration
+// ---------------------------------------------------------------------------
+
+fn index_dir<R: Runtime>(app: &AppHandle<R>) -> Result<PathBuf, String> {
+    Ok(crate::paths::app_config_dir(app)?.join(EMBEDDINGS_DIR))
+}
+
+async fn find_in_index(
+    app_config: &config::AppConfig,
+    index: &EmbeddingIndex,
+    prompt: &str,
+    limit: usize,
+) -> Result<Vec<RelevantTable>, String> {
+    let query = embed(
+        app_config,
+        &index.provider,
+        &index.model,
+        &[prompt.to_string()],
+    )
+    .await?;
+    let query = query
+        .first()
+        .ok_or("No embedding returned for the prompt")?;
+    Ok(index.rank(query, limit))
+}
+
+/// Schema context for `prompt` from the connection's index, or `None` when
+/// the connection has not been indexed.
+pub async fn relevant_schema_context(
+    app: &AppHandle,
+    connection_id: &str,
+    prompt: &str,
+) -> Result<Option<String>, String> {
+    let Some(index) = read_index_in(&index_dir(app)?, connection_id) else {
+        return Ok(None);
+    };
+    let app_config = config::load_config_internal(app);
+    let tables = find_in_index(&app_config, &index, prompt, RELEVANT_TABLES).await?;
+    Ok(Some(schema_context(&tables)))
+}
+
+/// Embed every table of the connection (of `schema`, when given) and store
+/// the index, replacing the previous one.
+#[tauri::command]
+pub async fn build_schema_embeddings(
+    app: AppHandle,
+    connection_id: String,
+    provider: String,
+    model: Option<String>,
+    schema: Option<String>,
+) -> Result<EmbeddingIndexInfo, String> {
+    let model = match model.filter(|m| !m.trim().is_empty()) {
+        Some(model) => model,
+        None => default_model(&provider)?.to_string(),
+    };
+
+    let saved_conn = find_connection_by_id(&app, &connection_id)?;
+    let expanded_params = expand_ssh_connection_params(&app, &saved_conn.params).await?;
+    let params = resolve_connection_params_with_id(&expanded_params, &connection_id)?;
+    let drv = driver_for(&saved_conn.params.driver).await?;
+    let mut columns = drv
+        .get_all_columns_batch(&params, schema.as_deref())
+        .await?
+        .into_iter()
+        .collect::<Vec<_>>();
+    columns.sort_by(|a, b| a.0.cmp(&b.0));
+
+    log::info!(
+        "Embedding {} tables of connection {} with {} {}",
+        columns.len(),
+        connection_id,
+        provider,
+        model
+    );
+    let documents: Vec<String> = columns
+        .iter()
+        .map(|(table, cols)| table_document(table, cols))
+        .collect();
+    let app_config = config::load_config_internal(&app);
+    let vectors = embed(&app_config, &provider, &model, &documents).await?;
+
+    let index = EmbeddingIndex {
+        provider,
+        model,
+        schema,
+        built_at: chrono::Utc::now().to_rfc3339(),
+        tables: columns
+            .into_iter()
+            .zip(documents)
+            .zip(vectors)
+            .map(|(((name, _), document), vector)| IndexedTable {
+                name,
+                document,
+                vector,
+            })
+            .collect(),
+    };
+    write_index_in(&index_dir(&app)?, &connection_id, &index)?;
+    Ok(index.info())
+}
+
+/// The tables of the connection's index closest to `prompt`.
+#[tauri::command]
+pub async fn find_relevant_schema(
+    app: AppHandle,
+    connection_id: String,
+    prompt: String,
+    limit: Option<usize>,
+) -> Result<Vec<RelevantTable>, String> {
+    let index = read_index_in(&index_dir(&app)?, &connection_id)
+        .ok_or("This connection has no schema embeddings index")?;
+    let app_config = config::load_config_internal(&app);
+    find_in_index(
+        &app_config,
+        &index,
+        &prompt,
+        limit.unwrap_or(RELEVANT_TABLES),
+    )
+    .await
+}
+
+#[tauri::command]
+pub fn get_schema_embeddings_info(
+    app: AppHandle,
+    connection_id: String,
+) -> Result<Option<EmbeddingIndexInfo>, String> {
+    Ok(read_index_in(&index_dir(&app)?, &connection_id).map(|index| index.info()))
+}
+
+#[tauri::command]
+pub fn delete_schema_embeddings(app: AppHandle, connection_id: String) -> Result<(), String> {
+    delete_index_in(&index_dir(&app)?, &connection_id)
+}
@@ -0,0 +1,120 @@
+#[cfg(test)]
+mod tests {
+    use crate::embeddings::{
+        cosine_similarity, default_model, delete_index_in, parse_ollama_embeddings,
+        parse_openai_embeddings, read_index_in, schema_context, table_document, write_index_in,
+        EmbeddingIndex, IndexedTable,
+    };
+    use crate::models::TableColumn;
+    use serde_json::json;
+    use tempfile::TempDir;
+
+    fn column(name: &str, data_type: &str, is_pk: bool) -> TableColumn {
+        TableColumn {
+            name: name.to_string(),
+            data_type: data_type.to_string(),
+            is_pk,
+            is_nullable: !is_pk,
+            is_auto_increment: false,
+            default_value: None,
+            character_maximum_length: None,
+        }
+    }
+
+    fn table(name: &str, vector: Vec<f32>) -> IndexedTable {
+        IndexedTable {
+            name: name.to_string(),
+            document: format!("Table: {} ()", name),
+            vector,
+        }
+    }
+
+    fn index() -> EmbeddingIndex {
+        EmbeddingIndex {
+            provider: "ollama".to_string(),
+            model: "nomic-embed-text".to_string(),
+            schema: None,
+            built_at: "2026-01-01T00:00:00Z".to_string(),
+            tables: vec![
+                table("orders", vec![1.0, 0.0]),
+                table("customers", vec![0.0, 1.0]),
+                table("order_items", vec![0.8, 0.2]),
+            ],
+        }
+    }
+
+    #[test]
+    fn renders_table_documents() {
+        let columns = vec![
+            column("id", "integer", true),
+            column("total", "numeric", false),
+        ];
+        assert_eq!(
+            table_document("orders", &columns),
+            "Table: orders (id integer PK, total numeric)"
+        );
+    }
+
+    #[test]
+    fn cosine_similarity_handles_edge_cases() {
+        assert!((cosine_similarity(&[1.0, 2.0], &[2.0, 4.0]) - 1.0).abs() < 1e-6);
+        assert_eq!(cosine_similarity(&[1.0, 0.0], &[0.0, 1.0]), 0.0);
+        assert_eq!(cosine_similarity(&[0.0, 0.0], &[1.0, 1.0]), 0.0);
+        assert_eq!(cosine_similarity(&[1.0], &[1.0, 1.0]), 0.0);
+        assert_eq!(cosine_similarity(&[], &[]), 0.0);
+    }
+
+    #[test]
+    fn ranks_closest_tables_first() {
+        let ranked = index().rank(&[1.0, 0.1], 2);
+        let names: Vec<&str> = ranked.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(names, ["orders", "order_items"]);
+        assert_eq!(
+            schema_context(&ranked),
+            "Table: orders ()\nTable: order_items ()"
+        );
+    }
+
+    #[test]
+    fn parses_provider_responses() {
+        // OpenAI may return items out of order; `index` says where they go.
+        let openai = json!({"data": [
+            {"index": 1, "embedding": [0.5, 0.5]},
+            {"index": 0, "embedding": [1.0, 0.0]}
+        ]});
+        assert_eq!(
+            parse_openai_embeddings(&openai).unwrap(),
+            vec![vec![1.0, 0.0], vec![0.5, 0.5]]
+        );
+        assert!(parse_openai_embeddings(&json!({"error": "bad key"})).is_err());
+
+        let ollama = json!({"embeddings": [[0.25, 0.75]]});
+        assert_eq!(
+            parse_ollama_embeddings(&ollama).unwrap(),
+            vec![vec![0.25, 0.75]]
+        );
+        assert!(parse_ollama_embeddings(&json!({"embeddings": [["x"]]})).is_err());
+    }
+
+    #[test]
+    fn only_embedding_providers_have_default_models() {
+        assert_eq!(default_model("openai").unwrap(), "text-embedding-3-small");
+        assert_eq!(default_model("ollama").unwrap(), "nomic-embed-text");
+        assert!(default_model("anthropic").is_err());
+    }
+
+    #[test]
+    fn index_round_trips_on_disk() {
+        let dir = TempDir::new().unwrap();
+        assert!(read_index_in(dir.path(), "conn-1").is_none());
+
+        write_index_in(dir.path(), "conn-1", &index()).unwrap();
+        let stored = read_index_in(dir.path(), "conn-1").unwrap();
+        assert_eq!(stored.info().table_count, 3);
+        assert_eq!(stored.tables[2].vector, vec![0.8, 0.2]);
+
+        delete_index_in(dir.path(), "conn-1").unwrap();
+        assert!(read_index_in(dir.path(), "conn-1").is_none());
+        delete_index_in(dir.path(), "conn-1").unwrap();
+    }
+}
@@ -0,0 +1,238 @@
+//! Streaming schema snapshots for the ER diagram.
+//!
+//! `get_schema_snapshot` answers with every table, column and foreign key in
+//! one response, which stalls the diagram window on schemas with thousands
+//! of tables. A streamed snapshot instead emits `er-snapshot://batch` events
+//! to the calling window: the table names first, so the diagram knows what
+//! is coming, then the columns and foreign keys in batches of tables. The
+//! command resolves with the number of tables once the last batch is out.
+//!
+//! A `tables` subset limits a snapshot, streamed or not, to a cluster of
+//! tables, so a diagram can open focused on one domain area without loading
+//! the rest. With a `depth`, tables up to that many foreign keys away from
+//! the selection are included too, following references in both directions.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Runtime, WebviewWindow};
+
+use crate::commands::{
+    driver_for, expand_ssh_connection_params, find_connection_by_id,
+    resolve_connection_params_with_id,
+};
+use crate::models::{ForeignKey, TableColumn, TableSchema};
+
+const SNAPSHOT_BATCH_EVENT: &str = "er-snapshot://batch";
+
+/// Tables per event when the caller does not pick a batch size.
+pub const DEFAULT_SNAPSHOT_BATCH_SIZE: usize = 100;
+
+/// One event of a streamed snapshot, told apart by `stream_id`.
+#[derive(Debug, Clone, Serialize)]
+pub struct SnapshotBatch {
+    pub stream_id: String,
+    #[serde(flatten)]
+    pub payload: SnapshotPayload,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum SnapshotPayload {
+    /// Every table in the snapshot, in display order. Always sent first.
+    Tables { tables: Vec<String> },
+    Columns {
+        columns: BTreeMap<String, Vec<TableColumn>>,
+    },
+    ForeignKeys {
+        foreign_keys: BTreeMap<String, Vec<ForeignKey>>,
+    },
+}
+
+// ---------------------------------------------------------------------------
+// Subsets (testable; pure)
+// ---------------------------------------------------------------------------
+
+/// The `seeds` plus every table at most `depth` foreign keys away from one.
+/// `edges` are `(table, referenced table)` pairs; they are followed both
+/// ways, so a table's parents and children are both neighbours.
+pub fn neighbourhood<'a>(
+    seeds: &[String],
+    edges: impl IntoIterator<Item = (&'a str, &'a str)>,
+    depth: u32,
+) -> HashSet<String> {
+    let mut adjacent: HashMap<&str, Vec<&str>> = HashMap::new();
+    for (table, referenced) in edges {
+        adjacent.entry(table).or_default().push(referenced);
+        adjacent.entry(referenced).or_default().push(table);
+    }
+
+    let mut reached: HashSet<String> = seeds.iter().cloned().collect();
+    let mut frontier: Vec<String> = seeds.to_vec();
+    for _ in 0..depth {
+        let mut next = Vec::new();
+        for table in &frontier {
+            for neighbour in adjacent.get(table.as_str()).into_iter().flatten() {
+                if reached.insert(neighbour.to_string()) {
+                    next.push(neighbour.to_string());
+                }
+            }
+        }
+        if next.is_empty() {
+            break;
+        }
+        frontier = next;
+    }
+    reached
+}
+
+/// Keep the tables of `snapshot` within `depth` foreign keys of `tables`.
+pub fn filter_snapshot(
+    snapshot: Vec<TableSchema>,
+    tables: &[String],
+    depth: u32,
+) -> Vec<TableSchema> {
+    let keep = neighbourhood(
+        tables,
+        snapshot.iter().flat_map(|table| {
+            table
+                .foreign_keys
+                .iter()
+                .map(|fk| (table.name.as_str(), fk.ref_table.as_str()))
+        }),
+        depth,
+    );
+    snapshot
+        .into_iter()
+        .filter(|table| keep.contains(&table.name))
+        .collect()
+}
+
+// ---------------------------------------------------------------------------
+// Batching (testable; pure)
+// ---------------------------------------------------------------------------
+
+/// Keep the tables named in `subset`, in the order the database listed
+/// them. Names in `subset` that do not exist are ignored.
+pub fn select_tables(all: Vec<String>, subset: Option<&[String]>) -> Vec<String> {
+    match subset {
+        Some(subset) => {
+            let wanted: HashSet<&str> = subset.iter().map(String::as_str).collect();
+            all.into_iter()
+                .filter(|name| wanted.contains(name.as_str()))
+                .collect()
+        }
+        None => all,
+    }
+}
+
+/// Split `by_table` into maps of at most `batch_size` of `tables` each.
+/// Tables missing from `by_table` get an empty list, so every table is
+/// reported exactly once.
+pub fn batch_by_table<T>(
+    tables: &[String],
+    mut by_table: HashMap<String, Vec<T>>,
+    batch_size: usize,
+) -> Vec<BTreeMap<String, Vec<T>>> {
+    tables
+        .chunks(batch_size.max(1))
+        .map(|chunk| {
+            chunk
+                .iter()
+                .map(|name| (name.clone(), by_table.remove(name).unwrap_or_default()))
+                .collect()
+        })
+        .collect()
+}
+
+// ---------------------------------------------------------------------------
+// Tauri commands
+// ---------------------------------------------------------------------------
+
+#[tauri::command]
+pub async fn stream_schema_snapshot<R: Runtime>(
+    app: AppHandle<R>,
+    window: WebviewWindow<R>,
+    connection_id: String,
+    stream_id: String,
+    schema: Option<String>,
+    tables: Option<Vec<String>>,
+    depth: Option<u32>,
+    batch_size: Option<usize>,
+) -> Result<usize, String> {
+    let saved_conn = find_connection_by_id(&app, &connection_id)?;
+    let expanded_params = expand_ssh_connection_params(&app, &saved_conn.params).await?;
+    let params = resolve_connection_params_with_id(&expanded_params, &connection_id)?;
+    let drv = driver_for(&saved_conn.params.driver).await?;
+    let batch_size = batch_size.unwrap_or(DEFAULT_SNAPSHOT_BATCH_SIZE);
+
+    let emit = |payload: SnapshotPayload| {
+        app.emit_to(
+            window.label(),
+            SNAPSHOT_BATCH_EVENT,
+            SnapshotBatch {
+                stream_id: stream_id.clone(),
+                payload,
+            },
+        )
+        .map_err(|e| format!("Failed to emit schema snapshot batch: {}", e))
+    };
+
+    // Neighbours are found through foreign keys, so those are read first
+    // when the subset has to be widened.
+    let mut foreign_keys = None;
+    let tables = match tables {
+        Some(tables) if depth.unwrap_or(0) > 0 => {
+            let by_table = drv
+                .get_all_foreign_keys_batch(&params, schema.as_deref())
+                .await?;
+            let edges = by_table.iter().flat_map(|(table, fks)| {
+                fks.iter()
+                    .map(move |fk| (table.as_str(), fk.ref_table.as_str()))
+            });
+            let widened = neighbourhood(&tables, edges, depth.unwrap_or(0));
+            foreign_keys = Some(by_table);
+            Some(widened.into_iter().collect::<Vec<_>>())
+        }
+        tables => tables,
+    };
+
+    let all = drv
+        .get_tables(&params, schema.as_deref())
+        .await?
+        .into_iter()
+        .map(|table| table.name)
+        .collect();
+    let names = select_tables(all, tables.as_deref());
+    log::info!(
+        "Streaming schema snapshot of {} table(s) on connection {}",
+        names.len(),
+        connection_id
+    );
+    emit(SnapshotPayload::Tables {
+        tables: names.clone(),
+    })?;
+    if names.is_empty() {
+        return Ok(0);
+    }
+
+    let columns = drv
+        .get_all_columns_batch(&params, schema.as_deref())
+        .await?;
+    for columns in batch_by_table(&names, columns, batch_size) {
+        emit(SnapshotPayload::Columns { columns })?;
+    }
+
+    let foreign_keys = match foreign_keys {
+        Some(foreign_keys) => foreign_keys,
+        None => {
+            drv.get_all_foreign_keys_batch(&params, schema.as_deref())
+                .await?
+        }
+    };
+    for foreign_keys in batch_by_table(&names, foreign_keys, batch_size) {
+        emit(SnapshotPayload::ForeignKeys { foreign_keys })?;
+    }
+
+    Ok(names.len())
+}
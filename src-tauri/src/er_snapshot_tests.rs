@@ -0,0 +1,108 @@
+#[cfg(test)]
+mod tests {
+    use crate::er_snapshot::{batch_by_table, filter_snapshot, neighbourhood, select_tables};
+    use crate::models::{ForeignKey, TableSchema};
+    use std::collections::HashMap;
+
+    fn names(names: &[&str]) -> Vec<String> {
+        names.iter().map(|n| n.to_string()).collect()
+    }
+
+    fn table(name: &str, references: &[&str]) -> TableSchema {
+        TableSchema {
+            name: name.to_string(),
+            columns: Vec::new(),
+            foreign_keys: references
+                .iter()
+                .map(|referenced| ForeignKey {
+                    name: format!("fk_{}_{}", name, referenced),
+                    column_name: format!("{}_id", referenced),
+                    ref_table: referenced.to_string(),
+                    ref_column: "id".to_string(),
+                    on_delete: None,
+                    on_update: None,
+                })
+                .collect(),
+        }
+    }
+
+    fn shop() -> Vec<TableSchema> {
+        vec![
+            table("customers", &[]),
+            table("orders", &["customers"]),
+            table("order_items", &["orders", "products"]),
+            table("products", &["suppliers"]),
+            table("suppliers", &[]),
+            table("audit_log", &[]),
+        ]
+    }
+
+    #[test]
+    fn neighbourhood_follows_references_both_ways() {
+        let edges = [("orders", "customers"), ("order_items", "orders")];
+        let reached = neighbourhood(&names(&["orders"]), edges, 1);
+        let mut reached: Vec<_> = reached.into_iter().collect();
+        reached.sort();
+        assert_eq!(reached, ["customers", "order_items", "orders"]);
+
+        assert_eq!(neighbourhood(&names(&["orders"]), edges, 0).len(), 1);
+    }
+
+    #[test]
+    fn filters_snapshots_to_n_degree_neighbours() {
+        let seeds = names(&["orders"]);
+        let tables = |depth| -> Vec<String> {
+            filter_snapshot(shop(), &seeds, depth)
+                .into_iter()
+                .map(|t| t.name)
+                .collect()
+        };
+
+        assert_eq!(tables(0), ["orders"]);
+        assert_eq!(tables(1), ["customers", "orders", "order_items"]);
+        assert_eq!(
+            tables(3),
+            [
+                "customers",
+                "orders",
+                "order_items",
+                "products",
+                "suppliers"
+            ]
+        );
+    }
+
+    #[test]
+    fn subset_keeps_database_order_and_skips_unknown_tables() {
+        let all = names(&["customers", "orders", "order_items", "products"]);
+        let subset = names(&["products", "orders", "archived"]);
+
+        assert_eq!(
+            select_tables(all.clone(), Some(&subset)),
+            ["orders", "products"]
+        );
+        assert_eq!(select_tables(all.clone(), None), all);
+    }
+
+    #[test]
+    fn batches_cover_every_table_once() {
+        let tables = names(&["a", "b", "c"]);
+        let mut columns = HashMap::new();
+        columns.insert("a".to_string(), vec![1, 2]);
+        columns.insert("c".to_string(), vec![3]);
+        columns.insert("not_selected".to_string(), vec![4]);
+
+        let batches = batch_by_table(&tables, columns, 2);
+        assert_eq!(batches.len(), 2);
+        assert_eq!(batches[0]["a"], [1, 2]);
+        assert!(batches[0]["b"].is_empty());
+        assert_eq!(batches[1]["c"], [3]);
+        assert!(!batches.iter().any(|b| b.contains_key("not_selected")));
+    }
+
+    #[test]
+    fn zero_batch_size_still_makes_progress() {
+        let batches = batch_by_table(&names(&["a", "b"]), HashMap::<_, Vec<u8>>::new(), 0);
+        assert_eq!(batches.len(), 2);
+    }
+}
@@ -1,29 +1,42 @@
+mod anonymize;
 mod format;
+mod json_sink;
+mod layout;
+mod parquet_sink;
 mod progress;
 mod sink;
+pub mod sql_dump;
+mod xlsx_sink;
 
 #[cfg(test)]
 mod tests;
 
+pub use anonymize::{
+    AnonymizationProfile, AnonymizeRule, AnonymizeSink, ColumnRule, FakeKind, SHUFFLE_WINDOW,
+};
 pub use format::{parse_csv_delimiter, value_to_csv_string, ExportFormat, DEFAULT_CSV_DELIMITER};
+pub use json_sink::{is_json_type, nest_value, JsonColumn, JsonExportSink, JsonLayout};
+pub use layout::{format_value, ColumnProjection, LayoutSink};
+pub use parquet_sink::{ParquetCompression, ParquetSink, DEFAULT_ROW_GROUP_SIZE};
 pub use progress::{ProgressEmitter, DEFAULT_INTERVAL as DEFAULT_PROGRESS_INTERVAL};
 pub use sink::{CsvSink, JsonSink, RowSink};
+pub use xlsx_sink::{XlsxSink, DEFAULT_SHEET_NAME, MAX_SHEET_ROWS};
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fs::File;
 use std::io::BufWriter;
 use std::sync::{Arc, Mutex};
 
 use serde::Serialize;
 use serde_json::Value;
-use tauri::{AppHandle, Emitter, Runtime, State};
+use tauri::{AppHandle, Emitter, Runtime, State, WebviewWindow};
 
 use crate::commands::{
     expand_ssh_connection_params, find_connection_by_id, register_abort_handle,
     resolve_connection_params_with_id, unregister_abort_handle, AbortHandleMap,
 };
-use crate::drivers::{mysql, postgres, sqlite};
-use crate::models::ConnectionParams;
+use crate::models::{ConnectionParams, TableColumn};
+use crate::preferences::ColumnLayout;
 
 pub struct ExportCancellationState {
     pub handles: Arc<Mutex<AbortHandleMap>>,
@@ -44,18 +57,26 @@ struct ExportProgressPayload {
 
 const EXPORT_PROGRESS_EVENT: &str = "export_progress";
 
+/// Rows read from the driver at a time. Each batch is written out before the
+/// next one is read, so a slow disk holds back the database stream instead
+/// of rows piling up in memory.
+const EXPORT_BATCH_SIZE: usize = 1000;
+
 fn sanitize_query(query: &str) -> String {
     query.trim().trim_end_matches(';').to_string()
 }
 
+/// Cancel the calling window's exports on `connection_id`.
 #[tauri::command]
-pub async fn cancel_export(
+pub async fn cancel_export<R: Runtime>(
+    window: WebviewWindow<R>,
     state: State<'_, ExportCancellationState>,
     connection_id: String,
 ) -> Result<(), String> {
+    let cancel_key = crate::window_state::cancellation_key(window.label(), &connection_id);
     let entries = {
         let mut handles = state.handles.lock().unwrap();
-        handles.remove(&connection_id).unwrap_or_default()
+        handles.remove(&cancel_key).unwrap_or_default()
     };
     for handle in entries {
         handle.abort();
@@ -66,15 +87,55 @@ pub async fn cancel_export(
 #[tauri::command]
 pub async fn export_query_to_file<R: Runtime>(
     app: AppHandle<R>,
+    window: WebviewWindow<R>,
     state: State<'_, ExportCancellationState>,
     connection_id: String,
     query: String,
     file_path: String,
     format: String,
     csv_delimiter: Option<String>,
+    table: Option<String>,
+    schema: Option<String>,
+    anonymization_profile_id: Option<String>,
+    include_provenance: Option<bool>,
+) -> Result<(), String> {
+    export_query_to_file_in_scope(
+        app,
+        window.label(),
+        state,
+        connection_id,
+        query,
+        file_path,
+        format,
+        csv_delimiter,
+        table,
+        schema,
+        anonymization_profile_id,
+        include_provenance,
+    )
+    .await
+}
+
+/// [`export_query_to_file`], cancellable under `cancel_scope` (see
+/// [`crate::window_state::cancellation_key`]).
+pub(crate) async fn export_query_to_file_in_scope<R: Runtime>(
+    app: AppHandle<R>,
+    cancel_scope: &str,
+    state: State<'_, ExportCancellationState>,
+    connection_id: String,
+    query: String,
+    file_path: String,
+    format: String,
+    csv_delimiter: Option<String>,
+    table: Option<String>,
+    schema: Option<String>,
+    anonymization_profile_id: Option<String>,
+    include_provenance: Option<bool>,
 ) -> Result<(), String> {
     let sanitized_query = sanitize_query(&query);
     let saved_conn = find_connection_by_id(&app, &connection_id)?;
+    // Also reached from the automation socket with any statement.
+    crate::read_only::ensure_query_allowed(&saved_conn.params, &sanitized_query)?;
     let expanded_params = expand_ssh_connection_params(&app, &saved_conn.params).await?;
     let params = resolve_connection_params_with_id(&expanded_params, &connection_id)?;
     let driver = saved_conn.params.driver.clone();
@@ -82,8 +143,30 @@ pub async fn export_query_to_file<R: Runtime>(
     let export_format = ExportFormat::parse(&format)?;
     let delimiter = parse_csv_delimiter(csv_delimiter.as_deref());
 
+    // When exporting a table, apply the saved column layout so the file
+    // matches what the user sees in the grid
+    let layout = match table.as_deref() {
+        Some(table) => {
+            crate::preferences::load_column_layout(&connection_id, schema.as_deref(), table)?
+        }
+        None => None,
+    };
+
+    let anonymization = match anonymization_profile_id.as_deref() {
+        Some(id) => Some(anonymize::find_profile_in(
+            &crate::paths::app_config_dir(&app)?,
+            id,
+        )?),
+        None => None,
+    };
+
     let app_for_task = app.clone();
-    let task_connection_id = connection_id.clone();
+    let task_schema = schema.clone();
+    let provenance_input = include_provenance
+        .unwrap_or(false)
+        .then(|| (params.clone(), sanitized_query.clone(), file_path.clone()));
+    let started_at = chrono::Utc::now();
+    let started = std::time::Instant::now();
 
     let task = tokio::spawn(async move {
         let file = File::create(&file_path).map_err(|e| e.to_string())?;
@@ -93,30 +176,319 @@ pub async fn export_query_to_file<R: Runtime>(
             &driver,
             &params,
             &sanitized_query,
+            task_schema.as_deref(),
             writer,
             export_format,
             delimiter,
+            layout,
+            anonymization,
         )
         .await
     });
 
     let abort_handle = Arc::new(task.abort_handle());
-    register_abort_handle(
-        &state.handles,
-        task_connection_id.clone(),
-        abort_handle.clone(),
-    );
+    let cancel_key = crate::window_state::cancellation_key(cancel_scope, &connection_id);
+    register_abort_handle(&state.handles, cancel_key.clone(), abort_handle.clone());
+
+    let result = task.await;
+
+    unregister_abort_handle(&state.handles, &cancel_key, &abort_handle);
+
+    match result {
+        Ok(Ok(())) => {}
+        Ok(Err(e)) => return Err(e),
+        Err(_) => return Err(crate::i18n::t("errors.exportCancelled")),
+    }
+
+    if let Some((params, query, file_path)) = provenance_input {
+        let elapsed = started.elapsed();
+        let drv = crate::commands::driver_for(&saved_conn.params.driver).await?;
+        let checksum =
+            crate::provenance::schema_checksum(drv.as_ref(), &params, schema.as_deref()).await;
+        let provenance = crate::provenance::describe(
+            &saved_conn,
+            schema.as_deref(),
+            &query,
+            started_at,
+            elapsed,
+            checksum,
+        );
+        crate::provenance::write_sidecar(std::path::Path::new(&file_path), &provenance)?;
+    }
+    Ok(())
+}
+
+/// What a single-format export reads: a whole table, or the rows of `query`.
+struct ExportSource {
+    connection_id: String,
+    table: Option<String>,
+    query: Option<String>,
+    schema: Option<String>,
+    anonymization_profile_id: Option<String>,
+}
+
+/// Export a table, or the result of `query`, to a Parquet file. Rows are
+/// written in row groups of `row_group_size`, compressed with `compression`
+/// (`none`, `snappy`, `gzip`, `zstd` or `lz4`; snappy by default). Progress
+/// and cancellation work as for `export_query_to_file`.
+#[tauri::command]
+pub async fn export_table_parquet<R: Runtime>(
+    app: AppHandle<R>,
+    window: WebviewWindow<R>,
+    state: State<'_, ExportCancellationState>,
+    connection_id: String,
+    file_path: String,
+    table: Option<String>,
+    query: Option<String>,
+    schema: Option<String>,
+    compression: Option<String>,
+    row_group_size: Option<usize>,
+    anonymization_profile_id: Option<String>,
+) -> Result<(), String> {
+    let compression = ParquetCompression::parse(compression.as_deref())?;
+    let source = ExportSource {
+        connection_id,
+        table,
+        query,
+        schema,
+        anonymization_profile_id,
+    };
+    export_to_sink(app, window, state, source, file_path, move |file| {
+        Ok(ParquetSink::new(
+            BufWriter::new(file),
+            compression,
+            row_group_size.unwrap_or(DEFAULT_ROW_GROUP_SIZE),
+        ))
+    })
+    .await
+}
+
+/// Export a table, or the result of `query`, to an Excel workbook. Rows go
+/// to the sheet `sheet_name` ("Results" by default); past `row_limit` rows
+/// (and always past Excel's own limit) they continue on further sheets.
+/// Progress and cancellation work as for `export_query_to_file`.
+#[tauri::command]
+pub async fn export_query_xlsx<R: Runtime>(
+    app: AppHandle<R>,
+    window: WebviewWindow<R>,
+    state: State<'_, ExportCancellationState>,
+    connection_id: String,
+    file_path: String,
+    table: Option<String>,
+    query: Option<String>,
+    schema: Option<String>,
+    sheet_name: Option<String>,
+    row_limit: Option<u32>,
+    anonymization_profile_id: Option<String>,
+) -> Result<(), String> {
+    // Fail on a bad sheet name before the query runs.
+    let sheet_name = xlsx_sink::sheet_name(sheet_name.as_deref())?;
+    let source = ExportSource {
+        connection_id,
+        table,
+        query,
+        schema,
+        anonymization_profile_id,
+    };
+    export_to_sink(app, window, state, source, file_path, move |file| {
+        XlsxSink::new(BufWriter::new(file), &sheet_name, row_limit)
+    })
+    .await
+}
+
+/// Export a table, or the result of `query`, as JSON: one array of row
+/// objects, or one object per line when `layout` is `ndjson`. JSON and JSONB
+/// columns of the table, and the `json_columns` of a query, are written as
+/// nested values rather than strings. `include_schema` puts the columns and
+/// their declared types first. Progress and cancellation work as for
+/// `export_query_to_file`.
+#[tauri::command]
+pub async fn export_query_json<R: Runtime>(
+    app: AppHandle<R>,
+    window: WebviewWindow<R>,
+    state: State<'_, ExportCancellationState>,
+    connection_id: String,
+    file_path: String,
+    table: Option<String>,
+    query: Option<String>,
+    schema: Option<String>,
+    layout: Option<String>,
+    include_schema: Option<bool>,
+    json_columns: Option<Vec<String>>,
+    anonymization_profile_id: Option<String>,
+) -> Result<(), String> {
+    let layout = JsonLayout::parse(layout.as_deref())?;
+    let declared = match table.as_deref() {
+        Some(table) => table_columns(&app, &connection_id, table, schema.as_deref()).await?,
+        None => Vec::new(),
+    };
+    let json_columns: HashSet<String> = json_columns
+        .unwrap_or_default()
+        .into_iter()
+        .chain(
+            declared
+                .iter()
+                .filter(|column| is_json_type(&column.data_type))
+                .map(|column| column.name.clone()),
+        )
+        .collect();
+    let header: Option<Vec<JsonColumn>> = include_schema.unwrap_or(false).then(|| {
+        declared
+            .into_iter()
+            .map(|column| JsonColumn {
+                name: column.name,
+                data_type: Some(column.data_type),
+            })
+            .collect()
+    });
+    let source = ExportSource {
+        connection_id,
+        table,
+        query,
+        schema,
+        anonymization_profile_id,
+    };
+    export_to_sink(app, window, state, source, file_path, move |file| {
+        Ok(JsonExportSink::new(
+            BufWriter::new(file),
+            layout,
+            json_columns,
+            header,
+        ))
+    })
+    .await
+}
+
+/// The declared columns of `table`, which tell the JSON export what each
+/// column holds.
+async fn table_columns<R: Runtime>(
+    app: &AppHandle<R>,
+    connection_id: &str,
+    table: &str,
+    schema: Option<&str>,
+) -> Result<Vec<TableColumn>, String> {
+    let saved_conn = find_connection_by_id(app, connection_id)?;
+    let expanded_params = expand_ssh_connection_params(app, &saved_conn.params).await?;
+    let params = resolve_connection_params_with_id(&expanded_params, connection_id)?;
+    let drv = crate::commands::driver_for(&saved_conn.params.driver).await?;
+    drv.get_columns(&params, table, schema).await
+}
+
+/// Shared body of the single-format export commands: streams `source` into
+/// the sink `open` builds on the output file.
+async fn export_to_sink<R, S, O>(
+    app: AppHandle<R>,
+    window: WebviewWindow<R>,
+    state: State<'_, ExportCancellationState>,
+    source: ExportSource,
+    file_path: String,
+    open: O,
+) -> Result<(), String>
+where
+    R: Runtime,
+    S: RowSink + Send + 'static,
+    O: FnOnce(File) -> Result<S, String> + Send + 'static,
+{
+    let ExportSource {
+        connection_id,
+        table,
+        query,
+        schema,
+        anonymization_profile_id,
+    } = source;
+    let saved_conn = find_connection_by_id(&app, &connection_id)?;
+    let expanded_params = expand_ssh_connection_params(&app, &saved_conn.params).await?;
+    let params = resolve_connection_params_with_id(&expanded_params, &connection_id)?;
+    let driver = saved_conn.params.driver.clone();
+
+    let query = match (query, table.as_deref()) {
+        (Some(query), _) => sanitize_query(&query),
+        (None, Some(table)) => {
+            let drv = crate::commands::driver_for(&driver).await?;
+            format!(
+                "SELECT * FROM {}",
+                crate::bulk_edit::table_ref(
+                    &drv.manifest().capabilities.identifier_quote,
+                    schema.as_deref(),
+                    table
+                )
+            )
+        }
+        (None, None) => return Err(crate::i18n::t("errors.nothingToExport")),
+    };
+
+    let layout = match table.as_deref() {
+        Some(table) => {
+            crate::preferences::load_column_layout(&connection_id, schema.as_deref(), table)?
+        }
+        None => None,
+    };
+
+    let anonymization = match anonymization_profile_id.as_deref() {
+        Some(id) => Some(anonymize::find_profile_in(
+            &crate::paths::app_config_dir(&app)?,
+            id,
+        )?),
+        None => None,
+    };
+
+    let app_for_task = app.clone();
+    let task = tokio::spawn(async move {
+        let file = File::create(&file_path).map_err(|e| e.to_string())?;
+        let mut sink = open(file)?;
+        let mut progress = progress_emitter(app_for_task);
+        export_through(
+            &driver,
+            &params,
+            &query,
+            schema.as_deref(),
+            &mut sink,
+            layout,
+            anonymization,
+            &mut progress,
+        )
+        .await?;
+        progress.finish();
+        Ok(())
+    });
+
+    let abort_handle = Arc::new(task.abort_handle());
+    let cancel_key = crate::window_state::cancellation_key(window.label(), &connection_id);
+    register_abort_handle(&state.handles, cancel_key.clone(), abort_handle.clone());
 
     let result = task.await;
 
-    unregister_abort_handle(&state.handles, &task_connection_id, &abort_handle);
+    unregister_abort_handle(&state.handles, &cancel_key, &abort_handle);
 
     match result {
         Ok(res) => res,
-        Err(_) => Err("Export cancelled".into()),
+        Err(_) => Err(crate::i18n::t("errors.exportCancelled")),
     }
 }
 
+#[tauri::command]
+pub fn list_export_profiles<R: Runtime>(
+    app: AppHandle<R>,
+) -> Result<Vec<AnonymizationProfile>, String> {
+    let dir = crate::paths::app_config_dir(&app)?;
+    Ok(anonymize::read_profiles_in(&dir))
+}
+
+/// Create or update an anonymization profile. Returns the stored profile with
+/// its id and salt filled in.
+#[tauri::command]
+pub fn save_export_profile<R: Runtime>(
+    app: AppHandle<R>,
+    profile: AnonymizationProfile,
+) -> Result<AnonymizationProfile, String> {
+    anonymize::upsert_profile_in(&crate::paths::app_config_dir(&app)?, profile)
+}
+
+#[tauri::command]
+pub fn delete_export_profile<R: Runtime>(app: AppHandle<R>, id: String) -> Result<(), String> {
+    anonymize::delete_profile_in(&crate::paths::app_config_dir(&app)?, &id)
+}
+
 /// Wires the driver stream, the row sink, and the progress emitter together.
 /// Kept as a free function so the spawned task body stays linear and the
 /// pieces remain individually unit-testable.
@@ -125,30 +497,43 @@ async fn run_export<R: Runtime>(
     driver: &str,
     params: &ConnectionParams,
     query: &str,
+    schema: Option<&str>,
     writer: BufWriter<File>,
     format: ExportFormat,
     delimiter: u8,
+    layout: Option<ColumnLayout>,
+    anonymization: Option<AnonymizationProfile>,
 ) -> Result<(), String> {
-    let app_for_progress = app.clone();
-    let mut progress = ProgressEmitter::new(DEFAULT_PROGRESS_INTERVAL, move |count| {
-        let _ = app_for_progress.emit(
-            EXPORT_PROGRESS_EVENT,
-            ExportProgressPayload {
-                rows_processed: count,
-            },
-        );
-    });
+    let mut progress = progress_emitter(app);
 
     match format {
         ExportFormat::Csv => {
             let mut sink = CsvSink::new(writer, delimiter);
-            stream_to_sink(driver, params, query, &mut sink, &mut progress).await?;
-            sink.finish()?;
+            export_through(
+                driver,
+                params,
+                query,
+                schema,
+                &mut sink,
+                layout,
+                anonymization,
+                &mut progress,
+            )
+            .await?;
         }
         ExportFormat::Json => {
             let mut sink = JsonSink::new(writer);
-            stream_to_sink(driver, params, query, &mut sink, &mut progress).await?;
-            sink.finish()?;
+            export_through(
+                driver,
+                params,
+                query,
+                schema,
+                &mut sink,
+                layout,
+                anonymization,
+                &mut progress,
+            )
+            .await?;
         }
     }
 
@@ -156,10 +541,47 @@ async fn run_export<R: Runtime>(
     Ok(())
 }
 
+fn progress_emitter<R: Runtime>(app: AppHandle<R>) -> ProgressEmitter<impl FnMut(u64) + Send> {
+    ProgressEmitter::new(DEFAULT_PROGRESS_INTERVAL, move |count| {
+        let _ = app.emit(
+            EXPORT_PROGRESS_EVENT,
+            ExportProgressPayload {
+                rows_processed: count,
+            },
+        );
+    })
+}
+
+/// Streams `query` into `sink` through the column layout and anonymization
+/// stages, then finishes the chain.
+async fn export_through<S, F>(
+    driver: &str,
+    params: &ConnectionParams,
+    query: &str,
+    schema: Option<&str>,
+    sink: &mut S,
+    layout: Option<ColumnLayout>,
+    anonymization: Option<AnonymizationProfile>,
+    progress: &mut ProgressEmitter<F>,
+) -> Result<(), String>
+where
+    S: RowSink + Send,
+    F: FnMut(u64) + Send,
+{
+    let mut layout_sink = LayoutSink::new(sink, layout);
+    let mut anonymize_sink = AnonymizeSink::new(&mut layout_sink, anonymization);
+    stream_to_sink(driver, params, query, schema, &mut anonymize_sink, progress).await?;
+    anonymize_sink.finish()
+}
+
+/// Reads every row of `query` into `sink`. Exports go through the driver's
+/// `execute_query_stream` rather than `execute_query`, so they are never
+/// paginated, truncated or preceded by a COUNT query.
 async fn stream_to_sink<S, F>(
     driver: &str,
     params: &ConnectionParams,
     query: &str,
+    schema: Option<&str>,
     sink: &mut S,
     progress: &mut ProgressEmitter<F>,
 ) -> Result<(), String>
@@ -167,16 +589,16 @@ where
     S: RowSink + Send,
     F: FnMut(u64) + Send,
 {
-    let mut on_row = |headers: &[String], values: &[Value]| -> Result<(), String> {
-        sink.write_row(headers, values)?;
-        progress.tick();
+    let drv = crate::commands::driver_for(driver).await?;
+    let mut on_batch = |headers: &[String], rows: Vec<Vec<Value>>| -> Result<(), String> {
+        for values in &rows {
+            sink.write_row(headers, values)?;
+            progress.tick();
+        }
         Ok(())
     };
 
-    match driver {
-        "mysql" => mysql::export::stream_query(params, query, &mut on_row).await,
-        "postgres" => postgres::export::stream_query(params, query, &mut on_row).await,
-        "sqlite" => sqlite::export::stream_query(params, query, &mut on_row).await,
-        other => Err(format!("Unsupported driver for export: {}", other)),
-    }
+    drv.execute_query_stream(params, query, EXPORT_BATCH_SIZE, schema, &mut on_batch)
+        .await
+        .map(|_| ())
 }
@@ -0,0 +1,484 @@
+//! Anonymization profiles applied while exporting, so production-shaped data
+//! can be pulled into development without personal information.
+//!
+//! A profile lists per-column rules:
+//!   - `hash`    — salted SHA-256 of the value
+//!   - `fake`    — a realistic replacement (name, email, phone, ...)
+//!   - `shuffle` — the column's values permuted across rows
+//!
+//! Hash and fake output is derived from the salt and the original value, so
+//! the same input always maps to the same output: joins between tables
+//! exported with the same profile still line up. Shuffling buffers rows in
+//! windows of `SHUFFLE_WINDOW` to keep memory bounded on large exports.
+//!
+//! Profiles are stored in `export_profiles.json` in the app config dir.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::Path;
+
+use super::format::value_to_csv_string;
+use super::sink::RowSink;
+
+const EXPORT_PROFILES_FILE: &str = "export_profiles.json";
+/// Rows buffered per shuffle window.
+pub const SHUFFLE_WINDOW: usize = 10_000;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum FakeKind {
+    Name,
+    FirstName,
+    LastName,
+    Email,
+    Phone,
+    Company,
+    City,
+    Address,
+    Text,
+    Number,
+    Date,
+    Uuid,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(tag = "method", rename_all = "camelCase")]
+pub enum AnonymizeRule {
+    Hash,
+    Shuffle,
+    Fake { kind: FakeKind },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ColumnRule {
+    pub column: String,
+    #[serde(flatten)]
+    pub rule: AnonymizeRule,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct AnonymizationProfile {
+    #[serde(default)]
+    pub id: String,
+    pub name: String,
+    /// Mixed into every hash and fake value. Generated when left empty.
+    #[serde(default)]
+    pub salt: String,
+    pub columns: Vec<ColumnRule>,
+    #[serde(default)]
+    pub created_at: Option<String>,
+    #[serde(default)]
+    pub updated_at: Option<String>,
+}
+
+impl AnonymizationProfile {
+    fn rule_for(&self, column: &str) -> Option<AnonymizeRule> {
+        self.columns
+            .iter()
+            .find(|c| c.column == column)
+            .map(|c| c.rule)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Value transforms
+// ---------------------------------------------------------------------------
+
+fn digest(salt: &str, column: &str, value: &str) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(salt.as_bytes());
+    hasher.update([0]);
+    hasher.update(column.as_bytes());
+    hasher.update([0]);
+    hasher.update(value.as_bytes());
+    hasher.finalize().into()
+}
+
+/// Small deterministic generator (SplitMix64) seeded from a digest.
+struct Seeded(u64);
+
+impl Seeded {
+    fn from_digest(bytes: &[u8; 32]) -> Self {
+        let mut seed = [0u8; 8];
+        seed.copy_from_slice(&bytes[..8]);
+        Self(u64::from_le_bytes(seed))
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    fn below(&mut self, n: usize) -> usize {
+        (self.next_u64() % n as u64) as usize
+    }
+
+    fn pick<'a>(&mut self, items: &[&'a str]) -> &'a str {
+        items[self.below(items.len())]
+    }
+}
+
+const FIRST_NAMES: &[&str] = &[
+    "Alice", "Bruno", "Chiara", "David", "Elena", "Farid", "Giulia", "Hannah", "Ivan", "Julia",
+    "Kenji", "Laura", "Marco", "Nadia", "Oscar", "Paola", "Quentin", "Rosa", "Samir", "Teresa",
+    "Umberto", "Vera", "Walter", "Xenia", "Yusuf", "Zoe",
+];
+const LAST_NAMES: &[&str] = &[
+    "Anderson",
+    "Bianchi",
+    "Costa",
+    "Dubois",
+    "Esposito",
+    "Fischer",
+    "Garcia",
+    "Hoffmann",
+    "Ivanova",
+    "Jensen",
+    "Kowalski",
+    "Lopez",
+    "Martin",
+    "Novak",
+    "Olsen",
+    "Petrov",
+    "Rossi",
+    "Schmidt",
+    "Tanaka",
+    "Umar",
+    "Varga",
+    "Weber",
+    "Young",
+    "Zimmermann",
+];
+const COMPANY_WORDS: &[&str] = &[
+    "Acme", "Blue", "Cedar", "Delta", "Echo", "Granite", "Harbor", "Iron", "Lumen", "Maple",
+    "North", "Orbit", "Pine", "Quartz", "River", "Summit", "Vertex",
+];
+const COMPANY_SUFFIXES: &[&str] = &[
+    "Labs",
+    "Systems",
+    "Group",
+    "Holdings",
+    "Works",
+    "Partners",
+    "Logistics",
+    "Foods",
+];
+const CITIES: &[&str] = &[
+    "Amsterdam",
+    "Berlin",
+    "Bologna",
+    "Boston",
+    "Denver",
+    "Dublin",
+    "Lisbon",
+    "Lyon",
+    "Madrid",
+    "Oslo",
+    "Porto",
+    "Prague",
+    "Seattle",
+    "Toronto",
+    "Vienna",
+    "Zurich",
+];
+const STREETS: &[&str] = &[
+    "Oak", "Elm", "Main", "Church", "Mill", "Park", "Station", "High", "Lake", "Hill",
+];
+const STREET_TYPES: &[&str] = &["St", "Ave", "Rd", "Lane", "Way"];
+const WORDS: &[&str] = &[
+    "lorem",
+    "ipsum",
+    "dolor",
+    "sit",
+    "amet",
+    "consectetur",
+    "adipiscing",
+    "elit",
+    "sed",
+    "do",
+    "eiusmod",
+    "tempor",
+    "incididunt",
+    "ut",
+    "labore",
+    "et",
+    "dolore",
+    "magna",
+    "aliqua",
+];
+
+fn fake_text(rng: &mut Seeded, original: &str) -> String {
+    let count = original.split_whitespace().count().clamp(1, 50);
+    let words: Vec<&str> = (0..count).map(|_| rng.pick(WORDS)).collect();
+    words.join(" ")
+}
+
+fn fake_number(rng: &mut Seeded, original: &Value) -> Value {
+    let text = value_to_csv_string(original);
+    let digits = text
+        .trim_start_matches('-')
+        .split('.')
+        .next()
+        .map_or(1, |int| int.len())
+        .clamp(1, 15);
+    let low = if digits == 1 {
+        0
+    } else {
+        10u64.pow(digits as u32 - 1)
+    };
+    let high = 10u64.pow(digits as u32);
+    let n = low + rng.next_u64() % (high - low);
+    match original {
+        Value::Number(_) => Value::from(n),
+        _ => Value::String(n.to_string()),
+    }
+}
+
+/// Replace `value` with fake data of `kind`. Deterministic for a given salt,
+/// column and value; NULL stays NULL.
+pub fn fake_value(kind: FakeKind, column: &str, value: &Value, salt: &str) -> Value {
+    if value.is_null() {
+        return Value::Null;
+    }
+    let original = value_to_csv_string(value);
+    let bytes = digest(salt, column, &original);
+    let mut rng = Seeded::from_digest(&bytes);
+
+    let fake = match kind {
+        FakeKind::Name => format!("{} {}", rng.pick(FIRST_NAMES), rng.pick(LAST_NAMES)),
+        FakeKind::FirstName => rng.pick(FIRST_NAMES).to_string(),
+        FakeKind::LastName => rng.pick(LAST_NAMES).to_string(),
+        FakeKind::Email => format!(
+            "{}.{}{}@example.com",
+            rng.pick(FIRST_NAMES).to_lowercase(),
+            rng.pick(LAST_NAMES).to_lowercase(),
+            rng.below(1000)
+        ),
+        FakeKind::Phone => format!("+1 555 {:03} {:04}", rng.below(1000), rng.below(10_000)),
+        FakeKind::Company => format!("{} {}", rng.pick(COMPANY_WORDS), rng.pick(COMPANY_SUFFIXES)),
+        FakeKind::City => rng.pick(CITIES).to_string(),
+        FakeKind::Address => format!(
+            "{} {} {}",
+            rng.below(999) + 1,
+            rng.pick(STREETS),
+            rng.pick(STREET_TYPES)
+        ),
+        FakeKind::Text => fake_text(&mut rng, &original),
+        FakeKind::Number => return fake_number(&mut rng, value),
+        FakeKind::Date => {
+            let epoch = chrono::NaiveDate::from_ymd_opt(1950, 1, 1).unwrap_or_default();
+            let date = epoch + chrono::Duration::days(rng.below(365 * 70) as i64);
+            date.format("%Y-%m-%d").to_string()
+        }
+        FakeKind::Uuid => {
+            let mut random = [0u8; 16];
+            random.copy_from_slice(&bytes[16..]);
+            uuid::Builder::from_random_bytes(random)
+                .into_uuid()
+                .to_string()
+        }
+    };
+    Value::String(fake)
+}
+
+/// Salted SHA-256 of `value` as hex; NULL stays NULL.
+pub fn hash_value(column: &str, value: &Value, salt: &str) -> Value {
+    if value.is_null() {
+        return Value::Null;
+    }
+    let bytes = digest(salt, column, &value_to_csv_string(value));
+    Value::String(bytes.iter().map(|b| format!("{:02x}", b)).collect())
+}
+
+/// Permute column `idx` across `rows` (Fisher-Yates), seeded by `seed`.
+pub fn shuffle_column(rows: &mut [Vec<Value>], idx: usize, seed: &[u8; 32]) {
+    let mut rng = Seeded::from_digest(seed);
+    for i in (1..rows.len()).rev() {
+        let j = rng.below(i + 1);
+        if i != j && idx < rows[i].len() && idx < rows[j].len() {
+            let tmp = std::mem::take(&mut rows[i][idx]);
+            rows[i][idx] = std::mem::replace(&mut rows[j][idx], tmp);
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Sink
+// ---------------------------------------------------------------------------
+
+/// Wraps another sink and anonymizes every row according to a profile before
+/// forwarding it. Without a profile rows pass through untouched. Columns the
+/// profile does not mention are exported as-is.
+pub struct AnonymizeSink<'a, S: RowSink> {
+    inner: &'a mut S,
+    profile: Option<AnonymizationProfile>,
+    rules: Option<Vec<Option<AnonymizeRule>>>,
+    headers: Vec<String>,
+    pending: Vec<Vec<Value>>,
+    windows: u64,
+}
+
+impl<'a, S: RowSink> AnonymizeSink<'a, S> {
+    pub fn new(inner: &'a mut S, profile: Option<AnonymizationProfile>) -> Self {
+        Self {
+            inner,
+            profile,
+            rules: None,
+            headers: Vec::new(),
+            pending: Vec::new(),
+            windows: 0,
+        }
+    }
+
+    fn flush_pending(&mut self) -> Result<(), String> {
+        let (Some(profile), Some(rules)) = (&self.profile, &self.rules) else {
+            return Ok(());
+        };
+        for (idx, rule) in rules.iter().enumerate() {
+            if *rule == Some(AnonymizeRule::Shuffle) {
+                let seed = digest(&profile.salt, &self.headers[idx], &self.windows.to_string());
+                shuffle_column(&mut self.pending, idx, &seed);
+            }
+        }
+        self.windows += 1;
+        for row in self.pending.drain(..) {
+            self.inner.write_row(&self.headers, &row)?;
+        }
+        Ok(())
+    }
+}
+
+impl<S: RowSink> RowSink for AnonymizeSink<'_, S> {
+    fn write_row(&mut self, headers: &[String], values: &[Value]) -> Result<(), String> {
+        let Some(profile) = &self.profile else {
+            return self.inner.write_row(headers, values);
+        };
+        if self.rules.is_none() {
+            self.headers = headers.to_vec();
+            self.rules = Some(headers.iter().map(|h| profile.rule_for(h)).collect());
+        }
+        let rules = self.rules.as_deref().unwrap_or_default();
+
+        let row: Vec<Value> = values
+            .iter()
+            .enumerate()
+            .map(|(idx, value)| match rules.get(idx).copied().flatten() {
+                Some(AnonymizeRule::Hash) => hash_value(&headers[idx], value, &profile.salt),
+                Some(AnonymizeRule::Fake { kind }) => {
+                    fake_value(kind, &headers[idx], value, &profile.salt)
+                }
+                Some(AnonymizeRule::Shuffle) | None => value.clone(),
+            })
+            .collect();
+
+        if !rules.contains(&Some(AnonymizeRule::Shuffle)) {
+            return self.inner.write_row(headers, &row);
+        }
+        self.pending.push(row);
+        if self.pending.len() >= SHUFFLE_WINDOW {
+            self.flush_pending()?;
+        }
+        Ok(())
+    }
+
+    fn finish(&mut self) -> Result<(), String> {
+        self.flush_pending()?;
+        self.inner.finish()
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Storage (testable; take a directory)
+// ---------------------------------------------------------------------------
+
+pub fn read_profiles_in(dir: &Path) -> Vec<AnonymizationProfile> {
+    fs::read_to_string(dir.join(EXPORT_PROFILES_FILE))
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn write_profiles_in(dir: &Path, profiles: &[AnonymizationProfile]) -> Result<(), String> {
+    fs::create_dir_all(dir).map_err(|e| format!("Failed to create config dir: {}", e))?;
+    let json = serde_json::to_string_pretty(profiles)
+        .map_err(|e| format!("Failed to serialize export profiles: {}", e))?;
+    fs::write(dir.join(EXPORT_PROFILES_FILE), json)
+        .map_err(|e| format!("Failed to save export profiles: {}", e))
+}
+
+pub fn find_profile_in(dir: &Path, id: &str) -> Result<AnonymizationProfile, String> {
+    read_profiles_in(dir)
+        .into_iter()
+        .find(|p| p.id == id)
+        .ok_or_else(|| crate::i18n::t_with("errors.exportProfileNotFound", &[("id", id)]))
+}
+
+/// Insert or replace `profile`, matched by id. A profile without an id gets a
+/// fresh one and one without a salt gets a random salt. Names must be unique
+/// and a column may only have one rule.
+pub fn upsert_profile_in(
+    dir: &Path,
+    mut profile: AnonymizationProfile,
+) -> Result<AnonymizationProfile, String> {
+    profile.name = profile.name.trim().to_string();
+    if profile.name.is_empty() {
+        return Err(crate::i18n::t("errors.exportProfileNameEmpty"));
+    }
+    for (i, rule) in profile.columns.iter().enumerate() {
+        if rule.column.trim().is_empty() {
+            return Err(crate::i18n::t("errors.exportProfileColumnEmpty"));
+        }
+        if profile.columns[..i].iter().any(|r| r.column == rule.column) {
+            return Err(crate::i18n::t_with(
+                "errors.exportProfileDuplicateRule",
+                &[("column", &rule.column)],
+            ));
+        }
+    }
+
+    let mut profiles = read_profiles_in(dir);
+    if profiles
+        .iter()
+        .any(|p| p.name == profile.name && p.id != profile.id)
+    {
+        return Err(crate::i18n::t_with(
+            "errors.exportProfileExists",
+            &[("name", &profile.name)],
+        ));
+    }
+
+    let now = chrono::Utc::now().to_rfc3339();
+    if profile.id.is_empty() {
+        profile.id = uuid::Uuid::new_v4().to_string();
+    }
+    if profile.salt.is_empty() {
+        profile.salt = uuid::Uuid::new_v4().simple().to_string();
+    }
+    profile.updated_at = Some(now.clone());
+
+    match profiles.iter_mut().find(|p| p.id == profile.id) {
+        Some(existing) => {
+            profile.created_at = existing.created_at.clone();
+            *existing = profile.clone();
+        }
+        None => {
+            profile.created_at = Some(now);
+            profiles.push(profile.clone());
+        }
+    }
+    write_profiles_in(dir, &profiles)?;
+    Ok(profile)
+}
+
+pub fn delete_profile_in(dir: &Path, id: &str) -> Result<(), String> {
+    let mut profiles = read_profiles_in(dir);
+    profiles.retain(|p| p.id != id);
+    write_profiles_in(dir, &profiles)
+}
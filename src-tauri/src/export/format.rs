@@ -11,7 +11,10 @@ impl ExportFormat {
         match s.trim().to_ascii_lowercase().as_str() {
             "csv" => Ok(Self::Csv),
             "json" => Ok(Self::Json),
-            other => Err(format!("Unsupported export format: {}", other)),
+            other => Err(crate::i18n::t_with(
+                "errors.unsupportedExportFormat",
+                &[("format", other)],
+            )),
         }
     }
 }
@@ -0,0 +1,202 @@
+use std::collections::{HashMap, HashSet};
+use std::io::Write;
+
+use serde::ser::SerializeMap;
+use serde::{Serialize, Serializer};
+use serde_json::Value;
+
+use super::sink::RowSink;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JsonLayout {
+    /// One JSON array of row objects.
+    Array,
+    /// One row object per line.
+    Ndjson,
+}
+
+impl JsonLayout {
+    /// An array when unset.
+    pub fn parse(value: Option<&str>) -> Result<Self, String> {
+        let Some(value) = value else {
+            return Ok(Self::Array);
+        };
+        match value.trim().to_ascii_lowercase().as_str() {
+            "array" | "json" => Ok(Self::Array),
+            "ndjson" | "jsonl" | "lines" => Ok(Self::Ndjson),
+            other => Err(crate::i18n::t_with(
+                "errors.unsupportedJsonLayout",
+                &[("layout", other)],
+            )),
+        }
+    }
+}
+
+/// A column of the schema header.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct JsonColumn {
+    pub name: String,
+    /// The declared type, when the export reads a table.
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub data_type: Option<String>,
+}
+
+/// Whether a column declared as `data_type` holds JSON documents.
+pub fn is_json_type(data_type: &str) -> bool {
+    matches!(
+        data_type.trim().to_ascii_lowercase().as_str(),
+        "json" | "jsonb"
+    )
+}
+
+/// The document a JSON column holds. Drivers that read JSON columns as text
+/// hand it over as a string; it is parsed so the file nests it. Text that is
+/// not valid JSON stays a string.
+pub fn nest_value(value: Value) -> Value {
+    match &value {
+        Value::String(text) => serde_json::from_str(text).unwrap_or(value),
+        _ => value,
+    }
+}
+
+/// A row as a JSON object with its keys in column order, which
+/// `serde_json::Map` would sort.
+struct RowObject<'a> {
+    headers: &'a [String],
+    values: Vec<Value>,
+}
+
+impl Serialize for RowObject<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(self.headers.len()))?;
+        for (name, value) in self.headers.iter().zip(&self.values) {
+            map.serialize_entry(name, value)?;
+        }
+        map.end()
+    }
+}
+
+/// Streaming JSON or NDJSON sink that writes JSON columns as nested values
+/// and keeps the columns in order.
+///
+/// With a schema header, an array export becomes
+/// `{"columns": [...], "rows": [...]}` and an NDJSON export starts with a
+/// `{"columns": [...]}` line.
+pub struct JsonExportSink<W: Write> {
+    writer: W,
+    layout: JsonLayout,
+    /// Columns whose values are parsed with [`nest_value`].
+    json_columns: HashSet<String>,
+    /// Declared columns and types, in table order, when a header is wanted.
+    schema: Option<Vec<JsonColumn>>,
+    started: bool,
+    rows: u64,
+}
+
+impl<W: Write> JsonExportSink<W> {
+    pub fn new(
+        writer: W,
+        layout: JsonLayout,
+        json_columns: HashSet<String>,
+        schema: Option<Vec<JsonColumn>>,
+    ) -> Self {
+        Self {
+            writer,
+            layout,
+            json_columns,
+            schema,
+            started: false,
+            rows: 0,
+        }
+    }
+
+    /// The header columns: those of the rows when there are any, with the
+    /// declared types; the declared columns otherwise.
+    fn header(&self, headers: Option<&[String]>) -> Option<Vec<JsonColumn>> {
+        let declared = self.schema.as_ref()?;
+        let Some(headers) = headers else {
+            return Some(declared.clone());
+        };
+        let types: HashMap<&str, &str> = declared
+            .iter()
+            .filter_map(|c| Some((c.name.as_str(), c.data_type.as_deref()?)))
+            .collect();
+        Some(
+            headers
+                .iter()
+                .map(|name| JsonColumn {
+                    name: name.clone(),
+                    data_type: types.get(name.as_str()).map(|t| t.to_string()),
+                })
+                .collect(),
+        )
+    }
+
+    fn start(&mut self, headers: Option<&[String]>) -> Result<(), String> {
+        self.started = true;
+        let header = self.header(headers);
+        self.write_start(header).map_err(|e| e.to_string())
+    }
+
+    fn write_start(&mut self, header: Option<Vec<JsonColumn>>) -> std::io::Result<()> {
+        match (self.layout, header) {
+            (JsonLayout::Array, None) => self.writer.write_all(b"[")?,
+            (JsonLayout::Array, Some(columns)) => {
+                self.writer.write_all(b"{\"columns\":")?;
+                serde_json::to_writer(&mut self.writer, &columns)?;
+                self.writer.write_all(b",\"rows\":[")?;
+            }
+            (JsonLayout::Ndjson, Some(columns)) => {
+                self.writer.write_all(b"{\"columns\":")?;
+                serde_json::to_writer(&mut self.writer, &columns)?;
+                self.writer.write_all(b"}\n")?;
+            }
+            (JsonLayout::Ndjson, None) => {}
+        }
+        Ok(())
+    }
+}
+
+impl<W: Write> RowSink for JsonExportSink<W> {
+    fn write_row(&mut self, headers: &[String], values: &[Value]) -> Result<(), String> {
+        if !self.started {
+            self.start(Some(headers))?;
+        }
+        let values = headers
+            .iter()
+            .enumerate()
+            .map(|(i, name)| {
+                let value = values.get(i).cloned().unwrap_or(Value::Null);
+                if self.json_columns.contains(name) {
+                    nest_value(value)
+                } else {
+                    value
+                }
+            })
+            .collect();
+        let row = RowObject { headers, values };
+
+        if self.layout == JsonLayout::Array && self.rows > 0 {
+            self.writer.write_all(b",").map_err(|e| e.to_string())?;
+        }
+        serde_json::to_writer(&mut self.writer, &row).map_err(|e| e.to_string())?;
+        if self.layout == JsonLayout::Ndjson {
+            self.writer.write_all(b"\n").map_err(|e| e.to_string())?;
+        }
+        self.rows += 1;
+        Ok(())
+    }
+
+    fn finish(&mut self) -> Result<(), String> {
+        if !self.started {
+            self.start(None)?;
+        }
+        let end: &[u8] = match (self.layout, self.schema.is_some()) {
+            (JsonLayout::Array, true) => b"]}",
+            (JsonLayout::Array, false) => b"]",
+            (JsonLayout::Ndjson, _) => b"",
+        };
+        self.writer.write_all(end).map_err(|e| e.to_string())?;
+        self.writer.flush().map_err(|e| e.to_string())
+    }
+}
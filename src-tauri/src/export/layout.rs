@@ -0,0 +1,261 @@
+use std::fmt::Write as _;
+
+use chrono::{DateTime, NaiveDate, NaiveDateTime};
+use serde_json::Value;
+
+use super::sink::RowSink;
+use crate::preferences::{ColumnDisplay, ColumnLayout};
+
+/// Maps driver columns onto the order, visibility and formatting of a saved
+/// column layout.
+///
+/// Columns listed in the layout come first, in layout order, unless hidden.
+/// Columns the layout does not know about (e.g. added to the table after the
+/// layout was saved) keep their relative order at the end.
+pub struct ColumnProjection {
+    indices: Vec<usize>,
+    headers: Vec<String>,
+    displays: Vec<Option<ColumnDisplay>>,
+}
+
+impl ColumnProjection {
+    pub fn new(layout: &ColumnLayout, headers: &[String]) -> Self {
+        let mut indices = Vec::with_capacity(headers.len());
+        let mut displays = Vec::with_capacity(headers.len());
+
+        for column in &layout.columns {
+            if column.hidden {
+                continue;
+            }
+            if let Some(idx) = headers.iter().position(|h| h == &column.name) {
+                if !indices.contains(&idx) {
+                    indices.push(idx);
+                    displays.push(Some(column.clone()));
+                }
+            }
+        }
+
+        for (idx, header) in headers.iter().enumerate() {
+            if !layout.columns.iter().any(|c| &c.name == header) {
+                indices.push(idx);
+                displays.push(None);
+            }
+        }
+
+        let headers = indices.iter().map(|&i| headers[i].clone()).collect();
+
+        Self {
+            indices,
+            headers,
+            displays,
+        }
+    }
+
+    pub fn headers(&self) -> &[String] {
+        &self.headers
+    }
+
+    pub fn apply(&self, values: &[Value]) -> Vec<Value> {
+        self.indices
+            .iter()
+            .zip(&self.displays)
+            .map(|(&idx, display)| {
+                let value = values.get(idx).cloned().unwrap_or(Value::Null);
+                match display {
+                    Some(display) => format_value(value, display),
+                    None => value,
+                }
+            })
+            .collect()
+    }
+}
+
+/// Applies the number/date formatting of `display` to a single value.
+/// Values that cannot be interpreted (or invalid patterns) are returned unchanged.
+pub fn format_value(value: Value, display: &ColumnDisplay) -> Value {
+    if display.decimals.is_some() || display.thousands_separator {
+        let text = match &value {
+            Value::Number(n) => Some(n.to_string()),
+            Value::String(s) => Some(s.trim().to_string()),
+            _ => None,
+        };
+        if let Some(formatted) =
+            text.and_then(|t| format_number(&t, display.decimals, display.thousands_separator))
+        {
+            return Value::String(formatted);
+        }
+    }
+
+    if let (Some(pattern), Value::String(s)) = (display.date_format.as_deref(), &value) {
+        if let Some(formatted) = format_date(s, pattern) {
+            return Value::String(formatted);
+        }
+    }
+
+    value
+}
+
+/// A number written out in decimal: sign, integer digits, fraction digits.
+struct DecimalText {
+    negative: bool,
+    int_digits: String,
+    frac_digits: String,
+}
+
+/// Reads `[+-]digits[.digits]` exactly, so big integers and long decimals
+/// keep every digit. Exponent forms (`1e21`) and other text `f64` accepts go
+/// through `f64`, which only they can come from.
+fn parse_decimal(text: &str) -> Option<DecimalText> {
+    let (negative, unsigned) = match text.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, text.strip_prefix('+').unwrap_or(text)),
+    };
+    let (int_part, frac_part) = unsigned.split_once('.').unwrap_or((unsigned, ""));
+    let is_digits = |s: &str| s.chars().all(|c| c.is_ascii_digit());
+    if (int_part.is_empty() && frac_part.is_empty())
+        || !is_digits(int_part)
+        || !is_digits(frac_part)
+    {
+        let n = text.parse::<f64>().ok().filter(|n| n.is_finite())?;
+        return parse_decimal(&n.to_string());
+    }
+
+    Some(DecimalText {
+        negative,
+        int_digits: if int_part.is_empty() {
+            "0".to_string()
+        } else {
+            int_part.to_string()
+        },
+        frac_digits: frac_part.to_string(),
+    })
+}
+
+/// Rounds `number` to `decimals` fraction digits, half away from zero.
+fn round_decimal(number: &mut DecimalText, decimals: usize) {
+    if number.frac_digits.len() <= decimals {
+        let padding = decimals - number.frac_digits.len();
+        number
+            .frac_digits
+            .extend(std::iter::repeat('0').take(padding));
+        return;
+    }
+
+    let round_up = number.frac_digits.as_bytes()[decimals] >= b'5';
+    number.frac_digits.truncate(decimals);
+    if !round_up {
+        return;
+    }
+
+    // Add one to the last kept digit, carrying through the fraction into
+    // the integer part.
+    let mut digits: Vec<u8> = number
+        .int_digits
+        .bytes()
+        .chain(number.frac_digits.bytes())
+        .collect();
+    let mut carry = true;
+    for digit in digits.iter_mut().rev() {
+        if *digit == b'9' {
+            *digit = b'0';
+        } else {
+            *digit += 1;
+            carry = false;
+            break;
+        }
+    }
+    if carry {
+        digits.insert(0, b'1');
+    }
+    let split = digits.len() - decimals;
+    number.frac_digits = String::from_utf8_lossy(&digits[split..]).to_string();
+    number.int_digits = String::from_utf8_lossy(&digits[..split]).to_string();
+}
+
+/// Formats the number written in `text` with `decimals` fraction digits
+/// and, optionally, `,` thousands separators. Works on the digits rather
+/// than an `f64`, so 64-bit ids and exact decimals are not rounded. `None`
+/// when `text` is not a number.
+fn format_number(text: &str, decimals: Option<u32>, thousands_separator: bool) -> Option<String> {
+    let mut number = parse_decimal(text)?;
+    if let Some(d) = decimals {
+        round_decimal(&mut number, d as usize);
+    }
+    let is_zero = number
+        .int_digits
+        .chars()
+        .chain(number.frac_digits.chars())
+        .all(|c| c == '0');
+
+    let int_part = &number.int_digits;
+    let mut grouped = String::with_capacity(int_part.len() + int_part.len() / 3);
+    for (i, ch) in int_part.chars().enumerate() {
+        if thousands_separator && i > 0 && (int_part.len() - i) % 3 == 0 {
+            grouped.push(',');
+        }
+        grouped.push(ch);
+    }
+
+    let sign = if number.negative && !is_zero { "-" } else { "" };
+    Some(if number.frac_digits.is_empty() {
+        format!("{}{}", sign, grouped)
+    } else {
+        format!("{}{}.{}", sign, grouped, number.frac_digits)
+    })
+}
+
+fn format_date(value: &str, pattern: &str) -> Option<String> {
+    let mut out = String::new();
+    let value = value.trim();
+
+    let written = if let Ok(dt) = DateTime::parse_from_rfc3339(value) {
+        write!(out, "{}", dt.format(pattern))
+    } else if let Some(dt) = ["%Y-%m-%d %H:%M:%S%.f", "%Y-%m-%dT%H:%M:%S%.f"]
+        .iter()
+        .find_map(|f| NaiveDateTime::parse_from_str(value, f).ok())
+    {
+        write!(out, "{}", dt.format(pattern))
+    } else if let Ok(d) = NaiveDate::parse_from_str(value, "%Y-%m-%d") {
+        write!(out, "{}", d.format(pattern))
+    } else {
+        return None;
+    };
+
+    // chrono reports invalid specifiers (or time fields on a plain date) as a fmt error
+    written.ok().map(|_| out)
+}
+
+/// Wraps another sink and projects every row through a column layout before
+/// forwarding it. Without a layout rows pass through untouched.
+pub struct LayoutSink<'a, S: RowSink> {
+    inner: &'a mut S,
+    layout: Option<ColumnLayout>,
+    projection: Option<ColumnProjection>,
+}
+
+impl<'a, S: RowSink> LayoutSink<'a, S> {
+    pub fn new(inner: &'a mut S, layout: Option<ColumnLayout>) -> Self {
+        Self {
+            inner,
+            layout,
+            projection: None,
+        }
+    }
+}
+
+impl<S: RowSink> RowSink for LayoutSink<'_, S> {
+    fn write_row(&mut self, headers: &[String], values: &[Value]) -> Result<(), String> {
+        let Some(layout) = &self.layout else {
+            return self.inner.write_row(headers, values);
+        };
+        let projection = self
+            .projection
+            .get_or_insert_with(|| ColumnProjection::new(layout, headers));
+        let projected = projection.apply(values);
+        self.inner.write_row(projection.headers(), &projected)
+    }
+
+    fn finish(&mut self) -> Result<(), String> {
+        self.inner.finish()
+    }
+}
@@ -0,0 +1,150 @@
+use std::io::Write;
+use std::sync::Arc;
+
+use arrow::datatypes::{Field, Schema, SchemaRef};
+use arrow::record_batch::RecordBatch;
+use parquet::arrow::ArrowWriter;
+use parquet::basic::{Compression, GzipLevel, ZstdLevel};
+use parquet::file::properties::WriterProperties;
+use serde_json::Value;
+
+use super::sink::RowSink;
+use crate::arrow_ipc::{column_array, infer_type};
+
+/// Rows buffered before they are written out as one row group.
+pub const DEFAULT_ROW_GROUP_SIZE: usize = 10_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParquetCompression {
+    None,
+    Snappy,
+    Gzip,
+    Zstd,
+    Lz4,
+}
+
+impl ParquetCompression {
+    /// Snappy when unset, as most Parquet readers default to it.
+    pub fn parse(value: Option<&str>) -> Result<Self, String> {
+        let Some(value) = value else {
+            return Ok(Self::Snappy);
+        };
+        match value.trim().to_ascii_lowercase().as_str() {
+            "none" | "uncompressed" => Ok(Self::None),
+            "snappy" => Ok(Self::Snappy),
+            "gzip" => Ok(Self::Gzip),
+            "zstd" => Ok(Self::Zstd),
+            "lz4" => Ok(Self::Lz4),
+            other => Err(crate::i18n::t_with(
+                "errors.unsupportedParquetCompression",
+                &[("compression", other)],
+            )),
+        }
+    }
+
+    fn codec(self) -> Compression {
+        match self {
+            Self::None => Compression::UNCOMPRESSED,
+            Self::Snappy => Compression::SNAPPY,
+            Self::Gzip => Compression::GZIP(GzipLevel::default()),
+            Self::Zstd => Compression::ZSTD(ZstdLevel::default()),
+            Self::Lz4 => Compression::LZ4_RAW,
+        }
+    }
+}
+
+/// Streaming Parquet sink. Rows are buffered and written one row group per
+/// `row_group_size` rows; the column types are inferred from the first
+/// group, so a later value that does not fit its column fails the export.
+pub struct ParquetSink<W: Write + Send> {
+    inner: Option<W>,
+    writer: Option<ArrowWriter<W>>,
+    schema: Option<SchemaRef>,
+    compression: ParquetCompression,
+    row_group_size: usize,
+    headers: Vec<String>,
+    rows: Vec<Vec<Value>>,
+}
+
+impl<W: Write + Send> ParquetSink<W> {
+    /// `row_group_size` is clamped to a minimum of 1.
+    pub fn new(inner: W, compression: ParquetCompression, row_group_size: usize) -> Self {
+        Self {
+            inner: Some(inner),
+            writer: None,
+            schema: None,
+            compression,
+            row_group_size: row_group_size.max(1),
+            headers: Vec::new(),
+            rows: Vec::new(),
+        }
+    }
+
+    fn writer(&mut self) -> Result<&mut ArrowWriter<W>, String> {
+        if self.writer.is_none() {
+            let fields: Vec<Field> = self
+                .headers
+                .iter()
+                .enumerate()
+                .map(|(i, name)| {
+                    let ty = infer_type(self.rows.iter().filter_map(|row| row.get(i)));
+                    Field::new(name, ty, true)
+                })
+                .collect();
+            let schema = Arc::new(Schema::new(fields));
+            let props = WriterProperties::builder()
+                .set_compression(self.compression.codec())
+                .set_max_row_group_size(self.row_group_size)
+                .build();
+            let inner = self
+                .inner
+                .take()
+                .ok_or_else(|| "Parquet writer already closed".to_string())?;
+            let writer = ArrowWriter::try_new(inner, schema.clone(), Some(props))
+                .map_err(|e| e.to_string())?;
+            self.schema = Some(schema);
+            self.writer = Some(writer);
+        }
+        Ok(self.writer.as_mut().expect("writer was just created"))
+    }
+
+    fn flush_rows(&mut self) -> Result<(), String> {
+        self.writer()?;
+        if self.rows.is_empty() {
+            return Ok(());
+        }
+        let schema = self.schema.clone().expect("schema is set with the writer");
+        let rows = std::mem::take(&mut self.rows);
+        let columns = schema
+            .fields()
+            .iter()
+            .enumerate()
+            .map(|(i, field)| column_array(field, &rows, i))
+            .collect::<Result<Vec<_>, _>>()?;
+        let batch = RecordBatch::try_new(schema, columns).map_err(|e| e.to_string())?;
+        let writer = self.writer()?;
+        writer.write(&batch).map_err(|e| e.to_string())?;
+        writer.flush().map_err(|e| e.to_string())
+    }
+}
+
+impl<W: Write + Send> RowSink for ParquetSink<W> {
+    fn write_row(&mut self, headers: &[String], values: &[Value]) -> Result<(), String> {
+        if self.headers.is_empty() {
+            self.headers = headers.to_vec();
+        }
+        self.rows.push(values.to_vec());
+        if self.rows.len() >= self.row_group_size {
+            self.flush_rows()?;
+        }
+        Ok(())
+    }
+
+    fn finish(&mut self) -> Result<(), String> {
+        self.flush_rows()?;
+        match self.writer.take() {
+            Some(writer) => writer.close().map(|_| ()).map_err(|e| e.to_string()),
+            None => Ok(()),
+        }
+    }
+}
@@ -0,0 +1,292 @@
+//! SQL dump export.
+//!
+//! Writes a mysqldump-style script for a database or a set of tables: a
+//! `DROP TABLE IF EXISTS` + `CREATE TABLE` pair per table followed by its
+//! rows as batched `INSERT` statements. Everything goes through the
+//! `DatabaseDriver` trait, so plugin drivers can be dumped too; rows are
+//! read with `execute_query_stream` and written one batch at a time, so
+//! large tables never sit in memory.
+//!
+//! Tables are written parents first, following their foreign keys, so the
+//! script restores without constraint errors. When tables reference each
+//! other in a cycle, the data is wrapped in statements that suspend or
+//! defer foreign key checks where the dialect has them.
+
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::sync::Arc;
+
+use serde::Deserialize;
+use serde_json::Value;
+use tauri::{AppHandle, Emitter, Runtime, State, WebviewWindow};
+
+use super::{ExportCancellationState, ExportProgressPayload, EXPORT_PROGRESS_EVENT};
+use crate::commands::{
+    driver_for, expand_ssh_connection_params, find_connection_by_id, register_abort_handle,
+    resolve_connection_params_with_id, unregister_abort_handle,
+};
+use crate::drivers::driver_trait::DatabaseDriver;
+use crate::dump_utils::{deferred_constraints, dependency_order};
+use crate::models::ConnectionParams;
+
+/// Rows per `INSERT` statement when the caller does not pick a size.
+pub const DEFAULT_SQL_DUMP_BATCH_SIZE: usize = 500;
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct SqlDumpOptions {
+    /// Write `DROP TABLE IF EXISTS` + `CREATE TABLE` for each table.
+    #[serde(default = "default_true")]
+    pub structure: bool,
+    /// Write the table rows as `INSERT` statements.
+    #[serde(default = "default_true")]
+    pub data: bool,
+    /// Tables to dump; `None` dumps every table of the database/schema.
+    #[serde(default)]
+    pub tables: Option<Vec<String>>,
+    /// Rows per `INSERT` statement.
+    #[serde(default)]
+    pub batch_size: Option<usize>,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+/// How identifiers and literals are spelled in the generated script.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SqlDumpDialect {
+    /// Opening identifier quote from the driver's capabilities (`"`, `` ` ``
+    /// or `[`).
+    pub quote: String,
+    /// MySQL treats `\` as an escape character inside string literals.
+    pub backslash_escapes: bool,
+    /// Write booleans as `TRUE`/`FALSE`; otherwise as `1`/`0` (SQL Server).
+    pub bool_keywords: bool,
+}
+
+impl SqlDumpDialect {
+    pub fn for_driver(driver: &str, identifier_quote: &str) -> Self {
+        Self {
+            quote: identifier_quote.to_string(),
+            backslash_escapes: driver == "mysql",
+            bool_keywords: driver != "mssql",
+        }
+    }
+
+    pub fn quote_identifier(&self, name: &str) -> String {
+        crate::drivers::common::quote_identifier(&self.quote, name)
+    }
+
+    /// `table`, qualified with `schema` when there is one.
+    pub fn table_ref(&self, schema: Option<&str>, table: &str) -> String {
+        match schema {
+            Some(s) => format!(
+                "{}.{}",
+                self.quote_identifier(s),
+                self.quote_identifier(table)
+            ),
+            None => self.quote_identifier(table),
+        }
+    }
+
+    pub fn literal(&self, value: &Value) -> String {
+        match value {
+            Value::Null => "NULL".to_string(),
+            Value::Bool(b) => match (self.bool_keywords, b) {
+                (true, true) => "TRUE".to_string(),
+                (true, false) => "FALSE".to_string(),
+                (false, true) => "1".to_string(),
+                (false, false) => "0".to_string(),
+            },
+            Value::Number(n) => n.to_string(),
+            Value::String(s) => self.string_literal(s),
+            other => self.string_literal(&other.to_string()),
+        }
+    }
+
+    fn string_literal(&self, s: &str) -> String {
+        let escaped = if self.backslash_escapes {
+            s.replace('\\', "\\\\")
+        } else {
+            s.to_string()
+        };
+        format!("'{}'", escaped.replace('\'', "''"))
+    }
+
+    /// One multi-row `INSERT` with an explicit column list.
+    pub fn insert_statement(
+        &self,
+        table_ref: &str,
+        columns: &[String],
+        rows: &[Vec<Value>],
+    ) -> String {
+        let cols: Vec<String> = columns.iter().map(|c| self.quote_identifier(c)).collect();
+        let values: Vec<String> = rows
+            .iter()
+            .map(|row| {
+                let cells: Vec<String> = row.iter().map(|v| self.literal(v)).collect();
+                format!("({})", cells.join(", "))
+            })
+            .collect();
+        format!(
+            "INSERT INTO {} ({}) VALUES\n{};",
+            table_ref,
+            cols.join(", "),
+            values.join(",\n")
+        )
+    }
+}
+
+/// Dump a database (or the selected tables) to `file_path`. Resolves with
+/// the number of rows written; progress is reported on the same event as
+/// the other exports and `cancel_export` aborts it.
+#[tauri::command]
+pub async fn export_sql_dump<R: Runtime>(
+    app: AppHandle<R>,
+    window: WebviewWindow<R>,
+    state: State<'_, ExportCancellationState>,
+    connection_id: String,
+    file_path: String,
+    options: SqlDumpOptions,
+    schema: Option<String>,
+) -> Result<u64, String> {
+    let saved_conn = find_connection_by_id(&app, &connection_id)?;
+    let expanded_params = expand_ssh_connection_params(&app, &saved_conn.params).await?;
+    let params = resolve_connection_params_with_id(&expanded_params, &connection_id)?;
+    let drv = driver_for(&saved_conn.params.driver).await?;
+
+    let app_for_task = app.clone();
+    let task = tokio::spawn(async move {
+        let file = File::create(&file_path).map_err(|e| {
+            crate::i18n::t_with("errors.createDumpFileFailed", &[("error", &e.to_string())])
+        })?;
+        let writer = BufWriter::new(file);
+        let mut on_progress = move |rows_processed: u64| {
+            let _ = app_for_task.emit(
+                EXPORT_PROGRESS_EVENT,
+                ExportProgressPayload { rows_processed },
+            );
+        };
+        write_sql_dump(
+            drv.as_ref(),
+            &params,
+            schema.as_deref(),
+            &options,
+            writer,
+            &mut on_progress,
+        )
+        .await
+    });
+
+    let abort_handle = Arc::new(task.abort_handle());
+    let cancel_key = crate::window_state::cancellation_key(window.label(), &connection_id);
+    register_abort_handle(&state.handles, cancel_key.clone(), abort_handle.clone());
+
+    let result = task.await;
+
+    unregister_abort_handle(&state.handles, &cancel_key, &abort_handle);
+
+    match result {
+        Ok(res) => res,
+        Err(_) => Err(crate::i18n::t("errors.exportCancelled")),
+    }
+}
+
+/// Writes the dump script to `writer` and returns the number of rows
+/// dumped. `on_progress` receives the running row count after each batch.
+pub async fn write_sql_dump<W: Write + Send>(
+    drv: &dyn DatabaseDriver,
+    params: &ConnectionParams,
+    schema: Option<&str>,
+    options: &SqlDumpOptions,
+    mut writer: W,
+    on_progress: &mut (dyn FnMut(u64) + Send),
+) -> Result<u64, String> {
+    let manifest = drv.manifest();
+    let dialect = SqlDumpDialect::for_driver(&manifest.id, &manifest.capabilities.identifier_quote);
+    let qualifier = if manifest.capabilities.schemas {
+        schema
+    } else {
+        None
+    };
+    let batch_size = options
+        .batch_size
+        .unwrap_or(DEFAULT_SQL_DUMP_BATCH_SIZE)
+        .max(1);
+    let write_err = |e: std::io::Error| format!("Failed to write dump file: {}", e);
+
+    let tables = match &options.tables {
+        Some(selection) => selection.clone(),
+        None => drv
+            .get_tables(params, schema)
+            .await?
+            .into_iter()
+            .map(|t| t.name)
+            .collect(),
+    };
+
+    // A driver that cannot list foreign keys still dumps, in listed order.
+    let foreign_keys = match drv.get_all_foreign_keys_batch(params, schema).await {
+        Ok(foreign_keys) => foreign_keys,
+        Err(e) => {
+            log::warn!(
+                "SQL dump: foreign keys unavailable, tables stay unordered: {}",
+                e
+            );
+            HashMap::new()
+        }
+    };
+    let (tables, has_cycles) = dependency_order(&tables, &foreign_keys);
+    let deferred = if has_cycles && options.data {
+        deferred_constraints(&manifest.id)
+    } else {
+        None
+    };
+
+    writeln!(writer, "-- Tabularis SQL Dump").map_err(write_err)?;
+    writeln!(writer, "-- Driver: {}", manifest.id).map_err(write_err)?;
+    writeln!(writer, "-- Database: {}", params.database).map_err(write_err)?;
+    writeln!(writer, "-- Date: {}\n", chrono::Local::now().to_rfc3339()).map_err(write_err)?;
+    if let Some((suspend, _)) = deferred {
+        writeln!(writer, "{}\n", suspend).map_err(write_err)?;
+    }
+
+    let mut total = 0u64;
+    for table in &tables {
+        let table_ref = dialect.table_ref(qualifier, table);
+
+        if options.structure {
+            let ddl = drv.get_table_ddl(params, table, schema).await?;
+            writeln!(writer, "-- Structure for table {}", table_ref).map_err(write_err)?;
+            writeln!(writer, "DROP TABLE IF EXISTS {};", table_ref).map_err(write_err)?;
+            writeln!(writer, "{}\n", ddl).map_err(write_err)?;
+        }
+
+        if options.data {
+            writeln!(writer, "-- Data for table {}", table_ref).map_err(write_err)?;
+            let query = format!("SELECT * FROM {}", table_ref);
+            let mut on_batch = |columns: &[String], rows: Vec<Vec<Value>>| -> Result<(), String> {
+                writeln!(
+                    writer,
+                    "{}",
+                    dialect.insert_statement(&table_ref, columns, &rows)
+                )
+                .map_err(write_err)?;
+                total += rows.len() as u64;
+                on_progress(total);
+                Ok(())
+            };
+            drv.execute_query_stream(params, &query, batch_size, schema, &mut on_batch)
+                .await?;
+            writeln!(writer).map_err(write_err)?;
+        }
+    }
+
+    if let Some((_, restore)) = deferred {
+        writeln!(writer, "{}", restore).map_err(write_err)?;
+    }
+
+    writer.flush().map_err(write_err)?;
+    Ok(total)
+}
@@ -1,7 +1,25 @@
+use super::anonymize::{
+    delete_profile_in, fake_value, find_profile_in, hash_value, read_profiles_in, shuffle_column,
+    upsert_profile_in, AnonymizationProfile, AnonymizeRule, AnonymizeSink, ColumnRule, FakeKind,
+};
 use super::format::{parse_csv_delimiter, value_to_csv_string, ExportFormat, DEFAULT_CSV_DELIMITER};
+use super::json_sink::{is_json_type, nest_value, JsonColumn, JsonExportSink, JsonLayout};
+use super::layout::{format_value, ColumnProjection, LayoutSink};
+use super::parquet_sink::{ParquetCompression, ParquetSink};
 use super::progress::ProgressEmitter;
 use super::sink::{CsvSink, JsonSink, RowSink};
+use super::sql_dump::SqlDumpDialect;
+use super::xlsx_sink::{
+    overflow_sheet_name, sheet_name as xlsx_sheet_name, XlsxSink, DEFAULT_SHEET_NAME,
+};
+use crate::arrow_ipc::infer_type;
+use crate::preferences::{ColumnDisplay, ColumnLayout};
+use arrow::array::{Array, Int64Array, StringArray};
+use arrow::datatypes::DataType;
+use arrow::record_batch::RecordBatch;
+use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
 use serde_json::{json, Value};
+use std::io::{Read, Seek, SeekFrom};
 
 // ---------------------------------------------------------------------------
 // ExportFormat::parse
@@ -266,3 +284,746 @@ fn json_missing_value_defaults_to_null() {
     let parsed: Value = serde_json::from_str(&String::from_utf8(buf).unwrap()).unwrap();
     assert_eq!(parsed, json!([{"a": 1, "b": null}]));
 }
+
+// ---------------------------------------------------------------------------
+// JsonExportSink
+// ---------------------------------------------------------------------------
+
+fn collect_json_export(
+    layout: JsonLayout,
+    json_columns: &[&str],
+    schema: Option<Vec<JsonColumn>>,
+    rows: &[Vec<Value>],
+) -> String {
+    let mut buf: Vec<u8> = Vec::new();
+    {
+        let json_columns = json_columns.iter().map(|c| c.to_string()).collect();
+        let mut sink = JsonExportSink::new(&mut buf, layout, json_columns, schema);
+        let headers = vec!["id".to_string(), "doc".to_string()];
+        for values in rows {
+            sink.write_row(&headers, values).unwrap();
+        }
+        sink.finish().unwrap();
+    }
+    String::from_utf8(buf).unwrap()
+}
+
+#[test]
+fn json_layout_defaults_to_array() {
+    assert_eq!(JsonLayout::parse(None).unwrap(), JsonLayout::Array);
+    assert_eq!(
+        JsonLayout::parse(Some("NDJSON")).unwrap(),
+        JsonLayout::Ndjson
+    );
+    assert_eq!(
+        JsonLayout::parse(Some("jsonl")).unwrap(),
+        JsonLayout::Ndjson
+    );
+    assert!(JsonLayout::parse(Some("yaml")).is_err());
+}
+
+#[test]
+fn json_columns_are_nested_not_strings() {
+    let out = collect_json_export(
+        JsonLayout::Array,
+        &["doc"],
+        None,
+        &[
+            vec![json!(1), json!(r#"{"tags":["a","b"]}"#)],
+            vec![json!(2), json!({"already": "nested"})],
+            vec![json!(3), json!("not json")],
+        ],
+    );
+    let parsed: Value = serde_json::from_str(&out).unwrap();
+    assert_eq!(
+        parsed,
+        json!([
+            {"id": 1, "doc": {"tags": ["a", "b"]}},
+            {"id": 2, "doc": {"already": "nested"}},
+            {"id": 3, "doc": "not json"}
+        ])
+    );
+}
+
+#[test]
+fn other_columns_keep_json_looking_text() {
+    let out = collect_json_export(
+        JsonLayout::Array,
+        &[],
+        None,
+        &[vec![json!(1), json!("[1, 2]")]],
+    );
+    assert_eq!(out, r#"[{"id":1,"doc":"[1, 2]"}]"#);
+}
+
+#[test]
+fn ndjson_writes_one_object_per_line() {
+    let out = collect_json_export(
+        JsonLayout::Ndjson,
+        &["doc"],
+        None,
+        &[vec![json!(1), json!("{}")], vec![json!(2), Value::Null]],
+    );
+    assert_eq!(out, "{\"id\":1,\"doc\":{}}\n{\"id\":2,\"doc\":null}\n");
+    assert_eq!(collect_json_export(JsonLayout::Ndjson, &[], None, &[]), "");
+}
+
+#[test]
+fn schema_header_lists_columns_and_declared_types() {
+    let schema = vec![
+        JsonColumn {
+            name: "id".into(),
+            data_type: Some("integer".into()),
+        },
+        JsonColumn {
+            name: "doc".into(),
+            data_type: Some("jsonb".into()),
+        },
+    ];
+    let out = collect_json_export(
+        JsonLayout::Array,
+        &["doc"],
+        Some(schema.clone()),
+        &[vec![json!(1), json!("{}")]],
+    );
+    let parsed: Value = serde_json::from_str(&out).unwrap();
+    assert_eq!(
+        parsed,
+        json!({
+            "columns": [{"name": "id", "type": "integer"}, {"name": "doc", "type": "jsonb"}],
+            "rows": [{"id": 1, "doc": {}}]
+        })
+    );
+
+    let out = collect_json_export(JsonLayout::Ndjson, &[], Some(schema), &[]);
+    let first: Value = serde_json::from_str(out.lines().next().unwrap()).unwrap();
+    assert_eq!(first["columns"][1]["type"], json!("jsonb"));
+}
+
+#[test]
+fn json_types_are_recognised() {
+    assert!(is_json_type("JSONB"));
+    assert!(is_json_type("json"));
+    assert!(!is_json_type("jsonpath"));
+    assert_eq!(nest_value(json!("42")), json!(42));
+}
+
+// ---------------------------------------------------------------------------
+// Column layout projection
+// ---------------------------------------------------------------------------
+
+fn display(name: &str) -> ColumnDisplay {
+    ColumnDisplay {
+        name: name.to_string(),
+        ..Default::default()
+    }
+}
+
+fn layout(columns: Vec<ColumnDisplay>) -> ColumnLayout {
+    ColumnLayout {
+        columns,
+        ..Default::default()
+    }
+}
+
+fn headers(names: &[&str]) -> Vec<String> {
+    names.iter().map(|s| s.to_string()).collect()
+}
+
+#[test]
+fn projection_reorders_columns_by_layout() {
+    let projection = ColumnProjection::new(
+        &layout(vec![display("c"), display("a"), display("b")]),
+        &headers(&["a", "b", "c"]),
+    );
+    assert_eq!(projection.headers(), headers(&["c", "a", "b"]));
+    assert_eq!(
+        projection.apply(&[json!(1), json!(2), json!(3)]),
+        vec![json!(3), json!(1), json!(2)]
+    );
+}
+
+#[test]
+fn projection_drops_hidden_columns() {
+    let mut hidden = display("b");
+    hidden.hidden = true;
+    let projection =
+        ColumnProjection::new(&layout(vec![display("a"), hidden]), &headers(&["a", "b"]));
+    assert_eq!(projection.headers(), headers(&["a"]));
+    assert_eq!(projection.apply(&[json!(1), json!(2)]), vec![json!(1)]);
+}
+
+#[test]
+fn projection_appends_columns_unknown_to_layout() {
+    let projection = ColumnProjection::new(
+        &layout(vec![display("b"), display("gone")]),
+        &headers(&["a", "b", "c"]),
+    );
+    assert_eq!(projection.headers(), headers(&["b", "a", "c"]));
+}
+
+#[test]
+fn format_value_applies_decimals_and_thousands() {
+    let mut d = display("amount");
+    d.decimals = Some(2);
+    d.thousands_separator = true;
+    assert_eq!(format_value(json!(1234567.891), &d), json!("1,234,567.89"));
+    assert_eq!(format_value(json!("-1000"), &d), json!("-1,000.00"));
+    assert_eq!(format_value(Value::Null, &d), Value::Null);
+}
+
+#[test]
+fn format_value_keeps_every_digit_of_big_and_exact_numbers() {
+    let mut d = display("id");
+    d.thousands_separator = true;
+    assert_eq!(
+        format_value(json!(9007199254740993u64), &d),
+        json!("9,007,199,254,740,993")
+    );
+    assert_eq!(
+        format_value(json!("12345678901234567890.123456789"), &d),
+        json!("12,345,678,901,234,567,890.123456789")
+    );
+
+    d.decimals = Some(2);
+    assert_eq!(format_value(json!("999.995"), &d), json!("1,000.00"));
+    assert_eq!(format_value(json!("-0.001"), &d), json!("0.00"));
+    assert_eq!(format_value(json!("0.1"), &d), json!("0.10"));
+}
+
+#[test]
+fn format_value_leaves_non_numeric_strings_untouched() {
+    let mut d = display("amount");
+    d.decimals = Some(2);
+    assert_eq!(format_value(json!("n/a"), &d), json!("n/a"));
+}
+
+#[test]
+fn format_value_reformats_dates() {
+    let mut d = display("created_at");
+    d.date_format = Some("%d/%m/%Y".to_string());
+    assert_eq!(
+        format_value(json!("2024-03-15 10:20:30"), &d),
+        json!("15/03/2024")
+    );
+    assert_eq!(format_value(json!("2024-03-15"), &d), json!("15/03/2024"));
+    assert_eq!(
+        format_value(json!("2024-03-15T10:20:30+02:00"), &d),
+        json!("15/03/2024")
+    );
+}
+
+#[test]
+fn format_value_keeps_value_when_date_pattern_does_not_fit() {
+    let mut d = display("day");
+    d.date_format = Some("%H:%M".to_string());
+    assert_eq!(format_value(json!("2024-03-15"), &d), json!("2024-03-15"));
+}
+
+#[test]
+fn layout_sink_writes_projected_csv() {
+    let mut buf: Vec<u8> = Vec::new();
+    {
+        let mut sink = CsvSink::new(&mut buf, b',');
+        let mut hidden = display("secret");
+        hidden.hidden = true;
+        let mut layout_sink = LayoutSink::new(
+            &mut sink,
+            Some(layout(vec![display("name"), display("id"), hidden])),
+        );
+        let headers = headers(&["id", "secret", "name"]);
+        layout_sink
+            .write_row(&headers, &[json!(1), json!("x"), json!("Alice")])
+            .unwrap();
+        layout_sink.finish().unwrap();
+    }
+    assert_eq!(String::from_utf8(buf).unwrap(), "name,id\nAlice,1\n");
+}
+
+#[test]
+fn layout_sink_without_layout_passes_rows_through() {
+    let mut buf: Vec<u8> = Vec::new();
+    {
+        let mut sink = CsvSink::new(&mut buf, b',');
+        let mut layout_sink = LayoutSink::new(&mut sink, None);
+        layout_sink
+            .write_row(&headers(&["a", "b"]), &[json!(1), json!(2)])
+            .unwrap();
+        layout_sink.finish().unwrap();
+    }
+    assert_eq!(String::from_utf8(buf).unwrap(), "a,b\n1,2\n");
+}
+
+// ---------------------------------------------------------------------------
+// Anonymization
+// ---------------------------------------------------------------------------
+
+#[derive(Default)]
+struct CollectSink {
+    rows: Vec<Vec<Value>>,
+    finished: bool,
+}
+
+impl RowSink for CollectSink {
+    fn write_row(&mut self, _headers: &[String], values: &[Value]) -> Result<(), String> {
+        self.rows.push(values.to_vec());
+        Ok(())
+    }
+
+    fn finish(&mut self) -> Result<(), String> {
+        self.finished = true;
+        Ok(())
+    }
+}
+
+fn profile(columns: Vec<(&str, AnonymizeRule)>) -> AnonymizationProfile {
+    AnonymizationProfile {
+        id: String::new(),
+        name: "dev".to_string(),
+        salt: "salt".to_string(),
+        columns: columns
+            .into_iter()
+            .map(|(column, rule)| ColumnRule {
+                column: column.to_string(),
+                rule,
+            })
+            .collect(),
+        created_at: None,
+        updated_at: None,
+    }
+}
+
+#[test]
+fn hash_value_is_salted_and_deterministic() {
+    let a = hash_value("email", &json!("a@b.c"), "s1");
+    assert_eq!(a, hash_value("email", &json!("a@b.c"), "s1"));
+    assert_ne!(a, hash_value("email", &json!("a@b.c"), "s2"));
+    assert_eq!(a.as_str().unwrap().len(), 64);
+    assert_eq!(hash_value("email", &Value::Null, "s1"), Value::Null);
+}
+
+#[test]
+fn fake_value_is_consistent_for_the_same_input() {
+    let a = fake_value(FakeKind::Email, "email", &json!("real@corp.com"), "s");
+    assert_eq!(
+        a,
+        fake_value(FakeKind::Email, "email", &json!("real@corp.com"), "s")
+    );
+    let email = a.as_str().unwrap();
+    assert!(email.ends_with("@example.com"));
+    assert!(!email.contains("real"));
+}
+
+#[test]
+fn fake_value_keeps_null_and_number_shape() {
+    assert_eq!(
+        fake_value(FakeKind::Name, "name", &Value::Null, "s"),
+        Value::Null
+    );
+    let n = fake_value(FakeKind::Number, "zip", &json!(12345), "s");
+    assert!(n.is_number());
+    assert_eq!(n.to_string().len(), 5);
+    let uuid = fake_value(FakeKind::Uuid, "id", &json!("x"), "s");
+    assert!(uuid::Uuid::parse_str(uuid.as_str().unwrap()).is_ok());
+}
+
+#[test]
+fn shuffle_column_permutes_only_that_column() {
+    let mut rows: Vec<Vec<Value>> = (0..50).map(|i| vec![json!(i), json!(i)]).collect();
+    shuffle_column(&mut rows, 1, &[7u8; 32]);
+
+    assert!(rows.iter().enumerate().all(|(i, r)| r[0] == json!(i)));
+    assert!(rows.iter().any(|r| r[0] != r[1]));
+    let mut shuffled: Vec<i64> = rows.iter().map(|r| r[1].as_i64().unwrap()).collect();
+    shuffled.sort();
+    assert_eq!(shuffled, (0..50).collect::<Vec<_>>());
+}
+
+#[test]
+fn anonymize_sink_applies_column_rules() {
+    let mut collect = CollectSink::default();
+    {
+        let mut sink = AnonymizeSink::new(
+            &mut collect,
+            Some(profile(vec![
+                ("email", AnonymizeRule::Hash),
+                (
+                    "name",
+                    AnonymizeRule::Fake {
+                        kind: FakeKind::Name,
+                    },
+                ),
+            ])),
+        );
+        let headers = headers(&["id", "name", "email"]);
+        sink.write_row(&headers, &[json!(1), json!("Mario Rossi"), json!("m@r.it")])
+            .unwrap();
+        sink.finish().unwrap();
+    }
+
+    let row = &collect.rows[0];
+    assert_eq!(row[0], json!(1));
+    assert_ne!(row[1], json!("Mario Rossi"));
+    assert_eq!(row[2], hash_value("email", &json!("m@r.it"), "salt"));
+    assert!(collect.finished);
+}
+
+#[test]
+fn anonymize_sink_flushes_shuffled_rows_on_finish() {
+    let mut collect = CollectSink::default();
+    {
+        let mut sink = AnonymizeSink::new(
+            &mut collect,
+            Some(profile(vec![("city", AnonymizeRule::Shuffle)])),
+        );
+        let headers = headers(&["id", "city"]);
+        for i in 0..20 {
+            sink.write_row(&headers, &[json!(i), json!(format!("c{}", i))])
+                .unwrap();
+        }
+        sink.finish().unwrap();
+    }
+
+    assert_eq!(collect.rows.len(), 20);
+    let mut cities: Vec<String> = collect
+        .rows
+        .iter()
+        .map(|r| r[1].as_str().unwrap().to_string())
+        .collect();
+    cities.sort();
+    let mut expected: Vec<String> = (0..20).map(|i| format!("c{}", i)).collect();
+    expected.sort();
+    assert_eq!(cities, expected);
+}
+
+#[test]
+fn anonymize_sink_without_profile_passes_rows_through() {
+    let mut collect = CollectSink::default();
+    {
+        let mut sink = AnonymizeSink::new(&mut collect, None);
+        sink.write_row(&headers(&["a"]), &[json!("x")]).unwrap();
+        sink.finish().unwrap();
+    }
+    assert_eq!(collect.rows, vec![vec![json!("x")]]);
+}
+
+#[test]
+fn profile_rules_round_trip_as_tagged_json() {
+    let rule: ColumnRule =
+        serde_json::from_value(json!({ "column": "email", "method": "fake", "kind": "email" }))
+            .unwrap();
+    assert_eq!(
+        rule.rule,
+        AnonymizeRule::Fake {
+            kind: FakeKind::Email
+        }
+    );
+    let rule: ColumnRule =
+        serde_json::from_value(json!({ "column": "ssn", "method": "hash" })).unwrap();
+    assert_eq!(rule.rule, AnonymizeRule::Hash);
+}
+
+#[test]
+fn upsert_profile_assigns_id_and_salt_and_updates_in_place() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let mut p = profile(vec![("email", AnonymizeRule::Hash)]);
+    p.salt = String::new();
+
+    let saved = upsert_profile_in(dir.path(), p).unwrap();
+    assert!(!saved.id.is_empty());
+    assert!(!saved.salt.is_empty());
+    assert!(saved.created_at.is_some());
+
+    let mut renamed = saved.clone();
+    renamed.name = "staging".to_string();
+    upsert_profile_in(dir.path(), renamed).unwrap();
+
+    let profiles = read_profiles_in(dir.path());
+    assert_eq!(profiles.len(), 1);
+    assert_eq!(profiles[0].name, "staging");
+    assert_eq!(profiles[0].salt, saved.salt);
+    assert_eq!(profiles[0].created_at, saved.created_at);
+    assert_eq!(
+        find_profile_in(dir.path(), &saved.id).unwrap().name,
+        "staging"
+    );
+}
+
+#[test]
+fn upsert_profile_rejects_duplicate_names_and_columns() {
+    let dir = tempfile::TempDir::new().unwrap();
+    upsert_profile_in(dir.path(), profile(vec![])).unwrap();
+
+    let err = upsert_profile_in(dir.path(), profile(vec![])).unwrap_err();
+    assert_eq!(
+        err,
+        crate::i18n::t_with("errors.exportProfileExists", &[("name", "dev")])
+    );
+
+    let mut dup = profile(vec![
+        ("a", AnonymizeRule::Hash),
+        ("a", AnonymizeRule::Shuffle),
+    ]);
+    dup.name = "other".to_string();
+    let err = upsert_profile_in(dir.path(), dup).unwrap_err();
+    assert_eq!(
+        err,
+        crate::i18n::t_with("errors.exportProfileDuplicateRule", &[("column", "a")])
+    );
+}
+
+#[test]
+fn delete_profile_removes_it() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let saved = upsert_profile_in(dir.path(), profile(vec![])).unwrap();
+    delete_profile_in(dir.path(), &saved.id).unwrap();
+    assert!(read_profiles_in(dir.path()).is_empty());
+    assert!(find_profile_in(dir.path(), &saved.id).is_err());
+}
+
+// ---------------------------------------------------------------------------
+// SQL dump
+// ---------------------------------------------------------------------------
+
+#[test]
+fn sql_dump_quotes_identifiers_per_driver() {
+    let pg = SqlDumpDialect::for_driver("postgres", "\"");
+    assert_eq!(pg.table_ref(Some("public"), "my\"t"), r#""public"."my""t""#);
+
+    let mysql = SqlDumpDialect::for_driver("mysql", "`");
+    assert_eq!(mysql.table_ref(None, "a`b"), "`a``b`");
+
+    let brackets = SqlDumpDialect::for_driver("mssql", "[");
+    assert_eq!(brackets.quote_identifier("a]b"), "[a]]b]");
+}
+
+#[test]
+fn sql_dump_literals_follow_dialect() {
+    let pg = SqlDumpDialect::for_driver("postgres", "\"");
+    assert_eq!(pg.literal(&json!("it's a \\ path")), r"'it''s a \ path'");
+    assert_eq!(pg.literal(&json!(true)), "TRUE");
+    assert_eq!(pg.literal(&Value::Null), "NULL");
+    assert_eq!(pg.literal(&json!({"k": 1})), r#"'{"k":1}'"#);
+
+    let mysql = SqlDumpDialect::for_driver("mysql", "`");
+    assert_eq!(mysql.literal(&json!("a\\b")), r"'a\\b'");
+
+    let mssql = SqlDumpDialect::for_driver("mssql", "\"");
+    assert_eq!(mssql.literal(&json!(false)), "0");
+}
+
+#[test]
+fn sql_dump_insert_lists_columns_and_rows() {
+    let dialect = SqlDumpDialect::for_driver("sqlite", "\"");
+    let sql = dialect.insert_statement(
+        r#""users""#,
+        &["id".to_string(), "name".to_string()],
+        &[vec![json!(1), json!("Alice")], vec![json!(2), Value::Null]],
+    );
+    assert_eq!(
+        sql,
+        "INSERT INTO \"users\" (\"id\", \"name\") VALUES\n(1, 'Alice'),\n(2, NULL);"
+    );
+}
+
+// ---------------------------------------------------------------------------
+// ParquetSink
+// ---------------------------------------------------------------------------
+
+fn collect_parquet(
+    row_group_size: usize,
+    rows: &[(Vec<&str>, Vec<Value>)],
+) -> Result<(usize, Vec<RecordBatch>), String> {
+    let file = tempfile::tempfile().unwrap();
+    let mut sink = ParquetSink::new(
+        file.try_clone().unwrap(),
+        ParquetCompression::Snappy,
+        row_group_size,
+    );
+    for (headers, values) in rows {
+        let headers_owned: Vec<String> = headers.iter().map(|s| s.to_string()).collect();
+        sink.write_row(&headers_owned, values)?;
+    }
+    sink.finish()?;
+    let builder = ParquetRecordBatchReaderBuilder::try_new(file).unwrap();
+    let row_groups = builder.metadata().num_row_groups();
+    let reader = builder.build().unwrap();
+    Ok((row_groups, reader.map(|batch| batch.unwrap()).collect()))
+}
+
+#[test]
+fn parquet_compression_defaults_to_snappy() {
+    assert_eq!(
+        ParquetCompression::parse(None),
+        Ok(ParquetCompression::Snappy)
+    );
+    assert_eq!(
+        ParquetCompression::parse(Some(" ZSTD ")),
+        Ok(ParquetCompression::Zstd)
+    );
+    assert_eq!(
+        ParquetCompression::parse(Some("uncompressed")),
+        Ok(ParquetCompression::None)
+    );
+    assert!(ParquetCompression::parse(Some("brotli")).is_err());
+}
+
+#[test]
+fn parquet_infers_column_types() {
+    assert_eq!(infer_type(&[json!(1), Value::Null]), DataType::Int64);
+    assert_eq!(infer_type(&[json!(1), json!(2.5)]), DataType::Float64);
+    assert_eq!(infer_type(&[json!(true)]), DataType::Boolean);
+    assert_eq!(infer_type(&[json!(1), json!("x")]), DataType::Utf8);
+    assert_eq!(infer_type(&[json!({"k": 1})]), DataType::Utf8);
+    assert_eq!(infer_type(&[Value::Null]), DataType::Utf8);
+}
+
+#[test]
+fn parquet_writes_one_row_group_per_chunk() {
+    let rows: Vec<_> = (0..5)
+        .map(|i| {
+            (
+                vec!["id", "name", "meta"],
+                vec![
+                    json!(i),
+                    if i == 2 {
+                        Value::Null
+                    } else {
+                        json!(format!("n{}", i))
+                    },
+                    json!({"i": i}),
+                ],
+            )
+        })
+        .collect();
+    let (row_groups, batches) = collect_parquet(2, &rows).unwrap();
+
+    assert_eq!(row_groups, 3);
+    assert_eq!(batches.len(), 1);
+    let batch = &batches[0];
+    assert_eq!(batch.num_rows(), 5);
+    let schema = batch.schema();
+    assert_eq!(schema.field(0).data_type(), &DataType::Int64);
+    assert_eq!(schema.field(1).data_type(), &DataType::Utf8);
+
+    let ids = batch
+        .column(0)
+        .as_any()
+        .downcast_ref::<Int64Array>()
+        .unwrap();
+    assert_eq!(ids.value(1), 1);
+    let meta = batch
+        .column(2)
+        .as_any()
+        .downcast_ref::<StringArray>()
+        .unwrap();
+    assert_eq!(meta.value(0), r#"{"i":0}"#);
+    let names = batch
+        .column(1)
+        .as_any()
+        .downcast_ref::<StringArray>()
+        .unwrap();
+    assert!(names.is_null(2));
+}
+
+#[test]
+fn parquet_rejects_values_that_change_a_column_type() {
+    let err = collect_parquet(
+        1,
+        &[(vec!["v"], vec![json!(1)]), (vec!["v"], vec![json!("one")])],
+    )
+    .unwrap_err();
+    assert!(err.contains("\"v\""));
+}
+
+#[test]
+fn parquet_empty_stream_writes_a_readable_file() {
+    let (row_groups, batches) = collect_parquet(10, &[]).unwrap();
+    assert_eq!(row_groups, 0);
+    assert!(batches.is_empty());
+}
+
+// ---------------------------------------------------------------------------
+// XlsxSink
+// ---------------------------------------------------------------------------
+
+/// Writes `rows` to a workbook and returns each sheet's XML.
+fn collect_xlsx(rows_per_sheet: Option<u32>, rows: &[(Vec<&str>, Vec<Value>)]) -> Vec<String> {
+    let mut file = tempfile::tempfile().unwrap();
+    let mut sink = XlsxSink::new(file.try_clone().unwrap(), "Orders", rows_per_sheet).unwrap();
+    for (headers, values) in rows {
+        let headers_owned: Vec<String> = headers.iter().map(|s| s.to_string()).collect();
+        sink.write_row(&headers_owned, values).unwrap();
+    }
+    sink.finish().unwrap();
+
+    file.seek(SeekFrom::Start(0)).unwrap();
+    let mut archive = zip::ZipArchive::new(file).unwrap();
+    (1..)
+        .map_while(|i| {
+            let mut xml = String::new();
+            archive
+                .by_name(&format!("xl/worksheets/sheet{}.xml", i))
+                .ok()?
+                .read_to_string(&mut xml)
+                .unwrap();
+            Some(xml)
+        })
+        .collect()
+}
+
+#[test]
+fn xlsx_sheet_name_defaults_and_is_validated() {
+    assert_eq!(xlsx_sheet_name(None).unwrap(), DEFAULT_SHEET_NAME);
+    assert_eq!(xlsx_sheet_name(Some("  ")).unwrap(), DEFAULT_SHEET_NAME);
+    assert_eq!(xlsx_sheet_name(Some(" Q1 ")).unwrap(), "Q1");
+    assert!(xlsx_sheet_name(Some("a/b")).is_err());
+    assert!(xlsx_sheet_name(Some("'quoted'")).is_err());
+    assert!(xlsx_sheet_name(Some(&"x".repeat(32))).is_err());
+}
+
+#[test]
+fn xlsx_overflow_sheets_are_numbered_within_the_name_limit() {
+    assert_eq!(overflow_sheet_name("Orders", 0), "Orders");
+    assert_eq!(overflow_sheet_name("Orders", 1), "Orders (2)");
+    let long = overflow_sheet_name(&"x".repeat(31), 11);
+    assert_eq!(long.chars().count(), 31);
+    assert!(long.ends_with(" (12)"));
+}
+
+#[test]
+fn xlsx_writes_typed_cells_and_leaves_nulls_empty() {
+    let sheets = collect_xlsx(
+        None,
+        &[(
+            vec!["id", "name", "active", "note", "big"],
+            vec![
+                json!(7),
+                json!("alice"),
+                json!(true),
+                Value::Null,
+                json!(9_007_199_254_740_993_i64),
+            ],
+        )],
+    );
+    assert_eq!(sheets.len(), 1);
+    let xml = &sheets[0];
+    assert!(xml.contains("<t>name</t>"));
+    assert!(xml.contains(r#"<c r="A2"><v>7</v></c>"#));
+    assert!(xml.contains("<t>alice</t>"));
+    assert!(xml.contains(r#"<c r="C2" t="b"><v>1</v></c>"#));
+    assert!(!xml.contains(r#"r="D2""#));
+    assert!(xml.contains("<t>9007199254740993</t>"));
+}
+
+#[test]
+fn xlsx_continues_on_a_new_sheet_past_the_row_limit() {
+    let rows: Vec<_> = (0..5).map(|i| (vec!["id"], vec![json!(i)])).collect();
+    let sheets = collect_xlsx(Some(2), &rows);
+    assert_eq!(sheets.len(), 3);
+    for sheet in &sheets {
+        assert!(sheet.contains("<t>id</t>"));
+    }
+    assert!(sheets[2].contains(r#"<c r="A2"><v>4</v></c>"#));
+    assert!(!sheets[2].contains(r#"r="A3""#));
+}
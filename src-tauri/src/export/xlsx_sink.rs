@@ -0,0 +1,185 @@
+use std::io::Write;
+
+use rust_xlsxwriter::{Format, Workbook, Worksheet};
+use serde_json::Value;
+
+use super::sink::RowSink;
+
+pub const DEFAULT_SHEET_NAME: &str = "Results";
+
+/// Data rows that fit on one sheet: Excel's 1,048,576 rows minus the header.
+pub const MAX_SHEET_ROWS: u32 = 1_048_575;
+
+const MAX_SHEET_NAME_CHARS: usize = 31;
+const MAX_COLUMNS: usize = 16_384;
+/// Longest text Excel keeps in a cell; longer values are cut.
+const MAX_CELL_CHARS: usize = 32_767;
+/// Integers beyond 2^53 lose digits as Excel numbers and are written as text.
+const MAX_EXACT_INTEGER: u64 = 1 << 53;
+
+/// The sheet name to use: `DEFAULT_SHEET_NAME` when unset or blank, an error
+/// when Excel would reject it.
+pub fn sheet_name(name: Option<&str>) -> Result<String, String> {
+    let name = name.map(str::trim).filter(|n| !n.is_empty());
+    let Some(name) = name else {
+        return Ok(DEFAULT_SHEET_NAME.to_string());
+    };
+    if name.chars().count() > MAX_SHEET_NAME_CHARS {
+        return Err(crate::i18n::t_with(
+            "errors.sheetNameTooLong",
+            &[("name", name), ("max", &MAX_SHEET_NAME_CHARS.to_string())],
+        ));
+    }
+    if let Some(c) = name.chars().find(|c| "[]:*?/\\".contains(*c)) {
+        return Err(crate::i18n::t_with(
+            "errors.sheetNameInvalidCharacter",
+            &[("name", name), ("character", &c.to_string())],
+        ));
+    }
+    if name.starts_with('\'') || name.ends_with('\'') {
+        return Err(crate::i18n::t_with(
+            "errors.sheetNameApostrophe",
+            &[("name", name)],
+        ));
+    }
+    Ok(name.to_string())
+}
+
+/// Name of the `index`-th sheet (0-based): `base`, then `base (2)`,
+/// `base (3)`, … shortened to fit Excel's limit.
+pub fn overflow_sheet_name(base: &str, index: usize) -> String {
+    if index == 0 {
+        return base.to_string();
+    }
+    let suffix = format!(" ({})", index + 1);
+    let kept: String = base
+        .chars()
+        .take(MAX_SHEET_NAME_CHARS - suffix.len())
+        .collect();
+    format!("{}{}", kept.trim_end(), suffix)
+}
+
+fn write_cell(sheet: &mut Worksheet, row: u32, col: u16, value: &Value) -> Result<(), String> {
+    let result = match value {
+        Value::Null => return Ok(()),
+        Value::Bool(b) => sheet.write_boolean(row, col, *b),
+        Value::Number(n) => {
+            let exact = n
+                .as_i64()
+                .map(|i| i.unsigned_abs() <= MAX_EXACT_INTEGER)
+                .or_else(|| n.as_u64().map(|u| u <= MAX_EXACT_INTEGER))
+                .unwrap_or(true);
+            match n.as_f64() {
+                Some(f) if exact => sheet.write_number(row, col, f),
+                _ => sheet.write_string(row, col, n.to_string()),
+            }
+        }
+        Value::String(s) if s.chars().count() > MAX_CELL_CHARS => {
+            sheet.write_string(row, col, s.chars().take(MAX_CELL_CHARS).collect::<String>())
+        }
+        Value::String(s) => sheet.write_string(row, col, s),
+        other => sheet.write_string(row, col, other.to_string()),
+    };
+    result.map(|_| ()).map_err(|e| e.to_string())
+}
+
+/// Streaming Excel sink. Sheets are written in constant-memory mode, so rows
+/// are spilled to temporary files rather than held until `finish`. Each
+/// sheet starts with a bold, frozen header row; NULL is an empty cell.
+pub struct XlsxSink<W: Write + Send> {
+    workbook: Workbook,
+    writer: Option<W>,
+    sheet_name: String,
+    rows_per_sheet: u32,
+    sheets: usize,
+    /// Next row to write on the current sheet; row 0 is the header.
+    row: u32,
+    headers: Vec<String>,
+    header_format: Format,
+}
+
+impl<W: Write + Send> XlsxSink<W> {
+    /// `rows_per_sheet` defaults to, and is capped at, `MAX_SHEET_ROWS`.
+    pub fn new(writer: W, sheet_name: &str, rows_per_sheet: Option<u32>) -> Result<Self, String> {
+        let mut sink = Self {
+            workbook: Workbook::new(),
+            writer: Some(writer),
+            sheet_name: sheet_name.to_string(),
+            rows_per_sheet: rows_per_sheet
+                .unwrap_or(MAX_SHEET_ROWS)
+                .clamp(1, MAX_SHEET_ROWS),
+            sheets: 0,
+            row: 1,
+            headers: Vec::new(),
+            header_format: Format::new().set_bold(),
+        };
+        sink.add_sheet()?;
+        Ok(sink)
+    }
+
+    fn add_sheet(&mut self) -> Result<(), String> {
+        let name = overflow_sheet_name(&self.sheet_name, self.sheets);
+        let sheet = self.workbook.add_worksheet_with_constant_memory();
+        sheet.set_name(&name).map_err(|e| e.to_string())?;
+        self.sheets += 1;
+        self.row = 1;
+        if !self.headers.is_empty() {
+            self.write_headers()?;
+        }
+        Ok(())
+    }
+
+    fn sheet(&mut self) -> Result<&mut Worksheet, String> {
+        self.workbook
+            .worksheet_from_index(self.sheets - 1)
+            .map_err(|e| e.to_string())
+    }
+
+    fn write_headers(&mut self) -> Result<(), String> {
+        let headers = self.headers.clone();
+        let format = self.header_format.clone();
+        let sheet = self.sheet()?;
+        for (col, name) in headers.iter().enumerate() {
+            sheet
+                .write_string_with_format(0, col as u16, name, &format)
+                .map_err(|e| e.to_string())?;
+        }
+        sheet.set_freeze_panes(1, 0).map_err(|e| e.to_string())?;
+        Ok(())
+    }
+}
+
+impl<W: Write + Send> RowSink for XlsxSink<W> {
+    fn write_row(&mut self, headers: &[String], values: &[Value]) -> Result<(), String> {
+        if self.headers.is_empty() {
+            if headers.len() > MAX_COLUMNS {
+                return Err(format!(
+                    "Excel sheets hold at most {} columns; the result has {}",
+                    MAX_COLUMNS,
+                    headers.len()
+                ));
+            }
+            self.headers = headers.to_vec();
+            self.write_headers()?;
+        }
+        if self.row > self.rows_per_sheet {
+            self.add_sheet()?;
+        }
+        let row = self.row;
+        let sheet = self.sheet()?;
+        for (col, value) in values.iter().take(MAX_COLUMNS).enumerate() {
+            write_cell(sheet, row, col as u16, value)?;
+        }
+        self.row += 1;
+        Ok(())
+    }
+
+    fn finish(&mut self) -> Result<(), String> {
+        let Some(writer) = self.writer.take() else {
+            return Ok(());
+        };
+        self.workbook
+            .save_to_writer(writer)
+            .map_err(|e| e.to_string())
+    }
+}
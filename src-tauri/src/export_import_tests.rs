@@ -35,11 +35,17 @@ mod tests {
                     ssh_key_file: None,
                     ssh_key_passphrase: None,
                     save_in_keychain: Some(true),
+                    read_only: false,
+                    auth_method: None,
+                    pool: None,
                     connection_id: None,
                 },
                 group_id: Some("group1".to_string()),
                 sort_order: Some(0),
                 detect_json_in_text_columns: None,
+                protected: None,
+                read_replicas: None,
+                connect_on_startup: None,
             }],
             ssh_connections: vec![SshConnection {
                 id: "ssh1".to_string(),
@@ -53,6 +59,7 @@ mod tests {
                 key_passphrase: None,
                 save_in_keychain: Some(true),
             }],
+            named_filters: Default::default(),
         };
 
         let json = serde_json::to_string(&payload).unwrap();
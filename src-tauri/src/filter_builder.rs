@@ -0,0 +1,522 @@
+//! Backend of the visual filter builder.
+//!
+//! The grid's filter UI sends a tree of AND/OR groups and column conditions;
+//! [`build_filter`] turns it into a WHERE clause (without the keyword) whose
+//! values are all `:pN` placeholders, to be run through
+//! `execute_query_with_params`. User input never reaches the SQL text: only
+//! column names do, quoted with the driver's identifier quote.
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tauri::{AppHandle, Runtime};
+
+use crate::drivers::common::quote_identifier;
+
+/// Escape character of every generated LIKE pattern. `!` rather than `\`
+/// because MySQL also treats the backslash as a string escape.
+const LIKE_ESCAPE: char = '!';
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Combinator {
+    And,
+    Or,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum FilterOperator {
+    Eq,
+    Neq,
+    Lt,
+    Lte,
+    Gt,
+    Gte,
+    In,
+    NotIn,
+    Between,
+    IsNull,
+    IsNotNull,
+    Contains,
+    NotContains,
+    StartsWith,
+    EndsWith,
+    /// A LIKE pattern written by the user: `%` and `_` keep their meaning.
+    Like,
+    NotLike,
+    /// Regular expression match, where the driver supports one (see
+    /// [`regex_operator`]).
+    Regex,
+    NotRegex,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum FilterNode {
+    #[serde(rename_all = "camelCase")]
+    Group {
+        combinator: Combinator,
+        #[serde(default)]
+        negate: bool,
+        #[serde(default)]
+        children: Vec<FilterNode>,
+    },
+    #[serde(rename_all = "camelCase")]
+    Condition {
+        column: String,
+        operator: FilterOperator,
+        /// Operand of comparison and text operators.
+        #[serde(default)]
+        value: Option<Value>,
+        /// Operands of `in`, `notIn` and `between` (two values).
+        #[serde(default)]
+        values: Vec<Value>,
+        /// Compare text ignoring case.
+        #[serde(default)]
+        case_insensitive: bool,
+    },
+}
+
+/// A WHERE clause with `:pN` placeholders and their values.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct BuiltFilter {
+    pub sql: String,
+    pub params: HashMap<String, Value>,
+}
+
+struct Builder<'a> {
+    driver: &'a str,
+    quote: &'a str,
+    params: HashMap<String, Value>,
+}
+
+impl Builder<'_> {
+    fn bind(&mut self, value: Value) -> String {
+        let name = format!("p{}", self.params.len());
+        self.params.insert(name.clone(), value);
+        format!(":{}", name)
+    }
+
+    fn node(&mut self, node: &FilterNode) -> Result<String, String> {
+        match node {
+            FilterNode::Group {
+                combinator,
+                negate,
+                children,
+            } => {
+                let sql = if children.is_empty() {
+                    // Neutral element: an empty AND matches everything.
+                    match combinator {
+                        Combinator::And => "1 = 1".to_string(),
+                        Combinator::Or => "1 = 0".to_string(),
+                    }
+                } else {
+                    let joiner = match combinator {
+                        Combinator::And => " AND ",
+                        Combinator::Or => " OR ",
+                    };
+                    let parts = children
+                        .iter()
+                        .map(|child| self.node(child))
+                        .collect::<Result<Vec<_>, _>>()?;
+                    parts.join(joiner)
+                };
+                Ok(if *negate {
+                    format!("NOT ({})", sql)
+                } else {
+                    format!("({})", sql)
+                })
+            }
+            FilterNode::Condition {
+                column,
+                operator,
+                value,
+                values,
+                case_insensitive,
+            } => self.condition(column, *operator, value.as_ref(), values, *case_insensitive),
+        }
+    }
+
+    fn condition(
+        &mut self,
+        column: &str,
+        operator: FilterOperator,
+        value: Option<&Value>,
+        values: &[Value],
+        case_insensitive: bool,
+    ) -> Result<String, String> {
+        if column.trim().is_empty() {
+            return Err("Filter condition without a column".to_string());
+        }
+        let col = quote_identifier(self.quote, column);
+        let operand = |op: FilterOperator| -> Result<Value, String> {
+            match value {
+                Some(v) if !v.is_null() => Ok(v.clone()),
+                _ => Err(format!("Filter on {} needs a value for {:?}", column, op)),
+            }
+        };
+
+        use FilterOperator::*;
+        Ok(match operator {
+            IsNull => format!("{} IS NULL", col),
+            IsNotNull => format!("{} IS NOT NULL", col),
+            Eq | Neq | Lt | Lte | Gt | Gte => {
+                let symbol = match operator {
+                    Eq => "=",
+                    Neq => "<>",
+                    Lt => "<",
+                    Lte => "<=",
+                    Gt => ">",
+                    _ => ">=",
+                };
+                let v = operand(operator)?;
+                let lower = case_insensitive && v.is_string() && matches!(operator, Eq | Neq);
+                let p = self.bind(v);
+                if lower {
+                    format!("LOWER({}) {} LOWER({})", col, symbol, p)
+                } else {
+                    format!("{} {} {}", col, symbol, p)
+                }
+            }
+            In | NotIn => {
+                if values.is_empty() {
+                    // `IN ()` is a syntax error; nothing is in an empty list.
+                    return Ok(if operator == In { "1 = 0" } else { "1 = 1" }.to_string());
+                }
+                let list: Vec<String> = values.iter().map(|v| self.bind(v.clone())).collect();
+                let keyword = if operator == In { "IN" } else { "NOT IN" };
+                format!("{} {} ({})", col, keyword, list.join(", "))
+            }
+            Between => {
+                let [low, high] = values else {
+                    return Err(format!("Filter on {} needs two values for Between", column));
+                };
+                let (low, high) = (self.bind(low.clone()), self.bind(high.clone()));
+                format!("{} BETWEEN {} AND {}", col, low, high)
+            }
+            Regex | NotRegex => {
+                let pattern = match operand(operator)? {
+                    Value::String(s) => s,
+                    other => other.to_string(),
+                };
+                let negated = operator == NotRegex;
+                let (op, pattern) =
+                    regex_operator(self.driver, negated, case_insensitive, pattern)?;
+                let p = self.bind(Value::String(pattern));
+                format!("{} {} {}", col, op, p)
+            }
+            Contains | NotContains | StartsWith | EndsWith | Like | NotLike => {
+                let v = operand(operator)?;
+                let text = match &v {
+                    Value::String(s) => s.clone(),
+                    other => other.to_string(),
+                };
+                let pattern = match operator {
+                    Like | NotLike => text,
+                    Contains | NotContains => format!("%{}%", escape_like(&text)),
+                    StartsWith => format!("{}%", escape_like(&text)),
+                    _ => format!("%{}", escape_like(&text)),
+                };
+                let negated = matches!(operator, NotContains | NotLike);
+                let p = self.bind(Value::String(pattern));
+                let escape = if matches!(operator, Like | NotLike) {
+                    String::new()
+                } else {
+                    format!(" ESCAPE '{}'", LIKE_ESCAPE)
+                };
+                let not = if negated { "NOT " } else { "" };
+                if case_insensitive && self.driver == "postgres" {
+                    format!("{} {}ILIKE {}{}", col, not, p, escape)
+                } else if case_insensitive {
+                    format!("LOWER({}) {}LIKE LOWER({}){}", col, not, p, escape)
+                } else {
+                    format!("{} {}LIKE {}{}", col, not, p, escape)
+                }
+            }
+        })
+    }
+}
+
+/// The regex match operator of `driver`, and the pattern to bind.
+///
+/// PostgreSQL has `~` / `~*`; MySQL, MariaDB and SQLite have `REGEXP`,
+/// made case-insensitive with an inline `(?i)` flag, which both the ICU
+/// (MySQL 8) and PCRE (MariaDB) engines accept. SQLite's `REGEXP` needs a
+/// loaded extension, which [`build_filter`] checks for. Other drivers have
+/// no portable regex operator.
+pub fn regex_operator(
+    driver: &str,
+    negated: bool,
+    case_insensitive: bool,
+    pattern: String,
+) -> Result<(&'static str, String), String> {
+    match driver {
+        "postgres" => Ok((
+            match (negated, case_insensitive) {
+                (false, false) => "~",
+                (false, true) => "~*",
+                (true, false) => "!~",
+                (true, true) => "!~*",
+            },
+            pattern,
+        )),
+        "mysql" | "sqlite" => Ok((
+            if negated { "NOT REGEXP" } else { "REGEXP" },
+            if case_insensitive {
+                format!("(?i){}", pattern)
+            } else {
+                pattern
+            },
+        )),
+        other => Err(format!(
+            "Regular expression filters are not supported by the {} driver",
+            other
+        )),
+    }
+}
+
+/// Whether `filter` contains a regex condition.
+pub fn uses_regex(filter: &FilterNode) -> bool {
+    match filter {
+        FilterNode::Group { children, .. } => children.iter().any(uses_regex),
+        FilterNode::Condition { operator, .. } => {
+            matches!(operator, FilterOperator::Regex | FilterOperator::NotRegex)
+        }
+    }
+}
+
+/// Escape LIKE wildcards (and `[`, a wildcard on SQL Server) so `text`
+/// matches literally with `ESCAPE '!'`.
+pub fn escape_like(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(c, '%' | '_' | '[') || c == LIKE_ESCAPE {
+            out.push(LIKE_ESCAPE);
+        }
+        out.push(c);
+    }
+    out
+}
+
+/// Compile `filter` for `driver`, quoting column names with `quote`.
+pub fn compile_filter(
+    driver: &str,
+    quote: &str,
+    filter: &FilterNode,
+) -> Result<BuiltFilter, String> {
+    let mut builder = Builder {
+        driver,
+        quote,
+        params: HashMap::new(),
+    };
+    let sql = builder.node(filter)?;
+    Ok(BuiltFilter {
+        sql,
+        params: builder.params,
+    })
+}
+
+/// Default number of distinct values listed by [`get_column_values`].
+const DEFAULT_COLUMN_VALUES_LIMIT: u32 = 500;
+const COLUMN_VALUES_LIMIT_CAP: u32 = 10_000;
+
+#[derive(Debug, Clone, Copy, Default, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum ColumnValuesOrder {
+    /// By value, ascending.
+    #[default]
+    Value,
+    /// Most frequent first.
+    Count,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct ColumnValueCount {
+    pub value: Value,
+    pub count: u64,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct ColumnValues {
+    /// Distinct values with their row counts. NULL, when present, is the
+    /// first entry, whatever the order.
+    pub values: Vec<ColumnValueCount>,
+    /// More distinct values exist than were listed.
+    pub truncated: bool,
+}
+
+/// The two statements behind [`get_column_values`]: the non-NULL distinct
+/// values with their counts, optionally narrowed to those containing
+/// `search`, and the number of NULLs.
+pub fn build_column_values_queries(
+    driver: &str,
+    quote: &str,
+    schema: Option<&str>,
+    table: &str,
+    column: &str,
+    search: Option<&str>,
+    order: ColumnValuesOrder,
+) -> Result<(BuiltFilter, String), String> {
+    let col = quote_identifier(quote, column);
+    let from = crate::bulk_edit::table_ref(quote, schema, table);
+    let mut conditions = vec![FilterNode::Condition {
+        column: column.to_string(),
+        operator: FilterOperator::IsNotNull,
+        value: None,
+        values: Vec::new(),
+        case_insensitive: false,
+    }];
+    if let Some(search) = search.filter(|s| !s.is_empty()) {
+        conditions.push(FilterNode::Condition {
+            column: column.to_string(),
+            operator: FilterOperator::Contains,
+            value: Some(Value::String(search.to_string())),
+            values: Vec::new(),
+            case_insensitive: true,
+        });
+    }
+    let filter = compile_filter(
+        driver,
+        quote,
+        &FilterNode::Group {
+            combinator: Combinator::And,
+            negate: false,
+            children: conditions,
+        },
+    )?;
+    let order_by = match order {
+        ColumnValuesOrder::Value => col.clone(),
+        ColumnValuesOrder::Count => format!("COUNT(*) DESC, {}", col),
+    };
+    let values = BuiltFilter {
+        sql: format!(
+            "SELECT {} AS value, COUNT(*) AS count FROM {} WHERE {} GROUP BY {} ORDER BY {}",
+            col, from, filter.sql, col, order_by
+        ),
+        params: filter.params,
+    };
+    let nulls = format!("SELECT COUNT(*) - COUNT({}) AS nulls FROM {}", col, from);
+    Ok((values, nulls))
+}
+
+/// A COUNT read back from a driver: a number, or a string for drivers
+/// that return 64-bit integers as text.
+fn count_of(value: &Value) -> u64 {
+    match value {
+        Value::Number(n) => n.as_u64().unwrap_or(0),
+        Value::String(s) => s.parse().unwrap_or(0),
+        _ => 0,
+    }
+}
+
+/// Turn the two query results into the listed values. `rows` holds up to
+/// `limit + 1` rows so truncation can be detected.
+pub fn collect_column_values(rows: Vec<Vec<Value>>, nulls: u64, limit: usize) -> ColumnValues {
+    let truncated = rows.len() > limit;
+    let mut values = Vec::with_capacity(rows.len().min(limit) + 1);
+    if nulls > 0 {
+        values.push(ColumnValueCount {
+            value: Value::Null,
+            count: nulls,
+        });
+    }
+    values.extend(rows.into_iter().take(limit).map(|row| {
+        let mut row = row.into_iter();
+        let value = row.next().unwrap_or(Value::Null);
+        let count = row.next().map_or(0, |c| count_of(&c));
+        ColumnValueCount { value, count }
+    }));
+    ColumnValues { values, truncated }
+}
+
+/// Distinct values of `column` with their counts, for the Excel-style
+/// checklist of the grid's column filter.
+#[tauri::command]
+pub async fn get_column_values<R: Runtime>(
+    app: AppHandle<R>,
+    connection_id: String,
+    table: String,
+    column: String,
+    schema: Option<String>,
+    search: Option<String>,
+    order: Option<ColumnValuesOrder>,
+    limit: Option<u32>,
+) -> Result<ColumnValues, String> {
+    let saved_conn = crate::commands::find_connection_by_id(&app, &connection_id)?;
+    let expanded_params =
+        crate::commands::expand_ssh_connection_params(&app, &saved_conn.params).await?;
+    let params =
+        crate::commands::resolve_connection_params_with_id(&expanded_params, &connection_id)?;
+    let drv = crate::commands::driver_for(&saved_conn.params.driver).await?;
+
+    let limit = limit
+        .unwrap_or(DEFAULT_COLUMN_VALUES_LIMIT)
+        .clamp(1, COLUMN_VALUES_LIMIT_CAP);
+    let (values_query, nulls_query) = build_column_values_queries(
+        &saved_conn.params.driver,
+        &drv.manifest().capabilities.identifier_quote,
+        schema.as_deref(),
+        &table,
+        &column,
+        search.as_deref(),
+        order.unwrap_or_default(),
+    )?;
+
+    let values = drv
+        .execute_query_with_params(
+            &params,
+            &values_query.sql,
+            &values_query.params,
+            Some(limit + 1),
+            1,
+            schema.as_deref(),
+        )
+        .await?;
+    // NULL never contains the search text.
+    let nulls = if search.as_deref().is_some_and(|s| !s.is_empty()) {
+        0
+    } else {
+        drv.execute_query(&params, &nulls_query, Some(1), 1, schema.as_deref())
+            .await?
+            .rows
+            .first()
+            .and_then(|row| row.first())
+            .map_or(0, count_of)
+    };
+
+    Ok(collect_column_values(values.rows, nulls, limit as usize))
+}
+
+/// Compile a filter tree for the driver of `connection_id`.
+#[tauri::command]
+pub async fn build_filter<R: Runtime>(
+    app: AppHandle<R>,
+    connection_id: String,
+    filter: FilterNode,
+) -> Result<BuiltFilter, String> {
+    let saved_conn = crate::commands::find_connection_by_id(&app, &connection_id)?;
+    let drv = crate::commands::driver_for(&saved_conn.params.driver).await?;
+    // SQLite parses `REGEXP` but only runs it when an extension provides
+    // the `regexp()` function.
+    if saved_conn.params.driver == "sqlite" && uses_regex(&filter) {
+        let expanded_params =
+            crate::commands::expand_ssh_connection_params(&app, &saved_conn.params).await?;
+        let params =
+            crate::commands::resolve_connection_params_with_id(&expanded_params, &connection_id)?;
+        drv.execute_query(&params, "SELECT 'a' REGEXP 'a'", Some(1), 1, None)
+            .await
+            .map_err(|e| {
+                format!(
+                    "Regular expression filters need a REGEXP extension loaded in SQLite: {}",
+                    e
+                )
+            })?;
+    }
+    compile_filter(
+        &saved_conn.params.driver,
+        &drv.manifest().capabilities.identifier_quote,
+        &filter,
+    )
+}
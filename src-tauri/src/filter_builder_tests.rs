@@ -0,0 +1,305 @@
+#[cfg(test)]
+mod tests {
+    use crate::filter_builder::{
+        build_column_values_queries, collect_column_values, compile_filter, escape_like,
+        uses_regex, ColumnValuesOrder, Combinator, FilterNode, FilterOperator,
+    };
+    use serde_json::{json, Value};
+
+    fn cond(column: &str, operator: FilterOperator, value: Option<Value>) -> FilterNode {
+        FilterNode::Condition {
+            column: column.into(),
+            operator,
+            value,
+            values: Vec::new(),
+            case_insensitive: false,
+        }
+    }
+
+    fn list(column: &str, operator: FilterOperator, values: Vec<Value>) -> FilterNode {
+        FilterNode::Condition {
+            column: column.into(),
+            operator,
+            value: None,
+            values,
+            case_insensitive: false,
+        }
+    }
+
+    fn group(combinator: Combinator, children: Vec<FilterNode>) -> FilterNode {
+        FilterNode::Group {
+            combinator,
+            negate: false,
+            children,
+        }
+    }
+
+    #[test]
+    fn nested_groups_bind_every_value() {
+        let filter = group(
+            Combinator::And,
+            vec![
+                cond("age", FilterOperator::Gte, Some(json!(18))),
+                group(
+                    Combinator::Or,
+                    vec![
+                        cond("status", FilterOperator::Eq, Some(json!("active"))),
+                        cond("deleted_at", FilterOperator::IsNull, None),
+                    ],
+                ),
+            ],
+        );
+        let built = compile_filter("postgres", "\"", &filter).unwrap();
+        assert_eq!(
+            built.sql,
+            "(\"age\" >= :p0 AND (\"status\" = :p1 OR \"deleted_at\" IS NULL))"
+        );
+        assert_eq!(built.params["p0"], json!(18));
+        assert_eq!(built.params["p1"], json!("active"));
+    }
+
+    #[test]
+    fn in_lists_and_between() {
+        let built = compile_filter(
+            "mysql",
+            "`",
+            &group(
+                Combinator::And,
+                vec![
+                    list("id", FilterOperator::In, vec![json!(1), json!(2)]),
+                    list("id", FilterOperator::NotIn, vec![]),
+                    list("price", FilterOperator::Between, vec![json!(5), json!(10)]),
+                ],
+            ),
+        )
+        .unwrap();
+        assert_eq!(
+            built.sql,
+            "(`id` IN (:p0, :p1) AND 1 = 1 AND `price` BETWEEN :p2 AND :p3)"
+        );
+        assert_eq!(built.params.len(), 4);
+
+        let empty_in = list("id", FilterOperator::In, vec![]);
+        assert_eq!(
+            compile_filter("mysql", "`", &empty_in).unwrap().sql,
+            "1 = 0"
+        );
+
+        let bad_between = list("id", FilterOperator::Between, vec![json!(1)]);
+        assert!(compile_filter("mysql", "`", &bad_between).is_err());
+    }
+
+    #[test]
+    fn text_operators_escape_wildcards() {
+        let built = compile_filter(
+            "sqlite",
+            "\"",
+            &cond("name", FilterOperator::Contains, Some(json!("50%_off!"))),
+        )
+        .unwrap();
+        assert_eq!(built.sql, "\"name\" LIKE :p0 ESCAPE '!'");
+        assert_eq!(built.params["p0"], json!("%50!%!_off!!%"));
+
+        let raw = compile_filter(
+            "sqlite",
+            "\"",
+            &cond("name", FilterOperator::Like, Some(json!("A%"))),
+        )
+        .unwrap();
+        assert_eq!(raw.sql, "\"name\" LIKE :p0");
+        assert_eq!(raw.params["p0"], json!("A%"));
+    }
+
+    #[test]
+    fn case_insensitive_matching_per_dialect() {
+        let filter = FilterNode::Condition {
+            column: "email".into(),
+            operator: FilterOperator::StartsWith,
+            value: Some(json!("Bob")),
+            values: Vec::new(),
+            case_insensitive: true,
+        };
+        assert_eq!(
+            compile_filter("postgres", "\"", &filter).unwrap().sql,
+            "\"email\" ILIKE :p0 ESCAPE '!'"
+        );
+        assert_eq!(
+            compile_filter("mysql", "`", &filter).unwrap().sql,
+            "LOWER(`email`) LIKE LOWER(:p0) ESCAPE '!'"
+        );
+    }
+
+    #[test]
+    fn negated_and_empty_groups() {
+        let negated = FilterNode::Group {
+            combinator: Combinator::Or,
+            negate: true,
+            children: vec![cond("a", FilterOperator::Eq, Some(json!(1)))],
+        };
+        assert_eq!(
+            compile_filter("postgres", "\"", &negated).unwrap().sql,
+            "NOT (\"a\" = :p0)"
+        );
+        assert_eq!(
+            compile_filter("postgres", "\"", &group(Combinator::And, vec![]))
+                .unwrap()
+                .sql,
+            "(1 = 1)"
+        );
+    }
+
+    #[test]
+    fn quotes_column_names_and_requires_values() {
+        let built = compile_filter(
+            "postgres",
+            "\"",
+            &cond("a\" OR 1=1 --", FilterOperator::Eq, Some(json!(1))),
+        )
+        .unwrap();
+        assert_eq!(built.sql, "\"a\"\" OR 1=1 --\" = :p0");
+        assert!(compile_filter("postgres", "\"", &cond("a", FilterOperator::Eq, None)).is_err());
+    }
+
+    #[test]
+    fn deserializes_frontend_tree() {
+        let filter: FilterNode = serde_json::from_value(json!({
+            "type": "group",
+            "combinator": "or",
+            "children": [
+                { "type": "condition", "column": "id", "operator": "notIn", "values": [1] },
+                { "type": "condition", "column": "name", "operator": "contains",
+                  "value": "x", "caseInsensitive": true }
+            ]
+        }))
+        .unwrap();
+        let built = compile_filter("postgres", "\"", &filter).unwrap();
+        assert_eq!(
+            built.sql,
+            "(\"id\" NOT IN (:p0) OR \"name\" ILIKE :p1 ESCAPE '!')"
+        );
+    }
+
+    #[test]
+    fn escape_like_handles_sql_server_brackets() {
+        assert_eq!(escape_like("[a]"), "![a]");
+    }
+
+    fn regex(operator: FilterOperator, case_insensitive: bool) -> FilterNode {
+        FilterNode::Condition {
+            column: "code".into(),
+            operator,
+            value: Some(json!("^A[0-9]+$")),
+            values: Vec::new(),
+            case_insensitive,
+        }
+    }
+
+    #[test]
+    fn regex_operators_per_dialect() {
+        let pg = |op, ci| {
+            compile_filter("postgres", "\"", &regex(op, ci))
+                .unwrap()
+                .sql
+        };
+        assert_eq!(pg(FilterOperator::Regex, false), "\"code\" ~ :p0");
+        assert_eq!(pg(FilterOperator::Regex, true), "\"code\" ~* :p0");
+        assert_eq!(pg(FilterOperator::NotRegex, false), "\"code\" !~ :p0");
+        assert_eq!(pg(FilterOperator::NotRegex, true), "\"code\" !~* :p0");
+
+        let built = compile_filter("mysql", "`", &regex(FilterOperator::NotRegex, true)).unwrap();
+        assert_eq!(built.sql, "`code` NOT REGEXP :p0");
+        assert_eq!(built.params["p0"], json!("(?i)^A[0-9]+$"));
+
+        let built = compile_filter("sqlite", "\"", &regex(FilterOperator::Regex, false)).unwrap();
+        assert_eq!(built.sql, "\"code\" REGEXP :p0");
+        assert_eq!(built.params["p0"], json!("^A[0-9]+$"));
+    }
+
+    #[test]
+    fn regex_is_a_capability_error_elsewhere() {
+        let err = compile_filter("mssql", "\"", &regex(FilterOperator::Regex, false)).unwrap_err();
+        assert!(err.contains("not supported by the mssql driver"), "{}", err);
+    }
+
+    #[test]
+    fn detects_nested_regex_conditions() {
+        let plain = group(
+            Combinator::And,
+            vec![cond("a", FilterOperator::Eq, Some(json!(1)))],
+        );
+        assert!(!uses_regex(&plain));
+        let nested = group(
+            Combinator::Or,
+            vec![
+                plain,
+                group(Combinator::And, vec![regex(FilterOperator::Regex, false)]),
+            ],
+        );
+        assert!(uses_regex(&nested));
+    }
+
+    #[test]
+    fn column_values_queries_group_non_null_values() {
+        let (values, nulls) = build_column_values_queries(
+            "postgres",
+            "\"",
+            Some("public"),
+            "users",
+            "city",
+            None,
+            ColumnValuesOrder::Value,
+        )
+        .unwrap();
+        assert_eq!(
+            values.sql,
+            "SELECT \"city\" AS value, COUNT(*) AS count FROM \"public\".\"users\" \
+             WHERE (\"city\" IS NOT NULL) GROUP BY \"city\" ORDER BY \"city\""
+        );
+        assert!(values.params.is_empty());
+        assert_eq!(
+            nulls,
+            "SELECT COUNT(*) - COUNT(\"city\") AS nulls FROM \"public\".\"users\""
+        );
+    }
+
+    #[test]
+    fn column_values_search_is_bound() {
+        let (values, _) = build_column_values_queries(
+            "mysql",
+            "`",
+            None,
+            "users",
+            "city",
+            Some("new_"),
+            ColumnValuesOrder::Count,
+        )
+        .unwrap();
+        assert_eq!(
+            values.sql,
+            "SELECT `city` AS value, COUNT(*) AS count FROM `users` \
+             WHERE (`city` IS NOT NULL AND LOWER(`city`) LIKE LOWER(:p0) ESCAPE '!') \
+             GROUP BY `city` ORDER BY COUNT(*) DESC, `city`"
+        );
+        assert_eq!(values.params["p0"], json!("%new!_%"));
+    }
+
+    #[test]
+    fn column_values_put_null_first_and_detect_truncation() {
+        let rows = vec![
+            vec![json!("Berlin"), json!(3)],
+            vec![json!("Paris"), json!("12")],
+            vec![json!("Rome"), json!(1)],
+        ];
+        let listed = collect_column_values(rows.clone(), 4, 2);
+        assert!(listed.truncated);
+        assert_eq!(listed.values.len(), 3);
+        assert_eq!(listed.values[0].value, Value::Null);
+        assert_eq!(listed.values[0].count, 4);
+        assert_eq!(listed.values[2].value, json!("Paris"));
+        assert_eq!(listed.values[2].count, 12);
+
+        let all = collect_column_values(rows, 0, 3);
+        assert!(!all.truncated);
+        assert_eq!(all.values[0].value, json!("Berlin"));
+    }
+}
@@ -9,6 +9,11 @@ use tokio::sync::{oneshot, Mutex, RwLock};
 static ACTIVE_CONNECTIONS: Lazy<Arc<RwLock<HashSet<String>>>> =
     Lazy::new(|| Arc::new(RwLock::new(HashSet::new())));
 
+/// Connections whose pool was evicted after failed pings, with the number
+/// of reconnect attempts made so far.
+static LOST_CONNECTIONS: Lazy<RwLock<HashMap<String, u32>>> =
+    Lazy::new(|| RwLock::new(HashMap::new()));
+
 static PING_STOP_TX: Lazy<Mutex<Option<oneshot::Sender<()>>>> = Lazy::new(|| Mutex::new(None));
 
 /// Default ping interval in seconds.
@@ -20,16 +25,33 @@ const FAILURE_THRESHOLD: u32 = 2;
 /// Per-ping timeout.
 const PING_TIMEOUT: Duration = Duration::from_secs(5);
 
+/// Reconnect attempts, one per ping interval, before a lost connection is
+/// given up and closed.
+const MAX_RECONNECT_ATTEMPTS: u32 = 10;
+
+/// Emitted when a connection's pool is evicted and reconnecting starts.
+pub const CONNECTION_LOST_EVENT: &str = "connection-lost";
+/// Emitted when a lost connection answers again.
+pub const CONNECTION_RESTORED_EVENT: &str = "connection-restored";
+/// Emitted when reconnecting is given up; the connection is closed.
+pub const CONNECTION_FAILED_EVENT: &str = "connection-health-failed";
+
 /// Register a connection ID as active (will be pinged).
 pub async fn register_connection(connection_id: String) {
     log::info!("Health check: registering connection {}", connection_id);
     ACTIVE_CONNECTIONS.write().await.insert(connection_id);
 }
 
-/// Unregister a connection ID (stop pinging it).
+/// Unregister a connection ID (stop pinging it, or reconnecting it).
 pub async fn unregister_connection(connection_id: &str) {
     log::info!("Health check: unregistering connection {}", connection_id);
     ACTIVE_CONNECTIONS.write().await.remove(connection_id);
+    LOST_CONNECTIONS.write().await.remove(connection_id);
+}
+
+/// IDs of the connections currently registered as open.
+pub async fn active_connection_ids() -> Vec<String> {
+    ACTIVE_CONNECTIONS.read().await.iter().cloned().collect()
 }
 
 /// Start the periodic ping loop. If a loop is already running it is stopped first.
@@ -85,9 +107,11 @@ pub async fn restart_ping_loop(app: tauri::AppHandle, interval_secs: u64) {
     start_ping_loop(app, interval_secs).await;
 }
 
-/// Ping every registered connection. On failure beyond the threshold,
-/// close the pool and emit an event to the frontend.
+/// Ping every registered connection. On failure beyond the threshold, the
+/// pool is evicted and the connection reconnected on the following ticks.
 async fn ping_all_connections(app: &tauri::AppHandle, failure_counts: &mut HashMap<String, u32>) {
+    reconnect_lost_connections(app).await;
+
     // Snapshot the active set so we don't hold the lock during I/O.
     let active: Vec<String> = ACTIVE_CONNECTIONS.read().await.iter().cloned().collect();
 
@@ -100,7 +124,7 @@ async fn ping_all_connections(app: &tauri::AppHandle, failure_counts: &mut HashM
         let app = app.clone();
         let conn_id = conn_id.clone();
         async move {
-            let result = ping_single_connection(&app, &conn_id).await;
+            let result = ping_single_connection(&app, &conn_id, false).await;
             (conn_id, result)
         }
     }))
@@ -125,11 +149,11 @@ async fn ping_all_connections(app: &tauri::AppHandle, failure_counts: &mut HashM
 
                 if *count >= FAILURE_THRESHOLD {
                     log::error!(
-                        "Health check: connection {} exceeded failure threshold, disconnecting",
+                        "Health check: connection {} exceeded failure threshold, reconnecting",
                         conn_id
                     );
                     failure_counts.remove(&conn_id);
-                    handle_connection_failure(app, &conn_id, &err).await;
+                    handle_connection_lost(app, &conn_id, &err).await;
                 }
             }
         }
@@ -137,7 +161,12 @@ async fn ping_all_connections(app: &tauri::AppHandle, failure_counts: &mut HashM
 }
 
 /// Ping a single connection by resolving its params and calling driver.ping().
-async fn ping_single_connection(app: &tauri::AppHandle, connection_id: &str) -> Result<(), String> {
+/// With `reconnect`, a missing pool (and SSH tunnel) is opened for it.
+async fn ping_single_connection(
+    app: &tauri::AppHandle,
+    connection_id: &str,
+    reconnect: bool,
+) -> Result<(), String> {
     let saved_conn = crate::commands::find_connection_by_id(app, connection_id)?;
 
     let expanded_params =
@@ -146,8 +175,14 @@ async fn ping_single_connection(app: &tauri::AppHandle, connection_id: &str) ->
         crate::commands::resolve_connection_params_with_id(&expanded_params, connection_id)?;
 
     // Check if pool exists before attempting ping (avoid creating new pools).
-    let is_builtin = matches!(params.driver.as_str(), "mysql" | "postgres" | "sqlite");
-    if is_builtin && !crate::pool_manager::has_pool(&params, Some(connection_id)).await {
+    let is_builtin = matches!(
+        params.driver.as_str(),
+        "mysql" | "postgres" | "sqlite" | "mssql"
+    );
+    if is_builtin
+        && !reconnect
+        && !crate::pool_manager::has_pool(&params, Some(connection_id)).await
+    {
         return Err("No active connection pool".into());
     }
 
@@ -157,37 +192,165 @@ async fn ping_single_connection(app: &tauri::AppHandle, connection_id: &str) ->
 
     tokio::time::timeout(PING_TIMEOUT, driver.ping(&params))
         .await
-        .map_err(|_| "Ping timed out".to_string())?
+        .map_err(|_| crate::i18n::t("errors.pingTimedOut"))?
 }
 
-/// Close the pool, unregister the connection, and emit a failure event.
-async fn handle_connection_failure(app: &tauri::AppHandle, connection_id: &str, error: &str) {
-    // Unregister first to prevent further pings.
-    unregister_connection(connection_id).await;
+/// Close the pool of a connection and the SSH tunnel under it, so the next
+/// use opens both anew. Returns false, leaving both open, while the
+/// connection has an open transaction: closing the pool would roll it back.
+/// The tunnel stays open while another open connection goes through it.
+async fn evict_connection(app: &tauri::AppHandle, connection_id: &str) -> bool {
+    if crate::pool_manager::has_transaction(connection_id).await {
+        log::warn!(
+            "Health check: connection {} has an open transaction, not closing its pool",
+            connection_id
+        );
+        return false;
+    }
+    let Ok(saved_conn) = crate::commands::find_connection_by_id(app, connection_id) else {
+        return true;
+    };
+    let Ok(mut params) =
+        crate::commands::expand_ssh_connection_params(app, &saved_conn.params).await
+    else {
+        return true;
+    };
+    // Saved connections are pooled by id, not by endpoint: no need to
+    // resolve the tunnel (and open one) to find the pool.
+    params.connection_id = Some(connection_id.to_string());
+    crate::pool_manager::close_pool_with_id(&params, Some(connection_id)).await;
+    if let Some(key) = crate::commands::ssh_tunnel_key(&params) {
+        if !tunnel_in_use(app, connection_id, &key).await {
+            crate::commands::close_ssh_tunnel(&params);
+        }
+    }
+    true
+}
 
-    // Close the pool (best-effort — if params can't be resolved the pool stays orphaned
-    // but will be reclaimed on next connect or app shutdown).
-    if let Ok(saved_conn) = crate::commands::find_connection_by_id(app, connection_id) {
-        if let Ok(expanded) =
+/// Whether an open or reconnecting connection other than `connection_id`
+/// goes through the SSH tunnel `key`.
+async fn tunnel_in_use(app: &tauri::AppHandle, connection_id: &str, key: &str) -> bool {
+    let mut others: HashSet<String> = ACTIVE_CONNECTIONS.read().await.clone();
+    others.extend(LOST_CONNECTIONS.read().await.keys().cloned());
+    others.remove(connection_id);
+    for other in others {
+        let Ok(saved_conn) = crate::commands::find_connection_by_id(app, &other) else {
+            continue;
+        };
+        let Ok(params) =
             crate::commands::expand_ssh_connection_params(app, &saved_conn.params).await
-        {
-            if let Ok(params) =
-                crate::commands::resolve_connection_params_with_id(&expanded, connection_id)
-            {
-                crate::pool_manager::close_pool_with_id(&params, Some(connection_id)).await;
-            }
+        else {
+            continue;
+        };
+        if crate::commands::ssh_tunnel_key(&params).as_deref() == Some(key) {
+            return true;
         }
     }
+    false
+}
 
-    // Notify frontend.
-    let payload = serde_json::json!({
-        "connectionId": connection_id,
-        "error": error,
-    });
-    if let Err(e) = app.emit("connection-health-failed", payload) {
-        log::error!(
-            "Health check: failed to emit connection-health-failed event: {}",
-            e
-        );
+fn emit_health_event(app: &tauri::AppHandle, event: &str, payload: serde_json::Value) {
+    if let Err(e) = app.emit(event, payload) {
+        log::error!("Health check: failed to emit {} event: {}", event, e);
+    }
+}
+
+/// Evict the pool of a connection that stopped answering and start
+/// reconnecting it.
+async fn handle_connection_lost(app: &tauri::AppHandle, connection_id: &str, error: &str) {
+    // Keep pinging it instead; the transaction fails on its next statement
+    // if the server is really gone.
+    if !evict_connection(app, connection_id).await {
+        return;
     }
+    ACTIVE_CONNECTIONS.write().await.remove(connection_id);
+    LOST_CONNECTIONS
+        .write()
+        .await
+        .insert(connection_id.to_string(), 0);
+    emit_health_event(
+        app,
+        CONNECTION_LOST_EVENT,
+        serde_json::json!({
+            "connectionId": connection_id,
+            "error": error,
+        }),
+    );
+}
+
+/// Try once to reconnect every lost connection. A connection that answers
+/// is pinged again as usual; one that keeps failing is closed after
+/// `MAX_RECONNECT_ATTEMPTS`.
+async fn reconnect_lost_connections(app: &tauri::AppHandle) {
+    let lost: Vec<String> = LOST_CONNECTIONS.read().await.keys().cloned().collect();
+    for conn_id in lost {
+        let result = ping_single_connection(app, &conn_id, true).await;
+        let attempts = {
+            let mut lost = LOST_CONNECTIONS.write().await;
+            let attempts = lost.get_mut(&conn_id).map(|attempts| {
+                *attempts += 1;
+                *attempts
+            });
+            if attempts.is_some_and(|a| result.is_ok() || a >= MAX_RECONNECT_ATTEMPTS) {
+                lost.remove(&conn_id);
+            }
+            attempts
+        };
+        let Some(attempts) = attempts else {
+            // Disconnected by the user meanwhile: close what the attempt
+            // opened, unless the connection was opened again since.
+            if !ACTIVE_CONNECTIONS.read().await.contains(&conn_id) {
+                evict_connection(app, &conn_id).await;
+            }
+            continue;
+        };
+        match result {
+            Ok(()) => {
+                log::info!(
+                    "Health check: connection {} restored after {} attempt(s)",
+                    conn_id,
+                    attempts
+                );
+                ACTIVE_CONNECTIONS.write().await.insert(conn_id.clone());
+                emit_health_event(
+                    app,
+                    CONNECTION_RESTORED_EVENT,
+                    serde_json::json!({
+                        "connectionId": conn_id,
+                        "attempts": attempts,
+                    }),
+                );
+            }
+            Err(err) => {
+                log::warn!(
+                    "Health check: reconnect {}/{} of {} failed: {}",
+                    attempts,
+                    MAX_RECONNECT_ATTEMPTS,
+                    conn_id,
+                    err
+                );
+                // A pool or tunnel opened by the attempt may be half-dead.
+                evict_connection(app, &conn_id).await;
+                if attempts >= MAX_RECONNECT_ATTEMPTS {
+                    handle_connection_failure(app, &conn_id, &err);
+                }
+            }
+        }
+    }
+}
+
+/// Give up on a lost connection and tell the frontend to close it.
+fn handle_connection_failure(app: &tauri::AppHandle, connection_id: &str, error: &str) {
+    log::error!(
+        "Health check: giving up on connection {}, disconnecting",
+        connection_id
+    );
+    emit_health_event(
+        app,
+        CONNECTION_FAILED_EVENT,
+        serde_json::json!({
+            "connectionId": connection_id,
+            "error": error,
+        }),
+    );
 }
@@ -0,0 +1,158 @@
+//! Key-based catalog for user-facing strings produced by the Rust layer.
+//!
+//! Catalogs live in `src-tauri/locales/<lang>.json`, use the same nested
+//! layout and `{{name}}` interpolation as the frontend i18next files, and are
+//! embedded at compile time. The active language follows the `language`
+//! setting in `config.json` (`"auto"` resolves from the OS locale); missing
+//! keys fall back to English, then to the key itself.
+//!
+//! Errors raised by Tabularis itself go through the catalog; text coming from
+//! a database driver, the OS or a library is embedded as reported.
+
+use once_cell::sync::Lazy;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::RwLock;
+
+pub const DEFAULT_LANGUAGE: &str = "en";
+
+static CATALOGS: Lazy<HashMap<&'static str, Value>> = Lazy::new(|| {
+    let sources: [(&str, &str); 7] = [
+        ("en", include_str!("../locales/en.json")),
+        ("it", include_str!("../locales/it.json")),
+        ("es", include_str!("../locales/es.json")),
+        ("zh", include_str!("../locales/zh.json")),
+        ("fr", include_str!("../locales/fr.json")),
+        ("de", include_str!("../locales/de.json")),
+        ("ja", include_str!("../locales/ja.json")),
+    ];
+    sources
+        .into_iter()
+        .filter_map(|(lang, src)| match serde_json::from_str(src) {
+            Ok(catalog) => Some((lang, catalog)),
+            Err(e) => {
+                log::error!("Invalid backend locale catalog {}: {}", lang, e);
+                None
+            }
+        })
+        .collect()
+});
+
+static CURRENT_LANGUAGE: Lazy<RwLock<String>> = Lazy::new(|| RwLock::new(resolve_language(None)));
+
+/// Languages with an embedded catalog.
+pub fn supported_languages() -> Vec<&'static str> {
+    let mut langs: Vec<&'static str> = CATALOGS.keys().copied().collect();
+    langs.sort_unstable();
+    langs
+}
+
+/// Normalize a locale tag (`it_IT.UTF-8`, `pt-BR`, `zh-Hans`) to its language code.
+pub fn normalize_locale(locale: &str) -> String {
+    locale
+        .split(['_', '-', '.', '@'])
+        .next()
+        .unwrap_or("")
+        .trim()
+        .to_ascii_lowercase()
+}
+
+fn system_locale() -> Option<String> {
+    ["LC_ALL", "LC_MESSAGES", "LANG"]
+        .iter()
+        .filter_map(|var| std::env::var(var).ok())
+        .find(|v| !v.is_empty() && v != "C" && v != "POSIX")
+}
+
+/// Resolve the `language` setting to a supported language code.
+/// `None` and `"auto"` use the OS locale; unsupported languages fall back to English.
+pub fn resolve_language(setting: Option<&str>) -> String {
+    let requested = match setting {
+        Some(lang) if !lang.is_empty() && !lang.eq_ignore_ascii_case("auto") => {
+            normalize_locale(lang)
+        }
+        _ => system_locale()
+            .map(|l| normalize_locale(&l))
+            .unwrap_or_default(),
+    };
+
+    if CATALOGS.contains_key(requested.as_str()) {
+        requested
+    } else {
+        DEFAULT_LANGUAGE.to_string()
+    }
+}
+
+/// Update the active language. Called whenever the config is (re)loaded.
+pub fn set_language(setting: Option<&str>) {
+    let resolved = resolve_language(setting);
+    if let Ok(mut current) = CURRENT_LANGUAGE.write() {
+        *current = resolved;
+    }
+}
+
+pub fn current_language() -> String {
+    CURRENT_LANGUAGE
+        .read()
+        .map(|l| l.clone())
+        .unwrap_or_else(|_| DEFAULT_LANGUAGE.to_string())
+}
+
+/// Every dotted key of one language's catalog.
+pub fn catalog_keys(lang: &str) -> Vec<String> {
+    fn collect(prefix: &str, node: &Value, keys: &mut Vec<String>) {
+        match node {
+            Value::Object(map) => {
+                for (name, child) in map {
+                    let key = if prefix.is_empty() {
+                        name.clone()
+                    } else {
+                        format!("{}.{}", prefix, name)
+                    };
+                    collect(&key, child, keys);
+                }
+            }
+            _ => keys.push(prefix.to_string()),
+        }
+    }
+    let mut keys = Vec::new();
+    if let Some(catalog) = CATALOGS.get(lang) {
+        collect("", catalog, &mut keys);
+    }
+    keys
+}
+
+/// Look up a dotted key (e.g. `errors.queryCancelled`) in one language's catalog.
+pub fn lookup(lang: &str, key: &str) -> Option<String> {
+    let mut node = CATALOGS.get(lang)?;
+    for part in key.split('.') {
+        node = node.get(part)?;
+    }
+    node.as_str().map(str::to_string)
+}
+
+/// Replace `{{name}}` placeholders with the supplied values.
+pub fn interpolate(template: &str, args: &[(&str, &str)]) -> String {
+    args.iter()
+        .fold(template.to_string(), |acc, (name, value)| {
+            acc.replace(&format!("{{{{{}}}}}", name), value)
+        })
+}
+
+/// Translate `key` into `lang`, falling back to English and then to the key.
+pub fn translate(lang: &str, key: &str, args: &[(&str, &str)]) -> String {
+    let template = lookup(lang, key)
+        .or_else(|| lookup(DEFAULT_LANGUAGE, key))
+        .unwrap_or_else(|| key.to_string());
+    interpolate(&template, args)
+}
+
+/// Translate `key` into the active language.
+pub fn t(key: &str) -> String {
+    translate(&current_language(), key, &[])
+}
+
+/// Translate `key` into the active language, filling `{{name}}` placeholders.
+pub fn t_with(key: &str, args: &[(&str, &str)]) -> String {
+    translate(&current_language(), key, args)
+}
@@ -0,0 +1,88 @@
+#[cfg(test)]
+mod tests {
+    use crate::i18n::{
+        catalog_keys, interpolate, lookup, normalize_locale, resolve_language, supported_languages,
+        translate, DEFAULT_LANGUAGE,
+    };
+
+    #[test]
+    fn normalizes_posix_and_bcp47_locales() {
+        assert_eq!(normalize_locale("it_IT.UTF-8"), "it");
+        assert_eq!(normalize_locale("pt-BR"), "pt");
+        assert_eq!(normalize_locale("zh-Hans"), "zh");
+        assert_eq!(normalize_locale("DE"), "de");
+    }
+
+    #[test]
+    fn explicit_language_setting_wins() {
+        assert_eq!(resolve_language(Some("it")), "it");
+        assert_eq!(resolve_language(Some("fr-FR")), "fr");
+    }
+
+    #[test]
+    fn unsupported_language_falls_back_to_english() {
+        assert_eq!(resolve_language(Some("xx")), DEFAULT_LANGUAGE);
+    }
+
+    #[test]
+    fn every_catalog_has_the_english_keys() {
+        let en = catalog_keys(DEFAULT_LANGUAGE);
+        assert!(en.contains(&"errors.connectionNotFound".to_string()));
+        for lang in supported_languages() {
+            for key in &en {
+                assert!(lookup(lang, key).is_some(), "{} is missing {}", lang, key);
+            }
+        }
+    }
+
+    #[test]
+    fn every_translation_keeps_the_english_placeholders() {
+        fn placeholders(template: &str) -> Vec<&str> {
+            let mut names: Vec<&str> = template
+                .split("{{")
+                .skip(1)
+                .filter_map(|part| part.split("}}").next())
+                .collect();
+            names.sort_unstable();
+            names
+        }
+        for key in catalog_keys(DEFAULT_LANGUAGE) {
+            let en = lookup(DEFAULT_LANGUAGE, &key).unwrap();
+            for lang in supported_languages() {
+                let translated = lookup(lang, &key).unwrap_or_default();
+                assert_eq!(
+                    placeholders(&translated),
+                    placeholders(&en),
+                    "{} {}",
+                    lang,
+                    key
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn translates_and_interpolates() {
+        assert_eq!(
+            translate("it", "errors.unsupportedDriver", &[("driver", "oracle")]),
+            "Driver non supportato: oracle"
+        );
+        assert_eq!(
+            translate("en", "errors.unsupportedDriver", &[("driver", "oracle")]),
+            "Unsupported driver: oracle"
+        );
+    }
+
+    #[test]
+    fn missing_key_falls_back_to_key() {
+        assert_eq!(
+            translate("it", "errors.doesNotExist", &[]),
+            "errors.doesNotExist"
+        );
+    }
+
+    #[test]
+    fn interpolate_leaves_unknown_placeholders() {
+        assert_eq!(interpolate("{{a}} and {{b}}", &[("a", "1")]), "1 and {{b}}");
+    }
+}
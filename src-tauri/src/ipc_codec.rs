@@ -0,0 +1,245 @@
+//! Compact encodings for large query results sent over the Tauri bridge.
+//!
+//! `execute_query` answers with JSON, which the webview must parse in full
+//! before the grid renders. `execute_query_packed` runs the same query but
+//! answers with raw bytes: one tag byte, then the body. The frontend lists
+//! the encodings it can decode (see `get_ipc_encodings`) and the most
+//! compact one it accepts is used:
+//!
+//! - `J`: JSON, when nothing else is accepted.
+//! - `M`: MessagePack with named fields, the same shape as the JSON.
+//! - `Z`: gzip-compressed MessagePack, only for bodies above the
+//!   compression threshold; browsers inflate it with
+//!   `DecompressionStream("gzip")`.
+//!
+//! `execute_query_arrow` answers with an untagged Arrow IPC stream instead
+//! (see [`crate::arrow_ipc`]). Drivers with the `arrow` capability produce
+//! it themselves; other results are converted on the host.
+
+use std::io::Write;
+use std::sync::Arc;
+
+use flate2::write::GzEncoder;
+use serde::Serialize;
+use tauri::ipc::Response;
+use tauri::{AppHandle, Runtime, State, WebviewWindow};
+
+use crate::commands::{
+    driver_for, expand_ssh_connection_params, find_connection_by_id, register_abort_handle,
+    resolve_connection_params_with_id, sanitize_user_query, unregister_abort_handle,
+    QueryCancellationState,
+};
+
+pub const ENCODING_JSON: &str = "json";
+pub const ENCODING_MSGPACK: &str = "msgpack";
+pub const ENCODING_GZIP: &str = "gzip";
+
+/// MessagePack bodies smaller than this are sent uncompressed: gzip costs
+/// more time than it saves on small results.
+pub const DEFAULT_COMPRESS_THRESHOLD: usize = 256 * 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Encoding {
+    Json,
+    MessagePack,
+    CompressedMessagePack,
+}
+
+impl Encoding {
+    /// The byte that precedes the body.
+    pub fn tag(self) -> u8 {
+        match self {
+            Self::Json => b'J',
+            Self::MessagePack => b'M',
+            Self::CompressedMessagePack => b'Z',
+        }
+    }
+}
+
+/// Encode `value` with the most compact encoding in `accept`, prefixed with
+/// its tag byte.
+pub fn encode<T: Serialize>(
+    value: &T,
+    accept: &[String],
+    compress_threshold: usize,
+) -> Result<(Encoding, Vec<u8>), String> {
+    let accepts = |encoding: &str| accept.iter().any(|a| a.eq_ignore_ascii_case(encoding));
+
+    let (encoding, body) = if accepts(ENCODING_MSGPACK) {
+        let packed = rmp_serde::to_vec_named(value)
+            .map_err(|e| format!("Failed to encode result as MessagePack: {}", e))?;
+        if accepts(ENCODING_GZIP) && packed.len() >= compress_threshold {
+            (Encoding::CompressedMessagePack, gzip(&packed)?)
+        } else {
+            (Encoding::MessagePack, packed)
+        }
+    } else {
+        let json = serde_json::to_vec(value)
+            .map_err(|e| format!("Failed to encode result as JSON: {}", e))?;
+        (Encoding::Json, json)
+    };
+
+    let mut framed = Vec::with_capacity(body.len() + 1);
+    framed.push(encoding.tag());
+    framed.extend_from_slice(&body);
+    Ok((encoding, framed))
+}
+
+fn gzip(bytes: &[u8]) -> Result<Vec<u8>, String> {
+    let mut encoder = GzEncoder::new(
+        Vec::with_capacity(bytes.len() / 4),
+        flate2::Compression::fast(),
+    );
+    encoder
+        .write_all(bytes)
+        .and_then(|_| encoder.finish())
+        .map_err(|e| format!("Failed to compress result: {}", e))
+}
+
+/// The encodings `execute_query_packed` can produce.
+#[tauri::command]
+pub fn get_ipc_encodings() -> Vec<&'static str> {
+    vec![ENCODING_JSON, ENCODING_MSGPACK, ENCODING_GZIP]
+}
+
+/// `execute_query`, answered as a tagged binary body in one of the
+/// `accept`ed encodings.
+#[tauri::command]
+pub async fn execute_query_packed<R: Runtime>(
+    app: AppHandle<R>,
+    window: WebviewWindow<R>,
+    state: State<'_, QueryCancellationState>,
+    connection_id: String,
+    query: String,
+    limit: Option<u32>,
+    page: Option<u32>,
+    schema: Option<String>,
+    table: Option<String>,
+    allow_costly: Option<bool>,
+    use_primary: Option<bool>,
+    saved_query_id: Option<String>,
+    accept: Vec<String>,
+    compress_threshold: Option<usize>,
+) -> Result<Response, String> {
+    let result = crate::commands::execute_query(
+        app,
+        window,
+        state,
+        connection_id,
+        query,
+        limit,
+        page,
+        schema,
+        table,
+        allow_costly,
+        use_primary,
+        saved_query_id,
+    )
+    .await?;
+
+    let threshold = compress_threshold.unwrap_or(DEFAULT_COMPRESS_THRESHOLD);
+    let (encoding, bytes) =
+        tokio::task::spawn_blocking(move || encode(&result, &accept, threshold))
+            .await
+            .map_err(|e| format!("Failed to encode result: {}", e))??;
+    log::debug!(
+        "Packed query result as {:?}: {} bytes",
+        encoding,
+        bytes.len()
+    );
+    Ok(Response::new(bytes))
+}
+
+/// `execute_query`, answered as an Arrow IPC stream.
+///
+/// Ad-hoc queries on drivers with the `arrow` capability are handed to the
+/// driver as-is, so its native Arrow output reaches the frontend without a
+/// round trip through JSON rows. Table browsing and connections with result
+/// scripts need the rows as JSON to rewrite them, so they run through
+/// `execute_query` and the result is converted afterwards.
+#[tauri::command]
+pub async fn execute_query_arrow<R: Runtime>(
+    app: AppHandle<R>,
+    window: WebviewWindow<R>,
+    state: State<'_, QueryCancellationState>,
+    connection_id: String,
+    query: String,
+    limit: Option<u32>,
+    page: Option<u32>,
+    schema: Option<String>,
+    table: Option<String>,
+    allow_costly: Option<bool>,
+    use_primary: Option<bool>,
+    saved_query_id: Option<String>,
+) -> Result<Response, String> {
+    let saved_conn = find_connection_by_id(&app, &connection_id)?;
+    let drv = driver_for(&saved_conn.params.driver).await?;
+    let native = drv.manifest().capabilities.arrow
+        && table.is_none()
+        && crate::result_scripts::load_result_scripts(&connection_id)?.is_empty();
+
+    if !native {
+        let result = crate::commands::execute_query(
+            app,
+            window,
+            state,
+            connection_id,
+            query,
+            limit,
+            page,
+            schema,
+            table,
+            allow_costly,
+            use_primary,
+            saved_query_id,
+        )
+        .await?;
+        let bytes = tokio::task::spawn_blocking(move || crate::arrow_ipc::result_to_ipc(&result))
+            .await
+            .map_err(|e| format!("Failed to encode result: {}", e))??;
+        return Ok(Response::new(bytes));
+    }
+
+    let sanitized_query = sanitize_user_query(&query);
+    crate::read_only::ensure_query_allowed(&saved_conn.params, &sanitized_query)?;
+    if !allow_costly.unwrap_or(false) {
+        crate::cost_guard::enforce(&app, &connection_id, &sanitized_query, schema.as_deref())
+            .await?;
+    }
+    let use_primary = use_primary.unwrap_or(false)
+        || crate::session_objects::has_objects(&connection_id)
+        || crate::pool_manager::has_transaction(&connection_id).await;
+    let (routed_params, pool_id) = crate::pool_manager::route_query(
+        &saved_conn,
+        &connection_id,
+        &sanitized_query,
+        use_primary,
+    );
+    let expanded_params = expand_ssh_connection_params(&app, &routed_params).await?;
+    let params = resolve_connection_params_with_id(&expanded_params, &pool_id)?;
+
+    let task = tokio::spawn(async move {
+        drv.execute_query_arrow(
+            &params,
+            &sanitized_query,
+            limit,
+            page.unwrap_or(1),
+            schema.as_deref(),
+        )
+        .await
+    });
+    let abort_handle = Arc::new(task.abort_handle());
+    let cancel_key = crate::window_state::cancellation_key(window.label(), &connection_id);
+    register_abort_handle(&state.handles, cancel_key.clone(), abort_handle.clone());
+    let result = task.await;
+    unregister_abort_handle(&state.handles, &cancel_key, &abort_handle);
+
+    match result {
+        Ok(Ok(bytes)) => {
+            log::debug!("Native Arrow result: {} bytes", bytes.len());
+            Ok(Response::new(bytes))
+        }
+        Ok(Err(e)) => Err(e),
+        Err(_) => Err(crate::i18n::t("errors.queryCancelled")),
+    }
+}
@@ -0,0 +1,74 @@
+#[cfg(test)]
+mod tests {
+    use crate::ipc_codec::{encode, Encoding, ENCODING_GZIP, ENCODING_MSGPACK};
+    use flate2::read::GzDecoder;
+    use serde::{Deserialize, Serialize};
+    use serde_json::{json, Value};
+    use std::io::Read;
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    struct Page {
+        columns: Vec<String>,
+        rows: Vec<Vec<Value>>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        note: Option<String>,
+    }
+
+    fn page(rows: usize) -> Page {
+        Page {
+            columns: vec!["id".to_string(), "name".to_string()],
+            rows: (0..rows)
+                .map(|i| vec![json!(i), json!(format!("row {}", i))])
+                .collect(),
+            note: None,
+        }
+    }
+
+    fn accept(encodings: &[&str]) -> Vec<String> {
+        encodings.iter().map(|e| e.to_string()).collect()
+    }
+
+    #[test]
+    fn falls_back_to_json() {
+        let (encoding, bytes) = encode(&page(2), &[], 0).unwrap();
+        assert_eq!(encoding, Encoding::Json);
+        assert_eq!(bytes[0], b'J');
+        let decoded: Page = serde_json::from_slice(&bytes[1..]).unwrap();
+        assert_eq!(decoded, page(2));
+    }
+
+    #[test]
+    fn packs_as_messagepack_below_the_threshold() {
+        let (encoding, bytes) = encode(
+            &page(2),
+            &accept(&[ENCODING_MSGPACK, ENCODING_GZIP]),
+            1 << 20,
+        )
+        .unwrap();
+        assert_eq!(encoding, Encoding::MessagePack);
+        assert_eq!(bytes[0], b'M');
+        let decoded: Page = rmp_serde::from_slice(&bytes[1..]).unwrap();
+        assert_eq!(decoded, page(2));
+    }
+
+    #[test]
+    fn compresses_large_bodies_when_gzip_is_accepted() {
+        let (encoding, bytes) = encode(&page(2000), &accept(&["MsgPack", "GZIP"]), 1024).unwrap();
+        assert_eq!(encoding, Encoding::CompressedMessagePack);
+        assert_eq!(bytes[0], b'Z');
+
+        let mut packed = Vec::new();
+        GzDecoder::new(&bytes[1..])
+            .read_to_end(&mut packed)
+            .unwrap();
+        assert!(bytes.len() < packed.len());
+        let decoded: Page = rmp_serde::from_slice(&packed).unwrap();
+        assert_eq!(decoded, page(2000));
+    }
+
+    #[test]
+    fn does_not_compress_without_gzip() {
+        let (encoding, _) = encode(&page(2000), &accept(&[ENCODING_MSGPACK]), 0).unwrap();
+        assert_eq!(encoding, Encoding::MessagePack);
+    }
+}
@@ -10,55 +10,224 @@ pub mod ai_commands;
 pub mod ai_notebook_export;
 #[cfg(test)]
 pub mod ai_notebook_export_tests;
+pub mod ai_stream;
+#[cfg(test)]
+pub mod ai_stream_tests;
+pub mod ai_tools;
+#[cfg(test)]
+pub mod ai_tools_tests;
+pub mod arrow_ipc;
+#[cfg(test)]
+pub mod arrow_ipc_tests;
+pub mod automation;
+#[cfg(test)]
+pub mod automation_tests;
+pub mod bookmarks;
+#[cfg(test)]
+pub mod bookmarks_tests;
+pub mod broadcast;
+#[cfg(test)]
+pub mod broadcast_tests;
+pub mod bulk_edit;
+#[cfg(test)]
+pub mod bulk_edit_tests;
 pub mod cli;
 pub mod clipboard_import;
+pub mod codegen;
+#[cfg(test)]
+pub mod codegen_tests;
 pub mod commands;
 pub mod config;
+pub mod config_store;
+#[cfg(test)]
+pub mod config_store_tests;
+pub mod connection_templates;
+#[cfg(test)]
+pub mod connection_templates_tests;
+pub mod cost_guard;
+#[cfg(test)]
+pub mod cost_guard_tests;
+pub mod crash_reports;
+#[cfg(test)]
+pub mod crash_reports_tests;
 pub mod credential_cache;
+pub mod custom_actions;
+#[cfg(test)]
+pub mod custom_actions_tests;
+pub mod data_rules;
+pub mod data_search;
+#[cfg(test)]
+pub mod data_search_tests;
+pub mod database_overview;
+#[cfg(test)]
+pub mod database_overview_tests;
+pub mod deep_link;
+#[cfg(test)]
+pub mod deep_link_tests;
+pub mod diagnostics;
+#[cfg(test)]
+pub mod diagnostics_tests;
 pub mod dump_commands; // Added
 #[cfg(test)]
 pub mod dump_commands_tests;
 pub mod dump_utils;
+pub mod embeddings;
+#[cfg(test)]
+pub mod embeddings_tests;
+pub mod er_snapshot;
+#[cfg(test)]
+pub mod er_snapshot_tests;
 pub mod explain_import;
 #[cfg(test)]
 pub mod explain_import_tests;
 pub mod export;
 #[cfg(test)]
 pub mod export_import_tests;
+pub mod filter_builder;
+#[cfg(test)]
+pub mod filter_builder_tests;
 pub mod health_check;
 pub mod heartbeat;
 #[cfg(test)]
 pub mod heartbeat_tests;
+pub mod i18n;
+#[cfg(test)]
+pub mod i18n_tests;
+pub mod ipc_codec;
+#[cfg(test)]
+pub mod ipc_codec_tests;
 pub mod json_viewer;
 pub mod keychain_utils;
+pub mod lineage;
+#[cfg(test)]
+pub mod lineage_tests;
+pub mod locks;
+#[cfg(test)]
+pub mod locks_tests;
 pub mod log_commands;
 pub mod logger;
 pub mod mcp;
 pub mod models;
 #[cfg(test)]
 pub mod models_tests;
+pub mod naming;
+#[cfg(test)]
+pub mod naming_tests;
 pub mod notebooks;
+pub mod page_sizing;
+#[cfg(test)]
+pub mod page_sizing_tests;
 pub mod paths; // Added
+#[cfg(test)]
+pub mod paths_tests;
 pub mod persistence;
+pub mod plan_history;
+#[cfg(test)]
+pub mod plan_history_tests;
 pub mod plugins;
 pub mod pool_manager;
 #[cfg(test)]
 pub mod pool_manager_tests;
 pub mod preferences;
+#[cfg(test)]
+pub mod preferences_tests;
+pub mod prewarm;
+#[cfg(test)]
+pub mod prewarm_tests;
+pub mod privileges;
+#[cfg(test)]
+pub mod privileges_tests;
+pub mod profiles;
+#[cfg(test)]
+pub mod profiles_tests;
+pub mod projects;
+#[cfg(test)]
+pub mod projects_tests;
+pub mod provenance;
+#[cfg(test)]
+pub mod provenance_tests;
 pub mod query_history;
 #[cfg(test)]
 pub mod query_history_tests;
+pub mod query_jobs;
+#[cfg(test)]
+pub mod query_jobs_tests;
+pub mod query_stream;
+#[cfg(test)]
+pub mod query_stream_tests;
+pub mod read_only;
+#[cfg(test)]
+pub mod read_only_tests;
+pub mod relationship_graph;
+#[cfg(test)]
+pub mod relationship_graph_tests;
+pub mod result_annotations;
+#[cfg(test)]
+pub mod result_annotations_tests;
+pub mod result_diff;
+#[cfg(test)]
+pub mod result_diff_tests;
+pub mod result_scripts;
+#[cfg(test)]
+pub mod result_scripts_tests;
+pub mod sample_data;
+#[cfg(test)]
+pub mod sample_data_tests;
+pub mod sandbox;
+#[cfg(test)]
+pub mod sandbox_tests;
 pub mod saved_queries;
 #[cfg(test)]
 pub mod saved_queries_tests;
+pub mod schema_diff;
+#[cfg(test)]
+pub mod schema_diff_tests;
+pub mod schema_search;
+#[cfg(test)]
+pub mod schema_search_tests;
+pub mod schema_watch;
+#[cfg(test)]
+pub mod schema_watch_tests;
+pub mod secrets;
+#[cfg(test)]
+pub mod secrets_tests;
+pub mod server_info;
+#[cfg(test)]
+pub mod server_info_tests;
+pub mod session_objects;
+#[cfg(test)]
+pub mod session_objects_tests;
+pub mod shutdown;
+#[cfg(test)]
+pub mod shutdown_tests;
+pub mod sql_files;
+#[cfg(test)]
+pub mod sql_files_tests;
+pub mod sql_schema;
+#[cfg(test)]
+pub mod sql_schema_tests;
 pub mod ssh_tunnel;
+pub mod table_activity;
+#[cfg(test)]
+pub mod table_activity_tests;
+pub mod table_copy;
+#[cfg(test)]
+pub mod table_copy_tests;
 pub mod task_manager;
+pub mod telemetry;
+#[cfg(test)]
+pub mod telemetry_tests;
 pub mod theme_commands;
 pub mod theme_models;
 pub mod updater;
+pub mod window_state;
+#[cfg(test)]
+pub mod window_state_tests;
 pub mod drivers {
     pub mod common;
+    pub mod demo;
     pub mod driver_trait;
+    pub mod mssql;
     pub mod mysql;
     pub mod postgres;
     pub mod registry;
@@ -86,6 +255,11 @@ fn is_debug_mode() -> bool {
     DEBUG_MODE.load(Ordering::Relaxed)
 }
 
+#[tauri::command]
+fn get_portable_data_dir() -> Option<String> {
+    paths::get_portable_root().map(|p| p.to_string_lossy().to_string())
+}
+
 #[tauri::command]
 fn open_devtools(window: tauri::WebviewWindow) {
     window.open_devtools();
@@ -115,12 +289,24 @@ pub fn run() {
 
     let args = cli::parse();
 
+    // Portable mode and profile must be settled before anything resolves a
+    // data directory.
+    let portable_root = paths::init_portable_mode(args.portable);
+    let profile = paths::init_profile(args.profile.as_deref());
+
+    if let Some(request) = args.command {
+        std::process::exit(automation::run_client(request));
+    }
+
     if args.mcp {
         let rt = tokio::runtime::Runtime::new().expect("Failed to create Tokio runtime");
         rt.block_on(mcp::run_mcp_server());
         return;
     }
 
+    // Save a redacted report on panic; the UI offers to send it on next launch.
+    crash_reports::install_panic_hook();
+
     // Configure log level based on debug flag
     // Default to Info level so users can see application logs
     let log_level = log::LevelFilter::Info;
@@ -144,11 +330,33 @@ pub fn run() {
     } else {
         log::info!("Debug mode disabled - standard logging active");
     }
+    if let Some(root) = &portable_root {
+        log::info!("Portable mode enabled - app data stored in {:?}", root);
+    }
+    if profile != paths::DEFAULT_PROFILE {
+        log::info!("Using profile '{}'", profile);
+    }
 
     // Install default drivers for sqlx::Any
     sqlx::any::install_default_drivers();
 
-    tauri::Builder::default()
+    // All TLS goes through rustls; pick its crypto provider before any connection.
+    pool_manager::ensure_rustls_crypto_provider();
+
+    let mut builder = tauri::Builder::default();
+    // Only the default profile is single-instance: a second launch is
+    // forwarded to the running one. The plugin keys its lock on the bundle
+    // identifier, which every profile shares, so registering it for another
+    // profile would hand that profile's launches to the default instance.
+    // Other profiles therefore run one process per launch.
+    if profile == paths::DEFAULT_PROFILE {
+        builder = builder.plugin(tauri_plugin_single_instance::init(|app, argv, _cwd| {
+            deep_link::on_second_instance(app, argv);
+        }));
+    }
+
+    builder
+        .plugin(tauri_plugin_deep_link::init())
         .plugin(tauri_plugin_clipboard_manager::init())
         .plugin(tauri_plugin_opener::init())
         .plugin(tauri_plugin_dialog::init())
@@ -157,13 +365,44 @@ pub fn run() {
         .manage(commands::QueryCancellationState::default())
         .manage(export::ExportCancellationState::default())
         .manage(dump_commands::DumpCancellationState::default())
+        .manage(data_search::DataSearchCancellationState::default())
+        .manage(table_copy::TableCopyCancellationState::default())
+        .manage(ai_stream::AiStreamState::default())
         .manage(log_buffer)
         .manage(std::sync::Arc::new(
             credential_cache::CredentialCache::default(),
         ))
         .manage(explain_import::PendingExplainFile::default())
         .manage(json_viewer::JsonViewerStore::default())
+        .manage(updater::StagedUpdate::default())
+        .manage(sql_files::SqlFileWatchState::default())
+        .manage(window_state::WindowRegistry::default())
         .setup(move |app| {
+            // Local socket for `tabularis <subcommand>` automation requests.
+            automation::start_server(app.handle().clone());
+
+            // `tabularis://` links, whether they start the app or arrive later.
+            {
+                use tauri_plugin_deep_link::DeepLinkExt;
+                #[cfg(any(target_os = "linux", all(debug_assertions, windows)))]
+                if let Err(e) = app.deep_link().register_all() {
+                    log::warn!("Failed to register tabularis:// links: {}", e);
+                }
+                let handle = app.handle().clone();
+                app.deep_link()
+                    .on_open_url(move |event| deep_link::open_urls(&handle, event.urls()));
+                if let Ok(Some(urls)) = app.deep_link().get_current() {
+                    deep_link::open_urls(app.handle(), urls);
+                }
+            }
+
+            // Move passwords still saved in plain text into the secret store.
+            if let Err(e) = paths::app_config_dir(app.handle())
+                .and_then(|dir| secrets::migrate_plaintext_secrets(&dir))
+            {
+                log::warn!("Failed to migrate plaintext credentials: {}", e);
+            }
+
             // Read persisted config to know which external plugins are enabled.
             // `None` means no preference has been saved yet → load all installed plugins.
             let active_ext_drivers =
@@ -174,6 +413,8 @@ pub fn run() {
                 drivers::registry::register_driver(drivers::mysql::MysqlDriver::new()).await;
                 drivers::registry::register_driver(drivers::postgres::PostgresDriver::new()).await;
                 drivers::registry::register_driver(drivers::sqlite::SqliteDriver::new()).await;
+                drivers::registry::register_driver(drivers::mssql::MssqlDriver::new()).await;
+                drivers::registry::register_driver(drivers::demo::DemoDriver::new()).await;
 
                 // Load only enabled external plugins (or all if no preference saved).
                 crate::plugins::manager::load_plugins(&app.handle(), active_ext_drivers.as_deref())
@@ -192,6 +433,32 @@ pub fn run() {
                 });
             }
 
+            // Start background schema change detection.
+            {
+                let config = crate::config::load_config_internal(&app.handle());
+                let interval = config
+                    .schema_watch_interval
+                    .unwrap_or(schema_watch::DEFAULT_SCHEMA_WATCH_INTERVAL);
+                let handle = app.handle().clone();
+                tauri::async_runtime::spawn(async move {
+                    schema_watch::start_schema_watch_loop(handle, interval as u64).await;
+                });
+            }
+
+            // Open "connect on startup" connections in the background.
+            prewarm::spawn(app.handle().clone());
+
+            // Keep idle pooled connections alive.
+            {
+                let config = crate::config::load_config_internal(&app.handle());
+                let interval = config
+                    .pool_keepalive_interval
+                    .unwrap_or(pool_manager::DEFAULT_KEEPALIVE_INTERVAL);
+                tauri::async_runtime::spawn(async move {
+                    pool_manager::start_keepalive_loop(interval as u64).await;
+                });
+            }
+
             // Watch for pending MCP approval requests and run periodic cleanup.
             ai_approval_watcher::spawn(app.handle().clone());
 
@@ -213,9 +480,7 @@ pub fn run() {
             // meant to be a dedicated plan viewer, not a full app launch.
             if let Some(path) = args.explain.clone() {
                 log::info!("CLI --explain received: {path}");
-                if let Err(e) =
-                    explain_import::spawn_visual_explain_window(app, Some(path))
-                {
+                if let Err(e) = explain_import::spawn_visual_explain_window(app, Some(path)) {
                     log::error!("Failed to open Visual Explain window: {e}");
                 }
                 // Close the default main window only AFTER visual-explain is
@@ -230,6 +495,7 @@ pub fn run() {
         })
         .invoke_handler(tauri::generate_handler![
             is_debug_mode,
+            get_portable_data_dir,
             open_devtools,
             close_devtools,
             commands::get_registered_drivers,
@@ -245,6 +511,7 @@ pub fn run() {
             commands::get_connections,
             commands::get_connection_by_id,
             commands::disconnect_connection,
+            commands::get_pool_stats,
             commands::register_active_connection,
             commands::get_data_types,
             commands::map_inferred_column_types,
@@ -273,7 +540,10 @@ pub fn run() {
             commands::get_indexes,
             commands::delete_record,
             commands::update_record,
+            bulk_edit::bulk_update_records,
+            bulk_edit::bulk_delete_records,
             commands::insert_record,
+            commands::insert_records_batch,
             commands::save_blob_to_file,
             commands::fetch_blob_as_data_url,
             commands::load_blob_from_file,
@@ -282,16 +552,25 @@ pub fn run() {
             commands::get_file_stats,
             commands::read_file_as_data_url,
             commands::execute_query,
+            commands::execute_query_with_params,
+            query_stream::execute_query_stream,
+            ipc_codec::execute_query_arrow,
+            ipc_codec::execute_query_packed,
+            ipc_codec::get_ipc_encodings,
             commands::execute_query_batch,
             commands::get_server_now,
             commands::explain_query_plan,
             commands::count_query,
             commands::cancel_query,
+            commands::begin_transaction,
+            commands::commit_transaction,
+            commands::rollback_transaction,
             commands::get_views,
             commands::get_view_definition,
             commands::create_view,
             commands::alter_view,
             commands::drop_view,
+            commands::create_table_from_query,
             commands::get_view_columns,
             commands::set_window_title,
             commands::open_er_diagram_window,
@@ -299,7 +578,14 @@ pub fn run() {
             explain_import::get_pending_explain_file,
             explain_import::open_visual_explain_window,
             export::export_query_to_file,
+            export::export_table_parquet,
+            export::export_query_xlsx,
+            export::export_query_json,
             export::cancel_export,
+            export::list_export_profiles,
+            export::save_export_profile,
+            export::delete_export_profile,
+            export::sql_dump::export_sql_dump,
             saved_queries::get_saved_queries,
             saved_queries::save_query,
             saved_queries::update_saved_query,
@@ -308,6 +594,17 @@ pub fn run() {
             query_history::add_query_history_entry,
             query_history::delete_query_history_entry,
             query_history::clear_query_history,
+            bookmarks::get_row_bookmarks,
+            bookmarks::add_row_bookmark,
+            bookmarks::update_row_bookmark,
+            bookmarks::delete_row_bookmark,
+            bookmarks::open_row_bookmark,
+            // Related data
+            lineage::trace_row,
+            lineage::lookup_referenced_row,
+            relationship_graph::get_relationship_graph,
+            filter_builder::build_filter,
+            filter_builder::get_column_values,
             // Config
             config::get_schema_preference,
             config::set_schema_preference,
@@ -322,6 +619,7 @@ pub fn run() {
             config::delete_ai_key,
             config::check_ai_key,
             config::check_ai_key_status,
+            secrets::rotate_secrets_key,
             config::get_system_prompt,
             config::save_system_prompt,
             config::reset_system_prompt,
@@ -345,10 +643,28 @@ pub fn run() {
             ai::generate_tab_rename,
             ai::suggest_table_name,
             ai::get_ai_models,
+            ai_stream::stream_ai_query,
+            ai_stream::stream_explain_ai_query,
+            ai_stream::cancel_ai_stream,
+            ai_tools::generate_ai_query_with_tools,
+            embeddings::build_schema_embeddings,
+            embeddings::find_relevant_schema,
+            embeddings::get_schema_embeddings_info,
+            embeddings::delete_schema_embeddings,
             // Clipboard Import
             clipboard_import::execute_clipboard_import,
             commands::get_schema_snapshot,
+            er_snapshot::stream_schema_snapshot,
+            sql_schema::parse_sql_schema,
+            sql_schema::apply_sql_schema,
+            schema_diff::diff_schemas,
+            // Code generation
+            codegen::list_code_templates,
+            codegen::save_code_template,
+            codegen::delete_code_template,
+            codegen::generate_code,
             // DDL generation
+            naming::check_naming,
             commands::get_create_table_sql,
             commands::get_add_column_sql,
             commands::get_alter_column_sql,
@@ -390,11 +706,106 @@ pub fn run() {
             dump_commands::cancel_dump,
             dump_commands::import_database,
             dump_commands::cancel_import,
+            sample_data::list_sample_databases,
+            sample_data::install_sample_database,
             dump_commands::cancel_dump,
             // Updater
             updater::check_for_updates,
             updater::download_and_install_update,
+            updater::get_release_notes,
+            updater::download_update_artifact,
+            updater::stage_update,
+            updater::get_staged_update,
             updater::get_installation_source,
+            // Telemetry
+            telemetry::record_telemetry_event,
+            telemetry::get_telemetry_queue,
+            telemetry::purge_telemetry_queue,
+            telemetry::flush_telemetry,
+            // Crash reports
+            crash_reports::list_crash_reports,
+            crash_reports::get_crash_report,
+            crash_reports::delete_crash_report,
+            crash_reports::send_crash_report,
+            // Diagnostics
+            diagnostics::diagnose_environment,
+            // Sandboxes
+            sandbox::list_sandboxes,
+            sandbox::create_sandbox,
+            sandbox::diff_sandbox,
+            sandbox::promote_sandbox,
+            sandbox::drop_sandbox,
+            // Result annotations
+            result_annotations::save_result_snapshot,
+            result_annotations::list_result_snapshots,
+            result_annotations::get_result_snapshot,
+            result_annotations::delete_result_snapshot,
+            result_annotations::annotate_result,
+            result_annotations::delete_result_annotation,
+            result_annotations::export_annotated_report,
+            // Session objects
+            session_objects::list_session_objects,
+            session_objects::drop_session_object,
+            // Schema watch
+            schema_watch::check_schema_changes,
+            // SQL files
+            sql_files::open_sql_file,
+            sql_files::save_sql_file,
+            sql_files::check_sql_file,
+            sql_files::watch_sql_file,
+            sql_files::unwatch_sql_file,
+            // Locks
+            locks::get_lock_waits,
+            locks::get_blocking_tree,
+            locks::kill_session,
+            // Data search
+            data_search::search_data,
+            data_search::cancel_data_search,
+            // Table copy
+            table_copy::copy_table,
+            table_copy::cancel_table_copy,
+            // Schema search
+            schema_search::search_schema_objects,
+            // Database overview
+            database_overview::get_database_overview,
+            // Server
+            server_info::get_server_info,
+            server_info::get_server_status,
+            server_info::get_server_variables,
+            // Users and privileges
+            privileges::get_users,
+            privileges::get_user_grants,
+            privileges::grant_privilege,
+            privileges::revoke_privilege,
+            // Connection templates
+            connection_templates::list_connection_templates,
+            connection_templates::save_connection_template,
+            connection_templates::delete_connection_template,
+            connection_templates::expand_connection_template,
+            // Broadcast
+            broadcast::broadcast_query,
+            result_diff::diff_query_results,
+            // Cost guard
+            cost_guard::estimate_query_cost,
+            // Plan history
+            plan_history::get_plan_history,
+            plan_history::get_plan_snapshots,
+            plan_history::diff_plan_snapshots,
+            plan_history::clear_plan_history,
+            // Table activity
+            table_activity::get_table_activity,
+            table_activity::clear_table_activity,
+            // Projects
+            projects::open_project,
+            projects::init_project,
+            projects::save_project,
+            projects::run_project,
+            // Profiles
+            profiles::list_profiles,
+            profiles::get_active_profile_name,
+            profiles::create_profile,
+            profiles::delete_profile,
+            profiles::switch_profile,
             // Logs
             log_commands::get_logs,
             log_commands::clear_logs,
@@ -408,6 +819,26 @@ pub fn run() {
             preferences::load_editor_preferences,
             preferences::delete_editor_preferences,
             preferences::list_all_preferences,
+            preferences::list_named_filters,
+            preferences::save_named_filter,
+            preferences::delete_named_filter,
+            preferences::apply_named_filter,
+            preferences::export_named_filters,
+            preferences::import_named_filters,
+            preferences::get_column_layout,
+            preferences::save_column_layout,
+            preferences::delete_column_layout,
+            preferences::get_computed_columns,
+            preferences::save_computed_columns,
+            data_rules::get_data_rules,
+            data_rules::save_data_rules,
+            data_rules::validate_data_rule_expression,
+            result_scripts::get_result_scripts,
+            result_scripts::save_result_scripts,
+            result_scripts::preview_result_script,
+            custom_actions::get_custom_actions,
+            custom_actions::save_custom_actions,
+            custom_actions::run_custom_action,
             // Notebooks
             notebooks::create_notebook,
             notebooks::save_notebook,
@@ -434,8 +865,31 @@ pub fn run() {
             task_manager::get_tabularis_children,
             task_manager::kill_plugin_process,
             task_manager::restart_plugin_process,
+            task_manager::get_running_queries,
+            task_manager::cancel_running_query,
             task_manager::open_task_manager_window,
+            deep_link::take_pending_deep_links,
+            window_state::open_main_window,
+            window_state::get_window_context,
+            window_state::set_window_active_connection,
+            window_state::set_window_preference,
         ])
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+        .build(tauri::generate_context!())
+        .expect("error while running tauri application")
+        .run(|app, event| match event {
+            tauri::RunEvent::ExitRequested { code, api, .. } => {
+                shutdown::on_exit_requested(app, code, &api);
+            }
+            tauri::RunEvent::WindowEvent {
+                label,
+                event: tauri::WindowEvent::Destroyed,
+                ..
+            } => {
+                window_state::on_window_destroyed(app, &label);
+            }
+            tauri::RunEvent::Exit => {
+                updater::install_staged_update(app);
+            }
+            _ => {}
+        });
 }
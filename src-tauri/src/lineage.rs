@@ -0,0 +1,466 @@
+//! Related-data explorer: the rows reachable from one row through foreign
+//! keys.
+//!
+//! Starting from a row, `trace_row` follows every foreign key of its table
+//! (to the parent row) and every foreign key pointing at it (to child rows),
+//! breadth-first, up to `max_depth` hops. A row reached twice is one node
+//! with several edges, so cycles (self-references, mutual references) end
+//! the walk instead of looping.
+
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::future::Future;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use tauri::{AppHandle, Runtime};
+
+use crate::bookmarks::build_bookmark_filter;
+use crate::bulk_edit::{table_ref, BoundStatement};
+use crate::models::{ForeignKey, QueryResult};
+
+const DEFAULT_MAX_DEPTH: u32 = 2;
+const MAX_DEPTH_CAP: u32 = 5;
+const DEFAULT_ROWS_PER_RELATION: u32 = 10;
+const ROWS_PER_RELATION_CAP: u32 = 100;
+const DEFAULT_MAX_NODES: usize = 200;
+const MAX_NODES_CAP: usize = 1000;
+
+/// A foreign key, with the columns of a composite key grouped together.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Relation {
+    pub name: String,
+    pub table: String,
+    pub ref_table: String,
+    /// (column of `table`, referenced column of `ref_table`)
+    pub columns: Vec<(String, String)>,
+}
+
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TraceLimits {
+    /// Hops from the starting row. Default 2, at most 5.
+    pub max_depth: Option<u32>,
+    /// Rows fetched per foreign key and row. Default 10, at most 100.
+    pub rows_per_relation: Option<u32>,
+    /// Rows in the whole graph. Default 200, at most 1000.
+    pub max_nodes: Option<usize>,
+}
+
+impl TraceLimits {
+    fn resolve(self) -> (u32, u32, usize) {
+        (
+            self.max_depth
+                .unwrap_or(DEFAULT_MAX_DEPTH)
+                .min(MAX_DEPTH_CAP),
+            self.rows_per_relation
+                .unwrap_or(DEFAULT_ROWS_PER_RELATION)
+                .clamp(1, ROWS_PER_RELATION_CAP),
+            self.max_nodes
+                .unwrap_or(DEFAULT_MAX_NODES)
+                .clamp(1, MAX_NODES_CAP),
+        )
+    }
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct LineageNode {
+    pub id: usize,
+    pub table: String,
+    pub row: Map<String, Value>,
+    /// Hops from the starting row (0 for the starting row itself).
+    pub depth: u32,
+}
+
+/// A reference from the row `from` (in the referencing table) to the row
+/// `to` (in the referenced table), through foreign key `constraint`.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct LineageEdge {
+    pub from: usize,
+    pub to: usize,
+    pub constraint: String,
+    pub columns: Vec<String>,
+    pub ref_columns: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct LineageGraph {
+    pub nodes: Vec<LineageNode>,
+    pub edges: Vec<LineageEdge>,
+    /// Rows were left out because of `rows_per_relation` or `max_nodes`.
+    pub truncated: bool,
+}
+
+/// Group per-column foreign keys (as returned by the drivers) by table and
+/// constraint name. Keys whose referenced column is unknown are dropped.
+pub fn group_relations(foreign_keys: &HashMap<String, Vec<ForeignKey>>) -> Vec<Relation> {
+    let mut tables: Vec<&String> = foreign_keys.keys().collect();
+    tables.sort();
+    let mut relations: Vec<Relation> = Vec::new();
+    for table in tables {
+        for fk in &foreign_keys[table] {
+            if fk.ref_column.is_empty() || fk.ref_table.is_empty() {
+                continue;
+            }
+            let pair = (fk.column_name.clone(), fk.ref_column.clone());
+            match relations
+                .iter_mut()
+                .find(|r| &r.table == table && r.name == fk.name && r.ref_table == fk.ref_table)
+            {
+                Some(relation) => relation.columns.push(pair),
+                None => relations.push(Relation {
+                    name: fk.name.clone(),
+                    table: table.clone(),
+                    ref_table: fk.ref_table.clone(),
+                    columns: vec![pair],
+                }),
+            }
+        }
+    }
+    relations
+}
+
+/// Values of `columns` in `row`, or `None` when one of them is missing or
+/// NULL (a NULL foreign key references nothing).
+fn key_of(row: &Map<String, Value>, columns: &[&String]) -> Option<Map<String, Value>> {
+    columns
+        .iter()
+        .map(|c| match row.get(c.as_str()) {
+            None | Some(Value::Null) => None,
+            Some(v) => Some(((*c).clone(), v.clone())),
+        })
+        .collect()
+}
+
+/// Rename the keys of `values` from one side of a relation to the other.
+fn rename_key(values: Map<String, Value>, pairs: &[(&String, &String)]) -> Map<String, Value> {
+    pairs
+        .iter()
+        .filter_map(|(from, to)| {
+            values
+                .get(from.as_str())
+                .map(|v| ((*to).clone(), v.clone()))
+        })
+        .collect()
+}
+
+fn rows_of(result: QueryResult) -> Vec<Map<String, Value>> {
+    let columns = result.columns;
+    result
+        .rows
+        .into_iter()
+        .map(|row| columns.iter().cloned().zip(row).collect())
+        .collect()
+}
+
+/// Identity of a row for cycle detection: its table and every value.
+fn row_identity(table: &str, row: &Map<String, Value>) -> String {
+    format!("{}\u{0}{}", table, Value::Object(row.clone()))
+}
+
+struct Walk {
+    graph: LineageGraph,
+    seen: HashMap<String, usize>,
+    edges: HashSet<(usize, usize, String)>,
+    max_nodes: usize,
+}
+
+impl Walk {
+    /// The node of `row`, added when new. `None` when the graph is full.
+    fn node(&mut self, table: &str, row: Map<String, Value>, depth: u32) -> Option<(usize, bool)> {
+        let identity = row_identity(table, &row);
+        if let Some(&id) = self.seen.get(&identity) {
+            return Some((id, false));
+        }
+        if self.graph.nodes.len() >= self.max_nodes {
+            self.graph.truncated = true;
+            return None;
+        }
+        let id = self.graph.nodes.len();
+        self.graph.nodes.push(LineageNode {
+            id,
+            table: table.to_string(),
+            row,
+            depth,
+        });
+        self.seen.insert(identity, id);
+        Some((id, true))
+    }
+
+    fn edge(&mut self, from: usize, to: usize, relation: &Relation) {
+        if self.edges.insert((from, to, relation.name.clone())) {
+            self.graph.edges.push(LineageEdge {
+                from,
+                to,
+                constraint: relation.name.clone(),
+                columns: relation.columns.iter().map(|(c, _)| c.clone()).collect(),
+                ref_columns: relation.columns.iter().map(|(_, r)| r.clone()).collect(),
+            });
+        }
+    }
+}
+
+/// Walk the graph from the row of `table` matching `key`. `fetch(table,
+/// filter, limit)` returns up to `limit` rows of `table` whose columns equal
+/// `filter`.
+pub async fn trace<F, Fut>(
+    table: &str,
+    key: Map<String, Value>,
+    relations: &[Relation],
+    limits: TraceLimits,
+    mut fetch: F,
+) -> Result<LineageGraph, String>
+where
+    F: FnMut(String, Map<String, Value>, u32) -> Fut,
+    Fut: Future<Output = Result<QueryResult, String>>,
+{
+    let (max_depth, rows_per_relation, max_nodes) = limits.resolve();
+    let mut walk = Walk {
+        graph: LineageGraph {
+            nodes: Vec::new(),
+            edges: Vec::new(),
+            truncated: false,
+        },
+        seen: HashMap::new(),
+        edges: HashSet::new(),
+        max_nodes,
+    };
+
+    let start = rows_of(fetch(table.to_string(), key, 1).await?)
+        .into_iter()
+        .next()
+        .ok_or_else(|| format!("No row of {} matches the given key", table))?;
+    let mut queue = VecDeque::new();
+    if let Some((id, _)) = walk.node(table, start, 0) {
+        queue.push_back(id);
+    }
+
+    while let Some(id) = queue.pop_front() {
+        let depth = walk.graph.nodes[id].depth;
+        if depth >= max_depth {
+            continue;
+        }
+        let table = walk.graph.nodes[id].table.clone();
+        let row = walk.graph.nodes[id].row.clone();
+
+        for relation in relations {
+            // Outgoing: this row references a parent row. Incoming: child
+            // rows reference this row. A self-reference is both.
+            let directions = [
+                (relation.table == table, true),
+                (relation.ref_table == table, false),
+            ];
+            for (applies, parent_side) in directions {
+                if !applies {
+                    continue;
+                }
+                let pairs: Vec<(&String, &String)> = if parent_side {
+                    relation.columns.iter().map(|(c, r)| (c, r)).collect()
+                } else {
+                    relation.columns.iter().map(|(c, r)| (r, c)).collect()
+                };
+                let own: Vec<&String> = pairs.iter().map(|(own, _)| *own).collect();
+                let Some(values) = key_of(&row, &own) else {
+                    continue;
+                };
+                let other_table = if parent_side {
+                    &relation.ref_table
+                } else {
+                    &relation.table
+                };
+                let result = fetch(
+                    other_table.clone(),
+                    rename_key(values, &pairs),
+                    rows_per_relation,
+                )
+                .await?;
+                let rows = rows_of(result);
+                // Referenced columns are unique, so only child lookups can be cut short.
+                if !parent_side && rows.len() as u32 >= rows_per_relation {
+                    walk.graph.truncated = true;
+                }
+                for other in rows {
+                    let Some((other_id, new)) = walk.node(other_table, other, depth + 1) else {
+                        break;
+                    };
+                    if parent_side {
+                        walk.edge(id, other_id, relation);
+                    } else {
+                        walk.edge(other_id, id, relation);
+                    }
+                    if new {
+                        queue.push_back(other_id);
+                    }
+                }
+            }
+        }
+    }
+
+    Ok(walk.graph)
+}
+
+/// The row a foreign key value points at, for the grid's hover popover.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ReferencedRow {
+    pub constraint: String,
+    pub table: String,
+    /// Referenced columns, in foreign key order.
+    pub columns: Vec<String>,
+    /// `None` when no row matches (or the value is NULL).
+    pub row: Option<Map<String, Value>>,
+}
+
+/// The key identifying the row referenced through `column` of `relation`:
+/// `value` for `column`, and the other values of `row` for the remaining
+/// columns of a composite key. `None` when some value is NULL.
+pub fn referenced_key(
+    relation: &Relation,
+    column: &str,
+    value: &Value,
+    row: Option<&Map<String, Value>>,
+) -> Result<Option<Map<String, Value>>, String> {
+    let mut key = Map::new();
+    for (own, referenced) in &relation.columns {
+        let v = if own == column {
+            Some(value)
+        } else {
+            row.and_then(|r| r.get(own))
+        };
+        match v {
+            None => {
+                return Err(format!(
+                    "Foreign key {} spans several columns; the value of {} is needed too",
+                    relation.name, own
+                ))
+            }
+            Some(Value::Null) => return Ok(None),
+            Some(v) => {
+                key.insert(referenced.clone(), v.clone());
+            }
+        }
+    }
+    Ok(Some(key))
+}
+
+/// `SELECT * FROM table WHERE <columns = :pN>`.
+pub fn build_lookup_query(
+    quote: &str,
+    schema: Option<&str>,
+    table: &str,
+    filter: &Map<String, Value>,
+) -> Result<BoundStatement, String> {
+    let mut params = HashMap::new();
+    let filter = build_bookmark_filter(quote, filter, &mut params)?;
+    Ok(BoundStatement {
+        sql: format!(
+            "SELECT * FROM {} WHERE {}",
+            table_ref(quote, schema, table),
+            filter
+        ),
+        params,
+    })
+}
+
+/// Trace the rows related to the row of `table` identified by `key`
+/// (usually its primary key values).
+#[tauri::command]
+pub async fn trace_row<R: Runtime>(
+    app: AppHandle<R>,
+    connection_id: String,
+    table: String,
+    key: Map<String, Value>,
+    schema: Option<String>,
+    limits: Option<TraceLimits>,
+) -> Result<LineageGraph, String> {
+    let saved_conn = crate::commands::find_connection_by_id(&app, &connection_id)?;
+    let expanded_params =
+        crate::commands::expand_ssh_connection_params(&app, &saved_conn.params).await?;
+    let params =
+        crate::commands::resolve_connection_params_with_id(&expanded_params, &connection_id)?;
+    let drv = crate::commands::driver_for(&saved_conn.params.driver).await?;
+
+    let foreign_keys = drv
+        .get_all_foreign_keys_batch(&params, schema.as_deref())
+        .await?;
+    let relations = group_relations(&foreign_keys);
+    let quote = drv.manifest().capabilities.identifier_quote.clone();
+
+    log::info!(
+        "Tracing related rows of {} on connection: {} | {} foreign key(s)",
+        table,
+        connection_id,
+        relations.len()
+    );
+
+    let (drv, params, schema) = (&drv, &params, schema.as_deref());
+    let quote = quote.as_str();
+    trace(
+        &table,
+        key,
+        &relations,
+        limits.unwrap_or_default(),
+        |table, filter, limit| async move {
+            let query = build_lookup_query(quote, schema, &table, &filter)?;
+            drv.execute_query_with_params(params, &query.sql, &query.params, Some(limit), 1, schema)
+                .await
+        },
+    )
+    .await
+}
+
+/// Fetch the row referenced by `value` in foreign key column `column` of
+/// `table`. For a composite foreign key, `row` supplies the other columns.
+#[tauri::command]
+pub async fn lookup_referenced_row<R: Runtime>(
+    app: AppHandle<R>,
+    connection_id: String,
+    table: String,
+    column: String,
+    value: Value,
+    schema: Option<String>,
+    row: Option<Map<String, Value>>,
+) -> Result<ReferencedRow, String> {
+    let saved_conn = crate::commands::find_connection_by_id(&app, &connection_id)?;
+    let expanded_params =
+        crate::commands::expand_ssh_connection_params(&app, &saved_conn.params).await?;
+    let params =
+        crate::commands::resolve_connection_params_with_id(&expanded_params, &connection_id)?;
+    let drv = crate::commands::driver_for(&saved_conn.params.driver).await?;
+
+    let foreign_keys = drv
+        .get_foreign_keys(&params, &table, schema.as_deref())
+        .await?;
+    let relation = group_relations(&HashMap::from([(table.clone(), foreign_keys)]))
+        .into_iter()
+        .find(|r| r.columns.iter().any(|(c, _)| c == &column))
+        .ok_or_else(|| format!("Column {} of {} is not a foreign key", column, table))?;
+
+    let found = match referenced_key(&relation, &column, &value, row.as_ref())? {
+        None => None,
+        Some(key) => {
+            let quote = &drv.manifest().capabilities.identifier_quote;
+            let query = build_lookup_query(quote, schema.as_deref(), &relation.ref_table, &key)?;
+            let result = drv
+                .execute_query_with_params(
+                    &params,
+                    &query.sql,
+                    &query.params,
+                    Some(1),
+                    1,
+                    schema.as_deref(),
+                )
+                .await?;
+            rows_of(result).into_iter().next()
+        }
+    };
+
+    Ok(ReferencedRow {
+        constraint: relation.name,
+        table: relation.ref_table,
+        columns: relation.columns.into_iter().map(|(_, r)| r).collect(),
+        row: found,
+    })
+}
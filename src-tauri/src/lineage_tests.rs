@@ -0,0 +1,294 @@
+#[cfg(test)]
+mod tests {
+    use crate::lineage::{
+        build_lookup_query, group_relations, referenced_key, trace, Relation, TraceLimits,
+    };
+    use crate::models::{ForeignKey, QueryResult};
+    use serde_json::{json, Map, Value};
+    use std::collections::HashMap;
+
+    fn fk(name: &str, column: &str, ref_table: &str, ref_column: &str) -> ForeignKey {
+        ForeignKey {
+            name: name.into(),
+            column_name: column.into(),
+            ref_table: ref_table.into(),
+            ref_column: ref_column.into(),
+            on_delete: None,
+            on_update: None,
+        }
+    }
+
+    fn relation(name: &str, table: &str, column: &str, ref_table: &str) -> Relation {
+        Relation {
+            name: name.into(),
+            table: table.into(),
+            ref_table: ref_table.into(),
+            columns: vec![(column.into(), "id".into())],
+        }
+    }
+
+    /// In-memory tables: name → (columns, rows).
+    type Db = HashMap<&'static str, (Vec<&'static str>, Vec<Vec<Value>>)>;
+
+    fn shop() -> Db {
+        HashMap::from([
+            (
+                "customers",
+                (
+                    vec!["id", "name", "referred_by"],
+                    vec![
+                        vec![json!(1), json!("Ada"), json!(2)],
+                        vec![json!(2), json!("Bob"), json!(1)],
+                    ],
+                ),
+            ),
+            (
+                "orders",
+                (
+                    vec!["id", "customer_id"],
+                    vec![
+                        vec![json!(10), json!(1)],
+                        vec![json!(11), json!(1)],
+                        vec![json!(12), json!(2)],
+                    ],
+                ),
+            ),
+            (
+                "order_items",
+                (
+                    vec!["id", "order_id"],
+                    vec![vec![json!(100), json!(10)], vec![json!(101), json!(12)]],
+                ),
+            ),
+        ])
+    }
+
+    fn shop_relations() -> Vec<Relation> {
+        vec![
+            relation("fk_orders_customer", "orders", "customer_id", "customers"),
+            relation("fk_items_order", "order_items", "order_id", "orders"),
+            relation("fk_referrer", "customers", "referred_by", "customers"),
+        ]
+    }
+
+    fn key(column: &str, value: Value) -> Map<String, Value> {
+        Map::from_iter([(column.to_string(), value)])
+    }
+
+    async fn run(
+        db: &Db,
+        table: &str,
+        id: i64,
+        limits: TraceLimits,
+    ) -> crate::lineage::LineageGraph {
+        trace(
+            table,
+            key("id", json!(id)),
+            &shop_relations(),
+            limits,
+            |table, filter, limit| {
+                let (columns, rows) = &db[table.as_str()];
+                let rows: Vec<Vec<Value>> = rows
+                    .iter()
+                    .filter(|row| {
+                        filter.iter().all(|(col, val)| {
+                            let idx = columns.iter().position(|c| c == col).unwrap();
+                            &row[idx] == val
+                        })
+                    })
+                    .take(limit as usize)
+                    .cloned()
+                    .collect();
+                let result = QueryResult {
+                    columns: columns.iter().map(|c| c.to_string()).collect(),
+                    rows,
+                    affected_rows: 0,
+                    truncated: false,
+                    truncation: None,
+                    pagination: None,
+                    style_hints: None,
+                    provenance: None,
+                };
+                async move { Ok(result) }
+            },
+        )
+        .await
+        .unwrap()
+    }
+
+    fn ids(graph: &crate::lineage::LineageGraph, table: &str) -> Vec<Value> {
+        let mut ids: Vec<Value> = graph
+            .nodes
+            .iter()
+            .filter(|n| n.table == table)
+            .map(|n| n.row["id"].clone())
+            .collect();
+        ids.sort_by_key(|v| v.as_i64());
+        ids
+    }
+
+    #[test]
+    fn groups_composite_foreign_keys() {
+        let fks = HashMap::from([(
+            "lines".to_string(),
+            vec![
+                fk("fk_lines_order", "order_no", "orders", "no"),
+                fk("fk_lines_order", "order_year", "orders", "year"),
+                fk("fk_lines_sku", "sku", "products", "sku"),
+                fk("fk_implicit", "x", "things", ""),
+            ],
+        )]);
+        let relations = group_relations(&fks);
+        assert_eq!(relations.len(), 2);
+        assert_eq!(
+            relations[0].columns,
+            vec![
+                ("order_no".to_string(), "no".to_string()),
+                ("order_year".to_string(), "year".to_string())
+            ]
+        );
+        assert_eq!(relations[1].ref_table, "products");
+    }
+
+    #[tokio::test]
+    async fn follows_keys_in_both_directions() {
+        let db = shop();
+        let graph = run(&db, "orders", 10, TraceLimits::default()).await;
+
+        assert_eq!(graph.nodes[0].table, "orders");
+        assert_eq!(graph.nodes[0].depth, 0);
+        // Parent customer, its referrer and their other order at depth 2.
+        assert_eq!(ids(&graph, "customers"), vec![json!(1), json!(2)]);
+        assert_eq!(ids(&graph, "orders"), vec![json!(10), json!(11)]);
+        assert_eq!(ids(&graph, "order_items"), vec![json!(100)]);
+        assert!(!graph.truncated);
+
+        let item = graph
+            .nodes
+            .iter()
+            .find(|n| n.table == "order_items")
+            .unwrap();
+        let edge = graph.edges.iter().find(|e| e.from == item.id).unwrap();
+        assert_eq!(edge.to, 0);
+        assert_eq!(edge.constraint, "fk_items_order");
+        assert_eq!(edge.columns, vec!["order_id"]);
+        assert_eq!(edge.ref_columns, vec!["id"]);
+    }
+
+    #[tokio::test]
+    async fn reference_cycles_terminate() {
+        let db = shop();
+        let limits = TraceLimits {
+            max_depth: Some(5),
+            ..Default::default()
+        };
+        let graph = run(&db, "customers", 1, limits).await;
+
+        // Every row is reachable, each exactly once.
+        assert_eq!(graph.nodes.len(), 7);
+        assert_eq!(ids(&graph, "customers"), vec![json!(1), json!(2)]);
+        // Ada and Bob refer each other: one edge per direction.
+        let referrals = graph
+            .edges
+            .iter()
+            .filter(|e| e.constraint == "fk_referrer")
+            .count();
+        assert_eq!(referrals, 2);
+    }
+
+    #[tokio::test]
+    async fn depth_and_row_limits_truncate() {
+        let db = shop();
+        let shallow = run(
+            &db,
+            "customers",
+            1,
+            TraceLimits {
+                max_depth: Some(0),
+                ..Default::default()
+            },
+        )
+        .await;
+        assert_eq!(shallow.nodes.len(), 1);
+        assert!(shallow.edges.is_empty());
+
+        let capped = run(
+            &db,
+            "customers",
+            1,
+            TraceLimits {
+                rows_per_relation: Some(1),
+                max_depth: Some(1),
+                ..Default::default()
+            },
+        )
+        .await;
+        // One of Ada's two orders, plus Bob as referrer.
+        assert_eq!(ids(&capped, "orders").len(), 1);
+        assert!(capped.truncated);
+
+        let small = run(
+            &db,
+            "customers",
+            1,
+            TraceLimits {
+                max_nodes: Some(2),
+                ..Default::default()
+            },
+        )
+        .await;
+        assert_eq!(small.nodes.len(), 2);
+        assert!(small.truncated);
+    }
+
+    #[test]
+    fn lookup_query_quotes_identifiers() {
+        let filter = key("customer_id", json!(7));
+        let query = build_lookup_query("\"", Some("public"), "orders", &filter).unwrap();
+        assert_eq!(
+            query.sql,
+            "SELECT * FROM \"public\".\"orders\" WHERE \"customer_id\" = :p0"
+        );
+        assert_eq!(query.params["p0"], json!(7));
+        assert_eq!(
+            build_lookup_query("`", None, "orders", &filter)
+                .unwrap()
+                .sql,
+            "SELECT * FROM `orders` WHERE `customer_id` = :p0"
+        );
+    }
+
+    #[test]
+    fn referenced_key_maps_to_parent_columns() {
+        let single = relation("fk_orders_customer", "orders", "customer_id", "customers");
+        assert_eq!(
+            referenced_key(&single, "customer_id", &json!(7), None).unwrap(),
+            Some(key("id", json!(7)))
+        );
+        assert_eq!(
+            referenced_key(&single, "customer_id", &Value::Null, None).unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn composite_referenced_key_needs_the_row() {
+        let composite = Relation {
+            name: "fk_lines_order".into(),
+            table: "lines".into(),
+            ref_table: "orders".into(),
+            columns: vec![
+                ("order_no".into(), "no".into()),
+                ("order_year".into(), "year".into()),
+            ],
+        };
+        assert!(referenced_key(&composite, "order_no", &json!(5), None).is_err());
+
+        let row = key("order_year", json!(2024));
+        let resolved = referenced_key(&composite, "order_no", &json!(5), Some(&row))
+            .unwrap()
+            .unwrap();
+        assert_eq!(resolved["no"], json!(5));
+        assert_eq!(resolved["year"], json!(2024));
+    }
+}
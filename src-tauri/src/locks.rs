@@ -0,0 +1,150 @@
+//! Lock waits and blocking sessions.
+//!
+//! Drivers report flat (blocked, blocking) pairs via `get_lock_waits`; this
+//! module turns them into a blocker → blocked tree for the UI and exposes a
+//! command to kill the session at the root of a chain.
+
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use tauri::{AppHandle, Runtime};
+
+use crate::commands::{
+    driver_for, expand_ssh_connection_params, find_connection_by_id,
+    resolve_connection_params_with_id,
+};
+use crate::models::{ConnectionParams, LockWait};
+
+/// A session in the blocking tree; `blocked` lists the sessions waiting on it.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockingSession {
+    pub pid: i64,
+    pub user: Option<String>,
+    pub query: Option<String>,
+    pub state: Option<String>,
+    /// How long this session has been waiting (absent for root blockers).
+    pub wait_seconds: Option<f64>,
+    /// Lock this session waits for.
+    pub lock_type: Option<String>,
+    pub relation: Option<String>,
+    pub blocked: Vec<BlockingSession>,
+}
+
+/// Build the blocker → blocked forest. Roots are sessions that block others
+/// without waiting themselves; sessions caught in a wait cycle (a deadlock
+/// the server has not resolved yet) are rooted at their lowest pid.
+pub fn build_blocking_tree(waits: &[LockWait]) -> Vec<BlockingSession> {
+    let mut children: HashMap<i64, Vec<&LockWait>> = HashMap::new();
+    let mut blockers: Vec<i64> = Vec::new();
+    let mut blocked: HashSet<i64> = HashSet::new();
+    let mut first_wait: HashMap<i64, &LockWait> = HashMap::new();
+
+    for wait in waits {
+        children.entry(wait.blocking_pid).or_default().push(wait);
+        if !blockers.contains(&wait.blocking_pid) {
+            blockers.push(wait.blocking_pid);
+        }
+        blocked.insert(wait.blocked_pid);
+        first_wait.entry(wait.blocking_pid).or_insert(wait);
+    }
+
+    fn expand(
+        pid: i64,
+        children: &HashMap<i64, Vec<&LockWait>>,
+        path: &mut Vec<i64>,
+        seen: &mut HashSet<i64>,
+    ) -> Vec<BlockingSession> {
+        seen.insert(pid);
+        path.push(pid);
+        let mut nodes = Vec::new();
+        for w in children.get(&pid).into_iter().flatten() {
+            if path.contains(&w.blocked_pid) {
+                continue;
+            }
+            nodes.push(BlockingSession {
+                pid: w.blocked_pid,
+                user: w.blocked_user.clone(),
+                query: w.blocked_query.clone(),
+                state: None,
+                wait_seconds: w.wait_seconds,
+                lock_type: w.lock_type.clone(),
+                relation: w.relation.clone(),
+                blocked: expand(w.blocked_pid, children, path, seen),
+            });
+        }
+        path.pop();
+        nodes
+    }
+
+    let root = |pid: i64, seen: &mut HashSet<i64>| {
+        let wait = first_wait.get(&pid);
+        BlockingSession {
+            pid,
+            user: wait.and_then(|w| w.blocking_user.clone()),
+            query: wait.and_then(|w| w.blocking_query.clone()),
+            state: wait.and_then(|w| w.blocking_state.clone()),
+            wait_seconds: None,
+            lock_type: None,
+            relation: None,
+            blocked: expand(pid, &children, &mut Vec::new(), seen),
+        }
+    };
+
+    let mut seen = HashSet::new();
+    let mut roots: Vec<BlockingSession> = blockers
+        .iter()
+        .filter(|pid| !blocked.contains(*pid))
+        .map(|pid| root(*pid, &mut seen))
+        .collect();
+
+    let mut cyclic: Vec<i64> = blockers.into_iter().filter(|p| !seen.contains(p)).collect();
+    cyclic.sort();
+    for pid in cyclic {
+        if !seen.contains(&pid) {
+            roots.push(root(pid, &mut seen));
+        }
+    }
+    roots
+}
+
+async fn resolve<R: Runtime>(
+    app: &AppHandle<R>,
+    connection_id: &str,
+) -> Result<(String, ConnectionParams), String> {
+    let saved_conn = find_connection_by_id(app, connection_id)?;
+    let expanded = expand_ssh_connection_params(app, &saved_conn.params).await?;
+    let params = resolve_connection_params_with_id(&expanded, connection_id)?;
+    Ok((saved_conn.params.driver, params))
+}
+
+#[tauri::command]
+pub async fn get_lock_waits<R: Runtime>(
+    app: AppHandle<R>,
+    connection_id: String,
+) -> Result<Vec<LockWait>, String> {
+    let (driver, params) = resolve(&app, &connection_id).await?;
+    driver_for(&driver).await?.get_lock_waits(&params).await
+}
+
+/// Current lock waits as a blocker → blocked tree.
+#[tauri::command]
+pub async fn get_blocking_tree<R: Runtime>(
+    app: AppHandle<R>,
+    connection_id: String,
+) -> Result<Vec<BlockingSession>, String> {
+    let waits = get_lock_waits(app, connection_id).await?;
+    Ok(build_blocking_tree(&waits))
+}
+
+/// Terminate a server session, e.g. the blocker at the root of a chain.
+#[tauri::command]
+pub async fn kill_session<R: Runtime>(
+    app: AppHandle<R>,
+    connection_id: String,
+    pid: i64,
+) -> Result<(), String> {
+    log::warn!("Killing session {} on connection {}", pid, connection_id);
+    let (driver, params) = resolve(&app, &connection_id).await?;
+    crate::read_only::ensure_writable(&params)?;
+    driver_for(&driver).await?.kill_session(&params, pid).await
+}
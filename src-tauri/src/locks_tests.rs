@@ -0,0 +1,61 @@
+#[cfg(test)]
+mod tests {
+    use crate::locks::build_blocking_tree;
+    use crate::models::LockWait;
+
+    fn wait(blocked: i64, blocking: i64) -> LockWait {
+        LockWait {
+            blocked_pid: blocked,
+            blocked_user: Some("app".to_string()),
+            blocked_query: Some(format!("UPDATE t SET v = {}", blocked)),
+            wait_seconds: Some(blocked as f64),
+            blocking_pid: blocking,
+            blocking_user: Some("admin".to_string()),
+            blocking_query: Some(format!("-- session {}", blocking)),
+            blocking_state: Some("idle in transaction".to_string()),
+            lock_type: Some("transactionid".to_string()),
+            relation: Some("public.t".to_string()),
+        }
+    }
+
+    #[test]
+    fn empty_waits_give_empty_tree() {
+        assert!(build_blocking_tree(&[]).is_empty());
+    }
+
+    #[test]
+    fn chain_is_rooted_at_the_head_blocker() {
+        // 1 blocks 2, 2 blocks 3, 1 also blocks 4.
+        let tree = build_blocking_tree(&[wait(2, 1), wait(3, 2), wait(4, 1)]);
+
+        assert_eq!(tree.len(), 1);
+        let root = &tree[0];
+        assert_eq!(root.pid, 1);
+        assert_eq!(root.state.as_deref(), Some("idle in transaction"));
+        assert_eq!(root.query.as_deref(), Some("-- session 1"));
+        assert_eq!(root.wait_seconds, None);
+
+        let blocked: Vec<i64> = root.blocked.iter().map(|s| s.pid).collect();
+        assert_eq!(blocked, vec![2, 4]);
+        assert_eq!(root.blocked[0].blocked[0].pid, 3);
+        assert_eq!(root.blocked[0].lock_type.as_deref(), Some("transactionid"));
+        assert_eq!(root.blocked[0].query.as_deref(), Some("UPDATE t SET v = 2"));
+    }
+
+    #[test]
+    fn independent_blockers_give_separate_roots() {
+        let tree = build_blocking_tree(&[wait(2, 1), wait(6, 5)]);
+        let roots: Vec<i64> = tree.iter().map(|s| s.pid).collect();
+        assert_eq!(roots, vec![1, 5]);
+    }
+
+    #[test]
+    fn wait_cycle_is_rooted_at_lowest_pid() {
+        let tree = build_blocking_tree(&[wait(8, 7), wait(7, 8)]);
+
+        assert_eq!(tree.len(), 1);
+        assert_eq!(tree[0].pid, 7);
+        assert_eq!(tree[0].blocked[0].pid, 8);
+        assert!(tree[0].blocked[0].blocked.is_empty());
+    }
+}
@@ -142,11 +142,43 @@ pub struct ConnectionParams {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub ssh_key_passphrase: Option<String>,
     pub save_in_keychain: Option<bool>,
+    /// Only read statements run; record edits and DDL are refused.
+    #[serde(default)]
+    pub read_only: bool,
+    /// `integrated` signs in as the Windows user through SSPI; unset uses the
+    /// username and password. SQL Server on Windows only.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub auth_method: Option<String>,
+    /// Pool sizing and timeouts; unset fields use `poolDefaults` from the
+    /// app config, then the driver's defaults.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pool: Option<PoolSettings>,
     // Connection ID for stable pooling (not persisted, set at runtime)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub connection_id: Option<String>,
 }
 
+/// Connection pool settings. Changes apply the next time the pool is opened.
+#[derive(Debug, Deserialize, Serialize, Clone, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct PoolSettings {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_connections: Option<u32>,
+    /// Connections opened along with the pool.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_connections: Option<u32>,
+    /// Seconds a query waits for a free connection.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub acquire_timeout_secs: Option<u64>,
+    /// Seconds an idle connection is kept before it is closed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub idle_timeout_secs: Option<u64>,
+    /// Milliseconds a statement runs before the server cancels it. MySQL
+    /// (`max_execution_time`, SELECT only) and PostgreSQL.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub statement_timeout_ms: Option<u64>,
+}
+
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct SavedConnection {
     pub id: String,
@@ -158,6 +190,27 @@ pub struct SavedConnection {
     pub sort_order: Option<i32>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub detect_json_in_text_columns: Option<bool>,
+    /// Production connection: ad-hoc queries are cost-checked before running.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub protected: Option<bool>,
+    /// Reader endpoints. Read-only queries are spread over them; writes, DDL
+    /// and anything unrecognised go to the writer (`params.host`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub read_replicas: Option<Vec<ReplicaEndpoint>>,
+    /// Open the tunnel and pool and read the first metadata in the
+    /// background when the app starts.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub connect_on_startup: Option<bool>,
+}
+
+/// A read replica of a saved connection. Credentials, SSL and SSH settings
+/// are shared with the writer.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ReplicaEndpoint {
+    pub host: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub port: Option<u16>,
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
@@ -184,6 +237,9 @@ pub struct ExportPayload {
     pub groups: Vec<ConnectionGroup>,
     pub connections: Vec<SavedConnection>,
     pub ssh_connections: Vec<SshConnection>,
+    /// Named filter presets keyed by connection id.
+    #[serde(default)]
+    pub named_filters: HashMap<String, Vec<crate::preferences::NamedFilter>>,
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
@@ -198,7 +254,7 @@ pub struct TableInfo {
     pub name: String,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TableColumn {
     pub name: String,
     pub data_type: String,
@@ -211,7 +267,7 @@ pub struct TableColumn {
     pub character_maximum_length: Option<u64>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ForeignKey {
     pub name: String,
     pub column_name: String,
@@ -238,6 +294,22 @@ pub struct Pagination {
     pub has_more: bool,
 }
 
+/// Presentation hint produced by a conditional formatting rule for one row
+/// (or one cell when `column` is set).
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct StyleHint {
+    pub row: usize,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub column: Option<String>,
+    pub rule_id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub color: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub background: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub badge: Option<String>,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct QueryResult {
     pub columns: Vec<String>,
@@ -245,7 +317,61 @@ pub struct QueryResult {
     pub affected_rows: u64,
     #[serde(default)]
     pub truncated: bool,
+    /// Why the driver stopped fetching early, when a result size guardrail
+    /// (not the page size) cut the result short.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub truncation: Option<ResultTruncation>,
     pub pagination: Option<Pagination>,
+    /// Conditional formatting hints, present only when table data rules matched.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub style_hints: Option<Vec<StyleHint>>,
+    /// Where the result came from, set by `execute_query`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub provenance: Option<ResultProvenance>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum TruncationReason {
+    MaxRows,
+    MaxBytes,
+}
+
+/// A result cut short by `drivers::common::ResultGuard`. `message` is
+/// localized and points the user to an export for the full result.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ResultTruncation {
+    pub reason: TruncationReason,
+    /// The row count or byte size that was reached.
+    pub limit: u64,
+    /// Rows kept in the result.
+    pub rows: u64,
+    pub message: String,
+}
+
+/// What produced a result, so snapshots and exports can be traced back to
+/// their source during audits.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ResultProvenance {
+    pub connection_id: String,
+    pub connection_name: String,
+    pub driver: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub database: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub schema: Option<String>,
+    /// Checksum of the schema's tables and columns (see
+    /// `schema_watch::checksum`), when known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub schema_checksum: Option<String>,
+    /// The SQL as sent to the database.
+    pub query: String,
+    /// RFC 3339 timestamp of when the query started.
+    pub executed_at: String,
+    pub execution_time_ms: f64,
+    pub app_version: String,
 }
 
 /// One statement's outcome within an `execute_batch` call. Exactly one of
@@ -285,6 +411,42 @@ impl BatchStatementResult {
     }
 }
 
+/// A row that `insert_records_batch` could not insert. `row` is the row's
+/// index in the submitted batch.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct RowInsertError {
+    pub row: usize,
+    pub error: String,
+}
+
+/// Outcome of an `insert_records_batch` call: the number of rows inserted
+/// and one entry per failed row, so a partially bad paste still lands the
+/// good rows and the grid can flag the rest.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct BatchInsertResult {
+    pub inserted: u64,
+    #[serde(default)]
+    pub errors: Vec<RowInsertError>,
+}
+
+impl BatchInsertResult {
+    /// Fold the outcome of another part of the same batch into this one.
+    pub fn extend(&mut self, other: BatchInsertResult) {
+        self.inserted += other.inserted;
+        self.errors.extend(other.errors);
+    }
+}
+
+/// Outcome of `insert_record`: the affected row count plus the inserted row
+/// as stored by the server, so generated keys and column defaults show up
+/// without a refetch. `row` is `None` when the driver cannot read it back.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct InsertedRecord {
+    pub affected_rows: u64,
+    #[serde(default)]
+    pub row: Option<HashMap<String, serde_json::Value>>,
+}
+
 /// A single node in a query execution plan tree.
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct ExplainNode {
@@ -359,6 +521,113 @@ pub struct RoutineParameter {
     pub ordinal_position: i32,
 }
 
+/// A session waiting on a lock held by another session.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct LockWait {
+    pub blocked_pid: i64,
+    pub blocked_user: Option<String>,
+    pub blocked_query: Option<String>,
+    pub wait_seconds: Option<f64>,
+    pub blocking_pid: i64,
+    pub blocking_user: Option<String>,
+    pub blocking_query: Option<String>,
+    pub blocking_state: Option<String>,
+    pub lock_type: Option<String>,
+    pub relation: Option<String>,
+}
+
+/// Size and statistics of one table, as far as the catalog knows them.
+/// Row counts are estimates refreshed by ANALYZE; `None` where the driver
+/// cannot tell.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct TableStats {
+    pub name: String,
+    pub row_estimate: Option<i64>,
+    pub data_bytes: Option<i64>,
+    pub index_bytes: Option<i64>,
+    /// When statistics were last gathered, as the server prints it.
+    pub last_analyzed: Option<String>,
+}
+
+/// One server status counter or configuration variable. Values are kept as
+/// the server prints them; `unit`, `category` and `description` are filled
+/// in where the server reports them (PostgreSQL settings).
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ServerValue {
+    pub name: String,
+    pub value: String,
+    pub unit: Option<String>,
+    pub category: Option<String>,
+    pub description: Option<String>,
+}
+
+impl ServerValue {
+    pub fn new(name: impl Into<String>, value: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            value: value.into(),
+            unit: None,
+            category: None,
+            description: None,
+        }
+    }
+}
+
+/// A database user (MySQL account) or login role (PostgreSQL).
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct DbUser {
+    pub name: String,
+    /// Host part of a MySQL account (`'app'@'%'`); `None` on PostgreSQL.
+    pub host: Option<String>,
+    pub can_login: bool,
+    pub superuser: bool,
+}
+
+/// What a privilege applies to.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum PrivilegeScope {
+    /// Server-wide (MySQL `*.*`).
+    Global,
+    Database,
+    /// PostgreSQL only.
+    Schema,
+    Table,
+}
+
+/// One privilege held by a user, e.g. `SELECT` on `shop.orders`.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct UserGrant {
+    /// Privilege keyword(s), e.g. `SELECT` or `ALL PRIVILEGES`.
+    pub privilege: String,
+    pub scope: PrivilegeScope,
+    pub database: Option<String>,
+    pub schema: Option<String>,
+    pub table: Option<String>,
+    /// Whether the user may pass the privilege on (`WITH GRANT OPTION`).
+    #[serde(default)]
+    pub grantable: bool,
+}
+
+/// A privilege to grant to, or revoke from, a user. A grant returned by
+/// `get_user_grants` can be passed back unchanged to revoke it.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct PrivilegeChange {
+    pub user: String,
+    /// MySQL account host; defaults to `%`.
+    pub host: Option<String>,
+    #[serde(flatten)]
+    pub grant: UserGrant,
+}
+
+/// One column of a schema signature, used to detect schema changes cheaply.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct ColumnSignature {
+    pub table: String,
+    pub column: String,
+    pub data_type: String,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ViewInfo {
     pub name: String,
@@ -384,6 +653,19 @@ pub struct ColumnDefinition {
     pub default_value: Option<String>,
 }
 
+impl From<&TableColumn> for ColumnDefinition {
+    fn from(column: &TableColumn) -> Self {
+        Self {
+            name: column.name.clone(),
+            data_type: column.data_type.clone(),
+            is_nullable: column.is_nullable,
+            is_pk: column.is_pk,
+            is_auto_increment: column.is_auto_increment,
+            default_value: column.default_value.clone(),
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct DataTypeInfo {
     pub name: String,
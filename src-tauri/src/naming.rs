@@ -0,0 +1,375 @@
+//! Naming checks for the table designer.
+//!
+//! `check_naming` runs before DDL is generated and warns about table and
+//! column names that break the chosen convention (snake_case, camelCase or
+//! PascalCase), are reserved words of the connection's dialect, exceed its
+//! identifier length, need quoting, or repeat. Each warning carries a fixed
+//! name that passes every check, so the designer can offer it in one click.
+//! Case conversion splits on acronyms: `userID` becomes `user_id`.
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Runtime};
+
+use crate::codegen::{camel_case, pascal_case, snake_case};
+use crate::commands::find_connection_by_id;
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum NamingConvention {
+    #[default]
+    SnakeCase,
+    CamelCase,
+    PascalCase,
+}
+
+impl NamingConvention {
+    fn apply(self, name: &str) -> String {
+        match self {
+            Self::SnakeCase => snake_case(name),
+            Self::CamelCase => camel_case(name),
+            Self::PascalCase => pascal_case(name),
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            Self::SnakeCase => "snake_case",
+            Self::CamelCase => "camelCase",
+            Self::PascalCase => "PascalCase",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum NamedObject {
+    Table,
+    Column,
+}
+
+impl NamedObject {
+    fn label(self) -> &'static str {
+        match self {
+            Self::Table => "Table",
+            Self::Column => "Column",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum NamingRule {
+    /// Does not follow the naming convention.
+    Convention,
+    /// A reserved word of the dialect.
+    Reserved,
+    /// Longer than the dialect allows.
+    Length,
+    /// Needs quoting: characters other than letters, digits and `_`, or a
+    /// leading digit.
+    Characters,
+    /// Another column has the same name, ignoring case.
+    Duplicate,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct NamingWarning {
+    pub object: NamedObject,
+    pub name: String,
+    pub rule: NamingRule,
+    pub message: String,
+    /// A name passing every check, when one can be derived.
+    pub suggestion: Option<String>,
+}
+
+// ---------------------------------------------------------------------------
+// Dialects (testable; pure)
+// ---------------------------------------------------------------------------
+
+// Reserved words, separated by whitespace.
+const MYSQL_RESERVED: &str = "\
+add all alter analyze and as asc before between bigint binary blob both by call cascade \
+case change char character check collate column condition constraint continue convert \
+create cross current_date current_time current_timestamp current_user cursor database \
+databases dec decimal declare default delayed delete desc describe distinct div double \
+drop dual each else elseif enclosed escaped exists exit explain false fetch float for \
+force foreign from fulltext generated grant group groups having if ignore in index \
+infile inner inout insert int integer interval into is iterate join key keys kill \
+lateral leading leave left like limit lines load localtime localtimestamp lock long loop \
+match mod natural not null numeric of on optimize option or order out outer over \
+partition precision primary procedure range rank read reads real recursive references \
+regexp release rename repeat replace require restrict return revoke right rlike row rows \
+schema schemas select separator set show signal smallint spatial sql starting stored \
+system table terminated then tinyint to trailing trigger true undo union unique unlock \
+unsigned update usage use using values varchar varying virtual when where while window \
+with write xor zerofill";
+
+const POSTGRES_RESERVED: &str = "\
+all analyse analyze and any array as asc asymmetric authorization binary both case cast \
+check collate collation column concurrently constraint create cross current_catalog \
+current_date current_role current_schema current_time current_timestamp current_user \
+default deferrable desc distinct do else end except false fetch for foreign freeze from \
+full grant group having ilike in initially inner intersect into is isnull join lateral \
+leading left like limit localtime localtimestamp natural not notnull null offset on only \
+or order outer overlaps placing primary references returning right select session_user \
+similar some symmetric system_user table tablesample then to trailing true union unique \
+user using variadic verbose when where window with";
+
+const SQLITE_RESERVED: &str = "\
+add all alter and as autoincrement between case check collate commit constraint create \
+default deferrable delete distinct drop else escape except exists foreign from group \
+having in index insert intersect into is isnull join limit not nothing notnull null on \
+or order primary references select set table then to transaction union unique update \
+using values when where";
+
+const MSSQL_RESERVED: &str = "\
+add all alter and any as asc authorization backup begin between break browse bulk by \
+cascade case check checkpoint close clustered coalesce collate column commit compute \
+constraint contains containstable continue convert create cross current current_date \
+current_time current_timestamp current_user cursor database dbcc deallocate declare \
+default delete deny desc disk distinct distributed double drop dump else end errlvl \
+escape except exec execute exists exit external fetch file fillfactor for foreign \
+freetext freetexttable from full function goto grant group having holdlock identity \
+identity_insert identitycol if in index inner insert intersect into is join key kill \
+left like lineno load merge national nocheck nonclustered not null nullif of off offsets \
+on open opendatasource openquery openrowset openxml option or order outer over percent \
+pivot plan precision primary print proc procedure public raiserror read readtext \
+reconfigure references replication restore restrict return revert revoke right rollback \
+rowcount rowguidcol rule save schema select session_user set setuser shutdown some \
+statistics system_user table tablesample textsize then to top tran transaction trigger \
+truncate try_convert tsequal union unique unpivot update updatetext use user values \
+varying view waitfor when where while with writetext";
+
+/// Whether `name` is a reserved word of `driver`, ignoring case. Plugin
+/// drivers have no list.
+pub fn is_reserved(driver: &str, name: &str) -> bool {
+    let words = match driver {
+        "mysql" => MYSQL_RESERVED,
+        "postgres" => POSTGRES_RESERVED,
+        "sqlite" => SQLITE_RESERVED,
+        "mssql" => MSSQL_RESERVED,
+        _ => return false,
+    };
+    words
+        .split_whitespace()
+        .any(|word| word.eq_ignore_ascii_case(name))
+}
+
+/// The longest identifier `driver` accepts, and whether it is counted in
+/// bytes rather than characters.
+fn max_length(driver: &str) -> Option<(usize, bool)> {
+    match driver {
+        "mysql" => Some((64, false)),
+        "postgres" => Some((63, true)),
+        "mssql" => Some((128, false)),
+        _ => None,
+    }
+}
+
+fn too_long(driver: &str, name: &str) -> Option<usize> {
+    let (max, bytes) = max_length(driver)?;
+    let length = if bytes {
+        name.len()
+    } else {
+        name.chars().count()
+    };
+    (length > max).then_some(max)
+}
+
+fn truncate(driver: &str, name: &str) -> String {
+    let Some((max, bytes)) = max_length(driver) else {
+        return name.to_string();
+    };
+    let mut out = String::new();
+    for c in name.chars() {
+        let length = if bytes {
+            out.len() + c.len_utf8()
+        } else {
+            out.chars().count() + 1
+        };
+        if length > max {
+            break;
+        }
+        out.push(c);
+    }
+    out.trim_end_matches('_').to_string()
+}
+
+/// Letters, digits and `_`, not starting with a digit: usable unquoted.
+fn is_plain(name: &str) -> bool {
+    name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+        && !name.starts_with(|c: char| c.is_ascii_digit())
+}
+
+// ---------------------------------------------------------------------------
+// Checks (testable; pure)
+// ---------------------------------------------------------------------------
+
+/// `name` fixed for every rule. Reserved table names take a plural
+/// (`order` → `orders`); reserved column names are prefixed with the table
+/// (`order` in `items` → `items_order`).
+pub fn fix_name(
+    driver: &str,
+    convention: NamingConvention,
+    object: NamedObject,
+    name: &str,
+    table: &str,
+) -> Option<String> {
+    let ascii: String = name
+        .chars()
+        .map(|c| if c.is_ascii() { c } else { '_' })
+        .collect();
+    let mut fixed = convention.apply(&ascii);
+    if fixed.is_empty() {
+        return None;
+    }
+    if fixed.starts_with(|c: char| c.is_ascii_digit()) {
+        let prefix = match object {
+            NamedObject::Table => "t",
+            NamedObject::Column => "c",
+        };
+        fixed = convention.apply(&format!("{}_{}", prefix, fixed));
+    }
+    if is_reserved(driver, &fixed) {
+        fixed = match object {
+            NamedObject::Table => format!("{}s", fixed),
+            NamedObject::Column => convention.apply(&format!("{}_{}", table, fixed)),
+        };
+        if is_reserved(driver, &fixed) {
+            fixed = convention.apply(&format!("{}_{}", fixed, object.label()));
+        }
+    }
+    Some(truncate(driver, &fixed))
+}
+
+fn check_one(
+    driver: &str,
+    convention: NamingConvention,
+    object: NamedObject,
+    name: &str,
+    table: &str,
+    warnings: &mut Vec<NamingWarning>,
+) {
+    let label = object.label();
+    if name.trim().is_empty() {
+        warnings.push(NamingWarning {
+            object,
+            name: name.to_string(),
+            rule: NamingRule::Characters,
+            message: format!("{} has no name", label),
+            suggestion: None,
+        });
+        return;
+    }
+    let suggestion = fix_name(driver, convention, object, name, table).filter(|s| s != name);
+    let mut warn = |rule, message: String| {
+        warnings.push(NamingWarning {
+            object,
+            name: name.to_string(),
+            rule,
+            message,
+            suggestion: suggestion.clone(),
+        })
+    };
+
+    if !is_plain(name) {
+        warn(
+            NamingRule::Characters,
+            format!("{} {} needs quoting in every statement", label, name),
+        );
+    }
+    if convention.apply(name) != name {
+        warn(
+            NamingRule::Convention,
+            format!("{} {} is not {}", label, name, convention.label()),
+        );
+    }
+    if is_reserved(driver, name) {
+        warn(
+            NamingRule::Reserved,
+            format!("{} {} is a reserved word in {}", label, name, driver),
+        );
+    }
+    if let Some(max) = too_long(driver, name) {
+        warn(
+            NamingRule::Length,
+            format!(
+                "{} {} is longer than the {} characters {} allows",
+                label, name, max, driver
+            ),
+        );
+    }
+}
+
+/// Warnings for the names of a table and its columns. `check_table` is
+/// false when only columns are added to an existing table.
+pub fn check_names(
+    driver: &str,
+    convention: NamingConvention,
+    table: &str,
+    check_table: bool,
+    columns: &[String],
+) -> Vec<NamingWarning> {
+    let mut warnings = Vec::new();
+    if check_table {
+        check_one(
+            driver,
+            convention,
+            NamedObject::Table,
+            table,
+            table,
+            &mut warnings,
+        );
+    }
+    let mut seen: Vec<String> = Vec::new();
+    for column in columns {
+        let key = column.to_lowercase();
+        if seen.contains(&key) {
+            warnings.push(NamingWarning {
+                object: NamedObject::Column,
+                name: column.clone(),
+                rule: NamingRule::Duplicate,
+                message: format!("Column {} appears more than once", column),
+                suggestion: None,
+            });
+            continue;
+        }
+        seen.push(key);
+        check_one(
+            driver,
+            convention,
+            NamedObject::Column,
+            column,
+            table,
+            &mut warnings,
+        );
+    }
+    warnings
+}
+
+// ---------------------------------------------------------------------------
+// Tauri commands
+// ---------------------------------------------------------------------------
+
+/// Check the names of a table (when `check_table`, the default) and of
+/// `columns` against the conventions of the connection's dialect. Run it
+/// before `get_create_table_sql` / `get_add_column_sql`; it never blocks
+/// them.
+#[tauri::command]
+pub fn check_naming<R: Runtime>(
+    app: AppHandle<R>,
+    connection_id: String,
+    table: String,
+    columns: Vec<String>,
+    check_table: Option<bool>,
+    convention: Option<NamingConvention>,
+) -> Result<Vec<NamingWarning>, String> {
+    let saved_conn = find_connection_by_id(&app, &connection_id)?;
+    Ok(check_names(
+        &saved_conn.params.driver,
+        convention.unwrap_or_default(),
+        &table,
+        check_table.unwrap_or(true),
+        &columns,
+    ))
+}
@@ -0,0 +1,119 @@
+#[cfg(test)]
+mod tests {
+    use crate::naming::{
+        check_names, fix_name, is_reserved, NamedObject, NamingConvention, NamingRule,
+    };
+
+    fn names(columns: &[&str]) -> Vec<String> {
+        columns.iter().map(|c| c.to_string()).collect()
+    }
+
+    #[test]
+    fn reserved_words_depend_on_the_dialect() {
+        assert!(is_reserved("postgres", "USER"));
+        assert!(!is_reserved("mysql", "user"));
+        assert!(is_reserved("mysql", "Key"));
+        assert!(is_reserved("mssql", "percent"));
+        assert!(!is_reserved("duckdb", "select"));
+    }
+
+    #[test]
+    fn convention_fixes_split_acronyms() {
+        let snake = NamingConvention::SnakeCase;
+        let column = NamedObject::Column;
+        assert_eq!(
+            fix_name("postgres", snake, column, "userID", "t").as_deref(),
+            Some("user_id")
+        );
+        assert_eq!(
+            fix_name("postgres", snake, column, "HTTPStatus", "t").as_deref(),
+            Some("http_status")
+        );
+        assert_eq!(
+            fix_name(
+                "postgres",
+                NamingConvention::CamelCase,
+                column,
+                "created_at",
+                "t"
+            )
+            .as_deref(),
+            Some("createdAt")
+        );
+        assert_eq!(
+            fix_name("postgres", snake, column, "2fa code", "t").as_deref(),
+            Some("c_2fa_code")
+        );
+    }
+
+    #[test]
+    fn reserved_names_get_a_plural_or_the_table() {
+        let snake = NamingConvention::SnakeCase;
+        assert_eq!(
+            fix_name("mysql", snake, NamedObject::Table, "Order", "Order").as_deref(),
+            Some("orders")
+        );
+        assert_eq!(
+            fix_name("mysql", snake, NamedObject::Column, "key", "api_tokens").as_deref(),
+            Some("api_tokens_key")
+        );
+    }
+
+    #[test]
+    fn warnings_list_every_broken_rule() {
+        let warnings = check_names(
+            "postgres",
+            NamingConvention::SnakeCase,
+            "UserAccounts",
+            true,
+            &names(&["id", "emailAddress", "user", "first name"]),
+        );
+        let found: Vec<(&str, NamingRule, Option<&str>)> = warnings
+            .iter()
+            .map(|w| (w.name.as_str(), w.rule, w.suggestion.as_deref()))
+            .collect();
+        assert_eq!(
+            found,
+            [
+                (
+                    "UserAccounts",
+                    NamingRule::Convention,
+                    Some("user_accounts")
+                ),
+                (
+                    "emailAddress",
+                    NamingRule::Convention,
+                    Some("email_address")
+                ),
+                ("user", NamingRule::Reserved, Some("user_accounts_user")),
+                ("first name", NamingRule::Characters, Some("first_name")),
+                ("first name", NamingRule::Convention, Some("first_name")),
+            ]
+        );
+        assert_eq!(warnings[2].object, NamedObject::Column);
+        assert_eq!(
+            warnings[2].message,
+            "Column user is a reserved word in postgres"
+        );
+    }
+
+    #[test]
+    fn long_and_repeated_names() {
+        let long = "a".repeat(70);
+        let warnings = check_names(
+            "mysql",
+            NamingConvention::SnakeCase,
+            "t",
+            false,
+            &names(&[&long, "Id", "id"]),
+        );
+        assert_eq!(warnings.len(), 3);
+        assert_eq!(warnings[0].rule, NamingRule::Length);
+        assert_eq!(warnings[0].suggestion.as_deref(), Some(&long[..64]));
+        assert_eq!(warnings[1].rule, NamingRule::Convention);
+        assert_eq!(warnings[2].rule, NamingRule::Duplicate);
+        assert_eq!(warnings[2].suggestion, None);
+        // No limit on SQLite.
+        assert!(check_names("sqlite", NamingConvention::SnakeCase, "t", false, &[long]).is_empty());
+    }
+}
@@ -0,0 +1,180 @@
+//! Adaptive page size for paginated results.
+//!
+//! The user's page size is a row count, but what a page costs is bytes: 500
+//! rows of a table with a few `TEXT` blobs can serialize to 10MB, while
+//! 500 rows of a two-column lookup table are a few kilobytes. When the
+//! first page comes back, [`adapt_first_page`] measures its average
+//! serialized row size, cuts a page that is too big down to the byte
+//! target (clamped to the user's min/max), and the width is remembered for
+//! the query. The next run of the same query asks for the fitting size up
+//! front with [`first_page_size`]; a statement is never run twice to grow
+//! a page. The result's `pagination.page_size` carries the size the
+//! frontend uses for the following pages.
+
+use std::collections::HashMap;
+use std::io::Write;
+use std::sync::Mutex;
+
+use once_cell::sync::Lazy;
+use serde_json::Value;
+
+use crate::models::QueryResult;
+
+/// Serialized size a page aims for: 1MB. Can be overridden via config.json
+/// with "resultPageTargetBytes".
+pub const DEFAULT_PAGE_TARGET_BYTES: u64 = 1024 * 1024;
+
+/// Fewest rows an adapted page holds. Can be overridden via config.json with
+/// "resultPageSizeMin".
+pub const DEFAULT_PAGE_SIZE_MIN: u32 = 50;
+
+/// Most rows an adapted page holds. Can be overridden via config.json with
+/// "resultPageSizeMax".
+pub const DEFAULT_PAGE_SIZE_MAX: u32 = 5000;
+
+/// Sizes within this factor of the requested one are not worth a change.
+const TOLERANCE: f64 = 1.25;
+
+/// Queries whose row width is remembered; the memory starts over when full.
+const MAX_REMEMBERED_QUERIES: usize = 500;
+
+/// Average row size of the last first page, by connection and query.
+static ROW_WIDTHS: Lazy<Mutex<HashMap<String, u64>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Bounds for the adapted page size.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PageSizeLimits {
+    pub min_rows: u32,
+    pub max_rows: u32,
+    pub target_bytes: u64,
+}
+
+impl PageSizeLimits {
+    pub fn new(min_rows: u32, max_rows: u32, target_bytes: u64) -> Self {
+        let min_rows = min_rows.max(1);
+        Self {
+            min_rows,
+            max_rows: max_rows.max(min_rows),
+            target_bytes: target_bytes.max(1),
+        }
+    }
+
+    /// The limits from the cached app config, or `None` when adaptive page
+    /// sizing is turned off ("adaptivePageSize": false).
+    pub fn from_config() -> Option<Self> {
+        let config = crate::config::get_cached_config();
+        if !config.adaptive_page_size.unwrap_or(true) {
+            return None;
+        }
+        Some(Self::new(
+            config.result_page_size_min.unwrap_or(DEFAULT_PAGE_SIZE_MIN),
+            config.result_page_size_max.unwrap_or(DEFAULT_PAGE_SIZE_MAX),
+            config
+                .result_page_target_bytes
+                .unwrap_or(DEFAULT_PAGE_TARGET_BYTES),
+        ))
+    }
+}
+
+/// Counts the bytes written to it, so rows can be measured without
+/// allocating their JSON text.
+struct ByteCounter(u64);
+
+impl Write for ByteCounter {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0 += buf.len() as u64;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Average JSON size of `rows`, or `None` when there are none.
+pub fn average_row_bytes(rows: &[Vec<Value>]) -> Option<u64> {
+    if rows.is_empty() {
+        return None;
+    }
+    let mut counter = ByteCounter(0);
+    for row in rows {
+        serde_json::to_writer(&mut counter, row).ok()?;
+    }
+    Some((counter.0 / rows.len() as u64).max(1))
+}
+
+/// The page size for rows of `avg_row_bytes`. Stays at `requested` unless
+/// the ideal size differs from it by more than [`TOLERANCE`].
+pub fn effective_page_size(requested: u32, avg_row_bytes: u64, limits: &PageSizeLimits) -> u32 {
+    let ideal = (limits.target_bytes / avg_row_bytes.max(1)).min(u32::MAX as u64) as u32;
+    let ideal = round_page_size(ideal.clamp(limits.min_rows, limits.max_rows));
+    let ratio = ideal as f64 / requested.max(1) as f64;
+    if (1.0 / TOLERANCE..=TOLERANCE).contains(&ratio) {
+        requested
+    } else {
+        ideal
+    }
+}
+
+/// Round down to a multiple of 50, or of 10 below 100, so page sizes stay
+/// readable in the pagination bar.
+fn round_page_size(size: u32) -> u32 {
+    let step = match size {
+        0..=9 => return size,
+        10..=99 => 10,
+        _ => 50,
+    };
+    size - size % step
+}
+
+/// The size to run the first page of a query with: fitted to the row width
+/// measured on its previous run, when there was one.
+pub fn first_page_size(requested: u32, avg_row_bytes: Option<u64>, limits: &PageSizeLimits) -> u32 {
+    avg_row_bytes.map_or(requested, |avg| effective_page_size(requested, avg, limits))
+}
+
+/// Fit the first page of `result` to its row width: a page that is too big
+/// is cut down in place. Returns the measured average row size, for
+/// [`first_page_size`] on the next run.
+pub fn adapt_first_page(result: &mut QueryResult, limits: &PageSizeLimits) -> Option<u64> {
+    let pagination = result.pagination.as_mut()?;
+    if pagination.page != 1 {
+        return None;
+    }
+    let requested = pagination.page_size;
+    let avg = average_row_bytes(&result.rows)?;
+    let size = effective_page_size(requested, avg, limits);
+
+    if size < requested {
+        if result.rows.len() > size as usize {
+            result.rows.truncate(size as usize);
+            pagination.has_more = true;
+        }
+        pagination.page_size = size;
+    }
+    Some(avg)
+}
+
+fn row_width_key(connection_id: &str, query: &str) -> String {
+    format!("{}\n{}", connection_id, query.trim())
+}
+
+/// Row width measured on the last run of `query`.
+pub fn remembered_row_bytes(connection_id: &str, query: &str) -> Option<u64> {
+    ROW_WIDTHS
+        .lock()
+        .ok()?
+        .get(&row_width_key(connection_id, query))
+        .copied()
+}
+
+pub fn remember_row_bytes(connection_id: &str, query: &str, avg_row_bytes: u64) {
+    let Ok(mut widths) = ROW_WIDTHS.lock() else {
+        return;
+    };
+    let key = row_width_key(connection_id, query);
+    if widths.len() >= MAX_REMEMBERED_QUERIES && !widths.contains_key(&key) {
+        widths.clear();
+    }
+    widths.insert(key, avg_row_bytes);
+}
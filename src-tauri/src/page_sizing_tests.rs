@@ -0,0 +1,112 @@
+#[cfg(test)]
+mod tests {
+    use crate::models::{Pagination, QueryResult};
+    use crate::page_sizing::{
+        adapt_first_page, average_row_bytes, effective_page_size, first_page_size,
+        remember_row_bytes, remembered_row_bytes, PageSizeLimits,
+    };
+    use serde_json::json;
+
+    fn limits() -> PageSizeLimits {
+        PageSizeLimits::new(50, 5000, 100_000)
+    }
+
+    /// A page of `rows` rows whose single column is `width` characters.
+    fn page(page: u32, page_size: u32, rows: usize, width: usize, has_more: bool) -> QueryResult {
+        QueryResult {
+            columns: vec!["v".to_string()],
+            rows: vec![vec![json!("x".repeat(width))]; rows],
+            affected_rows: 0,
+            truncated: false,
+            truncation: None,
+            pagination: Some(Pagination {
+                page,
+                page_size,
+                total_rows: None,
+                has_more,
+            }),
+            style_hints: None,
+            provenance: None,
+        }
+    }
+
+    #[test]
+    fn measures_serialized_rows() {
+        // ["ab",1] is 8 bytes, ["abcd",null] 13.
+        let rows = vec![
+            vec![json!("ab"), json!(1)],
+            vec![json!("abcd"), json!(null)],
+        ];
+        assert_eq!(average_row_bytes(&rows), Some(10));
+        assert_eq!(average_row_bytes(&[]), None);
+    }
+
+    #[test]
+    fn sizes_pages_to_the_byte_target() {
+        // 100KB target: 1KB rows fit 100, 20-byte rows fit 5000.
+        assert_eq!(effective_page_size(500, 1000, &limits()), 100);
+        assert_eq!(effective_page_size(500, 20, &limits()), 5000);
+        // Clamped to the user's bounds and rounded.
+        assert_eq!(effective_page_size(500, 50_000, &limits()), 50);
+        assert_eq!(effective_page_size(500, 70, &limits()), 1400);
+        assert_eq!(effective_page_size(500, 1300, &limits()), 70);
+    }
+
+    #[test]
+    fn keeps_sizes_close_to_the_request() {
+        assert_eq!(effective_page_size(500, 180, &limits()), 500);
+        assert_eq!(effective_page_size(500, 230, &limits()), 500);
+    }
+
+    #[test]
+    fn limits_keep_min_below_max() {
+        let limits = PageSizeLimits::new(0, 0, 0);
+        assert_eq!(
+            (limits.min_rows, limits.max_rows, limits.target_bytes),
+            (1, 1, 1)
+        );
+    }
+
+    #[test]
+    fn wide_first_page_is_cut_down() {
+        let mut result = page(1, 500, 500, 2000, false);
+        assert_eq!(adapt_first_page(&mut result, &limits()), Some(2004));
+        let pagination = result.pagination.unwrap();
+        assert_eq!(result.rows.len(), 50);
+        assert_eq!(pagination.page_size, 50);
+        assert!(pagination.has_more);
+    }
+
+    #[test]
+    fn narrow_first_page_sizes_the_next_run() {
+        let mut result = page(1, 500, 500, 10, true);
+        let avg = adapt_first_page(&mut result, &limits());
+        assert_eq!(avg, Some(14));
+        assert_eq!(result.rows.len(), 500);
+        assert_eq!(result.pagination.unwrap().page_size, 500);
+
+        // Unknown queries run with the requested size.
+        assert_eq!(first_page_size(500, None, &limits()), 500);
+        assert_eq!(first_page_size(500, avg, &limits()), 5000);
+    }
+
+    #[test]
+    fn row_widths_are_remembered_per_connection_and_query() {
+        remember_row_bytes("page-sizing-a", " SELECT * FROM wide ", 900);
+        assert_eq!(
+            remembered_row_bytes("page-sizing-a", "SELECT * FROM wide"),
+            Some(900)
+        );
+        assert_eq!(
+            remembered_row_bytes("page-sizing-b", "SELECT * FROM wide"),
+            None
+        );
+    }
+
+    #[test]
+    fn later_pages_are_left_alone() {
+        let mut result = page(2, 500, 500, 1000, true);
+        assert_eq!(adapt_first_page(&mut result, &limits()), None);
+        assert_eq!(result.rows.len(), 500);
+    }
+}
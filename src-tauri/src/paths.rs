@@ -1,7 +1,125 @@
 use directories::ProjectDirs;
-use std::path::PathBuf;
+use once_cell::sync::OnceCell;
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Manager, Runtime};
 
+/// Marker file that enables portable mode when placed next to the executable.
+pub const PORTABLE_MARKER: &str = ".portable";
+/// Directory (next to the executable) holding all app data in portable mode.
+pub const PORTABLE_DATA_DIR: &str = "tabularis-data";
+
+/// Profile whose data lives directly in the config directory.
+pub const DEFAULT_PROFILE: &str = "default";
+/// Sub-directory of the config directory holding the other profiles.
+pub const PROFILES_DIR: &str = "profiles";
+/// File (in the base config directory) remembering the last used profile.
+const ACTIVE_PROFILE_FILE: &str = "active_profile";
+
+static PORTABLE_ROOT: OnceCell<Option<PathBuf>> = OnceCell::new();
+static ACTIVE_PROFILE: OnceCell<String> = OnceCell::new();
+
+/// Portable data root for an executable living in `exe_dir`: enabled by the
+/// `--portable` flag or by a `.portable` marker file beside the binary.
+pub fn resolve_portable_root(exe_dir: &Path, forced: bool) -> Option<PathBuf> {
+    if forced || exe_dir.join(PORTABLE_MARKER).exists() {
+        Some(exe_dir.join(PORTABLE_DATA_DIR))
+    } else {
+        None
+    }
+}
+
+/// Decide once, at startup, whether app data lives beside the executable.
+/// Must run before anything resolves a data directory.
+pub fn init_portable_mode(forced: bool) -> Option<PathBuf> {
+    PORTABLE_ROOT
+        .get_or_init(|| {
+            let exe_dir = std::env::current_exe()
+                .ok()
+                .and_then(|exe| exe.parent().map(Path::to_path_buf))?;
+            let root = resolve_portable_root(&exe_dir, forced)?;
+            if let Err(e) = std::fs::create_dir_all(&root) {
+                // A read-only medium cannot host portable data; use the user dirs.
+                eprintln!("Portable mode disabled, cannot create {:?}: {}", root, e);
+                return None;
+            }
+            Some(root)
+        })
+        .clone()
+}
+
+/// The portable data root, when portable mode is active.
+pub fn get_portable_root() -> Option<PathBuf> {
+    PORTABLE_ROOT.get().cloned().flatten()
+}
+
+/// Profile names double as directory names: letters, digits, `-` and `_`.
+pub fn is_valid_profile_name(name: &str) -> bool {
+    !name.is_empty()
+        && name.len() <= 64
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+/// Data directory of `profile` under `base`. The default profile uses `base`
+/// itself, so single-profile installs keep their existing layout.
+pub fn profile_dir_in(base: &Path, profile: &str) -> PathBuf {
+    if profile == DEFAULT_PROFILE {
+        base.to_path_buf()
+    } else {
+        base.join(PROFILES_DIR).join(profile)
+    }
+}
+
+pub fn read_persisted_profile_in(base: &Path) -> Option<String> {
+    let name = std::fs::read_to_string(base.join(ACTIVE_PROFILE_FILE)).ok()?;
+    let name = name.trim();
+    is_valid_profile_name(name).then(|| name.to_string())
+}
+
+pub fn write_persisted_profile_in(base: &Path, profile: &str) -> Result<(), String> {
+    std::fs::create_dir_all(base).map_err(|e| e.to_string())?;
+    std::fs::write(base.join(ACTIVE_PROFILE_FILE), profile).map_err(|e| e.to_string())
+}
+
+/// Pick the profile for this process: `--profile` wins, then the last used
+/// profile, then the default one. Must run after `init_portable_mode` and
+/// before anything resolves a data directory.
+pub fn init_profile(cli_profile: Option<&str>) -> String {
+    ACTIVE_PROFILE
+        .get_or_init(|| {
+            let base = get_base_config_dir();
+            let requested = match cli_profile {
+                Some(name) if is_valid_profile_name(name) => Some(name.to_string()),
+                Some(name) => {
+                    eprintln!("Ignoring invalid profile name: {}", name);
+                    None
+                }
+                None => read_persisted_profile_in(&base),
+            };
+            requested.unwrap_or_else(|| DEFAULT_PROFILE.to_string())
+        })
+        .clone()
+}
+
+pub fn get_active_profile() -> String {
+    ACTIVE_PROFILE
+        .get()
+        .cloned()
+        .unwrap_or_else(|| DEFAULT_PROFILE.to_string())
+}
+
+/// Config directory of the active profile.
 pub fn get_app_config_dir() -> PathBuf {
+    profile_dir_in(&get_base_config_dir(), &get_active_profile())
+}
+
+/// Config directory shared by all profiles (the default profile's directory).
+pub fn get_base_config_dir() -> PathBuf {
+    if let Some(root) = get_portable_root() {
+        return root;
+    }
+
     if let Some(proj_dirs) = ProjectDirs::from("", "", "tabularis") {
 
         #[cfg(target_os = "windows")]
@@ -17,3 +135,29 @@ pub fn get_app_config_dir() -> PathBuf {
         PathBuf::from(".config/tabularis")
     }
 }
+
+/// Directory holding installed plugins.
+pub fn get_app_data_dir() -> Option<PathBuf> {
+    if let Some(root) = get_portable_root() {
+        return Some(root);
+    }
+    ProjectDirs::from("com", "debba", "tabularis").map(|d| d.data_dir().to_path_buf())
+}
+
+/// Tauri's config directory (or the portable root in portable mode),
+/// resolved for the active profile.
+pub fn app_config_dir<R: Runtime>(app: &AppHandle<R>) -> Result<PathBuf, String> {
+    let base = match get_portable_root() {
+        Some(root) => root,
+        None => app.path().app_config_dir().map_err(|e| e.to_string())?,
+    };
+    Ok(profile_dir_in(&base, &get_active_profile()))
+}
+
+/// Tauri's cache directory, or `cache/` under the portable root.
+pub fn app_cache_dir<R: Runtime>(app: &AppHandle<R>) -> Result<PathBuf, String> {
+    match get_portable_root() {
+        Some(root) => Ok(root.join("cache")),
+        None => app.path().app_cache_dir().map_err(|e| e.to_string()),
+    }
+}
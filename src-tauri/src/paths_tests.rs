@@ -0,0 +1,30 @@
+#[cfg(test)]
+mod tests {
+    use crate::paths::{resolve_portable_root, PORTABLE_DATA_DIR, PORTABLE_MARKER};
+    use tempfile::TempDir;
+
+    #[test]
+    fn portable_mode_is_off_without_marker_or_flag() {
+        let tmp = TempDir::new().unwrap();
+        assert_eq!(resolve_portable_root(tmp.path(), false), None);
+    }
+
+    #[test]
+    fn portable_flag_uses_data_dir_next_to_executable() {
+        let tmp = TempDir::new().unwrap();
+        assert_eq!(
+            resolve_portable_root(tmp.path(), true),
+            Some(tmp.path().join(PORTABLE_DATA_DIR))
+        );
+    }
+
+    #[test]
+    fn portable_marker_file_enables_portable_mode() {
+        let tmp = TempDir::new().unwrap();
+        std::fs::write(tmp.path().join(PORTABLE_MARKER), "").unwrap();
+        assert_eq!(
+            resolve_portable_root(tmp.path(), false),
+            Some(tmp.path().join(PORTABLE_DATA_DIR))
+        );
+    }
+}
@@ -1,29 +1,27 @@
-use crate::models::{ConnectionGroup, ConnectionsFile, SavedConnection};
-use std::fs;
+use crate::config_store::{self, Migration};
+use crate::models::{ConnectionGroup, ConnectionsFile, SavedConnection, SshConnection};
+use serde_json::{json, Value};
 use std::path::Path;
 
+/// Migrations of `connections.json`, oldest first; see `config_store`.
+pub const CONNECTIONS_MIGRATIONS: &[Migration] = &[wrap_connection_list];
+
+/// v0 → v1: before groups, the file was a bare array of connections.
+fn wrap_connection_list(value: &mut Value) {
+    if value.is_array() {
+        *value = json!({ "groups": [], "connections": value.take() });
+    }
+}
+
 /// Load connections file (raw, no keychain reads).
-/// Supports both old format (array of connections) and new format (with groups).
+/// Old files are migrated and a corrupt file is restored from its backup.
 /// Use `load_connections` or `load_connections_with_passwords` when passwords are needed.
 pub fn load_connections_file(path: &Path) -> Result<ConnectionsFile, String> {
-    if !path.exists() {
-        return Ok(ConnectionsFile::default());
+    match config_store::load_json(path, CONNECTIONS_MIGRATIONS)? {
+        Some(value) => serde_json::from_value(value)
+            .map_err(|_| "Failed to parse connections file".to_string()),
+        None => Ok(ConnectionsFile::default()),
     }
-    let content = fs::read_to_string(path).map_err(|e| e.to_string())?;
-
-    // Try parsing as the new format first
-    if let Ok(file) = serde_json::from_str::<ConnectionsFile>(&content) {
-        return Ok(file);
-    }
-
-    // Fall back to old format (array of connections)
-    let connections: Vec<SavedConnection> = serde_json::from_str(&content)
-        .map_err(|_| "Failed to parse connections file".to_string())?;
-
-    Ok(ConnectionsFile {
-        groups: Vec::new(),
-        connections,
-    })
 }
 
 /// Load connections list (raw, no keychain reads) — for listing UI.
@@ -32,13 +30,9 @@ pub fn load_connections(path: &Path) -> Result<Vec<SavedConnection>, String> {
     Ok(file.connections)
 }
 
-pub fn save_connections_file(path: &Path, file: &ConnectionsFile) -> Result<(), String> {
-    if let Some(parent) = path.parent() {
-        if !parent.exists() {
-            fs::create_dir_all(parent).map_err(|e| e.to_string())?;
-        }
-    }
-
+/// Write the connections file. The caller holds `config_store::lock(path)`
+/// from before it loaded the file, so no other writer's change is lost.
+pub fn write_connections_file(path: &Path, file: &ConnectionsFile) -> Result<(), String> {
     // Create a copy to sanitize passwords before saving to JSON
     let mut connections_to_save = Vec::new();
     for conn in &file.connections {
@@ -56,19 +50,19 @@ pub fn save_connections_file(path: &Path, file: &ConnectionsFile) -> Result<(),
         connections: connections_to_save,
     };
 
-    let json = serde_json::to_string_pretty(&to_save).map_err(|e| e.to_string())?;
-    fs::write(path, json).map_err(|e| e.to_string())
+    config_store::write_json(path, &to_save, CONNECTIONS_MIGRATIONS)
 }
 
 /// Legacy function for backward compatibility - saves using new format
 pub fn save_connections(path: &Path, connections: &[SavedConnection]) -> Result<(), String> {
+    let _lock = config_store::lock(path)?;
     // Load existing groups if any
     let existing = load_connections_file(path).unwrap_or_default();
     let file = ConnectionsFile {
         groups: existing.groups,
         connections: connections.to_vec(),
     };
-    save_connections_file(path, &file)
+    write_connections_file(path, &file)
 }
 
 pub fn load_groups(path: &Path) -> Result<Vec<ConnectionGroup>, String> {
@@ -77,7 +71,27 @@ pub fn load_groups(path: &Path) -> Result<Vec<ConnectionGroup>, String> {
 }
 
 pub fn save_groups(path: &Path, groups: &[ConnectionGroup]) -> Result<(), String> {
+    let _lock = config_store::lock(path)?;
     let mut file = load_connections_file(path).unwrap_or_default();
     file.groups = groups.to_vec();
-    save_connections_file(path, &file)
+    write_connections_file(path, &file)
+}
+
+/// Load `ssh_connections.json`, restoring it from its backup when corrupt.
+/// The file is a bare array: it has no version and no migrations.
+pub fn load_ssh_connections_file(path: &Path) -> Result<Vec<SshConnection>, String> {
+    match config_store::load_json(path, &[])? {
+        Some(value) => serde_json::from_value(value)
+            .map_err(|_| "Failed to parse SSH connections file".to_string()),
+        None => Ok(Vec::new()),
+    }
+}
+
+/// Write `ssh_connections.json` atomically, keeping a backup. The caller
+/// holds `config_store::lock(path)`.
+pub fn write_ssh_connections_file(
+    path: &Path,
+    connections: &[SshConnection],
+) -> Result<(), String> {
+    config_store::write_json(path, &connections, &[])
 }
@@ -0,0 +1,594 @@
+//! Execution plan history and regression alerts.
+//!
+//! Plans are stored per connection, grouped by query fingerprint (the query
+//! with literals replaced by `?`). A plan is captured every time the user runs
+//! EXPLAIN, and automatically for SELECT queries that are run often. Each new
+//! plan is compared with the previous one for the same query; when an index
+//! access turns into a full scan or the estimated cost jumps, a
+//! `plan-regression` event is emitted with the plan diff.
+//!
+//! Storage layout under the application config directory:
+//!   - `plan_history/<connection_id>.json` — every tracked query of a connection
+
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Emitter, Runtime};
+
+use crate::models::{ExplainNode, ExplainPlan};
+
+const PLAN_HISTORY_DIR: &str = "plan_history";
+const PLAN_REGRESSION_EVENT: &str = "plan-regression";
+/// Plans kept per query; older ones are dropped.
+pub const MAX_SNAPSHOTS_PER_QUERY: usize = 10;
+/// Queries tracked per connection; the least recently run are dropped.
+pub const MAX_TRACKED_QUERIES: usize = 200;
+/// A query is auto-captured on its Nth run, then every `RECAPTURE_EVERY` runs.
+pub const MIN_RUNS_FOR_CAPTURE: u64 = 3;
+pub const RECAPTURE_EVERY: u64 = 10;
+/// Estimated cost (or execution time) growth flagged as a regression.
+pub const COST_REGRESSION_FACTOR: f64 = 2.0;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlanSnapshot {
+    pub captured_at: String,
+    pub total_cost: Option<f64>,
+    pub execution_time_ms: Option<f64>,
+    pub plan: ExplainPlan,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlanHistoryEntry {
+    pub fingerprint: String,
+    /// First query seen with this fingerprint.
+    pub query: String,
+    pub run_count: u64,
+    pub last_run_at: String,
+    #[serde(default)]
+    pub snapshots: Vec<PlanSnapshot>,
+    /// Regressions found when the latest snapshot was captured.
+    #[serde(default)]
+    pub last_regressions: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct PlanHistorySummary {
+    pub fingerprint: String,
+    pub query: String,
+    pub run_count: u64,
+    pub last_run_at: String,
+    pub snapshot_count: usize,
+    pub last_captured_at: Option<String>,
+    pub regressed: bool,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "lowercase")]
+pub enum AccessKind {
+    Index,
+    FullScan,
+    Other,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct PlanLine {
+    pub depth: usize,
+    pub node_type: String,
+    pub relation: Option<String>,
+    pub access: AccessKind,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum DiffKind {
+    Same,
+    Added,
+    Removed,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct PlanDiffLine {
+    pub kind: DiffKind,
+    pub line: PlanLine,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct PlanDiff {
+    /// True when the plan shape (node types / relations) differs.
+    pub changed: bool,
+    pub lines: Vec<PlanDiffLine>,
+    /// Human-readable regressions; empty when the new plan is not worse.
+    pub regressions: Vec<String>,
+    /// New estimated cost divided by the old one, when both are known.
+    pub cost_ratio: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlanRegressionAlert {
+    pub connection_id: String,
+    pub fingerprint: String,
+    pub query: String,
+    pub diff: PlanDiff,
+}
+
+// ---------------------------------------------------------------------------
+// Fingerprints
+// ---------------------------------------------------------------------------
+
+/// Lower-cased query with string and numeric literals replaced by `?` and
+/// whitespace collapsed, so runs with different parameters group together.
+pub fn normalize_query(sql: &str) -> String {
+    let mut out = String::with_capacity(sql.len());
+    let mut chars = sql.trim().trim_end_matches(';').chars().peekable();
+    let mut prev: Option<char> = None;
+
+    while let Some(c) = chars.next() {
+        if c == '\'' {
+            // Skip to the closing quote, honouring '' escapes.
+            while let Some(n) = chars.next() {
+                if n == '\'' {
+                    if chars.peek() == Some(&'\'') {
+                        chars.next();
+                    } else {
+                        break;
+                    }
+                }
+            }
+            out.push('?');
+            prev = Some('?');
+        } else if c.is_ascii_digit() && !prev.map_or(false, |p| p.is_alphanumeric() || p == '_') {
+            while chars
+                .peek()
+                .map_or(false, |n| n.is_ascii_digit() || *n == '.')
+            {
+                chars.next();
+            }
+            out.push('?');
+            prev = Some('?');
+        } else if c.is_whitespace() {
+            if prev.map_or(false, |p| p != ' ') {
+                out.push(' ');
+                prev = Some(' ');
+            }
+        } else {
+            out.extend(c.to_lowercase());
+            prev = Some(c);
+        }
+    }
+    out.trim_end().to_string()
+}
+
+pub fn fingerprint(sql: &str) -> String {
+    crate::sql_files::content_hash(normalize_query(sql).as_bytes())[..16].to_string()
+}
+
+// ---------------------------------------------------------------------------
+// Plan comparison
+// ---------------------------------------------------------------------------
+
+/// Classify how a plan node reads its relation. Node names differ per driver
+/// ("Seq Scan", "Full Table Scan", SQLite's "Scan" without an index).
+pub fn classify_access(node: &ExplainNode) -> AccessKind {
+    let kind = node.node_type.to_lowercase();
+    if kind.contains("seq scan") || kind.contains("full table scan") {
+        AccessKind::FullScan
+    } else if kind == "scan" {
+        if node.index_condition.is_some() {
+            AccessKind::Index
+        } else {
+            AccessKind::FullScan
+        }
+    } else if kind.contains("index")
+        || kind.contains("lookup")
+        || kind.contains("range scan")
+        || kind == "search"
+    {
+        AccessKind::Index
+    } else {
+        AccessKind::Other
+    }
+}
+
+/// The plan tree as a depth-first list of lines.
+pub fn flatten_plan(plan: &ExplainPlan) -> Vec<PlanLine> {
+    fn walk(node: &ExplainNode, depth: usize, out: &mut Vec<PlanLine>) {
+        out.push(PlanLine {
+            depth,
+            node_type: node.node_type.clone(),
+            relation: node.relation.clone(),
+            access: classify_access(node),
+        });
+        for child in &node.children {
+            walk(child, depth + 1, out);
+        }
+    }
+    let mut lines = Vec::new();
+    walk(&plan.root, 0, &mut lines);
+    lines
+}
+
+/// Line diff of two flattened plans (longest common subsequence).
+fn diff_lines(old: &[PlanLine], new: &[PlanLine]) -> Vec<PlanDiffLine> {
+    let (n, m) = (old.len(), new.len());
+    let mut lcs = vec![vec![0usize; m + 1]; n + 1];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            lcs[i][j] = if old[i] == new[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut out = Vec::with_capacity(n.max(m));
+    let (mut i, mut j) = (0, 0);
+    while i < n || j < m {
+        if i < n && j < m && old[i] == new[j] {
+            out.push(PlanDiffLine {
+                kind: DiffKind::Same,
+                line: new[j].clone(),
+            });
+            i += 1;
+            j += 1;
+        } else if j < m && (i == n || lcs[i][j + 1] >= lcs[i + 1][j]) {
+            out.push(PlanDiffLine {
+                kind: DiffKind::Added,
+                line: new[j].clone(),
+            });
+            j += 1;
+        } else {
+            out.push(PlanDiffLine {
+                kind: DiffKind::Removed,
+                line: old[i].clone(),
+            });
+            i += 1;
+        }
+    }
+    out
+}
+
+fn access_by_relation(lines: &[PlanLine]) -> BTreeMap<String, BTreeSet<AccessKind>> {
+    let mut map: BTreeMap<String, BTreeSet<AccessKind>> = BTreeMap::new();
+    for line in lines {
+        if let Some(relation) = &line.relation {
+            map.entry(relation.clone()).or_default().insert(line.access);
+        }
+    }
+    map
+}
+
+fn ratio(old: Option<f64>, new: Option<f64>) -> Option<f64> {
+    match (old, new) {
+        (Some(old), Some(new)) if old > 0.0 => Some(new / old),
+        _ => None,
+    }
+}
+
+/// Compare two plans of the same query and list what got worse.
+pub fn diff_plans(old: &ExplainPlan, new: &ExplainPlan) -> PlanDiff {
+    let old_lines = flatten_plan(old);
+    let new_lines = flatten_plan(new);
+    let lines = diff_lines(&old_lines, &new_lines);
+    let changed = lines.iter().any(|l| l.kind != DiffKind::Same);
+
+    let mut regressions = Vec::new();
+    let old_access = access_by_relation(&old_lines);
+    for (relation, new_kinds) in access_by_relation(&new_lines) {
+        let had_index = old_access
+            .get(&relation)
+            .map_or(false, |kinds| kinds.contains(&AccessKind::Index));
+        if had_index
+            && new_kinds.contains(&AccessKind::FullScan)
+            && !new_kinds.contains(&AccessKind::Index)
+        {
+            regressions.push(format!(
+                "Full scan on {} replaced an index access",
+                relation
+            ));
+        }
+    }
+
+    let cost_ratio = ratio(old.root.total_cost, new.root.total_cost);
+    if let Some(r) = cost_ratio.filter(|r| *r >= COST_REGRESSION_FACTOR) {
+        regressions.push(format!("Estimated cost grew {:.1}x", r));
+    }
+    if let Some(r) =
+        ratio(old.execution_time_ms, new.execution_time_ms).filter(|r| *r >= COST_REGRESSION_FACTOR)
+    {
+        regressions.push(format!("Execution time grew {:.1}x", r));
+    }
+
+    PlanDiff {
+        changed,
+        lines,
+        regressions,
+        cost_ratio,
+    }
+}
+
+/// Whether the `run_count`-th run of a query should capture its plan.
+pub fn should_capture(run_count: u64) -> bool {
+    run_count == MIN_RUNS_FOR_CAPTURE
+        || (run_count > MIN_RUNS_FOR_CAPTURE
+            && (run_count - MIN_RUNS_FOR_CAPTURE) % RECAPTURE_EVERY == 0)
+}
+
+// ---------------------------------------------------------------------------
+// Storage (testable; take a directory)
+// ---------------------------------------------------------------------------
+
+fn history_path_in(dir: &Path, connection_id: &str) -> PathBuf {
+    dir.join(format!("{}.json", connection_id))
+}
+
+pub fn read_history_in(dir: &Path, connection_id: &str) -> Vec<PlanHistoryEntry> {
+    fs::read_to_string(history_path_in(dir, connection_id))
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn write_history_in(
+    dir: &Path,
+    connection_id: &str,
+    entries: &mut Vec<PlanHistoryEntry>,
+) -> Result<(), String> {
+    entries.sort_by(|a, b| b.last_run_at.cmp(&a.last_run_at));
+    entries.truncate(MAX_TRACKED_QUERIES);
+
+    fs::create_dir_all(dir).map_err(|e| format!("Failed to create plan history dir: {}", e))?;
+    let json = serde_json::to_string_pretty(entries)
+        .map_err(|e| format!("Failed to serialize plan history: {}", e))?;
+    fs::write(history_path_in(dir, connection_id), json)
+        .map_err(|e| format!("Failed to write plan history: {}", e))
+}
+
+fn entry_for<'a>(
+    entries: &'a mut Vec<PlanHistoryEntry>,
+    sql: &str,
+    now: &str,
+) -> &'a mut PlanHistoryEntry {
+    let fp = fingerprint(sql);
+    match entries.iter().position(|e| e.fingerprint == fp) {
+        Some(index) => &mut entries[index],
+        None => {
+            entries.push(PlanHistoryEntry {
+                fingerprint: fp,
+                query: sql.to_string(),
+                run_count: 0,
+                last_run_at: now.to_string(),
+                snapshots: Vec::new(),
+                last_regressions: Vec::new(),
+            });
+            entries.last_mut().unwrap()
+        }
+    }
+}
+
+/// Count one run of `sql` and return the new run count.
+pub fn count_run_in(dir: &Path, connection_id: &str, sql: &str, now: &str) -> Result<u64, String> {
+    let mut entries = read_history_in(dir, connection_id);
+    let entry = entry_for(&mut entries, sql, now);
+    entry.run_count += 1;
+    entry.last_run_at = now.to_string();
+    let count = entry.run_count;
+    write_history_in(dir, connection_id, &mut entries)?;
+    Ok(count)
+}
+
+/// Store a captured plan and compare it with the previous one for the same
+/// query. Returns the fingerprint and, when a previous plan exists, the diff.
+pub fn record_plan_in(
+    dir: &Path,
+    connection_id: &str,
+    plan: &ExplainPlan,
+    now: &str,
+) -> Result<(String, Option<PlanDiff>), String> {
+    let mut entries = read_history_in(dir, connection_id);
+    let entry = entry_for(&mut entries, &plan.original_query, now);
+
+    let diff = entry
+        .snapshots
+        .last()
+        .map(|prev| diff_plans(&prev.plan, plan));
+    entry.last_regressions = diff
+        .as_ref()
+        .map(|d| d.regressions.clone())
+        .unwrap_or_default();
+    entry.snapshots.push(PlanSnapshot {
+        captured_at: now.to_string(),
+        total_cost: plan.root.total_cost,
+        execution_time_ms: plan.execution_time_ms,
+        plan: plan.clone(),
+    });
+    let excess = entry
+        .snapshots
+        .len()
+        .saturating_sub(MAX_SNAPSHOTS_PER_QUERY);
+    entry.snapshots.drain(..excess);
+    let fp = entry.fingerprint.clone();
+
+    write_history_in(dir, connection_id, &mut entries)?;
+    Ok((fp, diff))
+}
+
+pub fn summarize(entry: &PlanHistoryEntry) -> PlanHistorySummary {
+    PlanHistorySummary {
+        fingerprint: entry.fingerprint.clone(),
+        query: entry.query.clone(),
+        run_count: entry.run_count,
+        last_run_at: entry.last_run_at.clone(),
+        snapshot_count: entry.snapshots.len(),
+        last_captured_at: entry.snapshots.last().map(|s| s.captured_at.clone()),
+        regressed: !entry.last_regressions.is_empty(),
+    }
+}
+
+// ---------------------------------------------------------------------------
+// App integration
+// ---------------------------------------------------------------------------
+
+fn history_dir<R: Runtime>(app: &AppHandle<R>) -> Result<PathBuf, String> {
+    Ok(crate::paths::app_config_dir(app)?.join(PLAN_HISTORY_DIR))
+}
+
+fn is_enabled() -> bool {
+    crate::config::get_cached_config()
+        .plan_history_enabled
+        .unwrap_or(true)
+}
+
+/// Store a plan produced by EXPLAIN and emit `plan-regression` when it is
+/// worse than the previous plan of the same query.
+pub fn capture<R: Runtime>(app: &AppHandle<R>, connection_id: &str, plan: &ExplainPlan) {
+    if !is_enabled() {
+        return;
+    }
+    let now = chrono::Utc::now().to_rfc3339();
+    let outcome = history_dir(app).and_then(|dir| record_plan_in(&dir, connection_id, plan, &now));
+
+    match outcome {
+        Ok((fingerprint, Some(diff))) if !diff.regressions.is_empty() => {
+            log::warn!(
+                "Plan regression on connection {}: {}",
+                connection_id,
+                diff.regressions.join("; ")
+            );
+            let alert = PlanRegressionAlert {
+                connection_id: connection_id.to_string(),
+                fingerprint,
+                query: plan.original_query.clone(),
+                diff,
+            };
+            if let Err(e) = app.emit(PLAN_REGRESSION_EVENT, &alert) {
+                log::warn!("Failed to emit {}: {}", PLAN_REGRESSION_EVENT, e);
+            }
+        }
+        Ok(_) => {}
+        Err(e) => log::warn!("Failed to record query plan: {}", e),
+    }
+}
+
+/// Count a successful run of a user query; SELECTs that are run often get
+/// their plan captured in the background.
+pub fn track_run<R: Runtime>(
+    app: AppHandle<R>,
+    connection_id: String,
+    query: String,
+    schema: Option<String>,
+) {
+    let upper = crate::drivers::common::strip_leading_sql_comments(&query).to_uppercase();
+    if !is_enabled() || !(upper.starts_with("SELECT") || upper.starts_with("WITH")) {
+        return;
+    }
+
+    tauri::async_runtime::spawn(async move {
+        let now = chrono::Utc::now().to_rfc3339();
+        let count = match history_dir(&app)
+            .and_then(|dir| count_run_in(&dir, &connection_id, &query, &now))
+        {
+            Ok(count) => count,
+            Err(e) => {
+                log::warn!("Failed to count query run: {}", e);
+                return;
+            }
+        };
+        if !should_capture(count) {
+            return;
+        }
+
+        let plan = async {
+            let saved_conn = crate::commands::find_connection_by_id(&app, &connection_id)?;
+            let expanded =
+                crate::commands::expand_ssh_connection_params(&app, &saved_conn.params).await?;
+            let params =
+                crate::commands::resolve_connection_params_with_id(&expanded, &connection_id)?;
+            let drv = crate::commands::driver_for(&saved_conn.params.driver).await?;
+            drv.explain_query(&params, &query, false, schema.as_deref())
+                .await
+        }
+        .await;
+
+        match plan {
+            Ok(plan) => capture(&app, &connection_id, &plan),
+            Err(e) => log::debug!("Skipping plan capture: {}", e),
+        }
+    });
+}
+
+pub fn remove_plan_history_for_connection<R: Runtime>(
+    app: &AppHandle<R>,
+    connection_id: &str,
+) -> Result<(), String> {
+    let path = history_path_in(&history_dir(app)?, connection_id);
+    if path.exists() {
+        fs::remove_file(path).map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
+// ---------------------------------------------------------------------------
+// Tauri commands
+// ---------------------------------------------------------------------------
+
+/// Tracked queries of a connection, most recently run first.
+#[tauri::command]
+pub fn get_plan_history<R: Runtime>(
+    app: AppHandle<R>,
+    connection_id: String,
+) -> Result<Vec<PlanHistorySummary>, String> {
+    Ok(read_history_in(&history_dir(&app)?, &connection_id)
+        .iter()
+        .map(summarize)
+        .collect())
+}
+
+/// Captured plans of one query, oldest first.
+#[tauri::command]
+pub fn get_plan_snapshots<R: Runtime>(
+    app: AppHandle<R>,
+    connection_id: String,
+    fingerprint: String,
+) -> Result<Vec<PlanSnapshot>, String> {
+    read_history_in(&history_dir(&app)?, &connection_id)
+        .into_iter()
+        .find(|e| e.fingerprint == fingerprint)
+        .map(|e| e.snapshots)
+        .ok_or_else(|| format!("No plan history for query {}", fingerprint))
+}
+
+/// Diff two captured plans of a query, by index in `get_plan_snapshots`.
+#[tauri::command]
+pub fn diff_plan_snapshots<R: Runtime>(
+    app: AppHandle<R>,
+    connection_id: String,
+    fingerprint: String,
+    from: usize,
+    to: usize,
+) -> Result<PlanDiff, String> {
+    let snapshots = get_plan_snapshots(app, connection_id, fingerprint)?;
+    let old = snapshots
+        .get(from)
+        .ok_or_else(|| format!("Plan snapshot {} not found", from))?;
+    let new = snapshots
+        .get(to)
+        .ok_or_else(|| format!("Plan snapshot {} not found", to))?;
+    Ok(diff_plans(&old.plan, &new.plan))
+}
+
+#[tauri::command]
+pub fn clear_plan_history<R: Runtime>(
+    app: AppHandle<R>,
+    connection_id: String,
+) -> Result<(), String> {
+    remove_plan_history_for_connection(&app, &connection_id)
+}
@@ -0,0 +1,203 @@
+#[cfg(test)]
+mod tests {
+    use crate::models::{ExplainNode, ExplainPlan};
+    use crate::plan_history::{
+        classify_access, count_run_in, diff_plans, fingerprint, normalize_query, read_history_in,
+        record_plan_in, should_capture, summarize, AccessKind, DiffKind, MAX_SNAPSHOTS_PER_QUERY,
+    };
+    use std::collections::HashMap;
+    use tempfile::TempDir;
+
+    fn node(node_type: &str, relation: Option<&str>, children: Vec<ExplainNode>) -> ExplainNode {
+        ExplainNode {
+            id: node_type.to_string(),
+            node_type: node_type.to_string(),
+            relation: relation.map(str::to_string),
+            startup_cost: None,
+            total_cost: None,
+            plan_rows: None,
+            actual_rows: None,
+            actual_time_ms: None,
+            actual_loops: None,
+            buffers_hit: None,
+            buffers_read: None,
+            filter: None,
+            index_condition: None,
+            join_type: None,
+            hash_condition: None,
+            extra: HashMap::new(),
+            children,
+        }
+    }
+
+    fn plan(query: &str, root: ExplainNode, cost: f64) -> ExplainPlan {
+        let mut root = root;
+        root.total_cost = Some(cost);
+        ExplainPlan {
+            root,
+            planning_time_ms: None,
+            execution_time_ms: None,
+            original_query: query.to_string(),
+            driver: "postgres".to_string(),
+            has_analyze_data: false,
+            raw_output: None,
+        }
+    }
+
+    fn indexed(query: &str, cost: f64) -> ExplainPlan {
+        plan(
+            query,
+            node(
+                "Nested Loop",
+                None,
+                vec![
+                    node("Index Scan", Some("orders"), vec![]),
+                    node("Index Scan", Some("users"), vec![]),
+                ],
+            ),
+            cost,
+        )
+    }
+
+    fn seq_scan(query: &str, cost: f64) -> ExplainPlan {
+        plan(
+            query,
+            node(
+                "Nested Loop",
+                None,
+                vec![
+                    node("Seq Scan", Some("orders"), vec![]),
+                    node("Index Scan", Some("users"), vec![]),
+                ],
+            ),
+            cost,
+        )
+    }
+
+    // --- Fingerprints ---
+
+    #[test]
+    fn normalize_replaces_literals_and_whitespace() {
+        assert_eq!(
+            normalize_query("SELECT *\n  FROM t1 WHERE id = 42 AND name = 'O''Brien';"),
+            "select * from t1 where id = ? and name = ?"
+        );
+    }
+
+    #[test]
+    fn fingerprint_groups_parameter_variants() {
+        assert_eq!(
+            fingerprint("SELECT * FROM users WHERE id = 1"),
+            fingerprint("select *  from users where id = 999")
+        );
+        assert_ne!(
+            fingerprint("SELECT * FROM users WHERE id = 1"),
+            fingerprint("SELECT * FROM orders WHERE id = 1")
+        );
+    }
+
+    // --- Plan comparison ---
+
+    #[test]
+    fn classify_access_across_drivers() {
+        assert_eq!(
+            classify_access(&node("Seq Scan", None, vec![])),
+            AccessKind::FullScan
+        );
+        assert_eq!(
+            classify_access(&node("Full Table Scan", None, vec![])),
+            AccessKind::FullScan
+        );
+        assert_eq!(
+            classify_access(&node("Scan", None, vec![])),
+            AccessKind::FullScan
+        );
+        assert_eq!(
+            classify_access(&node("Index Lookup", None, vec![])),
+            AccessKind::Index
+        );
+        assert_eq!(
+            classify_access(&node("Hash Join", None, vec![])),
+            AccessKind::Other
+        );
+    }
+
+    #[test]
+    fn seq_scan_replacing_index_is_a_regression() {
+        let diff = diff_plans(&indexed("q", 10.0), &seq_scan("q", 12.0));
+
+        assert!(diff.changed);
+        assert_eq!(diff.regressions.len(), 1);
+        assert!(diff.regressions[0].contains("orders"));
+        let kinds: Vec<DiffKind> = diff.lines.iter().map(|l| l.kind).collect();
+        assert!(kinds.contains(&DiffKind::Added));
+        assert!(kinds.contains(&DiffKind::Removed));
+    }
+
+    #[test]
+    fn cost_jump_is_a_regression() {
+        let diff = diff_plans(&indexed("q", 10.0), &indexed("q", 50.0));
+
+        assert!(!diff.changed);
+        assert_eq!(diff.cost_ratio, Some(5.0));
+        assert_eq!(diff.regressions, vec!["Estimated cost grew 5.0x"]);
+    }
+
+    #[test]
+    fn identical_plans_have_no_regressions() {
+        let diff = diff_plans(&indexed("q", 10.0), &indexed("q", 11.0));
+        assert!(!diff.changed);
+        assert!(diff.regressions.is_empty());
+        assert!(diff.lines.iter().all(|l| l.kind == DiffKind::Same));
+    }
+
+    #[test]
+    fn capture_schedule() {
+        let captured: Vec<u64> = (1..=30).filter(|n| should_capture(*n)).collect();
+        assert_eq!(captured, vec![3, 13, 23]);
+    }
+
+    // --- Storage ---
+
+    #[test]
+    fn record_plan_diffs_against_previous_snapshot() {
+        let tmp = TempDir::new().unwrap();
+        let query = "SELECT * FROM orders WHERE id = 1";
+
+        let (fp, first) = record_plan_in(tmp.path(), "c1", &indexed(query, 10.0), "t1").unwrap();
+        assert!(first.is_none());
+
+        let (_, second) = record_plan_in(tmp.path(), "c1", &seq_scan(query, 10.0), "t2").unwrap();
+        assert_eq!(second.unwrap().regressions.len(), 1);
+
+        let entries = read_history_in(tmp.path(), "c1");
+        assert_eq!(entries.len(), 1);
+        let summary = summarize(&entries[0]);
+        assert_eq!(summary.fingerprint, fp);
+        assert_eq!(summary.snapshot_count, 2);
+        assert!(summary.regressed);
+    }
+
+    #[test]
+    fn snapshots_are_capped() {
+        let tmp = TempDir::new().unwrap();
+        for i in 0..MAX_SNAPSHOTS_PER_QUERY + 3 {
+            record_plan_in(tmp.path(), "c1", &indexed("q", 10.0), &format!("t{:02}", i)).unwrap();
+        }
+
+        let entries = read_history_in(tmp.path(), "c1");
+        assert_eq!(entries[0].snapshots.len(), MAX_SNAPSHOTS_PER_QUERY);
+        assert_eq!(entries[0].snapshots[0].captured_at, "t03");
+    }
+
+    #[test]
+    fn count_run_groups_by_fingerprint() {
+        let tmp = TempDir::new().unwrap();
+        assert_eq!(count_run_in(tmp.path(), "c1", "SELECT 1", "t1").unwrap(), 1);
+        assert_eq!(
+            count_run_in(tmp.path(), "c1", "select  2", "t2").unwrap(),
+            2
+        );
+        assert_eq!(count_run_in(tmp.path(), "c2", "SELECT 1", "t3").unwrap(), 1);
+    }
+}
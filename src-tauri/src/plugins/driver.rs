@@ -2,32 +2,45 @@ use std::collections::HashMap;
 use std::path::PathBuf;
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
+use std::time::Duration;
 
 use async_trait::async_trait;
 use serde_json::{json, Value};
 use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
-use tokio::process::Command;
+use tokio::process::{Child, ChildStdin, Command};
 use tokio::sync::{mpsc, oneshot};
 
-use crate::drivers::driver_trait::{DatabaseDriver, PluginManifest};
+use crate::drivers::driver_trait::{insert_rows_individually, DatabaseDriver, PluginManifest};
 use crate::models::{
-    ColumnDefinition, ConnectionParams, DataTypeInfo, ExplainPlan, ForeignKey, Index, QueryResult,
-    RoutineInfo, RoutineParameter, TableColumn, TableInfo, TableSchema, ViewInfo,
+    BatchInsertResult, ColumnDefinition, ConnectionParams, DataTypeInfo, DbUser, ExplainPlan,
+    ForeignKey, Index, PrivilegeChange, QueryResult, RoutineInfo, RoutineParameter, ServerValue,
+    TableColumn, TableInfo, TableSchema, TableStats, TriggerInfo, UserGrant, ViewInfo,
 };
-use crate::plugins::rpc::{JsonRpcRequest, JsonRpcResponse};
+use crate::plugins::rpc::{JsonRpcNotification, JsonRpcRequest, JsonRpcResponse};
+
+/// How long a plugin may take to exit after the `shutdown` notification
+/// before its process is killed.
+const PLUGIN_SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// Messages queued for the plugin's stdin.
+enum Outgoing {
+    Request(JsonRpcRequest, oneshot::Sender<Result<Value, String>>),
+    Notification(JsonRpcNotification),
+}
 
 pub struct PluginProcess {
-    sender: mpsc::Sender<(JsonRpcRequest, oneshot::Sender<Result<Value, String>>)>,
+    sender: mpsc::Sender<Outgoing>,
     next_id: AtomicU64,
-    shutdown_tx: tokio::sync::Mutex<Option<oneshot::Sender<()>>>,
+    /// Asks the management task to stop the child; it answers on the
+    /// enclosed sender once the child is gone.
+    shutdown_tx: tokio::sync::Mutex<Option<oneshot::Sender<oneshot::Sender<()>>>>,
     pub pid: Option<u32>,
 }
 
 impl PluginProcess {
     async fn new(executable_path: PathBuf, interpreter: Option<String>) -> Result<Self, String> {
-        let (tx, rx) =
-            mpsc::channel::<(JsonRpcRequest, oneshot::Sender<Result<Value, String>>)>(100);
-        let (shutdown_tx, shutdown_rx) = oneshot::channel::<()>();
+        let (tx, rx) = mpsc::channel::<Outgoing>(100);
+        let (shutdown_tx, shutdown_rx) = oneshot::channel::<oneshot::Sender<()>>();
 
         // Spawn the child process directly in the async context so that any
         // spawn failure is immediately propagated as an error (no silent panic).
@@ -68,14 +81,17 @@ impl PluginProcess {
 
             loop {
                 tokio::select! {
-                    _ = &mut shutdown_rx => {
-                        log::info!("Plugin process shutdown requested, terminating child");
-                        let _ = child.kill().await;
+                    done = &mut shutdown_rx => {
+                        log::info!("Plugin process shutdown requested, asking child to exit");
+                        stop_child(&mut child, stdin).await;
+                        if let Ok(done) = done {
+                            let _ = done.send(());
+                        }
                         break;
                     }
                     msg = rx.recv() => {
                         match msg {
-                            Some((req, resp_tx)) => {
+                            Some(Outgoing::Request(req, resp_tx)) => {
                                 let id = req.id;
                                 pending_requests.insert(id, resp_tx);
 
@@ -89,6 +105,14 @@ impl PluginProcess {
                                     }
                                 }
                             }
+                            Some(Outgoing::Notification(note)) => {
+                                let mut note_str = serde_json::to_string(&note).unwrap();
+                                note_str.push('\n');
+
+                                if let Err(e) = stdin.write_all(note_str.as_bytes()).await {
+                                    log::error!("Failed to write to plugin stdin: {}", e);
+                                }
+                            }
                             None => {
                                 // Channel closed without explicit shutdown — kill the process anyway.
                                 log::warn!("Plugin process channel closed without shutdown signal, terminating child");
@@ -139,15 +163,42 @@ impl PluginProcess {
         })
     }
 
+    /// Stop the child and wait until it is gone. Waits at most
+    /// [`PLUGIN_SHUTDOWN_TIMEOUT`] for a clean exit before killing it.
     async fn shutdown(&self) {
-        let mut guard = self.shutdown_tx.lock().await;
-        if let Some(tx) = guard.take() {
-            let _ = tx.send(());
+        let tx = self.shutdown_tx.lock().await.take();
+        if let Some(tx) = tx {
+            let (done_tx, done_rx) = oneshot::channel();
+            // A failed send means the management task already ended along
+            // with the child.
+            if tx.send(done_tx).is_ok() {
+                let _ = done_rx.await;
+            }
         }
     }
 
     async fn call(&self, method: &str, params: Value) -> Result<Value, String> {
         let id = self.next_id.fetch_add(1, Ordering::SeqCst);
+        self.send_request(id, method, params).await
+    }
+
+    /// Like `call`, but when the returned future is dropped before the
+    /// plugin answers (the query task was aborted by `cancel_query`), the
+    /// plugin gets a `cancel_query` notification carrying the request id so
+    /// it can interrupt the statement instead of running it to completion.
+    async fn call_cancellable(&self, method: &str, params: Value) -> Result<Value, String> {
+        let id = self.next_id.fetch_add(1, Ordering::SeqCst);
+        let mut guard = CancelOnDrop {
+            sender: self.sender.clone(),
+            id,
+            armed: true,
+        };
+        let result = self.send_request(id, method, params).await;
+        guard.armed = false;
+        result
+    }
+
+    async fn send_request(&self, id: u64, method: &str, params: Value) -> Result<Value, String> {
         let req = JsonRpcRequest {
             jsonrpc: "2.0".to_string(),
             method: method.to_string(),
@@ -157,7 +208,7 @@ impl PluginProcess {
 
         let (tx, rx) = oneshot::channel();
         self.sender
-            .send((req, tx))
+            .send(Outgoing::Request(req, tx))
             .await
             .map_err(|_| "Plugin process channel closed".to_string())?;
 
@@ -166,6 +217,58 @@ impl PluginProcess {
     }
 }
 
+/// Send the `shutdown` notification and close stdin, then give the child
+/// [`PLUGIN_SHUTDOWN_TIMEOUT`] to exit on its own before killing it.
+async fn stop_child(child: &mut Child, mut stdin: ChildStdin) {
+    let note = JsonRpcNotification {
+        jsonrpc: "2.0".to_string(),
+        method: "shutdown".to_string(),
+        params: json!({}),
+    };
+    let mut note_str = serde_json::to_string(&note).unwrap();
+    note_str.push('\n');
+    if let Err(e) = stdin.write_all(note_str.as_bytes()).await {
+        log::warn!("Failed to send shutdown to plugin: {}", e);
+    }
+    // Closing stdin is the end-of-input signal for plugins that do not
+    // know the notification.
+    drop(stdin);
+
+    match tokio::time::timeout(PLUGIN_SHUTDOWN_TIMEOUT, child.wait()).await {
+        Ok(Ok(status)) => log::info!("Plugin process exited with {}", status),
+        Ok(Err(e)) => log::warn!("Failed to wait for plugin process: {}", e),
+        Err(_) => {
+            log::warn!("Plugin process did not exit in time, terminating child");
+            let _ = child.kill().await;
+        }
+    }
+}
+
+/// Sends `cancel_query` for request `id` when dropped while still armed.
+struct CancelOnDrop {
+    sender: mpsc::Sender<Outgoing>,
+    id: u64,
+    armed: bool,
+}
+
+impl Drop for CancelOnDrop {
+    fn drop(&mut self) {
+        if !self.armed {
+            return;
+        }
+        let note = JsonRpcNotification {
+            jsonrpc: "2.0".to_string(),
+            method: "cancel_query".to_string(),
+            params: json!({ "id": self.id }),
+        };
+        // Drop cannot await; a full queue only means the plugin finishes
+        // the query on its own.
+        if self.sender.try_send(Outgoing::Notification(note)).is_err() {
+            log::warn!("Could not send cancel_query for plugin request {}", self.id);
+        }
+    }
+}
+
 pub struct RpcDriver {
     manifest: PluginManifest,
     process: Arc<PluginProcess>,
@@ -438,6 +541,158 @@ impl DatabaseDriver for RpcDriver {
         serde_json::from_value(res).map_err(|e| e.to_string())
     }
 
+    async fn get_triggers(
+        &self,
+        params: &ConnectionParams,
+        schema: Option<&str>,
+    ) -> Result<Vec<TriggerInfo>, String> {
+        let res = self
+            .process
+            .call(
+                "get_triggers",
+                json!({ "params": params, "schema": schema }),
+            )
+            .await?;
+        serde_json::from_value(res).map_err(|e| e.to_string())
+    }
+
+    async fn get_trigger_definition(
+        &self,
+        params: &ConnectionParams,
+        trigger_name: &str,
+        table_name: &str,
+        schema: Option<&str>,
+    ) -> Result<String, String> {
+        let res = self.process.call("get_trigger_definition", json!({ "params": params, "trigger_name": trigger_name, "table_name": table_name, "schema": schema })).await?;
+        serde_json::from_value(res).map_err(|e| e.to_string())
+    }
+
+    async fn create_trigger(
+        &self,
+        params: &ConnectionParams,
+        trigger_sql: &str,
+        schema: Option<&str>,
+    ) -> Result<(), String> {
+        let res = self
+            .process
+            .call(
+                "create_trigger",
+                json!({ "params": params, "trigger_sql": trigger_sql, "schema": schema }),
+            )
+            .await?;
+        serde_json::from_value(res).map_err(|e| e.to_string())
+    }
+
+    async fn drop_trigger(
+        &self,
+        params: &ConnectionParams,
+        trigger_name: &str,
+        table_name: &str,
+        schema: Option<&str>,
+    ) -> Result<(), String> {
+        let res = self.process.call("drop_trigger", json!({ "params": params, "trigger_name": trigger_name, "table_name": table_name, "schema": schema })).await?;
+        serde_json::from_value(res).map_err(|e| e.to_string())
+    }
+
+    async fn get_server_version(&self, params: &ConnectionParams) -> Result<String, String> {
+        let res = self
+            .process
+            .call("get_server_version", json!({ "params": params }))
+            .await?;
+        serde_json::from_value(res).map_err(|e| e.to_string())
+    }
+
+    async fn get_server_status(
+        &self,
+        params: &ConnectionParams,
+    ) -> Result<Vec<ServerValue>, String> {
+        let res = self
+            .process
+            .call("get_server_status", json!({ "params": params }))
+            .await?;
+        serde_json::from_value(res).map_err(|e| e.to_string())
+    }
+
+    async fn get_server_variables(
+        &self,
+        params: &ConnectionParams,
+    ) -> Result<Vec<ServerValue>, String> {
+        let res = self
+            .process
+            .call("get_server_variables", json!({ "params": params }))
+            .await?;
+        serde_json::from_value(res).map_err(|e| e.to_string())
+    }
+
+    async fn get_table_stats(
+        &self,
+        params: &ConnectionParams,
+        schema: Option<&str>,
+    ) -> Result<Vec<TableStats>, String> {
+        let res = self
+            .process
+            .call(
+                "get_table_stats",
+                json!({ "params": params, "schema": schema }),
+            )
+            .await?;
+        serde_json::from_value(res).map_err(|e| e.to_string())
+    }
+
+    async fn get_users(&self, params: &ConnectionParams) -> Result<Vec<DbUser>, String> {
+        let res = self
+            .process
+            .call("get_users", json!({ "params": params }))
+            .await?;
+        serde_json::from_value(res).map_err(|e| e.to_string())
+    }
+
+    async fn get_user_grants(
+        &self,
+        params: &ConnectionParams,
+        user: &str,
+        host: Option<&str>,
+    ) -> Result<Vec<UserGrant>, String> {
+        let res = self
+            .process
+            .call(
+                "get_user_grants",
+                json!({ "params": params, "user": user, "host": host }),
+            )
+            .await?;
+        serde_json::from_value(res).map_err(|e| e.to_string())
+    }
+
+    async fn grant_privilege(
+        &self,
+        params: &ConnectionParams,
+        change: &PrivilegeChange,
+    ) -> Result<(), String> {
+        let res = self
+            .process
+            .call(
+                "grant_privilege",
+                json!({ "params": params, "change": change }),
+            )
+            .await?;
+        serde_json::from_value(res).map_err(|e| e.to_string())
+    }
+
+    async fn revoke_privilege(
+        &self,
+        params: &ConnectionParams,
+        change: &PrivilegeChange,
+    ) -> Result<(), String> {
+        let res = self
+            .process
+            .call(
+                "revoke_privilege",
+                json!({ "params": params, "change": change }),
+            )
+            .await?;
+        serde_json::from_value(res).map_err(|e| e.to_string())
+    }
+
     async fn execute_query(
         &self,
         params: &ConnectionParams,
@@ -446,10 +701,40 @@ impl DatabaseDriver for RpcDriver {
         page: u32,
         schema: Option<&str>,
     ) -> Result<QueryResult, String> {
-        let res = self.process.call("execute_query", json!({ "params": params, "query": query, "limit": limit, "page": page, "schema": schema })).await?;
+        let res = self.process.call_cancellable("execute_query", json!({ "params": params, "query": query, "limit": limit, "page": page, "schema": schema })).await?;
         serde_json::from_value(res).map_err(|e| e.to_string())
     }
 
+    async fn execute_query_arrow(
+        &self,
+        params: &ConnectionParams,
+        query: &str,
+        limit: Option<u32>,
+        page: u32,
+        schema: Option<&str>,
+    ) -> Result<Vec<u8>, String> {
+        if self.manifest.capabilities.arrow {
+            // The plugin answers with the IPC stream as a base64 string.
+            match self.process.call_cancellable("execute_query_arrow", json!({ "params": params, "query": query, "limit": limit, "page": page, "schema": schema })).await {
+                Ok(res) => {
+                    use base64::Engine;
+                    let encoded: String =
+                        serde_json::from_value(res).map_err(|e| e.to_string())?;
+                    return base64::engine::general_purpose::STANDARD
+                        .decode(encoded)
+                        .map_err(|e| format!("Invalid Arrow payload from plugin: {}", e));
+                }
+                Err(e) if e.contains("Method not found") || e.contains("not implemented") => {}
+                Err(e) => return Err(e),
+            }
+        }
+        // Fallback for plugins without Arrow support: convert the JSON result
+        let result = self
+            .execute_query(params, query, limit, page, schema)
+            .await?;
+        crate::arrow_ipc::result_to_ipc(&result)
+    }
+
     async fn explain_query(
         &self,
         params: &ConnectionParams,
@@ -479,6 +764,27 @@ impl DatabaseDriver for RpcDriver {
         serde_json::from_value(res).map_err(|e| e.to_string())
     }
 
+    async fn insert_records_batch(
+        &self,
+        params: &ConnectionParams,
+        table: &str,
+        rows: Vec<HashMap<String, serde_json::Value>>,
+        schema: Option<&str>,
+        max_blob_size: u64,
+    ) -> Result<BatchInsertResult, String> {
+        match self.process.call("insert_records_batch", json!({ "params": params, "table": table, "rows": rows, "schema": schema, "max_blob_size": max_blob_size })).await {
+            Ok(res) => serde_json::from_value(res).map_err(|e| e.to_string()),
+            Err(e) if e.contains("Method not found") || e.contains("not implemented") => {
+                // Fallback for plugins without batch support: one insert_record per row
+                Ok(
+                    insert_rows_individually(self, params, table, &rows, 0, schema, max_blob_size)
+                        .await,
+                )
+            }
+            Err(e) => Err(e),
+        }
+    }
+
     async fn update_record(
         &self,
         params: &ConnectionParams,
@@ -2,7 +2,6 @@ use std::fs;
 use std::io::Read;
 use std::path::{Path, PathBuf};
 
-use directories::ProjectDirs;
 use serde::{Deserialize, Serialize};
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
@@ -22,9 +21,9 @@ struct InstalledPluginManifest {
 }
 
 pub fn get_plugins_dir() -> Result<PathBuf, String> {
-    let proj_dirs = ProjectDirs::from("com", "debba", "tabularis")
-        .ok_or_else(|| "Could not determine project directories".to_string())?;
-    let plugins_dir = proj_dirs.data_dir().join("plugins");
+    let plugins_dir = crate::paths::get_app_data_dir()
+        .ok_or_else(|| "Could not determine project directories".to_string())?
+        .join("plugins");
     if !plugins_dir.exists() {
         fs::create_dir_all(&plugins_dir)
             .map_err(|e| format!("Failed to create plugins directory: {}", e))?;
@@ -3,7 +3,6 @@ use std::fs;
 use std::path::Path;
 use std::sync::Mutex;
 
-use directories::ProjectDirs;
 use once_cell::sync::Lazy;
 use serde::{Deserialize, Serialize};
 use tauri::AppHandle;
@@ -66,12 +65,12 @@ pub async fn load_plugins<R: tauri::Runtime>(app: &AppHandle<R>, enabled_ids: Op
         .plugins
         .unwrap_or_default();
 
-    let proj_dirs = match ProjectDirs::from("com", "debba", "tabularis") {
+    let data_dir = match crate::paths::get_app_data_dir() {
         Some(d) => d,
         None => return,
     };
 
-    let plugins_dir = proj_dirs.data_dir().join("plugins");
+    let plugins_dir = data_dir.join("plugins");
 
     if !plugins_dir.exists() {
         if let Err(e) = fs::create_dir_all(&plugins_dir) {
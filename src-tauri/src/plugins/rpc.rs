@@ -9,6 +9,14 @@ pub struct JsonRpcRequest {
     pub id: u64,
 }
 
+/// A request without an `id`: the plugin must not answer it.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct JsonRpcNotification {
+    pub jsonrpc: String,
+    pub method: String,
+    pub params: Value,
+}
+
 #[derive(Serialize, Deserialize, Debug)]
 pub struct JsonRpcError {
     pub code: i32,
@@ -3,6 +3,7 @@ use std::fs;
 use tempfile::tempdir;
 
 use super::installer::read_plugin_info_from_dir;
+use super::rpc::JsonRpcNotification;
 
 #[test]
 fn reads_installed_plugin_info_from_manifest() {
@@ -37,3 +38,18 @@ fn returns_error_for_invalid_manifest() {
 
     assert!(error.contains("Failed to parse plugin manifest"));
 }
+
+#[test]
+fn notification_serializes_without_id() {
+    let note = JsonRpcNotification {
+        jsonrpc: "2.0".to_string(),
+        method: "cancel_query".to_string(),
+        params: serde_json::json!({ "id": 7 }),
+    };
+
+    let value = serde_json::to_value(&note).expect("serialize notification");
+
+    assert_eq!(value["method"], "cancel_query");
+    assert_eq!(value["params"]["id"], 7);
+    assert!(value.get("id").is_none());
+}
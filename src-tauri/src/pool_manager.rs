@@ -1,15 +1,23 @@
-use crate::models::ConnectionParams;
-use deadpool_postgres::{Manager as PgPoolManager, Pool as PgPool};
+use crate::models::{ConnectionParams, PoolSettings, ReplicaEndpoint, SavedConnection};
+use deadpool_postgres::{
+    Manager as PgPoolManager, ManagerConfig as PgManagerConfig, Pool as PgPool, RecyclingMethod,
+};
 use once_cell::sync::Lazy;
+use rustls::pki_types::{CertificateDer, PrivateKeyDer};
 use rustls::{ClientConfig, RootCertStore};
 use rustls_platform_verifier::BuilderVerifierExt;
+use serde::Serialize;
 use sqlx::{sqlite::SqliteConnectOptions, MySql, Pool, Sqlite};
 use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
 use std::time::Duration;
-use tokio::sync::RwLock;
+use tiberius::{AuthMethod, Config as MssqlConfig, EncryptionLevel};
+use tokio::net::TcpStream;
+use tokio::sync::{oneshot, RwLock};
 use tokio_postgres::{config::SslMode as PgSslMode, Config as PgConfig};
 use tokio_postgres_rustls::MakeRustlsConnect;
+use tokio_util::compat::{Compat, TokioAsyncWriteCompatExt};
 
 /// `tokio_postgres` renders only the top-level error kind ("error performing
 /// TLS handshake"); the concrete cause lives in the `source()` chain.
@@ -25,7 +33,8 @@ pub(crate) fn format_error_chain<E: std::error::Error + ?Sized>(err: &E) -> Stri
 }
 
 /// rustls 0.23 needs a process-level `CryptoProvider`; install once.
-fn ensure_rustls_crypto_provider() {
+/// Called at startup (sqlx connections) and again before building Postgres pools.
+pub(crate) fn ensure_rustls_crypto_provider() {
     use std::sync::Once;
     static INSTALL: Once = Once::new();
     INSTALL.call_once(|| {
@@ -35,10 +44,22 @@ fn ensure_rustls_crypto_provider() {
 
 type PoolMap<T> = Arc<RwLock<HashMap<String, Pool<T>>>>;
 type PgPoolMap = Arc<RwLock<HashMap<String, PgPool>>>;
+type MssqlPoolMap = Arc<RwLock<HashMap<String, MssqlPool>>>;
+
+/// A SQL Server connection: TDS over a tokio TCP stream.
+pub type MssqlClient = tiberius::Client<Compat<TcpStream>>;
+pub type MssqlPool = deadpool::managed::Pool<MssqlManager>;
 
 static MYSQL_POOLS: Lazy<PoolMap<MySql>> = Lazy::new(|| Arc::new(RwLock::new(HashMap::new())));
 static POSTGRES_POOLS: Lazy<PgPoolMap> = Lazy::new(|| Arc::new(RwLock::new(HashMap::new())));
 static SQLITE_POOLS: Lazy<PoolMap<Sqlite>> = Lazy::new(|| Arc::new(RwLock::new(HashMap::new())));
+static MSSQL_POOLS: Lazy<MssqlPoolMap> = Lazy::new(|| Arc::new(RwLock::new(HashMap::new())));
+
+/// Default keep-alive interval in seconds.
+pub const DEFAULT_KEEPALIVE_INTERVAL: u32 = 60;
+
+static KEEPALIVE_STOP_TX: Lazy<tokio::sync::Mutex<Option<oneshot::Sender<()>>>> =
+    Lazy::new(|| tokio::sync::Mutex::new(None));
 
 const DEFAULT_MYSQL_CONNECT_TIMEOUT_MS: u64 = 60_000;
 const DEFAULT_MYSQL_TIMEZONE: &str = "SYSTEM";
@@ -68,6 +89,181 @@ fn mysql_numeric_setting(key: &str, default: u64) -> u64 {
         .unwrap_or(default)
 }
 
+/// Marks the pool id of a reader endpoint: `<connection id>:replica:<n>`.
+const REPLICA_ID_MARKER: &str = ":replica:";
+
+/// Round-robin position over reader endpoints, shared by all connections.
+static REPLICA_CURSOR: AtomicUsize = AtomicUsize::new(0);
+
+// ---------------------------------------------------------------------------
+// Read/write splitting
+// ---------------------------------------------------------------------------
+
+/// Whether `query` may run on a read replica. Only statements classified as
+/// plain reads qualify; locking reads, `SELECT ... INTO` and `EXPLAIN
+/// ANALYZE` (which executes the statement) stay on the writer.
+pub fn is_replica_safe(query: &str) -> bool {
+    if crate::ai_activity::classify_query_kind(query) != "select" {
+        return false;
+    }
+    let upper = query.to_uppercase();
+    let words: Vec<&str> = upper
+        .split(|c: char| !c.is_ascii_alphanumeric() && c != '_')
+        .filter(|w| !w.is_empty())
+        .collect();
+    let has = |kw: &str| words.contains(&kw);
+    let locking = words
+        .windows(2)
+        .any(|w| w[0] == "FOR" && matches!(w[1], "UPDATE" | "SHARE" | "NO" | "KEY"));
+    !(locking || has("INTO") || (words.first() == Some(&"EXPLAIN") && has("ANALYZE")))
+}
+
+/// Pool id of the `index`-th reader of `connection_id`.
+pub fn replica_connection_id(connection_id: &str, index: usize) -> String {
+    format!("{}{}{}", connection_id, REPLICA_ID_MARKER, index)
+}
+
+/// `params` pointed at a reader endpoint.
+pub fn replica_params(params: &ConnectionParams, endpoint: &ReplicaEndpoint) -> ConnectionParams {
+    let mut routed = params.clone();
+    routed.host = Some(endpoint.host.clone());
+    if endpoint.port.is_some() {
+        routed.port = endpoint.port;
+    }
+    routed
+}
+
+/// Pick the endpoint for `query` on `conn`: the connection params to use
+/// and the pool id to resolve them with. Reads go round-robin to the
+/// readers unless `use_primary` is set.
+pub fn route_query(
+    conn: &SavedConnection,
+    connection_id: &str,
+    query: &str,
+    use_primary: bool,
+) -> (ConnectionParams, String) {
+    let replicas = conn.read_replicas.as_deref().unwrap_or_default();
+    if use_primary || replicas.is_empty() || !is_replica_safe(query) {
+        return (conn.params.clone(), connection_id.to_string());
+    }
+    let index = REPLICA_CURSOR.fetch_add(1, Ordering::Relaxed) % replicas.len();
+    log::debug!(
+        "Routing read on {} to replica {} ({})",
+        connection_id,
+        index,
+        replicas[index].host
+    );
+    (
+        replica_params(&conn.params, &replicas[index]),
+        replica_connection_id(connection_id, index),
+    )
+}
+
+// ---------------------------------------------------------------------------
+// Pool settings
+// ---------------------------------------------------------------------------
+
+/// Pool settings with the fallbacks applied. Timeouts left `None` keep the
+/// pool library's default: none for deadpool, 30 s to acquire and 10 min
+/// idle for sqlx.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ResolvedPoolSettings {
+    pub max_connections: u32,
+    pub min_connections: u32,
+    pub acquire_timeout: Option<Duration>,
+    pub idle_timeout: Option<Duration>,
+    pub statement_timeout_ms: Option<u64>,
+}
+
+/// Resolve the pool settings of a connection: its own fields first, then
+/// the app-wide `defaults`, then the driver's. `min_connections` never
+/// exceeds `max_connections`, and a timeout of 0 counts as unset.
+pub fn resolve_pool_settings(
+    driver: &str,
+    own: Option<&PoolSettings>,
+    defaults: Option<&PoolSettings>,
+) -> ResolvedPoolSettings {
+    fn pick<T: Copy>(
+        own: Option<&PoolSettings>,
+        defaults: Option<&PoolSettings>,
+        field: fn(&PoolSettings) -> Option<T>,
+    ) -> Option<T> {
+        own.and_then(field).or_else(|| defaults.and_then(field))
+    }
+    let default_max = if driver == "sqlite" {
+        // SQLite has lower concurrency needs
+        5
+    } else {
+        10
+    };
+    let max_connections = pick(own, defaults, |s| s.max_connections)
+        .unwrap_or(default_max)
+        .max(1);
+    let timeout = |secs: Option<u64>| secs.filter(|&s| s > 0).map(Duration::from_secs);
+    ResolvedPoolSettings {
+        max_connections,
+        min_connections: pick(own, defaults, |s| s.min_connections)
+            .unwrap_or(0)
+            .min(max_connections),
+        acquire_timeout: timeout(pick(own, defaults, |s| s.acquire_timeout_secs)),
+        idle_timeout: timeout(pick(own, defaults, |s| s.idle_timeout_secs)),
+        statement_timeout_ms: pick(own, defaults, |s| s.statement_timeout_ms).filter(|&ms| ms > 0),
+    }
+}
+
+fn pool_settings(params: &ConnectionParams) -> ResolvedPoolSettings {
+    let config = crate::config::get_cached_config();
+    resolve_pool_settings(
+        &params.driver,
+        params.pool.as_ref(),
+        config.pool_defaults.as_ref(),
+    )
+}
+
+/// Build a deadpool pool with `settings`. deadpool has no idle timeout of
+/// its own: connections idle for longer are dropped on checkout instead of
+/// recycled.
+fn build_deadpool<M: deadpool::managed::Manager>(
+    manager: M,
+    settings: &ResolvedPoolSettings,
+) -> Result<deadpool::managed::Pool<M>, deadpool::managed::BuildError> {
+    let mut builder = deadpool::managed::Pool::builder(manager)
+        .max_size(settings.max_connections as usize)
+        .runtime(deadpool::Runtime::Tokio1)
+        .wait_timeout(settings.acquire_timeout);
+    if let Some(idle) = settings.idle_timeout {
+        builder = builder.pre_recycle(deadpool::managed::Hook::sync_fn(move |_, metrics| {
+            if metrics.last_used() > idle {
+                Err(deadpool::managed::HookError::Message("idle timeout".into()))
+            } else {
+                Ok(())
+            }
+        }));
+    }
+    builder.build()
+}
+
+/// Open `min_connections` connections of a new deadpool pool in the
+/// background; deadpool opens connections only on demand.
+fn warm_deadpool<M: deadpool::managed::Manager + 'static>(
+    pool: &deadpool::managed::Pool<M>,
+    min_connections: u32,
+) {
+    if min_connections == 0 {
+        return;
+    }
+    let pool = pool.clone();
+    tokio::spawn(async move {
+        let mut held = Vec::with_capacity(min_connections as usize);
+        for _ in 0..min_connections {
+            match pool.get().await {
+                Ok(conn) => held.push(conn),
+                Err(_) => break,
+            }
+        }
+    });
+}
+
 /// Build a stable connection key that works with SSH tunnels.
 /// If connection_id is provided (from saved connections), use it for stable pooling.
 /// Otherwise fall back to host:port:database (for ad-hoc connections).
@@ -123,13 +319,13 @@ fn build_mysql_options(
     options = options.ssl_mode(ssl_mode);
 
     // Apply SSL certificates if provided in params
-    if let Some(ca) = &params.ssl_ca {
+    if let Some(ca) = ssl_file(&params.ssl_ca) {
         options = options.ssl_ca(ca);
     }
-    if let Some(cert) = &params.ssl_cert {
+    if let Some(cert) = ssl_file(&params.ssl_cert) {
         options = options.ssl_client_cert(cert);
     }
-    if let Some(key) = &params.ssl_key {
+    if let Some(key) = ssl_file(&params.ssl_key) {
         options = options.ssl_client_key(key);
     }
 
@@ -144,24 +340,75 @@ fn build_postgres_configurations(params: &ConnectionParams) -> PgConfig {
         .host(params.host.as_deref().unwrap_or_default())
         .dbname(&format!("{}", params.database));
 
-    if let Some(ssl_mode) = params.ssl_mode.as_deref() {
-        match ssl_mode {
-            "disable" => {
-                cfg.ssl_mode(PgSslMode::Disable);
-            }
-            "require" => {
-                cfg.ssl_mode(PgSslMode::Require);
-            }
-            "prefer" => {
-                cfg.ssl_mode(PgSslMode::Prefer);
-            }
-            _ => {}
-        };
+    if let Some(ssl_mode) = postgres_ssl_mode(params.ssl_mode.as_deref()) {
+        cfg.ssl_mode(ssl_mode);
+    }
+    if let Some(options) = postgres_startup_options(params) {
+        cfg.options(&options);
     }
 
     cfg
 }
 
+/// Server settings sent in the startup packet: the statement timeout and,
+/// for a read-only connection, read-only transactions for the session.
+pub fn postgres_startup_options(params: &ConnectionParams) -> Option<String> {
+    let mut options = Vec::new();
+    if let Some(ms) = pool_settings(params).statement_timeout_ms {
+        options.push(format!("-c statement_timeout={}", ms));
+    }
+    if params.read_only {
+        options.push("-c default_transaction_read_only=on".to_string());
+    }
+    (!options.is_empty()).then(|| options.join(" "))
+}
+
+/// A TLS file setting (CA, client certificate or key), with blank values
+/// from the connection form treated as unset.
+fn ssl_file(value: &Option<String>) -> Option<&str> {
+    value.as_deref().map(str::trim).filter(|s| !s.is_empty())
+}
+
+/// Map a connection's `ssl_mode` to tokio-postgres, which only knows
+/// whether to encrypt. The libpq `verify-ca` / `verify-full` modes (and
+/// their MySQL spellings) become `Require`: the rustls connector always
+/// verifies the server certificate and host name. Unknown values keep the
+/// driver default (`prefer`).
+pub fn postgres_ssl_mode(ssl_mode: Option<&str>) -> Option<PgSslMode> {
+    match ssl_mode?.trim().to_ascii_lowercase().as_str() {
+        "disable" | "disabled" => Some(PgSslMode::Disable),
+        "allow" | "prefer" | "preferred" => Some(PgSslMode::Prefer),
+        "require" | "required" | "verify-ca" | "verify_ca" | "verify-full" | "verify_full"
+        | "verify_identity" => Some(PgSslMode::Require),
+        _ => None,
+    }
+}
+
+/// Read every certificate of a PEM file (a single cert or a bundle).
+fn read_pem_certs(path: &str, setting: &str) -> Result<Vec<CertificateDer<'static>>, String> {
+    let pem = std::fs::read(path)
+        .map_err(|e| format!("Failed to read {} file '{}': {}", setting, path, e))?;
+    let certs = rustls_pemfile::certs(&mut std::io::Cursor::new(&pem[..]))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Failed to parse {} '{}': {}", setting, path, e))?;
+    if certs.is_empty() {
+        return Err(format!(
+            "{} '{}' contained no PEM CERTIFICATE blocks",
+            setting, path
+        ));
+    }
+    Ok(certs)
+}
+
+/// Read the client private key (PKCS#8, PKCS#1 or SEC1 PEM).
+fn read_pem_key(path: &str) -> Result<PrivateKeyDer<'static>, String> {
+    let pem = std::fs::read(path)
+        .map_err(|e| format!("Failed to read ssl_key file '{}': {}", path, e))?;
+    rustls_pemfile::private_key(&mut std::io::Cursor::new(&pem[..]))
+        .map_err(|e| format!("Failed to parse ssl_key '{}': {}", path, e))?
+        .ok_or_else(|| format!("ssl_key '{}' contained no PEM private key", path))
+}
+
 /// Build the rustls connector for the PostgreSQL pool.
 ///
 /// `rustls` (not `native-tls`) because macOS Secure Transport applies a
@@ -180,41 +427,194 @@ fn build_postgres_configurations(params: &ConnectionParams) -> PgConfig {
 /// out-of-the-box RDS support can pull a fresh bundle at packaging time
 /// (e.g. via a Dockerfile `RUN curl ...` or a build script that drops it
 /// into `src-tauri/assets/`) and point users at the resulting path.
+///
+/// `ssl_cert` + `ssl_key` (PEM) enable client certificate authentication;
+/// setting only one of them is an error rather than a silent fallback.
 fn build_postgres_tls_connector(params: &ConnectionParams) -> Result<MakeRustlsConnect, String> {
     ensure_rustls_crypto_provider();
     let builder = ClientConfig::builder();
-    let user_ca = params.ssl_ca.as_deref().filter(|s| !s.trim().is_empty());
-    let config = match user_ca {
+    let builder = match ssl_file(&params.ssl_ca) {
         Some(ca_path) => {
-            let pem = std::fs::read(ca_path)
-                .map_err(|e| format!("Failed to read ssl_ca file '{}': {}", ca_path, e))?;
             let mut roots = RootCertStore::empty();
-            let mut cursor = std::io::Cursor::new(&pem[..]);
-            for cert in rustls_pemfile::certs(&mut cursor) {
-                let cert = cert
-                    .map_err(|e| format!("Failed to parse ssl_ca '{}': {}", ca_path, e))?;
-                roots.add(cert).map_err(|e| {
-                    format!("Failed to add ssl_ca cert from '{}': {}", ca_path, e)
-                })?;
-            }
-            if roots.is_empty() {
-                return Err(format!(
-                    "ssl_ca '{}' contained no PEM CERTIFICATE blocks",
-                    ca_path
-                ));
+            for cert in read_pem_certs(ca_path, "ssl_ca")? {
+                roots
+                    .add(cert)
+                    .map_err(|e| format!("Failed to add ssl_ca cert from '{}': {}", ca_path, e))?;
             }
-            builder.with_root_certificates(roots).with_no_client_auth()
+            builder.with_root_certificates(roots)
         }
         None => builder
             .with_platform_verifier()
-            .map_err(|e| format!("Failed to build platform TLS verifier: {}", e))?
-            .with_no_client_auth(),
+            .map_err(|e| format!("Failed to build platform TLS verifier: {}", e))?,
+    };
+    // Client certificate authentication (e.g. Cloud SQL, self-managed
+    // servers with `clientcert=verify-full`).
+    let config = match (ssl_file(&params.ssl_cert), ssl_file(&params.ssl_key)) {
+        (Some(cert_path), Some(key_path)) => builder
+            .with_client_auth_cert(
+                read_pem_certs(cert_path, "ssl_cert")?,
+                read_pem_key(key_path)?,
+            )
+            .map_err(|e| format!("Invalid client certificate or key: {}", e))?,
+        (None, None) => builder.with_no_client_auth(),
+        _ => {
+            return Err(
+                "A client certificate needs both ssl_cert and ssl_key to be set".to_string(),
+            )
+        }
     };
     Ok(MakeRustlsConnect::new(config))
 }
 
 fn build_sqlite_connectoptions(params: &ConnectionParams) -> SqliteConnectOptions {
-    SqliteConnectOptions::new().filename(params.database.to_string())
+    SqliteConnectOptions::new()
+        .filename(params.database.to_string())
+        .read_only(params.read_only)
+}
+
+/// Build the `tiberius` configuration for a SQL Server connection.
+///
+/// SQL Server instances usually present a self-signed certificate, so the
+/// certificate is trusted as-is unless `ssl_ca` names a CA to verify
+/// against (the same trade-off as PostgreSQL's `require`). `disable` still
+/// encrypts the login packet, as every TDS client does.
+pub fn build_mssql_config(params: &ConnectionParams) -> Result<MssqlConfig, String> {
+    let mut cfg = MssqlConfig::new();
+    cfg.host(params.host.as_deref().unwrap_or("localhost"));
+    cfg.port(params.port.unwrap_or(1433));
+    cfg.application_name("Tabularis");
+    cfg.authentication(mssql_auth(params)?);
+
+    let database = params.database.primary();
+    if !database.is_empty() {
+        cfg.database(database);
+    }
+
+    cfg.encryption(match params.ssl_mode.as_deref() {
+        Some("disable") => EncryptionLevel::Off,
+        Some("require") => EncryptionLevel::Required,
+        _ => EncryptionLevel::On,
+    });
+    match params.ssl_ca.as_deref().filter(|s| !s.trim().is_empty()) {
+        Some(ca_path) => cfg.trust_cert_ca(ca_path),
+        None => cfg.trust_cert(),
+    }
+
+    Ok(cfg)
+}
+
+/// The sign-in for `params.auth_method`: SQL Server login by default,
+/// the OS account for `integrated`.
+pub fn mssql_auth(params: &ConnectionParams) -> Result<AuthMethod, String> {
+    match params.auth_method.as_deref() {
+        None | Some("") | Some("password") => Ok(AuthMethod::sql_server(
+            params.username.as_deref().unwrap_or_default(),
+            params.password.as_deref().unwrap_or_default(),
+        )),
+        Some("integrated") => integrated_auth(),
+        Some(other) => Err(crate::i18n::t_with(
+            "errors.unsupportedAuthMethod",
+            &[("method", other)],
+        )),
+    }
+}
+
+/// Refuse an `auth_method` the connection's driver cannot use, so it is
+/// caught when the connection is saved or tested.
+pub fn ensure_auth_method_supported(params: &ConnectionParams) -> Result<(), String> {
+    match params.auth_method.as_deref() {
+        None | Some("") | Some("password") => Ok(()),
+        Some("integrated") if params.driver == "mssql" => integrated_auth().map(|_| ()),
+        Some(other) => Err(crate::i18n::t_with(
+            "errors.unsupportedAuthMethod",
+            &[("method", other)],
+        )),
+    }
+}
+
+#[cfg(windows)]
+fn integrated_auth() -> Result<AuthMethod, String> {
+    Ok(AuthMethod::Integrated)
+}
+
+#[cfg(not(windows))]
+fn integrated_auth() -> Result<AuthMethod, String> {
+    Err(crate::i18n::t("errors.integratedAuthUnavailable"))
+}
+
+async fn open_mssql_stream(config: &MssqlConfig) -> tiberius::Result<Compat<TcpStream>> {
+    let tcp = TcpStream::connect(config.get_addr()).await?;
+    tcp.set_nodelay(true)?;
+    Ok(tcp.compat_write())
+}
+
+/// Open one SQL Server connection, following the redirect Azure SQL
+/// gateways answer the login with.
+pub async fn connect_mssql(config: MssqlConfig) -> tiberius::Result<MssqlClient> {
+    ensure_rustls_crypto_provider();
+    let stream = open_mssql_stream(&config).await?;
+    let mut client = match tiberius::Client::connect(config.clone(), stream).await {
+        Ok(client) => client,
+        Err(tiberius::error::Error::Routing { host, port }) => {
+            let mut routed = config;
+            routed.host(&host);
+            routed.port(port);
+            let stream = open_mssql_stream(&routed).await?;
+            tiberius::Client::connect(routed, stream).await?
+        }
+        Err(e) => return Err(e),
+    };
+    // The driver quotes identifiers with double quotes in shared SQL
+    // (dumps, sandboxes); make sure the session accepts them.
+    client
+        .simple_query("SET QUOTED_IDENTIFIER ON; SET ANSI_NULLS ON")
+        .await?
+        .into_results()
+        .await?;
+    Ok(client)
+}
+
+/// deadpool manager for SQL Server; `tiberius` has no pool of its own.
+pub struct MssqlManager {
+    config: MssqlConfig,
+}
+
+impl deadpool::managed::Manager for MssqlManager {
+    type Type = MssqlClient;
+    type Error = tiberius::error::Error;
+
+    async fn create(&self) -> Result<MssqlClient, tiberius::error::Error> {
+        connect_mssql(self.config.clone()).await
+    }
+
+    async fn recycle(
+        &self,
+        client: &mut MssqlClient,
+        _: &deadpool::managed::Metrics,
+    ) -> deadpool::managed::RecycleResult<tiberius::error::Error> {
+        client
+            .simple_query("SELECT 1")
+            .await?
+            .into_results()
+            .await?;
+        Ok(())
+    }
+}
+
+/// sqlx pool options with `settings` applied.
+fn sqlx_pool_options<DB: sqlx::Database>(
+    settings: &ResolvedPoolSettings,
+) -> sqlx::pool::PoolOptions<DB> {
+    let mut options = sqlx::pool::PoolOptions::<DB>::new()
+        .max_connections(settings.max_connections)
+        .min_connections(settings.min_connections);
+    if let Some(timeout) = settings.acquire_timeout {
+        options = options.acquire_timeout(timeout);
+    }
+    if let Some(timeout) = settings.idle_timeout {
+        options = options.idle_timeout(timeout);
+    }
+    options
 }
 
 pub async fn get_mysql_pool(params: &ConnectionParams) -> Result<Pool<MySql>, String> {
@@ -269,14 +669,35 @@ async fn get_mysql_pool_for_database_with_id(
         key
     );
     let options = build_mysql_options(params, override_db)?;
+    let settings = pool_settings(params);
+    let read_only = params.read_only;
     let connect_timeout = Duration::from_millis(mysql_numeric_setting(
         "connectTimeout",
         DEFAULT_MYSQL_CONNECT_TIMEOUT_MS,
     ));
     let pool = tokio::time::timeout(
         connect_timeout,
-        sqlx::mysql::MySqlPoolOptions::new()
-            .max_connections(10)
+        sqlx_pool_options::<MySql>(&settings)
+            // Ping on checkout so a connection the server dropped
+            // (wait_timeout) is replaced instead of failing the query.
+            .test_before_acquire(true)
+            .after_connect(move |conn, _| {
+                Box::pin(async move {
+                    if let Some(ms) = settings.statement_timeout_ms {
+                        let sql = format!("SET SESSION max_execution_time = {}", ms);
+                        // MariaDB names it max_statement_time; a failed
+                        // SET must not keep the connection from opening.
+                        if let Err(e) = sqlx::Executor::execute(&mut *conn, sql.as_str()).await {
+                            log::warn!("Could not set the MySQL statement timeout: {}", e);
+                        }
+                    }
+                    if read_only {
+                        sqlx::Executor::execute(&mut *conn, "SET SESSION TRANSACTION READ ONLY")
+                            .await?;
+                    }
+                    Ok(())
+                })
+            })
             .connect_with(options),
     )
     .await
@@ -345,14 +766,19 @@ pub async fn get_postgres_pool_with_id(
         e
     })?;
 
-    let pool = PgPool::builder(PgPoolManager::new(cfg, tls_connector))
-        .max_size(10)
-        .build()
-        .map_err(|e| {
-            let detail = format_error_chain(&e);
-            log::error!("Failed to create PostgreSQL connection pool: {}", detail);
-            detail
-        })?;
+    // Verified recycling checks each connection on checkout, so one the
+    // server or a proxy closed while idle is replaced transparently.
+    let manager_config = PgManagerConfig {
+        recycling_method: RecyclingMethod::Verified,
+    };
+    let manager = PgPoolManager::from_config(cfg, tls_connector, manager_config);
+    let settings = pool_settings(params);
+    let pool = build_deadpool(manager, &settings).map_err(|e| {
+        let detail = format_error_chain(&e);
+        log::error!("Failed to create PostgreSQL connection pool: {}", detail);
+        detail
+    })?;
+    warm_deadpool(&pool, settings.min_connections);
 
     log::info!(
         "PostgreSQL connection pool created successfully for: {} (key: {})",
@@ -369,6 +795,64 @@ pub async fn get_postgres_pool_with_id(
     Ok(pool)
 }
 
+pub async fn get_mssql_pool(params: &ConnectionParams) -> Result<MssqlPool, String> {
+    let connection_id = params.connection_id.as_deref();
+    get_mssql_pool_with_id(params, connection_id).await
+}
+
+pub async fn get_mssql_pool_with_id(
+    params: &ConnectionParams,
+    connection_id: Option<&str>,
+) -> Result<MssqlPool, String> {
+    let key = build_connection_key(params, connection_id);
+
+    // Try to get existing pool
+    {
+        let pools = MSSQL_POOLS.read().await;
+        if let Some(pool) = pools.get(&key) {
+            log::debug!(
+                "Using existing SQL Server connection pool for: {} (key: {})",
+                params.database,
+                key
+            );
+            return Ok(pool.clone());
+        }
+    }
+
+    // Create new pool
+    log::info!(
+        "Creating new SQL Server connection pool for: {}@{:?} (key: {})",
+        params.username.as_deref().unwrap_or("unknown"),
+        params.host,
+        key
+    );
+
+    let manager = MssqlManager {
+        config: build_mssql_config(params)?,
+    };
+    let settings = pool_settings(params);
+    let pool = build_deadpool(manager, &settings).map_err(|e| {
+        let detail = format_error_chain(&e);
+        log::error!("Failed to create SQL Server connection pool: {}", detail);
+        detail
+    })?;
+    warm_deadpool(&pool, settings.min_connections);
+
+    log::info!(
+        "SQL Server connection pool created successfully for: {} (key: {})",
+        params.database,
+        key
+    );
+
+    // Store pool
+    {
+        let mut pools = MSSQL_POOLS.write().await;
+        pools.insert(key, pool.clone());
+    }
+
+    Ok(pool)
+}
+
 pub async fn get_sqlite_pool(params: &ConnectionParams) -> Result<Pool<Sqlite>, String> {
     let connection_id = params.connection_id.as_deref();
     get_sqlite_pool_with_id(params, connection_id).await
@@ -400,8 +884,7 @@ pub async fn get_sqlite_pool_with_id(
         key
     );
     let options = build_sqlite_connectoptions(params);
-    let pool = sqlx::sqlite::SqlitePoolOptions::new()
-        .max_connections(5) // SQLite has lower concurrency needs
+    let pool = sqlx_pool_options::<Sqlite>(&pool_settings(params))
         .connect_with(options)
         .await
         .map_err(|e| {
@@ -424,6 +907,15 @@ pub async fn get_sqlite_pool_with_id(
     Ok(pool)
 }
 
+/// Store a pool the caller built itself (e.g. the demo driver's in-memory
+/// database) under the key `params` resolve to. An existing pool wins, so
+/// two racing callers end up sharing one; the registered pool is returned.
+pub async fn register_sqlite_pool(params: &ConnectionParams, pool: Pool<Sqlite>) -> Pool<Sqlite> {
+    let key = build_connection_key(params, params.connection_id.as_deref());
+    let mut pools = SQLITE_POOLS.write().await;
+    pools.entry(key).or_insert(pool).clone()
+}
+
 /// Check whether a connection pool exists for the given params without creating one.
 pub async fn has_pool(params: &ConnectionParams, connection_id: Option<&str>) -> bool {
     has_pool_for_database(params, None, connection_id).await
@@ -444,6 +936,7 @@ pub async fn has_pool_for_database(
         "mysql" => MYSQL_POOLS.read().await.contains_key(&key),
         "postgres" => POSTGRES_POOLS.read().await.contains_key(&key),
         "sqlite" => SQLITE_POOLS.read().await.contains_key(&key),
+        "mssql" => MSSQL_POOLS.read().await.contains_key(&key),
         _ => false,
     }
 }
@@ -454,8 +947,17 @@ pub async fn close_pool(params: &ConnectionParams) {
     close_pool_with_id(params, connection_id).await;
 }
 
-/// Close a specific connection pool by connection_id
+/// Close a specific connection pool by connection_id, along with the pools of
+/// its read replicas.
 pub async fn close_pool_with_id(params: &ConnectionParams, connection_id: Option<&str>) {
+    if let Some(conn_id) = connection_id {
+        // An open transaction holds a pooled connection; closing a sqlx
+        // pool waits for it to come back.
+        if has_transaction(conn_id).await {
+            let _ = finish_transaction(conn_id, false).await;
+        }
+        close_replica_pools(&params.driver, conn_id).await;
+    }
     let key = build_connection_key(params, connection_id);
 
     match params.driver.as_str() {
@@ -474,6 +976,18 @@ pub async fn close_pool_with_id(params: &ConnectionParams, connection_id: Option
                     key
                 );
             }
+            // Pools opened for the other databases of the connection.
+            let prefix = format!("{}:", key);
+            let others: Vec<String> = pools
+                .keys()
+                .filter(|k| k.starts_with(&prefix))
+                .cloned()
+                .collect();
+            for other in others {
+                if let Some(pool) = pools.remove(&other) {
+                    pool.close().await;
+                }
+            }
         }
         "postgres" => {
             let mut pools = POSTGRES_POOLS.write().await;
@@ -507,12 +1021,433 @@ pub async fn close_pool_with_id(params: &ConnectionParams, connection_id: Option
                 );
             }
         }
+        "mssql" => {
+            let mut pools = MSSQL_POOLS.write().await;
+            if let Some(pool) = pools.remove(&key) {
+                log::info!(
+                    "Closing SQL Server connection pool for: {} (key: {})",
+                    params.database,
+                    key
+                );
+                pool.close();
+                log::info!(
+                    "SQL Server connection pool closed for: {} (key: {})",
+                    params.database,
+                    key
+                );
+            }
+        }
         _ => {}
     }
 }
 
+/// Close every reader pool opened by `route_query` for `connection_id`.
+async fn close_replica_pools(driver: &str, connection_id: &str) {
+    let prefix = format!("{}:conn:{}{}", driver, connection_id, REPLICA_ID_MARKER);
+    match driver {
+        "mysql" => {
+            let mut pools = MYSQL_POOLS.write().await;
+            let keys: Vec<String> = pools
+                .keys()
+                .filter(|k| k.starts_with(&prefix))
+                .cloned()
+                .collect();
+            for key in keys {
+                if let Some(pool) = pools.remove(&key) {
+                    log::info!("Closing MySQL replica pool (key: {})", key);
+                    pool.close().await;
+                }
+            }
+        }
+        "postgres" => {
+            let mut pools = POSTGRES_POOLS.write().await;
+            pools.retain(|key, pool| {
+                let keep = !key.starts_with(&prefix);
+                if !keep {
+                    log::info!("Closing PostgreSQL replica pool (key: {})", key);
+                    pool.close();
+                }
+                keep
+            });
+        }
+        "mssql" => {
+            let mut pools = MSSQL_POOLS.write().await;
+            pools.retain(|key, pool| {
+                let keep = !key.starts_with(&prefix);
+                if !keep {
+                    log::info!("Closing SQL Server replica pool (key: {})", key);
+                    pool.close();
+                }
+                keep
+            });
+        }
+        _ => {}
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Pool statistics
+// ---------------------------------------------------------------------------
+
+/// Connection counts of one open pool.
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct PoolStats {
+    /// Pool key: driver, connection, database and, for readers, the replica.
+    pub key: String,
+    pub max_connections: u32,
+    /// Open connections, idle or in use.
+    pub size: u32,
+    pub idle: u32,
+    pub active: u32,
+    /// Queries waiting for a connection. Only deadpool (PostgreSQL, SQL
+    /// Server) reports it.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub waiting: Option<u32>,
+}
+
+fn sqlx_pool_stats<DB: sqlx::Database>(key: &str, pool: &Pool<DB>) -> PoolStats {
+    let size = pool.size();
+    let idle = (pool.num_idle() as u32).min(size);
+    PoolStats {
+        key: key.to_string(),
+        max_connections: pool.options().get_max_connections(),
+        size,
+        idle,
+        active: size - idle,
+        waiting: None,
+    }
+}
+
+fn deadpool_stats<M: deadpool::managed::Manager>(
+    key: &str,
+    pool: &deadpool::managed::Pool<M>,
+) -> PoolStats {
+    let status = pool.status();
+    PoolStats {
+        key: key.to_string(),
+        max_connections: status.max_size as u32,
+        size: status.size as u32,
+        idle: status.available as u32,
+        active: status.size.saturating_sub(status.available) as u32,
+        waiting: Some(status.waiting as u32),
+    }
+}
+
+/// Stats of every open pool of `connection_id`: one per database and per
+/// read replica in use. Pools are not opened by asking.
+pub async fn pool_stats(driver: &str, connection_id: &str) -> Vec<PoolStats> {
+    let prefix = format!("{}:conn:{}:", driver, connection_id);
+    let owned = |key: &&String| key.starts_with(&prefix);
+    let mut stats: Vec<PoolStats> = match driver {
+        "mysql" => MYSQL_POOLS
+            .read()
+            .await
+            .iter()
+            .filter(|(key, _)| owned(key))
+            .map(|(key, pool)| sqlx_pool_stats(key, pool))
+            .collect(),
+        "postgres" => POSTGRES_POOLS
+            .read()
+            .await
+            .iter()
+            .filter(|(key, _)| owned(key))
+            .map(|(key, pool)| deadpool_stats(key, pool))
+            .collect(),
+        "sqlite" => SQLITE_POOLS
+            .read()
+            .await
+            .iter()
+            .filter(|(key, _)| owned(key))
+            .map(|(key, pool)| sqlx_pool_stats(key, pool))
+            .collect(),
+        "mssql" => MSSQL_POOLS
+            .read()
+            .await
+            .iter()
+            .filter(|(key, _)| owned(key))
+            .map(|(key, pool)| deadpool_stats(key, pool))
+            .collect(),
+        _ => Vec::new(),
+    };
+    stats.sort_by(|a, b| a.key.cmp(&b.key));
+    stats
+}
+
+// ---------------------------------------------------------------------------
+// Transactions
+// ---------------------------------------------------------------------------
+
+/// The connection held by a transaction opened from the editor. While it is
+/// open, the drivers run the connection's queries and record edits on it
+/// instead of on a pooled connection.
+pub enum TransactionConnection {
+    MySql(sqlx::Transaction<'static, MySql>),
+    Postgres(deadpool_postgres::Object),
+    Sqlite(sqlx::Transaction<'static, Sqlite>),
+    Mssql(deadpool::managed::Object<MssqlManager>),
+}
+
+/// `None` once the transaction has been committed or rolled back, so a
+/// statement that was waiting for the lock cannot run outside of it.
+pub type SharedTransaction = Arc<tokio::sync::Mutex<Option<TransactionConnection>>>;
+
+/// Open transactions keyed by connection id.
+static TRANSACTIONS: Lazy<RwLock<HashMap<String, SharedTransaction>>> =
+    Lazy::new(|| RwLock::new(HashMap::new()));
+
+/// Take a connection out of the pool for `params.connection_id` and start a
+/// transaction on it.
+pub async fn begin_transaction(params: &ConnectionParams) -> Result<(), String> {
+    let connection_id = params
+        .connection_id
+        .clone()
+        .ok_or_else(|| "Transactions need a saved connection".to_string())?;
+    if has_transaction(&connection_id).await {
+        return Err("A transaction is already open on this connection".into());
+    }
+
+    let conn = open_transaction(params).await?;
+    {
+        let mut transactions = TRANSACTIONS.write().await;
+        if !transactions.contains_key(&connection_id) {
+            log::info!("Transaction started on connection {}", connection_id);
+            transactions.insert(connection_id, Arc::new(tokio::sync::Mutex::new(Some(conn))));
+            return Ok(());
+        }
+    }
+    // Another begin won the race while we were connecting.
+    let _ = end_transaction(conn, false).await;
+    Err("A transaction is already open on this connection".into())
+}
+
+/// Commit (or roll back) the open transaction of `connection_id` and hand
+/// its connection back to the pool.
+pub async fn finish_transaction(connection_id: &str, commit: bool) -> Result<(), String> {
+    let shared = TRANSACTIONS
+        .write()
+        .await
+        .remove(connection_id)
+        .ok_or_else(|| "No transaction is open on this connection".to_string())?;
+    let Some(conn) = shared.lock().await.take() else {
+        return Ok(());
+    };
+
+    let result = end_transaction(conn, commit).await;
+    match &result {
+        Ok(()) => log::info!(
+            "Transaction {} on connection {}",
+            if commit { "committed" } else { "rolled back" },
+            connection_id
+        ),
+        Err(e) => log::error!("{} (connection {})", e, connection_id),
+    }
+    result
+}
+
+async fn open_transaction(params: &ConnectionParams) -> Result<TransactionConnection, String> {
+    let begin_err = |e: &dyn std::fmt::Display| format!("Failed to begin transaction: {}", e);
+    match params.driver.as_str() {
+        "mysql" => {
+            let pool = get_mysql_pool(params).await?;
+            let tx = pool.begin().await.map_err(|e| begin_err(&e))?;
+            Ok(TransactionConnection::MySql(tx))
+        }
+        "postgres" => {
+            let pool = get_postgres_pool(params).await?;
+            let client = pool.get().await.map_err(|e| format_error_chain(&e))?;
+            client
+                .batch_execute("BEGIN")
+                .await
+                .map_err(|e| begin_err(&format_error_chain(&e)))?;
+            Ok(TransactionConnection::Postgres(client))
+        }
+        "sqlite" => {
+            let pool = get_sqlite_pool(params).await?;
+            let tx = pool.begin().await.map_err(|e| begin_err(&e))?;
+            Ok(TransactionConnection::Sqlite(tx))
+        }
+        "mssql" => {
+            let pool = get_mssql_pool(params).await?;
+            let mut client = pool.get().await.map_err(|e| format_error_chain(&e))?;
+            // A plain batch, not sp_executesql: a transaction opened inside
+            // a procedure call must also end there.
+            client
+                .simple_query("BEGIN TRANSACTION")
+                .await
+                .map_err(|e| begin_err(&e))?
+                .into_results()
+                .await
+                .map_err(|e| begin_err(&e))?;
+            Ok(TransactionConnection::Mssql(client))
+        }
+        other => Err(format!(
+            "Transactions are not supported by the {} driver",
+            other
+        )),
+    }
+}
+
+/// Run `COMMIT` or `ROLLBACK` on `conn`. A Postgres or SQL Server
+/// connection on which that fails is dropped rather than returned to its
+/// pool, since it may still be inside the transaction.
+async fn end_transaction(conn: TransactionConnection, commit: bool) -> Result<(), String> {
+    let verb = if commit { "COMMIT" } else { "ROLLBACK" };
+    let finish_err = |e: &dyn std::fmt::Display| format!("Failed to {} transaction: {}", verb, e);
+    match conn {
+        TransactionConnection::MySql(tx) => {
+            let result = if commit {
+                tx.commit().await
+            } else {
+                tx.rollback().await
+            };
+            result.map_err(|e| finish_err(&e))
+        }
+        TransactionConnection::Sqlite(tx) => {
+            let result = if commit {
+                tx.commit().await
+            } else {
+                tx.rollback().await
+            };
+            result.map_err(|e| finish_err(&e))
+        }
+        TransactionConnection::Postgres(client) => {
+            let result = client
+                .batch_execute(verb)
+                .await
+                .map_err(|e| finish_err(&format_error_chain(&e)));
+            if result.is_err() {
+                drop(deadpool_postgres::Object::take(client));
+            }
+            result
+        }
+        TransactionConnection::Mssql(mut client) => {
+            let result = match client.simple_query(format!("{} TRANSACTION", verb)).await {
+                Ok(stream) => stream.into_results().await.map(|_| ()),
+                Err(e) => Err(e),
+            }
+            .map_err(|e| finish_err(&e));
+            if result.is_err() {
+                drop(deadpool::managed::Object::take(client));
+            }
+            result
+        }
+    }
+}
+
+/// The transaction open on `params.connection_id`, if any.
+pub async fn active_transaction(params: &ConnectionParams) -> Option<SharedTransaction> {
+    let connection_id = params.connection_id.as_deref()?;
+    TRANSACTIONS.read().await.get(connection_id).cloned()
+}
+
+pub async fn has_transaction(connection_id: &str) -> bool {
+    TRANSACTIONS.read().await.contains_key(connection_id)
+}
+
+// ---------------------------------------------------------------------------
+// Keep-alive
+// ---------------------------------------------------------------------------
+
+/// Ping every idle MySQL connection once. The idle connections are all
+/// checked out together (`try_acquire` never waits or opens a new one) so
+/// none is pinged twice; dead ones are closed rather than returned.
+async fn ping_idle_mysql(pool: &Pool<MySql>) {
+    let mut held = Vec::new();
+    while let Some(conn) = pool.try_acquire() {
+        held.push(conn);
+    }
+    for mut conn in held {
+        if let Err(e) = sqlx::Connection::ping(&mut *conn).await {
+            log::debug!("Keep-alive: closing dead MySQL connection: {}", e);
+            let _ = conn.close().await;
+        }
+    }
+}
+
+/// Check out every idle connection of a deadpool pool at once. Checkout
+/// runs the manager's recycle check, which pings the connection and
+/// replaces it if the server has dropped it.
+async fn ping_idle_deadpool<M: deadpool::managed::Manager>(pool: &deadpool::managed::Pool<M>) {
+    let idle = pool.status().available;
+    let mut held = Vec::with_capacity(idle);
+    for _ in 0..idle {
+        match pool.get().await {
+            Ok(conn) => held.push(conn),
+            Err(_) => break,
+        }
+    }
+}
+
+/// Ping the idle connections of every MySQL, PostgreSQL and SQL Server
+/// pool so servers that drop quiet sessions (MySQL `wait_timeout`, NAT and
+/// load balancer idle limits) never see one sit idle long enough.
+pub async fn ping_idle_connections() {
+    let mysql: Vec<Pool<MySql>> = MYSQL_POOLS.read().await.values().cloned().collect();
+    for pool in &mysql {
+        ping_idle_mysql(pool).await;
+    }
+    let postgres: Vec<PgPool> = POSTGRES_POOLS.read().await.values().cloned().collect();
+    for pool in &postgres {
+        ping_idle_deadpool(pool).await;
+    }
+    let mssql: Vec<MssqlPool> = MSSQL_POOLS.read().await.values().cloned().collect();
+    for pool in &mssql {
+        ping_idle_deadpool(pool).await;
+    }
+}
+
+/// Start the keep-alive loop. If a loop is already running it is stopped first.
+pub async fn start_keepalive_loop(interval_secs: u64) {
+    stop_keepalive_loop().await;
+
+    if interval_secs == 0 {
+        log::info!("Keep-alive: disabled (interval = 0)");
+        return;
+    }
+
+    let (stop_tx, mut stop_rx) = oneshot::channel::<()>();
+    *KEEPALIVE_STOP_TX.lock().await = Some(stop_tx);
+
+    log::info!("Keep-alive: starting with interval {}s", interval_secs);
+
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(interval_secs));
+        // The first tick fires immediately; there is nothing idle yet.
+        interval.tick().await;
+        loop {
+            tokio::select! {
+                _ = &mut stop_rx => {
+                    log::info!("Keep-alive: loop stopped");
+                    break;
+                }
+                _ = interval.tick() => {
+                    ping_idle_connections().await;
+                }
+            }
+        }
+    });
+}
+
+/// Stop the running keep-alive loop, if any.
+pub async fn stop_keepalive_loop() {
+    if let Some(tx) = KEEPALIVE_STOP_TX.lock().await.take() {
+        let _ = tx.send(());
+    }
+}
+
+/// Restart the keep-alive loop with a new interval.
+pub async fn restart_keepalive_loop(interval_secs: u64) {
+    stop_keepalive_loop().await;
+    start_keepalive_loop(interval_secs).await;
+}
+
 /// Close all connection pools (useful on app shutdown)
 pub async fn close_all_pools() {
+    let open: Vec<String> = TRANSACTIONS.read().await.keys().cloned().collect();
+    for connection_id in open {
+        let _ = finish_transaction(&connection_id, false).await;
+    }
     {
         let mut pools = MYSQL_POOLS.write().await;
         for (_, pool) in pools.drain() {
@@ -531,4 +1466,10 @@ pub async fn close_all_pools() {
             pool.close().await;
         }
     }
+    {
+        let mut pools = MSSQL_POOLS.write().await;
+        for (_, pool) in pools.drain() {
+            pool.close();
+        }
+    }
 }
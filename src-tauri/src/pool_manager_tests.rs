@@ -1,6 +1,14 @@
 #[cfg(test)]
 mod tests {
-    use crate::pool_manager::format_error_chain;
+    use crate::models::{ConnectionParams, PoolSettings, ReplicaEndpoint, SavedConnection};
+    use crate::pool_manager::{
+        ensure_auth_method_supported, format_error_chain, is_replica_safe, mssql_auth,
+        postgres_ssl_mode, postgres_startup_options, replica_connection_id, replica_params,
+        resolve_pool_settings, route_query, ResolvedPoolSettings,
+    };
+    use std::time::Duration;
+    use tiberius::AuthMethod;
+    use tokio_postgres::config::SslMode;
 
     #[test]
     fn format_error_chain_walks_sources() {
@@ -34,4 +42,243 @@ mod tests {
             "outer message -> inner cause"
         );
     }
+
+    // --- Read/write splitting ---
+
+    fn replicated(replicas: &[(&str, Option<u16>)]) -> SavedConnection {
+        SavedConnection {
+            id: "c1".to_string(),
+            name: "Primary".to_string(),
+            params: ConnectionParams {
+                driver: "postgres".to_string(),
+                host: Some("writer.internal".to_string()),
+                port: Some(5432),
+                ..Default::default()
+            },
+            group_id: None,
+            sort_order: None,
+            detect_json_in_text_columns: None,
+            protected: None,
+            read_replicas: Some(
+                replicas
+                    .iter()
+                    .map(|(host, port)| ReplicaEndpoint {
+                        host: host.to_string(),
+                        port: *port,
+                    })
+                    .collect(),
+            ),
+            connect_on_startup: None,
+        }
+    }
+
+    #[test]
+    fn plain_reads_are_replica_safe() {
+        assert!(is_replica_safe("SELECT * FROM users"));
+        assert!(is_replica_safe(
+            "-- report\nwith t as (select 1) select * from t"
+        ));
+        assert!(is_replica_safe("SHOW TABLES"));
+        assert!(is_replica_safe("EXPLAIN SELECT 1"));
+    }
+
+    #[test]
+    fn writes_and_locking_reads_stay_on_writer() {
+        assert!(!is_replica_safe("UPDATE users SET a = 1"));
+        assert!(!is_replica_safe("CREATE TABLE t (id int)"));
+        assert!(!is_replica_safe(
+            "SELECT * FROM jobs FOR UPDATE SKIP LOCKED"
+        ));
+        assert!(!is_replica_safe("SELECT * FROM t FOR NO KEY UPDATE"));
+        assert!(!is_replica_safe("SELECT * INTO archive FROM t"));
+        assert!(!is_replica_safe("EXPLAIN ANALYZE SELECT 1"));
+        assert!(!is_replica_safe(
+            "WITH d AS (DELETE FROM t RETURNING *) SELECT * FROM d"
+        ));
+        assert!(!is_replica_safe("CALL refresh()"));
+    }
+
+    #[test]
+    fn replica_params_keep_writer_port_when_unset() {
+        let conn = replicated(&[]);
+        let endpoint = ReplicaEndpoint {
+            host: "reader.internal".to_string(),
+            port: None,
+        };
+        let routed = replica_params(&conn.params, &endpoint);
+        assert_eq!(routed.host.as_deref(), Some("reader.internal"));
+        assert_eq!(routed.port, Some(5432));
+    }
+
+    #[test]
+    fn route_query_spreads_reads_and_keeps_writes_on_writer() {
+        let conn = replicated(&[("r0.internal", Some(6432)), ("r1.internal", None)]);
+
+        let (params, pool_id) = route_query(&conn, "c1", "INSERT INTO t VALUES (1)", false);
+        assert_eq!(params.host.as_deref(), Some("writer.internal"));
+        assert_eq!(pool_id, "c1");
+
+        let (params, pool_id) = route_query(&conn, "c1", "SELECT 1", true);
+        assert_eq!(params.host.as_deref(), Some("writer.internal"));
+        assert_eq!(pool_id, "c1");
+
+        let mut hosts = Vec::new();
+        for _ in 0..2 {
+            let (params, pool_id) = route_query(&conn, "c1", "SELECT 1", false);
+            assert!(pool_id.starts_with("c1:replica:"));
+            hosts.push(params.host.unwrap());
+        }
+        hosts.sort();
+        assert_eq!(hosts, vec!["r0.internal", "r1.internal"]);
+        assert_eq!(replica_connection_id("c1", 1), "c1:replica:1");
+    }
+
+    #[test]
+    fn postgres_ssl_mode_accepts_libpq_and_mysql_spellings() {
+        assert_eq!(postgres_ssl_mode(Some("disable")), Some(SslMode::Disable));
+        assert_eq!(postgres_ssl_mode(Some("DISABLED")), Some(SslMode::Disable));
+        assert_eq!(postgres_ssl_mode(Some("allow")), Some(SslMode::Prefer));
+        assert_eq!(postgres_ssl_mode(Some("preferred")), Some(SslMode::Prefer));
+        assert_eq!(postgres_ssl_mode(Some("require")), Some(SslMode::Require));
+        assert_eq!(postgres_ssl_mode(Some("verify-ca")), Some(SslMode::Require));
+        assert_eq!(
+            postgres_ssl_mode(Some("verify-full")),
+            Some(SslMode::Require)
+        );
+        assert_eq!(
+            postgres_ssl_mode(Some("verify_identity")),
+            Some(SslMode::Require)
+        );
+    }
+
+    #[test]
+    fn postgres_ssl_mode_keeps_default_when_unset_or_unknown() {
+        assert_eq!(postgres_ssl_mode(None), None);
+        assert_eq!(postgres_ssl_mode(Some("")), None);
+        assert_eq!(postgres_ssl_mode(Some("sometimes")), None);
+    }
+
+    // --- Pool settings ---
+
+    #[test]
+    fn pool_settings_fall_back_to_the_app_defaults_then_the_driver() {
+        assert_eq!(
+            resolve_pool_settings("sqlite", None, None),
+            ResolvedPoolSettings {
+                max_connections: 5,
+                min_connections: 0,
+                acquire_timeout: None,
+                idle_timeout: None,
+                statement_timeout_ms: None,
+            }
+        );
+
+        let own = PoolSettings {
+            max_connections: Some(4),
+            statement_timeout_ms: Some(0),
+            ..Default::default()
+        };
+        let defaults = PoolSettings {
+            max_connections: Some(20),
+            min_connections: Some(8),
+            acquire_timeout_secs: Some(5),
+            idle_timeout_secs: Some(0),
+            statement_timeout_ms: Some(30_000),
+        };
+        let resolved = resolve_pool_settings("postgres", Some(&own), Some(&defaults));
+        assert_eq!(resolved.max_connections, 4);
+        // Capped at the maximum.
+        assert_eq!(resolved.min_connections, 4);
+        assert_eq!(resolved.acquire_timeout, Some(Duration::from_secs(5)));
+        // 0 counts as unset.
+        assert_eq!(resolved.idle_timeout, None);
+        assert_eq!(resolved.statement_timeout_ms, None);
+
+        let empty = PoolSettings {
+            max_connections: Some(0),
+            ..Default::default()
+        };
+        assert_eq!(
+            resolve_pool_settings("mysql", Some(&empty), None).max_connections,
+            1
+        );
+    }
+
+    #[test]
+    fn postgres_startup_options_make_read_only_sessions() {
+        let params = ConnectionParams {
+            driver: "postgres".to_string(),
+            read_only: true,
+            pool: Some(PoolSettings {
+                statement_timeout_ms: Some(5_000),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        assert_eq!(
+            postgres_startup_options(&params).as_deref(),
+            Some("-c statement_timeout=5000 -c default_transaction_read_only=on")
+        );
+
+        let writable = ConnectionParams {
+            read_only: false,
+            ..params
+        };
+        assert_eq!(
+            postgres_startup_options(&writable).as_deref(),
+            Some("-c statement_timeout=5000")
+        );
+    }
+
+    #[test]
+    fn pool_settings_are_optional_in_saved_params() {
+        let params: ConnectionParams =
+            serde_json::from_str(r#"{"driver":"mysql","database":"app"}"#).unwrap();
+        assert_eq!(params.pool, None);
+        assert!(!serde_json::to_string(&params).unwrap().contains("pool"));
+
+        let params: ConnectionParams = serde_json::from_str(
+            r#"{"driver":"mysql","database":"app","pool":{"maxConnections":3,"idleTimeoutSecs":60}}"#,
+        )
+        .unwrap();
+        let pool = params.pool.unwrap();
+        assert_eq!(pool.max_connections, Some(3));
+        assert_eq!(pool.idle_timeout_secs, Some(60));
+    }
+
+    #[test]
+    fn mssql_signs_in_with_the_login_unless_integrated() {
+        let params = ConnectionParams {
+            driver: "mssql".to_string(),
+            username: Some("sa".to_string()),
+            password: Some("secret".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            mssql_auth(&params).unwrap(),
+            AuthMethod::sql_server("sa", "secret")
+        );
+
+        let integrated = ConnectionParams {
+            auth_method: Some("integrated".to_string()),
+            ..params.clone()
+        };
+        assert_eq!(mssql_auth(&integrated).is_ok(), cfg!(windows));
+        assert_eq!(
+            ensure_auth_method_supported(&integrated).is_ok(),
+            cfg!(windows)
+        );
+
+        let postgres = ConnectionParams {
+            driver: "postgres".to_string(),
+            ..integrated.clone()
+        };
+        assert!(ensure_auth_method_supported(&postgres).is_err());
+
+        let unknown = ConnectionParams {
+            auth_method: Some("ldap".to_string()),
+            ..params
+        };
+        assert!(mssql_auth(&unknown).is_err());
+    }
 }
@@ -1,16 +1,124 @@
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 use std::collections::HashMap;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Component, Path, PathBuf};
 
+use crate::drivers::common::quote_identifier;
+use crate::filter_builder::{compile_filter, FilterNode};
 use crate::paths::get_app_config_dir;
 
+const NAMED_FILTERS_FILE: &str = "named_filters.json";
+const NAMED_FILTERS_SHARE_VERSION: u32 = 1;
+const COLUMN_LAYOUTS_FILE: &str = "column_layouts.json";
+/// Current on-disk format version of `column_layouts.json`.
+pub const COLUMN_LAYOUTS_VERSION: u32 = 1;
+const COMPUTED_COLUMNS_FILE: &str = "computed_columns.json";
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EditorPreferences {
     pub tabs: Vec<serde_json::Value>,
     pub active_tab_id: Option<String>,
 }
 
+/// A saved filter/sort preset for a table (e.g. "active EU customers").
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct NamedFilter {
+    #[serde(default)]
+    pub id: String,
+    pub name: String,
+    pub table: String,
+    #[serde(default)]
+    pub schema: Option<String>,
+    /// Filter tree of the visual filter builder, compiled with bound
+    /// parameters when the preset is applied.
+    #[serde(default)]
+    pub filter: Option<FilterNode>,
+    #[serde(default)]
+    pub sort_column: Option<String>,
+    /// `"ASC"` or `"DESC"`. Default: ASC
+    #[serde(default)]
+    pub sort_direction: Option<String>,
+    #[serde(default)]
+    pub created_at: Option<String>,
+    #[serde(default)]
+    pub updated_at: Option<String>,
+}
+
+/// A named filter applied to its table: the SELECT statement and the values
+/// of its `:pN` placeholders, to be run through `execute_query_with_params`.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct NamedFilterQuery {
+    pub query: String,
+    pub params: HashMap<String, Value>,
+}
+
+/// Portable bundle used to share named filters between users or connections.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NamedFiltersShare {
+    pub version: u32,
+    pub filters: Vec<NamedFilter>,
+}
+
+/// Display settings for a single column of a table grid.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ColumnDisplay {
+    pub name: String,
+    #[serde(default)]
+    pub hidden: bool,
+    /// Width in pixels. `None` lets the grid size the column automatically.
+    #[serde(default)]
+    pub width: Option<u32>,
+    /// Fixed number of decimals applied to numeric values.
+    #[serde(default)]
+    pub decimals: Option<u32>,
+    /// Group the integer part of numeric values with `,`.
+    #[serde(default)]
+    pub thousands_separator: bool,
+    /// chrono strftime pattern applied to date/time values (e.g. `%d/%m/%Y`).
+    #[serde(default)]
+    pub date_format: Option<String>,
+}
+
+/// Column order, visibility and formatting for one table. `columns` is in display order.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ColumnLayout {
+    pub columns: Vec<ColumnDisplay>,
+    /// Incremented on every save; used to detect concurrent edits from other windows.
+    #[serde(default)]
+    pub revision: u32,
+    #[serde(default)]
+    pub updated_at: Option<String>,
+}
+
+/// On-disk container for all column layouts of a connection, keyed by `schema.table`.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct ColumnLayoutsFile {
+    #[serde(default)]
+    pub version: u32,
+    #[serde(default)]
+    pub layouts: HashMap<String, ColumnLayout>,
+}
+
+/// A virtual column computed by the database from a SQL expression
+/// (e.g. `price * qty`) and appended to the select list when browsing a table.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ComputedColumn {
+    pub name: String,
+    pub expression: String,
+    /// Default: true
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
 /// Get the preferences directory path
 fn get_preferences_dir() -> PathBuf {
     let mut config_dir = get_app_config_dir();
@@ -37,6 +145,20 @@ fn ensure_preferences_dir(connection_id: &str) -> Result<(), String> {
     Ok(())
 }
 
+/// Path of a per-connection preferences file, creating the connection's
+/// preferences directory if needed. Used by modules that keep their own
+/// per-table settings next to the editor preferences.
+pub(crate) fn connection_preferences_file(
+    connection_id: &str,
+    file_name: &str,
+) -> Result<PathBuf, String> {
+    ensure_preferences_dir(connection_id)?;
+    let mut path = get_preferences_dir();
+    path.push(connection_id);
+    path.push(file_name);
+    Ok(path)
+}
+
 #[tauri::command]
 pub async fn save_editor_preferences(
     connection_id: String,
@@ -122,3 +244,562 @@ pub async fn list_all_preferences() -> Result<HashMap<String, EditorPreferences>
 
     Ok(all_prefs)
 }
+
+/// Connection ids name a directory under the preferences dir, so they must
+/// be a single plain path component.
+pub fn validate_connection_id(connection_id: &str) -> Result<(), String> {
+    let mut components = Path::new(connection_id).components();
+    let single =
+        matches!(components.next(), Some(Component::Normal(_))) && components.next().is_none();
+    if !single || connection_id.contains(['/', '\\']) || connection_id.contains("..") {
+        return Err(format!("Invalid connection id: {}", connection_id));
+    }
+    Ok(())
+}
+
+/// Get the named filters file path for a specific connection
+fn get_named_filters_path(connection_id: &str) -> Result<PathBuf, String> {
+    validate_connection_id(connection_id)?;
+    let mut prefs_dir = get_preferences_dir();
+    prefs_dir.push(connection_id);
+    prefs_dir.push(NAMED_FILTERS_FILE);
+    Ok(prefs_dir)
+}
+
+/// Load all named filters saved for a connection.
+///
+/// Presets saved before filters were structured hold a raw WHERE string;
+/// they are skipped rather than run without their condition.
+pub fn load_named_filters(connection_id: &str) -> Result<Vec<NamedFilter>, String> {
+    let path = get_named_filters_path(connection_id)?;
+
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let content = fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read named filters file: {}", e))?;
+
+    Ok(parse_named_filters(&content)?
+        .into_iter()
+        .filter_map(|filter| {
+            filter
+                .map_err(|e| log::warn!("Skipping unreadable named filter: {}", e))
+                .ok()
+        })
+        .collect())
+}
+
+/// Parse a named filters file preset by preset, so one unreadable preset
+/// does not hide the others.
+pub fn parse_named_filters(content: &str) -> Result<Vec<Result<NamedFilter, String>>, String> {
+    let raw: Vec<Value> = serde_json::from_str(content)
+        .map_err(|e| format!("Failed to parse named filters file: {}", e))?;
+    Ok(raw
+        .into_iter()
+        .map(|value| serde_json::from_value::<NamedFilter>(value).map_err(|e| e.to_string()))
+        .collect())
+}
+
+/// Persist all named filters for a connection
+pub fn save_named_filters(connection_id: &str, filters: &[NamedFilter]) -> Result<(), String> {
+    let path = get_named_filters_path(connection_id)?;
+    ensure_preferences_dir(connection_id)?;
+
+    let json = serde_json::to_string_pretty(filters)
+        .map_err(|e| format!("Failed to serialize named filters: {}", e))?;
+
+    fs::write(&path, json).map_err(|e| format!("Failed to write named filters file: {}", e))
+}
+
+/// Insert or replace `filter` in `filters`.
+///
+/// Filters are matched by id; a filter without an id gets a fresh one. Names
+/// must be unique per table/schema so presets can be picked by name.
+pub fn upsert_named_filter(
+    filters: &mut Vec<NamedFilter>,
+    mut filter: NamedFilter,
+    now: &str,
+) -> Result<NamedFilter, String> {
+    let name = filter.name.trim().to_string();
+    if name.is_empty() {
+        return Err("Filter name cannot be empty".to_string());
+    }
+    filter.name = name;
+
+    let duplicate = filters.iter().any(|f| {
+        f.id != filter.id
+            && f.table == filter.table
+            && f.schema == filter.schema
+            && f.name.eq_ignore_ascii_case(&filter.name)
+    });
+    if duplicate {
+        return Err(format!(
+            "A filter named '{}' already exists for table '{}'",
+            filter.name, filter.table
+        ));
+    }
+
+    filter.updated_at = Some(now.to_string());
+
+    if let Some(existing) = filters
+        .iter_mut()
+        .find(|f| !filter.id.is_empty() && f.id == filter.id)
+    {
+        filter.created_at = existing.created_at.clone();
+        *existing = filter.clone();
+        return Ok(filter);
+    }
+
+    if filter.id.is_empty() {
+        filter.id = uuid::Uuid::new_v4().to_string();
+    }
+    filter.created_at = Some(now.to_string());
+    filters.push(filter.clone());
+    Ok(filter)
+}
+
+/// Merge shared filters into `filters`, assigning new ids and suffixing names
+/// that clash with existing presets on the same table. Returns the imported filters.
+pub fn merge_shared_filters(
+    filters: &mut Vec<NamedFilter>,
+    shared: Vec<NamedFilter>,
+    now: &str,
+) -> Vec<NamedFilter> {
+    let mut imported = Vec::new();
+
+    for mut filter in shared {
+        let base_name = filter.name.trim().to_string();
+        let mut name = base_name.clone();
+        let mut suffix = 2;
+        while filters.iter().any(|f| {
+            f.table == filter.table
+                && f.schema == filter.schema
+                && f.name.eq_ignore_ascii_case(&name)
+        }) {
+            name = format!("{} ({})", base_name, suffix);
+            suffix += 1;
+        }
+
+        filter.id = uuid::Uuid::new_v4().to_string();
+        filter.name = name;
+        filter.created_at = Some(now.to_string());
+        filter.updated_at = Some(now.to_string());
+        filters.push(filter.clone());
+        imported.push(filter);
+    }
+
+    imported
+}
+
+/// Builds the SELECT statement that applies a named filter to its table.
+///
+/// `quote` is the driver's identifier quote character. Filter values are
+/// bound as `:pN` parameters; only identifiers reach the SQL text.
+pub fn build_named_filter_query(
+    driver: &str,
+    quote: &str,
+    filter: &NamedFilter,
+) -> Result<NamedFilterQuery, String> {
+    let table_ref = match filter.schema.as_deref() {
+        Some(schema) if !schema.is_empty() => format!(
+            "{}.{}",
+            quote_identifier(quote, schema),
+            quote_identifier(quote, &filter.table)
+        ),
+        _ => quote_identifier(quote, &filter.table),
+    };
+
+    let mut query = format!("SELECT * FROM {}", table_ref);
+    let mut params = HashMap::new();
+
+    if let Some(node) = &filter.filter {
+        let built = compile_filter(driver, quote, node)?;
+        query.push_str(&format!(" WHERE {}", built.sql));
+        params = built.params;
+    }
+
+    if let Some(column) = filter.sort_column.as_deref() {
+        let direction = match filter.sort_direction.as_deref() {
+            Some(d) if d.eq_ignore_ascii_case("desc") => "DESC",
+            _ => "ASC",
+        };
+        query.push_str(&format!(
+            " ORDER BY {} {}",
+            quote_identifier(quote, column),
+            direction
+        ));
+    }
+
+    Ok(NamedFilterQuery { query, params })
+}
+
+#[tauri::command]
+pub async fn list_named_filters(
+    connection_id: String,
+    table: Option<String>,
+    schema: Option<String>,
+) -> Result<Vec<NamedFilter>, String> {
+    let filters = load_named_filters(&connection_id)?;
+
+    Ok(filters
+        .into_iter()
+        .filter(|f| table.as_ref().map_or(true, |t| &f.table == t))
+        .filter(|f| schema.is_none() || f.schema == schema)
+        .collect())
+}
+
+#[tauri::command]
+pub async fn save_named_filter(
+    connection_id: String,
+    filter: NamedFilter,
+) -> Result<NamedFilter, String> {
+    let mut filters = load_named_filters(&connection_id)?;
+    let now = chrono::Utc::now().to_rfc3339();
+    let saved = upsert_named_filter(&mut filters, filter, &now)?;
+    save_named_filters(&connection_id, &filters)?;
+    Ok(saved)
+}
+
+#[tauri::command]
+pub async fn delete_named_filter(connection_id: String, id: String) -> Result<(), String> {
+    let mut filters = load_named_filters(&connection_id)?;
+    let original_len = filters.len();
+    filters.retain(|f| f.id != id);
+
+    if filters.len() == original_len {
+        return Err(crate::i18n::t("errors.namedFilterNotFound"));
+    }
+
+    save_named_filters(&connection_id, &filters)
+}
+
+/// Returns the query that applies the named filter to its table, with the
+/// values of its placeholders.
+#[tauri::command]
+pub async fn apply_named_filter<R: tauri::Runtime>(
+    app: tauri::AppHandle<R>,
+    connection_id: String,
+    id: String,
+) -> Result<NamedFilterQuery, String> {
+    let filter = load_named_filters(&connection_id)?
+        .into_iter()
+        .find(|f| f.id == id)
+        .ok_or_else(|| crate::i18n::t("errors.namedFilterNotFound"))?;
+
+    let saved_conn = crate::commands::find_connection_by_id(&app, &connection_id)?;
+    let drv = crate::drivers::registry::get_driver(&saved_conn.params.driver)
+        .await
+        .ok_or_else(|| {
+            crate::i18n::t_with(
+                "errors.unsupportedDriver",
+                &[("driver", &saved_conn.params.driver)],
+            )
+        })?;
+
+    build_named_filter_query(
+        &saved_conn.params.driver,
+        &drv.manifest().capabilities.identifier_quote,
+        &filter,
+    )
+}
+
+/// Serializes the selected named filters (all when `ids` is empty) into a
+/// shareable JSON bundle.
+#[tauri::command]
+pub async fn export_named_filters(
+    connection_id: String,
+    ids: Vec<String>,
+) -> Result<String, String> {
+    let filters: Vec<NamedFilter> = load_named_filters(&connection_id)?
+        .into_iter()
+        .filter(|f| ids.is_empty() || ids.contains(&f.id))
+        .collect();
+
+    let share = NamedFiltersShare {
+        version: NAMED_FILTERS_SHARE_VERSION,
+        filters,
+    };
+
+    serde_json::to_string_pretty(&share)
+        .map_err(|e| format!("Failed to serialize named filters: {}", e))
+}
+
+#[tauri::command]
+pub async fn import_named_filters(
+    connection_id: String,
+    data: String,
+) -> Result<Vec<NamedFilter>, String> {
+    let share: NamedFiltersShare = serde_json::from_str(&data)
+        .map_err(|e| format!("Failed to parse shared filters: {}", e))?;
+
+    if share.version > NAMED_FILTERS_SHARE_VERSION {
+        return Err(format!(
+            "Unsupported shared filters version: {}",
+            share.version
+        ));
+    }
+
+    let mut filters = load_named_filters(&connection_id)?;
+    let now = chrono::Utc::now().to_rfc3339();
+    let imported = merge_shared_filters(&mut filters, share.filters, &now);
+    save_named_filters(&connection_id, &filters)?;
+    Ok(imported)
+}
+
+/// Get the column layouts file path for a specific connection
+fn get_column_layouts_path(connection_id: &str) -> PathBuf {
+    let mut prefs_dir = get_preferences_dir();
+    prefs_dir.push(connection_id);
+    prefs_dir.push(COLUMN_LAYOUTS_FILE);
+    prefs_dir
+}
+
+/// Key used to store a table's layout (`schema.table`, or just `table` without schema)
+pub fn column_layout_key(schema: Option<&str>, table: &str) -> String {
+    match schema {
+        Some(schema) if !schema.is_empty() => format!("{}.{}", schema, table),
+        _ => table.to_string(),
+    }
+}
+
+/// Parse a `column_layouts.json` file, upgrading older versions to the current one.
+pub fn parse_column_layouts(content: &str) -> Result<ColumnLayoutsFile, String> {
+    let mut file: ColumnLayoutsFile = serde_json::from_str(content)
+        .map_err(|e| format!("Failed to parse column layouts file: {}", e))?;
+
+    if file.version > COLUMN_LAYOUTS_VERSION {
+        return Err(format!(
+            "Column layouts file version {} is newer than supported version {}",
+            file.version, COLUMN_LAYOUTS_VERSION
+        ));
+    }
+
+    // Version 0 (unversioned) files share the current structure
+    file.version = COLUMN_LAYOUTS_VERSION;
+    Ok(file)
+}
+
+fn load_column_layouts(connection_id: &str) -> Result<ColumnLayoutsFile, String> {
+    let path = get_column_layouts_path(connection_id);
+
+    if !path.exists() {
+        return Ok(ColumnLayoutsFile {
+            version: COLUMN_LAYOUTS_VERSION,
+            layouts: HashMap::new(),
+        });
+    }
+
+    let content = fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read column layouts file: {}", e))?;
+
+    parse_column_layouts(&content)
+}
+
+fn save_column_layouts(connection_id: &str, file: &ColumnLayoutsFile) -> Result<(), String> {
+    ensure_preferences_dir(connection_id)?;
+
+    let path = get_column_layouts_path(connection_id);
+    let json = serde_json::to_string_pretty(file)
+        .map_err(|e| format!("Failed to serialize column layouts: {}", e))?;
+
+    fs::write(&path, json).map_err(|e| format!("Failed to write column layouts file: {}", e))
+}
+
+/// Load the column layout of a single table, if one has been saved
+pub fn load_column_layout(
+    connection_id: &str,
+    schema: Option<&str>,
+    table: &str,
+) -> Result<Option<ColumnLayout>, String> {
+    let mut file = load_column_layouts(connection_id)?;
+    Ok(file.layouts.remove(&column_layout_key(schema, table)))
+}
+
+#[tauri::command]
+pub async fn get_column_layout(
+    connection_id: String,
+    table: String,
+    schema: Option<String>,
+) -> Result<Option<ColumnLayout>, String> {
+    load_column_layout(&connection_id, schema.as_deref(), &table)
+}
+
+/// Save a table's column layout. When `expected_revision` is given and does not
+/// match the stored revision the save is rejected, so a stale window cannot
+/// overwrite newer changes.
+#[tauri::command]
+pub async fn save_column_layout(
+    connection_id: String,
+    table: String,
+    schema: Option<String>,
+    mut layout: ColumnLayout,
+    expected_revision: Option<u32>,
+) -> Result<ColumnLayout, String> {
+    let mut file = load_column_layouts(&connection_id)?;
+    let key = column_layout_key(schema.as_deref(), &table);
+    let current_revision = file.layouts.get(&key).map(|l| l.revision).unwrap_or(0);
+
+    if let Some(expected) = expected_revision {
+        if expected != current_revision {
+            return Err(format!(
+                "Column layout for {} was modified elsewhere (revision {}, expected {})",
+                key, current_revision, expected
+            ));
+        }
+    }
+
+    layout.revision = current_revision + 1;
+    layout.updated_at = Some(chrono::Utc::now().to_rfc3339());
+    file.layouts.insert(key, layout.clone());
+    save_column_layouts(&connection_id, &file)?;
+
+    Ok(layout)
+}
+
+#[tauri::command]
+pub async fn delete_column_layout(
+    connection_id: String,
+    table: String,
+    schema: Option<String>,
+) -> Result<(), String> {
+    let mut file = load_column_layouts(&connection_id)?;
+
+    if file
+        .layouts
+        .remove(&column_layout_key(schema.as_deref(), &table))
+        .is_some()
+    {
+        save_column_layouts(&connection_id, &file)?;
+    }
+
+    Ok(())
+}
+
+/// Get the computed columns file path for a specific connection
+fn get_computed_columns_path(connection_id: &str) -> PathBuf {
+    let mut prefs_dir = get_preferences_dir();
+    prefs_dir.push(connection_id);
+    prefs_dir.push(COMPUTED_COLUMNS_FILE);
+    prefs_dir
+}
+
+fn load_all_computed_columns(
+    connection_id: &str,
+) -> Result<HashMap<String, Vec<ComputedColumn>>, String> {
+    let path = get_computed_columns_path(connection_id);
+
+    if !path.exists() {
+        return Ok(HashMap::new());
+    }
+
+    let content = fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read computed columns file: {}", e))?;
+
+    serde_json::from_str(&content)
+        .map_err(|e| format!("Failed to parse computed columns file: {}", e))
+}
+
+fn save_all_computed_columns(
+    connection_id: &str,
+    columns: &HashMap<String, Vec<ComputedColumn>>,
+) -> Result<(), String> {
+    ensure_preferences_dir(connection_id)?;
+
+    let path = get_computed_columns_path(connection_id);
+    let json = serde_json::to_string_pretty(columns)
+        .map_err(|e| format!("Failed to serialize computed columns: {}", e))?;
+
+    fs::write(&path, json).map_err(|e| format!("Failed to write computed columns file: {}", e))
+}
+
+/// Load the computed columns defined for a single table
+pub fn load_computed_columns(
+    connection_id: &str,
+    schema: Option<&str>,
+    table: &str,
+) -> Result<Vec<ComputedColumn>, String> {
+    let mut all = load_all_computed_columns(connection_id)?;
+    Ok(all
+        .remove(&column_layout_key(schema, table))
+        .unwrap_or_default())
+}
+
+/// Validate a table's computed column definitions before they are saved.
+pub fn validate_computed_columns(columns: &[ComputedColumn]) -> Result<(), String> {
+    for (i, column) in columns.iter().enumerate() {
+        if column.name.trim().is_empty() {
+            return Err("Computed column name cannot be empty".to_string());
+        }
+        if column.expression.trim().is_empty() {
+            return Err(format!(
+                "Computed column '{}' has an empty expression",
+                column.name
+            ));
+        }
+        // The expression is spliced into the select list, so it must stay a
+        // single expression
+        if column.expression.contains(';') {
+            return Err(format!(
+                "Computed column '{}' expression must not contain ';'",
+                column.name
+            ));
+        }
+        if columns[..i]
+            .iter()
+            .any(|c| c.name.eq_ignore_ascii_case(&column.name))
+        {
+            return Err(format!("Duplicate computed column name: {}", column.name));
+        }
+    }
+    Ok(())
+}
+
+/// Inject enabled computed columns into a `SELECT * FROM ...` browse query.
+/// Other queries are returned unchanged.
+pub fn apply_computed_columns(quote: &str, query: &str, columns: &[ComputedColumn]) -> String {
+    let items: Vec<String> = columns
+        .iter()
+        .filter(|c| c.enabled)
+        .map(|c| {
+            format!(
+                "({}) AS {}",
+                c.expression.trim(),
+                quote_identifier(quote, &c.name)
+            )
+        })
+        .collect();
+
+    crate::drivers::common::append_select_columns(query, &items)
+        .unwrap_or_else(|| query.to_string())
+}
+
+#[tauri::command]
+pub async fn get_computed_columns(
+    connection_id: String,
+    table: String,
+    schema: Option<String>,
+) -> Result<Vec<ComputedColumn>, String> {
+    load_computed_columns(&connection_id, schema.as_deref(), &table)
+}
+
+/// Replace the computed columns of a table. An empty list removes them.
+#[tauri::command]
+pub async fn save_computed_columns(
+    connection_id: String,
+    table: String,
+    schema: Option<String>,
+    columns: Vec<ComputedColumn>,
+) -> Result<(), String> {
+    validate_computed_columns(&columns)?;
+
+    let mut all = load_all_computed_columns(&connection_id)?;
+    let key = column_layout_key(schema.as_deref(), &table);
+    if columns.is_empty() {
+        all.remove(&key);
+    } else {
+        all.insert(key, columns);
+    }
+
+    save_all_computed_columns(&connection_id, &all)
+}
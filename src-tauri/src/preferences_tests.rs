@@ -0,0 +1,210 @@
+#[cfg(test)]
+mod tests {
+    use crate::filter_builder::{FilterNode, FilterOperator};
+    use crate::preferences::{
+        apply_computed_columns, build_named_filter_query, column_layout_key, merge_shared_filters,
+        parse_column_layouts, parse_named_filters, upsert_named_filter, validate_computed_columns,
+        validate_connection_id, ComputedColumn, NamedFilter, COLUMN_LAYOUTS_VERSION,
+    };
+    use serde_json::json;
+
+    const NOW: &str = "2024-01-01T00:00:00Z";
+
+    fn equals(column: &str, value: &str) -> FilterNode {
+        FilterNode::Condition {
+            column: column.into(),
+            operator: FilterOperator::Eq,
+            value: Some(json!(value)),
+            values: Vec::new(),
+            case_insensitive: false,
+        }
+    }
+
+    fn make_filter(id: &str, name: &str, table: &str) -> NamedFilter {
+        NamedFilter {
+            id: id.into(),
+            name: name.into(),
+            table: table.into(),
+            schema: None,
+            filter: Some(equals("status", "active")),
+            sort_column: None,
+            sort_direction: None,
+            created_at: None,
+            updated_at: None,
+        }
+    }
+
+    #[test]
+    fn upsert_assigns_id_to_new_filter() {
+        let mut filters = Vec::new();
+        let saved =
+            upsert_named_filter(&mut filters, make_filter("", "Active", "users"), NOW).unwrap();
+        assert!(!saved.id.is_empty());
+        assert_eq!(saved.created_at.as_deref(), Some(NOW));
+        assert_eq!(filters.len(), 1);
+    }
+
+    #[test]
+    fn upsert_replaces_existing_by_id_and_keeps_created_at() {
+        let mut filters = vec![NamedFilter {
+            created_at: Some("2023-01-01T00:00:00Z".into()),
+            ..make_filter("f1", "Active", "users")
+        }];
+        let mut changed = make_filter("f1", "Active EU", "users");
+        changed.filter = Some(equals("region", "EU"));
+
+        let saved = upsert_named_filter(&mut filters, changed, NOW).unwrap();
+        assert_eq!(filters.len(), 1);
+        assert_eq!(filters[0].name, "Active EU");
+        assert_eq!(saved.created_at.as_deref(), Some("2023-01-01T00:00:00Z"));
+        assert_eq!(saved.updated_at.as_deref(), Some(NOW));
+    }
+
+    #[test]
+    fn upsert_rejects_duplicate_name_on_same_table() {
+        let mut filters = vec![make_filter("f1", "Active", "users")];
+        let result = upsert_named_filter(&mut filters, make_filter("", "active", "users"), NOW);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn upsert_allows_same_name_on_other_table() {
+        let mut filters = vec![make_filter("f1", "Active", "users")];
+        let result = upsert_named_filter(&mut filters, make_filter("", "Active", "orders"), NOW);
+        assert!(result.is_ok());
+        assert_eq!(filters.len(), 2);
+    }
+
+    #[test]
+    fn upsert_rejects_blank_name() {
+        let mut filters = Vec::new();
+        assert!(upsert_named_filter(&mut filters, make_filter("", "  ", "users"), NOW).is_err());
+    }
+
+    #[test]
+    fn merge_renames_conflicting_filters_and_assigns_new_ids() {
+        let mut filters = vec![make_filter("f1", "Active", "users")];
+        let imported = merge_shared_filters(
+            &mut filters,
+            vec![make_filter("f1", "Active", "users")],
+            NOW,
+        );
+        assert_eq!(imported.len(), 1);
+        assert_eq!(imported[0].name, "Active (2)");
+        assert_ne!(imported[0].id, "f1");
+        assert_eq!(filters.len(), 2);
+    }
+
+    #[test]
+    fn query_binds_filter_values_and_sorts() {
+        let mut filter = make_filter("f1", "Active", "users");
+        filter.schema = Some("public".into());
+        filter.filter = Some(equals("status", "x'; DROP TABLE users; --"));
+        filter.sort_column = Some("created_at".into());
+        filter.sort_direction = Some("desc".into());
+        let built = build_named_filter_query("postgres", "\"", &filter).unwrap();
+        assert_eq!(
+            built.query,
+            r#"SELECT * FROM "public"."users" WHERE "status" = :p0 ORDER BY "created_at" DESC"#
+        );
+        assert_eq!(built.params["p0"], json!("x'; DROP TABLE users; --"));
+    }
+
+    #[test]
+    fn query_without_filter_or_sort() {
+        let mut filter = make_filter("f1", "All", "users");
+        filter.filter = None;
+        let built = build_named_filter_query("mysql", "`", &filter).unwrap();
+        assert_eq!(built.query, "SELECT * FROM `users`");
+        assert!(built.params.is_empty());
+    }
+
+    #[test]
+    fn legacy_raw_where_presets_are_dropped() {
+        let content = json!([
+            { "id": "old", "name": "Old", "table": "users", "filter": "1 = 1; DROP TABLE users" },
+            { "id": "new", "name": "New", "table": "users",
+              "filter": { "type": "condition", "column": "id", "operator": "eq", "value": 1 } },
+        ])
+        .to_string();
+        let parsed = parse_named_filters(&content).unwrap();
+        assert!(parsed[0].is_err());
+        assert_eq!(parsed[1].as_ref().unwrap().id, "new");
+    }
+
+    #[test]
+    fn connection_ids_must_be_a_single_path_component() {
+        assert!(validate_connection_id("3f2b9c1e-conn").is_ok());
+        for id in ["", "..", "../../x", "a/b", "a\\b", "/etc", "a..b", "."] {
+            assert!(validate_connection_id(id).is_err(), "{}", id);
+        }
+    }
+
+    #[test]
+    fn column_layout_key_includes_schema_when_present() {
+        assert_eq!(column_layout_key(Some("public"), "users"), "public.users");
+        assert_eq!(column_layout_key(Some(""), "users"), "users");
+        assert_eq!(column_layout_key(None, "users"), "users");
+    }
+
+    #[test]
+    fn parse_column_layouts_upgrades_unversioned_file() {
+        let file = parse_column_layouts(
+            r#"{"layouts": {"users": {"columns": [{"name": "id", "width": 80}]}}}"#,
+        )
+        .unwrap();
+        assert_eq!(file.version, COLUMN_LAYOUTS_VERSION);
+        let layout = &file.layouts["users"];
+        assert_eq!(layout.columns[0].name, "id");
+        assert_eq!(layout.columns[0].width, Some(80));
+        assert!(!layout.columns[0].hidden);
+        assert_eq!(layout.revision, 0);
+    }
+
+    #[test]
+    fn parse_column_layouts_rejects_newer_version() {
+        let content = format!(
+            r#"{{"version": {}, "layouts": {{}}}}"#,
+            COLUMN_LAYOUTS_VERSION + 1
+        );
+        assert!(parse_column_layouts(&content).is_err());
+    }
+
+    fn computed(name: &str, expression: &str) -> ComputedColumn {
+        ComputedColumn {
+            name: name.into(),
+            expression: expression.into(),
+            enabled: true,
+        }
+    }
+
+    #[test]
+    fn computed_columns_are_appended_to_browse_query() {
+        let mut disabled = computed("off", "1");
+        disabled.enabled = false;
+        let query = apply_computed_columns(
+            "`",
+            "SELECT * FROM `orders` LIMIT 100",
+            &[computed("total", "price * qty"), disabled],
+        );
+        assert_eq!(
+            query,
+            "SELECT *, (price * qty) AS `total` FROM `orders` LIMIT 100"
+        );
+    }
+
+    #[test]
+    fn computed_columns_leave_custom_queries_untouched() {
+        let query = apply_computed_columns("\"", "SELECT id FROM orders", &[computed("t", "1")]);
+        assert_eq!(query, "SELECT id FROM orders");
+    }
+
+    #[test]
+    fn validate_computed_columns_rejects_bad_definitions() {
+        assert!(validate_computed_columns(&[computed("total", "price * qty")]).is_ok());
+        assert!(validate_computed_columns(&[computed(" ", "1")]).is_err());
+        assert!(validate_computed_columns(&[computed("a", "")]).is_err());
+        assert!(validate_computed_columns(&[computed("a", "1; DROP TABLE t")]).is_err());
+        assert!(validate_computed_columns(&[computed("a", "1"), computed("A", "2")]).is_err());
+    }
+}
@@ -0,0 +1,165 @@
+//! Background warm-up of connections marked "connect on startup".
+//!
+//! At launch each such connection opens its SSH tunnel and pool and reads
+//! the metadata the sidebar asks for first (the schema list, or the tables
+//! of the database/schema it will open). The metadata is parked here and
+//! handed to the first `get_schemas` / `get_tables` call for it, so the
+//! first click on the connection does not wait for the network. Parked
+//! entries are served once and expire after [`WARM_TTL`].
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use once_cell::sync::Lazy;
+use tauri::{AppHandle, Runtime};
+
+use crate::commands::{
+    driver_for, expand_ssh_connection_params, resolve_connection_params_with_id,
+};
+use crate::models::{SavedConnection, TableInfo};
+
+/// How long warmed metadata is served before it is considered stale.
+pub const WARM_TTL: Duration = Duration::from_secs(10 * 60);
+
+type WarmKey = (String, Option<String>);
+
+/// Metadata fetched ahead of time, keyed by connection and schema.
+pub struct WarmCache<T> {
+    entries: Mutex<HashMap<WarmKey, (Instant, T)>>,
+}
+
+impl<T> Default for WarmCache<T> {
+    fn default() -> Self {
+        Self {
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+}
+
+impl<T> WarmCache<T> {
+    pub fn store(&self, connection_id: &str, schema: Option<&str>, value: T) {
+        self.entries.lock().unwrap().insert(
+            (connection_id.to_string(), schema.map(str::to_string)),
+            (Instant::now(), value),
+        );
+    }
+
+    /// Remove and return the entry, unless it is older than `ttl`.
+    pub fn take(&self, connection_id: &str, schema: Option<&str>, ttl: Duration) -> Option<T> {
+        let key = (connection_id.to_string(), schema.map(str::to_string));
+        let (stored_at, value) = self.entries.lock().unwrap().remove(&key)?;
+        (stored_at.elapsed() <= ttl).then_some(value)
+    }
+
+    /// Drop every entry of `connection_id`.
+    pub fn forget(&self, connection_id: &str) {
+        self.entries
+            .lock()
+            .unwrap()
+            .retain(|(id, _), _| id != connection_id);
+    }
+}
+
+static WARM_SCHEMAS: Lazy<WarmCache<Vec<String>>> = Lazy::new(WarmCache::default);
+static WARM_TABLES: Lazy<WarmCache<Vec<TableInfo>>> = Lazy::new(WarmCache::default);
+
+/// Warmed schema list of `connection_id`, if any.
+pub fn take_schemas(connection_id: &str) -> Option<Vec<String>> {
+    WARM_SCHEMAS.take(connection_id, None, WARM_TTL)
+}
+
+/// Warmed table list of `connection_id` in `schema`, if any.
+pub fn take_tables(connection_id: &str, schema: Option<&str>) -> Option<Vec<TableInfo>> {
+    WARM_TABLES.take(connection_id, schema, WARM_TTL)
+}
+
+/// Drop warmed metadata of a connection whose settings changed.
+pub fn forget(connection_id: &str) {
+    WARM_SCHEMAS.forget(connection_id);
+    WARM_TABLES.forget(connection_id);
+}
+
+/// The saved connections to warm at launch.
+pub fn startup_connections(connections: &[SavedConnection]) -> Vec<&SavedConnection> {
+    connections
+        .iter()
+        .filter(|c| c.connect_on_startup.unwrap_or(false))
+        .collect()
+}
+
+/// The schema the sidebar lists tables of when `conn` is opened: the saved
+/// schema preference on drivers with schemas, the first database on
+/// multi-database connections, otherwise none.
+pub fn initial_schema(
+    conn: &SavedConnection,
+    has_schemas: bool,
+    schema_preference: Option<&str>,
+) -> Option<String> {
+    if has_schemas {
+        schema_preference.map(str::to_string)
+    } else if conn.params.database.is_multi() {
+        Some(conn.params.database.primary().to_string())
+    } else {
+        None
+    }
+}
+
+async fn warm<R: Runtime>(app: &AppHandle<R>, conn: &SavedConnection) -> Result<(), String> {
+    let expanded_params = expand_ssh_connection_params(app, &conn.params).await?;
+    let params = resolve_connection_params_with_id(&expanded_params, &conn.id)?;
+    let drv = driver_for(&conn.params.driver).await?;
+    let has_schemas = drv.manifest().capabilities.schemas;
+
+    if has_schemas {
+        let schemas = drv.get_schemas(&params).await?;
+        WARM_SCHEMAS.store(&conn.id, None, schemas);
+    }
+
+    let config = crate::config::load_config_internal(app);
+    let preference = config
+        .schema_preferences
+        .as_ref()
+        .and_then(|prefs| prefs.get(&conn.id))
+        .map(String::as_str);
+    let schema = initial_schema(conn, has_schemas, preference);
+    if has_schemas && schema.is_none() {
+        return Ok(());
+    }
+    let tables = drv.get_tables(&params, schema.as_deref()).await?;
+    WARM_TABLES.store(&conn.id, schema.as_deref(), tables);
+    Ok(())
+}
+
+/// Warm every "connect on startup" connection in the background.
+pub fn spawn<R: Runtime>(app: AppHandle<R>) {
+    let path = match crate::commands::get_config_path(&app) {
+        Ok(path) => path,
+        Err(e) => {
+            log::warn!("Skipping connection warm-up: {}", e);
+            return;
+        }
+    };
+    let connections = crate::persistence::load_connections_file(&path)
+        .map(|file| file.connections)
+        .unwrap_or_default();
+    let warm_list: Vec<SavedConnection> = startup_connections(&connections)
+        .into_iter()
+        .cloned()
+        .collect();
+
+    for conn in warm_list {
+        let app = app.clone();
+        tauri::async_runtime::spawn(async move {
+            let started = Instant::now();
+            match warm(&app, &conn).await {
+                Ok(()) => log::info!(
+                    "Warmed connection {} in {}ms",
+                    conn.name,
+                    started.elapsed().as_millis()
+                ),
+                Err(e) => log::warn!("Failed to warm connection {}: {}", conn.name, e),
+            }
+        });
+    }
+}
@@ -0,0 +1,85 @@
+#[cfg(test)]
+mod tests {
+    use crate::models::{ConnectionParams, DatabaseSelection, SavedConnection};
+    use crate::prewarm::{initial_schema, startup_connections, WarmCache};
+    use std::time::Duration;
+
+    fn connection(id: &str, connect_on_startup: Option<bool>) -> SavedConnection {
+        SavedConnection {
+            id: id.to_string(),
+            name: id.to_string(),
+            params: ConnectionParams {
+                driver: "postgres".to_string(),
+                database: DatabaseSelection::Single("app".to_string()),
+                ..Default::default()
+            },
+            group_id: None,
+            sort_order: None,
+            detect_json_in_text_columns: None,
+            protected: None,
+            read_replicas: None,
+            connect_on_startup,
+        }
+    }
+
+    #[test]
+    fn warms_only_flagged_connections() {
+        let connections = vec![
+            connection("a", Some(true)),
+            connection("b", None),
+            connection("c", Some(false)),
+        ];
+        let ids: Vec<&str> = startup_connections(&connections)
+            .iter()
+            .map(|c| c.id.as_str())
+            .collect();
+        assert_eq!(ids, ["a"]);
+    }
+
+    #[test]
+    fn picks_the_schema_the_sidebar_opens() {
+        let single = connection("a", Some(true));
+        assert_eq!(
+            initial_schema(&single, true, Some("sales")).as_deref(),
+            Some("sales")
+        );
+        assert_eq!(initial_schema(&single, true, None), None);
+        assert_eq!(initial_schema(&single, false, Some("sales")), None);
+
+        let mut multi = connection("b", Some(true));
+        multi.params.database =
+            DatabaseSelection::Multiple(vec!["first".to_string(), "second".to_string()]);
+        assert_eq!(
+            initial_schema(&multi, false, None).as_deref(),
+            Some("first")
+        );
+    }
+
+    #[test]
+    fn cache_serves_each_entry_once() {
+        let cache = WarmCache::default();
+        cache.store("conn", Some("public"), vec!["users"]);
+        let ttl = Duration::from_secs(60);
+
+        assert_eq!(cache.take("conn", None, ttl), None);
+        assert_eq!(cache.take("conn", Some("public"), ttl), Some(vec!["users"]));
+        assert_eq!(cache.take("conn", Some("public"), ttl), None);
+    }
+
+    #[test]
+    fn cache_drops_stale_and_forgotten_entries() {
+        let cache = WarmCache::default();
+        cache.store("conn", None, 1);
+        std::thread::sleep(Duration::from_millis(5));
+        assert_eq!(cache.take("conn", None, Duration::from_millis(1)), None);
+
+        cache.store("conn", None, 2);
+        cache.store("conn", Some("public"), 3);
+        cache.store("other", None, 4);
+        cache.forget("conn");
+        let ttl = Duration::from_secs(60);
+        assert_eq!(cache.take("conn", None, ttl), None);
+        assert_eq!(cache.take("conn", Some("public"), ttl), None);
+        assert_eq!(cache.take("other", None, ttl), Some(4));
+    }
+}
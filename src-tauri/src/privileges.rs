@@ -0,0 +1,112 @@
+//! Users and privileges.
+//!
+//! Drivers that advertise the `privileges` capability list users and their
+//! grants and run `GRANT`/`REVOKE` for them. Each driver builds its own
+//! statements; the privilege keyword is checked here first, since keywords
+//! cannot be bound as parameters.
+
+use tauri::{AppHandle, Runtime};
+
+use crate::commands::{
+    driver_for, expand_ssh_connection_params, find_connection_by_id,
+    resolve_connection_params_with_id,
+};
+use crate::models::{ConnectionParams, DbUser, PrivilegeChange, UserGrant};
+
+// ---------------------------------------------------------------------------
+// Validation (testable; pure)
+// ---------------------------------------------------------------------------
+
+/// `privilege` upper-cased with its whitespace collapsed, e.g. `all
+/// privileges` → `ALL PRIVILEGES`. Anything but letters, spaces and
+/// underscores is rejected.
+pub fn normalize_privilege(privilege: &str) -> Result<String, String> {
+    let words: Vec<&str> = privilege.split_whitespace().collect();
+    let valid = !words.is_empty()
+        && words
+            .iter()
+            .all(|w| w.chars().all(|c| c.is_ascii_alphabetic() || c == '_'));
+    if !valid {
+        return Err(format!("Invalid privilege: {:?}", privilege));
+    }
+    Ok(words.join(" ").to_ascii_uppercase())
+}
+
+// ---------------------------------------------------------------------------
+// Tauri commands
+// ---------------------------------------------------------------------------
+
+async fn resolve<R: Runtime>(
+    app: &AppHandle<R>,
+    connection_id: &str,
+) -> Result<(String, ConnectionParams), String> {
+    let saved_conn = find_connection_by_id(app, connection_id)?;
+    let expanded = expand_ssh_connection_params(app, &saved_conn.params).await?;
+    let params = resolve_connection_params_with_id(&expanded, connection_id)?;
+    Ok((saved_conn.params.driver, params))
+}
+
+#[tauri::command]
+pub async fn get_users<R: Runtime>(
+    app: AppHandle<R>,
+    connection_id: String,
+) -> Result<Vec<DbUser>, String> {
+    let (driver, params) = resolve(&app, &connection_id).await?;
+    driver_for(&driver).await?.get_users(&params).await
+}
+
+#[tauri::command]
+pub async fn get_user_grants<R: Runtime>(
+    app: AppHandle<R>,
+    connection_id: String,
+    user: String,
+    host: Option<String>,
+) -> Result<Vec<UserGrant>, String> {
+    let (driver, params) = resolve(&app, &connection_id).await?;
+    driver_for(&driver)
+        .await?
+        .get_user_grants(&params, &user, host.as_deref())
+        .await
+}
+
+#[tauri::command]
+pub async fn grant_privilege<R: Runtime>(
+    app: AppHandle<R>,
+    connection_id: String,
+    change: PrivilegeChange,
+) -> Result<(), String> {
+    let saved_conn = find_connection_by_id(&app, &connection_id)?;
+    crate::read_only::ensure_writable(&saved_conn.params)?;
+    log::warn!(
+        "Granting {} to {} on connection {}",
+        change.grant.privilege,
+        change.user,
+        connection_id
+    );
+    let (driver, params) = resolve(&app, &connection_id).await?;
+    driver_for(&driver)
+        .await?
+        .grant_privilege(&params, &change)
+        .await
+}
+
+#[tauri::command]
+pub async fn revoke_privilege<R: Runtime>(
+    app: AppHandle<R>,
+    connection_id: String,
+    change: PrivilegeChange,
+) -> Result<(), String> {
+    let saved_conn = find_connection_by_id(&app, &connection_id)?;
+    crate::read_only::ensure_writable(&saved_conn.params)?;
+    log::warn!(
+        "Revoking {} from {} on connection {}",
+        change.grant.privilege,
+        change.user,
+        connection_id
+    );
+    let (driver, params) = resolve(&app, &connection_id).await?;
+    driver_for(&driver)
+        .await?
+        .revoke_privilege(&params, &change)
+        .await
+}
@@ -0,0 +1,25 @@
+#[cfg(test)]
+mod tests {
+    use crate::privileges::normalize_privilege;
+
+    #[test]
+    fn privileges_are_upper_cased_and_collapsed() {
+        assert_eq!(normalize_privilege("select").unwrap(), "SELECT");
+        assert_eq!(
+            normalize_privilege("  all \t privileges ").unwrap(),
+            "ALL PRIVILEGES"
+        );
+        assert_eq!(
+            normalize_privilege("create_temporary_tables").unwrap(),
+            "CREATE_TEMPORARY_TABLES"
+        );
+    }
+
+    #[test]
+    fn anything_but_keywords_is_rejected() {
+        assert!(normalize_privilege("").is_err());
+        assert!(normalize_privilege("SELECT, INSERT").is_err());
+        assert!(normalize_privilege("SELECT ON *.* TO 'x'@'%'; --").is_err());
+        assert!(normalize_privilege("SELECT (id)").is_err());
+    }
+}
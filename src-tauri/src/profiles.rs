@@ -0,0 +1,190 @@
+//! App profiles (e.g. "work" / "personal") with isolated config, connections,
+//! saved queries and history.
+//!
+//! Each profile is a separate config directory (see `paths::profile_dir_in`).
+//! The profile is fixed for the lifetime of the process: switching persists
+//! the choice and relaunches the app with `--profile <name>`.
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+use tauri::AppHandle;
+
+use crate::keychain_utils;
+use crate::paths::{
+    get_active_profile, get_base_config_dir, is_valid_profile_name, profile_dir_in,
+    write_persisted_profile_in, DEFAULT_PROFILE, PROFILES_DIR,
+};
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ProfileInfo {
+    pub name: String,
+    pub active: bool,
+    pub path: String,
+}
+
+fn validate_name(name: &str) -> Result<(), String> {
+    if is_valid_profile_name(name) {
+        Ok(())
+    } else {
+        Err(format!(
+            "Invalid profile name '{}': use letters, digits, '-' or '_' (max 64)",
+            name
+        ))
+    }
+}
+
+/// The default profile followed by the other profiles, sorted by name.
+pub fn list_profiles_in(base: &Path, active: &str) -> Vec<ProfileInfo> {
+    let mut names: Vec<String> = fs::read_dir(base.join(PROFILES_DIR))
+        .map(|entries| {
+            entries
+                .filter_map(|e| e.ok())
+                .filter(|e| e.path().is_dir())
+                .filter_map(|e| e.file_name().to_str().map(str::to_string))
+                .filter(|n| is_valid_profile_name(n) && n != DEFAULT_PROFILE)
+                .collect()
+        })
+        .unwrap_or_default();
+    names.sort();
+    names.insert(0, DEFAULT_PROFILE.to_string());
+
+    names
+        .into_iter()
+        .map(|name| ProfileInfo {
+            active: name == active,
+            path: profile_dir_in(base, &name).to_string_lossy().to_string(),
+            name,
+        })
+        .collect()
+}
+
+pub fn create_profile_in(base: &Path, name: &str) -> Result<ProfileInfo, String> {
+    validate_name(name)?;
+    let dir = profile_dir_in(base, name);
+    if name == DEFAULT_PROFILE || dir.exists() {
+        return Err(format!("Profile '{}' already exists", name));
+    }
+
+    fs::create_dir_all(&dir).map_err(|e| format!("Failed to create profile: {}", e))?;
+    Ok(ProfileInfo {
+        name: name.to_string(),
+        active: false,
+        path: dir.to_string_lossy().to_string(),
+    })
+}
+
+pub fn delete_profile_in(base: &Path, name: &str, active: &str) -> Result<(), String> {
+    validate_name(name)?;
+    if name == DEFAULT_PROFILE {
+        return Err("The default profile cannot be deleted".to_string());
+    }
+    if name == active {
+        return Err("The active profile cannot be deleted".to_string());
+    }
+
+    let dir = profile_dir_in(base, name);
+    if !dir.exists() {
+        return Err(format!("Profile '{}' not found", name));
+    }
+    fs::remove_dir_all(&dir).map_err(|e| format!("Failed to delete profile: {}", e))
+}
+
+/// Ids of the database and SSH connections saved in a profile directory.
+pub fn connection_ids_in(profile_dir: &Path) -> Vec<String> {
+    let mut ids: Vec<String> =
+        crate::persistence::load_connections(&profile_dir.join("connections.json"))
+            .map(|conns| conns.into_iter().map(|c| c.id).collect())
+            .unwrap_or_default();
+
+    if let Ok(content) = fs::read_to_string(profile_dir.join("ssh_connections.json")) {
+        if let Ok(serde_json::Value::Array(items)) = serde_json::from_str(&content) {
+            ids.extend(
+                items
+                    .iter()
+                    .filter_map(|item| item.get("id")?.as_str().map(str::to_string)),
+            );
+        }
+    }
+    ids
+}
+
+/// Ids of profile `name` whose keychain secrets can go with it. Keychain
+/// entries are keyed by connection id alone, and a profile created from a
+/// copied config shares its ids: an id still used by another profile keeps
+/// its secrets.
+pub fn secret_ids_to_delete_in(base: &Path, name: &str) -> Vec<String> {
+    let in_use: std::collections::HashSet<String> = list_profiles_in(base, "")
+        .into_iter()
+        .filter(|p| p.name != name)
+        .flat_map(|p| connection_ids_in(Path::new(&p.path)))
+        .collect();
+    connection_ids_in(&profile_dir_in(base, name))
+        .into_iter()
+        .filter(|id| !in_use.contains(id))
+        .collect()
+}
+
+#[tauri::command]
+pub fn list_profiles() -> Vec<ProfileInfo> {
+    list_profiles_in(&get_base_config_dir(), &get_active_profile())
+}
+
+#[tauri::command]
+pub fn get_active_profile_name() -> String {
+    get_active_profile()
+}
+
+#[tauri::command]
+pub fn create_profile(name: String) -> Result<ProfileInfo, String> {
+    create_profile_in(&get_base_config_dir(), &name)
+}
+
+/// Delete a profile and the keychain secrets of its connections that no
+/// other profile uses.
+#[tauri::command]
+pub fn delete_profile(name: String) -> Result<(), String> {
+    let base = get_base_config_dir();
+    validate_name(&name)?;
+
+    if name != DEFAULT_PROFILE && name != get_active_profile() {
+        for id in secret_ids_to_delete_in(&base, &name) {
+            keychain_utils::delete_db_password(&id).ok();
+            keychain_utils::delete_ssh_password(&id).ok();
+            keychain_utils::delete_ssh_key_passphrase(&id).ok();
+        }
+    }
+
+    delete_profile_in(&base, &name, &get_active_profile())
+}
+
+/// Remember `name` as the profile to use and relaunch the app with it.
+#[tauri::command]
+pub fn switch_profile(app: AppHandle, name: String) -> Result<(), String> {
+    validate_name(&name)?;
+    let base = get_base_config_dir();
+    if !profile_dir_in(&base, &name).exists() {
+        return Err(format!("Profile '{}' not found", name));
+    }
+    if name == get_active_profile() {
+        return Ok(());
+    }
+
+    write_persisted_profile_in(&base, &name)?;
+
+    let exe =
+        std::env::current_exe().map_err(|e| format!("Failed to get executable path: {}", e))?;
+    let mut command = std::process::Command::new(exe);
+    command.arg("--profile").arg(&name);
+    if crate::paths::get_portable_root().is_some() {
+        command.arg("--portable");
+    }
+    command
+        .spawn()
+        .map_err(|e| format!("Failed to relaunch with profile '{}': {}", name, e))?;
+
+    log::info!("Switching to profile '{}'", name);
+    app.exit(0);
+    Ok(())
+}
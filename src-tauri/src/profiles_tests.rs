@@ -0,0 +1,121 @@
+#[cfg(test)]
+mod tests {
+    use crate::paths::{
+        is_valid_profile_name, profile_dir_in, read_persisted_profile_in,
+        write_persisted_profile_in, DEFAULT_PROFILE,
+    };
+    use crate::profiles::{
+        connection_ids_in, create_profile_in, delete_profile_in, list_profiles_in,
+        secret_ids_to_delete_in,
+    };
+    use tempfile::TempDir;
+
+    #[test]
+    fn profile_names_are_directory_safe() {
+        assert!(is_valid_profile_name("client-acme_2"));
+        assert!(!is_valid_profile_name(""));
+        assert!(!is_valid_profile_name("../work"));
+        assert!(!is_valid_profile_name("my profile"));
+        assert!(!is_valid_profile_name(&"a".repeat(65)));
+    }
+
+    #[test]
+    fn default_profile_uses_base_dir() {
+        let tmp = TempDir::new().unwrap();
+        assert_eq!(profile_dir_in(tmp.path(), DEFAULT_PROFILE), tmp.path());
+        assert_eq!(
+            profile_dir_in(tmp.path(), "work"),
+            tmp.path().join("profiles").join("work")
+        );
+    }
+
+    #[test]
+    fn persisted_profile_round_trip() {
+        let tmp = TempDir::new().unwrap();
+        assert_eq!(read_persisted_profile_in(tmp.path()), None);
+
+        write_persisted_profile_in(tmp.path(), "work").unwrap();
+        assert_eq!(
+            read_persisted_profile_in(tmp.path()).as_deref(),
+            Some("work")
+        );
+
+        std::fs::write(tmp.path().join("active_profile"), "../etc").unwrap();
+        assert_eq!(read_persisted_profile_in(tmp.path()), None);
+    }
+
+    #[test]
+    fn create_and_list_profiles() {
+        let tmp = TempDir::new().unwrap();
+        create_profile_in(tmp.path(), "work").unwrap();
+        create_profile_in(tmp.path(), "personal").unwrap();
+
+        let profiles = list_profiles_in(tmp.path(), "work");
+        let names: Vec<&str> = profiles.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, vec!["default", "personal", "work"]);
+        assert!(profiles[2].active);
+        assert!(!profiles[0].active);
+    }
+
+    #[test]
+    fn create_rejects_duplicates_and_invalid_names() {
+        let tmp = TempDir::new().unwrap();
+        create_profile_in(tmp.path(), "work").unwrap();
+        assert!(create_profile_in(tmp.path(), "work").is_err());
+        assert!(create_profile_in(tmp.path(), DEFAULT_PROFILE).is_err());
+        assert!(create_profile_in(tmp.path(), "a/b").is_err());
+    }
+
+    #[test]
+    fn delete_refuses_default_and_active_profiles() {
+        let tmp = TempDir::new().unwrap();
+        create_profile_in(tmp.path(), "work").unwrap();
+
+        assert!(delete_profile_in(tmp.path(), DEFAULT_PROFILE, "work").is_err());
+        assert!(delete_profile_in(tmp.path(), "work", "work").is_err());
+        assert!(delete_profile_in(tmp.path(), "missing", DEFAULT_PROFILE).is_err());
+
+        delete_profile_in(tmp.path(), "work", DEFAULT_PROFILE).unwrap();
+        assert!(!profile_dir_in(tmp.path(), "work").exists());
+    }
+
+    #[test]
+    fn connection_ids_cover_db_and_ssh_connections() {
+        let tmp = TempDir::new().unwrap();
+        std::fs::write(
+            tmp.path().join("ssh_connections.json"),
+            r#"[{"id": "ssh-1", "name": "bastion"}]"#,
+        )
+        .unwrap();
+
+        assert_eq!(connection_ids_in(tmp.path()), vec!["ssh-1".to_string()]);
+    }
+
+    #[test]
+    fn secrets_shared_with_another_profile_are_kept() {
+        let tmp = TempDir::new().unwrap();
+        let ssh = |dir: &std::path::Path, ids: &[&str]| {
+            let items: Vec<String> = ids
+                .iter()
+                .map(|id| format!(r#"{{"id": "{}"}}"#, id))
+                .collect();
+            std::fs::write(
+                dir.join("ssh_connections.json"),
+                format!("[{}]", items.join(",")),
+            )
+            .unwrap();
+        };
+        let work = create_profile_in(tmp.path(), "work").unwrap();
+        create_profile_in(tmp.path(), "copy").unwrap();
+        ssh(std::path::Path::new(&work.path), &["shared", "own"]);
+        ssh(&profile_dir_in(tmp.path(), "copy"), &["shared"]);
+
+        assert_eq!(
+            secret_ids_to_delete_in(tmp.path(), "work"),
+            vec!["own".to_string()]
+        );
+        // The default profile lives in the base directory.
+        ssh(tmp.path(), &["own"]);
+        assert!(secret_ids_to_delete_in(tmp.path(), "work").is_empty());
+    }
+}
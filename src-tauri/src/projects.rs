@@ -0,0 +1,362 @@
+//! Project mode: a folder of `.sql` files bound to a connection by a
+//! `tabularis.project.json` file, run in a fixed order. A lightweight
+//! migrations / automation workflow.
+//!
+//! Layout of a project folder:
+//!   - `tabularis.project.json` — connection, schema and run order
+//!   - `*.sql`                  — the scripts
+//!   - `.tabularis/state.json`  — last run of each file (hash, status, error)
+//!
+//! A file counts as "changed" when its content differs from the last
+//! successful run, so "run changed" re-applies edited and new scripts only.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Component, Path};
+use std::sync::Arc;
+use std::time::Instant;
+use tauri::{AppHandle, Emitter, Runtime, State, WebviewWindow};
+
+use crate::commands::{
+    driver_for, expand_ssh_connection_params, find_connection_by_id, register_abort_handle,
+    resolve_connection_params_with_id, sanitize_user_query, unregister_abort_handle,
+    QueryCancellationState,
+};
+
+pub const PROJECT_FILE: &str = "tabularis.project.json";
+const STATE_DIR: &str = ".tabularis";
+const STATE_FILE: &str = "state.json";
+const PROJECT_PROGRESS_EVENT: &str = "project://file-status";
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectConfig {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    pub connection_id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub schema: Option<String>,
+    /// Run order, as paths relative to the project folder. When empty, every
+    /// `.sql` file at the top of the folder runs, sorted by name.
+    #[serde(default)]
+    pub files: Vec<String>,
+    /// Keep running the next files after one fails. Default: false
+    #[serde(default)]
+    pub continue_on_error: bool,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum RunStatus {
+    Success,
+    Failed,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct FileRunRecord {
+    pub status: RunStatus,
+    /// Content hash of the file when it ran.
+    pub hash: String,
+    pub ran_at: String,
+    pub duration_ms: f64,
+    pub statements: usize,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectFileStatus {
+    pub file: String,
+    pub exists: bool,
+    pub hash: Option<String>,
+    /// True when the file never ran successfully with its current content.
+    pub changed: bool,
+    pub last_run: Option<FileRunRecord>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Project {
+    pub path: String,
+    pub config: ProjectConfig,
+    pub files: Vec<ProjectFileStatus>,
+}
+
+// ---------------------------------------------------------------------------
+// Project files (testable; take the project directory)
+// ---------------------------------------------------------------------------
+
+/// Project file entries must stay inside the project folder.
+fn validate_entry(entry: &str) -> Result<(), String> {
+    let path = Path::new(entry);
+    let escapes = path
+        .components()
+        .any(|c| !matches!(c, Component::Normal(_) | Component::CurDir));
+    if entry.trim().is_empty() || escapes {
+        return Err(format!("Invalid project file entry: {}", entry));
+    }
+    Ok(())
+}
+
+pub fn load_project_in(dir: &Path) -> Result<ProjectConfig, String> {
+    let path = dir.join(PROJECT_FILE);
+    let content = fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    let config: ProjectConfig = serde_json::from_str(&content)
+        .map_err(|e| format!("Failed to parse {}: {}", PROJECT_FILE, e))?;
+    for entry in &config.files {
+        validate_entry(entry)?;
+    }
+    Ok(config)
+}
+
+pub fn save_project_in(dir: &Path, config: &ProjectConfig) -> Result<(), String> {
+    for entry in &config.files {
+        validate_entry(entry)?;
+    }
+    fs::create_dir_all(dir).map_err(|e| format!("Failed to create project folder: {}", e))?;
+    let json = serde_json::to_string_pretty(config)
+        .map_err(|e| format!("Failed to serialize project: {}", e))?;
+    fs::write(dir.join(PROJECT_FILE), json).map_err(|e| format!("Failed to save project: {}", e))
+}
+
+/// The files to run, in order.
+pub fn resolve_files_in(dir: &Path, config: &ProjectConfig) -> Result<Vec<String>, String> {
+    if !config.files.is_empty() {
+        return Ok(config.files.clone());
+    }
+
+    let mut files: Vec<String> = fs::read_dir(dir)
+        .map_err(|e| format!("Failed to read project folder: {}", e))?
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.path().is_file())
+        .filter_map(|entry| entry.file_name().to_str().map(str::to_string))
+        .filter(|name| name.to_lowercase().ends_with(".sql"))
+        .collect();
+    files.sort();
+    Ok(files)
+}
+
+pub fn read_state_in(dir: &Path) -> BTreeMap<String, FileRunRecord> {
+    fs::read_to_string(dir.join(STATE_DIR).join(STATE_FILE))
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+pub fn record_run_in(dir: &Path, file: &str, record: FileRunRecord) -> Result<(), String> {
+    let mut state = read_state_in(dir);
+    state.insert(file.to_string(), record);
+
+    let state_dir = dir.join(STATE_DIR);
+    fs::create_dir_all(&state_dir).map_err(|e| format!("Failed to create state dir: {}", e))?;
+    let json = serde_json::to_string_pretty(&state)
+        .map_err(|e| format!("Failed to serialize project state: {}", e))?;
+    fs::write(state_dir.join(STATE_FILE), json)
+        .map_err(|e| format!("Failed to save project state: {}", e))
+}
+
+pub fn project_status_in(dir: &Path) -> Result<Project, String> {
+    let config = load_project_in(dir)?;
+    let mut state = read_state_in(dir);
+
+    let files = resolve_files_in(dir, &config)?
+        .into_iter()
+        .map(|file| {
+            let hash = fs::read(dir.join(&file))
+                .ok()
+                .map(|bytes| crate::sql_files::content_hash(&bytes));
+            let last_run = state.remove(&file);
+            let changed = hash.is_some()
+                && !last_run.as_ref().map_or(false, |run| {
+                    run.status == RunStatus::Success && Some(&run.hash) == hash.as_ref()
+                });
+            ProjectFileStatus {
+                exists: hash.is_some(),
+                file,
+                hash,
+                changed,
+                last_run,
+            }
+        })
+        .collect();
+
+    Ok(Project {
+        path: dir.to_string_lossy().to_string(),
+        config,
+        files,
+    })
+}
+
+/// Files a run should execute: all existing files, or only the changed ones.
+pub fn files_to_run(project: &Project, only_changed: bool) -> Vec<String> {
+    project
+        .files
+        .iter()
+        .filter(|f| f.exists && (!only_changed || f.changed))
+        .map(|f| f.file.clone())
+        .collect()
+}
+
+// ---------------------------------------------------------------------------
+// Tauri commands
+// ---------------------------------------------------------------------------
+
+/// Load a project folder with the status of each of its files.
+#[tauri::command]
+pub fn open_project(path: String) -> Result<Project, String> {
+    project_status_in(Path::new(&path))
+}
+
+/// Turn a folder into a project bound to `connection_id`.
+#[tauri::command]
+pub fn init_project(
+    path: String,
+    connection_id: String,
+    schema: Option<String>,
+) -> Result<Project, String> {
+    let dir = Path::new(&path);
+    if dir.join(PROJECT_FILE).exists() {
+        return Err(format!("{} already exists in this folder", PROJECT_FILE));
+    }
+    let config = ProjectConfig {
+        name: dir.file_name().map(|n| n.to_string_lossy().to_string()),
+        connection_id,
+        schema,
+        files: Vec::new(),
+        continue_on_error: false,
+    };
+    save_project_in(dir, &config)?;
+    project_status_in(dir)
+}
+
+#[tauri::command]
+pub fn save_project(path: String, config: ProjectConfig) -> Result<Project, String> {
+    let dir = Path::new(&path);
+    save_project_in(dir, &config)?;
+    project_status_in(dir)
+}
+
+/// Run the project's files in order, or only the changed ones. Each file runs
+/// as one batch on a single connection and stops the run at its first failing
+/// statement unless `continueOnError` is set. Emits `project://file-status`
+/// after each file; `cancel_query` on the project's connection, from the
+/// window that started the run, stops it.
+#[tauri::command]
+pub async fn run_project<R: Runtime>(
+    app: AppHandle<R>,
+    window: WebviewWindow<R>,
+    state: State<'_, QueryCancellationState>,
+    path: String,
+    only_changed: Option<bool>,
+) -> Result<Project, String> {
+    let dir = Path::new(&path);
+    let project = project_status_in(dir)?;
+    let config = project.config.clone();
+
+    let saved_conn = find_connection_by_id(&app, &config.connection_id)?;
+    let expanded_params = expand_ssh_connection_params(&app, &saved_conn.params).await?;
+    let params = resolve_connection_params_with_id(&expanded_params, &config.connection_id)?;
+    let drv = driver_for(&saved_conn.params.driver).await?;
+
+    let cancel_key = crate::window_state::cancellation_key(window.label(), &config.connection_id);
+    let files = files_to_run(&project, only_changed.unwrap_or(false));
+    log::info!(
+        "Running project {} on connection {} | {} file(s)",
+        path,
+        config.connection_id,
+        files.len()
+    );
+
+    for file in files {
+        let sql_file = crate::sql_files::read_sql_file(&dir.join(&file))?;
+        let statements: Vec<String> =
+            crate::dump_commands::split_sql_statements(&sql_file.content)?
+                .iter()
+                .map(|s| sanitize_user_query(s))
+                .collect();
+        let statement_count = statements.len();
+
+        let start = Instant::now();
+        let allowed = crate::read_only::ensure_statements_allowed(&saved_conn.params, &statements);
+        let (error, cancelled) = match allowed {
+            Err(e) => (Some(e), false),
+            Ok(()) => {
+                let task_drv = drv.clone();
+                let task_params = params.clone();
+                let schema = config.schema.clone();
+                let task_statements = statements.clone();
+                let task = tokio::spawn(async move {
+                    task_drv
+                        .execute_batch(
+                            &task_params,
+                            &task_statements,
+                            Some(1),
+                            1,
+                            schema.as_deref(),
+                        )
+                        .await
+                });
+
+                let abort_handle = Arc::new(task.abort_handle());
+                register_abort_handle(&state.handles, cancel_key.clone(), abort_handle.clone());
+                let result = task.await;
+                unregister_abort_handle(&state.handles, &cancel_key, &abort_handle);
+
+                let cancelled = result.is_err();
+                let error = match result {
+                    Ok(Ok(results)) => {
+                        for (statement, outcome) in statements.iter().zip(&results) {
+                            if outcome.result.is_some() {
+                                crate::table_activity::track_query(
+                                    &app,
+                                    &config.connection_id,
+                                    &saved_conn.params.driver,
+                                    statement,
+                                    config.schema.as_deref(),
+                                );
+                            }
+                        }
+                        results.iter().enumerate().find_map(|(i, r)| {
+                            r.error
+                                .as_ref()
+                                .map(|e| format!("Error at statement {}: {}", i + 1, e))
+                        })
+                    }
+                    Ok(Err(e)) => Some(e),
+                    Err(_) => Some(crate::i18n::t("errors.queryCancelled")),
+                };
+                (error, cancelled)
+            }
+        };
+
+        let record = FileRunRecord {
+            status: if error.is_none() {
+                RunStatus::Success
+            } else {
+                RunStatus::Failed
+            },
+            hash: sql_file.hash,
+            ran_at: chrono::Utc::now().to_rfc3339(),
+            duration_ms: start.elapsed().as_secs_f64() * 1000.0,
+            statements: statement_count,
+            error: error.clone(),
+        };
+        record_run_in(dir, &file, record.clone())?;
+        let _ = app.emit(
+            PROJECT_PROGRESS_EVENT,
+            serde_json::json!({ "path": path, "file": file, "run": record }),
+        );
+
+        if let Some(e) = error {
+            log::warn!("Project file {} failed: {}", file, e);
+            if cancelled || !config.continue_on_error {
+                break;
+            }
+        }
+    }
+
+    project_status_in(dir)
+}
@@ -0,0 +1,142 @@
+#[cfg(test)]
+mod tests {
+    use crate::projects::{
+        files_to_run, load_project_in, project_status_in, record_run_in, resolve_files_in,
+        save_project_in, FileRunRecord, ProjectConfig, RunStatus, PROJECT_FILE,
+    };
+    use crate::sql_files::content_hash;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn config(files: &[&str]) -> ProjectConfig {
+        ProjectConfig {
+            name: Some("migrations".to_string()),
+            connection_id: "conn-1".to_string(),
+            schema: None,
+            files: files.iter().map(|f| f.to_string()).collect(),
+            continue_on_error: false,
+        }
+    }
+
+    fn success(content: &str) -> FileRunRecord {
+        FileRunRecord {
+            status: RunStatus::Success,
+            hash: content_hash(content.as_bytes()),
+            ran_at: "2026-01-01T00:00:00Z".to_string(),
+            duration_ms: 1.0,
+            statements: 1,
+            error: None,
+        }
+    }
+
+    // --- Project file ---
+
+    #[test]
+    fn save_and_load_round_trip() {
+        let tmp = TempDir::new().unwrap();
+        let cfg = config(&["002.sql", "001.sql"]);
+
+        save_project_in(tmp.path(), &cfg).unwrap();
+
+        assert!(tmp.path().join(PROJECT_FILE).exists());
+        assert_eq!(load_project_in(tmp.path()).unwrap(), cfg);
+    }
+
+    #[test]
+    fn load_accepts_minimal_file() {
+        let tmp = TempDir::new().unwrap();
+        fs::write(
+            tmp.path().join(PROJECT_FILE),
+            r#"{ "connectionId": "conn-1" }"#,
+        )
+        .unwrap();
+
+        let cfg = load_project_in(tmp.path()).unwrap();
+        assert_eq!(cfg.connection_id, "conn-1");
+        assert!(cfg.files.is_empty());
+        assert!(!cfg.continue_on_error);
+    }
+
+    #[test]
+    fn rejects_entries_outside_the_project() {
+        let tmp = TempDir::new().unwrap();
+        assert!(save_project_in(tmp.path(), &config(&["../other.sql"])).is_err());
+        assert!(save_project_in(tmp.path(), &config(&["/etc/passwd"])).is_err());
+        assert!(save_project_in(tmp.path(), &config(&["sub/ok.sql"])).is_ok());
+    }
+
+    // --- Run order ---
+
+    #[test]
+    fn discovers_sql_files_sorted_when_no_order_given() {
+        let tmp = TempDir::new().unwrap();
+        for name in ["b.sql", "a.SQL", "notes.txt"] {
+            fs::write(tmp.path().join(name), "SELECT 1;").unwrap();
+        }
+
+        let files = resolve_files_in(tmp.path(), &config(&[])).unwrap();
+        assert_eq!(files, vec!["a.SQL", "b.sql"]);
+    }
+
+    #[test]
+    fn explicit_order_is_kept() {
+        let tmp = TempDir::new().unwrap();
+        let files = resolve_files_in(tmp.path(), &config(&["b.sql", "a.sql"])).unwrap();
+        assert_eq!(files, vec!["b.sql", "a.sql"]);
+    }
+
+    // --- Status ---
+
+    #[test]
+    fn status_tracks_changed_files() {
+        let tmp = TempDir::new().unwrap();
+        save_project_in(tmp.path(), &config(&["001.sql", "002.sql", "003.sql"])).unwrap();
+        fs::write(tmp.path().join("001.sql"), "CREATE TABLE a (id INT);").unwrap();
+        fs::write(tmp.path().join("002.sql"), "CREATE TABLE b (id INT);").unwrap();
+        record_run_in(tmp.path(), "001.sql", success("CREATE TABLE a (id INT);")).unwrap();
+        record_run_in(tmp.path(), "002.sql", success("old content")).unwrap();
+
+        let project = project_status_in(tmp.path()).unwrap();
+        let changed: Vec<(&str, bool, bool)> = project
+            .files
+            .iter()
+            .map(|f| (f.file.as_str(), f.exists, f.changed))
+            .collect();
+        assert_eq!(
+            changed,
+            vec![
+                ("001.sql", true, false),
+                ("002.sql", true, true),
+                ("003.sql", false, false),
+            ]
+        );
+        assert!(project.files[0].last_run.is_some());
+
+        assert_eq!(files_to_run(&project, true), vec!["002.sql"]);
+        assert_eq!(files_to_run(&project, false), vec!["001.sql", "002.sql"]);
+    }
+
+    #[test]
+    fn failed_run_keeps_file_changed() {
+        let tmp = TempDir::new().unwrap();
+        save_project_in(tmp.path(), &config(&["001.sql"])).unwrap();
+        fs::write(tmp.path().join("001.sql"), "BROKEN;").unwrap();
+        let mut failed = success("BROKEN;");
+        failed.status = RunStatus::Failed;
+        failed.error = Some("syntax error".to_string());
+        record_run_in(tmp.path(), "001.sql", failed).unwrap();
+
+        let project = project_status_in(tmp.path()).unwrap();
+        assert!(project.files[0].changed);
+        assert_eq!(
+            project.files[0].last_run.as_ref().unwrap().error.as_deref(),
+            Some("syntax error")
+        );
+    }
+
+    #[test]
+    fn missing_project_file_is_an_error() {
+        let tmp = TempDir::new().unwrap();
+        assert!(project_status_in(tmp.path()).is_err());
+    }
+}
@@ -0,0 +1,72 @@
+//! Provenance of query results and exported files.
+//!
+//! `execute_query` attaches a [`ResultProvenance`] to every result. Exports
+//! can write the same record next to the file, as `<file>.provenance.json`,
+//! so the data file itself keeps its plain CSV / JSON shape.
+
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use chrono::{DateTime, SecondsFormat, Utc};
+
+use crate::drivers::driver_trait::DatabaseDriver;
+use crate::models::{ConnectionParams, ResultProvenance, SavedConnection};
+
+/// Appended to the exported file's name.
+pub const SIDECAR_SUFFIX: &str = ".provenance.json";
+
+/// Describe a run of `query` on `conn`, started at `started_at`.
+pub fn describe(
+    conn: &SavedConnection,
+    schema: Option<&str>,
+    query: &str,
+    started_at: DateTime<Utc>,
+    elapsed: Duration,
+    schema_checksum: Option<String>,
+) -> ResultProvenance {
+    let database = conn.params.database.primary();
+    ResultProvenance {
+        connection_id: conn.id.clone(),
+        connection_name: conn.name.clone(),
+        driver: conn.params.driver.clone(),
+        database: (!database.is_empty()).then(|| database.to_string()),
+        schema: schema.map(str::to_string),
+        schema_checksum,
+        query: query.to_string(),
+        executed_at: started_at.to_rfc3339_opts(SecondsFormat::Millis, true),
+        execution_time_ms: elapsed.as_secs_f64() * 1000.0,
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+    }
+}
+
+/// Fresh checksum of the schema, or `None` (logged) when the catalog
+/// cannot be read.
+pub async fn schema_checksum(
+    drv: &dyn DatabaseDriver,
+    params: &ConnectionParams,
+    schema: Option<&str>,
+) -> Option<String> {
+    match drv.get_schema_signature(params, schema).await {
+        Ok(columns) => Some(crate::schema_watch::checksum(&columns)),
+        Err(e) => {
+            log::warn!("Could not read schema signature for provenance: {}", e);
+            None
+        }
+    }
+}
+
+/// `data.csv` → `data.csv.provenance.json`.
+pub fn sidecar_path(file_path: &Path) -> PathBuf {
+    let mut name = file_path.as_os_str().to_os_string();
+    name.push(SIDECAR_SUFFIX);
+    PathBuf::from(name)
+}
+
+/// Write `provenance` next to the exported `file_path`.
+pub fn write_sidecar(file_path: &Path, provenance: &ResultProvenance) -> Result<PathBuf, String> {
+    let path = sidecar_path(file_path);
+    let json = serde_json::to_string_pretty(provenance)
+        .map_err(|e| format!("Failed to serialize provenance: {}", e))?;
+    std::fs::write(&path, json).map_err(|e| format!("Failed to write provenance file: {}", e))?;
+    Ok(path)
+}
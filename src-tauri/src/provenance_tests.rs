@@ -0,0 +1,75 @@
+#[cfg(test)]
+mod tests {
+    use crate::models::{ConnectionParams, DatabaseSelection, ResultProvenance, SavedConnection};
+    use crate::provenance::{describe, sidecar_path, write_sidecar};
+    use chrono::{TimeZone, Utc};
+    use std::path::Path;
+    use std::time::Duration;
+
+    fn connection(database: &str) -> SavedConnection {
+        SavedConnection {
+            id: "c1".to_string(),
+            name: "Reporting".to_string(),
+            params: ConnectionParams {
+                driver: "postgres".to_string(),
+                database: DatabaseSelection::Single(database.to_string()),
+                ..Default::default()
+            },
+            group_id: None,
+            sort_order: None,
+            detect_json_in_text_columns: None,
+            protected: None,
+            read_replicas: None,
+            connect_on_startup: None,
+        }
+    }
+
+    fn provenance(database: &str) -> ResultProvenance {
+        describe(
+            &connection(database),
+            Some("public"),
+            "SELECT * FROM orders",
+            Utc.with_ymd_and_hms(2024, 5, 1, 12, 30, 0).unwrap(),
+            Duration::from_millis(42),
+            Some("abc123".to_string()),
+        )
+    }
+
+    #[test]
+    fn describes_the_run() {
+        let p = provenance("shop");
+        assert_eq!(p.connection_name, "Reporting");
+        assert_eq!(p.driver, "postgres");
+        assert_eq!(p.database.as_deref(), Some("shop"));
+        assert_eq!(p.schema.as_deref(), Some("public"));
+        assert_eq!(p.schema_checksum.as_deref(), Some("abc123"));
+        assert_eq!(p.executed_at, "2024-05-01T12:30:00.000Z");
+        assert!((p.execution_time_ms - 42.0).abs() < 1e-9);
+        assert_eq!(p.app_version, env!("CARGO_PKG_VERSION"));
+
+        assert_eq!(provenance("").database, None);
+    }
+
+    #[test]
+    fn serializes_in_camel_case() {
+        let json = serde_json::to_value(provenance("shop")).unwrap();
+        assert_eq!(json["connectionName"], "Reporting");
+        assert_eq!(json["schemaChecksum"], "abc123");
+        assert_eq!(json["executionTimeMs"], 42.0);
+    }
+
+    #[test]
+    fn sidecar_sits_next_to_the_export() {
+        assert_eq!(
+            sidecar_path(Path::new("/tmp/out/orders.csv")),
+            Path::new("/tmp/out/orders.csv.provenance.json")
+        );
+
+        let dir = tempfile::tempdir().unwrap();
+        let export = dir.path().join("orders.json");
+        let written = write_sidecar(&export, &provenance("shop")).unwrap();
+        let read: ResultProvenance =
+            serde_json::from_str(&std::fs::read_to_string(written).unwrap()).unwrap();
+        assert_eq!(read, provenance("shop"));
+    }
+}
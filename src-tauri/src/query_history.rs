@@ -2,7 +2,7 @@ use crate::config::load_config_internal;
 use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::PathBuf;
-use tauri::{AppHandle, Manager, Runtime};
+use tauri::{AppHandle, Runtime};
 use uuid::Uuid;
 
 const DEFAULT_MAX_HISTORY_ENTRIES: u32 = 500;
@@ -22,7 +22,7 @@ pub struct QueryHistoryEntry {
 }
 
 fn get_history_dir<R: Runtime>(app: &AppHandle<R>) -> Result<PathBuf, String> {
-    let config_dir = app.path().app_config_dir().map_err(|e| e.to_string())?;
+    let config_dir = crate::paths::app_config_dir(app)?;
     let history_dir = config_dir.join("query_history");
     if !history_dir.exists() {
         fs::create_dir_all(&history_dir).map_err(|e| e.to_string())?;
@@ -0,0 +1,178 @@
+//! Registry of the queries currently running, for the Task Manager.
+//!
+//! `execute_query` and friends register a job before spawning the driver
+//! task and run that task inside [`QueryJob::scope`]. Drivers that collect
+//! rows through `RowCollector` report their progress with
+//! [`record_progress`], which finds the job through a task-local, so no
+//! driver signature has to change. The job keeps the task's abort handle:
+//! the Task Manager cancels one query, not every query on its connection.
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+use chrono::{DateTime, Utc};
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use tokio::task::futures::TaskLocalFuture;
+use tokio::task::AbortHandle;
+
+/// Longest query text shown in the Task Manager, in characters.
+const QUERY_PREVIEW_CHARS: usize = 500;
+
+static JOBS: Lazy<Mutex<HashMap<u64, Arc<QueryJob>>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+static NEXT_ID: AtomicU64 = AtomicU64::new(1);
+
+tokio::task_local! {
+    static CURRENT_JOB: Arc<QueryJob>;
+}
+
+/// One running query.
+pub struct QueryJob {
+    id: u64,
+    connection_id: String,
+    connection_name: String,
+    query: String,
+    started_at: DateTime<Utc>,
+    started: Instant,
+    rows: AtomicU64,
+    bytes: AtomicU64,
+    abort: Mutex<Option<AbortHandle>>,
+}
+
+/// A running query as the Task Manager shows it.
+#[derive(Debug, Clone, Serialize)]
+pub struct RunningQuery {
+    pub id: u64,
+    pub connection_id: String,
+    pub connection_name: String,
+    pub query: String,
+    pub started_at: DateTime<Utc>,
+    pub elapsed_ms: u64,
+    /// Rows fetched so far.
+    pub rows: u64,
+    /// Estimated in-memory size of the rows fetched so far.
+    pub bytes: u64,
+}
+
+/// Removes its job from the registry when dropped, however the query ends.
+pub struct JobGuard {
+    job: Arc<QueryJob>,
+}
+
+impl JobGuard {
+    pub fn job(&self) -> &Arc<QueryJob> {
+        &self.job
+    }
+}
+
+impl Drop for JobGuard {
+    fn drop(&mut self) {
+        JOBS.lock().unwrap().remove(&self.job.id);
+    }
+}
+
+impl QueryJob {
+    /// Run `future` as this job, so progress it records lands here.
+    pub fn scope<F: Future>(self: &Arc<Self>, future: F) -> TaskLocalFuture<Arc<QueryJob>, F> {
+        CURRENT_JOB.scope(self.clone(), future)
+    }
+
+    /// Remember the handle that cancels the job's task.
+    pub fn set_abort_handle(&self, handle: AbortHandle) {
+        *self.abort.lock().unwrap() = Some(handle);
+    }
+
+    fn snapshot(&self) -> RunningQuery {
+        RunningQuery {
+            id: self.id,
+            connection_id: self.connection_id.clone(),
+            connection_name: self.connection_name.clone(),
+            query: self.query.clone(),
+            started_at: self.started_at,
+            elapsed_ms: self.started.elapsed().as_millis() as u64,
+            rows: self.rows.load(Ordering::Relaxed),
+            bytes: self.bytes.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// Register a query that is about to run. The job lives until the returned
+/// guard is dropped.
+pub fn start(connection_id: &str, connection_name: &str, query: &str) -> JobGuard {
+    let job = Arc::new(QueryJob {
+        id: NEXT_ID.fetch_add(1, Ordering::Relaxed),
+        connection_id: connection_id.to_string(),
+        connection_name: connection_name.to_string(),
+        query: preview(query),
+        started_at: Utc::now(),
+        started: Instant::now(),
+        rows: AtomicU64::new(0),
+        bytes: AtomicU64::new(0),
+        abort: Mutex::new(None),
+    });
+    JOBS.lock().unwrap().insert(job.id, job.clone());
+    JobGuard { job }
+}
+
+/// Record the totals fetched so far by the job running the current task.
+/// Does nothing outside a job.
+pub fn record_progress(rows: u64, bytes: u64) {
+    let _ = CURRENT_JOB.try_with(|job| {
+        job.rows.store(rows, Ordering::Relaxed);
+        job.bytes.store(bytes, Ordering::Relaxed);
+    });
+}
+
+/// The running queries, oldest first.
+pub fn list() -> Vec<RunningQuery> {
+    let mut queries: Vec<RunningQuery> = JOBS
+        .lock()
+        .unwrap()
+        .values()
+        .map(|job| job.snapshot())
+        .collect();
+    queries.sort_by_key(|q| (q.started_at, q.id));
+    queries
+}
+
+/// Abort the task of job `id`.
+pub fn cancel(id: u64) -> Result<(), String> {
+    let job = JOBS
+        .lock()
+        .unwrap()
+        .get(&id)
+        .cloned()
+        .ok_or_else(|| crate::i18n::t("errors.noRunningQuery"))?;
+    let abort = job.abort.lock().unwrap();
+    let handle = abort
+        .as_ref()
+        .ok_or_else(|| crate::i18n::t("errors.queryNotCancellableYet"))?;
+    handle.abort();
+    Ok(())
+}
+
+/// Abort the task of every running job, as the app shuts down. Returns
+/// how many tasks were aborted; jobs with no task attached yet are skipped.
+pub fn cancel_all() -> usize {
+    let jobs: Vec<Arc<QueryJob>> = JOBS.lock().unwrap().values().cloned().collect();
+    jobs.iter()
+        .filter(|job| match job.abort.lock().unwrap().as_ref() {
+            Some(handle) => {
+                handle.abort();
+                true
+            }
+            None => false,
+        })
+        .count()
+}
+
+fn preview(query: &str) -> String {
+    let query = query.trim();
+    match query.char_indices().nth(QUERY_PREVIEW_CHARS) {
+        Some((end, _)) => format!("{}…", &query[..end]),
+        None => query.to_string(),
+    }
+}
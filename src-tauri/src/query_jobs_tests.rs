@@ -0,0 +1,82 @@
+#[cfg(test)]
+mod tests {
+    use crate::query_jobs::{cancel, cancel_all, list, record_progress, start, RunningQuery};
+
+    // The registry is process-wide, so each test uses its own connection id.
+    fn jobs_for(connection_id: &str) -> Vec<RunningQuery> {
+        list()
+            .into_iter()
+            .filter(|q| q.connection_id == connection_id)
+            .collect()
+    }
+
+    #[test]
+    fn job_is_listed_until_its_guard_drops() {
+        let guard = start("jobs-listed", "Prod", "  SELECT 1  ");
+        let jobs = jobs_for("jobs-listed");
+        assert_eq!(jobs.len(), 1);
+        assert_eq!(jobs[0].connection_name, "Prod");
+        assert_eq!(jobs[0].query, "SELECT 1");
+        assert_eq!(jobs[0].rows, 0);
+
+        drop(guard);
+        assert!(jobs_for("jobs-listed").is_empty());
+    }
+
+    #[test]
+    fn long_queries_are_shortened() {
+        let query = format!("SELECT '{}'", "é".repeat(1000));
+        let _guard = start("jobs-preview", "Prod", &query);
+        let shown = &jobs_for("jobs-preview")[0].query;
+        assert_eq!(shown.chars().count(), 501);
+        assert!(shown.ends_with('…'));
+    }
+
+    #[tokio::test]
+    async fn progress_lands_on_the_scoped_job() {
+        let guard = start("jobs-progress", "Prod", "SELECT * FROM t");
+        let other = start("jobs-progress-other", "Prod", "SELECT 2");
+
+        guard.job().scope(async { record_progress(42, 4096) }).await;
+        // Outside any job, progress is dropped.
+        record_progress(7, 7);
+
+        let jobs = jobs_for("jobs-progress");
+        assert_eq!((jobs[0].rows, jobs[0].bytes), (42, 4096));
+        let others = jobs_for("jobs-progress-other");
+        assert_eq!((others[0].rows, others[0].bytes), (0, 0));
+        drop(other);
+    }
+
+    #[tokio::test]
+    async fn cancel_aborts_the_job_task() {
+        let guard = start("jobs-cancel", "Prod", "SELECT pg_sleep(60)");
+        let id = jobs_for("jobs-cancel")[0].id;
+        assert!(cancel(id).is_err(), "no task is attached yet");
+
+        let task = tokio::spawn(guard.job().scope(std::future::pending::<()>()));
+        guard.job().set_abort_handle(task.abort_handle());
+        cancel(id).unwrap();
+        assert!(task.await.unwrap_err().is_cancelled());
+
+        drop(guard);
+        assert!(cancel(id).is_err());
+    }
+
+    #[tokio::test]
+    async fn cancel_all_aborts_every_attached_task() {
+        let first = start("jobs-cancel-all", "Prod", "SELECT 1");
+        let second = start("jobs-cancel-all", "Prod", "SELECT 2");
+        let _pending = start("jobs-cancel-all", "Prod", "SELECT 3");
+
+        let first_task = tokio::spawn(first.job().scope(std::future::pending::<()>()));
+        first.job().set_abort_handle(first_task.abort_handle());
+        let second_task = tokio::spawn(second.job().scope(std::future::pending::<()>()));
+        second.job().set_abort_handle(second_task.abort_handle());
+
+        // Other tests may have jobs of their own running.
+        assert!(cancel_all() >= 2);
+        assert!(first_task.await.unwrap_err().is_cancelled());
+        assert!(second_task.await.unwrap_err().is_cancelled());
+    }
+}
@@ -0,0 +1,164 @@
+//! Streaming query results.
+//!
+//! `execute_query` collects every row before returning, which is fine for a
+//! page of results but not for a multi-million-row SELECT. A streamed query
+//! instead emits its rows as `query-stream://batch` events while the driver
+//! reads them, so neither the backend nor the IPC layer ever holds the whole
+//! result. The command itself resolves with the total row count once the
+//! last batch has been emitted; `cancel_query` aborts it like any other query.
+//! When the stream browses a table, its data rules style each batch as it
+//! goes, with row numbers counted from the start of the stream.
+
+use std::sync::Arc;
+
+use serde::Serialize;
+use serde_json::Value;
+use tauri::{AppHandle, Emitter, Runtime, State, WebviewWindow};
+
+use crate::commands::{
+    driver_for, expand_ssh_connection_params, find_connection_by_id, register_abort_handle,
+    resolve_connection_params_with_id, sanitize_user_query, unregister_abort_handle,
+    QueryCancellationState,
+};
+use crate::data_rules::CompiledDataRules;
+use crate::models::StyleHint;
+
+const QUERY_STREAM_BATCH_EVENT: &str = "query-stream://batch";
+
+/// Rows per event when the caller does not pick a batch size.
+pub const DEFAULT_STREAM_BATCH_SIZE: usize = 1000;
+
+/// One batch of a streamed result. `offset` is the number of rows emitted
+/// before this batch, so the frontend can place rows that arrive out of
+/// order and tell streams apart by `stream_id`.
+#[derive(Debug, Clone, Serialize)]
+pub struct QueryStreamBatch {
+    pub stream_id: String,
+    pub columns: Vec<String>,
+    pub rows: Vec<Vec<Value>>,
+    pub offset: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub style_hints: Option<Vec<StyleHint>>,
+}
+
+/// The batch of `rows` emitted after `offset` rows, styled by `rules`.
+pub fn build_batch(
+    stream_id: &str,
+    columns: &[String],
+    rows: Vec<Vec<Value>>,
+    offset: u64,
+    rules: &CompiledDataRules,
+) -> QueryStreamBatch {
+    let hints = rules.style_hints(columns, &rows, offset as usize);
+    QueryStreamBatch {
+        stream_id: stream_id.to_string(),
+        columns: columns.to_vec(),
+        rows,
+        offset,
+        style_hints: (!hints.is_empty()).then_some(hints),
+    }
+}
+
+#[tauri::command]
+pub async fn execute_query_stream<R: Runtime>(
+    app: AppHandle<R>,
+    window: WebviewWindow<R>,
+    state: State<'_, QueryCancellationState>,
+    connection_id: String,
+    stream_id: String,
+    query: String,
+    batch_size: Option<usize>,
+    schema: Option<String>,
+    use_primary: Option<bool>,
+    table: Option<String>,
+) -> Result<u64, String> {
+    log::info!(
+        "Streaming query on connection: {} | Query: {}",
+        connection_id,
+        query
+    );
+
+    let sanitized_query = sanitize_user_query(&query);
+
+    let saved_conn = find_connection_by_id(&app, &connection_id)?;
+    crate::read_only::ensure_query_allowed(&saved_conn.params, &sanitized_query)?;
+    let use_primary = use_primary.unwrap_or(false)
+        || crate::session_objects::has_objects(&connection_id)
+        || crate::pool_manager::has_transaction(&connection_id).await;
+    let (routed_params, pool_id) = crate::pool_manager::route_query(
+        &saved_conn,
+        &connection_id,
+        &sanitized_query,
+        use_primary,
+    );
+    let expanded_params = expand_ssh_connection_params(&app, &routed_params).await?;
+    let params = resolve_connection_params_with_id(&expanded_params, &pool_id)?;
+    let drv = driver_for(&saved_conn.params.driver).await?;
+    let batch_size = batch_size.unwrap_or(DEFAULT_STREAM_BATCH_SIZE);
+    let rules = CompiledDataRules::new(&match table.as_deref() {
+        Some(table) => {
+            crate::data_rules::load_data_rules(&connection_id, schema.as_deref(), table)?
+        }
+        None => Vec::new(),
+    });
+
+    let tracked = (sanitized_query.clone(), schema.clone());
+
+    // Batches go to the window that started the stream only.
+    let app_for_task = app.clone();
+    let target = window.label().to_string();
+    let task = tokio::spawn(async move {
+        let mut offset = 0u64;
+        let mut on_batch = |columns: &[String], rows: Vec<Vec<Value>>| -> Result<(), String> {
+            let count = rows.len() as u64;
+            app_for_task
+                .emit_to(
+                    target.as_str(),
+                    QUERY_STREAM_BATCH_EVENT,
+                    build_batch(&stream_id, columns, rows, offset, &rules),
+                )
+                .map_err(|e| format!("Failed to emit query stream batch: {}", e))?;
+            offset += count;
+            Ok(())
+        };
+        drv.execute_query_stream(
+            &params,
+            &sanitized_query,
+            batch_size,
+            schema.as_deref(),
+            &mut on_batch,
+        )
+        .await
+    });
+
+    let abort_handle = Arc::new(task.abort_handle());
+    let cancel_key = crate::window_state::cancellation_key(window.label(), &connection_id);
+    register_abort_handle(&state.handles, cancel_key.clone(), abort_handle.clone());
+
+    let result = task.await;
+
+    unregister_abort_handle(&state.handles, &cancel_key, &abort_handle);
+
+    match result {
+        Ok(Ok(total)) => {
+            log::info!("Query stream finished, emitted {} rows", total);
+            let (query, schema) = tracked;
+            crate::table_activity::track_query(
+                &app,
+                &connection_id,
+                &saved_conn.params.driver,
+                &query,
+                schema.as_deref(),
+            );
+            Ok(total)
+        }
+        Ok(Err(e)) => {
+            log::error!("Query stream failed: {}", e);
+            Err(e)
+        }
+        Err(_) => {
+            log::warn!("Query stream was cancelled");
+            Err(crate::i18n::t("errors.queryCancelled"))
+        }
+    }
+}
@@ -0,0 +1,36 @@
+#[cfg(test)]
+mod tests {
+    use crate::data_rules::{CompiledDataRules, DataRule};
+    use crate::query_stream::build_batch;
+    use serde_json::json;
+
+    #[test]
+    fn data_rules_style_each_batch_with_stream_row_numbers() {
+        let rules = CompiledDataRules::new(&[DataRule {
+            id: "big".into(),
+            name: None,
+            expression: "amount > 1000".into(),
+            column: Some("amount".into()),
+            color: None,
+            background: Some("#fee2e2".into()),
+            badge: None,
+            enabled: true,
+        }]);
+        let columns = vec!["id".to_string(), "amount".to_string()];
+
+        let batch = build_batch(
+            "s1",
+            &columns,
+            vec![vec![json!(3), json!(50)], vec![json!(4), json!(5000)]],
+            1000,
+            &rules,
+        );
+        let hints = batch.style_hints.unwrap();
+        assert_eq!(hints.len(), 1);
+        assert_eq!(hints[0].row, 1001);
+        assert_eq!(hints[0].column.as_deref(), Some("amount"));
+
+        let plain = build_batch("s1", &columns, vec![vec![json!(5), json!(1)]], 0, &rules);
+        assert!(plain.style_hints.is_none());
+    }
+}
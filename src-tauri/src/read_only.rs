@@ -0,0 +1,156 @@
+//! Read-only connections.
+//!
+//! The database enforces `read_only` for the session: PostgreSQL pools start
+//! with `default_transaction_read_only`, MySQL runs `SET SESSION TRANSACTION
+//! READ ONLY` on each connection and SQLite opens the file read-only. That
+//! also stops writes hidden in function calls (`SELECT dblink(...)`, a
+//! VOLATILE function). SQL Server has no such session setting.
+//!
+//! The statement classifier below is a pre-check that refuses obvious
+//! writes, record edits and DDL with a clear message before anything is
+//! sent. It only reads statement text, so it is not the enforcement.
+
+use crate::ai_activity::{classify_query_kind, strip_strings_and_comments};
+use crate::models::ConnectionParams;
+
+/// PostgreSQL functions that change state even when called from a SELECT.
+const POSTGRES_SIDE_EFFECT_FUNCTIONS: &[&str] = &[
+    "NEXTVAL",
+    "SETVAL",
+    "PG_TERMINATE_BACKEND",
+    "PG_CANCEL_BACKEND",
+    "PG_RELOAD_CONF",
+    "PG_ROTATE_LOGFILE",
+    "LO_IMPORT",
+    "LO_EXPORT",
+    "LO_UNLINK",
+    "DBLINK_EXEC",
+    "PG_DROP_REPLICATION_SLOT",
+];
+
+const WRITE_KEYWORDS: &[&str] = &[
+    "INSERT", "UPDATE", "DELETE", "MERGE", "REPLACE", "CREATE", "DROP", "ALTER", "TRUNCATE",
+];
+
+/// MongoDB shell methods that only read.
+const MONGO_READ_METHODS: &[&str] = &[
+    "find",
+    "findOne",
+    "aggregate",
+    "countDocuments",
+    "estimatedDocumentCount",
+    "count",
+    "distinct",
+];
+
+/// Refuse `query` on a read-only connection unless it only reads.
+pub fn ensure_query_allowed(params: &ConnectionParams, query: &str) -> Result<(), String> {
+    if params.read_only && !is_read_only_query(&params.driver, query) {
+        return Err(crate::i18n::t("errors.readOnlyConnection"));
+    }
+    Ok(())
+}
+
+/// Refuse any change (record edits, DDL) on a read-only connection.
+pub fn ensure_writable(params: &ConnectionParams) -> Result<(), String> {
+    if params.read_only {
+        return Err(crate::i18n::t("errors.readOnlyConnection"));
+    }
+    Ok(())
+}
+
+/// [`ensure_query_allowed`] for each statement of a script, naming the
+/// first one refused.
+pub fn ensure_statements_allowed(
+    params: &ConnectionParams,
+    statements: &[String],
+) -> Result<(), String> {
+    for (i, statement) in statements.iter().enumerate() {
+        ensure_query_allowed(params, statement)
+            .map_err(|e| format!("Error at statement {}: {}", i + 1, e))?;
+    }
+    Ok(())
+}
+
+/// Whether every statement of `query` only reads, in the dialect of `driver`.
+pub fn is_read_only_query(driver: &str, query: &str) -> bool {
+    if driver == "mongodb" {
+        return is_read_only_mongo_query(query);
+    }
+    let stripped = strip_strings_and_comments(query);
+    let mut statements = stripped
+        .split(';')
+        .map(str::trim)
+        .filter(|stmt| !stmt.is_empty())
+        .peekable();
+    statements.peek().is_some() && statements.all(|stmt| is_read_only_statement(driver, stmt))
+}
+
+/// Classify one statement whose literals and comments are already blanked.
+fn is_read_only_statement(driver: &str, stmt: &str) -> bool {
+    let upper = stmt.to_uppercase();
+    let words: Vec<&str> = upper
+        .split(|c: char| !c.is_ascii_alphanumeric() && c != '_')
+        .filter(|w| !w.is_empty())
+        .collect();
+    let has = |kw: &str| words.contains(&kw);
+
+    match words.first().copied() {
+        // EXPLAIN only plans the statement, unless ANALYZE makes it run.
+        Some("EXPLAIN") => {
+            return !has("ANALYZE") || !(has("INTO") || WRITE_KEYWORDS.iter().any(|kw| has(kw)));
+        }
+        // `PRAGMA name = value` changes the database; `PRAGMA name(arg)`
+        // only reads for the introspection pragmas the UI relies on.
+        Some("PRAGMA") => return driver == "sqlite" && !stmt.contains('='),
+        // `TABLE name` is shorthand for `SELECT * FROM name`.
+        Some("TABLE") => return matches!(driver, "postgres" | "mysql") && !has("INTO"),
+        _ => {}
+    }
+
+    if classify_query_kind(stmt) != "select" {
+        return false;
+    }
+    // Locking reads, `SELECT ... INTO` (a new table, variables or a file).
+    let locking = words.windows(2).any(|w| {
+        (w[0] == "FOR" && matches!(w[1], "UPDATE" | "SHARE" | "NO" | "KEY"))
+            || (w[0] == "LOCK" && w[1] == "IN")
+    });
+    if locking || has("INTO") {
+        return false;
+    }
+    !(driver == "postgres" && POSTGRES_SIDE_EFFECT_FUNCTIONS.iter().any(|f| has(f)))
+}
+
+/// Shell queries (`db.users.find({...})`) are classified by their method;
+/// an aggregation writing through `$out` or `$merge` is a write. SQL-style
+/// `SELECT` queries are reads.
+fn is_read_only_mongo_query(query: &str) -> bool {
+    let query = query.trim();
+    if query
+        .get(..6)
+        .is_some_and(|kw| kw.eq_ignore_ascii_case("SELECT"))
+    {
+        return true;
+    }
+    let Some(method) = mongo_method(query) else {
+        return false;
+    };
+    match method {
+        "aggregate" => !query.contains("$out") && !query.contains("$merge"),
+        _ => MONGO_READ_METHODS.contains(&method),
+    }
+}
+
+/// The collection method called by a shell query: the first identifier
+/// before a `(` other than `getCollection`.
+fn mongo_method(query: &str) -> Option<&str> {
+    query.match_indices('(').find_map(|(pos, _)| {
+        let head = &query[..pos];
+        let start = head
+            .rfind(|c: char| !(c.is_ascii_alphanumeric() || c == '_' || c == '$'))
+            .map_or(0, |i| i + 1);
+        let name = head[start..].trim();
+        (!name.is_empty() && name != "getCollection").then_some(name)
+    })
+}
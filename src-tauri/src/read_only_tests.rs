@@ -0,0 +1,191 @@
+#[cfg(test)]
+mod tests {
+    use crate::models::ConnectionParams;
+    use crate::read_only::{
+        ensure_query_allowed, ensure_statements_allowed, ensure_writable, is_read_only_query,
+    };
+
+    fn params(driver: &str, read_only: bool) -> ConnectionParams {
+        ConnectionParams {
+            driver: driver.to_string(),
+            read_only,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn plain_reads_are_allowed() {
+        for driver in ["postgres", "mysql", "sqlite", "mssql"] {
+            assert!(is_read_only_query(driver, "SELECT * FROM users"));
+            assert!(is_read_only_query(
+                driver,
+                "-- recent\nWITH r AS (SELECT * FROM orders) SELECT * FROM r"
+            ));
+            assert!(is_read_only_query(driver, "SELECT 1; SELECT 2;"));
+        }
+        assert!(is_read_only_query("mysql", "SHOW TABLES"));
+        assert!(is_read_only_query("postgres", "EXPLAIN SELECT 1"));
+    }
+
+    #[test]
+    fn writes_and_ddl_are_refused() {
+        for driver in ["postgres", "mysql", "sqlite"] {
+            assert!(!is_read_only_query(driver, "UPDATE users SET a = 1"));
+            assert!(!is_read_only_query(driver, "DROP TABLE users"));
+            assert!(!is_read_only_query(driver, "SELECT 1; DELETE FROM users"));
+            assert!(!is_read_only_query(driver, ""));
+        }
+        assert!(!is_read_only_query(
+            "postgres",
+            "WITH d AS (DELETE FROM users RETURNING *) SELECT * FROM d"
+        ));
+        assert!(!is_read_only_query("postgres", "CALL refresh_stats()"));
+    }
+
+    #[test]
+    fn keywords_inside_literals_do_not_count() {
+        assert!(is_read_only_query(
+            "postgres",
+            "SELECT 'DROP TABLE users; DELETE' AS note FROM t"
+        ));
+        assert!(!is_read_only_query(
+            "postgres",
+            "SELECT ';' AS sep; TRUNCATE t"
+        ));
+    }
+
+    #[test]
+    fn statements_with_side_effects_are_refused() {
+        assert!(!is_read_only_query(
+            "postgres",
+            "SELECT * FROM t FOR UPDATE"
+        ));
+        assert!(!is_read_only_query(
+            "mysql",
+            "SELECT * FROM t LOCK IN SHARE MODE"
+        ));
+        assert!(!is_read_only_query(
+            "postgres",
+            "SELECT * INTO backup FROM t"
+        ));
+        assert!(!is_read_only_query(
+            "mysql",
+            "SELECT * FROM t INTO OUTFILE '/tmp/t.csv'"
+        ));
+        assert!(!is_read_only_query(
+            "postgres",
+            "SELECT nextval('users_id_seq')"
+        ));
+        assert!(!is_read_only_query(
+            "postgres",
+            "EXPLAIN ANALYZE DELETE FROM users"
+        ));
+        assert!(is_read_only_query(
+            "postgres",
+            "EXPLAIN ANALYZE SELECT * FROM users"
+        ));
+        assert!(is_read_only_query("postgres", "EXPLAIN DELETE FROM users"));
+    }
+
+    #[test]
+    fn classification_depends_on_the_driver() {
+        assert!(is_read_only_query("sqlite", "PRAGMA table_info(users)"));
+        assert!(!is_read_only_query("sqlite", "PRAGMA journal_mode = WAL"));
+        assert!(!is_read_only_query("postgres", "PRAGMA table_info(users)"));
+        assert!(is_read_only_query("postgres", "TABLE users"));
+        assert!(!is_read_only_query("sqlite", "TABLE users"));
+        assert!(is_read_only_query("mysql", "SELECT nextval FROM counters"));
+    }
+
+    #[test]
+    fn mongodb_queries_are_classified_by_method() {
+        assert!(is_read_only_query(
+            "mongodb",
+            "db.users.find({ age: { $gt: 30 } })"
+        ));
+        assert!(is_read_only_query(
+            "mongodb",
+            "db.getCollection(\"users\").countDocuments({})"
+        ));
+        assert!(is_read_only_query(
+            "mongodb",
+            "db.orders.aggregate([{ $group: { _id: \"$status\" } }])"
+        ));
+        assert!(is_read_only_query("mongodb", "SELECT * FROM users"));
+        assert!(!is_read_only_query(
+            "mongodb",
+            "db.orders.aggregate([{ $out: \"archive\" }])"
+        ));
+        assert!(!is_read_only_query("mongodb", "db.users.deleteMany({})"));
+    }
+
+    #[test]
+    fn only_read_only_connections_are_restricted() {
+        assert!(ensure_query_allowed(&params("postgres", false), "DROP TABLE t").is_ok());
+        assert!(ensure_query_allowed(&params("postgres", true), "DROP TABLE t").is_err());
+        assert!(ensure_query_allowed(&params("postgres", true), "SELECT 1").is_ok());
+        assert!(ensure_writable(&params("sqlite", false)).is_ok());
+        assert!(ensure_writable(&params("sqlite", true)).is_err());
+    }
+
+    #[test]
+    fn scripts_name_the_first_statement_refused() {
+        let statements = vec![
+            "SELECT 1".to_string(),
+            "INSERT INTO audit VALUES (1)".to_string(),
+        ];
+        assert_eq!(
+            ensure_statements_allowed(&params("postgres", true), &statements).unwrap_err(),
+            format!(
+                "Error at statement 2: {}",
+                crate::i18n::t("errors.readOnlyConnection")
+            )
+        );
+        assert!(ensure_statements_allowed(&params("postgres", true), &statements[..1]).is_ok());
+        assert!(ensure_statements_allowed(&params("postgres", false), &statements).is_ok());
+    }
+
+    #[test]
+    fn read_only_defaults_to_false_in_saved_params() {
+        let params: ConnectionParams =
+            serde_json::from_str(r#"{"driver":"sqlite","database":"app.db"}"#).unwrap();
+        assert!(!params.read_only);
+    }
+
+    #[tokio::test]
+    async fn read_only_sqlite_pools_refuse_writes_in_the_database() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("read_only.db");
+        let setup = sqlx::SqlitePool::connect_with(
+            sqlx::sqlite::SqliteConnectOptions::new()
+                .filename(&path)
+                .create_if_missing(true),
+        )
+        .await
+        .unwrap();
+        sqlx::query("CREATE TABLE t (id INTEGER)")
+            .execute(&setup)
+            .await
+            .unwrap();
+        setup.close().await;
+
+        let params = ConnectionParams {
+            database: crate::models::DatabaseSelection::Single(path.to_string_lossy().into_owned()),
+            ..params("sqlite", true)
+        };
+        let id = "read-only-sqlite-test";
+        let pool = crate::pool_manager::get_sqlite_pool_with_id(&params, Some(id))
+            .await
+            .unwrap();
+        // Refused by SQLite itself, without the statement classifier.
+        assert!(sqlx::query("INSERT INTO t VALUES (1)")
+            .execute(&pool)
+            .await
+            .is_err());
+        assert!(sqlx::query("SELECT count(*) FROM t")
+            .fetch_one(&pool)
+            .await
+            .is_ok());
+        crate::pool_manager::close_pool_with_id(&params, Some(id)).await;
+    }
+}
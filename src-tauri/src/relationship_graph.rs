@@ -0,0 +1,194 @@
+//! Table-level foreign key graph for the relationship explorer.
+//!
+//! Unlike the ER diagram, which renders every column of a schema, this
+//! returns only tables and their links, optionally narrowed to a few tables
+//! and their neighbours, with each link's cardinality derived from the
+//! schema: a foreign key that is also the whole primary key is one-to-one,
+//! any other is many-to-one; a nullable one is optional.
+
+use std::collections::{HashMap, HashSet};
+
+use serde::Serialize;
+use tauri::{AppHandle, Runtime};
+
+use crate::lineage::group_relations;
+use crate::models::{ForeignKey, TableSchema};
+
+const DEFAULT_NEIGHBOUR_DEPTH: u32 = 1;
+
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum Cardinality {
+    OneToOne,
+    ManyToOne,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct RelationshipNode {
+    pub name: String,
+    pub column_count: usize,
+    pub primary_key: Vec<String>,
+    /// The primary key is made of foreign keys to two or more tables: the
+    /// table links them many-to-many.
+    pub junction: bool,
+}
+
+/// A link from the referencing table `source` to the referenced `target`.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct RelationshipEdge {
+    pub name: String,
+    pub source: String,
+    pub target: String,
+    pub columns: Vec<String>,
+    pub ref_columns: Vec<String>,
+    pub cardinality: Cardinality,
+    /// Some foreign key column is nullable, so a source row may have no
+    /// target.
+    pub optional: bool,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct RelationshipGraph {
+    pub nodes: Vec<RelationshipNode>,
+    pub edges: Vec<RelationshipEdge>,
+}
+
+/// Build the graph of `tables`. With `focus`, only those tables and the ones
+/// within `depth` links of them (in either direction) are kept.
+pub fn build_relationship_graph(
+    tables: &[TableSchema],
+    focus: Option<&[String]>,
+    depth: u32,
+) -> RelationshipGraph {
+    let foreign_keys: HashMap<String, Vec<ForeignKey>> = tables
+        .iter()
+        .map(|t| (t.name.clone(), t.foreign_keys.clone()))
+        .collect();
+    let known: HashSet<&str> = tables.iter().map(|t| t.name.as_str()).collect();
+    let relations: Vec<_> = group_relations(&foreign_keys)
+        .into_iter()
+        .filter(|r| known.contains(r.ref_table.as_str()))
+        .collect();
+
+    let kept: HashSet<&str> = match focus {
+        None => known,
+        Some(focus) => {
+            let mut kept: HashSet<&str> = focus
+                .iter()
+                .map(String::as_str)
+                .filter(|t| known.contains(t))
+                .collect();
+            let mut frontier = kept.clone();
+            for _ in 0..depth {
+                let mut next = HashSet::new();
+                for r in &relations {
+                    for (from, to) in [(&r.table, &r.ref_table), (&r.ref_table, &r.table)] {
+                        if frontier.contains(from.as_str()) && kept.insert(to.as_str()) {
+                            next.insert(to.as_str());
+                        }
+                    }
+                }
+                if next.is_empty() {
+                    break;
+                }
+                frontier = next;
+            }
+            kept
+        }
+    };
+
+    let edges: Vec<RelationshipEdge> = relations
+        .iter()
+        .filter(|r| kept.contains(r.table.as_str()) && kept.contains(r.ref_table.as_str()))
+        .map(|r| {
+            let source = tables.iter().find(|t| t.name == r.table);
+            let columns: Vec<String> = r.columns.iter().map(|(c, _)| c.clone()).collect();
+            let primary_key: HashSet<&str> = source
+                .map(|t| {
+                    t.columns
+                        .iter()
+                        .filter(|c| c.is_pk)
+                        .map(|c| c.name.as_str())
+                        .collect()
+                })
+                .unwrap_or_default();
+            let fk_columns: HashSet<&str> = columns.iter().map(String::as_str).collect();
+            let optional = source.is_some_and(|t| {
+                t.columns
+                    .iter()
+                    .any(|c| c.is_nullable && fk_columns.contains(c.name.as_str()))
+            });
+            RelationshipEdge {
+                name: r.name.clone(),
+                source: r.table.clone(),
+                target: r.ref_table.clone(),
+                ref_columns: r.columns.iter().map(|(_, c)| c.clone()).collect(),
+                cardinality: if !primary_key.is_empty() && primary_key == fk_columns {
+                    Cardinality::OneToOne
+                } else {
+                    Cardinality::ManyToOne
+                },
+                optional,
+                columns,
+            }
+        })
+        .collect();
+
+    let nodes = tables
+        .iter()
+        .filter(|t| kept.contains(t.name.as_str()))
+        .map(|t| {
+            let primary_key: Vec<String> = t
+                .columns
+                .iter()
+                .filter(|c| c.is_pk)
+                .map(|c| c.name.clone())
+                .collect();
+            let referenced: HashSet<&str> = relations
+                .iter()
+                .filter(|r| {
+                    r.table == t.name && r.columns.iter().all(|(c, _)| primary_key.contains(c))
+                })
+                .map(|r| r.ref_table.as_str())
+                .collect();
+            let covered = primary_key.iter().all(|pk| {
+                relations
+                    .iter()
+                    .any(|r| r.table == t.name && r.columns.iter().any(|(c, _)| c == pk))
+            });
+            RelationshipNode {
+                name: t.name.clone(),
+                column_count: t.columns.len(),
+                junction: referenced.len() >= 2 && covered,
+                primary_key,
+            }
+        })
+        .collect();
+
+    RelationshipGraph { nodes, edges }
+}
+
+#[tauri::command]
+pub async fn get_relationship_graph<R: Runtime>(
+    app: AppHandle<R>,
+    connection_id: String,
+    schema: Option<String>,
+    tables: Option<Vec<String>>,
+    depth: Option<u32>,
+) -> Result<RelationshipGraph, String> {
+    let saved_conn = crate::commands::find_connection_by_id(&app, &connection_id)?;
+    let expanded_params =
+        crate::commands::expand_ssh_connection_params(&app, &saved_conn.params).await?;
+    let params =
+        crate::commands::resolve_connection_params_with_id(&expanded_params, &connection_id)?;
+    let drv = crate::commands::driver_for(&saved_conn.params.driver).await?;
+
+    let snapshot = drv.get_schema_snapshot(&params, schema.as_deref()).await?;
+    Ok(build_relationship_graph(
+        &snapshot,
+        tables.as_deref(),
+        depth.unwrap_or(DEFAULT_NEIGHBOUR_DEPTH),
+    ))
+}
@@ -0,0 +1,146 @@
+#[cfg(test)]
+mod tests {
+    use crate::models::{ForeignKey, TableColumn, TableSchema};
+    use crate::relationship_graph::{build_relationship_graph, Cardinality};
+
+    fn column(name: &str, is_pk: bool, is_nullable: bool) -> TableColumn {
+        TableColumn {
+            name: name.into(),
+            data_type: "integer".into(),
+            is_pk,
+            is_nullable,
+            is_auto_increment: false,
+            default_value: None,
+            character_maximum_length: None,
+        }
+    }
+
+    fn fk(name: &str, column: &str, ref_table: &str) -> ForeignKey {
+        ForeignKey {
+            name: name.into(),
+            column_name: column.into(),
+            ref_table: ref_table.into(),
+            ref_column: "id".into(),
+            on_delete: None,
+            on_update: None,
+        }
+    }
+
+    fn table(name: &str, columns: Vec<TableColumn>, foreign_keys: Vec<ForeignKey>) -> TableSchema {
+        TableSchema {
+            name: name.into(),
+            columns,
+            foreign_keys,
+        }
+    }
+
+    /// users ← orders ← order_items → products; users ← profiles (1:1);
+    /// products ↔ tags through product_tags; audit_log is unrelated.
+    fn schema() -> Vec<TableSchema> {
+        vec![
+            table("users", vec![column("id", true, false)], vec![]),
+            table(
+                "profiles",
+                vec![column("user_id", true, false)],
+                vec![fk("fk_profiles_user", "user_id", "users")],
+            ),
+            table(
+                "orders",
+                vec![column("id", true, false), column("user_id", false, true)],
+                vec![fk("fk_orders_user", "user_id", "users")],
+            ),
+            table(
+                "order_items",
+                vec![
+                    column("id", true, false),
+                    column("order_id", false, false),
+                    column("product_id", false, false),
+                ],
+                vec![
+                    fk("fk_items_order", "order_id", "orders"),
+                    fk("fk_items_product", "product_id", "products"),
+                ],
+            ),
+            table("products", vec![column("id", true, false)], vec![]),
+            table("tags", vec![column("id", true, false)], vec![]),
+            table(
+                "product_tags",
+                vec![
+                    column("product_id", true, false),
+                    column("tag_id", true, false),
+                ],
+                vec![
+                    fk("fk_pt_product", "product_id", "products"),
+                    fk("fk_pt_tag", "tag_id", "tags"),
+                ],
+            ),
+            table("audit_log", vec![column("id", true, false)], vec![]),
+        ]
+    }
+
+    fn names(graph: &crate::relationship_graph::RelationshipGraph) -> Vec<&str> {
+        let mut names: Vec<&str> = graph.nodes.iter().map(|n| n.name.as_str()).collect();
+        names.sort();
+        names
+    }
+
+    #[test]
+    fn whole_schema_without_focus() {
+        let graph = build_relationship_graph(&schema(), None, 1);
+        assert_eq!(graph.nodes.len(), 8);
+        assert_eq!(graph.edges.len(), 6);
+    }
+
+    #[test]
+    fn derives_cardinality_and_optionality() {
+        let graph = build_relationship_graph(&schema(), None, 1);
+        let edge = |name: &str| graph.edges.iter().find(|e| e.name == name).unwrap();
+
+        let profile = edge("fk_profiles_user");
+        assert_eq!(profile.cardinality, Cardinality::OneToOne);
+        assert!(!profile.optional);
+
+        let order = edge("fk_orders_user");
+        assert_eq!(order.source, "orders");
+        assert_eq!(order.target, "users");
+        assert_eq!(order.cardinality, Cardinality::ManyToOne);
+        assert!(order.optional);
+    }
+
+    #[test]
+    fn detects_junction_tables() {
+        let graph = build_relationship_graph(&schema(), None, 1);
+        let junctions: Vec<&str> = graph
+            .nodes
+            .iter()
+            .filter(|n| n.junction)
+            .map(|n| n.name.as_str())
+            .collect();
+        assert_eq!(junctions, vec!["product_tags"]);
+    }
+
+    #[test]
+    fn focus_keeps_neighbours_within_depth() {
+        let focus = vec!["orders".to_string()];
+        let graph = build_relationship_graph(&schema(), Some(&focus), 1);
+        assert_eq!(names(&graph), vec!["order_items", "orders", "users"]);
+        assert_eq!(graph.edges.len(), 2);
+
+        let graph = build_relationship_graph(&schema(), Some(&focus), 2);
+        assert_eq!(
+            names(&graph),
+            vec!["order_items", "orders", "products", "profiles", "users"]
+        );
+
+        let graph = build_relationship_graph(&schema(), Some(&focus), 0);
+        assert_eq!(names(&graph), vec!["orders"]);
+        assert!(graph.edges.is_empty());
+    }
+
+    #[test]
+    fn ignores_unknown_tables() {
+        let focus = vec!["missing".to_string(), "audit_log".to_string()];
+        let graph = build_relationship_graph(&schema(), Some(&focus), 3);
+        assert_eq!(names(&graph), vec!["audit_log"]);
+    }
+}
@@ -0,0 +1,429 @@
+//! Annotated result snapshots.
+//!
+//! A snapshot freezes the rows of a query result so comments can be attached
+//! to single cells or whole rows, code-review style, and shared as a
+//! self-contained HTML report (print-friendly, so "Save as PDF" works from
+//! any browser).
+//!
+//! Storage layout under the application config directory:
+//!   - `result_snapshots/<snapshot_id>.json` — one snapshot with its annotations
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Runtime};
+
+use crate::models::QueryResult;
+
+const SNAPSHOTS_DIR: &str = "result_snapshots";
+/// Rows kept in a snapshot; the rest of the result is dropped.
+pub const MAX_SNAPSHOT_ROWS: usize = 10_000;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Annotation {
+    pub id: String,
+    /// Zero-based row index in the snapshot.
+    pub row: usize,
+    /// Annotated column; `None` annotates the whole row.
+    pub column: Option<String>,
+    pub text: String,
+    pub created_at: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ResultSnapshot {
+    pub id: String,
+    pub title: String,
+    pub connection_id: Option<String>,
+    pub query: String,
+    pub columns: Vec<String>,
+    pub rows: Vec<Vec<serde_json::Value>>,
+    /// True when the result had more than `MAX_SNAPSHOT_ROWS` rows.
+    #[serde(default)]
+    pub truncated: bool,
+    #[serde(default)]
+    pub annotations: Vec<Annotation>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ResultSnapshotSummary {
+    pub id: String,
+    pub title: String,
+    pub connection_id: Option<String>,
+    pub row_count: usize,
+    pub annotation_count: usize,
+    pub updated_at: String,
+}
+
+impl ResultSnapshot {
+    pub fn from_result(
+        title: &str,
+        connection_id: Option<String>,
+        query: &str,
+        result: QueryResult,
+    ) -> Self {
+        let now = chrono::Utc::now().to_rfc3339();
+        let truncated = result.truncated || result.rows.len() > MAX_SNAPSHOT_ROWS;
+        let mut rows = result.rows;
+        rows.truncate(MAX_SNAPSHOT_ROWS);
+        Self {
+            id: uuid::Uuid::new_v4().to_string(),
+            title: title.trim().to_string(),
+            connection_id,
+            query: query.to_string(),
+            columns: result.columns,
+            rows,
+            truncated,
+            annotations: Vec::new(),
+            created_at: now.clone(),
+            updated_at: now,
+        }
+    }
+
+    fn summary(&self) -> ResultSnapshotSummary {
+        ResultSnapshotSummary {
+            id: self.id.clone(),
+            title: self.title.clone(),
+            connection_id: self.connection_id.clone(),
+            row_count: self.rows.len(),
+            annotation_count: self.annotations.len(),
+            updated_at: self.updated_at.clone(),
+        }
+    }
+
+    /// Attach a comment to a row, or to one cell of it.
+    pub fn annotate(
+        &mut self,
+        row: usize,
+        column: Option<String>,
+        text: &str,
+    ) -> Result<Annotation, String> {
+        if row >= self.rows.len() {
+            return Err(format!("Row {} is not part of the snapshot", row));
+        }
+        if let Some(column) = &column {
+            if !self.columns.contains(column) {
+                return Err(format!("Column {} is not part of the snapshot", column));
+            }
+        }
+        let text = text.trim();
+        if text.is_empty() {
+            return Err("Annotation text cannot be empty".to_string());
+        }
+
+        let now = chrono::Utc::now().to_rfc3339();
+        let annotation = Annotation {
+            id: uuid::Uuid::new_v4().to_string(),
+            row,
+            column,
+            text: text.to_string(),
+            created_at: now.clone(),
+        };
+        self.annotations.push(annotation.clone());
+        self.updated_at = now;
+        Ok(annotation)
+    }
+
+    pub fn remove_annotation(&mut self, annotation_id: &str) -> Result<(), String> {
+        let before = self.annotations.len();
+        self.annotations.retain(|a| a.id != annotation_id);
+        if self.annotations.len() == before {
+            return Err(format!("Annotation not found: {}", annotation_id));
+        }
+        self.updated_at = chrono::Utc::now().to_rfc3339();
+        Ok(())
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Report (testable; pure)
+// ---------------------------------------------------------------------------
+
+pub fn escape_html(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+fn cell_text(value: &serde_json::Value) -> String {
+    match value {
+        serde_json::Value::Null => "NULL".to_string(),
+        serde_json::Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+const REPORT_STYLE: &str = "\
+body{font-family:-apple-system,Segoe UI,Helvetica,Arial,sans-serif;margin:24px;color:#1f2328}\
+h1{font-size:20px;margin:0 0 4px}\
+.meta{color:#656d76;font-size:12px;margin-bottom:16px}\
+pre{background:#f6f8fa;padding:12px;border-radius:6px;white-space:pre-wrap;font-size:12px}\
+table{border-collapse:collapse;font-size:12px;margin:16px 0}\
+th,td{border:1px solid #d0d7de;padding:4px 8px;text-align:left;vertical-align:top}\
+th{background:#f6f8fa}\
+td.null{color:#8c959f;font-style:italic}\
+tr.annotated td,td.annotated{background:#fff8c5}\
+sup{color:#9a6700;font-weight:600;margin-left:2px}\
+ol.notes li{margin-bottom:6px;font-size:13px}\
+.where{color:#656d76}\
+@media print{body{margin:0}tr{break-inside:avoid}}";
+
+/// Self-contained HTML report. Only annotated rows are rendered unless
+/// `all_rows` is set; notes are numbered in row order and referenced from
+/// their cells.
+pub fn render_html_report(snapshot: &ResultSnapshot, all_rows: bool) -> String {
+    let mut notes: Vec<&Annotation> = snapshot.annotations.iter().collect();
+    notes.sort_by(|a, b| (a.row, &a.created_at).cmp(&(b.row, &b.created_at)));
+    let note_number = |a: &Annotation| notes.iter().position(|n| n.id == a.id).unwrap_or(0) + 1;
+
+    let title = escape_html(&snapshot.title);
+    let mut html = String::new();
+    html.push_str("<!DOCTYPE html>\n<html><head><meta charset=\"utf-8\">");
+    html.push_str(&format!("<title>{}</title>", title));
+    html.push_str(&format!("<style>{}</style></head><body>\n", REPORT_STYLE));
+    html.push_str(&format!("<h1>{}</h1>\n", title));
+    html.push_str(&format!(
+        "<div class=\"meta\">Snapshot taken {} · {} rows{} · {} annotations</div>\n",
+        escape_html(&snapshot.created_at),
+        snapshot.rows.len(),
+        if snapshot.truncated {
+            " (truncated)"
+        } else {
+            ""
+        },
+        notes.len()
+    ));
+    html.push_str(&format!("<pre>{}</pre>\n", escape_html(&snapshot.query)));
+
+    html.push_str("<table><thead><tr><th>#</th>");
+    for column in &snapshot.columns {
+        html.push_str(&format!("<th>{}</th>", escape_html(column)));
+    }
+    html.push_str("</tr></thead><tbody>\n");
+    for (index, row) in snapshot.rows.iter().enumerate() {
+        let row_notes: Vec<&&Annotation> = notes.iter().filter(|a| a.row == index).collect();
+        if !all_rows && row_notes.is_empty() {
+            continue;
+        }
+        let row_marks: String = row_notes
+            .iter()
+            .filter(|a| a.column.is_none())
+            .map(|a| format!("<sup>[{}]</sup>", note_number(a)))
+            .collect();
+        let row_class = if row_marks.is_empty() {
+            ""
+        } else {
+            " class=\"annotated\""
+        };
+        html.push_str(&format!(
+            "<tr{}><td>{}{}</td>",
+            row_class,
+            index + 1,
+            row_marks
+        ));
+        for (column, value) in snapshot.columns.iter().zip(row) {
+            let marks: String = row_notes
+                .iter()
+                .filter(|a| a.column.as_deref() == Some(column.as_str()))
+                .map(|a| format!("<sup>[{}]</sup>", note_number(a)))
+                .collect();
+            let mut classes = Vec::new();
+            if value.is_null() {
+                classes.push("null");
+            }
+            if !marks.is_empty() {
+                classes.push("annotated");
+            }
+            let class = if classes.is_empty() {
+                String::new()
+            } else {
+                format!(" class=\"{}\"", classes.join(" "))
+            };
+            html.push_str(&format!(
+                "<td{}>{}{}</td>",
+                class,
+                escape_html(&cell_text(value)),
+                marks
+            ));
+        }
+        html.push_str("</tr>\n");
+    }
+    html.push_str("</tbody></table>\n");
+
+    if !notes.is_empty() {
+        html.push_str("<h2>Annotations</h2>\n<ol class=\"notes\">\n");
+        for note in &notes {
+            let place = match &note.column {
+                Some(column) => format!("row {}, {}", note.row + 1, escape_html(column)),
+                None => format!("row {}", note.row + 1),
+            };
+            html.push_str(&format!(
+                "<li><span class=\"where\">{}:</span> {}</li>\n",
+                place,
+                escape_html(&note.text).replace('\n', "<br>")
+            ));
+        }
+        html.push_str("</ol>\n");
+    }
+    html.push_str("</body></html>\n");
+    html
+}
+
+// ---------------------------------------------------------------------------
+// Storage (testable; take a directory)
+// ---------------------------------------------------------------------------
+
+fn snapshot_path(dir: &Path, id: &str) -> Result<PathBuf, String> {
+    // Ids come from the frontend; only accept our own uuids as file names.
+    uuid::Uuid::parse_str(id).map_err(|_| format!("Invalid snapshot id: {}", id))?;
+    Ok(dir.join(SNAPSHOTS_DIR).join(format!("{}.json", id)))
+}
+
+pub fn write_snapshot_in(dir: &Path, snapshot: &ResultSnapshot) -> Result<(), String> {
+    let path = snapshot_path(dir, &snapshot.id)?;
+    fs::create_dir_all(dir.join(SNAPSHOTS_DIR))
+        .map_err(|e| format!("Failed to create snapshot dir: {}", e))?;
+    let json = serde_json::to_string(snapshot)
+        .map_err(|e| format!("Failed to serialize snapshot: {}", e))?;
+    fs::write(path, json).map_err(|e| format!("Failed to save snapshot: {}", e))
+}
+
+pub fn read_snapshot_in(dir: &Path, id: &str) -> Result<ResultSnapshot, String> {
+    let content = fs::read_to_string(snapshot_path(dir, id)?)
+        .map_err(|_| format!("Result snapshot not found: {}", id))?;
+    serde_json::from_str(&content).map_err(|e| format!("Failed to parse snapshot: {}", e))
+}
+
+/// Summaries of every snapshot, most recently updated first.
+pub fn list_snapshots_in(dir: &Path) -> Vec<ResultSnapshotSummary> {
+    let mut summaries: Vec<ResultSnapshotSummary> = fs::read_dir(dir.join(SNAPSHOTS_DIR))
+        .map(|entries| {
+            entries
+                .filter_map(|e| e.ok())
+                .filter_map(|e| fs::read_to_string(e.path()).ok())
+                .filter_map(|content| serde_json::from_str::<ResultSnapshot>(&content).ok())
+                .map(|s| s.summary())
+                .collect()
+        })
+        .unwrap_or_default();
+    summaries.sort_by(|a, b| b.updated_at.cmp(&a.updated_at));
+    summaries
+}
+
+pub fn delete_snapshot_in(dir: &Path, id: &str) -> Result<(), String> {
+    let path = snapshot_path(dir, id)?;
+    if !path.exists() {
+        return Err(format!("Result snapshot not found: {}", id));
+    }
+    fs::remove_file(path).map_err(|e| format!("Failed to delete snapshot: {}", e))
+}
+
+/// Load, change and save a snapshot.
+fn update_snapshot_in<T>(
+    dir: &Path,
+    id: &str,
+    change: impl FnOnce(&mut ResultSnapshot) -> Result<T, String>,
+) -> Result<T, String> {
+    let mut snapshot = read_snapshot_in(dir, id)?;
+    let out = change(&mut snapshot)?;
+    write_snapshot_in(dir, &snapshot)?;
+    Ok(out)
+}
+
+// ---------------------------------------------------------------------------
+// Tauri commands
+// ---------------------------------------------------------------------------
+
+/// Freeze a result so it can be annotated.
+#[tauri::command]
+pub fn save_result_snapshot<R: Runtime>(
+    app: AppHandle<R>,
+    title: String,
+    connection_id: Option<String>,
+    query: String,
+    result: QueryResult,
+) -> Result<ResultSnapshot, String> {
+    let title = if title.trim().is_empty() {
+        crate::ai_notebook_export::derive_cell_name(&query, 1)
+    } else {
+        title
+    };
+    let snapshot = ResultSnapshot::from_result(&title, connection_id, &query, result);
+    write_snapshot_in(&crate::paths::app_config_dir(&app)?, &snapshot)?;
+    Ok(snapshot)
+}
+
+#[tauri::command]
+pub fn list_result_snapshots<R: Runtime>(
+    app: AppHandle<R>,
+) -> Result<Vec<ResultSnapshotSummary>, String> {
+    Ok(list_snapshots_in(&crate::paths::app_config_dir(&app)?))
+}
+
+#[tauri::command]
+pub fn get_result_snapshot<R: Runtime>(
+    app: AppHandle<R>,
+    id: String,
+) -> Result<ResultSnapshot, String> {
+    read_snapshot_in(&crate::paths::app_config_dir(&app)?, &id)
+}
+
+#[tauri::command]
+pub fn delete_result_snapshot<R: Runtime>(app: AppHandle<R>, id: String) -> Result<(), String> {
+    delete_snapshot_in(&crate::paths::app_config_dir(&app)?, &id)
+}
+
+#[tauri::command]
+pub fn annotate_result<R: Runtime>(
+    app: AppHandle<R>,
+    snapshot_id: String,
+    row: usize,
+    column: Option<String>,
+    text: String,
+) -> Result<Annotation, String> {
+    update_snapshot_in(
+        &crate::paths::app_config_dir(&app)?,
+        &snapshot_id,
+        |snapshot| snapshot.annotate(row, column, &text),
+    )
+}
+
+#[tauri::command]
+pub fn delete_result_annotation<R: Runtime>(
+    app: AppHandle<R>,
+    snapshot_id: String,
+    annotation_id: String,
+) -> Result<(), String> {
+    update_snapshot_in(
+        &crate::paths::app_config_dir(&app)?,
+        &snapshot_id,
+        |snapshot| snapshot.remove_annotation(&annotation_id),
+    )
+}
+
+/// Write the annotated report as HTML to `file_path`.
+#[tauri::command]
+pub fn export_annotated_report<R: Runtime>(
+    app: AppHandle<R>,
+    snapshot_id: String,
+    file_path: String,
+    all_rows: Option<bool>,
+) -> Result<(), String> {
+    let snapshot = read_snapshot_in(&crate::paths::app_config_dir(&app)?, &snapshot_id)?;
+    let html = render_html_report(&snapshot, all_rows.unwrap_or(false));
+    fs::write(&file_path, html).map_err(|e| format!("Failed to write report: {}", e))
+}
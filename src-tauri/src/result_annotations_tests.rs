@@ -0,0 +1,128 @@
+#[cfg(test)]
+mod tests {
+    use crate::models::QueryResult;
+    use crate::result_annotations::{
+        delete_snapshot_in, escape_html, list_snapshots_in, read_snapshot_in, render_html_report,
+        write_snapshot_in, ResultSnapshot, MAX_SNAPSHOT_ROWS,
+    };
+    use serde_json::json;
+    use tempfile::TempDir;
+
+    fn result(rows: Vec<Vec<serde_json::Value>>) -> QueryResult {
+        QueryResult {
+            columns: vec!["id".to_string(), "email".to_string()],
+            rows,
+            affected_rows: 0,
+            truncated: false,
+            truncation: None,
+            pagination: None,
+            style_hints: None,
+            provenance: None,
+        }
+    }
+
+    fn snapshot() -> ResultSnapshot {
+        ResultSnapshot::from_result(
+            "Duplicate emails",
+            Some("c1".to_string()),
+            "SELECT id, email FROM users",
+            result(vec![
+                vec![json!(1), json!("a@example.com")],
+                vec![json!(2), json!(null)],
+                vec![json!(3), json!("<b>@example.com")],
+            ]),
+        )
+    }
+
+    // --- annotations ---
+
+    #[test]
+    fn annotates_cells_and_rows() {
+        let mut s = snapshot();
+        let cell = s
+            .annotate(2, Some("email".to_string()), " not a valid address ")
+            .unwrap();
+        assert_eq!(cell.text, "not a valid address");
+        s.annotate(1, None, "missing email").unwrap();
+        assert_eq!(s.annotations.len(), 2);
+
+        s.remove_annotation(&cell.id).unwrap();
+        assert_eq!(s.annotations.len(), 1);
+        assert!(s.remove_annotation(&cell.id).is_err());
+    }
+
+    #[test]
+    fn rejects_annotations_outside_the_snapshot() {
+        let mut s = snapshot();
+        assert!(s.annotate(3, None, "out of range").is_err());
+        assert!(s
+            .annotate(0, Some("name".to_string()), "no column")
+            .is_err());
+        assert!(s.annotate(0, None, "   ").is_err());
+    }
+
+    #[test]
+    fn snapshot_keeps_at_most_max_rows() {
+        let rows = (0..MAX_SNAPSHOT_ROWS + 5)
+            .map(|i| vec![json!(i), json!("x")])
+            .collect();
+        let s = ResultSnapshot::from_result("Big", None, "SELECT 1", result(rows));
+        assert_eq!(s.rows.len(), MAX_SNAPSHOT_ROWS);
+        assert!(s.truncated);
+    }
+
+    // --- report ---
+
+    #[test]
+    fn escapes_html() {
+        assert_eq!(
+            escape_html("<a href=\"x\">Tom & 'Jerry'</a>"),
+            "&lt;a href=&quot;x&quot;&gt;Tom &amp; &#39;Jerry&#39;&lt;/a&gt;"
+        );
+    }
+
+    #[test]
+    fn report_lists_annotated_rows_with_numbered_notes() {
+        let mut s = snapshot();
+        s.annotate(2, Some("email".to_string()), "bad <domain>")
+            .unwrap();
+        s.annotate(1, None, "missing email").unwrap();
+
+        let html = render_html_report(&s, false);
+        assert!(html.contains("<title>Duplicate emails</title>"));
+        assert!(html.contains("&lt;b&gt;@example.com<sup>[2]</sup>"));
+        assert!(html.contains("<tr class=\"annotated\"><td>2<sup>[1]</sup></td>"));
+        assert!(html.contains("<td class=\"null\">NULL</td>"));
+        assert!(html.contains("row 3, email:</span> bad &lt;domain&gt;"));
+        assert!(!html.contains("a@example.com"));
+
+        assert!(render_html_report(&s, true).contains("a@example.com"));
+    }
+
+    // --- storage ---
+
+    #[test]
+    fn stores_lists_and_deletes_snapshots() {
+        let dir = TempDir::new().unwrap();
+        let mut s = snapshot();
+        s.annotate(0, None, "first").unwrap();
+        write_snapshot_in(dir.path(), &s).unwrap();
+
+        assert_eq!(read_snapshot_in(dir.path(), &s.id).unwrap(), s);
+        let summaries = list_snapshots_in(dir.path());
+        assert_eq!(summaries.len(), 1);
+        assert_eq!(summaries[0].row_count, 3);
+        assert_eq!(summaries[0].annotation_count, 1);
+
+        delete_snapshot_in(dir.path(), &s.id).unwrap();
+        assert!(list_snapshots_in(dir.path()).is_empty());
+        assert!(read_snapshot_in(dir.path(), &s.id).is_err());
+    }
+
+    #[test]
+    fn rejects_ids_that_are_not_uuids() {
+        let dir = TempDir::new().unwrap();
+        assert!(read_snapshot_in(dir.path(), "../connections").is_err());
+        assert!(delete_snapshot_in(dir.path(), "../connections").is_err());
+    }
+}
@@ -0,0 +1,289 @@
+//! Compare the results of two queries row by row.
+//!
+//! `diff_query_results` runs a query on each side (two queries, or the same
+//! query against two connections such as staging and production), lines the
+//! rows up by the chosen key columns and reports the rows only on the right
+//! (added), only on the left (removed) and present on both sides with
+//! different values (changed). Only columns both results share are compared;
+//! the others are listed so the UI can say why they are missing. Values are
+//! compared as the drivers return them, so `1` and `"1"` differ.
+
+use std::collections::{HashMap, HashSet};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tauri::{AppHandle, Runtime, State, WebviewWindow};
+
+use crate::broadcast::run_on_connection;
+use crate::commands::{sanitize_user_query, QueryCancellationState};
+use crate::models::QueryResult;
+
+/// Rows fetched per side when the caller does not say.
+pub const DEFAULT_DIFF_ROW_LIMIT: u32 = 10_000;
+
+/// One side of a diff.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DiffSide {
+    pub connection_id: String,
+    pub query: String,
+    pub schema: Option<String>,
+}
+
+/// A key present on both sides whose other values differ.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChangedRow {
+    pub key: Vec<Value>,
+    pub left: Vec<Value>,
+    pub right: Vec<Value>,
+    /// Names of the compared columns whose values differ.
+    pub changed_columns: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResultDiff {
+    /// Columns both results share, in left order. Every row below is laid
+    /// out in this order.
+    pub columns: Vec<String>,
+    pub key_columns: Vec<String>,
+    /// Columns only the left result has; they are not compared.
+    pub left_only_columns: Vec<String>,
+    /// Columns only the right result has; they are not compared.
+    pub right_only_columns: Vec<String>,
+    /// Rows whose key is only on the right.
+    pub added: Vec<Vec<Value>>,
+    /// Rows whose key is only on the left.
+    pub removed: Vec<Vec<Value>>,
+    pub changed: Vec<ChangedRow>,
+    pub unchanged: usize,
+    /// A side hit the row limit, so rows past it were not compared.
+    pub truncated: bool,
+}
+
+// ---------------------------------------------------------------------------
+// Diff (testable; pure)
+// ---------------------------------------------------------------------------
+
+fn column_index(columns: &[String], name: &str) -> Option<usize> {
+    columns.iter().position(|c| c == name)
+}
+
+fn column_indexes(columns: &[String], names: &[String]) -> Vec<usize> {
+    names
+        .iter()
+        .filter_map(|name| column_index(columns, name))
+        .collect()
+}
+
+fn is_partial(result: &QueryResult) -> bool {
+    result.truncated
+        || result.truncation.is_some()
+        || result.pagination.as_ref().is_some_and(|p| p.has_more)
+}
+
+/// Rows of `result` keyed by their key values, in result order. Fails on a
+/// key that is not unique.
+fn keyed_rows<'a>(
+    result: &'a QueryResult,
+    key_indexes: &[usize],
+    side: &str,
+) -> Result<Vec<(String, &'a Vec<Value>)>, String> {
+    let mut seen = HashSet::new();
+    let mut keyed = Vec::with_capacity(result.rows.len());
+    for row in &result.rows {
+        let key: Vec<&Value> = key_indexes
+            .iter()
+            .map(|&i| row.get(i).unwrap_or(&Value::Null))
+            .collect();
+        let key = serde_json::to_string(&key).map_err(|e| e.to_string())?;
+        if !seen.insert(key.clone()) {
+            return Err(format!(
+                "Key columns do not identify rows uniquely: {} appears more than once in the {} result",
+                key, side
+            ));
+        }
+        keyed.push((key, row));
+    }
+    Ok(keyed)
+}
+
+fn project(row: &[Value], indexes: &[usize]) -> Vec<Value> {
+    indexes
+        .iter()
+        .map(|&i| row.get(i).cloned().unwrap_or(Value::Null))
+        .collect()
+}
+
+/// Diff `left` against `right`, aligning rows on `key_columns`.
+pub fn diff_results(
+    left: &QueryResult,
+    right: &QueryResult,
+    key_columns: &[String],
+) -> Result<ResultDiff, String> {
+    if key_columns.is_empty() {
+        return Err("Choose at least one key column to match rows on".into());
+    }
+    for key in key_columns {
+        for (side, result) in [("left", left), ("right", right)] {
+            if column_index(&result.columns, key).is_none() {
+                return Err(format!(
+                    "Key column '{}' is missing from the {} result",
+                    key, side
+                ));
+            }
+        }
+    }
+
+    let columns: Vec<String> = left
+        .columns
+        .iter()
+        .filter(|c| right.columns.contains(c))
+        .cloned()
+        .collect();
+    let left_only_columns = left
+        .columns
+        .iter()
+        .filter(|c| !columns.contains(c))
+        .cloned()
+        .collect();
+    let right_only_columns = right
+        .columns
+        .iter()
+        .filter(|c| !columns.contains(c))
+        .cloned()
+        .collect();
+
+    let left_indexes = column_indexes(&left.columns, &columns);
+    let right_indexes = column_indexes(&right.columns, &columns);
+    let key_positions = column_indexes(&columns, key_columns);
+
+    let left_rows = keyed_rows(left, &column_indexes(&left.columns, key_columns), "left")?;
+    let right_rows = keyed_rows(right, &column_indexes(&right.columns, key_columns), "right")?;
+    let mut right_by_key: HashMap<&str, &Vec<Value>> = right_rows
+        .iter()
+        .map(|(key, row)| (key.as_str(), *row))
+        .collect();
+
+    let mut removed = Vec::new();
+    let mut changed = Vec::new();
+    let mut unchanged = 0;
+    for (key, left_row) in &left_rows {
+        let left_values = project(left_row, &left_indexes);
+        match right_by_key.remove(key.as_str()) {
+            None => removed.push(left_values),
+            Some(right_row) => {
+                let right_values = project(right_row, &right_indexes);
+                let changed_columns: Vec<String> = columns
+                    .iter()
+                    .zip(left_values.iter().zip(&right_values))
+                    .filter(|(_, (l, r))| l != r)
+                    .map(|(name, _)| name.clone())
+                    .collect();
+                if changed_columns.is_empty() {
+                    unchanged += 1;
+                } else {
+                    changed.push(ChangedRow {
+                        key: project(&left_values, &key_positions),
+                        left: left_values,
+                        right: right_values,
+                        changed_columns,
+                    });
+                }
+            }
+        }
+    }
+    let added = right_rows
+        .iter()
+        .filter(|(key, _)| right_by_key.contains_key(key.as_str()))
+        .map(|(_, row)| project(row, &right_indexes))
+        .collect();
+
+    Ok(ResultDiff {
+        columns,
+        key_columns: key_columns.to_vec(),
+        left_only_columns,
+        right_only_columns,
+        added,
+        removed,
+        changed,
+        unchanged,
+        truncated: is_partial(left) || is_partial(right),
+    })
+}
+
+// ---------------------------------------------------------------------------
+// Tauri commands
+// ---------------------------------------------------------------------------
+
+/// Run the query of each side and diff the results on `key_columns`.
+#[tauri::command]
+pub async fn diff_query_results<R: Runtime>(
+    app: AppHandle<R>,
+    window: WebviewWindow<R>,
+    state: State<'_, QueryCancellationState>,
+    left: DiffSide,
+    right: DiffSide,
+    key_columns: Vec<String>,
+    limit: Option<u32>,
+    allow_costly: Option<bool>,
+) -> Result<ResultDiff, String> {
+    let limit = Some(limit.unwrap_or(DEFAULT_DIFF_ROW_LIMIT));
+    let allow_costly = allow_costly.unwrap_or(false);
+    let left_query = sanitize_user_query(&left.query);
+    let right_query = sanitize_user_query(&right.query);
+    log::info!(
+        "Diffing query results of {} and {} on key ({})",
+        left.connection_id,
+        right.connection_id,
+        key_columns.join(", ")
+    );
+
+    let state = state.inner();
+    let (left_entry, right_entry) = futures::join!(
+        run_on_connection(
+            &app,
+            window.label(),
+            state,
+            &left.connection_id,
+            &left_query,
+            limit,
+            left.schema.as_deref(),
+            allow_costly,
+        ),
+        run_on_connection(
+            &app,
+            window.label(),
+            state,
+            &right.connection_id,
+            &right_query,
+            limit,
+            right.schema.as_deref(),
+            allow_costly,
+        ),
+    );
+
+    let mut results = Vec::with_capacity(2);
+    for (side, entry) in [("Left", left_entry), ("Right", right_entry)] {
+        match (entry.result, entry.error) {
+            (Some(result), None) => results.push(result),
+            (_, error) => {
+                return Err(format!(
+                    "{} query failed: {}",
+                    side,
+                    error.unwrap_or_else(|| "no result".into())
+                ))
+            }
+        }
+    }
+    let diff = diff_results(&results[0], &results[1], &key_columns)?;
+    log::info!(
+        "Query diff: {} added, {} removed, {} changed, {} unchanged",
+        diff.added.len(),
+        diff.removed.len(),
+        diff.changed.len(),
+        diff.unchanged
+    );
+    Ok(diff)
+}
@@ -0,0 +1,128 @@
+#[cfg(test)]
+mod tests {
+    use crate::models::{Pagination, QueryResult};
+    use crate::result_diff::diff_results;
+    use serde_json::{json, Value};
+
+    fn result(columns: &[&str], rows: Vec<Vec<Value>>) -> QueryResult {
+        QueryResult {
+            columns: columns.iter().map(|c| c.to_string()).collect(),
+            rows,
+            affected_rows: 0,
+            truncated: false,
+            truncation: None,
+            pagination: None,
+            style_hints: None,
+            provenance: None,
+        }
+    }
+
+    fn keys(names: &[&str]) -> Vec<String> {
+        names.iter().map(|n| n.to_string()).collect()
+    }
+
+    #[test]
+    fn reports_added_removed_and_changed_rows() {
+        let staging = result(
+            &["id", "name", "plan"],
+            vec![
+                vec![json!(1), json!("Ada"), json!("pro")],
+                vec![json!(2), json!("Bob"), json!("free")],
+                vec![json!(3), json!("Cy"), json!("free")],
+            ],
+        );
+        let production = result(
+            &["id", "name", "plan"],
+            vec![
+                vec![json!(4), json!("Dee"), json!("pro")],
+                vec![json!(3), json!("Cy"), json!("pro")],
+                vec![json!(1), json!("Ada"), json!("pro")],
+            ],
+        );
+
+        let diff = diff_results(&staging, &production, &keys(&["id"])).unwrap();
+        assert_eq!(diff.added, vec![vec![json!(4), json!("Dee"), json!("pro")]]);
+        assert_eq!(
+            diff.removed,
+            vec![vec![json!(2), json!("Bob"), json!("free")]]
+        );
+        assert_eq!(diff.changed.len(), 1);
+        assert_eq!(diff.changed[0].key, vec![json!(3)]);
+        assert_eq!(diff.changed[0].changed_columns, keys(&["plan"]));
+        assert_eq!(diff.changed[0].right[2], json!("pro"));
+        assert_eq!(diff.unchanged, 1);
+        assert!(!diff.truncated);
+    }
+
+    #[test]
+    fn compares_shared_columns_by_name() {
+        let left = result(
+            &["id", "email", "legacy"],
+            vec![vec![json!(1), json!("a@x.io"), json!(true)]],
+        );
+        let right = result(
+            &["created_at", "email", "id"],
+            vec![vec![json!("2026-01-01"), json!("a@x.io"), json!(1)]],
+        );
+
+        let diff = diff_results(&left, &right, &keys(&["id"])).unwrap();
+        assert_eq!(diff.columns, keys(&["id", "email"]));
+        assert_eq!(diff.left_only_columns, keys(&["legacy"]));
+        assert_eq!(diff.right_only_columns, keys(&["created_at"]));
+        assert_eq!(diff.unchanged, 1);
+        assert!(diff.changed.is_empty());
+    }
+
+    #[test]
+    fn matches_on_composite_keys() {
+        let left = result(
+            &["tenant", "id", "total"],
+            vec![
+                vec![json!("a"), json!(1), json!(10)],
+                vec![json!("b"), json!(1), json!(20)],
+            ],
+        );
+        let right = result(
+            &["tenant", "id", "total"],
+            vec![
+                vec![json!("b"), json!(1), json!(25)],
+                vec![json!("a"), json!(1), json!(10)],
+            ],
+        );
+
+        let diff = diff_results(&left, &right, &keys(&["tenant", "id"])).unwrap();
+        assert_eq!(diff.changed.len(), 1);
+        assert_eq!(diff.changed[0].key, vec![json!("b"), json!(1)]);
+        assert_eq!(diff.unchanged, 1);
+    }
+
+    #[test]
+    fn rejects_unusable_keys() {
+        let left = result(&["id"], vec![vec![json!(1)], vec![json!(1)]]);
+        let right = result(&["id"], vec![vec![json!(1)]]);
+
+        assert!(diff_results(&left, &right, &[]).is_err());
+        let missing = diff_results(&left, &right, &keys(&["uuid"])).unwrap_err();
+        assert!(missing.contains("'uuid'"), "{}", missing);
+        let duplicate = diff_results(&left, &right, &keys(&["id"])).unwrap_err();
+        assert!(duplicate.contains("left"), "{}", duplicate);
+    }
+
+    #[test]
+    fn flags_results_cut_short() {
+        let left = result(&["id"], vec![vec![json!(1)]]);
+        let mut right = result(&["id"], vec![vec![json!(1)]]);
+        right.pagination = Some(Pagination {
+            page: 1,
+            page_size: 1,
+            total_rows: None,
+            has_more: true,
+        });
+
+        assert!(
+            diff_results(&left, &right, &keys(&["id"]))
+                .unwrap()
+                .truncated
+        );
+    }
+}
@@ -0,0 +1,228 @@
+//! User scripts that post-process query results.
+//!
+//! Scripts are written in [Rhai](https://rhai.rs) and stored per connection.
+//! A script without `savedQueryId` runs on every ad-hoc query of the
+//! connection; one with it only when that saved query is run. Each script
+//! sees the result as two variables and may change both:
+//!
+//! - `columns`: array of column names;
+//! - `rows`: array of object maps keyed by column name.
+//!
+//! ```rhai
+//! columns.push("total");
+//! rows = rows.map(|r| { r.total = r.price * r.qty; r.email = "***"; r });
+//! ```
+//!
+//! Keys added to rows but missing from `columns` are appended as new
+//! columns; names removed from `columns` are dropped from the result.
+
+use std::collections::HashSet;
+use std::fs;
+
+use rhai::{Array, Dynamic, Engine, Map, Scope, AST};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::models::QueryResult;
+use crate::preferences::connection_preferences_file;
+
+const RESULT_SCRIPTS_FILE: &str = "result_scripts.json";
+
+/// Upper bound on the work one script may do over one result set.
+const MAX_OPERATIONS: u64 = 50_000_000;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ResultScript {
+    pub id: String,
+    #[serde(default)]
+    pub name: Option<String>,
+    pub source: String,
+    /// Run only for this saved query instead of every ad-hoc query.
+    #[serde(default)]
+    pub saved_query_id: Option<String>,
+    /// Default: true
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+/// Load the scripts of a connection, in execution order.
+pub fn load_result_scripts(connection_id: &str) -> Result<Vec<ResultScript>, String> {
+    let path = connection_preferences_file(connection_id, RESULT_SCRIPTS_FILE)?;
+
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let content = fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read result scripts file: {}", e))?;
+
+    serde_json::from_str(&content)
+        .map_err(|e| format!("Failed to parse result scripts file: {}", e))
+}
+
+/// The enabled scripts that apply to a query: connection-wide ones, plus
+/// those of `saved_query_id` when the query is a saved one.
+pub fn scripts_for<'a>(
+    scripts: &'a [ResultScript],
+    saved_query_id: Option<&str>,
+) -> Vec<&'a ResultScript> {
+    scripts
+        .iter()
+        .filter(|s| s.enabled)
+        .filter(|s| match s.saved_query_id.as_deref() {
+            None => true,
+            Some(id) => saved_query_id == Some(id),
+        })
+        .collect()
+}
+
+fn engine() -> Engine {
+    let mut engine = Engine::new();
+    engine.set_max_operations(MAX_OPERATIONS);
+    engine.on_print(|text| log::info!("[result script] {}", text));
+    engine.on_debug(|text, _, pos| log::debug!("[result script] {:?} {}", pos, text));
+    engine
+}
+
+fn compile(engine: &Engine, source: &str) -> Result<AST, String> {
+    engine.compile(source).map_err(|e| e.to_string())
+}
+
+/// Check that a script parses.
+pub fn validate_script(source: &str) -> Result<(), String> {
+    compile(&engine(), source).map(|_| ())
+}
+
+/// Run `scripts` in order over `result`. Style hints are dropped, as row
+/// positions may no longer match. Statements without a result set (DML,
+/// DDL) are left alone.
+pub fn apply_result_scripts(
+    result: &mut QueryResult,
+    scripts: &[&ResultScript],
+) -> Result<(), String> {
+    if scripts.is_empty() || result.columns.is_empty() {
+        return Ok(());
+    }
+    let engine = engine();
+    for script in scripts {
+        let label = script.name.as_deref().unwrap_or(&script.id);
+        let ast = compile(&engine, &script.source)
+            .map_err(|e| format!("Result script '{}' does not compile: {}", label, e))?;
+        run_script(&engine, &ast, result)
+            .map_err(|e| format!("Result script '{}' failed: {}", label, e))?;
+    }
+    result.style_hints = None;
+    Ok(())
+}
+
+fn run_script(engine: &Engine, ast: &AST, result: &mut QueryResult) -> Result<(), String> {
+    let columns: Array = result.columns.iter().cloned().map(Dynamic::from).collect();
+    let mut rows = Array::with_capacity(result.rows.len());
+    for row in &result.rows {
+        let mut map = Map::new();
+        for (column, value) in result.columns.iter().zip(row) {
+            map.insert(column.as_str().into(), to_dynamic(value)?);
+        }
+        rows.push(map.into());
+    }
+
+    let mut scope = Scope::new();
+    scope.push("columns", columns);
+    scope.push("rows", rows);
+    engine
+        .run_ast_with_scope(&mut scope, ast)
+        .map_err(|e| e.to_string())?;
+
+    let columns: Vec<String> = scope
+        .get_value::<Array>("columns")
+        .ok_or("`columns` must stay an array")?
+        .into_iter()
+        .map(|c| c.into_string().map_err(|_| "column names must be strings"))
+        .collect::<Result<_, _>>()?;
+    let rows: Vec<Map> = scope
+        .get_value::<Array>("rows")
+        .ok_or("`rows` must stay an array")?
+        .into_iter()
+        .map(|r| r.try_cast::<Map>().ok_or("rows must be object maps"))
+        .collect::<Result<_, _>>()?;
+
+    let columns = result_columns(columns, &result.columns, &rows);
+    result.rows = rows
+        .iter()
+        .map(|row| {
+            columns
+                .iter()
+                .map(|c| row.get(c.as_str()).map_or(Ok(Value::Null), from_dynamic))
+                .collect::<Result<Vec<_>, _>>()
+        })
+        .collect::<Result<_, _>>()?;
+    result.columns = columns;
+    Ok(())
+}
+
+/// The script's `columns`, followed by keys the script added to rows that
+/// were neither original columns nor listed.
+fn result_columns(mut columns: Vec<String>, original: &[String], rows: &[Map]) -> Vec<String> {
+    let mut known: HashSet<String> = columns.iter().chain(original).cloned().collect();
+    for row in rows {
+        for key in row.keys() {
+            if known.insert(key.to_string()) {
+                columns.push(key.to_string());
+            }
+        }
+    }
+    columns
+}
+
+fn to_dynamic(value: &Value) -> Result<Dynamic, String> {
+    rhai::serde::to_dynamic(value).map_err(|e| e.to_string())
+}
+
+fn from_dynamic(value: &Dynamic) -> Result<Value, String> {
+    rhai::serde::from_dynamic(value).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn get_result_scripts(connection_id: String) -> Result<Vec<ResultScript>, String> {
+    load_result_scripts(&connection_id)
+}
+
+/// Replace the scripts of a connection. Every script must compile.
+#[tauri::command]
+pub async fn save_result_scripts(
+    connection_id: String,
+    scripts: Vec<ResultScript>,
+) -> Result<(), String> {
+    for script in &scripts {
+        validate_script(&script.source)
+            .map_err(|e| format!("Invalid script {}: {}", script.id, e))?;
+    }
+
+    let path = connection_preferences_file(&connection_id, RESULT_SCRIPTS_FILE)?;
+    let json = serde_json::to_string_pretty(&scripts)
+        .map_err(|e| format!("Failed to serialize result scripts: {}", e))?;
+
+    fs::write(&path, json).map_err(|e| format!("Failed to write result scripts file: {}", e))
+}
+
+/// Run a script over a sample result, for previewing it in the editor.
+#[tauri::command]
+pub async fn preview_result_script(
+    source: String,
+    mut result: QueryResult,
+) -> Result<QueryResult, String> {
+    let script = ResultScript {
+        id: "preview".into(),
+        name: None,
+        source,
+        saved_query_id: None,
+        enabled: true,
+    };
+    apply_result_scripts(&mut result, &[&script])?;
+    Ok(result)
+}
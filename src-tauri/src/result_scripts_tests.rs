@@ -0,0 +1,153 @@
+#[cfg(test)]
+mod tests {
+    use crate::models::{QueryResult, StyleHint};
+    use crate::result_scripts::{apply_result_scripts, scripts_for, validate_script, ResultScript};
+    use serde_json::{json, Value};
+
+    fn script(id: &str, source: &str, saved_query_id: Option<&str>) -> ResultScript {
+        ResultScript {
+            id: id.into(),
+            name: None,
+            source: source.into(),
+            saved_query_id: saved_query_id.map(|s| s.into()),
+            enabled: true,
+        }
+    }
+
+    fn result(columns: &[&str], rows: Vec<Vec<Value>>) -> QueryResult {
+        QueryResult {
+            columns: columns.iter().map(|c| c.to_string()).collect(),
+            rows,
+            affected_rows: 0,
+            truncated: false,
+            truncation: None,
+            pagination: None,
+            style_hints: None,
+            provenance: None,
+        }
+    }
+
+    fn run(source: &str, result: &mut QueryResult) -> Result<(), String> {
+        let script = script("s1", source, None);
+        apply_result_scripts(result, &[&script])
+    }
+
+    #[test]
+    fn adds_derived_columns() {
+        let mut res = result(
+            &["price", "qty"],
+            vec![vec![json!(2.5), json!(4)], vec![json!(10), json!(1)]],
+        );
+        run(
+            "rows = rows.map(|r| { r.total = r.price * r.qty; r });",
+            &mut res,
+        )
+        .unwrap();
+        assert_eq!(res.columns, vec!["price", "qty", "total"]);
+        assert_eq!(res.rows[0][2], json!(10.0));
+        assert_eq!(res.rows[1][2], json!(10));
+    }
+
+    #[test]
+    fn redacts_values_in_place() {
+        let mut res = result(
+            &["id", "email"],
+            vec![
+                vec![json!(1), json!("ada@example.com")],
+                vec![json!(2), Value::Null],
+            ],
+        );
+        run(
+            r#"for i in 0..rows.len() { if rows[i].email != () { rows[i].email = "***"; } }"#,
+            &mut res,
+        )
+        .unwrap();
+        assert_eq!(res.rows[0], vec![json!(1), json!("***")]);
+        assert_eq!(res.rows[1], vec![json!(2), Value::Null]);
+    }
+
+    #[test]
+    fn reshapes_columns_and_rows() {
+        let mut res = result(
+            &["id", "secret", "name"],
+            vec![
+                vec![json!(1), json!("x"), json!("a")],
+                vec![json!(2), json!("y"), json!("b")],
+            ],
+        );
+        run(
+            r#"columns = ["name", "id"]; rows = rows.filter(|r| r.id > 1);"#,
+            &mut res,
+        )
+        .unwrap();
+        assert_eq!(res.columns, vec!["name", "id"]);
+        assert_eq!(res.rows, vec![vec![json!("b"), json!(2)]]);
+    }
+
+    #[test]
+    fn keeps_nested_json_values() {
+        let mut res = result(&["doc"], vec![vec![json!({"tags": ["a", "b"]})]]);
+        run("rows[0].count = rows[0].doc.tags.len();", &mut res).unwrap();
+        assert_eq!(res.columns, vec!["doc", "count"]);
+        assert_eq!(res.rows[0], vec![json!({"tags": ["a", "b"]}), json!(2)]);
+    }
+
+    #[test]
+    fn drops_style_hints() {
+        let mut res = result(&["id"], vec![vec![json!(1)]]);
+        res.style_hints = Some(vec![StyleHint {
+            row: 0,
+            column: None,
+            rule_id: "r1".into(),
+            color: None,
+            background: None,
+            badge: None,
+        }]);
+        run("let x = 1;", &mut res).unwrap();
+        assert!(res.style_hints.is_none());
+    }
+
+    #[test]
+    fn reports_script_errors() {
+        let mut res = result(&["id"], vec![vec![json!(1)]]);
+        let err = run("rows = 42;", &mut res).unwrap_err();
+        assert!(err.contains("s1"), "{}", err);
+        assert!(run("throw \"nope\";", &mut res).is_err());
+        assert!(validate_script("let = ;").is_err());
+        assert!(validate_script("rows.len()").is_ok());
+    }
+
+    #[test]
+    fn skips_results_without_columns() {
+        let mut res = result(&[], vec![]);
+        res.affected_rows = 3;
+        run("columns.push(\"extra\");", &mut res).unwrap();
+        assert!(res.columns.is_empty());
+    }
+
+    #[test]
+    fn runaway_scripts_are_stopped() {
+        let mut res = result(&["id"], vec![vec![json!(1)]]);
+        assert!(run("loop { }", &mut res).is_err());
+    }
+
+    #[test]
+    fn selects_scripts_for_the_query() {
+        let mut disabled = script("off", "", None);
+        disabled.enabled = false;
+        let scripts = vec![
+            script("all", "", None),
+            script("saved", "", Some("q1")),
+            script("other", "", Some("q2")),
+            disabled,
+        ];
+        let ids = |saved: Option<&str>| -> Vec<String> {
+            scripts_for(&scripts, saved)
+                .iter()
+                .map(|s| s.id.clone())
+                .collect()
+        };
+        assert_eq!(ids(None), vec!["all"]);
+        assert_eq!(ids(Some("q1")), vec!["all", "saved"]);
+    }
+}
@@ -0,0 +1,279 @@
+//! One-click sample databases for trying tabularis without data of one's own.
+//!
+//! Datasets are downloaded from their upstream projects rather than bundled,
+//! keeping the installer small. Each ships as a ready-made SQLite file and,
+//! where upstream provides one, as a SQL script per dialect that is run into
+//! an existing connection through the regular import path.
+
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use serde::Serialize;
+use tauri::{AppHandle, Runtime, State, WebviewWindow};
+
+use crate::commands::{
+    expand_ssh_connection_params, find_connection_by_id, register_abort_handle,
+    resolve_connection_params_with_id, unregister_abort_handle,
+};
+use crate::dump_commands::{
+    import_slot_key, run_sql_import, split_sql_statements, DumpCancellationState,
+};
+
+pub struct SampleDataset {
+    pub id: &'static str,
+    pub name: &'static str,
+    pub description: &'static str,
+    /// Ready-to-open SQLite database file.
+    pub sqlite_url: &'static str,
+    /// `(driver, url)` of SQL scripts that build the dataset in a connection.
+    pub scripts: &'static [(&'static str, &'static str)],
+}
+
+pub const SAMPLES: &[SampleDataset] = &[
+    SampleDataset {
+        id: "chinook",
+        name: "Chinook",
+        description: "Digital media store: artists, albums, tracks, customers and invoices.",
+        sqlite_url: "https://github.com/lerocha/chinook-database/releases/download/v1.4.5/Chinook_Sqlite.sqlite",
+        scripts: &[
+            (
+                "sqlite",
+                "https://github.com/lerocha/chinook-database/releases/download/v1.4.5/Chinook_Sqlite.sql",
+            ),
+            (
+                "mysql",
+                "https://github.com/lerocha/chinook-database/releases/download/v1.4.5/Chinook_MySql.sql",
+            ),
+            (
+                "postgres",
+                "https://github.com/lerocha/chinook-database/releases/download/v1.4.5/Chinook_PostgreSql.sql",
+            ),
+        ],
+    },
+    SampleDataset {
+        id: "northwind",
+        name: "Northwind",
+        description: "Trading company: products, suppliers, customers, orders and employees.",
+        sqlite_url: "https://raw.githubusercontent.com/jpwhite3/northwind-SQLite3/main/dist/northwind.db",
+        scripts: &[],
+    },
+];
+
+/// Every SQLite database file starts with this header.
+const SQLITE_MAGIC: &[u8] = b"SQLite format 3\0";
+
+#[derive(Debug, Serialize)]
+pub struct SampleDatasetInfo {
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    /// Drivers whose connections the dataset can be installed into.
+    pub drivers: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SampleInstallResult {
+    /// The SQLite file written, when no connection was given.
+    pub file_path: Option<String>,
+}
+
+pub fn find_sample(id: &str) -> Result<&'static SampleDataset, String> {
+    SAMPLES
+        .iter()
+        .find(|s| s.id == id)
+        .ok_or_else(|| format!("Unknown sample database: {}", id))
+}
+
+pub fn is_sqlite_file(bytes: &[u8]) -> bool {
+    bytes.starts_with(SQLITE_MAGIC)
+}
+
+/// Adapt an upstream script to run inside the target connection: statements
+/// that create, drop or switch databases, and psql meta-commands such as
+/// `\c`, are removed so the tables land in the connected database.
+pub fn prepare_script(sql: &str) -> Result<String, String> {
+    let sql = sql.trim_start_matches('\u{feff}');
+    let without_meta: String = sql
+        .lines()
+        .filter(|line| !line.trim_start().starts_with('\\'))
+        .map(|line| format!("{}\n", line))
+        .collect();
+
+    let statements = split_sql_statements(&without_meta)?;
+    Ok(statements
+        .into_iter()
+        .filter(|stmt| !switches_database(stmt))
+        .collect::<Vec<_>>()
+        .join("\n"))
+}
+
+fn switches_database(stmt: &str) -> bool {
+    let mut words = stmt.split_whitespace().map(|w| w.to_ascii_uppercase());
+    matches!(
+        (words.next().as_deref(), words.next().as_deref()),
+        (Some("USE"), _) | (Some("CREATE" | "DROP"), Some("DATABASE"))
+    )
+}
+
+#[tauri::command]
+pub fn list_sample_databases() -> Vec<SampleDatasetInfo> {
+    SAMPLES
+        .iter()
+        .map(|s| SampleDatasetInfo {
+            id: s.id.to_string(),
+            name: s.name.to_string(),
+            description: s.description.to_string(),
+            drivers: s.scripts.iter().map(|(d, _)| d.to_string()).collect(),
+        })
+        .collect()
+}
+
+/// Install a sample dataset. With `connection_id` the dataset's script for
+/// that connection's driver is imported into it (progress and cancellation
+/// go through `import_progress` / `cancel_import`). Without one, the SQLite
+/// file is saved to `file_path`, or to the app data directory, ready to be
+/// opened as a SQLite connection.
+#[tauri::command]
+pub async fn install_sample_database<R: Runtime>(
+    app: AppHandle<R>,
+    window: WebviewWindow<R>,
+    state: State<'_, DumpCancellationState>,
+    sample_id: String,
+    connection_id: Option<String>,
+    file_path: Option<String>,
+    schema: Option<String>,
+) -> Result<SampleInstallResult, String> {
+    let sample = find_sample(&sample_id)?;
+
+    let Some(connection_id) = connection_id else {
+        let path = install_sqlite_file(sample, file_path).await?;
+        return Ok(SampleInstallResult {
+            file_path: Some(path.to_string_lossy().to_string()),
+        });
+    };
+
+    let saved_conn = find_connection_by_id(&app, &connection_id)?;
+    crate::read_only::ensure_writable(&saved_conn.params)?;
+    let driver = saved_conn.params.driver.clone();
+    let (_, url) = sample
+        .scripts
+        .iter()
+        .find(|(d, _)| *d == driver)
+        .ok_or_else(|| {
+            format!(
+                "{} is not available for {} connections; install it as a SQLite file instead",
+                sample.name, driver
+            )
+        })?;
+    let expanded_params = expand_ssh_connection_params(&app, &saved_conn.params).await?;
+    let params = resolve_connection_params_with_id(&expanded_params, &connection_id)?;
+    let pg_schema = schema.unwrap_or_else(|| "public".to_string());
+    let app_handle = app.clone();
+
+    let task = tokio::spawn(async move {
+        let bytes = download(url).await?;
+        let script = prepare_script(&String::from_utf8_lossy(&bytes))?;
+        let total_bytes = script.len() as u64;
+        let reader = Box::new(std::io::Cursor::new(script.into_bytes()));
+        run_sql_import(
+            &app_handle,
+            &driver,
+            &params,
+            &pg_schema,
+            reader,
+            Some(total_bytes),
+        )
+        .await
+    });
+
+    let abort_handle = Arc::new(task.abort_handle());
+    let import_key = import_slot_key(&crate::window_state::cancellation_key(
+        window.label(),
+        &connection_id,
+    ));
+    register_abort_handle(&state.handles, import_key.clone(), abort_handle.clone());
+
+    let result = task.await;
+
+    unregister_abort_handle(&state.handles, &import_key, &abort_handle);
+
+    match result {
+        Ok(res) => res.map(|_| SampleInstallResult { file_path: None }),
+        Err(_) => Err(crate::i18n::t("errors.importCancelled")),
+    }
+}
+
+/// Download the dataset's SQLite file. The default location is reused when
+/// the sample was installed before; an explicit path is never overwritten.
+async fn install_sqlite_file(
+    sample: &SampleDataset,
+    file_path: Option<String>,
+) -> Result<PathBuf, String> {
+    let path = match file_path {
+        Some(path) => {
+            let path = PathBuf::from(path);
+            if path.exists() {
+                return Err(format!("{} already exists", path.display()));
+            }
+            path
+        }
+        None => {
+            let path = crate::paths::get_app_data_dir()
+                .ok_or("Could not resolve the app data directory")?
+                .join("samples")
+                .join(format!("{}.sqlite", sample.id));
+            if path.exists() {
+                return Ok(path);
+            }
+            path
+        }
+    };
+
+    let bytes = download(sample.sqlite_url).await?;
+    if !is_sqlite_file(&bytes) {
+        return Err(format!(
+            "The download for {} is not a SQLite database",
+            sample.name
+        ));
+    }
+
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+    }
+    // Write next to the target first so an interrupted install never
+    // leaves a truncated database behind.
+    let partial = path.with_extension("part");
+    std::fs::write(&partial, &bytes)
+        .map_err(|e| format!("Failed to write {}: {}", partial.display(), e))?;
+    std::fs::rename(&partial, &path)
+        .map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+
+    log::info!(
+        "Installed sample database '{}' at {}",
+        sample.id,
+        path.display()
+    );
+    Ok(path)
+}
+
+async fn download(url: &str) -> Result<Vec<u8>, String> {
+    log::info!("Downloading sample database from: {}", url);
+    let response = reqwest::get(url)
+        .await
+        .map_err(|e| format!("Failed to download sample database: {}", e))?;
+
+    let status = response.status();
+    if !status.is_success() {
+        return Err(format!(
+            "Failed to download sample database: server returned HTTP {} for URL: {}",
+            status, url
+        ));
+    }
+
+    let bytes = response
+        .bytes()
+        .await
+        .map_err(|e| format!("Failed to read sample database download: {}", e))?;
+    Ok(bytes.to_vec())
+}
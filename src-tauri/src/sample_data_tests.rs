@@ -0,0 +1,36 @@
+#[cfg(test)]
+mod tests {
+    use crate::sample_data::{find_sample, is_sqlite_file, prepare_script, SAMPLES};
+
+    #[test]
+    fn prepare_script_drops_database_statements() {
+        let sql = "\u{feff}DROP DATABASE IF EXISTS chinook;\n\
+                   CREATE DATABASE chinook;\n\
+                   \\c chinook;\n\
+                   USE chinook;\n\
+                   CREATE TABLE artist\n(\n    artist_id INT NOT NULL\n);\n\
+                   -- seed\n\
+                   INSERT INTO artist (artist_id) VALUES (1);\n";
+
+        let script = prepare_script(sql).unwrap();
+
+        assert_eq!(
+            script,
+            "CREATE TABLE artist\n(\n    artist_id INT NOT NULL\n);\nINSERT INTO artist (artist_id) VALUES (1);"
+        );
+    }
+
+    #[test]
+    fn samples_have_unique_ids() {
+        for sample in SAMPLES {
+            assert_eq!(find_sample(sample.id).unwrap().name, sample.name);
+        }
+        assert!(find_sample("sakila").is_err());
+    }
+
+    #[test]
+    fn sqlite_header_is_checked() {
+        assert!(is_sqlite_file(b"SQLite format 3\0\x10\x00"));
+        assert!(!is_sqlite_file(b"<!DOCTYPE html>"));
+    }
+}
@@ -0,0 +1,805 @@
+//! Per-connection sandboxes for destructive experiments.
+//!
+//! A sandbox is a copy of one table's structure (plus, optionally, a sample of
+//! its rows) in a scratch location next to the source:
+//!   - PostgreSQL: a `tabularis_sandbox_<id>` schema in the same database
+//!   - MySQL: a `tabularis_sandbox_<id>` database on the same server
+//!   - SQLite: a separate file under `sandboxes/` in the app config directory
+//!
+//! The sandbox can be diffed against its source (columns and row counts) and
+//! promoted, which makes the source rows match the sandbox rows by primary
+//! key inside one transaction. Structural changes are reported by the diff
+//! but never applied.
+//!
+//! Storage layout under the application config directory:
+//!   - `sandboxes.json`         — every sandbox, across connections
+//!   - `sandboxes/<id>.sqlite`  — SQLite sandbox files
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Runtime};
+
+use crate::commands::{
+    driver_for, expand_ssh_connection_params, find_connection_by_id,
+    resolve_connection_params_with_id,
+};
+use crate::models::{ConnectionParams, DatabaseSelection, TableColumn};
+use crate::pool_manager::{get_mysql_pool, get_postgres_pool, get_sqlite_pool};
+
+const SANDBOXES_FILE: &str = "sandboxes.json";
+const SANDBOXES_DIR: &str = "sandboxes";
+const SANDBOX_PREFIX: &str = "tabularis_sandbox_";
+/// Alias of the sandbox file while it is attached to a SQLite connection.
+const SQLITE_ALIAS: &str = "tabularis_sandbox";
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Sandbox {
+    pub id: String,
+    pub connection_id: String,
+    pub driver: String,
+    /// Schema (PostgreSQL) or database (MySQL) of the source table.
+    pub source_schema: Option<String>,
+    pub table: String,
+    /// Sandbox schema / database name, or the sandbox file path for SQLite.
+    pub location: String,
+    pub sample_rows: u32,
+    pub created_at: String,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ColumnChangeKind {
+    Added,
+    Removed,
+    TypeChanged,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ColumnChange {
+    pub column: String,
+    pub kind: ColumnChangeKind,
+    pub source_type: Option<String>,
+    pub sandbox_type: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SandboxDiff {
+    pub columns: Vec<ColumnChange>,
+    pub source_rows: i64,
+    pub sandbox_rows: i64,
+}
+
+// ---------------------------------------------------------------------------
+// SQL building (testable; no I/O)
+// ---------------------------------------------------------------------------
+
+pub fn sandbox_location_name(id: &str) -> String {
+    let short: String = id.chars().filter(|c| *c != '-').take(8).collect();
+    format!("{}{}", SANDBOX_PREFIX, short)
+}
+
+pub fn quote_ident(driver: &str, name: &str) -> String {
+    let quote = if driver == "mysql" { "`" } else { "\"" };
+    crate::drivers::common::quote_identifier(quote, name)
+}
+
+pub fn qualified(driver: &str, schema: Option<&str>, table: &str) -> String {
+    match schema {
+        Some(schema) => format!(
+            "{}.{}",
+            quote_ident(driver, schema),
+            quote_ident(driver, table)
+        ),
+        None => quote_ident(driver, table),
+    }
+}
+
+fn copy_rows_sql(driver: &str, target: &str, source: &str, sample_rows: u32) -> String {
+    let overriding = if driver == "postgres" {
+        " OVERRIDING SYSTEM VALUE"
+    } else {
+        ""
+    };
+    format!(
+        "INSERT INTO {}{} SELECT * FROM {} LIMIT {}",
+        target, overriding, source, sample_rows
+    )
+}
+
+/// Statements creating a PostgreSQL / MySQL sandbox of `table`.
+pub fn create_statements(
+    driver: &str,
+    source_schema: &str,
+    table: &str,
+    location: &str,
+    sample_rows: u32,
+) -> Result<Vec<String>, String> {
+    let source = qualified(driver, Some(source_schema), table);
+    let target = qualified(driver, Some(location), table);
+    let mut statements = match driver {
+        "postgres" => vec![
+            format!(
+                "CREATE SCHEMA IF NOT EXISTS {}",
+                quote_ident(driver, location)
+            ),
+            format!("CREATE TABLE {} (LIKE {} INCLUDING ALL)", target, source),
+        ],
+        "mysql" => vec![
+            format!(
+                "CREATE DATABASE IF NOT EXISTS {}",
+                quote_ident(driver, location)
+            ),
+            format!("CREATE TABLE {} LIKE {}", target, source),
+        ],
+        other => return Err(format!("Sandboxes are not supported for {}", other)),
+    };
+    if sample_rows > 0 {
+        statements.push(copy_rows_sql(driver, &target, &source, sample_rows));
+    }
+    Ok(statements)
+}
+
+/// Strip a leading keyword (case-insensitive, followed by a word boundary).
+fn skip_keyword<'a>(s: &'a str, keyword: &str) -> Option<&'a str> {
+    let s = s.trim_start();
+    let head = s.get(..keyword.len())?;
+    let rest = &s[keyword.len()..];
+    let boundary = rest
+        .chars()
+        .next()
+        .map_or(true, |c| !(c.is_alphanumeric() || c == '_'));
+    (head.eq_ignore_ascii_case(keyword) && boundary).then_some(rest)
+}
+
+/// Split a possibly quoted identifier off the start of `s`.
+fn skip_identifier(s: &str) -> Option<&str> {
+    let s = s.trim_start();
+    let close = match s.chars().next()? {
+        '"' => '"',
+        '`' => '`',
+        '[' => ']',
+        _ => {
+            let end = s
+                .find(|c: char| c.is_whitespace() || c == '(' || c == '.')
+                .unwrap_or(s.len());
+            return (end > 0).then(|| &s[end..]);
+        }
+    };
+    let mut chars = s.char_indices().skip(1).peekable();
+    while let Some((i, c)) = chars.next() {
+        if c == close {
+            // A doubled quote is an escaped quote inside the name.
+            if close != ']' && chars.peek().map(|(_, n)| *n) == Some(close) {
+                chars.next();
+                continue;
+            }
+            return Some(&s[i + c.len_utf8()..]);
+        }
+    }
+    None
+}
+
+/// Rewrite a SQLite `CREATE TABLE` statement so it creates `table` inside the
+/// attached database `schema`, keeping columns and constraints.
+pub fn sqlite_ddl_into(ddl: &str, schema: &str, table: &str) -> Result<String, String> {
+    let invalid = || format!("Unsupported table definition: {}", ddl);
+    let rest = skip_keyword(ddl, "CREATE").ok_or_else(invalid)?;
+    let rest = skip_keyword(rest, "TABLE").ok_or_else(invalid)?;
+    let rest = skip_keyword(rest, "IF")
+        .and_then(|r| skip_keyword(r, "NOT"))
+        .and_then(|r| skip_keyword(r, "EXISTS"))
+        .unwrap_or(rest);
+    let mut rest = skip_identifier(rest).ok_or_else(invalid)?;
+    if let Some(after_dot) = rest.trim_start().strip_prefix('.') {
+        rest = skip_identifier(after_dot).ok_or_else(invalid)?;
+    }
+    Ok(format!(
+        "CREATE TABLE {} {}",
+        qualified("sqlite", Some(schema), table),
+        rest.trim_start()
+    ))
+}
+
+/// Quote each of `columns`, pass it through `item` and join the results.
+fn each_column(
+    driver: &str,
+    columns: &[String],
+    sep: &str,
+    item: impl Fn(&str) -> String,
+) -> String {
+    columns
+        .iter()
+        .map(|c| item(&quote_ident(driver, c)))
+        .collect::<Vec<_>>()
+        .join(sep)
+}
+
+/// Statements making the source rows match the sandbox rows by primary key
+/// (`keys`), limited to the columns both tables share: source rows whose key
+/// is missing from the sandbox are deleted, then the sandbox rows are
+/// upserted, updating only the rows that changed.
+pub fn promote_statements(
+    driver: &str,
+    source: &str,
+    sandbox: &str,
+    columns: &[String],
+    keys: &[String],
+) -> Vec<String> {
+    let others: Vec<String> = columns
+        .iter()
+        .filter(|c| !keys.contains(c))
+        .cloned()
+        .collect();
+    let cols = each_column(driver, columns, ", ", str::to_string);
+    let same_key = each_column(driver, keys, " AND ", |k| {
+        format!("s.{} = {}.{}", k, source, k)
+    });
+
+    let delete = format!(
+        "DELETE FROM {} WHERE NOT EXISTS (SELECT 1 FROM {} AS s WHERE {})",
+        source, sandbox, same_key
+    );
+    let upsert = if driver == "mysql" {
+        // Without other columns, a no-op assignment keeps the existing rows.
+        let assigned: &[String] = if others.is_empty() { keys } else { &others };
+        format!(
+            "INSERT INTO {} ({}) SELECT {} FROM {} AS s ON DUPLICATE KEY UPDATE {}",
+            source,
+            cols,
+            cols,
+            sandbox,
+            each_column(driver, assigned, ", ", |c| format!("{} = s.{}", c, c))
+        )
+    } else {
+        let (overriding, distinct) = if driver == "postgres" {
+            (" OVERRIDING SYSTEM VALUE", "IS DISTINCT FROM")
+        } else {
+            ("", "IS NOT")
+        };
+        let action = if others.is_empty() {
+            "DO NOTHING".to_string()
+        } else {
+            format!(
+                "DO UPDATE SET {} WHERE {}",
+                each_column(driver, &others, ", ", |c| format!("{} = excluded.{}", c, c)),
+                each_column(driver, &others, " OR ", |c| {
+                    format!("t.{} {} excluded.{}", c, distinct, c)
+                })
+            )
+        };
+        // SQLite only parses ON CONFLICT after a SELECT with a WHERE clause.
+        format!(
+            "INSERT INTO {} AS t ({}){} SELECT {} FROM {} WHERE true ON CONFLICT ({}) {}",
+            source,
+            cols,
+            overriding,
+            cols,
+            sandbox,
+            each_column(driver, keys, ", ", str::to_string),
+            action
+        )
+    };
+    vec![delete, upsert]
+}
+
+pub fn diff_columns(source: &[TableColumn], sandbox: &[TableColumn]) -> Vec<ColumnChange> {
+    let mut changes = Vec::new();
+    for col in sandbox {
+        match source.iter().find(|s| s.name == col.name) {
+            None => changes.push(ColumnChange {
+                column: col.name.clone(),
+                kind: ColumnChangeKind::Added,
+                source_type: None,
+                sandbox_type: Some(col.data_type.clone()),
+            }),
+            Some(src) if !src.data_type.eq_ignore_ascii_case(&col.data_type) => {
+                changes.push(ColumnChange {
+                    column: col.name.clone(),
+                    kind: ColumnChangeKind::TypeChanged,
+                    source_type: Some(src.data_type.clone()),
+                    sandbox_type: Some(col.data_type.clone()),
+                })
+            }
+            Some(_) => {}
+        }
+    }
+    for col in source {
+        if !sandbox.iter().any(|s| s.name == col.name) {
+            changes.push(ColumnChange {
+                column: col.name.clone(),
+                kind: ColumnChangeKind::Removed,
+                source_type: Some(col.data_type.clone()),
+                sandbox_type: None,
+            });
+        }
+    }
+    changes
+}
+
+// ---------------------------------------------------------------------------
+// Registry (testable; take a directory)
+// ---------------------------------------------------------------------------
+
+pub fn read_sandboxes_in(dir: &Path) -> Vec<Sandbox> {
+    fs::read_to_string(dir.join(SANDBOXES_FILE))
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+pub fn write_sandboxes_in(dir: &Path, sandboxes: &[Sandbox]) -> Result<(), String> {
+    fs::create_dir_all(dir).map_err(|e| format!("Failed to create config dir: {}", e))?;
+    let json = serde_json::to_string_pretty(sandboxes)
+        .map_err(|e| format!("Failed to serialize sandboxes: {}", e))?;
+    fs::write(dir.join(SANDBOXES_FILE), json)
+        .map_err(|e| format!("Failed to save sandboxes: {}", e))
+}
+
+pub fn find_sandbox_in(dir: &Path, id: &str) -> Result<Sandbox, String> {
+    read_sandboxes_in(dir)
+        .into_iter()
+        .find(|s| s.id == id)
+        .ok_or_else(|| format!("Sandbox not found: {}", id))
+}
+
+// ---------------------------------------------------------------------------
+// Execution
+// ---------------------------------------------------------------------------
+
+/// Run `statements` on one connection inside a transaction. For SQLite the
+/// sandbox file is attached as `tabularis_sandbox` around the transaction.
+async fn run_statements(
+    params: &ConnectionParams,
+    sqlite_sandbox: Option<&str>,
+    statements: &[String],
+) -> Result<(), String> {
+    match params.driver.as_str() {
+        "postgres" => {
+            let pool = get_postgres_pool(params).await?;
+            let mut client = pool.get().await.map_err(|e| e.to_string())?;
+            let tx = client.transaction().await.map_err(|e| e.to_string())?;
+            for stmt in statements {
+                tx.batch_execute(stmt)
+                    .await
+                    .map_err(|e| format!("{}\nQuery: {}", e, stmt))?;
+            }
+            tx.commit().await.map_err(|e| e.to_string())
+        }
+        "mysql" => {
+            let pool = get_mysql_pool(params).await?;
+            let mut tx = pool.begin().await.map_err(|e| e.to_string())?;
+            for stmt in statements {
+                sqlx::raw_sql(stmt)
+                    .execute(&mut *tx)
+                    .await
+                    .map_err(|e| format!("{}\nQuery: {}", e, stmt))?;
+            }
+            tx.commit().await.map_err(|e| e.to_string())
+        }
+        "sqlite" => {
+            let path = sqlite_sandbox.ok_or("Missing SQLite sandbox file")?;
+            let pool = get_sqlite_pool(params).await?;
+            let mut conn = pool.acquire().await.map_err(|e| e.to_string())?;
+            sqlx::query(&format!("ATTACH DATABASE ? AS {}", SQLITE_ALIAS))
+                .bind(path)
+                .execute(&mut *conn)
+                .await
+                .map_err(|e| format!("Failed to attach sandbox: {}", e))?;
+
+            let mut result = sqlx::raw_sql("BEGIN")
+                .execute(&mut *conn)
+                .await
+                .map(|_| ())
+                .map_err(|e| e.to_string());
+            for stmt in statements {
+                if result.is_err() {
+                    break;
+                }
+                result = sqlx::raw_sql(stmt)
+                    .execute(&mut *conn)
+                    .await
+                    .map(|_| ())
+                    .map_err(|e| format!("{}\nQuery: {}", e, stmt));
+            }
+            let end = if result.is_ok() { "COMMIT" } else { "ROLLBACK" };
+            let ended = sqlx::raw_sql(end).execute(&mut *conn).await;
+            let _ = sqlx::raw_sql(&format!("DETACH DATABASE {}", SQLITE_ALIAS))
+                .execute(&mut *conn)
+                .await;
+            result?;
+            ended.map(|_| ()).map_err(|e| e.to_string())
+        }
+        other => Err(format!("Sandboxes are not supported for {}", other)),
+    }
+}
+
+async fn sqlite_table_ddl(params: &ConnectionParams, table: &str) -> Result<String, String> {
+    let pool = get_sqlite_pool(params).await?;
+    sqlx::query_scalar::<_, String>("SELECT sql FROM sqlite_master WHERE type='table' AND name = ?")
+        .bind(table)
+        .fetch_optional(&pool)
+        .await
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("Table not found: {}", table))
+}
+
+async fn count_rows(params: &ConnectionParams, table_ref: &str) -> Result<i64, String> {
+    let drv = driver_for(&params.driver).await?;
+    let result = drv
+        .execute_query(
+            params,
+            &format!("SELECT COUNT(*) FROM {}", table_ref),
+            None,
+            1,
+            None,
+        )
+        .await?;
+    let value = result
+        .rows
+        .first()
+        .and_then(|row| row.first())
+        .ok_or("COUNT returned no rows")?;
+    value
+        .as_i64()
+        .or_else(|| value.as_str().and_then(|s| s.parse().ok()))
+        .ok_or_else(|| format!("Unexpected COUNT result: {}", value))
+}
+
+/// Foreign keys referencing the source table, and triggers on it. Promotion
+/// deletes and rewrites source rows, which would cascade into the
+/// referencing rows or fire the triggers.
+async fn count_dependents(
+    params: &ConnectionParams,
+    sandbox: &Sandbox,
+) -> Result<(i64, i64), String> {
+    let table = sandbox.table.as_str();
+    match sandbox.driver.as_str() {
+        "postgres" => {
+            let (source_ref, _) = table_refs(sandbox);
+            let pool = get_postgres_pool(params).await?;
+            let client = pool.get().await.map_err(|e| e.to_string())?;
+            let row = client
+                .query_one(
+                    "SELECT \
+                       (SELECT COUNT(*) FROM pg_constraint \
+                        WHERE contype = 'f' AND confrelid = $1::text::regclass), \
+                       (SELECT COUNT(*) FROM pg_trigger \
+                        WHERE tgrelid = $1::text::regclass AND NOT tgisinternal)",
+                    &[&source_ref],
+                )
+                .await
+                .map_err(|e| e.to_string())?;
+            Ok((row.get(0), row.get(1)))
+        }
+        "mysql" => {
+            let schema = sandbox.source_schema.as_deref().unwrap_or_default();
+            let pool = get_mysql_pool(params).await?;
+            sqlx::query_as::<_, (i64, i64)>(
+                "SELECT \
+                   (SELECT COUNT(*) FROM information_schema.KEY_COLUMN_USAGE \
+                    WHERE REFERENCED_TABLE_SCHEMA = ? AND REFERENCED_TABLE_NAME = ?), \
+                   (SELECT COUNT(*) FROM information_schema.TRIGGERS \
+                    WHERE EVENT_OBJECT_SCHEMA = ? AND EVENT_OBJECT_TABLE = ?)",
+            )
+            .bind(schema)
+            .bind(table)
+            .bind(schema)
+            .bind(table)
+            .fetch_one(&pool)
+            .await
+            .map_err(|e| e.to_string())
+        }
+        "sqlite" => {
+            let pool = get_sqlite_pool(params).await?;
+            sqlx::query_as::<_, (i64, i64)>(
+                "SELECT \
+                   (SELECT COUNT(*) FROM sqlite_master m, pragma_foreign_key_list(m.name) f \
+                    WHERE m.type = 'table' AND f.\"table\" = ? COLLATE NOCASE), \
+                   (SELECT COUNT(*) FROM sqlite_master \
+                    WHERE type = 'trigger' AND tbl_name = ? COLLATE NOCASE)",
+            )
+            .bind(table)
+            .bind(table)
+            .fetch_one(&pool)
+            .await
+            .map_err(|e| e.to_string())
+        }
+        other => Err(format!("Sandboxes are not supported for {}", other)),
+    }
+}
+
+/// Connection parameters that read the SQLite sandbox file directly.
+fn sqlite_sandbox_params(params: &ConnectionParams, path: &str) -> ConnectionParams {
+    let mut sandbox = params.clone();
+    sandbox.database = DatabaseSelection::Single(path.to_string());
+    sandbox
+}
+
+/// Source and sandbox table references, as seen from the source connection
+/// (for SQLite, the sandbox reference is valid while the file is attached).
+fn table_refs(sandbox: &Sandbox) -> (String, String) {
+    let driver = sandbox.driver.as_str();
+    match driver {
+        "sqlite" => (
+            qualified(driver, Some("main"), &sandbox.table),
+            qualified(driver, Some(SQLITE_ALIAS), &sandbox.table),
+        ),
+        _ => (
+            qualified(driver, sandbox.source_schema.as_deref(), &sandbox.table),
+            qualified(driver, Some(&sandbox.location), &sandbox.table),
+        ),
+    }
+}
+
+async fn resolve_params<R: Runtime>(
+    app: &AppHandle<R>,
+    connection_id: &str,
+) -> Result<ConnectionParams, String> {
+    let saved_conn = find_connection_by_id(app, connection_id)?;
+    let expanded = expand_ssh_connection_params(app, &saved_conn.params).await?;
+    resolve_connection_params_with_id(&expanded, connection_id)
+}
+
+fn config_dir<R: Runtime>(app: &AppHandle<R>) -> Result<PathBuf, String> {
+    crate::paths::app_config_dir(app)
+}
+
+// ---------------------------------------------------------------------------
+// Tauri commands
+// ---------------------------------------------------------------------------
+
+/// Sandboxes of a connection (or of every connection).
+#[tauri::command]
+pub fn list_sandboxes<R: Runtime>(
+    app: AppHandle<R>,
+    connection_id: Option<String>,
+) -> Result<Vec<Sandbox>, String> {
+    Ok(read_sandboxes_in(&config_dir(&app)?)
+        .into_iter()
+        .filter(|s| {
+            connection_id
+                .as_ref()
+                .map_or(true, |id| &s.connection_id == id)
+        })
+        .collect())
+}
+
+/// Clone `table`'s structure, plus up to `sample_rows` rows, into a new sandbox.
+#[tauri::command]
+pub async fn create_sandbox<R: Runtime>(
+    app: AppHandle<R>,
+    connection_id: String,
+    table: String,
+    schema: Option<String>,
+    sample_rows: Option<u32>,
+) -> Result<Sandbox, String> {
+    let params = resolve_params(&app, &connection_id).await?;
+    crate::read_only::ensure_writable(&params)?;
+    let dir = config_dir(&app)?;
+    let id = uuid::Uuid::new_v4().to_string();
+    let sample_rows = sample_rows.unwrap_or(0);
+    let driver = params.driver.clone();
+
+    let (source_schema, location) = match driver.as_str() {
+        "sqlite" => {
+            let sandbox_dir = dir.join(SANDBOXES_DIR);
+            fs::create_dir_all(&sandbox_dir)
+                .map_err(|e| format!("Failed to create sandbox dir: {}", e))?;
+            let path = sandbox_dir.join(format!("{}.sqlite", id));
+            (None, path.to_string_lossy().to_string())
+        }
+        "postgres" => (
+            Some(schema.unwrap_or_else(|| "public".to_string())),
+            sandbox_location_name(&id),
+        ),
+        _ => (
+            Some(schema.unwrap_or_else(|| params.database.primary().to_string())),
+            sandbox_location_name(&id),
+        ),
+    };
+
+    let sandbox = Sandbox {
+        id,
+        connection_id,
+        driver: driver.clone(),
+        source_schema,
+        table,
+        location,
+        sample_rows,
+        created_at: chrono::Utc::now().to_rfc3339(),
+    };
+
+    if driver == "sqlite" {
+        let ddl = sqlite_table_ddl(&params, &sandbox.table).await?;
+        let (source, target) = table_refs(&sandbox);
+        let mut statements = vec![sqlite_ddl_into(&ddl, SQLITE_ALIAS, &sandbox.table)?];
+        if sample_rows > 0 {
+            statements.push(copy_rows_sql(&driver, &target, &source, sample_rows));
+        }
+        if let Err(e) = run_statements(&params, Some(&sandbox.location), &statements).await {
+            let _ = fs::remove_file(&sandbox.location);
+            return Err(e);
+        }
+    } else {
+        let statements = create_statements(
+            &driver,
+            sandbox.source_schema.as_deref().unwrap_or_default(),
+            &sandbox.table,
+            &sandbox.location,
+            sample_rows,
+        )?;
+        run_statements(&params, None, &statements).await?;
+    }
+
+    let mut sandboxes = read_sandboxes_in(&dir);
+    sandboxes.push(sandbox.clone());
+    write_sandboxes_in(&dir, &sandboxes)?;
+    log::info!(
+        "Created sandbox {} for table {}",
+        sandbox.location,
+        sandbox.table
+    );
+    Ok(sandbox)
+}
+
+/// Columns of the source table and of the sandbox table.
+async fn load_columns(
+    params: &ConnectionParams,
+    sandbox: &Sandbox,
+) -> Result<(Vec<TableColumn>, Vec<TableColumn>), String> {
+    let drv = driver_for(&params.driver).await?;
+    if sandbox.driver == "sqlite" {
+        let sandbox_params = sqlite_sandbox_params(params, &sandbox.location);
+        Ok((
+            drv.get_columns(params, &sandbox.table, None).await?,
+            drv.get_columns(&sandbox_params, &sandbox.table, None)
+                .await?,
+        ))
+    } else {
+        Ok((
+            drv.get_columns(params, &sandbox.table, sandbox.source_schema.as_deref())
+                .await?,
+            drv.get_columns(params, &sandbox.table, Some(&sandbox.location))
+                .await?,
+        ))
+    }
+}
+
+/// Column changes and row counts between the sandbox and its source table.
+#[tauri::command]
+pub async fn diff_sandbox<R: Runtime>(
+    app: AppHandle<R>,
+    id: String,
+) -> Result<SandboxDiff, String> {
+    let sandbox = find_sandbox_in(&config_dir(&app)?, &id)?;
+    let params = resolve_params(&app, &sandbox.connection_id).await?;
+    let (source_columns, sandbox_columns) = load_columns(&params, &sandbox).await?;
+
+    let (source_rows, sandbox_rows) = if sandbox.driver == "sqlite" {
+        let table = quote_ident("sqlite", &sandbox.table);
+        let sandbox_params = sqlite_sandbox_params(&params, &sandbox.location);
+        (
+            count_rows(&params, &table).await?,
+            count_rows(&sandbox_params, &table).await?,
+        )
+    } else {
+        let (source_ref, sandbox_ref) = table_refs(&sandbox);
+        (
+            count_rows(&params, &source_ref).await?,
+            count_rows(&params, &sandbox_ref).await?,
+        )
+    };
+
+    Ok(SandboxDiff {
+        columns: diff_columns(&source_columns, &sandbox_columns),
+        source_rows,
+        sandbox_rows,
+    })
+}
+
+/// Make the source table's rows match the sandbox rows by primary key
+/// (shared columns only), in a single transaction. Refused when other tables
+/// reference the source table or it has triggers.
+pub async fn promote(params: &ConnectionParams, sandbox: &Sandbox) -> Result<(), String> {
+    let (source_columns, sandbox_columns) = load_columns(params, sandbox).await?;
+
+    let shared: Vec<String> = sandbox_columns
+        .iter()
+        .filter(|c| source_columns.iter().any(|s| s.name == c.name))
+        .map(|c| c.name.clone())
+        .collect();
+    if shared.is_empty() {
+        return Err("Sandbox and source table have no columns in common".into());
+    }
+    let keys: Vec<String> = source_columns
+        .iter()
+        .filter(|c| c.is_pk)
+        .map(|c| c.name.clone())
+        .collect();
+    if keys.is_empty() {
+        return Err(format!(
+            "Table {} has no primary key to promote by",
+            sandbox.table
+        ));
+    }
+    if !keys.iter().all(|k| shared.contains(k)) {
+        return Err("The sandbox is missing primary key columns of the source table".into());
+    }
+
+    let (foreign_keys, triggers) = count_dependents(params, sandbox).await?;
+    if foreign_keys > 0 {
+        return Err(format!(
+            "Cannot promote into {}: other tables reference it with foreign keys",
+            sandbox.table
+        ));
+    }
+    if triggers > 0 {
+        return Err(format!(
+            "Cannot promote into {}: the table has triggers",
+            sandbox.table
+        ));
+    }
+
+    let (source_ref, sandbox_ref) = table_refs(sandbox);
+    let statements = promote_statements(&sandbox.driver, &source_ref, &sandbox_ref, &shared, &keys);
+    let sqlite_file = (sandbox.driver == "sqlite").then_some(sandbox.location.as_str());
+    run_statements(params, sqlite_file, &statements).await?;
+    log::info!(
+        "Promoted sandbox {} into table {}",
+        sandbox.location,
+        sandbox.table
+    );
+    Ok(())
+}
+
+/// Promote a sandbox into its source table; see [`promote`].
+#[tauri::command]
+pub async fn promote_sandbox<R: Runtime>(app: AppHandle<R>, id: String) -> Result<(), String> {
+    let sandbox = find_sandbox_in(&config_dir(&app)?, &id)?;
+    let params = resolve_params(&app, &sandbox.connection_id).await?;
+    crate::read_only::ensure_writable(&params)?;
+    promote(&params, &sandbox).await
+}
+
+/// Drop the sandbox schema / database / file and forget the sandbox.
+#[tauri::command]
+pub async fn drop_sandbox<R: Runtime>(app: AppHandle<R>, id: String) -> Result<(), String> {
+    let dir = config_dir(&app)?;
+    let sandbox = find_sandbox_in(&dir, &id)?;
+
+    match sandbox.driver.as_str() {
+        "sqlite" => {
+            let params = resolve_params(&app, &sandbox.connection_id).await?;
+            crate::pool_manager::close_pool(&sqlite_sandbox_params(&params, &sandbox.location))
+                .await;
+            if Path::new(&sandbox.location).exists() {
+                fs::remove_file(&sandbox.location)
+                    .map_err(|e| format!("Failed to delete sandbox file: {}", e))?;
+            }
+        }
+        driver => {
+            let params = resolve_params(&app, &sandbox.connection_id).await?;
+            let statement = match driver {
+                "postgres" => format!(
+                    "DROP SCHEMA IF EXISTS {} CASCADE",
+                    quote_ident(driver, &sandbox.location)
+                ),
+                _ => format!(
+                    "DROP DATABASE IF EXISTS {}",
+                    quote_ident(driver, &sandbox.location)
+                ),
+            };
+            run_statements(&params, None, &[statement]).await?;
+        }
+    }
+
+    let remaining: Vec<Sandbox> = read_sandboxes_in(&dir)
+        .into_iter()
+        .filter(|s| s.id != id)
+        .collect();
+    write_sandboxes_in(&dir, &remaining)
+}
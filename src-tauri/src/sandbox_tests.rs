@@ -0,0 +1,274 @@
+#[cfg(test)]
+mod tests {
+    use crate::models::{ConnectionParams, DatabaseSelection, TableColumn};
+    use crate::sandbox::{
+        create_statements, diff_columns, find_sandbox_in, promote, promote_statements, qualified,
+        read_sandboxes_in, sandbox_location_name, sqlite_ddl_into, write_sandboxes_in,
+        ColumnChangeKind, Sandbox,
+    };
+    use tempfile::TempDir;
+
+    fn column(name: &str, data_type: &str) -> TableColumn {
+        TableColumn {
+            name: name.to_string(),
+            data_type: data_type.to_string(),
+            is_pk: false,
+            is_nullable: true,
+            is_auto_increment: false,
+            default_value: None,
+            character_maximum_length: None,
+        }
+    }
+
+    // --- SQL building ---
+
+    #[test]
+    fn location_name_uses_short_id() {
+        assert_eq!(
+            sandbox_location_name("1b4e28ba-2fa1-11d2-883f-0016d3cca427"),
+            "tabularis_sandbox_1b4e28ba"
+        );
+    }
+
+    #[test]
+    fn qualified_quotes_per_driver() {
+        assert_eq!(qualified("mysql", Some("db"), "t`x"), "`db`.`t``x`");
+        assert_eq!(
+            qualified("postgres", Some("public"), "a\"b"),
+            r#""public"."a""b""#
+        );
+        assert_eq!(qualified("sqlite", None, "t"), r#""t""#);
+    }
+
+    #[test]
+    fn postgres_create_statements_with_sample() {
+        let stmts = create_statements("postgres", "public", "users", "sb", 100).unwrap();
+        assert_eq!(
+            stmts,
+            vec![
+                r#"CREATE SCHEMA IF NOT EXISTS "sb""#,
+                r#"CREATE TABLE "sb"."users" (LIKE "public"."users" INCLUDING ALL)"#,
+                r#"INSERT INTO "sb"."users" OVERRIDING SYSTEM VALUE SELECT * FROM "public"."users" LIMIT 100"#,
+            ]
+        );
+    }
+
+    #[test]
+    fn mysql_create_statements_structure_only() {
+        let stmts = create_statements("mysql", "shop", "orders", "sb", 0).unwrap();
+        assert_eq!(
+            stmts,
+            vec![
+                "CREATE DATABASE IF NOT EXISTS `sb`",
+                "CREATE TABLE `sb`.`orders` LIKE `shop`.`orders`",
+            ]
+        );
+    }
+
+    #[test]
+    fn create_statements_reject_other_drivers() {
+        assert!(create_statements("sqlite", "main", "t", "sb", 0).is_err());
+    }
+
+    #[test]
+    fn sqlite_ddl_is_moved_into_the_attached_database() {
+        let cases = [
+            "CREATE TABLE users (id INTEGER PRIMARY KEY, name TEXT)",
+            "create table if not exists \"users\"(id INTEGER PRIMARY KEY, name TEXT)",
+            "CREATE TABLE main.[users] (id INTEGER PRIMARY KEY, name TEXT)",
+            "CREATE TABLE `users` (id INTEGER PRIMARY KEY, name TEXT)",
+        ];
+        for ddl in cases {
+            let out = sqlite_ddl_into(ddl, "tabularis_sandbox", "users").unwrap();
+            assert_eq!(
+                out,
+                r#"CREATE TABLE "tabularis_sandbox"."users" (id INTEGER PRIMARY KEY, name TEXT)"#,
+                "input: {}",
+                ddl
+            );
+        }
+    }
+
+    #[test]
+    fn sqlite_ddl_handles_escaped_quotes() {
+        let out = sqlite_ddl_into(r#"CREATE TABLE "a""b" (x)"#, "sb", "a\"b").unwrap();
+        assert_eq!(out, r#"CREATE TABLE "sb"."a""b" (x)"#);
+    }
+
+    #[test]
+    fn sqlite_ddl_rejects_non_table_statements() {
+        assert!(sqlite_ddl_into("CREATE VIEW v AS SELECT 1", "sb", "v").is_err());
+    }
+
+    fn names(values: &[&str]) -> Vec<String> {
+        values.iter().map(|v| v.to_string()).collect()
+    }
+
+    #[test]
+    fn promote_matches_rows_by_primary_key() {
+        let stmts = promote_statements(
+            "postgres",
+            r#""public"."t""#,
+            r#""sb"."t""#,
+            &names(&["id", "name"]),
+            &names(&["id"]),
+        );
+        assert_eq!(
+            stmts,
+            vec![
+                r#"DELETE FROM "public"."t" WHERE NOT EXISTS (SELECT 1 FROM "sb"."t" AS s WHERE s."id" = "public"."t"."id")"#,
+                r#"INSERT INTO "public"."t" AS t ("id", "name") OVERRIDING SYSTEM VALUE SELECT "id", "name" FROM "sb"."t" WHERE true ON CONFLICT ("id") DO UPDATE SET "name" = excluded."name" WHERE t."name" IS DISTINCT FROM excluded."name""#,
+            ]
+        );
+    }
+
+    #[test]
+    fn mysql_promote_upserts_with_on_duplicate_key() {
+        let stmts = promote_statements(
+            "mysql",
+            "`shop`.`t`",
+            "`sb`.`t`",
+            &names(&["a", "b"]),
+            &names(&["a", "b"]),
+        );
+        assert_eq!(
+            stmts,
+            vec![
+                "DELETE FROM `shop`.`t` WHERE NOT EXISTS (SELECT 1 FROM `sb`.`t` AS s WHERE s.`a` = `shop`.`t`.`a` AND s.`b` = `shop`.`t`.`b`)",
+                "INSERT INTO `shop`.`t` (`a`, `b`) SELECT `a`, `b` FROM `sb`.`t` AS s ON DUPLICATE KEY UPDATE `a` = s.`a`, `b` = s.`b`",
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn promote_refuses_tables_with_cascading_children() {
+        let tmp = TempDir::new().unwrap();
+        let source_path = tmp.path().join("source.db").to_string_lossy().into_owned();
+        let sandbox_path = tmp.path().join("sandbox.db").to_string_lossy().into_owned();
+        let open = |path: &str| {
+            sqlx::SqlitePool::connect_with(
+                sqlx::sqlite::SqliteConnectOptions::new()
+                    .filename(path)
+                    .create_if_missing(true),
+            )
+        };
+        let source = open(&source_path).await.unwrap();
+        sqlx::raw_sql(
+            "CREATE TABLE parent (id INTEGER PRIMARY KEY, name TEXT);
+             CREATE TABLE child (id INTEGER PRIMARY KEY,
+                 parent_id INTEGER REFERENCES parent(id) ON DELETE CASCADE);
+             CREATE TABLE items (id INTEGER PRIMARY KEY, name TEXT);
+             INSERT INTO parent VALUES (1, 'a'), (2, 'b');
+             INSERT INTO child VALUES (10, 1), (20, 2);
+             INSERT INTO items VALUES (1, 'a'), (2, 'b');",
+        )
+        .execute(&source)
+        .await
+        .unwrap();
+        let sandbox_db = open(&sandbox_path).await.unwrap();
+        sqlx::raw_sql(
+            "CREATE TABLE parent (id INTEGER PRIMARY KEY, name TEXT);
+             CREATE TABLE items (id INTEGER PRIMARY KEY, name TEXT);
+             INSERT INTO parent VALUES (1, 'A');
+             INSERT INTO items VALUES (1, 'A'), (3, 'c');",
+        )
+        .execute(&sandbox_db)
+        .await
+        .unwrap();
+        sandbox_db.close().await;
+
+        crate::drivers::registry::register_driver(crate::drivers::sqlite::SqliteDriver::new())
+            .await;
+        let params = ConnectionParams {
+            driver: "sqlite".to_string(),
+            database: DatabaseSelection::Single(source_path.clone()),
+            ..Default::default()
+        };
+        let sandbox = |table: &str| Sandbox {
+            id: "s1".to_string(),
+            connection_id: "c1".to_string(),
+            driver: "sqlite".to_string(),
+            source_schema: None,
+            table: table.to_string(),
+            location: sandbox_path.clone(),
+            sample_rows: 0,
+            created_at: "2026-01-01T00:00:00Z".to_string(),
+        };
+
+        let err = promote(&params, &sandbox("parent")).await.unwrap_err();
+        assert!(err.contains("foreign keys"), "{}", err);
+        let children: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM child")
+            .fetch_one(&source)
+            .await
+            .unwrap();
+        assert_eq!(children, 2);
+
+        promote(&params, &sandbox("items")).await.unwrap();
+        let items: Vec<(i64, String)> = sqlx::query_as("SELECT id, name FROM items ORDER BY id")
+            .fetch_all(&source)
+            .await
+            .unwrap();
+        assert_eq!(items, vec![(1, "A".to_string()), (3, "c".to_string())]);
+
+        source.close().await;
+        crate::pool_manager::close_pool(&params).await;
+        crate::pool_manager::close_pool(&ConnectionParams {
+            database: DatabaseSelection::Single(sandbox_path),
+            ..params
+        })
+        .await;
+    }
+
+    #[test]
+    fn diff_columns_reports_added_removed_and_changed() {
+        let source = vec![
+            column("id", "int"),
+            column("name", "text"),
+            column("old", "int"),
+        ];
+        let sandbox = vec![
+            column("id", "INT"),
+            column("name", "varchar"),
+            column("new", "int"),
+        ];
+
+        let changes = diff_columns(&source, &sandbox);
+        let summary: Vec<(&str, ColumnChangeKind)> = changes
+            .iter()
+            .map(|c| (c.column.as_str(), c.kind))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("name", ColumnChangeKind::TypeChanged),
+                ("new", ColumnChangeKind::Added),
+                ("old", ColumnChangeKind::Removed),
+            ]
+        );
+        assert_eq!(changes[0].source_type.as_deref(), Some("text"));
+        assert_eq!(changes[0].sandbox_type.as_deref(), Some("varchar"));
+    }
+
+    // --- Registry ---
+
+    #[test]
+    fn registry_round_trip() {
+        let tmp = TempDir::new().unwrap();
+        assert!(read_sandboxes_in(tmp.path()).is_empty());
+
+        let sandbox = Sandbox {
+            id: "s1".to_string(),
+            connection_id: "c1".to_string(),
+            driver: "postgres".to_string(),
+            source_schema: Some("public".to_string()),
+            table: "users".to_string(),
+            location: "tabularis_sandbox_s1".to_string(),
+            sample_rows: 10,
+            created_at: "2026-01-01T00:00:00Z".to_string(),
+        };
+        write_sandboxes_in(tmp.path(), &[sandbox.clone()]).unwrap();
+
+        assert_eq!(find_sandbox_in(tmp.path(), "s1").unwrap(), sandbox);
+        assert!(find_sandbox_in(tmp.path(), "missing").is_err());
+    }
+}
@@ -2,7 +2,7 @@ use chrono::Utc;
 use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::PathBuf;
-use tauri::{AppHandle, Manager, Runtime};
+use tauri::{AppHandle, Runtime};
 use uuid::Uuid;
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -34,7 +34,7 @@ pub struct SavedQuery {
 }
 
 fn get_queries_dir<R: Runtime>(app: &AppHandle<R>) -> Result<PathBuf, String> {
-    let config_dir = app.path().app_config_dir().map_err(|e| e.to_string())?;
+    let config_dir = crate::paths::app_config_dir(app)?;
     let queries_dir = config_dir.join("saved_queries");
     if !queries_dir.exists() {
         fs::create_dir_all(&queries_dir).map_err(|e| e.to_string())?;
@@ -178,6 +178,50 @@ pub async fn update_saved_query<R: Runtime>(
     })
 }
 
+/// Pick the saved query called `name` (or with that id), within
+/// `connection_id` when given. A name shared by queries of several
+/// connections is ambiguous.
+pub fn select_saved_query<'a>(
+    meta_list: &'a [SavedQueryMeta],
+    name: &str,
+    connection_id: Option<&str>,
+) -> Result<&'a SavedQueryMeta, String> {
+    let matches: Vec<&SavedQueryMeta> = meta_list
+        .iter()
+        .filter(|m| connection_id.map_or(true, |c| m.connection_id == c))
+        .filter(|m| m.name == name || m.id == name)
+        .collect();
+    match matches.as_slice() {
+        [] => Err(format!("Saved query not found: {}", name)),
+        [meta] => Ok(meta),
+        _ => Err(format!(
+            "Several saved queries are named '{}'; choose one by connection",
+            name
+        )),
+    }
+}
+
+/// Load the saved query called `name`; see `select_saved_query`.
+pub fn find_saved_query<R: Runtime>(
+    app: &AppHandle<R>,
+    name: &str,
+    connection_id: Option<&str>,
+) -> Result<SavedQuery, String> {
+    let meta_list = read_meta(app)?;
+    let meta = select_saved_query(&meta_list, name, connection_id)?.clone();
+    let sql = fs::read_to_string(get_queries_dir(app)?.join(&meta.filename))
+        .map_err(|e| format!("Failed to read saved query '{}': {}", meta.name, e))?;
+    Ok(SavedQuery {
+        id: meta.id,
+        name: meta.name,
+        sql,
+        connection_id: meta.connection_id,
+        database: meta.database,
+        created_at: meta.created_at,
+        updated_at: meta.updated_at,
+    })
+}
+
 /// Set `database = Some(database)` on entries matching `connection_id` whose
 /// `database` is currently `None`. Returns the count of entries updated.
 pub fn backfill_missing_database(
@@ -1,6 +1,6 @@
 #[cfg(test)]
 mod tests {
-    use crate::saved_queries::{backfill_missing_database, SavedQueryMeta};
+    use crate::saved_queries::{backfill_missing_database, select_saved_query, SavedQueryMeta};
 
     fn make_meta(id: &str, connection_id: &str, database: Option<&str>) -> SavedQueryMeta {
         SavedQueryMeta {
@@ -68,4 +68,32 @@ mod tests {
         assert_eq!(updated, 0);
         assert_eq!(meta[0].database.as_deref(), Some(""));
     }
+
+    #[test]
+    fn selects_saved_query_by_name_or_id() {
+        let meta = vec![
+            make_meta("1", "conn-a", None),
+            make_meta("2", "conn-b", None),
+        ];
+        assert_eq!(select_saved_query(&meta, "query-2", None).unwrap().id, "2");
+        assert_eq!(select_saved_query(&meta, "1", None).unwrap().id, "1");
+        assert!(select_saved_query(&meta, "query-3", None).is_err());
+        assert!(select_saved_query(&meta, "query-2", Some("conn-a")).is_err());
+    }
+
+    #[test]
+    fn shared_saved_query_names_need_a_connection() {
+        let mut meta = vec![
+            make_meta("1", "conn-a", None),
+            make_meta("2", "conn-b", None),
+        ];
+        meta[1].name = "query-1".into();
+        assert!(select_saved_query(&meta, "query-1", None).is_err());
+        assert_eq!(
+            select_saved_query(&meta, "query-1", Some("conn-b"))
+                .unwrap()
+                .id,
+            "2"
+        );
+    }
 }
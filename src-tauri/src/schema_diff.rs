@@ -0,0 +1,502 @@
+//! Compare two schemas and generate the SQL that syncs them.
+//!
+//! `diff_schemas` reads `get_schema_snapshot` of a source and a target (two
+//! connections, or two schemas of one connection) and reports the tables,
+//! columns and foreign keys that differ. It also returns a migration script
+//! that makes the target match the source. The script is built with the
+//! target driver's DDL generators, so it is in the target's dialect. Drops
+//! have no generator, so they are written here with the driver's identifier
+//! quote. Anything a driver cannot generate becomes a warning and a comment
+//! in the script instead of failing the diff. Indexes are not compared
+//! because the snapshot does not include them.
+
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Runtime};
+
+use crate::commands::{
+    driver_for, expand_ssh_connection_params, find_connection_by_id,
+    resolve_connection_params_with_id,
+};
+use crate::drivers::common::quote_identifier;
+use crate::drivers::driver_trait::DatabaseDriver;
+use crate::models::{ColumnDefinition, ForeignKey, TableColumn, TableSchema};
+
+/// One side of a schema diff.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SchemaSide {
+    pub connection_id: String,
+    pub schema: Option<String>,
+}
+
+/// A column present on both sides with a different definition.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ColumnChange {
+    pub name: String,
+    /// The definition in the target.
+    pub from: TableColumn,
+    /// The definition in the source.
+    pub to: TableColumn,
+}
+
+/// Differences inside a table present on both sides.
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TableDiff {
+    pub name: String,
+    /// Columns only the source has.
+    pub added_columns: Vec<TableColumn>,
+    /// Columns only the target has.
+    pub removed_columns: Vec<String>,
+    pub changed_columns: Vec<ColumnChange>,
+    /// Foreign keys only the source has, one entry per column.
+    pub added_foreign_keys: Vec<ForeignKey>,
+    /// Names of the foreign keys only the target has.
+    pub removed_foreign_keys: Vec<String>,
+}
+
+impl TableDiff {
+    pub fn is_empty(&self) -> bool {
+        self.added_columns.is_empty()
+            && self.removed_columns.is_empty()
+            && self.changed_columns.is_empty()
+            && self.added_foreign_keys.is_empty()
+            && self.removed_foreign_keys.is_empty()
+    }
+}
+
+/// What differs between the source and the target.
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SchemaDiff {
+    /// Tables only the source has.
+    pub added_tables: Vec<String>,
+    /// Tables only the target has.
+    pub removed_tables: Vec<String>,
+    pub changed_tables: Vec<TableDiff>,
+}
+
+impl SchemaDiff {
+    pub fn is_empty(&self) -> bool {
+        self.added_tables.is_empty()
+            && self.removed_tables.is_empty()
+            && self.changed_tables.is_empty()
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SchemaDiffResult {
+    pub diff: SchemaDiff,
+    /// Statements that make the target match the source, `;`-terminated.
+    pub script: String,
+    /// Changes the target driver could not generate SQL for.
+    pub warnings: Vec<String>,
+}
+
+// ---------------------------------------------------------------------------
+// Diff (testable; pure)
+// ---------------------------------------------------------------------------
+
+fn same_column(a: &TableColumn, b: &TableColumn) -> bool {
+    a.data_type.trim().eq_ignore_ascii_case(b.data_type.trim())
+        && a.is_nullable == b.is_nullable
+        && a.is_pk == b.is_pk
+        && a.is_auto_increment == b.is_auto_increment
+        && a.default_value == b.default_value
+}
+
+fn referential_action(action: &Option<String>) -> String {
+    action
+        .as_deref()
+        .map(|a| a.trim().to_uppercase())
+        .filter(|a| !a.is_empty())
+        .unwrap_or_else(|| "NO ACTION".to_string())
+}
+
+/// Foreign keys of a table grouped into constraints by name, in order.
+pub(crate) fn constraints(foreign_keys: &[ForeignKey]) -> Vec<(&str, Vec<&ForeignKey>)> {
+    let mut grouped: Vec<(&str, Vec<&ForeignKey>)> = Vec::new();
+    for fk in foreign_keys {
+        match grouped.iter_mut().find(|(name, _)| *name == fk.name) {
+            Some((_, rows)) => rows.push(fk),
+            None => grouped.push((fk.name.as_str(), vec![fk])),
+        }
+    }
+    grouped
+}
+
+/// What a constraint does, ignoring its name, which usually differs between
+/// environments.
+fn constraint_signature(rows: &[&ForeignKey]) -> String {
+    let mut parts: Vec<String> = rows
+        .iter()
+        .map(|fk| {
+            format!(
+                "{}>{}.{} {} {}",
+                fk.column_name,
+                fk.ref_table,
+                fk.ref_column,
+                referential_action(&fk.on_delete),
+                referential_action(&fk.on_update)
+            )
+        })
+        .collect();
+    parts.sort();
+    parts.join(",")
+}
+
+fn diff_table(source: &TableSchema, target: &TableSchema) -> TableDiff {
+    let mut diff = TableDiff {
+        name: source.name.clone(),
+        ..Default::default()
+    };
+
+    for column in &source.columns {
+        match target.columns.iter().find(|c| c.name == column.name) {
+            None => diff.added_columns.push(column.clone()),
+            Some(existing) if !same_column(existing, column) => {
+                diff.changed_columns.push(ColumnChange {
+                    name: column.name.clone(),
+                    from: existing.clone(),
+                    to: column.clone(),
+                })
+            }
+            Some(_) => {}
+        }
+    }
+    diff.removed_columns = target
+        .columns
+        .iter()
+        .filter(|c| !source.columns.iter().any(|s| s.name == c.name))
+        .map(|c| c.name.clone())
+        .collect();
+
+    let source_constraints = constraints(&source.foreign_keys);
+    let target_constraints = constraints(&target.foreign_keys);
+    let target_signatures: HashSet<String> = target_constraints
+        .iter()
+        .map(|(_, rows)| constraint_signature(rows))
+        .collect();
+    let source_signatures: HashSet<String> = source_constraints
+        .iter()
+        .map(|(_, rows)| constraint_signature(rows))
+        .collect();
+    for (_, rows) in &source_constraints {
+        if !target_signatures.contains(&constraint_signature(rows)) {
+            diff.added_foreign_keys
+                .extend(rows.iter().map(|fk| (*fk).clone()));
+        }
+    }
+    diff.removed_foreign_keys = target_constraints
+        .iter()
+        .filter(|(_, rows)| !source_signatures.contains(&constraint_signature(rows)))
+        .map(|(name, _)| name.to_string())
+        .collect();
+
+    diff
+}
+
+/// Diff the `target` snapshot against `source`: what must change in the
+/// target for it to match the source.
+pub fn diff_snapshots(source: &[TableSchema], target: &[TableSchema]) -> SchemaDiff {
+    let target_by_name: HashMap<&str, &TableSchema> =
+        target.iter().map(|t| (t.name.as_str(), t)).collect();
+    let source_names: HashSet<&str> = source.iter().map(|t| t.name.as_str()).collect();
+
+    let mut diff = SchemaDiff::default();
+    for table in source {
+        match target_by_name.get(table.name.as_str()) {
+            None => diff.added_tables.push(table.name.clone()),
+            Some(existing) => {
+                let table_diff = diff_table(table, existing);
+                if !table_diff.is_empty() {
+                    diff.changed_tables.push(table_diff);
+                }
+            }
+        }
+    }
+    diff.removed_tables = target
+        .iter()
+        .filter(|t| !source_names.contains(t.name.as_str()))
+        .map(|t| t.name.clone())
+        .collect();
+    diff
+}
+
+// ---------------------------------------------------------------------------
+// Drop statements (testable; pure)
+// ---------------------------------------------------------------------------
+
+pub fn table_ref(quote: &str, schema: Option<&str>, table: &str) -> String {
+    match schema {
+        Some(schema) => format!(
+            "{}.{}",
+            quote_identifier(quote, schema),
+            quote_identifier(quote, table)
+        ),
+        None => quote_identifier(quote, table),
+    }
+}
+
+pub fn drop_table_sql(quote: &str, schema: Option<&str>, table: &str) -> String {
+    format!("DROP TABLE {}", table_ref(quote, schema, table))
+}
+
+pub fn drop_column_sql(quote: &str, schema: Option<&str>, table: &str, column: &str) -> String {
+    format!(
+        "ALTER TABLE {} DROP COLUMN {}",
+        table_ref(quote, schema, table),
+        quote_identifier(quote, column)
+    )
+}
+
+/// MySQL names the clause `DROP FOREIGN KEY`; the others use the standard
+/// `DROP CONSTRAINT`.
+pub fn drop_foreign_key_sql(
+    driver: &str,
+    quote: &str,
+    schema: Option<&str>,
+    table: &str,
+    name: &str,
+) -> String {
+    let clause = if driver == "mysql" {
+        "DROP FOREIGN KEY"
+    } else {
+        "DROP CONSTRAINT"
+    };
+    format!(
+        "ALTER TABLE {} {} {}",
+        table_ref(quote, schema, table),
+        clause,
+        quote_identifier(quote, name)
+    )
+}
+
+// ---------------------------------------------------------------------------
+// Script generation
+// ---------------------------------------------------------------------------
+
+/// Collects statements, turning generator failures into warnings.
+#[derive(Default)]
+struct Script {
+    statements: Vec<String>,
+    warnings: Vec<String>,
+}
+
+impl Script {
+    fn push(&mut self, what: String, generated: Result<Vec<String>, String>) {
+        match generated {
+            Ok(statements) => self.statements.extend(statements),
+            Err(e) => {
+                let warning = format!("{}: {}", what, e);
+                self.statements.push(format!("-- {}", warning));
+                self.warnings.push(warning);
+            }
+        }
+    }
+
+    fn render(&self) -> String {
+        self.statements
+            .iter()
+            .map(|s| {
+                if s.starts_with("--") {
+                    s.clone()
+                } else {
+                    format!("{};", s.trim_end().trim_end_matches(';'))
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+async fn add_foreign_keys(
+    script: &mut Script,
+    drv: &Arc<dyn DatabaseDriver>,
+    table: &str,
+    foreign_keys: &[ForeignKey],
+    schema: Option<&str>,
+) {
+    for (name, rows) in constraints(foreign_keys) {
+        let what = format!("Add foreign key {} on {}", name, table);
+        if rows.len() > 1 {
+            script.push(what, Err("composite foreign keys are not generated".into()));
+            continue;
+        }
+        let fk = rows[0];
+        let generated = drv
+            .get_create_foreign_key_sql(
+                table,
+                &fk.name,
+                &fk.column_name,
+                &fk.ref_table,
+                &fk.ref_column,
+                fk.on_delete.as_deref(),
+                fk.on_update.as_deref(),
+                schema,
+            )
+            .await;
+        script.push(what, generated);
+    }
+}
+
+/// Build the statements that apply `diff` to the target. Foreign keys are
+/// dropped first and added last, so tables and columns can change in
+/// between without tripping over them.
+async fn migration_script(
+    drv: &Arc<dyn DatabaseDriver>,
+    diff: &SchemaDiff,
+    source: &[TableSchema],
+    schema: Option<&str>,
+) -> Script {
+    let manifest = drv.manifest();
+    let driver = manifest.id.as_str();
+    let quote = manifest.capabilities.identifier_quote.as_str();
+    let mut script = Script::default();
+
+    for table in &diff.changed_tables {
+        for name in &table.removed_foreign_keys {
+            let what = format!("Drop foreign key {} on {}", name, table.name);
+            let generated = if driver == "sqlite" {
+                Err("SQLite cannot drop a foreign key without rebuilding the table".into())
+            } else {
+                Ok(vec![drop_foreign_key_sql(
+                    driver,
+                    quote,
+                    schema,
+                    &table.name,
+                    name,
+                )])
+            };
+            script.push(what, generated);
+        }
+    }
+
+    for name in &diff.removed_tables {
+        script.push(
+            format!("Drop table {}", name),
+            Ok(vec![drop_table_sql(quote, schema, name)]),
+        );
+    }
+
+    let source_by_name: HashMap<&str, &TableSchema> =
+        source.iter().map(|t| (t.name.as_str(), t)).collect();
+    for name in &diff.added_tables {
+        let Some(table) = source_by_name.get(name.as_str()) else {
+            continue;
+        };
+        let columns = table.columns.iter().map(ColumnDefinition::from).collect();
+        let generated = drv.get_create_table_sql(name, columns, schema).await;
+        script.push(format!("Create table {}", name), generated);
+    }
+
+    for table in &diff.changed_tables {
+        for column in &table.added_columns {
+            let generated = drv
+                .get_add_column_sql(&table.name, column.into(), schema)
+                .await;
+            script.push(
+                format!("Add column {}.{}", table.name, column.name),
+                generated,
+            );
+        }
+        for change in &table.changed_columns {
+            let generated = drv
+                .get_alter_column_sql(
+                    &table.name,
+                    (&change.from).into(),
+                    (&change.to).into(),
+                    schema,
+                )
+                .await;
+            script.push(
+                format!("Alter column {}.{}", table.name, change.name),
+                generated,
+            );
+        }
+        for column in &table.removed_columns {
+            script.push(
+                format!("Drop column {}.{}", table.name, column),
+                Ok(vec![drop_column_sql(quote, schema, &table.name, column)]),
+            );
+        }
+    }
+
+    for name in &diff.added_tables {
+        if let Some(table) = source_by_name.get(name.as_str()) {
+            add_foreign_keys(&mut script, drv, name, &table.foreign_keys, schema).await;
+        }
+    }
+    for table in &diff.changed_tables {
+        add_foreign_keys(
+            &mut script,
+            drv,
+            &table.name,
+            &table.added_foreign_keys,
+            schema,
+        )
+        .await;
+    }
+
+    script
+}
+
+async fn snapshot<R: Runtime>(
+    app: &AppHandle<R>,
+    side: &SchemaSide,
+) -> Result<(Vec<TableSchema>, Arc<dyn DatabaseDriver>), String> {
+    let saved_conn = find_connection_by_id(app, &side.connection_id)?;
+    let expanded_params = expand_ssh_connection_params(app, &saved_conn.params).await?;
+    let params = resolve_connection_params_with_id(&expanded_params, &side.connection_id)?;
+    let drv = driver_for(&saved_conn.params.driver).await?;
+    let tables = drv
+        .get_schema_snapshot(&params, side.schema.as_deref())
+        .await?;
+    Ok((tables, drv))
+}
+
+// ---------------------------------------------------------------------------
+// Tauri commands
+// ---------------------------------------------------------------------------
+
+/// Diff the `target` schema against `source` and generate the script that
+/// makes the target match, in the target's SQL dialect.
+#[tauri::command]
+pub async fn diff_schemas<R: Runtime>(
+    app: AppHandle<R>,
+    source: SchemaSide,
+    target: SchemaSide,
+) -> Result<SchemaDiffResult, String> {
+    log::info!(
+        "Diffing schema {}/{} against {}/{}",
+        target.connection_id,
+        target.schema.as_deref().unwrap_or("-"),
+        source.connection_id,
+        source.schema.as_deref().unwrap_or("-")
+    );
+    let (source_snapshot, target_snapshot) =
+        futures::try_join!(snapshot(&app, &source), snapshot(&app, &target))?;
+    let (source_tables, _) = source_snapshot;
+    let (target_tables, target_drv) = target_snapshot;
+
+    let diff = diff_snapshots(&source_tables, &target_tables);
+    let script =
+        migration_script(&target_drv, &diff, &source_tables, target.schema.as_deref()).await;
+    log::info!(
+        "Schema diff: {} added, {} removed, {} changed tables, {} warnings",
+        diff.added_tables.len(),
+        diff.removed_tables.len(),
+        diff.changed_tables.len(),
+        script.warnings.len()
+    );
+    Ok(SchemaDiffResult {
+        script: script.render(),
+        warnings: script.warnings,
+        diff,
+    })
+}
@@ -0,0 +1,150 @@
+#[cfg(test)]
+mod tests {
+    use crate::models::{ForeignKey, TableColumn, TableSchema};
+    use crate::schema_diff::{
+        diff_snapshots, drop_column_sql, drop_foreign_key_sql, drop_table_sql, table_ref,
+    };
+
+    fn column(name: &str, data_type: &str) -> TableColumn {
+        TableColumn {
+            name: name.to_string(),
+            data_type: data_type.to_string(),
+            is_pk: name == "id",
+            is_nullable: name != "id",
+            is_auto_increment: false,
+            default_value: None,
+            character_maximum_length: None,
+        }
+    }
+
+    fn fk(name: &str, column: &str, ref_table: &str) -> ForeignKey {
+        ForeignKey {
+            name: name.to_string(),
+            column_name: column.to_string(),
+            ref_table: ref_table.to_string(),
+            ref_column: "id".to_string(),
+            on_delete: None,
+            on_update: None,
+        }
+    }
+
+    fn table(name: &str, columns: Vec<TableColumn>, foreign_keys: Vec<ForeignKey>) -> TableSchema {
+        TableSchema {
+            name: name.to_string(),
+            columns,
+            foreign_keys,
+        }
+    }
+
+    #[test]
+    fn identical_snapshots_have_no_diff() {
+        let snapshot = vec![table("users", vec![column("id", "integer")], vec![])];
+        let other = vec![table("users", vec![column("id", "INTEGER")], vec![])];
+        assert!(diff_snapshots(&snapshot, &other).is_empty());
+    }
+
+    #[test]
+    fn finds_added_and_removed_tables() {
+        let source = vec![
+            table("users", vec![column("id", "integer")], vec![]),
+            table("orders", vec![column("id", "integer")], vec![]),
+        ];
+        let target = vec![
+            table("users", vec![column("id", "integer")], vec![]),
+            table("legacy_orders", vec![column("id", "integer")], vec![]),
+        ];
+
+        let diff = diff_snapshots(&source, &target);
+        assert_eq!(diff.added_tables, ["orders"]);
+        assert_eq!(diff.removed_tables, ["legacy_orders"]);
+        assert!(diff.changed_tables.is_empty());
+    }
+
+    #[test]
+    fn finds_column_changes() {
+        let mut email = column("email", "varchar(255)");
+        email.is_nullable = false;
+        let source = vec![table(
+            "users",
+            vec![column("id", "integer"), email, column("plan", "text")],
+            vec![],
+        )];
+        let target = vec![table(
+            "users",
+            vec![
+                column("id", "integer"),
+                column("email", "varchar(255)"),
+                column("nickname", "text"),
+            ],
+            vec![],
+        )];
+
+        let diff = diff_snapshots(&source, &target);
+        let users = &diff.changed_tables[0];
+        assert_eq!(users.name, "users");
+        assert_eq!(users.added_columns[0].name, "plan");
+        assert_eq!(users.removed_columns, ["nickname"]);
+        assert_eq!(users.changed_columns.len(), 1);
+        assert!(users.changed_columns[0].from.is_nullable);
+        assert!(!users.changed_columns[0].to.is_nullable);
+    }
+
+    #[test]
+    fn matches_foreign_keys_by_definition_not_name() {
+        let columns = || vec![column("id", "integer"), column("user_id", "integer")];
+        let source = vec![table(
+            "orders",
+            columns(),
+            vec![fk("fk_orders_user", "user_id", "users")],
+        )];
+        let renamed = vec![table(
+            "orders",
+            columns(),
+            vec![fk("orders_user_id_fkey", "user_id", "users")],
+        )];
+        assert!(diff_snapshots(&source, &renamed).is_empty());
+
+        let mut cascading = fk("fk_orders_user", "user_id", "users");
+        cascading.on_delete = Some("CASCADE".to_string());
+        let changed = vec![table("orders", columns(), vec![cascading])];
+        let diff = diff_snapshots(&source, &changed);
+        let orders = &diff.changed_tables[0];
+        assert_eq!(orders.added_foreign_keys[0].name, "fk_orders_user");
+        assert_eq!(orders.removed_foreign_keys, ["fk_orders_user"]);
+    }
+
+    #[test]
+    fn missing_referential_action_means_no_action() {
+        let columns = || vec![column("id", "integer"), column("user_id", "integer")];
+        let mut explicit = fk("fk", "user_id", "users");
+        explicit.on_delete = Some("no action".to_string());
+        let source = vec![table("orders", columns(), vec![explicit])];
+        let target = vec![table(
+            "orders",
+            columns(),
+            vec![fk("fk", "user_id", "users")],
+        )];
+        assert!(diff_snapshots(&source, &target).is_empty());
+    }
+
+    #[test]
+    fn builds_quoted_drop_statements() {
+        assert_eq!(
+            table_ref("\"", Some("pub\"lic"), "users"),
+            r#""pub""lic"."users""#
+        );
+        assert_eq!(drop_table_sql("`", None, "users"), "DROP TABLE `users`");
+        assert_eq!(
+            drop_column_sql("\"", Some("public"), "users", "nickname"),
+            r#"ALTER TABLE "public"."users" DROP COLUMN "nickname""#
+        );
+        assert_eq!(
+            drop_foreign_key_sql("mysql", "`", None, "orders", "fk_user"),
+            "ALTER TABLE `orders` DROP FOREIGN KEY `fk_user`"
+        );
+        assert_eq!(
+            drop_foreign_key_sql("postgres", "\"", None, "orders", "fk_user"),
+            r#"ALTER TABLE "orders" DROP CONSTRAINT "fk_user""#
+        );
+    }
+}
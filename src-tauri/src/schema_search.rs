@@ -0,0 +1,297 @@
+//! "Open anything": find tables, views, columns, indexes and routines by
+//! name.
+//!
+//! `search_schema_objects` reads the schema through the batch metadata
+//! calls (one query per kind of object on the built-in drivers) and ranks
+//! every name against the pattern, ignoring case: exact names first, then
+//! prefixes, substrings, and names holding the pattern's characters in
+//! order, so `usr_em` still finds `user_email`. A pattern with a dot,
+//! such as `users.em`, matches columns and indexes as `table.name`.
+
+use std::collections::HashMap;
+use std::future::Future;
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Runtime};
+
+use crate::commands::{
+    driver_for, expand_ssh_connection_params, find_connection_by_id,
+    resolve_connection_params_with_id,
+};
+use crate::models::{Index, RoutineInfo, TableColumn};
+
+const DEFAULT_LIMIT: usize = 50;
+const LIMIT_CAP: usize = 500;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum SchemaObjectKind {
+    Table,
+    View,
+    Column,
+    Index,
+    Routine,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct SchemaObjectHit {
+    pub kind: SchemaObjectKind,
+    pub name: String,
+    /// The table of a column or index.
+    pub table: Option<String>,
+    /// The data type of a column, the columns of an index, `PROCEDURE` or
+    /// `FUNCTION` for a routine.
+    pub detail: Option<String>,
+    /// Higher is better; hits come sorted by it.
+    pub score: u32,
+}
+
+/// The names searched, as the driver reported them.
+#[derive(Debug, Default)]
+pub struct SchemaCatalog {
+    pub tables: Vec<String>,
+    pub views: Vec<String>,
+    pub columns: HashMap<String, Vec<TableColumn>>,
+    pub indexes: HashMap<String, Vec<Index>>,
+    pub routines: Vec<RoutineInfo>,
+}
+
+// ---------------------------------------------------------------------------
+// Matching (testable; pure)
+// ---------------------------------------------------------------------------
+
+const EXACT: u32 = 1_000;
+const PREFIX: u32 = 800;
+const WORD: u32 = 600;
+const SUBSTRING: u32 = 500;
+const FUZZY: u32 = 100;
+
+fn is_word_start(name: &[char], at: usize) -> bool {
+    at == 0 || !name[at - 1].is_alphanumeric()
+}
+
+/// How well `candidate` matches `pattern`, ignoring case; `None` when it
+/// does not match at all.
+pub fn match_score(pattern: &str, candidate: &str) -> Option<u32> {
+    let pattern: Vec<char> = pattern.trim().to_lowercase().chars().collect();
+    let name: Vec<char> = candidate.to_lowercase().chars().collect();
+    if pattern.is_empty() || pattern.len() > name.len() {
+        return None;
+    }
+    if name == pattern {
+        return Some(EXACT);
+    }
+    if name.starts_with(&pattern) {
+        return Some(PREFIX);
+    }
+    let found: Vec<usize> = name
+        .windows(pattern.len())
+        .enumerate()
+        .filter(|(_, window)| *window == pattern.as_slice())
+        .map(|(at, _)| at)
+        .collect();
+    if !found.is_empty() {
+        // `email` in `user_email` beats `mail` in `user_email`.
+        let word_start = found.iter().any(|&at| is_word_start(&name, at));
+        return Some(if word_start { WORD } else { SUBSTRING });
+    }
+
+    // The pattern's characters in order; runs and word starts score more.
+    let mut score = FUZZY;
+    let mut next = 0;
+    let mut previous: Option<usize> = None;
+    for c in pattern {
+        let at = next + name[next..].iter().position(|&n| n == c)?;
+        if previous.is_some_and(|p| p + 1 == at) {
+            score += 5;
+        }
+        if is_word_start(&name, at) {
+            score += 10;
+        }
+        previous = Some(at);
+        next = at + 1;
+    }
+    Some(score.min(SUBSTRING - 1))
+}
+
+/// Match `name` alone, or as `table.name` when the pattern names a table.
+fn qualified_score(pattern: &str, table: &str, name: &str) -> Option<u32> {
+    if pattern.contains('.') {
+        match_score(pattern, &format!("{}.{}", table, name))
+    } else {
+        match_score(pattern, name)
+    }
+}
+
+/// The objects of `catalog` matching `pattern`, best first, at most
+/// `limit` of them. Among equal scores, shorter names come first.
+pub fn search_catalog(
+    pattern: &str,
+    catalog: &SchemaCatalog,
+    limit: usize,
+) -> Vec<SchemaObjectHit> {
+    let mut hits = Vec::new();
+    let mut push = |kind, name: &str, table: Option<&str>, detail: Option<String>, score| {
+        if let Some(score) = score {
+            hits.push(SchemaObjectHit {
+                kind,
+                name: name.to_string(),
+                table: table.map(str::to_string),
+                detail,
+                score,
+            });
+        }
+    };
+
+    for table in &catalog.tables {
+        push(
+            SchemaObjectKind::Table,
+            table,
+            None,
+            None,
+            match_score(pattern, table),
+        );
+    }
+    for view in &catalog.views {
+        push(
+            SchemaObjectKind::View,
+            view,
+            None,
+            None,
+            match_score(pattern, view),
+        );
+    }
+    for (table, columns) in &catalog.columns {
+        for column in columns {
+            push(
+                SchemaObjectKind::Column,
+                &column.name,
+                Some(table),
+                Some(column.data_type.clone()),
+                qualified_score(pattern, table, &column.name),
+            );
+        }
+    }
+    for (table, rows) in &catalog.indexes {
+        // One row per indexed column; one hit per index.
+        let mut indexes: Vec<(&str, Vec<&str>)> = Vec::new();
+        for row in rows {
+            match indexes.iter_mut().find(|(name, _)| *name == row.name) {
+                Some((_, columns)) => columns.push(row.column_name.as_str()),
+                None => indexes.push((row.name.as_str(), vec![row.column_name.as_str()])),
+            }
+        }
+        for (name, columns) in indexes {
+            push(
+                SchemaObjectKind::Index,
+                name,
+                Some(table),
+                Some(columns.join(", ")),
+                qualified_score(pattern, table, name),
+            );
+        }
+    }
+    for routine in &catalog.routines {
+        push(
+            SchemaObjectKind::Routine,
+            &routine.name,
+            None,
+            Some(routine.routine_type.clone()),
+            match_score(pattern, &routine.name),
+        );
+    }
+
+    hits.sort_by(|a, b| {
+        b.score
+            .cmp(&a.score)
+            .then_with(|| a.name.len().cmp(&b.name.len()))
+            .then_with(|| a.name.cmp(&b.name))
+            .then_with(|| a.table.cmp(&b.table))
+    });
+    hits.truncate(limit);
+    hits
+}
+
+// ---------------------------------------------------------------------------
+// Tauri commands
+// ---------------------------------------------------------------------------
+
+/// The result of `fetch` when `wanted`, nothing otherwise. A failure is
+/// logged and searched as nothing, so one kind of object the driver cannot
+/// list does not fail the search.
+async fn optional<T: Default>(
+    wanted: bool,
+    what: &str,
+    fetch: impl Future<Output = Result<T, String>>,
+) -> T {
+    if !wanted {
+        return T::default();
+    }
+    fetch.await.unwrap_or_else(|e| {
+        log::warn!("Schema search: cannot list {} ({})", what, e);
+        T::default()
+    })
+}
+
+/// Find schema objects whose names match `pattern`. `kinds` narrows the
+/// search (all kinds by default).
+#[tauri::command]
+pub async fn search_schema_objects<R: Runtime>(
+    app: AppHandle<R>,
+    connection_id: String,
+    pattern: String,
+    schema: Option<String>,
+    kinds: Option<Vec<SchemaObjectKind>>,
+    limit: Option<usize>,
+) -> Result<Vec<SchemaObjectHit>, String> {
+    if pattern.trim().is_empty() {
+        return Ok(Vec::new());
+    }
+    let saved_conn = find_connection_by_id(&app, &connection_id)?;
+    let expanded_params = expand_ssh_connection_params(&app, &saved_conn.params).await?;
+    let params = resolve_connection_params_with_id(&expanded_params, &connection_id)?;
+    let drv = driver_for(&saved_conn.params.driver).await?;
+    let capabilities = &drv.manifest().capabilities;
+    let (has_views, has_routines) = (capabilities.views, capabilities.routines);
+    let schema = schema.as_deref();
+    let wants = |kind| kinds.as_ref().map_or(true, |kinds| kinds.contains(&kind));
+
+    // Tables are listed first: when that fails, so would everything else.
+    let tables = drv.get_tables(&params, schema).await?;
+    let (views, columns, indexes, routines) = futures::join!(
+        optional(
+            wants(SchemaObjectKind::View) && has_views,
+            "views",
+            drv.get_views(&params, schema),
+        ),
+        optional(
+            wants(SchemaObjectKind::Column),
+            "columns",
+            drv.get_all_columns_batch(&params, schema),
+        ),
+        optional(
+            wants(SchemaObjectKind::Index),
+            "indexes",
+            drv.get_all_indexes_batch(&params, schema),
+        ),
+        optional(
+            wants(SchemaObjectKind::Routine) && has_routines,
+            "routines",
+            drv.get_routines(&params, schema),
+        ),
+    );
+    let catalog = SchemaCatalog {
+        tables: if wants(SchemaObjectKind::Table) {
+            tables.into_iter().map(|table| table.name).collect()
+        } else {
+            Vec::new()
+        },
+        views: views.into_iter().map(|view| view.name).collect(),
+        columns,
+        indexes,
+        routines,
+    };
+
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).clamp(1, LIMIT_CAP);
+    Ok(search_catalog(&pattern, &catalog, limit))
+}
@@ -0,0 +1,108 @@
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use crate::models::{Index, RoutineInfo, TableColumn};
+    use crate::schema_search::{match_score, search_catalog, SchemaCatalog, SchemaObjectKind};
+
+    fn column(name: &str) -> TableColumn {
+        TableColumn {
+            name: name.to_string(),
+            data_type: "varchar".to_string(),
+            is_pk: false,
+            is_nullable: true,
+            is_auto_increment: false,
+            default_value: None,
+            character_maximum_length: None,
+        }
+    }
+
+    fn index(name: &str, column_name: &str, seq_in_index: i32) -> Index {
+        Index {
+            name: name.to_string(),
+            column_name: column_name.to_string(),
+            is_unique: false,
+            is_primary: false,
+            seq_in_index,
+        }
+    }
+
+    fn catalog() -> SchemaCatalog {
+        SchemaCatalog {
+            tables: vec!["users".into(), "user_emails".into(), "orders".into()],
+            views: vec!["active_users".into()],
+            columns: HashMap::from([
+                ("users".to_string(), vec![column("id"), column("email")]),
+                ("orders".to_string(), vec![column("user_id")]),
+            ]),
+            indexes: HashMap::from([(
+                "orders".to_string(),
+                vec![
+                    index("idx_orders_user", "user_id", 1),
+                    index("idx_orders_user", "created_at", 2),
+                ],
+            )]),
+            routines: vec![RoutineInfo {
+                name: "purge_users".into(),
+                routine_type: "PROCEDURE".into(),
+                definition: None,
+            }],
+        }
+    }
+
+    #[test]
+    fn better_matches_score_higher() {
+        let exact = match_score("users", "Users").unwrap();
+        let prefix = match_score("user", "user_emails").unwrap();
+        let word = match_score("email", "user_email").unwrap();
+        let substring = match_score("mail", "user_email").unwrap();
+        let fuzzy = match_score("usr_em", "user_email").unwrap();
+        assert!(exact > prefix && prefix > word && word > substring && substring > fuzzy);
+        assert_eq!(match_score("xyz", "user_email"), None);
+        assert_eq!(match_score("  ", "users"), None);
+    }
+
+    #[test]
+    fn fuzzy_matches_prefer_word_starts() {
+        assert!(match_score("cid", "customer_id") > match_score("cid", "cupid"));
+        assert_eq!(match_score("emu", "user_email"), None);
+    }
+
+    #[test]
+    fn hits_are_typed_and_ranked() {
+        let hits = search_catalog("user", &catalog(), 50);
+        let found: Vec<(SchemaObjectKind, &str)> =
+            hits.iter().map(|h| (h.kind, h.name.as_str())).collect();
+        assert_eq!(found[0], (SchemaObjectKind::Table, "users"));
+        assert_eq!(found[1], (SchemaObjectKind::Column, "user_id"));
+        assert!(found.contains(&(SchemaObjectKind::View, "active_users")));
+        assert!(found.contains(&(SchemaObjectKind::Routine, "purge_users")));
+
+        let column = hits.iter().find(|h| h.name == "user_id").unwrap();
+        assert_eq!(column.table.as_deref(), Some("orders"));
+        assert_eq!(column.detail.as_deref(), Some("varchar"));
+    }
+
+    #[test]
+    fn indexes_are_one_hit_with_their_columns() {
+        let hits = search_catalog("idx_orders", &catalog(), 50);
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].kind, SchemaObjectKind::Index);
+        assert_eq!(hits[0].detail.as_deref(), Some("user_id, created_at"));
+    }
+
+    #[test]
+    fn a_dotted_pattern_matches_table_and_column() {
+        let hits = search_catalog("users.em", &catalog(), 50);
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].name, "email");
+        assert_eq!(hits[0].table.as_deref(), Some("users"));
+    }
+
+    #[test]
+    fn the_limit_keeps_the_best_hits() {
+        let hits = search_catalog("u", &catalog(), 2);
+        assert_eq!(hits.len(), 2);
+        assert_eq!(hits[0].name, "users");
+    }
+}
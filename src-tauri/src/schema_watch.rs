@@ -0,0 +1,297 @@
+//! Background schema change detection.
+//!
+//! Every `schema_watch_interval` seconds the columns of each open connection
+//! are read with one cheap catalog query (`get_schema_signature`) and hashed.
+//! When the hash differs from the previous observation, a `schema-changed`
+//! event lists the added, dropped and altered tables so the frontend can
+//! invalidate its metadata cache and flag the affected tabs.
+//!
+//! The first observation of a connection only records a baseline. DDL run
+//! from tabularis itself resets the baseline, so only changes made outside
+//! the app are reported.
+
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Runtime};
+use tokio::sync::oneshot;
+
+use crate::models::ColumnSignature;
+
+/// Default check interval in seconds.
+pub const DEFAULT_SCHEMA_WATCH_INTERVAL: u32 = 300;
+
+const SCHEMA_CHANGED_EVENT: &str = "schema-changed";
+
+/// Per-check timeout, so a slow catalog never stalls the loop.
+const CHECK_TIMEOUT: Duration = Duration::from_secs(15);
+
+static BASELINES: Lazy<Mutex<HashMap<String, Baseline>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+static WATCH_STOP_TX: Lazy<tokio::sync::Mutex<Option<oneshot::Sender<()>>>> =
+    Lazy::new(|| tokio::sync::Mutex::new(None));
+
+#[derive(Debug, Clone)]
+struct Baseline {
+    checksum: String,
+    columns: Vec<ColumnSignature>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct SchemaChange {
+    pub connection_id: String,
+    pub added_tables: Vec<String>,
+    pub dropped_tables: Vec<String>,
+    /// Tables whose columns were added, dropped or retyped.
+    pub changed_tables: Vec<String>,
+}
+
+impl SchemaChange {
+    pub fn is_empty(&self) -> bool {
+        self.added_tables.is_empty()
+            && self.dropped_tables.is_empty()
+            && self.changed_tables.is_empty()
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Signatures (testable; pure)
+// ---------------------------------------------------------------------------
+
+/// Order-independent hash of a schema signature.
+pub fn checksum(columns: &[ColumnSignature]) -> String {
+    let mut sorted: Vec<&ColumnSignature> = columns.iter().collect();
+    sorted.sort();
+    let mut content = String::new();
+    for c in sorted {
+        content.push_str(&c.table);
+        content.push('\u{1f}');
+        content.push_str(&c.column);
+        content.push('\u{1f}');
+        content.push_str(&c.data_type);
+        content.push('\u{1e}');
+    }
+    crate::sql_files::content_hash(content.as_bytes())
+}
+
+/// Tables added, dropped or altered between two signatures.
+pub fn diff_signatures(
+    connection_id: &str,
+    old: &[ColumnSignature],
+    new: &[ColumnSignature],
+) -> SchemaChange {
+    fn by_table(columns: &[ColumnSignature]) -> BTreeMap<&str, BTreeSet<(&str, &str)>> {
+        let mut tables: BTreeMap<&str, BTreeSet<(&str, &str)>> = BTreeMap::new();
+        for c in columns {
+            tables
+                .entry(c.table.as_str())
+                .or_default()
+                .insert((c.column.as_str(), c.data_type.as_str()));
+        }
+        tables
+    }
+
+    let old = by_table(old);
+    let new = by_table(new);
+    let mut change = SchemaChange {
+        connection_id: connection_id.to_string(),
+        ..SchemaChange::default()
+    };
+    for (table, columns) in &new {
+        match old.get(table) {
+            None => change.added_tables.push(table.to_string()),
+            Some(previous) if previous != columns => change.changed_tables.push(table.to_string()),
+            Some(_) => {}
+        }
+    }
+    for table in old.keys() {
+        if !new.contains_key(table) {
+            change.dropped_tables.push(table.to_string());
+        }
+    }
+    change
+}
+
+/// Statements that change the schema when run from tabularis itself.
+pub fn is_schema_ddl(sql: &str) -> bool {
+    let stripped = crate::drivers::common::strip_leading_sql_comments(sql);
+    let keyword = stripped
+        .split(|c: char| c.is_whitespace() || c == '(')
+        .next()
+        .unwrap_or_default()
+        .to_uppercase();
+    matches!(keyword.as_str(), "CREATE" | "ALTER" | "DROP" | "RENAME")
+}
+
+// ---------------------------------------------------------------------------
+// Baselines
+// ---------------------------------------------------------------------------
+
+/// Record `columns` for `connection_id` and return what changed since the
+/// previous observation. The first observation returns `None`.
+pub fn observe(connection_id: &str, columns: Vec<ColumnSignature>) -> Option<SchemaChange> {
+    let checksum = checksum(&columns);
+    let mut baselines = BASELINES.lock().unwrap_or_else(|e| e.into_inner());
+    let previous = baselines.insert(
+        connection_id.to_string(),
+        Baseline {
+            checksum: checksum.clone(),
+            columns,
+        },
+    )?;
+    if previous.checksum == checksum {
+        return None;
+    }
+    let current = &baselines[connection_id].columns;
+    let change = diff_signatures(connection_id, &previous.columns, current);
+    (!change.is_empty()).then_some(change)
+}
+
+/// Checksum of the last schema observed for `connection_id`, if the watch
+/// has checked it already.
+pub fn known_checksum(connection_id: &str) -> Option<String> {
+    BASELINES
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .get(connection_id)
+        .map(|b| b.checksum.clone())
+}
+
+/// Forget the baseline of a connection; the next check re-baselines silently.
+pub fn forget(connection_id: &str) {
+    BASELINES
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .remove(connection_id);
+}
+
+/// Called after a statement ran successfully from the app.
+pub fn note_local_ddl(connection_id: &str, sql: &str) {
+    if is_schema_ddl(sql) {
+        forget(connection_id);
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Watch loop
+// ---------------------------------------------------------------------------
+
+/// Start the periodic schema check. If a loop is already running it is stopped first.
+pub async fn start_schema_watch_loop(app: AppHandle, interval_secs: u64) {
+    stop_schema_watch_loop().await;
+
+    if interval_secs == 0 {
+        log::info!("Schema watch: disabled (interval = 0)");
+        return;
+    }
+
+    let (stop_tx, mut stop_rx) = oneshot::channel::<()>();
+    *WATCH_STOP_TX.lock().await = Some(stop_tx);
+
+    log::info!("Schema watch: starting with interval {}s", interval_secs);
+
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(interval_secs));
+        loop {
+            tokio::select! {
+                _ = &mut stop_rx => {
+                    log::info!("Schema watch: loop stopped");
+                    break;
+                }
+                _ = interval.tick() => {
+                    check_all_connections(&app).await;
+                }
+            }
+        }
+    });
+}
+
+/// Stop the running schema watch loop, if any.
+pub async fn stop_schema_watch_loop() {
+    if let Some(tx) = WATCH_STOP_TX.lock().await.take() {
+        let _ = tx.send(());
+    }
+}
+
+/// Restart the schema watch loop with a new interval.
+pub async fn restart_schema_watch_loop(app: AppHandle, interval_secs: u64) {
+    stop_schema_watch_loop().await;
+    start_schema_watch_loop(app, interval_secs).await;
+}
+
+async fn check_all_connections(app: &AppHandle) {
+    let active = crate::health_check::active_connection_ids().await;
+
+    // Drop baselines of connections that were closed in the meantime.
+    BASELINES
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .retain(|id, _| active.contains(id));
+
+    for connection_id in active {
+        if let Err(e) = check_connection(app, &connection_id).await {
+            log::debug!("Schema watch: check failed for {}: {}", connection_id, e);
+        }
+    }
+}
+
+/// Read the signature of one connection and emit an event when it changed.
+async fn check_connection<R: Runtime>(
+    app: &AppHandle<R>,
+    connection_id: &str,
+) -> Result<Option<SchemaChange>, String> {
+    let saved_conn = crate::commands::find_connection_by_id(app, connection_id)?;
+    let expanded = crate::commands::expand_ssh_connection_params(app, &saved_conn.params).await?;
+    let params = crate::commands::resolve_connection_params_with_id(&expanded, connection_id)?;
+
+    // Never open a pool just to check the schema.
+    let is_builtin = matches!(
+        params.driver.as_str(),
+        "mysql" | "postgres" | "sqlite" | "mssql"
+    );
+    if is_builtin && !crate::pool_manager::has_pool(&params, Some(connection_id)).await {
+        return Err("No active connection pool".into());
+    }
+
+    let drv = crate::commands::driver_for(&params.driver).await?;
+    let columns = tokio::time::timeout(CHECK_TIMEOUT, drv.get_schema_signature(&params, None))
+        .await
+        .map_err(|_| "Schema signature timed out".to_string())??;
+
+    let change = observe(connection_id, columns);
+    if let Some(change) = &change {
+        log::info!(
+            "Schema watch: schema of {} changed ({} added, {} dropped, {} altered)",
+            connection_id,
+            change.added_tables.len(),
+            change.dropped_tables.len(),
+            change.changed_tables.len()
+        );
+        if let Err(e) = app.emit(SCHEMA_CHANGED_EVENT, change) {
+            log::error!(
+                "Schema watch: failed to emit {} event: {}",
+                SCHEMA_CHANGED_EVENT,
+                e
+            );
+        }
+    }
+    Ok(change)
+}
+
+// ---------------------------------------------------------------------------
+// Tauri commands
+// ---------------------------------------------------------------------------
+
+/// Check a connection now instead of waiting for the next tick. Returns the
+/// change (also emitted as an event), or `None` when nothing changed or this
+/// was the first observation.
+#[tauri::command]
+pub async fn check_schema_changes<R: Runtime>(
+    app: AppHandle<R>,
+    connection_id: String,
+) -> Result<Option<SchemaChange>, String> {
+    check_connection(&app, &connection_id).await
+}
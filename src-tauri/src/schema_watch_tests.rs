@@ -0,0 +1,103 @@
+#[cfg(test)]
+mod tests {
+    use crate::models::ColumnSignature;
+    use crate::schema_watch::{
+        checksum, diff_signatures, forget, is_schema_ddl, note_local_ddl, observe,
+    };
+
+    fn col(table: &str, column: &str, data_type: &str) -> ColumnSignature {
+        ColumnSignature {
+            table: table.to_string(),
+            column: column.to_string(),
+            data_type: data_type.to_string(),
+        }
+    }
+
+    fn users() -> Vec<ColumnSignature> {
+        vec![
+            col("users", "id", "integer"),
+            col("users", "email", "text"),
+            col("orders", "id", "integer"),
+        ]
+    }
+
+    // --- checksum ---
+
+    #[test]
+    fn checksum_ignores_row_order() {
+        let mut reversed = users();
+        reversed.reverse();
+        assert_eq!(checksum(&users()), checksum(&reversed));
+    }
+
+    #[test]
+    fn checksum_changes_with_column_type() {
+        let mut retyped = users();
+        retyped[1].data_type = "varchar".to_string();
+        assert_ne!(checksum(&users()), checksum(&retyped));
+    }
+
+    // --- diff_signatures ---
+
+    #[test]
+    fn diff_reports_added_dropped_and_changed_tables() {
+        let new = vec![
+            col("users", "id", "bigint"),
+            col("users", "email", "text"),
+            col("invoices", "id", "integer"),
+        ];
+        let change = diff_signatures("c1", &users(), &new);
+        assert_eq!(change.connection_id, "c1");
+        assert_eq!(change.added_tables, vec!["invoices".to_string()]);
+        assert_eq!(change.dropped_tables, vec!["orders".to_string()]);
+        assert_eq!(change.changed_tables, vec!["users".to_string()]);
+    }
+
+    #[test]
+    fn diff_of_identical_signatures_is_empty() {
+        assert!(diff_signatures("c1", &users(), &users()).is_empty());
+    }
+
+    // --- is_schema_ddl ---
+
+    #[test]
+    fn detects_schema_ddl() {
+        assert!(is_schema_ddl("ALTER TABLE users ADD COLUMN age int"));
+        assert!(is_schema_ddl("-- migrate\ncreate table t(id int)"));
+        assert!(is_schema_ddl("DROP VIEW v"));
+        assert!(!is_schema_ddl("SELECT * FROM users"));
+        assert!(!is_schema_ddl("UPDATE users SET email = 'x'"));
+    }
+
+    // --- observe ---
+
+    #[test]
+    fn first_observation_only_records_baseline() {
+        let id = "schema-watch-first";
+        forget(id);
+        assert_eq!(observe(id, users()), None);
+        assert_eq!(observe(id, users()), None);
+    }
+
+    #[test]
+    fn observation_reports_external_change() {
+        let id = "schema-watch-change";
+        forget(id);
+        observe(id, users());
+        let mut new = users();
+        new.push(col("users", "age", "integer"));
+        let change = observe(id, new).expect("change");
+        assert_eq!(change.changed_tables, vec!["users".to_string()]);
+    }
+
+    #[test]
+    fn local_ddl_resets_baseline() {
+        let id = "schema-watch-local";
+        forget(id);
+        observe(id, users());
+        note_local_ddl(id, "ALTER TABLE users ADD COLUMN age int");
+        let mut new = users();
+        new.push(col("users", "age", "integer"));
+        assert_eq!(observe(id, new), None);
+    }
+}
@@ -0,0 +1,581 @@
+//! Storage for connection passwords, SSH credentials and AI API keys.
+//!
+//! Secrets go to the OS keychain. When the keychain cannot be reached (no
+//! Secret Service provider on Linux, a locked or missing keychain) they go
+//! to an encrypted file store instead: `secrets.enc`, sealed with
+//! AES-256-GCM under a random key kept owner-only in `secrets.key` beside
+//! it. The file store keeps passwords out of `connections.json`, which users
+//! sync and share; it does not protect against someone who can read the
+//! whole config directory.
+//!
+//! On machines without a keychain by design (a headless server), the
+//! `secretsFileOnly` setting keeps every secret in the file store. Its key
+//! can live elsewhere (`secretsKeyFile`) or come from a key management
+//! service (`secretsKeyCommand`, a command printing the key), and
+//! [`rotate_secrets_key`] re-encrypts the store under a new key.
+//!
+//! At startup, passwords still saved in plain text in `connections.json`
+//! and `ssh_connections.json` are moved into the store (see
+//! [`migrate_plaintext_secrets`]).
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use base64::Engine;
+use keyring::Entry;
+use once_cell::sync::Lazy;
+use ring::aead::{Aad, LessSafeKey, Nonce, UnboundKey, AES_256_GCM, NONCE_LEN};
+use ring::rand::{SecureRandom, SystemRandom};
+use serde::Serialize;
+
+use crate::keychain_utils::SERVICE_NAME;
+use crate::models::{SavedConnection, SshConnection};
+
+pub const SECRETS_FILE: &str = "secrets.enc";
+pub const SECRETS_KEY_FILE: &str = "secrets.key";
+
+const KEY_LEN: usize = 32;
+const SEALED_VERSION: u8 = 1;
+
+/// Serializes access to the file store: every write rewrites the whole file.
+static FILE_STORE_LOCK: Lazy<Mutex<()>> = Lazy::new(|| Mutex::new(()));
+
+/// The file store and whether it replaces the keychain, built from the
+/// config on first use (see [`file_store`] and [`invalidate_file_store`]).
+static FILE_STORE: Lazy<Mutex<Option<(Arc<FileSecretStore>, bool)>>> =
+    Lazy::new(|| Mutex::new(None));
+
+/// Where a secret was written.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SecretBackend {
+    Keychain,
+    EncryptedFile,
+}
+
+/// Encrypt `plaintext` under `key`: a version byte, a random nonce, then
+/// the ciphertext and its tag.
+pub fn seal(key: &[u8], plaintext: &[u8]) -> Result<Vec<u8>, String> {
+    let key = aead_key(key)?;
+    let mut nonce = [0u8; NONCE_LEN];
+    SystemRandom::new()
+        .fill(&mut nonce)
+        .map_err(|_| "Failed to generate a nonce".to_string())?;
+    let mut body = plaintext.to_vec();
+    key.seal_in_place_append_tag(Nonce::assume_unique_for_key(nonce), Aad::empty(), &mut body)
+        .map_err(|_| "Failed to encrypt secrets".to_string())?;
+
+    let mut sealed = Vec::with_capacity(1 + NONCE_LEN + body.len());
+    sealed.push(SEALED_VERSION);
+    sealed.extend_from_slice(&nonce);
+    sealed.extend_from_slice(&body);
+    Ok(sealed)
+}
+
+/// Decrypt what [`seal`] produced. Fails on a wrong key or altered data.
+pub fn open(key: &[u8], sealed: &[u8]) -> Result<Vec<u8>, String> {
+    let key = aead_key(key)?;
+    if sealed.len() < 1 + NONCE_LEN || sealed[0] != SEALED_VERSION {
+        return Err("Unrecognized secrets file format".to_string());
+    }
+    let nonce = Nonce::try_assume_unique_for_key(&sealed[1..1 + NONCE_LEN])
+        .map_err(|_| "Unrecognized secrets file format".to_string())?;
+    let mut body = sealed[1 + NONCE_LEN..].to_vec();
+    let plaintext = key
+        .open_in_place(nonce, Aad::empty(), &mut body)
+        .map_err(|_| "Failed to decrypt secrets: wrong key or corrupted file".to_string())?;
+    Ok(plaintext.to_vec())
+}
+
+fn aead_key(key: &[u8]) -> Result<LessSafeKey, String> {
+    UnboundKey::new(&AES_256_GCM, key)
+        .map(LessSafeKey::new)
+        .map_err(|_| format!("Secrets key must be {} bytes", KEY_LEN))
+}
+
+/// Write `bytes` to `path`, readable by the current user only.
+fn write_private(path: &Path, bytes: &[u8]) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let mut file = options.open(path).map_err(|e| e.to_string())?;
+    file.write_all(bytes).map_err(|e| e.to_string())
+}
+
+fn random_key() -> Result<Vec<u8>, String> {
+    let mut key = vec![0u8; KEY_LEN];
+    SystemRandom::new()
+        .fill(&mut key)
+        .map_err(|_| "Failed to generate a secrets key".to_string())?;
+    Ok(key)
+}
+
+/// The key printed by a key command: base64, surrounding whitespace ignored.
+pub fn decode_key(output: &str) -> Result<Vec<u8>, String> {
+    let key = base64::engine::general_purpose::STANDARD
+        .decode(output.trim())
+        .map_err(|e| format!("The secrets key command must print a base64 key: {}", e))?;
+    if key.len() != KEY_LEN {
+        return Err(format!(
+            "The secrets key command printed a {}-byte key; {} bytes are needed",
+            key.len(),
+            KEY_LEN
+        ));
+    }
+    Ok(key)
+}
+
+fn run_key_command(command: &str) -> Result<Vec<u8>, String> {
+    let (shell, flag) = if cfg!(windows) {
+        ("cmd", "/C")
+    } else {
+        ("sh", "-c")
+    };
+    let output = std::process::Command::new(shell)
+        .args([flag, command])
+        .output()
+        .map_err(|e| format!("Failed to run the secrets key command: {}", e))?;
+    if !output.status.success() {
+        return Err(format!(
+            "The secrets key command failed ({}): {}",
+            output.status,
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    decode_key(&String::from_utf8_lossy(&output.stdout))
+}
+
+/// Where the key of a [`FileSecretStore`] comes from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum KeySource {
+    /// A key file, created on first use.
+    File(PathBuf),
+    /// A command printing the key in base64, such as a KMS or vault CLI
+    /// call. The key is never written to disk.
+    Command(String),
+}
+
+/// `<key file>.new`: the key a rotation is switching to. It only exists
+/// while a rotation is in progress, or after one was interrupted.
+fn pending_key_path(path: &Path) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(".new");
+    path.with_file_name(name)
+}
+
+/// Encrypted file fallback for when the keychain is unavailable.
+pub struct FileSecretStore {
+    dir: PathBuf,
+    key_source: KeySource,
+    /// The key once read, so a key command runs once per store.
+    key: Mutex<Option<Vec<u8>>>,
+}
+
+impl FileSecretStore {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        let dir = dir.into();
+        let key_source = KeySource::File(dir.join(SECRETS_KEY_FILE));
+        Self {
+            dir,
+            key_source,
+            key: Mutex::new(None),
+        }
+    }
+
+    pub fn with_key_source(mut self, key_source: KeySource) -> Self {
+        self.key_source = key_source;
+        self.key = Mutex::new(None);
+        self
+    }
+
+    fn key(&self, create: bool) -> Result<Option<Vec<u8>>, String> {
+        if let Some(key) = self.key.lock().unwrap().clone() {
+            return Ok(Some(key));
+        }
+        let key = self.read_key(create)?;
+        if let Some(key) = &key {
+            self.remember_key(key);
+        }
+        Ok(key)
+    }
+
+    fn remember_key(&self, key: &[u8]) {
+        *self.key.lock().unwrap() = Some(key.to_vec());
+    }
+
+    fn read_key(&self, create: bool) -> Result<Option<Vec<u8>>, String> {
+        let path = match &self.key_source {
+            KeySource::File(path) => path,
+            KeySource::Command(command) => return run_key_command(command).map(Some),
+        };
+        if path.exists() {
+            let key = fs::read(path).map_err(|e| e.to_string())?;
+            return Ok(Some(key));
+        }
+        if !create {
+            return Ok(None);
+        }
+        let key = random_key()?;
+        write_private(path, &key)?;
+        Ok(Some(key))
+    }
+
+    fn load(&self) -> Result<BTreeMap<String, String>, String> {
+        let path = self.dir.join(SECRETS_FILE);
+        if !path.exists() {
+            return Ok(BTreeMap::new());
+        }
+        let sealed = fs::read(&path).map_err(|e| e.to_string())?;
+        let plaintext = match self.key(false)? {
+            Some(key) => open(&key, &sealed).or_else(|e| self.finish_rotation(&sealed).ok_or(e))?,
+            None => self.finish_rotation(&sealed).ok_or_else(|| {
+                format!(
+                    "{} is missing; cannot read {}",
+                    SECRETS_KEY_FILE, SECRETS_FILE
+                )
+            })?,
+        };
+        serde_json::from_slice(&plaintext).map_err(|e| e.to_string())
+    }
+
+    /// Open `sealed` with the key of an interrupted rotation and, when that
+    /// works, make it the current key.
+    fn finish_rotation(&self, sealed: &[u8]) -> Option<Vec<u8>> {
+        let KeySource::File(path) = &self.key_source else {
+            return None;
+        };
+        let pending = pending_key_path(path);
+        let key = fs::read(&pending).ok()?;
+        let plaintext = open(&key, sealed).ok()?;
+        log::warn!("Completing an interrupted rotation of the secrets key");
+        if let Err(e) = fs::rename(&pending, path) {
+            log::warn!("Failed to complete the secrets key rotation: {}", e);
+        }
+        self.remember_key(&key);
+        Some(plaintext)
+    }
+
+    fn save_with(&self, key: &[u8], secrets: &BTreeMap<String, String>) -> Result<(), String> {
+        let plaintext = serde_json::to_vec(secrets).map_err(|e| e.to_string())?;
+        let sealed = seal(key, &plaintext)?;
+        // Written aside and renamed, so the file is never half-sealed.
+        let path = self.dir.join(SECRETS_FILE);
+        let temp = self.dir.join(format!("{}.tmp", SECRETS_FILE));
+        write_private(&temp, &sealed)?;
+        fs::rename(&temp, &path).map_err(|e| e.to_string())
+    }
+
+    fn save(&self, secrets: &BTreeMap<String, String>) -> Result<(), String> {
+        let path = self.dir.join(SECRETS_FILE);
+        if secrets.is_empty() {
+            return match fs::remove_file(&path) {
+                Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e.to_string()),
+                _ => Ok(()),
+            };
+        }
+        let key = self.key(true)?.expect("key is created on demand");
+        self.save_with(&key, secrets)
+    }
+
+    /// Re-encrypt the store under a new key and return how many secrets it
+    /// holds. A key file is replaced by a new random key; the new key is
+    /// written as `<key file>.new` first, so an interruption leaves a key
+    /// that opens the store. A key command is expected to print the new
+    /// key already: `previous_key` (base64) opens the store.
+    pub fn rotate_key(&self, previous_key: Option<&str>) -> Result<usize, String> {
+        let _guard = FILE_STORE_LOCK.lock().unwrap();
+        match &self.key_source {
+            KeySource::File(path) => {
+                let secrets = self.load()?;
+                let pending = pending_key_path(path);
+                let key = random_key()?;
+                write_private(&pending, &key)?;
+                if !secrets.is_empty() {
+                    self.save_with(&key, &secrets)?;
+                }
+                fs::rename(&pending, path).map_err(|e| e.to_string())?;
+                self.remember_key(&key);
+                Ok(secrets.len())
+            }
+            KeySource::Command(_) => {
+                let previous = decode_key(previous_key.ok_or(
+                    "The secrets key comes from a command: pass the previous key to re-encrypt the store",
+                )?)?;
+                let path = self.dir.join(SECRETS_FILE);
+                if !path.exists() {
+                    return Ok(0);
+                }
+                let sealed = fs::read(&path).map_err(|e| e.to_string())?;
+                let secrets: BTreeMap<String, String> =
+                    serde_json::from_slice(&open(&previous, &sealed)?)
+                        .map_err(|e| e.to_string())?;
+                // The command prints the new key now.
+                *self.key.lock().unwrap() = None;
+                let key = self.key(true)?.expect("key commands always return a key");
+                self.save_with(&key, &secrets)?;
+                Ok(secrets.len())
+            }
+        }
+    }
+
+    pub fn get(&self, account: &str) -> Result<Option<String>, String> {
+        let _guard = FILE_STORE_LOCK.lock().unwrap();
+        Ok(self.load()?.remove(account))
+    }
+
+    pub fn set(&self, account: &str, value: &str) -> Result<(), String> {
+        let _guard = FILE_STORE_LOCK.lock().unwrap();
+        let mut secrets = self.load()?;
+        secrets.insert(account.to_string(), value.to_string());
+        self.save(&secrets)
+    }
+
+    pub fn delete(&self, account: &str) -> Result<(), String> {
+        let _guard = FILE_STORE_LOCK.lock().unwrap();
+        if !self.dir.join(SECRETS_FILE).exists() {
+            return Ok(());
+        }
+        let mut secrets = self.load()?;
+        if secrets.remove(account).is_some() {
+            self.save(&secrets)?;
+        }
+        Ok(())
+    }
+}
+
+fn configured(value: Option<String>) -> Option<String> {
+    value.filter(|v| !v.trim().is_empty())
+}
+
+/// The file store in the shared config directory: keychain entries are not
+/// per profile, so neither is the fallback. Its key source and whether it
+/// replaces the keychain come from the default profile's config. Built once
+/// and kept until [`invalidate_file_store`].
+fn file_store() -> (Arc<FileSecretStore>, bool) {
+    let mut cached = FILE_STORE.lock().unwrap();
+    if let Some((store, file_only)) = cached.as_ref() {
+        return (store.clone(), *file_only);
+    }
+    let base_dir = crate::paths::get_base_config_dir();
+    let config = crate::config::read_config_file(&base_dir.join("config.json")).unwrap_or_default();
+    let mut store = FileSecretStore::new(base_dir);
+    if let Some(command) = configured(config.secrets_key_command) {
+        store = store.with_key_source(KeySource::Command(command));
+    } else if let Some(path) = configured(config.secrets_key_file) {
+        store = store.with_key_source(KeySource::File(PathBuf::from(path)));
+    }
+    let store = Arc::new(store);
+    let file_only = config.secrets_file_only.unwrap_or(false);
+    *cached = Some((store.clone(), file_only));
+    (store, file_only)
+}
+
+/// Forget the cached file store and its key, so the next secret access
+/// reads the secret storage settings again. Called when the config is
+/// saved and after a key rotation.
+pub fn invalidate_file_store() {
+    *FILE_STORE.lock().unwrap() = None;
+}
+
+/// Errors meaning the keychain itself cannot be used, as opposed to a
+/// missing or malformed entry.
+fn keychain_unavailable(e: &keyring::Error) -> bool {
+    matches!(
+        e,
+        keyring::Error::PlatformFailure(_) | keyring::Error::NoStorageAccess(_)
+    )
+}
+
+/// Store `value` under `account`, in the keychain when possible.
+pub fn set_secret(account: &str, value: &str) -> Result<SecretBackend, String> {
+    let (file_store, file_only) = file_store();
+    if file_only {
+        file_store.set(account, value)?;
+        return Ok(SecretBackend::EncryptedFile);
+    }
+    match Entry::new(SERVICE_NAME, account).and_then(|entry| entry.set_password(value)) {
+        Ok(()) => {
+            // A copy written while the keychain was down is now stale.
+            file_store.delete(account).ok();
+            Ok(SecretBackend::Keychain)
+        }
+        Err(e) if keychain_unavailable(&e) => {
+            log::warn!(
+                "Keychain unavailable ({}), storing {} in the encrypted file store",
+                e,
+                account
+            );
+            file_store.set(account, value)?;
+            Ok(SecretBackend::EncryptedFile)
+        }
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+/// The secret stored under `account`, from the keychain or the file store.
+pub fn get_secret(account: &str) -> Result<Option<String>, String> {
+    let (file_store, file_only) = file_store();
+    if file_only {
+        return file_store.get(account);
+    }
+    match Entry::new(SERVICE_NAME, account).and_then(|entry| entry.get_password()) {
+        Ok(value) => Ok(Some(value)),
+        Err(keyring::Error::NoEntry) => file_store.get(account),
+        Err(e) if keychain_unavailable(&e) => file_store.get(account),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+/// Remove `account` from both stores. Missing entries are not an error.
+pub fn delete_secret(account: &str) -> Result<(), String> {
+    let (file_store, file_only) = file_store();
+    if file_only {
+        return file_store.delete(account);
+    }
+    let keychain = match Entry::new(SERVICE_NAME, account).and_then(|e| e.delete_credential()) {
+        Ok(()) | Err(keyring::Error::NoEntry) => Ok(()),
+        Err(e) if keychain_unavailable(&e) => Ok(()),
+        Err(e) => Err(e.to_string()),
+    };
+    file_store.delete(account)?;
+    keychain
+}
+
+fn non_empty(value: &Option<String>) -> Option<&str> {
+    value.as_deref().filter(|v| !v.trim().is_empty())
+}
+
+/// Move the plaintext passwords of `connections` into `store` and mark the
+/// connections as keychain-backed. Connections already using the keychain
+/// are left alone. Returns how many connections were migrated.
+pub fn migrate_connections(
+    connections: &mut [SavedConnection],
+    store: &mut dyn FnMut(&str, &str) -> Result<(), String>,
+) -> Result<usize, String> {
+    let mut migrated = 0;
+    for conn in connections.iter_mut() {
+        let params = &mut conn.params;
+        if params.save_in_keychain.unwrap_or(false) {
+            continue;
+        }
+        let password = non_empty(&params.password);
+        // Legacy embedded SSH credentials, read from the keychain by the
+        // SSH connection migration once the connection is keychain-backed.
+        let ssh_password = non_empty(&params.ssh_password);
+        let ssh_passphrase = non_empty(&params.ssh_key_passphrase);
+        if password.is_none() && ssh_password.is_none() && ssh_passphrase.is_none() {
+            continue;
+        }
+        if let Some(password) = password {
+            store(&format!("{}:db", conn.id), password)?;
+        }
+        if let Some(ssh_password) = ssh_password {
+            store(&format!("{}:ssh", conn.id), ssh_password)?;
+        }
+        if let Some(ssh_passphrase) = ssh_passphrase {
+            store(&format!("{}:ssh_passphrase", conn.id), ssh_passphrase)?;
+        }
+        params.password = None;
+        params.ssh_password = None;
+        params.ssh_key_passphrase = None;
+        params.save_in_keychain = Some(true);
+        migrated += 1;
+    }
+    Ok(migrated)
+}
+
+/// [`migrate_connections`] for saved SSH connections.
+pub fn migrate_ssh_connections(
+    connections: &mut [SshConnection],
+    store: &mut dyn FnMut(&str, &str) -> Result<(), String>,
+) -> Result<usize, String> {
+    let mut migrated = 0;
+    for ssh in connections.iter_mut() {
+        if ssh.save_in_keychain.unwrap_or(false) {
+            continue;
+        }
+        let password = non_empty(&ssh.password);
+        let passphrase = non_empty(&ssh.key_passphrase);
+        if password.is_none() && passphrase.is_none() {
+            continue;
+        }
+        if let Some(password) = password {
+            store(&format!("{}:ssh", ssh.id), password)?;
+        }
+        if let Some(passphrase) = passphrase {
+            store(&format!("{}:ssh_passphrase", ssh.id), passphrase)?;
+        }
+        ssh.password = None;
+        ssh.key_passphrase = None;
+        ssh.save_in_keychain = Some(true);
+        migrated += 1;
+    }
+    Ok(migrated)
+}
+
+/// Move plaintext passwords out of the connection files in `config_dir`.
+/// Skipped in portable mode, where credentials are meant to travel with
+/// the app folder rather than stay in this machine's keychain.
+pub fn migrate_plaintext_secrets(config_dir: &Path) -> Result<usize, String> {
+    if crate::paths::get_portable_root().is_some() {
+        return Ok(0);
+    }
+    let mut store = |account: &str, value: &str| set_secret(account, value).map(|_| ());
+    let mut migrated = 0;
+
+    let conn_path = config_dir.join("connections.json");
+    if conn_path.exists() {
+        let _lock = crate::config_store::lock(&conn_path)?;
+        let mut file = crate::persistence::load_connections_file(&conn_path)?;
+        let count = migrate_connections(&mut file.connections, &mut store)?;
+        if count > 0 {
+            crate::persistence::write_connections_file(&conn_path, &file)?;
+        }
+        migrated += count;
+    }
+
+    let ssh_path = config_dir.join("ssh_connections.json");
+    if ssh_path.exists() {
+        let _lock = crate::config_store::lock(&ssh_path)?;
+        let mut ssh_connections = crate::persistence::load_ssh_connections_file(&ssh_path)?;
+        let count = migrate_ssh_connections(&mut ssh_connections, &mut store)?;
+        if count > 0 {
+            crate::persistence::write_ssh_connections_file(&ssh_path, &ssh_connections)?;
+        }
+        migrated += count;
+    }
+
+    if migrated > 0 {
+        log::info!(
+            "Moved plaintext credentials of {} connection(s) to the secret store",
+            migrated
+        );
+    }
+    Ok(migrated)
+}
+
+// ---------------------------------------------------------------------------
+// Tauri commands
+// ---------------------------------------------------------------------------
+
+/// Re-encrypt the encrypted file store under a new key; returns how many
+/// secrets were re-encrypted. With `secretsKeyCommand`, rotate the key in
+/// the key management service first and pass the previous key (base64).
+#[tauri::command]
+pub fn rotate_secrets_key(previous_key: Option<String>) -> Result<usize, String> {
+    let (file_store, _) = file_store();
+    let count = file_store.rotate_key(previous_key.as_deref())?;
+    invalidate_file_store();
+    log::info!("Rotated the secrets key ({} secret(s))", count);
+    Ok(count)
+}
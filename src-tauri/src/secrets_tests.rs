@@ -0,0 +1,267 @@
+#[cfg(test)]
+mod tests {
+    use crate::models::{ConnectionParams, SavedConnection, SshConnection};
+    use crate::secrets::{
+        decode_key, migrate_connections, migrate_ssh_connections, open, seal, FileSecretStore,
+        KeySource, SECRETS_FILE, SECRETS_KEY_FILE,
+    };
+    use base64::Engine;
+    use std::collections::HashMap;
+    use tempfile::TempDir;
+
+    const KEY: [u8; 32] = [7; 32];
+
+    fn saved_conn(
+        id: &str,
+        password: Option<&str>,
+        save_in_keychain: Option<bool>,
+    ) -> SavedConnection {
+        SavedConnection {
+            id: id.to_string(),
+            name: id.to_string(),
+            params: ConnectionParams {
+                driver: "postgres".to_string(),
+                password: password.map(|p| p.to_string()),
+                save_in_keychain,
+                ..Default::default()
+            },
+            group_id: None,
+            sort_order: None,
+            detect_json_in_text_columns: None,
+            protected: None,
+            read_replicas: None,
+            connect_on_startup: None,
+        }
+    }
+
+    fn ssh_conn(id: &str, password: Option<&str>, passphrase: Option<&str>) -> SshConnection {
+        SshConnection {
+            id: id.to_string(),
+            name: id.to_string(),
+            host: "bastion".to_string(),
+            port: 22,
+            user: "deploy".to_string(),
+            auth_type: None,
+            password: password.map(|p| p.to_string()),
+            key_file: None,
+            key_passphrase: passphrase.map(|p| p.to_string()),
+            save_in_keychain: None,
+        }
+    }
+
+    #[test]
+    fn seal_round_trips() {
+        let sealed = seal(&KEY, b"hunter2").unwrap();
+        assert!(!sealed.windows(7).any(|w| w == b"hunter2"));
+        assert_eq!(open(&KEY, &sealed).unwrap(), b"hunter2");
+    }
+
+    #[test]
+    fn seal_uses_a_fresh_nonce() {
+        assert_ne!(seal(&KEY, b"same").unwrap(), seal(&KEY, b"same").unwrap());
+    }
+
+    #[test]
+    fn open_rejects_wrong_key_and_tampering() {
+        let sealed = seal(&KEY, b"hunter2").unwrap();
+        assert!(open(&[8; 32], &sealed).is_err());
+
+        let mut tampered = sealed.clone();
+        *tampered.last_mut().unwrap() ^= 1;
+        assert!(open(&KEY, &tampered).is_err());
+        assert!(open(&KEY, &sealed[..5]).is_err());
+    }
+
+    #[test]
+    fn file_store_sets_gets_and_deletes() {
+        let dir = TempDir::new().unwrap();
+        let store = FileSecretStore::new(dir.path());
+        assert_eq!(store.get("a:db").unwrap(), None);
+
+        store.set("a:db", "one").unwrap();
+        store.set("b:ssh", "two").unwrap();
+        assert!(dir.path().join(SECRETS_KEY_FILE).exists());
+
+        // A second store over the same directory reads the same secrets.
+        let reopened = FileSecretStore::new(dir.path());
+        assert_eq!(reopened.get("a:db").unwrap().as_deref(), Some("one"));
+
+        reopened.delete("a:db").unwrap();
+        assert_eq!(store.get("a:db").unwrap(), None);
+        assert_eq!(store.get("b:ssh").unwrap().as_deref(), Some("two"));
+
+        store.delete("b:ssh").unwrap();
+        assert!(!dir.path().join(SECRETS_FILE).exists());
+    }
+
+    #[test]
+    fn file_store_does_not_write_plaintext() {
+        let dir = TempDir::new().unwrap();
+        FileSecretStore::new(dir.path())
+            .set("a:db", "correct horse")
+            .unwrap();
+        let raw = std::fs::read(dir.path().join(SECRETS_FILE)).unwrap();
+        assert!(!String::from_utf8_lossy(&raw).contains("correct horse"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn file_store_key_is_owner_only() {
+        use std::os::unix::fs::PermissionsExt;
+        let dir = TempDir::new().unwrap();
+        FileSecretStore::new(dir.path()).set("a:db", "x").unwrap();
+        let mode = std::fs::metadata(dir.path().join(SECRETS_KEY_FILE))
+            .unwrap()
+            .permissions()
+            .mode();
+        assert_eq!(mode & 0o777, 0o600);
+    }
+
+    #[test]
+    fn file_store_fails_without_its_key() {
+        let dir = TempDir::new().unwrap();
+        FileSecretStore::new(dir.path()).set("a:db", "x").unwrap();
+        std::fs::remove_file(dir.path().join(SECRETS_KEY_FILE)).unwrap();
+        assert!(FileSecretStore::new(dir.path()).get("a:db").is_err());
+    }
+
+    #[test]
+    fn rotation_re_encrypts_under_a_new_key() {
+        let dir = TempDir::new().unwrap();
+        let store = FileSecretStore::new(dir.path());
+        store.set("a:db", "one").unwrap();
+        store.set("b:ssh", "two").unwrap();
+        let key_path = dir.path().join(SECRETS_KEY_FILE);
+        let old_key = std::fs::read(&key_path).unwrap();
+
+        assert_eq!(store.rotate_key(None).unwrap(), 2);
+        let new_key = std::fs::read(&key_path).unwrap();
+        assert_ne!(old_key, new_key);
+        let sealed = std::fs::read(dir.path().join(SECRETS_FILE)).unwrap();
+        assert!(open(&old_key, &sealed).is_err());
+        assert_eq!(store.get("a:db").unwrap().as_deref(), Some("one"));
+        assert!(!dir.path().join("secrets.key.new").exists());
+    }
+
+    #[test]
+    fn interrupted_rotation_keeps_the_store_readable() {
+        let dir = TempDir::new().unwrap();
+        let store = FileSecretStore::new(dir.path());
+        store.set("a:db", "one").unwrap();
+
+        // Crash after the store was resealed but before the key moved.
+        let key_path = dir.path().join(SECRETS_KEY_FILE);
+        let pending = dir.path().join("secrets.key.new");
+        std::fs::write(&pending, KEY).unwrap();
+        let sealed = seal(&KEY, br#"{"a:db":"one"}"#).unwrap();
+        std::fs::write(dir.path().join(SECRETS_FILE), sealed).unwrap();
+
+        assert_eq!(store.get("a:db").unwrap().as_deref(), Some("one"));
+        assert_eq!(std::fs::read(&key_path).unwrap(), KEY);
+        assert!(!pending.exists());
+    }
+
+    #[test]
+    fn key_commands_print_a_base64_key() {
+        let encoded = base64::engine::general_purpose::STANDARD.encode(KEY);
+        assert_eq!(decode_key(&format!("{}\n", encoded)).unwrap(), KEY);
+        assert!(decode_key("not base64!").is_err());
+        assert!(decode_key("c2hvcnQ=").is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn key_command_store_rotates_with_the_previous_key() {
+        let dir = TempDir::new().unwrap();
+        let engine = base64::engine::general_purpose::STANDARD;
+        let command = |key: &[u8]| KeySource::Command(format!("echo {}", engine.encode(key)));
+
+        let store = FileSecretStore::new(dir.path()).with_key_source(command(&KEY));
+        store.set("a:db", "one").unwrap();
+        assert!(!dir.path().join(SECRETS_KEY_FILE).exists());
+
+        let rotated = FileSecretStore::new(dir.path()).with_key_source(command(&[9; 32]));
+        assert!(rotated.get("a:db").is_err());
+        assert!(rotated.rotate_key(None).is_err());
+        assert_eq!(rotated.rotate_key(Some(&engine.encode(KEY))).unwrap(), 1);
+        assert_eq!(rotated.get("a:db").unwrap().as_deref(), Some("one"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn key_command_runs_once_per_store() {
+        let dir = TempDir::new().unwrap();
+        let runs = dir.path().join("runs");
+        let command = format!(
+            "echo run >> '{}'; echo {}",
+            runs.display(),
+            base64::engine::general_purpose::STANDARD.encode(KEY)
+        );
+        let store = FileSecretStore::new(dir.path()).with_key_source(KeySource::Command(command));
+        store.set("a:db", "one").unwrap();
+        store.set("b:db", "two").unwrap();
+        assert_eq!(store.get("a:db").unwrap().as_deref(), Some("one"));
+        assert_eq!(std::fs::read_to_string(&runs).unwrap().lines().count(), 1);
+    }
+
+    #[test]
+    fn migrates_plaintext_connection_passwords() {
+        let mut connections = vec![
+            saved_conn("plain", Some("pw"), None),
+            saved_conn("opted-out", Some("pw2"), Some(false)),
+            saved_conn("keychain", Some("kept"), Some(true)),
+            saved_conn("no-password", None, None),
+        ];
+        connections[0].params.ssh_password = Some("legacy-ssh".to_string());
+
+        let mut stored = HashMap::new();
+        let migrated = migrate_connections(&mut connections, &mut |account, value| {
+            stored.insert(account.to_string(), value.to_string());
+            Ok(())
+        })
+        .unwrap();
+
+        assert_eq!(migrated, 2);
+        assert_eq!(stored["plain:db"], "pw");
+        assert_eq!(stored["plain:ssh"], "legacy-ssh");
+        assert_eq!(stored["opted-out:db"], "pw2");
+        assert_eq!(stored.len(), 3);
+
+        assert_eq!(connections[0].params.password, None);
+        assert_eq!(connections[0].params.ssh_password, None);
+        assert_eq!(connections[0].params.save_in_keychain, Some(true));
+        assert_eq!(connections[2].params.password.as_deref(), Some("kept"));
+        assert_eq!(connections[3].params.save_in_keychain, None);
+    }
+
+    #[test]
+    fn failed_store_leaves_connection_untouched() {
+        let mut connections = vec![saved_conn("plain", Some("pw"), None)];
+        let result = migrate_connections(&mut connections, &mut |_, _| Err("locked".to_string()));
+        assert!(result.is_err());
+        assert_eq!(connections[0].params.password.as_deref(), Some("pw"));
+        assert_eq!(connections[0].params.save_in_keychain, None);
+    }
+
+    #[test]
+    fn migrates_plaintext_ssh_credentials() {
+        let mut connections = vec![
+            ssh_conn("pw", Some("secret"), None),
+            ssh_conn("key", None, Some("phrase")),
+            ssh_conn("agent", None, Some("  ")),
+        ];
+        let mut stored = HashMap::new();
+        let migrated = migrate_ssh_connections(&mut connections, &mut |account, value| {
+            stored.insert(account.to_string(), value.to_string());
+            Ok(())
+        })
+        .unwrap();
+
+        assert_eq!(migrated, 2);
+        assert_eq!(stored["pw:ssh"], "secret");
+        assert_eq!(stored["key:ssh_passphrase"], "phrase");
+        assert_eq!(connections[1].key_passphrase, None);
+        assert_eq!(connections[1].save_in_keychain, Some(true));
+        assert_eq!(connections[2].save_in_keychain, None);
+    }
+}
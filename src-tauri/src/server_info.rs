@@ -0,0 +1,396 @@
+//! Server flavor and version detection.
+//!
+//! Several servers answer to the same driver: MariaDB and Vitess to
+//! `mysql`, CockroachDB and YugabyteDB to `postgres`. When a connection is
+//! registered, its version string and extensions are read once, parsed into
+//! a [`ServerInfo`] and cached by connection id until the connection closes.
+//!
+//! Drivers gate features on it. Where they have connection params they look
+//! it up with [`for_params`]; DDL generation takes no params, so the DDL
+//! commands run it inside [`scope`] and drivers read [`current`], which
+//! keeps driver signatures unchanged.
+//!
+//! Drivers with the `server_status` capability also report their status
+//! counters and configuration variables, for the monitoring dashboard.
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::RwLock;
+
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use tauri::{AppHandle, Runtime};
+use tokio::task::futures::TaskLocalFuture;
+
+use crate::commands::{
+    driver_for, expand_ssh_connection_params, find_connection_by_id,
+    resolve_connection_params_with_id,
+};
+use crate::models::{ConnectionParams, ServerValue};
+
+static SERVER_INFO: Lazy<RwLock<HashMap<String, ServerInfo>>> =
+    Lazy::new(|| RwLock::new(HashMap::new()));
+
+tokio::task_local! {
+    static CURRENT: Option<ServerInfo>;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ServerFlavor {
+    Mysql,
+    Mariadb,
+    Vitess,
+    Postgres,
+    Cockroachdb,
+    Yugabytedb,
+    Sqlite,
+    Unknown,
+}
+
+impl ServerFlavor {
+    pub fn name(&self) -> &'static str {
+        match self {
+            ServerFlavor::Mysql => "MySQL",
+            ServerFlavor::Mariadb => "MariaDB",
+            ServerFlavor::Vitess => "Vitess",
+            ServerFlavor::Postgres => "PostgreSQL",
+            ServerFlavor::Cockroachdb => "CockroachDB",
+            ServerFlavor::Yugabytedb => "YugabyteDB",
+            ServerFlavor::Sqlite => "SQLite",
+            ServerFlavor::Unknown => "Unknown server",
+        }
+    }
+}
+
+/// `major.minor.patch`, compared in that order.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+pub struct ServerVersion {
+    pub major: u32,
+    pub minor: u32,
+    pub patch: u32,
+}
+
+impl ServerVersion {
+    pub fn at_least(&self, major: u32, minor: u32, patch: u32) -> bool {
+        (self.major, self.minor, self.patch) >= (major, minor, patch)
+    }
+}
+
+impl std::fmt::Display for ServerVersion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
+/// SQL features that differ between flavors and versions.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ServerFeatures {
+    /// `WITH` common table expressions.
+    pub ctes: bool,
+    /// `OVER (...)` window functions.
+    pub window_functions: bool,
+    /// `RETURNING` on INSERT, UPDATE or DELETE.
+    pub returning: bool,
+    /// `CREATE ROLE` and role grants.
+    pub roles: bool,
+    /// `ALTER TABLE ... RENAME COLUMN`.
+    pub rename_column: bool,
+    /// `GENERATED ... AS IDENTITY` columns.
+    pub identity_columns: bool,
+    /// Privileges readable from the catalog ACLs (`aclexplode`).
+    pub catalog_acls: bool,
+    /// `FOREIGN KEY` constraints.
+    pub foreign_keys: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ServerInfo {
+    pub flavor: ServerFlavor,
+    /// The product's own version; for YugabyteDB the PostgreSQL version it
+    /// is compatible with, since that decides which SQL it accepts.
+    pub version: ServerVersion,
+    /// As reported by the server.
+    pub version_string: String,
+    pub features: ServerFeatures,
+    /// Installed extensions that change the catalog, e.g. `timescaledb`.
+    pub extensions: Vec<String>,
+}
+
+impl ServerInfo {
+    pub fn has_extension(&self, name: &str) -> bool {
+        self.extensions.iter().any(|ext| ext == name)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Detection (testable; pure)
+// ---------------------------------------------------------------------------
+
+/// The first `major[.minor[.patch]]` in `text`, e.g. `8.0.32` in
+/// `8.0.32-0ubuntu0.22.04.2`.
+pub fn parse_version(text: &str) -> ServerVersion {
+    let start = match text.find(|c: char| c.is_ascii_digit()) {
+        Some(start) => start,
+        None => return ServerVersion::default(),
+    };
+    let numbers: Vec<u32> = text[start..]
+        .split(|c: char| !c.is_ascii_digit() && c != '.')
+        .next()
+        .unwrap_or("")
+        .split('.')
+        .map_while(|part| part.parse().ok())
+        .collect();
+    ServerVersion {
+        major: numbers.first().copied().unwrap_or(0),
+        minor: numbers.get(1).copied().unwrap_or(0),
+        patch: numbers.get(2).copied().unwrap_or(0),
+    }
+}
+
+fn flavor_of(driver: &str, version_string: &str) -> ServerFlavor {
+    let lower = version_string.to_ascii_lowercase();
+    if lower.contains("mariadb") {
+        ServerFlavor::Mariadb
+    } else if lower.contains("vitess") {
+        ServerFlavor::Vitess
+    } else if lower.contains("cockroachdb") {
+        ServerFlavor::Cockroachdb
+    } else if lower.contains("-yb-") {
+        ServerFlavor::Yugabytedb
+    } else {
+        match driver {
+            "mysql" => ServerFlavor::Mysql,
+            "postgres" => ServerFlavor::Postgres,
+            "sqlite" => ServerFlavor::Sqlite,
+            _ => ServerFlavor::Unknown,
+        }
+    }
+}
+
+/// What a `flavor` server at `version` supports. Unknown servers support
+/// nothing, so callers fall back to the most portable SQL.
+pub fn features_of(flavor: ServerFlavor, version: ServerVersion) -> ServerFeatures {
+    let v = |major, minor, patch| version.at_least(major, minor, patch);
+    match flavor {
+        ServerFlavor::Mysql => ServerFeatures {
+            ctes: v(8, 0, 0),
+            window_functions: v(8, 0, 0),
+            returning: false,
+            roles: v(8, 0, 0),
+            rename_column: v(8, 0, 0),
+            identity_columns: false,
+            catalog_acls: false,
+            foreign_keys: true,
+        },
+        ServerFlavor::Mariadb => ServerFeatures {
+            ctes: v(10, 2, 1),
+            window_functions: v(10, 2, 0),
+            returning: v(10, 5, 0),
+            roles: v(10, 0, 5),
+            rename_column: v(10, 5, 2),
+            identity_columns: false,
+            catalog_acls: false,
+            foreign_keys: true,
+        },
+        // Vitess passes SQL through to the MySQL behind it, but manages no
+        // users of its own and cannot enforce foreign keys across shards.
+        ServerFlavor::Vitess => ServerFeatures {
+            ctes: v(8, 0, 0),
+            window_functions: v(8, 0, 0),
+            returning: false,
+            roles: false,
+            rename_column: v(8, 0, 0),
+            identity_columns: false,
+            catalog_acls: false,
+            foreign_keys: false,
+        },
+        ServerFlavor::Postgres | ServerFlavor::Yugabytedb => ServerFeatures {
+            ctes: true,
+            window_functions: true,
+            returning: true,
+            roles: true,
+            rename_column: true,
+            identity_columns: v(10, 0, 0),
+            catalog_acls: true,
+            foreign_keys: true,
+        },
+        ServerFlavor::Cockroachdb => ServerFeatures {
+            ctes: true,
+            window_functions: true,
+            returning: true,
+            roles: true,
+            rename_column: true,
+            identity_columns: v(21, 2, 0),
+            catalog_acls: false,
+            foreign_keys: true,
+        },
+        ServerFlavor::Sqlite => ServerFeatures {
+            ctes: v(3, 8, 3),
+            window_functions: v(3, 25, 0),
+            returning: v(3, 35, 0),
+            roles: false,
+            rename_column: v(3, 25, 0),
+            identity_columns: false,
+            catalog_acls: false,
+            foreign_keys: true,
+        },
+        ServerFlavor::Unknown => ServerFeatures::default(),
+    }
+}
+
+/// Parse what `driver`'s server reported as its version.
+///
+/// MariaDB may prefix its version with `5.5.5-` for old replication
+/// clients; Vitess and PlanetScale append `-Vitess` to the MySQL version
+/// they emulate; CockroachDB reports `CockroachDB CCL v23.1.11 (...)`; YugabyteDB
+/// reports a PostgreSQL version string with a `-YB-` release suffix.
+pub fn detect(driver: &str, version_string: &str) -> ServerInfo {
+    let flavor = flavor_of(driver, version_string);
+    let number = match flavor {
+        ServerFlavor::Mariadb => version_string
+            .strip_prefix("5.5.5-")
+            .unwrap_or(version_string),
+        _ => version_string,
+    };
+    let version = parse_version(number);
+    ServerInfo {
+        flavor,
+        version,
+        version_string: version_string.to_string(),
+        features: features_of(flavor, version),
+        extensions: Vec::new(),
+    }
+}
+
+/// The `values` whose name contains `filter`, ignoring case.
+pub fn filter_values(values: Vec<ServerValue>, filter: Option<&str>) -> Vec<ServerValue> {
+    let needle = match filter.map(str::trim) {
+        Some(needle) if !needle.is_empty() => needle.to_lowercase(),
+        _ => return values,
+    };
+    values
+        .into_iter()
+        .filter(|value| value.name.to_lowercase().contains(&needle))
+        .collect()
+}
+
+// ---------------------------------------------------------------------------
+// Cache
+// ---------------------------------------------------------------------------
+
+pub fn remember(connection_id: &str, info: ServerInfo) {
+    SERVER_INFO
+        .write()
+        .unwrap()
+        .insert(connection_id.to_string(), info);
+}
+
+pub fn cached(connection_id: &str) -> Option<ServerInfo> {
+    SERVER_INFO.read().unwrap().get(connection_id).cloned()
+}
+
+/// Drop what was detected for a connection that has been closed.
+pub fn forget(connection_id: &str) {
+    SERVER_INFO.write().unwrap().remove(connection_id);
+}
+
+/// The server behind `params`, if it has been detected.
+pub fn for_params(params: &ConnectionParams) -> Option<ServerInfo> {
+    params.connection_id.as_deref().and_then(cached)
+}
+
+/// Run `future` with `info` available through [`current`].
+pub fn scope<F: Future>(
+    info: Option<ServerInfo>,
+    future: F,
+) -> TaskLocalFuture<Option<ServerInfo>, F> {
+    CURRENT.scope(info, future)
+}
+
+/// The server the running DDL is generated for, if known.
+pub fn current() -> Option<ServerInfo> {
+    CURRENT.try_with(|info| info.clone()).ok().flatten()
+}
+
+// ---------------------------------------------------------------------------
+// Tauri commands
+// ---------------------------------------------------------------------------
+
+async fn resolve<R: Runtime>(
+    app: &AppHandle<R>,
+    connection_id: &str,
+) -> Result<(String, ConnectionParams), String> {
+    let saved_conn = find_connection_by_id(app, connection_id)?;
+    let expanded = expand_ssh_connection_params(app, &saved_conn.params).await?;
+    let params = resolve_connection_params_with_id(&expanded, connection_id)?;
+    Ok((saved_conn.params.driver, params))
+}
+
+/// Ask the server for its version and cache what it says.
+pub async fn detect_server<R: Runtime>(
+    app: &AppHandle<R>,
+    connection_id: &str,
+) -> Result<ServerInfo, String> {
+    let (driver, params) = resolve(app, connection_id).await?;
+    let drv = driver_for(&driver).await?;
+    let version_string = drv.get_server_version(&params).await?;
+    let mut info = detect(&driver, &version_string);
+    info.extensions = match drv.get_server_extensions(&params).await {
+        Ok(extensions) => extensions,
+        Err(e) => {
+            log::debug!("Could not list the extensions of {}: {}", connection_id, e);
+            Vec::new()
+        }
+    };
+    log::info!(
+        "Connection {} is {} {} ({})",
+        connection_id,
+        info.flavor.name(),
+        info.version,
+        info.version_string
+    );
+    remember(connection_id, info.clone());
+    Ok(info)
+}
+
+#[tauri::command]
+pub async fn get_server_info<R: Runtime>(
+    app: AppHandle<R>,
+    connection_id: String,
+) -> Result<ServerInfo, String> {
+    match cached(&connection_id) {
+        Some(info) => Ok(info),
+        None => detect_server(&app, &connection_id).await,
+    }
+}
+
+#[tauri::command]
+pub async fn get_server_status<R: Runtime>(
+    app: AppHandle<R>,
+    connection_id: String,
+    filter: Option<String>,
+) -> Result<Vec<ServerValue>, String> {
+    let (driver, params) = resolve(&app, &connection_id).await?;
+    let status = driver_for(&driver)
+        .await?
+        .get_server_status(&params)
+        .await?;
+    Ok(filter_values(status, filter.as_deref()))
+}
+
+#[tauri::command]
+pub async fn get_server_variables<R: Runtime>(
+    app: AppHandle<R>,
+    connection_id: String,
+    filter: Option<String>,
+) -> Result<Vec<ServerValue>, String> {
+    let (driver, params) = resolve(&app, &connection_id).await?;
+    let variables = driver_for(&driver)
+        .await?
+        .get_server_variables(&params)
+        .await?;
+    Ok(filter_values(variables, filter.as_deref()))
+}
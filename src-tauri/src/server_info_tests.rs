@@ -0,0 +1,148 @@
+#[cfg(test)]
+mod tests {
+    use crate::models::ServerValue;
+    use crate::server_info::{
+        current, detect, filter_values, parse_version, scope, ServerFlavor, ServerVersion,
+    };
+
+    fn version(major: u32, minor: u32, patch: u32) -> ServerVersion {
+        ServerVersion {
+            major,
+            minor,
+            patch,
+        }
+    }
+
+    #[test]
+    fn versions_are_read_from_the_first_number() {
+        assert_eq!(parse_version("8.0.32-0ubuntu0.22.04.2"), version(8, 0, 32));
+        assert_eq!(
+            parse_version("PostgreSQL 15.3 on x86_64-pc-linux-gnu"),
+            version(15, 3, 0)
+        );
+        assert_eq!(parse_version("PostgreSQL 16beta1"), version(16, 0, 0));
+        assert_eq!(parse_version("3.45.1"), version(3, 45, 1));
+        assert_eq!(parse_version("no digits"), ServerVersion::default());
+    }
+
+    #[test]
+    fn mysql_and_mariadb_are_told_apart() {
+        let mysql = detect("mysql", "5.7.44-log");
+        assert_eq!(mysql.flavor, ServerFlavor::Mysql);
+        assert!(!mysql.features.ctes);
+        assert!(!mysql.features.roles);
+
+        let mysql8 = detect("mysql", "8.0.36");
+        assert!(mysql8.features.ctes);
+        assert!(mysql8.features.roles);
+        assert!(!mysql8.features.returning);
+
+        let mariadb = detect("mysql", "10.11.6-MariaDB-0+deb12u1");
+        assert_eq!(mariadb.flavor, ServerFlavor::Mariadb);
+        assert_eq!(mariadb.version, version(10, 11, 6));
+        assert!(mariadb.features.ctes);
+        assert!(mariadb.features.returning);
+    }
+
+    #[test]
+    fn mariadb_replication_prefix_is_skipped() {
+        let info = detect("mysql", "5.5.5-10.4.32-MariaDB");
+        assert_eq!(info.flavor, ServerFlavor::Mariadb);
+        assert_eq!(info.version, version(10, 4, 32));
+        assert!(!info.features.returning);
+    }
+
+    #[test]
+    fn vitess_is_recognised_with_its_limits() {
+        let vitess = detect("mysql", "8.0.30-Vitess");
+        assert_eq!(vitess.flavor, ServerFlavor::Vitess);
+        assert_eq!(vitess.version, version(8, 0, 30));
+        assert!(vitess.features.ctes);
+        assert!(!vitess.features.roles);
+        assert!(!vitess.features.foreign_keys);
+
+        let planetscale = detect("mysql", "8.0.31-vitess");
+        assert_eq!(planetscale.flavor, ServerFlavor::Vitess);
+        assert!(detect("mysql", "8.0.36").features.foreign_keys);
+    }
+
+    #[test]
+    fn postgres_compatible_servers_are_recognised() {
+        let postgres = detect("postgres", "PostgreSQL 9.6.24 on x86_64-pc-linux-gnu");
+        assert_eq!(postgres.flavor, ServerFlavor::Postgres);
+        assert!(!postgres.features.identity_columns);
+        assert!(postgres.features.catalog_acls);
+
+        let cockroach = detect(
+            "postgres",
+            "CockroachDB CCL v23.1.11 (x86_64-pc-linux-gnu, built 2023/09/27)",
+        );
+        assert_eq!(cockroach.flavor, ServerFlavor::Cockroachdb);
+        assert_eq!(cockroach.version, version(23, 1, 11));
+        assert!(cockroach.features.identity_columns);
+        assert!(!cockroach.features.catalog_acls);
+
+        let yugabyte = detect(
+            "postgres",
+            "PostgreSQL 11.2-YB-2.18.0.0-b0 on x86_64-pc-linux-gnu",
+        );
+        assert_eq!(yugabyte.flavor, ServerFlavor::Yugabytedb);
+        assert_eq!(yugabyte.version, version(11, 2, 0));
+        assert!(yugabyte.features.identity_columns);
+    }
+
+    #[test]
+    fn sqlite_features_follow_its_version() {
+        let old = detect("sqlite", "3.22.0");
+        assert!(!old.features.rename_column);
+        assert!(!old.features.returning);
+
+        let new = detect("sqlite", "3.45.1");
+        assert!(new.features.rename_column);
+        assert!(new.features.returning);
+    }
+
+    #[test]
+    fn unknown_servers_support_nothing() {
+        let info = detect("duckdb", "v1.0.0");
+        assert_eq!(info.flavor, ServerFlavor::Unknown);
+        assert!(!info.features.ctes);
+        assert!(!info.features.identity_columns);
+    }
+
+    #[tokio::test]
+    async fn current_is_only_set_inside_a_scope() {
+        assert!(current().is_none());
+        let info = detect("postgres", "PostgreSQL 16.2");
+        let seen = scope(Some(info.clone()), async { current() }).await;
+        assert_eq!(seen, Some(info));
+        assert!(scope(None, async { current() }).await.is_none());
+    }
+
+    fn names(values: &[ServerValue]) -> Vec<&str> {
+        values.iter().map(|v| v.name.as_str()).collect()
+    }
+
+    #[test]
+    fn values_are_filtered_by_name_ignoring_case() {
+        let values = vec![
+            ServerValue::new("Threads_connected", "4"),
+            ServerValue::new("Threads_running", "1"),
+            ServerValue::new("Uptime", "3600"),
+        ];
+        assert_eq!(
+            names(&filter_values(values.clone(), Some("threads"))),
+            ["Threads_connected", "Threads_running"]
+        );
+        assert_eq!(names(&filter_values(values.clone(), Some("  "))).len(), 3);
+        assert_eq!(names(&filter_values(values, None)).len(), 3);
+    }
+
+    #[test]
+    fn extensions_are_looked_up_by_name() {
+        let mut info = detect("postgres", "PostgreSQL 15.4");
+        assert!(!info.has_extension("timescaledb"));
+        info.extensions = vec!["timescaledb".into()];
+        assert!(info.has_extension("timescaledb"));
+    }
+}
@@ -0,0 +1,383 @@
+//! Temporary tables and views created during a session.
+//!
+//! Successful statements run from the editor are parsed for
+//! `CREATE TEMP[ORARY] TABLE|VIEW` and matching `DROP` statements, so the
+//! sidebar can list the user's scratch objects per connection. Tracked
+//! objects are dropped (best effort) when the connection is closed.
+//!
+//! Temporary objects belong to the pooled connection that created them: a
+//! drop that runs on another pooled connection does nothing, and the object
+//! disappears when the pool closes. The registry lives in memory only.
+
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use tauri::{AppHandle, Emitter, Runtime};
+
+use crate::commands::{
+    driver_for, expand_ssh_connection_params, find_connection_by_id,
+    resolve_connection_params_with_id,
+};
+use crate::drivers::driver_trait::DatabaseDriver;
+use crate::models::ConnectionParams;
+
+const SESSION_OBJECTS_EVENT: &str = "session-objects://changed";
+
+static REGISTRY: Lazy<Mutex<SessionObjects>> = Lazy::new(|| Mutex::new(SessionObjects::default()));
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum SessionObjectKind {
+    Table,
+    View,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionObject {
+    pub id: String,
+    pub kind: SessionObjectKind,
+    pub schema: Option<String>,
+    pub name: String,
+    pub created_at: String,
+}
+
+/// A temp-object statement recognised by `parse_session_ddl`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SessionDdl {
+    Created {
+        kind: SessionObjectKind,
+        schema: Option<String>,
+        name: String,
+    },
+    Dropped {
+        kind: SessionObjectKind,
+        names: Vec<(Option<String>, String)>,
+    },
+}
+
+// ---------------------------------------------------------------------------
+// Parsing (testable; pure)
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Word(String),
+    Quoted(String),
+    Punct(char),
+}
+
+impl Token {
+    fn is_keyword(&self, keyword: &str) -> bool {
+        matches!(self, Token::Word(w) if w.eq_ignore_ascii_case(keyword))
+    }
+
+    fn identifier(&self) -> Option<String> {
+        match self {
+            Token::Word(w) | Token::Quoted(w) => Some(w.clone()),
+            Token::Punct(_) => None,
+        }
+    }
+}
+
+/// Tokens up to the first `(` or `;` — enough to read an object name.
+fn tokenize_head(sql: &str) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let mut chars = sql.chars().peekable();
+    while let Some(&c) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+        } else if let Some(close) = match c {
+            '"' => Some('"'),
+            '`' => Some('`'),
+            '[' => Some(']'),
+            _ => None,
+        } {
+            chars.next();
+            let mut ident = String::new();
+            while let Some(ch) = chars.next() {
+                if ch == close {
+                    // Doubled closing quote is an escaped quote.
+                    if close != ']' && chars.peek() == Some(&close) {
+                        chars.next();
+                    } else {
+                        break;
+                    }
+                }
+                ident.push(ch);
+            }
+            tokens.push(Token::Quoted(ident));
+        } else if c.is_alphanumeric() || c == '_' || c == '$' {
+            let mut word = String::new();
+            while let Some(&ch) = chars.peek() {
+                if !(ch.is_alphanumeric() || ch == '_' || ch == '$') {
+                    break;
+                }
+                word.push(ch);
+                chars.next();
+            }
+            tokens.push(Token::Word(word));
+        } else if c == '(' || c == ';' {
+            break;
+        } else {
+            tokens.push(Token::Punct(c));
+            chars.next();
+        }
+    }
+    tokens
+}
+
+fn object_kind(token: Option<&Token>) -> Option<SessionObjectKind> {
+    match token {
+        Some(t) if t.is_keyword("TABLE") => Some(SessionObjectKind::Table),
+        Some(t) if t.is_keyword("VIEW") => Some(SessionObjectKind::View),
+        _ => None,
+    }
+}
+
+/// Reads `name` or `schema.name` starting at `pos`; returns the next position.
+fn qualified_name(tokens: &[Token], pos: usize) -> Option<(Option<String>, String, usize)> {
+    let first = tokens.get(pos)?.identifier()?;
+    if tokens.get(pos + 1) == Some(&Token::Punct('.')) {
+        let second = tokens.get(pos + 2)?.identifier()?;
+        return Some((Some(first), second, pos + 3));
+    }
+    Some((None, first, pos + 1))
+}
+
+/// Recognise `CREATE TEMP TABLE/VIEW` and `DROP TABLE/VIEW` statements.
+pub fn parse_session_ddl(sql: &str) -> Option<SessionDdl> {
+    let tokens = tokenize_head(crate::drivers::common::strip_leading_sql_comments(sql));
+    let mut pos = 1;
+    let skip = |pos: &mut usize, keywords: &[&str]| {
+        while tokens
+            .get(*pos)
+            .map_or(false, |t| keywords.iter().any(|k| t.is_keyword(k)))
+        {
+            *pos += 1;
+        }
+    };
+
+    match tokens.first() {
+        Some(t) if t.is_keyword("CREATE") => {
+            skip(&mut pos, &["OR", "REPLACE", "GLOBAL", "LOCAL"]);
+            if !tokens
+                .get(pos)
+                .map_or(false, |t| t.is_keyword("TEMP") || t.is_keyword("TEMPORARY"))
+            {
+                return None;
+            }
+            pos += 1;
+            let kind = object_kind(tokens.get(pos))?;
+            pos += 1;
+            skip(&mut pos, &["IF", "NOT", "EXISTS"]);
+            let (schema, name, _) = qualified_name(&tokens, pos)?;
+            Some(SessionDdl::Created { kind, schema, name })
+        }
+        Some(t) if t.is_keyword("DROP") => {
+            skip(&mut pos, &["TEMPORARY"]);
+            let kind = object_kind(tokens.get(pos))?;
+            pos += 1;
+            skip(&mut pos, &["IF", "EXISTS"]);
+            let mut names = Vec::new();
+            while let Some((schema, name, next)) = qualified_name(&tokens, pos) {
+                if name.eq_ignore_ascii_case("CASCADE") || name.eq_ignore_ascii_case("RESTRICT") {
+                    break;
+                }
+                names.push((schema, name));
+                pos = next;
+                if tokens.get(pos) != Some(&Token::Punct(',')) {
+                    break;
+                }
+                pos += 1;
+            }
+            (!names.is_empty()).then_some(SessionDdl::Dropped { kind, names })
+        }
+        _ => None,
+    }
+}
+
+/// `DROP` statement for a tracked object.
+pub fn drop_statement(driver: &str, object: &SessionObject) -> String {
+    let target = crate::sandbox::qualified(driver, object.schema.as_deref(), &object.name);
+    match (object.kind, driver) {
+        (SessionObjectKind::Table, "mysql") => format!("DROP TEMPORARY TABLE IF EXISTS {}", target),
+        (SessionObjectKind::Table, _) => format!("DROP TABLE IF EXISTS {}", target),
+        (SessionObjectKind::View, _) => format!("DROP VIEW IF EXISTS {}", target),
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Registry (testable; no app state)
+// ---------------------------------------------------------------------------
+
+/// Tracked objects per connection id, in creation order.
+#[derive(Debug, Default)]
+pub struct SessionObjects(HashMap<String, Vec<SessionObject>>);
+
+fn same_name(a: &str, b: &str) -> bool {
+    a.eq_ignore_ascii_case(b)
+}
+
+impl SessionObjects {
+    pub fn list(&self, connection_id: &str) -> Vec<SessionObject> {
+        self.0.get(connection_id).cloned().unwrap_or_default()
+    }
+
+    /// Record the effect of `ddl`; returns true when the list changed.
+    pub fn apply(&mut self, connection_id: &str, ddl: SessionDdl, now: &str) -> bool {
+        let objects = self.0.entry(connection_id.to_string()).or_default();
+        match ddl {
+            SessionDdl::Created { kind, schema, name } => {
+                let exists = objects.iter().any(|o| {
+                    o.kind == kind
+                        && same_name(&o.name, &name)
+                        && o.schema.as_deref().map(str::to_lowercase)
+                            == schema.as_deref().map(str::to_lowercase)
+                });
+                if !exists {
+                    objects.push(SessionObject {
+                        id: uuid::Uuid::new_v4().to_string(),
+                        kind,
+                        schema,
+                        name,
+                        created_at: now.to_string(),
+                    });
+                }
+                !exists
+            }
+            SessionDdl::Dropped { kind, names } => {
+                let before = objects.len();
+                objects.retain(|o| {
+                    o.kind != kind
+                        || !names.iter().any(|(schema, name)| {
+                            same_name(&o.name, name)
+                                && schema.as_deref().map_or(true, |s| {
+                                    o.schema.as_deref().map_or(false, |os| same_name(os, s))
+                                })
+                        })
+                });
+                objects.len() != before
+            }
+        }
+    }
+
+    pub fn remove(&mut self, connection_id: &str, id: &str) -> Option<SessionObject> {
+        let objects = self.0.get_mut(connection_id)?;
+        let idx = objects.iter().position(|o| o.id == id)?;
+        Some(objects.remove(idx))
+    }
+
+    pub fn take_all(&mut self, connection_id: &str) -> Vec<SessionObject> {
+        self.0.remove(connection_id).unwrap_or_default()
+    }
+}
+
+// ---------------------------------------------------------------------------
+// App hooks
+// ---------------------------------------------------------------------------
+
+fn notify<R: Runtime>(app: &AppHandle<R>, connection_id: &str) {
+    let _ = app.emit(
+        SESSION_OBJECTS_EVENT,
+        serde_json::json!({ "connectionId": connection_id }),
+    );
+}
+
+/// Whether the session of `connection_id` has tracked temporary objects.
+pub fn has_objects(connection_id: &str) -> bool {
+    REGISTRY
+        .lock()
+        .map(|registry| !registry.list(connection_id).is_empty())
+        .unwrap_or(false)
+}
+
+/// Record a successfully executed statement; emits
+/// `session-objects://changed` when it created or dropped a tracked object.
+pub fn track<R: Runtime>(app: &AppHandle<R>, connection_id: &str, sql: &str) {
+    let Some(ddl) = parse_session_ddl(sql) else {
+        return;
+    };
+    let changed = match REGISTRY.lock() {
+        Ok(mut registry) => registry.apply(connection_id, ddl, &chrono::Utc::now().to_rfc3339()),
+        Err(_) => false,
+    };
+    if changed {
+        notify(app, connection_id);
+    }
+}
+
+async fn drop_object(
+    drv: &Arc<dyn DatabaseDriver>,
+    params: &ConnectionParams,
+    driver: &str,
+    object: &SessionObject,
+) -> Result<(), String> {
+    let sql = drop_statement(driver, object);
+    drv.execute_query(params, &sql, None, 1, None).await?;
+    Ok(())
+}
+
+/// Drop every tracked object of a connection before its pool is closed.
+pub async fn cleanup<R: Runtime>(
+    app: &AppHandle<R>,
+    connection_id: &str,
+    driver: &str,
+    params: &ConnectionParams,
+) {
+    let objects = match REGISTRY.lock() {
+        Ok(mut registry) => registry.take_all(connection_id),
+        Err(_) => return,
+    };
+    if objects.is_empty() {
+        return;
+    }
+    if let Ok(drv) = driver_for(driver).await {
+        for object in &objects {
+            if let Err(e) = drop_object(&drv, params, driver, object).await {
+                log::debug!("Failed to drop session object {}: {}", object.name, e);
+            }
+        }
+    }
+    notify(app, connection_id);
+}
+
+// ---------------------------------------------------------------------------
+// Tauri commands
+// ---------------------------------------------------------------------------
+
+#[tauri::command]
+pub fn list_session_objects(connection_id: String) -> Result<Vec<SessionObject>, String> {
+    let registry = REGISTRY
+        .lock()
+        .map_err(|e| format!("Failed to lock session objects: {}", e))?;
+    Ok(registry.list(&connection_id))
+}
+
+#[tauri::command]
+pub async fn drop_session_object<R: Runtime>(
+    app: AppHandle<R>,
+    connection_id: String,
+    id: String,
+) -> Result<(), String> {
+    let object = REGISTRY
+        .lock()
+        .map_err(|e| format!("Failed to lock session objects: {}", e))?
+        .list(&connection_id)
+        .into_iter()
+        .find(|o| o.id == id)
+        .ok_or_else(|| format!("Session object not found: {}", id))?;
+
+    let saved_conn = find_connection_by_id(&app, &connection_id)?;
+    let expanded = expand_ssh_connection_params(&app, &saved_conn.params).await?;
+    let params = resolve_connection_params_with_id(&expanded, &connection_id)?;
+    let drv = driver_for(&saved_conn.params.driver).await?;
+    drop_object(&drv, &params, &saved_conn.params.driver, &object).await?;
+
+    if let Ok(mut registry) = REGISTRY.lock() {
+        registry.remove(&connection_id, &id);
+    }
+    notify(&app, &connection_id);
+    Ok(())
+}
@@ -0,0 +1,138 @@
+#[cfg(test)]
+mod tests {
+    use crate::session_objects::{
+        drop_statement, parse_session_ddl, SessionDdl, SessionObjectKind, SessionObjects,
+    };
+
+    const NOW: &str = "2026-01-01T00:00:00Z";
+
+    fn created(kind: SessionObjectKind, schema: Option<&str>, name: &str) -> SessionDdl {
+        SessionDdl::Created {
+            kind,
+            schema: schema.map(str::to_string),
+            name: name.to_string(),
+        }
+    }
+
+    // --- parse_session_ddl ---
+
+    #[test]
+    fn parses_temp_tables_across_dialects() {
+        assert_eq!(
+            parse_session_ddl("CREATE TEMP TABLE scratch (id int)"),
+            Some(created(SessionObjectKind::Table, None, "scratch"))
+        );
+        assert_eq!(
+            parse_session_ddl("create temporary table if not exists `tmp_orders` as select 1"),
+            Some(created(SessionObjectKind::Table, None, "tmp_orders"))
+        );
+        assert_eq!(
+            parse_session_ddl("CREATE GLOBAL TEMPORARY TABLE pg_temp.\"My Table\"(a int)"),
+            Some(created(
+                SessionObjectKind::Table,
+                Some("pg_temp"),
+                "My Table"
+            ))
+        );
+    }
+
+    #[test]
+    fn parses_temp_views_after_comments() {
+        assert_eq!(
+            parse_session_ddl("-- quick look\nCREATE OR REPLACE TEMP VIEW v_recent AS SELECT 1"),
+            Some(created(SessionObjectKind::View, None, "v_recent"))
+        );
+    }
+
+    #[test]
+    fn ignores_permanent_objects_and_other_statements() {
+        assert_eq!(parse_session_ddl("CREATE TABLE users (id int)"), None);
+        assert_eq!(parse_session_ddl("CREATE VIEW v AS SELECT 1"), None);
+        assert_eq!(parse_session_ddl("SELECT * FROM temp_table"), None);
+        assert_eq!(parse_session_ddl(""), None);
+    }
+
+    #[test]
+    fn parses_drop_lists() {
+        assert_eq!(
+            parse_session_ddl("DROP TEMPORARY TABLE IF EXISTS a, db.b CASCADE"),
+            Some(SessionDdl::Dropped {
+                kind: SessionObjectKind::Table,
+                names: vec![
+                    (None, "a".to_string()),
+                    (Some("db".to_string()), "b".to_string())
+                ],
+            })
+        );
+        assert_eq!(parse_session_ddl("DROP INDEX idx"), None);
+    }
+
+    // --- registry ---
+
+    #[test]
+    fn registry_tracks_creates_and_drops_per_connection() {
+        let mut registry = SessionObjects::default();
+        assert!(registry.apply(
+            "c1",
+            created(SessionObjectKind::Table, None, "scratch"),
+            NOW
+        ));
+        // Re-creating the same object does not duplicate it.
+        assert!(!registry.apply(
+            "c1",
+            created(SessionObjectKind::Table, None, "SCRATCH"),
+            NOW
+        ));
+        registry.apply("c1", created(SessionObjectKind::View, None, "v"), NOW);
+        assert_eq!(registry.list("c1").len(), 2);
+        assert!(registry.list("c2").is_empty());
+
+        let dropped = parse_session_ddl("DROP TABLE scratch").unwrap();
+        assert!(registry.apply("c1", dropped, NOW));
+        let remaining = registry.list("c1");
+        assert_eq!(remaining.len(), 1);
+        assert_eq!(remaining[0].kind, SessionObjectKind::View);
+    }
+
+    #[test]
+    fn registry_remove_and_take_all() {
+        let mut registry = SessionObjects::default();
+        registry.apply("c1", created(SessionObjectKind::Table, None, "a"), NOW);
+        registry.apply("c1", created(SessionObjectKind::Table, None, "b"), NOW);
+
+        let id = registry.list("c1")[0].id.clone();
+        assert_eq!(registry.remove("c1", &id).unwrap().name, "a");
+        assert!(registry.remove("c1", &id).is_none());
+
+        let all = registry.take_all("c1");
+        assert_eq!(all.len(), 1);
+        assert!(registry.list("c1").is_empty());
+    }
+
+    // --- drop_statement ---
+
+    #[test]
+    fn drop_statement_per_dialect() {
+        let mut registry = SessionObjects::default();
+        registry.apply(
+            "c1",
+            created(SessionObjectKind::Table, Some("pg_temp"), "t"),
+            NOW,
+        );
+        registry.apply("c1", created(SessionObjectKind::View, None, "v"), NOW);
+        let objects = registry.list("c1");
+
+        assert_eq!(
+            drop_statement("postgres", &objects[0]),
+            "DROP TABLE IF EXISTS \"pg_temp\".\"t\""
+        );
+        assert_eq!(
+            drop_statement("mysql", &objects[0]),
+            "DROP TEMPORARY TABLE IF EXISTS `pg_temp`.`t`"
+        );
+        assert_eq!(
+            drop_statement("sqlite", &objects[1]),
+            "DROP VIEW IF EXISTS \"v\""
+        );
+    }
+}
@@ -0,0 +1,190 @@
+//! Ordered shutdown on application exit.
+//!
+//! Without it, quitting dropped everything at once: running queries and
+//! exports were cut off mid-stream and plugin processes could outlive the
+//! app. The first exit request is now held back while [`run`] winds things
+//! down in order, each step bounded by [`STEP_TIMEOUT`]:
+//!
+//! 1. windows are told to persist their session state ([`SHUTDOWN_EVENT`]);
+//! 2. running queries, exports, dumps and imports are aborted;
+//! 3. open transactions are rolled back and the connection pools closed;
+//! 4. plugin processes get a `shutdown` notification and are killed if they
+//!    do not exit in time.
+//!
+//! The exit then goes ahead with the code it was requested with.
+
+use std::future::Future;
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::time::{Duration, Instant};
+
+use tauri::{AppHandle, Emitter, ExitRequestApi, Manager, Runtime};
+
+use crate::commands::{abort_all_handles, QueryCancellationState};
+use crate::data_search::DataSearchCancellationState;
+use crate::dump_commands::DumpCancellationState;
+use crate::export::ExportCancellationState;
+
+/// Emitted to every window when the shutdown starts.
+pub const SHUTDOWN_EVENT: &str = "app-shutting-down";
+
+/// Longest a single shutdown step may take before it is skipped.
+pub const STEP_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Minimum time between [`SHUTDOWN_EVENT`] and the exit, so windows can
+/// finish writing their state.
+const SESSION_FLUSH_GRACE: Duration = Duration::from_millis(500);
+
+const IDLE: u8 = 0;
+const RUNNING: u8 = 1;
+const DONE: u8 = 2;
+
+/// What to do with an exit request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExitDecision {
+    /// First request: hold the exit back and run the shutdown.
+    Start,
+    /// The shutdown is still running: keep holding the exit back.
+    Hold,
+    /// The shutdown has finished: let the exit through.
+    Allow,
+}
+
+/// Makes sure the shutdown runs once, however many exit requests arrive.
+pub struct ShutdownGate {
+    state: AtomicU8,
+}
+
+impl ShutdownGate {
+    pub const fn new() -> Self {
+        Self {
+            state: AtomicU8::new(IDLE),
+        }
+    }
+
+    pub fn on_exit_requested(&self) -> ExitDecision {
+        match self
+            .state
+            .compare_exchange(IDLE, RUNNING, Ordering::SeqCst, Ordering::SeqCst)
+        {
+            Ok(_) => ExitDecision::Start,
+            Err(RUNNING) => ExitDecision::Hold,
+            Err(_) => ExitDecision::Allow,
+        }
+    }
+
+    pub fn finish(&self) {
+        self.state.store(DONE, Ordering::SeqCst);
+    }
+}
+
+impl Default for ShutdownGate {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+static GATE: ShutdownGate = ShutdownGate::new();
+
+/// Run `step` for at most `timeout`. Returns `false` when it timed out.
+pub async fn run_step<F: Future<Output = ()>>(name: &str, timeout: Duration, step: F) -> bool {
+    let started = Instant::now();
+    match tokio::time::timeout(timeout, step).await {
+        Ok(()) => {
+            log::info!(
+                "Shutdown: {} done in {}ms",
+                name,
+                started.elapsed().as_millis()
+            );
+            true
+        }
+        Err(_) => {
+            log::warn!(
+                "Shutdown: {} did not finish within {}s, moving on",
+                name,
+                timeout.as_secs()
+            );
+            false
+        }
+    }
+}
+
+fn abort_running_tasks<R: Runtime>(app: &AppHandle<R>) -> usize {
+    let mut aborted = crate::query_jobs::cancel_all();
+    if let Some(state) = app.try_state::<QueryCancellationState>() {
+        aborted += abort_all_handles(&state.handles);
+    }
+    if let Some(state) = app.try_state::<ExportCancellationState>() {
+        aborted += abort_all_handles(&state.handles);
+    }
+    if let Some(state) = app.try_state::<DumpCancellationState>() {
+        aborted += abort_all_handles(&state.handles);
+    }
+    if let Some(state) = app.try_state::<DataSearchCancellationState>() {
+        aborted += abort_all_handles(&state.handles);
+    }
+    aborted
+}
+
+/// Run the shutdown steps in order.
+pub async fn run<R: Runtime>(app: &AppHandle<R>, notify_windows: bool) {
+    let started = Instant::now();
+    if notify_windows {
+        if let Err(e) = app.emit(SHUTDOWN_EVENT, ()) {
+            log::warn!("Shutdown: failed to notify windows: {}", e);
+        }
+    }
+
+    let aborted = abort_running_tasks(app);
+    if aborted > 0 {
+        log::info!("Shutdown: aborted {} running task(s)", aborted);
+    }
+    run_step(
+        "writing table activity",
+        STEP_TIMEOUT,
+        crate::table_activity::flush_pending(app),
+    )
+    .await;
+    run_step(
+        "closing connection pools",
+        STEP_TIMEOUT,
+        crate::pool_manager::close_all_pools(),
+    )
+    .await;
+    run_step(
+        "stopping plugins",
+        STEP_TIMEOUT,
+        crate::drivers::registry::shutdown_all(),
+    )
+    .await;
+
+    if notify_windows {
+        if let Some(rest) = SESSION_FLUSH_GRACE.checked_sub(started.elapsed()) {
+            tokio::time::sleep(rest).await;
+        }
+    }
+    log::info!("Shutdown completed in {}ms", started.elapsed().as_millis());
+}
+
+/// Handle `RunEvent::ExitRequested`: hold the first exit back, run the
+/// shutdown, then exit with the requested code.
+pub fn on_exit_requested<R: Runtime>(app: &AppHandle<R>, code: Option<i32>, api: &ExitRequestApi) {
+    match GATE.on_exit_requested() {
+        ExitDecision::Start if code == Some(tauri::RESTART_EXIT_CODE) => {
+            // A restart cannot be held back, so shut down before returning.
+            // Windows stop processing events here, so they are not notified.
+            tauri::async_runtime::block_on(run(app, false));
+            GATE.finish();
+        }
+        ExitDecision::Start => {
+            api.prevent_exit();
+            let app = app.clone();
+            tauri::async_runtime::spawn(async move {
+                run(&app, true).await;
+                GATE.finish();
+                app.exit(code.unwrap_or(0));
+            });
+        }
+        ExitDecision::Hold => api.prevent_exit(),
+        ExitDecision::Allow => {}
+    }
+}
@@ -0,0 +1,29 @@
+#[cfg(test)]
+mod tests {
+    use crate::shutdown::{run_step, ExitDecision, ShutdownGate};
+    use std::time::Duration;
+
+    #[test]
+    fn gate_runs_the_shutdown_once() {
+        let gate = ShutdownGate::new();
+        assert_eq!(gate.on_exit_requested(), ExitDecision::Start);
+        assert_eq!(gate.on_exit_requested(), ExitDecision::Hold);
+
+        gate.finish();
+        assert_eq!(gate.on_exit_requested(), ExitDecision::Allow);
+        assert_eq!(gate.on_exit_requested(), ExitDecision::Allow);
+    }
+
+    #[tokio::test]
+    async fn steps_report_whether_they_finished() {
+        assert!(run_step("quick", Duration::from_secs(1), async {}).await);
+        assert!(
+            !run_step(
+                "stuck",
+                Duration::from_millis(10),
+                std::future::pending::<()>()
+            )
+            .await
+        );
+    }
+}
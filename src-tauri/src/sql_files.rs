@@ -0,0 +1,323 @@
+//! Open, save and watch `.sql` files on disk so an editor tab can be bound to
+//! a file (e.g. a project's migration scripts) instead of copy-pasting.
+//!
+//! Every read returns a SHA-256 of the content. Saves pass back the hash the
+//! tab was loaded with, so a file changed by another program is never
+//! silently overwritten. Watched files emit `sql-file://changed` when their
+//! content on disk no longer matches what the tab last loaded or saved.
+
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::UNIX_EPOCH;
+use tauri::{AppHandle, Emitter, Runtime, State};
+
+const SQL_FILE_CHANGED_EVENT: &str = "sql-file://changed";
+/// Refuse to load files larger than this into an editor tab.
+pub const MAX_SQL_FILE_BYTES: u64 = 20 * 1024 * 1024;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SqlFile {
+    pub path: String,
+    pub name: String,
+    pub content: String,
+    pub hash: String,
+    /// Last modification time in milliseconds since the Unix epoch.
+    pub modified_at: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SqlFileStatus {
+    pub exists: bool,
+    pub hash: Option<String>,
+    /// True when the content on disk differs from `expected_hash`.
+    pub changed: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SqlFileChange {
+    pub tab_id: String,
+    pub path: String,
+    pub hash: Option<String>,
+    pub deleted: bool,
+}
+
+struct WatchedFile {
+    path: PathBuf,
+    /// Hash of the content the tab knows about; shared with the watcher callback.
+    known_hash: Arc<Mutex<Option<String>>>,
+    _watcher: RecommendedWatcher,
+}
+
+/// Active file watchers, keyed by editor tab id.
+#[derive(Default)]
+pub struct SqlFileWatchState(Mutex<HashMap<String, WatchedFile>>);
+
+// ---------------------------------------------------------------------------
+// File access (testable; plain paths)
+// ---------------------------------------------------------------------------
+
+pub fn content_hash(content: &[u8]) -> String {
+    Sha256::digest(content)
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+fn modified_millis(path: &Path) -> Option<u64> {
+    let modified = fs::metadata(path).ok()?.modified().ok()?;
+    Some(modified.duration_since(UNIX_EPOCH).ok()?.as_millis() as u64)
+}
+
+fn disk_hash(path: &Path) -> Option<String> {
+    fs::read(path).ok().map(|bytes| content_hash(&bytes))
+}
+
+pub fn read_sql_file(path: &Path) -> Result<SqlFile, String> {
+    let size = fs::metadata(path)
+        .map_err(|e| format!("Failed to open {}: {}", path.display(), e))?
+        .len();
+    if size > MAX_SQL_FILE_BYTES {
+        return Err(format!(
+            "File is too large to open ({} bytes, max {})",
+            size, MAX_SQL_FILE_BYTES
+        ));
+    }
+
+    let bytes = fs::read(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    let hash = content_hash(&bytes);
+    let content = String::from_utf8(bytes)
+        .map_err(|_| format!("{} is not a UTF-8 text file", path.display()))?;
+
+    Ok(SqlFile {
+        path: path.to_string_lossy().to_string(),
+        name: path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default(),
+        content,
+        hash,
+        modified_at: modified_millis(path),
+    })
+}
+
+/// Write `content` to `path`. Unless `force` is set, the save is rejected when
+/// the file exists and its content no longer matches `expected_hash`.
+/// The file is written to a sibling temp file first and renamed into place.
+pub fn write_sql_file(
+    path: &Path,
+    content: &str,
+    expected_hash: Option<&str>,
+    force: bool,
+) -> Result<SqlFile, String> {
+    if !force {
+        if let (Some(expected), Some(current)) = (expected_hash, disk_hash(path)) {
+            if expected != current {
+                return Err(format!(
+                    "{} was changed on disk by another program",
+                    path.display()
+                ));
+            }
+        }
+    }
+
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        fs::create_dir_all(parent).map_err(|e| format!("Failed to create folder: {}", e))?;
+    }
+
+    let file_name = path
+        .file_name()
+        .ok_or_else(|| format!("Invalid file path: {}", path.display()))?;
+    let tmp = path.with_file_name(format!(".{}.tmp", file_name.to_string_lossy()));
+    fs::write(&tmp, content).map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+    fs::rename(&tmp, path).map_err(|e| {
+        let _ = fs::remove_file(&tmp);
+        format!("Failed to write {}: {}", path.display(), e)
+    })?;
+
+    read_sql_file(path)
+}
+
+pub fn sql_file_status(path: &Path, expected_hash: Option<&str>) -> SqlFileStatus {
+    let hash = disk_hash(path);
+    SqlFileStatus {
+        exists: hash.is_some(),
+        changed: hash.as_deref() != expected_hash,
+        hash,
+    }
+}
+
+/// Whether a watcher event on the parent directory concerns `path`.
+/// Editors often save through a rename, so the file itself cannot be watched.
+pub fn event_touches(event: &Event, path: &Path) -> bool {
+    matches!(
+        event.kind,
+        EventKind::Create(_) | EventKind::Modify(_) | EventKind::Remove(_)
+    ) && event.paths.iter().any(|p| p == path)
+}
+
+// ---------------------------------------------------------------------------
+// Watching
+// ---------------------------------------------------------------------------
+
+fn handle_watch_event<R: Runtime>(
+    app: &AppHandle<R>,
+    tab_id: &str,
+    path: &Path,
+    known_hash: &Mutex<Option<String>>,
+) {
+    let current = disk_hash(path);
+    let Ok(mut known) = known_hash.lock() else {
+        return;
+    };
+    if *known == current {
+        // Our own save, or a touch that did not change the content.
+        return;
+    }
+    *known = current.clone();
+
+    let change = SqlFileChange {
+        tab_id: tab_id.to_string(),
+        path: path.to_string_lossy().to_string(),
+        deleted: current.is_none(),
+        hash: current,
+    };
+    if let Err(e) = app.emit(SQL_FILE_CHANGED_EVENT, &change) {
+        log::warn!("Failed to emit {}: {}", SQL_FILE_CHANGED_EVENT, e);
+    }
+}
+
+fn start_watch<R: Runtime>(
+    app: AppHandle<R>,
+    tab_id: String,
+    path: PathBuf,
+    known_hash: Arc<Mutex<Option<String>>>,
+) -> Result<RecommendedWatcher, String> {
+    let dir = path
+        .parent()
+        .map(Path::to_path_buf)
+        .ok_or_else(|| format!("Invalid file path: {}", path.display()))?;
+
+    let watched_path = path.clone();
+    let mut watcher = notify::recommended_watcher(move |res: notify::Result<Event>| match res {
+        Ok(event) if event_touches(&event, &watched_path) => {
+            handle_watch_event(&app, &tab_id, &watched_path, &known_hash)
+        }
+        Ok(_) => {}
+        Err(e) => log::warn!("SQL file watcher error: {}", e),
+    })
+    .map_err(|e| format!("Failed to watch {}: {}", path.display(), e))?;
+
+    watcher
+        .watch(&dir, RecursiveMode::NonRecursive)
+        .map_err(|e| format!("Failed to watch {}: {}", path.display(), e))?;
+    Ok(watcher)
+}
+
+fn update_known_hash(state: &SqlFileWatchState, path: &Path, hash: Option<String>) {
+    if let Ok(watched) = state.0.lock() {
+        for file in watched.values().filter(|f| f.path == path) {
+            if let Ok(mut known) = file.known_hash.lock() {
+                *known = hash.clone();
+            }
+        }
+    }
+}
+
+/// Watcher events carry absolute, symlink-free paths.
+fn canonical(path: PathBuf) -> PathBuf {
+    match (path.parent(), path.file_name()) {
+        (Some(parent), Some(name)) => fs::canonicalize(parent)
+            .map(|p| p.join(name))
+            .unwrap_or(path),
+        _ => path,
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tauri commands
+// ---------------------------------------------------------------------------
+
+#[tauri::command]
+pub fn open_sql_file(path: String) -> Result<SqlFile, String> {
+    read_sql_file(Path::new(&path))
+}
+
+/// Save a tab to disk. Pass the hash the tab was loaded with as
+/// `expected_hash`; a mismatch means the file changed externally and the save
+/// is refused unless `force` is set.
+#[tauri::command]
+pub fn save_sql_file(
+    state: State<'_, SqlFileWatchState>,
+    path: String,
+    content: String,
+    expected_hash: Option<String>,
+    force: Option<bool>,
+) -> Result<SqlFile, String> {
+    let path = canonical(PathBuf::from(path));
+    // Record the new hash first so the watcher ignores our own write.
+    update_known_hash(&state, &path, Some(content_hash(content.as_bytes())));
+    let result = write_sql_file(
+        &path,
+        &content,
+        expected_hash.as_deref(),
+        force.unwrap_or(false),
+    );
+    if result.is_err() {
+        update_known_hash(&state, &path, disk_hash(&path));
+    }
+    result
+}
+
+/// Compare the file on disk with the hash a tab knows about (e.g. when the
+/// window regains focus).
+#[tauri::command]
+pub fn check_sql_file(path: String, expected_hash: Option<String>) -> SqlFileStatus {
+    sql_file_status(Path::new(&path), expected_hash.as_deref())
+}
+
+/// Bind `tab_id` to `path` and emit `sql-file://changed` on external edits.
+/// Re-watching a tab replaces its previous binding.
+#[tauri::command]
+pub fn watch_sql_file<R: Runtime>(
+    app: AppHandle<R>,
+    state: State<'_, SqlFileWatchState>,
+    tab_id: String,
+    path: String,
+    known_hash: Option<String>,
+) -> Result<(), String> {
+    let path = canonical(PathBuf::from(path));
+    let known_hash = Arc::new(Mutex::new(known_hash.or_else(|| disk_hash(&path))));
+    let watcher = start_watch(app, tab_id.clone(), path.clone(), known_hash.clone())?;
+
+    let mut watched = state
+        .0
+        .lock()
+        .map_err(|e| format!("Failed to lock watcher state: {}", e))?;
+    watched.insert(
+        tab_id,
+        WatchedFile {
+            path,
+            known_hash,
+            _watcher: watcher,
+        },
+    );
+    Ok(())
+}
+
+#[tauri::command]
+pub fn unwatch_sql_file(state: State<'_, SqlFileWatchState>, tab_id: String) -> Result<(), String> {
+    let mut watched = state
+        .0
+        .lock()
+        .map_err(|e| format!("Failed to lock watcher state: {}", e))?;
+    watched.remove(&tab_id);
+    Ok(())
+}
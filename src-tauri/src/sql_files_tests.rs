@@ -0,0 +1,101 @@
+#[cfg(test)]
+mod tests {
+    use crate::sql_files::{
+        content_hash, event_touches, read_sql_file, sql_file_status, write_sql_file,
+    };
+    use notify::event::{CreateKind, ModifyKind};
+    use notify::{Event, EventKind};
+    use tempfile::TempDir;
+
+    #[test]
+    fn read_returns_content_hash_and_name() {
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join("001_init.sql");
+        std::fs::write(&path, "SELECT 1;").unwrap();
+
+        let file = read_sql_file(&path).unwrap();
+
+        assert_eq!(file.name, "001_init.sql");
+        assert_eq!(file.content, "SELECT 1;");
+        assert_eq!(file.hash, content_hash(b"SELECT 1;"));
+        assert!(file.modified_at.is_some());
+    }
+
+    #[test]
+    fn read_rejects_non_utf8_files() {
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join("dump.sql");
+        std::fs::write(&path, [0xff, 0xfe, 0x00]).unwrap();
+
+        assert!(read_sql_file(&path).is_err());
+    }
+
+    #[test]
+    fn write_creates_new_file() {
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join("scripts").join("new.sql");
+
+        let file = write_sql_file(&path, "SELECT 2;", None, false).unwrap();
+
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "SELECT 2;");
+        assert_eq!(file.hash, content_hash(b"SELECT 2;"));
+    }
+
+    #[test]
+    fn write_refuses_to_overwrite_external_changes() {
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join("a.sql");
+        let loaded = write_sql_file(&path, "v1", None, false).unwrap();
+        std::fs::write(&path, "edited elsewhere").unwrap();
+
+        assert!(write_sql_file(&path, "v2", Some(&loaded.hash), false).is_err());
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "edited elsewhere");
+
+        write_sql_file(&path, "v2", Some(&loaded.hash), true).unwrap();
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "v2");
+    }
+
+    #[test]
+    fn write_accepts_matching_hash() {
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join("a.sql");
+        let loaded = write_sql_file(&path, "v1", None, false).unwrap();
+
+        let saved = write_sql_file(&path, "v2", Some(&loaded.hash), false).unwrap();
+        assert_eq!(saved.content, "v2");
+    }
+
+    #[test]
+    fn status_reports_changes_and_deletion() {
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join("a.sql");
+        std::fs::write(&path, "v1").unwrap();
+        let hash = content_hash(b"v1");
+
+        assert!(!sql_file_status(&path, Some(&hash)).changed);
+
+        std::fs::write(&path, "v2").unwrap();
+        assert!(sql_file_status(&path, Some(&hash)).changed);
+
+        std::fs::remove_file(&path).unwrap();
+        let status = sql_file_status(&path, Some(&hash));
+        assert!(!status.exists);
+        assert!(status.changed);
+    }
+
+    #[test]
+    fn event_touches_matches_only_the_watched_path() {
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join("a.sql");
+
+        let modify = Event::new(EventKind::Modify(ModifyKind::Any)).add_path(path.clone());
+        let other =
+            Event::new(EventKind::Create(CreateKind::File)).add_path(tmp.path().join(".a.sql.tmp"));
+        let access =
+            Event::new(EventKind::Access(notify::event::AccessKind::Any)).add_path(path.clone());
+
+        assert!(event_touches(&modify, &path));
+        assert!(!event_touches(&other, &path));
+        assert!(!event_touches(&access, &path));
+    }
+}
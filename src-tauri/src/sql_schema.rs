@@ -0,0 +1,934 @@
+//! Schemas read from a SQL script instead of a database.
+//!
+//! `parse_sql_schema` parses a CREATE TABLE script, such as a migrations
+//! file, into the same [`TableSchema`] model `get_schema_snapshot` returns,
+//! so the ER diagram and schema browser can show a schema without
+//! connecting to any server. The script is split into statements and each
+//! is parsed on its own: `CREATE TABLE`, `ALTER TABLE` (columns, keys,
+//! renames) and `DROP TABLE` are replayed in order, other statements are
+//! ignored, and those the parser cannot read are reported and skipped.
+//!
+//! `apply_sql_schema` goes the other way: it creates the tables of such a
+//! script on a connection, with column types mapped to the target's
+//! dialect, and reports how each statement went.
+
+use std::collections::HashMap;
+
+use serde::Serialize;
+use sqlparser::ast::{
+    AlterTableOperation, ColumnDef, ColumnOption, ObjectName, ObjectType, ReferentialAction,
+    Statement, TableConstraint,
+};
+use sqlparser::dialect::{
+    Dialect, GenericDialect, MsSqlDialect, MySqlDialect, PostgreSqlDialect, SQLiteDialect,
+};
+use sqlparser::parser::Parser;
+use sqlparser::tokenizer::Token;
+use tauri::{AppHandle, Runtime};
+
+use crate::commands::{
+    driver_for, expand_ssh_connection_params, find_connection_by_id,
+    resolve_connection_params_with_id,
+};
+use crate::drivers::driver_trait::DatabaseDriver;
+use crate::dump_commands::split_sql_statements;
+use crate::dump_utils::dependency_order;
+use crate::models::{ColumnDefinition, ConnectionParams, ForeignKey, TableColumn, TableSchema};
+use crate::schema_diff::constraints;
+
+#[derive(Debug, Serialize)]
+pub struct ParsedSqlSchema {
+    /// In the order the script creates them.
+    pub tables: Vec<TableSchema>,
+    /// Statements that could not be parsed or applied, and why.
+    pub warnings: Vec<String>,
+}
+
+/// One statement run by `apply_sql_schema`.
+#[derive(Debug, Serialize)]
+pub struct AppliedStatement {
+    /// What the statement does, such as `Create table users`.
+    pub what: String,
+    /// `None` when the target driver could not generate it.
+    pub sql: Option<String>,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AppliedSqlSchema {
+    /// In the order they ran; a failed statement does not stop the others.
+    pub statements: Vec<AppliedStatement>,
+    /// Statements of the script that could not be parsed.
+    pub warnings: Vec<String>,
+    pub failed: usize,
+}
+
+// ---------------------------------------------------------------------------
+// Parsing (testable; pure)
+// ---------------------------------------------------------------------------
+
+/// The SQL dialect of `driver`; the generic one when it has none of its own.
+pub(crate) fn dialect_for(driver: Option<&str>) -> Box<dyn Dialect> {
+    match driver {
+        Some("mysql") => Box::new(MySqlDialect {}),
+        Some("postgres") => Box::new(PostgreSqlDialect {}),
+        Some("sqlite") => Box::new(SQLiteDialect {}),
+        Some("mssql") => Box::new(MsSqlDialect {}),
+        _ => Box::new(GenericDialect {}),
+    }
+}
+
+/// The unquoted table name; a schema qualifier is dropped, as
+/// [`TableSchema`] has no place for it.
+fn table_name(name: &ObjectName) -> String {
+    name.0
+        .last()
+        .map(|ident| ident.value.clone())
+        .unwrap_or_default()
+}
+
+fn action(action: &Option<ReferentialAction>) -> Option<String> {
+    action.as_ref().map(ToString::to_string)
+}
+
+/// `VARCHAR(255)` and the like: the length of a character type.
+fn character_length(data_type: &str) -> Option<u64> {
+    let upper = data_type.to_ascii_uppercase();
+    if !upper.contains("CHAR") {
+        return None;
+    }
+    let start = upper.find('(')? + 1;
+    let len = upper[start..].find(|c: char| !c.is_ascii_digit())?;
+    upper[start..start + len].parse().ok()
+}
+
+fn is_serial(data_type: &str) -> bool {
+    matches!(
+        data_type.to_ascii_uppercase().as_str(),
+        "SERIAL" | "BIGSERIAL" | "SMALLSERIAL" | "SERIAL2" | "SERIAL4" | "SERIAL8"
+    )
+}
+
+/// MySQL's `AUTO_INCREMENT`, SQLite's `AUTOINCREMENT`.
+fn is_auto_increment(tokens: &[Token]) -> bool {
+    tokens.iter().any(|token| {
+        matches!(token, Token::Word(word)
+            if word.value.eq_ignore_ascii_case("auto_increment")
+                || word.value.eq_ignore_ascii_case("autoincrement"))
+    })
+}
+
+fn column_of(table: &str, def: &ColumnDef, foreign_keys: &mut Vec<ForeignKey>) -> TableColumn {
+    let data_type = def.data_type.to_string();
+    let mut column = TableColumn {
+        name: def.name.value.clone(),
+        is_pk: false,
+        is_nullable: true,
+        is_auto_increment: is_serial(&data_type),
+        default_value: None,
+        character_maximum_length: character_length(&data_type),
+        data_type,
+    };
+    for option in &def.options {
+        match &option.option {
+            ColumnOption::NotNull => column.is_nullable = false,
+            ColumnOption::Unique {
+                is_primary: true, ..
+            } => {
+                column.is_pk = true;
+                column.is_nullable = false;
+            }
+            ColumnOption::Default(expr) => column.default_value = Some(expr.to_string()),
+            ColumnOption::DialectSpecific(tokens) if is_auto_increment(tokens) => {
+                column.is_auto_increment = true;
+            }
+            ColumnOption::Identity(_) => column.is_auto_increment = true,
+            ColumnOption::Generated {
+                generation_expr: None,
+                ..
+            } => column.is_auto_increment = true,
+            ColumnOption::ForeignKey {
+                foreign_table,
+                referred_columns,
+                on_delete,
+                on_update,
+                ..
+            } => foreign_keys.push(ForeignKey {
+                name: option
+                    .name
+                    .as_ref()
+                    .map(|name| name.value.clone())
+                    .unwrap_or_else(|| format!("fk_{}_{}", table, def.name.value)),
+                column_name: def.name.value.clone(),
+                ref_table: table_name(foreign_table),
+                ref_column: referred_columns
+                    .first()
+                    .map(|column| column.value.clone())
+                    .unwrap_or_default(),
+                on_delete: action(on_delete),
+                on_update: action(on_update),
+            }),
+            _ => {}
+        }
+    }
+    column
+}
+
+/// Apply a table-level constraint. Composite foreign keys become one
+/// [`ForeignKey`] per column pair under the same name, as drivers report
+/// them.
+fn apply_constraint(table: &mut TableSchema, constraint: &TableConstraint) {
+    match constraint {
+        TableConstraint::PrimaryKey { columns, .. } => {
+            for column in &mut table.columns {
+                if columns.iter().any(|pk| pk.value == column.name) {
+                    column.is_pk = true;
+                    column.is_nullable = false;
+                }
+            }
+        }
+        TableConstraint::ForeignKey {
+            name,
+            columns,
+            foreign_table,
+            referred_columns,
+            on_delete,
+            on_update,
+            ..
+        } => {
+            let name = name
+                .as_ref()
+                .map(|name| name.value.clone())
+                .unwrap_or_else(|| {
+                    let columns: Vec<&str> = columns.iter().map(|c| c.value.as_str()).collect();
+                    format!("fk_{}_{}", table.name, columns.join("_"))
+                });
+            for (column, referred) in columns.iter().zip(referred_columns) {
+                table.foreign_keys.push(ForeignKey {
+                    name: name.clone(),
+                    column_name: column.value.clone(),
+                    ref_table: table_name(foreign_table),
+                    ref_column: referred.value.clone(),
+                    on_delete: action(on_delete),
+                    on_update: action(on_update),
+                });
+            }
+        }
+        _ => {}
+    }
+}
+
+fn apply_alter(table: &mut TableSchema, operation: &AlterTableOperation) {
+    match operation {
+        AlterTableOperation::AddColumn { column_def, .. } => {
+            let column = column_of(&table.name, column_def, &mut table.foreign_keys);
+            table.columns.push(column);
+        }
+        AlterTableOperation::DropColumn { column_name, .. } => {
+            table.columns.retain(|c| c.name != column_name.value);
+            table
+                .foreign_keys
+                .retain(|fk| fk.column_name != column_name.value);
+        }
+        AlterTableOperation::RenameColumn {
+            old_column_name,
+            new_column_name,
+        } => {
+            for column in &mut table.columns {
+                if column.name == old_column_name.value {
+                    column.name = new_column_name.value.clone();
+                }
+            }
+            for fk in &mut table.foreign_keys {
+                if fk.column_name == old_column_name.value {
+                    fk.column_name = new_column_name.value.clone();
+                }
+            }
+        }
+        AlterTableOperation::AddConstraint(constraint) => apply_constraint(table, constraint),
+        _ => {}
+    }
+}
+
+/// Replay one parsed statement on `tables`.
+fn apply_statement(tables: &mut Vec<TableSchema>, statement: &Statement) -> Result<(), String> {
+    match statement {
+        Statement::CreateTable(create) => {
+            let name = table_name(&create.name);
+            let mut table = TableSchema {
+                name: name.clone(),
+                columns: Vec::new(),
+                foreign_keys: Vec::new(),
+            };
+            for def in &create.columns {
+                let column = column_of(&name, def, &mut table.foreign_keys);
+                table.columns.push(column);
+            }
+            for constraint in &create.constraints {
+                apply_constraint(&mut table, constraint);
+            }
+            // A re-created table replaces the earlier definition.
+            tables.retain(|t| t.name != name);
+            tables.push(table);
+        }
+        Statement::AlterTable {
+            name, operations, ..
+        } => {
+            let name = table_name(name);
+            let index = tables
+                .iter()
+                .position(|t| t.name == name)
+                .ok_or_else(|| format!("ALTER TABLE of unknown table {}", name))?;
+            for operation in operations {
+                if let AlterTableOperation::RenameTable { table_name: to } = operation {
+                    let to = table_name(to);
+                    for fk in tables.iter_mut().flat_map(|t| &mut t.foreign_keys) {
+                        if fk.ref_table == name {
+                            fk.ref_table = to.clone();
+                        }
+                    }
+                    tables[index].name = to;
+                } else {
+                    apply_alter(&mut tables[index], operation);
+                }
+            }
+        }
+        Statement::Drop {
+            object_type: ObjectType::Table,
+            names,
+            ..
+        } => {
+            let dropped: Vec<String> = names.iter().map(table_name).collect();
+            tables.retain(|t| !dropped.contains(&t.name));
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+/// Parse `sql` as written for `driver` (any SQL when `None`).
+pub fn parse_schema(sql: &str, driver: Option<&str>) -> Result<ParsedSqlSchema, String> {
+    let dialect = dialect_for(driver);
+    let mut tables = Vec::new();
+    let mut warnings = Vec::new();
+    for (i, text) in split_sql_statements(sql)?.iter().enumerate() {
+        let statements = match Parser::parse_sql(dialect.as_ref(), text) {
+            Ok(statements) => statements,
+            Err(e) => {
+                warnings.push(format!("Statement {} skipped: {}", i + 1, e));
+                continue;
+            }
+        };
+        for statement in &statements {
+            if let Err(e) = apply_statement(&mut tables, statement) {
+                warnings.push(format!("Statement {} skipped: {}", i + 1, e));
+            }
+        }
+    }
+    Ok(ParsedSqlSchema { tables, warnings })
+}
+
+// ---------------------------------------------------------------------------
+// Type mapping (testable; pure)
+// ---------------------------------------------------------------------------
+
+/// What a column type holds, whatever a dialect calls it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum TypeKind {
+    Bool,
+    TinyInt,
+    SmallInt,
+    Int,
+    BigInt,
+    Decimal,
+    Double,
+    Real,
+    Char,
+    VarChar,
+    Text,
+    Binary,
+    Blob,
+    Date,
+    Time,
+    DateTime,
+    TimestampTz,
+    Json,
+    Uuid,
+}
+
+/// A column type taken apart: `INT(11) UNSIGNED` is `INT`, `11`, unsigned.
+struct ParsedType<'a> {
+    /// Upper case, without arguments or modifiers: `CHARACTER VARYING`.
+    name: String,
+    /// What is between the parentheses, as written.
+    args: Option<&'a str>,
+    unsigned: bool,
+    time_zone: bool,
+    array: bool,
+}
+
+impl ParsedType<'_> {
+    /// `VARCHAR` with no length: unbounded on PostgreSQL, an error on
+    /// MySQL and a single character on SQL Server.
+    fn is_unsized(&self) -> bool {
+        self.args.is_none()
+            && matches!(
+                self.name.as_str(),
+                "VARCHAR" | "NVARCHAR" | "VARBINARY" | "CHARACTER VARYING"
+            )
+    }
+
+    fn sized(&self, name: &str) -> String {
+        match self.args {
+            Some(args) => format!("{}({})", name, args),
+            None => name.to_string(),
+        }
+    }
+}
+
+fn parse_type(data_type: &str) -> ParsedType<'_> {
+    let data_type = data_type.trim();
+    let (args, rest) = match (data_type.find('('), data_type.rfind(')')) {
+        (Some(open), Some(close)) if open < close => (
+            Some(data_type[open + 1..close].trim()),
+            format!("{} {}", &data_type[..open], &data_type[close + 1..]),
+        ),
+        _ => (None, data_type.to_string()),
+    };
+    let upper = rest.to_ascii_uppercase();
+    let array = upper.contains("[]");
+    let time_zone = upper.contains("WITH TIME ZONE");
+    let upper = upper
+        .replace("[]", " ")
+        .replace("WITHOUT TIME ZONE", " ")
+        .replace("WITH TIME ZONE", " ");
+    let words: Vec<&str> = upper.split_whitespace().collect();
+    ParsedType {
+        name: words
+            .iter()
+            .filter(|word| !matches!(**word, "UNSIGNED" | "SIGNED" | "ZEROFILL"))
+            .copied()
+            .collect::<Vec<_>>()
+            .join(" "),
+        args,
+        unsigned: words.contains(&"UNSIGNED"),
+        time_zone,
+        array,
+    }
+}
+
+fn kind_of(ty: &ParsedType) -> Option<TypeKind> {
+    let max = ty.args.is_some_and(|args| args.eq_ignore_ascii_case("max"));
+    Some(match ty.name.as_str() {
+        "BOOL" | "BOOLEAN" => TypeKind::Bool,
+        "BIT" if matches!(ty.args, None | Some("1")) => TypeKind::Bool,
+        "TINYINT" if ty.args == Some("1") => TypeKind::Bool,
+        "TINYINT" => TypeKind::TinyInt,
+        "SMALLINT" | "INT2" | "SMALLSERIAL" | "SERIAL2" | "YEAR" => TypeKind::SmallInt,
+        "MEDIUMINT" | "INT" | "INTEGER" | "INT4" | "SERIAL" | "SERIAL4" => TypeKind::Int,
+        "BIGINT" | "INT8" | "BIGSERIAL" | "SERIAL8" => TypeKind::BigInt,
+        "DECIMAL" | "DEC" | "NUMERIC" => TypeKind::Decimal,
+        // FLOAT is single precision on MySQL only; doubles lose nothing.
+        "DOUBLE" | "DOUBLE PRECISION" | "FLOAT8" | "FLOAT" => TypeKind::Double,
+        "REAL" | "FLOAT4" => TypeKind::Real,
+        "CHAR" | "CHARACTER" | "NCHAR" | "BPCHAR" => TypeKind::Char,
+        "VARCHAR" | "NVARCHAR" | "CHARACTER VARYING" | "VARCHAR2" if !max => TypeKind::VarChar,
+        "VARCHAR" | "NVARCHAR" | "TEXT" | "TINYTEXT" | "MEDIUMTEXT" | "LONGTEXT" | "NTEXT"
+        | "CLOB" | "CITEXT" | "ENUM" | "SET" => TypeKind::Text,
+        "BINARY" | "VARBINARY" if !max => TypeKind::Binary,
+        "VARBINARY" | "BLOB" | "TINYBLOB" | "MEDIUMBLOB" | "LONGBLOB" | "BYTEA" | "IMAGE" => {
+            TypeKind::Blob
+        }
+        "DATE" => TypeKind::Date,
+        "TIME" | "TIMETZ" => TypeKind::Time,
+        "TIMESTAMPTZ" | "DATETIMEOFFSET" => TypeKind::TimestampTz,
+        "TIMESTAMP" | "DATETIME" | "DATETIME2" | "SMALLDATETIME" if ty.time_zone => {
+            TypeKind::TimestampTz
+        }
+        "TIMESTAMP" | "DATETIME" | "DATETIME2" | "SMALLDATETIME" => TypeKind::DateTime,
+        "JSON" | "JSONB" => TypeKind::Json,
+        "UUID" | "UNIQUEIDENTIFIER" => TypeKind::Uuid,
+        _ => return None,
+    })
+}
+
+/// What a column of `data_type` holds; `None` for types not known here.
+pub(crate) fn type_kind(data_type: &str) -> Option<TypeKind> {
+    kind_of(&parse_type(data_type))
+}
+
+/// `INTEGER[]` and the like.
+pub(crate) fn is_array_type(data_type: &str) -> bool {
+    parse_type(data_type).array
+}
+
+/// Whether `target` takes `ty` as written. Drivers this module knows
+/// nothing about take every type.
+fn is_native(ty: &ParsedType, target: &str) -> bool {
+    let name = ty.name.as_str();
+    match target {
+        "mysql" => {
+            !ty.array
+                && !ty.time_zone
+                && !ty.is_unsized()
+                && matches!(
+                    name,
+                    "TINYINT"
+                        | "SMALLINT"
+                        | "MEDIUMINT"
+                        | "INT"
+                        | "INTEGER"
+                        | "BIGINT"
+                        | "DECIMAL"
+                        | "NUMERIC"
+                        | "FLOAT"
+                        | "DOUBLE"
+                        | "BIT"
+                        | "BOOL"
+                        | "BOOLEAN"
+                        | "CHAR"
+                        | "VARCHAR"
+                        | "BINARY"
+                        | "VARBINARY"
+                        | "TINYTEXT"
+                        | "TEXT"
+                        | "MEDIUMTEXT"
+                        | "LONGTEXT"
+                        | "TINYBLOB"
+                        | "BLOB"
+                        | "MEDIUMBLOB"
+                        | "LONGBLOB"
+                        | "ENUM"
+                        | "SET"
+                        | "DATE"
+                        | "TIME"
+                        | "DATETIME"
+                        | "TIMESTAMP"
+                        | "YEAR"
+                        | "JSON"
+                )
+        }
+        "postgres" => {
+            !ty.unsigned
+                && matches!(
+                    name,
+                    "SMALLINT"
+                        | "INTEGER"
+                        | "INT"
+                        | "BIGINT"
+                        | "INT2"
+                        | "INT4"
+                        | "INT8"
+                        | "SMALLSERIAL"
+                        | "SERIAL"
+                        | "BIGSERIAL"
+                        | "DECIMAL"
+                        | "NUMERIC"
+                        | "REAL"
+                        | "DOUBLE PRECISION"
+                        | "FLOAT4"
+                        | "FLOAT8"
+                        | "BOOLEAN"
+                        | "BOOL"
+                        | "CHAR"
+                        | "CHARACTER"
+                        | "VARCHAR"
+                        | "CHARACTER VARYING"
+                        | "TEXT"
+                        | "CITEXT"
+                        | "BYTEA"
+                        | "DATE"
+                        | "TIME"
+                        | "TIMETZ"
+                        | "TIMESTAMP"
+                        | "TIMESTAMPTZ"
+                        | "INTERVAL"
+                        | "JSON"
+                        | "JSONB"
+                        | "UUID"
+                        | "INET"
+                        | "CIDR"
+                        | "MACADDR"
+                        | "MONEY"
+                        | "XML"
+                )
+        }
+        "mssql" => {
+            !ty.array
+                && !ty.unsigned
+                && !ty.is_unsized()
+                && matches!(
+                    name,
+                    "BIT"
+                        | "TINYINT"
+                        | "SMALLINT"
+                        | "INT"
+                        | "BIGINT"
+                        | "DECIMAL"
+                        | "NUMERIC"
+                        | "MONEY"
+                        | "SMALLMONEY"
+                        | "FLOAT"
+                        | "REAL"
+                        | "CHAR"
+                        | "VARCHAR"
+                        | "NCHAR"
+                        | "NVARCHAR"
+                        | "BINARY"
+                        | "VARBINARY"
+                        | "DATE"
+                        | "TIME"
+                        | "DATETIME"
+                        | "DATETIME2"
+                        | "SMALLDATETIME"
+                        | "DATETIMEOFFSET"
+                        | "UNIQUEIDENTIFIER"
+                        | "XML"
+                )
+        }
+        // Any name is valid on SQLite; types are mapped to its usual ones.
+        "sqlite" => false,
+        _ => true,
+    }
+}
+
+fn render(kind: TypeKind, ty: &ParsedType, target: &str) -> String {
+    use TypeKind::*;
+    let unsigned = ty.unsigned;
+    let name = match target {
+        "postgres" => match kind {
+            Bool => "BOOLEAN",
+            TinyInt => "SMALLINT",
+            SmallInt if unsigned => "INTEGER",
+            SmallInt => "SMALLINT",
+            Int if unsigned => "BIGINT",
+            Int => "INTEGER",
+            BigInt if unsigned => "NUMERIC(20)",
+            BigInt => "BIGINT",
+            Decimal => return ty.sized("NUMERIC"),
+            Double => "DOUBLE PRECISION",
+            Real => "REAL",
+            Char => return ty.sized("CHAR"),
+            VarChar => return ty.sized("VARCHAR"),
+            Text => "TEXT",
+            Binary | Blob => "BYTEA",
+            Date => "DATE",
+            Time => return ty.sized("TIME"),
+            DateTime => return ty.sized("TIMESTAMP"),
+            TimestampTz => return ty.sized("TIMESTAMPTZ"),
+            Json => "JSONB",
+            Uuid => "UUID",
+        },
+        "mysql" => match kind {
+            Bool => "TINYINT(1)",
+            TinyInt | SmallInt | Int | BigInt => {
+                let name = match kind {
+                    TinyInt => "TINYINT",
+                    SmallInt => "SMALLINT",
+                    Int => "INT",
+                    _ => "BIGINT",
+                };
+                return if unsigned {
+                    format!("{} UNSIGNED", name)
+                } else {
+                    name.to_string()
+                };
+            }
+            Decimal => return ty.sized("DECIMAL"),
+            Double => "DOUBLE",
+            Real => "FLOAT",
+            Char => return ty.sized("CHAR"),
+            // Unbounded, but a VARCHAR can still be a key.
+            VarChar if ty.args.is_none() => "VARCHAR(255)",
+            VarChar => return ty.sized("VARCHAR"),
+            Text => "LONGTEXT",
+            Binary if ty.args.is_some() => return ty.sized("VARBINARY"),
+            Binary | Blob => "LONGBLOB",
+            Date => "DATE",
+            Time => return ty.sized("TIME"),
+            DateTime | TimestampTz => return ty.sized("DATETIME"),
+            Json => "JSON",
+            Uuid => "CHAR(36)",
+        },
+        "mssql" => match kind {
+            Bool => "BIT",
+            // TINYINT has no sign on SQL Server.
+            TinyInt if unsigned => "TINYINT",
+            TinyInt => "SMALLINT",
+            SmallInt if unsigned => "INT",
+            SmallInt => "SMALLINT",
+            Int if unsigned => "BIGINT",
+            Int => "INT",
+            BigInt if unsigned => "DECIMAL(20, 0)",
+            BigInt => "BIGINT",
+            Decimal => return ty.sized("DECIMAL"),
+            Double => "FLOAT",
+            Real => "REAL",
+            Char => return ty.sized("NCHAR"),
+            VarChar => match ty.args.and_then(|args| args.parse::<u32>().ok()) {
+                Some(len) if len <= 4000 => return format!("NVARCHAR({})", len),
+                _ => "NVARCHAR(MAX)",
+            },
+            Text | Json => "NVARCHAR(MAX)",
+            Binary if ty.args.is_some() => return ty.sized("VARBINARY"),
+            Binary | Blob => "VARBINARY(MAX)",
+            Date => "DATE",
+            Time => return ty.sized("TIME"),
+            DateTime => return ty.sized("DATETIME2"),
+            TimestampTz => return ty.sized("DATETIMEOFFSET"),
+            Uuid => "UNIQUEIDENTIFIER",
+        },
+        _ => match kind {
+            Bool => "BOOLEAN",
+            TinyInt | SmallInt | Int | BigInt => "INTEGER",
+            Decimal => return ty.sized("NUMERIC"),
+            Double | Real => "REAL",
+            Char | VarChar | Text | Json | Uuid => "TEXT",
+            Binary | Blob => "BLOB",
+            Date => "DATE",
+            Time => "TIME",
+            DateTime | TimestampTz => "DATETIME",
+        },
+    };
+    name.to_string()
+}
+
+/// `data_type` as the `target` driver names it. Types that have no
+/// counterpart there are kept as written.
+pub fn map_type(data_type: &str, target: &str) -> String {
+    let ty = parse_type(data_type);
+    if is_native(&ty, target) {
+        return data_type.trim().to_string();
+    }
+    if ty.array {
+        return match target {
+            "mysql" => "JSON",
+            "mssql" => "NVARCHAR(MAX)",
+            _ => "TEXT",
+        }
+        .to_string();
+    }
+    match kind_of(&ty) {
+        Some(kind) => render(kind, &ty, target),
+        None => data_type.trim().to_string(),
+    }
+}
+
+/// A column default as `target` reads it. PostgreSQL casts are dropped
+/// elsewhere, and a `nextval()` default is left to the auto-increment.
+pub fn map_default(default: &str, target: &str) -> Option<String> {
+    let mut value = default.trim();
+    if value.to_ascii_lowercase().starts_with("nextval(") {
+        return None;
+    }
+    if target == "postgres" {
+        return Some(value.to_string());
+    }
+    // A `::` after the last quote is a cast, not part of a string.
+    while let Some(cast) = value.rfind("::") {
+        if value.rfind('\'').is_some_and(|quote| quote > cast) {
+            break;
+        }
+        value = value[..cast].trim_end();
+    }
+    let mapped = match value.to_ascii_lowercase().as_str() {
+        "now()" | "current_timestamp()" => "CURRENT_TIMESTAMP",
+        "true" if target == "mssql" => "1",
+        "false" if target == "mssql" => "0",
+        _ => value,
+    };
+    Some(mapped.to_string())
+}
+
+/// `column` as the `target` driver can create it.
+pub fn map_column(column: &TableColumn, target: &str) -> ColumnDefinition {
+    let mut definition = ColumnDefinition::from(column);
+    definition.data_type = if target == "sqlite" && column.is_auto_increment {
+        // Only an INTEGER PRIMARY KEY can be AUTOINCREMENT.
+        "INTEGER".to_string()
+    } else {
+        map_type(&column.data_type, target)
+    };
+    definition.default_value = column
+        .default_value
+        .as_deref()
+        .and_then(|default| map_default(default, target));
+    definition
+}
+
+// ---------------------------------------------------------------------------
+// Apply
+// ---------------------------------------------------------------------------
+
+/// Runs the statements of an apply on the target, recording each outcome.
+struct Apply<'a> {
+    drv: &'a dyn DatabaseDriver,
+    params: &'a ConnectionParams,
+    schema: Option<&'a str>,
+    statements: Vec<AppliedStatement>,
+}
+
+impl Apply<'_> {
+    /// Run what the driver generated for `what`. A failure is recorded and
+    /// the next statements still run.
+    async fn run(&mut self, what: String, generated: Result<Vec<String>, String>) {
+        let statements = match generated {
+            Ok(statements) => statements,
+            Err(e) => {
+                self.statements.push(AppliedStatement {
+                    what,
+                    sql: None,
+                    error: Some(e),
+                });
+                return;
+            }
+        };
+        for sql in statements {
+            let error = self
+                .drv
+                .execute_query(self.params, &sql, None, 1, self.schema)
+                .await
+                .err();
+            self.statements.push(AppliedStatement {
+                what: what.clone(),
+                sql: Some(sql),
+                error,
+            });
+        }
+    }
+
+    async fn create_table(&mut self, table: &TableSchema, target: &str) {
+        let columns = table
+            .columns
+            .iter()
+            .map(|column| map_column(column, target))
+            .collect();
+        let generated = self
+            .drv
+            .get_create_table_sql(&table.name, columns, self.schema)
+            .await;
+        self.run(format!("Create table {}", table.name), generated)
+            .await;
+    }
+
+    async fn add_foreign_keys(&mut self, table: &TableSchema) {
+        for (name, rows) in constraints(&table.foreign_keys) {
+            let what = format!("Add foreign key {} on {}", name, table.name);
+            if rows.len() > 1 {
+                self.run(what, Err("composite foreign keys are not generated".into()))
+                    .await;
+                continue;
+            }
+            let fk = rows[0];
+            let generated = self
+                .drv
+                .get_create_foreign_key_sql(
+                    &table.name,
+                    &fk.name,
+                    &fk.column_name,
+                    &fk.ref_table,
+                    &fk.ref_column,
+                    fk.on_delete.as_deref(),
+                    fk.on_update.as_deref(),
+                    self.schema,
+                )
+                .await;
+            self.run(what, generated).await;
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tauri commands
+// ---------------------------------------------------------------------------
+
+/// Parse a CREATE TABLE script into tables for the ER diagram. `dialect`
+/// is a driver id (`mysql`, `postgres`, `sqlite`, `mssql`).
+#[tauri::command]
+pub async fn parse_sql_schema(
+    sql: String,
+    dialect: Option<String>,
+) -> Result<ParsedSqlSchema, String> {
+    let parsed = parse_schema(&sql, dialect.as_deref())?;
+    log::info!(
+        "Parsed {} table(s) from a SQL script ({} statement(s) skipped)",
+        parsed.tables.len(),
+        parsed.warnings.len()
+    );
+    Ok(parsed)
+}
+
+/// Create the tables of a SQL script on `connection_id`. `dialect` is the
+/// driver id the script was written for; column types are mapped to the
+/// target's. Tables are created parents first and their foreign keys added
+/// once every table exists, so reference cycles need no special care.
+#[tauri::command]
+pub async fn apply_sql_schema<R: Runtime>(
+    app: AppHandle<R>,
+    connection_id: String,
+    sql: String,
+    dialect: Option<String>,
+    schema: Option<String>,
+) -> Result<AppliedSqlSchema, String> {
+    let parsed = parse_schema(&sql, dialect.as_deref())?;
+    if parsed.tables.is_empty() {
+        return Err("The script creates no tables".into());
+    }
+    let saved_conn = find_connection_by_id(&app, &connection_id)?;
+    crate::read_only::ensure_writable(&saved_conn.params)?;
+    let expanded_params = expand_ssh_connection_params(&app, &saved_conn.params).await?;
+    let params = resolve_connection_params_with_id(&expanded_params, &connection_id)?;
+    let drv = driver_for(&saved_conn.params.driver).await?;
+    let target = drv.manifest().id.clone();
+
+    let names: Vec<String> = parsed.tables.iter().map(|t| t.name.clone()).collect();
+    let foreign_keys: HashMap<String, Vec<ForeignKey>> = parsed
+        .tables
+        .iter()
+        .map(|t| (t.name.clone(), t.foreign_keys.clone()))
+        .collect();
+    let (ordered, _) = dependency_order(&names, &foreign_keys);
+    let by_name: HashMap<&str, &TableSchema> =
+        parsed.tables.iter().map(|t| (t.name.as_str(), t)).collect();
+    let tables: Vec<&TableSchema> = ordered
+        .iter()
+        .filter_map(|name| by_name.get(name.as_str()).copied())
+        .collect();
+    log::info!(
+        "Applying {} table(s) from a SQL script to connection {}",
+        tables.len(),
+        connection_id
+    );
+
+    let mut apply = Apply {
+        drv: drv.as_ref(),
+        params: &params,
+        schema: schema.as_deref(),
+        statements: Vec::new(),
+    };
+    crate::server_info::scope(crate::server_info::cached(&connection_id), async {
+        for table in &tables {
+            apply.create_table(table, &target).await;
+        }
+        for table in &tables {
+            apply.add_foreign_keys(table).await;
+        }
+    })
+    .await;
+
+    let failed = apply
+        .statements
+        .iter()
+        .filter(|statement| statement.error.is_some())
+        .count();
+    log::info!(
+        "Applied SQL script: {} statement(s), {} failed",
+        apply.statements.len(),
+        failed
+    );
+    Ok(AppliedSqlSchema {
+        statements: apply.statements,
+        warnings: parsed.warnings,
+        failed,
+    })
+}
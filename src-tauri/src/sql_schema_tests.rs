@@ -0,0 +1,215 @@
+#[cfg(test)]
+mod tests {
+    use crate::models::{TableColumn, TableSchema};
+    use crate::sql_schema::{map_column, map_default, map_type, parse_schema};
+
+    fn table<'a>(tables: &'a [TableSchema], name: &str) -> &'a TableSchema {
+        tables
+            .iter()
+            .find(|t| t.name == name)
+            .unwrap_or_else(|| panic!("table {} missing", name))
+    }
+
+    fn column<'a>(table: &'a TableSchema, name: &str) -> &'a TableColumn {
+        table
+            .columns
+            .iter()
+            .find(|c| c.name == name)
+            .unwrap_or_else(|| panic!("column {} missing", name))
+    }
+
+    #[test]
+    fn mysql_tables_keep_their_columns_and_keys() {
+        let sql = "
+-- users first
+CREATE TABLE `users` (
+  `id` INT NOT NULL AUTO_INCREMENT,
+  `email` VARCHAR(255) NOT NULL,
+  `status` VARCHAR(20) DEFAULT 'active',
+  PRIMARY KEY (`id`)
+);
+CREATE TABLE `orders` (
+  `id` INT PRIMARY KEY AUTO_INCREMENT,
+  `user_id` INT NOT NULL,
+  CONSTRAINT `fk_orders_user` FOREIGN KEY (`user_id`) REFERENCES `users` (`id`) ON DELETE CASCADE
+);
+";
+        let parsed = parse_schema(sql, Some("mysql")).unwrap();
+        assert!(parsed.warnings.is_empty(), "{:?}", parsed.warnings);
+        let names: Vec<&str> = parsed.tables.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(names, ["users", "orders"]);
+
+        let users = table(&parsed.tables, "users");
+        let id = column(users, "id");
+        assert!(id.is_pk && id.is_auto_increment && !id.is_nullable);
+        let email = column(users, "email");
+        assert_eq!(email.character_maximum_length, Some(255));
+        assert!(!email.is_nullable);
+        assert_eq!(
+            column(users, "status").default_value.as_deref(),
+            Some("'active'")
+        );
+
+        let orders = table(&parsed.tables, "orders");
+        assert!(column(orders, "id").is_pk);
+        let fk = &orders.foreign_keys[0];
+        assert_eq!(fk.name, "fk_orders_user");
+        assert_eq!(fk.column_name, "user_id");
+        assert_eq!(
+            (fk.ref_table.as_str(), fk.ref_column.as_str()),
+            ("users", "id")
+        );
+        assert_eq!(fk.on_delete.as_deref(), Some("CASCADE"));
+    }
+
+    #[test]
+    fn postgres_serials_and_inline_references() {
+        let sql = "
+CREATE TABLE public.authors (id BIGSERIAL PRIMARY KEY, name TEXT);
+CREATE TABLE public.books (
+  id INT GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,
+  author_id BIGINT REFERENCES public.authors (id)
+);
+";
+        let parsed = parse_schema(sql, Some("postgres")).unwrap();
+        assert!(parsed.warnings.is_empty(), "{:?}", parsed.warnings);
+        assert!(column(table(&parsed.tables, "authors"), "id").is_auto_increment);
+
+        let books = table(&parsed.tables, "books");
+        assert!(column(books, "id").is_auto_increment);
+        let fk = &books.foreign_keys[0];
+        assert_eq!(fk.name, "fk_books_author_id");
+        assert_eq!(fk.ref_table, "authors");
+    }
+
+    #[test]
+    fn migrations_are_replayed_in_order() {
+        let sql = "
+CREATE TABLE teams (id INTEGER PRIMARY KEY);
+CREATE TABLE people (id INTEGER PRIMARY KEY, nick TEXT, legacy TEXT);
+ALTER TABLE people ADD COLUMN team_id INTEGER;
+ALTER TABLE people ADD CONSTRAINT people_team FOREIGN KEY (team_id) REFERENCES teams (id);
+ALTER TABLE people DROP COLUMN legacy;
+ALTER TABLE people RENAME COLUMN nick TO nickname;
+ALTER TABLE teams RENAME TO squads;
+CREATE TABLE scratch (id INTEGER);
+DROP TABLE scratch;
+";
+        let parsed = parse_schema(sql, Some("sqlite")).unwrap();
+        assert!(parsed.warnings.is_empty(), "{:?}", parsed.warnings);
+        let names: Vec<&str> = parsed.tables.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(names, ["squads", "people"]);
+
+        let people = table(&parsed.tables, "people");
+        let columns: Vec<&str> = people.columns.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(columns, ["id", "nickname", "team_id"]);
+        assert_eq!(people.foreign_keys[0].name, "people_team");
+        assert_eq!(people.foreign_keys[0].ref_table, "squads");
+    }
+
+    #[test]
+    fn unreadable_statements_are_reported_and_skipped() {
+        let sql = "
+CREATE TABLE ok (id INT);
+CREATE TABLE broken (id INT,;
+ALTER TABLE missing ADD COLUMN x INT;
+INSERT INTO ok VALUES (1);
+";
+        let parsed = parse_schema(sql, None).unwrap();
+        assert_eq!(parsed.tables.len(), 1);
+        assert_eq!(parsed.warnings.len(), 2);
+        assert!(parsed.warnings[0].starts_with("Statement 2 skipped"));
+        assert!(parsed.warnings[1].contains("unknown table missing"));
+    }
+
+    #[test]
+    fn composite_foreign_keys_share_a_name() {
+        let sql = "
+CREATE TABLE parent (a INT, b INT, PRIMARY KEY (a, b));
+CREATE TABLE child (
+  a INT, b INT,
+  FOREIGN KEY (a, b) REFERENCES parent (a, b) ON UPDATE SET NULL
+);
+";
+        let parsed = parse_schema(sql, None).unwrap();
+        let parent = table(&parsed.tables, "parent");
+        assert!(parent.columns.iter().all(|c| c.is_pk));
+        let child = table(&parsed.tables, "child");
+        assert_eq!(child.foreign_keys.len(), 2);
+        assert!(child
+            .foreign_keys
+            .iter()
+            .all(|fk| fk.name == "fk_child_a_b"));
+        assert_eq!(child.foreign_keys[1].ref_column, "b");
+        assert_eq!(child.foreign_keys[0].on_update.as_deref(), Some("SET NULL"));
+    }
+
+    #[test]
+    fn types_are_mapped_to_the_target_dialect() {
+        let cases = [
+            ("TINYINT(1)", "postgres", "BOOLEAN"),
+            ("INT(11) UNSIGNED", "postgres", "BIGINT"),
+            ("DATETIME", "postgres", "TIMESTAMP"),
+            ("LONGTEXT", "postgres", "TEXT"),
+            ("MEDIUMBLOB", "postgres", "BYTEA"),
+            ("DOUBLE", "postgres", "DOUBLE PRECISION"),
+            ("BOOLEAN", "mysql", "BOOLEAN"),
+            ("CHARACTER VARYING", "mysql", "VARCHAR(255)"),
+            ("TIMESTAMP(3) WITH TIME ZONE", "mysql", "DATETIME(3)"),
+            ("BYTEA", "mysql", "LONGBLOB"),
+            ("UUID", "mysql", "CHAR(36)"),
+            ("INTEGER[]", "mysql", "JSON"),
+            ("TEXT", "mssql", "NVARCHAR(MAX)"),
+            ("TIMESTAMP", "mssql", "DATETIME2"),
+            ("CHARACTER VARYING(8000)", "mssql", "NVARCHAR(MAX)"),
+            ("TINYINT", "mssql", "TINYINT"),
+            ("UNIQUEIDENTIFIER", "postgres", "UUID"),
+            ("VARCHAR(40)", "sqlite", "TEXT"),
+            ("BIGINT UNSIGNED", "sqlite", "INTEGER"),
+        ];
+        for (data_type, target, expected) in cases {
+            assert_eq!(
+                map_type(data_type, target),
+                expected,
+                "{} on {}",
+                data_type,
+                target
+            );
+        }
+    }
+
+    #[test]
+    fn unknown_types_and_drivers_keep_the_type() {
+        assert_eq!(map_type("GEOMETRY", "postgres"), "GEOMETRY");
+        assert_eq!(map_type("tsvector", "mysql"), "tsvector");
+        assert_eq!(map_type("TINYINT(1)", "duckdb"), "TINYINT(1)");
+    }
+
+    #[test]
+    fn defaults_lose_postgres_casts_elsewhere() {
+        assert_eq!(
+            map_default("'active'::character varying", "mysql").as_deref(),
+            Some("'active'")
+        );
+        assert_eq!(map_default("'a::b'", "sqlite").as_deref(), Some("'a::b'"));
+        assert_eq!(
+            map_default("now()", "mysql").as_deref(),
+            Some("CURRENT_TIMESTAMP")
+        );
+        assert_eq!(map_default("TRUE", "mssql").as_deref(), Some("1"));
+        assert_eq!(
+            map_default("nextval('users_id_seq'::regclass)", "postgres"),
+            None
+        );
+    }
+
+    #[test]
+    fn sqlite_auto_increment_columns_become_integer() {
+        let parsed = parse_schema("CREATE TABLE t (id BIGSERIAL PRIMARY KEY);", None).unwrap();
+        let id = column(table(&parsed.tables, "t"), "id");
+        let mapped = map_column(id, "sqlite");
+        assert_eq!(mapped.data_type, "INTEGER");
+        assert!(mapped.is_pk && mapped.is_auto_increment);
+        assert_eq!(map_column(id, "mysql").data_type, "BIGINT");
+    }
+}
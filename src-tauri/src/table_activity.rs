@@ -0,0 +1,673 @@
+//! Per-table activity: which tables are read and written through the app.
+//!
+//! Every statement that runs successfully (editor queries and batches,
+//! streamed queries, table browsing, project runs) is scanned for the tables
+//! it reads (`FROM`, `JOIN`, `USING`) and writes (`INSERT INTO`, `UPDATE`,
+//! `DELETE FROM`, `MERGE INTO`, `TRUNCATE`); grid and bulk edits count as
+//! writes to their table. Counts are kept per table with a per-day breakdown
+//! of the last [`HEAT_MAP_DAYS`] days for the heat map. They are buffered in
+//! memory and written [`FLUSH_DELAY`] after the first unwritten one, so a
+//! burst of queries costs a single write of the file.
+//!
+//! `get_table_activity` merges the counts with the connection's table list,
+//! so tables nobody touched show up too, and flags archive candidates:
+//! tables not used for `idle_days` while tracking has been on for at least
+//! that long.
+//!
+//! Storage layout under the application config directory:
+//!   - `table_activity/<connection_id>.json` — the counts of a connection
+
+use chrono::{DateTime, Duration, Utc};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use sqlparser::keywords::Keyword;
+use sqlparser::tokenizer::{Token, Tokenizer};
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tauri::{AppHandle, Runtime};
+
+use crate::models::ConnectionParams;
+
+const TABLE_ACTIVITY_DIR: &str = "table_activity";
+/// Days of per-day counts kept for the heat map.
+pub const HEAT_MAP_DAYS: i64 = 90;
+/// Unused days after which a table is an archive candidate, by default.
+pub const DEFAULT_IDLE_DAYS: u32 = 90;
+
+/// How long counts wait in memory before they are written.
+pub const FLUSH_DELAY: std::time::Duration = std::time::Duration::from_secs(5);
+
+/// Serializes read-modify-write of the activity files.
+static WRITE_LOCK: Lazy<Mutex<()>> = Lazy::new(|| Mutex::new(()));
+
+/// Counts not written yet, per connection id.
+static PENDING: Lazy<Mutex<HashMap<String, Vec<ActivityRecord>>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// A table a statement reads or writes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TableAccess {
+    pub schema: Option<String>,
+    pub table: String,
+    pub write: bool,
+}
+
+/// The tables one statement or edit touched, waiting to be counted.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ActivityRecord {
+    /// Schema of the tables named without one.
+    pub default_schema: Option<String>,
+    pub accesses: Vec<TableAccess>,
+    pub at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct TableActivity {
+    /// The schema the statement named, or the active one.
+    pub schema: Option<String>,
+    pub table: String,
+    pub reads: u64,
+    pub writes: u64,
+    pub last_read_at: Option<String>,
+    pub last_write_at: Option<String>,
+    /// Reads and writes per `YYYY-MM-DD` (UTC).
+    #[serde(default)]
+    pub daily: BTreeMap<String, u64>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ActivityLog {
+    /// When the first statement of the connection was counted.
+    pub tracking_since: Option<String>,
+    #[serde(default)]
+    pub tables: Vec<TableActivity>,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct TableActivityRow {
+    pub schema: Option<String>,
+    pub table: String,
+    pub reads: u64,
+    pub writes: u64,
+    pub last_used_at: Option<String>,
+    pub daily: BTreeMap<String, u64>,
+    pub archive_candidate: bool,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct TableActivityReport {
+    pub tracking_since: Option<String>,
+    pub idle_days: u32,
+    /// Every table of the schema, most used first.
+    pub tables: Vec<TableActivityRow>,
+}
+
+// ---------------------------------------------------------------------------
+// Statement scanning (testable; pure)
+// ---------------------------------------------------------------------------
+
+fn is_keyword(token: Option<&Token>, keyword: &str) -> bool {
+    matches!(token, Some(Token::Word(w)) if w.quote_style.is_none()
+        && w.value.eq_ignore_ascii_case(keyword))
+}
+
+/// `db.schema.table` starting at `pos`: the schema (the part before the
+/// table), the table and the position after the name.
+fn qualified_name(tokens: &[Token], mut pos: usize) -> Option<(Option<String>, String, usize)> {
+    let mut parts = Vec::new();
+    while let Some(Token::Word(w)) = tokens.get(pos) {
+        parts.push(w.value.clone());
+        pos += 1;
+        if tokens.get(pos) != Some(&Token::Period) {
+            break;
+        }
+        pos += 1;
+    }
+    let table = parts.pop()?;
+    Some((parts.pop(), table, pos))
+}
+
+/// Skip an alias after a table name: `AS x`, or a bare word that is not a
+/// keyword.
+fn skip_alias(tokens: &[Token], pos: usize) -> usize {
+    if is_keyword(tokens.get(pos), "AS") {
+        return pos + 2;
+    }
+    match tokens.get(pos) {
+        Some(Token::Word(w)) if w.quote_style.is_some() || w.keyword == Keyword::NoKeyword => {
+            pos + 1
+        }
+        _ => pos,
+    }
+}
+
+/// The table named at `pos`, unless a read is a function call. Returns the
+/// position after the name.
+fn push_table(
+    tokens: &[Token],
+    pos: usize,
+    write: bool,
+    accesses: &mut Vec<TableAccess>,
+) -> Option<usize> {
+    let (schema, table, next) = qualified_name(tokens, pos)?;
+    if write || tokens.get(next) != Some(&Token::LParen) {
+        accesses.push(TableAccess {
+            schema,
+            table,
+            write,
+        });
+    }
+    Some(next)
+}
+
+/// The comma-separated tables after `FROM`, `JOIN` or `USING`. Subqueries
+/// and table functions are left to the main scan.
+fn table_list(tokens: &[Token], mut pos: usize, write: bool, accesses: &mut Vec<TableAccess>) {
+    loop {
+        if is_keyword(tokens.get(pos), "ONLY") {
+            pos += 1;
+        }
+        if is_keyword(tokens.get(pos), "LATERAL") {
+            return;
+        }
+        let Some(next) = push_table(tokens, pos, write, accesses) else {
+            return;
+        };
+        pos = skip_alias(tokens, next);
+        if tokens.get(pos) != Some(&Token::Comma) {
+            return;
+        }
+        pos += 1;
+    }
+}
+
+/// Names defined by `WITH name AS (...)`, which are not tables.
+fn cte_names(tokens: &[Token]) -> Vec<String> {
+    let mut names = Vec::new();
+    for (i, token) in tokens.iter().enumerate() {
+        let Token::Word(w) = token else { continue };
+        let mut pos = i + 1;
+        if tokens.get(pos) == Some(&Token::LParen) {
+            // Column list: `name (a, b) AS (...)`.
+            while tokens.get(pos).is_some_and(|t| t != &Token::RParen) {
+                pos += 1;
+            }
+            pos += 1;
+        }
+        if is_keyword(tokens.get(pos), "AS") && tokens.get(pos + 1) == Some(&Token::LParen) {
+            names.push(w.value.to_lowercase());
+        }
+    }
+    names
+}
+
+/// The tables `sql` reads and writes, each once. `driver` picks the
+/// identifier quoting; SQL the tokenizer cannot read yields nothing.
+pub fn extract_tables(driver: &str, sql: &str) -> Vec<TableAccess> {
+    let dialect = crate::sql_schema::dialect_for(Some(driver));
+    let Ok(tokens) = Tokenizer::new(dialect.as_ref(), sql).tokenize() else {
+        return Vec::new();
+    };
+    let tokens: Vec<Token> = tokens
+        .into_iter()
+        .filter(|t| !matches!(t, Token::Whitespace(_)))
+        .collect();
+
+    let mut accesses = Vec::new();
+    // Per parenthesis depth: whether a statement runs there, so that the
+    // FROM of `EXTRACT(YEAR FROM d)` is not taken for a table.
+    let mut levels = vec![false];
+    for (i, token) in tokens.iter().enumerate() {
+        let in_statement = *levels.last().unwrap_or(&false);
+        let next = tokens.get(i + 1);
+        match token {
+            Token::LParen => levels.push(false),
+            Token::RParen if levels.len() > 1 => {
+                levels.pop();
+            }
+            Token::SemiColon => levels = vec![false],
+            Token::Word(w) if w.quote_style.is_none() => {
+                let word = w.value.to_uppercase();
+                let previous = i.checked_sub(1).and_then(|p| tokens.get(p));
+                match word.as_str() {
+                    "SELECT" => *levels.last_mut().unwrap() = true,
+                    "FROM" if in_statement && !is_keyword(previous, "DISTINCT") => table_list(
+                        &tokens,
+                        i + 1,
+                        is_keyword(previous, "DELETE"),
+                        &mut accesses,
+                    ),
+                    "JOIN" | "USING" if in_statement => {
+                        table_list(&tokens, i + 1, false, &mut accesses)
+                    }
+                    "UPDATE" => {
+                        *levels.last_mut().unwrap() = true;
+                        let mut pos = i + 1;
+                        while ["LOW_PRIORITY", "IGNORE", "ONLY"]
+                            .iter()
+                            .any(|k| is_keyword(tokens.get(pos), k))
+                        {
+                            pos += 1;
+                        }
+                        push_table(&tokens, pos, true, &mut accesses);
+                    }
+                    "DELETE" => {
+                        *levels.last_mut().unwrap() = true;
+                        if !is_keyword(next, "FROM") {
+                            push_table(&tokens, i + 1, true, &mut accesses);
+                        }
+                    }
+                    "INSERT" | "REPLACE" | "MERGE" => {
+                        if word != "INSERT" && !is_keyword(next, "INTO") {
+                            continue;
+                        }
+                        *levels.last_mut().unwrap() = true;
+                        let mut pos = i + 1;
+                        while [
+                            "LOW_PRIORITY",
+                            "DELAYED",
+                            "HIGH_PRIORITY",
+                            "IGNORE",
+                            "OR",
+                            "REPLACE",
+                            "ROLLBACK",
+                            "ABORT",
+                            "FAIL",
+                            "OVERWRITE",
+                            "TABLE",
+                            "INTO",
+                        ]
+                        .iter()
+                        .any(|k| is_keyword(tokens.get(pos), k))
+                        {
+                            pos += 1;
+                        }
+                        push_table(&tokens, pos, true, &mut accesses);
+                    }
+                    "TRUNCATE" => {
+                        let mut pos = i + 1;
+                        while is_keyword(tokens.get(pos), "TABLE")
+                            || is_keyword(tokens.get(pos), "ONLY")
+                        {
+                            pos += 1;
+                        }
+                        table_list(&tokens, pos, true, &mut accesses);
+                    }
+                    _ => {}
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let ctes = cte_names(&tokens);
+    let mut unique: Vec<TableAccess> = Vec::new();
+    for access in accesses {
+        let is_cte = access.schema.is_none() && ctes.contains(&access.table.to_lowercase());
+        if !is_cte && !unique.contains(&access) {
+            unique.push(access);
+        }
+    }
+    unique
+}
+
+// ---------------------------------------------------------------------------
+// Report (testable; pure)
+// ---------------------------------------------------------------------------
+
+fn parse_time(value: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(value)
+        .ok()
+        .map(|t| t.with_timezone(&Utc))
+}
+
+fn latest(a: Option<String>, b: Option<&String>) -> Option<String> {
+    match (a, b) {
+        (Some(a), Some(b)) if parse_time(b) > parse_time(&a) => Some(b.clone()),
+        (None, Some(b)) => Some(b.clone()),
+        (a, _) => a,
+    }
+}
+
+fn same_name(a: &str, b: &str) -> bool {
+    a.eq_ignore_ascii_case(b)
+}
+
+/// Counts of the `tables` of `schema` (names from the driver), most used
+/// first. Activity recorded without a schema ran in the connection's
+/// `default_schema`, so it only counts there (or in a report without a
+/// schema) rather than once in every schema holding a table of that name.
+pub fn build_report(
+    log: &ActivityLog,
+    schema: Option<&str>,
+    default_schema: Option<&str>,
+    tables: &[String],
+    idle_days: u32,
+    now: DateTime<Utc>,
+) -> TableActivityReport {
+    let cutoff = now - Duration::days(i64::from(idle_days));
+    let tracked_long_enough = log
+        .tracking_since
+        .as_deref()
+        .and_then(parse_time)
+        .is_some_and(|since| since <= cutoff);
+
+    let mut rows: Vec<TableActivityRow> = tables
+        .iter()
+        .map(|table| {
+            let mut row = TableActivityRow {
+                schema: schema.map(str::to_string),
+                table: table.clone(),
+                reads: 0,
+                writes: 0,
+                last_used_at: None,
+                daily: BTreeMap::new(),
+                archive_candidate: false,
+            };
+            let matching = log.tables.iter().filter(|entry| {
+                same_name(&entry.table, table)
+                    && match (entry.schema.as_deref(), schema) {
+                        (Some(a), Some(b)) => same_name(a, b),
+                        (Some(_), None) => false,
+                        (None, Some(b)) => default_schema.is_some_and(|d| same_name(d, b)),
+                        (None, None) => true,
+                    }
+            });
+            for entry in matching {
+                row.reads += entry.reads;
+                row.writes += entry.writes;
+                row.last_used_at = latest(row.last_used_at, entry.last_read_at.as_ref());
+                row.last_used_at = latest(row.last_used_at, entry.last_write_at.as_ref());
+                for (day, count) in &entry.daily {
+                    *row.daily.entry(day.clone()).or_default() += count;
+                }
+            }
+            row.archive_candidate = tracked_long_enough
+                && row
+                    .last_used_at
+                    .as_deref()
+                    .and_then(parse_time)
+                    .map_or(true, |used| used < cutoff);
+            row
+        })
+        .collect();
+    rows.sort_by(|a, b| {
+        (b.reads + b.writes)
+            .cmp(&(a.reads + a.writes))
+            .then_with(|| a.table.cmp(&b.table))
+    });
+
+    TableActivityReport {
+        tracking_since: log.tracking_since.clone(),
+        idle_days,
+        tables: rows,
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Storage (testable; take a directory)
+// ---------------------------------------------------------------------------
+
+fn log_path_in(dir: &Path, connection_id: &str) -> PathBuf {
+    dir.join(format!("{}.json", connection_id))
+}
+
+pub fn read_log_in(dir: &Path, connection_id: &str) -> ActivityLog {
+    fs::read_to_string(log_path_in(dir, connection_id))
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+/// Count every record; tables named without a schema are counted in the
+/// record's `default_schema`. Per-day counts older than [`HEAT_MAP_DAYS`]
+/// before the latest record are dropped.
+pub fn record_in(
+    dir: &Path,
+    connection_id: &str,
+    records: &[ActivityRecord],
+) -> Result<(), String> {
+    let Some(latest) = records.iter().map(|r| r.at).max() else {
+        return Ok(());
+    };
+    let _guard = WRITE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let mut log = read_log_in(dir, connection_id);
+    let oldest_day = (latest - Duration::days(HEAT_MAP_DAYS - 1))
+        .format("%Y-%m-%d")
+        .to_string();
+
+    for record in records {
+        let timestamp = record.at.to_rfc3339();
+        let day = record.at.format("%Y-%m-%d").to_string();
+        log.tracking_since.get_or_insert_with(|| timestamp.clone());
+        for access in &record.accesses {
+            let schema = access
+                .schema
+                .clone()
+                .or_else(|| record.default_schema.clone());
+            let index = match log
+                .tables
+                .iter()
+                .position(|e| e.schema == schema && e.table == access.table)
+            {
+                Some(index) => index,
+                None => {
+                    log.tables.push(TableActivity {
+                        schema,
+                        table: access.table.clone(),
+                        ..Default::default()
+                    });
+                    log.tables.len() - 1
+                }
+            };
+            let entry = &mut log.tables[index];
+            if access.write {
+                entry.writes += 1;
+                entry.last_write_at = latest(entry.last_write_at.take(), Some(&timestamp));
+            } else {
+                entry.reads += 1;
+                entry.last_read_at = latest(entry.last_read_at.take(), Some(&timestamp));
+            }
+            *entry.daily.entry(day.clone()).or_default() += 1;
+        }
+    }
+    for entry in &mut log.tables {
+        entry.daily.retain(|day, _| *day >= oldest_day);
+    }
+
+    let json = serde_json::to_string_pretty(&log)
+        .map_err(|e| format!("Failed to serialize table activity: {}", e))?;
+    crate::config_store::write_atomic(&log_path_in(dir, connection_id), &json)
+}
+
+// ---------------------------------------------------------------------------
+// App integration
+// ---------------------------------------------------------------------------
+
+fn activity_dir<R: Runtime>(app: &AppHandle<R>) -> Result<PathBuf, String> {
+    Ok(crate::paths::app_config_dir(app)?.join(TABLE_ACTIVITY_DIR))
+}
+
+fn is_enabled() -> bool {
+    crate::config::get_cached_config()
+        .table_activity_enabled
+        .unwrap_or(true)
+}
+
+/// Schema that unqualified names resolve to when a statement ran without
+/// one: the driver's default schema, or the connection's database on MySQL.
+pub fn default_schema(params: &ConnectionParams) -> Option<String> {
+    match params.driver.as_str() {
+        "postgres" => Some("public".to_string()),
+        "mssql" => Some("dbo".to_string()),
+        "mysql" => Some(params.database.primary().to_string()).filter(|db| !db.is_empty()),
+        _ => None,
+    }
+}
+
+/// Queue the counts of one statement; the first queued record of a
+/// connection schedules the write of its file [`FLUSH_DELAY`] later.
+fn record<R: Runtime>(
+    app: &AppHandle<R>,
+    connection_id: &str,
+    schema: Option<&str>,
+    accesses: Vec<TableAccess>,
+) {
+    if accesses.is_empty() {
+        return;
+    }
+    let record = ActivityRecord {
+        default_schema: schema.map(str::to_string),
+        accesses,
+        at: Utc::now(),
+    };
+    let first = {
+        let mut pending = PENDING.lock().unwrap_or_else(|e| e.into_inner());
+        let records = pending.entry(connection_id.to_string()).or_default();
+        records.push(record);
+        records.len() == 1
+    };
+    if first {
+        let app = app.clone();
+        let connection_id = connection_id.to_string();
+        tauri::async_runtime::spawn(async move {
+            tokio::time::sleep(FLUSH_DELAY).await;
+            flush_connection(&app, &connection_id).await;
+        });
+    }
+}
+
+/// Write the queued counts of `connection_id` to its file.
+async fn flush_connection<R: Runtime>(app: &AppHandle<R>, connection_id: &str) {
+    let records = PENDING
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .remove(connection_id)
+        .unwrap_or_default();
+    if records.is_empty() {
+        return;
+    }
+    let dir = match activity_dir(app) {
+        Ok(dir) => dir,
+        Err(e) => {
+            log::warn!("Failed to record table activity: {}", e);
+            return;
+        }
+    };
+    let connection_id = connection_id.to_string();
+    let outcome = tokio::task::spawn_blocking(move || record_in(&dir, &connection_id, &records))
+        .await
+        .map_err(|e| e.to_string())
+        .and_then(|written| written);
+    if let Err(e) = outcome {
+        log::warn!("Failed to record table activity: {}", e);
+    }
+}
+
+/// Write every queued count now, e.g. before a report or on shutdown.
+pub async fn flush_pending<R: Runtime>(app: &AppHandle<R>) {
+    let connection_ids: Vec<String> = PENDING
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .keys()
+        .cloned()
+        .collect();
+    for connection_id in connection_ids {
+        flush_connection(app, &connection_id).await;
+    }
+}
+
+/// Count the tables a successfully executed statement read and wrote.
+pub fn track_query<R: Runtime>(
+    app: &AppHandle<R>,
+    connection_id: &str,
+    driver: &str,
+    sql: &str,
+    schema: Option<&str>,
+) {
+    if is_enabled() {
+        record(app, connection_id, schema, extract_tables(driver, sql));
+    }
+}
+
+/// Count an edit made from the data grid.
+pub fn track_edit<R: Runtime>(
+    app: &AppHandle<R>,
+    connection_id: &str,
+    schema: Option<&str>,
+    table: &str,
+) {
+    if is_enabled() {
+        let access = TableAccess {
+            schema: None,
+            table: table.to_string(),
+            write: true,
+        };
+        record(app, connection_id, schema, vec![access]);
+    }
+}
+
+pub fn remove_table_activity_for_connection<R: Runtime>(
+    app: &AppHandle<R>,
+    connection_id: &str,
+) -> Result<(), String> {
+    PENDING
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .remove(connection_id);
+    let path = log_path_in(&activity_dir(app)?, connection_id);
+    if path.exists() {
+        fs::remove_file(path).map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
+// ---------------------------------------------------------------------------
+// Tauri commands
+// ---------------------------------------------------------------------------
+
+/// Reads, writes and daily activity of every table of `schema`, with the
+/// tables unused for `idle_days` (default 90) flagged as archive candidates.
+#[tauri::command]
+pub async fn get_table_activity<R: Runtime>(
+    app: AppHandle<R>,
+    connection_id: String,
+    schema: Option<String>,
+    idle_days: Option<u32>,
+) -> Result<TableActivityReport, String> {
+    let saved_conn = crate::commands::find_connection_by_id(&app, &connection_id)?;
+    let expanded = crate::commands::expand_ssh_connection_params(&app, &saved_conn.params).await?;
+    let params = crate::commands::resolve_connection_params_with_id(&expanded, &connection_id)?;
+    let drv = crate::commands::driver_for(&saved_conn.params.driver).await?;
+    let tables: Vec<String> = drv
+        .get_tables(&params, schema.as_deref())
+        .await?
+        .into_iter()
+        .map(|t| t.name)
+        .collect();
+
+    flush_connection(&app, &connection_id).await;
+    let log = read_log_in(&activity_dir(&app)?, &connection_id);
+    Ok(build_report(
+        &log,
+        schema.as_deref(),
+        default_schema(&params).as_deref(),
+        &tables,
+        idle_days.unwrap_or(DEFAULT_IDLE_DAYS),
+        Utc::now(),
+    ))
+}
+
+#[tauri::command]
+pub fn clear_table_activity<R: Runtime>(
+    app: AppHandle<R>,
+    connection_id: String,
+) -> Result<(), String> {
+    remove_table_activity_for_connection(&app, &connection_id)
+}
@@ -0,0 +1,219 @@
+#[cfg(test)]
+mod tests {
+    use crate::table_activity::{
+        build_report, extract_tables, read_log_in, record_in, ActivityRecord, TableAccess,
+        HEAT_MAP_DAYS,
+    };
+    use chrono::{DateTime, Duration, TimeZone, Utc};
+
+    fn access(schema: Option<&str>, table: &str, write: bool) -> TableAccess {
+        TableAccess {
+            schema: schema.map(str::to_string),
+            table: table.to_string(),
+            write,
+        }
+    }
+
+    fn record(
+        default_schema: Option<&str>,
+        accesses: &[TableAccess],
+        at: DateTime<Utc>,
+    ) -> ActivityRecord {
+        ActivityRecord {
+            default_schema: default_schema.map(str::to_string),
+            accesses: accesses.to_vec(),
+            at,
+        }
+    }
+
+    fn names(tables: &[&str]) -> Vec<String> {
+        tables.iter().map(|t| t.to_string()).collect()
+    }
+
+    #[test]
+    fn reads_come_from_from_and_join_clauses() {
+        let sql = "SELECT u.id, EXTRACT(YEAR FROM o.created_at) \
+                   FROM public.users u, items JOIN \"Orders\" o ON o.user_id = u.id \
+                   WHERE u.id IN (SELECT user_id FROM bans) AND a IS DISTINCT FROM b";
+        assert_eq!(
+            extract_tables("postgres", sql),
+            vec![
+                access(Some("public"), "users", false),
+                access(None, "items", false),
+                access(None, "Orders", false),
+                access(None, "bans", false),
+            ]
+        );
+    }
+
+    #[test]
+    fn writes_name_their_target_table() {
+        assert_eq!(
+            extract_tables(
+                "mysql",
+                "INSERT IGNORE INTO `log` (msg) SELECT msg FROM staging; \
+                 UPDATE accounts SET total = 0; DELETE FROM sessions WHERE id = 1"
+            ),
+            vec![
+                access(None, "log", true),
+                access(None, "staging", false),
+                access(None, "accounts", true),
+                access(None, "sessions", true),
+            ]
+        );
+        assert_eq!(
+            extract_tables("postgres", "TRUNCATE TABLE a, b"),
+            vec![access(None, "a", true), access(None, "b", true)]
+        );
+    }
+
+    #[test]
+    fn ctes_and_table_functions_are_not_tables() {
+        assert_eq!(
+            extract_tables(
+                "postgres",
+                "WITH recent (id) AS (SELECT id FROM orders) \
+                 SELECT * FROM recent, generate_series(1, 3) JOIN recent r2 USING (id)"
+            ),
+            vec![access(None, "orders", false)]
+        );
+        assert!(extract_tables("postgres", "SELECT 1").is_empty());
+    }
+
+    #[test]
+    fn counts_are_kept_per_day_and_old_days_dropped() {
+        let dir = tempfile::tempdir().unwrap();
+        let start = Utc.with_ymd_and_hms(2026, 1, 1, 12, 0, 0).unwrap();
+        record_in(
+            dir.path(),
+            "c1",
+            &[at(
+                Some("public"),
+                &[access(None, "users", false), access(None, "users", true)],
+                start,
+            )],
+        )
+        .unwrap();
+        let later = start + Duration::days(HEAT_MAP_DAYS);
+        record_in(
+            dir.path(),
+            "c1",
+            &[record(
+                Some("public"),
+                &[access(None, "users", false)],
+                later,
+            )],
+        )
+        .unwrap();
+
+        let log = read_log_in(dir.path(), "c1");
+        assert_eq!(log.tracking_since, Some(start.to_rfc3339()));
+        assert_eq!(log.tables.len(), 1);
+        let users = &log.tables[0];
+        assert_eq!(users.schema.as_deref(), Some("public"));
+        assert_eq!((users.reads, users.writes), (2, 1));
+        assert_eq!(users.last_write_at, Some(start.to_rfc3339()));
+        assert_eq!(
+            users.daily.iter().collect::<Vec<_>>(),
+            [(&"2026-04-01".to_string(), &1)]
+        );
+    }
+
+    #[test]
+    fn unused_tables_become_archive_candidates() {
+        let dir = tempfile::tempdir().unwrap();
+        let start = Utc.with_ymd_and_hms(2026, 1, 1, 0, 0, 0).unwrap();
+        record_in(
+            dir.path(),
+            "c1",
+            &[record(Some("public"), &[access(None, "old", false)], start)],
+        )
+        .unwrap();
+        let now = start + Duration::days(100);
+        record_in(
+            dir.path(),
+            "c1",
+            &[record(None, &[access(None, "Users", true)], now)],
+        )
+        .unwrap();
+        let log = read_log_in(dir.path(), "c1");
+
+        let report = build_report(
+            &log,
+            Some("public"),
+            Some("public"),
+            &names(&["never", "old", "users"]),
+            90,
+            now,
+        );
+        let rows: Vec<(&str, u64, bool)> = report
+            .tables
+            .iter()
+            .map(|r| (r.table.as_str(), r.reads + r.writes, r.archive_candidate))
+            .collect();
+        assert_eq!(
+            rows,
+            [("old", 1, true), ("users", 1, false), ("never", 0, true)]
+        );
+
+        // Too soon to tell after a few days of tracking.
+        let early = build_report(
+            &log,
+            Some("public"),
+            Some("public"),
+            &names(&["never"]),
+            90,
+            start,
+        );
+        assert!(!early.tables[0].archive_candidate);
+    }
+
+    #[test]
+    fn activity_without_a_schema_only_counts_in_the_default_schema() {
+        let dir = tempfile::tempdir().unwrap();
+        let now = Utc.with_ymd_and_hms(2026, 1, 1, 0, 0, 0).unwrap();
+        record_in(
+            dir.path(),
+            "c1",
+            &[record(None, &[access(None, "users", false)], now)],
+        )
+        .unwrap();
+        let log = read_log_in(dir.path(), "c1");
+
+        let reads = |schema: &str| {
+            build_report(
+                &log,
+                Some(schema),
+                Some("public"),
+                &names(&["users"]),
+                90,
+                now,
+            )
+            .tables[0]
+                .reads
+        };
+        assert_eq!(reads("public"), 1);
+        assert_eq!(reads("sales"), 0);
+    }
+
+    #[test]
+    fn a_batch_of_records_is_written_at_once() {
+        let dir = tempfile::tempdir().unwrap();
+        let start = Utc.with_ymd_and_hms(2026, 1, 1, 0, 0, 0).unwrap();
+        let later = start + Duration::hours(1);
+        record_in(
+            dir.path(),
+            "c1",
+            &[
+                record(Some("public"), &[access(None, "users", true)], start),
+                record(Some("public"), &[access(None, "users", true)], later),
+            ],
+        )
+        .unwrap();
+
+        let log = read_log_in(dir.path(), "c1");
+        assert_eq!(log.tracking_since, Some(start.to_rfc3339()));
+        assert_eq!(log.tables[0].writes, 2);
+        assert_eq!(log.tables[0].last_write_at, Some(later.to_rfc3339()));
+    }
+}
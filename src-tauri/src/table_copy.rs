@@ -0,0 +1,501 @@
+//! Copy a table from one connection to another, across drivers.
+//!
+//! `copy_table` creates the table on the target through the target driver's
+//! DDL generator, with the column types mapped to its dialect (see
+//! `sql_schema::map_column`), then reads the source a page at a time, in
+//! primary key order, and writes each page as one multi-row `INSERT`.
+//! After each page a `table-copy://progress` event goes to the calling
+//! window, told apart by `copy_id`; `cancel_table_copy` stops the copy
+//! between two statements. Rows already written stay on the target.
+//!
+//! Values are adjusted to the target column where drivers disagree:
+//! booleans read as `0`/`1` go into boolean columns as booleans and the
+//! other way round. Explicit ids go into SQL Server identity columns, and
+//! PostgreSQL sequences are moved past the copied ids.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use serde::Serialize;
+use serde_json::Value;
+use tauri::{AppHandle, Emitter, Runtime, State, WebviewWindow};
+
+use crate::clipboard_import::IfExistsStrategy;
+use crate::commands::{
+    driver_for, expand_ssh_connection_params, find_connection_by_id, register_abort_handle,
+    resolve_connection_params_with_id, unregister_abort_handle, AbortHandleMap,
+};
+use crate::drivers::driver_trait::DatabaseDriver;
+use crate::export::sql_dump::SqlDumpDialect;
+use crate::models::{ColumnDefinition, ConnectionParams, TableColumn};
+use crate::sql_schema::{map_column, type_kind, TypeKind};
+
+const TABLE_COPY_PROGRESS_EVENT: &str = "table-copy://progress";
+
+/// Rows read and inserted at a time when the caller does not pick a size.
+pub const DEFAULT_BATCH_SIZE: u32 = 500;
+const BATCH_SIZE_CAP: u32 = 10_000;
+
+/// Abort handles of running copies, keyed by `copy_id`.
+pub struct TableCopyCancellationState {
+    pub handles: Arc<Mutex<AbortHandleMap>>,
+}
+
+impl Default for TableCopyCancellationState {
+    fn default() -> Self {
+        Self {
+            handles: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct TableCopyProgress {
+    pub copy_id: String,
+    pub rows_copied: u64,
+    /// Rows of the source table, when the driver counted them.
+    pub total_rows: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct TableCopySummary {
+    pub target_table: String,
+    pub table_created: bool,
+    pub rows_copied: u64,
+    /// Source columns left out because the target table has no such column.
+    pub skipped_columns: Vec<String>,
+}
+
+// ---------------------------------------------------------------------------
+// Statements (testable; pure)
+// ---------------------------------------------------------------------------
+
+/// `value` as a column of `target_type` takes it. MySQL and SQLite read
+/// booleans as numbers, PostgreSQL as booleans; neither side accepts the
+/// other's spelling.
+pub fn convert_value(value: Value, target_type: &str) -> Value {
+    match (type_kind(target_type), value) {
+        (Some(TypeKind::Bool), Value::Number(n)) => Value::Bool(n.as_f64() != Some(0.0)),
+        (Some(TypeKind::Bool), Value::String(s)) => match s.trim().to_ascii_lowercase().as_str() {
+            "t" | "true" | "y" | "yes" | "1" => Value::Bool(true),
+            "f" | "false" | "n" | "no" | "0" => Value::Bool(false),
+            _ => Value::String(s),
+        },
+        (
+            Some(
+                TypeKind::TinyInt
+                | TypeKind::SmallInt
+                | TypeKind::Int
+                | TypeKind::BigInt
+                | TypeKind::Decimal
+                | TypeKind::Double
+                | TypeKind::Real,
+            ),
+            Value::Bool(b),
+        ) => Value::from(u8::from(b)),
+        (_, value) => value,
+    }
+}
+
+/// The query reading the source table, ordered by its primary key so that
+/// pages do not overlap.
+pub fn page_query(
+    dialect: &SqlDumpDialect,
+    schema: Option<&str>,
+    table: &str,
+    primary_key: &[String],
+) -> String {
+    let mut query = format!("SELECT * FROM {}", dialect.table_ref(schema, table));
+    if !primary_key.is_empty() {
+        let columns: Vec<String> = primary_key
+            .iter()
+            .map(|c| dialect.quote_identifier(c))
+            .collect();
+        query.push_str(&format!(" ORDER BY {}", columns.join(", ")));
+    }
+    query
+}
+
+/// One multi-row `INSERT` of `rows`. `identity` wraps it in
+/// `SET IDENTITY_INSERT` on SQL Server, which otherwise refuses explicit
+/// values for an identity column.
+pub fn insert_batch(
+    dialect: &SqlDumpDialect,
+    driver: &str,
+    table_ref: &str,
+    columns: &[String],
+    rows: &[Vec<Value>],
+    identity: bool,
+) -> String {
+    let insert = dialect.insert_statement(table_ref, columns, rows);
+    if identity && driver == "mssql" {
+        format!(
+            "SET IDENTITY_INSERT {t} ON;\n{}\nSET IDENTITY_INSERT {t} OFF;",
+            insert,
+            t = table_ref
+        )
+    } else {
+        insert.trim_end_matches(';').to_string()
+    }
+}
+
+/// The statement moving the sequence behind a PostgreSQL serial column
+/// past the copied values, so the next insert does not collide.
+pub fn sequence_reset(
+    dialect: &SqlDumpDialect,
+    driver: &str,
+    table_ref: &str,
+    column: &str,
+) -> Option<String> {
+    if driver != "postgres" {
+        return None;
+    }
+    let quoted = dialect.quote_identifier(column);
+    Some(format!(
+        "SELECT setval(pg_get_serial_sequence({}, {}), COALESCE(MAX({}), 0) + 1, false) FROM {}",
+        dialect.literal(&Value::String(table_ref.to_string())),
+        dialect.literal(&Value::String(column.to_string())),
+        quoted,
+        table_ref
+    ))
+}
+
+// ---------------------------------------------------------------------------
+// Copy
+// ---------------------------------------------------------------------------
+
+/// One side of the copy.
+struct Endpoint {
+    drv: Arc<dyn DatabaseDriver>,
+    params: ConnectionParams,
+    driver: String,
+    dialect: SqlDumpDialect,
+    /// The schema, when the driver qualifies tables with one.
+    qualifier: Option<String>,
+    schema: Option<String>,
+}
+
+impl Endpoint {
+    async fn open<R: Runtime>(
+        app: &AppHandle<R>,
+        connection_id: &str,
+        schema: Option<String>,
+    ) -> Result<Self, String> {
+        let saved_conn = find_connection_by_id(app, connection_id)?;
+        let expanded_params = expand_ssh_connection_params(app, &saved_conn.params).await?;
+        let params = resolve_connection_params_with_id(&expanded_params, connection_id)?;
+        let drv = driver_for(&saved_conn.params.driver).await?;
+        let capabilities = &drv.manifest().capabilities;
+        let dialect =
+            SqlDumpDialect::for_driver(&saved_conn.params.driver, &capabilities.identifier_quote);
+        let qualifier = schema.clone().filter(|_| capabilities.schemas);
+        Ok(Self {
+            drv,
+            params,
+            driver: saved_conn.params.driver.clone(),
+            dialect,
+            qualifier,
+            schema,
+        })
+    }
+
+    fn table_ref(&self, table: &str) -> String {
+        self.dialect.table_ref(self.qualifier.as_deref(), table)
+    }
+
+    async fn execute(&self, sql: &str) -> Result<(), String> {
+        self.drv
+            .execute_query(&self.params, sql, None, 1, self.schema.as_deref())
+            .await
+            .map(|_| ())
+    }
+
+    async fn columns(&self, table: &str) -> Result<Vec<TableColumn>, String> {
+        self.drv
+            .get_columns(&self.params, table, self.schema.as_deref())
+            .await
+    }
+}
+
+struct TableCopy<'a, F: FnMut(u64, Option<u64>)> {
+    source: &'a Endpoint,
+    target: &'a Endpoint,
+    target_connection_id: &'a str,
+    batch_size: u32,
+    on_progress: F,
+}
+
+impl<F: FnMut(u64, Option<u64>)> TableCopy<'_, F> {
+    /// Create (or replace) the target table as `if_exists` says. Returns
+    /// whether it was created.
+    async fn prepare(
+        &self,
+        table: &str,
+        columns: &[TableColumn],
+        if_exists: &IfExistsStrategy,
+    ) -> Result<bool, String> {
+        let target = self.target;
+        let exists = target
+            .drv
+            .get_tables(&target.params, target.schema.as_deref())
+            .await?
+            .iter()
+            .any(|t| t.name == table);
+        if exists {
+            match if_exists {
+                IfExistsStrategy::Fail => {
+                    return Err(format!("Table {} already exists on the target", table))
+                }
+                IfExistsStrategy::Append => return Ok(false),
+                IfExistsStrategy::Replace => target
+                    .execute(&format!("DROP TABLE {}", target.table_ref(table)))
+                    .await
+                    .map_err(|e| format!("Failed to drop existing table: {}", e))?,
+            }
+        }
+
+        let definitions: Vec<ColumnDefinition> = columns
+            .iter()
+            .map(|c| map_column(c, &target.driver))
+            .collect();
+        let statements = crate::server_info::scope(
+            crate::server_info::cached(self.target_connection_id),
+            target
+                .drv
+                .get_create_table_sql(table, definitions, target.schema.as_deref()),
+        )
+        .await
+        .map_err(|e| format!("Failed to generate CREATE TABLE SQL: {}", e))?;
+        for statement in &statements {
+            target
+                .execute(statement)
+                .await
+                .map_err(|e| format!("Failed to create table: {}", e))?;
+        }
+        Ok(true)
+    }
+
+    /// Copy the rows page by page. Returns the number of rows written and
+    /// the source columns the target has no place for.
+    async fn rows(
+        &mut self,
+        source_table: &str,
+        source_columns: &[TableColumn],
+        target_table: &str,
+    ) -> Result<(u64, Vec<String>), String> {
+        let (source, target) = (self.source, self.target);
+        let target_columns = target.columns(target_table).await?;
+        let target_types: HashMap<&str, &str> = target_columns
+            .iter()
+            .map(|c| (c.name.as_str(), c.data_type.as_str()))
+            .collect();
+        let identity = target_columns.iter().any(|c| c.is_auto_increment);
+        let primary_key: Vec<String> = source_columns
+            .iter()
+            .filter(|c| c.is_pk)
+            .map(|c| c.name.clone())
+            .collect();
+        let query = page_query(
+            &source.dialect,
+            source.qualifier.as_deref(),
+            source_table,
+            &primary_key,
+        );
+        let table_ref = target.table_ref(target_table);
+
+        let mut copied = 0u64;
+        let mut skipped: Vec<String> = Vec::new();
+        let mut page = 1;
+        loop {
+            let result = source
+                .drv
+                .execute_query(
+                    &source.params,
+                    &query,
+                    Some(self.batch_size),
+                    page,
+                    source.schema.as_deref(),
+                )
+                .await?;
+            if result.rows.is_empty() {
+                break;
+            }
+
+            // Only the columns the target table has.
+            let mut kept: Vec<(usize, &str)> = Vec::new();
+            for (i, name) in result.columns.iter().enumerate() {
+                match target_types.get(name.as_str()) {
+                    Some(&data_type) => kept.push((i, data_type)),
+                    None if !skipped.contains(name) => skipped.push(name.clone()),
+                    None => {}
+                }
+            }
+            if kept.is_empty() {
+                return Err(format!(
+                    "Table {} has none of the source columns",
+                    target_table
+                ));
+            }
+            let columns: Vec<String> = kept
+                .iter()
+                .map(|&(i, _)| result.columns[i].clone())
+                .collect();
+            let rows: Vec<Vec<Value>> = result
+                .rows
+                .into_iter()
+                .map(|row| {
+                    kept.iter()
+                        .map(|&(i, data_type)| {
+                            convert_value(row.get(i).cloned().unwrap_or(Value::Null), data_type)
+                        })
+                        .collect()
+                })
+                .collect();
+
+            let statement = insert_batch(
+                &target.dialect,
+                &target.driver,
+                &table_ref,
+                &columns,
+                &rows,
+                identity,
+            );
+            target.execute(&statement).await.map_err(|e| {
+                format!(
+                    "Failed to insert rows (batch starting at {}): {}",
+                    copied, e
+                )
+            })?;
+            copied += rows.len() as u64;
+            let total_rows = result.pagination.as_ref().and_then(|p| p.total_rows);
+            (self.on_progress)(copied, total_rows);
+
+            // A driver that did not page the result returned every row.
+            let has_more = result.pagination.as_ref().is_some_and(|p| p.has_more);
+            if !has_more || (rows.len() as u32) < self.batch_size {
+                break;
+            }
+            page += 1;
+        }
+
+        for column in target_columns.iter().filter(|c| c.is_auto_increment) {
+            if let Some(sql) =
+                sequence_reset(&target.dialect, &target.driver, &table_ref, &column.name)
+            {
+                if let Err(e) = target.execute(&sql).await {
+                    log::warn!(
+                        "Table copy: cannot reset the sequence of {}: {}",
+                        column.name,
+                        e
+                    );
+                }
+            }
+        }
+        Ok((copied, skipped))
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tauri commands
+// ---------------------------------------------------------------------------
+
+/// Copy `source_table` to the target connection as `target_table` (the
+/// same name by default). `if_exists` says what to do when the target
+/// already has the table: fail (the default), append, or replace it.
+#[tauri::command]
+pub async fn copy_table<R: Runtime>(
+    app: AppHandle<R>,
+    window: WebviewWindow<R>,
+    state: State<'_, TableCopyCancellationState>,
+    copy_id: String,
+    source_connection_id: String,
+    source_table: String,
+    source_schema: Option<String>,
+    target_connection_id: String,
+    target_table: Option<String>,
+    target_schema: Option<String>,
+    if_exists: Option<IfExistsStrategy>,
+    batch_size: Option<u32>,
+) -> Result<TableCopySummary, String> {
+    let source = Endpoint::open(&app, &source_connection_id, source_schema).await?;
+    let target = Endpoint::open(&app, &target_connection_id, target_schema).await?;
+    crate::read_only::ensure_writable(&target.params)?;
+    let target_table = target_table
+        .map(|t| t.trim().to_string())
+        .filter(|t| !t.is_empty())
+        .unwrap_or_else(|| source_table.clone());
+    let if_exists = if_exists.unwrap_or(IfExistsStrategy::Fail);
+    let batch_size = batch_size
+        .unwrap_or(DEFAULT_BATCH_SIZE)
+        .clamp(1, BATCH_SIZE_CAP);
+    log::info!(
+        "Copying table {} from connection {} ({}) to {} on connection {} ({})",
+        source_table,
+        source_connection_id,
+        source.driver,
+        target_table,
+        target_connection_id,
+        target.driver
+    );
+
+    let task_copy_id = copy_id.clone();
+    let task = tokio::spawn(async move {
+        let source_columns = source.columns(&source_table).await?;
+        if source_columns.is_empty() {
+            return Err(format!("Table {} has no columns", source_table));
+        }
+        let mut copy = TableCopy {
+            source: &source,
+            target: &target,
+            target_connection_id: &target_connection_id,
+            batch_size,
+            on_progress: |rows_copied, total_rows| {
+                let progress = TableCopyProgress {
+                    copy_id: task_copy_id.clone(),
+                    rows_copied,
+                    total_rows,
+                };
+                if let Err(e) = app.emit_to(window.label(), TABLE_COPY_PROGRESS_EVENT, &progress) {
+                    log::warn!("Failed to emit table copy progress: {}", e);
+                }
+            },
+        };
+        let table_created = copy
+            .prepare(&target_table, &source_columns, &if_exists)
+            .await?;
+        let (rows_copied, skipped_columns) = copy
+            .rows(&source_table, &source_columns, &target_table)
+            .await?;
+        Ok(TableCopySummary {
+            target_table,
+            table_created,
+            rows_copied,
+            skipped_columns,
+        })
+    });
+
+    let abort_handle = Arc::new(task.abort_handle());
+    register_abort_handle(&state.handles, copy_id.clone(), abort_handle.clone());
+    let result = task.await;
+    unregister_abort_handle(&state.handles, &copy_id, &abort_handle);
+
+    match result {
+        Ok(summary) => summary,
+        Err(_) => Err("Table copy cancelled".to_string()),
+    }
+}
+
+#[tauri::command]
+pub async fn cancel_table_copy(
+    state: State<'_, TableCopyCancellationState>,
+    copy_id: String,
+) -> Result<(), String> {
+    let entries = {
+        let mut handles = state.handles.lock().unwrap();
+        handles.remove(&copy_id).unwrap_or_default()
+    };
+    for handle in entries {
+        handle.abort();
+    }
+    Ok(())
+}
@@ -0,0 +1,76 @@
+#[cfg(test)]
+mod tests {
+    use crate::export::sql_dump::SqlDumpDialect;
+    use crate::table_copy::{convert_value, insert_batch, page_query, sequence_reset};
+    use serde_json::{json, Value};
+
+    fn names(columns: &[&str]) -> Vec<String> {
+        columns.iter().map(|c| c.to_string()).collect()
+    }
+
+    #[test]
+    fn booleans_follow_the_target_column() {
+        assert_eq!(convert_value(json!(1), "boolean"), json!(true));
+        assert_eq!(convert_value(json!(0), "TINYINT(1)"), json!(false));
+        assert_eq!(convert_value(json!("t"), "bool"), json!(true));
+        assert_eq!(convert_value(json!("maybe"), "bool"), json!("maybe"));
+        assert_eq!(convert_value(json!(true), "INTEGER"), json!(1));
+        assert_eq!(convert_value(json!(false), "tinyint"), json!(0));
+        assert_eq!(convert_value(json!(true), "TEXT"), json!(true));
+        assert_eq!(convert_value(Value::Null, "boolean"), Value::Null);
+    }
+
+    #[test]
+    fn pages_are_read_in_primary_key_order() {
+        let postgres = SqlDumpDialect::for_driver("postgres", "\"");
+        assert_eq!(
+            page_query(
+                &postgres,
+                Some("public"),
+                "order_lines",
+                &names(&["order_id", "line"])
+            ),
+            "SELECT * FROM \"public\".\"order_lines\" ORDER BY \"order_id\", \"line\""
+        );
+        let mysql = SqlDumpDialect::for_driver("mysql", "`");
+        assert_eq!(page_query(&mysql, None, "log", &[]), "SELECT * FROM `log`");
+    }
+
+    #[test]
+    fn batches_allow_explicit_identities_on_sql_server() {
+        let rows = vec![vec![json!(1), json!("a")], vec![json!(2), Value::Null]];
+        let mssql = SqlDumpDialect::for_driver("mssql", "[");
+        let sql = insert_batch(&mssql, "mssql", "[t]", &names(&["id", "name"]), &rows, true);
+        assert_eq!(
+            sql,
+            "SET IDENTITY_INSERT [t] ON;\n\
+             INSERT INTO [t] ([id], [name]) VALUES\n(1, 'a'),\n(2, NULL);\n\
+             SET IDENTITY_INSERT [t] OFF;"
+        );
+
+        let sqlite = SqlDumpDialect::for_driver("sqlite", "\"");
+        let sql = insert_batch(
+            &sqlite,
+            "sqlite",
+            "\"t\"",
+            &names(&["id", "name"]),
+            &rows[..1],
+            true,
+        );
+        assert_eq!(sql, "INSERT INTO \"t\" (\"id\", \"name\") VALUES\n(1, 'a')");
+    }
+
+    #[test]
+    fn postgres_sequences_are_moved_past_the_copied_ids() {
+        let postgres = SqlDumpDialect::for_driver("postgres", "\"");
+        assert_eq!(
+            sequence_reset(&postgres, "postgres", "\"public\".\"users\"", "Id").as_deref(),
+            Some(
+                "SELECT setval(pg_get_serial_sequence('\"public\".\"users\"', 'Id'), \
+                 COALESCE(MAX(\"Id\"), 0) + 1, false) FROM \"public\".\"users\""
+            )
+        );
+        let mysql = SqlDumpDialect::for_driver("mysql", "`");
+        assert_eq!(sequence_reset(&mysql, "mysql", "`users`", "id"), None);
+    }
+}
@@ -10,6 +10,7 @@ use tokio::time::{sleep, Duration};
 use crate::drivers::registry;
 use crate::plugins::installer;
 use crate::plugins::manager::load_plugin_from_dir;
+use crate::query_jobs::{self, RunningQuery};
 
 // ---------------------------------------------------------------------------
 // Persistent System instance — required so that delta fields (cpu_usage,
@@ -362,6 +363,18 @@ pub async fn restart_plugin_process(
     Ok(())
 }
 
+/// Queries running right now, with how long they have run and how much
+/// they have fetched.
+#[tauri::command]
+pub fn get_running_queries() -> Vec<RunningQuery> {
+    query_jobs::list()
+}
+
+#[tauri::command]
+pub fn cancel_running_query(id: u64) -> Result<(), String> {
+    query_jobs::cancel(id)
+}
+
 #[tauri::command]
 pub async fn open_task_manager_window(app: AppHandle) -> Result<(), String> {
     if let Some(existing) = app.get_webview_window("task-manager") {
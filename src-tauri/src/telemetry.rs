@@ -0,0 +1,464 @@
+//! Opt-in anonymous usage and error reporting.
+//!
+//! Nothing is recorded unless `telemetryEnabled` is set in `config.json`.
+//! Events are scrubbed *before* they are queued, so the queue on disk is
+//! exactly what would be sent. `flush_telemetry` posts the queue in batches
+//! to `telemetryEndpoint` and drops every batch the endpoint accepted.
+//!
+//! Storage layout under the application config directory:
+//!   - `telemetry_queue.jsonl` — pending events (one event per line)
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::fs::{self, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use tauri::AppHandle;
+
+use crate::paths::get_app_config_dir;
+
+const QUEUE_FILENAME: &str = "telemetry_queue.jsonl";
+/// Oldest events are dropped once the queue grows past this size.
+pub const MAX_QUEUED_EVENTS: usize = 1000;
+/// Events posted per request by `flush_telemetry`.
+pub const BATCH_SIZE: usize = 100;
+/// Scrubbed strings longer than this are truncated.
+const MAX_TEXT_LEN: usize = 500;
+/// Words after which database errors name an object without quoting it
+/// (`table users has no column named email`).
+const NAME_KEYWORDS: [&str; 11] = [
+    "table", "column", "named", "relation", "database", "schema", "role", "user", "host", "index",
+    "view",
+];
+/// Keywords that SQLite follows with a colon (`no such table: orders`).
+/// Others, like `database:`, end a phrase rather than introduce a name.
+const COLON_NAME_KEYWORDS: [&str; 4] = ["table", "column", "index", "view"];
+/// Words that can follow a name keyword without being a name.
+const NAME_STOPWORDS: [&str; 17] = [
+    "a", "an", "the", "is", "has", "does", "not", "in", "for", "with", "of", "to", "on", "at",
+    "or", "and", "named",
+];
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct TelemetryEvent {
+    pub id: String,
+    /// `"usage"` or `"error"`.
+    pub kind: String,
+    pub name: String,
+    #[serde(default)]
+    pub properties: Map<String, Value>,
+    pub app_version: String,
+    pub os: String,
+    pub timestamp: String,
+}
+
+/// Body posted to the telemetry endpoint.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TelemetryBatch {
+    pub events: Vec<TelemetryEvent>,
+}
+
+// ---------------------------------------------------------------------------
+// Anonymization
+// ---------------------------------------------------------------------------
+
+fn is_email(token: &str) -> bool {
+    match token.split_once('@') {
+        Some((user, domain)) => !user.is_empty() && domain.contains('.'),
+        None => false,
+    }
+}
+
+fn is_ipv4(token: &str) -> bool {
+    let host = token.split(':').next().unwrap_or(token);
+    let parts: Vec<&str> = host.split('.').collect();
+    parts.len() == 4
+        && parts
+            .iter()
+            .all(|p| !p.is_empty() && p.len() <= 3 && p.parse::<u8>().is_ok())
+}
+
+/// The address of an IPv6 token (`::1`, `fe80::1%eth0`, or the part in
+/// brackets of `[2001:db8::1]:5432`), zone included.
+fn ipv6_address(token: &str) -> Option<&str> {
+    let address = token.trim_start_matches('[');
+    let address = address.split(']').next().unwrap_or(address);
+    let ip = address.split('%').next().unwrap_or(address);
+    (ip.contains(':') && ip.parse::<std::net::Ipv6Addr>().is_ok()).then_some(address)
+}
+
+/// A dotted host name (`db.prod.example.com`), optionally with a port, or any
+/// name followed by a port (`localhost:5432`). The last label must hold a
+/// letter so version numbers are left alone.
+fn is_host(token: &str) -> bool {
+    let (host, port) = match token.rsplit_once(':') {
+        Some((host, port)) => (host, Some(port)),
+        None => (token, None),
+    };
+    if port.is_some_and(|p| p.is_empty() || !p.chars().all(|c| c.is_ascii_digit())) {
+        return false;
+    }
+    let labels: Vec<&str> = host.split('.').collect();
+    let valid = labels.iter().all(|label| {
+        !label.is_empty()
+            && label
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    });
+    let named = labels
+        .last()
+        .is_some_and(|label| label.chars().any(|c| c.is_ascii_alphabetic()));
+    valid && named && (labels.len() >= 2 || port.is_some())
+}
+
+/// An unquoted database object name following one of `NAME_KEYWORDS`.
+fn is_bare_name(token: &str, previous: Option<&str>) -> bool {
+    let follows_keyword = previous.is_some_and(|prev| {
+        let prev = prev.to_ascii_lowercase();
+        match prev.strip_suffix(':') {
+            Some(keyword) => COLON_NAME_KEYWORDS.contains(&keyword),
+            None => NAME_KEYWORDS.contains(&prev.as_str()),
+        }
+    });
+    follows_keyword
+        && !NAME_STOPWORDS.contains(&token.to_ascii_lowercase().as_str())
+        && token
+            .chars()
+            .all(|c| c.is_alphanumeric() || c == '_' || c == '.' || c == '$')
+}
+
+fn is_uuid(token: &str) -> bool {
+    token.len() == 36
+        && token.char_indices().all(|(i, c)| match i {
+            8 | 13 | 18 | 23 => c == '-',
+            _ => c.is_ascii_hexdigit(),
+        })
+}
+
+fn is_path(token: &str) -> bool {
+    let bytes = token.as_bytes();
+    let windows_drive = bytes.len() > 2
+        && bytes[0].is_ascii_alphabetic()
+        && bytes[1] == b':'
+        && (bytes[2] == b'\\' || bytes[2] == b'/');
+    windows_drive || token.starts_with("\\\\") || token.matches('/').count() >= 2
+}
+
+/// Scrub one whitespace-separated token; `previous` is the token before it.
+fn scrub_token(token: &str, previous: Option<&str>) -> String {
+    let trimmed = token.trim_matches(|c: char| "\"`()[]{},;".contains(c));
+    if trimmed.is_empty() {
+        return token.to_string();
+    }
+    if let Some(address) = ipv6_address(trimmed) {
+        return token.replacen(address, "<ip>", 1);
+    }
+    // Sentence punctuation, as in `failed to connect to db.example.com:`.
+    let trimmed = trimmed.trim_end_matches(|c: char| ".:\"`()[]{},;".contains(c));
+    if trimmed.is_empty() {
+        return token.to_string();
+    }
+
+    let replacement = if let Some((scheme, _)) = trimmed.split_once("://") {
+        Some(format!("{}://<host>", scheme))
+    } else if is_email(trimmed) {
+        Some("<email>".to_string())
+    } else if is_ipv4(trimmed) {
+        Some("<ip>".to_string())
+    } else if is_uuid(trimmed) {
+        Some("<id>".to_string())
+    } else if is_path(trimmed) {
+        Some("<path>".to_string())
+    } else if is_host(trimmed) {
+        Some("<host>".to_string())
+    } else if is_bare_name(trimmed, previous) {
+        Some("<name>".to_string())
+    } else if trimmed.chars().filter(|c| c.is_ascii_digit()).count() >= 5 {
+        Some("<n>".to_string())
+    } else {
+        None
+    };
+
+    match replacement {
+        Some(r) => token.replacen(trimmed, &r, 1),
+        None => token.to_string(),
+    }
+}
+
+/// Blank out single-quoted literals (`'alice'` → `'?'`), which typically carry
+/// row data in SQL error messages, and double-quoted names (`"orders"` →
+/// `"?"`), which carry hosts, users and object names. An apostrophe inside a
+/// word (`Can't`) does not open a literal.
+fn mask_quoted_literals(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut open_quote: Option<char> = None;
+    let mut previous: Option<char> = None;
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        match open_quote {
+            Some(quote) if c == quote => open_quote = None,
+            Some(_) => {}
+            None if c == '\''
+                && previous.is_some_and(char::is_alphanumeric)
+                && chars.peek().is_some_and(|next| next.is_alphanumeric()) =>
+            {
+                out.push(c)
+            }
+            None if c == '\'' || c == '"' => {
+                out.push(c);
+                out.push('?');
+                out.push(c);
+                open_quote = Some(c);
+            }
+            None => out.push(c),
+        }
+        previous = Some(c);
+    }
+    out
+}
+
+/// Remove identifying details from free text: the home directory, quoted
+/// literals and names, e-mail addresses, IPv4 and IPv6 addresses, UUIDs, file
+/// paths, host names (with or without a port), URL hosts, unquoted object
+/// names in database errors and long numbers (ids, phone numbers).
+pub fn scrub_text(text: &str, home_dir: Option<&str>) -> String {
+    let mut text = text.to_string();
+    if let Some(home) = home_dir.filter(|h| h.len() > 1) {
+        text = text.replace(home, "~");
+    }
+
+    let masked = mask_quoted_literals(&text);
+    let tokens: Vec<&str> = masked.split_whitespace().collect();
+    let scrubbed: Vec<String> = tokens
+        .iter()
+        .enumerate()
+        .map(|(i, token)| scrub_token(token, i.checked_sub(1).map(|p| tokens[p])))
+        .collect();
+    let mut joined = scrubbed.join(" ");
+
+    if joined.chars().count() > MAX_TEXT_LEN {
+        joined = joined.chars().take(MAX_TEXT_LEN).collect();
+        joined.push('…');
+    }
+    joined
+}
+
+/// Scrub every string inside `value`, recursively.
+pub fn scrub_value(value: Value, home_dir: Option<&str>) -> Value {
+    match value {
+        Value::String(s) => Value::String(scrub_text(&s, home_dir)),
+        Value::Array(items) => Value::Array(
+            items
+                .into_iter()
+                .map(|v| scrub_value(v, home_dir))
+                .collect(),
+        ),
+        Value::Object(map) => Value::Object(
+            map.into_iter()
+                .map(|(k, v)| (k, scrub_value(v, home_dir)))
+                .collect(),
+        ),
+        other => other,
+    }
+}
+
+fn home_dir() -> Option<String> {
+    directories::BaseDirs::new().map(|d| d.home_dir().to_string_lossy().to_string())
+}
+
+/// Build a scrubbed event stamped with the app version, OS and current time.
+pub fn build_event(
+    kind: &str,
+    name: &str,
+    properties: Map<String, Value>,
+    home_dir: Option<&str>,
+) -> TelemetryEvent {
+    let properties = match scrub_value(Value::Object(properties), home_dir) {
+        Value::Object(map) => map,
+        _ => Map::new(),
+    };
+
+    TelemetryEvent {
+        id: uuid::Uuid::new_v4().to_string(),
+        kind: kind.to_string(),
+        name: scrub_text(name, home_dir),
+        properties,
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+        os: std::env::consts::OS.to_string(),
+        timestamp: chrono::Utc::now().to_rfc3339(),
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Queue (testable; take a directory)
+// ---------------------------------------------------------------------------
+
+fn queue_path_in(dir: &Path) -> PathBuf {
+    dir.join(QUEUE_FILENAME)
+}
+
+pub fn read_queue_in(dir: &Path) -> Result<Vec<TelemetryEvent>, String> {
+    let path = queue_path_in(dir);
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let file = fs::File::open(&path).map_err(|e| e.to_string())?;
+    let events = BufReader::new(file)
+        .lines()
+        .map_while(Result::ok)
+        .filter(|line| !line.trim().is_empty())
+        .filter_map(|line| match serde_json::from_str(line.trim()) {
+            Ok(event) => Some(event),
+            Err(_) => {
+                log::warn!("Skipping unparsable telemetry entry");
+                None
+            }
+        })
+        .collect();
+    Ok(events)
+}
+
+fn write_queue_in(dir: &Path, events: &[TelemetryEvent]) -> Result<(), String> {
+    if events.is_empty() {
+        return purge_in(dir);
+    }
+
+    let mut content = String::new();
+    for event in events {
+        content.push_str(&serde_json::to_string(event).map_err(|e| e.to_string())?);
+        content.push('\n');
+    }
+    fs::write(queue_path_in(dir), content).map_err(|e| e.to_string())
+}
+
+pub fn enqueue_in(dir: &Path, event: &TelemetryEvent, max_events: usize) -> Result<(), String> {
+    if !dir.exists() {
+        fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    }
+
+    let line = serde_json::to_string(event).map_err(|e| e.to_string())?;
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(queue_path_in(dir))
+        .map_err(|e| e.to_string())?;
+    writeln!(file, "{}", line).map_err(|e| e.to_string())?;
+
+    let events = read_queue_in(dir)?;
+    if events.len() > max_events {
+        write_queue_in(dir, &events[events.len() - max_events..])?;
+    }
+    Ok(())
+}
+
+/// Drop the events with the given ids (the ones the endpoint accepted).
+pub fn remove_sent_in(dir: &Path, sent_ids: &[String]) -> Result<(), String> {
+    let remaining: Vec<TelemetryEvent> = read_queue_in(dir)?
+        .into_iter()
+        .filter(|e| !sent_ids.contains(&e.id))
+        .collect();
+    write_queue_in(dir, &remaining)
+}
+
+pub fn purge_in(dir: &Path) -> Result<(), String> {
+    let path = queue_path_in(dir);
+    if path.exists() {
+        fs::remove_file(&path).map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
+// ---------------------------------------------------------------------------
+// Public default-dir wrappers (used at runtime)
+// ---------------------------------------------------------------------------
+
+fn is_enabled() -> bool {
+    crate::config::get_cached_config().telemetry_enabled == Some(true)
+}
+
+/// Queue an event when telemetry is enabled; a no-op otherwise.
+pub fn record(kind: &str, name: &str, properties: Map<String, Value>) -> Result<(), String> {
+    if !is_enabled() {
+        return Ok(());
+    }
+    let event = build_event(kind, name, properties, home_dir().as_deref());
+    enqueue_in(&get_app_config_dir(), &event, MAX_QUEUED_EVENTS)
+}
+
+pub fn purge() -> Result<(), String> {
+    purge_in(&get_app_config_dir())
+}
+
+// ---------------------------------------------------------------------------
+// Tauri commands
+// ---------------------------------------------------------------------------
+
+#[tauri::command]
+pub fn record_telemetry_event(
+    kind: String,
+    name: String,
+    properties: Option<Map<String, Value>>,
+) -> Result<(), String> {
+    if kind != "usage" && kind != "error" {
+        return Err(format!("Unknown telemetry event kind: {}", kind));
+    }
+    record(&kind, &name, properties.unwrap_or_default())
+}
+
+/// The queued events, exactly as they would be sent.
+#[tauri::command]
+pub fn get_telemetry_queue() -> Result<Vec<TelemetryEvent>, String> {
+    read_queue_in(&get_app_config_dir())
+}
+
+#[tauri::command]
+pub fn purge_telemetry_queue() -> Result<(), String> {
+    purge()
+}
+
+/// Post the queue to the configured endpoint in batches. Stops at the first
+/// failed batch, keeping it (and everything after it) for the next attempt.
+/// Returns the number of events sent.
+#[tauri::command]
+pub async fn flush_telemetry(app: AppHandle) -> Result<usize, String> {
+    let config = crate::config::load_config_internal(&app);
+    if config.telemetry_enabled != Some(true) {
+        purge()?;
+        return Ok(0);
+    }
+    let endpoint = config
+        .telemetry_endpoint
+        .filter(|e| !e.trim().is_empty())
+        .ok_or_else(|| "No telemetry endpoint configured".to_string())?;
+
+    let dir = get_app_config_dir();
+    let events = read_queue_in(&dir)?;
+    let client = reqwest::Client::new();
+    let mut sent = 0;
+
+    for chunk in events.chunks(BATCH_SIZE) {
+        let batch = TelemetryBatch {
+            events: chunk.to_vec(),
+        };
+        let res = client
+            .post(&endpoint)
+            .header("User-Agent", "Tabularis")
+            .json(&batch)
+            .send()
+            .await
+            .map_err(|e| format!("Failed to send telemetry: {}", e))?;
+
+        if !res.status().is_success() {
+            return Err(format!("Telemetry endpoint error: {}", res.status()));
+        }
+
+        let ids: Vec<String> = chunk.iter().map(|e| e.id.clone()).collect();
+        remove_sent_in(&dir, &ids)?;
+        sent += chunk.len();
+    }
+
+    Ok(sent)
+}
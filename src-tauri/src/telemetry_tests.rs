@@ -0,0 +1,219 @@
+#[cfg(test)]
+mod tests {
+    use crate::telemetry::{
+        build_event, enqueue_in, purge_in, read_queue_in, remove_sent_in, scrub_text, scrub_value,
+    };
+    use serde_json::{json, Map, Value};
+    use tempfile::TempDir;
+
+    fn props(value: Value) -> Map<String, Value> {
+        value.as_object().cloned().unwrap()
+    }
+
+    // -----------------------------------------------------------------------
+    // Anonymization
+    // -----------------------------------------------------------------------
+
+    #[test]
+    fn scrub_masks_quoted_literals() {
+        assert_eq!(
+            scrub_text("Duplicate entry 'alice' for key 'email'", None),
+            "Duplicate entry '?' for key '?'"
+        );
+    }
+
+    #[test]
+    fn scrub_replaces_identifiers() {
+        let text =
+            "connect to 10.0.0.12:5432 as bob@corp.com id 550e8400-e29b-41d4-a716-446655440000";
+        assert_eq!(scrub_text(text, None), "connect to <ip> as <email> id <id>");
+    }
+
+    #[test]
+    fn scrub_replaces_paths_and_home_dir() {
+        assert_eq!(
+            scrub_text("cannot open /home/alice/db/app.sqlite", Some("/home/alice")),
+            "cannot open <path>"
+        );
+        assert_eq!(
+            scrub_text("cannot open C:\\Users\\alice\\app.db", None),
+            "cannot open <path>"
+        );
+    }
+
+    #[test]
+    fn scrub_strips_url_hosts_and_long_numbers() {
+        assert_eq!(
+            scrub_text(
+                "GET https://db.internal.corp/api failed (order 1234567)",
+                None
+            ),
+            "GET https://<host> failed (order <n>)"
+        );
+    }
+
+    #[test]
+    fn scrub_masks_hosts_with_and_without_ports() {
+        assert_eq!(
+            scrub_text(
+                "failed to connect to prod-db.acme.internal:5432: Connection refused",
+                None
+            ),
+            "failed to connect to <host>: Connection refused"
+        );
+        assert_eq!(
+            scrub_text("ping db.acme.io and localhost:5432 from v0.9.0", None),
+            "ping <host> and <host> from v0.9.0"
+        );
+    }
+
+    #[test]
+    fn scrub_masks_ipv6_addresses() {
+        assert_eq!(
+            scrub_text("connect to [2001:db8::1]:5432 or fe80::1%eth0 or ::1", None),
+            "connect to [<ip>]:5432 or <ip> or <ip>"
+        );
+    }
+
+    #[test]
+    fn scrub_masks_postgres_connection_errors() {
+        assert_eq!(
+            scrub_text(
+                "connection to server at \"db.prod.acme.io\" (10.1.2.3), port 5432 failed: \
+                 FATAL:  database \"payroll\" does not exist",
+                None
+            ),
+            "connection to server at \"?\" (<ip>), port 5432 failed: \
+             FATAL: database \"?\" does not exist"
+        );
+        assert_eq!(
+            scrub_text(
+                "error returned from database: password authentication failed for user \"alice\"",
+                None
+            ),
+            "error returned from database: password authentication failed for user \"?\""
+        );
+    }
+
+    #[test]
+    fn scrub_masks_mysql_connection_errors() {
+        assert_eq!(
+            scrub_text(
+                "error returned from database: 1045 (28000): Access denied for user \
+                 'bob'@'172.16.0.4' (using password: YES)",
+                None
+            ),
+            "error returned from database: 1045 (<n>): Access denied for user \
+             '?'@'?' (using password: YES)"
+        );
+        assert_eq!(
+            scrub_text(
+                "Can't connect to MySQL server on 'db-primary.internal:3306' (111)",
+                None
+            ),
+            "Can't connect to MySQL server on '?' (111)"
+        );
+    }
+
+    #[test]
+    fn scrub_masks_bare_object_names() {
+        assert_eq!(
+            scrub_text("no such table: orders", None),
+            "no such table: <name>"
+        );
+        assert_eq!(
+            scrub_text("table users has no column named email", None),
+            "table <name> has no column named <name>"
+        );
+    }
+
+    #[test]
+    fn scrub_keeps_plain_text() {
+        assert_eq!(
+            scrub_text("query_executed  in 12 ms", None),
+            "query_executed in 12 ms"
+        );
+    }
+
+    #[test]
+    fn scrub_value_recurses_and_keeps_non_strings() {
+        let value = json!({
+            "driver": "postgres",
+            "rows": 42,
+            "errors": ["user bob@x.io denied"],
+            "nested": { "host": "192.168.1.1" }
+        });
+        assert_eq!(
+            scrub_value(value, None),
+            json!({
+                "driver": "postgres",
+                "rows": 42,
+                "errors": ["user <email> denied"],
+                "nested": { "host": "<ip>" }
+            })
+        );
+    }
+
+    #[test]
+    fn build_event_scrubs_properties() {
+        let event = build_event(
+            "error",
+            "query_failed",
+            props(json!({ "message": "no such table 'secrets'" })),
+            None,
+        );
+        assert_eq!(event.kind, "error");
+        assert_eq!(event.properties["message"], json!("no such table '?'"));
+        assert_eq!(event.app_version, env!("CARGO_PKG_VERSION"));
+    }
+
+    // -----------------------------------------------------------------------
+    // Queue
+    // -----------------------------------------------------------------------
+
+    #[test]
+    fn read_missing_queue_is_empty() {
+        let tmp = TempDir::new().unwrap();
+        assert!(read_queue_in(tmp.path()).unwrap().is_empty());
+    }
+
+    #[test]
+    fn enqueue_caps_queue_dropping_oldest() {
+        let tmp = TempDir::new().unwrap();
+        let events: Vec<_> = (0..5)
+            .map(|i| build_event("usage", &format!("e{}", i), Map::new(), None))
+            .collect();
+        for event in &events {
+            enqueue_in(tmp.path(), event, 3).unwrap();
+        }
+
+        let queued = read_queue_in(tmp.path()).unwrap();
+        let names: Vec<&str> = queued.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(names, vec!["e2", "e3", "e4"]);
+    }
+
+    #[test]
+    fn remove_sent_keeps_unsent_events() {
+        let tmp = TempDir::new().unwrap();
+        let a = build_event("usage", "a", Map::new(), None);
+        let b = build_event("usage", "b", Map::new(), None);
+        enqueue_in(tmp.path(), &a, 10).unwrap();
+        enqueue_in(tmp.path(), &b, 10).unwrap();
+
+        remove_sent_in(tmp.path(), &[a.id.clone()]).unwrap();
+
+        assert_eq!(read_queue_in(tmp.path()).unwrap(), vec![b]);
+    }
+
+    #[test]
+    fn purge_removes_queue() {
+        let tmp = TempDir::new().unwrap();
+        enqueue_in(tmp.path(), &build_event("usage", "a", Map::new(), None), 10).unwrap();
+
+        purge_in(tmp.path()).unwrap();
+
+        assert!(read_queue_in(tmp.path()).unwrap().is_empty());
+        // Purging an empty queue is fine too.
+        purge_in(tmp.path()).unwrap();
+    }
+}
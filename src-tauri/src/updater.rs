@@ -1,10 +1,14 @@
 use reqwest::Client;
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::cmp::Ordering;
 use std::fs;
 use std::path::PathBuf;
+use std::sync::Mutex;
 use std::time::{SystemTime, UNIX_EPOCH};
 use tauri::Emitter;
-use tauri::{AppHandle, Manager};
+use tauri::{AppHandle, Manager, State};
 
 // Strutture dati
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -17,6 +21,10 @@ pub struct UpdateCheckResult {
     pub release_url: String,
     pub published_at: String,
     pub download_urls: Vec<DownloadAsset>,
+    #[serde(default)]
+    pub channel: String,
+    #[serde(default)]
+    pub prerelease: bool,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -28,21 +36,41 @@ pub struct DownloadAsset {
     pub platform: String,
 }
 
+/// Notes of a single release, as shown in the "What's new" dialog.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ReleaseNotes {
+    pub version: String,
+    pub notes: String,
+    pub release_url: String,
+    pub published_at: String,
+    pub prerelease: bool,
+}
+
 // Cache structure
 #[derive(Serialize, Deserialize, Debug, Clone)]
 struct UpdateCheckCache {
     last_checked: u64,
     last_result: Option<UpdateCheckResult>,
+    /// Channel the cached result was fetched for (absent in old caches = stable)
+    #[serde(default)]
+    channel: Option<String>,
 }
 
 // GitHub API response
 #[derive(Deserialize, Debug)]
 struct GitHubRelease {
     tag_name: String,
+    #[serde(default)]
     body: String,
     html_url: String,
+    #[serde(default)]
     published_at: String,
     assets: Vec<GitHubAsset>,
+    #[serde(default)]
+    prerelease: bool,
+    #[serde(default)]
+    draft: bool,
 }
 
 #[derive(Deserialize, Debug)]
@@ -55,6 +83,20 @@ struct GitHubAsset {
 // Constants
 const GITHUB_REPO: &str = "TabularisDB/tabularis";
 const CACHE_DURATION_SECS: u64 = 43200; // 12 hours
+const CHANNEL_STABLE: &str = "stable";
+const CHANNEL_BETA: &str = "beta";
+/// Checksum files published next to the release artifacts (sha256sum format)
+const CHECKSUM_ASSET_NAMES: [&str; 4] = [
+    "sha256sums",
+    "sha256sums.txt",
+    "checksums.txt",
+    "checksums.sha256",
+];
+
+/// An update downloaded ahead of time, installed when the app exits.
+#[derive(Default)]
+pub struct StagedUpdate(Mutex<Option<(tauri_plugin_updater::Update, Vec<u8>)>>);
+
 /// Returns the installation source: "snap", "aur", or None for direct installs.
 /// Only meaningful on Linux; always returns None on other platforms.
 fn detect_installation_source() -> Option<String> {
@@ -98,8 +140,7 @@ pub fn get_installation_source() -> Option<String> {
 
 // Helper functions
 fn get_cache_path(app: &AppHandle) -> Option<PathBuf> {
-    app.path()
-        .app_config_dir()
+    crate::paths::app_config_dir(app)
         .ok()
         .map(|p| p.join("update_check_cache.json"))
 }
@@ -118,19 +159,106 @@ fn parse_version(version: &str) -> Option<(u32, u32, u32)> {
     Some((major, minor, patch))
 }
 
+/// Splits `v1.2.3-beta.1` into `("1.2.3", Some("beta.1"))`.
+fn split_prerelease(version: &str) -> (&str, Option<&str>) {
+    let clean = version.trim_start_matches('v');
+    match clean.split_once('-') {
+        Some((core, pre)) => (core, Some(pre)),
+        None => (clean, None),
+    }
+}
+
+/// Semver precedence for pre-release identifiers: numeric parts compare
+/// numerically and rank below alphanumeric ones.
+fn compare_prerelease(a: &str, b: &str) -> Ordering {
+    let mut a_parts = a.split('.');
+    let mut b_parts = b.split('.');
+    loop {
+        let ord = match (a_parts.next(), b_parts.next()) {
+            (None, None) => return Ordering::Equal,
+            (None, Some(_)) => return Ordering::Less,
+            (Some(_), None) => return Ordering::Greater,
+            (Some(x), Some(y)) => match (x.parse::<u64>(), y.parse::<u64>()) {
+                (Ok(x), Ok(y)) => x.cmp(&y),
+                (Ok(_), Err(_)) => Ordering::Less,
+                (Err(_), Ok(_)) => Ordering::Greater,
+                (Err(_), Err(_)) => x.cmp(y),
+            },
+        };
+        if ord != Ordering::Equal {
+            return ord;
+        }
+    }
+}
+
+/// Compares two versions; a pre-release sorts below its final release.
+fn compare_versions(a: &str, b: &str) -> Option<Ordering> {
+    let (a_core, a_pre) = split_prerelease(a);
+    let (b_core, b_pre) = split_prerelease(b);
+    let ord = parse_version(a_core)?.cmp(&parse_version(b_core)?);
+
+    Some(ord.then_with(|| match (a_pre, b_pre) {
+        (None, None) => Ordering::Equal,
+        (None, Some(_)) => Ordering::Greater,
+        (Some(_), None) => Ordering::Less,
+        (Some(x), Some(y)) => compare_prerelease(x, y),
+    }))
+}
+
 fn is_newer_version(current: &str, latest: &str) -> bool {
-    match (parse_version(current), parse_version(latest)) {
-        (Some(c), Some(l)) => l > c,
-        _ => false,
+    compare_versions(latest, current) == Some(Ordering::Greater)
+}
+
+/// Maps the `updateChannel` setting to a known channel, defaulting to stable.
+fn normalize_channel(channel: Option<&str>) -> &'static str {
+    match channel {
+        Some(c) if c.trim().eq_ignore_ascii_case(CHANNEL_BETA) => CHANNEL_BETA,
+        _ => CHANNEL_STABLE,
     }
 }
 
-async fn fetch_latest_release() -> Result<GitHubRelease, String> {
+/// Whether `release` is offered on `channel`. Beta also receives stable releases.
+fn is_release_in_channel(release: &GitHubRelease, channel: &str) -> bool {
+    !release.draft
+        && (channel == CHANNEL_BETA || !release.prerelease)
+        && parse_version(split_prerelease(&release.tag_name).0).is_some()
+}
+
+/// Picks the newest release offered on `channel`.
+fn pick_channel_release(releases: Vec<GitHubRelease>, channel: &str) -> Option<GitHubRelease> {
+    releases
+        .into_iter()
+        .filter(|r| is_release_in_channel(r, channel))
+        .max_by(|a, b| compare_versions(&a.tag_name, &b.tag_name).unwrap_or(Ordering::Equal))
+}
+
+/// Notes of every release on `channel` newer than `current`, newest first.
+fn collect_release_notes(
+    releases: Vec<GitHubRelease>,
+    current: &str,
+    channel: &str,
+) -> Vec<ReleaseNotes> {
+    let mut newer: Vec<GitHubRelease> = releases
+        .into_iter()
+        .filter(|r| is_release_in_channel(r, channel) && is_newer_version(current, &r.tag_name))
+        .collect();
+    newer.sort_by(|a, b| compare_versions(&b.tag_name, &a.tag_name).unwrap_or(Ordering::Equal));
+
+    newer
+        .into_iter()
+        .map(|r| ReleaseNotes {
+            version: r.tag_name.trim_start_matches('v').to_string(),
+            notes: r.body,
+            release_url: r.html_url,
+            published_at: r.published_at,
+            prerelease: r.prerelease,
+        })
+        .collect()
+}
+
+async fn github_get<T: DeserializeOwned>(path: &str) -> Result<T, String> {
     let client = Client::new();
-    let url = format!(
-        "https://api.github.com/repos/{}/releases/latest",
-        GITHUB_REPO
-    );
+    let url = format!("https://api.github.com/repos/{}/{}", GITHUB_REPO, path);
 
     let res = client
         .get(&url)
@@ -144,11 +272,202 @@ async fn fetch_latest_release() -> Result<GitHubRelease, String> {
         return Err(format!("GitHub API error: {}", res.status()));
     }
 
-    res.json::<GitHubRelease>()
+    res.json::<T>()
         .await
         .map_err(|e| format!("Failed to parse response: {}", e))
 }
 
+async fn fetch_latest_release() -> Result<GitHubRelease, String> {
+    github_get("releases/latest").await
+}
+
+async fn fetch_releases() -> Result<Vec<GitHubRelease>, String> {
+    github_get("releases?per_page=30").await
+}
+
+/// Latest release on `channel`. `/releases/latest` never returns
+/// pre-releases, so the beta channel scans the recent release list instead.
+async fn fetch_channel_release(channel: &str) -> Result<GitHubRelease, String> {
+    if channel == CHANNEL_BETA {
+        pick_channel_release(fetch_releases().await?, channel)
+            .ok_or_else(|| "No release found on the beta channel".to_string())
+    } else {
+        fetch_latest_release().await
+    }
+}
+
+/// Name of the checksum file covering `asset_name`: a per-file `<name>.sha256`
+/// is preferred over a combined checksums list.
+fn find_checksum_asset<'a>(assets: &'a [GitHubAsset], asset_name: &str) -> Option<&'a GitHubAsset> {
+    let per_file = format!("{}.sha256", asset_name);
+    assets.iter().find(|a| a.name == per_file).or_else(|| {
+        assets
+            .iter()
+            .find(|a| CHECKSUM_ASSET_NAMES.contains(&a.name.to_ascii_lowercase().as_str()))
+    })
+}
+
+/// Extracts the SHA-256 of `asset_name` from a `sha256sum`-style listing
+/// (`<hash>  <file>` or `<hash> *<file>`). A file holding a single bare hash
+/// applies to the asset it was published for.
+fn parse_checksum(content: &str, asset_name: &str) -> Option<String> {
+    let is_hash = |h: &str| h.len() == 64 && h.chars().all(|c| c.is_ascii_hexdigit());
+    let lines: Vec<&str> = content
+        .lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty())
+        .collect();
+
+    for line in &lines {
+        let mut parts = line.split_whitespace();
+        let (Some(hash), file) = (parts.next(), parts.next()) else {
+            continue;
+        };
+        if !is_hash(hash) {
+            continue;
+        }
+        match file {
+            Some(file) if file.trim_start_matches('*') == asset_name => {
+                return Some(hash.to_ascii_lowercase())
+            }
+            None if lines.len() == 1 => return Some(hash.to_ascii_lowercase()),
+            _ => {}
+        }
+    }
+    None
+}
+
+/// What a downloaded artifact is checked against before it is kept.
+enum ArtifactCheck {
+    /// The `<asset>.sig` minisign signature made with the release key, and
+    /// the updater public key from `tauri.conf.json`.
+    Signature { signature: String, pubkey: String },
+    /// The SHA-256 from a checksum file of the same release.
+    Checksum(String),
+}
+
+impl ArtifactCheck {
+    fn verify(&self, bytes: &[u8]) -> Result<(), String> {
+        match self {
+            ArtifactCheck::Signature { signature, pubkey } => {
+                verify_signature(bytes, signature, pubkey)
+            }
+            ArtifactCheck::Checksum(expected) => {
+                let actual = sha256_hex(bytes);
+                if &actual == expected {
+                    Ok(())
+                } else {
+                    Err(format!(
+                        "Checksum mismatch: expected {}, got {}",
+                        expected, actual
+                    ))
+                }
+            }
+        }
+    }
+}
+
+/// Verifies `data` against a Tauri updater signature (a base64-encoded
+/// minisign signature) with the base64-encoded minisign public key, the same
+/// way the updater plugin checks the bundles it installs.
+fn verify_signature(data: &[u8], signature: &str, pubkey: &str) -> Result<(), String> {
+    use base64::Engine;
+
+    let decode = |value: &str| -> Result<String, String> {
+        let bytes = base64::engine::general_purpose::STANDARD
+            .decode(value.trim())
+            .map_err(|e| e.to_string())?;
+        String::from_utf8(bytes).map_err(|e| e.to_string())
+    };
+    let public_key = decode(pubkey)
+        .and_then(|key| minisign_verify::PublicKey::decode(&key).map_err(|e| e.to_string()))
+        .map_err(|e| format!("Invalid updater public key: {}", e))?;
+    let signature = decode(signature)
+        .and_then(|sig| minisign_verify::Signature::decode(&sig).map_err(|e| e.to_string()))
+        .map_err(|e| format!("Invalid update signature: {}", e))?;
+    public_key
+        .verify(data, &signature, true)
+        .map_err(|e| format!("Signature verification failed: {}", e))
+}
+
+/// The updater public key from the `plugins.updater` section of
+/// `tauri.conf.json`.
+fn updater_pubkey(app: &AppHandle) -> Option<String> {
+    app.config()
+        .plugins
+        .0
+        .get("updater")?
+        .get("pubkey")?
+        .as_str()
+        .map(str::to_string)
+}
+
+fn sha256_hex(bytes: &[u8]) -> String {
+    Sha256::digest(bytes)
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+async fn download_bytes(
+    app: &AppHandle,
+    url: &str,
+    report_progress: bool,
+) -> Result<Vec<u8>, String> {
+    let mut res = Client::new()
+        .get(url)
+        .header("User-Agent", "Tabularis")
+        .send()
+        .await
+        .map_err(|e| format!("Network error: {}", e))?;
+
+    if !res.status().is_success() {
+        return Err(format!("Download failed: {}", res.status()));
+    }
+
+    let total = res.content_length();
+    let mut bytes = Vec::with_capacity(total.unwrap_or(0) as usize);
+    while let Some(chunk) = res
+        .chunk()
+        .await
+        .map_err(|e| format!("Download failed: {}", e))?
+    {
+        bytes.extend_from_slice(&chunk);
+        if report_progress {
+            if let Some(total) = total.filter(|t| *t > 0) {
+                let progress = (bytes.len() as f64 / total as f64 * 100.0) as u32;
+                let _ = app.emit("update-progress", progress);
+            }
+        }
+    }
+
+    Ok(bytes)
+}
+
+/// Updater configured for `channel`. Stable uses the endpoint from
+/// `tauri.conf.json`; beta reads the `latest.json` of the newest beta release.
+async fn build_channel_updater(
+    app: &AppHandle,
+    channel: &str,
+) -> Result<tauri_plugin_updater::Updater, String> {
+    use tauri_plugin_updater::UpdaterExt;
+
+    let mut builder = app.updater_builder();
+    if channel == CHANNEL_BETA {
+        let release = fetch_channel_release(channel).await?;
+        let endpoint = format!(
+            "https://github.com/{}/releases/download/{}/latest.json",
+            GITHUB_REPO, release.tag_name
+        );
+        let url = endpoint
+            .parse::<tauri::Url>()
+            .map_err(|e| format!("Invalid updater endpoint: {}", e))?;
+        builder = builder.endpoints(vec![url]).map_err(|e| e.to_string())?;
+    }
+
+    builder.build().map_err(|e| e.to_string())
+}
+
 fn categorize_asset(name: &str) -> String {
     if name.ends_with(".dmg") || name.contains("darwin") || name.contains("macos") {
         "macos".to_string()
@@ -170,6 +489,7 @@ pub async fn check_for_updates(app: AppHandle, force: bool) -> Result<UpdateChec
     }
 
     let config = crate::config::load_config_internal(&app);
+    let channel = normalize_channel(config.update_channel.as_deref());
 
     // Check if updates are disabled
     if !force && config.check_for_updates == Some(false) {
@@ -187,7 +507,10 @@ pub async fn check_for_updates(app: AppHandle, force: bool) -> Result<UpdateChec
                             .unwrap_or_default()
                             .as_secs();
 
-                        if now - cache.last_checked < CACHE_DURATION_SECS {
+                        let same_channel =
+                            cache.channel.as_deref().unwrap_or(CHANNEL_STABLE) == channel;
+
+                        if same_channel && now - cache.last_checked < CACHE_DURATION_SECS {
                             if let Some(result) = cache.last_result {
                                 // Invalidate cache if the app was updated since it was written
                                 if result.current_version == env!("CARGO_PKG_VERSION") {
@@ -201,8 +524,8 @@ pub async fn check_for_updates(app: AppHandle, force: bool) -> Result<UpdateChec
         }
     }
 
-    // Fetch latest release from GitHub
-    let release = fetch_latest_release().await?;
+    // Fetch latest release of the configured channel from GitHub
+    let release = fetch_channel_release(channel).await?;
 
     let current_version = env!("CARGO_PKG_VERSION");
     let latest_version = release.tag_name.trim_start_matches('v');
@@ -226,6 +549,8 @@ pub async fn check_for_updates(app: AppHandle, force: bool) -> Result<UpdateChec
         release_url: release.html_url,
         published_at: release.published_at,
         download_urls,
+        channel: channel.to_string(),
+        prerelease: release.prerelease,
     };
 
     // Save to cache
@@ -238,6 +563,7 @@ pub async fn check_for_updates(app: AppHandle, force: bool) -> Result<UpdateChec
         let cache = UpdateCheckCache {
             last_checked: timestamp,
             last_result: Some(result.clone()),
+            channel: Some(channel.to_string()),
         };
 
         if let Ok(content) = serde_json::to_string(&cache) {
@@ -248,12 +574,191 @@ pub async fn check_for_updates(app: AppHandle, force: bool) -> Result<UpdateChec
     Ok(result)
 }
 
+/// Release notes of every version between the installed one and the latest
+/// release on `channel` (defaults to the configured channel), newest first.
+#[tauri::command]
+pub async fn get_release_notes(
+    app: AppHandle,
+    channel: Option<String>,
+) -> Result<Vec<ReleaseNotes>, String> {
+    let config = crate::config::load_config_internal(&app);
+    let channel = normalize_channel(channel.as_deref().or(config.update_channel.as_deref()));
+
+    let releases = fetch_releases().await?;
+    Ok(collect_release_notes(
+        releases,
+        env!("CARGO_PKG_VERSION"),
+        channel,
+    ))
+}
+
+/// Download a release artifact of the configured channel into the app cache
+/// and return its path. A previously downloaded copy that still verifies is
+/// reused.
+///
+/// Updater bundles are published with a `<asset>.sig` minisign signature
+/// made with the release key; those are verified against the public key
+/// in `tauri.conf.json`, so a tampered release is rejected. Other artifacts
+/// (`.dmg`, `.deb`, ...) are only covered by the SHA-256 checksum file of the
+/// same release: that catches corrupted downloads but not a release whose
+/// files and checksums were both replaced.
+///
+/// Artifacts are always downloaded whole: releases publish full bundles
+/// only, so there is nothing to build a delta download from.
+#[tauri::command]
+pub async fn download_update_artifact(
+    app: AppHandle,
+    asset_name: String,
+) -> Result<String, String> {
+    let config = crate::config::load_config_internal(&app);
+    let channel = normalize_channel(config.update_channel.as_deref());
+    let release = fetch_channel_release(channel).await?;
+
+    let asset = release
+        .assets
+        .iter()
+        .find(|a| a.name == asset_name)
+        .ok_or_else(|| {
+            format!(
+                "Asset {} not found in release {}",
+                asset_name, release.tag_name
+            )
+        })?;
+
+    let signature_name = format!("{}.sig", asset_name);
+    let check = match release.assets.iter().find(|a| a.name == signature_name) {
+        Some(signature_asset) => {
+            let pubkey = updater_pubkey(&app).ok_or("No updater public key configured")?;
+            let signature =
+                download_bytes(&app, &signature_asset.browser_download_url, false).await?;
+            ArtifactCheck::Signature {
+                signature: String::from_utf8_lossy(&signature).to_string(),
+                pubkey,
+            }
+        }
+        None => {
+            let checksum_asset = find_checksum_asset(&release.assets, &asset_name)
+                .ok_or_else(|| format!("No signature or checksum published for {}", asset_name))?;
+            let checksums =
+                download_bytes(&app, &checksum_asset.browser_download_url, false).await?;
+            let expected = parse_checksum(&String::from_utf8_lossy(&checksums), &asset_name)
+                .ok_or_else(|| format!("No checksum published for {}", asset_name))?;
+            log::warn!(
+                "No signature published for {}, verifying its checksum only",
+                asset_name
+            );
+            ArtifactCheck::Checksum(expected)
+        }
+    };
+
+    let dir = crate::paths::app_cache_dir(&app)
+        .map_err(|e| format!("Failed to resolve cache dir: {}", e))?
+        .join("updates")
+        .join(release.tag_name.trim_start_matches('v'));
+    fs::create_dir_all(&dir).map_err(|e| format!("Failed to create updates dir: {}", e))?;
+    let path = dir.join(&asset_name);
+
+    if let Ok(existing) = fs::read(&path) {
+        if check.verify(&existing).is_ok() {
+            return Ok(path.to_string_lossy().to_string());
+        }
+    }
+
+    let bytes = download_bytes(&app, &asset.browser_download_url, true).await?;
+    check
+        .verify(&bytes)
+        .map_err(|e| format!("{} failed verification: {}", asset_name, e))?;
+
+    fs::write(&path, &bytes).map_err(|e| format!("Failed to write update file: {}", e))?;
+    Ok(path.to_string_lossy().to_string())
+}
+
+/// Download the update for the configured channel now and install it when
+/// the app exits. The updater plugin verifies the artifact signature.
+/// Returns the staged version.
+#[tauri::command]
+pub async fn stage_update(
+    app: AppHandle,
+    staged: State<'_, StagedUpdate>,
+) -> Result<String, String> {
+    if is_managed_package() {
+        return Err("Updates are managed by the package manager".to_string());
+    }
+
+    let config = crate::config::load_config_internal(&app);
+    let channel = normalize_channel(config.update_channel.as_deref());
+    let updater = build_channel_updater(&app, channel).await?;
+
+    let update = updater
+        .check()
+        .await
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| "No update available".to_string())?;
+
+    let mut downloaded = 0;
+    let bytes = update
+        .download(
+            |chunk_length, content_length| {
+                downloaded += chunk_length;
+                let progress = if let Some(total) = content_length {
+                    (downloaded as f64 / total as f64 * 100.0) as u32
+                } else {
+                    0
+                };
+
+                let _ = app.emit("update-progress", progress);
+            },
+            || {
+                let _ = app.emit("update-staged", ());
+            },
+        )
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let version = update.version.clone();
+    let mut guard = staged
+        .0
+        .lock()
+        .map_err(|e| format!("Failed to lock staged update: {}", e))?;
+    *guard = Some((update, bytes));
+
+    Ok(version)
+}
+
+/// Version of the update waiting to be installed on exit, if any.
+#[tauri::command]
+pub fn get_staged_update(staged: State<'_, StagedUpdate>) -> Option<String> {
+    staged
+        .0
+        .lock()
+        .ok()
+        .and_then(|guard| guard.as_ref().map(|(update, _)| update.version.clone()))
+}
+
+/// Install the staged update, if any. Called once the app is exiting.
+pub fn install_staged_update(app: &AppHandle) {
+    let Some(staged) = app.try_state::<StagedUpdate>() else {
+        return;
+    };
+    let pending = match staged.0.lock() {
+        Ok(mut guard) => guard.take(),
+        Err(_) => None,
+    };
+
+    if let Some((update, bytes)) = pending {
+        log::info!("Installing staged update {}", update.version);
+        if let Err(e) = update.install(bytes) {
+            log::error!("Failed to install staged update: {}", e);
+        }
+    }
+}
+
 #[tauri::command]
 pub async fn download_and_install_update(app: AppHandle) -> Result<(), String> {
     // Usa tauri-plugin-updater per gestire il download e installazione
-    use tauri_plugin_updater::UpdaterExt;
-
-    let updater = app.updater_builder().build().map_err(|e| e.to_string())?;
+    let config = crate::config::load_config_internal(&app);
+    let channel = normalize_channel(config.update_channel.as_deref());
+    let updater = build_channel_updater(&app, channel).await?;
 
     if let Some(update) = updater.check().await.map_err(|e| e.to_string())? {
         // Emetti eventi per aggiornare la UI sul progresso
@@ -400,6 +905,153 @@ mod tests {
         assert_eq!(CACHE_DURATION_SECS / 3600, 12); // Verify it's 12 hours
     }
 
+    // Pre-release / channel tests
+    fn release(tag: &str, prerelease: bool) -> GitHubRelease {
+        GitHubRelease {
+            tag_name: tag.to_string(),
+            body: format!("notes {}", tag),
+            html_url: format!("https://example.com/{}", tag),
+            published_at: String::new(),
+            assets: Vec::new(),
+            prerelease,
+            draft: false,
+        }
+    }
+
+    #[test]
+    fn test_version_comparison_prerelease() {
+        assert!(is_newer_version("0.9.0", "0.10.0-beta.1"));
+        assert!(is_newer_version("0.10.0-beta.1", "0.10.0"));
+        assert!(is_newer_version("0.10.0-beta.2", "0.10.0-beta.10"));
+        assert!(is_newer_version("0.10.0-alpha", "0.10.0-beta"));
+        assert!(!is_newer_version("0.10.0", "0.10.0-beta.3"));
+        assert!(!is_newer_version("0.10.0-beta.1", "0.10.0-beta.1"));
+    }
+
+    #[test]
+    fn test_normalize_channel() {
+        assert_eq!(normalize_channel(None), CHANNEL_STABLE);
+        assert_eq!(normalize_channel(Some("Beta")), CHANNEL_BETA);
+        assert_eq!(normalize_channel(Some("nightly")), CHANNEL_STABLE);
+    }
+
+    #[test]
+    fn test_pick_channel_release() {
+        let releases = || {
+            let mut draft = release("v0.12.0", false);
+            draft.draft = true;
+            vec![
+                release("v0.10.0", false),
+                release("v0.11.0-beta.2", true),
+                release("v0.10.1", false),
+                draft,
+            ]
+        };
+
+        let stable = pick_channel_release(releases(), CHANNEL_STABLE).unwrap();
+        assert_eq!(stable.tag_name, "v0.10.1");
+        let beta = pick_channel_release(releases(), CHANNEL_BETA).unwrap();
+        assert_eq!(beta.tag_name, "v0.11.0-beta.2");
+    }
+
+    #[test]
+    fn test_collect_release_notes_newest_first() {
+        let releases = vec![
+            release("v0.9.0", false),
+            release("v0.10.1", false),
+            release("v0.10.0", false),
+            release("v0.11.0-beta.1", true),
+        ];
+
+        let notes = collect_release_notes(releases, "0.9.0", CHANNEL_STABLE);
+        let versions: Vec<&str> = notes.iter().map(|n| n.version.as_str()).collect();
+        assert_eq!(versions, vec!["0.10.1", "0.10.0"]);
+        assert_eq!(notes[0].notes, "notes v0.10.1");
+    }
+
+    // Checksum tests
+    const HASH: &str = "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad";
+
+    #[test]
+    fn test_sha256_hex() {
+        assert_eq!(sha256_hex(b"abc"), HASH);
+    }
+
+    #[test]
+    fn test_parse_checksum_listing() {
+        let listing = format!(
+            "{}  Tabularis_0.9.0_x64.dmg\n{} *tabularis_0.9.0_amd64.AppImage\n",
+            "0".repeat(64),
+            HASH.to_uppercase()
+        );
+        assert_eq!(
+            parse_checksum(&listing, "tabularis_0.9.0_amd64.AppImage").as_deref(),
+            Some(HASH)
+        );
+        assert_eq!(parse_checksum(&listing, "missing.deb"), None);
+    }
+
+    #[test]
+    fn test_parse_checksum_single_hash_file() {
+        assert_eq!(
+            parse_checksum(&format!("{}\n", HASH), "any.msi").as_deref(),
+            Some(HASH)
+        );
+        assert_eq!(parse_checksum("not-a-hash", "any.msi"), None);
+    }
+
+    #[test]
+    fn test_find_checksum_asset_prefers_per_file() {
+        let asset = |name: &str| GitHubAsset {
+            name: name.to_string(),
+            browser_download_url: String::new(),
+            size: 0,
+        };
+        let assets = vec![
+            asset("SHA256SUMS"),
+            asset("app.msi"),
+            asset("app.msi.sha256"),
+        ];
+
+        assert_eq!(
+            find_checksum_asset(&assets, "app.msi").map(|a| a.name.as_str()),
+            Some("app.msi.sha256")
+        );
+        assert_eq!(
+            find_checksum_asset(&assets, "app.dmg").map(|a| a.name.as_str()),
+            Some("SHA256SUMS")
+        );
+    }
+
+    // Signature tests: a throwaway key pair, and its signature of SIGNED_DATA
+    // in the format of a Tauri `.sig` file.
+    const SIGNED_DATA: &[u8] = b"tabularis update";
+    const TEST_PUBKEY: &str = "dW50cnVzdGVkIGNvbW1lbnQ6IG1pbmlzaWduIHB1YmxpYyBrZXk6IDA4MDcwNjA1MDQwMzAyMDEKUldRQkFnTUVCUVlIQ0FPaEI3L3p6aEMrSFhEZEdPZEx3SmxuNU5Zd202VU5YeDNjaG1RU1ZURzQK";
+    const TEST_SIGNATURE: &str = "dW50cnVzdGVkIGNvbW1lbnQ6IHNpZ25hdHVyZSBmcm9tIHRhdXJpIHNlY3JldCBrZXkKUlVRQkFnTUVCUVlIQ0ErN01VbG5HellyOFE0YVNnUXJQOEo2NFpUN2IyUU4raHRzSmFxMnJ1a1pkWUdmdWhQTis1Nm5sNHhCNUpyWk9pTUNiR05vT1UwSytJYmlFaFdSaUFJPQp0cnVzdGVkIGNvbW1lbnQ6IHRpbWVzdGFtcDoxNzAwMDAwMDAwCWZpbGU6YXBwLkFwcEltYWdlClBlS0Z3K2RIVVBsMkxmRHVvSHhqa0ZVSWREYVo2citzdkpVOTFleWJIMktVTDE4VTRVbENWV3ZuR0llVnY5djkrTmpHbHVKbDRBMHYxNGc0SGp0aUJBPT0K";
+
+    #[test]
+    fn test_verify_signature() {
+        assert!(verify_signature(SIGNED_DATA, TEST_SIGNATURE, TEST_PUBKEY).is_ok());
+        assert!(verify_signature(b"tampered update", TEST_SIGNATURE, TEST_PUBKEY).is_err());
+        assert!(verify_signature(SIGNED_DATA, "not-a-signature", TEST_PUBKEY).is_err());
+    }
+
+    #[test]
+    fn test_verify_signature_rejects_other_keys() {
+        // The release key in tauri.conf.json did not make the test signature.
+        let conf: serde_json::Value =
+            serde_json::from_str(include_str!("../tauri.conf.json")).unwrap();
+        let release_key = conf["plugins"]["updater"]["pubkey"].as_str().unwrap();
+        assert!(verify_signature(SIGNED_DATA, TEST_SIGNATURE, release_key).is_err());
+    }
+
+    #[test]
+    fn test_artifact_check_checksum() {
+        let check = ArtifactCheck::Checksum(HASH.to_string());
+        assert!(check.verify(b"abc").is_ok());
+        assert!(check.verify(b"abd").is_err());
+    }
+
     // Mutex to serialize env var mutations across parallel tests
     static ENV_MUTEX: std::sync::LazyLock<std::sync::Mutex<()>> =
         std::sync::LazyLock::new(|| std::sync::Mutex::new(()));
@@ -0,0 +1,242 @@
+//! State scoped to one main window.
+//!
+//! Several main windows can be open at once, so two databases can be
+//! compared side by side. The first window is labelled `main`; windows
+//! opened later are `main-2`, `main-3`, ...
+//!
+//! Each window has its own open connections, the connection it is focused
+//! on, and preferences that apply to it only. Connection pools are shared:
+//! a pool is closed when the last window holding it lets go. Queries are
+//! cancelled per window, so cancelling in one window leaves a query on the
+//! same connection in another window running.
+
+use std::collections::{BTreeSet, HashMap};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+use serde::Serialize;
+use serde_json::{Map, Value};
+use tauri::{AppHandle, Manager, Runtime, State, WebviewUrl, WebviewWindow, WebviewWindowBuilder};
+
+use crate::commands::QueryCancellationState;
+
+pub const MAIN_WINDOW: &str = "main";
+
+/// Cancellation scope of tasks started without a window, such as requests
+/// from the automation socket.
+pub const BACKGROUND_TASKS: &str = "background";
+
+static NEXT_WINDOW: AtomicUsize = AtomicUsize::new(2);
+
+/// What one main window is working on.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WindowContext {
+    pub active_connection_id: Option<String>,
+    pub open_connections: BTreeSet<String>,
+    pub preferences: Map<String, Value>,
+}
+
+/// Contexts of the open main windows, keyed by window label.
+#[derive(Default)]
+pub struct WindowRegistry {
+    windows: Mutex<HashMap<String, WindowContext>>,
+}
+
+// ---------------------------------------------------------------------------
+// Registry (testable; pure)
+// ---------------------------------------------------------------------------
+
+impl WindowRegistry {
+    pub fn context(&self, label: &str) -> WindowContext {
+        self.windows
+            .lock()
+            .unwrap()
+            .get(label)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Record that `label` opened `connection_id`. The first connection a
+    /// window opens becomes its active one.
+    pub fn open_connection(&self, label: &str, connection_id: &str) {
+        let mut windows = self.windows.lock().unwrap();
+        let context = windows.entry(label.to_string()).or_default();
+        context.open_connections.insert(connection_id.to_string());
+        if context.active_connection_id.is_none() {
+            context.active_connection_id = Some(connection_id.to_string());
+        }
+    }
+
+    pub fn set_active_connection(&self, label: &str, connection_id: Option<String>) {
+        let mut windows = self.windows.lock().unwrap();
+        let context = windows.entry(label.to_string()).or_default();
+        if let Some(id) = &connection_id {
+            context.open_connections.insert(id.clone());
+        }
+        context.active_connection_id = connection_id;
+    }
+
+    /// Let go of `connection_id` in `label`. Returns whether no window
+    /// holds it any more, i.e. whether its pool can be closed.
+    pub fn release_connection(&self, label: &str, connection_id: &str) -> bool {
+        let mut windows = self.windows.lock().unwrap();
+        if let Some(context) = windows.get_mut(label) {
+            context.open_connections.remove(connection_id);
+            if context.active_connection_id.as_deref() == Some(connection_id) {
+                context.active_connection_id = context.open_connections.iter().next().cloned();
+            }
+        }
+        !windows
+            .values()
+            .any(|context| context.open_connections.contains(connection_id))
+    }
+
+    /// Set a preference for `label` only. `null` removes it.
+    pub fn set_preference(&self, label: &str, key: &str, value: Value) {
+        let mut windows = self.windows.lock().unwrap();
+        let preferences = &mut windows.entry(label.to_string()).or_default().preferences;
+        if value.is_null() {
+            preferences.remove(key);
+        } else {
+            preferences.insert(key.to_string(), value);
+        }
+    }
+
+    /// Forget a closed window. Returns its context and the connections no
+    /// other window holds.
+    pub fn remove_window(&self, label: &str) -> (WindowContext, Vec<String>) {
+        let mut windows = self.windows.lock().unwrap();
+        let context = windows.remove(label).unwrap_or_default();
+        let orphaned = context
+            .open_connections
+            .iter()
+            .filter(|id| {
+                !windows
+                    .values()
+                    .any(|other| other.open_connections.contains(*id))
+            })
+            .cloned()
+            .collect();
+        (context, orphaned)
+    }
+}
+
+/// Whether `label` is a main window rather than a tool window such as the
+/// task manager or Visual Explain.
+pub fn is_main_window(label: &str) -> bool {
+    label == MAIN_WINDOW
+        || label
+            .strip_prefix("main-")
+            .is_some_and(|n| n.parse::<usize>().is_ok())
+}
+
+/// The window that receives app-wide requests such as deep links: `main`,
+/// or the oldest main window still open once `main` was closed.
+pub fn primary_main_window<R: Runtime>(app: &AppHandle<R>) -> Option<WebviewWindow<R>> {
+    app.get_webview_window(MAIN_WINDOW).or_else(|| {
+        app.webview_windows()
+            .into_iter()
+            .filter_map(|(label, window)| {
+                let n = label.strip_prefix("main-")?.parse::<usize>().ok()?;
+                Some((n, window))
+            })
+            .min_by_key(|(n, _)| *n)
+            .map(|(_, window)| window)
+    })
+}
+
+/// Key under which queries on `connection_id` are registered for
+/// cancellation: `<scope>:<connection id>`, where the scope is the label of
+/// the window that started them or [`BACKGROUND_TASKS`].
+pub fn cancellation_key(scope: &str, connection_id: &str) -> String {
+    format!("{}:{}", scope, connection_id)
+}
+
+// ---------------------------------------------------------------------------
+// Window lifecycle
+// ---------------------------------------------------------------------------
+
+/// Clean up after a main window was closed: cancel its queries and close
+/// the pools of connections no other window holds.
+pub fn on_window_destroyed<R: Runtime>(app: &AppHandle<R>, label: &str) {
+    if !is_main_window(label) {
+        return;
+    }
+    let Some(registry) = app.try_state::<WindowRegistry>() else {
+        return;
+    };
+    let (context, orphaned) = registry.remove_window(label);
+
+    if let Some(state) = app.try_state::<QueryCancellationState>() {
+        let keys: Vec<String> = context
+            .open_connections
+            .iter()
+            .map(|id| cancellation_key(label, id))
+            .collect();
+        let mut handles = state.handles.lock().unwrap();
+        for handle in keys.iter().filter_map(|key| handles.remove(key)).flatten() {
+            handle.abort();
+        }
+    }
+
+    if orphaned.is_empty() {
+        return;
+    }
+    log::info!(
+        "Window {} closed, closing {} connection(s)",
+        label,
+        orphaned.len()
+    );
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        for connection_id in orphaned {
+            if let Err(e) = crate::commands::close_connection(&app, &connection_id).await {
+                log::warn!("Failed to close connection {}: {}", connection_id, e);
+            }
+        }
+    });
+}
+
+// ---------------------------------------------------------------------------
+// Tauri commands
+// ---------------------------------------------------------------------------
+
+/// Open another main window with no connections. Returns its label.
+#[tauri::command]
+pub async fn open_main_window<R: Runtime>(app: AppHandle<R>) -> Result<String, String> {
+    let label = format!("main-{}", NEXT_WINDOW.fetch_add(1, Ordering::Relaxed));
+    WebviewWindowBuilder::new(&app, &label, WebviewUrl::App("/".into()))
+        .title("tabularis")
+        .inner_size(1280.0, 800.0)
+        .build()
+        .map_err(|e| format!("Failed to create window: {}", e))?;
+    Ok(label)
+}
+
+#[tauri::command]
+pub fn get_window_context<R: Runtime>(
+    window: WebviewWindow<R>,
+    registry: State<'_, WindowRegistry>,
+) -> WindowContext {
+    registry.context(window.label())
+}
+
+#[tauri::command]
+pub fn set_window_active_connection<R: Runtime>(
+    window: WebviewWindow<R>,
+    registry: State<'_, WindowRegistry>,
+    connection_id: Option<String>,
+) {
+    registry.set_active_connection(window.label(), connection_id);
+}
+
+#[tauri::command]
+pub fn set_window_preference<R: Runtime>(
+    window: WebviewWindow<R>,
+    registry: State<'_, WindowRegistry>,
+    key: String,
+    value: Value,
+) {
+    registry.set_preference(window.label(), &key, value);
+}
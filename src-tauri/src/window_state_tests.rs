@@ -0,0 +1,72 @@
+#[cfg(test)]
+mod tests {
+    use crate::window_state::{cancellation_key, is_main_window, WindowRegistry, BACKGROUND_TASKS};
+    use serde_json::json;
+
+    #[test]
+    fn first_opened_connection_becomes_active() {
+        let registry = WindowRegistry::default();
+        registry.open_connection("main", "staging");
+        registry.open_connection("main", "production");
+
+        let context = registry.context("main");
+        assert_eq!(context.active_connection_id.as_deref(), Some("staging"));
+        assert_eq!(context.open_connections.len(), 2);
+
+        registry.set_active_connection("main", Some("production".to_string()));
+        assert_eq!(
+            registry.context("main").active_connection_id.as_deref(),
+            Some("production")
+        );
+    }
+
+    #[test]
+    fn pools_are_released_by_the_last_window() {
+        let registry = WindowRegistry::default();
+        registry.open_connection("main", "staging");
+        registry.open_connection("main-2", "staging");
+
+        assert!(!registry.release_connection("main", "staging"));
+        assert!(registry.context("main").active_connection_id.is_none());
+        assert!(registry.release_connection("main-2", "staging"));
+    }
+
+    #[test]
+    fn closing_a_window_orphans_only_its_own_connections() {
+        let registry = WindowRegistry::default();
+        registry.open_connection("main", "staging");
+        registry.open_connection("main-2", "staging");
+        registry.open_connection("main-2", "production");
+
+        let (context, orphaned) = registry.remove_window("main-2");
+        assert_eq!(context.open_connections.len(), 2);
+        assert_eq!(orphaned, ["production"]);
+        assert!(registry.context("main-2").open_connections.is_empty());
+    }
+
+    #[test]
+    fn preferences_are_per_window() {
+        let registry = WindowRegistry::default();
+        registry.set_preference("main-2", "theme", json!("light"));
+        assert_eq!(registry.context("main-2").preferences["theme"], "light");
+        assert!(registry.context("main").preferences.is_empty());
+
+        registry.set_preference("main-2", "theme", json!(null));
+        assert!(registry.context("main-2").preferences.is_empty());
+    }
+
+    #[test]
+    fn cancellation_keys_are_scoped_to_windows_and_background_tasks() {
+        assert_eq!(cancellation_key("main", "prod"), "main:prod");
+        assert_eq!(cancellation_key("main-3", "prod"), "main-3:prod");
+        assert_eq!(
+            cancellation_key(BACKGROUND_TASKS, "prod"),
+            "background:prod"
+        );
+
+        assert!(is_main_window("main"));
+        assert!(is_main_window("main-12"));
+        assert!(!is_main_window("main-window"));
+        assert!(!is_main_window("task-manager"));
+    }
+}
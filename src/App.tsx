@@ -16,6 +16,7 @@ import { TaskManagerPage } from "./pages/TaskManagerPage";
 import { VisualExplainPage } from "./pages/VisualExplainPage";
 import { JsonViewerPage } from "./pages/JsonViewerPage";
 import { ConnectionHealthMonitor } from "./components/ConnectionHealthMonitor";
+import { DeepLinkHandler } from "./components/DeepLinkHandler";
 import { EditorErrorBoundary } from "./components/ui/EditorErrorBoundary";
 import { UpdateNotificationModal } from "./components/modals/UpdateNotificationModal";
 import { CommunityModal } from "./components/modals/CommunityModal";
@@ -107,6 +108,7 @@ export function App() {
       <AlertProvider>
         <BrowserRouter>
           <ConnectionHealthMonitor />
+          <DeepLinkHandler />
           <KeybindingsProvider>
             <PluginSlotProvider>
               <PluginModalProvider>
@@ -0,0 +1,81 @@
+import { useEffect, useRef } from "react";
+import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
+import { useTranslation } from "react-i18next";
+import { useNavigate } from "react-router-dom";
+import { useAlert } from "../hooks/useAlert";
+import { useDatabase } from "../hooks/useDatabase";
+
+export type DeepLinkAction =
+  | { action: "connect"; connectionId: string }
+  | {
+      action: "openQuery";
+      connectionId: string;
+      name: string;
+      sql: string;
+      database: string | null;
+    };
+
+/**
+ * Headless component that opens the tabularis:// links queued by the backend.
+ * Saved query links open in a new editor tab without running.
+ * Must be rendered inside AlertProvider and BrowserRouter.
+ */
+export function DeepLinkHandler() {
+  const { connect, isConnectionOpen, switchConnection } = useDatabase();
+  const { showAlert } = useAlert();
+  const { t } = useTranslation();
+  const navigate = useNavigate();
+
+  const open = async (link: DeepLinkAction) => {
+    try {
+      if (isConnectionOpen(link.connectionId)) {
+        switchConnection(link.connectionId);
+      } else {
+        await connect(link.connectionId);
+      }
+      if (link.action === "openQuery") {
+        navigate("/editor", {
+          state: {
+            initialQuery: link.sql,
+            queryName: link.name,
+            preventAutoRun: true,
+            schema: link.database ?? undefined,
+            targetConnectionId: link.connectionId,
+          },
+        });
+      } else {
+        navigate("/editor");
+      }
+    } catch (e) {
+      showAlert(`${t("deepLink.openFailed")}: ${String(e)}`, {
+        kind: "error",
+        title: t("deepLink.title"),
+      });
+    }
+  };
+
+  // The listener is registered once; it always calls the latest handler.
+  const openRef = useRef(open);
+  openRef.current = open;
+
+  useEffect(() => {
+    const drain = async () => {
+      const links = await invoke<DeepLinkAction[]>("take_pending_deep_links");
+      for (const link of links) {
+        await openRef.current(link);
+      }
+    };
+
+    // Links that launched the app were queued before this window loaded.
+    drain().catch(console.error);
+    const unlisten = listen("deep-link", () => {
+      drain().catch(console.error);
+    });
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, []);
+
+  return null;
+}
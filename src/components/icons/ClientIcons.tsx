@@ -29,6 +29,19 @@ export const MiniMaxIcon = ({ size = 20, className }: IconProps) => (
   </svg>
 );
 
+export const GeminiIcon = ({ size = 20, className }: IconProps) => (
+  <svg
+    viewBox="0 0 24 24"
+    width={size}
+    height={size}
+    className={className}
+    fill="currentColor"
+    xmlns="http://www.w3.org/2000/svg"
+  >
+    <path d="M12 24A14.3 14.3 0 0 0 0 12 14.3 14.3 0 0 0 12 0a14.3 14.3 0 0 0 12 12 14.3 14.3 0 0 0-12 12Z" />
+  </svg>
+);
+
 export const OpenRouterIcon = ({ size = 20, className }: IconProps) => (
   <svg
     viewBox="-45 32.23 556.5 433.48"
@@ -16,6 +16,17 @@ interface TableColumn {
   data_type: string;
 }
 
+interface SchemaEmbeddingsInfo {
+  provider: string;
+  model: string;
+  schema: string | null;
+  builtAt: string;
+  tableCount: number;
+}
+
+// Providers that can embed the schema for relevant-table lookup
+const EMBEDDING_PROVIDERS = ["openai", "ollama"];
+
 export const AiQueryModal = ({ isOpen, onClose, onInsert }: AiQueryModalProps) => {
   const { activeConnectionId, tables, activeSchema } = useDatabase();
   const { settings } = useSettings();
@@ -25,6 +36,8 @@ export const AiQueryModal = ({ isOpen, onClose, onInsert }: AiQueryModalProps) =
   const [error, setError] = useState<string | null>(null);
   const [schemaContext, setSchemaContext] = useState<string>("");
   const [isSchemaLoading, setIsSchemaLoading] = useState(false);
+  const [indexInfo, setIndexInfo] = useState<SchemaEmbeddingsInfo | null>(null);
+  const [isIndexing, setIsIndexing] = useState(false);
 
   const loadSchema = useCallback(async () => {
     setIsSchemaLoading(true);
@@ -66,6 +79,33 @@ export const AiQueryModal = ({ isOpen, onClose, onInsert }: AiQueryModalProps) =
     }
   }, [isOpen, activeConnectionId, tables, loadSchema]);
 
+  useEffect(() => {
+    if (!isOpen || !activeConnectionId) return;
+    invoke<SchemaEmbeddingsInfo | null>("get_schema_embeddings_info", {
+      connectionId: activeConnectionId,
+    })
+      .then(setIndexInfo)
+      .catch(() => setIndexInfo(null));
+  }, [isOpen, activeConnectionId]);
+
+  const handleBuildIndex = async () => {
+    if (!activeConnectionId || !settings.aiProvider) return;
+    setIsIndexing(true);
+    setError(null);
+    try {
+      const info = await invoke<SchemaEmbeddingsInfo>("build_schema_embeddings", {
+        connectionId: activeConnectionId,
+        provider: settings.aiProvider,
+        ...(activeSchema ? { schema: activeSchema } : {}),
+      });
+      setIndexInfo(info);
+    } catch (err) {
+      setError(String(err));
+    } finally {
+      setIsIndexing(false);
+    }
+  };
+
   const handleGenerate = async () => {
     if (!prompt.trim() || !settings.aiProvider) {
         setError("Please configure AI provider in Settings and enter a prompt.");
@@ -81,7 +121,8 @@ export const AiQueryModal = ({ isOpen, onClose, onInsert }: AiQueryModalProps) =
           provider: settings.aiProvider,
           model: settings.aiModel || "", // Default fallback handled by backend (first model in list)
           prompt,
-          schema: schemaContext
+          schema: schemaContext,
+          connection_id: activeConnectionId,
         }
       });
       onInsert(sql);
@@ -140,6 +181,27 @@ export const AiQueryModal = ({ isOpen, onClose, onInsert }: AiQueryModalProps) =
             </div>
           )}
 
+          {(indexInfo ||
+            EMBEDDING_PROVIDERS.includes(settings.aiProvider ?? "")) && (
+            <div className="flex items-center justify-between gap-2 text-xs text-muted">
+              <span>
+                {indexInfo
+                  ? `Schema index: ${indexInfo.tableCount} tables, built ${new Date(indexInfo.builtAt).toLocaleString()}`
+                  : "Index the schema so large databases send only relevant tables"}
+              </span>
+              {EMBEDDING_PROVIDERS.includes(settings.aiProvider ?? "") && (
+                <button
+                  onClick={handleBuildIndex}
+                  disabled={isIndexing}
+                  className="flex items-center gap-1 text-blue-400 hover:text-blue-300 disabled:opacity-50"
+                >
+                  {isIndexing && <Loader2 size={12} className="animate-spin" />}
+                  {indexInfo ? "Rebuild index" : "Build index"}
+                </button>
+              )}
+            </div>
+          )}
+
           {isSchemaLoading && (
             <div className="flex items-center gap-2 text-xs text-muted">
                 <Loader2 size={12} className="animate-spin" />
@@ -1,4 +1,4 @@
-import { useState, useEffect, useCallback } from "react";
+import { useState, useEffect, useCallback, useRef } from "react";
 import { useTranslation } from "react-i18next";
 import { invoke } from "@tauri-apps/api/core";
 import { listen } from "@tauri-apps/api/event";
@@ -13,6 +13,15 @@ interface ImportProgress {
   total_statements: number;
   percentage: number;
   current_operation: string;
+  current_table: string | null;
+}
+
+interface ImportFailure {
+  statement_number: number;
+  line: number;
+  table: string | null;
+  error: string;
+  statement: string;
 }
 
 interface ImportDatabaseModalProps {
@@ -38,13 +47,20 @@ export const ImportDatabaseModal = ({
   const [isImporting, setIsImporting] = useState(false);
   const [progress, setProgress] = useState<ImportProgress | null>(null);
   const [error, setError] = useState<string | null>(null);
+  const [failure, setFailure] = useState<ImportFailure | null>(null);
   const [success, setSuccess] = useState(false);
+  // The backend error text is localized, so cancellation is tracked here.
+  const [cancelled, setCancelled] = useState(false);
+  const cancelledRef = useRef(false);
   const [elapsedTime, setElapsedTime] = useState(0); // in seconds
   const [startTime, setStartTime] = useState<number | null>(null);
 
   const startImport = useCallback(async () => {
     setIsImporting(true);
     setError(null);
+    setCancelled(false);
+    cancelledRef.current = false;
+    setFailure(null);
     setSuccess(false);
     setStartTime(Date.now());
     setElapsedTime(0);
@@ -72,7 +88,7 @@ export const ImportDatabaseModal = ({
       setError(errorMsg);
       setIsImporting(false);
 
-      if (!errorMsg.includes("cancelled")) {
+      if (!cancelledRef.current) {
         showAlert(t("dump.importFailure") + ": " + errorMsg, {
           kind: "error",
         });
@@ -87,6 +103,7 @@ export const ImportDatabaseModal = ({
       setIsImporting(false);
       setProgress(null);
       setError(null);
+      setFailure(null);
       setSuccess(false);
       setElapsedTime(0);
       setStartTime(null);
@@ -100,9 +117,13 @@ export const ImportDatabaseModal = ({
     const unlisten = listen<ImportProgress>("import_progress", (event) => {
       setProgress(event.payload);
     });
+    const unlistenFailure = listen<ImportFailure>("import_failed", (event) => {
+      setFailure(event.payload);
+    });
 
     return () => {
       unlisten.then((fn) => fn());
+      unlistenFailure.then((fn) => fn());
     };
   }, [isOpen, startImport]);
 
@@ -125,6 +146,8 @@ export const ImportDatabaseModal = ({
     }
 
     try {
+      cancelledRef.current = true;
+      setCancelled(true);
       await invoke("cancel_import", { connectionId });
       showAlert(t("dump.importCancelled"), { kind: "info" });
       onClose();
@@ -176,8 +199,8 @@ export const ImportDatabaseModal = ({
           {/* Progress Information */}
           {progress && (
             <div className="space-y-3">
-              {/* Progress Bar - show determinate or indeterminate based on total */}
-              {progress.total_statements > 0 && progress.percentage > 0 ? (
+              {/* Progress Bar - determinate once the share of the file read is known */}
+              {progress.percentage > 0 ? (
                 <>
                   <div className="w-full bg-surface-secondary rounded-full h-3 overflow-hidden border border-default">
                     <div
@@ -189,10 +212,12 @@ export const ImportDatabaseModal = ({
                   {/* Stats */}
                   <div className="flex justify-between text-sm text-muted">
                     <span>
-                      {t("dump.statementsExecuted", {
-                        count: progress.statements_executed,
-                        total: progress.total_statements,
-                      })}
+                      {progress.total_statements > 0
+                        ? t("dump.statementsExecuted", {
+                            count: progress.statements_executed,
+                            total: progress.total_statements,
+                          })
+                        : progress.statements_executed.toLocaleString()}
                     </span>
                     <span className="font-semibold text-primary">
                       {progress.percentage.toFixed(1)}%
@@ -240,12 +265,30 @@ export const ImportDatabaseModal = ({
           {/* Error Message */}
           {error && !isImporting && (
             <div className="text-center text-red-500 text-sm">
-              {error.includes("cancelled")
+              {cancelled
                 ? t("dump.importCancelled")
                 : t("dump.importFailed")}
             </div>
           )}
 
+          {/* Failure Summary */}
+          {failure && !isImporting && (
+            <div className="text-xs bg-surface-secondary border border-default rounded p-3 space-y-1">
+              <div className="font-medium text-primary">
+                {t("dump.failedAtLine", {
+                  statement: failure.statement_number,
+                  line: failure.line,
+                })}
+                {failure.table &&
+                  ` · ${t("dump.failedInTable", { table: failure.table })}`}
+              </div>
+              <div className="text-red-500 break-words">{failure.error}</div>
+              <pre className="font-mono text-muted whitespace-pre-wrap break-all max-h-32 overflow-auto">
+                {failure.statement}
+              </pre>
+            </div>
+          )}
+
           {/* File Info */}
           <div className="text-xs text-muted text-center mt-2">
             {t("dump.importingFrom")}: {filePath.split(/[/\\]/).pop()}
@@ -24,6 +24,7 @@ import {
   OpenAIIcon,
   AnthropicIcon,
   MiniMaxIcon,
+  GeminiIcon,
   OpenRouterIcon,
   OllamaIcon,
 } from "../icons/ClientIcons";
@@ -53,6 +54,11 @@ const PROVIDERS: Array<{
     label: "MiniMax",
     icon: <MiniMaxIcon size={18} className="text-[#6c6cff]" />,
   },
+  {
+    id: "gemini",
+    label: "Gemini",
+    icon: <GeminiIcon size={18} className="text-[#4285f4]" />,
+  },
   {
     id: "openrouter",
     label: "OpenRouter",
@@ -129,6 +135,9 @@ export function AiTab() {
       const openrouter = await invoke<AiKeyStatus>("check_ai_key_status", {
         provider: "openrouter",
       });
+      const gemini = await invoke<AiKeyStatus>("check_ai_key_status", {
+        provider: "gemini",
+      });
       const customOpenai = await invoke<AiKeyStatus>("check_ai_key_status", {
         provider: "custom-openai",
       });
@@ -137,6 +146,7 @@ export function AiTab() {
         openai,
         anthropic,
         openrouter,
+        gemini,
         "custom-openai": customOpenai,
         ollama,
       });
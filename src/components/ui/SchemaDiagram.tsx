@@ -14,7 +14,6 @@ import {
 } from "@xyflow/react";
 import "@xyflow/react/dist/style.css";
 import dagre from "dagre";
-import { useEditor } from "../../hooks/useEditor";
 import { SchemaTableNodeComponent } from "./SchemaTableNode";
 import { Loader2, ArrowLeftRight, ArrowUpDown, Maximize2, Focus } from "lucide-react";
 import { useTranslation } from "react-i18next";
@@ -22,6 +21,8 @@ import { ContextMenu } from "./ContextMenu";
 import { useSearchParams } from "react-router-dom";
 import { useSettings } from "../../hooks/useSettings";
 import { DEFAULT_SETTINGS } from "../../contexts/SettingsContext";
+import { streamSchemaSnapshot } from "../../utils/schemaDiagram";
+import type { TableSchema } from "../../types/editor";
 
 const nodeTypes = {
   schemaTable: SchemaTableNodeComponent,
@@ -88,12 +89,13 @@ const SchemaDiagramContent = ({
   schema,
 }: SchemaDiagramContentProps) => {
   const { t } = useTranslation();
-  const { getSchema } = useEditor();
   const { settings } = useSettings();
   const [nodes, setNodes, onNodesChange] = useNodesState<Node>([]);
   const [edges, setEdges, onEdgesChange] = useEdgesState<Edge>([]);
   const { fitView, zoomIn, zoomOut } = useReactFlow();
   const [loading, setLoading] = useState(false);
+  const [progress, setProgress] = useState<{ loaded: number; total: number } | null>(null);
+  const [snapshot, setSnapshot] = useState<TableSchema[] | null>(null);
   
   // Use settings value directly, with fallback to default
   const layoutDirectionFromSettings = (settings.erDiagramDefaultLayout ?? DEFAULT_SETTINGS.erDiagramDefaultLayout) as "LR" | "TB";
@@ -112,6 +114,10 @@ const SchemaDiagramContent = ({
     tableId: string;
   } | null>(null);
   const [searchParams] = useSearchParams();
+  // Tables the window was opened on; empty means the whole schema
+  const tableSubset = useMemo(() => searchParams.getAll("table"), [searchParams]);
+  // How many foreign keys away from the subset to include
+  const subsetDepth = Number(searchParams.get("depth")) || 0;
 
   // Callback per gestire il click su una tabella
   const handleTableClick = useCallback((tableId: string) => {
@@ -182,70 +188,19 @@ const SchemaDiagramContent = ({
     const loadSchema = async () => {
       if (!connectionId) return;
       setLoading(true);
+      setProgress(null);
 
       try {
-        const fetchedSchema = await getSchema(connectionId, undefined, schema);
-        if (!isMounted) return;
-
-        // Build nodes and edges with optimizations
-        const initialNodes: Node[] = [];
-        const initialEdges: Edge[] = [];
-        const tableSet = new Set(fetchedSchema.map((t) => t.name));
-
-        fetchedSchema.forEach((table) => {
-          // Build FK lookup Set ONCE per table for O(1) lookups
-          const fkColumnNames = new Set(
-            table.foreign_keys.map((fk) => fk.column_name),
-          );
-
-          initialNodes.push({
-            id: table.name,
-            type: "schemaTable",
-            position: { x: 0, y: 0 },
-            data: {
-              label: table.name,
-              columns: table.columns.map((c) => ({
-                name: c.name,
-                type: c.data_type,
-                isPk: c.is_pk,
-                isFk: fkColumnNames.has(c.name), // O(1) lookup
-              })),
-            },
-          });
-
-          table.foreign_keys.forEach((fk) => {
-            if (tableSet.has(fk.ref_table)) {
-              initialEdges.push({
-                id: `e-${table.name}-${fk.column_name}-${fk.ref_table}-${fk.ref_column}`,
-                source: table.name,
-                target: fk.ref_table,
-                sourceHandle: fk.column_name,
-                targetHandle: fk.ref_column,
-                animated: initialEdges.length < ANIMATION_THRESHOLD, // Conditional animation
-                style: { stroke: "#6366f1", strokeWidth: 1.5 },
-                type: "smoothstep",
-              });
-            }
-          });
-        });
-
-        // Calculate layout
-        const { nodes: layoutedNodes, edges: layoutedEdges } =
-          getLayoutedElements(initialNodes, initialEdges, layoutDirection);
-
-        if (isMounted) {
-          setAllNodes(layoutedNodes);
-          setAllEdges(layoutedEdges);
-          setNodes(layoutedNodes);
-          setEdges(layoutedEdges);
-
-          // Fit view after rendering
-          setTimeout(() => {
-            if (isMounted) {
-              fitView({ padding: 0.2 });
-            }
-          }, 100);
-        }
+        const fetchedSchema = await streamSchemaSnapshot(
+          connectionId,
+          schema,
+          tableSubset,
+          subsetDepth,
+          (loaded, total) => {
+            if (isMounted) setProgress({ loaded, total });
+          },
+        );
+        if (isMounted) setSnapshot(fetchedSchema);
       } catch (e) {
         console.error("Failed to load schema diagram", e);
       } finally {
@@ -260,16 +215,67 @@ const SchemaDiagramContent = ({
     return () => {
       isMounted = false;
     };
-  }, [
-    connectionId,
-    refreshTrigger,
-    getSchema,
-    fitView,
-    setNodes,
-    setEdges,
-    layoutDirection,
-    schema,
-  ]);
+  }, [connectionId, refreshTrigger, tableSubset, subsetDepth, schema]);
+
+  // Build and lay out nodes and edges once the snapshot is loaded
+  useEffect(() => {
+    if (!snapshot) return;
+
+    // Build nodes and edges with optimizations
+    const initialNodes: Node[] = [];
+    const initialEdges: Edge[] = [];
+    const tableSet = new Set(snapshot.map((t) => t.name));
+
+    snapshot.forEach((table) => {
+      // Build FK lookup Set ONCE per table for O(1) lookups
+      const fkColumnNames = new Set(
+        table.foreign_keys.map((fk) => fk.column_name),
+      );
+
+      initialNodes.push({
+        id: table.name,
+        type: "schemaTable",
+        position: { x: 0, y: 0 },
+        data: {
+          label: table.name,
+          columns: table.columns.map((c) => ({
+            name: c.name,
+            type: c.data_type,
+            isPk: c.is_pk,
+            isFk: fkColumnNames.has(c.name), // O(1) lookup
+          })),
+        },
+      });
+
+      table.foreign_keys.forEach((fk) => {
+        if (tableSet.has(fk.ref_table)) {
+          initialEdges.push({
+            id: `e-${table.name}-${fk.column_name}-${fk.ref_table}-${fk.ref_column}`,
+            source: table.name,
+            target: fk.ref_table,
+            sourceHandle: fk.column_name,
+            targetHandle: fk.ref_column,
+            animated: initialEdges.length < ANIMATION_THRESHOLD, // Conditional animation
+            style: { stroke: "#6366f1", strokeWidth: 1.5 },
+            type: "smoothstep",
+          });
+        }
+      });
+    });
+
+    // Calculate layout
+    const { nodes: layoutedNodes, edges: layoutedEdges } =
+      getLayoutedElements(initialNodes, initialEdges, layoutDirection);
+
+    setAllNodes(layoutedNodes);
+    setAllEdges(layoutedEdges);
+    setNodes(layoutedNodes);
+    setEdges(layoutedEdges);
+
+    // Fit view after rendering
+    const timer = setTimeout(() => fitView({ padding: 0.2 }), 100);
+    return () => clearTimeout(timer);
+  }, [snapshot, layoutDirection, fitView, setNodes, setEdges]);
 
   // Effetto per filtrare i nodi quando una tabella è selezionata
   useEffect(() => {
@@ -327,7 +333,11 @@ const SchemaDiagramContent = ({
         <div className="absolute inset-0 z-50 flex items-center justify-center bg-base/80 backdrop-blur-sm">
           <div className="flex flex-col items-center gap-3 text-secondary">
             <Loader2 size={32} className="animate-spin text-indigo-500" />
-            <span>Generating Diagram...</span>
+            <span>
+              {progress && progress.loaded < progress.total
+                ? t("erDiagram.loadingTables", progress)
+                : "Generating Diagram..."}
+            </span>
           </div>
         </div>
       )}
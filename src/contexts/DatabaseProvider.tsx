@@ -110,6 +110,18 @@ export const DatabaseProvider = ({ children }: { children: ReactNode }) => {
     updateTitle();
   }, [activeConnectionName, activeDatabaseName, activeSchema, activeCapabilities, selectedDatabases]);
 
+  // Each window has its own active connection in the backend.
+  useEffect(() => {
+    const syncActiveConnection = async () => {
+      try {
+        await invoke('set_window_active_connection', { connectionId: activeConnectionId });
+      } catch (e) {
+        console.error('Failed to update window active connection', e);
+      }
+    };
+    syncActiveConnection();
+  }, [activeConnectionId]);
+
   const updateConnectionData = useCallback((connectionId: string, updates: Partial<ConnectionData>) => {
     setConnectionDataMap(prev => ({
       ...prev,
@@ -4,6 +4,7 @@ import type { Tab, SchemaCache, TableSchema } from "../types/editor";
 import { EditorContext } from "./EditorContext";
 import { useDatabase } from "../hooks/useDatabase";
 import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
 import {
   generateTabId,
   loadEditorPreferences,
@@ -125,7 +126,14 @@ export const EditorProvider = ({ children }: { children: ReactNode }) => {
       flushAllPendingSaves();
     };
     window.addEventListener("beforeunload", handleBeforeUnload);
-    return () => window.removeEventListener("beforeunload", handleBeforeUnload);
+    // The backend announces an app exit before closing pools and plugins
+    const unlistenShutdown = listen("app-shutting-down", () => {
+      flushAllPendingSaves();
+    });
+    return () => {
+      window.removeEventListener("beforeunload", handleBeforeUnload);
+      unlistenShutdown.then((fn) => fn());
+    };
   }, []);
 
   // Save tabs to file storage when they change
@@ -84,6 +84,12 @@ export const SettingsProvider = ({ children }: { children: ReactNode }) => {
                 provider: "minimax",
               });
               if (hasMiniMax) detectedProvider = "minimax";
+              else {
+                const hasGemini = await invoke<boolean>("check_ai_key", {
+                  provider: "gemini",
+                });
+                if (hasGemini) detectedProvider = "gemini";
+              }
             }
             }
           }
@@ -97,7 +103,7 @@ export const SettingsProvider = ({ children }: { children: ReactNode }) => {
 
             // Only set provider if not already set
             if (!finalSettings.aiProvider) {
-              finalSettings.aiProvider = detectedProvider as "openai" | "anthropic" | "openrouter" | "minimax";
+              finalSettings.aiProvider = detectedProvider as "openai" | "anthropic" | "openrouter" | "minimax" | "gemini";
             }
             // Only set model if not already set AND we have a model available
             if (!finalSettings.aiModel && firstModel) {
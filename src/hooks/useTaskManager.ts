@@ -1,6 +1,10 @@
 import { useCallback, useEffect, useRef, useState } from "react";
 import { invoke } from "@tauri-apps/api/core";
-import type { ProcessInfo, SystemStats } from "../utils/taskManager";
+import type {
+  ProcessInfo,
+  RunningQuery,
+  SystemStats,
+} from "../utils/taskManager";
 import { buildProcessRows } from "../utils/taskManager";
 
 const POLL_INTERVAL_MS = 2000;
@@ -8,32 +12,39 @@ const POLL_INTERVAL_MS = 2000;
 interface UseTaskManagerResult {
   processes: ProcessInfo[];
   systemStats: SystemStats | null;
+  runningQueries: RunningQuery[];
   loading: boolean;
   error: string | null;
   killing: Set<string>;
   restarting: Set<string>;
+  cancelling: Set<number>;
   refresh: () => Promise<void>;
   killProcess: (pluginId: string) => Promise<void>;
   restartProcess: (pluginId: string) => Promise<void>;
+  cancelQuery: (id: number) => Promise<void>;
 }
 
 export function useTaskManager(): UseTaskManagerResult {
   const [processes, setProcesses] = useState<ProcessInfo[]>([]);
   const [systemStats, setSystemStats] = useState<SystemStats | null>(null);
+  const [runningQueries, setRunningQueries] = useState<RunningQuery[]>([]);
   const [loading, setLoading] = useState(true);
   const [error, setError] = useState<string | null>(null);
   const [killing, setKilling] = useState<Set<string>>(new Set());
   const [restarting, setRestarting] = useState<Set<string>>(new Set());
+  const [cancelling, setCancelling] = useState<Set<number>>(new Set());
   const intervalRef = useRef<ReturnType<typeof setInterval> | null>(null);
 
   const fetchData = useCallback(async () => {
     try {
-      const [procs, stats] = await Promise.all([
+      const [procs, stats, queries] = await Promise.all([
         invoke<ProcessInfo[]>("get_process_list"),
         invoke<SystemStats>("get_system_stats"),
+        invoke<RunningQuery[]>("get_running_queries"),
       ]);
       setProcesses(buildProcessRows(procs));
       setSystemStats(stats);
+      setRunningQueries(queries);
       setError(null);
     } catch (e) {
       setError(e instanceof Error ? e.message : String(e));
@@ -96,15 +107,37 @@ export function useTaskManager(): UseTaskManagerResult {
     [fetchData],
   );
 
+  const cancelQuery = useCallback(
+    async (id: number) => {
+      setCancelling((prev) => new Set(prev).add(id));
+      try {
+        await invoke("cancel_running_query", { id });
+        await fetchData();
+      } catch (e) {
+        setError(e instanceof Error ? e.message : String(e));
+      } finally {
+        setCancelling((prev) => {
+          const next = new Set(prev);
+          next.delete(id);
+          return next;
+        });
+      }
+    },
+    [fetchData],
+  );
+
   return {
     processes,
     systemStats,
+    runningQueries,
     loading,
     error,
     killing,
     restarting,
+    cancelling,
     refresh,
     killProcess,
     restartProcess,
+    cancelQuery,
   };
 }
@@ -1,7 +1,7 @@
 import { useState, useEffect, useRef, useCallback, useMemo } from "react";
 import { useLocation, useNavigate } from "react-router-dom";
 import { useTranslation } from "react-i18next";
-import { reconstructTableQuery } from "../utils/editor";
+import { reconstructTableQuery, resolvePageSize } from "../utils/editor";
 import { isMultiDatabaseCapable } from "../utils/database";
 import { isReadonly } from "../utils/driverCapabilities";
 import {
@@ -94,6 +94,7 @@ import { useEditor } from "../hooks/useEditor";
 import { useConnectionLayoutContext } from "../hooks/useConnectionLayoutContext";
 import { useKeybindings } from "../hooks/useKeybindings";
 import type {
+  BatchInsertResult,
   BatchStatementResult,
   QueryResult,
   Tab,
@@ -181,6 +182,11 @@ export const Editor = () => {
     message: string;
   }>({ isOpen: false, message: "" });
 
+  // Connections with a transaction opened from the toolbar
+  const [openTransactions, setOpenTransactions] = useState<Set<string>>(
+    new Set(),
+  );
+
   const [exportState, setExportState] = useState<{
     isOpen: boolean;
     status: ExportStatus;
@@ -672,12 +678,13 @@ export const Editor = () => {
 
       try {
         const start = performance.now();
-        // Use settings.resultPageSize for Page Size (pagination), ignoring the "Total Limit" input which is handled in SQL
-        // Fallback to 100 if settings not loaded yet
-        const pageSize =
-          settings.resultPageSize && settings.resultPageSize > 0
-            ? settings.resultPageSize
-            : 100;
+        // Use settings.resultPageSize for Page Size (pagination), ignoring the "Total Limit" input which is handled in SQL.
+        // Later pages keep the size the backend adapted the first page to.
+        const pageSize = resolvePageSize(
+          settings.resultPageSize,
+          pageNum,
+          targetTab?.result?.pagination,
+        );
         const res = await invoke<QueryResult>("execute_query", {
           connectionId: activeConnectionId,
           query: textToRun,
@@ -930,10 +937,11 @@ export const Editor = () => {
       const entry = currentTab?.results?.find((r) => r.id === entryId);
       if (!entry) return;
 
-      const pageSize =
-        settings.resultPageSize && settings.resultPageSize > 0
-          ? settings.resultPageSize
-          : 100;
+      const pageSize = resolvePageSize(
+        settings.resultPageSize,
+        pageNum,
+        entry.result?.pagination,
+      );
       const schema = currentTab?.schema ?? activeSchema;
 
       // Mark this entry as loading
@@ -1211,6 +1219,38 @@ export const Editor = () => {
       runQuery(currentTab.query, currentTab.page);
   }, [activeConnectionId, runQuery]);
 
+  const inTransaction =
+    !!activeConnectionId && openTransactions.has(activeConnectionId);
+
+  const handleTransaction = useCallback(
+    async (action: "begin" | "commit" | "rollback") => {
+      if (!activeConnectionId) return;
+      const connectionId = activeConnectionId;
+      try {
+        await invoke(`${action}_transaction`, { connectionId });
+        setOpenTransactions((prev) => {
+          const next = new Set(prev);
+          if (action === "begin") next.add(connectionId);
+          else next.delete(connectionId);
+          return next;
+        });
+        // Show the committed or restored rows
+        if (action !== "begin") handleRefresh();
+      } catch (e) {
+        console.error(`Failed to ${action} transaction:`, e);
+        showAlert(String(e), { title: t("common.error"), kind: "error" });
+        // The backend drops the transaction even when COMMIT/ROLLBACK fails
+        if (action !== "begin")
+          setOpenTransactions((prev) => {
+            const next = new Set(prev);
+            next.delete(connectionId);
+            return next;
+          });
+      }
+    },
+    [activeConnectionId, handleRefresh, showAlert, t],
+  );
+
   const handleToolbarUpdate = useCallback(
     (filter: string, sort: string, limit: number | undefined) => {
       if (!activeTabIdRef.current) return;
@@ -1846,18 +1886,23 @@ export const Editor = () => {
         );
       }
 
-      // Insertions
+      // Insertions: one batch call; rows that fail stay pending
+      const failedInsertions = new Set<string>();
+      const insertErrors: string[] = [];
       if (insertions.length > 0) {
         promises.push(
-          ...insertions.map((insertion) =>
-            invoke("insert_record", {
-              connectionId: activeConnectionId,
-              table: activeTable,
-              data: insertion.data,
-              ...(activeSchema ? { schema: activeSchema } : {}),
-              ...databaseParam,
-            }),
-          ),
+          invoke<BatchInsertResult>("insert_records_batch", {
+            connectionId: activeConnectionId,
+            table: activeTable,
+            rows: insertions.map((insertion) => insertion.data),
+            ...(activeSchema ? { schema: activeSchema } : {}),
+            ...databaseParam,
+          }).then((result) => {
+            result.errors.forEach(({ row, error }) => {
+              failedInsertions.add(insertions[row].tempId);
+              insertErrors.push(error);
+            });
+          }),
         );
       }
 
@@ -1871,7 +1916,10 @@ export const Editor = () => {
       // Partial cleanup - remove only processed changes
       updates.forEach((u) => delete newPendingChanges[String(u.pkVal)]);
       deletions.forEach((d) => delete newPendingDeletions[String(d)]);
-      insertions.forEach((i) => delete newPendingInsertions[i.tempId]);
+      insertions.forEach((i) => {
+        if (!failedInsertions.has(i.tempId))
+          delete newPendingInsertions[i.tempId];
+      });
 
       // Cleanup empty change objects
       Object.keys(newPendingChanges).forEach((key) => {
@@ -1907,6 +1955,13 @@ export const Editor = () => {
           pendingInsertions: remainingInsertions,
         },
       );
+
+      if (insertErrors.length > 0) {
+        showAlert(t("dataGrid.updateFailed") + insertErrors[0], {
+          title: t("common.error"),
+          kind: "error",
+        });
+      }
     } catch (e) {
       console.error("Batch update failed", e);
       updateActiveTab({ isLoading: false });
@@ -2583,6 +2638,36 @@ export const Editor = () => {
           </button>
         )}
 
+        {/* Transaction Buttons */}
+        {!activeTab.readOnly &&
+          (inTransaction ? (
+            <div className="flex items-center gap-1">
+              <button
+                onClick={() => handleTransaction("commit")}
+                className="flex items-center gap-2 px-3 py-1.5 bg-green-700 hover:bg-green-600 text-white rounded text-sm font-medium"
+                title={t("editor.commitTransaction")}
+              >
+                <Check size={16} /> {t("editor.commitTransaction")}
+              </button>
+              <button
+                onClick={() => handleTransaction("rollback")}
+                className="flex items-center gap-2 px-3 py-1.5 bg-red-700 hover:bg-red-600 text-white rounded text-sm font-medium"
+                title={t("editor.rollbackTransaction")}
+              >
+                <Undo2 size={16} /> {t("editor.rollbackTransaction")}
+              </button>
+            </div>
+          ) : (
+            <button
+              onClick={() => handleTransaction("begin")}
+              disabled={!activeConnectionId}
+              className="flex items-center gap-2 px-3 py-1.5 bg-surface-secondary hover:bg-surface text-primary rounded text-sm font-medium disabled:opacity-50 disabled:cursor-not-allowed border border-strong"
+              title={t("editor.beginTransaction")}
+            >
+              {t("editor.beginTransaction")}
+            </button>
+          ))}
+
         <div className="relative ml-auto">
           <button
             onClick={() => setExportMenuOpen(!exportMenuOpen)}
@@ -20,6 +20,7 @@ import {
   TriangleAlert,
   ChevronRight,
   ChevronDown,
+  Timer,
 } from "lucide-react";
 import clsx from "clsx";
 import { useTaskManager } from "../hooks/useTaskManager";
@@ -30,7 +31,8 @@ import {
   getStatusBadgeColor,
   sortProcesses,
 } from "../utils/taskManager";
-import type { ProcessInfo, ProcessSortKey, TabularisSelfStats, ChildProcessInfo, TabularisChildProcess } from "../utils/taskManager";
+import { formatDuration } from "../utils/formatTime";
+import type { ProcessInfo, ProcessSortKey, RunningQuery, TabularisSelfStats, ChildProcessInfo, TabularisChildProcess } from "../utils/taskManager";
 
 
 // ---------------------------------------------------------------------------
@@ -417,6 +419,86 @@ const ProcessRow = ({
   );
 };
 
+// ---------------------------------------------------------------------------
+// Running queries
+// ---------------------------------------------------------------------------
+interface RunningQueriesPanelProps {
+  queries: RunningQuery[];
+  cancelling: Set<number>;
+  onCancel: (id: number) => void;
+}
+
+const RunningQueriesPanel = ({ queries, cancelling, onCancel }: RunningQueriesPanelProps) => {
+  const { t } = useTranslation();
+  const th = "px-4 py-3 text-left text-xs font-medium text-muted uppercase tracking-wide";
+
+  return (
+    <div className="bg-elevated border border-default rounded-xl overflow-hidden">
+      <div className="px-5 py-4 border-b border-default flex items-center gap-2">
+        <Timer size={15} className="text-blue-400" />
+        <h2 className="text-sm font-semibold text-primary">{t("taskManager.runningQueries.title")}</h2>
+        {queries.length > 0 && (
+          <span className="ml-auto text-xs text-muted bg-surface-secondary px-2 py-0.5 rounded-full">
+            {queries.length}
+          </span>
+        )}
+      </div>
+
+      {queries.length === 0 ? (
+        <p className="px-5 py-6 text-sm text-muted text-center">{t("taskManager.runningQueries.empty")}</p>
+      ) : (
+        <div className="overflow-x-auto">
+          <table className="w-full text-sm">
+            <thead>
+              <tr className="border-b border-default bg-base/50">
+                <th className={th}>{t("taskManager.runningQueries.colConnection")}</th>
+                <th className={th}>{t("taskManager.runningQueries.colQuery")}</th>
+                <th className={th}>{t("taskManager.runningQueries.colElapsed")}</th>
+                <th className={th}>{t("taskManager.runningQueries.colRows")}</th>
+                <th className={th} title={t("taskManager.runningQueries.fetchedTooltip")}>
+                  {t("taskManager.runningQueries.colFetched")}
+                </th>
+                <th className="px-4 py-3" />
+              </tr>
+            </thead>
+            <tbody className="divide-y divide-default">
+              {queries.map((q) => (
+                <tr key={q.id} className="hover:bg-surface-secondary/30 transition-colors">
+                  <td className="px-4 py-3 font-medium text-primary whitespace-nowrap">{q.connection_name}</td>
+                  <td className="px-4 py-3 max-w-[320px]">
+                    <code className="block truncate font-mono text-xs text-secondary" title={q.query}>
+                      {q.query}
+                    </code>
+                  </td>
+                  <td className="px-4 py-3 text-secondary whitespace-nowrap">{formatDuration(q.elapsed_ms)}</td>
+                  <td className="px-4 py-3 text-secondary">{q.rows.toLocaleString()}</td>
+                  <td className="px-4 py-3 text-secondary whitespace-nowrap">{formatBytes(q.bytes)}</td>
+                  <td className="px-4 py-3">
+                    <div className="flex justify-end">
+                      <button
+                        onClick={() => onCancel(q.id)}
+                        disabled={cancelling.has(q.id)}
+                        className="flex items-center gap-1 px-2.5 py-1 rounded-md text-xs bg-red-500/10 border border-red-500/20 text-red-400 hover:bg-red-500/20 transition-colors disabled:opacity-40 disabled:cursor-not-allowed"
+                      >
+                        {cancelling.has(q.id) ? (
+                          <Loader2 size={12} className="animate-spin" />
+                        ) : (
+                          <Square size={12} />
+                        )}
+                        {t("taskManager.runningQueries.cancel")}
+                      </button>
+                    </div>
+                  </td>
+                </tr>
+              ))}
+            </tbody>
+          </table>
+        </div>
+      )}
+    </div>
+  );
+};
+
 // ---------------------------------------------------------------------------
 // Main page
 // ---------------------------------------------------------------------------
@@ -425,13 +507,16 @@ export const TaskManagerPage = () => {
   const {
     processes,
     systemStats,
+    runningQueries,
     loading,
     error,
     killing,
     restarting,
+    cancelling,
     refresh,
     killProcess,
     restartProcess,
+    cancelQuery,
   } = useTaskManager();
 
   const [sortKey, setSortKey] = useState<ProcessSortKey>("plugin_name");
@@ -592,6 +677,12 @@ export const TaskManagerPage = () => {
             <TabularisSelfPanel stats={systemStats.tabularis} />
           )}
 
+          <RunningQueriesPanel
+            queries={runningQueries}
+            cancelling={cancelling}
+            onCancel={cancelQuery}
+          />
+
           {/* Plugin processes table */}
           <div className="bg-elevated border border-default rounded-xl overflow-hidden">
             <div className="px-5 py-4 border-b border-default flex items-center gap-2">
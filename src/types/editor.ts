@@ -50,6 +50,23 @@ export interface BatchStatementResult {
   execution_time_ms: number | null;
 }
 
+/// Outcome of an `insert_records_batch` invocation. Mirrors
+/// `src-tauri/src/models.rs::BatchInsertResult`; `row` indexes into the
+/// submitted rows.
+export interface BatchInsertResult {
+  inserted: number;
+  errors: { row: number; error: string }[];
+}
+
+/// Outcome of an `insert_record` invocation. Mirrors
+/// `src-tauri/src/models.rs::InsertedRecord`; `row` is the inserted row as
+/// stored (generated keys, defaults), or null when the driver cannot read
+/// it back.
+export interface InsertedRecord {
+  affected_rows: number;
+  row: Record<string, unknown> | null;
+}
+
 export interface QueryResultEntry {
   id: string;
   queryIndex: number;
@@ -29,6 +29,14 @@ export interface DriverCapabilities {
   readonly?: boolean;
   /** Supports listing and managing database triggers. Defaults to false. */
   triggers?: boolean;
+  /** Supports listing users and granting or revoking their privileges. Defaults to false. */
+  privileges?: boolean;
+  /** Reports server status counters and configuration variables. Defaults to false. */
+  server_status?: boolean;
+  /** Reports row estimates and sizes of tables. Defaults to false. */
+  table_stats?: boolean;
+  /** Returns query results as an Arrow IPC stream natively (`execute_query_arrow`). Defaults to false. */
+  arrow?: boolean;
 }
 
 export type PluginSettingType = "string" | "boolean" | "number" | "select";
@@ -15,6 +15,7 @@ export interface ParsedConnectionString {
   username?: string;
   password?: string;
   database: string;
+  ssl_mode?: string;
 }
 
 export interface ConnectionStringParseResult {
@@ -45,6 +46,35 @@ function normalizeProtocol(protocol: string): string {
   return protocol.replace(/:$/, "").trim().toLowerCase();
 }
 
+/**
+ * SSL mode from the query parameters hosted providers put in their
+ * connection strings: `sslmode`/`ssl-mode`, Prisma's `sslaccept` and the
+ * `ssl={"rejectUnauthorized":true}` PlanetScale hands out.
+ */
+function parseSslMode(search: URLSearchParams): string | undefined {
+  const mode = search.get("sslmode") ?? search.get("ssl-mode");
+  if (mode?.trim()) {
+    return mode.trim().toLowerCase().replace(/-/g, "_");
+  }
+
+  const accept = search.get("sslaccept")?.trim().toLowerCase();
+  if (accept === "strict") return "verify_identity";
+  if (accept === "accept_invalid_certs") return "required";
+
+  const ssl = search.get("ssl")?.trim();
+  if (!ssl) return undefined;
+  if (ssl === "true") return "required";
+  if (ssl === "false") return "disabled";
+  try {
+    const options = JSON.parse(ssl) as { rejectUnauthorized?: boolean };
+    return options.rejectUnauthorized === false
+      ? "required"
+      : "verify_identity";
+  } catch {
+    return undefined;
+  }
+}
+
 function getProtocolFromExample(example?: string | null): string | null {
   if (!example?.trim()) return null;
 
@@ -187,6 +217,8 @@ export function parseConnectionString(
     };
   }
 
+  const ssl_mode = parseSslMode(url.searchParams);
+
   return {
     success: true,
     params: {
@@ -196,6 +228,7 @@ export function parseConnectionString(
       username,
       password,
       database,
+      ...(ssl_mode ? { ssl_mode } : {}),
     },
   };
 }
@@ -213,6 +246,7 @@ export function toConnectionParams(
     username: parsed.username,
     password: parsed.password,
     database: parsed.database,
+    ssl_mode: parsed.ssl_mode,
   };
 }
 
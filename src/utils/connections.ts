@@ -19,6 +19,7 @@ export interface ConnectionParams {
   port?: number;
   username?: string;
   password?: string;
+  ssl_mode?: string;
   ssh_enabled?: boolean;
   ssh_connection_id?: string;
   // Legacy fields (for backward compatibility)
@@ -28,6 +29,8 @@ export interface ConnectionParams {
   ssh_password?: string;
   ssh_key_file?: string;
   ssh_key_passphrase?: string;
+  read_only?: boolean;
+  auth_method?: string;
 }
 
 /**
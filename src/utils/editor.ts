@@ -3,6 +3,7 @@ import type {
   SchemaCache,
   TableSchema,
   EditorPreferences,
+  Pagination,
 } from "../types/editor";
 import { quoteTableRef } from "./identifiers";
 import { invoke } from "@tauri-apps/api/core";
@@ -391,6 +392,25 @@ export function validatePageNumber(
   return !isNaN(num) && num >= 1 && num <= totalPages;
 }
 
+/**
+ * Resolve the page size to request for a page of a result
+ * @param settingsPageSize - Page size from the user settings
+ * @param pageNum - Page being requested
+ * @param pagination - Pagination of the result currently shown, if any
+ * @returns The page size the first page settled on when moving through
+ * pages (the backend adapts it to the row width), otherwise the setting
+ */
+export function resolvePageSize(
+  settingsPageSize: number | undefined,
+  pageNum: number,
+  pagination?: Pagination | null,
+): number {
+  if (pageNum > 1 && pagination && pagination.page_size > 0) {
+    return pagination.page_size;
+  }
+  return settingsPageSize && settingsPageSize > 0 ? settingsPageSize : 100;
+}
+
 /**
  * Calculate total pages based on total rows and page size
  * @param totalRows - Total number of rows
@@ -95,14 +95,13 @@ export const OPEN_SOURCE_LIBRARY_SECTIONS: readonly OpenSourceLibrarySection[] =
       { name: "infer", version: "0.16" },
       { name: "keyring", version: "3.6.3" },
       { name: "log", version: "0.4" },
-      { name: "native-tls", version: "0.2.13" },
       { name: "once_cell", version: "1.20" },
-      { name: "openssl", version: "0.10" },
       { name: "postgres-native-tls", version: "0.5.1" },
       { name: "reqwest", version: "0.13.1" },
       { name: "russh", version: "0.43" },
       { name: "russh-keys", version: "0.43" },
       { name: "rust_decimal", version: "1.36" },
+      { name: "rustls", version: "0.23" },
       { name: "serde", version: "1.0" },
       { name: "serde_json", version: "1.0" },
       { name: "serde_yaml", version: "0.9.34" },
@@ -3,6 +3,10 @@
  * Extracted from SchemaDiagramPage.tsx for testability
  */
 
+import { invoke } from '@tauri-apps/api/core';
+import { listen } from '@tauri-apps/api/event';
+import type { ForeignKey, TableColumn, TableSchema } from '../types/editor';
+
 /**
  * Connection parameters parsed from URL
  */
@@ -99,3 +103,90 @@ export function shouldAnimateEdges(edgeCount: number): boolean {
   // Only animate first 50 edges to prevent performance issues
   return edgeCount <= 50;
 }
+
+/**
+ * One `er-snapshot://batch` event of a streamed schema snapshot
+ */
+export type SnapshotBatch = { stream_id: string } & (
+  | { kind: 'tables'; tables: string[] }
+  | { kind: 'columns'; columns: Record<string, TableColumn[]> }
+  | { kind: 'foreign_keys'; foreign_keys: Record<string, ForeignKey[]> }
+);
+
+/**
+ * Fold a snapshot batch into the tables received so far
+ * @param tables - Tables received so far, in display order
+ * @param batch - The batch to apply
+ * @returns The updated tables
+ */
+export function applySnapshotBatch(tables: TableSchema[], batch: SnapshotBatch): TableSchema[] {
+  switch (batch.kind) {
+    case 'tables':
+      return batch.tables.map((name) => ({ name, columns: [], foreign_keys: [] }));
+    case 'columns':
+      return tables.map((table) =>
+        batch.columns[table.name] ? { ...table, columns: batch.columns[table.name] } : table,
+      );
+    case 'foreign_keys':
+      return tables.map((table) =>
+        batch.foreign_keys[table.name]
+          ? { ...table, foreign_keys: batch.foreign_keys[table.name] }
+          : table,
+      );
+  }
+}
+
+/**
+ * Load a schema snapshot in batches instead of one response, so very large
+ * schemas do not stall the diagram window
+ * @param connectionId - Connection to read the schema from
+ * @param schema - Schema to read, if the database has schemas
+ * @param tables - Only load these tables (all tables when empty)
+ * @param depth - Also load tables up to this many foreign keys away from `tables`
+ * @param onProgress - Called with the number of tables loaded and the total
+ * @returns Every table with its columns and foreign keys
+ */
+export async function streamSchemaSnapshot(
+  connectionId: string,
+  schema: string | undefined,
+  tables: string[],
+  depth: number,
+  onProgress?: (loaded: number, total: number) => void,
+): Promise<TableSchema[]> {
+  const streamId = crypto.randomUUID();
+  let snapshot: TableSchema[] = [];
+  let loaded = 0;
+  let linked = 0;
+  // Events may arrive after the command resolves: the stream is complete
+  // once every table got its foreign keys, the last kind of batch sent.
+  let complete: () => void = () => {};
+  const allLinked = new Promise<void>((resolve) => {
+    complete = resolve;
+  });
+
+  const unlisten = await listen<SnapshotBatch>('er-snapshot://batch', (event) => {
+    const batch = event.payload;
+    if (batch.stream_id !== streamId) return;
+    snapshot = applySnapshotBatch(snapshot, batch);
+    if (batch.kind === 'columns') {
+      loaded += Object.keys(batch.columns).length;
+      onProgress?.(loaded, snapshot.length);
+    } else if (batch.kind === 'foreign_keys') {
+      linked += Object.keys(batch.foreign_keys).length;
+      if (linked >= snapshot.length) complete();
+    }
+  });
+
+  try {
+    const total = await invoke<number>('stream_schema_snapshot', {
+      connectionId,
+      streamId,
+      ...(schema ? { schema } : {}),
+      ...(tables.length > 0 ? { tables, depth } : {}),
+    });
+    if (total > 0) await allLinked;
+    return snapshot;
+  } finally {
+    unlisten();
+  }
+}
@@ -46,6 +46,19 @@ export interface SystemStats {
   tabularis: TabularisSelfStats | null;
 }
 
+export interface RunningQuery {
+  id: number;
+  connection_id: string;
+  connection_name: string;
+  query: string;
+  started_at: string;
+  elapsed_ms: number;
+  /** Rows fetched so far. */
+  rows: number;
+  /** Estimated in-memory size of the rows fetched so far. */
+  bytes: number;
+}
+
 export type ProcessSortKey = keyof Pick<
   ProcessInfo,
   "plugin_name" | "cpu_percent" | "memory_bytes" | "status"
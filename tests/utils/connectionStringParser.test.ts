@@ -273,6 +273,51 @@ describe("connectionStringParser", () => {
     });
   });
 
+  describe("SSL parameters", () => {
+    function sslModeOf(connectionString: string): string | undefined {
+      const result = parseConnectionString(
+        connectionString,
+        CAPABILITY_DRIVERS,
+      );
+      return result.success ? result.params.ssl_mode : "failed";
+    }
+
+    it("should read sslmode and ssl-mode", () => {
+      expect(sslModeOf("postgresql://u:p@host/db?sslmode=verify-full")).toBe(
+        "verify_full",
+      );
+      expect(sslModeOf("mysql://u:p@host/db?ssl-mode=REQUIRED")).toBe(
+        "required",
+      );
+    });
+
+    it("should map Prisma's sslaccept", () => {
+      expect(
+        sslModeOf("mysql://u:p@aws.connect.psdb.cloud/db?sslaccept=strict"),
+      ).toBe("verify_identity");
+      expect(
+        sslModeOf("mysql://u:p@host/db?sslaccept=accept_invalid_certs"),
+      ).toBe("required");
+    });
+
+    it("should map PlanetScale's ssl options", () => {
+      expect(
+        sslModeOf(
+          'mysql://u:p@aws.connect.psdb.cloud/db?ssl={"rejectUnauthorized":true}',
+        ),
+      ).toBe("verify_identity");
+      expect(
+        sslModeOf('mysql://u:p@host/db?ssl={"rejectUnauthorized":false}'),
+      ).toBe("required");
+      expect(sslModeOf("mysql://u:p@host/db?ssl=false")).toBe("disabled");
+    });
+
+    it("should leave the mode unset without SSL parameters", () => {
+      expect(sslModeOf("mysql://u:p@host/db")).toBeUndefined();
+      expect(sslModeOf("mysql://u:p@host/db?ssl=maybe")).toBeUndefined();
+    });
+  });
+
   describe("toConnectionParams", () => {
     it("should convert parsed result to ConnectionParams", () => {
       const parsed: ParsedConnectionString = {
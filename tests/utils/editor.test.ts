@@ -19,6 +19,7 @@ import {
   formatExportFileName,
   validatePageNumber,
   calculateTotalPages,
+  resolvePageSize,
 } from "../../src/utils/editor";
 
 describe("editor", () => {
@@ -971,4 +972,28 @@ describe("editor", () => {
       expect(calculateTotalPages(10, 3)).toBe(4);
     });
   });
+
+  describe("resolvePageSize", () => {
+    const pagination = {
+      page: 1,
+      page_size: 50,
+      total_rows: null,
+      has_more: true,
+    };
+
+    it("should use the setting for the first page", () => {
+      expect(resolvePageSize(500, 1, pagination)).toBe(500);
+      expect(resolvePageSize(500, 1)).toBe(500);
+    });
+
+    it("should keep the adapted page size for later pages", () => {
+      expect(resolvePageSize(500, 2, pagination)).toBe(50);
+      expect(resolvePageSize(500, 3, null)).toBe(500);
+    });
+
+    it("should fall back to 100 without a valid setting", () => {
+      expect(resolvePageSize(undefined, 1)).toBe(100);
+      expect(resolvePageSize(0, 2, { ...pagination, page_size: 0 })).toBe(100);
+    });
+  });
 });
@@ -0,0 +1,37 @@
+import { describe, it, expect } from 'vitest';
+import type { AiProvider } from '../../src/contexts/SettingsContext';
+import { getProviderLabel } from '../../src/utils/settingsUI';
+import { detectAIProviderFromKeys } from '../../src/utils/settings';
+
+describe('Gemini AI Provider Integration', () => {
+  const noKeys: Record<AiProvider, boolean> = {
+    openai: false,
+    anthropic: false,
+    openrouter: false,
+    minimax: false,
+    gemini: false,
+    ollama: false,
+    'custom-openai': false,
+  };
+
+  it('should return Gemini label', () => {
+    expect(getProviderLabel('gemini')).toBe('Gemini');
+  });
+
+  it('should detect gemini when only gemini key is available', () => {
+    const result = detectAIProviderFromKeys(
+      { ...noKeys, gemini: true },
+      { gemini: ['gemini-2.5-pro', 'gemini-2.5-flash'] },
+    );
+    expect(result.provider).toBe('gemini');
+    expect(result.model).toBe('gemini-2.5-pro');
+  });
+
+  it('should prefer minimax over gemini', () => {
+    const result = detectAIProviderFromKeys(
+      { ...noKeys, minimax: true, gemini: true },
+      { minimax: ['MiniMax-M2.7'], gemini: ['gemini-2.5-pro'] },
+    );
+    expect(result.provider).toBe('minimax');
+  });
+});
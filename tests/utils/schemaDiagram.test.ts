@@ -5,6 +5,7 @@ import {
   isFullscreenActive,
   shouldShowMinimap,
   shouldAnimateEdges,
+  applySnapshotBatch,
   type ConnectionParams,
 } from '../../src/utils/schemaDiagram';
 
@@ -204,4 +205,27 @@ describe('schemaDiagram', () => {
       expect(shouldAnimateEdges(-100)).toBe(true);
     });
   });
+
+  describe('applySnapshotBatch', () => {
+    const column = { name: 'id', data_type: 'int', is_pk: true, is_nullable: false, is_auto_increment: true };
+
+    it('should build tables from streamed batches', () => {
+      let tables = applySnapshotBatch([], { stream_id: 's', kind: 'tables', tables: ['users', 'orders'] });
+      expect(tables.map((t) => t.name)).toEqual(['users', 'orders']);
+      expect(tables[0].columns).toEqual([]);
+
+      tables = applySnapshotBatch(tables, { stream_id: 's', kind: 'columns', columns: { orders: [column] } });
+      tables = applySnapshotBatch(tables, {
+        stream_id: 's',
+        kind: 'foreign_keys',
+        foreign_keys: {
+          orders: [{ name: 'fk_user', column_name: 'user_id', ref_table: 'users', ref_column: 'id' }],
+        },
+      });
+
+      expect(tables[0].columns).toEqual([]);
+      expect(tables[1].columns).toEqual([column]);
+      expect(tables[1].foreign_keys[0].ref_table).toBe('users');
+    });
+  });
 });